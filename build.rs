@@ -102,6 +102,9 @@ fn main() {
         println!("cargo:rerun-if-changed=proto/vector.proto");
         println!("cargo:rerun-if-changed=proto/dnstap.proto");
         println!("cargo:rerun-if-changed=proto/ddsketch.proto");
+        println!("cargo:rerun-if-changed=proto/zipkin.proto");
+        println!("cargo:rerun-if-changed=proto/jaeger/model.proto");
+        println!("cargo:rerun-if-changed=proto/jaeger/collector.proto");
 
         let mut prost_build = prost_build::Config::new();
         prost_build.btree_map(&["."]);
@@ -114,6 +117,8 @@ fn main() {
                     "proto/vector.proto",
                     "proto/dnstap.proto",
                     "proto/ddsketch.proto",
+                    "proto/zipkin.proto",
+                    "proto/jaeger/collector.proto",
                 ],
                 &["proto/", "lib/vector-core/proto/"],
             )
@@ -0,0 +1,357 @@
+use std::{collections::BTreeMap, net::IpAddr};
+
+use cidr_utils::cidr::IpCidr;
+use vrl_core::prelude::*;
+
+use crate::{vrl_util, Case, Condition, TableRegistry, TableSearch};
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpCidrLookup;
+impl Function for IpCidrLookup {
+    fn identifier(&self) -> &'static str {
+        "ip_cidr_lookup"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "table",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "field",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "select",
+                kind: kind::ARRAY,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "find the most specific range",
+            source: r#"ip_cidr_lookup!("networks", "10.1.2.3")"#,
+            result: Ok(r#"{"cidr": "10.1.0.0/16", "name": "office"}"#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        state: &state::Compiler,
+        _info: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let registry = state
+            .get_external_context::<TableRegistry>()
+            .ok_or(Box::new(vrl_util::Error::TablesNotLoaded) as Box<dyn DiagnosticError>)?;
+
+        let tables = registry
+            .table_ids()
+            .into_iter()
+            .map(Value::from)
+            .collect::<Vec<_>>();
+
+        let table = arguments
+            .required_enum("table", &tables)?
+            .try_bytes_utf8_lossy()
+            .expect("table is not valid utf8")
+            .into_owned();
+        let value = arguments.required("value");
+
+        let field = arguments
+            .optional_literal("field")?
+            .map(|literal| {
+                literal
+                    .to_value()
+                    .try_bytes_utf8_lossy()
+                    .map(|field| field.into_owned())
+            })
+            .transpose()
+            .expect("field should be a string") // This will have been caught by the type checker.
+            .unwrap_or_else(|| "cidr".to_owned());
+
+        let select = arguments.optional("select");
+
+        Ok(Box::new(IpCidrLookupFn {
+            table,
+            value,
+            field,
+            select,
+            enrichment_tables: registry.as_readonly(),
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct IpCidrLookupFn {
+    table: String,
+    value: Box<dyn Expression>,
+    field: String,
+    select: Option<Box<dyn Expression>>,
+    enrichment_tables: TableSearch,
+}
+
+impl Expression for IpCidrLookupFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value: IpAddr = self
+            .value
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .parse()
+            .map_err(|err| format!("unable to parse IP address: {}", err))?;
+
+        let select = self
+            .select
+            .as_ref()
+            .map(|array| match array.resolve(ctx)? {
+                Value::Array(arr) => arr
+                    .iter()
+                    .map(|value| Ok(value.try_bytes_utf8_lossy()?.to_string()))
+                    .collect::<std::result::Result<Vec<_>, _>>(),
+                value => Err(value::Error::Expected {
+                    got: value.kind(),
+                    expected: Kind::Array,
+                }),
+            })
+            .transpose()?;
+
+        let condition = [Condition::ContainsIp {
+            field: &self.field,
+            value,
+        }];
+
+        // Every range containing the address is returned, since the most specific one can only
+        // be picked once they have all been found. Columns are selected afterwards, as the range
+        // is needed to pick it even if it isn't selected.
+        let data = self
+            .enrichment_tables
+            .find_table_rows(&self.table, Case::Sensitive, &condition, None, None)?
+            .into_iter()
+            .max_by_key(|row| row.get(&self.field).and_then(prefix_len))
+            .ok_or("no rows found")?
+            .into_iter()
+            .filter(|(column, _)| {
+                select
+                    .as_ref()
+                    .map_or(true, |select| select.contains(column))
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        Ok(Value::Object(data))
+    }
+
+    fn update_state(
+        &mut self,
+        state: &mut state::Compiler,
+    ) -> std::result::Result<(), ExpressionError> {
+        match state.get_external_context_mut::<TableRegistry>() {
+            Some(registry) => Ok(registry.add_range_index(&self.table, &self.field)?),
+            // We shouldn't reach this point since the type checker will ensure the table exists before this function is called.
+            None => unreachable!("enrichment tables aren't loaded"),
+        }
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new()
+            .fallible()
+            .add_object::<(), Kind>(map! { (): Kind::all() })
+    }
+}
+
+/// Parse a CIDR range from a table, as tables are loaded with every column as a string.
+pub fn parse_cidr(value: &Value) -> Option<IpCidr> {
+    match value {
+        Value::Bytes(bytes) => std::str::from_utf8(bytes)
+            .ok()
+            .and_then(|cidr| IpCidr::from_str(cidr.trim()).ok()),
+        _ => None,
+    }
+}
+
+fn prefix_len(value: &Value) -> Option<u8> {
+    parse_cidr(value).map(|cidr| cidr.get_bits())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use shared::{btreemap, TimeZone};
+
+    use super::*;
+    use crate::{IndexHandle, Table};
+
+    /// A table of ranges that evaluates `ContainsIp` conditions the way the file table does.
+    #[derive(Clone)]
+    struct RangesTable(Vec<BTreeMap<String, Value>>, Arc<Mutex<Vec<String>>>);
+
+    impl Table for RangesTable {
+        fn find_table_row(
+            &self,
+            case: Case,
+            condition: &[Condition],
+            select: Option<&[String]>,
+            index: Option<IndexHandle>,
+        ) -> std::result::Result<BTreeMap<String, Value>, String> {
+            let mut rows = self.find_table_rows(case, condition, select, index)?;
+            rows.pop().ok_or_else(|| "no rows found".to_string())
+        }
+
+        fn find_table_rows(
+            &self,
+            _case: Case,
+            condition: &[Condition],
+            _select: Option<&[String]>,
+            _index: Option<IndexHandle>,
+        ) -> std::result::Result<Vec<BTreeMap<String, Value>>, String> {
+            Ok(self
+                .0
+                .iter()
+                .filter(|row| {
+                    condition.iter().all(|condition| match condition {
+                        Condition::ContainsIp { field, value } => row
+                            .get(*field)
+                            .and_then(parse_cidr)
+                            .map_or(false, |cidr| cidr.contains(*value)),
+                        _ => false,
+                    })
+                })
+                .cloned()
+                .collect())
+        }
+
+        fn add_index(
+            &mut self,
+            _case: Case,
+            _fields: &[&str],
+        ) -> std::result::Result<IndexHandle, String> {
+            Ok(IndexHandle(0))
+        }
+
+        fn add_range_index(&mut self, field: &str) -> std::result::Result<(), String> {
+            self.1.lock().unwrap().push(field.to_string());
+            Ok(())
+        }
+
+        fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+            Vec::new()
+        }
+
+        fn needs_reload(&self) -> bool {
+            false
+        }
+    }
+
+    fn lookup(value: &str, select: Option<Vec<&str>>) -> Resolved {
+        let registry = TableRegistry::default();
+        let mut tables: std::collections::HashMap<String, Box<dyn Table + Send + Sync>> =
+            Default::default();
+        tables.insert(
+            "networks".to_string(),
+            Box::new(RangesTable(
+                vec![
+                    btreemap! { "cidr" => "10.0.0.0/8", "name" => "internal" },
+                    btreemap! { "cidr" => "10.1.0.0/16", "name" => "office" },
+                    btreemap! { "cidr" => "10.1.2.0/24", "name" => "printers" },
+                    btreemap! { "cidr" => "2001:db8::/32", "name" => "documentation" },
+                ],
+                Default::default(),
+            )),
+        );
+        registry.load(tables);
+
+        let func = IpCidrLookupFn {
+            table: "networks".to_string(),
+            value: Value::from(value).into_expression(),
+            field: "cidr".to_string(),
+            select: select.map(|select| Value::from(select).into_expression()),
+            enrichment_tables: registry.as_readonly(),
+        };
+
+        let tz = TimeZone::default();
+        let mut object: Value = BTreeMap::new().into();
+        let mut runtime_state = vrl_core::state::Runtime::default();
+        let mut ctx = Context::new(&mut object, &mut runtime_state, &tz);
+
+        registry.finish_load();
+
+        func.resolve(&mut ctx)
+    }
+
+    #[test]
+    fn longest_prefix_match() {
+        assert_eq!(
+            Ok(value!({ "cidr": "10.1.2.0/24", "name": "printers" })),
+            lookup("10.1.2.3", None)
+        );
+        assert_eq!(
+            Ok(value!({ "cidr": "10.1.0.0/16", "name": "office" })),
+            lookup("10.1.3.3", None)
+        );
+        assert_eq!(
+            Ok(value!({ "cidr": "10.0.0.0/8", "name": "internal" })),
+            lookup("10.2.0.1", None)
+        );
+        assert_eq!(
+            Ok(value!({ "cidr": "2001:db8::/32", "name": "documentation" })),
+            lookup("2001:db8::1", None)
+        );
+    }
+
+    #[test]
+    fn select() {
+        assert_eq!(
+            Ok(value!({ "name": "printers" })),
+            lookup("10.1.2.3", Some(vec!["name"]))
+        );
+    }
+
+    #[test]
+    fn no_match() {
+        assert_eq!(
+            Err("no rows found".to_string()),
+            lookup("192.168.0.1", None).map_err(|err| err.to_string())
+        );
+        assert_eq!(
+            Err("unable to parse IP address: invalid IP address syntax".to_string()),
+            lookup("INVALID", None).map_err(|err| err.to_string())
+        );
+    }
+
+    #[test]
+    fn adds_range_index() {
+        let range_indexes = Arc::new(Mutex::new(Vec::new()));
+        let registry = TableRegistry::default();
+        let mut tables: std::collections::HashMap<String, Box<dyn Table + Send + Sync>> =
+            Default::default();
+        tables.insert(
+            "networks".to_string(),
+            Box::new(RangesTable(Vec::new(), range_indexes.clone())),
+        );
+        registry.load(tables);
+
+        let mut func = IpCidrLookupFn {
+            table: "networks".to_string(),
+            value: Value::from("10.1.2.3").into_expression(),
+            field: "network".to_string(),
+            select: None,
+            enrichment_tables: registry.as_readonly(),
+        };
+
+        let mut compiler = state::Compiler::new();
+        compiler.set_external_context(Some(Box::new(registry)));
+
+        assert_eq!(Ok(()), func.update_state(&mut compiler));
+        assert_eq!(vec!["network".to_string()], *range_indexes.lock().unwrap());
+    }
+}
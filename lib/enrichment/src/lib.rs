@@ -1,11 +1,12 @@
 pub mod find_enrichment_table_records;
 pub mod get_enrichment_table_record;
+pub mod ip_cidr_lookup;
 pub mod tables;
 
 #[cfg(test)]
 mod test_util;
 mod vrl_util;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, net::IpAddr};
 
 use dyn_clone::DynClone;
 pub use tables::{TableRegistry, TableSearch};
@@ -24,6 +25,10 @@ pub enum Condition<'a> {
         from: chrono::DateTime<chrono::Utc>,
         to: chrono::DateTime<chrono::Utc>,
     },
+    /// The number in the field is between from and to (inclusive).
+    BetweenNumbers { field: &'a str, from: f64, to: f64 },
+    /// The field is a CIDR range that contains the address.
+    ContainsIp { field: &'a str, value: IpAddr },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -69,6 +74,21 @@ pub trait Table: DynClone {
     /// Returns a list of the field names that are in each index
     fn index_fields(&self) -> Vec<(Case, Vec<String>)>;
 
+    /// Hints to the enrichment table that the field is going to be searched with range conditions
+    /// (`BetweenDates`, `BetweenNumbers` or `ContainsIp`) to allow it to index the data in
+    /// advance. Tables that can't index ranges can ignore this, and scan the rows instead.
+    ///
+    /// # Errors
+    /// Errors if the field is not in the table.
+    fn add_range_index(&mut self, _field: &str) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Returns a list of the field names that have range indexes.
+    fn range_index_fields(&self) -> Vec<String> {
+        Vec::new()
+    }
+
     /// Returns true if the underlying data has changed and the table needs reloading.
     fn needs_reload(&self) -> bool;
 }
@@ -81,5 +101,6 @@ pub fn vrl_functions() -> Vec<Box<dyn vrl_core::Function>> {
             as Box<dyn vrl_core::Function>,
         Box::new(find_enrichment_table_records::FindEnrichmentTableRecords)
             as Box<dyn vrl_core::Function>,
+        Box::new(ip_cidr_lookup::IpCidrLookup) as Box<dyn vrl_core::Function>,
     ]
 }
@@ -150,6 +150,25 @@ impl TableRegistry {
         }
     }
 
+    /// Adds a range index for the given field to the given Enrichment Table.
+    ///
+    /// If we are in the reading stage, this function will error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the Mutex is poisoned.
+    pub fn add_range_index(&mut self, table: &str, field: &str) -> Result<(), String> {
+        let mut locked = self.loading.lock().unwrap();
+
+        match *locked {
+            None => Err("finish_load has been called".to_string()),
+            Some(ref mut tables) => match tables.get_mut(table) {
+                None => Err(format!("table '{}' not loaded", table)),
+                Some(table) => table.add_range_index(field),
+            },
+        }
+    }
+
     /// Returns a cheaply clonable struct through that provides lock free read
     /// access to the enrichment tables.
     pub fn as_readonly(&self) -> TableSearch {
@@ -168,6 +187,18 @@ impl TableRegistry {
         }
     }
 
+    /// Returns the fields that have range indexes in the given table.
+    /// If the table is reloaded we need these to reapply them to the new reloaded tables.
+    pub fn range_index_fields(&self, table: &str) -> Vec<String> {
+        match &**self.tables.load() {
+            Some(tables) => tables
+                .get(table)
+                .map(|table| table.range_index_fields())
+                .unwrap_or_default(),
+            None => Vec::new(),
+        }
+    }
+
     /// Checks if the table needs reloading.
     /// If in doubt (the table isn't in our list) we return true.
     pub fn needs_reload(&self, table: &str) -> bool {
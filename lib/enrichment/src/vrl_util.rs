@@ -50,24 +50,53 @@ pub(crate) fn evaluate_condition<'a>(
 
     Ok(match value {
         Value::Object(map) if map.contains_key("from") && map.contains_key("to") => {
-            Condition::BetweenDates {
-                field: key,
-                from: *map
-                    .get("from")
-                    .expect("should contain from")
-                    .as_timestamp()
-                    .ok_or("from in condition must be a timestamp")?,
-                to: *map
-                    .get("to")
-                    .expect("should contain to")
-                    .as_timestamp()
-                    .ok_or("to in condition must be a timestamp")?,
+            let from = map.get("from").expect("should contain from");
+            let to = map.get("to").expect("should contain to");
+
+            match from {
+                Value::Timestamp(from) => Condition::BetweenDates {
+                    field: key,
+                    from: *from,
+                    to: *to
+                        .as_timestamp()
+                        .ok_or("to in condition must be a timestamp")?,
+                },
+                Value::Integer(_) | Value::Float(_) => Condition::BetweenNumbers {
+                    field: key,
+                    from: number(from).expect("from is a number"),
+                    to: number(to).ok_or("to in condition must be a number")?,
+                },
+                _ => return Err("from in condition must be a timestamp or a number".into()),
             }
         }
+        Value::Object(map) if map.contains_key("contains_ip") => Condition::ContainsIp {
+            field: key,
+            value: map
+                .get("contains_ip")
+                .expect("should contain contains_ip")
+                .try_bytes_utf8_lossy()?
+                .parse()
+                .map_err(|err| format!("unable to parse IP address: {}", err))?,
+        },
         _ => Condition::Equals { field: key, value },
     })
 }
 
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(number) => Some(*number as f64),
+        Value::Float(number) => Some(number.into_inner()),
+        _ => None,
+    }
+}
+
+/// Range conditions are objects with `from` and `to`, or `contains_ip`, fields.
+fn is_range_condition(value: &expression::Expr) -> bool {
+    matches!(value, expression::Expr::Container(expression::Container {
+        variant: expression::Variant::Object(map),
+    }) if (map.contains_key("from") && map.contains_key("to")) || map.contains_key("contains_ip"))
+}
+
 /// Add an index for the given condition to the given enrichment table.
 pub(crate) fn add_index(
     state: &mut state::Compiler,
@@ -79,17 +108,22 @@ pub(crate) fn add_index(
 
     match registry {
         Some(ref mut table) => {
-            let fields = condition
+            let (ranges, fields): (Vec<_>, Vec<_>) = condition
                 .iter()
-                .filter_map(|(field, value)| match value {
-                    expression::Expr::Container(expression::Container {
-                        variant: expression::Variant::Object(map),
-                    }) if map.contains_key("from") && map.contains_key("to") => None,
-                    _ => Some(field.as_ref()),
-                })
+                .partition(|(_, value)| is_range_condition(value));
+
+            let fields = fields
+                .into_iter()
+                .map(|(field, _)| field.as_ref())
                 .collect::<Vec<_>>();
             let index = table.add_index(tablename, case, &fields)?;
 
+            // Range conditions can't be found by hashing their values like exact matches, so are
+            // indexed separately.
+            for (field, _) in ranges {
+                table.add_range_index(tablename, field)?;
+            }
+
             Ok(index)
         }
         // We shouldn't reach this point since the type checker will ensure the table exists before this function is called.
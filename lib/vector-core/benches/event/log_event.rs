@@ -43,6 +43,60 @@ fn rename_key_flat(c: &mut Criterion) {
     });
 }
 
+fn insert_nested_labels(c: &mut Criterion) {
+    let mut group: BenchmarkGroup<WallTime> =
+        c.benchmark_group("vector_core::event::log_event::LogEvent::insert");
+    group.sampling_mode(SamplingMode::Auto);
+
+    // Mirrors the `kubernetes.pod_labels.*` fields that every event from a pod
+    // repeats, which makes the cost of allocating keys visible.
+    let labels: Vec<(String, String)> = (0..20)
+        .map(|i| {
+            (
+                format!("kubernetes.pod_labels.label_{}", i),
+                format!("value_{}", i),
+            )
+        })
+        .collect();
+
+    group.bench_function("insert (20 nested labels)", move |b| {
+        b.iter_batched(
+            LogEvent::default,
+            |mut log_event| {
+                for (key, value) in &labels {
+                    log_event.insert(key, value.as_str());
+                }
+                log_event
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn clone_and_modify(c: &mut Criterion) {
+    let mut group: BenchmarkGroup<WallTime> =
+        c.benchmark_group("vector_core::event::log_event::LogEvent::clone");
+    group.sampling_mode(SamplingMode::Auto);
+
+    let mut log_event = LogEvent::default();
+    for i in 0..20 {
+        log_event.insert(format!("kubernetes.pod_labels.label_{}", i), i);
+    }
+
+    let shared = log_event.clone();
+    group.bench_function("clone", move |b| {
+        b.iter(|| shared.clone());
+    });
+
+    group.bench_function("clone and insert", move |b| {
+        b.iter(|| {
+            let mut copy = log_event.clone();
+            copy.insert("modified", true);
+            copy
+        });
+    });
+}
+
 criterion_group!(
     name = benches;
     config = Criterion::default()
@@ -58,5 +112,5 @@ criterion_group!(
         .nresamples(100_000)
         // total samples to collect within the set measurement time
         .sample_size(150);
-    targets = rename_key_flat
+    targets = rename_key_flat, insert_nested_labels, clone_and_modify
 );
@@ -78,6 +78,8 @@ fn create_disk_v2_variant(_max_events: usize, max_size: u64) -> BufferType {
     BufferType::DiskV2 {
         max_size,
         when_full: WhenFull::DropNewest,
+        flush_interval_ms: None,
+        flush_every_n_records: None,
     }
 }
 
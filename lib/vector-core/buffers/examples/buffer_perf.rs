@@ -271,6 +271,8 @@ where
             BufferType::DiskV2 {
                 max_size: max_size_bytes,
                 when_full,
+                flush_interval_ms: None,
+                flush_every_n_records: None,
             }
         }
         s => panic!(
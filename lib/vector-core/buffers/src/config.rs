@@ -1,4 +1,4 @@
-use std::{fmt, path::PathBuf};
+use std::{fmt, path::PathBuf, time::Duration};
 
 use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
 use snafu::{ResultExt, Snafu};
@@ -33,7 +33,17 @@ enum BufferTypeKind {
     DiskV2,
 }
 
-const ALL_FIELDS: [&str; 4] = ["type", "max_events", "max_size", "when_full"];
+const ALL_FIELDS: [&str; 6] = [
+    "type",
+    "max_events",
+    "max_size",
+    "when_full",
+    "flush_interval_ms",
+    "flush_every_n_records",
+];
+
+/// Fields that only apply to `disk_v2` buffers.
+const FLUSH_FIELDS: [&str; 2] = ["flush_interval_ms", "flush_every_n_records"];
 
 struct BufferTypeVisitor;
 
@@ -46,6 +56,8 @@ impl BufferTypeVisitor {
         let mut max_events: Option<usize> = None;
         let mut max_size: Option<u64> = None;
         let mut when_full: Option<WhenFull> = None;
+        let mut flush_interval_ms: Option<u64> = None;
+        let mut flush_every_n_records: Option<u64> = None;
         while let Some(key) = map.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -72,6 +84,18 @@ impl BufferTypeVisitor {
                     }
                     when_full = Some(map.next_value()?);
                 }
+                "flush_interval_ms" => {
+                    if flush_interval_ms.is_some() {
+                        return Err(de::Error::duplicate_field("flush_interval_ms"));
+                    }
+                    flush_interval_ms = Some(map.next_value()?);
+                }
+                "flush_every_n_records" => {
+                    if flush_every_n_records.is_some() {
+                        return Err(de::Error::duplicate_field("flush_every_n_records"));
+                    }
+                    flush_every_n_records = Some(map.next_value()?);
+                }
                 other => {
                     return Err(de::Error::unknown_field(other, &ALL_FIELDS));
                 }
@@ -79,6 +103,21 @@ impl BufferTypeVisitor {
         }
         let kind = kind.unwrap_or(BufferTypeKind::MemoryV1);
         let when_full = when_full.unwrap_or_default();
+        if !matches!(kind, BufferTypeKind::DiskV2) {
+            let field = match (flush_interval_ms, flush_every_n_records) {
+                (Some(_), _) => Some(FLUSH_FIELDS[0]),
+                (None, Some(_)) => Some(FLUSH_FIELDS[1]),
+                (None, None) => None,
+            };
+            if let Some(field) = field {
+                let expected: &[&str] = if matches!(kind, BufferTypeKind::DiskV1) {
+                    &["type", "max_size", "when_full"]
+                } else {
+                    &["type", "max_events", "when_full"]
+                };
+                return Err(de::Error::unknown_field(field, expected));
+            }
+        }
         match kind {
             BufferTypeKind::MemoryV1 => {
                 if max_size.is_some() {
@@ -120,12 +159,26 @@ impl BufferTypeVisitor {
                 if max_events.is_some() {
                     return Err(de::Error::unknown_field(
                         "max_events",
-                        &["type", "max_size", "when_full"],
+                        &[
+                            "type",
+                            "max_size",
+                            "when_full",
+                            "flush_interval_ms",
+                            "flush_every_n_records",
+                        ],
+                    ));
+                }
+                if flush_every_n_records == Some(0) {
+                    return Err(de::Error::invalid_value(
+                        de::Unexpected::Unsigned(0),
+                        &"a number of records greater than zero",
                     ));
                 }
                 Ok(BufferType::DiskV2 {
                     max_size: max_size.ok_or_else(|| de::Error::missing_field("max_size"))?,
                     when_full,
+                    flush_interval_ms,
+                    flush_every_n_records,
                 })
             }
         }
@@ -249,6 +302,20 @@ pub enum BufferType {
         max_size: u64,
         #[serde(default)]
         when_full: WhenFull,
+        /// Maximum time, in milliseconds, between flushes of the buffer to disk.
+        ///
+        /// Records written since the last flush may be lost if Vector crashes, so this bounds the
+        /// window of data loss.  Longer intervals allow more records to be committed in a single
+        /// flush, which matters most on disks where each `fsync` is expensive, such as network
+        /// attached storage.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        flush_interval_ms: Option<u64>,
+        /// Maximum number of records written between flushes of the buffer to disk.
+        ///
+        /// When set, the buffer is flushed after this many records even if `flush_interval_ms` has
+        /// not elapsed, bounding the number of records that may be lost instead of just the time.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        flush_every_n_records: Option<u64>,
     },
 }
 
@@ -291,10 +358,15 @@ impl BufferType {
             BufferType::DiskV2 {
                 when_full,
                 max_size,
+                flush_interval_ms,
+                flush_every_n_records,
             } => {
                 warn!("!!!! The `disk_v2` buffer type is not yet stable.  Data loss may be encountered. !!!!");
                 let data_dir = data_dir.ok_or(BufferBuildError::RequiresDataDir)?;
-                builder.stage(DiskV2Buffer::new(id, data_dir, max_size), when_full);
+                let buffer = DiskV2Buffer::new(id, data_dir, max_size)
+                    .flush_interval(flush_interval_ms.map(Duration::from_millis))
+                    .flush_every_n_records(flush_every_n_records);
+                builder.stage(buffer, when_full);
             }
         };
 
@@ -406,7 +478,7 @@ mod test {
         let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
         assert_eq!(
             error.to_string(),
-            "unknown field `foo`, expected one of `type`, `max_events`, `max_size`, `when_full` at line 1 column 4"
+            "unknown field `foo`, expected one of `type`, `max_events`, `max_size`, `when_full`, `flush_interval_ms`, `flush_every_n_records` at line 1 column 4"
         );
     }
 
@@ -563,7 +635,43 @@ max_events: 42
             BufferType::DiskV2 {
                 max_size: 1024,
                 when_full: WhenFull::Block,
+                flush_interval_ms: None,
+                flush_every_n_records: None,
             },
         );
     }
+
+    #[test]
+    fn parse_disk_v2_flush_policy() {
+        check_single_stage(
+            r#"
+          type: disk_v2
+          max_size: 1024
+          flush_interval_ms: 5000
+          flush_every_n_records: 1000
+          "#,
+            BufferType::DiskV2 {
+                max_size: 1024,
+                when_full: WhenFull::Block,
+                flush_interval_ms: Some(5000),
+                flush_every_n_records: Some(1000),
+            },
+        );
+
+        let source = r#"type: disk_v2
+max_size: 1024
+flush_every_n_records: 0
+"#;
+        assert!(serde_yaml::from_str::<BufferConfig>(source).is_err());
+
+        let source = r#"type: disk
+max_size: 1024
+flush_interval_ms: 5000
+"#;
+        let error = serde_yaml::from_str::<BufferConfig>(source).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown field `flush_interval_ms`, expected one of `type`, `max_size`, `when_full`"
+        );
+    }
 }
@@ -62,6 +62,13 @@ pub struct DiskBufferConfig {
     /// In the event that data had not yet been durably written to disk, and Vector crashed, the
     /// amount of data written since the last flush would be lost.
     pub(crate) flush_interval: Duration,
+
+    /// Number of records written after which the ledger and data files are flushed, regardless of
+    /// `flush_interval`.
+    ///
+    /// This bounds the number of records that could be lost, in the same way that `flush_interval`
+    /// bounds the window of time, and is not used when `None`.
+    pub(crate) flush_every_n_records: Option<u64>,
 }
 
 impl DiskBufferConfig {
@@ -75,6 +82,7 @@ impl DiskBufferConfig {
             max_data_file_size: None,
             max_record_size: None,
             flush_interval: None,
+            flush_every_n_records: None,
         }
     }
 }
@@ -86,6 +94,7 @@ pub struct DiskBufferConfigBuilder {
     max_data_file_size: Option<u64>,
     max_record_size: Option<usize>,
     flush_interval: Option<Duration>,
+    flush_every_n_records: Option<u64>,
 }
 
 impl DiskBufferConfigBuilder {
@@ -142,12 +151,23 @@ impl DiskBufferConfigBuilder {
     /// amount of data written since the last flush would be lost.
     ///
     /// Defaults to 500ms.
-    #[allow(dead_code)]
     pub fn flush_interval(mut self, interval: Duration) -> Self {
         self.flush_interval = Some(interval);
         self
     }
 
+    /// Sets the number of records written after which the ledger and data files are flushed.
+    ///
+    /// Records are still flushed at least every `flush_interval`, so this only makes flushes more
+    /// frequent, trading throughput for a smaller number of records that could be lost if Vector
+    /// crashed.
+    ///
+    /// Defaults to no limit.
+    pub fn flush_every_n_records(mut self, amount: u64) -> Self {
+        self.flush_every_n_records = Some(amount);
+        self
+    }
+
     /// Consumes this builder and constructs a `DiskBufferConfig`.
     pub fn build(self) -> DiskBufferConfig {
         let max_data_file_size = self
@@ -176,6 +196,7 @@ impl DiskBufferConfigBuilder {
             max_data_file_size,
             max_record_size,
             flush_interval,
+            flush_every_n_records: self.flush_every_n_records,
         }
     }
 }
@@ -221,6 +221,8 @@ pub struct Ledger {
     unacked_reader_file_id_offset: AtomicU16,
     // Last flush of all unflushed files: ledger, data file, etc.
     last_flush: AtomicCell<Instant>,
+    // Number of records written since the last flush.
+    unflushed_records: AtomicU64,
     // Tracks usage data about the buffer.
     usage_handle: BufferUsageHandle,
 }
@@ -372,6 +374,7 @@ impl Ledger {
     /// Tracks the statistics of a successful write.
     pub fn track_write(&self, record_size: u64) {
         self.increment_total_buffer_size(record_size);
+        self.unflushed_records.fetch_add(1, Ordering::AcqRel);
         self.usage_handle
             .increment_received_event_count_and_byte_size(1, record_size);
     }
@@ -469,14 +472,21 @@ impl Ledger {
     /// In the case of concurrent callers when the flush deadline has been exceeded, only one caller
     /// will get a return value of `true`, and the others will receive `false`.  The caller that
     /// receives `true` is responsible for flushing the necessary files.
+    ///
+    /// Files are flushed once the flush interval has elapsed, or once `flush_every_n_records`
+    /// records have been written since the last flush, whichever comes first.
     pub fn should_flush(&self) -> bool {
         let last_flush = self.last_flush.load();
-        if last_flush.elapsed() > self.config.flush_interval
+        let records_exceeded = self.config.flush_every_n_records.map_or(false, |n| {
+            self.unflushed_records.load(Ordering::Acquire) >= n
+        });
+        if (records_exceeded || last_flush.elapsed() > self.config.flush_interval)
             && self
                 .last_flush
                 .compare_exchange(last_flush, Instant::now())
                 .is_ok()
         {
+            self.unflushed_records.store(0, Ordering::Release);
             return true;
         }
 
@@ -607,6 +617,7 @@ impl Ledger {
             pending_acks: AtomicUsize::new(0),
             unacked_reader_file_id_offset: AtomicU16::new(0),
             last_flush: AtomicCell::new(Instant::now()),
+            unflushed_records: AtomicU64::new(0),
             usage_handle,
         };
         ledger.update_buffer_size().await?;
@@ -681,6 +692,7 @@ impl fmt::Debug for Ledger {
             )
             .field("writer_done", &self.writer_done.load(Ordering::Acquire))
             .field("last_flush", &self.last_flush)
+            .field("unflushed_records", &self.unflushed_records)
             .finish()
     }
 }
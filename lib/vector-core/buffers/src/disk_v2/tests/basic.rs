@@ -1,7 +1,10 @@
 use tokio_test::{assert_pending, assert_ready, task::spawn};
 use tracing::Instrument;
 
-use super::{create_default_buffer, install_tracing_helpers, with_temp_dir, SizedRecord};
+use super::{
+    create_buffer_with_flush_every_n_records, create_default_buffer, install_tracing_helpers,
+    with_temp_dir, SizedRecord,
+};
 use crate::{assert_buffer_is_empty, assert_buffer_records};
 
 #[tokio::test]
@@ -58,6 +61,36 @@ async fn basic_read_write_loop() {
     .await;
 }
 
+#[tokio::test]
+async fn flushes_after_configured_record_count() {
+    with_temp_dir(|dir| {
+        let data_dir = dir.to_path_buf();
+
+        async move {
+            let (mut writer, _reader, _acker, ledger) =
+                create_buffer_with_flush_every_n_records(data_dir, 4).await;
+
+            for _ in 0..3 {
+                writer
+                    .write_record(SizedRecord(32))
+                    .await
+                    .expect("write should not fail");
+            }
+            assert!(!ledger.should_flush());
+
+            writer
+                .write_record(SizedRecord(32))
+                .await
+                .expect("write should not fail");
+            assert!(ledger.should_flush());
+
+            // Flushing resets the count, so the next flush waits for another four records.
+            assert!(!ledger.should_flush());
+        }
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn reader_exits_cleanly_when_writer_done_and_in_flight_acks() {
     let assertion_registry = install_tracing_helpers();
@@ -1,4 +1,4 @@
-use std::{future::Future, io, path::Path, str::FromStr, sync::Arc};
+use std::{future::Future, io, path::Path, str::FromStr, sync::Arc, time::Duration};
 
 use bytes::{Buf, BufMut};
 use core_common::byte_size_of::ByteSizeOf;
@@ -284,6 +284,26 @@ where
         .expect("should not fail to create buffer")
 }
 
+pub(crate) async fn create_buffer_with_flush_every_n_records<P, R>(
+    data_dir: P,
+    flush_every_n_records: u64,
+) -> (Writer<R>, Reader<R>, Acker, Arc<Ledger>)
+where
+    P: AsRef<Path>,
+    R: Bufferable,
+{
+    // Keep the flush interval out of the way so that only the record count triggers flushes.
+    let config = DiskBufferConfig::from_path(data_dir)
+        .flush_interval(Duration::from_secs(3600))
+        .flush_every_n_records(flush_every_n_records)
+        .build();
+    let usage_handle = BufferUsageHandle::noop();
+
+    Buffer::from_config_inner(config, usage_handle)
+        .await
+        .expect("should not fail to create buffer")
+}
+
 pub(crate) async fn with_temp_dir<F, Fut, V>(f: F) -> V
 where
     F: FnOnce(&Path) -> Fut,
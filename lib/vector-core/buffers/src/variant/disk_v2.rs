@@ -3,6 +3,7 @@ use std::{
     path::PathBuf,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -25,6 +26,8 @@ pub struct DiskV2Buffer {
     id: String,
     data_dir: PathBuf,
     max_size: u64,
+    flush_interval: Option<Duration>,
+    flush_every_n_records: Option<u64>,
 }
 
 impl DiskV2Buffer {
@@ -33,8 +36,24 @@ impl DiskV2Buffer {
             id,
             data_dir,
             max_size,
+            flush_interval: None,
+            flush_every_n_records: None,
         }
     }
+
+    /// Sets the maximum time between flushes of the buffer to disk.
+    #[must_use]
+    pub fn flush_interval(mut self, interval: Option<Duration>) -> Self {
+        self.flush_interval = interval;
+        self
+    }
+
+    /// Sets the maximum number of records written between flushes of the buffer to disk.
+    #[must_use]
+    pub fn flush_every_n_records(mut self, amount: Option<u64>) -> Self {
+        self.flush_every_n_records = amount;
+        self
+    }
 }
 
 #[async_trait]
@@ -55,9 +74,15 @@ where
 
         // Create the actual buffer subcomponents.
         let buffer_path = self.data_dir.join("buffer").join("v2").join(self.id);
-        let config = DiskBufferConfig::from_path(buffer_path)
-            .max_buffer_size(self.max_size as u64)
-            .build();
+        let mut builder =
+            DiskBufferConfig::from_path(buffer_path).max_buffer_size(self.max_size as u64);
+        if let Some(interval) = self.flush_interval {
+            builder = builder.flush_interval(interval);
+        }
+        if let Some(amount) = self.flush_every_n_records {
+            builder = builder.flush_every_n_records(amount);
+        }
+        let config = builder.build();
         let (writer, reader, acker) = Buffer::from_config(config, usage_handle).await?;
 
         let wrapped_reader = WrappedReader::new(reader);
@@ -178,6 +203,14 @@ where
             error!("failed to write record to the buffer: {}", e);
         }
 
+        // Write whatever else is already waiting before flushing, so that a burst of records is
+        // committed together rather than flushed one by one.
+        while let Ok(record) = input.try_recv() {
+            if let Err(e) = writer.write_record(record).await {
+                error!("failed to write record to the buffer: {}", e);
+            }
+        }
+
         if let Err(e) = writer.flush().await {
             error!("failed to flush the buffer: {}", e);
         }
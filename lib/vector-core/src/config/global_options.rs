@@ -1,4 +1,4 @@
-use std::{fs::DirBuilder, path::PathBuf};
+use std::{collections::BTreeMap, fs::DirBuilder, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 use shared::TimeZone;
@@ -47,6 +47,47 @@ pub struct GlobalOptions {
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
     )]
     pub acknowledgements: AcknowledgementsConfig,
+    /// The most connections all TCP-based sources together have open at once.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub max_connections: Option<u32>,
+    /// The longest transforms wait for more events to fill a batch with, in milliseconds. Without
+    /// it, transforms take whatever events are ready, which can be very few with bursty inputs.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub coalesce_latency_ms: Option<u64>,
+    /// Synthetic events sent on behalf of every source, so that silent sources can be detected
+    /// downstream.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub source_heartbeat: Option<SourceHeartbeatConfig>,
+    /// How long sinks keep the state of metric series that aren't updated anymore, in seconds.
+    /// Without it, the state is kept until the series ends explicitly. Sinks can override it.
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    pub expire_metrics_secs: Option<u64>,
+}
+
+/// The heartbeat events sent through the default output of every source.
+///
+/// Sources whose default output takes logs send log events with the `message`, the source's ID
+/// and type, and the `fields`, while sources of metrics send a gauge tagged the same way.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SourceHeartbeatConfig {
+    /// The interval between heartbeats, in seconds.
+    #[serde(default = "default_heartbeat_interval_secs")]
+    pub interval_secs: u64,
+    /// The message of heartbeat log events, and the name of heartbeat metrics.
+    #[serde(default = "default_heartbeat_message")]
+    pub message: String,
+    /// Fields added to heartbeat log events, and tags added to heartbeat metrics.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+const fn default_heartbeat_interval_secs() -> u64 {
+    60
+}
+
+fn default_heartbeat_message() -> String {
+    "heartbeat".to_owned()
 }
 
 impl GlobalOptions {
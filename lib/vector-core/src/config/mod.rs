@@ -5,7 +5,7 @@ mod id;
 mod log_schema;
 pub mod proxy;
 
-pub use global_options::GlobalOptions;
+pub use global_options::{GlobalOptions, SourceHeartbeatConfig};
 pub use id::ComponentKey;
 pub use log_schema::{init_log_schema, log_schema, LogSchema};
 
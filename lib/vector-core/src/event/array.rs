@@ -2,9 +2,9 @@
 //! This module contains the definitions and wrapper types for handling
 //! arrays of type `Event`, in the various forms they may appear.
 
-use std::{iter, vec};
+use std::{iter, slice, vec};
 
-use super::{Event, LogEvent, Metric};
+use super::{Event, EventMutRef, EventRef, LogEvent, Metric};
 use crate::ByteSizeOf;
 
 /// The core trait to abstract over any type that may work as an array
@@ -16,6 +16,21 @@ pub trait EventContainer: ByteSizeOf {
     type IntoIter: Iterator<Item = Event>;
     /// Turn this container into an iterator of events.
     fn into_events(self) -> Self::IntoIter;
+
+    /// The number of events in this container.
+    fn len(&self) -> usize;
+
+    /// Check if this container is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over references to the events in this container.
+    fn iter_events(&self) -> EventArrayIter<'_>;
+
+    /// Iterate over mutable references to the events in this container,
+    /// for modifying them in place.
+    fn iter_events_mut(&mut self) -> EventArrayIterMut<'_>;
 }
 
 impl EventContainer for Event {
@@ -23,6 +38,24 @@ impl EventContainer for Event {
     fn into_events(self) -> Self::IntoIter {
         iter::once(self)
     }
+
+    fn len(&self) -> usize {
+        1
+    }
+
+    fn iter_events(&self) -> EventArrayIter<'_> {
+        match self {
+            Self::Log(log) => log.iter_events(),
+            Self::Metric(metric) => metric.iter_events(),
+        }
+    }
+
+    fn iter_events_mut(&mut self) -> EventArrayIterMut<'_> {
+        match self {
+            Self::Log(log) => log.iter_events_mut(),
+            Self::Metric(metric) => metric.iter_events_mut(),
+        }
+    }
 }
 
 impl EventContainer for LogEvent {
@@ -30,6 +63,18 @@ impl EventContainer for LogEvent {
     fn into_events(self) -> Self::IntoIter {
         iter::once(self.into())
     }
+
+    fn len(&self) -> usize {
+        1
+    }
+
+    fn iter_events(&self) -> EventArrayIter<'_> {
+        EventArrayIter::Logs(slice::from_ref(self).iter())
+    }
+
+    fn iter_events_mut(&mut self) -> EventArrayIterMut<'_> {
+        EventArrayIterMut::Logs(slice::from_mut(self).iter_mut())
+    }
 }
 
 impl EventContainer for Metric {
@@ -37,6 +82,18 @@ impl EventContainer for Metric {
     fn into_events(self) -> Self::IntoIter {
         iter::once(self.into())
     }
+
+    fn len(&self) -> usize {
+        1
+    }
+
+    fn iter_events(&self) -> EventArrayIter<'_> {
+        EventArrayIter::Metrics(slice::from_ref(self).iter())
+    }
+
+    fn iter_events_mut(&mut self) -> EventArrayIterMut<'_> {
+        EventArrayIterMut::Metrics(slice::from_mut(self).iter_mut())
+    }
 }
 
 /// The type alias for an array of `LogEvent` elements.
@@ -47,6 +104,18 @@ impl EventContainer for LogArray {
     fn into_events(self) -> Self::IntoIter {
         self.into_iter().map(Into::into)
     }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn iter_events(&self) -> EventArrayIter<'_> {
+        EventArrayIter::Logs(self.iter())
+    }
+
+    fn iter_events_mut(&mut self) -> EventArrayIterMut<'_> {
+        EventArrayIterMut::Logs(self.iter_mut())
+    }
 }
 
 /// The type alias for an array of `Metric` elements.
@@ -57,6 +126,18 @@ impl EventContainer for MetricArray {
     fn into_events(self) -> Self::IntoIter {
         self.into_iter().map(Into::into)
     }
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn iter_events(&self) -> EventArrayIter<'_> {
+        EventArrayIter::Metrics(self.iter())
+    }
+
+    fn iter_events_mut(&mut self) -> EventArrayIterMut<'_> {
+        EventArrayIterMut::Metrics(self.iter_mut())
+    }
 }
 
 /// An array of one of the `Event` variants exclusively.
@@ -94,6 +175,27 @@ impl EventContainer for EventArray {
             Self::Metrics(a) => EventArrayIntoIter::Metrics(a.into_iter()),
         }
     }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Logs(a) => a.len(),
+            Self::Metrics(a) => a.len(),
+        }
+    }
+
+    fn iter_events(&self) -> EventArrayIter<'_> {
+        match self {
+            Self::Logs(a) => a.iter_events(),
+            Self::Metrics(a) => a.iter_events(),
+        }
+    }
+
+    fn iter_events_mut(&mut self) -> EventArrayIterMut<'_> {
+        match self {
+            Self::Logs(a) => a.iter_events_mut(),
+            Self::Metrics(a) => a.iter_events_mut(),
+        }
+    }
 }
 
 /// The iterator type for `EventArray`.
@@ -114,3 +216,59 @@ impl Iterator for EventArrayIntoIter {
         }
     }
 }
+
+/// The iterator type for references to the events in an `EventContainer`.
+pub enum EventArrayIter<'a> {
+    /// An iterator over references to type `LogEvent`.
+    Logs(slice::Iter<'a, LogEvent>),
+    /// An iterator over references to type `Metric`.
+    Metrics(slice::Iter<'a, Metric>),
+}
+
+impl<'a> Iterator for EventArrayIter<'a> {
+    type Item = EventRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Logs(i) => i.next().map(Into::into),
+            Self::Metrics(i) => i.next().map(Into::into),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Logs(i) => i.size_hint(),
+            Self::Metrics(i) => i.size_hint(),
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for EventArrayIter<'a> {}
+
+/// The iterator type for mutable references to the events in an `EventContainer`.
+pub enum EventArrayIterMut<'a> {
+    /// An iterator over mutable references to type `LogEvent`.
+    Logs(slice::IterMut<'a, LogEvent>),
+    /// An iterator over mutable references to type `Metric`.
+    Metrics(slice::IterMut<'a, Metric>),
+}
+
+impl<'a> Iterator for EventArrayIterMut<'a> {
+    type Item = EventMutRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Self::Logs(i) => i.next().map(Into::into),
+            Self::Metrics(i) => i.next().map(Into::into),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Self::Logs(i) => i.size_hint(),
+            Self::Metrics(i) => i.size_hint(),
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for EventArrayIterMut<'a> {}
@@ -123,6 +123,7 @@ fn hash_value<H: Hasher>(hasher: &mut H, value: &Value) {
         Value::Bytes(val) => val.hash(hasher),
         Value::Boolean(val) => val.hash(hasher),
         Value::Integer(val) => val.hash(hasher),
+        Value::Decimal(val) => val.hash(hasher),
         Value::Timestamp(val) => val.hash(hasher),
         // Non-trivial.
         Value::Float(val) => hash_f64(hasher, *val),
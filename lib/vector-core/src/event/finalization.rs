@@ -38,7 +38,13 @@ impl PartialOrd for EventFinalizers {
 
 impl ByteSizeOf for EventFinalizers {
     fn allocated_bytes(&self) -> usize {
-        self.0.iter().fold(0, |acc, arc| acc + arc.size_of())
+        // Each finalizer takes a pointer in this array, and shares an
+        // allocation with the two reference counts of its `Arc`.
+        self.0.iter().fold(0, |acc, arc| {
+            acc + mem::size_of::<Arc<EventFinalizer>>()
+                + 2 * mem::size_of::<usize>()
+                + arc.size_of()
+        })
     }
 }
 
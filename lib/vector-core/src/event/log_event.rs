@@ -9,7 +9,7 @@ use std::{
 use bytes::Bytes;
 use chrono::Utc;
 use derivative::Derivative;
-use getset::{Getters, MutGetters};
+use getset::Getters;
 use serde::{Deserialize, Serialize, Serializer};
 use shared::EventDataEq;
 
@@ -17,40 +17,49 @@ use super::{
     finalization::{BatchNotifier, EventFinalizer},
     legacy_lookup::Segment,
     metadata::EventMetadata,
+    size_cache::SizeCache,
     util, EventFinalizers, Finalizable, Lookup, PathComponent, Value,
 };
 use crate::{config::log_schema, event::MaybeAsLogMut, ByteSizeOf};
 
-#[derive(Clone, Debug, Getters, MutGetters, PartialEq, PartialOrd, Derivative, Deserialize)]
+#[derive(Clone, Debug, Getters, PartialEq, PartialOrd, Derivative, Deserialize)]
 pub struct LogEvent {
     // **IMPORTANT:** Due to numerous legacy reasons this **must** be a Map variant.
-    #[derivative(Default(value = "Value::from(BTreeMap::default())"))]
+    //
+    // The fields are shared between clones, so that fanning an event out to
+    // several components doesn't copy them until one of them is modified.
+    #[derivative(Default(value = "Arc::new(Value::from(BTreeMap::default()))"))]
     #[serde(flatten)]
-    fields: Value,
+    fields: Arc<Value>,
 
-    #[getset(get = "pub", get_mut = "pub")]
+    #[getset(get = "pub")]
     #[serde(skip)]
     metadata: EventMetadata,
+
+    #[serde(skip)]
+    size_cache: SizeCache,
 }
 
 impl Default for LogEvent {
     fn default() -> Self {
         Self {
-            fields: Value::Map(BTreeMap::new()),
+            fields: Arc::new(Value::Map(BTreeMap::new())),
             metadata: EventMetadata::default(),
+            size_cache: SizeCache::default(),
         }
     }
 }
 
 impl ByteSizeOf for LogEvent {
     fn allocated_bytes(&self) -> usize {
-        self.fields.allocated_bytes() + self.metadata.allocated_bytes()
+        self.size_cache
+            .get_or_compute(|| self.fields.allocated_bytes() + self.metadata.allocated_bytes())
     }
 }
 
 impl Finalizable for LogEvent {
     fn take_finalizers(&mut self) -> EventFinalizers {
-        self.metadata.take_finalizers()
+        self.metadata_mut().take_finalizers()
     }
 }
 
@@ -58,15 +67,20 @@ impl LogEvent {
     #[must_use]
     pub fn new_with_metadata(metadata: EventMetadata) -> Self {
         Self {
-            fields: Value::Map(Default::default()),
+            fields: Arc::new(Value::Map(Default::default())),
             metadata,
+            size_cache: SizeCache::default(),
         }
     }
 
     ///  Create a `LogEvent` into a tuple of its components
     pub fn from_parts(map: BTreeMap<String, Value>, metadata: EventMetadata) -> Self {
-        let fields = Value::Map(map);
-        Self { fields, metadata }
+        let fields = Arc::new(Value::Map(map));
+        Self {
+            fields,
+            metadata,
+            size_cache: SizeCache::default(),
+        }
     }
 
     /// Convert a `LogEvent` into a tuple of its components
@@ -76,25 +90,38 @@ impl LogEvent {
     /// Panics if the fields of the `LogEvent` are not a `Value::Map`.
     pub fn into_parts(self) -> (BTreeMap<String, Value>, EventMetadata) {
         (
-            self.fields
+            Self::unwrap_fields(self.fields)
                 .into_map()
                 .unwrap_or_else(|| unreachable!("fields must be a map")),
             self.metadata,
         )
     }
 
+    /// Takes the fields out of their `Arc`, copying them if they're still
+    /// shared with another event.
+    fn unwrap_fields(fields: Arc<Value>) -> Value {
+        Arc::try_unwrap(fields).unwrap_or_else(|fields| (*fields).clone())
+    }
+
     pub fn with_batch_notifier(mut self, batch: &Arc<BatchNotifier>) -> Self {
+        self.size_cache.invalidate();
         self.metadata = self.metadata.with_batch_notifier(batch);
         self
     }
 
     pub fn with_batch_notifier_option(mut self, batch: &Option<Arc<BatchNotifier>>) -> Self {
+        self.size_cache.invalidate();
         self.metadata = self.metadata.with_batch_notifier_option(batch);
         self
     }
 
     pub fn add_finalizer(&mut self, finalizer: EventFinalizer) {
-        self.metadata.add_finalizer(finalizer);
+        self.metadata_mut().add_finalizer(finalizer);
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut EventMetadata {
+        self.size_cache.invalidate();
+        &mut self.metadata
     }
 
     #[instrument(level = "trace", skip(self, key), fields(key = %key.as_ref()))]
@@ -158,7 +185,7 @@ impl LogEvent {
         K: AsRef<str> + Into<String> + PartialEq + Display,
     {
         if from_key != to_key {
-            if let Some(val) = self.fields.as_map_mut().remove(from_key.as_ref()) {
+            if let Some(val) = self.as_map_mut().remove(from_key.as_ref()) {
                 self.insert_flat(to_key, val);
             }
         }
@@ -198,7 +225,7 @@ impl LogEvent {
 
     #[instrument(level = "trace", skip(self))]
     pub fn keys<'a>(&'a self) -> impl Iterator<Item = String> + 'a {
-        match &self.fields {
+        match self.fields.as_ref() {
             Value::Map(map) => util::log::keys(map),
             _ => unreachable!(),
         }
@@ -216,7 +243,7 @@ impl LogEvent {
 
     #[instrument(level = "trace", skip(self))]
     pub fn as_map(&self) -> &BTreeMap<String, Value> {
-        match &self.fields {
+        match self.fields.as_ref() {
             Value::Map(map) => map,
             _ => unreachable!(),
         }
@@ -224,8 +251,9 @@ impl LogEvent {
 
     #[instrument(level = "trace", skip(self))]
     pub fn as_map_mut(&mut self) -> &mut BTreeMap<String, Value> {
-        match self.fields {
-            Value::Map(ref mut map) => map,
+        self.size_cache.invalidate();
+        match Arc::make_mut(&mut self.fields) {
+            Value::Map(map) => map,
             _ => unreachable!(),
         }
     }
@@ -282,7 +310,7 @@ impl LogEvent {
                 Some(current_val) => current_val.merge(incoming_val),
             }
         }
-        self.metadata.merge(incoming.metadata);
+        self.metadata_mut().merge(incoming.metadata);
     }
 }
 
@@ -324,15 +352,16 @@ impl From<String> for LogEvent {
 impl From<BTreeMap<String, Value>> for LogEvent {
     fn from(map: BTreeMap<String, Value>) -> Self {
         LogEvent {
-            fields: Value::Map(map),
+            fields: Arc::new(Value::Map(map)),
             metadata: EventMetadata::default(),
+            size_cache: SizeCache::default(),
         }
     }
 }
 
 impl From<LogEvent> for BTreeMap<String, Value> {
     fn from(event: LogEvent) -> BTreeMap<String, Value> {
-        match event.fields {
+        match LogEvent::unwrap_fields(event.fields) {
             Value::Map(map) => map,
             _ => unreachable!(),
         }
@@ -342,8 +371,9 @@ impl From<LogEvent> for BTreeMap<String, Value> {
 impl From<HashMap<String, Value>> for LogEvent {
     fn from(map: HashMap<String, Value>) -> Self {
         LogEvent {
-            fields: map.into_iter().collect(),
+            fields: Arc::new(map.into_iter().collect()),
             metadata: EventMetadata::default(),
+            size_cache: SizeCache::default(),
         }
     }
 }
@@ -380,7 +410,7 @@ impl TryInto<serde_json::Value> for LogEvent {
     type Error = crate::Error;
 
     fn try_into(self) -> Result<serde_json::Value, Self::Error> {
-        Ok(serde_json::to_value(self.fields)?)
+        Ok(serde_json::to_value(self.fields.as_ref())?)
     }
 }
 
@@ -854,4 +884,16 @@ mod test {
 
         shared::assert_event_data_eq!(merged, expected);
     }
+
+    #[test]
+    fn clones_share_fields_until_modified() {
+        let original = LogEvent::from("hello world");
+        let mut copy = original.clone();
+        assert!(Arc::ptr_eq(&original.fields, &copy.fields));
+
+        copy.insert("copied", true);
+        assert!(!Arc::ptr_eq(&original.fields, &copy.fields));
+        assert!(!original.contains("copied"));
+        assert_eq!(original["message"], copy["message"]);
+    }
 }
@@ -1,4 +1,5 @@
 use mlua::prelude::*;
+use rust_decimal::prelude::ToPrimitive;
 
 use super::util::{table_is_timestamp, table_to_timestamp, timestamp_to_table};
 use crate::event::Value;
@@ -10,6 +11,11 @@ impl<'a> ToLua<'a> for Value {
             Value::Bytes(b) => lua.create_string(b.as_ref()).map(LuaValue::String),
             Value::Integer(i) => Ok(LuaValue::Integer(i)),
             Value::Float(f) => Ok(LuaValue::Number(f)),
+            // Lua only has 64-bit integers and floats, so decimals lose precision here.
+            Value::Decimal(d) => Ok(match (d.fract().is_zero(), d.to_i64()) {
+                (true, Some(i)) => LuaValue::Integer(i),
+                _ => LuaValue::Number(d.to_f64().unwrap_or(f64::NAN)),
+            }),
             Value::Boolean(b) => Ok(LuaValue::Boolean(b)),
             Value::Timestamp(t) => timestamp_to_table(lua, t).map(LuaValue::Table),
             Value::Map(m) => lua.create_table_from(m.into_iter()).map(LuaValue::Table),
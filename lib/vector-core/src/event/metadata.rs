@@ -1,12 +1,13 @@
 #![deny(missing_docs)]
 
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
+use chrono::{DateTime, Utc};
 use getset::{Getters, Setters};
 use serde::{Deserialize, Serialize};
 use shared::EventDataEq;
 
-use super::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus};
+use super::{BatchNotifier, EventFinalizer, EventFinalizers, EventStatus, Value};
 use crate::ByteSizeOf;
 
 /// The top-level metadata structure contained by both `struct Metric`
@@ -23,6 +24,16 @@ pub struct EventMetadata {
     #[getset(get = "pub", set = "pub")]
     #[serde(default, skip)]
     splunk_hec_token: Option<Arc<str>>,
+    /// The time at which the event happened, as determined by the source's
+    /// event time configuration. Used by time-based transforms for watermarking.
+    #[getset(get = "pub", set = "pub")]
+    #[serde(default, skip)]
+    event_time: Option<DateTime<Utc>>,
+    /// Details of where the event came from, such as the peer address or
+    /// Kafka topic, kept out of the event itself. Exposed to VRL and
+    /// templates under the `source.` prefix.
+    #[serde(default, skip)]
+    source: BTreeMap<String, Value>,
     #[serde(default, skip)]
     finalizers: EventFinalizers,
 }
@@ -32,7 +43,7 @@ impl ByteSizeOf for EventMetadata {
         // NOTE we don't count the `str` here because it's allocated somewhere
         // else. We're just moving around the pointer, which is already captured
         // by `ByteSizeOf::size_of`.
-        self.finalizers.allocated_bytes()
+        self.source.allocated_bytes() + self.finalizers.allocated_bytes()
     }
 }
 
@@ -59,6 +70,8 @@ impl EventMetadata {
     /// Merge the other `EventMetadata` into this.
     /// If a Datadog API key is not set in `self`, the one from `other` will be used.
     /// If a Splunk HEC token is not set in `self`, the one from `other` will be used.
+    /// The earliest of the two event times is kept.
+    /// Source fields from `other` are only added where `self` doesn't already have them.
     pub fn merge(&mut self, other: Self) {
         self.finalizers.merge(other.finalizers);
         if self.datadog_api_key.is_none() {
@@ -67,6 +80,33 @@ impl EventMetadata {
         if self.splunk_hec_token.is_none() {
             self.splunk_hec_token = other.splunk_hec_token;
         }
+        self.event_time = match (self.event_time, other.event_time) {
+            (Some(this), Some(other)) => Some(this.min(other)),
+            (this, other) => this.or(other),
+        };
+        for (key, value) in other.source {
+            self.source.entry(key).or_insert(value);
+        }
+    }
+
+    /// Get the source field with the given name, without the `source.` prefix.
+    pub fn source_field(&self, name: &str) -> Option<&Value> {
+        self.source.get(name)
+    }
+
+    /// Set the source field with the given name, without the `source.` prefix.
+    pub fn set_source_field(&mut self, name: impl Into<String>, value: impl Into<Value>) {
+        self.source.insert(name.into(), value.into());
+    }
+
+    /// Remove the source field with the given name, returning its value.
+    pub fn remove_source_field(&mut self, name: &str) -> Option<Value> {
+        self.source.remove(name)
+    }
+
+    /// All of the source fields attached to the event.
+    pub fn source_fields(&self) -> &BTreeMap<String, Value> {
+        &self.source
     }
 
     /// Update the finalizer(s) status.
@@ -14,37 +14,45 @@ use serde::{Deserialize, Serialize};
 use shared::EventDataEq;
 
 use crate::{
-    event::{BatchNotifier, EventFinalizer, EventFinalizers, EventMetadata, Finalizable},
+    event::{
+        size_cache::SizeCache, BatchNotifier, EventFinalizer, EventFinalizers, EventMetadata,
+        Finalizable,
+    },
     metrics::{AgentDDSketch, Handle},
     ByteSizeOf,
 };
 
-#[derive(Clone, Debug, Deserialize, Getters, MutGetters, PartialEq, PartialOrd, Serialize)]
+#[derive(Clone, Debug, Deserialize, Getters, PartialEq, PartialOrd, Serialize)]
 pub struct Metric {
-    #[getset(get = "pub")]
+    /// The name and tags, shared between clones until one of them is modified.
     #[serde(flatten)]
-    pub(super) series: MetricSeries,
+    pub(super) series: Arc<MetricSeries>,
 
-    #[getset(get = "pub", get_mut = "pub")]
+    #[getset(get = "pub")]
     #[serde(flatten)]
     pub(super) data: MetricData,
 
-    #[getset(get = "pub", get_mut = "pub")]
+    #[getset(get = "pub")]
     #[serde(skip_serializing, default = "EventMetadata::default")]
     metadata: EventMetadata,
+
+    #[serde(skip)]
+    size_cache: SizeCache,
 }
 
 impl ByteSizeOf for Metric {
     fn allocated_bytes(&self) -> usize {
-        self.series.allocated_bytes()
-            + self.data.allocated_bytes()
-            + self.metadata.allocated_bytes()
+        self.size_cache.get_or_compute(|| {
+            self.series.allocated_bytes()
+                + self.data.allocated_bytes()
+                + self.metadata.allocated_bytes()
+        })
     }
 }
 
 impl Finalizable for Metric {
     fn take_finalizers(&mut self) -> EventFinalizers {
-        self.metadata.take_finalizers()
+        self.metadata_mut().take_finalizers()
     }
 }
 
@@ -101,6 +109,11 @@ pub struct MetricData {
     #[getset(get = "pub", get_mut = "pub")]
     #[serde(flatten)]
     pub value: MetricValue,
+
+    /// Marks the last data point of the series. Sinks that keep state per series forget the series
+    /// when they receive it, instead of waiting for it to expire.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub series_ended: bool,
 }
 
 impl PartialOrd for MetricData {
@@ -541,31 +554,33 @@ impl Metric {
         metadata: EventMetadata,
     ) -> Self {
         Self {
-            series: MetricSeries {
+            series: Arc::new(MetricSeries {
                 name: MetricName {
                     name: name.into(),
                     namespace: None,
                 },
                 tags: None,
-            },
+            }),
             data: MetricData {
                 timestamp: None,
                 kind,
                 value,
+                series_ended: false,
             },
             metadata,
+            size_cache: SizeCache::default(),
         }
     }
 
     #[inline]
     pub fn with_name(mut self, name: impl Into<String>) -> Self {
-        self.series.name.name = name.into();
+        self.series_mut().name.name = name.into();
         self
     }
 
     #[inline]
     pub fn with_namespace<T: Into<String>>(mut self, namespace: Option<T>) -> Self {
-        self.series.name.namespace = namespace.map(Into::into);
+        self.series_mut().name.namespace = namespace.map(Into::into);
         self
     }
 
@@ -576,42 +591,78 @@ impl Metric {
     }
 
     pub fn add_finalizer(&mut self, finalizer: EventFinalizer) {
-        self.metadata.add_finalizer(finalizer);
+        self.metadata_mut().add_finalizer(finalizer);
     }
 
     pub fn with_batch_notifier(mut self, batch: &Arc<BatchNotifier>) -> Self {
+        self.size_cache.invalidate();
         self.metadata = self.metadata.with_batch_notifier(batch);
         self
     }
 
     pub fn with_batch_notifier_option(mut self, batch: &Option<Arc<BatchNotifier>>) -> Self {
+        self.size_cache.invalidate();
         self.metadata = self.metadata.with_batch_notifier_option(batch);
         self
     }
 
     #[inline]
     pub fn with_tags(mut self, tags: Option<MetricTags>) -> Self {
-        self.series.tags = tags;
+        self.series_mut().tags = tags;
         self
     }
 
     #[inline]
     pub fn with_value(mut self, value: MetricValue) -> Self {
+        self.size_cache.invalidate();
         self.data.value = value;
         self
     }
 
+    #[inline]
+    pub fn with_series_ended(mut self, series_ended: bool) -> Self {
+        self.data.series_ended = series_ended;
+        self
+    }
+
+    #[inline]
+    pub fn series(&self) -> &MetricSeries {
+        &self.series
+    }
+
+    /// Returns the series for modification, first copying it if it's shared
+    /// with another metric.
+    #[inline]
+    pub(super) fn series_mut(&mut self) -> &mut MetricSeries {
+        self.size_cache.invalidate();
+        Arc::make_mut(&mut self.series)
+    }
+
+    #[inline]
+    pub fn data_mut(&mut self) -> &mut MetricData {
+        self.size_cache.invalidate();
+        &mut self.data
+    }
+
+    #[inline]
+    pub fn metadata_mut(&mut self) -> &mut EventMetadata {
+        self.size_cache.invalidate();
+        &mut self.metadata
+    }
+
     #[inline]
     pub fn into_parts(self) -> (MetricSeries, MetricData, EventMetadata) {
-        (self.series, self.data, self.metadata)
+        let series = Arc::try_unwrap(self.series).unwrap_or_else(|series| (*series).clone());
+        (series, self.data, self.metadata)
     }
 
     #[inline]
     pub fn from_parts(series: MetricSeries, data: MetricData, metadata: EventMetadata) -> Self {
         Self {
-            series,
+            series: Arc::new(series),
             data,
             metadata,
+            size_cache: SizeCache::default(),
         }
     }
 
@@ -621,6 +672,7 @@ impl Metric {
             series: self.series,
             data: self.data.into_absolute(),
             metadata: self.metadata,
+            size_cache: SizeCache::default(),
         }
     }
 
@@ -630,6 +682,7 @@ impl Metric {
             series: self.series,
             data: self.data.into_incremental(),
             metadata: self.metadata,
+            size_cache: SizeCache::default(),
         }
     }
 
@@ -687,7 +740,7 @@ impl Metric {
 
     #[inline]
     pub fn take_namespace(&mut self) -> Option<String> {
-        self.series.name.namespace.take()
+        self.series_mut().name.namespace.take()
     }
 
     #[inline]
@@ -710,11 +763,16 @@ impl Metric {
         self.data.kind
     }
 
+    #[inline]
+    pub fn series_ended(&self) -> bool {
+        self.data.series_ended
+    }
+
     /// Remove the tag entry for the named key, if it exists, and return
     /// the old value. *Note:* This will drop the tags map if the tag
     /// was the last entry in it.
     pub fn remove_tag(&mut self, key: &str) -> Option<String> {
-        self.series.remove_tag(key)
+        self.series_mut().remove_tag(key)
     }
 
     /// Returns `true` if `name` tag is present, and matches the provided `value`
@@ -732,39 +790,39 @@ impl Metric {
     /// Set or updates the string value of a tag. *Note:* This will
     /// create the tags map if it is not present.
     pub fn insert_tag(&mut self, name: String, value: String) -> Option<String> {
-        self.series.insert_tag(name, value)
+        self.series_mut().insert_tag(name, value)
     }
 
     /// Get the tag entry for the named key. *Note:* This will create
     /// the tags map if it is not present, even if nothing is later
     /// inserted.
     pub fn tag_entry(&mut self, key: String) -> btree_map::Entry<String, String> {
-        self.series.tag_entry(key)
+        self.series_mut().tag_entry(key)
     }
 
     /// Zero out the data in this metric
     pub fn zero(&mut self) {
-        self.data.zero();
+        self.data_mut().zero();
     }
 
     /// Add the data from the other metric to this one. The `other` must
     /// be incremental and contain the same value type as this one.
     #[must_use]
     pub fn add(&mut self, other: impl AsRef<MetricData>) -> bool {
-        self.data.add(other.as_ref())
+        self.data_mut().add(other.as_ref())
     }
 
     /// Update this `MetricData` by adding the value from another.
     #[must_use]
     pub fn update(&mut self, other: impl AsRef<MetricData>) -> bool {
-        self.data.update(other.as_ref())
+        self.data_mut().update(other.as_ref())
     }
 
     /// Subtract the data from the other metric from this one. The
     /// `other` must contain the same value type as this one.
     #[must_use]
     pub fn subtract(&mut self, other: impl AsRef<MetricData>) -> bool {
-        self.data.subtract(other.as_ref())
+        self.data_mut().subtract(other.as_ref())
     }
 }
 
@@ -814,6 +872,7 @@ impl MetricData {
             timestamp: self.timestamp,
             kind: MetricKind::Absolute,
             value: self.value,
+            series_ended: self.series_ended,
         }
     }
 
@@ -823,6 +882,7 @@ impl MetricData {
             timestamp: self.timestamp,
             kind: MetricKind::Incremental,
             value: self.value,
+            series_ended: self.series_ended,
         }
     }
 
@@ -836,6 +896,7 @@ impl MetricData {
             timestamp,
             kind,
             value,
+            series_ended: false,
         }
     }
 
@@ -1277,6 +1338,22 @@ mod test {
         Utc.ymd(2018, 11, 14).and_hms_nano(8, 9, 10, 11)
     }
 
+    #[test]
+    fn clones_share_series_until_modified() {
+        let original = Metric::new(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(tags()));
+        let mut copy = original.clone();
+        assert!(Arc::ptr_eq(&original.series, &copy.series));
+
+        copy.insert_tag("copied".to_owned(), "true".to_owned());
+        assert!(!Arc::ptr_eq(&original.series, &copy.series));
+        assert_eq!(original.tags(), Some(&tags()));
+    }
+
     fn tags() -> MetricTags {
         vec![
             ("normal_tag".to_owned(), "value".to_owned()),
@@ -12,7 +12,9 @@ use prost::{DecodeError, EncodeError, Message};
 use shared::EventDataEq;
 
 use crate::ByteSizeOf;
-pub use array::{EventArray, EventContainer, LogArray, MetricArray};
+pub use array::{
+    EventArray, EventArrayIter, EventArrayIterMut, EventContainer, LogArray, MetricArray,
+};
 pub use finalization::{
     BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizer, EventFinalizers, EventStatus,
     Finalizable,
@@ -38,12 +40,14 @@ pub mod merge_state;
 mod metadata;
 pub mod metric;
 pub mod proto;
+mod size_cache;
 #[cfg(test)]
 mod test;
 pub mod util;
 mod value;
 #[cfg(feature = "vrl")]
 mod vrl_target;
+pub mod watermark;
 
 pub const PARTIAL: &str = "_partial";
 
@@ -404,6 +408,35 @@ impl<'a> From<&'a Metric> for EventRef<'a> {
     }
 }
 
+/// A wrapper for mutable references to inner event types, where reconstituting
+/// a full `Event` from a `LogEvent` or `Metric` might be inconvenient.
+#[derive(Debug)]
+pub enum EventMutRef<'a> {
+    Log(&'a mut LogEvent),
+    Metric(&'a mut Metric),
+}
+
+impl<'a> From<&'a mut Event> for EventMutRef<'a> {
+    fn from(event: &'a mut Event) -> Self {
+        match event {
+            Event::Log(log) => log.into(),
+            Event::Metric(metric) => metric.into(),
+        }
+    }
+}
+
+impl<'a> From<&'a mut LogEvent> for EventMutRef<'a> {
+    fn from(log: &'a mut LogEvent) -> Self {
+        Self::Log(log)
+    }
+}
+
+impl<'a> From<&'a mut Metric> for EventMutRef<'a> {
+    fn from(metric: &'a mut Metric) -> Self {
+        Self::Metric(metric)
+    }
+}
+
 impl EncodeBytes<Event> for Event {
     type Error = EncodeError;
 
@@ -29,6 +29,90 @@ impl From<Metric> for Event {
     }
 }
 
+impl From<event::EventArray> for EventArray {
+    fn from(array: event::EventArray) -> Self {
+        let events = match array {
+            event::EventArray::Logs(logs) => event_array::Events::Logs(LogArray {
+                logs: logs.into_iter().map(Into::into).collect(),
+            }),
+            event::EventArray::Metrics(metrics) => event_array::Events::Metrics(MetricArray {
+                metrics: metrics.into_iter().map(Into::into).collect(),
+            }),
+        };
+        Self {
+            events: Some(events),
+        }
+    }
+}
+
+impl From<EventArray> for event::EventArray {
+    fn from(array: EventArray) -> Self {
+        match array.events {
+            Some(event_array::Events::Logs(array)) => {
+                Self::Logs(array.logs.into_iter().map(Into::into).collect())
+            }
+            Some(event_array::Events::Metrics(array)) => {
+                Self::Metrics(array.metrics.into_iter().map(Into::into).collect())
+            }
+            None => Self::Logs(Vec::new()),
+        }
+    }
+}
+
+impl EventArray {
+    /// The number of events in this array.
+    pub fn len(&self) -> usize {
+        match &self.events {
+            Some(event_array::Events::Logs(array)) => array.logs.len(),
+            Some(event_array::Events::Metrics(array)) => array.metrics.len(),
+            None => 0,
+        }
+    }
+
+    /// Returns `true` if the array has no events.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Adds the event to this array, if it is of the same type as the events
+    /// already in it. Otherwise, the event is handed back.
+    pub fn try_push(&mut self, event: Event) -> Result<(), Event> {
+        match (&mut self.events, event) {
+            (Some(event_array::Events::Logs(array)), Event::Log(log)) => array.logs.push(log),
+            (Some(event_array::Events::Metrics(array)), Event::Metric(metric)) => {
+                array.metrics.push(metric);
+            }
+            (None, Event::Log(log)) => {
+                self.events = Some(event_array::Events::Logs(LogArray { logs: vec![log] }));
+            }
+            (None, Event::Metric(metric)) => {
+                self.events = Some(event_array::Events::Metrics(MetricArray {
+                    metrics: vec![metric],
+                }));
+            }
+            (_, event) => return Err(event),
+        }
+        Ok(())
+    }
+
+    /// Splits this array into individually wrapped events.
+    pub fn into_wrappers(self) -> Vec<EventWrapper> {
+        match self.events {
+            Some(event_array::Events::Logs(array)) => array
+                .logs
+                .into_iter()
+                .map(|log| Event::Log(log).into())
+                .collect(),
+            Some(event_array::Events::Metrics(array)) => array
+                .metrics
+                .into_iter()
+                .map(|metric| Event::Metric(metric).into())
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
 impl From<Log> for event::LogEvent {
     fn from(log: Log) -> Self {
         let fields = log
@@ -113,6 +197,7 @@ impl From<Metric> for event::Metric {
             .with_namespace(namespace)
             .with_tags(tags)
             .with_timestamp(timestamp)
+            .with_series_ended(metric.series_ended)
     }
 }
 
@@ -164,6 +249,7 @@ impl From<event::Metric> for WithMetadata<Metric> {
         });
 
         let tags = series.tags.unwrap_or_default();
+        let series_ended = data.series_ended;
 
         let kind = match data.kind {
             event::MetricKind::Incremental => metric::Kind::Incremental,
@@ -234,6 +320,7 @@ impl From<event::Metric> for WithMetadata<Metric> {
             tags,
             kind,
             value: Some(metric),
+            series_ended,
         };
         Self { data, metadata }
     }
@@ -326,6 +413,13 @@ fn decode_value(input: Value) -> Option<event::Value> {
         )),
         Some(value::Kind::Integer(value)) => Some(event::Value::Integer(value)),
         Some(value::Kind::Float(value)) => Some(event::Value::Float(value)),
+        Some(value::Kind::Decimal(value)) => match value.parse() {
+            Ok(value) => Some(event::Value::Decimal(value)),
+            Err(_) => {
+                error!(message = "Encoded event contains an invalid decimal.", %value);
+                None
+            }
+        },
         Some(value::Kind::Boolean(value)) => Some(event::Value::Boolean(value)),
         Some(value::Kind::Map(map)) => decode_map(map.fields),
         Some(value::Kind::Array(array)) => decode_array(array.items),
@@ -371,6 +465,7 @@ fn encode_value(value: event::Value) -> Value {
             })),
             event::Value::Integer(value) => Some(value::Kind::Integer(value)),
             event::Value::Float(value) => Some(value::Kind::Float(value)),
+            event::Value::Decimal(value) => Some(value::Kind::Decimal(value.to_string())),
             event::Value::Boolean(value) => Some(value::Kind::Boolean(value)),
             event::Value::Map(fields) => Some(value::Kind::Map(encode_map(fields))),
             event::Value::Array(items) => Some(value::Kind::Array(encode_array(items))),
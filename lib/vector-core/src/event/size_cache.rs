@@ -0,0 +1,52 @@
+use std::{
+    cmp::Ordering,
+    sync::atomic::{self, AtomicUsize},
+};
+
+/// Remembers the allocated size of an event between calls to
+/// [`ByteSizeOf::allocated_bytes`](crate::ByteSizeOf::allocated_bytes).
+///
+/// The owning event must call [`SizeCache::invalidate`] before handing out
+/// mutable access to anything that contributes to its size. Clones start out
+/// empty, since a cloned collection doesn't keep the spare capacity of the
+/// original. The cache never affects equality or ordering of its owner.
+#[derive(Debug, Default)]
+pub(crate) struct SizeCache(AtomicUsize);
+
+impl SizeCache {
+    /// Returns the cached size, computing and storing it first if needed.
+    pub(crate) fn get_or_compute(&self, compute: impl FnOnce() -> usize) -> usize {
+        // Zero marks an empty cache, so sizes are stored one higher.
+        match self.0.load(atomic::Ordering::Relaxed) {
+            0 => {
+                let size = compute();
+                self.0
+                    .store(size.saturating_add(1), atomic::Ordering::Relaxed);
+                size
+            }
+            cached => cached - 1,
+        }
+    }
+
+    pub(crate) fn invalidate(&mut self) {
+        *self.0.get_mut() = 0;
+    }
+}
+
+impl Clone for SizeCache {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl PartialEq for SizeCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl PartialOrd for SizeCache {
+    fn partial_cmp(&self, _other: &Self) -> Option<Ordering> {
+        Some(Ordering::Equal)
+    }
+}
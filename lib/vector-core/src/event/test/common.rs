@@ -100,7 +100,7 @@ impl Arbitrary for Metric {
         let metadata = EventMetadata::arbitrary(g);
         let mut metric = Metric::new_with_metadata(name, kind, value, metadata);
         metric.data = MetricData::arbitrary(g);
-        metric.series = MetricSeries::arbitrary(g);
+        *metric.series_mut() = MetricSeries::arbitrary(g);
 
         metric
     }
@@ -121,10 +121,10 @@ impl Arbitrary for Metric {
                     })
                 })
                 .flat_map(|metric| {
-                    let series = metric.series.clone();
+                    let series = metric.series().clone();
                     series.shrink().map(move |series| {
                         let mut new_metric = metric.clone();
-                        new_metric.series = series;
+                        *new_metric.series_mut() = series;
                         new_metric
                     })
                 }),
@@ -523,6 +523,7 @@ impl Arbitrary for MetricData {
             timestamp: dt,
             kind: MetricKind::arbitrary(g),
             value: MetricValue::arbitrary(g),
+            series_ended: bool::arbitrary(g),
         }
     }
 
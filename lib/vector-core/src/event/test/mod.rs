@@ -57,3 +57,59 @@ fn event_iteration_order() {
         ]
     );
 }
+
+#[test]
+fn event_array_iteration() {
+    let mut array = EventArray::Logs(vec![LogEvent::default(), LogEvent::default()]);
+    assert_eq!(array.len(), 2);
+    assert!(!array.is_empty());
+
+    for (i, event) in array.iter_events_mut().enumerate() {
+        match event {
+            EventMutRef::Log(log) => {
+                log.insert("index", i as i64);
+            }
+            EventMutRef::Metric(_) => unreachable!(),
+        }
+    }
+
+    let indexes = array
+        .iter_events()
+        .map(|event| match event {
+            EventRef::Log(log) => log.get("index").cloned(),
+            EventRef::Metric(_) => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(indexes, vec![Some(Value::from(0_i64)), Some(Value::from(1_i64))]);
+
+    let mut array = EventArray::Metrics(vec![]);
+    assert!(array.is_empty());
+    assert_eq!(array.iter_events().count(), 0);
+    assert_eq!(array.iter_events_mut().count(), 0);
+}
+
+#[test]
+fn event_iteration_by_reference() {
+    let mut event = Event::Metric(Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    ));
+    assert_eq!(event.len(), 1);
+
+    for event in event.iter_events_mut() {
+        match event {
+            EventMutRef::Metric(metric) => {
+                metric.insert_tag("host".into(), "localhost".into());
+            }
+            EventMutRef::Log(_) => unreachable!(),
+        }
+    }
+
+    let mut events = event.iter_events();
+    assert!(matches!(
+        events.next(),
+        Some(EventRef::Metric(metric)) if metric.tag_value("host").as_deref() == Some("localhost")
+    ));
+    assert!(events.next().is_none());
+}
@@ -65,6 +65,41 @@ fn back_and_forth_through_bytes() {
         .quickcheck(inner as fn(Event) -> TestResult);
 }
 
+// Byte values that aren't valid UTF-8 survive the trip unchanged
+#[test]
+fn binary_values_through_bytes() {
+    let binary = bytes::Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]);
+    let mut event = Event::from("raw log line");
+    event.as_mut_log().insert("payload", binary.clone());
+    event
+        .as_mut_log()
+        .insert("nested.payload", Value::Array(vec![binary.clone().into()]));
+
+    let mut buffer = BytesMut::with_capacity(64);
+    Event::encode(event, &mut buffer).unwrap();
+    let actual = Event::decode(buffer).unwrap();
+
+    assert_eq!(actual.as_log()["payload"], Value::Bytes(binary.clone()));
+    assert_eq!(actual.as_log()["nested.payload[0]"], Value::Bytes(binary));
+}
+
+// Decimals keep their exact value and scale through the protobuf encoding
+#[test]
+fn decimal_values_through_proto() {
+    let price: rust_decimal::Decimal = "1234567890.123456789".parse().unwrap();
+    let mut event = Event::from("raw log line");
+    event.as_mut_log().insert("price", price);
+    event.as_mut_log().insert("id", u64::MAX);
+
+    let mut buffer = BytesMut::with_capacity(64);
+    Event::encode(event, &mut buffer).unwrap();
+    let actual = Event::decode(buffer).unwrap();
+
+    assert_eq!(actual.as_log()["price"], Value::Decimal(price));
+    assert_eq!(actual.as_log()["price"].to_string_lossy(), "1234567890.123456789");
+    assert_eq!(actual.as_log()["id"], Value::Decimal(u64::MAX.into()));
+}
+
 #[test]
 fn serialization() {
     let mut event = Event::from("raw log line");
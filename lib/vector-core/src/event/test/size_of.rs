@@ -1,4 +1,4 @@
-use std::mem;
+use std::{collections::BTreeMap, mem};
 
 use quickcheck::{Arbitrary, Gen, QuickCheck, TestResult};
 
@@ -62,6 +62,45 @@ fn size_greater_than_allocated_size() {
         .quickcheck(inner as fn(Event) -> TestResult);
 }
 
+#[test]
+fn nested_values_count_contents_once() {
+    let inner = Value::from("value");
+    let mut map = BTreeMap::new();
+    map.insert(String::from("key"), inner.clone());
+    let outer = Value::Array(vec![Value::Map(map)]);
+
+    // The array holds one map value, which holds one key and one value.
+    let expected = mem::size_of::<Value>()
+        + mem::size_of::<Value>()
+        + String::from("key").size_of()
+        + inner.size_of();
+    assert_eq!(expected, outer.size_of());
+}
+
+#[test]
+fn cached_size_follows_mutation() {
+    let mut log = LogEvent::from("message");
+    let before = log.size_of();
+    assert_eq!(before, log.size_of());
+
+    log.metadata_mut()
+        .set_source_field("path", "/var/log/messages");
+    assert!(log.size_of() > before);
+
+    let mut metric = Metric::new(
+        "counter",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    );
+    let before = metric.size_of();
+    metric.insert_tag(String::from("host"), String::from("localhost"));
+    let with_tag = metric.size_of();
+    assert!(with_tag > before);
+
+    metric.remove_tag("host");
+    assert_eq!(before, metric.size_of());
+}
+
 //
 // Log Events
 //
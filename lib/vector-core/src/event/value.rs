@@ -9,6 +9,7 @@ use std::{
 use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
 use lookup::{Field, FieldBuf, Lookup, LookupBuf, Segment, SegmentBuf};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use serde::{Deserialize, Serialize, Serializer};
 use toml::value::Value as TomlValue;
 
@@ -22,6 +23,9 @@ pub enum Value {
     Bytes(Bytes),
     Integer(i64),
     Float(f64),
+    /// An exact numeric value, used for integers that do not fit in an `i64`
+    /// and for decimals that must not lose precision through `f64`.
+    Decimal(Decimal),
     Boolean(bool),
     Timestamp(DateTime<Utc>),
     Map(BTreeMap<String, Value>),
@@ -54,6 +58,7 @@ impl PartialEq<Value> for Value {
                 }
             }
             (Value::Integer(a), Value::Integer(b)) => a.eq(b),
+            (Value::Decimal(a), Value::Decimal(b)) => a.eq(b),
             (Value::Map(a), Value::Map(b)) => a.eq(b),
             (Value::Null, Value::Null) => true,
             (Value::Timestamp(a), Value::Timestamp(b)) => a.eq(b),
@@ -92,6 +97,9 @@ impl Hash for Value {
             Value::Integer(v) => {
                 v.hash(state);
             }
+            Value::Decimal(v) => {
+                v.hash(state);
+            }
             Value::Map(v) => {
                 v.hash(state);
             }
@@ -109,8 +117,10 @@ impl ByteSizeOf for Value {
     fn allocated_bytes(&self) -> usize {
         match self {
             Value::Bytes(bytes) => bytes.len(),
-            Value::Map(map) => map.size_of(),
-            Value::Array(arr) => arr.size_of(),
+            // The map or vector itself is already counted in the size of the
+            // `Value`, only its contents are allocated.
+            Value::Map(map) => map.allocated_bytes(),
+            Value::Array(arr) => arr.allocated_bytes(),
             _ => 0,
         }
     }
@@ -124,6 +134,7 @@ impl Serialize for Value {
         match &self {
             Value::Integer(i) => serializer.serialize_i64(*i),
             Value::Float(f) => serializer.serialize_f64(*f),
+            Value::Decimal(d) => serialize_decimal(d, serializer),
             Value::Boolean(b) => serializer.serialize_bool(*b),
             Value::Bytes(_) | Value::Timestamp(_) => {
                 serializer.serialize_str(&self.to_string_lossy())
@@ -135,6 +146,28 @@ impl Serialize for Value {
     }
 }
 
+/// Serializes a decimal as an exact integer whenever it has no fractional part
+/// and fits in a `u64` or `i64`, falling back to a float otherwise. Encoders
+/// that need the exact representation of fractional values convert decimals to
+/// strings before serializing.
+fn serialize_decimal<S>(d: &Decimal, serializer: S) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if d.fract().is_zero() {
+        if let Some(i) = d.to_i64() {
+            return serializer.serialize_i64(i);
+        }
+        if let Some(u) = d.to_u64() {
+            return serializer.serialize_u64(u);
+        }
+    }
+    match d.to_f64() {
+        Some(f) => serializer.serialize_f64(f),
+        None => serializer.serialize_str(&d.to_string()),
+    }
+}
+
 impl From<Bytes> for Value {
     fn from(bytes: Bytes) -> Self {
         Value::Bytes(bytes)
@@ -212,6 +245,31 @@ impl From<f64> for Value {
     }
 }
 
+impl From<Decimal> for Value {
+    fn from(value: Decimal) -> Self {
+        Value::Decimal(value)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(value: u64) -> Self {
+        i64::try_from(value).map_or_else(|_| Value::Decimal(value.into()), Value::Integer)
+    }
+}
+
+impl From<i128> for Value {
+    fn from(value: i128) -> Self {
+        match i64::try_from(value) {
+            Ok(i) => Value::Integer(i),
+            // Values beyond the 96-bit decimal mantissa can only be kept as floats.
+            Err(_) => value
+                .to_string()
+                .parse::<Decimal>()
+                .map_or(Value::Float(value as f64), Value::Decimal),
+        }
+    }
+}
+
 impl From<BTreeMap<String, Value>> for Value {
     fn from(value: BTreeMap<String, Value>) -> Self {
         Value::Map(value)
@@ -264,7 +322,11 @@ impl From<serde_json::Value> for Value {
                     n.as_f64()
                         .map_or_else(|| Value::Bytes(n.to_string().into()), Value::Float)
                 };
-                n.as_i64().map_or_else(float_or_byte, Value::Integer)
+                match (n.as_i64(), n.as_u64()) {
+                    (Some(i), _) => Value::Integer(i),
+                    (None, Some(u)) => Value::from(u),
+                    (None, None) => float_or_byte(),
+                }
             }
             serde_json::Value::String(s) => Value::Bytes(Bytes::from(s)),
             serde_json::Value::Object(obj) => Value::Map(
@@ -288,6 +350,7 @@ impl TryInto<serde_json::Value> for Value {
             Value::Boolean(v) => Ok(serde_json::Value::from(v)),
             Value::Integer(v) => Ok(serde_json::Value::from(v)),
             Value::Float(v) => Ok(serde_json::Value::from(v)),
+            Value::Decimal(v) => Ok(serde_json::to_value(Value::Decimal(v))?),
             Value::Bytes(v) => Ok(serde_json::Value::from(String::from_utf8(v.to_vec())?)),
             Value::Map(v) => Ok(serde_json::to_value(v)?),
             Value::Array(v) => Ok(serde_json::to_value(v)?),
@@ -301,13 +364,14 @@ impl TryInto<serde_json::Value> for Value {
 impl From<vrl_core::Value> for Value {
     fn from(v: vrl_core::Value) -> Self {
         use vrl_core::Value::{
-            Array, Boolean, Bytes, Float, Integer, Null, Object, Regex, Timestamp,
+            Array, Boolean, Bytes, Decimal, Float, Integer, Null, Object, Regex, Timestamp,
         };
 
         match v {
             Bytes(v) => Value::Bytes(v),
             Integer(v) => Value::Integer(v),
             Float(v) => Value::Float(*v),
+            Decimal(v) => Value::Decimal(v),
             Boolean(v) => Value::Boolean(v),
             Object(v) => Value::Map(v.into_iter().map(|(k, v)| (k, v.into())).collect()),
             Array(v) => Value::Array(v.into_iter().map(Into::into).collect()),
@@ -327,6 +391,7 @@ impl From<Value> for vrl_core::Value {
             Value::Bytes(v) => v.into(),
             Value::Integer(v) => v.into(),
             Value::Float(v) => v.into(),
+            Value::Decimal(v) => v.into(),
             Value::Boolean(v) => v.into(),
             Value::Map(v) => Object(v.into_iter().map(|(k, v)| (k, v.into())).collect()),
             Value::Array(v) => Array(v.into_iter().map(Into::into).collect()),
@@ -344,6 +409,7 @@ impl Value {
             Value::Timestamp(timestamp) => timestamp_to_string(timestamp),
             Value::Integer(num) => format!("{}", num),
             Value::Float(num) => format!("{}", num),
+            Value::Decimal(num) => format!("{}", num),
             Value::Boolean(b) => format!("{}", b),
             Value::Map(map) => serde_json::to_string(map).expect("Cannot serialize map"),
             Value::Array(arr) => serde_json::to_string(arr).expect("Cannot serialize array"),
@@ -357,6 +423,7 @@ impl Value {
             Value::Timestamp(timestamp) => Bytes::from(timestamp_to_string(timestamp)),
             Value::Integer(num) => Bytes::from(format!("{}", num)),
             Value::Float(num) => Bytes::from(format!("{}", num)),
+            Value::Decimal(num) => Bytes::from(format!("{}", num)),
             Value::Boolean(b) => Bytes::from(format!("{}", b)),
             Value::Map(map) => Bytes::from(serde_json::to_vec(map).expect("Cannot serialize map")),
             Value::Array(arr) => {
@@ -433,6 +500,7 @@ impl Value {
             Value::Timestamp(_) => "timestamp",
             Value::Integer(_) => "integer",
             Value::Float(_) => "float",
+            Value::Decimal(_) => "decimal",
             Value::Boolean(_) => "boolean",
             Value::Map(_) => "map",
             Value::Array(_) => "array",
@@ -484,6 +552,7 @@ impl Value {
             | Value::Bytes(_)
             | Value::Timestamp(_)
             | Value::Float(_)
+            | Value::Decimal(_)
             | Value::Integer(_) => false,
             Value::Null => true,
             Value::Map(v) => v.is_empty(),
@@ -751,6 +820,7 @@ impl Value {
             | (Some(segment), Value::Bytes(_))
             | (Some(segment), Value::Timestamp(_))
             | (Some(segment), Value::Float(_))
+            | (Some(segment), Value::Decimal(_))
             | (Some(segment), Value::Integer(_))
             | (Some(segment), Value::Null) => {
                 trace!("Encountered descent into a primitive.");
@@ -837,6 +907,7 @@ impl Value {
             | (Some(segment), Value::Bytes(_))
             | (Some(segment), Value::Timestamp(_))
             | (Some(segment), Value::Float(_))
+            | (Some(segment), Value::Decimal(_))
             | (Some(segment), Value::Integer(_))
             | (Some(segment), Value::Null) => {
                 if working_lookup.is_empty() {
@@ -1023,6 +1094,7 @@ impl Value {
             | (Some(_s), Value::Bytes(_))
             | (Some(_s), Value::Timestamp(_))
             | (Some(_s), Value::Float(_))
+            | (Some(_s), Value::Decimal(_))
             | (Some(_s), Value::Integer(_))
             | (Some(_s), Value::Null) => {
                 trace!("Mismatched primitive field while trying to use segment.");
@@ -1072,6 +1144,7 @@ impl Value {
             | (_, Value::Bytes(_))
             | (_, Value::Timestamp(_))
             | (_, Value::Float(_))
+            | (_, Value::Decimal(_))
             | (_, Value::Integer(_))
             | (_, Value::Null) => unimplemented!(),
             // Descend into a coalesce
@@ -1183,6 +1256,7 @@ impl Value {
             | Value::Bytes(_)
             | Value::Timestamp(_)
             | Value::Float(_)
+            | Value::Decimal(_)
             | Value::Integer(_)
             | Value::Null => Box::new(prefix.into_iter()),
             Value::Map(m) => {
@@ -1289,6 +1363,7 @@ impl Value {
             | Value::Bytes(_)
             | Value::Timestamp(_)
             | Value::Float(_)
+            | Value::Decimal(_)
             | Value::Integer(_)
             | Value::Null => Box::new(prefix.map(move |v| (v, self)).into_iter()),
             Value::Map(m) => {
@@ -1380,6 +1455,39 @@ mod test {
         }
     }
 
+    mod value_decimal {
+        use super::*;
+
+        #[test]
+        fn large_unsigned_integers_are_exact() {
+            let json: serde_json::Value = serde_json::from_str("18446744073709551615").unwrap();
+            let value = Value::from(json);
+
+            assert_eq!(value, Value::Decimal(Decimal::from(u64::MAX)));
+            assert_eq!(serde_json::to_string(&value).unwrap(), "18446744073709551615");
+            assert_eq!(value.to_string_lossy(), "18446744073709551615");
+        }
+
+        #[test]
+        fn integers_in_range_stay_integers() {
+            assert_eq!(Value::from(42_u64), Value::Integer(42));
+            assert_eq!(Value::from(-42_i128), Value::Integer(-42));
+            assert_eq!(
+                Value::from(i128::from(i64::MIN) - 1),
+                Value::Decimal(Decimal::from(i64::MIN) - Decimal::from(1))
+            );
+        }
+
+        #[test]
+        fn fractional_decimals() {
+            let value = Value::from("12.30".parse::<Decimal>().unwrap());
+
+            assert_eq!(value.kind(), "decimal");
+            assert_eq!(value.to_string_lossy(), "12.30");
+            assert_eq!(serde_json::to_string(&value).unwrap(), "12.3");
+        }
+    }
+
     mod value_hash {
         use super::*;
 
@@ -1772,6 +1880,7 @@ mod test {
                                 let is_match = match vector_value {
                                     Value::Boolean(_) => expected_type.eq("boolean"),
                                     Value::Integer(_) => expected_type.eq("integer"),
+                                    Value::Decimal(_) => expected_type.eq("decimal"),
                                     Value::Bytes(_) => expected_type.eq("bytes"),
                                     Value::Array { .. } => expected_type.eq("array"),
                                     Value::Map(_) => expected_type.eq("map"),
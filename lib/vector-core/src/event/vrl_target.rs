@@ -17,6 +17,9 @@ const VALID_METRIC_PATHS_GET: &str = ".name, .namespace, .timestamp, .kind, .tag
 /// fields such as `.tags.host.thing`.
 const MAX_METRIC_PATH_DEPTH: usize = 3;
 
+/// Metadata keys starting with this prefix refer to the event's source fields.
+const SOURCE_METADATA_PREFIX: &str = "source.";
+
 /// An adapter to turn `Event`s into `vrl_core::Target`s.
 #[derive(Debug, Clone)]
 pub enum VrlTarget {
@@ -90,22 +93,23 @@ impl vrl_core::Target for VrlTarget {
                         }
                         ["name"] => {
                             let value = value.try_bytes().map_err(|e| e.to_string())?;
-                            metric.series.name.name = String::from_utf8_lossy(&value).into_owned();
+                            metric.series_mut().name.name =
+                                String::from_utf8_lossy(&value).into_owned();
                             return Ok(());
                         }
                         ["namespace"] => {
                             let value = value.try_bytes().map_err(|e| e.to_string())?;
-                            metric.series.name.namespace =
+                            metric.series_mut().name.namespace =
                                 Some(String::from_utf8_lossy(&value).into_owned());
                             return Ok(());
                         }
                         ["timestamp"] => {
                             let value = value.try_timestamp().map_err(|e| e.to_string())?;
-                            metric.data.timestamp = Some(value);
+                            metric.data_mut().timestamp = Some(value);
                             return Ok(());
                         }
                         ["kind"] => {
-                            metric.data.kind = MetricKind::try_from(value)?;
+                            metric.data_mut().kind = MetricKind::try_from(value)?;
                             return Ok(());
                         }
                         _ => {
@@ -225,11 +229,13 @@ impl vrl_core::Target for VrlTarget {
                 if let Some(paths) = path.to_alternative_components(MAX_METRIC_PATH_DEPTH).get(0) {
                     match paths.as_slice() {
                         ["namespace"] => {
-                            return Ok(metric.series.name.namespace.take().map(Into::into))
+                            return Ok(metric.series_mut().name.namespace.take().map(Into::into))
+                        }
+                        ["timestamp"] => {
+                            return Ok(metric.data_mut().timestamp.take().map(Into::into))
                         }
-                        ["timestamp"] => return Ok(metric.data.timestamp.take().map(Into::into)),
                         ["tags"] => {
-                            return Ok(metric.series.tags.take().map(|map| {
+                            return Ok(metric.series_mut().tags.take().map(|map| {
                                 map.into_iter()
                                     .map(|(k, v)| (k, v.into()))
                                     .collect::<vrl_core::Value>()
@@ -266,11 +272,16 @@ impl vrl_core::Target for VrlTarget {
                 .splunk_hec_token()
                 .as_ref()
                 .map(|token| vrl_core::Value::from(token.to_string()))),
-            _ => Err(format!("key {} not available", key)),
+            _ => match key.strip_prefix(SOURCE_METADATA_PREFIX) {
+                Some(name) => Ok(metadata
+                    .source_field(name)
+                    .map(|value| value.clone().into())),
+                None => Err(format!("key {} not available", key)),
+            },
         }
     }
 
-    fn set_metadata(&mut self, key: &str, value: String) -> Result<(), String> {
+    fn set_metadata(&mut self, key: &str, value: vrl_core::Value) -> Result<(), String> {
         let metadata = match self {
             VrlTarget::LogEvent(_, metadata) => metadata,
             VrlTarget::Metric(metric) => metric.metadata_mut(),
@@ -278,14 +289,22 @@ impl vrl_core::Target for VrlTarget {
 
         match key {
             "datadog_api_key" => {
-                metadata.set_datadog_api_key(Some(Arc::from(value.as_str())));
+                let value = value.try_bytes_utf8_lossy().map_err(|e| e.to_string())?;
+                metadata.set_datadog_api_key(Some(Arc::from(value.as_ref())));
                 Ok(())
             }
             "splunk_hec_token" => {
-                metadata.set_splunk_hec_token(Some(Arc::from(value.as_str())));
+                let value = value.try_bytes_utf8_lossy().map_err(|e| e.to_string())?;
+                metadata.set_splunk_hec_token(Some(Arc::from(value.as_ref())));
                 Ok(())
             }
-            _ => Err(format!("key {} not available", key)),
+            _ => match key.strip_prefix(SOURCE_METADATA_PREFIX) {
+                Some(name) => {
+                    metadata.set_source_field(name, Value::from(value));
+                    Ok(())
+                }
+                None => Err(format!("key {} not available", key)),
+            },
         }
     }
 
@@ -304,7 +323,13 @@ impl vrl_core::Target for VrlTarget {
                 metadata.set_splunk_hec_token(None);
                 Ok(())
             }
-            _ => Err(format!("key {} not available", key)),
+            _ => match key.strip_prefix(SOURCE_METADATA_PREFIX) {
+                Some(name) => {
+                    metadata.remove_source_field(name);
+                    Ok(())
+                }
+                None => Err(format!("key {} not available", key)),
+            },
         }
     }
 }
@@ -809,4 +834,29 @@ mod test {
             target.get(&LookupBuf::from_str("tags.foo.flork").unwrap())
         );
     }
+
+    #[test]
+    fn source_metadata() {
+        let mut log = LogEvent::from("hello");
+        log.metadata_mut().set_source_field("peer_ip", "10.0.0.1");
+        let mut target = VrlTarget::new(Event::Log(log));
+
+        assert_eq!(
+            target.get_metadata("source.peer_ip"),
+            Ok(Some(vrl_core::Value::from("10.0.0.1")))
+        );
+        assert_eq!(target.get_metadata("source.topic"), Ok(None));
+
+        target
+            .set_metadata("source.partition", vrl_core::Value::from(3))
+            .unwrap();
+        target.remove_metadata("source.peer_ip").unwrap();
+        assert!(target.get_metadata("peer_ip").is_err());
+
+        let event = target.into_events().next().unwrap();
+        let metadata = event.as_log().metadata();
+        assert_eq!(metadata.source_field("peer_ip"), None);
+        assert_eq!(metadata.source_field("partition"), Some(&Value::from(3)));
+        assert_eq!(event.as_log().get("partition"), None);
+    }
 }
@@ -0,0 +1,157 @@
+#![deny(missing_docs)]
+//! Event-time tracking and watermarks.
+//!
+//! Sources may be configured to stamp each event with its *event time*, the
+//! moment the event actually happened as opposed to when Vector received it.
+//! Time-based transforms can then track a [`Watermark`] over the event times
+//! they observe to decide whether a newly arrived event is late.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{Event, Value};
+
+/// Configuration of which field carries the event time for a source.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventTimeConfig {
+    /// The log field holding the event time. Metrics always use their own timestamp.
+    pub field: String,
+}
+
+impl EventTimeConfig {
+    /// Stamp the event time onto the event metadata, if it can be determined.
+    ///
+    /// Log fields are accepted either as timestamps or as integer Unix
+    /// timestamps in seconds. Events without a usable value are left as-is.
+    pub fn stamp(&self, event: &mut Event) {
+        let event_time = match event {
+            Event::Log(log) => log.get(&self.field).and_then(value_to_event_time),
+            Event::Metric(metric) => metric.timestamp(),
+        };
+        if let Some(event_time) = event_time {
+            event.metadata_mut().set_event_time(Some(event_time));
+        }
+    }
+}
+
+fn value_to_event_time(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Timestamp(ts) => Some(*ts),
+        Value::Integer(secs) => Utc.timestamp_opt(*secs, 0).single(),
+        _ => None,
+    }
+}
+
+/// A low watermark over observed event times.
+///
+/// The watermark trails the greatest event time seen so far by the allowed
+/// lateness. Events with an event time before the watermark are considered
+/// late. Events without an event time are never late.
+#[derive(Clone, Debug)]
+pub struct Watermark {
+    allowed_lateness: Duration,
+    max_event_time: Option<DateTime<Utc>>,
+}
+
+impl Watermark {
+    /// Create a new watermark allowing events to arrive up to `allowed_lateness` out of order.
+    pub fn new(allowed_lateness: Duration) -> Self {
+        Self {
+            allowed_lateness,
+            max_event_time: None,
+        }
+    }
+
+    /// The current watermark, or `None` if no event time has been observed yet.
+    pub fn current(&self) -> Option<DateTime<Utc>> {
+        self.max_event_time.map(|max| max - self.allowed_lateness)
+    }
+
+    /// Advance the watermark with an observed event time.
+    pub fn observe(&mut self, event_time: DateTime<Utc>) {
+        if self.max_event_time.map_or(true, |max| event_time > max) {
+            self.max_event_time = Some(event_time);
+        }
+    }
+
+    /// Returns `true` if the given event time falls before the current watermark.
+    pub fn is_late(&self, event_time: DateTime<Utc>) -> bool {
+        self.current()
+            .map_or(false, |watermark| event_time < watermark)
+    }
+
+    /// Check whether the event is late and, if it is not, advance the watermark with it.
+    pub fn observe_event(&mut self, event: &Event) -> bool {
+        match event.metadata().event_time() {
+            Some(event_time) if self.is_late(*event_time) => true,
+            Some(event_time) => {
+                self.observe(*event_time);
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn ts(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp(secs, 0)
+    }
+
+    #[test]
+    fn stamps_log_event_time() {
+        let config = EventTimeConfig {
+            field: "happened_at".into(),
+        };
+
+        let mut log = LogEvent::default();
+        log.insert("happened_at", ts(100));
+        let mut event = Event::from(log);
+        config.stamp(&mut event);
+        assert_eq!(event.metadata().event_time(), &Some(ts(100)));
+
+        let mut log = LogEvent::default();
+        log.insert("happened_at", 200);
+        let mut event = Event::from(log);
+        config.stamp(&mut event);
+        assert_eq!(event.metadata().event_time(), &Some(ts(200)));
+
+        let mut event = Event::from(LogEvent::from("no event time"));
+        config.stamp(&mut event);
+        assert_eq!(event.metadata().event_time(), &None);
+    }
+
+    #[test]
+    fn watermark_trails_max_event_time() {
+        let mut watermark = Watermark::new(Duration::seconds(10));
+        assert_eq!(watermark.current(), None);
+        assert!(!watermark.is_late(ts(0)));
+
+        watermark.observe(ts(100));
+        watermark.observe(ts(50));
+        assert_eq!(watermark.current(), Some(ts(90)));
+        assert!(!watermark.is_late(ts(90)));
+        assert!(watermark.is_late(ts(89)));
+    }
+
+    #[test]
+    fn watermark_observes_events() {
+        let mut watermark = Watermark::new(Duration::seconds(5));
+
+        let mut on_time = Event::from(LogEvent::default());
+        on_time.metadata_mut().set_event_time(Some(ts(100)));
+        assert!(!watermark.observe_event(&on_time));
+
+        let mut late = Event::from(LogEvent::default());
+        late.metadata_mut().set_event_time(Some(ts(94)));
+        assert!(watermark.observe_event(&late));
+        assert_eq!(watermark.current(), Some(ts(95)));
+
+        assert!(!watermark.observe_event(&Event::from(LogEvent::default())));
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, num::NonZeroUsize};
 
 use async_trait::async_trait;
 use indexmap::IndexMap;
@@ -19,6 +19,16 @@ pub enum ExpandType {
     Serial { alias: bool },
 }
 
+/// How the events of a transform are spread over multiple tasks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Parallelism {
+    /// The number of tasks running the transform.
+    pub tasks: NonZeroUsize,
+    /// The field whose value picks the task an event goes to, so that the events sharing a value
+    /// keep their order. Without it, batches of events are spread over the tasks in turn.
+    pub key_field: Option<String>,
+}
+
 #[derive(Debug, Default)]
 pub struct TransformContext {
     // This is optional because currently there are a lot of places we use `TransformContext` that
@@ -60,6 +70,12 @@ pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone
         false
     }
 
+    /// Returns how the events of the transform are spread over a fixed number of tasks, if the
+    /// transform has been configured to do so. This takes precedence over `enable_concurrency`.
+    fn parallelism(&self) -> Option<Parallelism> {
+        None
+    }
+
     /// Allows to detect if a transform can be embedded in another transform.
     /// It's used by the pipelines transform for now.
     fn nestable(&self, _parents: &HashSet<&'static str>) -> bool {
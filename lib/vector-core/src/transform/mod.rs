@@ -11,7 +11,7 @@ use crate::{
 
 #[cfg(any(feature = "lua"))]
 pub mod runtime_transform;
-pub use config::{ExpandType, TransformConfig, TransformContext};
+pub use config::{ExpandType, Parallelism, TransformConfig, TransformContext};
 
 mod config;
 
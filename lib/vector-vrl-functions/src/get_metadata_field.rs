@@ -17,11 +17,18 @@ impl Function for GetMetadataField {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "Get the datadog api key",
-            source: r#"get_metadata_field("datadog_api_key")"#,
-            result: Ok("null"),
-        }]
+        &[
+            Example {
+                title: "Get the datadog api key",
+                source: r#"get_metadata_field("datadog_api_key")"#,
+                result: Ok("null"),
+            },
+            Example {
+                title: "Get the address of the peer an event was received from",
+                source: r#"get_metadata_field("source.peer_ip")"#,
+                result: Ok("null"),
+            },
+        ]
     }
 
     fn compile(
@@ -30,12 +37,7 @@ impl Function for GetMetadataField {
         _ctx: &FunctionCompileContext,
         mut arguments: ArgumentList,
     ) -> Compiled {
-        let keys = vec![value!("datadog_api_key"), value!("splunk_hec_token")];
-        let key = arguments
-            .required_enum("key", &keys)?
-            .try_bytes_utf8_lossy()
-            .expect("key not bytes")
-            .to_string();
+        let key = crate::metadata_key(&mut arguments)?;
 
         Ok(Box::new(GetMetadataFieldFn { key }))
     }
@@ -55,6 +57,10 @@ impl Expression for GetMetadataFieldFn {
     }
 
     fn type_def(&self, _: &state::Compiler) -> TypeDef {
-        TypeDef::new().infallible().bytes().add_null()
+        if crate::is_source_key(&self.key) {
+            TypeDef::new().infallible().unknown()
+        } else {
+            TypeDef::new().infallible().bytes().add_null()
+        }
     }
 }
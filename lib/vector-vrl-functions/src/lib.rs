@@ -1,7 +1,50 @@
+use vrl_core::{function::ArgumentList, Value};
+
 pub mod get_metadata_field;
 pub mod remove_metadata_field;
 pub mod set_metadata_field;
 
+/// Metadata keys with a fixed meaning, used by specific sinks.
+const FIXED_KEYS: &[&str] = &["datadog_api_key", "splunk_hec_token"];
+
+/// The prefix of the keys sources attach to describe where an event came from, such as
+/// `source.peer_ip` or `source.topic`.
+const SOURCE_PREFIX: &str = "source.";
+
+/// Reads the literal `key` argument, which must either be one of the fixed keys or a key in the
+/// `source.` namespace.
+fn metadata_key(arguments: &mut ArgumentList) -> Result<String, vrl_core::function::Error> {
+    let value = arguments.required_literal("key")?.to_value();
+    let key = match value.as_bytes() {
+        Some(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        None => {
+            return Err(vrl_core::function::Error::InvalidArgument {
+                keyword: "key",
+                value,
+                error: "metadata key must be a string",
+            })
+        }
+    };
+
+    let valid = FIXED_KEYS.contains(&key.as_str())
+        || key
+            .strip_prefix(SOURCE_PREFIX)
+            .map_or(false, |name| !name.is_empty());
+    if valid {
+        Ok(key)
+    } else {
+        Err(vrl_core::function::Error::InvalidArgument {
+            keyword: "key",
+            value: Value::from(key),
+            error: r#"metadata key must be "datadog_api_key", "splunk_hec_token" or start with "source.""#,
+        })
+    }
+}
+
+fn is_source_key(key: &str) -> bool {
+    key.starts_with(SOURCE_PREFIX)
+}
+
 pub fn vrl_functions() -> Vec<Box<dyn vrl_core::Function>> {
     vec![
         Box::new(get_metadata_field::GetMetadataField) as Box<dyn vrl_core::Function>,
@@ -30,12 +30,7 @@ impl Function for RemoveMetadataField {
         _ctx: &FunctionCompileContext,
         mut arguments: ArgumentList,
     ) -> Compiled {
-        let keys = vec![value!("datadog_api_key"), value!("splunk_hec_token")];
-        let key = arguments
-            .required_enum("key", &keys)?
-            .try_bytes_utf8_lossy()
-            .expect("key not bytes")
-            .to_string();
+        let key = crate::metadata_key(&mut arguments)?;
 
         Ok(Box::new(RemoveMetadataFieldFn { key }))
     }
@@ -17,18 +17,25 @@ impl Function for SetMetadataField {
             },
             Parameter {
                 keyword: "value",
-                kind: kind::BYTES,
+                kind: kind::ANY,
                 required: true,
             },
         ]
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "Sets the datadog api key",
-            source: r#"set_metadata_field("datadog_api_key", "abc123")"#,
-            result: Ok("null"),
-        }]
+        &[
+            Example {
+                title: "Sets the datadog api key",
+                source: r#"set_metadata_field("datadog_api_key", "abc123")"#,
+                result: Ok("null"),
+            },
+            Example {
+                title: "Sets a source field",
+                source: r#"set_metadata_field("source.tenant", "acme")"#,
+                result: Ok("null"),
+            },
+        ]
     }
 
     fn compile(
@@ -37,12 +44,7 @@ impl Function for SetMetadataField {
         _ctx: &FunctionCompileContext,
         mut arguments: ArgumentList,
     ) -> Compiled {
-        let keys = vec![value!("datadog_api_key"), value!("splunk_hec_token")];
-        let key = arguments
-            .required_enum("key", &keys)?
-            .try_bytes_utf8_lossy()
-            .expect("key not bytes")
-            .to_string();
+        let key = crate::metadata_key(&mut arguments)?;
         let value = arguments.required("value");
 
         Ok(Box::new(SetMetadataFieldFn { key, value }))
@@ -57,7 +59,11 @@ struct SetMetadataFieldFn {
 
 impl Expression for SetMetadataFieldFn {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
-        let value = self.value.resolve(ctx)?.try_bytes_utf8_lossy()?.to_string();
+        let value = self.value.resolve(ctx)?;
+        if !crate::is_source_key(&self.key) {
+            // The fixed keys only ever hold strings.
+            value.try_bytes_utf8_lossy()?;
+        }
         ctx.target_mut().set_metadata(&self.key, value)?;
         Ok(Value::Null)
     }
@@ -170,6 +170,7 @@ fn serde_to_vrl(value: serde_json::Value) -> Value {
             .into(),
         Value::Bool(v) => v.into(),
         Value::Number(v) if v.is_f64() => v.as_f64().unwrap().into(),
+        Value::Number(v) if v.is_u64() => v.as_u64().unwrap().into(),
         Value::Number(v) => v.as_i64().unwrap_or(i64::MAX).into(),
         Value::String(v) => v.into(),
         Value::Array(v) => v.into_iter().map(serde_to_vrl).collect::<Vec<_>>().into(),
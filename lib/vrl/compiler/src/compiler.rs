@@ -5,7 +5,7 @@ use diagnostic::DiagnosticError;
 use ordered_float::NotNan;
 use parser::ast::{self, AssignmentOp, Node};
 
-use crate::{expression::*, Function, Program, State, Value};
+use crate::{expression::*, optimizer, Function, Program, State, Value};
 
 pub type Errors = Vec<Box<dyn DiagnosticError>>;
 
@@ -29,16 +29,20 @@ impl<'a> Compiler<'a> {
     }
 
     pub(super) fn compile(mut self, ast: parser::Program) -> Result<Program, Errors> {
-        let expressions = self
-            .compile_root_exprs(ast)
-            .into_iter()
-            .map(|expr| Box::new(expr) as _)
-            .collect();
+        let expressions = self.compile_root_exprs(ast);
 
         if !self.errors.is_empty() {
             return Err(self.errors);
         }
 
+        let expressions = expressions
+            .into_iter()
+            .map(|mut expr| {
+                optimizer::fold(&mut expr, self.state);
+                Box::new(expr) as _
+            })
+            .collect();
+
         Ok(Program {
             expressions,
             fallible: self.fallible,
@@ -357,7 +361,10 @@ impl<'a> Compiler<'a> {
 
     fn compile_function_argument(&mut self, node: Node<ast::FunctionArgument>) -> FunctionArgument {
         let ast::FunctionArgument { ident, expr } = node.into_inner();
-        let expr = Node::new(expr.span(), self.compile_expr(expr));
+        let span = expr.span();
+        let mut expr = self.compile_expr(expr);
+        optimizer::fold(&mut expr, self.state);
+        let expr = Node::new(span, expr);
         FunctionArgument::new(ident, expr)
     }
 
@@ -141,6 +141,12 @@ impl Assignment {
 
         Self { variant }
     }
+
+    pub(crate) fn expr_mut(&mut self) -> &mut Expr {
+        match &mut self.variant {
+            Variant::Single { expr, .. } | Variant::Infallible { expr, .. } => expr,
+        }
+    }
 }
 
 impl Expression for Assignment {
@@ -7,7 +7,7 @@ use crate::{
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Block {
-    inner: Vec<Expr>,
+    pub(crate) inner: Vec<Expr>,
 }
 
 impl Block {
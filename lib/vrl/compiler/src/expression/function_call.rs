@@ -5,6 +5,7 @@ use diagnostic::{DiagnosticError, Label, Note, Urls};
 use crate::{
     expression::{levenstein, ExpressionError, FunctionArgument, Noop},
     function::{ArgumentList, FunctionCompileContext, Parameter},
+    optimizer,
     parser::{Ident, Node},
     value::Kind,
     Context, Expression, Function, Resolved, Span, State, TypeDef,
@@ -16,6 +17,9 @@ pub struct FunctionCall {
     expr: Box<dyn Expression>,
     maybe_fallible_arguments: bool,
 
+    // Calls to pure functions with constant arguments are resolved once, at compile-time.
+    constant: bool,
+
     // used for enhancing runtime error messages (using abort-instruction).
     //
     // TODO: have span store line/col details to further improve this.
@@ -59,6 +63,11 @@ impl FunctionCall {
             }
         };
 
+        let constant = function.is_pure()
+            && arguments
+                .iter()
+                .all(|argument| optimizer::is_constant(argument.inner().inner()));
+
         // Check function arity.
         if arguments.len() > function.parameters().len() {
             let arguments_span = {
@@ -192,6 +201,7 @@ impl FunctionCall {
             abort_on_error,
             expr,
             maybe_fallible_arguments,
+            constant,
             span: call_span,
             arguments_fmt,
             arguments_dbg,
@@ -199,6 +209,10 @@ impl FunctionCall {
         })
     }
 
+    pub(crate) fn is_constant(&self) -> bool {
+        self.constant
+    }
+
     pub fn noop() -> Self {
         let expr = Box::new(Noop) as _;
 
@@ -206,6 +220,7 @@ impl FunctionCall {
             abort_on_error: false,
             expr,
             maybe_fallible_arguments: false,
+            constant: false,
             span: Span::default(),
             arguments_fmt: vec![],
             arguments_dbg: vec![],
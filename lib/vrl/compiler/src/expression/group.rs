@@ -7,7 +7,7 @@ use crate::{
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Group {
-    inner: Box<Expr>,
+    pub(crate) inner: Box<Expr>,
 }
 
 impl Group {
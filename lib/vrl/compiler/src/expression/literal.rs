@@ -5,6 +5,7 @@ use chrono::{DateTime, SecondsFormat, Utc};
 use diagnostic::{DiagnosticError, Label, Note, Urls};
 use ordered_float::NotNan;
 use parser::ast::{self, Node};
+use rust_decimal::Decimal as RustDecimal;
 
 use crate::{expression::Resolved, value::Regex, Context, Expression, Span, State, TypeDef, Value};
 
@@ -13,6 +14,7 @@ pub enum Literal {
     String(Bytes),
     Integer(i64),
     Float(NotNan<f64>),
+    Decimal(RustDecimal),
     Boolean(bool),
     Regex(Regex),
     Timestamp(DateTime<Utc>),
@@ -27,6 +29,7 @@ impl Literal {
             String(v) => Value::Bytes(v.clone()),
             Integer(v) => Value::Integer(*v),
             Float(v) => Value::Float(v.to_owned()),
+            Decimal(v) => Value::Decimal(*v),
             Boolean(v) => Value::Boolean(*v),
             Regex(v) => Value::Regex(v.clone()),
             Timestamp(v) => Value::Timestamp(v.to_owned()),
@@ -76,6 +79,7 @@ impl Expression for Literal {
             String(_) => TypeDef::new().bytes(),
             Integer(_) => TypeDef::new().integer(),
             Float(_) => TypeDef::new().float(),
+            Decimal(_) => TypeDef::new().decimal(),
             Boolean(_) => TypeDef::new().boolean(),
             Regex(_) => TypeDef::new().regex(),
             Timestamp(_) => TypeDef::new().timestamp(),
@@ -94,6 +98,7 @@ impl fmt::Display for Literal {
             String(v) => write!(f, r#""{}""#, std::string::String::from_utf8_lossy(v)),
             Integer(v) => v.fmt(f),
             Float(v) => v.fmt(f),
+            Decimal(v) => v.fmt(f),
             Boolean(v) => v.fmt(f),
             Regex(v) => v.fmt(f),
             Timestamp(v) => write!(f, "t'{}'", v.to_rfc3339_opts(SecondsFormat::AutoSi, true)),
@@ -209,6 +214,14 @@ impl TryFrom<f64> for Literal {
     }
 }
 
+// Literal::Decimal ------------------------------------------------------------
+
+impl From<RustDecimal> for Literal {
+    fn from(v: RustDecimal) -> Self {
+        Literal::Decimal(v)
+    }
+}
+
 // Literal::Boolean ------------------------------------------------------------
 
 impl From<bool> for Literal {
@@ -7,7 +7,7 @@ use crate::{
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Object {
-    inner: BTreeMap<String, Expr>,
+    pub(crate) inner: BTreeMap<String, Expr>,
 }
 
 impl Object {
@@ -5,7 +5,8 @@ use diagnostic::{DiagnosticError, Label, Note, Span, Urls};
 use crate::{
     expression::{self, Expr, Noop, Resolved},
     parser::{ast, Node},
-    value, Context, Expression, State, TypeDef, Value,
+    value::{self, IntegerOverflow},
+    Context, Expression, State, TypeDef, Value,
 };
 
 #[derive(Clone, PartialEq)]
@@ -13,6 +14,7 @@ pub struct Op {
     pub(crate) lhs: Box<Expr>,
     pub(crate) rhs: Box<Expr>,
     pub(crate) opcode: ast::Opcode,
+    pub(crate) overflow: IntegerOverflow,
 }
 
 impl Op {
@@ -70,6 +72,7 @@ impl Op {
             lhs: Box::new(lhs),
             rhs: Box::new(rhs),
             opcode,
+            overflow: state.integer_overflow(),
         })
     }
 
@@ -81,6 +84,7 @@ impl Op {
             lhs,
             rhs,
             opcode: ast::Opcode::Eq,
+            overflow: IntegerOverflow::default(),
         }
     }
 }
@@ -94,11 +98,11 @@ impl Expression for Op {
         let mut rhs = || self.rhs.resolve(ctx);
 
         match self.opcode {
-            Mul => lhs?.try_mul(rhs()?),
+            Mul => lhs?.try_mul_with_overflow(rhs()?, self.overflow),
             Div => lhs?.try_div(rhs()?),
-            Add => lhs?.try_add(rhs()?),
-            Sub => lhs?.try_sub(rhs()?),
-            Rem => lhs?.try_rem(rhs()?),
+            Add => lhs?.try_add_with_overflow(rhs()?, self.overflow),
+            Sub => lhs?.try_sub_with_overflow(rhs()?, self.overflow),
+            Rem => lhs?.try_rem_with_overflow(rhs()?, self.overflow),
             Or => lhs?.try_or(rhs),
             And => match lhs? {
                 Null | Boolean(false) => Ok(false.into()),
@@ -126,6 +130,21 @@ impl Expression for Op {
         let lhs_kind = lhs_def.kind();
         let rhs_kind = rhs_def.kind();
 
+        // Decimals only stay decimals when combined with other decimals or
+        // integers, any other operand falls back to the float or integer rules.
+        let decimal_operands = (lhs_kind.is_decimal()
+            && (rhs_kind.is_decimal() || rhs_kind.is_integer()))
+            || (lhs_kind.is_integer() && rhs_kind.is_decimal());
+        let maybe_decimal = lhs_kind.contains_decimal() || rhs_kind.contains_decimal();
+
+        let numeric = |kind: K| {
+            if maybe_decimal {
+                kind | K::Decimal
+            } else {
+                kind
+            }
+        };
+
         match self.opcode {
             // ok/err ?? ok
             Err if rhs_def.is_infallible() => lhs_def.merge(rhs_def).infallible(),
@@ -179,12 +198,15 @@ impl Expression for Op {
             // ... <  ...
             // ... <= ...
             Gt | Ge | Lt | Le => lhs_def
-                .fallible_unless(K::Integer | K::Float)
-                .merge(rhs_def.fallible_unless(K::Integer | K::Float))
+                .fallible_unless(K::Integer | K::Float | K::Decimal)
+                .merge(rhs_def.fallible_unless(K::Integer | K::Float | K::Decimal))
                 .scalar(K::Boolean),
 
+            // d'1.5' / 2
+            Div if decimal_operands => TypeDef::new().fallible().decimal(),
+
             // ... / ...
-            Div => TypeDef::new().fallible().float(),
+            Div => TypeDef::new().fallible().scalar(numeric(K::Float)),
 
             // "bar" + ...
             // ... + "bar"
@@ -202,16 +224,26 @@ impl Expression for Op {
             // 1.0 * ...
             // 1.0 % ...
             Add | Sub | Mul | Rem if lhs_kind.is_float() || rhs_kind.is_float() => lhs_def
-                .fallible_unless(K::Integer | K::Float)
-                .merge(rhs_def.fallible_unless(K::Integer | K::Float))
+                .fallible_unless(K::Integer | K::Float | K::Decimal)
+                .merge(rhs_def.fallible_unless(K::Integer | K::Float | K::Decimal))
                 .scalar(K::Float),
 
+            // d'1.5' + 1
+            // 1 - d'1.5'
+            // d'1.5' * d'1.5'
+            Add | Sub | Mul | Rem if decimal_operands => lhs_def.merge(rhs_def).scalar(K::Decimal),
+
             // 1 + 1
             // 1 - 1
             // 1 * 1
             // 1 % 1
             Add | Sub | Mul | Rem if lhs_kind.is_integer() && rhs_kind.is_integer() => {
-                lhs_def.merge(rhs_def).scalar(K::Integer)
+                let type_def = lhs_def.merge(rhs_def).scalar(K::Integer);
+
+                match self.overflow {
+                    IntegerOverflow::Wrap => type_def,
+                    IntegerOverflow::Error => type_def.fallible(),
+                }
             }
 
             // "bar" * 1
@@ -229,14 +261,14 @@ impl Expression for Op {
             Add | Mul => lhs_def
                 .merge(rhs_def)
                 .fallible()
-                .scalar(K::Bytes | K::Integer | K::Float),
+                .scalar(numeric(K::Bytes | K::Integer | K::Float)),
 
             // ... - ...
             // ... % ...
             Sub | Rem => lhs_def
                 .merge(rhs_def)
                 .fallible()
-                .scalar(K::Integer | K::Float),
+                .scalar(numeric(K::Integer | K::Float)),
         }
     }
 }
@@ -384,6 +416,18 @@ mod tests {
             lhs: Box::new(lhs.into()),
             rhs: Box::new(rhs.into()),
             opcode,
+            overflow: IntegerOverflow::Wrap,
+        }
+    }
+
+    fn checked_op(
+        opcode: ast::Opcode,
+        lhs: impl TryInto<Literal> + fmt::Debug + Clone,
+        rhs: impl TryInto<Literal> + fmt::Debug + Clone,
+    ) -> Op {
+        Op {
+            overflow: IntegerOverflow::Error,
+            ..op(opcode, lhs, rhs)
         }
     }
 
@@ -391,6 +435,10 @@ mod tests {
         NotNan::new(f).unwrap()
     }
 
+    fn d(d: &str) -> rust_decimal::Decimal {
+        d.parse().unwrap()
+    }
+
     test_type_def![
         or_exact {
             expr: |_| op(Or, "foo", true),
@@ -542,6 +590,26 @@ mod tests {
             want: TypeDef::new().fallible().float(),
         }
 
+        divide_decimal_integer {
+            expr: |_| op(Div, d("1.5"), 2),
+            want: TypeDef::new().fallible().decimal(),
+        }
+
+        add_decimal_integer {
+            expr: |_| op(Add, d("1.5"), 1),
+            want: TypeDef::new().decimal(),
+        }
+
+        add_decimal_float {
+            expr: |_| op(Add, d("1.5"), f(1.0)),
+            want: TypeDef::new().float(),
+        }
+
+        subtract_decimal_other {
+            expr: |_| op(Sub, d("1.5"), "foo"),
+            want: TypeDef::new().fallible().integer().add_float().add_decimal(),
+        }
+
         and_null {
             expr: |_| op(And, (), ()),
             want: TypeDef::new().infallible().boolean(),
@@ -672,15 +740,32 @@ mod tests {
             want: TypeDef::new().fallible().boolean(),
         }
 
+        add_integer_overflow_error {
+            expr: |_| checked_op(Add, 1, 1),
+            want: TypeDef::new().fallible().integer(),
+        }
+
+        multiply_integer_overflow_error {
+            expr: |_| checked_op(Mul, 1, 1),
+            want: TypeDef::new().fallible().integer(),
+        }
+
+        add_float_overflow_error {
+            expr: |_| checked_op(Add, 1, 1.0),
+            want: TypeDef::new().infallible().float(),
+        }
+
         error_or_rhs_infallible {
             expr: |_| Op {
                 lhs: Box::new(Op {
                     lhs: Box::new(Literal::from("foo").into()),
                     rhs: Box::new(Literal::from(1).into()),
                     opcode: Div,
+                    overflow: IntegerOverflow::Wrap,
                 }.into()),
                 rhs: Box::new(Literal::from(true).into()),
                 opcode: Err,
+                overflow: IntegerOverflow::Wrap,
             },
             want: TypeDef::new().float().add_boolean(),
         }
@@ -691,13 +776,16 @@ mod tests {
                     lhs: Box::new(Literal::from("foo").into()),
                     rhs: Box::new(Literal::from(1).into()),
                     opcode: Div,
+                    overflow: IntegerOverflow::Wrap,
                 }.into()),
                 rhs: Box::new(Op {
                     lhs: Box::new(Literal::from(true).into()),
                     rhs: Box::new(Literal::from(1).into()),
                     opcode: Div,
+                    overflow: IntegerOverflow::Wrap,
                 }.into()),
                 opcode: Err,
+                overflow: IntegerOverflow::Wrap,
             },
             want: TypeDef::new().fallible().float(),
         }
@@ -708,17 +796,21 @@ mod tests {
                     lhs: Box::new(Literal::from("foo").into()),
                     rhs: Box::new(Literal::from(1).into()),
                     opcode: Div,
+                    overflow: IntegerOverflow::Wrap,
                 }.into()),
                 rhs: Box::new(Op {
                     lhs: Box::new(Op {
                         lhs: Box::new(Literal::from(true).into()),
                         rhs: Box::new(Literal::from(1).into()),
                         opcode: Div,
+                        overflow: IntegerOverflow::Wrap,
                     }.into()),
                     rhs: Box::new(Literal::from("foo").into()),
                     opcode: Err,
+                    overflow: IntegerOverflow::Wrap,
                 }.into()),
                 opcode: Err,
+                overflow: IntegerOverflow::Wrap,
             },
             want: TypeDef::new().float().add_bytes(),
         }
@@ -733,6 +825,7 @@ mod tests {
                     }.into()),
                 rhs: Box::new(Literal::from("another string").into()),
                 opcode: Or,
+                overflow: IntegerOverflow::Wrap,
             },
             want: TypeDef::new().bytes(),
         }
@@ -747,8 +840,32 @@ mod tests {
                 }.into()),
                 rhs: Box::new(Literal::from("another string").into()),
                 opcode: Or,
+                overflow: IntegerOverflow::Wrap,
             },
             want: TypeDef::new().bytes().add_integer(),
         }
     ];
+
+    #[test]
+    fn integer_overflow() {
+        let mut target = Value::Null;
+        let mut runtime_state = crate::state::Runtime::default();
+        let tz = shared::TimeZone::default();
+        let mut ctx = Context::new(&mut target, &mut runtime_state, &tz);
+
+        let wrapped = op(Add, i64::MAX, 1).resolve(&mut ctx).unwrap();
+        assert_eq!(wrapped, Value::Integer(i64::MIN));
+
+        let wrapped = op(Mul, i64::MAX, 2).resolve(&mut ctx).unwrap();
+        assert_eq!(wrapped, Value::Integer(-2));
+
+        assert!(checked_op(Add, i64::MAX, 1).resolve(&mut ctx).is_err());
+        assert!(checked_op(Sub, i64::MIN, 1).resolve(&mut ctx).is_err());
+        assert!(checked_op(Mul, i64::MAX, 2).resolve(&mut ctx).is_err());
+        assert!(checked_op(Rem, 1, 0).resolve(&mut ctx).is_err());
+        assert_eq!(
+            checked_op(Add, 1, 2).resolve(&mut ctx).unwrap(),
+            Value::Integer(3)
+        );
+    }
 }
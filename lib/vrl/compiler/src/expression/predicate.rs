@@ -13,7 +13,7 @@ pub type Result = std::result::Result<Predicate, Error>;
 
 #[derive(Clone, PartialEq)]
 pub struct Predicate {
-    inner: Vec<Expr>,
+    pub(crate) inner: Vec<Expr>,
 }
 
 impl Predicate {
@@ -14,6 +14,18 @@ impl Unary {
     pub fn new(variant: Variant) -> Self {
         Self { variant }
     }
+
+    pub(crate) fn not(&self) -> &Not {
+        match &self.variant {
+            Variant::Not(not) => not,
+        }
+    }
+
+    pub(crate) fn not_mut(&mut self) -> &mut Not {
+        match &mut self.variant {
+            Variant::Not(not) => not,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
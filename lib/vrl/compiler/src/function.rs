@@ -30,6 +30,15 @@ pub trait Function: Sync + fmt::Debug {
         "TODO"
     }
 
+    /// Whether the function always returns the same value for the same
+    /// arguments, without reading the target or having side effects.
+    ///
+    /// Calls to pure functions with constant arguments are resolved once at
+    /// compile-time, instead of for every event.
+    fn is_pure(&self) -> bool {
+        false
+    }
+
     /// One or more examples demonstrating usage of the function in VRL source
     /// code.
     fn examples(&self) -> &'static [Example];
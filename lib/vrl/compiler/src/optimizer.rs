@@ -0,0 +1,188 @@
+//! Simplifies compiled programs, so that work that's the same for every event
+//! is done once at compile-time.
+//!
+//! Operations, negations and calls to pure functions whose operands are all
+//! constant are folded into the value they resolve to, and `if` statements
+//! with a constant predicate are replaced by the branch that's taken.
+//!
+//! Only infallible expressions are folded, so that folding never changes how
+//! a program type-checks, and expressions that fail when resolved are left
+//! for the runtime to report.
+
+use std::collections::BTreeMap;
+
+use shared::TimeZone;
+
+use crate::{
+    expression::{container::Variant, Block, Container, Expr, Literal},
+    parser::ast::Opcode,
+    state::Runtime,
+    Context, Expression, State, Value,
+};
+
+/// Folds the constant parts of `expr`, starting from its innermost expressions.
+pub(crate) fn fold(expr: &mut Expr, state: &State) {
+    fold_children(expr, state);
+
+    if let Some(folded) = folded(expr, state) {
+        *expr = folded;
+    }
+}
+
+/// Returns whether `expr` always resolves to the same value.
+///
+/// Variables aren't considered constant, as the value the compiler tracks for
+/// them might have been assigned in a branch that isn't taken.
+pub(crate) fn is_constant(expr: &Expr) -> bool {
+    match expr {
+        Expr::Literal(_) => true,
+        Expr::Container(container) => match &container.variant {
+            Variant::Group(group) => is_constant(&group.inner),
+            Variant::Array(array) => array.inner.iter().all(is_constant),
+            Variant::Object(object) => object.inner.values().all(is_constant),
+            Variant::Block(_) => false,
+        },
+        _ => false,
+    }
+}
+
+fn fold_children(expr: &mut Expr, state: &State) {
+    let fold_all = |exprs: &mut Vec<Expr>| exprs.iter_mut().for_each(|expr| fold(expr, state));
+
+    match expr {
+        Expr::Container(container) => match &mut container.variant {
+            Variant::Group(group) => fold(&mut group.inner, state),
+            Variant::Block(block) => fold_all(&mut block.inner),
+            Variant::Array(array) => fold_all(&mut array.inner),
+            Variant::Object(object) => object.inner.values_mut().for_each(|expr| fold(expr, state)),
+        },
+        Expr::IfStatement(if_statement) => {
+            fold_all(&mut if_statement.predicate.inner);
+            fold_all(&mut if_statement.consequent.inner);
+            if let Some(alternative) = &mut if_statement.alternative {
+                fold_all(&mut alternative.inner);
+            }
+        }
+        Expr::Op(op) => {
+            fold(&mut op.lhs, state);
+            fold(&mut op.rhs, state);
+        }
+        Expr::Assignment(assignment) => fold(assignment.expr_mut(), state),
+        Expr::Unary(unary) => fold(&mut unary.not_mut().inner, state),
+
+        // Function arguments are folded before the function is compiled, so
+        // that functions can make use of constant arguments at compile-time.
+        Expr::FunctionCall(_)
+        | Expr::Literal(_)
+        | Expr::Query(_)
+        | Expr::Variable(_)
+        | Expr::Noop(_)
+        | Expr::Abort(_) => {}
+    }
+}
+
+/// Returns the expression `expr` can be replaced with, if any.
+fn folded(expr: &Expr, state: &State) -> Option<Expr> {
+    if expr.type_def(state).is_fallible() {
+        return None;
+    }
+
+    match expr {
+        Expr::Container(container) => match &container.variant {
+            Variant::Group(group) if is_constant(&group.inner) => Some(*group.inner.clone()),
+            _ => None,
+        },
+        Expr::IfStatement(if_statement) => {
+            let predicate = match if_statement.predicate.inner.as_slice() {
+                [predicate] if is_constant(predicate) => resolve(predicate)?,
+                _ => return None,
+            };
+
+            match (predicate, &if_statement.alternative) {
+                (Value::Boolean(true), _) => Some(block(if_statement.consequent.clone())),
+                (Value::Boolean(false), Some(alternative)) => Some(block(alternative.clone())),
+                (Value::Boolean(false), None) => Some(Literal::Null.into()),
+                _ => None,
+            }
+        }
+        Expr::Op(op) => {
+            if !is_constant(&op.lhs) {
+                return None;
+            }
+
+            // `false && ...` and `true || ...` don't depend on their right-hand side.
+            match (op.opcode, resolve(&op.lhs)?) {
+                (Opcode::And, Value::Null | Value::Boolean(false)) => {
+                    Some(Literal::Boolean(false).into())
+                }
+                (Opcode::Or, lhs) if !matches!(lhs, Value::Null | Value::Boolean(false)) => {
+                    Some(lhs.into_expr())
+                }
+                _ if is_constant(&op.rhs) => resolve(expr).map(Value::into_expr),
+                _ => None,
+            }
+        }
+        Expr::Unary(unary) if is_constant(&unary.not().inner) => {
+            resolve(expr).map(Value::into_expr)
+        }
+        Expr::FunctionCall(call) if call.is_constant() => resolve(expr).map(Value::into_expr),
+        _ => None,
+    }
+}
+
+fn block(block: Block) -> Expr {
+    Container::new(Variant::Block(block)).into()
+}
+
+/// Resolves an expression that doesn't depend on the event, returning `None` if
+/// it fails.
+fn resolve(expr: &Expr) -> Option<Value> {
+    let mut target = Value::Object(BTreeMap::new());
+    let mut runtime = Runtime::default();
+    let timezone = TimeZone::default();
+    let mut ctx = Context::new(&mut target, &mut runtime, &timezone);
+
+    expr.resolve(&mut ctx).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compile;
+
+    fn compiled(source: &str) -> String {
+        let program = compile(::parser::parse(source).unwrap(), &[]).unwrap();
+
+        format!("{:?}", program[0])
+    }
+
+    #[test]
+    fn folds_constant_operations() {
+        assert_eq!(compiled(".a = (1 + 2) * 3 == 9"), compiled(".a = true"));
+        assert_eq!(compiled(r#".a = !("foo" == "bar")"#), compiled(".a = true"));
+        assert_eq!(compiled(".a = [1 + 1, 3]"), compiled(".a = [2, 3]"));
+    }
+
+    #[test]
+    fn short_circuits_constant_left_hand_sides() {
+        assert_eq!(compiled(".a = false && .b == 1"), compiled(".a = false"));
+        assert_eq!(compiled(r#".a = "x" || .b"#), compiled(r#".a = "x""#));
+        assert_ne!(compiled(".a = true && .b == 1"), compiled(".a = .b == 1"));
+    }
+
+    #[test]
+    fn eliminates_dead_branches() {
+        assert_eq!(
+            compiled(".a = if 1 > 2 { .b } else { .c }"),
+            compiled(".a = { .c }")
+        );
+        assert_eq!(compiled(".a = if false { .b }"), compiled(".a = null"));
+    }
+
+    #[test]
+    fn leaves_variables_alone() {
+        let source = "x = 1\n.a = x + 1";
+        let program = compile(::parser::parse(source).unwrap(), &[]).unwrap();
+
+        assert!(format!("{:?}", program[1]).contains("Op("));
+    }
+}
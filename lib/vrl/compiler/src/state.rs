@@ -1,6 +1,6 @@
 use std::{any::Any, collections::HashMap};
 
-use crate::{expression::assignment, parser::ast::Ident, TypeDef, Value};
+use crate::{expression::assignment, parser::ast::Ident, value::IntegerOverflow, TypeDef, Value};
 
 /// The state held by the compiler.
 ///
@@ -17,6 +17,9 @@ pub struct Compiler {
     /// context passed between the client program and a VRL function.
     external_context: Option<Box<dyn Any>>,
 
+    /// How integer arithmetic in the compiled program handles overflow.
+    integer_overflow: IntegerOverflow,
+
     /// On request, the compiler can store its state in this field, which can
     /// later be used to revert the compiler state to the previously stored
     /// state.
@@ -77,6 +80,7 @@ impl Compiler {
             target,
             variables,
             external_context: None,
+            integer_overflow: self.integer_overflow,
             snapshot: None,
         };
 
@@ -102,6 +106,19 @@ impl Compiler {
         self.external_context = data;
     }
 
+    /// Sets how integer arithmetic handles results that don't fit in a signed
+    /// 64-bit integer.
+    ///
+    /// With [`IntegerOverflow::Error`], integer arithmetic becomes fallible.
+    pub fn set_integer_overflow(&mut self, overflow: IntegerOverflow) {
+        self.integer_overflow = overflow;
+    }
+
+    /// Returns how integer arithmetic handles overflow.
+    pub fn integer_overflow(&self) -> IntegerOverflow {
+        self.integer_overflow
+    }
+
     /// Retrieves the first data of the required type from the external context.
     pub fn get_external_context<T: 'static>(&self) -> Option<&T> {
         self.external_context
@@ -57,7 +57,7 @@ pub trait Target: std::fmt::Debug {
         Err("metadata not available".to_string())
     }
 
-    fn set_metadata(&mut self, _key: &str, _value: String) -> Result<(), String> {
+    fn set_metadata(&mut self, _key: &str, _value: Value) -> Result<(), String> {
         Err("metadata not available".to_string())
     }
 
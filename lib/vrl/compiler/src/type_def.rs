@@ -12,8 +12,8 @@
 //! that statement is run, `.x` could contain either an string or an integer, we won't know until
 //! runtime exactly which.
 //!
-//! `TypeKind` is a concrete type for a path, `Bytes` (string), `Integer`, `Float`, `Boolean`,
-//! `Timestamp`, `Regex`, `Null` or `Array` or `Object`.
+//! `TypeKind` is a concrete type for a path, `Bytes` (string), `Integer`, `Float`, `Decimal`,
+//! `Boolean`, `Timestamp`, `Regex`, `Null` or `Array` or `Object`.
 //!
 //! `Array` is a Map of `Index` -> `KindInfo`.
 //! `Index` can be a specific index into that array, or `Any` which represents any index found within
@@ -91,6 +91,9 @@ impl From<Kind> for KindInfo {
         if kind.contains_float() {
             set.insert(TypeKind::Float);
         }
+        if kind.contains_decimal() {
+            set.insert(TypeKind::Decimal);
+        }
         if kind.contains_boolean() {
             set.insert(TypeKind::Boolean);
         }
@@ -447,6 +450,7 @@ pub enum TypeKind {
     Bytes,
     Integer,
     Float,
+    Decimal,
     Boolean,
     Timestamp,
     Regex,
@@ -464,6 +468,7 @@ impl TypeKind {
             Bytes => Kind::Bytes,
             Integer => Kind::Integer,
             Float => Kind::Float,
+            Decimal => Kind::Decimal,
             Boolean => Kind::Boolean,
             Timestamp => Kind::Timestamp,
             Regex => Kind::Regex,
@@ -648,6 +653,16 @@ impl TypeDef {
         self.add_scalar(Kind::Float)
     }
 
+    #[inline]
+    pub fn decimal(self) -> Self {
+        self.scalar(Kind::Decimal)
+    }
+
+    #[inline]
+    pub fn add_decimal(self) -> Self {
+        self.add_scalar(Kind::Decimal)
+    }
+
     #[inline]
     pub fn boolean(self) -> Self {
         self.scalar(Kind::Boolean)
@@ -884,6 +899,11 @@ impl TypeDef {
         self.is("float")
     }
 
+    #[inline]
+    pub fn is_decimal(&self) -> bool {
+        self.is("decimal")
+    }
+
     #[inline]
     pub fn is_boolean(&self) -> bool {
         self.is("boolean")
@@ -923,6 +943,7 @@ impl TypeDef {
                     "bytes" => matches!(v, TypeKind::Bytes),
                     "integer" => matches!(v, TypeKind::Integer),
                     "float" => matches!(v, TypeKind::Float),
+                    "decimal" => matches!(v, TypeKind::Decimal),
                     "boolean" => matches!(v, TypeKind::Boolean),
                     "timestamp" => matches!(v, TypeKind::Timestamp),
                     "regex" => matches!(v, TypeKind::Regex),
@@ -12,9 +12,11 @@ use std::{collections::BTreeMap, fmt};
 
 use bytes::Bytes;
 use chrono::{DateTime, SecondsFormat, Utc};
+pub use arithmetic::IntegerOverflow;
 pub use error::Error;
 pub use kind::Kind;
 use ordered_float::NotNan;
+use rust_decimal::Decimal;
 
 pub use self::regex::Regex;
 
@@ -23,6 +25,7 @@ pub enum Value {
     Bytes(Bytes),
     Integer(i64),
     Float(NotNan<f64>),
+    Decimal(Decimal),
     Boolean(bool),
     Object(BTreeMap<String, Value>),
     Array(Vec<Value>),
@@ -46,6 +49,7 @@ impl fmt::Display for Value {
             ),
             Value::Integer(val) => write!(f, "{}", val),
             Value::Float(val) => write!(f, "{}", val),
+            Value::Decimal(val) => write!(f, "{}", val),
             Value::Boolean(val) => write!(f, "{}", val),
             Value::Object(map) => {
                 let joined = map
@@ -77,6 +81,7 @@ impl From<serde_json::Value> for Value {
         match json_value {
             serde_json::Value::Bool(b) => Value::Boolean(b),
             serde_json::Value::Number(n) if n.is_i64() => n.as_i64().unwrap().into(),
+            serde_json::Value::Number(n) if n.is_u64() => n.as_u64().unwrap().into(),
             serde_json::Value::Number(n) if n.is_f64() => n.as_f64().unwrap().into(),
             serde_json::Value::Number(n) => n.to_string().into(),
             serde_json::Value::String(s) => Value::Bytes(Bytes::from(s)),
@@ -1,18 +1,94 @@
 use std::{collections::BTreeMap, convert::TryFrom};
 
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
 use super::{Error, Value};
 use crate::ExpressionError;
 
+/// How integer arithmetic handles results that don't fit in a signed 64-bit
+/// integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IntegerOverflow {
+    /// The result wraps around at the boundary of the type, e.g.
+    /// `9223372036854775807 + 1` results in `-9223372036854775808`.
+    Wrap,
+
+    /// The operation returns an error.
+    Error,
+}
+
+impl Default for IntegerOverflow {
+    fn default() -> Self {
+        IntegerOverflow::Wrap
+    }
+}
+
+impl IntegerOverflow {
+    fn apply(
+        self,
+        lhv: i64,
+        rhv: i64,
+        wrapping: fn(i64, i64) -> i64,
+        checked: fn(i64, i64) -> Option<i64>,
+    ) -> Result<i64, Error> {
+        match self {
+            IntegerOverflow::Wrap => Ok(wrapping(lhv, rhv)),
+            IntegerOverflow::Error => checked(lhv, rhv).ok_or(Error::Overflow),
+        }
+    }
+}
+
 impl Value {
+    /// Returns both sides as decimals if one of them is a decimal and the other
+    /// one is either a decimal or an integer.
+    ///
+    /// Floats are never promoted to decimals, as that would suggest a precision
+    /// the float never had. Mixing a decimal with a float results in a float.
+    fn decimal_operands(&self, rhs: &Self) -> Option<(Decimal, Decimal)> {
+        match (self, rhs) {
+            (Value::Decimal(_), Value::Decimal(_) | Value::Integer(_))
+            | (Value::Integer(_), Value::Decimal(_)) => {
+                Some((Decimal::try_from(self).ok()?, Decimal::try_from(rhs).ok()?))
+            }
+            _ => None,
+        }
+    }
+
     /// Similar to [`std::ops::Mul`], but fallible (e.g. `TryMul`).
+    ///
+    /// Integer overflow wraps around, see [`Value::try_mul_with_overflow`].
     pub fn try_mul(self, rhs: Self) -> Result<Self, Error> {
+        self.try_mul_with_overflow(rhs, IntegerOverflow::Wrap)
+    }
+
+    /// Similar to [`Value::try_mul`], with integer overflow handled according
+    /// to `overflow`.
+    pub fn try_mul_with_overflow(
+        self,
+        rhs: Self,
+        overflow: IntegerOverflow,
+    ) -> Result<Self, Error> {
         let err = || Error::Mul(self.kind(), rhs.kind());
 
+        if let Some((lhv, rhv)) = self.decimal_operands(&rhs) {
+            return lhv.checked_mul(rhv).map(Into::into).ok_or_else(err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_bytes() => rhs.try_bytes()?.repeat(lhv as usize).into(),
             Value::Integer(lhv) if rhs.is_float() => (lhv as f64 * rhs.try_float()?).into(),
-            Value::Integer(lhv) => (lhv * i64::try_from(&rhs).map_err(|_| err())?).into(),
+            Value::Integer(lhv) => {
+                let rhv = i64::try_from(&rhs).map_err(|_| err())?;
+                overflow
+                    .apply(lhv, rhv, i64::wrapping_mul, i64::checked_mul)?
+                    .into()
+            }
             Value::Float(lhv) => (lhv * f64::try_from(&rhs).map_err(|_| err())?).into(),
+            Value::Decimal(_) if rhs.is_float() => {
+                (f64::try_from(&self).map_err(|_| err())? * rhs.try_float()?).into()
+            }
             Value::Bytes(lhv) if rhs.is_integer() => lhv.repeat(rhs.try_integer()? as usize).into(),
             _ => return Err(err()),
         };
@@ -24,6 +100,14 @@ impl Value {
     pub fn try_div(self, rhs: Self) -> Result<Self, Error> {
         let err = || Error::Div(self.kind(), rhs.kind());
 
+        if let Some((lhv, rhv)) = self.decimal_operands(&rhs) {
+            if rhv.is_zero() {
+                return Err(Error::DivideByZero);
+            }
+
+            return lhv.checked_div(rhv).map(Into::into).ok_or_else(err);
+        }
+
         let rhv = f64::try_from(&rhs).map_err(|_| err())?;
 
         if rhv == 0.0 {
@@ -33,6 +117,7 @@ impl Value {
         let value = match self {
             Value::Integer(lhv) => (lhv as f64 / rhv).into(),
             Value::Float(lhv) => (lhv.into_inner() / rhv).into(),
+            Value::Decimal(_) => (f64::try_from(&self).map_err(|_| err())? / rhv).into(),
             _ => return Err(err()),
         };
 
@@ -40,13 +125,37 @@ impl Value {
     }
 
     /// Similar to [`std::ops::Add`], but fallible (e.g. `TryAdd`).
+    ///
+    /// Integer overflow wraps around, see [`Value::try_add_with_overflow`].
     pub fn try_add(self, rhs: Self) -> Result<Self, Error> {
+        self.try_add_with_overflow(rhs, IntegerOverflow::Wrap)
+    }
+
+    /// Similar to [`Value::try_add`], with integer overflow handled according
+    /// to `overflow`.
+    pub fn try_add_with_overflow(
+        self,
+        rhs: Self,
+        overflow: IntegerOverflow,
+    ) -> Result<Self, Error> {
         let err = || Error::Add(self.kind(), rhs.kind());
 
+        if let Some((lhv, rhv)) = self.decimal_operands(&rhs) {
+            return lhv.checked_add(rhv).map(Into::into).ok_or_else(err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => (lhv as f64 + rhs.try_float()?).into(),
-            Value::Integer(lhv) => (lhv + i64::try_from(&rhs).map_err(|_| err())?).into(),
+            Value::Integer(lhv) => {
+                let rhv = i64::try_from(&rhs).map_err(|_| err())?;
+                overflow
+                    .apply(lhv, rhv, i64::wrapping_add, i64::checked_add)?
+                    .into()
+            }
             Value::Float(lhv) => (lhv + f64::try_from(&rhs).map_err(|_| err())?).into(),
+            Value::Decimal(_) if rhs.is_float() => {
+                (f64::try_from(&self).map_err(|_| err())? + rhs.try_float()?).into()
+            }
             Value::Bytes(_) if rhs.is_null() => self,
             Value::Bytes(_) if rhs.is_bytes() => format!(
                 "{}{}",
@@ -62,13 +171,37 @@ impl Value {
     }
 
     /// Similar to [`std::ops::Sub`], but fallible (e.g. `TrySub`).
+    ///
+    /// Integer overflow wraps around, see [`Value::try_sub_with_overflow`].
     pub fn try_sub(self, rhs: Self) -> Result<Self, Error> {
+        self.try_sub_with_overflow(rhs, IntegerOverflow::Wrap)
+    }
+
+    /// Similar to [`Value::try_sub`], with integer overflow handled according
+    /// to `overflow`.
+    pub fn try_sub_with_overflow(
+        self,
+        rhs: Self,
+        overflow: IntegerOverflow,
+    ) -> Result<Self, Error> {
         let err = || Error::Sub(self.kind(), rhs.kind());
 
+        if let Some((lhv, rhv)) = self.decimal_operands(&rhs) {
+            return lhv.checked_sub(rhv).map(Into::into).ok_or_else(err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => (lhv as f64 - rhs.try_float()?).into(),
-            Value::Integer(lhv) => (lhv - i64::try_from(&rhs).map_err(|_| err())?).into(),
+            Value::Integer(lhv) => {
+                let rhv = i64::try_from(&rhs).map_err(|_| err())?;
+                overflow
+                    .apply(lhv, rhv, i64::wrapping_sub, i64::checked_sub)?
+                    .into()
+            }
             Value::Float(lhv) => (lhv - f64::try_from(&rhs).map_err(|_| err())?).into(),
+            Value::Decimal(_) if rhs.is_float() => {
+                (f64::try_from(&self).map_err(|_| err())? - rhs.try_float()?).into()
+            }
             _ => return Err(err()),
         };
 
@@ -113,13 +246,41 @@ impl Value {
     }
 
     /// Similar to [`std::ops::Rem`], but fallible (e.g. `TryRem`).
+    ///
+    /// Integer overflow wraps around, see [`Value::try_rem_with_overflow`].
     pub fn try_rem(self, rhs: Self) -> Result<Self, Error> {
+        self.try_rem_with_overflow(rhs, IntegerOverflow::Wrap)
+    }
+
+    /// Similar to [`Value::try_rem`], with integer overflow handled according
+    /// to `overflow`.
+    pub fn try_rem_with_overflow(
+        self,
+        rhs: Self,
+        overflow: IntegerOverflow,
+    ) -> Result<Self, Error> {
         let err = || Error::Rem(self.kind(), rhs.kind());
 
+        if let Some((lhv, rhv)) = self.decimal_operands(&rhs) {
+            return lhv.checked_rem(rhv).map(Into::into).ok_or_else(err);
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => (lhv as f64 % rhs.try_float()?).into(),
-            Value::Integer(lhv) => (lhv % i64::try_from(&rhs).map_err(|_| err())?).into(),
+            Value::Integer(lhv) => {
+                let rhv = i64::try_from(&rhs).map_err(|_| err())?;
+                if rhv == 0 && overflow == IntegerOverflow::Error {
+                    return Err(Error::DivideByZero);
+                }
+
+                overflow
+                    .apply(lhv, rhv, i64::wrapping_rem, i64::checked_rem)?
+                    .into()
+            }
             Value::Float(lhv) => (lhv % f64::try_from(&rhs).map_err(|_| err())?).into(),
+            Value::Decimal(_) if rhs.is_float() => {
+                (f64::try_from(&self).map_err(|_| err())? % rhs.try_float()?).into()
+            }
             _ => return Err(err()),
         };
 
@@ -130,12 +291,19 @@ impl Value {
     pub fn try_gt(self, rhs: Self) -> Result<Self, Error> {
         let err = || Error::Rem(self.kind(), rhs.kind());
 
+        if let Some((lhv, rhv)) = self.decimal_operands(&rhs) {
+            return Ok((lhv > rhv).into());
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => (lhv as f64 > rhs.try_float()?).into(),
             Value::Integer(lhv) => (lhv > i64::try_from(&rhs).map_err(|_| err())?).into(),
             Value::Float(lhv) => {
                 (lhv.into_inner() > f64::try_from(&rhs).map_err(|_| err())?).into()
             }
+            Value::Decimal(_) => {
+                (f64::try_from(&self).map_err(|_| err())? > rhs.try_float()?).into()
+            }
             Value::Bytes(lhv) => (lhv > rhs.try_bytes()?).into(),
             _ => return Err(err()),
         };
@@ -147,12 +315,19 @@ impl Value {
     pub fn try_ge(self, rhs: Self) -> Result<Self, Error> {
         let err = || Error::Ge(self.kind(), rhs.kind());
 
+        if let Some((lhv, rhv)) = self.decimal_operands(&rhs) {
+            return Ok((lhv >= rhv).into());
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => (lhv as f64 >= rhs.try_float()?).into(),
             Value::Integer(lhv) => (lhv >= i64::try_from(&rhs).map_err(|_| err())?).into(),
             Value::Float(lhv) => {
                 (lhv.into_inner() >= f64::try_from(&rhs).map_err(|_| err())?).into()
             }
+            Value::Decimal(_) => {
+                (f64::try_from(&self).map_err(|_| err())? >= rhs.try_float()?).into()
+            }
             Value::Bytes(lhv) => (lhv >= rhs.try_bytes()?).into(),
             _ => return Err(err()),
         };
@@ -164,12 +339,19 @@ impl Value {
     pub fn try_lt(self, rhs: Self) -> Result<Self, Error> {
         let err = || Error::Ge(self.kind(), rhs.kind());
 
+        if let Some((lhv, rhv)) = self.decimal_operands(&rhs) {
+            return Ok((lhv < rhv).into());
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => ((lhv as f64) < rhs.try_float()?).into(),
             Value::Integer(lhv) => (lhv < i64::try_from(&rhs).map_err(|_| err())?).into(),
             Value::Float(lhv) => {
                 (lhv.into_inner() < f64::try_from(&rhs).map_err(|_| err())?).into()
             }
+            Value::Decimal(_) => {
+                (f64::try_from(&self).map_err(|_| err())? < rhs.try_float()?).into()
+            }
             Value::Bytes(lhv) => (lhv < rhs.try_bytes()?).into(),
             _ => return Err(err()),
         };
@@ -181,12 +363,19 @@ impl Value {
     pub fn try_le(self, rhs: Self) -> Result<Self, Error> {
         let err = || Error::Ge(self.kind(), rhs.kind());
 
+        if let Some((lhv, rhv)) = self.decimal_operands(&rhs) {
+            return Ok((lhv <= rhv).into());
+        }
+
         let value = match self {
             Value::Integer(lhv) if rhs.is_float() => (lhv as f64 <= rhs.try_float()?).into(),
             Value::Integer(lhv) => (lhv <= i64::try_from(&rhs).map_err(|_| err())?).into(),
             Value::Float(lhv) => {
                 (lhv.into_inner() <= f64::try_from(&rhs).map_err(|_| err())?).into()
             }
+            Value::Decimal(_) => {
+                (f64::try_from(&self).map_err(|_| err())? <= rhs.try_float()?).into()
+            }
             Value::Bytes(lhv) => (lhv <= rhs.try_bytes()?).into(),
             _ => return Err(err()),
         };
@@ -215,6 +404,10 @@ impl Value {
     pub fn eq_lossy(&self, rhs: &Self) -> bool {
         use Value::*;
 
+        if let Some((lhv, rhv)) = self.decimal_operands(rhs) {
+            return lhv == rhv;
+        }
+
         match self {
             Integer(lhv) => f64::try_from(rhs)
                 .map(|rhv| *lhv as f64 == rhv)
@@ -224,6 +417,11 @@ impl Value {
                 .map(|rhv| lhv.into_inner() == rhv)
                 .unwrap_or(false),
 
+            Decimal(_) => match (f64::try_from(self), rhs) {
+                (Ok(lhv), Float(rhv)) => lhv == rhv.into_inner(),
+                _ => false,
+            },
+
             _ => self == rhs,
         }
     }
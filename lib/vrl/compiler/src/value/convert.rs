@@ -3,6 +3,7 @@ use std::{borrow::Cow, collections::BTreeMap, convert::TryFrom, iter::FromIterat
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use ordered_float::NotNan;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 
 use super::{Error, Kind, Regex, Value};
 use crate::{
@@ -27,6 +28,7 @@ impl Value {
             Bytes(v) => Literal::from(v).into(),
             Integer(v) => Literal::from(v).into(),
             Float(v) => Literal::from(v).into(),
+            Decimal(v) => Literal::from(v).into(),
             Boolean(v) => Literal::from(v).into(),
             Object(v) => {
                 let object = crate::expression::Object::from(
@@ -114,7 +116,7 @@ impl From<u32> for Value {
 
 impl From<u64> for Value {
     fn from(v: u64) -> Self {
-        Value::Integer(v as i64)
+        i64::try_from(v).map_or_else(|_| Value::Decimal(v.into()), Value::Integer)
     }
 }
 
@@ -131,6 +133,7 @@ impl TryFrom<&Value> for i64 {
         match v {
             Value::Integer(v) => Ok(*v),
             Value::Float(v) => Ok(v.into_inner() as i64),
+            Value::Decimal(d) => d.to_i64().ok_or_else(|| Error::Coerce(v.kind(), Kind::Integer)),
             _ => Err(Error::Coerce(v.kind(), Kind::Integer)),
         }
     }
@@ -174,6 +177,7 @@ impl TryFrom<&Value> for f64 {
         match v {
             Value::Integer(v) => Ok(*v as f64),
             Value::Float(v) => Ok(v.into_inner()),
+            Value::Decimal(d) => d.to_f64().ok_or_else(|| Error::Coerce(v.kind(), Kind::Float)),
             _ => Err(Error::Coerce(v.kind(), Kind::Float)),
         }
     }
@@ -198,6 +202,49 @@ impl From<f64> for Value {
 //     }
 // }
 
+// Value::Decimal --------------------------------------------------------------
+
+impl Value {
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, Value::Decimal(_))
+    }
+
+    pub fn as_decimal(&self) -> Option<&Decimal> {
+        match self {
+            Value::Decimal(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn try_decimal(self) -> Result<Decimal, Error> {
+        match self {
+            Value::Decimal(v) => Ok(v),
+            _ => Err(Error::Expected {
+                got: self.kind(),
+                expected: Kind::Decimal,
+            }),
+        }
+    }
+}
+
+impl From<Decimal> for Value {
+    fn from(v: Decimal) -> Self {
+        Value::Decimal(v)
+    }
+}
+
+impl TryFrom<&Value> for Decimal {
+    type Error = Error;
+
+    fn try_from(v: &Value) -> Result<Self, Self::Error> {
+        match v {
+            Value::Integer(i) => Ok(Decimal::from(*i)),
+            Value::Decimal(d) => Ok(*d),
+            _ => Err(Error::Coerce(v.kind(), Kind::Decimal)),
+        }
+    }
+}
+
 // Value::Bytes ----------------------------------------------------------------
 
 impl Value {
@@ -240,6 +287,7 @@ impl Value {
             Value::Bytes(bytes) => Ok(bytes.clone()),
             Value::Integer(i) => Ok(Bytes::copy_from_slice(&i.to_le_bytes())),
             Value::Float(f) => Ok(Bytes::copy_from_slice(&f.into_inner().to_le_bytes())),
+            Value::Decimal(d) => Ok(Bytes::copy_from_slice(&d.serialize())),
             Value::Boolean(b) => Ok(if *b {
                 Bytes::copy_from_slice(&[1_u8])
             } else {
@@ -55,6 +55,9 @@ pub enum Error {
 
     #[error("can't merge type {1} into {0}")]
     Merge(Kind, Kind),
+
+    #[error("integer overflow")]
+    Overflow,
 }
 
 impl DiagnosticError for Error {
@@ -78,6 +81,7 @@ impl DiagnosticError for Error {
             Lt(..) => 313,
             Le(..) => 314,
             Merge(..) => 315,
+            Overflow => 316,
         }
     }
 }
@@ -4,6 +4,7 @@ use std::{fmt, ops::Deref};
 
 use chrono::{TimeZone, Utc};
 use regex::Regex;
+use rust_decimal::Decimal;
 
 use super::Value;
 use crate::value;
@@ -17,9 +18,11 @@ pub const ARRAY: u16 = 1 << 6;
 pub const TIMESTAMP: u16 = 1 << 7;
 pub const REGEX: u16 = 1 << 8;
 pub const NULL: u16 = 1 << 9;
+pub const DECIMAL: u16 = 1 << 10;
 
-pub const ANY: u16 = BYTES | INTEGER | FLOAT | BOOLEAN | OBJECT | ARRAY | TIMESTAMP | REGEX | NULL;
-pub const SCALAR: u16 = BYTES | INTEGER | FLOAT | BOOLEAN | TIMESTAMP | REGEX | NULL;
+pub const ANY: u16 =
+    BYTES | INTEGER | FLOAT | DECIMAL | BOOLEAN | OBJECT | ARRAY | TIMESTAMP | REGEX | NULL;
+pub const SCALAR: u16 = BYTES | INTEGER | FLOAT | DECIMAL | BOOLEAN | TIMESTAMP | REGEX | NULL;
 pub const CONTAINER: u16 = OBJECT | ARRAY;
 
 bitflags::bitflags! {
@@ -33,6 +36,7 @@ bitflags::bitflags! {
         const Timestamp = TIMESTAMP;
         const Regex = REGEX;
         const Null = NULL;
+        const Decimal = DECIMAL;
     }
 }
 
@@ -82,6 +86,7 @@ impl Kind {
             Kind::Bytes => "\"string\"",
             Kind::Integer => "\"integer\"",
             Kind::Float => "\"float\"",
+            Kind::Decimal => "\"decimal\"",
             Kind::Boolean => "\"boolean\"",
             Kind::Object => "\"object\"",
             Kind::Array => "\"array\"",
@@ -99,6 +104,7 @@ impl Kind {
             Kind::Bytes => "string",
             Kind::Integer => "integer",
             Kind::Float => "float",
+            Kind::Decimal => "decimal",
             Kind::Boolean => "boolean",
             Kind::Object => "object",
             Kind::Array => "array",
@@ -117,6 +123,7 @@ impl Kind {
             Kind::Bytes
                 | Kind::Integer
                 | Kind::Float
+                | Kind::Decimal
                 | Kind::Boolean
                 | Kind::Object
                 | Kind::Array
@@ -140,6 +147,7 @@ impl Kind {
             Kind::Bytes => value!(""),
             Kind::Integer => value!(0),
             Kind::Float => value!(0.0),
+            Kind::Decimal => Value::Decimal(Decimal::ZERO),
             Kind::Boolean => value!(false),
             Kind::Object => value!({}),
             Kind::Array => value!([]),
@@ -215,6 +223,7 @@ impl_kind![
     (Bytes, bytes),
     (Integer, integer),
     (Float, float),
+    (Decimal, decimal),
     (Boolean, boolean),
     (Object, object),
     (Array, array),
@@ -237,6 +246,7 @@ impl From<&Value> for Kind {
             Value::Bytes(_) => Kind::Bytes,
             Value::Integer(_) => Kind::Integer,
             Value::Float(_) => Kind::Float,
+            Value::Decimal(_) => Kind::Decimal,
             Value::Boolean(_) => Kind::Boolean,
             Value::Object(_) => Kind::Object,
             Value::Array(_) => Kind::Array,
@@ -1,10 +1,15 @@
 use std::{
     hash::{Hash, Hasher},
     ops::Deref,
+    sync::Arc,
 };
 
+/// A compiled regex, shared between its clones.
+///
+/// Cloning a [`regex::Regex`] gives the clone an empty match cache, so regex
+/// literals would otherwise lose theirs every time they're resolved.
 #[derive(Debug, Clone)]
-pub struct Regex(regex::Regex);
+pub struct Regex(Arc<regex::Regex>);
 
 impl PartialEq for Regex {
     fn eq(&self, other: &Self) -> bool {
@@ -28,6 +33,6 @@ impl Deref for Regex {
 
 impl From<regex::Regex> for Regex {
     fn from(regex: regex::Regex) -> Self {
-        Self(regex)
+        Self(Arc::new(regex))
     }
 }
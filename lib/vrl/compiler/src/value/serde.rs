@@ -2,6 +2,7 @@ use std::{collections::BTreeMap, convert::TryFrom, fmt};
 
 use bytes::Bytes;
 use chrono::SecondsFormat;
+use rust_decimal::prelude::ToPrimitive;
 use serde::{
     de::{Error, MapAccess, SeqAccess, Visitor},
     Deserialize, Serialize, Serializer,
@@ -20,6 +21,18 @@ impl Serialize for Value {
             Bytes(v) => serializer.serialize_str(&String::from_utf8_lossy(v)),
             Integer(v) => serializer.serialize_i64(*v),
             Float(v) => serializer.serialize_f64(v.into_inner()),
+            Decimal(v) if v.fract().is_zero() && v.to_i64().is_some() => {
+                serializer.serialize_i64(v.to_i64().unwrap())
+            }
+            Decimal(v) if v.fract().is_zero() && v.to_u64().is_some() => {
+                serializer.serialize_u64(v.to_u64().unwrap())
+            }
+            // Floats are printed using their shortest round-trip representation,
+            // so decimals with up to 15 significant digits keep their exact text.
+            Decimal(v) => match v.to_f64() {
+                Some(f) => serializer.serialize_f64(f),
+                None => serializer.serialize_str(&v.to_string()),
+            },
             Boolean(v) => serializer.serialize_bool(*v),
             Object(v) => serializer.collect_map(v),
             Array(v) => serializer.collect_seq(v),
@@ -59,7 +72,7 @@ impl<'de> Deserialize<'de> for Value {
 
             #[inline]
             fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
-                Ok((value as i64).into())
+                Ok(value.into())
             }
 
             #[inline]
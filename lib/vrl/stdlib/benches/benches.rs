@@ -14,10 +14,16 @@ criterion_group!(
               assert_eq,
               r#bool,
               ceil,
+              checked_add,
+              checked_mul,
+              checked_sub,
+              clamp,
               compact,
+              compress,
               contains,
               decode_base64,
               decode_percent,
+              decompress,
               // TODO: Cannot pass a Path to bench_function
               //del,
               downcase,
@@ -26,9 +32,13 @@ criterion_group!(
               encode_json,
               encode_logfmt,
               encode_percent,
+              encode_prometheus_text,
+              encode_url,
+              encode_xml,
               ends_with,
               // TODO: Cannot pass a Path to bench_function
               //exists
+              exp,
               find,
               flatten,
               floor,
@@ -36,11 +46,13 @@ criterion_group!(
               format_int,
               format_number,
               format_timestamp,
+              from_syslog_facility,
               get,
               get_env_var,
               get_hostname,
               includes,
               int,
+              ip_anonymize,
               ip_aton,
               ip_cidr_contains,
               ip_ntoa,
@@ -49,29 +61,44 @@ criterion_group!(
               ipv6_to_ipv4,
               is_array,
               is_boolean,
+              is_decimal,
               is_float,
               is_integer,
               is_null,
               is_nullish,
               is_object,
+              is_private_ip,
               is_regex,
               is_string,
               is_timestamp,
+              is_valid_utf8,
+              jaro_winkler,
               join,
+              jq_subset,
               length,
+              levenshtein,
+              ln,
               log,
+              log10,
+              log2,
               r#match,
               match_any,
               match_array,
               match_datadog_query,
               md5,
+              mean,
+              median,
               merge,
+              metaphone,
+              normalize_url,
               // TODO: value is dynamic so we cannot assert equality
               //now,
               object,
               parse_apache_log,
+              parse_auditd,
               parse_aws_alb_log,
               parse_aws_cloudwatch_log_subscription_message,
+              parse_aws_firehose_cloudwatch_logs,
               parse_aws_vpc_flow_log,
               parse_common_log,
               parse_csv,
@@ -84,42 +111,58 @@ criterion_group!(
               parse_int,
               parse_json,
               parse_nginx_log,
+              parse_prometheus_remote_write,
               parse_query_string,
               parse_regex,
               parse_regex_all,
               parse_ruby_hash,
+              parse_semver,
               parse_syslog,
+              parse_syslog_priority,
               parse_timestamp,
               parse_tokens,
               parse_url,
               parse_user_agent,
               parse_xml,
+              percentile,
+              pow,
               push,
               redact,
               remove,
               replace,
               reverse_dns,
               round,
+              saturating_add,
+              saturating_mul,
+              saturating_sub,
+              semver_compare,
+              semver_matches,
               set,
               sha1,
               sha2,
               sha3,
               slice,
+              soundex,
               split,
               starts_with,
+              stddev,
               string,
               strip_ansi_escape_codes,
               strip_whitespace,
+              sum,
               tally,
               tally_value,
               timestamp,
               to_bool,
+              to_decimal,
               to_float,
               to_int,
+              to_ocsf,
               to_regex,
               to_string,
               to_syslog_facility,
               to_syslog_level,
+              to_syslog_priority,
               to_syslog_severity,
               to_timestamp,
               to_unix_timestamp,
@@ -129,7 +172,8 @@ criterion_group!(
               //unnest
               // TODO: value is dynamic so we cannot assert equality
               //uuidv4,
-              upcase
+              upcase,
+              xpath
 );
 criterion_main!(benches);
 
@@ -187,6 +231,42 @@ bench_function! {
     }
 }
 
+bench_function! {
+    checked_add => vrl_stdlib::CheckedAdd;
+
+    literal {
+        args: func_args![value: 1, other: 2],
+        want: Ok(3),
+    }
+}
+
+bench_function! {
+    checked_mul => vrl_stdlib::CheckedMul;
+
+    literal {
+        args: func_args![value: 3, other: 4],
+        want: Ok(12),
+    }
+}
+
+bench_function! {
+    checked_sub => vrl_stdlib::CheckedSub;
+
+    literal {
+        args: func_args![value: 5, other: 2],
+        want: Ok(3),
+    }
+}
+
+bench_function! {
+    clamp => vrl_stdlib::Clamp;
+
+    literal {
+        args: func_args![value: 120, min: 0, max: 100],
+        want: Ok(100),
+    }
+}
+
 bench_function! {
     compact => vrl_stdlib::Compact;
 
@@ -205,6 +285,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    compress => vrl_stdlib::Compress;
+
+    snappy {
+        args: func_args![value: "hello world", algorithm: "snappy"],
+        want: Ok(Bytes::from_static(b"\x0b\x28hello world")),
+    }
+}
+
 bench_function! {
     contains => vrl_stdlib::Contains;
 
@@ -237,6 +326,23 @@ bench_function! {
     }
 }
 
+bench_function! {
+    decompress => vrl_stdlib::Decompress;
+
+    gzip {
+        args: func_args![
+            value: Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xcb\x48\xcd\xc9\xc9\x57\x28\xcf\x2f\xca\x49\x01\x00\x85\x11\x4a\x0d\x0b\x00\x00\x00"),
+            algorithm: "gzip"
+        ],
+        want: Ok("hello world"),
+    }
+
+    snappy {
+        args: func_args![value: Bytes::from_static(b"\x0b\x28hello world"), algorithm: "snappy"],
+        want: Ok("hello world"),
+    }
+}
+
 bench_function! {
     downcase => vrl_stdlib::Downcase;
 
@@ -356,6 +462,36 @@ bench_function! {
     }
 }
 
+bench_function! {
+    encode_prometheus_text => vrl_stdlib::EncodePrometheusText;
+
+    literal {
+        args: func_args![value: value!([{"name": "http_requests_total", "type": "counter", "labels": {"code": "200"}, "value": 1027}])],
+        want: Ok("# TYPE http_requests_total counter\nhttp_requests_total{code=\"200\"} 1027\n"),
+    }
+}
+
+bench_function! {
+    encode_url => vrl_stdlib::EncodeUrl;
+
+    literal {
+        args: func_args![value: value!({"scheme": "https", "host": "vector.dev", "path": "/docs/", "query": {"q": "remap"}})],
+        want: Ok("https://vector.dev/docs/?q=remap"),
+    }
+}
+
+bench_function! {
+    encode_xml => vrl_stdlib::EncodeXml;
+
+    literal {
+        args: func_args![
+            value: value!({"book": {"@category": "CHILDREN", "title": {"@lang": "en", "text": "Harry Potter"}, "year": 2005}}),
+            include_declaration: false,
+        ],
+        want: Ok(r#"<book category="CHILDREN"><title lang="en">Harry Potter</title><year>2005</year></book>"#),
+    }
+}
+
 bench_function! {
     ends_with => vrl_stdlib::EndsWith;
 
@@ -370,6 +506,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    exp => vrl_stdlib::Exp;
+
+    literal {
+        args: func_args![value: 0],
+        want: Ok(1.0),
+    }
+}
+
 bench_function! {
     find => vrl_stdlib::Find;
 
@@ -474,6 +619,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    from_syslog_facility => vrl_stdlib::FromSyslogFacility;
+
+    literal {
+        args: func_args![value: value!("local7")],
+        want: Ok(value!(23)),
+    }
+}
+
 bench_function! {
     get_env_var => vrl_stdlib::GetEnvVar;
 
@@ -502,6 +656,170 @@ bench_function! {
     }
 }
 
+bench_function! {
+    jaro_winkler => vrl_stdlib::JaroWinkler;
+
+    literal {
+        args: func_args![value: "martha", other: "marhta"],
+        want: Ok(0.9611111111111111),
+    }
+}
+
+bench_function! {
+    levenshtein => vrl_stdlib::Levenshtein;
+
+    literal {
+        args: func_args![value: "kitten", other: "sitting"],
+        want: Ok(3),
+    }
+}
+
+bench_function! {
+    ln => vrl_stdlib::Ln;
+
+    literal {
+        args: func_args![value: 1],
+        want: Ok(0.0),
+    }
+}
+
+bench_function! {
+    log10 => vrl_stdlib::Log10;
+
+    literal {
+        args: func_args![value: 1000],
+        want: Ok(3.0),
+    }
+}
+
+bench_function! {
+    log2 => vrl_stdlib::Log2;
+
+    literal {
+        args: func_args![value: 1024],
+        want: Ok(10.0),
+    }
+}
+
+bench_function! {
+    mean => vrl_stdlib::Mean;
+
+    literal {
+        args: func_args![value: value!([2, 4, 9])],
+        want: Ok(5.0),
+    }
+}
+
+bench_function! {
+    median => vrl_stdlib::Median;
+
+    literal {
+        args: func_args![value: value!([7, 1, 3, 10])],
+        want: Ok(5.0),
+    }
+}
+
+bench_function! {
+    metaphone => vrl_stdlib::Metaphone;
+
+    literal {
+        args: func_args![value: "Christine"],
+        want: Ok("KRSTN"),
+    }
+}
+
+bench_function! {
+    parse_prometheus_remote_write => vrl_stdlib::ParsePrometheusRemoteWrite;
+
+    literal {
+        args: func_args![value: Bytes::from_static(b"\x5b\xf0\x5a\x0a\x40\x0a\x1f\x0a\x08__name__\x12\x13http_requests_total\x0a\x0b\x0a\x04code\x12\x03200\x12\x10\x09\x00\x00\x00\x00\x00\x0c\x90\x40\x10\x80\xa8\xc8\x8e\xf9\x2e\x1a\x17\x08\x01\x12\x13http_requests_total")],
+        want: Ok(value!([{
+            "name": "http_requests_total",
+            "type": "counter",
+            "labels": {"code": "200"},
+            "timestamp": (Utc.ymd(2021, 2, 11).and_hms(16, 0, 0)),
+            "value": 1027.0,
+        }])),
+    }
+}
+
+bench_function! {
+    parse_syslog_priority => vrl_stdlib::ParseSyslogPriority;
+
+    integer {
+        args: func_args![value: value!(165)],
+        want: Ok(value!({"facility": "local4", "severity": "notice"})),
+    }
+
+    pri_header {
+        args: func_args![value: value!("<165>")],
+        want: Ok(value!({"facility": "local4", "severity": "notice"})),
+    }
+}
+
+bench_function! {
+    percentile => vrl_stdlib::Percentile;
+
+    literal {
+        args: func_args![value: value!([1, 2, 3, 4]), percentile: 75],
+        want: Ok(3.25),
+    }
+}
+
+bench_function! {
+    pow => vrl_stdlib::Pow;
+
+    literal {
+        args: func_args![value: 2, exponent: 10],
+        want: Ok(1024.0),
+    }
+}
+
+bench_function! {
+    saturating_add => vrl_stdlib::SaturatingAdd;
+
+    literal {
+        args: func_args![value: i64::MAX, other: 1],
+        want: Ok(i64::MAX),
+    }
+}
+
+bench_function! {
+    saturating_mul => vrl_stdlib::SaturatingMul;
+
+    literal {
+        args: func_args![value: i64::MAX, other: 2],
+        want: Ok(i64::MAX),
+    }
+}
+
+bench_function! {
+    saturating_sub => vrl_stdlib::SaturatingSub;
+
+    literal {
+        args: func_args![value: 5, other: 2],
+        want: Ok(3),
+    }
+}
+
+bench_function! {
+    semver_compare => vrl_stdlib::SemverCompare;
+
+    literal {
+        args: func_args![value: "1.10.0", other: "1.9.3"],
+        want: Ok(1),
+    }
+}
+
+bench_function! {
+    semver_matches => vrl_stdlib::SemverMatches;
+
+    literal {
+        args: func_args![version: "1.4.2", requirement: ">=1.2, <2"],
+        want: Ok(true),
+    }
+}
+
 bench_function! {
     set => vrl_stdlib::Set;
 
@@ -530,6 +848,20 @@ bench_function! {
     }
 }
 
+bench_function! {
+    ip_anonymize => vrl_stdlib::IpAnonymize;
+
+    zero {
+        args: func_args![value: "192.168.10.32"],
+        want: Ok("192.168.10.0"),
+    }
+
+    hash {
+        args: func_args![value: "192.168.10.32", mode: "hash"],
+        want: Ok("192.168.10.174"),
+    }
+}
+
 bench_function! {
     ip_aton => vrl_stdlib::IpAton;
 
@@ -642,6 +974,20 @@ bench_function! {
     }
 }
 
+bench_function! {
+    is_decimal => vrl_stdlib::IsDecimal;
+
+    decimal {
+        args: func_args![value: Value::Decimal(12.into())],
+        want: Ok(true),
+    }
+
+    float {
+        args: func_args![value: 0.577],
+        want: Ok(false),
+    }
+}
+
 bench_function! {
     is_float => vrl_stdlib::IsFloat;
 
@@ -722,6 +1068,20 @@ bench_function! {
     }
 }
 
+bench_function! {
+    is_private_ip => vrl_stdlib::IsPrivateIp;
+
+    private {
+        args: func_args![value: "10.0.0.1"],
+        want: Ok(true),
+    }
+
+    public {
+        args: func_args![value: "8.8.8.8"],
+        want: Ok(false),
+    }
+}
+
 bench_function! {
     is_regex => vrl_stdlib::IsRegex;
 
@@ -764,6 +1124,32 @@ bench_function! {
     }
 }
 
+bench_function! {
+    is_valid_utf8 => vrl_stdlib::IsValidUtf8;
+
+    valid {
+        args: func_args![value: "foobar"],
+        want: Ok(true),
+    }
+
+    invalid {
+        args: func_args![value: Bytes::from_static(b"\xde\xad\xbe\xef")],
+        want: Ok(false),
+    }
+}
+
+bench_function! {
+    jq_subset => vrl_stdlib::JqSubset;
+
+    literal {
+        args: func_args![
+            value: value!({"users": [{"name": "ana", "age": 31}, {"name": "bo", "age": 17}]}),
+            program: ".users[] | select(.age >= 18) | {name, adult: true}",
+        ],
+        want: Ok(value!([{"name": "ana", "adult": true}])),
+    }
+}
+
 bench_function! {
     join => vrl_stdlib::Join;
 
@@ -1028,6 +1414,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    normalize_url => vrl_stdlib::NormalizeUrl;
+
+    literal {
+        args: func_args![value: "HTTPS://Vector.dev:443/docs/../reference/?b=2&a=1"],
+        want: Ok("https://vector.dev/reference/?a=1&b=2"),
+    }
+}
+
 bench_function! {
     object => vrl_stdlib::Object;
 
@@ -1126,6 +1521,31 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_aws_firehose_cloudwatch_logs => vrl_stdlib::ParseAwsFirehoseCloudWatchLogs;
+
+    literal {
+        args: func_args![value: "H4sIAAAAAAACA3WOTQuCQBCG/8ucDdw+BL0JmqdOeguJTSdZ0F3ZGYsQ/3urJXhpbu/Hw7wjdEgkGyzePUIESVzEt0ua53GWggfmpdE6W2zO2a1pMmuG3iWMxLtmEYufs0XZrQF9lQc03Kmyqmdl9Fm1jJYgukLiSkrL2YVy4dMnap6zEVQ9f3YwKzeSZef+icD3hfBPQegfQm8d73oPZYlh8n7Y/i92FFuMsDK6hqmcPqgHcn4KAQAA"],
+        want: Ok(value!([{
+            "id": "1",
+            "timestamp": (Utc.timestamp(1600110569, 39000000)),
+            "message": "first",
+            "owner": "111111111111",
+            "log_group": "test-group",
+            "log_stream": "test-stream",
+            "subscription_filters": ["Destination"],
+        }, {
+            "id": "2",
+            "timestamp": (Utc.timestamp(1600110569, 41000000)),
+            "message": "second",
+            "owner": "111111111111",
+            "log_group": "test-group",
+            "log_stream": "test-stream",
+            "subscription_filters": ["Destination"],
+        }]))
+    }
+}
+
 bench_function! {
     parse_aws_vpc_flow_log => vrl_stdlib::ParseAwsVpcFlowLog;
 
@@ -1214,6 +1634,45 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_auditd => vrl_stdlib::ParseAuditd;
+
+    literal {
+        args: func_args![value: vec![
+            r#"type=SYSCALL msg=audit(1364481363.243:24287): arch=c000003e syscall=59 success=yes exit=0 ppid=2686 pid=3538 auid=1000 uid=1000 comm="ls" exe="/usr/bin/ls" key=(null)"#,
+            r#"type=EXECVE msg=audit(1364481363.243:24287): argc=2 a0="ls" a1=2F746D702F6120622063"#,
+        ]],
+        want: Ok(value!([{
+            "id": "1364481363.243:24287",
+            "timestamp": (Utc.timestamp(1364481363, 243_000_000)),
+            "sequence": 24287,
+            "records": [
+                {
+                    "type": "SYSCALL",
+                    "arch": "c000003e",
+                    "syscall": "59",
+                    "success": "yes",
+                    "exit": "0",
+                    "ppid": "2686",
+                    "pid": "3538",
+                    "auid": "1000",
+                    "uid": "1000",
+                    "comm": "ls",
+                    "exe": "/usr/bin/ls",
+                    "key": null,
+                },
+                {
+                    "type": "EXECVE",
+                    "argc": "2",
+                    "a0": "ls",
+                    "a1": "/tmp/a b c",
+                    "argv": ["ls", "/tmp/a b c"],
+                },
+            ],
+        }])),
+    }
+}
+
 bench_function! {
     parse_common_log => vrl_stdlib::ParseCommonLog;
 
@@ -1527,6 +1986,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_semver => vrl_stdlib::ParseSemver;
+
+    literal {
+        args: func_args![value: "1.2.3-rc.1+build.5"],
+        want: Ok(value!({"major": 1, "minor": 2, "patch": 3, "pre": "rc.1", "build": "build.5"})),
+    }
+}
+
 bench_function! {
     parse_syslog => vrl_stdlib::ParseSyslog;
 
@@ -1606,6 +2074,7 @@ bench_function! {
                         "host": "vector.dev",
                         "port": null,
                         "path": "/",
+                        "path_segments": [],
                         "query": {},
                         "fragment": null,
         }))
@@ -1939,6 +2408,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    soundex => vrl_stdlib::Soundex;
+
+    literal {
+        args: func_args![value: "Ashcraft"],
+        want: Ok("A261"),
+    }
+}
+
 bench_function! {
     split => vrl_stdlib::Split;
 
@@ -1967,6 +2445,20 @@ bench_function! {
     }
 }
 
+bench_function! {
+    stddev => vrl_stdlib::Stddev;
+
+    population {
+        args: func_args![value: value!([2, 4, 4, 4, 5, 5, 7, 9])],
+        want: Ok(2.0),
+    }
+
+    sample {
+        args: func_args![value: value!([1, 3, 5]), sample: true],
+        want: Ok(2.0),
+    }
+}
+
 bench_function! {
     string => vrl_stdlib::String;
 
@@ -1996,6 +2488,20 @@ bench_function! {
     }
 }
 
+bench_function! {
+    sum => vrl_stdlib::Sum;
+
+    integers {
+        args: func_args![value: value!([3, 5, 8])],
+        want: Ok(16),
+    }
+
+    floats {
+        args: func_args![value: value!([0.5, 1, 2.25])],
+        want: Ok(3.75),
+    }
+}
+
 bench_function! {
     tag_types_externally => vrl_stdlib::TagTypesExternally;
 
@@ -2121,6 +2627,20 @@ bench_function! {
     }
 }
 
+bench_function! {
+    to_decimal => vrl_stdlib::ToDecimal;
+
+    string {
+        args: func_args![value: "1234.5678"],
+        want: Ok(Value::Decimal("1234.5678".parse().unwrap()))
+    }
+
+    int {
+        args: func_args![value: 20],
+        want: Ok(Value::Decimal(20.into()))
+    }
+}
+
 bench_function! {
     to_float => vrl_stdlib::ToFloat;
 
@@ -2169,6 +2689,38 @@ bench_function! {
     }
 }
 
+bench_function! {
+    to_ocsf => vrl_stdlib::ToOcsf;
+
+    syslog_auth {
+        args: func_args![
+            value: value!({
+                "appname": "sshd",
+                "hostname": "bastion",
+                "message": "Accepted publickey for alice from 10.0.0.1 port 52314 ssh2",
+                "timestamp": (DateTime::parse_from_rfc3339("2022-03-01T00:00:00Z").unwrap().with_timezone(&Utc)),
+            }),
+            class: "authentication",
+            profile: "syslog_auth",
+        ],
+        want: Ok(value!({
+            "activity_id": 1,
+            "auth_protocol": "publickey",
+            "category_uid": 3,
+            "class_uid": 3002,
+            "dst_endpoint": {"hostname": "bastion"},
+            "metadata": {"product": {"name": "sshd"}, "version": "1.0.0"},
+            "severity_id": 1,
+            "src_endpoint": {"ip": "10.0.0.1", "port": 52314},
+            "status": "Success",
+            "status_id": 1,
+            "time": 1_646_092_800_000_i64,
+            "type_uid": 300_201,
+            "user": {"name": "alice"},
+        }))
+    }
+}
+
 bench_function! {
     to_regex => vrl_stdlib::ToRegex;
 
@@ -2220,6 +2772,20 @@ bench_function! {
     }
 }
 
+bench_function! {
+    to_syslog_priority => vrl_stdlib::ToSyslogPriority;
+
+    names {
+        args: func_args![facility: value!("local4"), severity: value!("notice")],
+        want: Ok(value!(165)),
+    }
+
+    codes {
+        args: func_args![facility: value!(20), severity: value!(5)],
+        want: Ok(value!(165)),
+    }
+}
+
 bench_function! {
     to_syslog_severity => vrl_stdlib::ToSyslogSeverity;
 
@@ -2305,3 +2871,15 @@ bench_function! {
         want: Ok("FOO")
     }
 }
+
+bench_function! {
+    xpath => vrl_stdlib::Xpath;
+
+    literal {
+        args: func_args![
+            value: r#"<books><book id="1" lang="en"><title>Dune</title></book><book id="2" lang="fr"><title>Vingt mille lieues sous les mers</title></book></books>"#,
+            expr: "//book[@lang='en']/title",
+        ],
+        want: Ok(value!(["Dune"])),
+    }
+}
@@ -1,6 +1,8 @@
 use vrl::prelude::*;
 
-use crate::util::round_to_precision;
+use rust_decimal::RoundingStrategy;
+
+use crate::util::{round_decimal_to_precision, round_to_precision};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Ceil;
@@ -14,7 +16,7 @@ impl Function for Ceil {
         &[
             Parameter {
                 keyword: "value",
-                kind: kind::FLOAT | kind::INTEGER,
+                kind: kind::FLOAT | kind::INTEGER | kind::DECIMAL,
                 required: true,
             },
             Parameter {
@@ -61,10 +63,13 @@ impl Expression for CeilFn {
 
         match self.value.resolve(ctx)? {
             Value::Float(f) => Ok(round_to_precision(*f, precision, f64::ceil).into()),
+            Value::Decimal(d) => round_decimal_to_precision(d, precision, RoundingStrategy::ToPositiveInfinity)
+                .map(Into::into)
+                .ok_or_else(|| "decimal precision out of range".into()),
             value @ Value::Integer(_) => Ok(value),
             value => Err(value::Error::Expected {
                 got: value.kind(),
-                expected: Kind::Float | Kind::Integer,
+                expected: Kind::Float | Kind::Integer | Kind::Decimal,
             }
             .into()),
         }
@@ -72,8 +77,8 @@ impl Expression for CeilFn {
 
     fn type_def(&self, state: &state::Compiler) -> TypeDef {
         TypeDef::new().scalar(match self.value.type_def(state).kind() {
-            v if v.is_float() || v.is_integer() => v,
-            _ => Kind::Integer | Kind::Float,
+            v if v.is_float() || v.is_integer() || v.is_decimal() => v,
+            _ => Kind::Integer | Kind::Float | Kind::Decimal,
         })
     }
 }
@@ -0,0 +1,101 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct CheckedAdd;
+
+impl Function for CheckedAdd {
+    fn identifier(&self) -> &'static str {
+        "checked_add"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "adds integers",
+                source: "checked_add!(1, 2)",
+                result: Ok("3"),
+            },
+            Example {
+                title: "overflow",
+                source: "checked_add!(9223372036854775807, 1)",
+                result: Err(r#"function call error for "checked_add" at (0:36): integer overflow"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(Box::new(CheckedAddFn { value, other }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CheckedAddFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl Expression for CheckedAddFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_integer()?;
+        let other = self.other.resolve(ctx)?.try_integer()?;
+
+        value
+            .checked_add(other)
+            .map(Into::into)
+            .ok_or_else(|| "integer overflow".into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().integer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        checked_add => CheckedAdd;
+
+        add {
+            args: func_args![value: 1, other: 2],
+            want: Ok(3),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        overflow {
+            args: func_args![value: i64::MAX, other: 1],
+            want: Err("integer overflow"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        underflow {
+            args: func_args![value: i64::MIN, other: -1],
+            want: Err("integer overflow"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+    ];
+}
@@ -0,0 +1,101 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct CheckedMul;
+
+impl Function for CheckedMul {
+    fn identifier(&self) -> &'static str {
+        "checked_mul"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "multiplies integers",
+                source: "checked_mul!(3, 4)",
+                result: Ok("12"),
+            },
+            Example {
+                title: "overflow",
+                source: "checked_mul!(9223372036854775807, 2)",
+                result: Err(r#"function call error for "checked_mul" at (0:36): integer overflow"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(Box::new(CheckedMulFn { value, other }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CheckedMulFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl Expression for CheckedMulFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_integer()?;
+        let other = self.other.resolve(ctx)?.try_integer()?;
+
+        value
+            .checked_mul(other)
+            .map(Into::into)
+            .ok_or_else(|| "integer overflow".into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().integer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        checked_mul => CheckedMul;
+
+        mul {
+            args: func_args![value: 3, other: 4],
+            want: Ok(12),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        overflow {
+            args: func_args![value: i64::MAX, other: 2],
+            want: Err("integer overflow"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        underflow {
+            args: func_args![value: i64::MIN, other: 2],
+            want: Err("integer overflow"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+    ];
+}
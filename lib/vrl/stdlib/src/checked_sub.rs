@@ -0,0 +1,101 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct CheckedSub;
+
+impl Function for CheckedSub {
+    fn identifier(&self) -> &'static str {
+        "checked_sub"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "subtracts integers",
+                source: "checked_sub!(5, 2)",
+                result: Ok("3"),
+            },
+            Example {
+                title: "overflow",
+                source: "checked_sub!(-9223372036854775807, 2)",
+                result: Err(r#"function call error for "checked_sub" at (0:37): integer overflow"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(Box::new(CheckedSubFn { value, other }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CheckedSubFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl Expression for CheckedSubFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_integer()?;
+        let other = self.other.resolve(ctx)?.try_integer()?;
+
+        value
+            .checked_sub(other)
+            .map(Into::into)
+            .ok_or_else(|| "integer overflow".into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().integer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        checked_sub => CheckedSub;
+
+        sub {
+            args: func_args![value: 5, other: 2],
+            want: Ok(3),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        overflow {
+            args: func_args![value: i64::MIN, other: 1],
+            want: Err("integer overflow"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        underflow {
+            args: func_args![value: i64::MAX, other: -1],
+            want: Err("integer overflow"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+    ];
+}
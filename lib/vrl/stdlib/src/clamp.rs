@@ -0,0 +1,155 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Clamp;
+
+impl Function for Clamp {
+    fn identifier(&self) -> &'static str {
+        "clamp"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
+                required: true,
+            },
+            Parameter {
+                keyword: "min",
+                kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
+                required: true,
+            },
+            Parameter {
+                keyword: "max",
+                kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "within bounds",
+                source: "clamp!(42, 0, 100)",
+                result: Ok("42"),
+            },
+            Example {
+                title: "above maximum",
+                source: "clamp!(1.5, 0.0, 1.0)",
+                result: Ok("1.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let min = arguments.required("min");
+        let max = arguments.required("max");
+
+        Ok(Box::new(ClampFn { value, min, max }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ClampFn {
+    value: Box<dyn Expression>,
+    min: Box<dyn Expression>,
+    max: Box<dyn Expression>,
+}
+
+impl Expression for ClampFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let min = self.min.resolve(ctx)?;
+        let max = self.max.resolve(ctx)?;
+
+        for number in [&value, &min, &max] {
+            if !matches!(
+                number,
+                Value::Integer(_) | Value::Float(_) | Value::Decimal(_)
+            ) {
+                return Err(value::Error::Expected {
+                    got: number.kind(),
+                    expected: Kind::Integer | Kind::Float | Kind::Decimal,
+                }
+                .into());
+            }
+        }
+        if min.clone().try_gt(max.clone())?.try_boolean()? {
+            return Err(format!("`min` ({}) is greater than `max` ({})", min, max).into());
+        }
+
+        // The bound replaces the value, so the result has the type of whichever one is returned.
+        if value.clone().try_lt(min.clone())?.try_boolean()? {
+            Ok(min)
+        } else if value.clone().try_gt(max.clone())?.try_boolean()? {
+            Ok(max)
+        } else {
+            Ok(value)
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        let numbers = Kind::Integer | Kind::Float | Kind::Decimal;
+        let kind = (self.value.type_def(state).kind()
+            | self.min.type_def(state).kind()
+            | self.max.type_def(state).kind())
+            & numbers;
+
+        TypeDef::new()
+            .fallible()
+            .scalar(if kind.is_empty() { numbers } else { kind })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        clamp => Clamp;
+
+        within {
+            args: func_args![value: 42, min: 0, max: 100],
+            want: Ok(42),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        below {
+            args: func_args![value: -3, min: 0, max: 100],
+            want: Ok(0),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        above {
+            args: func_args![value: 1.5, min: 0.0, max: 1.0],
+            want: Ok(1.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        mixed_types {
+            args: func_args![value: 120, min: 0.0, max: 99.5],
+            want: Ok(99.5),
+            tdef: TypeDef::new().fallible().scalar(Kind::Integer | Kind::Float),
+        }
+
+        decimal {
+            args: func_args![value: Value::Decimal("1.05".parse().unwrap()), min: 0, max: 1],
+            want: Ok(1),
+            tdef: TypeDef::new().fallible().scalar(Kind::Integer | Kind::Decimal),
+        }
+
+        inverted_bounds {
+            args: func_args![value: 5, min: 10, max: 1],
+            want: Err("`min` (10) is greater than `max` (1)"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+    ];
+}
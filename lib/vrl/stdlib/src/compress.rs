@@ -0,0 +1,185 @@
+use std::{io::Write, str::FromStr};
+
+use flate2::write::{GzEncoder, ZlibEncoder};
+use vrl::prelude::*;
+
+use crate::util::Compression;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Compress;
+
+impl Function for Compress {
+    fn identifier(&self) -> &'static str {
+        "compress"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let algorithm = arguments
+            .required_enum("algorithm", &Compression::all_value())?
+            .try_bytes_utf8_lossy()
+            .map(|s| Compression::from_str(&s).expect("validated enum"))
+            .expect("algorithm not bytes");
+
+        Ok(Box::new(CompressFn { value, algorithm }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "snappy in base64",
+            source: r#"encode_base64(compress!("hello world", "snappy"))"#,
+            result: Ok("CyhoZWxsbyB3b3JsZA=="),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CompressFn {
+    value: Box<dyn Expression>,
+    algorithm: Compression,
+}
+
+impl Expression for CompressFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+
+        let result = match self.algorithm {
+            Compression::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder
+                    .write_all(&value)
+                    .and_then(|_| encoder.finish())
+                    .map_err(|error| error.to_string())
+            }
+            Compression::Zlib => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder
+                    .write_all(&value)
+                    .and_then(|_| encoder.finish())
+                    .map_err(|error| error.to_string())
+            }
+            Compression::Zstd => {
+                zstd::stream::encode_all(&value[..], zstd::DEFAULT_COMPRESSION_LEVEL)
+                    .map_err(|error| error.to_string())
+            }
+            Compression::Snappy => snap::raw::Encoder::new()
+                .compress_vec(&value)
+                .map_err(|error| error.to_string()),
+            Compression::Lz4 => {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+                encoder
+                    .write_all(&value)
+                    .map_err(|error| error.to_string())
+                    .and_then(|_| encoder.finish().map_err(|error| error.to_string()))
+            }
+        };
+
+        result.map(Value::from).map_err(|error| {
+            format!(
+                "unable to compress value with {}: {}",
+                self.algorithm.as_str(),
+                error
+            )
+            .into()
+        })
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        // Compressing in memory can only fail for values too large for the algorithm.
+        TypeDef::new().bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::BTreeMap, io::Read};
+
+    use shared::TimeZone;
+    use vrl::prelude::expression::Literal;
+
+    use super::*;
+
+    test_function![
+        compress => Compress;
+
+        snappy {
+            args: func_args![value: "hello world", algorithm: "snappy"],
+            want: Ok(Bytes::from_static(b"\x0b\x28hello world")),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        unknown_algorithm {
+            args: func_args![value: "hello world", algorithm: "brotli"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+    ];
+
+    #[test]
+    fn round_trips() {
+        let mut object: Value = BTreeMap::new().into();
+        let mut runtime_state = vrl::state::Runtime::default();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut object, &mut runtime_state, &tz);
+
+        for algorithm in [
+            Compression::Gzip,
+            Compression::Zlib,
+            Compression::Zstd,
+            Compression::Snappy,
+            Compression::Lz4,
+        ] {
+            let f = CompressFn {
+                value: Box::new(Literal::String(Bytes::from_static(b"hello world"))),
+                algorithm,
+            };
+            let compressed = f.resolve(&mut ctx).unwrap().try_bytes().unwrap();
+
+            let mut output = Vec::new();
+            match algorithm {
+                Compression::Gzip => {
+                    flate2::read::GzDecoder::new(&compressed[..])
+                        .read_to_end(&mut output)
+                        .unwrap();
+                }
+                Compression::Zlib => {
+                    flate2::read::ZlibDecoder::new(&compressed[..])
+                        .read_to_end(&mut output)
+                        .unwrap();
+                }
+                Compression::Zstd => output = zstd::stream::decode_all(&compressed[..]).unwrap(),
+                Compression::Snappy => {
+                    output = snap::raw::Decoder::new()
+                        .decompress_vec(&compressed)
+                        .unwrap()
+                }
+                Compression::Lz4 => {
+                    lz4_flex::frame::FrameDecoder::new(&compressed[..])
+                        .read_to_end(&mut output)
+                        .unwrap();
+                }
+            }
+            assert_eq!(output, b"hello world", "{}", algorithm.as_str());
+        }
+    }
+}
@@ -12,6 +12,10 @@ impl Function for DecodeBase64 {
         "decode_base64"
     }
 
+    fn is_pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
@@ -0,0 +1,248 @@
+use std::{io::Read, str::FromStr};
+
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use vrl::prelude::*;
+
+use crate::util::Compression;
+
+/// Values can decompress to many times their size, so the output is bounded unless `max_bytes`
+/// says otherwise.
+const DEFAULT_MAX_BYTES: i64 = 10 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Decompress;
+
+impl Function for Decompress {
+    fn identifier(&self) -> &'static str {
+        "decompress"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_bytes",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let algorithm = arguments
+            .required_enum("algorithm", &Compression::all_value())?
+            .try_bytes_utf8_lossy()
+            .map(|s| Compression::from_str(&s).expect("validated enum"))
+            .expect("algorithm not bytes");
+        let max_bytes = arguments
+            .optional("max_bytes")
+            .unwrap_or_else(|| expr!(DEFAULT_MAX_BYTES));
+
+        Ok(Box::new(DecompressFn {
+            value,
+            algorithm,
+            max_bytes,
+        }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "gzip in base64",
+            source: r#"decompress!(decode_base64!("H4sIAAAAAAACA8tIzcnJVyjPL8pJAQCFEUoNCwAAAA=="), "gzip")"#,
+            result: Ok("hello world"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecompressFn {
+    value: Box<dyn Expression>,
+    algorithm: Compression,
+    max_bytes: Box<dyn Expression>,
+}
+
+impl Expression for DecompressFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+        let max_bytes = self.max_bytes.resolve(ctx)?.try_integer()?;
+        let max_bytes = usize::try_from(max_bytes).map_err(|_| "max_bytes must not be negative")?;
+
+        let result = match self.algorithm {
+            // Concatenated gzip members are common, CloudWatch Logs subscriptions being one
+            // example, so all of them are decompressed rather than only the first.
+            Compression::Gzip => read_limited(MultiGzDecoder::new(&value[..]), max_bytes),
+            Compression::Zlib => read_limited(ZlibDecoder::new(&value[..]), max_bytes),
+            Compression::Zstd => zstd::stream::read::Decoder::new(&value[..])
+                .map_err(|error| error.to_string())
+                .and_then(|decoder| read_limited(decoder, max_bytes)),
+            // The block format declares its length up front, and the decoder allocates all of it.
+            Compression::Snappy => snap::raw::decompress_len(&value)
+                .map_err(|error| error.to_string())
+                .and_then(|length| check_length(length, max_bytes))
+                .and_then(|_| {
+                    snap::raw::Decoder::new()
+                        .decompress_vec(&value)
+                        .map_err(|error| error.to_string())
+                }),
+            Compression::Lz4 => {
+                read_limited(lz4_flex::frame::FrameDecoder::new(&value[..]), max_bytes)
+            }
+        };
+
+        result.map(Value::from).map_err(|error| {
+            format!(
+                "unable to decompress value with {}: {}",
+                self.algorithm.as_str(),
+                error
+            )
+            .into()
+        })
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        // Always fallible, as the value may not be validly compressed.
+        TypeDef::new().bytes().fallible()
+    }
+}
+
+/// Reads all of `reader`, but fails rather than read more than `max_bytes` from it.
+fn read_limited(reader: impl Read, max_bytes: usize) -> Result<Vec<u8>, String> {
+    let mut output = Vec::new();
+    reader
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut output)
+        .map_err(|error| error.to_string())?;
+    check_length(output.len(), max_bytes)?;
+    Ok(output)
+}
+
+fn check_length(length: usize, max_bytes: usize) -> Result<(), String> {
+    if length > max_bytes {
+        Err(format!(
+            "decompressed value is larger than {} bytes",
+            max_bytes
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    test_function![
+        decompress => Decompress;
+
+        gzip {
+            args: func_args![
+                value: Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xcb\x48\xcd\xc9\xc9\x57\x28\xcf\x2f\xca\x49\x01\x00\x85\x11\x4a\x0d\x0b\x00\x00\x00"),
+                algorithm: "gzip"
+            ],
+            want: Ok("hello world"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        zlib {
+            args: func_args![
+                value: Bytes::from_static(b"\x78\x9c\xcb\x48\xcd\xc9\xc9\x57\x28\xcf\x2f\xca\x49\x01\x00\x1a\x0b\x04\x5d"),
+                algorithm: "zlib"
+            ],
+            want: Ok("hello world"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        zstd {
+            args: func_args![
+                value: Bytes::from_static(b"\x28\xb5\x2f\xfd\x00\x58\x59\x00\x00\x68\x65\x6c\x6c\x6f\x20\x77\x6f\x72\x6c\x64"),
+                algorithm: "zstd"
+            ],
+            want: Ok("hello world"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        snappy {
+            args: func_args![
+                value: Bytes::from_static(b"\x0b\x28hello world"),
+                algorithm: "snappy"
+            ],
+            want: Ok("hello world"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        lz4 {
+            args: func_args![
+                value: Bytes::from_static(b"\x04\x22\x4d\x18\x60\x40\x82\x0b\x00\x00\x80\x68\x65\x6c\x6c\x6f\x20\x77\x6f\x72\x6c\x64\x00\x00\x00\x00"),
+                algorithm: "lz4"
+            ],
+            want: Ok("hello world"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        gzip_too_large {
+            args: func_args![
+                value: Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\xcb\x48\xcd\xc9\xc9\x57\x28\xcf\x2f\xca\x49\x01\x00\x85\x11\x4a\x0d\x0b\x00\x00\x00"),
+                algorithm: "gzip",
+                max_bytes: 10
+            ],
+            want: Err("unable to decompress value with gzip: decompressed value is larger than 10 bytes"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        snappy_too_large {
+            args: func_args![
+                value: Bytes::from_static(b"\x0b\x28hello world"),
+                algorithm: "snappy",
+                max_bytes: 10
+            ],
+            want: Err("unable to decompress value with snappy: decompressed value is larger than 10 bytes"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        snappy_declaring_too_much {
+            args: func_args![
+                value: Bytes::from_static(b"\xff\xff\xff\xff\x0f"),
+                algorithm: "snappy"
+            ],
+            want: Err("unable to decompress value with snappy: decompressed value is larger than 10485760 bytes"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        exactly_max_bytes {
+            args: func_args![
+                value: Bytes::from_static(b"\x0b\x28hello world"),
+                algorithm: "snappy",
+                max_bytes: 11
+            ],
+            want: Ok("hello world"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "hello world", algorithm: "zlib"],
+            want: Err("unable to decompress value with zlib: corrupt deflate stream"),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+
+        unknown_algorithm {
+            args: func_args![value: "hello world", algorithm: "brotli"],
+            want: Err(r#"invalid enum variant""#),
+            tdef: TypeDef::new().bytes().fallible(),
+        }
+    ];
+}
@@ -8,6 +8,10 @@ impl Function for Downcase {
         "downcase"
     }
 
+    fn is_pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[Parameter {
             keyword: "value",
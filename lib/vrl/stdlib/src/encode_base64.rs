@@ -12,6 +12,10 @@ impl Function for EncodeBase64 {
         "encode_base64"
     }
 
+    fn is_pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
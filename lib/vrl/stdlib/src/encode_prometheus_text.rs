@@ -0,0 +1,480 @@
+use std::collections::BTreeMap;
+
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodePrometheusText;
+
+impl Function for EncodePrometheusText {
+    fn identifier(&self) -> &'static str {
+        "encode_prometheus_text"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "encode counter",
+                source: r#"encode_prometheus_text!([{"name": "http_requests_total", "type": "counter", "labels": {"code": "200"}, "value": 1027}])"#,
+                result: Ok(
+                    r##""# TYPE http_requests_total counter\nhttp_requests_total{code=\"200\"} 1027\n""##,
+                ),
+            },
+            Example {
+                title: "encode histogram",
+                source: r#"encode_prometheus_text!([{"name": "request_duration_seconds", "type": "histogram", "buckets": [{"upper_limit": 0.5, "count": 3}, {"upper_limit": 1, "count": 5}], "sum": 2.5, "count": 6}])"#,
+                result: Ok(
+                    r##""# TYPE request_duration_seconds histogram\nrequest_duration_seconds_bucket{le=\"0.5\"} 3\nrequest_duration_seconds_bucket{le=\"1\"} 5\nrequest_duration_seconds_bucket{le=\"+Inf\"} 6\nrequest_duration_seconds_sum 2.5\nrequest_duration_seconds_count 6\n""##,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(EncodePrometheusTextFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodePrometheusTextFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for EncodePrometheusTextFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let metrics = self.value.resolve(ctx)?.try_array()?;
+
+        // Samples of the same metric must be written together, under a single `TYPE` line, so
+        // they are grouped by name, in the order the names first appear in.
+        let mut families: Vec<Family> = Vec::new();
+        for (index, metric) in metrics.iter().enumerate() {
+            let metric = match metric {
+                Value::Object(metric) => metric,
+                value => {
+                    return Err(format!(
+                        "metric at index {} must be an object, not {}",
+                        index,
+                        value.kind()
+                    )
+                    .into())
+                }
+            };
+            add_metric(&mut families, metric)
+                .map_err(|error| format!("metric at index {}: {}", index, error))?;
+        }
+
+        let mut text = String::new();
+        for family in families {
+            if let Some(help) = family.help {
+                text.push_str("# HELP ");
+                text.push_str(&family.name);
+                text.push(' ');
+                escape_into(&mut text, &help, false);
+                text.push('\n');
+            }
+            text.push_str("# TYPE ");
+            text.push_str(&family.name);
+            text.push(' ');
+            text.push_str(family.kind.as_str());
+            text.push('\n');
+            text.push_str(&family.samples);
+        }
+
+        Ok(text.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().bytes()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+    Summary,
+    Untyped,
+}
+
+impl MetricKind {
+    fn parse(kind: &str) -> std::result::Result<Self, String> {
+        Ok(match kind {
+            "counter" => Self::Counter,
+            "gauge" => Self::Gauge,
+            "histogram" => Self::Histogram,
+            "summary" => Self::Summary,
+            "untyped" => Self::Untyped,
+            kind => return Err(format!(r#"unknown type "{}""#, kind)),
+        })
+    }
+
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+            Self::Summary => "summary",
+            Self::Untyped => "untyped",
+        }
+    }
+}
+
+/// The samples of all the metrics with the same name.
+struct Family {
+    name: String,
+    kind: MetricKind,
+    help: Option<String>,
+    samples: String,
+}
+
+fn add_metric(
+    families: &mut Vec<Family>,
+    metric: &BTreeMap<String, Value>,
+) -> std::result::Result<(), String> {
+    let name = match metric.get("name") {
+        Some(Value::Bytes(name)) => String::from_utf8_lossy(name).into_owned(),
+        _ => return Err("`name` must be a string".to_owned()),
+    };
+    if !is_valid_metric_name(&name) {
+        return Err(format!(r#"invalid metric name "{}""#, name));
+    }
+    let kind = match metric.get("type") {
+        Some(Value::Bytes(kind)) => MetricKind::parse(&String::from_utf8_lossy(kind))?,
+        None | Some(Value::Null) => MetricKind::Untyped,
+        _ => return Err("`type` must be a string".to_owned()),
+    };
+    let help = match metric.get("help") {
+        Some(Value::Bytes(help)) => Some(String::from_utf8_lossy(help).into_owned()),
+        None | Some(Value::Null) => None,
+        _ => return Err("`help` must be a string".to_owned()),
+    };
+    let labels = match metric.get("labels") {
+        Some(Value::Object(labels)) => labels
+            .iter()
+            .filter(|(_, value)| !value.is_null())
+            .map(|(name, value)| {
+                if !is_valid_label_name(name) {
+                    return Err(format!(r#"invalid label name "{}""#, name));
+                }
+                Ok((name.as_str(), to_label_value(value)?))
+            })
+            .collect::<std::result::Result<Vec<_>, String>>()?,
+        None | Some(Value::Null) => Vec::new(),
+        _ => return Err("`labels` must be an object".to_owned()),
+    };
+    let timestamp = match metric.get("timestamp") {
+        Some(Value::Timestamp(timestamp)) => Some(timestamp.timestamp_millis()),
+        Some(Value::Integer(millis)) => Some(*millis),
+        None | Some(Value::Null) => None,
+        _ => return Err("`timestamp` must be a timestamp or an integer".to_owned()),
+    };
+
+    let index = match families.iter().position(|family| family.name == name) {
+        Some(index) => {
+            let family = &mut families[index];
+            if family.kind != kind {
+                return Err(format!(
+                    r#"metric "{}" is both a {} and a {}"#,
+                    name,
+                    family.kind.as_str(),
+                    kind.as_str()
+                ));
+            }
+            if family.help.is_none() {
+                family.help = help;
+            }
+            index
+        }
+        None => {
+            families.push(Family {
+                name: name.clone(),
+                kind,
+                help,
+                samples: String::new(),
+            });
+            families.len() - 1
+        }
+    };
+    let mut writer = SampleWriter {
+        samples: &mut families[index].samples,
+        name: &name,
+        labels: &labels,
+        timestamp,
+    };
+
+    match kind {
+        MetricKind::Counter | MetricKind::Gauge | MetricKind::Untyped => {
+            writer.write("", None, &number_field(metric, "value")?);
+        }
+        MetricKind::Histogram => {
+            let count = number_field(metric, "count")?;
+            let mut has_infinite_bucket = false;
+            for bucket in array_field(metric, "buckets")? {
+                let upper_limit = number_field(bucket, "upper_limit")?;
+                has_infinite_bucket |= upper_limit == "+Inf";
+                writer.write(
+                    "_bucket",
+                    Some(("le", &upper_limit)),
+                    &number_field(bucket, "count")?,
+                );
+            }
+            if !has_infinite_bucket {
+                writer.write("_bucket", Some(("le", "+Inf")), &count);
+            }
+            writer.write("_sum", None, &number_field(metric, "sum")?);
+            writer.write("_count", None, &count);
+        }
+        MetricKind::Summary => {
+            for quantile in array_field(metric, "quantiles")? {
+                writer.write(
+                    "",
+                    Some(("quantile", &number_field(quantile, "quantile")?)),
+                    &number_field(quantile, "value")?,
+                );
+            }
+            writer.write("_sum", None, &number_field(metric, "sum")?);
+            writer.write("_count", None, &number_field(metric, "count")?);
+        }
+    }
+
+    Ok(())
+}
+
+struct SampleWriter<'a> {
+    samples: &'a mut String,
+    name: &'a str,
+    labels: &'a [(&'a str, String)],
+    timestamp: Option<i64>,
+}
+
+impl<'a> SampleWriter<'a> {
+    /// Writes a sample line of the metric, whose name ends with `suffix`, with an extra label.
+    fn write(&mut self, suffix: &str, extra_label: Option<(&str, &str)>, value: &str) {
+        let samples = &mut *self.samples;
+        samples.push_str(self.name);
+        samples.push_str(suffix);
+
+        let labels = self
+            .labels
+            .iter()
+            .map(|(name, value)| (*name, value.as_str()))
+            .chain(extra_label);
+        for (index, (name, value)) in labels.enumerate() {
+            samples.push(if index == 0 { '{' } else { ',' });
+            samples.push_str(name);
+            samples.push_str("=\"");
+            escape_into(samples, value, true);
+            samples.push('"');
+        }
+        if !self.labels.is_empty() || extra_label.is_some() {
+            samples.push('}');
+        }
+
+        samples.push(' ');
+        samples.push_str(value);
+        if let Some(timestamp) = self.timestamp {
+            samples.push(' ');
+            samples.push_str(&timestamp.to_string());
+        }
+        samples.push('\n');
+    }
+}
+
+fn array_field<'a>(
+    object: &'a BTreeMap<String, Value>,
+    field: &str,
+) -> std::result::Result<Vec<&'a BTreeMap<String, Value>>, String> {
+    match object.get(field) {
+        Some(Value::Array(values)) => values
+            .iter()
+            .map(|value| match value {
+                Value::Object(object) => Ok(object),
+                _ => Err(format!("`{}` must be an array of objects", field)),
+            })
+            .collect(),
+        _ => Err(format!("`{}` must be an array", field)),
+    }
+}
+
+/// Renders the number in `field` of `object` the way Prometheus writes it.
+fn number_field(
+    object: &BTreeMap<String, Value>,
+    field: &str,
+) -> std::result::Result<String, String> {
+    match object.get(field) {
+        Some(Value::Integer(int)) => Ok(int.to_string()),
+        Some(Value::Decimal(decimal)) => Ok(decimal.to_string()),
+        Some(Value::Float(float)) => Ok(format_float(float.into_inner())),
+        _ => Err(format!("`{}` must be a number", field)),
+    }
+}
+
+fn format_float(float: f64) -> String {
+    if float.is_nan() {
+        "NaN".to_owned()
+    } else if float == f64::INFINITY {
+        "+Inf".to_owned()
+    } else if float == f64::NEG_INFINITY {
+        "-Inf".to_owned()
+    } else {
+        float.to_string()
+    }
+}
+
+fn to_label_value(value: &Value) -> std::result::Result<String, String> {
+    Ok(match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Value::Integer(int) => int.to_string(),
+        Value::Float(float) => format_float(float.into_inner()),
+        Value::Decimal(decimal) => decimal.to_string(),
+        Value::Boolean(boolean) => boolean.to_string(),
+        value => return Err(format!("can't encode {} as a label value", value.kind())),
+    })
+}
+
+/// Whether `name` matches `[a-zA-Z_:][a-zA-Z0-9_:]*`.
+fn is_valid_metric_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+/// Whether `name` matches `[a-zA-Z_][a-zA-Z0-9_]*`.
+fn is_valid_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Escapes backslashes and newlines, and in label values double quotes as well.
+fn escape_into(text: &mut String, value: &str, label_value: bool) {
+    for c in value.chars() {
+        match c {
+            '\\' => text.push_str(r"\\"),
+            '\n' => text.push_str(r"\n"),
+            '"' if label_value => text.push_str("\\\""),
+            c => text.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    test_function![
+        encode_prometheus_text => EncodePrometheusText;
+
+        counter {
+            args: func_args![value: value!([{"name": "http_requests_total", "type": "counter", "labels": {"code": "200", "method": "post"}, "value": 1027}])],
+            want: Ok("# TYPE http_requests_total counter\nhttp_requests_total{code=\"200\",method=\"post\"} 1027\n"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        untyped_by_default {
+            args: func_args![value: value!([{"name": "temperature", "value": 21.5}])],
+            want: Ok("# TYPE temperature untyped\ntemperature 21.5\n"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        groups_by_name {
+            args: func_args![value: value!([
+                {"name": "up", "type": "gauge", "labels": {"job": "a"}, "value": 1},
+                {"name": "load", "type": "gauge", "value": 0.25},
+                {"name": "up", "type": "gauge", "help": "Whether the job is up.", "labels": {"job": "b"}, "value": 0},
+            ])],
+            want: Ok("# HELP up Whether the job is up.\n# TYPE up gauge\nup{job=\"a\"} 1\nup{job=\"b\"} 0\n# TYPE load gauge\nload 0.25\n"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        histogram {
+            args: func_args![value: value!([{
+                "name": "request_duration_seconds",
+                "type": "histogram",
+                "labels": {"path": "/"},
+                "buckets": [{"upper_limit": 0.5, "count": 3}, {"upper_limit": 1, "count": 5}],
+                "sum": 2.5,
+                "count": 6,
+            }])],
+            want: Ok("# TYPE request_duration_seconds histogram\n\
+                      request_duration_seconds_bucket{path=\"/\",le=\"0.5\"} 3\n\
+                      request_duration_seconds_bucket{path=\"/\",le=\"1\"} 5\n\
+                      request_duration_seconds_bucket{path=\"/\",le=\"+Inf\"} 6\n\
+                      request_duration_seconds_sum{path=\"/\"} 2.5\n\
+                      request_duration_seconds_count{path=\"/\"} 6\n"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        summary {
+            args: func_args![value: value!([{
+                "name": "rpc_duration_seconds",
+                "type": "summary",
+                "quantiles": [{"quantile": 0.5, "value": 0.05}, {"quantile": 0.99, "value": 0.2}],
+                "sum": 17.5,
+                "count": 200,
+            }])],
+            want: Ok("# TYPE rpc_duration_seconds summary\n\
+                      rpc_duration_seconds{quantile=\"0.5\"} 0.05\n\
+                      rpc_duration_seconds{quantile=\"0.99\"} 0.2\n\
+                      rpc_duration_seconds_sum 17.5\n\
+                      rpc_duration_seconds_count 200\n"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        timestamps {
+            args: func_args![value: value!([
+                {"name": "a", "value": 1, "timestamp": (Utc.ymd(2021, 2, 11).and_hms(16, 0, 0))},
+                {"name": "b", "value": 2, "timestamp": 1613059200000_i64},
+            ])],
+            want: Ok("# TYPE a untyped\na 1 1613059200000\n# TYPE b untyped\nb 2 1613059200000\n"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        escaping {
+            args: func_args![value: value!([{"name": "a", "help": "line\\one\nline two", "labels": {"path": "C:\\\"x\"\n"}, "value": 1}])],
+            want: Ok("# HELP a line\\\\one\\nline two\n# TYPE a untyped\na{path=\"C:\\\\\\\"x\\\"\\n\"} 1\n"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid_metric_name {
+            args: func_args![value: value!([{"name": "1up", "value": 1}])],
+            want: Err(r#"metric at index 0: invalid metric name "1up""#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid_label_name {
+            args: func_args![value: value!([{"name": "up", "labels": {"a-b": "c"}, "value": 1}])],
+            want: Err(r#"metric at index 0: invalid label name "a-b""#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        conflicting_types {
+            args: func_args![value: value!([{"name": "up", "type": "gauge", "value": 1}, {"name": "up", "type": "counter", "value": 1}])],
+            want: Err(r#"metric at index 1: metric "up" is both a gauge and a counter"#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        missing_value {
+            args: func_args![value: value!([{"name": "up", "type": "gauge"}])],
+            want: Err("metric at index 0: `value` must be a number"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
@@ -0,0 +1,379 @@
+use std::collections::BTreeMap;
+
+use chrono::SecondsFormat;
+use vrl::prelude::*;
+
+struct EncodeXmlConfig<'a> {
+    /// Key prefix marking a value as an attribute, e.g. `{a: { "@href": "test" }}` -> `<a href="test">`.
+    attr_prefix: &'a str,
+    /// Key holding the text content of an element that also has attributes or children.
+    text_key: &'a str,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeXml;
+
+impl Function for EncodeXml {
+    fn identifier(&self) -> &'static str {
+        "encode_xml"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "root",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "attr_prefix",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "text_key",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "include_declaration",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "encode object",
+                source: r#"encode_xml!({"book": {"@category": "CHILDREN", "title": {"@lang": "en", "text": "Harry Potter"}, "year": 2005}}, include_declaration: false)"#,
+                result: Ok(
+                    r#"s'<book category="CHILDREN"><title lang="en">Harry Potter</title><year>2005</year></book>'"#,
+                ),
+            },
+            Example {
+                title: "custom root element",
+                source: r#"encode_xml!({"item": ["apple", "pear"]}, root: "order")"#,
+                result: Ok(
+                    r#"s'<?xml version="1.0" encoding="UTF-8"?><order><item>apple</item><item>pear</item></order>'"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let root = arguments.optional("root");
+        let attr_prefix = arguments
+            .optional("attr_prefix")
+            .unwrap_or_else(|| expr!("@"));
+        let text_key = arguments
+            .optional("text_key")
+            .unwrap_or_else(|| expr!("text"));
+        let include_declaration = arguments
+            .optional("include_declaration")
+            .unwrap_or_else(|| expr!(true));
+
+        Ok(Box::new(EncodeXmlFn {
+            value,
+            root,
+            attr_prefix,
+            text_key,
+            include_declaration,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodeXmlFn {
+    value: Box<dyn Expression>,
+    root: Option<Box<dyn Expression>>,
+    attr_prefix: Box<dyn Expression>,
+    text_key: Box<dyn Expression>,
+    include_declaration: Box<dyn Expression>,
+}
+
+impl Expression for EncodeXmlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let object = self.value.resolve(ctx)?.try_object()?;
+
+        let root = match &self.root {
+            Some(expr) => Some(expr.resolve(ctx)?.try_bytes_utf8_lossy()?.into_owned()),
+            None => None,
+        };
+
+        let attr_prefix = self.attr_prefix.resolve(ctx)?;
+        let attr_prefix = attr_prefix.try_bytes_utf8_lossy()?;
+        let text_key = self.text_key.resolve(ctx)?;
+        let text_key = text_key.try_bytes_utf8_lossy()?;
+        let include_declaration = self.include_declaration.resolve(ctx)?.try_boolean()?;
+
+        let config = EncodeXmlConfig {
+            attr_prefix: &attr_prefix,
+            text_key: &text_key,
+        };
+
+        let mut xml = String::new();
+        if include_declaration {
+            xml.push_str(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        }
+
+        match root {
+            Some(root) => write_element(&mut xml, &root, &Value::Object(object), &config)?,
+            None => {
+                // Without an explicit root, the object itself must describe a single element.
+                let mut iter = object.iter();
+                match (iter.next(), iter.next()) {
+                    (Some((name, Value::Array(_))), None) => {
+                        return Err(format!(r#"root element "{}" can't be an array"#, name).into())
+                    }
+                    (Some((name, value)), None) => write_element(&mut xml, name, value, &config)?,
+                    _ => {
+                        return Err(
+                            "object must have exactly one key to use as the root element, or set `root`".into(),
+                        )
+                    }
+                }
+            }
+        }
+
+        Ok(xml.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().bytes()
+    }
+}
+
+/// Write `value` as an element called `name`. Arrays are written as repeated elements.
+fn write_element(
+    xml: &mut String,
+    name: &str,
+    value: &Value,
+    config: &EncodeXmlConfig<'_>,
+) -> std::result::Result<(), String> {
+    if !is_valid_name(name) {
+        return Err(format!(r#"invalid element name "{}""#, name));
+    }
+
+    match value {
+        Value::Array(values) => {
+            for value in values {
+                if let Value::Array(_) = value {
+                    return Err(format!(r#"element "{}" can't contain nested arrays"#, name));
+                }
+                write_element(xml, name, value, config)?;
+            }
+        }
+        Value::Object(map) => write_object(xml, name, map, config)?,
+        Value::Null => {
+            xml.push('<');
+            xml.push_str(name);
+            xml.push_str("/>");
+        }
+        value => {
+            xml.push('<');
+            xml.push_str(name);
+            xml.push('>');
+            escape_into(xml, &to_text(value)?, false);
+            xml.push_str("</");
+            xml.push_str(name);
+            xml.push('>');
+        }
+    }
+
+    Ok(())
+}
+
+fn write_object(
+    xml: &mut String,
+    name: &str,
+    map: &BTreeMap<String, Value>,
+    config: &EncodeXmlConfig<'_>,
+) -> std::result::Result<(), String> {
+    xml.push('<');
+    xml.push_str(name);
+
+    let mut text = None;
+    let mut children = Vec::with_capacity(map.len());
+
+    for (key, value) in map {
+        match key.strip_prefix(config.attr_prefix) {
+            Some(attr) if !config.attr_prefix.is_empty() => {
+                if !is_valid_name(attr) {
+                    return Err(format!(r#"invalid attribute name "{}""#, attr));
+                }
+                if value.is_null() {
+                    continue;
+                }
+                xml.push(' ');
+                xml.push_str(attr);
+                xml.push_str("=\"");
+                escape_into(xml, &to_text(value)?, true);
+                xml.push('"');
+            }
+            _ if key == config.text_key => text = Some(value),
+            _ => children.push((key, value)),
+        }
+    }
+
+    if text.map_or(true, Value::is_null) && children.is_empty() {
+        xml.push_str("/>");
+        return Ok(());
+    }
+
+    xml.push('>');
+    if let Some(text) = text.filter(|text| !text.is_null()) {
+        escape_into(xml, &to_text(text)?, false);
+    }
+    for (key, value) in children {
+        write_element(xml, key, value, config)?;
+    }
+    xml.push_str("</");
+    xml.push_str(name);
+    xml.push('>');
+
+    Ok(())
+}
+
+/// Render a scalar value as text content.
+fn to_text(value: &Value) -> std::result::Result<String, String> {
+    Ok(match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+        Value::Integer(int) => int.to_string(),
+        Value::Float(float) => float.to_string(),
+        Value::Decimal(decimal) => decimal.to_string(),
+        Value::Boolean(boolean) => boolean.to_string(),
+        Value::Timestamp(timestamp) => timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        Value::Regex(regex) => regex.as_str().to_owned(),
+        Value::Null => String::new(),
+        Value::Object(_) | Value::Array(_) => {
+            return Err(format!("can't encode {} as text", value.kind()))
+        }
+    })
+}
+
+/// Whether `name` can be used as an XML element or attribute name.
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' || c == ':' => {}
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_alphanumeric() || matches!(c, '_' | ':' | '-' | '.'))
+}
+
+fn escape_into(xml: &mut String, text: &str, attribute: bool) {
+    for c in text.chars() {
+        match c {
+            '&' => xml.push_str("&amp;"),
+            '<' => xml.push_str("&lt;"),
+            '>' => xml.push_str("&gt;"),
+            '"' if attribute => xml.push_str("&quot;"),
+            '\'' if attribute => xml.push_str("&apos;"),
+            c => xml.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    test_function![
+        encode_xml => EncodeXml;
+
+        simple {
+            args: func_args![value: value!({"a": "test"}), include_declaration: false],
+            want: Ok("<a>test</a>"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        declaration {
+            args: func_args![value: value!({"a": "test"})],
+            want: Ok(r#"<?xml version="1.0" encoding="UTF-8"?><a>test</a>"#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        attributes_and_text {
+            args: func_args![value: value!({"a": {"@href": "https://vector.dev", "@rel": null, "text": "test"}}), include_declaration: false],
+            want: Ok(r#"<a href="https://vector.dev">test</a>"#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        custom_conventions {
+            args: func_args![
+                value: value!({"a": {"_href": "https://vector.dev", "value": "test"}}),
+                attr_prefix: "_",
+                text_key: "value",
+                include_declaration: false,
+            ],
+            want: Ok(r#"<a href="https://vector.dev">test</a>"#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        nested {
+            args: func_args![
+                value: value!({"note": {"to": "Tove", "from": "Jani", "items": {"item": [1, 2.5, true]}, "empty": null}}),
+                include_declaration: false,
+            ],
+            want: Ok("<note><empty/><from>Jani</from><items><item>1</item><item>2.5</item><item>true</item></items><to>Tove</to></note>"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        root {
+            args: func_args![
+                value: value!({"@xmlns:soap": "http://www.w3.org/2003/05/soap-envelope/", "Body": {"GetPrice": {"Item": "Apples"}}, "Header": {}}),
+                root: "soap:Envelope",
+                include_declaration: false,
+            ],
+            want: Ok(r#"<soap:Envelope xmlns:soap="http://www.w3.org/2003/05/soap-envelope/"><Body><GetPrice><Item>Apples</Item></GetPrice></Body><Header/></soap:Envelope>"#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        escaping {
+            args: func_args![value: value!({"a": {"@title": r#"say "hi""#, "text": "1 < 2 & 3 > 2"}}), include_declaration: false],
+            want: Ok(r#"<a title="say &quot;hi&quot;">1 &lt; 2 &amp; 3 &gt; 2</a>"#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        timestamp {
+            args: func_args![value: value!({"at": (Utc.ymd(2021, 2, 11).and_hms(16, 0, 0))}), include_declaration: false],
+            want: Ok("<at>2021-02-11T16:00:00Z</at>"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        ambiguous_root {
+            args: func_args![value: value!({"a": 1, "b": 2})],
+            want: Err("object must have exactly one key to use as the root element, or set `root`"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        array_root {
+            args: func_args![value: value!({"a": [1, 2]})],
+            want: Err(r#"root element "a" can't be an array"#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid_name {
+            args: func_args![value: value!({"a": {"1b": 2}})],
+            want: Err(r#"invalid element name "1b""#),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
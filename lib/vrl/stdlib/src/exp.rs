@@ -0,0 +1,89 @@
+use vrl::prelude::*;
+
+use crate::util::finite_float;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Exp;
+
+impl Function for Exp {
+    fn identifier(&self) -> &'static str {
+        "exp"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "exponential",
+            source: "exp!(0)",
+            result: Ok("1.0"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ExpFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ExpFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ExpFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = f64::try_from(&self.value.resolve(ctx)?)?;
+
+        Ok(finite_float(value.exp())?)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        exp => Exp;
+
+        zero {
+            args: func_args![value: 0],
+            want: Ok(1.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        one {
+            args: func_args![value: 1.0],
+            want: Ok(std::f64::consts::E),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        negative {
+            args: func_args![value: -1000],
+            want: Ok(0.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        overflow {
+            args: func_args![value: 1000],
+            want: Err("result is too large to be a float"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+    ];
+}
@@ -13,7 +13,7 @@ impl Function for FormatNumber {
         &[
             Parameter {
                 keyword: "value",
-                kind: kind::INTEGER | kind::FLOAT,
+                kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
                 required: true,
             },
             Parameter {
@@ -75,10 +75,11 @@ impl Expression for FormatNumberFn {
         let value: Decimal = match self.value.resolve(ctx)? {
             Value::Integer(v) => v.into(),
             Value::Float(v) => Decimal::from_f64(*v).expect("not NaN"),
+            Value::Decimal(v) => v,
             value => {
                 return Err(value::Error::Expected {
                     got: value.kind(),
-                    expected: Kind::Integer | Kind::Float,
+                    expected: Kind::Integer | Kind::Float | Kind::Decimal,
                 }
                 .into())
             }
@@ -0,0 +1,113 @@
+use vrl::prelude::*;
+
+use crate::util::syslog_facility_code;
+
+#[derive(Clone, Copy, Debug)]
+pub struct FromSyslogFacility;
+
+impl Function for FromSyslogFacility {
+    fn identifier(&self) -> &'static str {
+        "from_syslog_facility"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid",
+                source: "from_syslog_facility!(s'local7')",
+                result: Ok("23"),
+            },
+            Example {
+                title: "invalid",
+                source: "from_syslog_facility!(s'foobar')",
+                result: Err(
+                    r#"function call error for "from_syslog_facility" at (0:32): syslog facility foobar not valid"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(FromSyslogFacilityFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FromSyslogFacilityFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for FromSyslogFacilityFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let facility = self.value.resolve(ctx)?;
+        let facility = facility.try_bytes_utf8_lossy()?;
+
+        syslog_facility_code(&facility)
+            .map(Into::into)
+            .ok_or_else(|| format!("syslog facility {} not valid", facility).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().integer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        from_syslog_facility => FromSyslogFacility;
+
+        kern {
+            args: func_args![value: value!("kern")],
+            want: Ok(value!(0)),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        authpriv {
+            args: func_args![value: value!("authpriv")],
+            want: Ok(value!(10)),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        solaris_cron {
+            args: func_args![value: value!("solaris-cron")],
+            want: Ok(value!(15)),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        local7 {
+            args: func_args![value: value!("local7")],
+            want: Ok(value!(23)),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        invalid_facility {
+            args: func_args![value: value!("local8")],
+            want: Err("syslog facility local8 not valid"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        invalid_facility_non_string {
+            args: func_args![value: value!(3)],
+            want: Err(r#"expected "string", got "integer""#),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+    ];
+}
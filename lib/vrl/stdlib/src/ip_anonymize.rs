@@ -0,0 +1,267 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+use sha_2::{Digest, Sha256};
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Mode {
+    Zero,
+    Hash,
+}
+
+impl Mode {
+    fn all_value() -> Vec<Value> {
+        vec!["zero", "hash"].into_iter().map(Value::from).collect()
+    }
+}
+
+impl FromStr for Mode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "zero" => Ok(Self::Zero),
+            "hash" => Ok(Self::Hash),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpAnonymize;
+
+impl Function for IpAnonymize {
+    fn identifier(&self) -> &'static str {
+        "ip_anonymize"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "mode",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "ipv4_prefix",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "ipv6_prefix",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "ipv4",
+                source: r#"ip_anonymize!("192.168.10.32")"#,
+                result: Ok("192.168.10.0"),
+            },
+            Example {
+                title: "ipv6",
+                source: r#"ip_anonymize!("2001:4f8:3:ba:2e0:81ff:fe22:d1f1")"#,
+                result: Ok("2001:4f8:3::"),
+            },
+            Example {
+                title: "custom prefix",
+                source: r#"ip_anonymize!("192.168.10.32", ipv4_prefix: 16)"#,
+                result: Ok("192.168.0.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let mode = arguments
+            .optional_enum("mode", &Mode::all_value())?
+            .map(|mode| {
+                mode.try_bytes_utf8_lossy()
+                    .map(|s| Mode::from_str(&s).expect("validated enum"))
+                    .expect("mode not bytes")
+            })
+            .unwrap_or(Mode::Zero);
+        let ipv4_prefix = arguments.optional("ipv4_prefix").unwrap_or(expr!(24));
+        let ipv6_prefix = arguments.optional("ipv6_prefix").unwrap_or(expr!(48));
+        let key = arguments.optional("key");
+
+        Ok(Box::new(IpAnonymizeFn {
+            value,
+            mode,
+            ipv4_prefix,
+            ipv6_prefix,
+            key,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpAnonymizeFn {
+    value: Box<dyn Expression>,
+    mode: Mode,
+    ipv4_prefix: Box<dyn Expression>,
+    ipv6_prefix: Box<dyn Expression>,
+    key: Option<Box<dyn Expression>>,
+}
+
+impl Expression for IpAnonymizeFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value: IpAddr = self
+            .value
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .parse()
+            .map_err(|err| format!("unable to parse IP address: {}", err))?;
+
+        let hash = match self.mode {
+            Mode::Zero => None,
+            Mode::Hash => {
+                let mut hasher = Sha256::new();
+                if let Some(key) = &self.key {
+                    hasher.update(key.resolve(ctx)?.try_bytes()?);
+                }
+                match value {
+                    IpAddr::V4(addr) => hasher.update(addr.octets()),
+                    IpAddr::V6(addr) => hasher.update(addr.octets()),
+                }
+                Some(hasher.finalize())
+            }
+        };
+
+        let anonymized = match value {
+            IpAddr::V4(addr) => {
+                let prefix = self.ipv4_prefix.resolve(ctx)?.try_integer()?;
+                if !(0..=32).contains(&prefix) {
+                    return Err("ipv4_prefix must be between 0 and 32".into());
+                }
+
+                let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+                let host = hash.map_or(0, |hash| {
+                    u32::from_be_bytes(hash[..4].try_into().expect("hash is 32 bytes"))
+                });
+                IpAddr::V4(Ipv4Addr::from((u32::from(addr) & mask) | (host & !mask)))
+            }
+            IpAddr::V6(addr) => {
+                let prefix = self.ipv6_prefix.resolve(ctx)?.try_integer()?;
+                if !(0..=128).contains(&prefix) {
+                    return Err("ipv6_prefix must be between 0 and 128".into());
+                }
+
+                let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+                let host = hash.map_or(0, |hash| {
+                    u128::from_be_bytes(hash[..16].try_into().expect("hash is 32 bytes"))
+                });
+                IpAddr::V6(Ipv6Addr::from((u128::from(addr) & mask) | (host & !mask)))
+            }
+        };
+
+        Ok(anonymized.to_string().into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function! [
+        ip_anonymize => IpAnonymize;
+
+        ipv4 {
+            args: func_args![value: "192.168.10.32"],
+            want: Ok(value!("192.168.10.0")),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        ipv4_prefix {
+            args: func_args![value: "192.168.10.32", ipv4_prefix: 20],
+            want: Ok(value!("192.168.0.0")),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        ipv4_full_prefix {
+            args: func_args![value: "192.168.10.32", ipv4_prefix: 32],
+            want: Ok(value!("192.168.10.32")),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        ipv4_no_prefix {
+            args: func_args![value: "192.168.10.32", ipv4_prefix: 0],
+            want: Ok(value!("0.0.0.0")),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        ipv6 {
+            args: func_args![value: "2001:4f8:3:ba:2e0:81ff:fe22:d1f1"],
+            want: Ok(value!("2001:4f8:3::")),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        ipv6_prefix {
+            args: func_args![value: "2001:4f8:3:ba:2e0:81ff:fe22:d1f1", ipv6_prefix: 64],
+            want: Ok(value!("2001:4f8:3:ba::")),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        hash {
+            args: func_args![value: "192.168.10.32", mode: "hash"],
+            want: Ok(value!("192.168.10.174")),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        hash_other_host {
+            args: func_args![value: "192.168.10.33", mode: "hash"],
+            want: Ok(value!("192.168.10.9")),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        hash_key {
+            args: func_args![value: "192.168.10.32", mode: "hash", key: "secret"],
+            want: Ok(value!("192.168.10.158")),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        hash_ipv6 {
+            args: func_args![value: "2001:4f8:3:ba:2e0:81ff:fe22:d1f1", mode: "hash"],
+            want: Ok(value!("2001:4f8:3:d988:3536:42b5:49ce:83f")),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid_prefix {
+            args: func_args![value: "192.168.10.32", ipv4_prefix: 33],
+            want: Err("ipv4_prefix must be between 0 and 32"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid_address {
+            args: func_args![value: "INVALID"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
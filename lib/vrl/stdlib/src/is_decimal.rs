@@ -0,0 +1,96 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsDecimal;
+
+impl Function for IsDecimal {
+    fn identifier(&self) -> &'static str {
+        "is_decimal"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ANY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "decimal",
+                source: r#"is_decimal(to_decimal!("0.577"))"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "float",
+                source: r#"is_decimal(0.577)"#,
+                result: Ok("false"),
+            },
+            Example {
+                title: "boolean",
+                source: r#"is_decimal(true)"#,
+                result: Ok("false"),
+            },
+            Example {
+                title: "null",
+                source: r#"is_decimal(null)"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(IsDecimalFn { value }))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct IsDecimalFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for IsDecimalFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.value.resolve(ctx).map(|v| value!(v.is_decimal()))
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().boolean()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        is_decimal => IsDecimal;
+
+        bytes {
+            args: func_args![value: value!("foobar")],
+            want: Ok(value!(false)),
+            tdef: TypeDef::new().infallible().boolean(),
+        }
+
+        float {
+            args: func_args![value: value!(0.577)],
+            want: Ok(value!(false)),
+            tdef: TypeDef::new().infallible().boolean(),
+        }
+
+        decimal {
+            args: func_args![value: Value::Decimal(12.into())],
+            want: Ok(value!(true)),
+            tdef: TypeDef::new().infallible().boolean(),
+        }
+    ];
+}
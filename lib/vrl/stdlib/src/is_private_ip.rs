@@ -0,0 +1,169 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsPrivateIp;
+
+impl Function for IsPrivateIp {
+    fn identifier(&self) -> &'static str {
+        "is_private_ip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "private",
+                source: r#"is_private_ip!("192.168.10.32")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "public",
+                source: r#"is_private_ip!("8.8.8.8")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(IsPrivateIpFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IsPrivateIpFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for IsPrivateIpFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value: IpAddr = self
+            .value
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .parse()
+            .map_err(|err| format!("unable to parse IP address: {}", err))?;
+
+        Ok(match value {
+            IpAddr::V4(addr) => is_private_ipv4(addr),
+            IpAddr::V6(addr) => is_private_ipv6(addr),
+        }
+        .into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().boolean()
+    }
+}
+
+/// Addresses that aren't routable on the internet: the RFC 1918 private ranges, the RFC 6598
+/// shared address space used for carrier-grade NAT, and loopback and link local addresses.
+fn is_private_ipv4(addr: Ipv4Addr) -> bool {
+    let octets = addr.octets();
+    addr.is_private()
+        || addr.is_loopback()
+        || addr.is_link_local()
+        || (octets[0] == 100 && (octets[1] & 0b1100_0000) == 64)
+}
+
+/// Unique local (`fc00::/7`), link local (`fe80::/10`) and loopback addresses, as well as IPv4
+/// mapped addresses of private IPv4 addresses.
+fn is_private_ipv6(addr: Ipv6Addr) -> bool {
+    let segments = addr.segments();
+    addr.is_loopback()
+        || (segments[0] & 0xfe00) == 0xfc00
+        || (segments[0] & 0xffc0) == 0xfe80
+        || match segments {
+            [0, 0, 0, 0, 0, 0xffff, ..] => is_private_ipv4(Ipv4Addr::new(
+                (segments[6] >> 8) as u8,
+                segments[6] as u8,
+                (segments[7] >> 8) as u8,
+                segments[7] as u8,
+            )),
+            _ => false,
+        }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function! [
+        is_private_ip => IsPrivateIp;
+
+        rfc1918 {
+            args: func_args![value: "172.16.4.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        shared_address_space {
+            args: func_args![value: "100.64.0.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        loopback {
+            args: func_args![value: "127.0.0.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        link_local {
+            args: func_args![value: "169.254.169.254"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        public {
+            args: func_args![value: "172.32.0.1"],
+            want: Ok(value!(false)),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        ipv6_unique_local {
+            args: func_args![value: "fd12:3456:789a:1::1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        ipv6_link_local {
+            args: func_args![value: "fe80::1ff:fe23:4567:890a"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        ipv6_mapped {
+            args: func_args![value: "::ffff:10.0.0.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        ipv6_public {
+            args: func_args![value: "2001:4f8:3:ba:2e0:81ff:fe22:d1f1"],
+            want: Ok(value!(false)),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        invalid {
+            args: func_args![value: "INVALID"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+    ];
+}
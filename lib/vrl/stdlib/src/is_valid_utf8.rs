@@ -0,0 +1,88 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsValidUtf8;
+
+impl Function for IsValidUtf8 {
+    fn identifier(&self) -> &'static str {
+        "is_valid_utf8"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid",
+                source: r#"is_valid_utf8("foobar")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "invalid",
+                source: r#"is_valid_utf8(decode_base64!("3q2+7w=="))"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(IsValidUtf8Fn { value }))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct IsValidUtf8Fn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for IsValidUtf8Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_bytes()?;
+
+        Ok(std::str::from_utf8(&value).is_ok().into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().boolean()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        is_valid_utf8 => IsValidUtf8;
+
+        valid {
+            args: func_args![value: value!("foobar")],
+            want: Ok(value!(true)),
+            tdef: TypeDef::new().infallible().boolean(),
+        }
+
+        invalid {
+            args: func_args![value: Bytes::from_static(b"\xde\xad\xbe\xef")],
+            want: Ok(value!(false)),
+            tdef: TypeDef::new().infallible().boolean(),
+        }
+
+        truncated {
+            args: func_args![value: Bytes::from_static(b"caf\xc3")],
+            want: Ok(value!(false)),
+            tdef: TypeDef::new().infallible().boolean(),
+        }
+    ];
+}
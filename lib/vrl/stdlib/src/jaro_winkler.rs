@@ -0,0 +1,99 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct JaroWinkler;
+
+impl Function for JaroWinkler {
+    fn identifier(&self) -> &'static str {
+        "jaro_winkler"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "jaro winkler",
+            source: r#"round(jaro_winkler("martha", "marhta"), precision: 2)"#,
+            result: Ok("0.96"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(Box::new(JaroWinklerFn { value, other }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct JaroWinklerFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl Expression for JaroWinklerFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self
+            .value
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .into_owned();
+        let other = self
+            .other
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .into_owned();
+
+        Ok(strsim::jaro_winkler(&value, &other).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        jaro_winkler => JaroWinkler;
+
+        transposition {
+            args: func_args![value: "martha", other: "marhta"],
+            want: Ok(0.9611111111111111),
+            tdef: TypeDef::new().infallible().float(),
+        }
+
+        equal {
+            args: func_args![value: "vector.dev", other: "vector.dev"],
+            want: Ok(1.0),
+            tdef: TypeDef::new().infallible().float(),
+        }
+
+        nothing_in_common {
+            args: func_args![value: "abc", other: "xyz"],
+            want: Ok(0.0),
+            tdef: TypeDef::new().infallible().float(),
+        }
+    ];
+}
@@ -0,0 +1,1533 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct JqSubset;
+
+impl Function for JqSubset {
+    fn identifier(&self) -> &'static str {
+        "jq_subset"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "program",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "filter and reshape",
+                source: r#"jq_subset!({"users": [{"name": "ana", "age": 31}, {"name": "bo", "age": 17}]}, ".users[] | select(.age >= 18) | {name, adult: true}")"#,
+                result: Ok(r#"[{ "adult": true, "name": "ana" }]"#),
+            },
+            Example {
+                title: "recursive descent",
+                source: r#"jq_subset!({"a": {"id": 1, "b": [{"id": 2}, {"c": 3}]}}, "[.. | .id? // empty]")"#,
+                result: Ok("[[1, 2]]"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        let program = arguments.required_literal("program")?.to_value();
+        let filter = program
+            .try_bytes_utf8_lossy()
+            .expect("jq program not bytes")
+            .parse::<Filter>()
+            .map_err(|error| vrl::function::Error::InvalidArgument {
+                keyword: "program",
+                value: program.clone(),
+                error,
+            })?;
+
+        Ok(Box::new(JqFn { value, filter }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct JqFn {
+    value: Box<dyn Expression>,
+    filter: Filter,
+}
+
+impl Expression for JqFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        Ok(self.filter.run(&value)?.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new()
+            .fallible()
+            .array_mapped::<(), Kind>(map! { (): Kind::all() })
+    }
+}
+
+/// A parsed jq program.
+///
+/// This is not a jq engine, but a limited subset of the jq language: paths (`.foo`, `.[0]`,
+/// `.[]`, `.[1:3]`, `..`), pipes, commas, `?`, `//`, arithmetic and comparison operators,
+/// `and`/`or`, array and object construction, `if`/`elif`/`else`, and the builtins listed in
+/// [`BUILTINS`]. Anything else, such as variables, assignment operators, `reduce`/`foreach`,
+/// string interpolation, or user-defined functions, is rejected when the program is parsed.
+#[derive(Debug, Clone, PartialEq)]
+enum Filter {
+    Identity,
+    Recurse,
+    Literal(Value),
+    Index(Box<Filter>, Box<Filter>),
+    Slice(Box<Filter>, Option<Box<Filter>>, Option<Box<Filter>>),
+    Iterate(Box<Filter>),
+    Try(Box<Filter>),
+    Pipe(Box<Filter>, Box<Filter>),
+    Comma(Box<Filter>, Box<Filter>),
+    Alternative(Box<Filter>, Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Binary(Op, Box<Filter>, Box<Filter>),
+    Negate(Box<Filter>),
+    Array(Option<Box<Filter>>),
+    Object(Vec<(Filter, Filter)>),
+    If(Vec<(Filter, Filter)>, Box<Filter>),
+    Call(&'static str, Vec<Filter>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// The supported builtin functions, and their number of arguments.
+const BUILTINS: &[(&str, usize)] = &[
+    ("add", 0),
+    ("all", 0),
+    ("any", 0),
+    ("ascii_downcase", 0),
+    ("ascii_upcase", 0),
+    ("empty", 0),
+    ("endswith", 1),
+    ("first", 0),
+    ("flatten", 0),
+    ("from_entries", 0),
+    ("has", 1),
+    ("join", 1),
+    ("keys", 0),
+    ("last", 0),
+    ("length", 0),
+    ("map", 1),
+    ("map_values", 1),
+    ("max", 0),
+    ("min", 0),
+    ("not", 0),
+    ("recurse", 0),
+    ("reverse", 0),
+    ("select", 1),
+    ("sort", 0),
+    ("sort_by", 1),
+    ("split", 1),
+    ("startswith", 1),
+    ("to_entries", 0),
+    ("tonumber", 0),
+    ("tostring", 0),
+    ("type", 0),
+    ("unique", 0),
+    ("with_entries", 1),
+];
+
+const KEYWORDS: &[&str] = &["and", "elif", "else", "end", "if", "or", "then"];
+
+/// The error for jq keywords outside of the supported subset.
+fn unsupported_keyword(keyword: &str) -> Option<&'static str> {
+    let error = match keyword {
+        "as" => "jq variable bindings aren't supported",
+        "def" => "jq function definitions aren't supported",
+        "reduce" | "foreach" => "jq `reduce` and `foreach` aren't supported",
+        "try" | "catch" => "jq `try`/`catch` isn't supported, use `?` instead",
+        "label" | "break" => "jq `label`/`break` aren't supported",
+        "import" | "include" => "jq modules aren't supported",
+        _ => return None,
+    };
+    Some(error)
+}
+
+const ASSIGNMENT_OPERATORS: &[&str] = &["|=", "+=", "-=", "*=", "/=", "%=", "//="];
+
+// ----------------------------------------------------------------------------
+// lexer
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Dot,
+    DotDot,
+    Field(String),
+    Ident(String),
+    Literal(Value),
+    Punct(&'static str),
+}
+
+const PUNCTUATION: &[&str] = &[
+    "==", "!=", "<=", ">=", "//", "|", ",", "(", ")", "[", "]", "{", "}", ":", ";", "?", "+", "-",
+    "*", "/", "%", "<", ">",
+];
+
+fn is_ident_start(c: char) -> bool {
+    c.is_ascii_alphabetic() || c == '_'
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn lex(mut s: &str) -> std::result::Result<Vec<Token>, &'static str> {
+    let mut tokens = Vec::new();
+
+    loop {
+        s = s.trim_start();
+        let c = match s.chars().next() {
+            Some(c) => c,
+            None => return Ok(tokens),
+        };
+
+        if c == '#' {
+            s = s.find('\n').map_or("", |end| &s[end..]);
+        } else if let Some(rest) = s.strip_prefix("..") {
+            tokens.push(Token::DotDot);
+            s = rest;
+        } else if let Some(rest) = s.strip_prefix('.') {
+            if rest.starts_with(is_ident_start) {
+                let end = rest
+                    .find(|c: char| !is_ident(c))
+                    .unwrap_or_else(|| rest.len());
+                tokens.push(Token::Field(rest[..end].to_owned()));
+                s = &rest[end..];
+            } else {
+                tokens.push(Token::Dot);
+                s = rest;
+            }
+        } else if is_ident_start(c) {
+            let end = s.find(|c: char| !is_ident(c)).unwrap_or_else(|| s.len());
+            tokens.push(Token::Ident(s[..end].to_owned()));
+            s = &s[end..];
+        } else if c.is_ascii_digit() {
+            let (number, rest) = lex_number(s)?;
+            tokens.push(Token::Literal(number));
+            s = rest;
+        } else if c == '"' {
+            let (string, rest) = lex_string(&s[1..])?;
+            tokens.push(Token::Literal(string.into()));
+            s = rest;
+        } else if c == '$' {
+            return Err("jq variables aren't supported");
+        } else if c == '@' {
+            return Err("jq formats such as `@base64` aren't supported");
+        } else if ASSIGNMENT_OPERATORS.iter().any(|op| s.starts_with(op))
+            || (c == '=' && !s.starts_with("=="))
+        {
+            return Err("jq assignment operators aren't supported");
+        } else if let Some(punct) = PUNCTUATION.iter().find(|p| s.starts_with(*p)) {
+            tokens.push(Token::Punct(*punct));
+            s = &s[punct.len()..];
+        } else {
+            return Err("unexpected character in jq program");
+        }
+    }
+}
+
+fn lex_number(s: &str) -> std::result::Result<(Value, &str), &'static str> {
+    let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+
+    let mut end = digits(s);
+    let mut float = false;
+
+    if s[end..].starts_with('.') {
+        float = true;
+        end += 1;
+        end += digits(&s[end..]);
+    }
+    if s[end..].starts_with(|c: char| c == 'e' || c == 'E') {
+        float = true;
+        end += 1;
+        if s[end..].starts_with(|c: char| c == '+' || c == '-') {
+            end += 1;
+        }
+        end += digits(&s[end..]);
+    }
+
+    let number = &s[..end];
+    let value = match number.parse::<i64>() {
+        Ok(int) if !float => int.into(),
+        _ => number
+            .parse::<f64>()
+            .map_err(|_| "invalid number in jq program")?
+            .into(),
+    };
+
+    Ok((value, &s[end..]))
+}
+
+fn lex_string(s: &str) -> std::result::Result<(String, &str), &'static str> {
+    let mut string = String::new();
+    let mut chars = s.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((string, &s[i + 1..])),
+            '\\' => match chars.next().map(|(_, c)| c) {
+                Some('"') => string.push('"'),
+                Some('\\') => string.push('\\'),
+                Some('/') => string.push('/'),
+                Some('n') => string.push('\n'),
+                Some('r') => string.push('\r'),
+                Some('t') => string.push('\t'),
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                    let c = u32::from_str_radix(&hex, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or("invalid unicode escape in jq string")?;
+                    string.push(c);
+                }
+                Some('(') => return Err("jq string interpolation isn't supported"),
+                _ => return Err("invalid escape in jq string"),
+            },
+            c => string.push(c),
+        }
+    }
+
+    Err("unterminated string in jq program")
+}
+
+// ----------------------------------------------------------------------------
+// parser
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl std::str::FromStr for Filter {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: lex(s)?,
+            pos: 0,
+        };
+
+        let filter = parser.pipe()?;
+        match parser.peek() {
+            None => Ok(filter),
+            Some(Token::Ident(ident)) => {
+                Err(unsupported_keyword(ident).unwrap_or("unexpected token in jq program"))
+            }
+            Some(_) => Err("unexpected token in jq program"),
+        }
+    }
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat(&mut self, punct: &str) -> bool {
+        match self.peek() {
+            Some(Token::Punct(p)) if *p == punct => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(ident)) if ident == keyword => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect(
+        &mut self,
+        punct: &str,
+        error: &'static str,
+    ) -> std::result::Result<(), &'static str> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(error)
+        }
+    }
+
+    fn pipe(&mut self) -> std::result::Result<Filter, &'static str> {
+        let lhs = self.comma()?;
+
+        if self.eat("|") {
+            Ok(Filter::Pipe(Box::new(lhs), Box::new(self.pipe()?)))
+        } else {
+            Ok(lhs)
+        }
+    }
+
+    fn comma(&mut self) -> std::result::Result<Filter, &'static str> {
+        let mut lhs = self.alternative()?;
+
+        while self.eat(",") {
+            lhs = Filter::Comma(Box::new(lhs), Box::new(self.alternative()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn alternative(&mut self) -> std::result::Result<Filter, &'static str> {
+        let lhs = self.or()?;
+
+        if self.eat("//") {
+            Ok(Filter::Alternative(
+                Box::new(lhs),
+                Box::new(self.alternative()?),
+            ))
+        } else {
+            Ok(lhs)
+        }
+    }
+
+    fn or(&mut self) -> std::result::Result<Filter, &'static str> {
+        let mut lhs = self.and()?;
+
+        while self.eat_keyword("or") {
+            lhs = Filter::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> std::result::Result<Filter, &'static str> {
+        let mut lhs = self.comparison()?;
+
+        while self.eat_keyword("and") {
+            lhs = Filter::And(Box::new(lhs), Box::new(self.comparison()?));
+        }
+
+        Ok(lhs)
+    }
+
+    fn comparison(&mut self) -> std::result::Result<Filter, &'static str> {
+        let lhs = self.additive()?;
+
+        let op = match self.peek() {
+            Some(Token::Punct("==")) => Op::Eq,
+            Some(Token::Punct("!=")) => Op::Ne,
+            Some(Token::Punct("<")) => Op::Lt,
+            Some(Token::Punct("<=")) => Op::Le,
+            Some(Token::Punct(">")) => Op::Gt,
+            Some(Token::Punct(">=")) => Op::Ge,
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+
+        Ok(Filter::Binary(
+            op,
+            Box::new(lhs),
+            Box::new(self.additive()?),
+        ))
+    }
+
+    fn additive(&mut self) -> std::result::Result<Filter, &'static str> {
+        let mut lhs = self.multiplicative()?;
+
+        loop {
+            let op = if self.eat("+") {
+                Op::Add
+            } else if self.eat("-") {
+                Op::Sub
+            } else {
+                return Ok(lhs);
+            };
+            lhs = Filter::Binary(op, Box::new(lhs), Box::new(self.multiplicative()?));
+        }
+    }
+
+    fn multiplicative(&mut self) -> std::result::Result<Filter, &'static str> {
+        let mut lhs = self.postfix()?;
+
+        loop {
+            let op = if self.eat("*") {
+                Op::Mul
+            } else if self.eat("/") {
+                Op::Div
+            } else if self.eat("%") {
+                Op::Rem
+            } else {
+                return Ok(lhs);
+            };
+            lhs = Filter::Binary(op, Box::new(lhs), Box::new(self.postfix()?));
+        }
+    }
+
+    fn postfix(&mut self) -> std::result::Result<Filter, &'static str> {
+        let mut term = self.primary()?;
+
+        loop {
+            term = match self.peek() {
+                Some(Token::Field(field)) => {
+                    let field = Filter::Literal(field.as_str().into());
+                    self.pos += 1;
+                    Filter::Index(Box::new(term), Box::new(field))
+                }
+                Some(Token::Dot) => match self.tokens.get(self.pos + 1) {
+                    Some(Token::Literal(key @ Value::Bytes(_))) => {
+                        let key = Filter::Literal(key.clone());
+                        self.pos += 2;
+                        Filter::Index(Box::new(term), Box::new(key))
+                    }
+                    _ => return Ok(term),
+                },
+                Some(Token::Punct("[")) => {
+                    self.pos += 1;
+                    self.brackets(term)?
+                }
+                Some(Token::Punct("?")) => {
+                    self.pos += 1;
+                    Filter::Try(Box::new(term))
+                }
+                _ => return Ok(term),
+            };
+        }
+    }
+
+    /// Parse the remainder of `[]`, `[index]`, or `[from:to]` after the opening bracket.
+    fn brackets(&mut self, term: Filter) -> std::result::Result<Filter, &'static str> {
+        let term = Box::new(term);
+
+        if self.eat("]") {
+            return Ok(Filter::Iterate(term));
+        }
+
+        let from = if self.eat(":") {
+            None
+        } else {
+            let index = self.pipe()?;
+            if !self.eat(":") {
+                self.expect("]", "expected `]` in jq program")?;
+                return Ok(Filter::Index(term, Box::new(index)));
+            }
+            Some(Box::new(index))
+        };
+
+        let to = if self.eat("]") {
+            None
+        } else {
+            let to = self.pipe()?;
+            self.expect("]", "expected `]` in jq program")?;
+            Some(Box::new(to))
+        };
+
+        if from.is_none() && to.is_none() {
+            return Err("jq slices need at least one bound");
+        }
+
+        Ok(Filter::Slice(term, from, to))
+    }
+
+    fn primary(&mut self) -> std::result::Result<Filter, &'static str> {
+        let filter = match self.next().ok_or("unexpected end of jq program")? {
+            Token::Dot => match self.peek() {
+                Some(Token::Literal(key @ Value::Bytes(_))) => {
+                    let key = Filter::Literal(key.clone());
+                    self.pos += 1;
+                    Filter::Index(Box::new(Filter::Identity), Box::new(key))
+                }
+                _ => Filter::Identity,
+            },
+            Token::DotDot => Filter::Recurse,
+            Token::Field(field) => Filter::Index(
+                Box::new(Filter::Identity),
+                Box::new(Filter::Literal(field.into())),
+            ),
+            Token::Literal(value) => Filter::Literal(value),
+            Token::Punct("(") => {
+                let filter = self.pipe()?;
+                self.expect(")", "expected `)` in jq program")?;
+                filter
+            }
+            Token::Punct("[") if self.eat("]") => Filter::Array(None),
+            Token::Punct("[") => {
+                let filter = self.pipe()?;
+                self.expect("]", "expected `]` in jq program")?;
+                Filter::Array(Some(Box::new(filter)))
+            }
+            Token::Punct("{") => self.object()?,
+            Token::Punct("-") => Filter::Negate(Box::new(self.postfix()?)),
+            Token::Ident(ident) => match ident.as_str() {
+                "true" => Filter::Literal(true.into()),
+                "false" => Filter::Literal(false.into()),
+                "null" => Filter::Literal(Value::Null),
+                "if" => self.conditional()?,
+                ident if KEYWORDS.contains(&ident) => {
+                    return Err("unexpected keyword in jq program")
+                }
+                ident => match unsupported_keyword(ident) {
+                    Some(error) => return Err(error),
+                    None => self.call(ident)?,
+                },
+            },
+            Token::Punct(_) => return Err("unexpected token in jq program"),
+        };
+
+        Ok(filter)
+    }
+
+    fn call(&mut self, name: &str) -> std::result::Result<Filter, &'static str> {
+        let mut args = Vec::new();
+        if self.eat("(") {
+            loop {
+                args.push(self.pipe()?);
+                if self.eat(")") {
+                    break;
+                }
+                self.expect(";", "expected `;` or `)` in jq function call")?;
+            }
+        }
+
+        match BUILTINS
+            .iter()
+            .find(|(builtin, arity)| *builtin == name && *arity == args.len())
+        {
+            Some((name, _)) => Ok(Filter::Call(*name, args)),
+            None => Err("unknown jq function, only the builtins listed in the `jq_subset` docs are supported"),
+        }
+    }
+
+    fn object(&mut self) -> std::result::Result<Filter, &'static str> {
+        let mut entries = Vec::new();
+        if self.eat("}") {
+            return Ok(Filter::Object(entries));
+        }
+
+        loop {
+            let (key, shorthand) = match self.next() {
+                Some(Token::Ident(key)) => (Filter::Literal(key.into()), true),
+                Some(Token::Literal(key @ Value::Bytes(_))) => (Filter::Literal(key), true),
+                Some(Token::Punct("(")) => {
+                    let key = self.pipe()?;
+                    self.expect(")", "expected `)` in jq program")?;
+                    (key, false)
+                }
+                _ => return Err("expected an object key in jq program"),
+            };
+
+            let value = if self.eat(":") {
+                self.alternative()?
+            } else if shorthand {
+                // `{foo}` is short for `{foo: .foo}`.
+                Filter::Index(Box::new(Filter::Identity), Box::new(key.clone()))
+            } else {
+                return Err("expected `:` in jq object");
+            };
+            entries.push((key, value));
+
+            if self.eat("}") {
+                return Ok(Filter::Object(entries));
+            }
+            self.expect(",", "expected `,` or `}` in jq object")?;
+        }
+    }
+
+    fn conditional(&mut self) -> std::result::Result<Filter, &'static str> {
+        let mut branches = Vec::new();
+
+        loop {
+            let condition = self.pipe()?;
+            if !self.eat_keyword("then") {
+                return Err("expected `then` in jq program");
+            }
+            branches.push((condition, self.pipe()?));
+
+            if self.eat_keyword("elif") {
+                continue;
+            }
+
+            let otherwise = if self.eat_keyword("else") {
+                self.pipe()?
+            } else {
+                Filter::Identity
+            };
+            if !self.eat_keyword("end") {
+                return Err("expected `end` in jq program");
+            }
+
+            return Ok(Filter::If(branches, Box::new(otherwise)));
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// interpreter
+
+type Output = std::result::Result<Vec<Value>, String>;
+
+impl Filter {
+    /// Run the program against `input`, returning all of its outputs.
+    fn run(&self, input: &Value) -> Output {
+        match self {
+            Filter::Identity => Ok(vec![input.clone()]),
+            Filter::Recurse => {
+                let mut outputs = Vec::new();
+                recurse(input, &mut outputs);
+                Ok(outputs)
+            }
+            Filter::Literal(value) => Ok(vec![value.clone()]),
+            Filter::Index(term, index) => {
+                let targets = term.run(input)?;
+                let mut outputs = Vec::new();
+                for index in index.run(input)? {
+                    for target in &targets {
+                        outputs.push(self::index(target, &index)?);
+                    }
+                }
+                Ok(outputs)
+            }
+            Filter::Slice(term, from, to) => {
+                let targets = term.run(input)?;
+                let froms = from
+                    .as_ref()
+                    .map_or(Ok(vec![Value::Null]), |from| from.run(input))?;
+                let tos = to
+                    .as_ref()
+                    .map_or(Ok(vec![Value::Null]), |to| to.run(input))?;
+                let mut outputs = Vec::new();
+                for to in &tos {
+                    for from in &froms {
+                        for target in &targets {
+                            outputs.push(slice(target, from, to)?);
+                        }
+                    }
+                }
+                Ok(outputs)
+            }
+            Filter::Iterate(term) => {
+                let mut outputs = Vec::new();
+                for value in term.run(input)? {
+                    outputs.extend(iterate(value)?);
+                }
+                Ok(outputs)
+            }
+            Filter::Try(term) => Ok(term.run(input).unwrap_or_default()),
+            Filter::Pipe(lhs, rhs) => {
+                let mut outputs = Vec::new();
+                for value in lhs.run(input)? {
+                    outputs.extend(rhs.run(&value)?);
+                }
+                Ok(outputs)
+            }
+            Filter::Comma(lhs, rhs) => {
+                let mut outputs = lhs.run(input)?;
+                outputs.extend(rhs.run(input)?);
+                Ok(outputs)
+            }
+            Filter::Alternative(lhs, rhs) => {
+                let outputs: Vec<_> = lhs
+                    .run(input)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(is_truthy)
+                    .collect();
+                if outputs.is_empty() {
+                    rhs.run(input)
+                } else {
+                    Ok(outputs)
+                }
+            }
+            Filter::And(lhs, rhs) => {
+                let mut outputs = Vec::new();
+                for value in lhs.run(input)? {
+                    if is_truthy(&value) {
+                        outputs.extend(rhs.run(input)?.iter().map(|v| is_truthy(v).into()));
+                    } else {
+                        outputs.push(false.into());
+                    }
+                }
+                Ok(outputs)
+            }
+            Filter::Or(lhs, rhs) => {
+                let mut outputs = Vec::new();
+                for value in lhs.run(input)? {
+                    if is_truthy(&value) {
+                        outputs.push(true.into());
+                    } else {
+                        outputs.extend(rhs.run(input)?.iter().map(|v| is_truthy(v).into()));
+                    }
+                }
+                Ok(outputs)
+            }
+            Filter::Binary(op, lhs, rhs) => {
+                let lhs = lhs.run(input)?;
+                let mut outputs = Vec::new();
+                for rhs in rhs.run(input)? {
+                    for lhs in &lhs {
+                        outputs.push(binary(*op, lhs, &rhs)?);
+                    }
+                }
+                Ok(outputs)
+            }
+            Filter::Negate(term) => term
+                .run(input)?
+                .into_iter()
+                .map(|value| match value {
+                    Value::Integer(int) => Ok(int
+                        .checked_neg()
+                        .map_or_else(|| (-(int as f64)).into(), Into::into)),
+                    Value::Float(float) => Ok((-float.into_inner()).into()),
+                    value => Err(format!("{} cannot be negated", type_name(&value))),
+                })
+                .collect(),
+            Filter::Array(None) => Ok(vec![Value::Array(vec![])]),
+            Filter::Array(Some(filter)) => Ok(vec![Value::Array(filter.run(input)?)]),
+            Filter::Object(entries) => {
+                let mut objects = vec![BTreeMap::new()];
+                for (key, value) in entries {
+                    let keys = key.run(input)?;
+                    let values = value.run(input)?;
+                    let mut next = Vec::with_capacity(objects.len() * keys.len() * values.len());
+                    for object in &objects {
+                        for key in &keys {
+                            let key = match key {
+                                Value::Bytes(key) => String::from_utf8_lossy(key).into_owned(),
+                                key => {
+                                    return Err(format!(
+                                        "object keys must be strings, got {}",
+                                        type_name(key)
+                                    ))
+                                }
+                            };
+                            for value in &values {
+                                let mut object = object.clone();
+                                object.insert(key.clone(), value.clone());
+                                next.push(object);
+                            }
+                        }
+                    }
+                    objects = next;
+                }
+                Ok(objects.into_iter().map(Value::Object).collect())
+            }
+            Filter::If(branches, otherwise) => conditional(branches, otherwise, input),
+            Filter::Call(name, args) => call(name, args, input),
+        }
+    }
+}
+
+fn conditional(branches: &[(Filter, Filter)], otherwise: &Filter, input: &Value) -> Output {
+    let (condition, then) = match branches.split_first() {
+        Some((branch, _)) => branch,
+        None => return otherwise.run(input),
+    };
+
+    let mut outputs = Vec::new();
+    for value in condition.run(input)? {
+        if is_truthy(&value) {
+            outputs.extend(then.run(input)?);
+        } else {
+            outputs.extend(conditional(&branches[1..], otherwise, input)?);
+        }
+    }
+
+    Ok(outputs)
+}
+
+fn call(name: &str, args: &[Filter], input: &Value) -> Output {
+    let output = match (name, args, input) {
+        ("empty", _, _) => return Ok(vec![]),
+        ("not", _, value) => (!is_truthy(value)).into(),
+        ("length", _, value) => match value {
+            Value::Null => 0.into(),
+            Value::Integer(int) => int.abs().into(),
+            Value::Float(float) => float.into_inner().abs().into(),
+            Value::Decimal(decimal) => decimal.abs().into(),
+            Value::Bytes(bytes) => String::from_utf8_lossy(bytes).chars().count().into(),
+            Value::Array(array) => array.len().into(),
+            Value::Object(object) => object.len().into(),
+            value => return Err(format!("{} has no length", type_name(value))),
+        },
+        ("keys", _, Value::Object(object)) => object
+            .keys()
+            .map(|key| Value::from(key.as_str()))
+            .collect::<Vec<_>>()
+            .into(),
+        ("keys", _, Value::Array(array)) => {
+            (0..array.len()).map(Value::from).collect::<Vec<_>>().into()
+        }
+        ("type", _, value) => type_name(value).into(),
+        ("add", _, value) => {
+            let mut sum = Value::Null;
+            for value in iterate(value.clone())? {
+                sum = binary(Op::Add, &sum, &value)?;
+            }
+            sum
+        }
+        ("any", _, value) => iterate(value.clone())?.iter().any(is_truthy).into(),
+        ("all", _, value) => iterate(value.clone())?.iter().all(is_truthy).into(),
+        ("to_entries", _, value) => to_entries(value)?,
+        ("from_entries", _, value) => from_entries(value)?,
+        ("with_entries", [filter], value) => {
+            let mut entries = Vec::new();
+            for entry in iterate(to_entries(value)?)? {
+                entries.extend(filter.run(&entry)?);
+            }
+            from_entries(&Value::Array(entries))?
+        }
+        ("select", [filter], value) => {
+            return Ok(filter
+                .run(value)?
+                .iter()
+                .filter(|condition| is_truthy(condition))
+                .map(|_| value.clone())
+                .collect())
+        }
+        ("map", [filter], value) => {
+            let mut outputs = Vec::new();
+            for value in iterate(value.clone())? {
+                outputs.extend(filter.run(&value)?);
+            }
+            outputs.into()
+        }
+        ("map_values", [filter], Value::Array(array)) => {
+            let mut outputs = Vec::with_capacity(array.len());
+            for value in array {
+                outputs.extend(filter.run(value)?.into_iter().next());
+            }
+            outputs.into()
+        }
+        ("map_values", [filter], Value::Object(object)) => {
+            let mut outputs = BTreeMap::new();
+            for (key, value) in object {
+                if let Some(value) = filter.run(value)?.into_iter().next() {
+                    outputs.insert(key.clone(), value);
+                }
+            }
+            outputs.into()
+        }
+        ("has", [key], value) => {
+            return key
+                .run(value)?
+                .iter()
+                .map(|key| match (value, key) {
+                    (Value::Object(object), Value::Bytes(key)) => {
+                        Ok(object.contains_key(&*String::from_utf8_lossy(key)).into())
+                    }
+                    (Value::Array(array), Value::Integer(index)) => {
+                        Ok((*index >= 0 && (*index as usize) < array.len()).into())
+                    }
+                    (value, key) => Err(format!(
+                        "cannot check whether {} has a {} key",
+                        type_name(value),
+                        type_name(key)
+                    )),
+                })
+                .collect()
+        }
+        ("tostring", _, Value::Bytes(bytes)) => Value::Bytes(bytes.clone()),
+        ("tostring", _, value) => serde_json::to_string(value)
+            .map_err(|err| err.to_string())?
+            .into(),
+        ("tonumber", _, Value::Integer(_) | Value::Float(_) | Value::Decimal(_)) => input.clone(),
+        ("tonumber", _, Value::Bytes(bytes)) => {
+            let string = String::from_utf8_lossy(bytes);
+            match string.parse::<i64>() {
+                Ok(int) => int.into(),
+                Err(_) => string
+                    .parse::<f64>()
+                    .map_err(|_| format!("cannot parse {:?} as a number", string))?
+                    .into(),
+            }
+        }
+        ("ascii_downcase", _, Value::Bytes(bytes)) => bytes.to_ascii_lowercase().into(),
+        ("ascii_upcase", _, Value::Bytes(bytes)) => bytes.to_ascii_uppercase().into(),
+        ("sort", _, Value::Array(array)) => {
+            let mut array = array.clone();
+            array.sort_by(compare);
+            array.into()
+        }
+        ("sort_by", [filter], Value::Array(array)) => {
+            let mut keyed = Vec::with_capacity(array.len());
+            for value in array {
+                keyed.push((Value::Array(filter.run(value)?), value.clone()));
+            }
+            keyed.sort_by(|(a, _), (b, _)| compare(a, b));
+            keyed
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect::<Vec<_>>()
+                .into()
+        }
+        ("unique", _, Value::Array(array)) => {
+            let mut array = array.clone();
+            array.sort_by(compare);
+            array.dedup_by(|a, b| compare(a, b) == Ordering::Equal);
+            array.into()
+        }
+        ("reverse", _, Value::Null) => Value::Array(vec![]),
+        ("reverse", _, Value::Array(array)) => {
+            array.iter().rev().cloned().collect::<Vec<_>>().into()
+        }
+        ("reverse", _, Value::Bytes(bytes)) => String::from_utf8_lossy(bytes)
+            .chars()
+            .rev()
+            .collect::<String>()
+            .into(),
+        ("first", _, Value::Array(array)) => array.first().cloned().unwrap_or(Value::Null),
+        ("last", _, Value::Array(array)) => array.last().cloned().unwrap_or(Value::Null),
+        ("min", _, Value::Array(array)) => array
+            .iter()
+            .min_by(|a, b| compare(a, b))
+            .cloned()
+            .unwrap_or(Value::Null),
+        ("max", _, Value::Array(array)) => array
+            .iter()
+            .max_by(|a, b| compare(a, b))
+            .cloned()
+            .unwrap_or(Value::Null),
+        ("flatten", _, Value::Array(array)) => {
+            let mut flat = Vec::with_capacity(array.len());
+            flatten(array, &mut flat);
+            flat.into()
+        }
+        ("join", [separator], Value::Array(array)) => {
+            return separator
+                .run(input)?
+                .iter()
+                .map(|separator| {
+                    let separator = match separator {
+                        Value::Bytes(bytes) => String::from_utf8_lossy(bytes),
+                        value => return Err(format!("cannot join with {}", type_name(value))),
+                    };
+                    let parts = array
+                        .iter()
+                        .map(|value| match value {
+                            Value::Null => Ok(String::new()),
+                            Value::Bytes(bytes) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+                            Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => {
+                                Ok(value.to_string())
+                            }
+                            value => Err(format!("cannot join {}", type_name(value))),
+                        })
+                        .collect::<std::result::Result<Vec<_>, _>>()?;
+                    Ok(parts.join(&separator).into())
+                })
+                .collect()
+        }
+        ("split", [separator], Value::Bytes(bytes)) => {
+            let string = String::from_utf8_lossy(bytes);
+            return separator
+                .run(input)?
+                .iter()
+                .map(|separator| match separator {
+                    Value::Bytes(separator) => Ok(string
+                        .split(&*String::from_utf8_lossy(separator))
+                        .map(Value::from)
+                        .collect::<Vec<_>>()
+                        .into()),
+                    value => Err(format!("cannot split with {}", type_name(value))),
+                })
+                .collect();
+        }
+        ("startswith" | "endswith", [affix], Value::Bytes(bytes)) => {
+            return affix
+                .run(input)?
+                .iter()
+                .map(|affix| match affix {
+                    Value::Bytes(affix) if name == "startswith" => {
+                        Ok(bytes.starts_with(affix).into())
+                    }
+                    Value::Bytes(affix) => Ok(bytes.ends_with(affix).into()),
+                    _ => Err(format!("{}() requires string inputs", name)),
+                })
+                .collect()
+        }
+        ("recurse", _, value) => {
+            let mut outputs = Vec::new();
+            recurse(value, &mut outputs);
+            return Ok(outputs);
+        }
+        (name, _, value) => {
+            return Err(format!("{} cannot be used with {}", name, type_name(value)))
+        }
+    };
+
+    Ok(vec![output])
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Boolean(_) => "boolean",
+        Value::Integer(_) | Value::Float(_) | Value::Decimal(_) => "number",
+        Value::Bytes(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+        Value::Timestamp(_) => "timestamp",
+        Value::Regex(_) => "regex",
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Boolean(false))
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Integer(int) => Some(*int as f64),
+        Value::Float(float) => Some(float.into_inner()),
+        Value::Decimal(_) => f64::try_from(value).ok(),
+        _ => None,
+    }
+}
+
+fn recurse(value: &Value, outputs: &mut Vec<Value>) {
+    outputs.push(value.clone());
+
+    match value {
+        Value::Array(array) => array.iter().for_each(|value| recurse(value, outputs)),
+        Value::Object(object) => object.values().for_each(|value| recurse(value, outputs)),
+        _ => {}
+    }
+}
+
+fn iterate(value: Value) -> Output {
+    match value {
+        Value::Array(array) => Ok(array),
+        Value::Object(object) => Ok(object.into_values().collect()),
+        value => Err(format!("cannot iterate over {}", type_name(&value))),
+    }
+}
+
+fn flatten(array: &[Value], flat: &mut Vec<Value>) {
+    for value in array {
+        match value {
+            Value::Array(array) => flatten(array, flat),
+            value => flat.push(value.clone()),
+        }
+    }
+}
+
+fn index(target: &Value, index: &Value) -> std::result::Result<Value, String> {
+    match (target, index) {
+        (Value::Null, Value::Bytes(_) | Value::Integer(_) | Value::Null) => Ok(Value::Null),
+        (Value::Object(object), Value::Bytes(key)) => Ok(object
+            .get(&*String::from_utf8_lossy(key))
+            .cloned()
+            .unwrap_or(Value::Null)),
+        (Value::Array(array), Value::Integer(index)) => {
+            let index = if *index < 0 {
+                array.len() as i64 + index
+            } else {
+                *index
+            };
+            Ok(usize::try_from(index)
+                .ok()
+                .and_then(|index| array.get(index))
+                .cloned()
+                .unwrap_or(Value::Null))
+        }
+        (Value::Array(_), Value::Float(index)) => {
+            self::index(target, &Value::from(index.into_inner().floor() as i64))
+        }
+        (target, Value::Bytes(key)) => Err(format!(
+            "cannot index {} with {:?}",
+            type_name(target),
+            String::from_utf8_lossy(key)
+        )),
+        (target, index) => Err(format!(
+            "cannot index {} with {}",
+            type_name(target),
+            type_name(index)
+        )),
+    }
+}
+
+fn slice(target: &Value, from: &Value, to: &Value) -> std::result::Result<Value, String> {
+    let bound = |value: &Value, len: usize, default: usize| -> std::result::Result<usize, String> {
+        match value {
+            Value::Null => Ok(default),
+            value => match as_f64(value) {
+                Some(bound) if bound < 0.0 => Ok((len as f64 + bound).max(0.0) as usize),
+                Some(bound) => Ok((bound as usize).min(len)),
+                None => Err(format!("cannot slice with {}", type_name(value))),
+            },
+        }
+    };
+
+    match target {
+        Value::Null => Ok(Value::Null),
+        Value::Array(array) => {
+            let from = bound(from, array.len(), 0)?;
+            let to = bound(to, array.len(), array.len())?.max(from);
+            Ok(array[from..to].to_vec().into())
+        }
+        Value::Bytes(bytes) => {
+            let chars: Vec<char> = String::from_utf8_lossy(bytes).chars().collect();
+            let from = bound(from, chars.len(), 0)?;
+            let to = bound(to, chars.len(), chars.len())?.max(from);
+            Ok(chars[from..to].iter().collect::<String>().into())
+        }
+        target => Err(format!("cannot slice {}", type_name(target))),
+    }
+}
+
+fn to_entries(value: &Value) -> std::result::Result<Value, String> {
+    match value {
+        Value::Object(object) => Ok(object
+            .iter()
+            .map(|(key, value)| {
+                let mut entry = BTreeMap::new();
+                entry.insert("key".to_owned(), Value::from(key.as_str()));
+                entry.insert("value".to_owned(), value.clone());
+                Value::Object(entry)
+            })
+            .collect::<Vec<_>>()
+            .into()),
+        value => Err(format!("{} has no keys", type_name(value))),
+    }
+}
+
+fn from_entries(value: &Value) -> std::result::Result<Value, String> {
+    let mut object = BTreeMap::new();
+
+    for entry in iterate(value.clone())? {
+        let entry = match entry {
+            Value::Object(entry) => entry,
+            entry => return Err(format!("cannot use {} as an entry", type_name(&entry))),
+        };
+
+        let key = ["key", "k", "name", "Name", "Key", "K"]
+            .iter()
+            .find_map(|field| entry.get(*field).filter(|key| is_truthy(key)));
+        let key = match key {
+            Some(Value::Bytes(key)) => String::from_utf8_lossy(key).into_owned(),
+            Some(key @ (Value::Integer(_) | Value::Float(_) | Value::Boolean(_))) => {
+                key.to_string()
+            }
+            _ => return Err("entries need a string key".to_owned()),
+        };
+        let value = ["value", "v", "Value", "V"]
+            .iter()
+            .find_map(|field| entry.get(*field))
+            .cloned()
+            .unwrap_or(Value::Null);
+
+        object.insert(key, value);
+    }
+
+    Ok(object.into())
+}
+
+fn binary(op: Op, lhs: &Value, rhs: &Value) -> std::result::Result<Value, String> {
+    let error = |verb: &str| {
+        format!(
+            "{} and {} cannot be {}",
+            type_name(lhs),
+            type_name(rhs),
+            verb
+        )
+    };
+
+    Ok(match op {
+        Op::Eq => (compare(lhs, rhs) == Ordering::Equal).into(),
+        Op::Ne => (compare(lhs, rhs) != Ordering::Equal).into(),
+        Op::Lt => (compare(lhs, rhs) == Ordering::Less).into(),
+        Op::Le => (compare(lhs, rhs) != Ordering::Greater).into(),
+        Op::Gt => (compare(lhs, rhs) == Ordering::Greater).into(),
+        Op::Ge => (compare(lhs, rhs) != Ordering::Less).into(),
+        Op::Add => match (lhs, rhs) {
+            (Value::Null, value) | (value, Value::Null) => value.clone(),
+            (Value::Integer(a), Value::Integer(b)) => a
+                .checked_add(*b)
+                .map_or_else(|| (*a as f64 + *b as f64).into(), Into::into),
+            (Value::Bytes(a), Value::Bytes(b)) => [&a[..], &b[..]].concat().into(),
+            (Value::Array(a), Value::Array(b)) => {
+                a.iter().chain(b).cloned().collect::<Vec<_>>().into()
+            }
+            (Value::Object(a), Value::Object(b)) => {
+                let mut merged = a.clone();
+                merged.extend(b.iter().map(|(k, v)| (k.clone(), v.clone())));
+                merged.into()
+            }
+            _ => match (as_f64(lhs), as_f64(rhs)) {
+                (Some(a), Some(b)) => (a + b).into(),
+                _ => return Err(error("added")),
+            },
+        },
+        Op::Sub => match (lhs, rhs) {
+            (Value::Integer(a), Value::Integer(b)) => a
+                .checked_sub(*b)
+                .map_or_else(|| (*a as f64 - *b as f64).into(), Into::into),
+            (Value::Array(a), Value::Array(b)) => a
+                .iter()
+                .filter(|a| !b.iter().any(|b| compare(a, b) == Ordering::Equal))
+                .cloned()
+                .collect::<Vec<_>>()
+                .into(),
+            _ => match (as_f64(lhs), as_f64(rhs)) {
+                (Some(a), Some(b)) => (a - b).into(),
+                _ => return Err(error("subtracted")),
+            },
+        },
+        Op::Mul => match (lhs, rhs) {
+            (Value::Integer(a), Value::Integer(b)) => a
+                .checked_mul(*b)
+                .map_or_else(|| (*a as f64 * *b as f64).into(), Into::into),
+            (Value::Object(a), Value::Object(b)) => deep_merge(a, b).into(),
+            _ => match (as_f64(lhs), as_f64(rhs)) {
+                (Some(a), Some(b)) => (a * b).into(),
+                _ => return Err(error("multiplied")),
+            },
+        },
+        Op::Div => match (lhs, rhs) {
+            (Value::Bytes(a), Value::Bytes(b)) => String::from_utf8_lossy(a)
+                .split(&*String::from_utf8_lossy(b))
+                .map(Value::from)
+                .collect::<Vec<_>>()
+                .into(),
+            _ => match (as_f64(lhs), as_f64(rhs)) {
+                (Some(_), Some(b)) if b == 0.0 => {
+                    return Err(error("divided because the divisor is zero"))
+                }
+                (Some(a), Some(b)) => match (lhs, rhs) {
+                    (Value::Integer(a), Value::Integer(b)) if a.checked_rem(*b) == Some(0) => {
+                        (a / b).into()
+                    }
+                    _ => (a / b).into(),
+                },
+                _ => return Err(error("divided")),
+            },
+        },
+        Op::Rem => match (as_f64(lhs), as_f64(rhs)) {
+            (Some(a), Some(b)) => match b as i64 {
+                0 => return Err(error("divided because the divisor is zero")),
+                b => (a as i64).wrapping_rem(b).into(),
+            },
+            _ => return Err(error("divided")),
+        },
+    })
+}
+
+fn deep_merge(a: &BTreeMap<String, Value>, b: &BTreeMap<String, Value>) -> BTreeMap<String, Value> {
+    let mut merged = a.clone();
+
+    for (key, value) in b {
+        let value = match (merged.get(key), value) {
+            (Some(Value::Object(a)), Value::Object(b)) => deep_merge(a, b).into(),
+            _ => value.clone(),
+        };
+        merged.insert(key.clone(), value);
+    }
+
+    merged
+}
+
+/// Order values the way jq does: null, false, true, numbers, strings, arrays, objects.
+/// Timestamps and regexes, which jq doesn't have, sort between strings and arrays.
+fn compare(lhs: &Value, rhs: &Value) -> Ordering {
+    fn rank(value: &Value) -> u8 {
+        match value {
+            Value::Null => 0,
+            Value::Boolean(false) => 1,
+            Value::Boolean(true) => 2,
+            Value::Integer(_) | Value::Float(_) | Value::Decimal(_) => 3,
+            Value::Bytes(_) => 4,
+            Value::Timestamp(_) => 5,
+            Value::Regex(_) => 6,
+            Value::Array(_) => 7,
+            Value::Object(_) => 8,
+        }
+    }
+
+    match (lhs, rhs) {
+        (Value::Integer(a), Value::Integer(b)) => a.cmp(b),
+        (Value::Bytes(a), Value::Bytes(b)) => a.cmp(b),
+        (Value::Timestamp(a), Value::Timestamp(b)) => a.cmp(b),
+        (Value::Regex(a), Value::Regex(b)) => a.as_str().cmp(b.as_str()),
+        (Value::Array(a), Value::Array(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(a, b)| compare(a, b))
+            .find(|ordering| *ordering != Ordering::Equal)
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        (Value::Object(a), Value::Object(b)) => a.keys().cmp(b.keys()).then_with(|| {
+            a.values()
+                .zip(b.values())
+                .map(|(a, b)| compare(a, b))
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        }),
+        _ => match (as_f64(lhs), as_f64(rhs)) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            _ => rank(lhs).cmp(&rank(rhs)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_def() -> TypeDef {
+        TypeDef::new()
+            .fallible()
+            .array_mapped::<(), Kind>(map! { (): Kind::all() })
+    }
+
+    test_function![
+        jq_subset => JqSubset;
+
+        identity {
+            args: func_args![value: value!({"a": 1}), program: "."],
+            want: Ok(value!([{"a": 1}])),
+            tdef: type_def(),
+        }
+
+        paths {
+            args: func_args![value: value!({"a": {"b": [1, 2, 3]}, "c d": true}), program: r#".a.b[1], .a.b[-1], ."c d", .a["b"][1:], .missing.field"#],
+            want: Ok(value!([2, 3, true, [2, 3], null])),
+            tdef: type_def(),
+        }
+
+        iterate_and_select {
+            args: func_args![
+                value: value!({"users": [{"name": "ana", "age": 31}, {"name": "bo", "age": 17}, {"name": "cy", "age": 45}]}),
+                program: "[.users[] | select(.age >= 18 and .name != \"cy\") | .name]",
+            ],
+            want: Ok(value!([["ana"]])),
+            tdef: type_def(),
+        }
+
+        recursive_descent {
+            args: func_args![value: value!({"a": {"id": 1, "b": [{"id": 2}, {"c": 3}]}}), program: "[.. | .id? // empty]"],
+            want: Ok(value!([[1, 2]])),
+            tdef: type_def(),
+        }
+
+        object_construction {
+            args: func_args![value: value!({"user": "ana", "titles": ["a", "b"], "k": "key"}), program: r#"{user, title: .titles[], (.k): 1 + 2 * 3}"#],
+            want: Ok(value!([
+                {"user": "ana", "title": "a", "key": 7},
+                {"user": "ana", "title": "b", "key": 7},
+            ])),
+            tdef: type_def(),
+        }
+
+        conditionals {
+            args: func_args![value: value!([1, 5, 10]), program: r#"map(if . < 3 then "low" elif . < 8 then "mid" else "high" end)"#],
+            want: Ok(value!([["low", "mid", "high"]])),
+            tdef: type_def(),
+        }
+
+        builtins {
+            args: func_args![
+                value: value!({"b": 2, "a": 1, "tags": ["x", "y", "x"]}),
+                program: r#"(keys | join(",")), (.tags | unique | length), (to_entries | map(select(.value | type == "number")) | from_entries | add), (.tags | sort_by(.) | reverse | first)"#,
+            ],
+            want: Ok(value!(["a,b,tags", 2, 3, "y"])),
+            tdef: type_def(),
+        }
+
+        with_entries {
+            args: func_args![value: value!({"a": 1, "b": 2}), program: r#"with_entries(select(.value > 1) | {key: (.key | ascii_upcase), value})"#],
+            want: Ok(value!([{"B": 2}])),
+            tdef: type_def(),
+        }
+
+        arithmetic {
+            args: func_args![value: value!({"a": 7, "b": 2}), program: r#".a / .b, .a % .b, 10 / 5, -.a, "a,b" / ",", [1, 2, 3] - [2], {"x": {"y": 1}} * {"x": {"z": 2}}"#],
+            want: Ok(value!([3.5, 1, 2, -7, ["a", "b"], [1, 3], {"x": {"y": 1, "z": 2}}])),
+            tdef: type_def(),
+        }
+
+        runtime_error {
+            args: func_args![value: value!({"a": "text"}), program: ".a[]"],
+            want: Err("cannot iterate over string"),
+            tdef: type_def(),
+        }
+
+        suppressed_error {
+            args: func_args![value: value!({"a": "text"}), program: ".a[]?, .a"],
+            want: Ok(value!(["text"])),
+            tdef: type_def(),
+        }
+    ];
+
+    const UNKNOWN_FUNCTION: &str =
+        "unknown jq function, only the builtins listed in the `jq_subset` docs are supported";
+
+    #[test]
+    fn parse_errors() {
+        for (program, error) in [
+            (".a[", "unexpected end of jq program"),
+            (".a | foo", UNKNOWN_FUNCTION),
+            ("map", UNKNOWN_FUNCTION),
+            ("limit(1; .[])", UNKNOWN_FUNCTION),
+            (". as x", "jq variable bindings aren't supported"),
+            ("$x", "jq variables aren't supported"),
+            ("if . then 1", "expected `end` in jq program"),
+            ("\"a", "unterminated string in jq program"),
+            ("\"\\(.a)\"", "jq string interpolation isn't supported"),
+            (
+                "reduce .[] as x (0; 1)",
+                "jq `reduce` and `foreach` aren't supported",
+            ),
+            ("def f: .; f", "jq function definitions aren't supported"),
+            (
+                "try .a",
+                "jq `try`/`catch` isn't supported, use `?` instead",
+            ),
+            (".a = 1", "jq assignment operators aren't supported"),
+            (".a |= . + 1", "jq assignment operators aren't supported"),
+            (".a //= 1", "jq assignment operators aren't supported"),
+            ("@base64", "jq formats such as `@base64` aren't supported"),
+            ("{(.a)}", "expected `:` in jq object"),
+            (".a )", "unexpected token in jq program"),
+        ] {
+            assert_eq!(program.parse::<Filter>(), Err(error), "{}", program);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Levenshtein;
+
+impl Function for Levenshtein {
+    fn identifier(&self) -> &'static str {
+        "levenshtein"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "levenshtein",
+            source: r#"levenshtein("kitten", "sitting")"#,
+            result: Ok("3"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(Box::new(LevenshteinFn { value, other }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LevenshteinFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl Expression for LevenshteinFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self
+            .value
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .into_owned();
+        let other = self
+            .other
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .into_owned();
+
+        Ok((strsim::levenshtein(&value, &other) as i64).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().integer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        levenshtein => Levenshtein;
+
+        substitutions_and_insertion {
+            args: func_args![value: "kitten", other: "sitting"],
+            want: Ok(3),
+            tdef: TypeDef::new().infallible().integer(),
+        }
+
+        equal {
+            args: func_args![value: "vector.dev", other: "vector.dev"],
+            want: Ok(0),
+            tdef: TypeDef::new().infallible().integer(),
+        }
+
+        empty {
+            args: func_args![value: "", other: "abc"],
+            want: Ok(3),
+            tdef: TypeDef::new().infallible().integer(),
+        }
+
+        characters_not_bytes {
+            args: func_args![value: "café", other: "cafe"],
+            want: Ok(1),
+            tdef: TypeDef::new().infallible().integer(),
+        }
+    ];
+}
@@ -12,14 +12,26 @@ mod assert_eq;
 mod boolean;
 #[cfg(feature = "ceil")]
 mod ceil;
+#[cfg(feature = "checked_add")]
+mod checked_add;
+#[cfg(feature = "checked_mul")]
+mod checked_mul;
+#[cfg(feature = "checked_sub")]
+mod checked_sub;
+#[cfg(feature = "clamp")]
+mod clamp;
 #[cfg(feature = "compact")]
 mod compact;
+#[cfg(feature = "compress")]
+mod compress;
 #[cfg(feature = "contains")]
 mod contains;
 #[cfg(feature = "decode_base64")]
 mod decode_base64;
 #[cfg(feature = "decode_percent")]
 mod decode_percent;
+#[cfg(feature = "decompress")]
+mod decompress;
 #[cfg(feature = "del")]
 mod del;
 #[cfg(feature = "downcase")]
@@ -34,10 +46,18 @@ mod encode_key_value;
 mod encode_logfmt;
 #[cfg(feature = "encode_percent")]
 mod encode_percent;
+#[cfg(feature = "encode_prometheus_text")]
+mod encode_prometheus_text;
+#[cfg(feature = "encode_url")]
+mod encode_url;
+#[cfg(feature = "encode_xml")]
+mod encode_xml;
 #[cfg(feature = "ends_with")]
 mod ends_with;
 #[cfg(feature = "exists")]
 mod exists;
+#[cfg(feature = "exp")]
+mod exp;
 #[cfg(feature = "find")]
 mod find;
 #[cfg(feature = "flatten")]
@@ -52,6 +72,8 @@ mod format_int;
 mod format_number;
 #[cfg(feature = "format_timestamp")]
 mod format_timestamp;
+#[cfg(feature = "from_syslog_facility")]
+mod from_syslog_facility;
 #[cfg(feature = "get")]
 mod get;
 #[cfg(feature = "get_env_var")]
@@ -62,6 +84,8 @@ mod get_hostname;
 mod includes;
 #[cfg(feature = "integer")]
 mod integer;
+#[cfg(feature = "ip_anonymize")]
+mod ip_anonymize;
 #[cfg(feature = "ip_aton")]
 mod ip_aton;
 #[cfg(feature = "ip_cidr_contains")]
@@ -78,6 +102,8 @@ mod ipv6_to_ipv4;
 mod is_array;
 #[cfg(feature = "is_boolean")]
 mod is_boolean;
+#[cfg(feature = "is_decimal")]
+mod is_decimal;
 #[cfg(feature = "is_float")]
 mod is_float;
 #[cfg(feature = "is_integer")]
@@ -88,16 +114,28 @@ mod is_null;
 mod is_nullish;
 #[cfg(feature = "is_object")]
 mod is_object;
+#[cfg(feature = "is_private_ip")]
+mod is_private_ip;
 #[cfg(feature = "is_regex")]
 mod is_regex;
 #[cfg(feature = "is_string")]
 mod is_string;
 #[cfg(feature = "is_timestamp")]
 mod is_timestamp;
+#[cfg(feature = "is_valid_utf8")]
+mod is_valid_utf8;
+#[cfg(feature = "jaro_winkler")]
+mod jaro_winkler;
 #[cfg(feature = "join")]
 mod join;
+#[cfg(feature = "jq_subset")]
+mod jq_subset;
 #[cfg(feature = "length")]
 mod length;
+#[cfg(feature = "levenshtein")]
+mod levenshtein;
+#[cfg(feature = "ln")]
+mod ln;
 #[cfg(feature = "log")]
 mod log;
 #[cfg(any(
@@ -108,6 +146,10 @@ mod log;
 mod log_util;
 #[cfg(feature = "match")]
 mod r#match;
+#[cfg(feature = "log10")]
+mod log10;
+#[cfg(feature = "log2")]
+mod log2;
 #[cfg(feature = "match_any")]
 mod match_any;
 #[cfg(feature = "match_array")]
@@ -116,8 +158,16 @@ mod match_array;
 mod match_datadog_query;
 #[cfg(feature = "md5")]
 mod md5;
+#[cfg(feature = "mean")]
+mod mean;
+#[cfg(feature = "median")]
+mod median;
 #[cfg(feature = "merge")]
 mod merge;
+#[cfg(feature = "metaphone")]
+mod metaphone;
+#[cfg(feature = "normalize_url")]
+mod normalize_url;
 #[cfg(feature = "now")]
 mod now;
 #[cfg(feature = "object")]
@@ -126,10 +176,14 @@ mod object;
 mod only_fields;
 #[cfg(feature = "parse_apache_log")]
 mod parse_apache_log;
+#[cfg(feature = "parse_auditd")]
+mod parse_auditd;
 #[cfg(feature = "parse_aws_alb_log")]
 mod parse_aws_alb_log;
 #[cfg(feature = "parse_aws_cloudwatch_log_subscription_message")]
 mod parse_aws_cloudwatch_log_subscription_message;
+#[cfg(feature = "parse_aws_firehose_cloudwatch_logs")]
+mod parse_aws_firehose_cloudwatch_logs;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 mod parse_aws_vpc_flow_log;
 #[cfg(feature = "parse_common_log")]
@@ -158,6 +212,8 @@ mod parse_linux_authorization;
 mod parse_logfmt;
 #[cfg(feature = "parse_nginx_log")]
 mod parse_nginx_log;
+#[cfg(feature = "parse_prometheus_remote_write")]
+mod parse_prometheus_remote_write;
 #[cfg(feature = "parse_query_string")]
 mod parse_query_string;
 #[cfg(feature = "parse_regex")]
@@ -166,8 +222,12 @@ mod parse_regex;
 mod parse_regex_all;
 #[cfg(feature = "parse_ruby_hash")]
 mod parse_ruby_hash;
+#[cfg(feature = "parse_semver")]
+mod parse_semver;
 #[cfg(feature = "parse_syslog")]
 mod parse_syslog;
+#[cfg(feature = "parse_syslog_priority")]
+mod parse_syslog_priority;
 #[cfg(feature = "parse_timestamp")]
 mod parse_timestamp;
 #[cfg(feature = "parse_tokens")]
@@ -178,6 +238,10 @@ mod parse_url;
 mod parse_user_agent;
 #[cfg(feature = "parse_xml")]
 mod parse_xml;
+#[cfg(feature = "percentile")]
+mod percentile;
+#[cfg(feature = "pow")]
+mod pow;
 #[cfg(feature = "push")]
 mod push;
 #[cfg(feature = "redact")]
@@ -190,6 +254,16 @@ mod replace;
 mod reverse_dns;
 #[cfg(feature = "round")]
 mod round;
+#[cfg(feature = "saturating_add")]
+mod saturating_add;
+#[cfg(feature = "saturating_mul")]
+mod saturating_mul;
+#[cfg(feature = "saturating_sub")]
+mod saturating_sub;
+#[cfg(feature = "semver_compare")]
+mod semver_compare;
+#[cfg(feature = "semver_matches")]
+mod semver_matches;
 #[cfg(feature = "set")]
 mod set;
 #[cfg(feature = "sha1")]
@@ -200,16 +274,24 @@ mod sha2;
 mod sha3;
 #[cfg(feature = "slice")]
 mod slice;
+#[cfg(feature = "snowflake_id")]
+mod snowflake_id;
+#[cfg(feature = "soundex")]
+mod soundex;
 #[cfg(feature = "split")]
 mod split;
 #[cfg(feature = "starts_with")]
 mod starts_with;
+#[cfg(feature = "stddev")]
+mod stddev;
 #[cfg(feature = "string")]
 mod string;
 #[cfg(feature = "strip_ansi_escape_codes")]
 mod strip_ansi_escape_codes;
 #[cfg(feature = "strip_whitespace")]
 mod strip_whitespace;
+#[cfg(feature = "sum")]
+mod sum;
 #[cfg(feature = "tag_types_externally")]
 mod tag_types_externally;
 #[cfg(feature = "tally")]
@@ -220,10 +302,14 @@ mod tally_value;
 mod timestamp;
 #[cfg(feature = "to_bool")]
 mod to_bool;
+#[cfg(feature = "to_decimal")]
+mod to_decimal;
 #[cfg(feature = "to_float")]
 mod to_float;
 #[cfg(feature = "to_int")]
 mod to_int;
+#[cfg(feature = "to_ocsf")]
+mod to_ocsf;
 #[cfg(feature = "to_regex")]
 mod to_regex;
 #[cfg(feature = "to_string")]
@@ -232,6 +318,8 @@ mod to_string;
 mod to_syslog_facility;
 #[cfg(feature = "to_syslog_level")]
 mod to_syslog_level;
+#[cfg(feature = "to_syslog_priority")]
+mod to_syslog_priority;
 #[cfg(feature = "to_syslog_severity")]
 mod to_syslog_severity;
 #[cfg(feature = "to_timestamp")]
@@ -240,6 +328,8 @@ mod to_timestamp;
 mod to_unix_timestamp;
 #[cfg(feature = "truncate")]
 mod truncate;
+#[cfg(feature = "ulid")]
+mod ulid;
 #[cfg(feature = "unique")]
 mod unique;
 #[cfg(feature = "unnest")]
@@ -248,7 +338,11 @@ mod unnest;
 mod upcase;
 #[cfg(feature = "uuid_v4")]
 mod uuid_v4;
+#[cfg(feature = "uuid_v7")]
+mod uuid_v7;
 
+#[cfg(feature = "xpath")]
+mod xpath;
 // -----------------------------------------------------------------------------
 
 #[cfg(feature = "append")]
@@ -261,14 +355,26 @@ pub use assert_eq::AssertEq;
 pub use boolean::Boolean;
 #[cfg(feature = "ceil")]
 pub use ceil::Ceil;
+#[cfg(feature = "checked_add")]
+pub use checked_add::CheckedAdd;
+#[cfg(feature = "checked_mul")]
+pub use checked_mul::CheckedMul;
+#[cfg(feature = "checked_sub")]
+pub use checked_sub::CheckedSub;
+#[cfg(feature = "clamp")]
+pub use clamp::Clamp;
 #[cfg(feature = "compact")]
 pub use compact::Compact;
+#[cfg(feature = "compress")]
+pub use compress::Compress;
 #[cfg(feature = "contains")]
 pub use contains::Contains;
 #[cfg(feature = "decode_base64")]
 pub use decode_base64::DecodeBase64;
 #[cfg(feature = "decode_percent")]
 pub use decode_percent::DecodePercent;
+#[cfg(feature = "decompress")]
+pub use decompress::Decompress;
 #[cfg(feature = "del")]
 pub use del::Del;
 #[cfg(feature = "downcase")]
@@ -283,10 +389,18 @@ pub use encode_key_value::EncodeKeyValue;
 pub use encode_logfmt::EncodeLogfmt;
 #[cfg(feature = "encode_percent")]
 pub use encode_percent::EncodePercent;
+#[cfg(feature = "encode_prometheus_text")]
+pub use encode_prometheus_text::EncodePrometheusText;
+#[cfg(feature = "encode_url")]
+pub use encode_url::EncodeUrl;
+#[cfg(feature = "encode_xml")]
+pub use encode_xml::EncodeXml;
 #[cfg(feature = "ends_with")]
 pub use ends_with::EndsWith;
 #[cfg(feature = "exists")]
 pub use exists::Exists;
+#[cfg(feature = "exp")]
+pub use exp::Exp;
 #[cfg(feature = "find")]
 pub use find::Find;
 #[cfg(feature = "flatten")]
@@ -301,6 +415,8 @@ pub use format_int::FormatInt;
 pub use format_number::FormatNumber;
 #[cfg(feature = "format_timestamp")]
 pub use format_timestamp::FormatTimestamp;
+#[cfg(feature = "from_syslog_facility")]
+pub use from_syslog_facility::FromSyslogFacility;
 #[cfg(feature = "get")]
 pub use get::Get;
 #[cfg(feature = "get_env_var")]
@@ -311,6 +427,8 @@ pub use get_hostname::GetHostname;
 pub use includes::Includes;
 #[cfg(feature = "integer")]
 pub use integer::Integer;
+#[cfg(feature = "ip_anonymize")]
+pub use ip_anonymize::IpAnonymize;
 #[cfg(feature = "ip_aton")]
 pub use ip_aton::IpAton;
 #[cfg(feature = "ip_cidr_contains")]
@@ -327,6 +445,8 @@ pub use ipv6_to_ipv4::Ipv6ToIpV4;
 pub use is_array::IsArray;
 #[cfg(feature = "is_boolean")]
 pub use is_boolean::IsBoolean;
+#[cfg(feature = "is_decimal")]
+pub use is_decimal::IsDecimal;
 #[cfg(feature = "is_float")]
 pub use is_float::IsFloat;
 #[cfg(feature = "is_integer")]
@@ -337,26 +457,50 @@ pub use is_null::IsNull;
 pub use is_nullish::IsNullish;
 #[cfg(feature = "is_object")]
 pub use is_object::IsObject;
+#[cfg(feature = "is_private_ip")]
+pub use is_private_ip::IsPrivateIp;
 #[cfg(feature = "is_regex")]
 pub use is_regex::IsRegex;
 #[cfg(feature = "is_string")]
 pub use is_string::IsString;
 #[cfg(feature = "is_timestamp")]
 pub use is_timestamp::IsTimestamp;
+#[cfg(feature = "is_valid_utf8")]
+pub use is_valid_utf8::IsValidUtf8;
+#[cfg(feature = "jaro_winkler")]
+pub use jaro_winkler::JaroWinkler;
 #[cfg(feature = "join")]
 pub use join::Join;
+#[cfg(feature = "jq_subset")]
+pub use jq_subset::JqSubset;
 #[cfg(feature = "length")]
 pub use length::Length;
+#[cfg(feature = "levenshtein")]
+pub use levenshtein::Levenshtein;
+#[cfg(feature = "ln")]
+pub use ln::Ln;
 #[cfg(feature = "log")]
 pub use log::Log;
+#[cfg(feature = "log10")]
+pub use log10::Log10;
+#[cfg(feature = "log2")]
+pub use log2::Log2;
 #[cfg(feature = "match_any")]
 pub use match_any::MatchAny;
 #[cfg(feature = "match_array")]
 pub use match_array::MatchArray;
 #[cfg(feature = "match_datadog_query")]
 pub use match_datadog_query::MatchDatadogQuery;
+#[cfg(feature = "mean")]
+pub use mean::Mean;
+#[cfg(feature = "median")]
+pub use median::Median;
 #[cfg(feature = "merge")]
 pub use merge::Merge;
+#[cfg(feature = "metaphone")]
+pub use metaphone::Metaphone;
+#[cfg(feature = "normalize_url")]
+pub use normalize_url::NormalizeUrl;
 #[cfg(feature = "now")]
 pub use now::Now;
 #[cfg(feature = "object")]
@@ -365,10 +509,14 @@ pub use object::Object;
 pub use only_fields::OnlyFields;
 #[cfg(feature = "parse_apache_log")]
 pub use parse_apache_log::ParseApacheLog;
+#[cfg(feature = "parse_auditd")]
+pub use parse_auditd::ParseAuditd;
 #[cfg(feature = "parse_aws_alb_log")]
 pub use parse_aws_alb_log::ParseAwsAlbLog;
 #[cfg(feature = "parse_aws_cloudwatch_log_subscription_message")]
 pub use parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubscriptionMessage;
+#[cfg(feature = "parse_aws_firehose_cloudwatch_logs")]
+pub use parse_aws_firehose_cloudwatch_logs::ParseAwsFirehoseCloudWatchLogs;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 pub use parse_aws_vpc_flow_log::ParseAwsVpcFlowLog;
 #[cfg(feature = "parse_common_log")]
@@ -397,6 +545,8 @@ pub use parse_linux_authorization::ParseLinuxAuthorization;
 pub use parse_logfmt::ParseLogFmt;
 #[cfg(feature = "parse_nginx_log")]
 pub use parse_nginx_log::ParseNginxLog;
+#[cfg(feature = "parse_prometheus_remote_write")]
+pub use parse_prometheus_remote_write::ParsePrometheusRemoteWrite;
 #[cfg(feature = "parse_query_string")]
 pub use parse_query_string::ParseQueryString;
 #[cfg(feature = "parse_regex")]
@@ -405,8 +555,12 @@ pub use parse_regex::ParseRegex;
 pub use parse_regex_all::ParseRegexAll;
 #[cfg(feature = "parse_ruby_hash")]
 pub use parse_ruby_hash::ParseRubyHash;
+#[cfg(feature = "parse_semver")]
+pub use parse_semver::ParseSemver;
 #[cfg(feature = "parse_syslog")]
 pub use parse_syslog::ParseSyslog;
+#[cfg(feature = "parse_syslog_priority")]
+pub use parse_syslog_priority::ParseSyslogPriority;
 #[cfg(feature = "parse_timestamp")]
 pub use parse_timestamp::ParseTimestamp;
 #[cfg(feature = "parse_tokens")]
@@ -417,6 +571,10 @@ pub use parse_url::ParseUrl;
 pub use parse_user_agent::ParseUserAgent;
 #[cfg(feature = "parse_xml")]
 pub use parse_xml::ParseXml;
+#[cfg(feature = "percentile")]
+pub use percentile::Percentile;
+#[cfg(feature = "pow")]
+pub use pow::Pow;
 #[cfg(feature = "push")]
 pub use push::Push;
 #[cfg(feature = "match")]
@@ -431,6 +589,16 @@ pub use replace::Replace;
 pub use reverse_dns::ReverseDns;
 #[cfg(feature = "round")]
 pub use round::Round;
+#[cfg(feature = "saturating_add")]
+pub use saturating_add::SaturatingAdd;
+#[cfg(feature = "saturating_mul")]
+pub use saturating_mul::SaturatingMul;
+#[cfg(feature = "saturating_sub")]
+pub use saturating_sub::SaturatingSub;
+#[cfg(feature = "semver_compare")]
+pub use semver_compare::SemverCompare;
+#[cfg(feature = "semver_matches")]
+pub use semver_matches::SemverMatches;
 #[cfg(feature = "set")]
 pub use set::Set;
 #[cfg(feature = "sha2")]
@@ -439,16 +607,24 @@ pub use sha2::Sha2;
 pub use sha3::Sha3;
 #[cfg(feature = "slice")]
 pub use slice::Slice;
+#[cfg(feature = "snowflake_id")]
+pub use snowflake_id::SnowflakeId;
+#[cfg(feature = "soundex")]
+pub use soundex::Soundex;
 #[cfg(feature = "split")]
 pub use split::Split;
 #[cfg(feature = "starts_with")]
 pub use starts_with::StartsWith;
+#[cfg(feature = "stddev")]
+pub use stddev::Stddev;
 #[cfg(feature = "string")]
 pub use string::String;
 #[cfg(feature = "strip_ansi_escape_codes")]
 pub use strip_ansi_escape_codes::StripAnsiEscapeCodes;
 #[cfg(feature = "strip_whitespace")]
 pub use strip_whitespace::StripWhitespace;
+#[cfg(feature = "sum")]
+pub use sum::Sum;
 #[cfg(feature = "tag_types_externally")]
 pub use tag_types_externally::TagTypesExternally;
 #[cfg(feature = "tally")]
@@ -459,10 +635,14 @@ pub use tally_value::TallyValue;
 pub use timestamp::Timestamp;
 #[cfg(feature = "to_bool")]
 pub use to_bool::ToBool;
+#[cfg(feature = "to_decimal")]
+pub use to_decimal::ToDecimal;
 #[cfg(feature = "to_float")]
 pub use to_float::ToFloat;
 #[cfg(feature = "to_int")]
 pub use to_int::ToInt;
+#[cfg(feature = "to_ocsf")]
+pub use to_ocsf::ToOcsf;
 #[cfg(feature = "to_regex")]
 pub use to_regex::ToRegex;
 #[cfg(feature = "to_string")]
@@ -471,6 +651,8 @@ pub use to_string::ToString;
 pub use to_syslog_facility::ToSyslogFacility;
 #[cfg(feature = "to_syslog_level")]
 pub use to_syslog_level::ToSyslogLevel;
+#[cfg(feature = "to_syslog_priority")]
+pub use to_syslog_priority::ToSyslogPriority;
 #[cfg(feature = "to_syslog_severity")]
 pub use to_syslog_severity::ToSyslogSeverity;
 #[cfg(feature = "to_timestamp")]
@@ -479,6 +661,8 @@ pub use to_timestamp::ToTimestamp;
 pub use to_unix_timestamp::ToUnixTimestamp;
 #[cfg(feature = "truncate")]
 pub use truncate::Truncate;
+#[cfg(feature = "ulid")]
+pub use ulid::Ulid;
 #[cfg(feature = "unique")]
 pub use unique::Unique;
 #[cfg(feature = "unnest")]
@@ -487,6 +671,10 @@ pub use unnest::Unnest;
 pub use upcase::Upcase;
 #[cfg(feature = "uuid_v4")]
 pub use uuid_v4::UuidV4;
+#[cfg(feature = "uuid_v7")]
+pub use uuid_v7::UuidV7;
+#[cfg(feature = "xpath")]
+pub use xpath::Xpath;
 
 #[cfg(feature = "array")]
 pub use crate::array::Array;
@@ -509,14 +697,26 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Boolean),
         #[cfg(feature = "ceil")]
         Box::new(Ceil),
+        #[cfg(feature = "checked_add")]
+        Box::new(CheckedAdd),
+        #[cfg(feature = "checked_mul")]
+        Box::new(CheckedMul),
+        #[cfg(feature = "checked_sub")]
+        Box::new(CheckedSub),
+        #[cfg(feature = "clamp")]
+        Box::new(Clamp),
         #[cfg(feature = "compact")]
         Box::new(Compact),
+        #[cfg(feature = "compress")]
+        Box::new(Compress),
         #[cfg(feature = "contains")]
         Box::new(Contains),
         #[cfg(feature = "decode_base64")]
         Box::new(DecodeBase64),
         #[cfg(feature = "decode_percent")]
         Box::new(DecodePercent),
+        #[cfg(feature = "decompress")]
+        Box::new(Decompress),
         #[cfg(feature = "del")]
         Box::new(Del),
         #[cfg(feature = "downcase")]
@@ -531,10 +731,18 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(EncodeLogfmt),
         #[cfg(feature = "encode_percent")]
         Box::new(EncodePercent),
+        #[cfg(feature = "encode_prometheus_text")]
+        Box::new(EncodePrometheusText),
+        #[cfg(feature = "encode_url")]
+        Box::new(EncodeUrl),
+        #[cfg(feature = "encode_xml")]
+        Box::new(EncodeXml),
         #[cfg(feature = "ends_with")]
         Box::new(EndsWith),
         #[cfg(feature = "exists")]
         Box::new(Exists),
+        #[cfg(feature = "exp")]
+        Box::new(Exp),
         #[cfg(feature = "find")]
         Box::new(Find),
         #[cfg(feature = "flatten")]
@@ -549,6 +757,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(FormatNumber),
         #[cfg(feature = "format_timestamp")]
         Box::new(FormatTimestamp),
+        #[cfg(feature = "from_syslog_facility")]
+        Box::new(FromSyslogFacility),
         #[cfg(feature = "get")]
         Box::new(Get),
         #[cfg(feature = "get_env_var")]
@@ -559,6 +769,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Includes),
         #[cfg(feature = "integer")]
         Box::new(Integer),
+        #[cfg(feature = "ip_anonymize")]
+        Box::new(IpAnonymize),
         #[cfg(feature = "ip_aton")]
         Box::new(IpAton),
         #[cfg(feature = "ip_cidr_contains")]
@@ -575,6 +787,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(IsArray),
         #[cfg(feature = "is_boolean")]
         Box::new(IsBoolean),
+        #[cfg(feature = "is_decimal")]
+        Box::new(IsDecimal),
         #[cfg(feature = "is_float")]
         Box::new(IsFloat),
         #[cfg(feature = "is_integer")]
@@ -585,18 +799,34 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(IsNullish),
         #[cfg(feature = "is_object")]
         Box::new(IsObject),
+        #[cfg(feature = "is_private_ip")]
+        Box::new(IsPrivateIp),
         #[cfg(feature = "is_regex")]
         Box::new(IsRegex),
         #[cfg(feature = "is_string")]
         Box::new(IsString),
         #[cfg(feature = "is_timestamp")]
         Box::new(IsTimestamp),
+        #[cfg(feature = "is_valid_utf8")]
+        Box::new(IsValidUtf8),
+        #[cfg(feature = "jaro_winkler")]
+        Box::new(JaroWinkler),
         #[cfg(feature = "join")]
         Box::new(Join),
+        #[cfg(feature = "jq_subset")]
+        Box::new(JqSubset),
         #[cfg(feature = "length")]
         Box::new(Length),
+        #[cfg(feature = "levenshtein")]
+        Box::new(Levenshtein),
+        #[cfg(feature = "ln")]
+        Box::new(Ln),
         #[cfg(feature = "log")]
         Box::new(Log),
+        #[cfg(feature = "log10")]
+        Box::new(Log10),
+        #[cfg(feature = "log2")]
+        Box::new(Log2),
         #[cfg(feature = "match")]
         Box::new(Match),
         #[cfg(feature = "match_any")]
@@ -607,8 +837,16 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(MatchDatadogQuery),
         #[cfg(feature = "md5")]
         Box::new(Md5),
+        #[cfg(feature = "mean")]
+        Box::new(Mean),
+        #[cfg(feature = "median")]
+        Box::new(Median),
         #[cfg(feature = "merge")]
         Box::new(Merge),
+        #[cfg(feature = "metaphone")]
+        Box::new(Metaphone),
+        #[cfg(feature = "normalize_url")]
+        Box::new(NormalizeUrl),
         #[cfg(feature = "now")]
         Box::new(Now),
         // We are not sure if this is the way we want to expose this functionality yet
@@ -619,10 +857,14 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Object),
         #[cfg(feature = "parse_apache_log")]
         Box::new(ParseApacheLog),
+        #[cfg(feature = "parse_auditd")]
+        Box::new(ParseAuditd),
         #[cfg(feature = "parse_aws_alb_log")]
         Box::new(ParseAwsAlbLog),
         #[cfg(feature = "parse_aws_cloudwatch_log_subscription_message")]
         Box::new(ParseAwsCloudWatchLogSubscriptionMessage),
+        #[cfg(feature = "parse_aws_firehose_cloudwatch_logs")]
+        Box::new(ParseAwsFirehoseCloudWatchLogs),
         #[cfg(feature = "parse_aws_vpc_flow_log")]
         Box::new(ParseAwsVpcFlowLog),
         #[cfg(feature = "parse_common_log")]
@@ -651,6 +893,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseLogFmt),
         #[cfg(feature = "parse_nginx_log")]
         Box::new(ParseNginxLog),
+        #[cfg(feature = "parse_prometheus_remote_write")]
+        Box::new(ParsePrometheusRemoteWrite),
         #[cfg(feature = "parse_query_string")]
         Box::new(ParseQueryString),
         #[cfg(feature = "parse_regex")]
@@ -659,8 +903,12 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseRegexAll),
         #[cfg(feature = "parse_ruby_hash")]
         Box::new(ParseRubyHash),
+        #[cfg(feature = "parse_semver")]
+        Box::new(ParseSemver),
         #[cfg(feature = "parse_syslog")]
         Box::new(ParseSyslog),
+        #[cfg(feature = "parse_syslog_priority")]
+        Box::new(ParseSyslogPriority),
         #[cfg(feature = "parse_timestamp")]
         Box::new(ParseTimestamp),
         #[cfg(feature = "parse_tokens")]
@@ -671,6 +919,10 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseUserAgent),
         #[cfg(feature = "parse_xml")]
         Box::new(ParseXml),
+        #[cfg(feature = "percentile")]
+        Box::new(Percentile),
+        #[cfg(feature = "pow")]
+        Box::new(Pow),
         #[cfg(feature = "push")]
         Box::new(Push),
         #[cfg(feature = "redact")]
@@ -683,6 +935,16 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ReverseDns),
         #[cfg(feature = "round")]
         Box::new(Round),
+        #[cfg(feature = "saturating_add")]
+        Box::new(SaturatingAdd),
+        #[cfg(feature = "saturating_mul")]
+        Box::new(SaturatingMul),
+        #[cfg(feature = "saturating_sub")]
+        Box::new(SaturatingSub),
+        #[cfg(feature = "semver_compare")]
+        Box::new(SemverCompare),
+        #[cfg(feature = "semver_matches")]
+        Box::new(SemverMatches),
         #[cfg(feature = "set")]
         Box::new(Set),
         #[cfg(feature = "sha1")]
@@ -693,16 +955,24 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Sha3),
         #[cfg(feature = "slice")]
         Box::new(Slice),
+        #[cfg(feature = "snowflake_id")]
+        Box::new(SnowflakeId),
+        #[cfg(feature = "soundex")]
+        Box::new(Soundex),
         #[cfg(feature = "split")]
         Box::new(Split),
         #[cfg(feature = "starts_with")]
         Box::new(StartsWith),
+        #[cfg(feature = "stddev")]
+        Box::new(Stddev),
         #[cfg(feature = "string")]
         Box::new(String),
         #[cfg(feature = "strip_ansi_escape_codes")]
         Box::new(StripAnsiEscapeCodes),
         #[cfg(feature = "strip_whitespace")]
         Box::new(StripWhitespace),
+        #[cfg(feature = "sum")]
+        Box::new(Sum),
         #[cfg(feature = "tally")]
         Box::new(Tally),
         #[cfg(feature = "tally_value")]
@@ -713,10 +983,14 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Timestamp),
         #[cfg(feature = "to_bool")]
         Box::new(ToBool),
+        #[cfg(feature = "to_decimal")]
+        Box::new(ToDecimal),
         #[cfg(feature = "to_float")]
         Box::new(ToFloat),
         #[cfg(feature = "to_int")]
         Box::new(ToInt),
+        #[cfg(feature = "to_ocsf")]
+        Box::new(ToOcsf),
         #[cfg(feature = "to_regex")]
         Box::new(ToRegex),
         #[cfg(feature = "to_string")]
@@ -725,6 +999,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ToSyslogFacility),
         #[cfg(feature = "to_syslog_level")]
         Box::new(ToSyslogLevel),
+        #[cfg(feature = "to_syslog_priority")]
+        Box::new(ToSyslogPriority),
         #[cfg(feature = "to_syslog_severity")]
         Box::new(ToSyslogSeverity),
         #[cfg(feature = "to_timestamp")]
@@ -733,6 +1009,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ToUnixTimestamp),
         #[cfg(feature = "truncate")]
         Box::new(Truncate),
+        #[cfg(feature = "ulid")]
+        Box::new(Ulid),
         #[cfg(feature = "unique")]
         Box::new(Unique),
         #[cfg(feature = "unnest")]
@@ -741,5 +1019,9 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Upcase),
         #[cfg(feature = "uuid_v4")]
         Box::new(UuidV4),
+        #[cfg(feature = "uuid_v7")]
+        Box::new(UuidV7),
+        #[cfg(feature = "xpath")]
+        Box::new(Xpath),
     ]
 }
@@ -0,0 +1,96 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ln;
+
+impl Function for Ln {
+    fn identifier(&self) -> &'static str {
+        "ln"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "natural logarithm",
+            source: "ln!(1)",
+            result: Ok("0.0"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(LnFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LnFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for LnFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = f64::try_from(&self.value.resolve(ctx)?)?;
+        if value <= 0.0 {
+            return Err(format!("can't take the logarithm of {}", value).into());
+        }
+
+        Ok(value.ln().into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        ln => Ln;
+
+        one {
+            args: func_args![value: 1],
+            want: Ok(0.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        e {
+            args: func_args![value: std::f64::consts::E],
+            want: Ok(1.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        decimal {
+            args: func_args![value: Value::Decimal("1.0".parse().unwrap())],
+            want: Ok(0.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        zero {
+            args: func_args![value: 0],
+            want: Err("can't take the logarithm of 0"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        negative {
+            args: func_args![value: -2.5],
+            want: Err("can't take the logarithm of -2.5"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+    ];
+}
@@ -0,0 +1,90 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Log10;
+
+impl Function for Log10 {
+    fn identifier(&self) -> &'static str {
+        "log10"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "common logarithm",
+            source: "log10!(1000)",
+            result: Ok("3.0"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(Log10Fn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Log10Fn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for Log10Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = f64::try_from(&self.value.resolve(ctx)?)?;
+        if value <= 0.0 {
+            return Err(format!("can't take the logarithm of {}", value).into());
+        }
+
+        Ok(value.log10().into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        log10 => Log10;
+
+        integer {
+            args: func_args![value: 1000],
+            want: Ok(3.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        float {
+            args: func_args![value: 0.01],
+            want: Ok(-2.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        zero {
+            args: func_args![value: 0],
+            want: Err("can't take the logarithm of 0"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        negative {
+            args: func_args![value: -2.5],
+            want: Err("can't take the logarithm of -2.5"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+    ];
+}
@@ -0,0 +1,90 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Log2;
+
+impl Function for Log2 {
+    fn identifier(&self) -> &'static str {
+        "log2"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "binary logarithm",
+            source: "log2!(1024)",
+            result: Ok("10.0"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(Log2Fn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Log2Fn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for Log2Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = f64::try_from(&self.value.resolve(ctx)?)?;
+        if value <= 0.0 {
+            return Err(format!("can't take the logarithm of {}", value).into());
+        }
+
+        Ok(value.log2().into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        log2 => Log2;
+
+        integer {
+            args: func_args![value: 1024],
+            want: Ok(10.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        float {
+            args: func_args![value: 0.5],
+            want: Ok(-1.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        zero {
+            args: func_args![value: 0],
+            want: Err("can't take the logarithm of 0"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        negative {
+            args: func_args![value: -2.5],
+            want: Err("can't take the logarithm of -2.5"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+    ];
+}
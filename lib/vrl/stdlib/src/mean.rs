@@ -0,0 +1,90 @@
+use vrl::prelude::*;
+
+use crate::util::{array_to_floats, finite_float};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Mean;
+
+impl Function for Mean {
+    fn identifier(&self) -> &'static str {
+        "mean"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "mean",
+            source: "mean!([2, 4, 9])",
+            result: Ok("5.0"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(MeanFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MeanFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for MeanFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let values = array_to_floats(&self.value.resolve(ctx)?.try_array()?)?;
+        let sum: f64 = values.iter().sum();
+
+        Ok(finite_float(sum / values.len() as f64)?)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        mean => Mean;
+
+        integers {
+            args: func_args![value: value!([2, 4, 9])],
+            want: Ok(5.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        mixed {
+            args: func_args![value: value!([1, 2.5, (Value::Decimal("4.5".parse().unwrap()))])],
+            want: Ok(2.6666666666666665),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        empty {
+            args: func_args![value: value!([])],
+            want: Err("array must not be empty"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        not_a_number {
+            args: func_args![value: value!([1, null])],
+            want: Err(r#"element at index 1 must be a number, not "null""#),
+            tdef: TypeDef::new().fallible().float(),
+        }
+    ];
+}
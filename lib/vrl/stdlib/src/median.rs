@@ -0,0 +1,83 @@
+use vrl::prelude::*;
+
+use crate::{percentile::percentile_of, util::array_to_floats};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Median;
+
+impl Function for Median {
+    fn identifier(&self) -> &'static str {
+        "median"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "median",
+            source: "median!([7, 1, 3, 10])",
+            result: Ok("5.0"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(MedianFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MedianFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for MedianFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let values = array_to_floats(&self.value.resolve(ctx)?.try_array()?)?;
+
+        Ok(percentile_of(values, 50.0).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        median => Median;
+
+        odd {
+            args: func_args![value: value!([9, 1.5, 4])],
+            want: Ok(4.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        even {
+            args: func_args![value: value!([7, 1, 3, 10])],
+            want: Ok(5.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        empty {
+            args: func_args![value: value!([])],
+            want: Err("array must not be empty"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+    ];
+}
@@ -0,0 +1,263 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Metaphone;
+
+impl Function for Metaphone {
+    fn identifier(&self) -> &'static str {
+        "metaphone"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "metaphone",
+                source: r#"metaphone("Knight")"#,
+                result: Ok("NT"),
+            },
+            Example {
+                title: "similar sounding names",
+                source: r#"metaphone("Catherine") == metaphone("Kathryn")"#,
+                result: Ok("true"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(MetaphoneFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct MetaphoneFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for MetaphoneFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self
+            .value
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .into_owned();
+
+        Ok(metaphone(&value).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().bytes()
+    }
+}
+
+/// Encodes the ASCII letters of `value` with Lawrence Philips' original Metaphone algorithm,
+/// ignoring any other characters. `0` stands for the "th" sound.
+fn metaphone(value: &str) -> String {
+    let mut word = value
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|byte| byte.to_ascii_uppercase())
+        .collect::<Vec<_>>();
+
+    // Some initial letters are silent, or pronounced differently.
+    match word.as_slice() {
+        [b'A', b'E', ..] | [b'G' | b'K' | b'P', b'N', ..] | [b'W', b'R', ..] => {
+            word.remove(0);
+        }
+        [b'W', b'H', ..] => {
+            word.remove(1);
+        }
+        [b'X', ..] => word[0] = b'S',
+        _ => {}
+    }
+
+    let at = |index: usize| word.get(index).copied();
+    let is_vowel = |letter: Option<u8>| matches!(letter, Some(b'A' | b'E' | b'I' | b'O' | b'U'));
+    let is_front_vowel = |letter: Option<u8>| matches!(letter, Some(b'E' | b'I' | b'Y'));
+
+    let mut code = String::new();
+    let mut index = 0;
+    while index < word.len() {
+        let letter = word[index];
+        let previous = index.checked_sub(1).and_then(at);
+        let next = at(index + 1);
+
+        // Doubled letters are encoded once, except for C.
+        if previous == Some(letter) && letter != b'C' {
+            index += 1;
+            continue;
+        }
+
+        match letter {
+            b'A' | b'E' | b'I' | b'O' | b'U' => {
+                // Vowels are only encoded at the start.
+                if index == 0 {
+                    code.push(letter as char);
+                }
+            }
+            b'B' => {
+                // Silent in a final MB, as in "thumb".
+                if !(previous == Some(b'M') && next.is_none()) {
+                    code.push('B');
+                }
+            }
+            b'C' => {
+                if previous == Some(b'S') && is_front_vowel(next) {
+                    // Silent in SCE, SCI and SCY.
+                } else if next == Some(b'I') && at(index + 2) == Some(b'A') {
+                    code.push('X');
+                } else if is_front_vowel(next) {
+                    code.push('S');
+                } else if next == Some(b'H') {
+                    // Hard in SCH and an initial CHR, as in "school" and "Christ".
+                    let hard = previous == Some(b'S') || (index == 0 && !is_vowel(at(index + 2)));
+                    code.push(if hard { 'K' } else { 'X' });
+                } else {
+                    code.push('K');
+                }
+            }
+            b'D' => {
+                if next == Some(b'G') && is_front_vowel(at(index + 2)) {
+                    code.push('J');
+                    index += 2;
+                } else {
+                    code.push('T');
+                }
+            }
+            b'G' => {
+                // Silent in a GH that isn't followed by a vowel, as in "night", and in a final GN or
+                // GNED, as in "sign" and "signed".
+                let silent = (next == Some(b'H') && !is_vowel(at(index + 2)))
+                    || (index > 0
+                        && next == Some(b'N')
+                        && matches!(&word[index + 2..], [] | [b'E', b'D']));
+                if !silent {
+                    code.push(if is_front_vowel(next) { 'J' } else { 'K' });
+                }
+            }
+            b'H' => {
+                if is_vowel(next) && !matches!(previous, Some(b'C' | b'G' | b'P' | b'S' | b'T')) {
+                    code.push('H');
+                }
+            }
+            b'K' => {
+                if previous != Some(b'C') {
+                    code.push('K');
+                }
+            }
+            b'P' => code.push(if next == Some(b'H') { 'F' } else { 'P' }),
+            b'Q' => code.push('K'),
+            b'S' => {
+                let sh = next == Some(b'H')
+                    || (next == Some(b'I') && matches!(at(index + 2), Some(b'O' | b'A')));
+                code.push(if sh { 'X' } else { 'S' });
+            }
+            b'T' => {
+                if next == Some(b'I') && matches!(at(index + 2), Some(b'O' | b'A')) {
+                    code.push('X');
+                } else if next == Some(b'H') {
+                    code.push('0');
+                } else if !(next == Some(b'C') && at(index + 2) == Some(b'H')) {
+                    code.push('T');
+                }
+            }
+            b'V' => code.push('F'),
+            b'W' | b'Y' => {
+                if is_vowel(next) {
+                    code.push(letter as char);
+                }
+            }
+            b'X' => code.push_str("KS"),
+            b'Z' => code.push('S'),
+            // F, J, L, M, N and R are encoded as themselves.
+            letter => code.push(letter as char),
+        }
+
+        index += 1;
+    }
+
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        metaphone => Metaphone;
+
+        silent_letters {
+            args: func_args![value: "Knight"],
+            want: Ok("NT"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        th {
+            args: func_args![value: "Thumb"],
+            want: Ok("0M"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        initial_letters {
+            args: func_args![value: "Xavier"],
+            want: Ok("SFR"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        ch {
+            args: func_args![value: "Church"],
+            want: Ok("XRX"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        hard_ch {
+            args: func_args![value: "Christine"],
+            want: Ok("KRSTN"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        dge {
+            args: func_args![value: "Dodge"],
+            want: Ok("TJ"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        tio {
+            args: func_args![value: "Nation"],
+            want: Ok("NXN"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        similar_sounding {
+            args: func_args![value: "Kathryn"],
+            want: Ok("K0RN"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        ignores_non_letters {
+            args: func_args![value: "micros0ft"],
+            want: Ok("MKRSFT"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        empty {
+            args: func_args![value: ""],
+            want: Ok(""),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+    ];
+}
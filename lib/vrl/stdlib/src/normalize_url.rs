@@ -0,0 +1,151 @@
+use url::Url;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct NormalizeUrl;
+
+impl Function for NormalizeUrl {
+    fn identifier(&self) -> &'static str {
+        "normalize_url"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "sort_query",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "remove_fragment",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "normalize url",
+                source: r#"normalize_url!("HTTPS://Vector.dev:443/docs/../reference/?b=2&a=1&")"#,
+                result: Ok("https://vector.dev/reference/?a=1&b=2"),
+            },
+            Example {
+                title: "remove fragment",
+                source: r#"normalize_url!("https://vector.dev/docs?b=2&a=1#setup", sort_query: false, remove_fragment: true)"#,
+                result: Ok("https://vector.dev/docs?b=2&a=1"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let sort_query = arguments
+            .optional("sort_query")
+            .unwrap_or_else(|| expr!(true));
+        let remove_fragment = arguments
+            .optional("remove_fragment")
+            .unwrap_or_else(|| expr!(false));
+
+        Ok(Box::new(NormalizeUrlFn {
+            value,
+            sort_query,
+            remove_fragment,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct NormalizeUrlFn {
+    value: Box<dyn Expression>,
+    sort_query: Box<dyn Expression>,
+    remove_fragment: Box<dyn Expression>,
+}
+
+impl Expression for NormalizeUrlFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let string = value.try_bytes_utf8_lossy()?;
+        let sort_query = self.sort_query.resolve(ctx)?.try_boolean()?;
+        let remove_fragment = self.remove_fragment.resolve(ctx)?.try_boolean()?;
+
+        // Parsing already lowercases the scheme and host, drops the default port, resolves `.`
+        // and `..` path segments and percent-encodes consistently.
+        let mut url = Url::parse(&string).map_err(|e| format!("unable to parse url: {}", e))?;
+
+        let mut pairs = url
+            .query_pairs()
+            .into_owned()
+            .filter(|(key, value)| !key.is_empty() || !value.is_empty())
+            .collect::<Vec<_>>();
+        if sort_query {
+            // The sort is stable, so repeated parameters keep their order.
+            pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+        }
+        if pairs.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+
+        if remove_fragment || url.fragment() == Some("") {
+            url.set_fragment(None);
+        }
+
+        Ok(String::from(url).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        normalize_url => NormalizeUrl;
+
+        defaults {
+            args: func_args![value: value!("HTTP://User@Vector.DEV:80/a/./b/../c?z=1&y=2&z=0#")],
+            want: Ok("http://User@vector.dev/a/c?y=2&z=1&z=0"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        keeps_query_order {
+            args: func_args![value: value!("https://vector.dev/?b=2&a=1#top"), sort_query: false],
+            want: Ok("https://vector.dev/?b=2&a=1#top"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        removes_empty_query {
+            args: func_args![value: value!("https://vector.dev/docs?&"), remove_fragment: true],
+            want: Ok("https://vector.dev/docs"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        unicode_host {
+            args: func_args![value: value!("https://Bücher.example/")],
+            want: Ok("https://xn--bcher-kva.example/"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+
+        invalid {
+            args: func_args![value: value!("vector.dev")],
+            want: Err("unable to parse url: relative URL without a base"),
+            tdef: TypeDef::new().fallible().bytes(),
+        }
+    ];
+}
@@ -0,0 +1,520 @@
+use std::collections::BTreeMap;
+
+use chrono::{TimeZone, Utc};
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseAuditd;
+
+impl Function for ParseAuditd {
+    fn identifier(&self) -> &'static str {
+        "parse_auditd"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES | kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "joined records",
+            source: r#"parse_auditd!(["type=SYSCALL msg=audit(1364481363.243:24287): syscall=59 comm=\"ls\" key=(null)", "type=EXECVE msg=audit(1364481363.243:24287): argc=2 a0=\"ls\" a1=2F746D702F6120622063"])"#,
+            result: Ok(
+                r#"[{ "id": "1364481363.243:24287", "records": [{ "comm": "ls", "key": null, "syscall": "59", "type": "SYSCALL" }, { "a0": "ls", "a1": "/tmp/a b c", "argc": "2", "argv": ["ls", "/tmp/a b c"], "type": "EXECVE" }], "sequence": 24287, "timestamp": t'2013-03-28T14:36:03.243Z' }]"#,
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseAuditdFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseAuditdFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseAuditdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let lines = match self.value.resolve(ctx)? {
+            Value::Array(values) => values
+                .into_iter()
+                .map(|value| value.try_bytes_utf8_lossy().map(|s| s.into_owned()))
+                .collect::<std::result::Result<Vec<_>, _>>()?,
+            value => value
+                .try_bytes_utf8_lossy()?
+                .lines()
+                .map(ToOwned::to_owned)
+                .collect(),
+        };
+
+        let mut events: Vec<Event> = Vec::new();
+        for line in lines.iter().filter(|line| !line.trim().is_empty()) {
+            let record = parse_record(line)?;
+            match events.iter_mut().find(|event| event.id == record.id) {
+                Some(event) => event.records.push(record.fields),
+                None => events.push(Event {
+                    id: record.id,
+                    timestamp: record.timestamp,
+                    sequence: record.sequence,
+                    records: vec![record.fields],
+                }),
+            }
+        }
+
+        Ok(events
+            .into_iter()
+            .map(Value::from)
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().array_mapped::<(), TypeDef>(
+            map![(): TypeDef::new().object::<&str, TypeDef>(inner_type_def())],
+        )
+    }
+}
+
+fn inner_type_def() -> BTreeMap<&'static str, TypeDef> {
+    map! {
+        "id": TypeDef::new().bytes(),
+        "timestamp": TypeDef::new().timestamp(),
+        "sequence": TypeDef::new().integer(),
+        "records": TypeDef::new().array_mapped::<(), Kind>(map![(): Kind::Object]),
+    }
+}
+
+/// All records of one audit event, which share the same `msg=audit(...)` identifier.
+struct Event {
+    id: String,
+    timestamp: Value,
+    sequence: i64,
+    records: Vec<BTreeMap<String, Value>>,
+}
+
+impl From<Event> for Value {
+    fn from(event: Event) -> Self {
+        let mut map = BTreeMap::new();
+        map.insert("id".to_owned(), event.id.into());
+        map.insert("timestamp".to_owned(), event.timestamp);
+        map.insert("sequence".to_owned(), event.sequence.into());
+        map.insert(
+            "records".to_owned(),
+            event
+                .records
+                .into_iter()
+                .map(Value::from)
+                .collect::<Vec<_>>()
+                .into(),
+        );
+        map.into()
+    }
+}
+
+struct Record {
+    id: String,
+    timestamp: Value,
+    sequence: i64,
+    fields: BTreeMap<String, Value>,
+}
+
+/// Fields whose values auditd hex-encodes, leaving them unquoted, when they
+/// contain spaces, quotes, or control characters. Other fields, such as the
+/// numeric `old` and `new` of `CONFIG_CHANGE` records, are never encoded.
+const ENCODED_FIELDS: &[&str] = &[
+    "acct",
+    "cmd",
+    "comm",
+    "cwd",
+    "data",
+    "dir",
+    "exe",
+    "file",
+    "key",
+    "name",
+    "ocomm",
+    "path",
+    "proctitle",
+    "vm",
+];
+
+/// The separator between raw and interpreted fields in `log_format = ENRICHED` records.
+const ENRICHED_SEPARATOR: char = '\u{1d}';
+
+fn parse_record(line: &str) -> std::result::Result<Record, String> {
+    let invalid = || format!("unable to parse auditd record: {}", line);
+
+    let (raw, enriched) = match line.split_once(ENRICHED_SEPARATOR) {
+        Some((raw, enriched)) => (raw, Some(enriched)),
+        None => (line, None),
+    };
+
+    let pairs = tokenize(raw);
+    let execve = pairs
+        .iter()
+        .any(|pair| *pair == ("type", Token::Bare("EXECVE")));
+
+    let mut fields = BTreeMap::new();
+    let mut header = None;
+    let mut argv = BTreeMap::new();
+
+    for (key, value) in pairs {
+        match (key, value) {
+            ("msg", Token::Bare(msg)) if msg.starts_with("audit(") => {
+                header = Some(parse_header(msg).ok_or_else(invalid)?);
+            }
+            // Messages from user space carry their own key/value list, e.g. `msg='op=login res=success'`.
+            ("msg", Token::SingleQuoted(msg)) => {
+                for (key, value) in tokenize(msg) {
+                    fields.insert(key.to_owned(), decode(key, value, false));
+                }
+            }
+            (key, value) => {
+                let decoded = decode(key, value, execve);
+                if let Some(index) = execve.then(|| execve_argument_index(key)).flatten() {
+                    let arg = argv.entry(index).or_insert_with(String::new);
+                    if let Value::Bytes(bytes) = &decoded {
+                        arg.push_str(&String::from_utf8_lossy(bytes));
+                    }
+                }
+                if !key.contains('[') {
+                    fields.insert(key.to_owned(), decoded);
+                }
+            }
+        }
+    }
+
+    if let Some(enriched) = enriched {
+        for (key, value) in tokenize(enriched) {
+            fields.insert(key.to_owned(), decode(key, value, false));
+        }
+    }
+
+    let (id, timestamp, sequence) = header.ok_or_else(invalid)?;
+    if !fields.contains_key("type") {
+        return Err(invalid());
+    }
+
+    if execve {
+        // Arguments too long for a single field are split into `aN[0]`, `aN[1]`, ...
+        // and are only complete once joined back together.
+        for (index, arg) in &argv {
+            fields.insert(format!("a{}", index), arg.clone().into());
+        }
+        fields.retain(|key, _| !(key.starts_with('a') && key.ends_with("_len")));
+        fields.insert(
+            "argv".to_owned(),
+            argv.into_values()
+                .map(Value::from)
+                .collect::<Vec<_>>()
+                .into(),
+        );
+    }
+
+    Ok(Record {
+        id,
+        timestamp,
+        sequence,
+        fields,
+    })
+}
+
+/// Parse the `audit(1364481363.243:24287)` event identifier into its
+/// identifier, timestamp, and sequence number.
+fn parse_header(msg: &str) -> Option<(String, Value, i64)> {
+    let id = msg.strip_prefix("audit(")?.strip_suffix("):")?;
+    let (timestamp, sequence) = id.split_once(':')?;
+    let (secs, millis) = timestamp.split_once('.')?;
+    let nanos = millis.parse::<u32>().ok()?.checked_mul(1_000_000)?;
+    let timestamp = Utc.timestamp_opt(secs.parse().ok()?, nanos).single()?;
+
+    Some((id.to_owned(), timestamp.into(), sequence.parse().ok()?))
+}
+
+/// Returns the argument index for `aN` and `aN[M]` keys.
+fn execve_argument_index(key: &str) -> Option<usize> {
+    let index = key.strip_prefix('a')?;
+    let index = index.split_once('[').map_or(index, |(index, _)| index);
+    index.parse().ok()
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Bare(&'a str),
+    DoubleQuoted(&'a str),
+    SingleQuoted(&'a str),
+}
+
+/// Split a record into `key=value` pairs. Values may be bare, double-quoted,
+/// or single-quoted; auditd never escapes quotes inside quoted values.
+fn tokenize(s: &str) -> Vec<(&str, Token<'_>)> {
+    let mut pairs = Vec::new();
+    let mut rest = s.trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or_else(|| rest.len());
+        let key = &rest[..key_end];
+        rest = &rest[key_end..];
+
+        if let Some(value) = rest.strip_prefix('=') {
+            let (token, remainder) = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let value = &value[1..];
+                    let end = value.find(quote).unwrap_or_else(|| value.len());
+                    let token = if quote == '"' {
+                        Token::DoubleQuoted(&value[..end])
+                    } else {
+                        Token::SingleQuoted(&value[..end])
+                    };
+                    (token, value.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = value
+                        .find(char::is_whitespace)
+                        .unwrap_or_else(|| value.len());
+                    (Token::Bare(&value[..end]), &value[end..])
+                }
+            };
+            pairs.push((key, token));
+            rest = remainder;
+        }
+
+        rest = rest.trim_start();
+    }
+
+    pairs
+}
+
+fn decode(key: &str, token: Token<'_>, execve: bool) -> Value {
+    match token {
+        Token::DoubleQuoted(value) | Token::SingleQuoted(value) => value.into(),
+        Token::Bare("(null)") => Value::Null,
+        Token::Bare(value)
+            if is_hex(value)
+                && (ENCODED_FIELDS.contains(&key)
+                    || (execve && execve_argument_index(key).is_some())) =>
+        {
+            match hex::decode(value) {
+                // `proctitle` separates the arguments with NUL bytes.
+                Ok(bytes) => String::from_utf8_lossy(&bytes).replace('\0', " ").into(),
+                Err(_) => value.into(),
+            }
+        }
+        Token::Bare(value) => value.into(),
+    }
+}
+
+/// Whether `value` could be hex-encoded by auditd, which always encodes whole bytes.
+fn is_hex(value: &str) -> bool {
+    !value.is_empty() && value.len() % 2 == 0 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_def() -> TypeDef {
+        TypeDef::new().fallible().array_mapped::<(), TypeDef>(
+            map![(): TypeDef::new().object::<&str, TypeDef>(inner_type_def())],
+        )
+    }
+
+    fn timestamp() -> Value {
+        Utc.timestamp(1364481363, 243_000_000).into()
+    }
+
+    test_function![
+        parse_auditd => ParseAuditd;
+
+        syscall {
+            args: func_args![value: r#"type=SYSCALL msg=audit(1364481363.243:24287): arch=c000003e syscall=2 success=no exit=-13 a0=7fffd19c5592 a1=0 ppid=2686 pid=3538 auid=1000 uid=1000 tty=pts0 comm="cat" exe="/usr/bin/cat" key="sshd_config""#],
+            want: Ok(value!([{
+                "id": "1364481363.243:24287",
+                "timestamp": (timestamp()),
+                "sequence": 24287,
+                "records": [{
+                    "type": "SYSCALL",
+                    "arch": "c000003e",
+                    "syscall": "2",
+                    "success": "no",
+                    "exit": "-13",
+                    "a0": "7fffd19c5592",
+                    "a1": "0",
+                    "ppid": "2686",
+                    "pid": "3538",
+                    "auid": "1000",
+                    "uid": "1000",
+                    "tty": "pts0",
+                    "comm": "cat",
+                    "exe": "/usr/bin/cat",
+                    "key": "sshd_config",
+                }],
+            }])),
+            tdef: type_def(),
+        }
+
+        joined_by_id {
+            args: func_args![value: vec![
+                r#"type=SYSCALL msg=audit(1364481363.243:24287): syscall=59 success=yes comm=6D7920636F6D6D exe="/usr/bin/ls" key=(null)"#,
+                r#"type=EXECVE msg=audit(1364481363.243:24287): argc=3 a0="ls" a1="-l" a2=2F746D702F6120622063"#,
+                r#"type=CWD msg=audit(1364481363.243:24287): cwd="/root""#,
+                r#"type=PROCTITLE msg=audit(1364481363.243:24288): proctitle=6C73002D6C"#,
+            ]],
+            want: Ok(value!([
+                {
+                    "id": "1364481363.243:24287",
+                    "timestamp": (timestamp()),
+                    "sequence": 24287,
+                    "records": [
+                        {
+                            "type": "SYSCALL",
+                            "syscall": "59",
+                            "success": "yes",
+                            "comm": "my comm",
+                            "exe": "/usr/bin/ls",
+                            "key": null,
+                        },
+                        {
+                            "type": "EXECVE",
+                            "argc": "3",
+                            "a0": "ls",
+                            "a1": "-l",
+                            "a2": "/tmp/a b c",
+                            "argv": ["ls", "-l", "/tmp/a b c"],
+                        },
+                        {
+                            "type": "CWD",
+                            "cwd": "/root",
+                        },
+                    ],
+                },
+                {
+                    "id": "1364481363.243:24288",
+                    "timestamp": (timestamp()),
+                    "sequence": 24288,
+                    "records": [{
+                        "type": "PROCTITLE",
+                        "proctitle": "ls -l",
+                    }],
+                },
+            ])),
+            tdef: type_def(),
+        }
+
+        split_execve_argument {
+            args: func_args![value: r#"type=EXECVE msg=audit(1364481363.243:24287): argc=2 a0="echo" a1_len=10 a1[0]=68656C6C6F a1[1]=776F726C64"#],
+            want: Ok(value!([{
+                "id": "1364481363.243:24287",
+                "timestamp": (timestamp()),
+                "sequence": 24287,
+                "records": [{
+                    "type": "EXECVE",
+                    "argc": "2",
+                    "a0": "echo",
+                    "a1": "helloworld",
+                    "argv": ["echo", "helloworld"],
+                }],
+            }])),
+            tdef: type_def(),
+        }
+
+        user_message {
+            args: func_args![value: r#"node=host1 type=USER_LOGIN msg=audit(1364481363.243:24287): pid=1 uid=0 msg='op=login acct="root" exe="/usr/sbin/sshd" res=success'"#],
+            want: Ok(value!([{
+                "id": "1364481363.243:24287",
+                "timestamp": (timestamp()),
+                "sequence": 24287,
+                "records": [{
+                    "node": "host1",
+                    "type": "USER_LOGIN",
+                    "pid": "1",
+                    "uid": "0",
+                    "op": "login",
+                    "acct": "root",
+                    "exe": "/usr/sbin/sshd",
+                    "res": "success",
+                }],
+            }])),
+            tdef: type_def(),
+        }
+
+        enriched {
+            args: func_args![value: "type=SYSCALL msg=audit(1364481363.243:24287): arch=c000003e syscall=2\u{1d}ARCH=x86_64 SYSCALL=open"],
+            want: Ok(value!([{
+                "id": "1364481363.243:24287",
+                "timestamp": (timestamp()),
+                "sequence": 24287,
+                "records": [{
+                    "type": "SYSCALL",
+                    "arch": "c000003e",
+                    "syscall": "2",
+                    "ARCH": "x86_64",
+                    "SYSCALL": "open",
+                }],
+            }])),
+            tdef: type_def(),
+        }
+
+        numeric_fields {
+            args: func_args![value: r#"type=CONFIG_CHANGE msg=audit(1364481363.243:24287): auid=1000 ses=2 op=set audit_backlog_limit=8192 old=64 new=8192 res=1"#],
+            want: Ok(value!([{
+                "id": "1364481363.243:24287",
+                "timestamp": (timestamp()),
+                "sequence": 24287,
+                "records": [{
+                    "type": "CONFIG_CHANGE",
+                    "auid": "1000",
+                    "ses": "2",
+                    "op": "set",
+                    "audit_backlog_limit": "8192",
+                    "old": "64",
+                    "new": "8192",
+                    "res": "1",
+                }],
+            }])),
+            tdef: type_def(),
+        }
+
+        odd_length_hex {
+            args: func_args![value: r#"type=PATH msg=audit(1364481363.243:24287): item=0 name=ABC"#],
+            want: Ok(value!([{
+                "id": "1364481363.243:24287",
+                "timestamp": (timestamp()),
+                "sequence": 24287,
+                "records": [{
+                    "type": "PATH",
+                    "item": "0",
+                    "name": "ABC",
+                }],
+            }])),
+            tdef: type_def(),
+        }
+
+        overflowing_millis {
+            args: func_args![value: "type=SYSCALL msg=audit(1364481363.4295:24287): syscall=2"],
+            want: Err("unable to parse auditd record: type=SYSCALL msg=audit(1364481363.4295:24287): syscall=2"),
+            tdef: type_def(),
+        }
+
+        invalid {
+            args: func_args![value: "not an audit record"],
+            want: Err("unable to parse auditd record: not an audit record"),
+            tdef: type_def(),
+        }
+    ];
+}
@@ -0,0 +1,215 @@
+use std::{collections::BTreeMap, io::Read};
+
+use flate2::read::MultiGzDecoder;
+use shared::aws_cloudwatch_logs_subscription::{
+    AwsCloudWatchLogsSubscriptionMessage, AwsCloudWatchLogsSubscriptionMessageType,
+};
+use vrl::prelude::*;
+
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseAwsFirehoseCloudWatchLogs;
+
+impl Function for ParseAwsFirehoseCloudWatchLogs {
+    fn identifier(&self) -> &'static str {
+        "parse_aws_firehose_cloudwatch_logs"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "firehose record",
+            source: r#"parse_aws_firehose_cloudwatch_logs!("H4sIAAAAAAACA3WOTQuCQBCG/8ucDdw+BL0JmqdOeguJTSdZ0F3ZGYsQ/3urJXhpbu/Hw7wjdEgkGyzePUIESVzEt0ua53GWggfmpdE6W2zO2a1pMmuG3iWMxLtmEYufs0XZrQF9lQc03Kmyqmdl9Fm1jJYgukLiSkrL2YVy4dMnap6zEVQ9f3YwKzeSZef+icD3hfBPQegfQm8d73oPZYlh8n7Y/i92FFuMsDK6hqmcPqgHcn4KAQAA")"#,
+            result: Ok(indoc! {r#"[
+                {
+                    "id": "1",
+                    "log_group": "test-group",
+                    "log_stream": "test-stream",
+                    "message": "first",
+                    "owner": "111111111111",
+                    "subscription_filters": ["Destination"],
+                    "timestamp": "2020-09-14T19:09:29.039Z"
+                },
+                {
+                    "id": "2",
+                    "log_group": "test-group",
+                    "log_stream": "test-stream",
+                    "message": "second",
+                    "owner": "111111111111",
+                    "subscription_filters": ["Destination"],
+                    "timestamp": "2020-09-14T19:09:29.041Z"
+                }
+            ]"#}),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseAwsFirehoseCloudWatchLogsFn { value }))
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseAwsFirehoseCloudWatchLogsFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseAwsFirehoseCloudWatchLogsFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?.try_bytes()?;
+        let data = unwrap_record(&bytes)?;
+
+        let mut events = Vec::new();
+        // Firehose may deliver several subscription messages in one record, one after the other.
+        for message in serde_json::Deserializer::from_slice(&data)
+            .into_iter::<AwsCloudWatchLogsSubscriptionMessage>()
+        {
+            let message = message.map_err(|e| format!("unable to parse: {}", e))?;
+            // Control messages only check that the destination is reachable.
+            if matches!(
+                message.message_type,
+                AwsCloudWatchLogsSubscriptionMessageType::ControlMessage
+            ) {
+                continue;
+            }
+
+            events.extend(message.log_events.into_iter().map(|event| {
+                Value::from(map![
+                    "id": event.id,
+                    "timestamp": event.timestamp,
+                    "message": event.message,
+                    "owner": message.owner.clone(),
+                    "log_group": message.log_group.clone(),
+                    "log_stream": message.log_stream.clone(),
+                    "subscription_filters": message.subscription_filters.clone(),
+                ])
+            }));
+        }
+
+        Ok(events.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new()
+            .fallible() // Decoding or parsing error
+            .array_mapped::<(), TypeDef>(map! {
+                (): TypeDef::new().object::<&str, TypeDef>(inner_type_def())
+            })
+    }
+}
+
+/// Undoes the encoding of the record data, which is base64 encoded and gzip compressed as
+/// delivered by Firehose, but may already have been decoded, or also decompressed, upstream.
+fn unwrap_record(bytes: &[u8]) -> std::result::Result<Vec<u8>, String> {
+    let compressed = if bytes.starts_with(b"{") || bytes.starts_with(GZIP_MAGIC) {
+        bytes.to_vec()
+    } else {
+        base64::decode(bytes).map_err(|_| "unable to decode value from base64".to_owned())?
+    };
+
+    if compressed.starts_with(GZIP_MAGIC) {
+        let mut data = Vec::new();
+        MultiGzDecoder::new(&compressed[..])
+            .read_to_end(&mut data)
+            .map_err(|e| format!("unable to decompress value: {}", e))?;
+        Ok(data)
+    } else {
+        Ok(compressed)
+    }
+}
+
+fn inner_type_def() -> BTreeMap<&'static str, TypeDef> {
+    map! {
+        "id": Kind::Bytes,
+        "timestamp": Kind::Timestamp,
+        "message": Kind::Bytes,
+        "owner": Kind::Bytes,
+        "log_group": Kind::Bytes,
+        "log_stream": Kind::Bytes,
+        "subscription_filters": TypeDef::new().array_mapped::<(), Kind>(map! {
+            (): Kind::Bytes
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn tdef() -> TypeDef {
+        TypeDef::new().fallible().array_mapped::<(), TypeDef>(map! {
+            (): TypeDef::new().object::<&str, TypeDef>(inner_type_def())
+        })
+    }
+
+    fn event(id: &str, timestamp: i64, message: &str) -> Value {
+        map![
+            "id": id,
+            "timestamp": Utc.timestamp_millis(timestamp),
+            "message": message,
+            "owner": "111111111111",
+            "log_group": "test-group",
+            "log_stream": "test-stream",
+            "subscription_filters": vec!["Destination"],
+        ]
+        .into()
+    }
+
+    test_function![
+        parse_aws_firehose_cloudwatch_logs => ParseAwsFirehoseCloudWatchLogs;
+
+        base64_gzip {
+            args: func_args![value: "H4sIAAAAAAACA3WOTQuCQBCG/8ucDdw+BL0JmqdOeguJTSdZ0F3ZGYsQ/3urJXhpbu/Hw7wjdEgkGyzePUIESVzEt0ua53GWggfmpdE6W2zO2a1pMmuG3iWMxLtmEYufs0XZrQF9lQc03Kmyqmdl9Fm1jJYgukLiSkrL2YVy4dMnap6zEVQ9f3YwKzeSZef+icD3hfBPQegfQm8d73oPZYlh8n7Y/i92FFuMsDK6hqmcPqgHcn4KAQAA"],
+            want: Ok(vec![
+                event("1", 1600110569039, "first"),
+                event("2", 1600110569041, "second"),
+            ]),
+            tdef: tdef(),
+        }
+
+        concatenated_members {
+            args: func_args![value: "H4sIAAAAAAACA3WOTQuCQBCG/8ucDdw+BL0JmqdOeguJTSdZ0F3ZGYsQ/3urJXhpbu/Hw7wjdEgkGyzePUIESVzEt0ua53GWggfmpdE6W2zO2a1pMmuG3iWMxLtmEYufs0XZrQF9lQc03Kmyqmdl9Fm1jJYgukLiSkrL2YVy4dMnap6zEVQ9f3YwKzeSZef+icD3hfBPQegfQm8d73oPZYlh8n7Y/i92FFuMsDK6hqmcPqgHcn4KAQAAH4sIAAAAAAACA3WOTQuCQBCG/8ucDXbRAr0JmqdOdosIs0kWdFd2xiLE/95oCV2a2/vxzMwIHRJVDR5fPUICWXpML4e8LNMihwDc06IXW/+M2K1rCu+GXhJG4k2ziMUv2WPVrQF9VAA0XKn2pmfj7N60jJ4gOUEmJWOr2YXzwucPtDxnI5ibrAkFZiNPctXJPb1TSmu13cUqjIP1eendjSeGKfhi0V8s0r8YYe3sDabz9AZHGSm2CgEAAA=="],
+            want: Ok(vec![
+                event("1", 1600110569039, "first"),
+                event("2", 1600110569041, "second"),
+                event("3", 1600110569039, "first"),
+                event("4", 1600110569041, "second"),
+            ]),
+            tdef: tdef(),
+        }
+
+        plain_json {
+            args: func_args![value: r#"{"messageType":"DATA_MESSAGE","owner":"111111111111","logGroup":"test-group","logStream":"test-stream","subscriptionFilters":["Destination"],"logEvents":[{"id":"1","timestamp":1600110569039,"message":"first"}]}"#],
+            want: Ok(vec![event("1", 1600110569039, "first")]),
+            tdef: tdef(),
+        }
+
+        control_message {
+            args: func_args![value: "H4sIAAAAAAACAzWO0QqCMBiFX2XsOmJSFHkXot5YQgpdhMTSv22km2wzCfHdm2mXH+dwzjfgBoyhDPJPC9jHQXrOL2lyP4VZdoxDvMKql6CnpFZd1VNb8kQx44JasVirrnXZTJnVQJsZTfcwpRatFUpGoragDfZvxa8XvkHaCQcsqrluhdOwtHFj3o4QzyOEbPaH7eqvNwlcE7TooUXPRwGH8iUkQxxobTlST1S5JSHp9IwioYErA2s8FuMXGDFzzuwAAAA="],
+            want: Ok(Vec::<Value>::new()),
+            tdef: tdef(),
+        }
+
+        invalid_base64 {
+            args: func_args![value: "not base64!"],
+            want: Err("unable to decode value from base64"),
+            tdef: tdef(),
+        }
+    ];
+}
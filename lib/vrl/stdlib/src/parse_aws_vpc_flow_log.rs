@@ -116,12 +116,25 @@ fn inner_type_def() -> BTreeMap<&'static str, Kind> {
         "bytes": Kind::Integer | Kind::Null,
         "dstaddr": Kind::Bytes | Kind::Null,
         "dstport": Kind::Integer | Kind::Null,
+        "ecs_cluster_arn": Kind::Bytes | Kind::Null,
+        "ecs_cluster_name": Kind::Bytes | Kind::Null,
+        "ecs_container_id": Kind::Bytes | Kind::Null,
+        "ecs_container_instance_arn": Kind::Bytes | Kind::Null,
+        "ecs_container_instance_id": Kind::Bytes | Kind::Null,
+        "ecs_second_container_id": Kind::Bytes | Kind::Null,
+        "ecs_service_name": Kind::Bytes | Kind::Null,
+        "ecs_task_arn": Kind::Bytes | Kind::Null,
+        "ecs_task_definition_arn": Kind::Bytes | Kind::Null,
+        "ecs_task_id": Kind::Bytes | Kind::Null,
         "end": Kind::Integer | Kind::Null,
+        "flow_direction": Kind::Bytes | Kind::Null,
         "instance_id": Kind::Bytes | Kind::Null,
         "interface_id": Kind::Bytes | Kind::Null,
         "log_status": Kind::Bytes | Kind::Null,
         "packets": Kind::Integer | Kind::Null,
+        "pkt_dst_aws_service": Kind::Bytes | Kind::Null,
         "pkt_dstaddr": Kind::Bytes | Kind::Null,
+        "pkt_src_aws_service": Kind::Bytes | Kind::Null,
         "pkt_srcaddr": Kind::Bytes | Kind::Null,
         "protocol": Kind::Integer | Kind::Null,
         "region": Kind::Bytes | Kind::Null,
@@ -132,6 +145,7 @@ fn inner_type_def() -> BTreeMap<&'static str, Kind> {
         "sublocation_type": Kind::Bytes | Kind::Null,
         "subnet_id": Kind::Bytes | Kind::Null,
         "tcp_flags": Kind::Integer | Kind::Null,
+        "traffic_path": Kind::Integer | Kind::Null,
         "type": Kind::Bytes | Kind::Null,
         "version": Kind::Integer | Kind::Null,
         "vpc_id": Kind::Bytes | Kind::Null,
@@ -140,6 +154,41 @@ fn inner_type_def() -> BTreeMap<&'static str, Kind> {
 
 type ParseResult<T> = std::result::Result<T, String>;
 
+const DEFAULT_FORMAT: &str = "version account_id interface_id srcaddr dstaddr srcport dstport protocol packets bytes start end action log_status";
+
+/// The version of the flow log format each field was added in, for fields added after version 2.
+/// A record's `version` is the highest version of the fields it was published with, so it must
+/// be at least the version of every field in the format.
+///
+/// https://docs.aws.amazon.com/vpc/latest/userguide/flow-logs.html#flow-logs-fields
+const FIELD_VERSIONS: &[(&str, i64)] = &[
+    ("vpc_id", 3),
+    ("subnet_id", 3),
+    ("instance_id", 3),
+    ("tcp_flags", 3),
+    ("type", 3),
+    ("pkt_srcaddr", 3),
+    ("pkt_dstaddr", 3),
+    ("region", 4),
+    ("az_id", 4),
+    ("sublocation_type", 4),
+    ("sublocation_id", 4),
+    ("pkt_src_aws_service", 5),
+    ("pkt_dst_aws_service", 5),
+    ("flow_direction", 5),
+    ("traffic_path", 5),
+    ("ecs_cluster_arn", 7),
+    ("ecs_cluster_name", 7),
+    ("ecs_container_instance_arn", 7),
+    ("ecs_container_instance_id", 7),
+    ("ecs_container_id", 7),
+    ("ecs_second_container_id", 7),
+    ("ecs_service_name", 7),
+    ("ecs_task_definition_arn", 7),
+    ("ecs_task_arn", 7),
+    ("ecs_task_id", 7),
+];
+
 #[allow(clippy::unnecessary_wraps)] // match other parse methods
 fn identity<'a>(_key: &'a str, value: &'a str) -> ParseResult<&'a str> {
     Ok(value)
@@ -168,31 +217,75 @@ macro_rules! create_match {
     };
 }
 
+/// Normalize a field of the format, which can be given as it is in the flow log's configuration,
+/// such as `${pkt-srcaddr}`, or as the name of the parsed field, such as `pkt_srcaddr`.
+fn field_name(field: &str) -> String {
+    field
+        .strip_prefix("${")
+        .and_then(|field| field.strip_suffix('}'))
+        .unwrap_or(field)
+        .replace('-', "_")
+}
+
+fn check_versions(log: &BTreeMap<String, Value>) -> ParseResult<()> {
+    let version = match log.get("version") {
+        Some(Value::Integer(version)) => *version,
+        _ => return Ok(()),
+    };
+
+    match FIELD_VERSIONS
+        .iter()
+        .find(|(field, field_version)| *field_version > version && log.contains_key(*field))
+    {
+        Some((field, field_version)) => Err(format!(
+            "field `{}` requires version {}, but the record is version {}",
+            field, field_version, version
+        )),
+        None => Ok(()),
+    }
+}
+
 fn parse_log(input: &str, format: Option<&str>) -> ParseResult<Value> {
     let mut log = BTreeMap::new();
 
-    let mut input = input.split(' ');
+    // Records delivered through Firehose or CloudWatch Logs can have trailing newlines, and
+    // custom formats are often written with more than one space between fields.
+    let mut input = input.split_ascii_whitespace();
     let mut format = format
-        .unwrap_or("version account_id interface_id srcaddr dstaddr srcport dstport protocol packets bytes start end action log_status")
-        .split(' ');
+        .unwrap_or(DEFAULT_FORMAT)
+        .split_ascii_whitespace()
+        .map(field_name);
 
     loop {
         return match (format.next(), input.next()) {
             (Some(key), Some(value)) => {
                 create_match!(
-                    log, key, value,
+                    log, key.as_str(), value,
                     "account_id" => parse_i64,
                     "action" => identity,
                     "az_id" => identity,
                     "bytes" => parse_i64,
                     "dstaddr" => identity,
                     "dstport" => parse_i64,
+                    "ecs_cluster_arn" => identity,
+                    "ecs_cluster_name" => identity,
+                    "ecs_container_id" => identity,
+                    "ecs_container_instance_arn" => identity,
+                    "ecs_container_instance_id" => identity,
+                    "ecs_second_container_id" => identity,
+                    "ecs_service_name" => identity,
+                    "ecs_task_arn" => identity,
+                    "ecs_task_definition_arn" => identity,
+                    "ecs_task_id" => identity,
                     "end" => parse_i64,
+                    "flow_direction" => identity,
                     "instance_id" => identity,
                     "interface_id" => identity,
                     "log_status" => identity,
                     "packets" => parse_i64,
+                    "pkt_dst_aws_service" => identity,
                     "pkt_dstaddr" => identity,
+                    "pkt_src_aws_service" => identity,
                     "pkt_srcaddr" => identity,
                     "protocol" => parse_i64,
                     "region" => identity,
@@ -203,6 +296,7 @@ fn parse_log(input: &str, format: Option<&str>) -> ParseResult<Value> {
                     "sublocation_type" => identity,
                     "subnet_id" => identity,
                     "tcp_flags" => parse_i64,
+                    "traffic_path" => parse_i64,
                     "type" => identity,
                     "version" => parse_i64,
                     "vpc_id" => identity
@@ -212,7 +306,7 @@ fn parse_log(input: &str, format: Option<&str>) -> ParseResult<Value> {
             }
             (None, Some(value)) => Err(format!("no key for value: `{}`", value)),
             (Some(key), None) => Err(format!("no item for key: `{}`", key)),
-            (None, None) => Ok(log.into()),
+            (None, None) => check_versions(&log).map(|_| log.into()),
         };
     }
 }
@@ -326,5 +420,86 @@ mod tests {
              })),
              tdef: TypeDef::new().fallible().object::<&str, Kind>(inner_type_def()),
          }
+
+        skipdata {
+             args: func_args![value: "2 123456789010 eni-11111111aaaaaaaaa - - - - - - - 1431280876 1431280934 - SKIPDATA\n"],
+             want: Ok(value!({
+                 "account_id": 123456789010_i64,
+                 "action": null,
+                 "bytes": null,
+                 "dstaddr": null,
+                 "dstport": null,
+                 "end": 1431280934,
+                 "interface_id": "eni-11111111aaaaaaaaa",
+                 "log_status": "SKIPDATA",
+                 "packets": null,
+                 "protocol": null,
+                 "srcaddr": null,
+                 "srcport": null,
+                 "start": 1431280876,
+                 "version": 2
+             })),
+             tdef: TypeDef::new().fallible().object::<&str, Kind>(inner_type_def()),
+         }
+
+        aws_format_v5 {
+             args: func_args![value: "5 52.95.128.179 10.0.0.71 80 34210 6 1616729292 1616729349 IPv4 14 15044 123456789012 vpc-abcdefab012345678 subnet-aaaaaaaa012345678 i-0c50d5961bcb2d47b eni-1235b8ca123456789 ap-southeast-2 apse2-az3 - - ACCEPT 19 52.95.128.179 10.0.0.71 S3 - - ingress OK",
+                              format: "${version} ${srcaddr} ${dstaddr} ${srcport} ${dstport} ${protocol} ${start} ${end} ${type} ${packets} ${bytes} ${account-id} ${vpc-id} ${subnet-id} ${instance-id} ${interface-id} ${region} ${az-id} ${sublocation-type} ${sublocation-id} ${action} ${tcp-flags} ${pkt-srcaddr} ${pkt-dstaddr} ${pkt-src-aws-service} ${pkt-dst-aws-service} ${traffic-path} ${flow-direction} ${log-status}"],
+             want: Ok(value!({
+                 "account_id": 123456789012_i64,
+                 "action": "ACCEPT",
+                 "az_id": "apse2-az3",
+                 "bytes": 15044,
+                 "dstaddr": "10.0.0.71",
+                 "dstport": 34210,
+                 "end": 1616729349,
+                 "flow_direction": "ingress",
+                 "instance_id": "i-0c50d5961bcb2d47b",
+                 "interface_id": "eni-1235b8ca123456789",
+                 "log_status": "OK",
+                 "packets": 14,
+                 "pkt_dst_aws_service": null,
+                 "pkt_dstaddr": "10.0.0.71",
+                 "pkt_src_aws_service": "S3",
+                 "pkt_srcaddr": "52.95.128.179",
+                 "protocol": 6,
+                 "region": "ap-southeast-2",
+                 "srcaddr": "52.95.128.179",
+                 "srcport": 80,
+                 "start": 1616729292,
+                 "subnet_id": "subnet-aaaaaaaa012345678",
+                 "sublocation_id": null,
+                 "sublocation_type": null,
+                 "tcp_flags": 19,
+                 "traffic_path": null,
+                 "type": "IPv4",
+                 "version": 5,
+                 "vpc_id": "vpc-abcdefab012345678"
+             })),
+             tdef: TypeDef::new().fallible().object::<&str, Kind>(inner_type_def()),
+         }
+
+        ecs_v7 {
+             args: func_args![value: "7 eni-1235b8ca123456789 arn:aws:ecs:us-east-1:123456789012:cluster/web web 0f3c4b1e2d web-service arn:aws:ecs:us-east-1:123456789012:task/web/0f3c4b1e2d 0f3c4b1e2d",
+                              format: "version interface-id ecs-cluster-arn ecs-cluster-name ecs-container-id ecs-service-name ecs-task-arn ecs-task-id"],
+             want: Ok(value!({
+                 "ecs_cluster_arn": "arn:aws:ecs:us-east-1:123456789012:cluster/web",
+                 "ecs_cluster_name": "web",
+                 "ecs_container_id": "0f3c4b1e2d",
+                 "ecs_service_name": "web-service",
+                 "ecs_task_arn": "arn:aws:ecs:us-east-1:123456789012:task/web/0f3c4b1e2d",
+                 "ecs_task_id": "0f3c4b1e2d",
+                 "interface_id": "eni-1235b8ca123456789",
+                 "version": 7
+             })),
+             tdef: TypeDef::new().fallible().object::<&str, Kind>(inner_type_def()),
+         }
+
+        field_newer_than_version {
+             args: func_args![value: "3 eni-1235b8ca123456789 ingress",
+                              format: "version interface_id flow_direction"],
+             want: Err("field `flow_direction` requires version 5, but the record is version 3"),
+             tdef: TypeDef::new().fallible().object::<&str, Kind>(inner_type_def()),
+         }
     ];
 }
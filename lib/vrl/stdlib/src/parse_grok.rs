@@ -8,12 +8,14 @@ use vrl::{
 #[derive(Debug)]
 pub enum Error {
     InvalidGrokPattern(grok::Error),
+    InvalidPatternFile(String),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Error::InvalidGrokPattern(err) => write!(f, "{}", err),
+            Error::InvalidPatternFile(err) => write!(f, "{}", err),
         }
     }
 }
@@ -33,6 +35,12 @@ impl DiagnosticError for Error {
                     Span::default(),
                 )]
             }
+            Error::InvalidPatternFile(err) => {
+                vec![Label::primary(
+                    format!("grok pattern file error: {}", err),
+                    Span::default(),
+                )]
+            }
         }
     }
 }
@@ -62,6 +70,11 @@ impl Function for ParseGrok {
                 kind: kind::BOOLEAN,
                 required: false,
             },
+            Parameter {
+                keyword: "alias_sources",
+                kind: kind::ARRAY,
+                required: false,
+            },
         ]
     }
 
@@ -99,7 +112,44 @@ impl Function for ParseGrok {
             .expect("grok pattern not bytes")
             .into_owned();
 
+        let alias_sources = arguments
+            .optional_array("alias_sources")?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|expr| {
+                let path = expr
+                    .as_value()
+                    .ok_or(vrl::function::Error::ExpectedStaticExpression {
+                        keyword: "alias_sources",
+                        expr,
+                    })?
+                    .try_bytes_utf8_lossy()
+                    .expect("alias source not bytes")
+                    .into_owned();
+                Ok(path)
+            })
+            .collect::<std::result::Result<Vec<String>, vrl::function::Error>>()?;
+
+        let aliases = crate::util::read_grok_pattern_files(&alias_sources)
+            .map_err(|e| Box::new(Error::InvalidPatternFile(e)) as Box<dyn DiagnosticError>)?;
+
         let mut grok = grok::Grok::with_patterns();
+        for (name, pattern) in &aliases {
+            grok.insert_definition(name, pattern);
+        }
+
+        // Compile every loaded pattern, so that mistakes in pattern files are reported when the
+        // program is compiled, even if the pattern itself doesn't reference them.
+        for name in aliases.keys() {
+            grok.compile(&format!("%{{{}}}", name), false)
+                .map_err(|e| {
+                    Box::new(Error::InvalidPatternFile(format!(
+                        "pattern {:?} is invalid: {}",
+                        name, e
+                    ))) as Box<dyn DiagnosticError>
+                })?;
+        }
+
         let pattern = Arc::new(
             grok.compile(&pattern, true)
                 .map_err(|e| Box::new(Error::InvalidGrokPattern(e)) as Box<dyn DiagnosticError>)?,
@@ -228,5 +278,31 @@ mod test {
                 (): Kind::all(),
             }),
         }
+
+        alias_sources {
+            args: func_args![ value: "2020-10-02T23:22:12.223222Z info Hello world",
+                              pattern: "%{APP_PREFIX} %{GREEDYDATA:message}",
+                              alias_sources: vec![concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/grok/common.patterns")],
+            ],
+            want: Ok(Value::from(btreemap! {
+                "timestamp" => "2020-10-02T23:22:12.223222Z",
+                "level" => "info",
+                "message" => "Hello world",
+            })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! {
+                (): Kind::all(),
+            }),
+        }
+
+        invalid_alias_source {
+            args: func_args![ value: "foo",
+                              pattern: "%{GREEDYDATA:message}",
+                              alias_sources: vec![concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/grok/invalid.patterns")],
+            ],
+            want: Err("pattern \"APP_PREFIX\" is invalid: The given pattern definition name \"APP_TIMESTAMP\" could not be found in the definition map"),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! {
+                (): Kind::all(),
+            }),
+        }
     ];
 }
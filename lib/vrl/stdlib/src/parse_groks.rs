@@ -12,12 +12,14 @@ use vrl::{
 #[derive(Debug)]
 pub enum Error {
     InvalidGrokPattern(datadog_grok::parse_grok_rules::Error),
+    InvalidPatternFile(String),
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Error::InvalidGrokPattern(err) => write!(f, "{}", err),
+            Error::InvalidPatternFile(err) => write!(f, "{}", err),
         }
     }
 }
@@ -37,6 +39,12 @@ impl DiagnosticError for Error {
                     Span::default(),
                 )]
             }
+            Error::InvalidPatternFile(err) => {
+                vec![Label::primary(
+                    format!("grok pattern file error: {}", err),
+                    Span::default(),
+                )]
+            }
         }
     }
 }
@@ -71,6 +79,11 @@ impl Function for ParseGroks {
                 kind: kind::OBJECT,
                 required: false,
             },
+            Parameter {
+                keyword: "alias_sources",
+                kind: kind::ARRAY,
+                required: false,
+            },
         ]
     }
 
@@ -127,7 +140,29 @@ impl Function for ParseGroks {
             })
             .collect::<std::result::Result<Vec<String>, vrl::function::Error>>()?;
 
-        let aliases = arguments
+        let alias_sources = arguments
+            .optional_array("alias_sources")?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|expr| {
+                let path = expr
+                    .as_value()
+                    .ok_or(vrl::function::Error::ExpectedStaticExpression {
+                        keyword: "alias_sources",
+                        expr,
+                    })?
+                    .try_bytes_utf8_lossy()
+                    .expect("alias source not bytes")
+                    .into_owned();
+                Ok(path)
+            })
+            .collect::<std::result::Result<Vec<String>, vrl::function::Error>>()?;
+
+        // aliases defined inline take precedence over those loaded from files
+        let mut aliases = crate::util::read_grok_pattern_files(&alias_sources)
+            .map_err(|e| Box::new(Error::InvalidPatternFile(e)) as Box<dyn DiagnosticError>)?;
+
+        let inline_aliases = arguments
             .optional_object("aliases")?
             .unwrap_or_default()
             .into_iter()
@@ -146,6 +181,7 @@ impl Function for ParseGroks {
                 Ok((key, alias))
             })
             .collect::<std::result::Result<BTreeMap<String, String>, vrl::function::Error>>()?;
+        aliases.extend(inline_aliases);
 
         // we use a datadog library here because it is a superset of grok
         let grok_rules = parse_grok_rules::parse_grok_rules(&patterns, aliases)
@@ -362,5 +398,38 @@ mod test {
                 (): Kind::all(),
             }),
         }
+
+        alias_sources {
+            args: func_args![
+                value: "2020-10-02T23:22:12.223222Z info 200 hello world",
+                patterns: Value::Array(vec!["%{APP_PREFIX} %{_status} %{GREEDYDATA:message}".into()]),
+                aliases: value!({
+                    "_status": "%{POSINT:status}",
+                    "APP_LEVEL": "%{word:level}"
+                }),
+                alias_sources: vec![concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/grok/common.patterns")],
+            ],
+            want: Ok(Value::from(btreemap! {
+                "timestamp" => "2020-10-02T23:22:12.223222Z",
+                "level" => "info",
+                "status" => "200",
+                "message" => "hello world"
+            })),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! {
+                (): Kind::all(),
+            }),
+        }
+
+        missing_alias_source {
+            args: func_args![
+                value: "foo",
+                patterns: vec!["%{APP_PREFIX}"],
+                alias_sources: vec!["/nonexistent/grok.patterns"],
+            ],
+            want: Err("unable to read pattern file \"/nonexistent/grok.patterns\": No such file or directory (os error 2)"),
+            tdef: TypeDef::new().fallible().object::<(), Kind>(map! {
+                (): Kind::all(),
+            }),
+        }
     ];
 }
@@ -8,6 +8,10 @@ impl Function for ParseInt {
         "parse_int"
     }
 
+    fn is_pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
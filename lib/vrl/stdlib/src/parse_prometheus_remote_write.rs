@@ -0,0 +1,320 @@
+use std::collections::BTreeMap;
+
+use chrono::{TimeZone, Utc};
+use prometheus_parser::{proto, GroupKey, GroupKind, MetricGroup};
+use prost::Message;
+use vrl::prelude::*;
+
+/// Snappy blocks declare how large they decompress to, and the decoder allocates all of it up
+/// front, so requests declaring more than this are refused.
+const MAX_DECOMPRESSED_BYTES: usize = 32 * 1024 * 1024;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParsePrometheusRemoteWrite;
+
+impl Function for ParsePrometheusRemoteWrite {
+    fn identifier(&self) -> &'static str {
+        "parse_prometheus_remote_write"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "compressed",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "parse remote write request",
+            source: r#"parse_prometheus_remote_write!(decode_base64!("W/BaCkAKHwoIX19uYW1lX18SE2h0dHBfcmVxdWVzdHNfdG90YWwKCwoEY29kZRIDMjAwEhAJAAAAAAAMkEAQgKjIjvkuGhcIARITaHR0cF9yZXF1ZXN0c190b3RhbA=="))"#,
+            result: Ok(
+                r#"[{"name": "http_requests_total", "type": "counter", "labels": {"code": "200"}, "timestamp": "2021-02-11T16:00:00Z", "value": 1027.0}]"#,
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let compressed = arguments
+            .optional("compressed")
+            .unwrap_or_else(|| expr!(true));
+
+        Ok(Box::new(ParsePrometheusRemoteWriteFn { value, compressed }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParsePrometheusRemoteWriteFn {
+    value: Box<dyn Expression>,
+    compressed: Box<dyn Expression>,
+}
+
+impl Expression for ParsePrometheusRemoteWriteFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let bytes = self.value.resolve(ctx)?.try_bytes()?;
+        let compressed = self.compressed.resolve(ctx)?.try_boolean()?;
+
+        // Remote write requests are compressed with the snappy block format, unless the `http`
+        // source already decompressed them according to their `Content-Encoding` header.
+        let bytes = if compressed {
+            let length = snap::raw::decompress_len(&bytes)
+                .map_err(|error| format!("unable to decompress request: {}", error))?;
+            if length > MAX_DECOMPRESSED_BYTES {
+                return Err(format!(
+                    "unable to decompress request: it decompresses to {} bytes, more than the {} allowed",
+                    length, MAX_DECOMPRESSED_BYTES
+                )
+                .into());
+            }
+            snap::raw::Decoder::new()
+                .decompress_vec(&bytes)
+                .map_err(|error| format!("unable to decompress request: {}", error))?
+                .into()
+        } else {
+            bytes
+        };
+        let request = proto::WriteRequest::decode(bytes)
+            .map_err(|error| format!("unable to decode write request: {}", error))?;
+        let groups = prometheus_parser::parse_request(request)
+            .map_err(|error| format!("unable to parse write request: {}", error))?;
+
+        let mut metrics = Vec::new();
+        for group in groups {
+            add_group(&mut metrics, group)?;
+        }
+
+        Ok(metrics.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new()
+            .fallible()
+            .array_mapped::<(), Kind>(map! {(): Kind::Object})
+    }
+}
+
+/// Adds the metrics of `group` in the structure `encode_prometheus_text` takes, so that requests
+/// can be encoded back into the text format.
+fn add_group(metrics: &mut Vec<Value>, group: MetricGroup) -> std::result::Result<(), String> {
+    let MetricGroup {
+        name,
+        metrics: kind,
+    } = group;
+
+    match kind {
+        GroupKind::Counter(group) => add_simple(metrics, &name, "counter", group),
+        GroupKind::Gauge(group) => add_simple(metrics, &name, "gauge", group),
+        GroupKind::Untyped(group) => add_simple(metrics, &name, "untyped", group),
+        GroupKind::Histogram(group) => {
+            for (key, histogram) in group {
+                let mut metric = metric_object(&name, "histogram", key)?;
+                let buckets = histogram
+                    .buckets
+                    .into_iter()
+                    .map(|bucket| {
+                        value!({
+                            "upper_limit": (bucket.bucket),
+                            "count": (i64::from(bucket.count)),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                metric.insert("buckets".to_owned(), buckets.into());
+                metric.insert("sum".to_owned(), histogram.sum.into());
+                metric.insert("count".to_owned(), i64::from(histogram.count).into());
+                metrics.push(metric.into());
+            }
+            Ok(())
+        }
+        GroupKind::Summary(group) => {
+            for (key, summary) in group {
+                let mut metric = metric_object(&name, "summary", key)?;
+                let quantiles = summary
+                    .quantiles
+                    .into_iter()
+                    .map(|quantile| {
+                        value!({
+                            "quantile": (quantile.quantile),
+                            "value": (quantile.value),
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                metric.insert("quantiles".to_owned(), quantiles.into());
+                metric.insert("sum".to_owned(), summary.sum.into());
+                metric.insert("count".to_owned(), i64::from(summary.count).into());
+                metrics.push(metric.into());
+            }
+            Ok(())
+        }
+    }
+}
+
+fn add_simple(
+    metrics: &mut Vec<Value>,
+    name: &str,
+    kind: &str,
+    group: impl IntoIterator<Item = (GroupKey, prometheus_parser::SimpleMetric)>,
+) -> std::result::Result<(), String> {
+    for (key, simple) in group {
+        let mut metric = metric_object(name, kind, key)?;
+        metric.insert("value".to_owned(), simple.value.into());
+        metrics.push(metric.into());
+    }
+    Ok(())
+}
+
+fn metric_object(
+    name: &str,
+    kind: &str,
+    key: GroupKey,
+) -> std::result::Result<BTreeMap<String, Value>, String> {
+    let labels = key
+        .labels
+        .into_iter()
+        .map(|(name, value)| (name, value.into()))
+        .collect::<BTreeMap<_, Value>>();
+
+    let mut metric = BTreeMap::new();
+    metric.insert("name".to_owned(), name.into());
+    metric.insert("type".to_owned(), kind.into());
+    metric.insert("labels".to_owned(), labels.into());
+    if let Some(millis) = key.timestamp {
+        let timestamp = Utc
+            .timestamp_millis_opt(millis)
+            .single()
+            .ok_or_else(|| format!("invalid timestamp {}", millis))?;
+        metric.insert("timestamp".to_owned(), timestamp.into());
+    }
+    Ok(metric)
+}
+
+#[cfg(test)]
+mod tests {
+    use proto::MetricType;
+
+    use super::*;
+
+    fn request(series: &[(&[(&str, &str)], f64)], metadata: &[(&str, MetricType)]) -> Vec<u8> {
+        proto::WriteRequest {
+            timeseries: series
+                .iter()
+                .map(|(labels, value)| proto::TimeSeries {
+                    labels: labels
+                        .iter()
+                        .map(|(name, value)| proto::Label {
+                            name: (*name).to_owned(),
+                            value: (*value).to_owned(),
+                        })
+                        .collect(),
+                    samples: vec![proto::Sample {
+                        value: *value,
+                        timestamp: 1613059200000,
+                    }],
+                })
+                .collect(),
+            metadata: metadata
+                .iter()
+                .map(|(name, kind)| proto::MetricMetadata {
+                    r#type: *kind as i32,
+                    metric_family_name: (*name).to_owned(),
+                    help: String::new(),
+                    unit: String::new(),
+                })
+                .collect(),
+        }
+        .encode_to_vec()
+    }
+
+    fn compressed(request: Vec<u8>) -> Bytes {
+        snap::raw::Encoder::new()
+            .compress_vec(&request)
+            .unwrap()
+            .into()
+    }
+
+    test_function![
+        parse_prometheus_remote_write => ParsePrometheusRemoteWrite;
+
+        counter {
+            args: func_args![value: compressed(request(
+                &[(&[("__name__", "http_requests_total"), ("code", "200"), ("method", "post")], 1027.0)],
+                &[("http_requests_total", MetricType::Counter)],
+            ))],
+            want: Ok(value!([{
+                "name": "http_requests_total",
+                "type": "counter",
+                "labels": {"code": "200", "method": "post"},
+                "timestamp": (Utc.ymd(2021, 2, 11).and_hms(16, 0, 0)),
+                "value": 1027.0,
+            }])),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! {(): Kind::Object}),
+        }
+
+        histogram {
+            args: func_args![value: compressed(request(
+                &[
+                    (&[("__name__", "request_duration_seconds_bucket"), ("le", "0.5")], 3.0),
+                    (&[("__name__", "request_duration_seconds_bucket"), ("le", "1")], 5.0),
+                    (&[("__name__", "request_duration_seconds_bucket"), ("le", "+Inf")], 6.0),
+                    (&[("__name__", "request_duration_seconds_sum")], 2.5),
+                    (&[("__name__", "request_duration_seconds_count")], 6.0),
+                ],
+                &[("request_duration_seconds", MetricType::Histogram)],
+            ))],
+            want: Ok(value!([{
+                "name": "request_duration_seconds",
+                "type": "histogram",
+                "labels": {},
+                "timestamp": (Utc.ymd(2021, 2, 11).and_hms(16, 0, 0)),
+                "buckets": [
+                    {"upper_limit": 0.5, "count": 3},
+                    {"upper_limit": 1.0, "count": 5},
+                    {"upper_limit": (f64::INFINITY), "count": 6},
+                ],
+                "sum": 2.5,
+                "count": 6,
+            }])),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! {(): Kind::Object}),
+        }
+
+        untyped_uncompressed {
+            args: func_args![
+                value: Bytes::from(request(&[(&[("__name__", "temperature")], 21.5)], &[])),
+                compressed: false
+            ],
+            want: Ok(value!([{
+                "name": "temperature",
+                "type": "untyped",
+                "labels": {},
+                "timestamp": (Utc.ymd(2021, 2, 11).and_hms(16, 0, 0)),
+                "value": 21.5,
+            }])),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! {(): Kind::Object}),
+        }
+
+        missing_name {
+            args: func_args![value: compressed(request(&[(&[("code", "200")], 1.0)], &[]))],
+            want: Err("unable to parse write request: request is missing metric name label"),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! {(): Kind::Object}),
+        }
+
+        too_large {
+            args: func_args![value: Bytes::from_static(b"\xff\xff\xff\xff\x0f")],
+            want: Err("unable to decompress request: it decompresses to 4294967295 bytes, more than the 33554432 allowed"),
+            tdef: TypeDef::new().fallible().array_mapped::<(), Kind>(map! {(): Kind::Object}),
+        }
+    ];
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use semver::Version;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseSemver;
+
+impl Function for ParseSemver {
+    fn identifier(&self) -> &'static str {
+        "parse_semver"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "lenient",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "parse version",
+                source: r#"parse_semver!("1.2.3-rc.1+build.5")"#,
+                result: Ok(
+                    r#"{"build": "build.5", "major": 1, "minor": 2, "patch": 3, "pre": "rc.1"}"#,
+                ),
+            },
+            Example {
+                title: "parse partial version",
+                source: r#"parse_semver!("v7.32", lenient: true)"#,
+                result: Ok(r#"{"build": null, "major": 7, "minor": 32, "patch": 0, "pre": null}"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let lenient = arguments
+            .optional("lenient")
+            .unwrap_or_else(|| expr!(false));
+
+        Ok(Box::new(ParseSemverFn { value, lenient }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseSemverFn {
+    value: Box<dyn Expression>,
+    lenient: Box<dyn Expression>,
+}
+
+impl Expression for ParseSemverFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let lenient = self.lenient.resolve(ctx)?.try_boolean()?;
+        let version = parse_version(&value.try_bytes_utf8_lossy()?, lenient)?;
+
+        let number = |number: u64| {
+            i64::try_from(number)
+                .map(Value::from)
+                .map_err(|_| format!("version number {} is too large", number))
+        };
+        let label = |label: &str| {
+            if label.is_empty() {
+                Value::Null
+            } else {
+                label.to_owned().into()
+            }
+        };
+
+        let mut map = BTreeMap::new();
+        map.insert("major".to_owned(), number(version.major)?);
+        map.insert("minor".to_owned(), number(version.minor)?);
+        map.insert("patch".to_owned(), number(version.patch)?);
+        map.insert("pre".to_owned(), label(version.pre.as_str()));
+        map.insert("build".to_owned(), label(version.build.as_str()));
+
+        Ok(map.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().object::<&str, Kind>(type_def())
+    }
+}
+
+fn type_def() -> BTreeMap<&'static str, Kind> {
+    map! {
+        "major": Kind::Integer,
+        "minor": Kind::Integer,
+        "patch": Kind::Integer,
+        "pre": Kind::Bytes | Kind::Null,
+        "build": Kind::Bytes | Kind::Null,
+    }
+}
+
+/// Parses `value` as a semantic version. When `lenient`, a leading `v` is ignored, and missing
+/// minor and patch numbers are taken to be zero, so that `v1.2` parses as `1.2.0`.
+pub(crate) fn parse_version(value: &str, lenient: bool) -> std::result::Result<Version, String> {
+    let parsed = if lenient {
+        let value = value.trim();
+        let value = value
+            .strip_prefix(|c| c == 'v' || c == 'V')
+            .unwrap_or(value);
+        let core_end = value.find(|c| c == '-' || c == '+').unwrap_or(value.len());
+        let (core, rest) = value.split_at(core_end);
+        let padding = match core.matches('.').count() {
+            0 => ".0.0",
+            1 => ".0",
+            _ => "",
+        };
+        Version::parse(&format!("{}{}{}", core, padding, rest))
+    } else {
+        Version::parse(value)
+    };
+
+    parsed.map_err(|error| format!("unable to parse version: {}", error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_semver => ParseSemver;
+
+        full {
+            args: func_args![value: "1.2.3-rc.1+build.5"],
+            want: Ok(value!({major: 1, minor: 2, patch: 3, pre: "rc.1", build: "build.5"})),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(type_def()),
+        }
+
+        release {
+            args: func_args![value: "10.0.1"],
+            want: Ok(value!({major: 10, minor: 0, patch: 1, pre: null, build: null})),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(type_def()),
+        }
+
+        strict {
+            args: func_args![value: "v1.2"],
+            want: Err("unable to parse version: unexpected character 'v' while parsing major version number"),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(type_def()),
+        }
+
+        lenient {
+            args: func_args![value: " v1.2-beta ", lenient: true],
+            want: Ok(value!({major: 1, minor: 2, patch: 0, pre: "beta", build: null})),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(type_def()),
+        }
+
+        lenient_major_only {
+            args: func_args![value: "7", lenient: true],
+            want: Ok(value!({major: 7, minor: 0, patch: 0, pre: null, build: null})),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(type_def()),
+        }
+
+        invalid {
+            args: func_args![value: "latest", lenient: true],
+            want: Err("unable to parse version: unexpected character 'l' while parsing major version number"),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(type_def()),
+        }
+    ];
+}
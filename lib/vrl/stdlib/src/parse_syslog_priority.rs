@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use vrl::prelude::*;
+
+use crate::util::{SYSLOG_FACILITIES, SYSLOG_SEVERITIES};
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseSyslogPriority;
+
+impl Function for ParseSyslogPriority {
+    fn identifier(&self) -> &'static str {
+        "parse_syslog_priority"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES | kind::INTEGER,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "integer",
+                source: "parse_syslog_priority!(34)",
+                result: Ok(r#"{"facility": "auth", "severity": "crit"}"#),
+            },
+            Example {
+                title: "PRI header",
+                source: "parse_syslog_priority!(s'<165>')",
+                result: Ok(r#"{"facility": "local4", "severity": "notice"}"#),
+            },
+            Example {
+                title: "invalid",
+                source: "parse_syslog_priority!(192)",
+                result: Err(
+                    r#"function call error for "parse_syslog_priority" at (0:27): syslog priority 192 not valid"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ParseSyslogPriorityFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseSyslogPriorityFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ParseSyslogPriorityFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let priority = match self.value.resolve(ctx)? {
+            Value::Integer(priority) => priority,
+            value => {
+                let bytes = value.try_bytes_utf8_lossy()?;
+                let digits = bytes
+                    .strip_prefix('<')
+                    .and_then(|s| s.strip_suffix('>'))
+                    .unwrap_or(&bytes);
+                digits
+                    .parse::<i64>()
+                    .map_err(|_| format!("syslog priority {} not valid", bytes))?
+            }
+        };
+
+        // PRI: https://datatracker.ietf.org/doc/html/rfc5424#section-6.2.1
+        if !(0..SYSLOG_FACILITIES.len() as i64 * 8).contains(&priority) {
+            return Err(format!("syslog priority {} not valid", priority).into());
+        }
+        let facility = SYSLOG_FACILITIES[(priority / 8) as usize];
+        let severity = SYSLOG_SEVERITIES[(priority % 8) as usize];
+
+        let mut map = BTreeMap::new();
+        map.insert("facility".to_owned(), facility.into());
+        map.insert("severity".to_owned(), severity.into());
+
+        Ok(map.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().object::<&str, Kind>(map! {
+            "facility": Kind::Bytes,
+            "severity": Kind::Bytes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        parse_syslog_priority => ParseSyslogPriority;
+
+        integer {
+            args: func_args![value: value!(34)],
+            want: Ok(value!({"facility": "auth", "severity": "crit"})),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(map! {
+                "facility": Kind::Bytes,
+                "severity": Kind::Bytes,
+            }),
+        }
+
+        zero {
+            args: func_args![value: value!(0)],
+            want: Ok(value!({"facility": "kern", "severity": "emerg"})),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(map! {
+                "facility": Kind::Bytes,
+                "severity": Kind::Bytes,
+            }),
+        }
+
+        pri_header {
+            args: func_args![value: value!("<191>")],
+            want: Ok(value!({"facility": "local7", "severity": "debug"})),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(map! {
+                "facility": Kind::Bytes,
+                "severity": Kind::Bytes,
+            }),
+        }
+
+        bare_string {
+            args: func_args![value: value!("13")],
+            want: Ok(value!({"facility": "user", "severity": "notice"})),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(map! {
+                "facility": Kind::Bytes,
+                "severity": Kind::Bytes,
+            }),
+        }
+
+        too_large {
+            args: func_args![value: value!(192)],
+            want: Err("syslog priority 192 not valid"),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(map! {
+                "facility": Kind::Bytes,
+                "severity": Kind::Bytes,
+            }),
+        }
+
+        negative {
+            args: func_args![value: value!(-1)],
+            want: Err("syslog priority -1 not valid"),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(map! {
+                "facility": Kind::Bytes,
+                "severity": Kind::Bytes,
+            }),
+        }
+
+        not_a_number {
+            args: func_args![value: value!("<abc>")],
+            want: Err("syslog priority <abc> not valid"),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(map! {
+                "facility": Kind::Bytes,
+                "severity": Kind::Bytes,
+            }),
+        }
+    ];
+}
@@ -1,3 +1,6 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Utc};
 use shared::conversion::Conversion;
 use vrl::prelude::*;
 
@@ -10,11 +13,28 @@ impl Function for ParseTimestamp {
     }
 
     fn examples(&self) -> &'static [Example] {
-        &[Example {
-            title: "valid",
-            source: r#"parse_timestamp!("11-Feb-2021 16:00 +00:00", format: "%v %R %z")"#,
-            result: Ok("t'2021-02-11T16:00:00Z'"),
-        }]
+        &[
+            Example {
+                title: "valid",
+                source: r#"parse_timestamp!("11-Feb-2021 16:00 +00:00", format: "%v %R %z")"#,
+                result: Ok("t'2021-02-11T16:00:00Z'"),
+            },
+            Example {
+                title: "fallback formats",
+                source: r#"parse_timestamp!("2021-02-11 16:00:00 +0000", format: ["%v %R %z", "%F %T %z"])"#,
+                result: Ok("t'2021-02-11T16:00:00Z'"),
+            },
+            Example {
+                title: "locale",
+                source: r#"parse_timestamp!("11. März 2021 16:00 +0000", format: "%d. %B %Y %R %z", locale: "de")"#,
+                result: Ok("t'2021-03-11T16:00:00Z'"),
+            },
+            Example {
+                title: "include matched format",
+                source: r#"parse_timestamp!("2021-02-11 16:00:00 +0000", format: ["%v %R %z", "%F %T %z"], include_format: true)"#,
+                result: Ok(r#"{ "format": "%F %T %z", "timestamp": t'2021-02-11T16:00:00Z' }"#),
+            },
+        ]
     }
 
     fn compile(
@@ -25,8 +45,25 @@ impl Function for ParseTimestamp {
     ) -> Compiled {
         let value = arguments.required("value");
         let format = arguments.required("format");
+        let locale = arguments
+            .optional_enum("locale", &Locale::all_value())?
+            .map(|s| {
+                Locale::from_name(&s.try_bytes_utf8_lossy().expect("locale not bytes"))
+                    .expect("validated enum")
+            });
+        let year_pivot = arguments.optional("year_pivot");
+        let include_format = arguments
+            .optional_literal("include_format")?
+            .and_then(|literal| literal.to_value().as_boolean())
+            .unwrap_or(false);
 
-        Ok(Box::new(ParseTimestampFn { value, format }))
+        Ok(Box::new(ParseTimestampFn {
+            value,
+            format,
+            locale,
+            year_pivot,
+            include_format,
+        }))
     }
 
     fn parameters(&self) -> &'static [Parameter] {
@@ -38,17 +75,402 @@ impl Function for ParseTimestamp {
             },
             Parameter {
                 keyword: "format",
-                kind: kind::BYTES,
+                kind: kind::BYTES | kind::ARRAY,
                 required: true,
             },
+            Parameter {
+                keyword: "locale",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "year_pivot",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "include_format",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 }
 
+/// The locales whose month and weekday names can be translated before parsing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Locale {
+    De,
+    Es,
+    Fr,
+    It,
+    Nl,
+    Pt,
+}
+
+impl Locale {
+    fn all_value() -> Vec<Value> {
+        use Locale::*;
+
+        vec![De, Es, Fr, It, Nl, Pt]
+            .into_iter()
+            .map(|locale| locale.as_str().into())
+            .collect()
+    }
+
+    const fn as_str(self) -> &'static str {
+        use Locale::*;
+
+        match self {
+            De => "de",
+            Es => "es",
+            Fr => "fr",
+            It => "it",
+            Nl => "nl",
+            Pt => "pt",
+        }
+    }
+
+    fn from_name(s: &str) -> Option<Self> {
+        use Locale::*;
+
+        match s {
+            "de" => Some(De),
+            "es" => Some(Es),
+            "fr" => Some(Fr),
+            "it" => Some(It),
+            "nl" => Some(Nl),
+            "pt" => Some(Pt),
+            _ => None,
+        }
+    }
+
+    /// Localized month and weekday names (full and abbreviated, lowercase)
+    /// mapped to their English equivalents. Chrono accepts both short and
+    /// long English names for `%b`/`%B` and `%a`/`%A`, so full names suffice.
+    ///
+    /// Abbreviations that would be ambiguous between a month and a weekday
+    /// in the same locale (e.g. Spanish `mar`) resolve to the month.
+    fn names(self) -> &'static [(&'static str, &'static str)] {
+        use Locale::*;
+
+        match self {
+            De => &[
+                ("januar", "January"),
+                ("jan", "January"),
+                ("jänner", "January"),
+                ("februar", "February"),
+                ("feb", "February"),
+                ("märz", "March"),
+                ("mär", "March"),
+                ("mrz", "March"),
+                ("april", "April"),
+                ("apr", "April"),
+                ("mai", "May"),
+                ("juni", "June"),
+                ("jun", "June"),
+                ("juli", "July"),
+                ("jul", "July"),
+                ("august", "August"),
+                ("aug", "August"),
+                ("september", "September"),
+                ("sep", "September"),
+                ("sept", "September"),
+                ("oktober", "October"),
+                ("okt", "October"),
+                ("november", "November"),
+                ("nov", "November"),
+                ("dezember", "December"),
+                ("dez", "December"),
+                ("montag", "Monday"),
+                ("mo", "Monday"),
+                ("dienstag", "Tuesday"),
+                ("di", "Tuesday"),
+                ("mittwoch", "Wednesday"),
+                ("mi", "Wednesday"),
+                ("donnerstag", "Thursday"),
+                ("do", "Thursday"),
+                ("freitag", "Friday"),
+                ("fr", "Friday"),
+                ("samstag", "Saturday"),
+                ("sa", "Saturday"),
+                ("sonntag", "Sunday"),
+                ("so", "Sunday"),
+            ],
+            Es => &[
+                ("enero", "January"),
+                ("ene", "January"),
+                ("febrero", "February"),
+                ("feb", "February"),
+                ("marzo", "March"),
+                ("mar", "March"),
+                ("abril", "April"),
+                ("abr", "April"),
+                ("mayo", "May"),
+                ("may", "May"),
+                ("junio", "June"),
+                ("jun", "June"),
+                ("julio", "July"),
+                ("jul", "July"),
+                ("agosto", "August"),
+                ("ago", "August"),
+                ("septiembre", "September"),
+                ("sep", "September"),
+                ("sept", "September"),
+                ("octubre", "October"),
+                ("oct", "October"),
+                ("noviembre", "November"),
+                ("nov", "November"),
+                ("diciembre", "December"),
+                ("dic", "December"),
+                ("lunes", "Monday"),
+                ("lun", "Monday"),
+                ("martes", "Tuesday"),
+                ("miércoles", "Wednesday"),
+                ("mié", "Wednesday"),
+                ("jueves", "Thursday"),
+                ("jue", "Thursday"),
+                ("viernes", "Friday"),
+                ("vie", "Friday"),
+                ("sábado", "Saturday"),
+                ("sáb", "Saturday"),
+                ("domingo", "Sunday"),
+                ("dom", "Sunday"),
+            ],
+            Fr => &[
+                ("janvier", "January"),
+                ("janv", "January"),
+                ("février", "February"),
+                ("févr", "February"),
+                ("fév", "February"),
+                ("mars", "March"),
+                ("avril", "April"),
+                ("avr", "April"),
+                ("mai", "May"),
+                ("juin", "June"),
+                ("juillet", "July"),
+                ("juil", "July"),
+                ("août", "August"),
+                ("septembre", "September"),
+                ("sept", "September"),
+                ("octobre", "October"),
+                ("oct", "October"),
+                ("novembre", "November"),
+                ("nov", "November"),
+                ("décembre", "December"),
+                ("déc", "December"),
+                ("lundi", "Monday"),
+                ("lun", "Monday"),
+                ("mardi", "Tuesday"),
+                ("mar", "Tuesday"),
+                ("mercredi", "Wednesday"),
+                ("mer", "Wednesday"),
+                ("jeudi", "Thursday"),
+                ("jeu", "Thursday"),
+                ("vendredi", "Friday"),
+                ("ven", "Friday"),
+                ("samedi", "Saturday"),
+                ("sam", "Saturday"),
+                ("dimanche", "Sunday"),
+                ("dim", "Sunday"),
+            ],
+            It => &[
+                ("gennaio", "January"),
+                ("gen", "January"),
+                ("febbraio", "February"),
+                ("feb", "February"),
+                ("marzo", "March"),
+                ("mar", "March"),
+                ("aprile", "April"),
+                ("apr", "April"),
+                ("maggio", "May"),
+                ("mag", "May"),
+                ("giugno", "June"),
+                ("giu", "June"),
+                ("luglio", "July"),
+                ("lug", "July"),
+                ("agosto", "August"),
+                ("ago", "August"),
+                ("settembre", "September"),
+                ("set", "September"),
+                ("ottobre", "October"),
+                ("ott", "October"),
+                ("novembre", "November"),
+                ("nov", "November"),
+                ("dicembre", "December"),
+                ("dic", "December"),
+                ("lunedì", "Monday"),
+                ("lun", "Monday"),
+                ("martedì", "Tuesday"),
+                ("mercoledì", "Wednesday"),
+                ("mer", "Wednesday"),
+                ("giovedì", "Thursday"),
+                ("gio", "Thursday"),
+                ("venerdì", "Friday"),
+                ("ven", "Friday"),
+                ("sabato", "Saturday"),
+                ("sab", "Saturday"),
+                ("domenica", "Sunday"),
+                ("dom", "Sunday"),
+            ],
+            Nl => &[
+                ("januari", "January"),
+                ("jan", "January"),
+                ("februari", "February"),
+                ("feb", "February"),
+                ("maart", "March"),
+                ("mrt", "March"),
+                ("april", "April"),
+                ("apr", "April"),
+                ("mei", "May"),
+                ("juni", "June"),
+                ("jun", "June"),
+                ("juli", "July"),
+                ("jul", "July"),
+                ("augustus", "August"),
+                ("aug", "August"),
+                ("september", "September"),
+                ("sep", "September"),
+                ("oktober", "October"),
+                ("okt", "October"),
+                ("november", "November"),
+                ("nov", "November"),
+                ("december", "December"),
+                ("dec", "December"),
+                ("maandag", "Monday"),
+                ("ma", "Monday"),
+                ("dinsdag", "Tuesday"),
+                ("di", "Tuesday"),
+                ("woensdag", "Wednesday"),
+                ("wo", "Wednesday"),
+                ("donderdag", "Thursday"),
+                ("do", "Thursday"),
+                ("vrijdag", "Friday"),
+                ("vr", "Friday"),
+                ("zaterdag", "Saturday"),
+                ("za", "Saturday"),
+                ("zondag", "Sunday"),
+                ("zo", "Sunday"),
+            ],
+            Pt => &[
+                ("janeiro", "January"),
+                ("jan", "January"),
+                ("fevereiro", "February"),
+                ("fev", "February"),
+                ("março", "March"),
+                ("mar", "March"),
+                ("abril", "April"),
+                ("abr", "April"),
+                ("maio", "May"),
+                ("mai", "May"),
+                ("junho", "June"),
+                ("jun", "June"),
+                ("julho", "July"),
+                ("jul", "July"),
+                ("agosto", "August"),
+                ("ago", "August"),
+                ("setembro", "September"),
+                ("set", "September"),
+                ("outubro", "October"),
+                ("out", "October"),
+                ("novembro", "November"),
+                ("nov", "November"),
+                ("dezembro", "December"),
+                ("dez", "December"),
+                ("seg", "Monday"),
+                ("ter", "Tuesday"),
+                ("qua", "Wednesday"),
+                ("qui", "Thursday"),
+                ("sex", "Friday"),
+                ("sáb", "Saturday"),
+                ("dom", "Sunday"),
+            ],
+        }
+    }
+
+    /// Replace every localized month or weekday name in `s` with its English
+    /// equivalent, leaving all other text untouched.
+    fn translate(self, s: &str) -> String {
+        let names = self.names();
+        let mut translated = String::with_capacity(s.len());
+        let mut word = String::new();
+
+        let mut flush = |word: &mut String, translated: &mut String| {
+            if !word.is_empty() {
+                let lower = word.to_lowercase();
+                match names.iter().find(|(name, _)| *name == lower) {
+                    Some((_, english)) => translated.push_str(english),
+                    None => translated.push_str(word),
+                }
+                word.clear();
+            }
+        };
+
+        for c in s.chars() {
+            if c.is_alphabetic() {
+                word.push(c);
+            } else {
+                flush(&mut word, &mut translated);
+                translated.push(c);
+            }
+        }
+        flush(&mut word, &mut translated);
+
+        translated
+    }
+}
+
+/// Move a year parsed from a two-digit `%y` specifier into the century
+/// selected by `pivot`: years below the pivot land in the 2000s, the rest
+/// in the 1900s. Chrono itself always pivots at 70.
+fn apply_year_pivot(timestamp: DateTime<Utc>, pivot: i64) -> Option<DateTime<Utc>> {
+    let year = timestamp.year();
+    let two_digit = i64::from(year.rem_euclid(100));
+    let century = if two_digit < pivot { 2000 } else { 1900 };
+
+    timestamp.with_year(century + two_digit as i32)
+}
+
+fn parse_with_format(
+    value: Bytes,
+    format: &str,
+    timezone: shared::TimeZone,
+    year_pivot: Option<i64>,
+) -> std::result::Result<DateTime<Utc>, String> {
+    let timestamp = Conversion::parse(format!("timestamp|{}", format), timezone)
+        .map_err(|e| format!("{}", e))?
+        .convert::<Value>(value)
+        .map_err(|e| e.to_string())?
+        .try_timestamp()
+        .map_err(|e| e.to_string())?;
+
+    match year_pivot {
+        Some(pivot) if format.contains("%y") => apply_year_pivot(timestamp, pivot)
+            .ok_or_else(|| format!("unable to apply year pivot {} to {}", pivot, timestamp)),
+        _ => Ok(timestamp),
+    }
+}
+
 #[derive(Debug, Clone)]
 struct ParseTimestampFn {
     value: Box<dyn Expression>,
     format: Box<dyn Expression>,
+    locale: Option<Locale>,
+    year_pivot: Option<Box<dyn Expression>>,
+    include_format: bool,
+}
+
+impl ParseTimestampFn {
+    fn wrap(&self, timestamp: Value, format: Option<String>) -> Value {
+        if !self.include_format {
+            return timestamp;
+        }
+
+        let mut map = BTreeMap::new();
+        map.insert("timestamp".to_owned(), timestamp);
+        map.insert("format".to_owned(), format.into());
+        map.into()
+    }
 }
 
 impl Expression for ParseTimestampFn {
@@ -57,22 +479,67 @@ impl Expression for ParseTimestampFn {
 
         match value {
             Value::Bytes(v) => {
-                let bytes = self.format.resolve(ctx)?;
-                let format = bytes.try_bytes_utf8_lossy()?;
-                Conversion::parse(format!("timestamp|{}", format), ctx.timezone().to_owned())
-                    .map_err(|e| format!("{}", e))?
-                    .convert(v)
-                    .map_err(|e| e.to_string().into())
+                let formats = match self.format.resolve(ctx)? {
+                    Value::Array(formats) => formats
+                        .into_iter()
+                        .map(|format| format.try_bytes_utf8_lossy().map(|s| s.into_owned()))
+                        .collect::<std::result::Result<Vec<_>, _>>()?,
+                    format => vec![format.try_bytes_utf8_lossy()?.into_owned()],
+                };
+                if formats.is_empty() {
+                    return Err("at least one format must be provided".into());
+                }
+
+                let year_pivot = match &self.year_pivot {
+                    Some(expr) => {
+                        let pivot = expr.resolve(ctx)?.try_integer()?;
+                        if !(0..=100).contains(&pivot) {
+                            return Err(format!(
+                                "year pivot must be between 0 and 100, got {}",
+                                pivot
+                            )
+                            .into());
+                        }
+                        Some(pivot)
+                    }
+                    None => None,
+                };
+
+                let v = match self.locale {
+                    Some(locale) => Bytes::from(locale.translate(&String::from_utf8_lossy(&v))),
+                    None => v,
+                };
+
+                let mut errors = Vec::with_capacity(formats.len());
+                for format in formats {
+                    match parse_with_format(v.clone(), &format, *ctx.timezone(), year_pivot) {
+                        Ok(timestamp) => return Ok(self.wrap(timestamp.into(), Some(format))),
+                        Err(error) => errors.push(format!("{:?}: {}", format, error)),
+                    }
+                }
+
+                match errors.len() {
+                    1 => Err(errors.remove(0).into()),
+                    _ => Err(format!("no format matched: {}", errors.join(", ")).into()),
+                }
             }
-            Value::Timestamp(_) => Ok(value),
+            Value::Timestamp(_) => Ok(self.wrap(value, None)),
             _ => Err("unable to convert value to timestamp".into()),
         }
     }
 
     fn type_def(&self, _: &state::Compiler) -> TypeDef {
-        TypeDef::new()
-            .fallible() // Always fallible because the format needs to be parsed at runtime
-            .timestamp()
+        // Always fallible because the format needs to be parsed at runtime
+        let type_def = TypeDef::new().fallible();
+
+        if self.include_format {
+            type_def.object::<&str, Kind>(map! {
+                "timestamp": Kind::Timestamp,
+                "format": Kind::Bytes | Kind::Null,
+            })
+        } else {
+            type_def.timestamp()
+        }
     }
 }
 
@@ -128,5 +595,108 @@ mod tests {
             tdef: TypeDef::new().fallible().timestamp(),
             tz: shared::TimeZone::Named(chrono_tz::Europe::Paris),
         }
+
+        parse_fallback_formats {
+            args: func_args![
+                value: "2019-10-16 12:00:00 +0000",
+                format: vec!["%d/%m/%Y:%H:%M:%S %z", "%Y-%m-%d %H:%M:%S %z"]
+            ],
+            want: Ok(value!(
+                DateTime::parse_from_rfc2822("Wed, 16 Oct 2019 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )),
+            tdef: TypeDef::new().fallible().timestamp(),
+            tz: shared::TimeZone::default(),
+        }
+
+        parse_no_format_matched {
+            args: func_args![
+                value: "yesterday",
+                format: vec!["%d/%m/%Y:%H:%M:%S %z", "%Y-%m-%d %H:%M:%S %z"]
+            ],
+            want: Err(r#"no format matched: "%d/%m/%Y:%H:%M:%S %z": Invalid timestamp "yesterday": input contains invalid characters, "%Y-%m-%d %H:%M:%S %z": Invalid timestamp "yesterday": input contains invalid characters"#),
+            tdef: TypeDef::new().fallible().timestamp(),
+            tz: shared::TimeZone::default(),
+        }
+
+        parse_locale_month {
+            args: func_args![
+                value: "Mittwoch, 16. Oktober 2019 12:00:00 +0000",
+                format: "%A, %d. %B %Y %H:%M:%S %z",
+                locale: "de"
+            ],
+            want: Ok(value!(
+                DateTime::parse_from_rfc2822("Wed, 16 Oct 2019 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )),
+            tdef: TypeDef::new().fallible().timestamp(),
+            tz: shared::TimeZone::default(),
+        }
+
+        parse_locale_abbreviated {
+            args: func_args![
+                value: "16 déc 2019 12:00:00 +0000",
+                format: "%d %b %Y %H:%M:%S %z",
+                locale: "fr"
+            ],
+            want: Ok(value!(
+                DateTime::parse_from_rfc2822("Mon, 16 Dec 2019 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )),
+            tdef: TypeDef::new().fallible().timestamp(),
+            tz: shared::TimeZone::default(),
+        }
+
+        parse_year_pivot_past {
+            args: func_args![
+                value: "16/10/65 12:00:00 +0000",
+                format: "%d/%m/%y %H:%M:%S %z",
+                year_pivot: 50
+            ],
+            want: Ok(value!(
+                DateTime::parse_from_rfc2822("Sat, 16 Oct 1965 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )),
+            tdef: TypeDef::new().fallible().timestamp(),
+            tz: shared::TimeZone::default(),
+        }
+
+        parse_year_pivot_future {
+            args: func_args![
+                value: "16/10/75 12:00:00 +0000",
+                format: "%d/%m/%y %H:%M:%S %z",
+                year_pivot: 80
+            ],
+            want: Ok(value!(
+                DateTime::parse_from_rfc2822("Wed, 16 Oct 2075 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)
+            )),
+            tdef: TypeDef::new().fallible().timestamp(),
+            tz: shared::TimeZone::default(),
+        }
+
+        parse_include_format {
+            args: func_args![
+                value: "2019-10-16 12:00:00 +0000",
+                format: vec!["%d/%m/%Y:%H:%M:%S %z", "%Y-%m-%d %H:%M:%S %z"],
+                include_format: true
+            ],
+            want: Ok(value!({
+                "timestamp": (DateTime::parse_from_rfc2822("Wed, 16 Oct 2019 12:00:00 +0000")
+                    .unwrap()
+                    .with_timezone(&Utc)),
+                "format": "%Y-%m-%d %H:%M:%S %z",
+            })),
+            tdef: TypeDef::new().fallible().object::<&str, Kind>(map! {
+                "timestamp": Kind::Timestamp,
+                "format": Kind::Bytes | Kind::Null,
+            }),
+            tz: shared::TimeZone::default(),
+        }
     ];
 }
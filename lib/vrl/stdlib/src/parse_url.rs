@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
-use url::Url;
+use percent_encoding::percent_decode_str;
+use url::{Host, Url};
 use vrl::prelude::*;
 
 #[derive(Clone, Copy, Debug)]
@@ -23,6 +24,16 @@ impl Function for ParseUrl {
                 kind: kind::BOOLEAN,
                 required: false,
             },
+            Parameter {
+                keyword: "decode_punycode",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+            Parameter {
+                keyword: "multi_value_query",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 
@@ -37,6 +48,7 @@ impl Function for ParseUrl {
                     "host": "vector.dev",
                     "password": "",
                     "path": "/",
+                    "path_segments": [],
                     "port": null,
                     "query": {},
                     "scheme": "https",
@@ -53,6 +65,7 @@ impl Function for ParseUrl {
                     "host": "vector.dev",
                     "password": "",
                     "path": "/",
+                    "path_segments": [],
                     "port": 443,
                     "query": {},
                     "scheme": "https",
@@ -60,6 +73,23 @@ impl Function for ParseUrl {
                 }
             "#}),
             },
+            Example {
+                title: "parse internationalized url",
+                source: r#"parse_url!("https://xn--bcher-kva.example/a/b%20c?tag=x&tag=y", decode_punycode: true, multi_value_query: true)"#,
+                result: Ok(indoc! {r#"
+                {
+                    "fragment": null,
+                    "host": "bücher.example",
+                    "password": "",
+                    "path": "/a/b%20c",
+                    "path_segments": ["a", "b c"],
+                    "port": null,
+                    "query": {"tag": ["x", "y"]},
+                    "scheme": "https",
+                    "username": ""
+                }
+            "#}),
+            },
         ]
     }
 
@@ -73,10 +103,20 @@ impl Function for ParseUrl {
         let default_known_ports = arguments
             .optional("default_known_ports")
             .unwrap_or_else(|| expr!(false));
+        let decode_punycode = arguments
+            .optional("decode_punycode")
+            .unwrap_or_else(|| expr!(false));
+        // Changes the type of the query values, so it must be known when compiling.
+        let multi_value_query = arguments
+            .optional_literal("multi_value_query")?
+            .and_then(|literal| literal.to_value().as_boolean())
+            .unwrap_or(false);
 
         Ok(Box::new(ParseUrlFn {
             value,
             default_known_ports,
+            decode_punycode,
+            multi_value_query,
         }))
     }
 }
@@ -85,6 +125,8 @@ impl Function for ParseUrl {
 struct ParseUrlFn {
     value: Box<dyn Expression>,
     default_known_ports: Box<dyn Expression>,
+    decode_punycode: Box<dyn Expression>,
+    multi_value_query: bool,
 }
 
 impl Expression for ParseUrlFn {
@@ -92,19 +134,32 @@ impl Expression for ParseUrlFn {
         let value = self.value.resolve(ctx)?;
         let string = value.try_bytes_utf8_lossy()?;
 
-        let default_known_ports = self.default_known_ports.resolve(ctx)?.try_boolean()?;
+        let options = Options {
+            default_known_ports: self.default_known_ports.resolve(ctx)?.try_boolean()?,
+            decode_punycode: self.decode_punycode.resolve(ctx)?.try_boolean()?,
+            multi_value_query: self.multi_value_query,
+        };
 
         Url::parse(&string)
             .map_err(|e| format!("unable to parse url: {}", e).into())
-            .map(|url| url_to_value(url, default_known_ports))
+            .map(|url| url_to_value(url, options))
     }
 
     fn type_def(&self, _: &state::Compiler) -> TypeDef {
-        TypeDef::new().fallible().object(type_def())
+        TypeDef::new()
+            .fallible()
+            .object(type_def(self.multi_value_query))
     }
 }
 
-fn url_to_value(url: Url, default_known_ports: bool) -> Value {
+#[derive(Clone, Copy, Debug)]
+struct Options {
+    default_known_ports: bool,
+    decode_punycode: bool,
+    multi_value_query: bool,
+}
+
+fn url_to_value(url: Url, options: Options) -> Value {
     let mut map = BTreeMap::<&str, Value>::new();
 
     map.insert("scheme", url.scheme().to_owned().into());
@@ -117,40 +172,87 @@ fn url_to_value(url: Url, default_known_ports: bool) -> Value {
             .into(),
     );
     map.insert("path", url.path().to_owned().into());
-    map.insert("host", url.host_str().map(ToOwned::to_owned).into());
+    map.insert("path_segments", path_segments(&url).into());
+
+    let host = match url.host() {
+        Some(Host::Domain(domain)) if options.decode_punycode => {
+            // Labels that fail to decode are kept as they are.
+            Some(idna::domain_to_unicode(domain).0)
+        }
+        host => host.map(|host| host.to_string()),
+    };
+    map.insert("host", host.into());
 
-    let port = if default_known_ports {
+    let port = if options.default_known_ports {
         url.port_or_known_default()
     } else {
         url.port()
     };
     map.insert("port", port.into());
     map.insert("fragment", url.fragment().map(ToOwned::to_owned).into());
-    map.insert(
-        "query",
-        url.query_pairs()
-            .into_owned()
-            .map(|(k, v)| (k, v.into()))
-            .collect::<BTreeMap<String, Value>>()
-            .into(),
-    );
+    map.insert("query", query(&url, options.multi_value_query).into());
 
     map.into_iter()
         .map(|(k, v)| (k.to_owned(), v))
         .collect::<Value>()
 }
 
-fn type_def() -> BTreeMap<&'static str, TypeDef> {
+/// Returns the percent-decoded segments of the path, without empty ones.
+fn path_segments(url: &Url) -> Vec<Value> {
+    url.path_segments()
+        .into_iter()
+        .flatten()
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            percent_decode_str(segment)
+                .decode_utf8_lossy()
+                .into_owned()
+                .into()
+        })
+        .collect()
+}
+
+/// Returns the query parameters. Later values of a repeated parameter replace earlier ones,
+/// unless `multi_value` is set, in which case they are all kept in an array.
+fn query(url: &Url, multi_value: bool) -> BTreeMap<String, Value> {
+    let mut query = BTreeMap::new();
+    for (key, value) in url.query_pairs().into_owned() {
+        let value = Value::from(value);
+        if !multi_value {
+            query.insert(key, value);
+            continue;
+        }
+        query
+            .entry(key)
+            .and_modify(|values| match values {
+                Value::Array(values) => values.push(value.clone()),
+                first => *first = Value::Array(vec![first.clone(), value.clone()]),
+            })
+            .or_insert(value);
+    }
+    query
+}
+
+fn type_def(multi_value_query: bool) -> BTreeMap<&'static str, TypeDef> {
+    let query_value = if multi_value_query {
+        Kind::Bytes | Kind::Array
+    } else {
+        Kind::Bytes
+    };
+
     map! {
         "scheme": Kind::Bytes,
         "username": Kind::Bytes,
         "password": Kind::Bytes,
         "path": Kind::Bytes | Kind::Null,
+        "path_segments": TypeDef::new().array_mapped::<(), Kind>(map! {
+            (): Kind::Bytes,
+        }),
         "host": Kind::Bytes,
         "port": Kind::Integer | Kind::Null,
         "fragment": Kind::Bytes | Kind::Null,
         "query": TypeDef::new().object::<(), Kind>(map! {
-            (): Kind::Bytes,
+            (): query_value,
         }),
     }
 }
@@ -169,12 +271,13 @@ mod tests {
                 host: "vector.dev",
                 password: "",
                 path: "/",
+                path_segments: [],
                 port: (),
                 query: {},
                 scheme: "https",
                 username: "",
             })),
-            tdef: TypeDef::new().fallible().object::<&'static str, TypeDef>(type_def()),
+            tdef: TypeDef::new().fallible().object::<&'static str, TypeDef>(type_def(false)),
         }
 
         default_port_specified {
@@ -184,12 +287,13 @@ mod tests {
                 host: "vector.dev",
                 password: "",
                 path: "/",
+                path_segments: [],
                 port: (),
                 query: {},
                 scheme: "https",
                 username: "",
             })),
-            tdef: TypeDef::new().fallible().object::<&'static str, TypeDef>(type_def()),
+            tdef: TypeDef::new().fallible().object::<&'static str, TypeDef>(type_def(false)),
         }
 
         default_port {
@@ -199,12 +303,77 @@ mod tests {
                 host: "vector.dev",
                 password: "",
                 path: "/",
+                path_segments: [],
                 port: 443,
                 query: {},
                 scheme: "https",
                 username: "",
             })),
-            tdef: TypeDef::new().fallible().object::<&'static str, TypeDef>(type_def()),
+            tdef: TypeDef::new().fallible().object::<&'static str, TypeDef>(type_def(false)),
+        }
+
+        path_segments {
+            args: func_args![value: value!("https://vector.dev/docs//reference/caf%C3%A9/?a=1&a=2")],
+            want: Ok(value!({
+                fragment: (),
+                host: "vector.dev",
+                password: "",
+                path: "/docs//reference/caf%C3%A9/",
+                path_segments: ["docs", "reference", "café"],
+                port: (),
+                query: {a: "2"},
+                scheme: "https",
+                username: "",
+            })),
+            tdef: TypeDef::new().fallible().object::<&'static str, TypeDef>(type_def(false)),
+        }
+
+        multi_value_query {
+            args: func_args![value: value!("https://vector.dev/?a=1&b=2&a=3"), multi_value_query: true],
+            want: Ok(value!({
+                fragment: (),
+                host: "vector.dev",
+                password: "",
+                path: "/",
+                path_segments: [],
+                port: (),
+                query: {a: ["1", "3"], b: "2"},
+                scheme: "https",
+                username: "",
+            })),
+            tdef: TypeDef::new().fallible().object::<&'static str, TypeDef>(type_def(true)),
+        }
+
+        punycode {
+            args: func_args![value: value!("https://xn--bcher-kva.example"), decode_punycode: true],
+            want: Ok(value!({
+                fragment: (),
+                host: "bücher.example",
+                password: "",
+                path: "/",
+                path_segments: [],
+                port: (),
+                query: {},
+                scheme: "https",
+                username: "",
+            })),
+            tdef: TypeDef::new().fallible().object::<&'static str, TypeDef>(type_def(false)),
+        }
+
+        punycode_not_decoded_by_default {
+            args: func_args![value: value!("https://bücher.example")],
+            want: Ok(value!({
+                fragment: (),
+                host: "xn--bcher-kva.example",
+                password: "",
+                path: "/",
+                path_segments: [],
+                port: (),
+                query: {},
+                scheme: "https",
+                username: "",
+            })),
+            tdef: TypeDef::new().fallible().object::<&'static str, TypeDef>(type_def(false)),
         }
     ];
 }
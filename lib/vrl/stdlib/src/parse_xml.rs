@@ -22,6 +22,10 @@ struct ParseXmlConfig<'a> {
     parse_null: bool,
     /// Parse numeric values as integers/floats. Default: true.
     parse_number: bool,
+    /// Element names that are always wrapped in an array, even if they occur once. Default: [].
+    always_array: Vec<String>,
+    /// Drop namespace prefixes from element and attribute names. Default: true.
+    strip_namespaces: bool,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -62,6 +66,8 @@ impl Function for ParseXml {
         let parse_bool = arguments.optional("parse_bool");
         let parse_null = arguments.optional("parse_null");
         let parse_number = arguments.optional("parse_number");
+        let always_array = arguments.optional("always_array");
+        let strip_namespaces = arguments.optional("strip_namespaces");
 
         Ok(Box::new(ParseXmlFn {
             value,
@@ -73,6 +79,8 @@ impl Function for ParseXml {
             parse_bool,
             parse_null,
             parse_number,
+            always_array,
+            strip_namespaces,
         }))
     }
 
@@ -123,6 +131,16 @@ impl Function for ParseXml {
                 kind: kind::BOOLEAN,
                 required: false,
             },
+            Parameter {
+                keyword: "always_array",
+                kind: kind::ARRAY,
+                required: false,
+            },
+            Parameter {
+                keyword: "strip_namespaces",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
         ]
     }
 }
@@ -139,6 +157,8 @@ struct ParseXmlFn {
     parse_bool: Option<Box<dyn Expression>>,
     parse_null: Option<Box<dyn Expression>>,
     parse_number: Option<Box<dyn Expression>>,
+    always_array: Option<Box<dyn Expression>>,
+    strip_namespaces: Option<Box<dyn Expression>>,
 }
 
 impl Expression for ParseXmlFn {
@@ -186,6 +206,21 @@ impl Expression for ParseXmlFn {
             None => true,
         };
 
+        let always_array = match &self.always_array {
+            Some(expr) => expr
+                .resolve(ctx)?
+                .try_array()?
+                .into_iter()
+                .map(|name| name.try_bytes_utf8_lossy().map(|name| name.into_owned()))
+                .collect::<std::result::Result<_, _>>()?,
+            None => vec![],
+        };
+
+        let strip_namespaces = match &self.strip_namespaces {
+            Some(expr) => expr.resolve(ctx)?.try_boolean()?,
+            None => true,
+        };
+
         let config = ParseXmlConfig {
             include_attr,
             attr_prefix,
@@ -194,6 +229,8 @@ impl Expression for ParseXmlFn {
             parse_bool,
             parse_null,
             parse_number,
+            always_array,
+            strip_namespaces,
         };
 
         // Trim whitespace around XML elements, if applicable.
@@ -232,7 +269,7 @@ fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
             let name = if n.tag_name().name() == "" {
                 config.text_key.to_string()
             } else {
-                n.tag_name().name().to_string()
+                element_name(n, config)
             };
 
             // Transform the node into a VRL `Value`.
@@ -255,7 +292,11 @@ fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
                     };
                 }
                 Entry::Vacant(entry) => {
-                    entry.insert(value);
+                    if config.always_array.contains(entry.key()) {
+                        entry.insert(Value::Array(vec![value]));
+                    } else {
+                        entry.insert(value);
+                    }
                 }
             }
         }
@@ -272,8 +313,13 @@ fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
             // Expand attributes, if required.
             if config.include_attr {
                 for attr in node.attributes() {
+                    let name = match attr.namespace() {
+                        Some(uri) if !config.strip_namespaces => prefixed(node, uri, attr.name()),
+                        _ => attr.name().to_owned(),
+                    };
+
                     map.insert(
-                        format!("{}{}", config.attr_prefix, attr.name()),
+                        format!("{}{}", config.attr_prefix, name),
                         attr.value().into(),
                     );
                 }
@@ -300,11 +346,15 @@ fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
 
                         // If the node is an element, treat it as an object.
                         if node.is_element() {
+                            let name = element_name(node, config);
+                            let value = Value::Object(recurse(node));
+
                             let mut map = BTreeMap::new();
-                            map.insert(
-                                node.tag_name().name().to_string(),
-                                Value::Object(recurse(node)),
-                            );
+                            if config.always_array.contains(&name) {
+                                map.insert(name, Value::Array(vec![value]));
+                            } else {
+                                map.insert(name, value);
+                            }
 
                             Value::Object(map)
                         } else {
@@ -322,6 +372,25 @@ fn process_node<'a>(node: Node, config: &ParseXmlConfig<'a>) -> Value {
     }
 }
 
+/// Return the name of an element, including its namespace prefix if namespaces aren't stripped.
+fn element_name(node: Node, config: &ParseXmlConfig<'_>) -> String {
+    let name = node.tag_name();
+
+    match name.namespace() {
+        Some(uri) if !config.strip_namespaces => prefixed(node, uri, name.name()),
+        _ => name.name().to_owned(),
+    }
+}
+
+/// Prefix a local name with the prefix bound to the namespace `uri` in scope of `node`. Names
+/// in the default namespace have no prefix.
+fn prefixed(node: Node, uri: &str, name: &str) -> String {
+    match node.lookup_prefix(uri) {
+        Some(prefix) if !prefix.is_empty() => format!("{}:{}", prefix, name),
+        _ => name.to_owned(),
+    }
+}
+
 /// Process a text node, and return the correct `Value` type based on config.
 fn process_text<'a>(text: &'a str, config: &ParseXmlConfig<'a>) -> Value {
     match text {
@@ -515,6 +584,36 @@ mod tests {
             tdef: type_def(),
         }
 
+        always_array {
+            args: func_args![ value: r#"<a><b>one</b><c>two</c></a>"#, always_array: vec!["b"] ],
+            want: Ok(value!({ "a": { "b": ["one"], "c": "two" } })),
+            tdef: type_def(),
+        }
+
+        always_array_repeated {
+            args: func_args![ value: r#"<a><b>one</b><b>two</b></a>"#, always_array: vec!["b"] ],
+            want: Ok(value!({ "a": { "b": ["one", "two"] } })),
+            tdef: type_def(),
+        }
+
+        custom_attr_prefix {
+            args: func_args![ value: r#"<a href="https://vector.dev">test</a>"#, attr_prefix: "" ],
+            want: Ok(value!({ "a": { "href": "https://vector.dev", "text": "test" } })),
+            tdef: type_def(),
+        }
+
+        strip_namespaces {
+            args: func_args![ value: r#"<s:a xmlns:s="urn:s" xmlns:x="urn:x" x:id="1"><s:b>one</s:b><s:c>two</s:c></s:a>"# ],
+            want: Ok(value!({ "a": { "@id": "1", "b": "one", "c": "two" } })),
+            tdef: type_def(),
+        }
+
+        keep_namespaces {
+            args: func_args![ value: r#"<s:a xmlns:s="urn:s" xmlns:x="urn:x" x:id="1"><s:b>one</s:b><c>two</c></s:a>"#, strip_namespaces: false ],
+            want: Ok(value!({ "s:a": { "@x:id": "1", "s:b": "one", "c": "two" } })),
+            tdef: type_def(),
+        }
+
         invalid_token {
             args: func_args![ value: "true" ],
             want: Err("unable to parse xml: unknown token at 1:1"),
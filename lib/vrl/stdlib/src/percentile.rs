@@ -0,0 +1,137 @@
+use std::cmp::Ordering;
+
+use vrl::prelude::*;
+
+use crate::util::array_to_floats;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Percentile;
+
+impl Function for Percentile {
+    fn identifier(&self) -> &'static str {
+        "percentile"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "percentile",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "percentile",
+                source: "percentile!([15, 20, 35, 40, 50], 25)",
+                result: Ok("20.0"),
+            },
+            Example {
+                title: "interpolated",
+                source: "percentile!([1, 2, 3, 4], 75)",
+                result: Ok("3.25"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let percentile = arguments.required("percentile");
+
+        Ok(Box::new(PercentileFn { value, percentile }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PercentileFn {
+    value: Box<dyn Expression>,
+    percentile: Box<dyn Expression>,
+}
+
+impl Expression for PercentileFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let values = array_to_floats(&self.value.resolve(ctx)?.try_array()?)?;
+        let percentile = f64::try_from(&self.percentile.resolve(ctx)?)?;
+        if !(0.0..=100.0).contains(&percentile) {
+            return Err(format!("percentile must be between 0 and 100, not {}", percentile).into());
+        }
+
+        Ok(percentile_of(values, percentile).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().float()
+    }
+}
+
+/// Returns the `percentile` of `values`, which must not be empty, interpolating linearly between
+/// the closest ranks when it falls between two of them.
+pub(crate) fn percentile_of(mut values: Vec<f64>, percentile: f64) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    let rank = percentile / 100.0 * (values.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    if lower == upper {
+        return values[lower];
+    }
+    values[lower] + (values[upper] - values[lower]) * (rank - lower as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        percentile => Percentile;
+
+        exact_rank {
+            args: func_args![value: value!([50, 15, 40, 20, 35]), percentile: 25],
+            want: Ok(20.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        interpolated {
+            args: func_args![value: value!([1, 2, 3, 4]), percentile: 75.0],
+            want: Ok(3.25),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        bounds {
+            args: func_args![value: value!([3.5, -1, 7]), percentile: 100],
+            want: Ok(7.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        single {
+            args: func_args![value: value!([42]), percentile: 99],
+            want: Ok(42.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        out_of_range {
+            args: func_args![value: value!([1, 2]), percentile: 101],
+            want: Err("percentile must be between 0 and 100, not 101"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        empty {
+            args: func_args![value: value!([]), percentile: 50],
+            want: Err("array must not be empty"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+    ];
+}
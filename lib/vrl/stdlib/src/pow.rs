@@ -0,0 +1,112 @@
+use vrl::prelude::*;
+
+use crate::util::finite_float;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Pow;
+
+impl Function for Pow {
+    fn identifier(&self) -> &'static str {
+        "pow"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
+                required: true,
+            },
+            Parameter {
+                keyword: "exponent",
+                kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "power",
+                source: "pow!(2, 10)",
+                result: Ok("1024.0"),
+            },
+            Example {
+                title: "root",
+                source: "pow!(16, 0.5)",
+                result: Ok("4.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let exponent = arguments.required("exponent");
+
+        Ok(Box::new(PowFn { value, exponent }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PowFn {
+    value: Box<dyn Expression>,
+    exponent: Box<dyn Expression>,
+}
+
+impl Expression for PowFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = f64::try_from(&self.value.resolve(ctx)?)?;
+        let exponent = f64::try_from(&self.exponent.resolve(ctx)?)?;
+
+        Ok(finite_float(value.powf(exponent))?)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        pow => Pow;
+
+        integers {
+            args: func_args![value: 2, exponent: 10],
+            want: Ok(1024.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        fractional_exponent {
+            args: func_args![value: 16.0, exponent: 0.5],
+            want: Ok(4.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        negative_exponent {
+            args: func_args![value: 2, exponent: -2],
+            want: Ok(0.25),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        root_of_negative {
+            args: func_args![value: -8, exponent: 0.5],
+            want: Err("result is not a real number"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        overflow {
+            args: func_args![value: 10, exponent: 400],
+            want: Err("result is too large to be a float"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+    ];
+}
@@ -8,6 +8,10 @@ impl Function for Replace {
         "replace"
     }
 
+    fn is_pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
@@ -1,6 +1,8 @@
 use vrl::prelude::*;
 
-use crate::util::round_to_precision;
+use rust_decimal::RoundingStrategy;
+
+use crate::util::{round_decimal_to_precision, round_to_precision};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Round;
@@ -14,7 +16,7 @@ impl Function for Round {
         &[
             Parameter {
                 keyword: "value",
-                kind: kind::INTEGER | kind::FLOAT,
+                kind: kind::INTEGER | kind::FLOAT | kind::DECIMAL,
                 required: true,
             },
             Parameter {
@@ -70,10 +72,13 @@ impl Expression for RoundFn {
 
         match self.value.resolve(ctx)? {
             Value::Float(f) => Ok(round_to_precision(f.into_inner(), precision, f64::round).into()),
+            Value::Decimal(d) => round_decimal_to_precision(d, precision, RoundingStrategy::MidpointAwayFromZero)
+                .map(Into::into)
+                .ok_or_else(|| "decimal precision out of range".into()),
             value @ Value::Integer(_) => Ok(value),
             value => Err(value::Error::Expected {
                 got: value.kind(),
-                expected: Kind::Float | Kind::Integer,
+                expected: Kind::Float | Kind::Integer | Kind::Decimal,
             }
             .into()),
         }
@@ -132,5 +137,21 @@ mod tests {
              want: Ok(9876543210123456789098765432101234567890987654321.98765),
              tdef: TypeDef::new().infallible().integer(),
          }
+
+        decimal {
+             args: func_args![value: "1234.565".parse::<rust_decimal::Decimal>().unwrap(),
+                              precision: 2
+             ],
+             want: Ok("1234.57".parse::<rust_decimal::Decimal>().unwrap()),
+             tdef: TypeDef::new().infallible().integer(),
+         }
+
+        decimal_negative_precision {
+             args: func_args![value: "1250.5".parse::<rust_decimal::Decimal>().unwrap(),
+                              precision: -2
+             ],
+             want: Ok("1300".parse::<rust_decimal::Decimal>().unwrap()),
+             tdef: TypeDef::new().infallible().integer(),
+         }
     ];
 }
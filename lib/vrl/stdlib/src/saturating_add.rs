@@ -0,0 +1,98 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SaturatingAdd;
+
+impl Function for SaturatingAdd {
+    fn identifier(&self) -> &'static str {
+        "saturating_add"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "adds integers",
+                source: "saturating_add(1, 2)",
+                result: Ok("3"),
+            },
+            Example {
+                title: "overflow",
+                source: "saturating_add(9223372036854775807, 1)",
+                result: Ok("9223372036854775807"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(Box::new(SaturatingAddFn { value, other }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SaturatingAddFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl Expression for SaturatingAddFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_integer()?;
+        let other = self.other.resolve(ctx)?.try_integer()?;
+
+        Ok(value.saturating_add(other).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().integer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        saturating_add => SaturatingAdd;
+
+        add {
+            args: func_args![value: 1, other: 2],
+            want: Ok(3),
+            tdef: TypeDef::new().infallible().integer(),
+        }
+
+        overflow {
+            args: func_args![value: i64::MAX, other: 1],
+            want: Ok(i64::MAX),
+            tdef: TypeDef::new().infallible().integer(),
+        }
+
+        underflow {
+            args: func_args![value: i64::MIN, other: -1],
+            want: Ok(i64::MIN),
+            tdef: TypeDef::new().infallible().integer(),
+        }
+    ];
+}
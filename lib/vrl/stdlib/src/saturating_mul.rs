@@ -0,0 +1,98 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SaturatingMul;
+
+impl Function for SaturatingMul {
+    fn identifier(&self) -> &'static str {
+        "saturating_mul"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "multiplies integers",
+                source: "saturating_mul(3, 4)",
+                result: Ok("12"),
+            },
+            Example {
+                title: "overflow",
+                source: "saturating_mul(9223372036854775807, 2)",
+                result: Ok("9223372036854775807"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+
+        Ok(Box::new(SaturatingMulFn { value, other }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SaturatingMulFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+}
+
+impl Expression for SaturatingMulFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_integer()?;
+        let other = self.other.resolve(ctx)?.try_integer()?;
+
+        Ok(value.saturating_mul(other).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().integer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        saturating_mul => SaturatingMul;
+
+        mul {
+            args: func_args![value: 3, other: 4],
+            want: Ok(12),
+            tdef: TypeDef::new().infallible().integer(),
+        }
+
+        overflow {
+            args: func_args![value: i64::MAX, other: 2],
+            want: Ok(i64::MAX),
+            tdef: TypeDef::new().infallible().integer(),
+        }
+
+        underflow {
+            args: func_args![value: i64::MIN, other: 2],
+            want: Ok(i64::MIN),
+            tdef: TypeDef::new().infallible().integer(),
+        }
+    ];
+}
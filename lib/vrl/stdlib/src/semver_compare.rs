@@ -0,0 +1,161 @@
+use std::cmp::Ordering;
+
+use vrl::prelude::*;
+
+use crate::parse_semver::parse_version;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SemverCompare;
+
+impl Function for SemverCompare {
+    fn identifier(&self) -> &'static str {
+        "semver_compare"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "other",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "lenient",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "older version",
+                source: r#"semver_compare!("1.10.0", "1.9.3")"#,
+                result: Ok("1"),
+            },
+            Example {
+                title: "pre-release",
+                source: r#"semver_compare!("2.0.0-rc.1", "2.0.0")"#,
+                result: Ok("-1"),
+            },
+            Example {
+                title: "equal versions",
+                source: r#"semver_compare!("v2", "2.0.0", lenient: true)"#,
+                result: Ok("0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let other = arguments.required("other");
+        let lenient = arguments
+            .optional("lenient")
+            .unwrap_or_else(|| expr!(false));
+
+        Ok(Box::new(SemverCompareFn {
+            value,
+            other,
+            lenient,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SemverCompareFn {
+    value: Box<dyn Expression>,
+    other: Box<dyn Expression>,
+    lenient: Box<dyn Expression>,
+}
+
+impl Expression for SemverCompareFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let other = self.other.resolve(ctx)?;
+        let lenient = self.lenient.resolve(ctx)?.try_boolean()?;
+
+        let value = parse_version(&value.try_bytes_utf8_lossy()?, lenient)?;
+        let other = parse_version(&other.try_bytes_utf8_lossy()?, lenient)?;
+
+        // Build metadata doesn't take part in the precedence of versions.
+        let ordering = value
+            .major
+            .cmp(&other.major)
+            .then(value.minor.cmp(&other.minor))
+            .then(value.patch.cmp(&other.patch))
+            .then(value.pre.cmp(&other.pre));
+
+        Ok(match ordering {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        }
+        .into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().integer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        semver_compare => SemverCompare;
+
+        greater {
+            args: func_args![value: "1.10.0", other: "1.9.3"],
+            want: Ok(1),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        less {
+            args: func_args![value: "0.9.9", other: "1.0.0"],
+            want: Ok(-1),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        pre_release_precedes_release {
+            args: func_args![value: "2.0.0-rc.1", other: "2.0.0"],
+            want: Ok(-1),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        pre_release_ordering {
+            args: func_args![value: "1.0.0-alpha.10", other: "1.0.0-alpha.9"],
+            want: Ok(1),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        ignores_build_metadata {
+            args: func_args![value: "1.0.0+linux", other: "1.0.0+darwin"],
+            want: Ok(0),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        lenient {
+            args: func_args![value: "v2", other: "2.0.0", lenient: true],
+            want: Ok(0),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        invalid {
+            args: func_args![value: "2", other: "2.0.0"],
+            want: Err("unable to parse version: unexpected end of input while parsing major version number"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+    ];
+}
@@ -0,0 +1,142 @@
+use semver::VersionReq;
+use vrl::prelude::*;
+
+use crate::parse_semver::parse_version;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SemverMatches;
+
+impl Function for SemverMatches {
+    fn identifier(&self) -> &'static str {
+        "semver_matches"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "version",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "requirement",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "lenient",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "matching version",
+                source: r#"semver_matches!("1.4.2", ">=1.2, <2")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "old agent",
+                source: r#"semver_matches!("v0.9", "<1.0.0", lenient: true)"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "pre-release",
+                source: r#"semver_matches!("2.0.0-rc.1", "^1.2")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let version = arguments.required("version");
+        let requirement = arguments.required("requirement");
+        let lenient = arguments
+            .optional("lenient")
+            .unwrap_or_else(|| expr!(false));
+
+        Ok(Box::new(SemverMatchesFn {
+            version,
+            requirement,
+            lenient,
+        }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SemverMatchesFn {
+    version: Box<dyn Expression>,
+    requirement: Box<dyn Expression>,
+    lenient: Box<dyn Expression>,
+}
+
+impl Expression for SemverMatchesFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let version = self.version.resolve(ctx)?;
+        let requirement = self.requirement.resolve(ctx)?;
+        let lenient = self.lenient.resolve(ctx)?.try_boolean()?;
+
+        let version = parse_version(&version.try_bytes_utf8_lossy()?, lenient)?;
+        let requirement = VersionReq::parse(&requirement.try_bytes_utf8_lossy()?)
+            .map_err(|error| format!("unable to parse requirement: {}", error))?;
+
+        Ok(requirement.matches(&version).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().boolean()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        semver_matches => SemverMatches;
+
+        matches {
+            args: func_args![version: "1.4.2", requirement: ">=1.2, <2"],
+            want: Ok(true),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        does_not_match {
+            args: func_args![version: "2.0.0", requirement: ">=1.2, <2"],
+            want: Ok(false),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        caret_and_tilde {
+            args: func_args![version: "1.9.0", requirement: "~1.9"],
+            want: Ok(true),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        pre_release_excluded {
+            args: func_args![version: "2.0.0-rc.1", requirement: ">=1.0.0"],
+            want: Ok(false),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        lenient {
+            args: func_args![version: "v0.9", requirement: "<1.0.0", lenient: true],
+            want: Ok(true),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+
+        invalid_requirement {
+            args: func_args![version: "1.0.0", requirement: "about 1"],
+            want: Err("unable to parse requirement: unexpected character 'a' while parsing major version number"),
+            tdef: TypeDef::new().fallible().boolean(),
+        }
+    ];
+}
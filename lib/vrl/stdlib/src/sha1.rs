@@ -9,6 +9,10 @@ impl Function for Sha1 {
         "sha1"
     }
 
+    fn is_pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[Parameter {
             keyword: "value",
@@ -9,6 +9,10 @@ impl Function for Sha2 {
         "sha2"
     }
 
+    fn is_pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[
             Parameter {
@@ -0,0 +1,160 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use vrl::prelude::*;
+
+/// The Twitter epoch (2010-11-04T01:42:54.657Z), which leaves room for IDs until 2080.
+const EPOCH_MILLIS: u64 = 1_288_834_974_657;
+const WORKER_BITS: u32 = 10;
+const SEQUENCE_BITS: u32 = 12;
+const MAX_WORKER_ID: i64 = (1 << WORKER_BITS) - 1;
+
+/// The last issued millisecond and sequence number, as `millis << SEQUENCE_BITS | sequence`.
+static LAST: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Clone, Copy, Debug)]
+pub struct SnowflakeId;
+
+impl Function for SnowflakeId {
+    fn identifier(&self) -> &'static str {
+        "snowflake_id"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "worker_id",
+            kind: kind::INTEGER,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "generate Snowflake ID",
+            source: r#"snowflake_id!(42) > 0"#,
+            result: Ok("true"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let worker_id = arguments.required("worker_id");
+
+        Ok(Box::new(SnowflakeIdFn { worker_id }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SnowflakeIdFn {
+    worker_id: Box<dyn Expression>,
+}
+
+impl Expression for SnowflakeIdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let worker_id = self.worker_id.resolve(ctx)?.try_integer()?;
+        if !(0..=MAX_WORKER_ID).contains(&worker_id) {
+            return Err(format!(
+                "`worker_id` must be between 0 and {}, got {}",
+                MAX_WORKER_ID, worker_id
+            )
+            .into());
+        }
+
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+
+        Ok(snowflake_id(next(millis), worker_id as u64).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().integer()
+    }
+}
+
+/// Claims the next millisecond and sequence number, never going backwards. Once the sequence of a
+/// millisecond is used up, or the clock steps back, IDs borrow from the following milliseconds
+/// instead of blocking until the clock catches up.
+fn next(millis: u64) -> u64 {
+    let now = millis.saturating_sub(EPOCH_MILLIS) << SEQUENCE_BITS;
+    let mut last = LAST.load(Ordering::Relaxed);
+    loop {
+        let next = now.max(last + 1);
+        match LAST.compare_exchange_weak(last, next, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return next,
+            Err(actual) => last = actual,
+        }
+    }
+}
+
+/// Lays out a Snowflake ID: 41 bits of milliseconds since the epoch, 10 bits of worker ID and a
+/// 12 bit sequence number. The sign bit stays clear, so IDs are positive integers.
+fn snowflake_id(next: u64, worker_id: u64) -> i64 {
+    let millis = (next >> SEQUENCE_BITS) & ((1 << 41) - 1);
+    let sequence = next & ((1 << SEQUENCE_BITS) - 1);
+
+    ((millis << (WORKER_BITS + SEQUENCE_BITS)) | (worker_id << SEQUENCE_BITS) | sequence) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::TimeZone;
+
+    use super::*;
+
+    test_type_def![worker_id {
+        expr: |_| {
+            SnowflakeIdFn {
+                worker_id: expr!(42),
+            }
+        },
+        want: TypeDef::new().fallible().integer(),
+    }];
+
+    fn resolve(worker_id: i64) -> Resolved {
+        let mut state = vrl::state::Runtime::default();
+        let mut object: Value = map![].into();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut object, &mut state, &tz);
+
+        SnowflakeIdFn {
+            worker_id: expr!(worker_id),
+        }
+        .resolve(&mut ctx)
+    }
+
+    #[test]
+    fn increasing() {
+        let first = resolve(42).unwrap().try_integer().unwrap();
+        let second = resolve(42).unwrap().try_integer().unwrap();
+
+        assert!(first > 0);
+        assert!(second > first);
+        assert_eq!((second >> SEQUENCE_BITS) & MAX_WORKER_ID, 42);
+    }
+
+    #[test]
+    fn invalid_worker_id() {
+        assert_eq!(
+            resolve(1024).unwrap_err().to_string(),
+            "`worker_id` must be between 0 and 1023, got 1024"
+        );
+        assert!(resolve(-1).is_err());
+    }
+
+    #[test]
+    fn layout() {
+        let next = (1_000 << SEQUENCE_BITS) | 7;
+        assert_eq!(snowflake_id(next, 1), (1_000 << 22) | (1 << 12) | 7);
+
+        // Running out of sequence numbers carries over into the next millisecond.
+        let next = (1_000 << SEQUENCE_BITS) | 4095;
+        assert!(snowflake_id(next + 1, 1) > snowflake_id(next, 1));
+    }
+}
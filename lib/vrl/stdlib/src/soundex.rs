@@ -0,0 +1,164 @@
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Soundex;
+
+impl Function for Soundex {
+    fn identifier(&self) -> &'static str {
+        "soundex"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "soundex",
+                source: r#"soundex("Robert")"#,
+                result: Ok("R163"),
+            },
+            Example {
+                title: "similar sounding names",
+                source: r#"soundex("Robert") == soundex("Rupert")"#,
+                result: Ok("true"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(SoundexFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SoundexFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for SoundexFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self
+            .value
+            .resolve(ctx)?
+            .try_bytes_utf8_lossy()?
+            .into_owned();
+
+        Ok(soundex(&value).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().bytes()
+    }
+}
+
+/// Encodes the ASCII letters of `value` with American Soundex, ignoring any other characters.
+fn soundex(value: &str) -> String {
+    let mut letters = value
+        .chars()
+        .filter(char::is_ascii_alphabetic)
+        .map(|letter| letter.to_ascii_uppercase());
+    let first = match letters.next() {
+        Some(first) => first,
+        None => return String::new(),
+    };
+
+    let mut code = String::with_capacity(4);
+    code.push(first);
+    let mut previous = digit(first);
+    for letter in letters {
+        if code.len() == 4 {
+            break;
+        }
+        // Letters with the same digit are encoded once if they are only separated by H or W,
+        // but twice if they are separated by a vowel.
+        if letter == 'H' || letter == 'W' {
+            continue;
+        }
+        let digit = digit(letter);
+        if let Some(digit) = digit.filter(|digit| Some(*digit) != previous) {
+            code.push(digit);
+        }
+        previous = digit;
+    }
+    while code.len() < 4 {
+        code.push('0');
+    }
+
+    code
+}
+
+const fn digit(letter: char) -> Option<char> {
+    match letter {
+        'B' | 'F' | 'P' | 'V' => Some('1'),
+        'C' | 'G' | 'J' | 'K' | 'Q' | 'S' | 'X' | 'Z' => Some('2'),
+        'D' | 'T' => Some('3'),
+        'L' => Some('4'),
+        'M' | 'N' => Some('5'),
+        'R' => Some('6'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        soundex => Soundex;
+
+        name {
+            args: func_args![value: "Robert"],
+            want: Ok("R163"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        same_digits_separated_by_h {
+            args: func_args![value: "Ashcraft"],
+            want: Ok("A261"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        same_digits_separated_by_vowel {
+            args: func_args![value: "Tymczak"],
+            want: Ok("T522"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        first_letter_digit {
+            args: func_args![value: "Pfister"],
+            want: Ok("P236"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        padded {
+            args: func_args![value: "lee"],
+            want: Ok("L000"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        ignores_non_letters {
+            args: func_args![value: "O'Hara"],
+            want: Ok("O600"),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+
+        empty {
+            args: func_args![value: "123"],
+            want: Ok(""),
+            tdef: TypeDef::new().infallible().bytes(),
+        }
+    ];
+}
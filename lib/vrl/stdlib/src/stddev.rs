@@ -0,0 +1,127 @@
+use vrl::prelude::*;
+
+use crate::util::{array_to_floats, finite_float};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Stddev;
+
+impl Function for Stddev {
+    fn identifier(&self) -> &'static str {
+        "stddev"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::ARRAY,
+                required: true,
+            },
+            Parameter {
+                keyword: "sample",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "population standard deviation",
+                source: "stddev!([2, 4, 4, 4, 5, 5, 7, 9])",
+                result: Ok("2.0"),
+            },
+            Example {
+                title: "sample standard deviation",
+                source: "stddev!([1, 3, 5], sample: true)",
+                result: Ok("2.0"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let sample = arguments.optional("sample").unwrap_or_else(|| expr!(false));
+
+        Ok(Box::new(StddevFn { value, sample }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StddevFn {
+    value: Box<dyn Expression>,
+    sample: Box<dyn Expression>,
+}
+
+impl Expression for StddevFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let values = array_to_floats(&self.value.resolve(ctx)?.try_array()?)?;
+        let sample = self.sample.resolve(ctx)?.try_boolean()?;
+
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let squares: f64 = values.iter().map(|value| (value - mean).powi(2)).sum();
+
+        // The sample standard deviation divides by one less than the number of values, to
+        // correct for the bias of estimating the mean from the same values.
+        let divisor = if sample {
+            if values.len() < 2 {
+                return Err("the sample standard deviation needs at least two numbers".into());
+            }
+            count - 1.0
+        } else {
+            count
+        };
+
+        Ok(finite_float((squares / divisor).sqrt())?)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().float()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        stddev => Stddev;
+
+        population {
+            args: func_args![value: value!([2, 4, 4, 4, 5, 5, 7, 9])],
+            want: Ok(2.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        sample {
+            args: func_args![value: value!([1, 3, 5]), sample: true],
+            want: Ok(2.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        single {
+            args: func_args![value: value!([1.5])],
+            want: Ok(0.0),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        single_sample {
+            args: func_args![value: value!([1.5]), sample: true],
+            want: Err("the sample standard deviation needs at least two numbers"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+
+        empty {
+            args: func_args![value: value!([])],
+            want: Err("array must not be empty"),
+            tdef: TypeDef::new().fallible().float(),
+        }
+    ];
+}
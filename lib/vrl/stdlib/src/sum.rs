@@ -0,0 +1,136 @@
+use vrl::{prelude::*, value::IntegerOverflow};
+
+use crate::util::finite_float;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sum;
+
+impl Function for Sum {
+    fn identifier(&self) -> &'static str {
+        "sum"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ARRAY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "integers",
+                source: "sum!([3, 5, 8])",
+                result: Ok("16"),
+            },
+            Example {
+                title: "floats",
+                source: "sum!([0.5, 1, 2.25])",
+                result: Ok("3.75"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(SumFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SumFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for SumFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let array = self.value.resolve(ctx)?.try_array()?;
+
+        // The numbers are added the way the `+` operator adds them, so integers and decimals
+        // stay exact unless a float is added, but integer overflow is an error rather than
+        // wrapping around.
+        let mut sum = Value::Integer(0);
+        for (index, value) in array.into_iter().enumerate() {
+            if !matches!(
+                value,
+                Value::Integer(_) | Value::Float(_) | Value::Decimal(_)
+            ) {
+                return Err(format!(
+                    "element at index {} must be a number, not {}",
+                    index,
+                    value.kind()
+                )
+                .into());
+            }
+            sum = sum.try_add_with_overflow(value, IntegerOverflow::Error)?;
+            if let Value::Float(float) = sum {
+                sum = finite_float(float.into_inner())?;
+            }
+        }
+
+        Ok(sum)
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new()
+            .fallible()
+            .scalar(Kind::Integer | Kind::Float | Kind::Decimal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        sum => Sum;
+
+        integers {
+            args: func_args![value: value!([3, 5, 8])],
+            want: Ok(16),
+            tdef: TypeDef::new().fallible().scalar(Kind::Integer | Kind::Float | Kind::Decimal),
+        }
+
+        floats {
+            args: func_args![value: value!([0.5, 1, 2.25])],
+            want: Ok(3.75),
+            tdef: TypeDef::new().fallible().scalar(Kind::Integer | Kind::Float | Kind::Decimal),
+        }
+
+        decimals {
+            args: func_args![value: Value::Array(vec![
+                Value::Decimal("0.1".parse().unwrap()),
+                Value::Decimal("0.2".parse().unwrap()),
+                Value::Integer(1),
+            ])],
+            want: Ok(Value::Decimal("1.3".parse().unwrap())),
+            tdef: TypeDef::new().fallible().scalar(Kind::Integer | Kind::Float | Kind::Decimal),
+        }
+
+        empty {
+            args: func_args![value: value!([])],
+            want: Ok(0),
+            tdef: TypeDef::new().fallible().scalar(Kind::Integer | Kind::Float | Kind::Decimal),
+        }
+
+        not_a_number {
+            args: func_args![value: value!([1, "2"])],
+            want: Err(r#"element at index 1 must be a number, not "string""#),
+            tdef: TypeDef::new().fallible().scalar(Kind::Integer | Kind::Float | Kind::Decimal),
+        }
+
+        overflow {
+            args: func_args![value: value!([9_223_372_036_854_775_807_i64, 1])],
+            want: Err("integer overflow"),
+            tdef: TypeDef::new().fallible().scalar(Kind::Integer | Kind::Float | Kind::Decimal),
+        }
+    ];
+}
@@ -96,6 +96,7 @@ fn tag_type_externally(value: Value) -> Value {
         value @ Value::Bytes(_) => (Some("string"), value),
         value @ Value::Integer(_) => (Some("integer"), value),
         value @ Value::Float(_) => (Some("float"), value),
+        value @ Value::Decimal(_) => (Some("decimal"), value),
         value @ Value::Boolean(_) => (Some("boolean"), value),
         Value::Object(object) => (
             None,
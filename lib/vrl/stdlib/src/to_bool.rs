@@ -159,6 +159,7 @@ impl Expression for ToBoolFn {
             Boolean(_) => Ok(value),
             Integer(v) => Ok(Boolean(v != 0)),
             Float(v) => Ok(Boolean(v != 0.0)),
+            Decimal(v) => Ok(Boolean(!v.is_zero())),
             Null => Ok(Boolean(false)),
             Bytes(v) => Conversion::Boolean
                 .convert(v)
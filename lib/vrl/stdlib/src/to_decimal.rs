@@ -0,0 +1,167 @@
+use std::str::FromStr;
+
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct ToDecimal;
+
+impl Function for ToDecimal {
+    fn identifier(&self) -> &'static str {
+        "to_decimal"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::ANY,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "integer",
+                source: "to_decimal(5)",
+                result: Ok("5"),
+            },
+            Example {
+                title: "float",
+                source: "to_decimal!(5.25)",
+                result: Ok("5.25"),
+            },
+            Example {
+                title: "true",
+                source: "to_decimal(true)",
+                result: Ok("1"),
+            },
+            Example {
+                title: "null",
+                source: "to_decimal(null)",
+                result: Ok("0"),
+            },
+            Example {
+                title: "valid string",
+                source: "to_decimal!(s'12.34')",
+                result: Ok("12.34"),
+            },
+            Example {
+                title: "large integer string",
+                source: "to_decimal!(s'18446744073709551615')",
+                result: Ok("18446744073709551615"),
+            },
+            Example {
+                title: "invalid string",
+                source: "to_decimal!(s'foobar')",
+                result: Err(
+                    r#"function call error for "to_decimal" at (0:22): Invalid decimal "foobar""#,
+                ),
+            },
+            Example {
+                title: "array",
+                source: "to_decimal!([])",
+                result: Err(
+                    r#"function call error for "to_decimal" at (0:15): unable to coerce "array" into "decimal""#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(Box::new(ToDecimalFn { value }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ToDecimalFn {
+    value: Box<dyn Expression>,
+}
+
+impl Expression for ToDecimalFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        match value {
+            Value::Decimal(_) => Ok(value),
+            Value::Integer(v) => Ok(Decimal::from(v).into()),
+            Value::Float(v) => Decimal::from_f64(*v)
+                .map(Into::into)
+                .ok_or_else(|| format!(r#"Invalid decimal "{}""#, v).into()),
+            Value::Boolean(v) => Ok(Decimal::from(i64::from(v)).into()),
+            Value::Null => Ok(Decimal::ZERO.into()),
+            Value::Bytes(v) => {
+                let v = String::from_utf8_lossy(&v);
+
+                Decimal::from_str(&v)
+                    .or_else(|_| Decimal::from_scientific(&v))
+                    .map(Into::into)
+                    .map_err(|_| format!(r#"Invalid decimal "{}""#, v).into())
+            }
+            v => Err(format!(r#"unable to coerce {} into "decimal""#, v.kind()).into()),
+        }
+    }
+
+    fn type_def(&self, state: &state::Compiler) -> TypeDef {
+        TypeDef::new()
+            .with_fallibility(self.value.type_def(state).has_kind(
+                Kind::Bytes
+                    | Kind::Float
+                    | Kind::Timestamp
+                    | Kind::Array
+                    | Kind::Object
+                    | Kind::Regex,
+            ))
+            .decimal()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decimal(v: &str) -> rust_decimal::Decimal {
+        v.parse().unwrap()
+    }
+
+    test_function![
+        to_decimal => ToDecimal;
+
+        string {
+             args: func_args![value: "1234.5678"],
+             want: Ok(decimal("1234.5678")),
+             tdef: TypeDef::new().fallible().decimal(),
+        }
+
+        large_integer_string {
+             args: func_args![value: "18446744073709551615"],
+             want: Ok(decimal("18446744073709551615")),
+             tdef: TypeDef::new().fallible().decimal(),
+        }
+
+        scientific_string {
+             args: func_args![value: "1.5e3"],
+             want: Ok(decimal("1500")),
+             tdef: TypeDef::new().fallible().decimal(),
+        }
+
+        integer {
+             args: func_args![value: 20],
+             want: Ok(decimal("20")),
+             tdef: TypeDef::new().infallible().decimal(),
+        }
+
+        float {
+             args: func_args![value: 20.5],
+             want: Ok(decimal("20.5")),
+             tdef: TypeDef::new().fallible().decimal(),
+        }
+    ];
+}
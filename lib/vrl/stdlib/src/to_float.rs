@@ -111,6 +111,7 @@ impl Expression for ToFloatFn {
         match value {
             Float(_) => Ok(value),
             Integer(v) => Ok((v as f64).into()),
+            Decimal(_) => Ok(f64::try_from(&value)?.into()),
             Boolean(v) => Ok(NotNan::new(if v { 1.0 } else { 0.0 }).unwrap().into()),
             Null => Ok(0.0.into()),
             Timestamp(v) => Ok((v.timestamp_nanos() as f64 / 1_000_000_000_f64).into()),
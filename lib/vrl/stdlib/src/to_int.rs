@@ -111,6 +111,7 @@ impl Expression for ToIntFn {
         match value {
             Integer(_) => Ok(value),
             Float(v) => Ok(Integer(v.into_inner() as i64)),
+            Decimal(_) => Ok(Integer(i64::try_from(&value)?)),
             Boolean(v) => Ok(Integer(if v { 1 } else { 0 })),
             Null => Ok(0.into()),
             Bytes(v) => Conversion::Integer
@@ -126,7 +127,9 @@ impl Expression for ToIntFn {
             .with_fallibility(
                 self.value
                     .type_def(state)
-                    .has_kind(Kind::Bytes | Kind::Array | Kind::Object | Kind::Regex),
+                    .has_kind(
+                        Kind::Bytes | Kind::Decimal | Kind::Array | Kind::Object | Kind::Regex,
+                    ),
             )
             .integer()
     }
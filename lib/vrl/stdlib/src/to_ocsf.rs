@@ -0,0 +1,824 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use regex::Regex;
+use vrl::prelude::*;
+
+/// The version of the Open Cybersecurity Schema Framework events are mapped to.
+const OCSF_VERSION: &str = "1.0.0";
+
+/// The attributes every OCSF event class requires.
+const BASE_REQUIRED_ATTRIBUTES: &[&str] = &[
+    "activity_id",
+    "category_uid",
+    "class_uid",
+    "metadata.product",
+    "metadata.version",
+    "severity_id",
+    "time",
+    "type_uid",
+];
+
+lazy_static! {
+    static ref REGEX_SSHD: Regex = Regex::new(
+        r#"(?x)                                   # Ignore whitespace and comments in the regex expression.
+        ^(?P<result>Accepted|Failed)\s            # Match whether the attempt succeeded.
+        (?P<method>\S+)\s                         # Match the authentication method, e.g. `publickey`.
+        for\s(?:invalid\suser\s)?(?P<user>\S+)\s  # Match the user, which may not exist.
+        from\s(?P<ip>\S+)\s                       # Match the address of the client.
+        port\s(?P<port>\d+)                       # Match the port of the client.
+    "#)
+    .expect("failed compiling regex for sshd");
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Class {
+    Authentication,
+    NetworkActivity,
+    ApiActivity,
+}
+
+impl Class {
+    fn all_value() -> Vec<Value> {
+        vec!["authentication", "network_activity", "api_activity"]
+            .into_iter()
+            .map(Value::from)
+            .collect()
+    }
+
+    const fn class_uid(self) -> i64 {
+        match self {
+            Self::Authentication => 3002,
+            Self::NetworkActivity => 4001,
+            Self::ApiActivity => 6003,
+        }
+    }
+
+    const fn category_uid(self) -> i64 {
+        self.class_uid() / 1000
+    }
+
+    /// The attributes this class requires on top of the base ones.
+    const fn required_attributes(self) -> &'static [&'static str] {
+        match self {
+            Self::Authentication => &["user"],
+            Self::NetworkActivity => &["dst_endpoint", "src_endpoint"],
+            Self::ApiActivity => &["actor", "api", "src_endpoint"],
+        }
+    }
+}
+
+impl FromStr for Class {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "authentication" => Ok(Self::Authentication),
+            "network_activity" => Ok(Self::NetworkActivity),
+            "api_activity" => Ok(Self::ApiActivity),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The format of the value being mapped.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Profile {
+    /// A CloudTrail record.
+    CloudTrail,
+    /// A VPC flow log, as parsed by `parse_aws_vpc_flow_log`.
+    VpcFlow,
+    /// An sshd authentication message, as parsed by `parse_syslog`.
+    SyslogAuth,
+}
+
+impl Profile {
+    fn all_value() -> Vec<Value> {
+        vec!["cloudtrail", "vpc_flow", "syslog_auth"]
+            .into_iter()
+            .map(Value::from)
+            .collect()
+    }
+
+    fn supports(self, class: Class) -> bool {
+        matches!(
+            (self, class),
+            (Self::CloudTrail, Class::ApiActivity)
+                | (Self::CloudTrail, Class::Authentication)
+                | (Self::VpcFlow, Class::NetworkActivity)
+                | (Self::SyslogAuth, Class::Authentication)
+        )
+    }
+}
+
+impl FromStr for Profile {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cloudtrail" => Ok(Self::CloudTrail),
+            "vpc_flow" => Ok(Self::VpcFlow),
+            "syslog_auth" => Ok(Self::SyslogAuth),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ToOcsf;
+
+impl Function for ToOcsf {
+    fn identifier(&self) -> &'static str {
+        "to_ocsf"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::OBJECT,
+                required: true,
+            },
+            Parameter {
+                keyword: "class",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "profile",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let class = arguments
+            .required_enum("class", &Class::all_value())?
+            .try_bytes_utf8_lossy()
+            .map(|s| Class::from_str(&s).expect("validated enum"))
+            .expect("class not bytes");
+        let profile_value = arguments.required_enum("profile", &Profile::all_value())?;
+        let profile = profile_value
+            .try_bytes_utf8_lossy()
+            .map(|s| Profile::from_str(&s).expect("validated enum"))
+            .expect("profile not bytes");
+
+        if !profile.supports(class) {
+            return Err(Box::new(vrl::function::Error::InvalidArgument {
+                keyword: "profile",
+                value: profile_value,
+                error: "profile can't be mapped to the given class",
+            }));
+        }
+
+        Ok(Box::new(ToOcsfFn {
+            value,
+            class,
+            profile,
+        }))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "sshd login",
+            source: r#"to_ocsf!({"appname": "sshd", "hostname": "bastion", "message": "Accepted publickey for alice from 10.0.0.1 port 52314 ssh2", "timestamp": t'2022-03-01T00:00:00Z'}, "authentication", "syslog_auth")"#,
+            result: Ok(indoc! {r#"{
+                "activity_id": 1,
+                "auth_protocol": "publickey",
+                "category_uid": 3,
+                "class_uid": 3002,
+                "dst_endpoint": {"hostname": "bastion"},
+                "metadata": {"product": {"name": "sshd"}, "version": "1.0.0"},
+                "severity_id": 1,
+                "src_endpoint": {"ip": "10.0.0.1", "port": 52314},
+                "status": "Success",
+                "status_id": 1,
+                "time": 1646092800000,
+                "type_uid": 300201,
+                "user": {"name": "alice"}
+            }"#}),
+        }]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ToOcsfFn {
+    value: Box<dyn Expression>,
+    class: Class,
+    profile: Profile,
+}
+
+impl Expression for ToOcsfFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?.try_object()?;
+
+        let (activity_id, mut event) = match (self.profile, self.class) {
+            (Profile::CloudTrail, Class::ApiActivity) => from_cloudtrail_api_activity(&value),
+            (Profile::CloudTrail, Class::Authentication) => from_cloudtrail_authentication(&value),
+            (Profile::VpcFlow, _) => from_vpc_flow(&value),
+            (Profile::SyslogAuth, _) => from_syslog_auth(&value)?,
+            _ => unreachable!("validated at compile time"),
+        };
+
+        let class_uid = self.class.class_uid();
+        insert(&mut event, &["activity_id"], activity_id);
+        insert(&mut event, &["category_uid"], self.class.category_uid());
+        insert(&mut event, &["class_uid"], class_uid);
+        insert(&mut event, &["type_uid"], class_uid * 100 + activity_id);
+        insert(&mut event, &["metadata", "version"], OCSF_VERSION);
+
+        if let Some(attribute) = BASE_REQUIRED_ATTRIBUTES
+            .iter()
+            .chain(self.class.required_attributes())
+            .find(|attribute| get(&event, attribute).is_none())
+        {
+            return Err(format!("missing required OCSF attribute {:?}", attribute).into());
+        }
+
+        Ok(event.into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().object::<(), Kind>(map! {
+            (): Kind::all()
+        })
+    }
+}
+
+/// Look up a dotted path, treating nulls as missing.
+fn get<'a>(object: &'a BTreeMap<String, Value>, path: &str) -> Option<&'a Value> {
+    let mut parts = path.split('.');
+    let mut value = object.get(parts.next()?)?;
+    for part in parts {
+        value = value.as_object()?.get(part)?;
+    }
+    (!value.is_null()).then(|| value)
+}
+
+/// Insert a value at a path, creating objects along the way. Nulls aren't inserted, so that
+/// mapping a missing field leaves the attribute unset.
+fn insert(object: &mut BTreeMap<String, Value>, path: &[&str], value: impl Into<Value>) {
+    let value = value.into();
+    if value.is_null() {
+        return;
+    }
+
+    let (last, parents) = path.split_last().expect("path is not empty");
+    let mut object = object;
+    for parent in parents {
+        let entry = object
+            .entry((*parent).to_owned())
+            .or_insert_with(|| Value::from(BTreeMap::new()));
+        object = entry.as_object_mut().expect("only objects are inserted");
+    }
+    object.insert((*last).to_owned(), value);
+}
+
+fn field(object: &BTreeMap<String, Value>, path: &str) -> Value {
+    get(object, path).cloned().unwrap_or(Value::Null)
+}
+
+fn millis(value: Option<&Value>) -> Value {
+    match value {
+        Some(Value::Timestamp(timestamp)) => timestamp.timestamp_millis().into(),
+        Some(Value::Bytes(bytes)) => DateTime::parse_from_rfc3339(&String::from_utf8_lossy(bytes))
+            .map(|timestamp| timestamp.with_timezone(&Utc).timestamp_millis().into())
+            .unwrap_or(Value::Null),
+        _ => Value::Null,
+    }
+}
+
+fn cloudtrail_common(record: &BTreeMap<String, Value>) -> BTreeMap<String, Value> {
+    let mut event = BTreeMap::new();
+    insert(&mut event, &["time"], millis(get(record, "eventTime")));
+    insert(&mut event, &["severity_id"], 1);
+    insert(&mut event, &["cloud", "provider"], "AWS");
+    insert(&mut event, &["cloud", "region"], field(record, "awsRegion"));
+    insert(
+        &mut event,
+        &["cloud", "account_uid"],
+        field(record, "recipientAccountId"),
+    );
+    insert(&mut event, &["metadata", "product", "name"], "CloudTrail");
+    insert(&mut event, &["metadata", "product", "vendor_name"], "AWS");
+    insert(
+        &mut event,
+        &["metadata", "product", "version"],
+        field(record, "eventVersion"),
+    );
+    insert(&mut event, &["metadata", "uid"], field(record, "eventID"));
+    insert(
+        &mut event,
+        &["src_endpoint", "ip"],
+        field(record, "sourceIPAddress"),
+    );
+    insert(
+        &mut event,
+        &["http_request", "user_agent"],
+        field(record, "userAgent"),
+    );
+    event
+}
+
+fn cloudtrail_user(record: &BTreeMap<String, Value>) -> BTreeMap<String, Value> {
+    let mut user = BTreeMap::new();
+    for (attribute, path) in [
+        ("type", "userIdentity.type"),
+        ("uid", "userIdentity.principalId"),
+        ("name", "userIdentity.userName"),
+        ("account_uid", "userIdentity.accountId"),
+        ("credential_uid", "userIdentity.accessKeyId"),
+    ] {
+        insert(&mut user, &[attribute], field(record, path));
+    }
+    user
+}
+
+/// Map an API call recorded by CloudTrail, with the activity taken from the verb the operation
+/// starts with.
+fn from_cloudtrail_api_activity(
+    record: &BTreeMap<String, Value>,
+) -> (i64, BTreeMap<String, Value>) {
+    let operation = get(record, "eventName")
+        .and_then(|value| value.try_bytes_utf8_lossy().ok())
+        .unwrap_or_default();
+    let activity_id = if operation.starts_with("Create") {
+        1
+    } else if ["Get", "Describe", "List", "Head", "Lookup"]
+        .iter()
+        .any(|verb| operation.starts_with(verb))
+    {
+        2
+    } else if ["Update", "Put", "Modify", "Set"]
+        .iter()
+        .any(|verb| operation.starts_with(verb))
+    {
+        3
+    } else if ["Delete", "Remove"]
+        .iter()
+        .any(|verb| operation.starts_with(verb))
+    {
+        4
+    } else {
+        99
+    };
+
+    let mut event = cloudtrail_common(record);
+    let user = cloudtrail_user(record);
+    if !user.is_empty() {
+        insert(&mut event, &["actor", "user"], user);
+    }
+    insert(
+        &mut event,
+        &["actor", "invoked_by"],
+        field(record, "userIdentity.invokedBy"),
+    );
+    insert(
+        &mut event,
+        &["api", "operation"],
+        field(record, "eventName"),
+    );
+    insert(
+        &mut event,
+        &["api", "service", "name"],
+        field(record, "eventSource"),
+    );
+    insert(
+        &mut event,
+        &["api", "request", "uid"],
+        field(record, "requestID"),
+    );
+
+    let failed = get(record, "errorCode").is_some();
+    insert(
+        &mut event,
+        &["status"],
+        if failed { "Failure" } else { "Success" },
+    );
+    insert(&mut event, &["status_id"], if failed { 2 } else { 1 });
+    insert(&mut event, &["status_code"], field(record, "errorCode"));
+    insert(
+        &mut event,
+        &["status_detail"],
+        field(record, "errorMessage"),
+    );
+
+    for (attribute, path) in [
+        ("request_parameters", "requestParameters"),
+        ("response_elements", "responseElements"),
+        ("additional_event_data", "additionalEventData"),
+    ] {
+        insert(&mut event, &["unmapped", attribute], field(record, path));
+    }
+
+    (activity_id, event)
+}
+
+/// Map a console sign in recorded by CloudTrail.
+fn from_cloudtrail_authentication(
+    record: &BTreeMap<String, Value>,
+) -> (i64, BTreeMap<String, Value>) {
+    let mut event = cloudtrail_common(record);
+    let user = cloudtrail_user(record);
+    if !user.is_empty() {
+        insert(&mut event, &["user"], user);
+    }
+
+    let succeeded = matches!(
+        get(record, "responseElements.ConsoleLogin"),
+        Some(Value::Bytes(result)) if result.as_ref() == b"Success"
+    );
+    insert(
+        &mut event,
+        &["status"],
+        if succeeded { "Success" } else { "Failure" },
+    );
+    insert(&mut event, &["status_id"], if succeeded { 1 } else { 2 });
+    insert(
+        &mut event,
+        &["status_detail"],
+        field(record, "errorMessage"),
+    );
+    insert(
+        &mut event,
+        &["is_mfa"],
+        get(record, "additionalEventData.MFAUsed")
+            .map(|used| matches!(used, Value::Bytes(used) if used.as_ref() == b"Yes")),
+    );
+
+    // Logon
+    (1, event)
+}
+
+/// Map a flow log record, whose `start` and `end` are in seconds since the epoch.
+fn from_vpc_flow(record: &BTreeMap<String, Value>) -> (i64, BTreeMap<String, Value>) {
+    let seconds_to_millis = |path| match get(record, path) {
+        Some(Value::Integer(seconds)) => Value::from(seconds * 1000),
+        _ => Value::Null,
+    };
+
+    let mut event = BTreeMap::new();
+    insert(&mut event, &["time"], seconds_to_millis("end"));
+    insert(&mut event, &["start_time"], seconds_to_millis("start"));
+    insert(&mut event, &["end_time"], seconds_to_millis("end"));
+    insert(&mut event, &["severity_id"], 1);
+    insert(
+        &mut event,
+        &["src_endpoint", "ip"],
+        field(record, "srcaddr"),
+    );
+    insert(
+        &mut event,
+        &["src_endpoint", "port"],
+        field(record, "srcport"),
+    );
+    insert(
+        &mut event,
+        &["src_endpoint", "interface_uid"],
+        field(record, "interface_id"),
+    );
+    insert(
+        &mut event,
+        &["dst_endpoint", "ip"],
+        field(record, "dstaddr"),
+    );
+    insert(
+        &mut event,
+        &["dst_endpoint", "port"],
+        field(record, "dstport"),
+    );
+    insert(
+        &mut event,
+        &["connection_info", "protocol_num"],
+        field(record, "protocol"),
+    );
+    insert(
+        &mut event,
+        &["traffic", "packets"],
+        field(record, "packets"),
+    );
+    insert(&mut event, &["traffic", "bytes"], field(record, "bytes"));
+    insert(&mut event, &["cloud", "provider"], "AWS");
+    insert(
+        &mut event,
+        &["cloud", "account_uid"],
+        // Account ids are parsed as integers, but OCSF uids are strings.
+        get(record, "account_id").map(|id| match id {
+            Value::Integer(id) => Value::from(id.to_string()),
+            id => id.clone(),
+        }),
+    );
+    insert(&mut event, &["metadata", "product", "name"], "Amazon VPC");
+    insert(&mut event, &["metadata", "product", "vendor_name"], "AWS");
+    insert(
+        &mut event,
+        &["metadata", "product", "version"],
+        field(record, "version"),
+    );
+
+    // Traffic, or Refuse for rejected connections.
+    let activity_id = match get(record, "action") {
+        Some(Value::Bytes(action)) if action.as_ref() == b"REJECT" => 5,
+        _ => 6,
+    };
+    (activity_id, event)
+}
+
+/// Map an sshd log in attempt.
+fn from_syslog_auth(
+    record: &BTreeMap<String, Value>,
+) -> std::result::Result<(i64, BTreeMap<String, Value>), ExpressionError> {
+    let message = get(record, "message")
+        .and_then(|value| value.try_bytes_utf8_lossy().ok())
+        .unwrap_or_default();
+    let captures = REGEX_SSHD
+        .captures(&message)
+        .ok_or("unrecognized authentication message")?;
+    let succeeded = &captures["result"] == "Accepted";
+
+    let mut event = BTreeMap::new();
+    insert(&mut event, &["time"], millis(get(record, "timestamp")));
+    insert(&mut event, &["severity_id"], if succeeded { 1 } else { 2 });
+    insert(
+        &mut event,
+        &["status"],
+        if succeeded { "Success" } else { "Failure" },
+    );
+    insert(&mut event, &["status_id"], if succeeded { 1 } else { 2 });
+    insert(&mut event, &["auth_protocol"], &captures["method"]);
+    insert(&mut event, &["user", "name"], &captures["user"]);
+    insert(&mut event, &["src_endpoint", "ip"], &captures["ip"]);
+    insert(
+        &mut event,
+        &["src_endpoint", "port"],
+        captures["port"].parse::<i64>().ok(),
+    );
+    insert(
+        &mut event,
+        &["dst_endpoint", "hostname"],
+        field(record, "hostname"),
+    );
+    insert(
+        &mut event,
+        &["metadata", "product", "name"],
+        get(record, "appname")
+            .cloned()
+            .unwrap_or_else(|| "sshd".into()),
+    );
+
+    // Logon
+    Ok((1, event))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tdef() -> TypeDef {
+        TypeDef::new().fallible().object::<(), Kind>(map! {
+            (): Kind::all()
+        })
+    }
+
+    test_function![
+        to_ocsf => ToOcsf;
+
+        cloudtrail_api_activity {
+            args: func_args![
+                value: map![
+                    "eventVersion": "1.08",
+                    "eventTime": "2022-03-01T00:00:00Z",
+                    "eventSource": "s3.amazonaws.com",
+                    "eventName": "DeleteBucket",
+                    "awsRegion": "us-east-1",
+                    "sourceIPAddress": "10.0.0.1",
+                    "userAgent": "aws-cli/2.4.0",
+                    "requestID": "ABC123",
+                    "eventID": "e6d8b4c2",
+                    "recipientAccountId": "111122223333",
+                    "userIdentity": map![
+                        "type": "IAMUser",
+                        "principalId": "AIDAEXAMPLE",
+                        "userName": "alice",
+                        "accountId": "111122223333",
+                    ],
+                    "requestParameters": map!["bucketName": "my-bucket"],
+                    "responseElements": Value::Null,
+                ],
+                class: "api_activity",
+                profile: "cloudtrail",
+            ],
+            want: Ok(map![
+                "activity_id": 4,
+                "actor": map![
+                    "user": map![
+                        "account_uid": "111122223333",
+                        "name": "alice",
+                        "type": "IAMUser",
+                        "uid": "AIDAEXAMPLE",
+                    ],
+                ],
+                "api": map![
+                    "operation": "DeleteBucket",
+                    "request": map!["uid": "ABC123"],
+                    "service": map!["name": "s3.amazonaws.com"],
+                ],
+                "category_uid": 6,
+                "class_uid": 6003,
+                "cloud": map![
+                    "account_uid": "111122223333",
+                    "provider": "AWS",
+                    "region": "us-east-1",
+                ],
+                "http_request": map!["user_agent": "aws-cli/2.4.0"],
+                "metadata": map![
+                    "product": map![
+                        "name": "CloudTrail",
+                        "vendor_name": "AWS",
+                        "version": "1.08",
+                    ],
+                    "uid": "e6d8b4c2",
+                    "version": "1.0.0",
+                ],
+                "severity_id": 1,
+                "src_endpoint": map!["ip": "10.0.0.1"],
+                "status": "Success",
+                "status_id": 1,
+                "time": 1_646_092_800_000_i64,
+                "type_uid": 600_304,
+                "unmapped": map![
+                    "request_parameters": map!["bucketName": "my-bucket"],
+                ],
+            ]),
+            tdef: tdef(),
+        }
+
+        cloudtrail_authentication {
+            args: func_args![
+                value: map![
+                    "eventVersion": "1.08",
+                    "eventTime": "2022-03-01T00:00:00Z",
+                    "eventName": "ConsoleLogin",
+                    "awsRegion": "us-east-1",
+                    "sourceIPAddress": "10.0.0.1",
+                    "recipientAccountId": "111122223333",
+                    "userIdentity": map!["type": "IAMUser", "userName": "alice"],
+                    "responseElements": map!["ConsoleLogin": "Failure"],
+                    "additionalEventData": map!["MFAUsed": "No"],
+                    "errorMessage": "Failed authentication",
+                ],
+                class: "authentication",
+                profile: "cloudtrail",
+            ],
+            want: Ok(map![
+                "activity_id": 1,
+                "category_uid": 3,
+                "class_uid": 3002,
+                "cloud": map![
+                    "account_uid": "111122223333",
+                    "provider": "AWS",
+                    "region": "us-east-1",
+                ],
+                "is_mfa": false,
+                "metadata": map![
+                    "product": map![
+                        "name": "CloudTrail",
+                        "vendor_name": "AWS",
+                        "version": "1.08",
+                    ],
+                    "version": "1.0.0",
+                ],
+                "severity_id": 1,
+                "src_endpoint": map!["ip": "10.0.0.1"],
+                "status": "Failure",
+                "status_detail": "Failed authentication",
+                "status_id": 2,
+                "time": 1_646_092_800_000_i64,
+                "type_uid": 300_201,
+                "user": map!["name": "alice", "type": "IAMUser"],
+            ]),
+            tdef: tdef(),
+        }
+
+        vpc_flow {
+            args: func_args![
+                value: map![
+                    "version": 2,
+                    "account_id": 111_122_223_333_i64,
+                    "interface_id": "eni-1235b8ca",
+                    "srcaddr": "172.31.16.139",
+                    "dstaddr": "172.31.16.21",
+                    "srcport": 20641,
+                    "dstport": 22,
+                    "protocol": 6,
+                    "packets": 20,
+                    "bytes": 4249,
+                    "start": 1_418_530_010,
+                    "end": 1_418_530_070,
+                    "action": "REJECT",
+                    "log_status": "OK",
+                ],
+                class: "network_activity",
+                profile: "vpc_flow",
+            ],
+            want: Ok(map![
+                "activity_id": 5,
+                "category_uid": 4,
+                "class_uid": 4001,
+                "cloud": map!["account_uid": "111122223333", "provider": "AWS"],
+                "connection_info": map!["protocol_num": 6],
+                "dst_endpoint": map!["ip": "172.31.16.21", "port": 22],
+                "end_time": 1_418_530_070_000_i64,
+                "metadata": map![
+                    "product": map![
+                        "name": "Amazon VPC",
+                        "vendor_name": "AWS",
+                        "version": 2,
+                    ],
+                    "version": "1.0.0",
+                ],
+                "severity_id": 1,
+                "src_endpoint": map![
+                    "interface_uid": "eni-1235b8ca",
+                    "ip": "172.31.16.139",
+                    "port": 20641,
+                ],
+                "start_time": 1_418_530_010_000_i64,
+                "time": 1_418_530_070_000_i64,
+                "traffic": map!["bytes": 4249, "packets": 20],
+                "type_uid": 400_105,
+            ]),
+            tdef: tdef(),
+        }
+
+        syslog_auth_failure {
+            args: func_args![
+                value: map![
+                    "appname": "sshd",
+                    "hostname": "bastion",
+                    "message": "Failed password for invalid user bob from 203.0.113.5 port 40022 ssh2",
+                    "timestamp": DateTime::parse_from_rfc3339("2022-03-01T00:00:00Z").unwrap().with_timezone(&Utc),
+                ],
+                class: "authentication",
+                profile: "syslog_auth",
+            ],
+            want: Ok(map![
+                "activity_id": 1,
+                "auth_protocol": "password",
+                "category_uid": 3,
+                "class_uid": 3002,
+                "dst_endpoint": map!["hostname": "bastion"],
+                "metadata": map![
+                    "product": map!["name": "sshd"],
+                    "version": "1.0.0",
+                ],
+                "severity_id": 2,
+                "src_endpoint": map!["ip": "203.0.113.5", "port": 40022],
+                "status": "Failure",
+                "status_id": 2,
+                "time": 1_646_092_800_000_i64,
+                "type_uid": 300_201,
+                "user": map!["name": "bob"],
+            ]),
+            tdef: tdef(),
+        }
+
+        syslog_auth_unrecognized {
+            args: func_args![
+                value: map!["appname": "sshd", "message": "Connection closed by 203.0.113.5 port 40022"],
+                class: "authentication",
+                profile: "syslog_auth",
+            ],
+            want: Err("unrecognized authentication message"),
+            tdef: tdef(),
+        }
+
+        missing_required_attribute {
+            args: func_args![
+                value: map!["eventName": "ListBuckets", "sourceIPAddress": "10.0.0.1"],
+                class: "api_activity",
+                profile: "cloudtrail",
+            ],
+            want: Err(r#"missing required OCSF attribute "time""#),
+            tdef: tdef(),
+        }
+
+        unsupported_class {
+            args: func_args![
+                value: map![],
+                class: "network_activity",
+                profile: "cloudtrail",
+            ],
+            want: Err("invalid argument"),
+            tdef: tdef(),
+        }
+    ];
+}
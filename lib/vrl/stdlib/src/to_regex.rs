@@ -9,6 +9,10 @@ impl Function for ToRegex {
         "to_regex"
     }
 
+    fn is_pure(&self) -> bool {
+        true
+    }
+
     fn parameters(&self) -> &'static [Parameter] {
         &[Parameter {
             keyword: "value",
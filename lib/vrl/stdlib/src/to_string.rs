@@ -103,6 +103,7 @@ impl Expression for ToStringFn {
             v @ Bytes(_) => v,
             Integer(v) => v.to_string().into(),
             Float(v) => v.to_string().into(),
+            Decimal(v) => v.to_string().into(),
             Boolean(v) => v.to_string().into(),
             Timestamp(v) => v.to_rfc3339_opts(SecondsFormat::AutoSi, true).into(),
             Null => "".into(),
@@ -119,6 +120,7 @@ impl Expression for ToStringFn {
                 Kind::Bytes
                     | Kind::Integer
                     | Kind::Float
+                    | Kind::Decimal
                     | Kind::Boolean
                     | Kind::Null
                     | Kind::Timestamp,
@@ -0,0 +1,155 @@
+use vrl::prelude::*;
+
+use crate::util::{syslog_facility_code, syslog_severity_code, SYSLOG_FACILITIES};
+
+#[derive(Clone, Copy, Debug)]
+pub struct ToSyslogPriority;
+
+impl Function for ToSyslogPriority {
+    fn identifier(&self) -> &'static str {
+        "to_syslog_priority"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "facility",
+                kind: kind::BYTES | kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "severity",
+                kind: kind::BYTES | kind::INTEGER,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "names",
+                source: "to_syslog_priority!(s'auth', s'crit')",
+                result: Ok("34"),
+            },
+            Example {
+                title: "codes",
+                source: "to_syslog_priority!(20, 5)",
+                result: Ok("165"),
+            },
+            Example {
+                title: "invalid",
+                source: "to_syslog_priority!(s'auth', s'foobar')",
+                result: Err(
+                    r#"function call error for "to_syslog_priority" at (0:39): syslog severity foobar not valid"#,
+                ),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let facility = arguments.required("facility");
+        let severity = arguments.required("severity");
+
+        Ok(Box::new(ToSyslogPriorityFn { facility, severity }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ToSyslogPriorityFn {
+    facility: Box<dyn Expression>,
+    severity: Box<dyn Expression>,
+}
+
+impl Expression for ToSyslogPriorityFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let facility = match self.facility.resolve(ctx)? {
+            Value::Integer(code) if (0..SYSLOG_FACILITIES.len() as i64).contains(&code) => code,
+            Value::Integer(code) => return Err(format!("facility code {} not valid", code).into()),
+            value => {
+                let name = value.try_bytes_utf8_lossy()?;
+                syslog_facility_code(&name)
+                    .ok_or_else(|| format!("syslog facility {} not valid", name))?
+            }
+        };
+
+        let severity = match self.severity.resolve(ctx)? {
+            Value::Integer(code) if (0..=7).contains(&code) => code,
+            Value::Integer(code) => return Err(format!("severity level {} not valid", code).into()),
+            value => {
+                let name = value.try_bytes_utf8_lossy()?;
+                syslog_severity_code(&name)
+                    .ok_or_else(|| format!("syslog severity {} not valid", name))?
+            }
+        };
+
+        // PRI: https://datatracker.ietf.org/doc/html/rfc5424#section-6.2.1
+        Ok((facility * 8 + severity).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().fallible().integer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        to_syslog_priority => ToSyslogPriority;
+
+        names {
+            args: func_args![facility: value!("auth"), severity: value!("crit")],
+            want: Ok(value!(34)),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        severity_alias {
+            args: func_args![facility: value!("local4"), severity: value!("warn")],
+            want: Ok(value!(164)),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        codes {
+            args: func_args![facility: value!(20), severity: value!(5)],
+            want: Ok(value!(165)),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        mixed {
+            args: func_args![facility: value!(0), severity: value!("emerg")],
+            want: Ok(value!(0)),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        maximum {
+            args: func_args![facility: value!("local7"), severity: value!("debug")],
+            want: Ok(value!(191)),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        invalid_facility_name {
+            args: func_args![facility: value!("nope"), severity: value!(1)],
+            want: Err("syslog facility nope not valid"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        invalid_facility_code {
+            args: func_args![facility: value!(24), severity: value!(1)],
+            want: Err("facility code 24 not valid"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+
+        invalid_severity_code {
+            args: func_args![facility: value!(1), severity: value!(-1)],
+            want: Err("severity level -1 not valid"),
+            tdef: TypeDef::new().fallible().integer(),
+        }
+    ];
+}
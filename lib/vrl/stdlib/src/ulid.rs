@@ -0,0 +1,112 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use vrl::prelude::*;
+
+/// Crockford's base32 alphabet, which leaves out `I`, `L`, `O` and `U`.
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+#[derive(Clone, Copy, Debug)]
+pub struct Ulid;
+
+impl Function for Ulid {
+    fn identifier(&self) -> &'static str {
+        "ulid"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "generate ULID",
+            source: r#"length(ulid())"#,
+            result: Ok("26"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        _: ArgumentList,
+    ) -> Compiled {
+        Ok(Box::new(UlidFn))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct UlidFn;
+
+impl Expression for UlidFn {
+    fn resolve(&self, _: &mut Context) -> Resolved {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+
+        Ok(Bytes::copy_from_slice(&ulid(millis, rand::random())).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().bytes()
+    }
+}
+
+/// Encodes 48 bits of Unix milliseconds followed by 80 random bits as 26 base32 characters, so
+/// that ULIDs sort lexicographically by time.
+fn ulid(millis: u64, random: u128) -> [u8; 26] {
+    let value = (u128::from(millis & 0xffff_ffff_ffff) << 80) | (random & ((1 << 80) - 1));
+
+    let mut ulid = [0; 26];
+    for (i, char) in ulid.iter_mut().rev().enumerate() {
+        *char = ALPHABET[((value >> (5 * i)) & 0x1f) as usize];
+    }
+    ulid
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::TimeZone;
+
+    use super::*;
+
+    test_type_def![default {
+        expr: |_| { UlidFn },
+        want: TypeDef::new().infallible().bytes(),
+    }];
+
+    #[test]
+    fn ulid() {
+        let mut state = vrl::state::Runtime::default();
+        let mut object: Value = map![].into();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut object, &mut state, &tz);
+        let value = UlidFn.resolve(&mut ctx).unwrap();
+
+        match value {
+            Value::Bytes(val) => {
+                assert_eq!(val.len(), 26);
+                assert!(val.iter().all(|byte| ALPHABET.contains(byte)));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn encoding() {
+        assert_eq!(
+            &super::ulid(1613059200000, 0),
+            b"01EY8X45000000000000000000"
+        );
+        assert_eq!(
+            &super::ulid(1613059200000, u128::MAX),
+            b"01EY8X4500ZZZZZZZZZZZZZZZZ"
+        );
+        assert_eq!(
+            &super::ulid(0xffff_ffff_ffff, u128::MAX),
+            b"7ZZZZZZZZZZZZZZZZZZZZZZZZZ"
+        );
+    }
+
+    #[test]
+    fn sortable() {
+        assert!(super::ulid(1613059200000, u128::MAX) < super::ulid(1613059200001, 0));
+    }
+}
@@ -11,6 +11,31 @@ where
     fun(num * multiplier as f64) / multiplier
 }
 
+/// Rounds the given decimal to the given precision using the given strategy.
+/// A negative precision rounds to the left of the decimal point.
+#[cfg(any(feature = "ceil", feature = "floor", feature = "round"))]
+pub(crate) fn round_decimal_to_precision(
+    num: rust_decimal::Decimal,
+    precision: i64,
+    strategy: rust_decimal::RoundingStrategy,
+) -> Option<rust_decimal::Decimal> {
+    use rust_decimal::Decimal;
+
+    if precision >= 0 {
+        let precision = u32::try_from(precision).unwrap_or(u32::MAX);
+        return Some(num.round_dp_with_strategy(precision, strategy));
+    }
+
+    let multiplier = u32::try_from(-precision)
+        .ok()
+        .and_then(|exp| 10_i64.checked_pow(exp))
+        .map(Decimal::from)?;
+
+    num.checked_div(multiplier)?
+        .round_dp_with_strategy(0, strategy)
+        .checked_mul(multiplier)
+}
+
 /// Takes a set of captures that have resulted from matching a regular expression
 /// against some text and fills a BTreeMap with the result.
 ///
@@ -122,3 +147,221 @@ impl std::str::FromStr for Base64Charset {
         }
     }
 }
+
+/// Syslog facility names, indexed by their facility code.
+///
+/// See <https://en.wikipedia.org/wiki/Syslog#Facility>.
+#[cfg(any(
+    feature = "from_syslog_facility",
+    feature = "parse_syslog_priority",
+    feature = "to_syslog_priority"
+))]
+pub(crate) const SYSLOG_FACILITIES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+/// Syslog severity names, indexed by their severity code.
+///
+/// See <https://en.wikipedia.org/wiki/Syslog#Severity_level>.
+#[cfg(any(feature = "parse_syslog_priority", feature = "to_syslog_priority"))]
+pub(crate) const SYSLOG_SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+#[cfg(any(feature = "from_syslog_facility", feature = "to_syslog_priority"))]
+pub(crate) fn syslog_facility_code(name: &str) -> Option<i64> {
+    SYSLOG_FACILITIES
+        .iter()
+        .position(|facility| *facility == name)
+        .map(|code| code as i64)
+}
+
+#[cfg(feature = "to_syslog_priority")]
+pub(crate) fn syslog_severity_code(name: &str) -> Option<i64> {
+    match name {
+        "panic" => Some(0),
+        "error" => Some(3),
+        "warn" => Some(4),
+        _ => SYSLOG_SEVERITIES
+            .iter()
+            .position(|severity| *severity == name)
+            .map(|code| code as i64),
+    }
+}
+
+/// Read grok pattern definitions from files in the Logstash pattern file format, where each
+/// line is a pattern name followed by whitespace and its definition. Empty lines and lines
+/// starting with `#` are skipped.
+///
+/// Defining the same pattern name twice, whether in the same file or across files, is an error,
+/// so that the outcome doesn't depend on the order of the files.
+#[cfg(any(feature = "parse_grok", feature = "parse_groks"))]
+pub(crate) fn read_grok_pattern_files(
+    paths: &[String],
+) -> Result<std::collections::BTreeMap<String, String>, String> {
+    use std::collections::btree_map::Entry;
+
+    let mut patterns = std::collections::BTreeMap::new();
+    let mut sources = std::collections::BTreeMap::new();
+
+    for path in paths {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| format!("unable to read pattern file {:?}: {}", path, err))?;
+
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (name, pattern) = line
+                .split_once(char::is_whitespace)
+                .map(|(name, pattern)| (name, pattern.trim_start()))
+                .filter(|(_, pattern)| !pattern.is_empty())
+                .ok_or_else(|| {
+                    format!(
+                        "invalid pattern definition at {}:{}, expected a name followed by a pattern",
+                        path,
+                        number + 1
+                    )
+                })?;
+
+            match patterns.entry(name.to_owned()) {
+                Entry::Occupied(_) => {
+                    return Err(format!(
+                        "pattern {:?} in {:?} is already defined in {:?}",
+                        name, path, sources[name]
+                    ))
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(pattern.to_owned());
+                    sources.insert(name.to_owned(), path.clone());
+                }
+            }
+        }
+    }
+
+    Ok(patterns)
+}
+
+#[cfg(any(feature = "compress", feature = "decompress"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Compression {
+    Gzip,
+    Zlib,
+    Zstd,
+    Snappy,
+    Lz4,
+}
+
+#[cfg(any(feature = "compress", feature = "decompress"))]
+impl Compression {
+    pub(crate) fn all_value() -> Vec<vrl::Value> {
+        use Compression::*;
+
+        vec![Gzip, Zlib, Zstd, Snappy, Lz4]
+            .into_iter()
+            .map(|c| c.as_str().into())
+            .collect()
+    }
+
+    pub(crate) const fn as_str(self) -> &'static str {
+        use Compression::*;
+
+        match self {
+            Gzip => "gzip",
+            Zlib => "zlib",
+            Zstd => "zstd",
+            Snappy => "snappy",
+            Lz4 => "lz4",
+        }
+    }
+}
+
+#[cfg(any(feature = "compress", feature = "decompress"))]
+impl std::str::FromStr for Compression {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        use Compression::*;
+
+        match s {
+            "gzip" => Ok(Gzip),
+            "zlib" => Ok(Zlib),
+            "zstd" => Ok(Zstd),
+            "snappy" => Ok(Snappy),
+            "lz4" => Ok(Lz4),
+            _ => Err("unknown compression algorithm"),
+        }
+    }
+}
+
+/// Converts the numbers in `array` to floats, for the aggregations computed over floats.
+#[cfg(any(
+    feature = "mean",
+    feature = "median",
+    feature = "percentile",
+    feature = "stddev"
+))]
+pub(crate) fn array_to_floats(array: &[vrl::Value]) -> Result<Vec<f64>, String> {
+    if array.is_empty() {
+        return Err("array must not be empty".to_owned());
+    }
+
+    array
+        .iter()
+        .enumerate()
+        .map(|(index, value)| match value {
+            vrl::Value::Integer(_) | vrl::Value::Float(_) | vrl::Value::Decimal(_) => {
+                f64::try_from(value).map_err(|error| error.to_string())
+            }
+            value => Err(format!(
+                "element at index {} must be a number, not {}",
+                index,
+                value.kind()
+            )),
+        })
+        .collect()
+}
+
+/// Returns the result of a float computation, unless it overflowed or has no real result, which
+/// a float value can't represent.
+#[cfg(any(
+    feature = "exp",
+    feature = "mean",
+    feature = "pow",
+    feature = "stddev",
+    feature = "sum"
+))]
+pub(crate) fn finite_float(float: f64) -> Result<vrl::Value, String> {
+    if float.is_nan() {
+        Err("result is not a real number".to_owned())
+    } else if float.is_infinite() {
+        Err("result is too large to be a float".to_owned())
+    } else {
+        Ok(float.into())
+    }
+}
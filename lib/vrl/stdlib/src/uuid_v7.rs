@@ -0,0 +1,110 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct UuidV7;
+
+impl Function for UuidV7 {
+    fn identifier(&self) -> &'static str {
+        "uuid_v7"
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "generate UUID v7",
+            source: r#"uuid_v7() != """#,
+            result: Ok("true"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        _: ArgumentList,
+    ) -> Compiled {
+        Ok(Box::new(UuidV7Fn))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct UuidV7Fn;
+
+impl Expression for UuidV7Fn {
+    fn resolve(&self, _: &mut Context) -> Resolved {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_millis() as u64);
+        let mut buf = [0; 36];
+        let uuid = uuid::Uuid::from_u128(uuid_v7(millis, rand::random()))
+            .to_hyphenated()
+            .encode_lower(&mut buf);
+
+        Ok(Bytes::copy_from_slice(uuid.as_bytes()).into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new().infallible().bytes()
+    }
+}
+
+/// Lays out a UUIDv7: 48 bits of Unix milliseconds, the version, 12 random bits, the RFC 4122
+/// variant and 62 more random bits.
+fn uuid_v7(millis: u64, random: u128) -> u128 {
+    (u128::from(millis & 0xffff_ffff_ffff) << 80)
+        | (0x7 << 76)
+        | (random & (0xfff << 64))
+        | (0b10 << 62)
+        | (random & 0x3fff_ffff_ffff_ffff)
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::TimeZone;
+
+    use super::*;
+
+    test_type_def![default {
+        expr: |_| { UuidV7Fn },
+        want: TypeDef::new().infallible().bytes(),
+    }];
+
+    #[test]
+    fn uuid_v7() {
+        let mut state = vrl::state::Runtime::default();
+        let mut object: Value = map![].into();
+        let tz = TimeZone::default();
+        let mut ctx = Context::new(&mut object, &mut state, &tz);
+        let value = UuidV7Fn.resolve(&mut ctx).unwrap();
+
+        assert!(matches!(&value, Value::Bytes(_)));
+
+        match value {
+            Value::Bytes(val) => {
+                let val = String::from_utf8_lossy(&val);
+                let uuid = uuid::Uuid::parse_str(&val).expect("valid UUID V7");
+                assert_eq!(uuid.get_version_num(), 7);
+                assert_eq!(uuid.get_variant(), Some(uuid::Variant::RFC4122));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn layout() {
+        let uuid = uuid::Uuid::from_u128(super::uuid_v7(1613059200000, u128::MAX));
+        assert_eq!(uuid.to_string(), "017791d2-1400-7fff-bfff-ffffffffffff");
+
+        let uuid = uuid::Uuid::from_u128(super::uuid_v7(1613059200000, 0));
+        assert_eq!(uuid.to_string(), "017791d2-1400-7000-8000-000000000000");
+    }
+
+    #[test]
+    fn sortable() {
+        let earlier = super::uuid_v7(1613059200000, u128::MAX);
+        let later = super::uuid_v7(1613059200001, 0);
+        assert!(earlier < later);
+    }
+}
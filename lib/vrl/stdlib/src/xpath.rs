@@ -0,0 +1,546 @@
+use std::collections::HashSet;
+
+use roxmltree::{Document, Node, NodeId};
+use vrl::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Xpath;
+
+impl Function for Xpath {
+    fn identifier(&self) -> &'static str {
+        "xpath"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "expr",
+                kind: kind::BYTES,
+                required: true,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "element text",
+                source: r#"xpath!(s'<books><book><title>Dune</title></book><book><title>Emma</title></book></books>', "//title")"#,
+                result: Ok(r#"["Dune", "Emma"]"#),
+            },
+            Example {
+                title: "attribute with predicate",
+                source: r#"xpath!(s'<books><book id="1" lang="en"/><book id="2" lang="fr"/></books>', "/books/book[@lang='fr']/@id")"#,
+                result: Ok(r#"["2"]"#),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::Compiler,
+        _ctx: &FunctionCompileContext,
+        mut arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        let expr = arguments.required_literal("expr")?.to_value();
+        let path = expr
+            .try_bytes_utf8_lossy()
+            .expect("xpath expression not bytes")
+            .parse::<Path>()
+            .map_err(|error| vrl::function::Error::InvalidArgument {
+                keyword: "expr",
+                value: expr.clone(),
+                error,
+            })?;
+
+        Ok(Box::new(XpathFn { value, path }))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct XpathFn {
+    value: Box<dyn Expression>,
+    path: Path,
+}
+
+impl Expression for XpathFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let string = value.try_bytes_utf8_lossy()?;
+
+        let doc = Document::parse(&string).map_err(|e| format!("unable to parse xml: {}", e))?;
+
+        Ok(self
+            .path
+            .evaluate(doc.root())
+            .into_iter()
+            .map(Value::from)
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    fn type_def(&self, _: &state::Compiler) -> TypeDef {
+        TypeDef::new()
+            .fallible()
+            .array_mapped::<(), Kind>(map! { (): Kind::Bytes })
+    }
+}
+
+/// A compiled location path, covering the subset of XPath 1.0 that is useful for extracting
+/// values: child (`/`) and descendant (`//`) steps, `.` and `..`, element names and `*`,
+/// attributes, `text()`, and `[n]`, `[last()]`, `[@attr]`, `[@attr='value']`, `[name]` and
+/// `[name='value']` predicates.
+///
+/// Namespace prefixes are ignored when matching names.
+#[derive(Debug, Clone, PartialEq)]
+struct Path {
+    steps: Vec<(Axis, Step)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Axis {
+    Child,
+    Descendant,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Current,
+    Parent,
+    Text,
+    Attribute(Name),
+    Element(Name, Vec<Predicate>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Name {
+    Any,
+    Local(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Predicate {
+    Position(usize),
+    Last,
+    Attribute(Name, Option<String>),
+    Child(Name, Option<String>),
+}
+
+/// A node selected by a path. Attributes are kept as the element they belong to and their
+/// index in it, as no further steps can be taken from them.
+enum Item<'a, 'input> {
+    Node(Node<'a, 'input>),
+    Attribute(Node<'a, 'input>, usize),
+}
+
+impl Item<'_, '_> {
+    /// The position of the item in the document, with attributes right after their element.
+    fn position(&self) -> (NodeId, usize) {
+        match self {
+            Item::Node(node) => (node.id(), 0),
+            Item::Attribute(node, index) => (node.id(), index + 1),
+        }
+    }
+}
+
+impl Name {
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            Name::Any => true,
+            Name::Local(local) => local == name,
+        }
+    }
+}
+
+impl Predicate {
+    fn matches(&self, node: Node, position: usize, last: usize) -> bool {
+        match self {
+            Predicate::Position(n) => position == *n,
+            Predicate::Last => position == last,
+            Predicate::Attribute(name, value) => node.attributes().iter().any(|attr| {
+                name.matches(attr.name()) && value.as_deref().map_or(true, |v| attr.value() == v)
+            }),
+            Predicate::Child(name, value) => node.children().any(|child| {
+                child.is_element()
+                    && name.matches(child.tag_name().name())
+                    && value.as_deref().map_or(true, |v| string_value(child) == v)
+            }),
+        }
+    }
+}
+
+impl Path {
+    fn evaluate(&self, root: Node) -> Vec<String> {
+        let mut items = vec![Item::Node(root)];
+
+        for (axis, step) in &self.steps {
+            let mut next = Vec::new();
+
+            for item in items {
+                let node = match item {
+                    Item::Node(node) => node,
+                    Item::Attribute(..) => continue,
+                };
+
+                let contexts: Vec<Node> = match axis {
+                    Axis::Child => vec![node],
+                    Axis::Descendant => node.descendants().collect(),
+                };
+
+                for context in contexts {
+                    step.select(context, &mut next);
+                }
+            }
+
+            // Steps from overlapping contexts can select the same node more than once, and out of
+            // document order.
+            let mut seen = HashSet::with_capacity(next.len());
+            next.retain(|item| seen.insert(item.position()));
+            next.sort_unstable_by_key(|item| item.position());
+            items = next;
+        }
+
+        items
+            .into_iter()
+            .map(|item| match item {
+                Item::Node(node) => string_value(node),
+                Item::Attribute(node, index) => node.attributes()[index].value().to_owned(),
+            })
+            .collect()
+    }
+}
+
+impl Step {
+    fn select<'a, 'input>(&self, node: Node<'a, 'input>, items: &mut Vec<Item<'a, 'input>>) {
+        match self {
+            Step::Current => items.push(Item::Node(node)),
+            Step::Parent => items.extend(node.parent().map(Item::Node)),
+            Step::Text => items.extend(node.children().filter(Node::is_text).map(Item::Node)),
+            Step::Attribute(name) => items.extend(
+                node.attributes()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, attr)| name.matches(attr.name()))
+                    .map(|(index, _)| Item::Attribute(node, index)),
+            ),
+            Step::Element(name, predicates) => {
+                let mut candidates: Vec<Node> = node
+                    .children()
+                    .filter(|child| child.is_element() && name.matches(child.tag_name().name()))
+                    .collect();
+
+                // Each predicate filters the result of the previous one, so positions are
+                // relative to the nodes that are left.
+                for predicate in predicates {
+                    let last = candidates.len();
+                    candidates = candidates
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(i, node)| predicate.matches(*node, i + 1, last))
+                        .map(|(_, node)| node)
+                        .collect();
+                }
+
+                items.extend(candidates.into_iter().map(Item::Node));
+            }
+        }
+    }
+}
+
+/// The XPath string-value of a node: the concatenation of all its descendant text.
+fn string_value(node: Node) -> String {
+    node.descendants()
+        .filter(Node::is_text)
+        .filter_map(|node| node.text())
+        .collect()
+}
+
+impl std::str::FromStr for Path {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut parser = Parser { input: s.trim() };
+        let mut steps = Vec::new();
+
+        // Relative paths are evaluated from the document root, same as absolute ones.
+        let mut axis = if parser.eat("//") {
+            Axis::Descendant
+        } else {
+            parser.eat("/");
+            Axis::Child
+        };
+
+        loop {
+            steps.push((axis, parser.step()?));
+
+            axis = if parser.eat("//") {
+                Axis::Descendant
+            } else if parser.eat("/") {
+                Axis::Child
+            } else if parser.input.is_empty() {
+                break;
+            } else {
+                return Err("unexpected character in xpath expression");
+            };
+        }
+
+        Ok(Path { steps })
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn eat(&mut self, token: &str) -> bool {
+        match self.input.strip_prefix(token) {
+            Some(rest) => {
+                self.input = rest.trim_start();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn step(&mut self) -> std::result::Result<Step, &'static str> {
+        if self.eat("..") {
+            Ok(Step::Parent)
+        } else if self.eat(".") {
+            Ok(Step::Current)
+        } else if self.eat("text()") {
+            Ok(Step::Text)
+        } else if self.eat("@") {
+            Ok(Step::Attribute(self.name()?))
+        } else {
+            let name = self.name()?;
+            let mut predicates = Vec::new();
+            while self.eat("[") {
+                predicates.push(self.predicate()?);
+                if !self.eat("]") {
+                    return Err("unterminated predicate in xpath expression");
+                }
+            }
+            Ok(Step::Element(name, predicates))
+        }
+    }
+
+    fn predicate(&mut self) -> std::result::Result<Predicate, &'static str> {
+        if self.eat("last()") {
+            return Ok(Predicate::Last);
+        }
+
+        let digits = self
+            .input
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or_else(|| self.input.len());
+        if digits > 0 {
+            let position = self.input[..digits]
+                .parse()
+                .map_err(|_| "invalid position in xpath predicate")?;
+            self.input = self.input[digits..].trim_start();
+            return match position {
+                0 => Err("xpath positions start at 1"),
+                n => Ok(Predicate::Position(n)),
+            };
+        }
+
+        let attribute = self.eat("@");
+        let name = self.name()?;
+        let value = if self.eat("=") {
+            Some(self.literal()?)
+        } else {
+            None
+        };
+
+        Ok(if attribute {
+            Predicate::Attribute(name, value)
+        } else {
+            Predicate::Child(name, value)
+        })
+    }
+
+    fn name(&mut self) -> std::result::Result<Name, &'static str> {
+        if self.eat("*") {
+            return Ok(Name::Any);
+        }
+
+        let end = self
+            .input
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')))
+            .unwrap_or_else(|| self.input.len());
+        let name = &self.input[..end];
+        if name.is_empty() {
+            return Err("expected a name in xpath expression");
+        }
+        self.input = self.input[end..].trim_start();
+
+        // Match on the local part, ignoring any namespace prefix.
+        let local = name.rsplit(':').next().unwrap_or(name);
+
+        Ok(Name::Local(local.to_owned()))
+    }
+
+    fn literal(&mut self) -> std::result::Result<String, &'static str> {
+        let quote = match self.input.chars().next() {
+            Some(quote @ ('"' | '\'')) => quote,
+            _ => return Err("expected a quoted string in xpath predicate"),
+        };
+
+        let rest = &self.input[1..];
+        let end = rest
+            .find(quote)
+            .ok_or("unterminated string in xpath predicate")?;
+        let literal = rest[..end].to_owned();
+        self.input = rest[end + 1..].trim_start();
+
+        Ok(literal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOOKS: &str = r#"<?xml version="1.0"?>
+        <library xmlns:b="urn:books">
+            <b:book id="1" lang="en"><title>Dune</title><author>Frank Herbert</author></b:book>
+            <b:book id="2" lang="fr"><title>Vingt mille lieues sous les mers</title><author>Jules Verne</author></b:book>
+            <b:book id="3" lang="en"><title>Emma</title><author>Jane Austen</author></b:book>
+        </library>"#;
+
+    fn type_def() -> TypeDef {
+        TypeDef::new()
+            .fallible()
+            .array_mapped::<(), Kind>(map! { (): Kind::Bytes })
+    }
+
+    test_function![
+        xpath => Xpath;
+
+        absolute {
+            args: func_args![value: BOOKS, expr: "/library/book/title"],
+            want: Ok(value!(["Dune", "Vingt mille lieues sous les mers", "Emma"])),
+            tdef: type_def(),
+        }
+
+        descendant {
+            args: func_args![value: BOOKS, expr: "//author"],
+            want: Ok(value!(["Frank Herbert", "Jules Verne", "Jane Austen"])),
+            tdef: type_def(),
+        }
+
+        attribute {
+            args: func_args![value: BOOKS, expr: "//book/@id"],
+            want: Ok(value!(["1", "2", "3"])),
+            tdef: type_def(),
+        }
+
+        attribute_predicate {
+            args: func_args![value: BOOKS, expr: "//b:book[@lang='en']/title"],
+            want: Ok(value!(["Dune", "Emma"])),
+            tdef: type_def(),
+        }
+
+        child_predicate {
+            args: func_args![value: BOOKS, expr: r#"//book[author="Jane Austen"]/@id"#],
+            want: Ok(value!(["3"])),
+            tdef: type_def(),
+        }
+
+        position {
+            args: func_args![value: BOOKS, expr: "/library/book[2]/title/text()"],
+            want: Ok(value!(["Vingt mille lieues sous les mers"])),
+            tdef: type_def(),
+        }
+
+        last {
+            args: func_args![value: BOOKS, expr: "/library/*[last()]/author"],
+            want: Ok(value!(["Jane Austen"])),
+            tdef: type_def(),
+        }
+
+        parent {
+            args: func_args![value: BOOKS, expr: "//title/../@lang"],
+            want: Ok(value!(["en", "fr", "en"])),
+            tdef: type_def(),
+        }
+
+        element_string_value {
+            args: func_args![value: "<a><b>one</b><c>two</c></a>", expr: "/a"],
+            want: Ok(value!(["onetwo"])),
+            tdef: type_def(),
+        }
+
+        overlapping_descendants {
+            args: func_args![value: "<a><a><b>one</b></a><b>two</b></a>", expr: "//a//b"],
+            want: Ok(value!(["one", "two"])),
+            tdef: type_def(),
+        }
+
+        no_match {
+            args: func_args![value: BOOKS, expr: "//publisher"],
+            want: Ok(value!([])),
+            tdef: type_def(),
+        }
+
+        invalid_xml {
+            args: func_args![value: "true", expr: "/a"],
+            want: Err("unable to parse xml: unknown token at 1:1"),
+            tdef: type_def(),
+        }
+    ];
+
+    #[test]
+    fn parse_path() {
+        assert_eq!(
+            "//a/b[1][@c='d']/@*".parse::<Path>(),
+            Ok(Path {
+                steps: vec![
+                    (
+                        Axis::Descendant,
+                        Step::Element(Name::Local("a".to_owned()), vec![])
+                    ),
+                    (
+                        Axis::Child,
+                        Step::Element(
+                            Name::Local("b".to_owned()),
+                            vec![
+                                Predicate::Position(1),
+                                Predicate::Attribute(
+                                    Name::Local("c".to_owned()),
+                                    Some("d".to_owned())
+                                ),
+                            ]
+                        )
+                    ),
+                    (Axis::Child, Step::Attribute(Name::Any)),
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn parse_invalid_path() {
+        assert_eq!(
+            "/a[".parse::<Path>(),
+            Err("expected a name in xpath expression")
+        );
+        assert_eq!(
+            "/a[1".parse::<Path>(),
+            Err("unterminated predicate in xpath expression")
+        );
+        assert_eq!("/a[0]".parse::<Path>(), Err("xpath positions start at 1"));
+        assert_eq!(
+            "/a|/b".parse::<Path>(),
+            Err("unexpected character in xpath expression")
+        );
+    }
+}
@@ -14,6 +14,9 @@ use crate::Test;
 /// This mostly consists of functions that have a non-deterministic result.
 const SKIP_FUNCTION_EXAMPLES: &[&str] = &[
     "uuid_v4",
+    "uuid_v7",
+    "ulid",
+    "snowflake_id",
     "strip_ansi_escape_codes",
     "get_hostname",
     "now",
@@ -0,0 +1,340 @@
+//! Attributes heap usage to the component that allocated it.
+//!
+//! [`TrackingAllocator`] wraps the global allocator and appends the ID of the current allocation
+//! group to each allocation, so that the bytes can be released from the same group however the
+//! allocation is later freed. [`AllocationLayer`] gives each component span its own group and
+//! makes it current while that span is entered. Tracking has to be compiled in with the
+//! `allocation-tracing` feature, and then enabled at startup with `--allocation-tracing`.
+
+use std::{
+    alloc::{GlobalAlloc, Layout},
+    cell::Cell,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+use tokio::time::interval;
+use tracing::{span, Id, Subscriber};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+use crate::internal_events::ComponentAllocatedBytes;
+
+/// The number of allocation groups that can be registered. Components registered beyond this
+/// are counted as unattributed.
+const MAX_GROUPS: usize = 1024;
+
+/// Allocations made outside of any component belong to this group, which isn't reported.
+const UNATTRIBUTED: usize = 0;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicI64 = AtomicI64::new(0);
+
+/// The bytes currently allocated by each group.
+static ALLOCATED: [AtomicI64; MAX_GROUPS] = [ZERO; MAX_GROUPS];
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO_TOTAL: AtomicU64 = AtomicU64::new(0);
+
+/// The bytes ever allocated by each group, counting the growth of reallocations.
+static TOTAL_ALLOCATED: [AtomicU64; MAX_GROUPS] = [ZERO_TOTAL; MAX_GROUPS];
+
+/// The number of allocations and reallocations made by each group.
+static ALLOCATIONS: [AtomicU64; MAX_GROUPS] = [ZERO_TOTAL; MAX_GROUPS];
+
+/// The component each group was registered for, indexed by group ID minus one.
+static GROUPS: Lazy<Mutex<Vec<ComponentGroup>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+thread_local! {
+    static CURRENT_GROUP: Cell<usize> = Cell::new(UNATTRIBUTED);
+}
+
+/// Starts attributing allocations to components. Allocations made before this are never
+/// attributed.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Emits the bytes currently allocated by each component every second.
+pub async fn report() {
+    let mut interval = interval(Duration::from_secs(1));
+    loop {
+        interval.tick().await;
+        let groups = GROUPS.lock().expect("poisoned lock").clone();
+        for (index, group) in groups.iter().enumerate() {
+            emit!(&ComponentAllocatedBytes {
+                component_kind: &group.kind,
+                component_id: &group.id,
+                component_type: &group.typetag,
+                bytes: ALLOCATED[index + 1].load(Ordering::Relaxed).max(0),
+            });
+        }
+    }
+}
+
+/// The allocations made by a component since tracking was enabled.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComponentAllocations {
+    pub kind: String,
+    pub id: String,
+    pub typetag: String,
+    /// The bytes the component has allocated and not yet freed.
+    pub allocated_bytes: i64,
+    pub total_allocated_bytes: u64,
+    pub allocations: u64,
+}
+
+/// Returns the allocations of each component registered so far.
+pub fn component_allocations() -> Vec<ComponentAllocations> {
+    let groups = GROUPS.lock().expect("poisoned lock").clone();
+    groups
+        .into_iter()
+        .enumerate()
+        .map(|(index, group)| ComponentAllocations {
+            kind: group.kind,
+            id: group.id,
+            typetag: group.typetag,
+            allocated_bytes: ALLOCATED[index + 1].load(Ordering::Relaxed).max(0),
+            total_allocated_bytes: TOTAL_ALLOCATED[index + 1].load(Ordering::Relaxed),
+            allocations: ALLOCATIONS[index + 1].load(Ordering::Relaxed),
+        })
+        .collect()
+}
+
+fn current_group() -> usize {
+    if !is_enabled() {
+        return UNATTRIBUTED;
+    }
+    // The thread local is unavailable while the thread is being torn down.
+    CURRENT_GROUP
+        .try_with(|group| group.get())
+        .unwrap_or(UNATTRIBUTED)
+}
+
+fn set_current_group(group: usize) {
+    let _ = CURRENT_GROUP.try_with(|current| current.set(group));
+}
+
+/// Returns the layout with room for the group ID after it, and the offset of the group ID.
+fn tracked_layout(layout: Layout) -> (Layout, usize) {
+    layout
+        .extend(Layout::new::<usize>())
+        .expect("allocation size overflow")
+}
+
+/// A global allocator that counts the bytes allocated by each allocation group.
+pub struct TrackingAllocator<A> {
+    inner: A,
+}
+
+impl<A> TrackingAllocator<A> {
+    pub const fn new(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let (tracked, offset) = tracked_layout(layout);
+        let ptr = self.inner.alloc(tracked);
+        if !ptr.is_null() {
+            let group = current_group();
+            track(ptr, offset, group, layout.size());
+            count(group, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let (tracked, offset) = tracked_layout(layout);
+        let ptr = self.inner.alloc_zeroed(tracked);
+        if !ptr.is_null() {
+            let group = current_group();
+            track(ptr, offset, group, layout.size());
+            count(group, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let (tracked, offset) = tracked_layout(layout);
+        untrack(ptr, offset, layout.size());
+        self.inner.dealloc(ptr, tracked);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let (tracked, offset) = tracked_layout(layout);
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let (new_tracked, new_offset) = tracked_layout(new_layout);
+
+        // The allocation stays with the group that first allocated it.
+        let group = untrack(ptr, offset, layout.size());
+        let new_ptr = self.inner.realloc(ptr, tracked, new_tracked.size());
+        if new_ptr.is_null() {
+            track(ptr, offset, group, layout.size());
+        } else {
+            track(new_ptr, new_offset, group, new_size);
+            count(group, new_size.saturating_sub(layout.size()));
+        }
+        new_ptr
+    }
+}
+
+unsafe fn track(ptr: *mut u8, offset: usize, group: usize, size: usize) {
+    (ptr.add(offset) as *mut usize).write_unaligned(group);
+    if group != UNATTRIBUTED {
+        ALLOCATED[group].fetch_add(size as i64, Ordering::Relaxed);
+    }
+}
+
+fn count(group: usize, size: usize) {
+    if group != UNATTRIBUTED {
+        TOTAL_ALLOCATED[group].fetch_add(size as u64, Ordering::Relaxed);
+        ALLOCATIONS[group].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+unsafe fn untrack(ptr: *mut u8, offset: usize, size: usize) -> usize {
+    let group = (ptr.add(offset) as *const usize).read_unaligned();
+    if group != UNATTRIBUTED {
+        ALLOCATED[group].fetch_sub(size as i64, Ordering::Relaxed);
+    }
+    group
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ComponentGroup {
+    kind: String,
+    id: String,
+    typetag: String,
+}
+
+impl tracing::field::Visit for ComponentGroup {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        match field.name() {
+            "component_kind" => self.kind = format!("{:?}", value),
+            "component_id" => self.id = format!("{:?}", value),
+            "component_type" => self.typetag = format!("{:?}", value),
+            _ => {}
+        }
+    }
+
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        match field.name() {
+            "component_kind" => self.kind = value.to_owned(),
+            "component_id" => self.id = value.to_owned(),
+            "component_type" => self.typetag = value.to_owned(),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the group for the component, registering it if it's new. Components keep their
+/// group across reloads.
+fn register_group(component: ComponentGroup) -> usize {
+    let mut groups = GROUPS.lock().expect("poisoned lock");
+    if let Some(index) = groups.iter().position(|group| *group == component) {
+        return index + 1;
+    }
+    if groups.len() + 1 >= MAX_GROUPS {
+        return UNATTRIBUTED;
+    }
+    groups.push(component);
+    groups.len()
+}
+
+/// The allocation group of a component span.
+struct AllocationGroup(usize);
+
+/// Gives each component span an allocation group, and makes it current while the span is
+/// entered.
+pub struct AllocationLayer;
+
+impl<S> Layer<S> for AllocationLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if !is_enabled() || attrs.metadata().fields().field("component_id").is_none() {
+            return;
+        }
+        let mut component = ComponentGroup::default();
+        attrs.record(&mut component);
+        let group = register_group(component);
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(AllocationGroup(group));
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(group) = span.extensions().get::<AllocationGroup>() {
+                set_current_group(group.0);
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let exited = ctx.span(id).map_or(false, |span| {
+            span.extensions().get::<AllocationGroup>().is_some()
+        });
+        if !exited {
+            return;
+        }
+        // Fall back to the group of the innermost component span that's still entered.
+        let mut group = UNATTRIBUTED;
+        let mut span = ctx.lookup_current();
+        while let Some(current) = span {
+            if let Some(AllocationGroup(id)) = current.extensions().get::<AllocationGroup>() {
+                group = *id;
+                break;
+            }
+            span = current.parent();
+        }
+        set_current_group(group);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::alloc::System;
+
+    use super::*;
+
+    #[test]
+    fn attributes_allocations_to_group() {
+        let allocator = TrackingAllocator::new(System);
+        let group = register_group(ComponentGroup {
+            kind: "transform".into(),
+            id: "attributes_allocations_to_group".into(),
+            typetag: "remap".into(),
+        });
+        enable();
+        set_current_group(group);
+
+        let layout = Layout::from_size_align(100, 8).unwrap();
+        unsafe {
+            let ptr = allocator.alloc(layout);
+            assert_eq!(ALLOCATED[group].load(Ordering::Relaxed), 100);
+
+            // Growing the allocation from another group keeps it with the first.
+            set_current_group(UNATTRIBUTED);
+            let ptr = allocator.realloc(ptr, layout, 300);
+            assert_eq!(ALLOCATED[group].load(Ordering::Relaxed), 300);
+
+            allocator.dealloc(ptr, Layout::from_size_align(300, 8).unwrap());
+            assert_eq!(ALLOCATED[group].load(Ordering::Relaxed), 0);
+        }
+        assert_eq!(TOTAL_ALLOCATED[group].load(Ordering::Relaxed), 300);
+        assert_eq!(ALLOCATIONS[group].load(Ordering::Relaxed), 2);
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
         sort,
     },
     config::{ComponentKey, OutputId},
+    event::MetricValue,
     filter_check,
 };
 
@@ -58,6 +59,18 @@ impl filter::CustomFilter<Sink> for SinksFilter {
     }
 }
 
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum SinkHealth {
+    /// The healthcheck hasn't finished yet, or is disabled
+    Unknown,
+    /// The last healthcheck passed
+    Healthy,
+    /// The last healthcheck failed
+    Unhealthy,
+    /// The last healthcheck failed, and is required to pass
+    Degraded,
+}
+
 #[derive(Enum, Copy, Clone, Eq, PartialEq)]
 pub enum SinksSortFieldName {
     ComponentKey,
@@ -118,6 +131,27 @@ impl Sink {
         metrics::by_component_key(self.get_component_key())
             .into_sink_metrics(self.get_component_type())
     }
+
+    /// Sink health, according to its last healthcheck
+    pub async fn health(&self) -> SinkHealth {
+        let metrics = metrics::by_component_key(self.get_component_key());
+        let gauge = |name: &str| {
+            metrics
+                .iter()
+                .find(|metric| metric.name() == name)
+                .and_then(|metric| match metric.value() {
+                    MetricValue::Gauge { value } => Some(*value),
+                    _ => None,
+                })
+        };
+
+        match (gauge("component_degraded"), gauge("component_healthy")) {
+            (Some(degraded), _) if degraded > 0.0 => SinkHealth::Degraded,
+            (_, Some(healthy)) if healthy > 0.0 => SinkHealth::Healthy,
+            (_, Some(_)) => SinkHealth::Unhealthy,
+            _ => SinkHealth::Unknown,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,53 @@
+use async_graphql::{Enum, Object};
+use chrono::{DateTime, Utc};
+
+use crate::event::{Metric, MetricValue};
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum CircuitBreakerStateType {
+    /// Requests are sent to the endpoint
+    Closed,
+    /// A single request is probing whether the endpoint recovered
+    HalfOpen,
+    /// Requests are held back until the endpoint is probed again
+    Open,
+}
+
+pub struct CircuitBreaker(Metric);
+
+impl CircuitBreaker {
+    pub const fn new(m: Metric) -> Self {
+        Self(m)
+    }
+
+    pub fn get_timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.timestamp()
+    }
+
+    pub fn get_state(&self) -> CircuitBreakerStateType {
+        match self.0.value() {
+            MetricValue::Gauge { value } if *value >= 2.0 => CircuitBreakerStateType::Open,
+            MetricValue::Gauge { value } if *value >= 1.0 => CircuitBreakerStateType::HalfOpen,
+            _ => CircuitBreakerStateType::Closed,
+        }
+    }
+}
+
+#[Object]
+impl CircuitBreaker {
+    /// Metric timestamp
+    pub async fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.get_timestamp()
+    }
+
+    /// Circuit breaker state
+    pub async fn state(&self) -> CircuitBreakerStateType {
+        self.get_state()
+    }
+}
+
+impl From<Metric> for CircuitBreaker {
+    fn from(m: Metric) -> Self {
+        Self(m)
+    }
+}
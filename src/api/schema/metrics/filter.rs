@@ -1,12 +1,12 @@
-use std::collections::BTreeMap;
+use std::{cmp::Ordering, collections::BTreeMap};
 
 use async_stream::stream;
 use tokio::time::Duration;
 use tokio_stream::{Stream, StreamExt};
 
 use super::{
-    EventsInTotal, EventsOutTotal, ProcessedBytesTotal, ProcessedEventsTotal, ReceivedEventsTotal,
-    SentEventsTotal,
+    CircuitBreaker, EventsInTotal, EventsOutTotal, ProcessedBytesTotal, ProcessedEventsTotal,
+    ReceivedEventsTotal, SentEventsTotal,
 };
 use crate::{
     config::ComponentKey,
@@ -52,6 +52,30 @@ pub trait MetricsFilter<'a> {
     fn events_in_total(&self) -> Option<EventsInTotal>;
     fn events_out_total(&self) -> Option<EventsOutTotal>;
     fn sent_events_total(&self) -> Option<SentEventsTotal>;
+    fn circuit_breaker(&self) -> Option<CircuitBreaker>;
+}
+
+/// Returns the metric reporting the least healthy circuit breaker, as a sink may have several.
+fn least_healthy_circuit_breaker<'a, I: IntoIterator<Item = &'a Metric>>(
+    metrics: I,
+) -> Option<CircuitBreaker> {
+    metrics
+        .into_iter()
+        .filter(|m| m.name() == "circuit_breaker_state")
+        .max_by(|m1, m2| {
+            gauge_value(m1)
+                .partial_cmp(&gauge_value(m2))
+                .unwrap_or(Ordering::Equal)
+        })
+        .cloned()
+        .map(CircuitBreaker::new)
+}
+
+fn gauge_value(metric: &Metric) -> f64 {
+    match metric.value() {
+        MetricValue::Gauge { value } => *value,
+        _ => 0.0,
+    }
 }
 
 impl<'a> MetricsFilter<'a> for Vec<Metric> {
@@ -96,6 +120,10 @@ impl<'a> MetricsFilter<'a> for Vec<Metric> {
 
         Some(SentEventsTotal::new(sum))
     }
+
+    fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        least_healthy_circuit_breaker(self.iter())
+    }
 }
 
 impl<'a> MetricsFilter<'a> for Vec<&'a Metric> {
@@ -158,6 +186,10 @@ impl<'a> MetricsFilter<'a> for Vec<&'a Metric> {
 
         Some(SentEventsTotal::new(sum))
     }
+
+    fn circuit_breaker(&self) -> Option<CircuitBreaker> {
+        least_healthy_circuit_breaker(self.iter().copied())
+    }
 }
 
 /// Returns a stream of `Metric`s, collected at the provided millisecond interval.
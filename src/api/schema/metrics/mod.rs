@@ -1,3 +1,4 @@
+mod circuit_breaker;
 mod errors;
 mod events_in;
 mod events_out;
@@ -16,6 +17,7 @@ mod host;
 
 use async_graphql::{Interface, Object, Subscription};
 use chrono::{DateTime, Utc};
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerStateType};
 pub use errors::{ComponentErrorsTotal, ErrorsTotal};
 pub use events_in::{ComponentEventsInThroughput, ComponentEventsInTotal, EventsInTotal};
 pub use events_out::{ComponentEventsOutThroughput, ComponentEventsOutTotal, EventsOutTotal};
@@ -45,4 +45,9 @@ impl GenericSinkMetrics {
     pub async fn sent_events_total(&self) -> Option<metrics::SentEventsTotal> {
         self.0.sent_events_total()
     }
+
+    /// Circuit breaker of the requests made by the current sink, if it has one
+    pub async fn circuit_breaker(&self) -> Option<metrics::CircuitBreaker> {
+        self.0.circuit_breaker()
+    }
 }
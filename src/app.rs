@@ -21,6 +21,8 @@ use crate::{
 };
 #[cfg(feature = "api-client")]
 use crate::{tap, top};
+#[cfg(all(feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+use crate::bench;
 
 pub static WORKER_THREADS: OnceNonZeroUsize = OnceNonZeroUsize::new();
 
@@ -107,6 +109,11 @@ impl Application {
 
         metrics::init_global().expect("metrics initialization failed");
 
+        #[cfg(feature = "allocation-tracing")]
+        if root_opts.allocation_tracing {
+            crate::allocations::enable();
+        }
+
         let mut rt_builder = runtime::Builder::new_multi_thread();
         rt_builder.enable_all().thread_name("vector-worker");
 
@@ -139,6 +146,8 @@ impl Application {
                     let code = match s {
                         SubCommand::Generate(g) => generate::cmd(&g),
                         SubCommand::Graph(g) => graph::cmd(&g),
+                        #[cfg(all(feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+                        SubCommand::Bench(b) => bench::cmd(&b).await,
                         SubCommand::List(l) => list::cmd(&l),
                         SubCommand::Test(t) => unit_test::cmd(&t).await,
                         #[cfg(windows)]
@@ -243,6 +252,10 @@ impl Application {
         rt.block_on(async move {
             emit!(&VectorStarted);
             tokio::spawn(heartbeat::heartbeat());
+            #[cfg(feature = "allocation-tracing")]
+            if crate::allocations::is_enabled() {
+                tokio::spawn(crate::allocations::report());
+            }
 
             // Configure the API server, if applicable.
             #[cfg(feature = "api")]
@@ -1,3 +1,6 @@
+use std::{path::PathBuf, process::Stdio, time::Duration};
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Configuration for configuring authentication strategy for AWS.
@@ -16,6 +19,21 @@ pub enum AwsAuthentication {
     },
     Role {
         assume_role: String,
+        external_id: Option<String>,
+        session_name: Option<String>,
+        session_duration_secs: Option<u64>,
+        /// Roles assumed in turn after `assume_role`, each with the credentials of the previous one.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        role_chain: Vec<ChainedRole>,
+    },
+    WebIdentity {
+        web_identity_token_file: PathBuf,
+        role_arn: String,
+        session_name: Option<String>,
+        session_duration_secs: Option<u64>,
+    },
+    CredentialProcess {
+        credential_process: String,
     },
     // Default variant is used instead of Option<AWSAuthentication> since even for
     // None we need to build `AwsCredentialsProvider`.
@@ -25,6 +43,112 @@ pub enum AwsAuthentication {
     Default {},
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ChainedRole {
+    pub role_arn: String,
+    pub external_id: Option<String>,
+}
+
+/// A role to assume, with the options to assume it with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssumedRole {
+    pub role_arn: String,
+    pub external_id: Option<String>,
+    pub session_name: String,
+    pub session_duration: Option<Duration>,
+}
+
+const DEFAULT_SESSION_NAME: &str = "default";
+
+impl AwsAuthentication {
+    /// Returns the roles to assume, in order, if any.
+    pub fn assumed_roles(&self) -> Vec<AssumedRole> {
+        match self {
+            Self::Role {
+                assume_role,
+                external_id,
+                session_name,
+                session_duration_secs,
+                role_chain,
+            } => {
+                let session_name = session_name
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_SESSION_NAME.to_owned());
+                let session_duration = session_duration_secs.map(Duration::from_secs);
+
+                std::iter::once(AssumedRole {
+                    role_arn: assume_role.clone(),
+                    external_id: external_id.clone(),
+                    session_name: session_name.clone(),
+                    session_duration,
+                })
+                .chain(role_chain.iter().map(|role| AssumedRole {
+                    role_arn: role.role_arn.clone(),
+                    external_id: role.external_id.clone(),
+                    session_name: session_name.clone(),
+                    session_duration,
+                }))
+                .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the key that components with the same STS session share, if the credentials come
+    /// from one.
+    pub fn session_key(&self) -> Option<String> {
+        match self {
+            Self::Role { .. } | Self::WebIdentity { .. } => {
+                Some(serde_json::to_string(self).expect("auth is serializable"))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Credentials printed by a `credential_process` command.
+///
+/// See https://docs.aws.amazon.com/cli/latest/userguide/cli-configure-sourcing-external.html
+#[derive(Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProcessCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+    pub expiration: Option<DateTime<Utc>>,
+}
+
+/// Runs `command` through the shell, and returns the credentials it prints.
+pub async fn run_credential_process(command: &str) -> Result<ProcessCredentials, String> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = tokio::process::Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = tokio::process::Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let output = shell
+        .arg(command)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|error| format!("Failed to run credential process: {}", error))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Credential process failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .map_err(|error| format!("Credential process printed invalid credentials: {}", error))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,7 +206,7 @@ mod tests {
         .unwrap();
 
         match config.auth {
-            AwsAuthentication::Role { assume_role } => assert_eq!(&assume_role, "auth.root"),
+            AwsAuthentication::Role { assume_role, .. } => assert_eq!(&assume_role, "auth.root"),
             _ => panic!(),
         }
     }
@@ -139,4 +263,83 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn parsing_role_chain() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.assume_role = "root"
+            auth.external_id = "id"
+            auth.session_duration_secs = 900
+            auth.role_chain = [{ role_arn = "second" }, { role_arn = "third", external_id = "other" }]
+        "#,
+        )
+        .unwrap();
+
+        let roles = config.auth.assumed_roles();
+        assert_eq!(
+            roles
+                .iter()
+                .map(|role| (role.role_arn.as_str(), role.external_id.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("root", Some("id")),
+                ("second", None),
+                ("third", Some("other"))
+            ]
+        );
+        assert!(roles.iter().all(
+            |role| role.session_duration == Some(Duration::from_secs(900))
+                && role.session_name == "default"
+        ));
+    }
+
+    #[test]
+    fn parsing_web_identity() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.web_identity_token_file = "/var/run/secrets/token"
+            auth.role_arn = "root"
+            auth.session_duration_secs = 3600
+        "#,
+        )
+        .unwrap();
+
+        assert!(matches!(config.auth, AwsAuthentication::WebIdentity { .. }));
+        assert!(config.auth.session_key().is_some());
+    }
+
+    #[test]
+    fn parsing_credential_process() {
+        let config = toml::from_str::<ComponentConfig>(
+            r#"
+            auth.credential_process = "get-credentials --profile foo"
+        "#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            config.auth,
+            AwsAuthentication::CredentialProcess { .. }
+        ));
+        assert!(config.auth.session_key().is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_credential_process() {
+        let credentials = run_credential_process(
+            r#"echo '{"Version": 1, "AccessKeyId": "key", "SecretAccessKey": "secret", "Expiration": "2030-01-01T00:00:00Z"}'"#,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(credentials.access_key_id, "key");
+        assert_eq!(credentials.secret_access_key, "secret");
+        assert_eq!(credentials.session_token, None);
+        assert!(credentials.expiration.is_some());
+
+        assert!(run_credential_process("exit 1").await.is_err());
+        assert!(run_credential_process("echo '{}'").await.is_err());
+    }
 }
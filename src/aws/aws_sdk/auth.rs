@@ -1,11 +1,27 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, SystemTime},
+};
+
 use aws_config::{
     default_provider::credentials::default_provider,
-    meta::credentials::LazyCachingCredentialsProvider, profile::ProfileFileCredentialsProvider,
+    meta::credentials::LazyCachingCredentialsProvider,
+    profile::ProfileFileCredentialsProvider,
     sts::AssumeRoleProviderBuilder,
+    web_identity_token::{StaticConfiguration, WebIdentityTokenCredentialsProvider},
 };
-use aws_types::{credentials::SharedCredentialsProvider, Credentials};
+use aws_types::{
+    credentials::{future, CredentialsError, ProvideCredentials, SharedCredentialsProvider},
+    Credentials,
+};
+use once_cell::sync::Lazy;
+
+use crate::aws::auth::{run_credential_process, AwsAuthentication};
 
-use crate::aws::auth::AwsAuthentication;
+/// STS sessions, keyed by the authentication options they were created for.
+static SESSIONS: Lazy<Mutex<HashMap<String, SharedCredentialsProvider>>> =
+    Lazy::new(Default::default);
 
 impl AwsAuthentication {
     pub async fn credentials_provider(&self) -> SharedCredentialsProvider {
@@ -33,16 +49,77 @@ impl AwsAuthentication {
                         .build(),
                 )
             }
-            AwsAuthentication::Role { assume_role } => SharedCredentialsProvider::new(
-                AssumeRoleProviderBuilder::new(assume_role)
-                    .build(default_credentials_provider().await),
-            ),
+            AwsAuthentication::Role { .. } | AwsAuthentication::WebIdentity { .. } => {
+                let key = self.session_key().expect("roles have STS sessions");
+                if let Some(provider) = SESSIONS.lock().expect("poisoned lock").get(&key) {
+                    return provider.clone();
+                }
+
+                let provider = self.session_credentials_provider().await;
+                SESSIONS
+                    .lock()
+                    .expect("poisoned lock")
+                    .entry(key)
+                    .or_insert(provider)
+                    .clone()
+            }
+            AwsAuthentication::CredentialProcess { credential_process } => {
+                SharedCredentialsProvider::new(
+                    LazyCachingCredentialsProvider::builder()
+                        .load(CredentialProcessProvider {
+                            command: credential_process.clone(),
+                        })
+                        .build(),
+                )
+            }
             AwsAuthentication::Default {} => {
                 SharedCredentialsProvider::new(default_credentials_provider().await)
             }
         }
     }
 
+    /// Builds the credentials provider of a new STS session.
+    async fn session_credentials_provider(&self) -> SharedCredentialsProvider {
+        match self {
+            AwsAuthentication::WebIdentity {
+                web_identity_token_file,
+                role_arn,
+                session_name,
+                session_duration_secs,
+            } => {
+                if session_duration_secs.is_some() {
+                    warn!("Option `auth.session_duration_secs` is not supported with web identity tokens by this component, and is ignored.");
+                }
+                SharedCredentialsProvider::new(
+                    WebIdentityTokenCredentialsProvider::builder()
+                        .static_configuration(StaticConfiguration {
+                            web_identity_token_file: web_identity_token_file.clone(),
+                            role_arn: role_arn.clone(),
+                            session_name: session_name
+                                .clone()
+                                .unwrap_or_else(|| "default".to_owned()),
+                        })
+                        .build(),
+                )
+            }
+            _ => {
+                let mut provider = default_credentials_provider().await;
+                for role in self.assumed_roles() {
+                    let mut builder = AssumeRoleProviderBuilder::new(role.role_arn)
+                        .session_name(role.session_name);
+                    if let Some(external_id) = role.external_id {
+                        builder = builder.external_id(external_id);
+                    }
+                    if let Some(session_duration) = role.session_duration {
+                        builder = builder.session_length(session_duration);
+                    }
+                    provider = SharedCredentialsProvider::new(builder.build(provider));
+                }
+                provider
+            }
+        }
+    }
+
     #[cfg(test)]
     pub fn test_auth() -> AwsAuthentication {
         AwsAuthentication::Static {
@@ -55,3 +132,32 @@ impl AwsAuthentication {
 async fn default_credentials_provider() -> SharedCredentialsProvider {
     SharedCredentialsProvider::new(default_provider().await)
 }
+
+/// Credentials printed by a `credential_process` command.
+#[derive(Debug)]
+struct CredentialProcessProvider {
+    command: String,
+}
+
+impl ProvideCredentials for CredentialProcessProvider {
+    fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+    where
+        Self: 'a,
+    {
+        future::ProvideCredentials::new(async move {
+            let credentials = run_credential_process(&self.command)
+                .await
+                .map_err(|error| CredentialsError::ProviderError(error.into()))?;
+            Ok(Credentials::new(
+                credentials.access_key_id,
+                credentials.secret_access_key,
+                credentials.session_token,
+                credentials.expiration.map(|expiration| {
+                    SystemTime::UNIX_EPOCH
+                        + Duration::from_secs(expiration.timestamp().max(0) as u64)
+                }),
+                "credential_process",
+            ))
+        })
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use rusoto_core::Region;
 
 use crate::aws::{auth::AwsAuthentication, rusoto::AwsCredentialsProvider};
@@ -43,13 +45,37 @@ impl AwsAuthentication {
                         .as_str(),
                 )
             }
-            Self::Role { assume_role } => {
+            Self::Role { .. } => {
                 if old_assume_role.is_some() {
                     warn!(
                         "Ignoring option `assume_role`, instead using option `auth.assume_role`."
                     );
                 }
-                AwsCredentialsProvider::new(region, Some(assume_role.clone()))
+                AwsCredentialsProvider::new_with_roles(region, self)
+            }
+            Self::WebIdentity {
+                web_identity_token_file,
+                role_arn,
+                session_name,
+                session_duration_secs,
+            } => {
+                if old_assume_role.is_some() {
+                    warn!("Ignoring option `assume_role`, instead using web identity options.");
+                }
+                AwsCredentialsProvider::new_with_web_identity(
+                    region,
+                    self,
+                    web_identity_token_file,
+                    role_arn,
+                    session_name.as_deref(),
+                    session_duration_secs.map(Duration::from_secs),
+                )
+            }
+            Self::CredentialProcess { credential_process } => {
+                if old_assume_role.is_some() {
+                    warn!("Ignoring option `assume_role`, instead using option `auth.credential_process`.");
+                }
+                AwsCredentialsProvider::new_with_credential_process(credential_process)
             }
             Self::Default {} => AwsCredentialsProvider::new(region, old_assume_role),
         }
@@ -57,7 +83,7 @@ impl AwsAuthentication {
 }
 #[cfg(test)]
 mod test {
-    use std::{fs::File, io::Write};
+    use std::{fs::File, io::Write, sync::Arc};
 
     use rusoto_core::Region;
 
@@ -90,4 +116,23 @@ mod test {
         drop(tmpfile);
         tmpdir.close().unwrap();
     }
+
+    #[test]
+    fn shares_role_sessions() {
+        let auth = |external_id: &str| AwsAuthentication::Role {
+            assume_role: "arn:aws:iam::123456789012:role/shared".to_owned(),
+            external_id: Some(external_id.to_owned()),
+            session_name: None,
+            session_duration_secs: None,
+            role_chain: Vec::new(),
+        };
+        let session = |auth: &AwsAuthentication| match auth.build(&Region::UsEast1, None).unwrap() {
+            AwsCredentialsProvider::Role(provider) => provider.0,
+            _ => panic!(),
+        };
+
+        let first = session(&auth("first"));
+        assert!(Arc::ptr_eq(&first, &session(&auth("first"))));
+        assert!(!Arc::ptr_eq(&first, &session(&auth("second"))));
+    }
 }
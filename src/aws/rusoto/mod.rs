@@ -3,8 +3,11 @@ pub mod region;
 
 //TODO: replace with direct import
 use std::{
+    collections::HashMap,
     fmt, io,
+    path::Path,
     pin::Pin,
+    sync::{Arc, Mutex},
     task::{Context, Poll},
     time::Duration,
 };
@@ -20,7 +23,7 @@ use hyper::{
     body::{Body, HttpBody},
     client,
 };
-use once_cell::sync::OnceCell;
+use once_cell::sync::{Lazy, OnceCell};
 use regex::bytes::RegexSet;
 pub use region::{region_from_endpoint, RegionOrEndpoint};
 use rusoto_core::{
@@ -32,7 +35,7 @@ use rusoto_core::{
 };
 use rusoto_credential::{
     AutoRefreshingProvider, AwsCredentials, ChainProvider, CredentialsError, ProvideAwsCredentials,
-    StaticProvider,
+    Secret, StaticProvider, Variable,
 };
 use rusoto_signature::{SignedRequest, SignedRequestPayload};
 use rusoto_sts::{StsAssumeRoleSessionCredentialsProvider, StsClient, WebIdentityProvider};
@@ -40,6 +43,7 @@ use snafu::{ResultExt, Snafu};
 use tower::{Service, ServiceExt};
 
 pub use super::auth::AwsAuthentication;
+use super::auth::{run_credential_process, AssumedRole};
 use crate::{config::ProxyConfig, http::HttpError, tls::MaybeTlsSettings};
 // use crate::http;
 
@@ -111,13 +115,55 @@ impl ProvideAwsCredentials for CustomChainProvider {
     }
 }
 
+/// Credentials shared between components, so that they use the same STS session.
+#[derive(Clone)]
+pub struct SharedCredentialsProvider(Arc<dyn ProvideAwsCredentials + Send + Sync>);
+
+impl SharedCredentialsProvider {
+    fn new(provider: impl ProvideAwsCredentials + Send + Sync + 'static) -> Self {
+        Self(Arc::new(provider))
+    }
+}
+
+#[async_trait]
+impl ProvideAwsCredentials for SharedCredentialsProvider {
+    async fn credentials(&self) -> Result<AwsCredentials, CredentialsError> {
+        self.0.credentials().await
+    }
+}
+
+/// STS sessions, keyed by the region and authentication options they were created for.
+static SESSIONS: Lazy<Mutex<HashMap<String, SharedCredentialsProvider>>> =
+    Lazy::new(Default::default);
+
+/// Credentials printed by a `credential_process` command.
+pub struct CredentialProcessProvider {
+    command: String,
+}
+
+#[async_trait]
+impl ProvideAwsCredentials for CredentialProcessProvider {
+    async fn credentials(&self) -> Result<AwsCredentials, CredentialsError> {
+        let credentials = run_credential_process(&self.command)
+            .await
+            .map_err(CredentialsError::new)?;
+        Ok(AwsCredentials::new(
+            credentials.access_key_id,
+            credentials.secret_access_key,
+            credentials.session_token,
+            credentials.expiration,
+        ))
+    }
+}
+
 // A place-holder for the types of AWS credentials we support
 #[allow(clippy::large_enum_variant)] // discovered during Rust upgrade to 1.57; just allowing for now since we did previously
 pub enum AwsCredentialsProvider {
     Default(AutoRefreshingProvider<CustomChainProvider>),
-    Role(AutoRefreshingProvider<StsAssumeRoleSessionCredentialsProvider>),
+    Role(SharedCredentialsProvider),
     Static(StaticProvider),
     File(AutoRefreshingProvider<ProfileProvider>),
+    Process(AutoRefreshingProvider<CredentialProcessProvider>),
 }
 
 impl fmt::Debug for AwsCredentialsProvider {
@@ -127,6 +173,7 @@ impl fmt::Debug for AwsCredentialsProvider {
             Self::Role(_) => "role",
             Self::Static(_) => "static",
             Self::File(_) => "file",
+            Self::Process(_) => "process",
         };
 
         f.debug_tuple("AwsCredentialsProvider")
@@ -138,40 +185,101 @@ impl fmt::Debug for AwsCredentialsProvider {
 impl AwsCredentialsProvider {
     pub fn new(region: &Region, assume_role: Option<String>) -> crate::Result<Self> {
         if let Some(role) = assume_role {
-            debug!("Using STS assume role credentials for AWS.");
+            Self::new_with_roles(
+                region,
+                &AwsAuthentication::Role {
+                    assume_role: role,
+                    external_id: None,
+                    session_name: None,
+                    session_duration_secs: None,
+                    role_chain: Vec::new(),
+                },
+            )
+        } else {
+            debug!("Using default credentials provider for AWS.");
+            let mut chain = CustomChainProvider::new();
+            // 8 seconds because our default healthcheck timeout
+            // is 10 seconds.
+            chain.set_timeout(Duration::from_secs(8));
+
+            let creds = AutoRefreshingProvider::new(chain).context(InvalidAwsCredentialsSnafu)?;
 
-            let dispatcher = rusoto_core::request::HttpClient::new()
-                .map_err(|_| AwsRusotoError::DispatcherError)?;
+            Ok(Self::Default(creds))
+        }
+    }
 
+    /// Assumes the roles of `auth` in turn, starting from the default credentials, or reuses the
+    /// STS session of another component that assumed the same roles.
+    pub fn new_with_roles(region: &Region, auth: &AwsAuthentication) -> crate::Result<Self> {
+        debug!("Using STS assume role credentials for AWS.");
+
+        Self::shared_session(region, auth, || {
             let mut credentials = CustomChainProvider::new();
             credentials.set_timeout(Duration::from_secs(8));
+            let mut provider = SharedCredentialsProvider::new(credentials);
 
-            let sts = StsClient::new_with(dispatcher, credentials, region.clone());
+            for role in auth.assumed_roles() {
+                provider = assume_role(region, provider, role)?;
+            }
 
-            let provider = StsAssumeRoleSessionCredentialsProvider::new(
-                sts,
-                role,
-                "default".to_owned(),
-                None,
-                None,
-                None,
-                None,
+            Ok(provider)
+        })
+    }
+
+    /// Assumes a role with a web identity token, or reuses the STS session of another component
+    /// that assumed the same role.
+    pub fn new_with_web_identity(
+        region: &Region,
+        auth: &AwsAuthentication,
+        web_identity_token_file: &Path,
+        role_arn: &str,
+        session_name: Option<&str>,
+        session_duration: Option<Duration>,
+    ) -> crate::Result<Self> {
+        debug!("Using web identity credentials for AWS.");
+
+        Self::shared_session(region, auth, || {
+            let mut provider = WebIdentityProvider::new(
+                Variable::<Secret, CredentialsError>::from_text_file(web_identity_token_file),
+                Variable::<String, CredentialsError>::with_value(role_arn),
+                session_name.map(|name| {
+                    Variable::<Option<String>, CredentialsError>::with_value(Some(name.to_owned()))
+                }),
             );
+            provider.duration_seconds = session_duration.map(|duration| duration.as_secs() as i64);
 
             let creds =
                 AutoRefreshingProvider::new(provider).context(InvalidAwsCredentialsSnafu)?;
-            Ok(Self::Role(creds))
-        } else {
-            debug!("Using default credentials provider for AWS.");
-            let mut chain = CustomChainProvider::new();
-            // 8 seconds because our default healthcheck timeout
-            // is 10 seconds.
-            chain.set_timeout(Duration::from_secs(8));
+            Ok(SharedCredentialsProvider::new(creds))
+        })
+    }
 
-            let creds = AutoRefreshingProvider::new(chain).context(InvalidAwsCredentialsSnafu)?;
+    fn shared_session(
+        region: &Region,
+        auth: &AwsAuthentication,
+        build: impl FnOnce() -> crate::Result<SharedCredentialsProvider>,
+    ) -> crate::Result<Self> {
+        let key = auth
+            .session_key()
+            .map(|key| format!("{}/{}", region.name(), key));
+        let mut sessions = SESSIONS.lock().expect("poisoned lock");
+        if let Some(provider) = key.as_ref().and_then(|key| sessions.get(key)) {
+            return Ok(Self::Role(provider.clone()));
+        }
 
-            Ok(Self::Default(creds))
+        let provider = build()?;
+        if let Some(key) = key {
+            sessions.insert(key, provider.clone());
         }
+        Ok(Self::Role(provider))
+    }
+
+    pub fn new_with_credential_process(command: &str) -> crate::Result<Self> {
+        let creds = AutoRefreshingProvider::new(CredentialProcessProvider {
+            command: command.to_owned(),
+        })
+        .context(InvalidAwsCredentialsSnafu)?;
+        Ok(Self::Process(creds))
     }
 
     pub fn new_minimal<A: Into<String>, S: Into<String>>(access_key: A, secret_key: S) -> Self {
@@ -199,11 +307,36 @@ impl ProvideAwsCredentials for AwsCredentialsProvider {
             Self::Role(p) => p.credentials(),
             Self::Static(p) => p.credentials(),
             Self::File(p) => p.credentials(),
+            Self::Process(p) => p.credentials(),
         };
         fut.await
     }
 }
 
+/// Assumes `role` with the credentials of `provider`.
+fn assume_role(
+    region: &Region,
+    provider: SharedCredentialsProvider,
+    role: AssumedRole,
+) -> crate::Result<SharedCredentialsProvider> {
+    let dispatcher =
+        rusoto_core::request::HttpClient::new().map_err(|_| AwsRusotoError::DispatcherError)?;
+    let sts = StsClient::new_with(dispatcher, provider, region.clone());
+
+    let provider = StsAssumeRoleSessionCredentialsProvider::new(
+        sts,
+        role.role_arn,
+        role.session_name,
+        role.external_id,
+        role.session_duration,
+        None,
+        None,
+    );
+
+    let creds = AutoRefreshingProvider::new(provider).context(InvalidAwsCredentialsSnafu)?;
+    Ok(SharedCredentialsProvider::new(creds))
+}
+
 #[derive(Debug, Clone)]
 pub struct HttpClient<T> {
     client: T,
@@ -0,0 +1,440 @@
+//! Shared support for the `azure_event_hubs` source and sink, which talk to Event Hubs directly
+//! over AMQP 1.0 rather than through its Kafka compatible endpoint.
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use fe2o3_amqp::{
+    connection::ConnectionHandle,
+    sasl_profile::SaslProfile,
+    types::{
+        messaging::{ApplicationProperties, Body, Message, Properties},
+        primitives::{SimpleValue, Value as AmqpValue},
+    },
+    Connection, Delivery, Receiver, Sender, Session,
+};
+use hyper::Body as HyperBody;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use snafu::Snafu;
+
+use crate::{event::Value, http::HttpClient};
+
+/// Event Hubs only accepts AMQP over TLS.
+const AMQPS_PORT: u16 = 5671;
+
+/// The Azure Instance Metadata Service endpoint that hands out managed identity tokens.
+const IMDS_TOKEN_ENDPOINT: &str = "http://169.254.169.254/metadata/identity/oauth2/token";
+const IMDS_API_VERSION: &str = "2018-02-01";
+/// The resource Azure AD tokens for Event Hubs are issued for.
+const EVENT_HUBS_RESOURCE: &str = "https://eventhubs.azure.net";
+
+#[derive(Debug, Snafu)]
+pub enum EventHubsError {
+    #[snafu(display("Invalid connection string: {}", reason))]
+    InvalidConnectionString { reason: &'static str },
+    #[snafu(display(
+        "No event hub specified, either set `event_hub` or include `EntityPath` in the connection string"
+    ))]
+    MissingEventHub,
+    #[snafu(display("Management request failed: {}", reason))]
+    Management { reason: String },
+    #[snafu(display("Failed to get a managed identity token: {}", reason))]
+    ManagedIdentity { reason: String },
+}
+
+/// The parts of an Event Hubs connection string, as found under "Shared access policies" in the
+/// Azure portal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectionString {
+    /// The fully qualified namespace, such as `my-namespace.servicebus.windows.net`.
+    pub namespace: String,
+    pub key_name: String,
+    pub key: String,
+    pub entity_path: Option<String>,
+}
+
+impl FromStr for ConnectionString {
+    type Err = EventHubsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut namespace = None;
+        let mut key_name = None;
+        let mut key = None;
+        let mut entity_path = None;
+
+        for part in s.split(';').map(str::trim).filter(|part| !part.is_empty()) {
+            let (name, value) =
+                part.split_once('=')
+                    .ok_or(EventHubsError::InvalidConnectionString {
+                        reason: "expected `Name=value` pairs separated by `;`",
+                    })?;
+
+            match name.to_ascii_lowercase().as_str() {
+                "endpoint" => {
+                    let host = value
+                        .strip_prefix("sb://")
+                        .ok_or(EventHubsError::InvalidConnectionString {
+                            reason: "`Endpoint` must start with `sb://`",
+                        })?
+                        .trim_end_matches('/');
+                    namespace = Some(host.to_owned());
+                }
+                "sharedaccesskeyname" => key_name = Some(value.to_owned()),
+                // The key is base64, so may itself end in `=`.
+                "sharedaccesskey" => key = Some(value.to_owned()),
+                "entitypath" => entity_path = Some(value.to_owned()),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            namespace: namespace.ok_or(EventHubsError::InvalidConnectionString {
+                reason: "missing `Endpoint`",
+            })?,
+            key_name: key_name.ok_or(EventHubsError::InvalidConnectionString {
+                reason: "missing `SharedAccessKeyName`",
+            })?,
+            key: key.ok_or(EventHubsError::InvalidConnectionString {
+                reason: "missing `SharedAccessKey`",
+            })?,
+            entity_path,
+        })
+    }
+}
+
+impl ConnectionString {
+    /// The event hub to use, preferring one given explicitly over the connection string's.
+    pub fn event_hub(&self, event_hub: Option<&str>) -> Result<String, EventHubsError> {
+        event_hub
+            .or_else(|| self.entity_path.as_deref())
+            .map(Into::into)
+            .ok_or(EventHubsError::MissingEventHub)
+    }
+
+    /// Open a connection, authenticating with the shared access key over SASL PLAIN.
+    pub async fn connect(&self, container_id: &str) -> crate::Result<ConnectionHandle<()>> {
+        let connection = Connection::builder()
+            .container_id(container_id)
+            .hostname(self.namespace.as_str())
+            .sasl_profile(SaslProfile::Plain {
+                username: self.key_name.clone(),
+                password: self.key.clone(),
+            })
+            .open(format!("amqps://{}:{}", self.namespace, AMQPS_PORT).as_str())
+            .await?;
+
+        Ok(connection)
+    }
+}
+
+/// Open a connection that is authenticated afterwards, by putting a token with [`put_token`].
+pub async fn connect_anonymous(
+    namespace: &str,
+    container_id: &str,
+) -> crate::Result<ConnectionHandle<()>> {
+    let connection = Connection::builder()
+        .container_id(container_id)
+        .hostname(namespace)
+        .sasl_profile(SaslProfile::Anonymous)
+        .open(format!("amqps://{}:{}", namespace, AMQPS_PORT).as_str())
+        .await?;
+
+    Ok(connection)
+}
+
+/// An Azure AD access token, along with when it stops being accepted.
+#[derive(Clone, Debug)]
+pub struct AccessToken {
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+struct ImdsToken {
+    access_token: String,
+    // Seconds since the epoch, sent as a string.
+    expires_on: String,
+}
+
+/// Get a token for Event Hubs from the managed identity of the Azure resource Vector runs on.
+///
+/// `client_id` picks one of several user assigned identities, otherwise the system assigned
+/// identity is used.
+pub async fn managed_identity_token(
+    client: &HttpClient,
+    client_id: Option<&str>,
+) -> crate::Result<AccessToken> {
+    let mut uri = format!(
+        "{}?api-version={}&resource={}",
+        IMDS_TOKEN_ENDPOINT, IMDS_API_VERSION, EVENT_HUBS_RESOURCE
+    );
+    if let Some(client_id) = client_id {
+        uri.push_str("&client_id=");
+        uri.push_str(client_id);
+    }
+
+    let request = http::Request::get(uri)
+        .header("Metadata", "true")
+        .body(HyperBody::empty())?;
+    let response = client.send(request).await?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+
+    if !status.is_success() {
+        return Err(Box::new(EventHubsError::ManagedIdentity {
+            reason: format!(
+                "unexpected status code {}: {}",
+                status,
+                String::from_utf8_lossy(&body)
+            ),
+        }));
+    }
+
+    let token: ImdsToken = serde_json::from_slice(&body)?;
+    let expires_on = token
+        .expires_on
+        .parse()
+        .map_err(|_| EventHubsError::ManagedIdentity {
+            reason: format!("invalid `expires_on` of {:?}", token.expires_on),
+        })?;
+
+    Ok(AccessToken {
+        token: token.access_token,
+        expires_at: Utc.timestamp(expires_on, 0),
+    })
+}
+
+/// Authorize an anonymous connection to use `event_hub`, with claims based security.
+pub async fn put_token(
+    connection: &mut ConnectionHandle<()>,
+    namespace: &str,
+    event_hub: &str,
+    token: &AccessToken,
+) -> crate::Result<()> {
+    let audience = format!("amqp://{}/{}", namespace, event_hub);
+    let properties = ApplicationProperties::builder()
+        .insert("operation", "put-token")
+        .insert("type", "jwt")
+        .insert("name", audience)
+        .build();
+
+    request(
+        connection,
+        "$cbs",
+        properties,
+        AmqpValue::String(token.token.clone()),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Ask the `$management` node for the ids of the event hub's partitions.
+pub async fn partition_ids(
+    connection: &mut ConnectionHandle<()>,
+    event_hub: &str,
+) -> crate::Result<Vec<String>> {
+    let properties = ApplicationProperties::builder()
+        .insert("operation", "READ")
+        .insert("type", "com.microsoft:eventhub")
+        .insert("name", event_hub)
+        .build();
+    let response = request(connection, "$management", properties, AmqpValue::Null).await?;
+
+    let ids = match response.body() {
+        Body::Value(value) => match &value.0 {
+            AmqpValue::Map(map) => map.get(&AmqpValue::from("partition_ids")).cloned(),
+            _ => None,
+        },
+        _ => None,
+    };
+    let ids = match ids {
+        Some(AmqpValue::Array(ids)) => ids.0,
+        Some(AmqpValue::List(ids)) => ids,
+        _ => {
+            return Err(Box::new(EventHubsError::Management {
+                reason: "response is missing `partition_ids`".into(),
+            }))
+        }
+    };
+
+    Ok(ids
+        .into_iter()
+        .filter_map(|id| match id {
+            AmqpValue::String(id) => Some(id),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Send a request to one of the service's nodes, such as `$management` or `$cbs`, and wait for
+/// the response.
+async fn request(
+    connection: &mut ConnectionHandle<()>,
+    node: &str,
+    application_properties: ApplicationProperties,
+    body: AmqpValue,
+) -> crate::Result<Delivery<AmqpValue>> {
+    let reply_to = format!("vector-{}", node.trim_start_matches('$'));
+
+    let mut session = Session::begin(connection).await?;
+    let mut sender = Sender::attach(&mut session, format!("{}-sender", reply_to), node).await?;
+    let mut receiver = Receiver::builder()
+        .name(format!("{}-receiver", reply_to))
+        .source(node)
+        .target(reply_to.as_str())
+        .attach(&mut session)
+        .await?;
+
+    let request = Message::builder()
+        .properties(
+            Properties::builder()
+                .message_id(uuid::Uuid::new_v4().to_string())
+                .reply_to(reply_to.as_str())
+                .build(),
+        )
+        .application_properties(application_properties)
+        .value(body)
+        .build();
+    sender.send(request).await?;
+
+    let response: Delivery<AmqpValue> = receiver.recv().await?;
+    receiver.accept(&response).await?;
+
+    sender.close().await?;
+    receiver.close().await?;
+    session.end().await?;
+
+    // `$management` answers with 200, and `$cbs` with 202.
+    let status = response
+        .message()
+        .application_properties
+        .as_ref()
+        .and_then(|properties| {
+            properties
+                .0
+                .get("status-code")
+                .or_else(|| properties.0.get("statusCode"))
+        })
+        .cloned();
+    match status {
+        None | Some(SimpleValue::Int(200)) | Some(SimpleValue::Int(202)) => Ok(response),
+        Some(status) => Err(Box::new(EventHubsError::Management {
+            reason: format!("{} returned status code {:?}", node, status),
+        })),
+    }
+}
+
+/// The payload of a message, which Event Hubs always sends as a single data section, unless it
+/// was published by another AMQP client as a value.
+pub fn body_bytes(body: &Body<AmqpValue>) -> Option<Bytes> {
+    match body {
+        Body::Data(data) => Some(Bytes::copy_from_slice(&data.0)),
+        Body::Value(value) => match &value.0 {
+            AmqpValue::Binary(bytes) => Some(Bytes::copy_from_slice(bytes)),
+            AmqpValue::String(string) => Some(Bytes::copy_from_slice(string.as_bytes())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn to_value(value: &AmqpValue) -> Value {
+    match value {
+        AmqpValue::Null => Value::Null,
+        AmqpValue::Bool(value) => Value::from(*value),
+        AmqpValue::Ubyte(value) => Value::from(*value as i64),
+        AmqpValue::Ushort(value) => Value::from(*value as i64),
+        AmqpValue::Uint(value) => Value::from(*value as i64),
+        AmqpValue::Ulong(value) => ulong_to_value(*value),
+        AmqpValue::Byte(value) => Value::from(*value as i64),
+        AmqpValue::Short(value) => Value::from(*value as i64),
+        AmqpValue::Int(value) => Value::from(*value as i64),
+        AmqpValue::Long(value) => Value::from(*value),
+        AmqpValue::Float(value) => Value::from(value.into_inner() as f64),
+        AmqpValue::Double(value) => Value::from(value.into_inner()),
+        AmqpValue::Char(value) => Value::from(value.to_string()),
+        AmqpValue::Timestamp(value) => Value::from(Utc.timestamp_millis(value.milliseconds())),
+        AmqpValue::Uuid(value) => {
+            Value::from(uuid::Uuid::from_bytes(*value.as_inner()).to_string())
+        }
+        AmqpValue::Binary(value) => Value::from(Bytes::copy_from_slice(value)),
+        AmqpValue::String(value) => Value::from(value.as_str()),
+        AmqpValue::Symbol(value) => Value::from(value.as_str()),
+        AmqpValue::List(values) => Value::from(values.iter().map(to_value).collect::<Vec<_>>()),
+        AmqpValue::Array(values) => Value::from(values.0.iter().map(to_value).collect::<Vec<_>>()),
+        AmqpValue::Map(map) => Value::from(
+            map.iter()
+                .map(|(key, value)| (key_to_string(key), to_value(value)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+        other => Value::from(format!("{:?}", other)),
+    }
+}
+
+/// Integer values are signed, so the largest unsigned ones are kept as decimals instead.
+pub fn ulong_to_value(value: u64) -> Value {
+    i64::try_from(value).map_or_else(|_| Value::Decimal(Decimal::from(value)), Value::from)
+}
+
+fn key_to_string(key: &AmqpValue) -> String {
+    match key {
+        AmqpValue::String(key) => key.clone(),
+        AmqpValue::Symbol(key) => key.as_str().to_owned(),
+        other => match to_value(other) {
+            Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            value => value.to_string_lossy(),
+        },
+    }
+}
+
+pub fn simple_to_value(value: &SimpleValue) -> Value {
+    to_value(&AmqpValue::from(value.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_connection_string() {
+        let parsed: ConnectionString = "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=RootManageSharedAccessKey;SharedAccessKey=c2VjcmV0=;EntityPath=logs"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            parsed,
+            ConnectionString {
+                namespace: "vector.servicebus.windows.net".into(),
+                key_name: "RootManageSharedAccessKey".into(),
+                key: "c2VjcmV0=".into(),
+                entity_path: Some("logs".into()),
+            }
+        );
+        assert_eq!(parsed.event_hub(None).unwrap(), "logs");
+        assert_eq!(parsed.event_hub(Some("other")).unwrap(), "other");
+    }
+
+    #[test]
+    fn converts_ulongs_without_wrapping() {
+        assert_eq!(to_value(&AmqpValue::Ulong(42)), Value::Integer(42));
+        assert_eq!(
+            to_value(&AmqpValue::Ulong(u64::MAX)),
+            Value::Decimal(Decimal::from(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn rejects_incomplete_connection_string() {
+        let error = "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=key"
+            .parse::<ConnectionString>()
+            .unwrap_err();
+        assert!(error.to_string().contains("SharedAccessKey"));
+
+        assert!("Endpoint=https://vector.servicebus.windows.net/"
+            .parse::<ConnectionString>()
+            .is_err());
+    }
+}
@@ -0,0 +1,333 @@
+//! Runs a config in-process with generated input and discarded output, and reports how fast
+//! events went through it.
+//!
+//! Each source is replaced with a `demo_logs` source, and each sink with a `blackhole` sink that
+//! keeps the original inputs and buffer, so that only the transforms and buffers of the config
+//! are measured.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use structopt::StructOpt;
+use vector_core::event::MetricValue;
+
+use crate::{
+    cli::handle_config_errors,
+    config::{self, ComponentKey, ConfigDiff, SinkOuter, SourceOuter},
+    sinks::blackhole::BlackholeConfig,
+    sources::demo_logs::DemoLogsConfig,
+    topology,
+};
+
+#[derive(StructOpt, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Opts {
+    /// Read configuration from one or more files. Wildcard paths are supported.
+    /// File format is detected from the file name.
+    /// If zero files are specified the default config path
+    /// `/etc/vector/vector.toml` will be targeted.
+    #[structopt(
+        name = "config",
+        short,
+        long,
+        env = "VECTOR_CONFIG",
+        use_delimiter(true)
+    )]
+    paths: Vec<PathBuf>,
+
+    /// Vector config files in TOML format.
+    #[structopt(name = "config-toml", long, use_delimiter(true))]
+    paths_toml: Vec<PathBuf>,
+
+    /// Vector config files in JSON format.
+    #[structopt(name = "config-json", long, use_delimiter(true))]
+    paths_json: Vec<PathBuf>,
+
+    /// Vector config files in YAML format.
+    #[structopt(name = "config-yaml", long, use_delimiter(true))]
+    paths_yaml: Vec<PathBuf>,
+
+    /// Read configuration from files in one or more directories.
+    /// File format is detected from the file name.
+    ///
+    /// Files not ending in .toml, .json, .yaml, or .yml will be ignored.
+    #[structopt(
+        name = "config-dir",
+        short = "C",
+        long,
+        env = "VECTOR_CONFIG_DIR",
+        use_delimiter(true)
+    )]
+    pub config_dirs: Vec<PathBuf>,
+
+    /// The number of events to generate, split between the sources of the config.
+    /// Accepts `k`, `M` and `G` suffixes, such as `1M`.
+    #[structopt(long, default_value = "1M", parse(try_from_str = parse_count))]
+    events: usize,
+
+    /// The `demo_logs` format of the generated events, such as `json` or `nginx_access`.
+    #[structopt(long, default_value = "json")]
+    format: String,
+
+    /// Print the report as JSON.
+    #[structopt(long)]
+    json: bool,
+}
+
+impl Opts {
+    fn paths_with_formats(&self) -> Vec<config::ConfigPath> {
+        config::merge_path_lists(vec![
+            (&self.paths, None),
+            (&self.paths_toml, Some(config::Format::Toml)),
+            (&self.paths_json, Some(config::Format::Json)),
+            (&self.paths_yaml, Some(config::Format::Yaml)),
+        ])
+        .map(|(path, hint)| config::ConfigPath::File(path, hint))
+        .chain(
+            self.config_dirs
+                .iter()
+                .map(|dir| config::ConfigPath::Dir(dir.to_path_buf())),
+        )
+        .collect()
+    }
+}
+
+fn parse_count(count: &str) -> Result<usize, String> {
+    let (digits, multiplier) = match count.chars().last() {
+        Some('k' | 'K') => (&count[..count.len() - 1], 1_000),
+        Some('M') => (&count[..count.len() - 1], 1_000_000),
+        Some('G') => (&count[..count.len() - 1], 1_000_000_000),
+        _ => (count, 1),
+    };
+    digits
+        .parse::<usize>()
+        .ok()
+        .and_then(|count| count.checked_mul(multiplier))
+        .ok_or_else(|| format!("{} is not a valid number of events", count))
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Report {
+    events: usize,
+    elapsed_secs: f64,
+    events_per_sec: f64,
+    components: BTreeMap<String, ComponentReport>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct ComponentReport {
+    kind: String,
+    received_events: u64,
+    sent_events: u64,
+    cpu_secs: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allocations: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    allocated_bytes: Option<u64>,
+}
+
+pub async fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    let paths = opts.paths_with_formats();
+    let paths = match config::process_paths(&paths) {
+        Some(paths) => paths,
+        None => return exitcode::CONFIG,
+    };
+
+    let mut builder = match config::load_builder_from_paths(&paths) {
+        Ok((builder, _warnings)) => builder,
+        Err(errors) => return handle_config_errors(errors),
+    };
+    if builder.sources.is_empty() {
+        return handle_config_errors(vec![
+            "The config has no sources to generate events for.".into()
+        ]);
+    }
+
+    // Every source gets an equal share of the events, with the first taking the remainder.
+    let sources = builder.sources.len();
+    for (index, source) in builder.sources.values_mut().enumerate() {
+        let count = opts.events / sources + if index == 0 { opts.events % sources } else { 0 };
+        let mut demo_logs = toml::value::Table::new();
+        demo_logs.insert("interval".into(), 0.0.into());
+        demo_logs.insert("count".into(), (count as i64).into());
+        demo_logs.insert("format".into(), opts.format.clone().into());
+        let demo_logs: DemoLogsConfig = match toml::Value::Table(demo_logs).try_into() {
+            Ok(demo_logs) => demo_logs,
+            Err(error) => return handle_config_errors(vec![error.to_string()]),
+        };
+        *source = SourceOuter::new(demo_logs);
+    }
+    for sink in builder.sinks.values_mut() {
+        let mut blackhole = SinkOuter::new(
+            sink.inputs.clone(),
+            Box::new(BlackholeConfig {
+                // The totals are in the report instead.
+                print_interval_secs: 24 * 60 * 60,
+                ..Default::default()
+            }),
+        );
+        blackhole.buffer = sink.buffer.clone();
+        *sink = blackhole;
+    }
+    builder.healthchecks.enabled = false;
+    #[cfg(feature = "api")]
+    {
+        builder.api.enabled = false;
+    }
+
+    let config = match builder.build() {
+        Ok(config) => config,
+        Err(errors) => return handle_config_errors(errors),
+    };
+    let kinds = config
+        .sources
+        .keys()
+        .map(|key| (key.clone(), "source"))
+        .chain(
+            config
+                .transforms
+                .keys()
+                .map(|key| (key.clone(), "transform")),
+        )
+        .chain(config.sinks.keys().map(|key| (key.clone(), "sink")))
+        .collect::<HashMap<_, _>>();
+
+    topology::measure_poll_time();
+    let start = Instant::now();
+    let diff = ConfigDiff::initial(&config);
+    let pieces = match topology::build_or_log_errors(&config, &diff, HashMap::new()).await {
+        Some(pieces) => pieces,
+        None => return exitcode::CONFIG,
+    };
+    let (topology, _crash) = match topology::start_validated(config, diff, pieces).await {
+        Some(running) => running,
+        None => return exitcode::CONFIG,
+    };
+    topology.sources_finished().await;
+    topology.stop().await;
+    let elapsed = start.elapsed();
+
+    let report = build_report(opts.events, elapsed, &kinds);
+    if opts.json {
+        #[allow(clippy::print_stdout)]
+        {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&report).expect("report is serializable")
+            );
+        }
+    } else {
+        print_report(&report);
+    }
+
+    exitcode::OK
+}
+
+fn build_report(
+    events: usize,
+    elapsed: Duration,
+    kinds: &HashMap<ComponentKey, &'static str>,
+) -> Report {
+    let mut report = Report {
+        events,
+        elapsed_secs: elapsed.as_secs_f64(),
+        events_per_sec: events as f64 / elapsed.as_secs_f64(),
+        components: BTreeMap::new(),
+    };
+    for (key, kind) in kinds {
+        report.components.insert(
+            key.id().to_owned(),
+            ComponentReport {
+                kind: (*kind).to_owned(),
+                ..Default::default()
+            },
+        );
+    }
+
+    for (key, poll_time) in topology::poll_times() {
+        if let Some(component) = report.components.get_mut(key.id()) {
+            component.cpu_secs = poll_time.as_secs_f64();
+        }
+    }
+
+    if let Ok(controller) = crate::metrics::Controller::get() {
+        for metric in controller.capture_metrics() {
+            let component = match metric
+                .tag_value("component_id")
+                .and_then(|id| report.components.get_mut(&id))
+            {
+                Some(component) => component,
+                None => continue,
+            };
+            let value = match metric.value() {
+                MetricValue::Counter { value } => *value as u64,
+                _ => continue,
+            };
+            match metric.name() {
+                "component_received_events_total" => component.received_events += value,
+                "component_sent_events_total" => component.sent_events += value,
+                _ => {}
+            }
+        }
+    }
+
+    #[cfg(feature = "allocation-tracing")]
+    if crate::allocations::is_enabled() {
+        for allocations in crate::allocations::component_allocations() {
+            if let Some(component) = report.components.get_mut(&allocations.id) {
+                component.allocations = Some(allocations.allocations);
+                component.allocated_bytes = Some(allocations.total_allocated_bytes);
+            }
+        }
+    }
+
+    report
+}
+
+#[allow(clippy::print_stdout)]
+fn print_report(report: &Report) {
+    println!(
+        "{} events in {:.3}s: {:.0} events/s",
+        report.events, report.elapsed_secs, report.events_per_sec
+    );
+    println!();
+    println!(
+        "{:<32} {:<10} {:>12} {:>12} {:>10} {:>12} {:>14}",
+        "component", "kind", "received", "sent", "cpu (s)", "allocations", "allocated (B)"
+    );
+    for (id, component) in &report.components {
+        println!(
+            "{:<32} {:<10} {:>12} {:>12} {:>10.3} {:>12} {:>14}",
+            id,
+            component.kind,
+            component.received_events,
+            component.sent_events,
+            component.cpu_secs,
+            component
+                .allocations
+                .map_or_else(|| "-".to_owned(), |allocations| allocations.to_string()),
+            component
+                .allocated_bytes
+                .map_or_else(|| "-".to_owned(), |bytes| bytes.to_string()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_counts() {
+        assert_eq!(parse_count("500"), Ok(500));
+        assert_eq!(parse_count("10k"), Ok(10_000));
+        assert_eq!(parse_count("1M"), Ok(1_000_000));
+        assert_eq!(parse_count("2G"), Ok(2_000_000_000));
+        assert!(parse_count("M").is_err());
+        assert!(parse_count("1.5M").is_err());
+    }
+}
@@ -2,6 +2,8 @@ use std::path::PathBuf;
 
 use structopt::{clap::AppSettings, StructOpt};
 
+#[cfg(all(feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+use crate::bench;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api-client")]
@@ -37,13 +39,9 @@ impl Opts {
             | Some(SubCommand::Graph(_))
             | Some(SubCommand::Generate(_))
             | Some(SubCommand::List(_))
-            | Some(SubCommand::Test(_)) => {
-                if self.root.verbose == 0 {
-                    (self.root.quiet + 1, self.root.verbose)
-                } else {
-                    (self.root.quiet, self.root.verbose - 1)
-                }
-            }
+            | Some(SubCommand::Test(_)) => self.root.quieter(),
+            #[cfg(all(feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+            Some(SubCommand::Bench(_)) => self.root.quieter(),
             _ => (self.root.quiet, self.root.verbose),
         };
         match quiet_level {
@@ -151,9 +149,24 @@ pub struct RootOpts {
     /// Watch for changes in configuration file, and reload accordingly.
     #[structopt(short, long, env = "VECTOR_WATCH_CONFIG")]
     pub watch_config: bool,
+
+    /// Attribute heap allocations to the components that made them, and report them with the
+    /// `component_allocated_bytes` internal metric.
+    #[cfg(feature = "allocation-tracing")]
+    #[structopt(long, env = "VECTOR_ALLOCATION_TRACING")]
+    pub allocation_tracing: bool,
 }
 
 impl RootOpts {
+    /// The quiet and verbose levels of subcommands that log one level less than Vector does.
+    const fn quieter(&self) -> (u8, u8) {
+        if self.verbose == 0 {
+            (self.quiet + 1, self.verbose)
+        } else {
+            (self.quiet, self.verbose - 1)
+        }
+    }
+
     /// Return a list of config paths with the associated formats.
     pub fn config_paths_with_formats(&self) -> Vec<config::ConfigPath> {
         config::merge_path_lists(vec![
@@ -191,6 +204,11 @@ pub enum SubCommand {
     /// Output the topology as visual representation using the DOT language which can be rendered by GraphViz
     Graph(graph::Opts),
 
+    /// Run a config in-process with generated input and discarded output, then report its throughput
+    /// and the CPU time and allocations of each component.
+    #[cfg(all(feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+    Bench(bench::Opts),
+
     /// Display topology and metrics in the console, for a local or remote Vector instance
     #[cfg(feature = "api-client")]
     Top(top::Opts),
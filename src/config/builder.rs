@@ -39,6 +39,95 @@ pub struct ConfigBuilder {
     #[serde(default)]
     pub tests: Vec<TestDefinition<String>>,
     pub provider: Option<Box<dyn provider::ProviderConfig>>,
+    /// Other config files to load along with this one, resolved relative to it.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    #[serde(default, skip_serializing_if = "ExtendOptions::is_empty")]
+    pub extend: ExtendOptions,
+}
+
+/// Additions to transforms and sinks defined in another config file, such as a shared base
+/// config that several team configs include.
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExtendOptions {
+    #[serde(default)]
+    pub transforms: IndexMap<ComponentKey, ExtendComponent>,
+    #[serde(default)]
+    pub sinks: IndexMap<ComponentKey, ExtendComponent>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ExtendComponent {
+    /// Inputs to add to the component.
+    #[serde(default)]
+    pub inputs: Vec<String>,
+}
+
+impl ExtendOptions {
+    pub fn is_empty(&self) -> bool {
+        self.transforms.is_empty() && self.sinks.is_empty()
+    }
+
+    fn merge(&mut self, with: Self) {
+        for (key, component) in with.transforms {
+            self.transforms
+                .entry(key)
+                .or_default()
+                .inputs
+                .extend(component.inputs);
+        }
+        for (key, component) in with.sinks {
+            self.sinks
+                .entry(key)
+                .or_default()
+                .inputs
+                .extend(component.inputs);
+        }
+    }
+
+    /// Adds the extra inputs to the components they extend, which must exist by now.
+    pub(super) fn apply(
+        self,
+        transforms: &mut IndexMap<ComponentKey, TransformOuter<String>>,
+        sinks: &mut IndexMap<ComponentKey, SinkOuter<String>>,
+    ) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        for (key, component) in self.transforms {
+            match transforms.get_mut(&key) {
+                Some(transform) => extend_inputs(&mut transform.inputs, component.inputs),
+                None => errors.push(format!(
+                    "extend.transforms.{} refers to a transform that isn't defined",
+                    key
+                )),
+            }
+        }
+        for (key, component) in self.sinks {
+            match sinks.get_mut(&key) {
+                Some(sink) => extend_inputs(&mut sink.inputs, component.inputs),
+                None => errors.push(format!(
+                    "extend.sinks.{} refers to a sink that isn't defined",
+                    key
+                )),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn extend_inputs(inputs: &mut Vec<String>, extra: Vec<String>) {
+    for input in extra {
+        if !inputs.contains(&input) {
+            inputs.push(input);
+        }
+    }
 }
 
 #[cfg(feature = "datadog-pipelines")]
@@ -110,6 +199,8 @@ impl From<Config> for ConfigBuilder {
             transforms,
             provider: None,
             tests,
+            include: Vec::new(),
+            extend: ExtendOptions::default(),
         }
     }
 }
@@ -202,6 +293,8 @@ impl ConfigBuilder {
         }
 
         self.provider = with.provider;
+        self.include.extend(with.include);
+        self.extend.merge(with.extend);
 
         if self.global.proxy.http.is_some() && with.global.proxy.http.is_some() {
             errors.push("conflicting values for 'proxy.http' found".to_owned());
@@ -217,6 +310,45 @@ impl ConfigBuilder {
 
         self.global.proxy = self.global.proxy.merge(&with.global.proxy);
 
+        match (self.global.max_connections, with.global.max_connections) {
+            (Some(this), Some(that)) if this != that => {
+                errors.push("conflicting values for 'max_connections' found".to_owned());
+            }
+            (None, that) => self.global.max_connections = that,
+            _ => (),
+        }
+
+        match (
+            self.global.coalesce_latency_ms,
+            with.global.coalesce_latency_ms,
+        ) {
+            (Some(this), Some(that)) if this != that => {
+                errors.push("conflicting values for 'coalesce_latency_ms' found".to_owned());
+            }
+            (None, that) => self.global.coalesce_latency_ms = that,
+            _ => (),
+        }
+
+        match (
+            self.global.expire_metrics_secs,
+            with.global.expire_metrics_secs,
+        ) {
+            (Some(this), Some(that)) if this != that => {
+                errors.push("conflicting values for 'expire_metrics_secs' found".to_owned());
+            }
+            (None, that) => self.global.expire_metrics_secs = that,
+            _ => (),
+        }
+
+        if let Some(that) = with.global.source_heartbeat {
+            match &self.global.source_heartbeat {
+                Some(this) if *this != that => {
+                    errors.push("conflicting values for 'source_heartbeat' found".to_owned());
+                }
+                _ => self.global.source_heartbeat = Some(that),
+            }
+        }
+
         if self.global.data_dir.is_none() || self.global.data_dir == default_data_dir() {
             self.global.data_dir = with.global.data_dir;
         } else if with.global.data_dir != default_data_dir()
@@ -20,6 +20,13 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         errors.extend(name_errors);
     }
 
+    // Extensions name components as they're written in the config, so they're applied before
+    // any components are expanded.
+    let extend = std::mem::take(&mut builder.extend);
+    if let Err(extend_errors) = extend.apply(&mut builder.transforms, &mut builder.sinks) {
+        errors.extend(extend_errors);
+    }
+
     let expansions = expand_macros(&mut builder)?;
 
     expand_globs(&mut builder);
@@ -51,6 +58,8 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
         transforms,
         tests,
         provider: _,
+        include: _,
+        extend: _,
     } = builder;
 
     let graph = match Graph::new(&sources, &transforms, &sinks) {
@@ -67,6 +76,8 @@ pub fn compile(mut builder: ConfigBuilder) -> Result<(Config, Vec<String>), Vec<
 
     if let Err(e) = graph.check_for_cycles() {
         errors.push(e);
+    } else if let Err(ordering_errors) = validation::check_ordering(&graph, &sources, &sinks) {
+        errors.extend(ordering_errors);
     }
 
     // Inputs are resolved from string into OutputIds as part of graph construction, so update them
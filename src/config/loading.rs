@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs::{File, ReadDir},
     path::{Path, PathBuf},
     sync::Mutex,
@@ -221,19 +221,77 @@ fn load_builder_from_file(
     path: &Path,
     format: Format,
     builder: &mut ConfigBuilder,
+    loaded_paths: &mut HashSet<PathBuf>,
 ) -> Result<Vec<String>, Vec<String>> {
-    match load_from_file(path, format)? {
-        Some((_, loaded, warnings)) => {
-            builder.append(loaded)?;
-            Ok(warnings)
+    // A file included by several configs, such as a shared base config, is only loaded once.
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+    if !loaded_paths.insert(canonical) {
+        return Ok(Vec::new());
+    }
+
+    match load_from_file::<ConfigBuilder>(path, format)? {
+        Some((_, mut loaded, mut warnings)) => {
+            let mut errors = Vec::new();
+            for include in std::mem::take(&mut loaded.include) {
+                let included = match resolve_include(path, &include) {
+                    Ok(included) => included,
+                    Err(error) => {
+                        errors.push(error);
+                        continue;
+                    }
+                };
+                for included_path in included {
+                    let format = Format::from_path(&included_path).unwrap_or_default();
+                    match load_builder_from_file(&included_path, format, builder, loaded_paths) {
+                        Ok(warns) => warnings.extend(warns),
+                        Err(errs) => errors.extend(errs),
+                    }
+                }
+            }
+
+            if let Err(errs) = builder.append(loaded) {
+                errors.extend(
+                    errs.into_iter()
+                        .map(|error| format!("{} (in {:?})", error, path)),
+                );
+            }
+
+            if errors.is_empty() {
+                Ok(warnings)
+            } else {
+                Err(errors)
+            }
         }
         None => Ok(Vec::new()),
     }
 }
 
+/// Resolves an `include` entry, which may be a glob, relative to the file that includes it.
+fn resolve_include(path: &Path, include: &str) -> Result<Vec<PathBuf>, String> {
+    let pattern = path
+        .parent()
+        .map(|parent| parent.join(include))
+        .unwrap_or_else(|| PathBuf::from(include));
+    let paths = glob(&pattern.to_string_lossy())
+        .map_err(|error| format!("Invalid include {:?} in {:?}: {}.", include, path, error))?
+        .filter_map(Result::ok)
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+
+    if paths.is_empty() {
+        Err(format!(
+            "Include {:?} in {:?} didn't match any files.",
+            include, path
+        ))
+    } else {
+        Ok(paths)
+    }
+}
+
 fn load_builder_from_dir(
     path: &Path,
     builder: &mut ConfigBuilder,
+    loaded_paths: &mut HashSet<PathBuf>,
 ) -> Result<Vec<String>, Vec<String>> {
     let readdir = read_dir(path)?;
     let mut warnings = Vec::new();
@@ -245,7 +303,12 @@ fn load_builder_from_dir(
                 if entry_path.is_file() {
                     // skip any unknown file formats
                     if let Ok(format) = Format::from_path(direntry.path()) {
-                        match load_builder_from_file(&direntry.path(), format, builder) {
+                        match load_builder_from_file(
+                            &direntry.path(),
+                            format,
+                            builder,
+                            loaded_paths,
+                        ) {
                             Ok(warns) => warnings.extend(warns),
                             Err(errs) => errors.extend(errs),
                         }
@@ -334,6 +397,7 @@ pub fn load_builder_from_paths(
     config_paths: &[ConfigPath],
 ) -> Result<(ConfigBuilder, Vec<String>), Vec<String>> {
     let mut result = ConfigBuilder::default();
+    let mut loaded_paths = HashSet::new();
     let mut warnings = Vec::new();
     let mut errors = Vec::new();
 
@@ -346,13 +410,14 @@ pub fn load_builder_from_paths(
                         .or_else(move || Format::from_path(&path).ok())
                         .unwrap_or_default(),
                     &mut result,
+                    &mut loaded_paths,
                 ) {
                     Ok(warns) => warnings.extend(warns),
                     Err(errs) => errors.extend(errs),
                 };
             }
             ConfigPath::Dir(path) => {
-                match load_builder_from_dir(path, &mut result) {
+                match load_builder_from_dir(path, &mut result, &mut loaded_paths) {
                     Ok(warns) => warnings.extend(warns),
                     Err(errs) => errors.extend(errs),
                 };
@@ -386,7 +451,12 @@ fn load_from_inputs(
     let mut warnings = Vec::new();
 
     for (input, format) in inputs {
-        if let Err(errs) = load(input, format).and_then(|(n, warn)| {
+        if let Err(errs) = load(input, format).and_then(|(n, warn): (ConfigBuilder, _)| {
+            if !n.include.is_empty() {
+                return Err(vec![
+                    "`include` can only be used in config files.".to_owned()
+                ]);
+            }
             warnings.extend(warn);
             config.append(n)
         }) {
@@ -498,4 +568,43 @@ mod tests {
         let (_, warnings) = load_builder_from_paths(&configs).unwrap();
         assert!(warnings.is_empty());
     }
+
+    #[test]
+    fn load_includes_shared_config_once() {
+        let path = PathBuf::from(".").join("tests").join("config-include");
+        let configs = vec![
+            ConfigPath::File(path.join("team-a.toml"), None),
+            ConfigPath::File(path.join("team-b.toml"), None),
+        ];
+        let (builder, warnings) = load_builder_from_paths(&configs).unwrap();
+        assert!(warnings.is_empty());
+        assert!(builder.include.is_empty());
+        assert_eq!(builder.sources.len(), 1);
+        assert_eq!(builder.transforms.len(), 2);
+
+        let (config, _) = builder.build_with_warnings().unwrap();
+        let sink = config
+            .sinks
+            .get(&ComponentKey::from("shared_console"))
+            .unwrap();
+        assert_eq!(sink.inputs.len(), 3);
+    }
+
+    #[test]
+    fn load_extend_missing_component() {
+        let builder = crate::config::ConfigBuilder::from_toml(
+            r#"
+            [sources.in]
+            type = "demo_logs"
+            format = "json"
+
+            [extend.sinks.out]
+            inputs = ["in"]
+            "#,
+        );
+        let errors = builder.build_with_warnings().unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|error| error == "extend.sinks.out refers to a sink that isn't defined"));
+    }
 }
@@ -4,6 +4,7 @@ use std::{
     hash::Hash,
     net::SocketAddr,
     path::PathBuf,
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -12,16 +13,17 @@ use indexmap::IndexMap; // IndexMap preserves insertion order, allowing us to ou
 use serde::{Deserialize, Serialize};
 use vector_core::buffers::{Acker, BufferConfig, BufferType};
 pub use vector_core::{
-    config::{AcknowledgementsConfig, DataType, GlobalOptions, Output},
-    transform::{ExpandType, TransformConfig, TransformContext},
+    config::{AcknowledgementsConfig, DataType, GlobalOptions, Output, SourceHeartbeatConfig},
+    transform::{ExpandType, Parallelism, TransformConfig, TransformContext},
 };
 
 use crate::{
     conditions,
-    event::Metric,
+    event::{watermark::EventTimeConfig, Metric},
     shutdown::ShutdownSignal,
     sinks::{self, util::UriSerde},
     sources,
+    topology::silence::SilenceConfig,
     transforms::noop::Noop,
     SourceSender,
 };
@@ -116,6 +118,9 @@ pub struct Config {
 pub struct HealthcheckOptions {
     pub enabled: bool,
     pub require_healthy: bool,
+    /// Whether a reload is rolled back when the healthchecks of new or changed sinks fail, which
+    /// `require_healthy` also does on top of failing startup.
+    pub require_healthy_on_reload: bool,
 }
 
 impl HealthcheckOptions {
@@ -128,6 +133,7 @@ impl HealthcheckOptions {
     fn merge(&mut self, other: Self) {
         self.enabled &= other.enabled;
         self.require_healthy |= other.require_healthy;
+        self.require_healthy_on_reload |= other.require_healthy_on_reload;
     }
 }
 
@@ -136,6 +142,7 @@ impl Default for HealthcheckOptions {
         Self {
             enabled: true,
             require_healthy: false,
+            require_healthy_on_reload: false,
         }
     }
 }
@@ -162,6 +169,18 @@ pub struct SourceOuter {
         skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
     )]
     pub proxy: ProxyConfig,
+    /// Which field of the source's events carries the event time used for watermarking.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_time: Option<EventTimeConfig>,
+    /// Whether the source's events have to keep their order through the topology.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    pub ordered: bool,
+    /// When the source is considered silent, for alerting on sources that stop sending events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub silence: Option<SilenceConfig>,
     #[serde(flatten)]
     pub(super) inner: Box<dyn SourceConfig>,
 }
@@ -171,6 +190,9 @@ impl SourceOuter {
         Self {
             inner: Box::new(source),
             proxy: Default::default(),
+            event_time: None,
+            ordered: false,
+            silence: None,
         }
     }
 }
@@ -255,6 +277,13 @@ pub struct SinkOuter<T> {
     )]
     proxy: ProxyConfig,
 
+    // Overrides the global option of the same name.
+    #[serde(
+        default,
+        skip_serializing_if = "vector_core::serde::skip_serializing_if_default"
+    )]
+    expire_metrics_secs: Option<u64>,
+
     #[serde(flatten)]
     pub inner: Box<dyn SinkConfig>,
 }
@@ -268,6 +297,7 @@ impl<T> SinkOuter<T> {
             healthcheck_uri: None,
             inner,
             proxy: Default::default(),
+            expire_metrics_secs: None,
         }
     }
 
@@ -306,6 +336,13 @@ impl<T> SinkOuter<T> {
         &self.proxy
     }
 
+    /// How long the sink keeps the state of metric series that aren't updated anymore.
+    pub fn expire_metrics(&self, globals: &GlobalOptions) -> Option<Duration> {
+        self.expire_metrics_secs
+            .or(globals.expire_metrics_secs)
+            .map(Duration::from_secs)
+    }
+
     fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> SinkOuter<U> {
         let inputs = self.inputs.iter().map(f).collect();
         self.with_inputs(inputs)
@@ -319,6 +356,7 @@ impl<T> SinkOuter<T> {
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            expire_metrics_secs: self.expire_metrics_secs,
         }
     }
 }
@@ -328,6 +366,11 @@ impl<T> SinkOuter<T> {
 pub struct SinkHealthcheckOptions {
     pub enabled: bool,
     pub uri: Option<UriSerde>,
+    /// How often to run the healthcheck again once the sink is running, for sinks that support it.
+    /// It's only run when the sink starts by default, and it can't be 0.
+    pub interval_secs: Option<u64>,
+    /// Whether a failing healthcheck reports the sink as degraded, rather than only logging.
+    pub required: bool,
 }
 
 impl Default for SinkHealthcheckOptions {
@@ -335,21 +378,26 @@ impl Default for SinkHealthcheckOptions {
         Self {
             enabled: true,
             uri: None,
+            interval_secs: None,
+            required: false,
         }
     }
 }
 
 impl From<bool> for SinkHealthcheckOptions {
     fn from(enabled: bool) -> Self {
-        Self { enabled, uri: None }
+        Self {
+            enabled,
+            ..Self::default()
+        }
     }
 }
 
 impl From<UriSerde> for SinkHealthcheckOptions {
     fn from(uri: UriSerde) -> Self {
         Self {
-            enabled: true,
             uri: Some(uri),
+            ..Self::default()
         }
     }
 }
@@ -366,6 +414,13 @@ pub trait SinkConfig: core::fmt::Debug + Send + Sync {
 
     fn sink_type(&self) -> &'static str;
 
+    /// A healthcheck that can be run again for as long as the sink runs, for
+    /// `healthcheck.interval_secs`. It's built once and called for every recheck, so it should
+    /// hold on to the clients it needs. Sinks without one only run their healthcheck on build.
+    fn recheck(&self, _cx: &SinkContext) -> crate::Result<Option<sinks::HealthcheckFn>> {
+        Ok(None)
+    }
+
     /// Resources that the sink is using.
     fn resources(&self) -> Vec<Resource> {
         Vec::new()
@@ -378,6 +433,7 @@ pub struct SinkContext {
     pub healthcheck: SinkHealthcheckOptions,
     pub globals: GlobalOptions,
     pub proxy: ProxyConfig,
+    pub expire_metrics: Option<Duration>,
 }
 
 impl SinkContext {
@@ -388,6 +444,7 @@ impl SinkContext {
             healthcheck: SinkHealthcheckOptions::default(),
             globals: GlobalOptions::default(),
             proxy: ProxyConfig::default(),
+            expire_metrics: None,
         }
     }
 
@@ -402,6 +459,10 @@ impl SinkContext {
     pub const fn proxy(&self) -> &ProxyConfig {
         &self.proxy
     }
+
+    pub const fn expire_metrics(&self) -> Option<Duration> {
+        self.expire_metrics
+    }
 }
 
 pub type SinkDescription = ComponentDescription<Box<dyn SinkConfig>>;
@@ -740,6 +801,38 @@ impl Config {
             .cloned()
             .unwrap_or_else(|| vec![identifier.clone()])
     }
+
+    /// Returns the transforms that the events of ordered sources go through.
+    pub fn ordered_transforms(&self) -> HashSet<ComponentKey> {
+        let mut ordered = self
+            .sources
+            .iter()
+            .filter(|(_, source)| source.ordered)
+            .map(|(key, _)| key.clone())
+            .collect::<HashSet<_>>();
+        let mut transforms = HashSet::new();
+
+        // Transforms are added until none of the remaining ones have an ordered input.
+        loop {
+            let downstream = self
+                .transforms
+                .iter()
+                .filter(|(key, transform)| {
+                    !transforms.contains(*key)
+                        && transform
+                            .inputs
+                            .iter()
+                            .any(|input| ordered.contains(&input.component))
+                })
+                .map(|(key, _)| key.clone())
+                .collect::<Vec<_>>();
+            if downstream.is_empty() {
+                return transforms;
+            }
+            ordered.extend(downstream.iter().cloned());
+            transforms.extend(downstream);
+        }
+    }
 }
 
 #[cfg(all(
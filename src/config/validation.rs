@@ -1,6 +1,12 @@
 use std::collections::HashMap;
 
-use super::{builder::ConfigBuilder, ComponentKey, Config, OutputId, Resource};
+use indexmap::IndexMap;
+
+use super::{
+    builder::ConfigBuilder, graph::Graph, ComponentKey, Config, OutputId, Resource, SinkConfig,
+    SinkOuter, SourceOuter,
+};
+use crate::sinks::util::Concurrency;
 
 /// Check that provide + topology config aren't present in the same builder, which is an error.
 pub fn check_provider(config: &ConfigBuilder) -> Result<(), Vec<String>> {
@@ -139,6 +145,71 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
     }
 }
 
+/// Check that the events of each ordered source reach sinks through a single path, as events
+/// taking different paths can't be kept in order relative to each other, and that the sink sends
+/// one request at a time, as concurrent requests can complete in any order.
+///
+/// Must only be called on graphs without cycles.
+pub fn check_ordering(
+    graph: &Graph,
+    sources: &IndexMap<ComponentKey, SourceOuter>,
+    sinks: &IndexMap<ComponentKey, SinkOuter<String>>,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (key, _) in sources.iter().filter(|(_, source)| source.ordered) {
+        let paths = graph.paths_to_sink_from(key);
+        if paths.len() > 1 {
+            let paths = paths
+                .iter()
+                .map(|path| {
+                    path.iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" -> ")
+                })
+                .collect::<Vec<_>>();
+            errors.push(format!(
+                "Source \"{}\" is ordered, but its events reach sinks through more than one path: {}",
+                key,
+                paths.join(", ")
+            ));
+            continue;
+        }
+
+        let sink_key = match paths.first().and_then(|path| path.last()) {
+            Some(sink_key) => sink_key,
+            None => continue,
+        };
+        let concurrency = sinks
+            .get(sink_key)
+            .and_then(|sink| request_concurrency(sink.inner.as_ref()));
+        if matches!(concurrency, Some(concurrency) if concurrency != Concurrency::Fixed(1)) {
+            errors.push(format!(
+                "Sink \"{}\" receives the events of ordered source \"{}\", but may send several requests at once. Set its `request.concurrency` to 1",
+                sink_key, key
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Returns the request concurrency of sinks that send requests. It's only known to the sink's own
+/// config, so it's read from the `request.concurrency` option the sink is serialized with.
+fn request_concurrency(sink: &dyn SinkConfig) -> Option<Concurrency> {
+    let config = serde_json::to_value(sink).ok()?;
+    let request = config.get("request")?;
+    match request.get("concurrency") {
+        Some(concurrency) => serde_json::from_value(concurrency.clone()).ok(),
+        None => Some(Concurrency::None),
+    }
+}
+
 pub fn warnings(config: &Config) -> Vec<String> {
     let mut warnings = vec![];
 
@@ -189,6 +260,15 @@ pub fn warnings(config: &Config) -> Vec<String> {
         }
     }
 
+    for key in config.ordered_transforms() {
+        if config.transforms[&key].inner.parallelism().is_some() {
+            warnings.push(format!(
+                "Transform \"{}\" ignores `parallelism`, as it transforms the events of an ordered source",
+                key
+            ));
+        }
+    }
+
     warnings
 }
 
@@ -2,17 +2,23 @@ use std::{
     collections::{BTreeMap, HashMap},
     fs,
     hash::Hasher,
-    path::PathBuf,
-    time::SystemTime,
+    io,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
 };
 
 use bytes::Bytes;
-use enrichment::{Case, Condition, IndexHandle, Table};
+use chrono::{DateTime, Utc};
+use cidr_utils::cidr::IpCidr;
+use enrichment::{ip_cidr_lookup::parse_cidr, Case, Condition, IndexHandle, Table};
 use serde::{Deserialize, Serialize};
 use shared::{conversion::Conversion, datetime::TimeZone};
 use tracing::trace;
 use vrl::Value;
 
+use super::remote::{Client, RemoteFile, S3Source};
 use crate::config::{EnrichmentTableConfig, EnrichmentTableDescription};
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
@@ -35,13 +41,36 @@ impl Default for Encoding {
     }
 }
 
-#[derive(Deserialize, Serialize, Default, Debug, Eq, PartialEq, Clone)]
+/// Where the data of the table is loaded from.
+#[derive(Deserialize, Serialize, Debug, Clone, Derivative)]
+#[derivative(Default)]
+#[serde(untagged)]
+enum Source {
+    #[derivative(Default)]
+    Path {
+        path: PathBuf,
+    },
+    Url {
+        url: String,
+    },
+    S3 {
+        s3: S3Source,
+    },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Derivative)]
+#[derivative(Default)]
 struct FileC {
-    path: PathBuf,
+    #[serde(flatten)]
+    source: Source,
     encoding: Encoding,
+    /// How often the data is fetched again when it is loaded from a URL or S3.
+    #[serde(default = "default_refresh_interval_secs")]
+    #[derivative(Default(value = "default_refresh_interval_secs()"))]
+    refresh_interval_secs: u64,
 }
 
-#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct FileConfig {
     file: FileC,
     #[serde(default)]
@@ -52,6 +81,10 @@ const fn default_delimiter() -> char {
     ','
 }
 
+const fn default_refresh_interval_secs() -> u64 {
+    300
+}
+
 impl FileConfig {
     fn parse_column(
         &self,
@@ -112,8 +145,34 @@ impl FileConfig {
 
     fn load_file(
         &self,
+        path: &Path,
         timezone: TimeZone,
     ) -> crate::Result<(Vec<String>, Vec<Vec<Value>>, SystemTime)> {
+        let (headers, data) = self.parse(fs::File::open(path)?, timezone)?;
+
+        trace!(
+            "Loaded enrichment file {} with headers {:?}.",
+            path.to_str().unwrap_or("path with invalid utf"),
+            headers
+        );
+
+        let modified = fs::metadata(path)?.modified()?;
+
+        Ok((headers, data, modified))
+    }
+
+    /// Parses the data of a table that has been fetched from a URL or S3.
+    pub(super) fn load_data(&self, data: &[u8], timezone: TimeZone) -> crate::Result<File> {
+        let (headers, data) = self.parse(data, timezone)?;
+
+        Ok(File::new(self.clone(), SystemTime::now(), data, headers))
+    }
+
+    fn parse(
+        &self,
+        data: impl io::Read,
+        timezone: TimeZone,
+    ) -> crate::Result<(Vec<String>, Vec<Vec<Value>>)> {
         let Encoding::Csv {
             include_headers,
             delimiter,
@@ -122,7 +181,7 @@ impl FileConfig {
         let mut reader = csv::ReaderBuilder::new()
             .has_headers(include_headers)
             .delimiter(delimiter as u8)
-            .from_path(&self.file.path)?;
+            .from_reader(data);
 
         let headers = if include_headers {
             reader
@@ -150,15 +209,7 @@ impl FileConfig {
             })
             .collect::<crate::Result<Vec<_>>>()?;
 
-        trace!(
-            "Loaded enrichment file {} with headers {:?}.",
-            self.file.path.to_str().unwrap_or("path with invalid utf"),
-            headers
-        );
-
-        let modified = fs::metadata(&self.file.path)?.modified()?;
-
-        Ok((headers, data, modified))
+        Ok((headers, data))
     }
 }
 
@@ -169,9 +220,28 @@ impl EnrichmentTableConfig for FileConfig {
         &self,
         globals: &crate::config::GlobalOptions,
     ) -> crate::Result<Box<dyn Table + Send + Sync>> {
-        let (headers, data, modified) = self.load_file(globals.timezone)?;
+        let client = match &self.file.source {
+            Source::Path { path } => {
+                let (headers, data, modified) = self.load_file(path, globals.timezone)?;
+
+                return Ok(Box::new(File::new(self.clone(), modified, data, headers)));
+            }
+            Source::Url { .. } | Source::S3 { .. } if self.file.refresh_interval_secs == 0 => {
+                return Err("`refresh_interval_secs` must be greater than 0".into());
+            }
+            Source::Url { url } => Client::http(url, &globals.proxy)?,
+            Source::S3 { s3 } => Client::s3(s3, &globals.proxy)?,
+        };
 
-        Ok(Box::new(File::new(self.clone(), modified, data, headers)))
+        Ok(Box::new(
+            RemoteFile::load(
+                self.clone(),
+                client,
+                Duration::from_secs(self.file.refresh_interval_secs),
+                globals.timezone,
+            )
+            .await?,
+        ))
     }
 }
 
@@ -181,17 +251,111 @@ inventory::submit! {
 
 impl_generate_config_from_default!(FileConfig);
 
+/// An index of a column that is searched with range conditions. The rows are indexed by each of
+/// the types of value a range condition can match, so that the rows matching a condition can be
+/// found without scanning the table.
+#[derive(Clone, Default)]
+struct RangeIndex {
+    /// The rows with a number in the column, sorted by the number.
+    numbers: Vec<(f64, usize)>,
+    /// The rows with a date in the column, sorted by the date.
+    dates: Vec<(DateTime<Utc>, usize)>,
+    /// The rows with a CIDR range in the column, by the IP version and prefix length of the range
+    /// and then by its network address.
+    networks: BTreeMap<(u8, u8), HashMap<u128, Vec<usize>>>,
+}
+
+impl RangeIndex {
+    fn new(data: &[Vec<Value>], column: usize) -> Self {
+        let mut index = Self::default();
+
+        for (idx, row) in data.iter().enumerate() {
+            match &row[column] {
+                Value::Integer(number) => index.numbers.push((*number as f64, idx)),
+                Value::Float(number) => index.numbers.push((number.into_inner(), idx)),
+                Value::Timestamp(date) => index.dates.push((*date, idx)),
+                value => {
+                    let network = match parse_cidr(value) {
+                        Some(IpCidr::V4(cidr)) => {
+                            (4, cidr.get_bits(), u128::from(cidr.get_prefix()))
+                        }
+                        Some(IpCidr::V6(cidr)) => (6, cidr.get_bits(), cidr.get_prefix()),
+                        None => continue,
+                    };
+                    let (version, bits, address) = network;
+                    index
+                        .networks
+                        .entry((version, bits))
+                        .or_default()
+                        .entry(address)
+                        .or_default()
+                        .push(idx);
+                }
+            }
+        }
+
+        index
+            .numbers
+            .sort_by(|a, b| a.partial_cmp(b).expect("numbers are never NaN"));
+        index.dates.sort();
+
+        index
+    }
+
+    fn numbers(&self, from: f64, to: f64) -> Vec<usize> {
+        let start = self.numbers.partition_point(|(number, _)| *number < from);
+        let end = self.numbers.partition_point(|(number, _)| *number <= to);
+        sorted_rows(self.numbers.get(start..end).unwrap_or_default())
+    }
+
+    fn dates(&self, from: &DateTime<Utc>, to: &DateTime<Utc>) -> Vec<usize> {
+        let start = self.dates.partition_point(|(date, _)| date < from);
+        let end = self.dates.partition_point(|(date, _)| date <= to);
+        sorted_rows(self.dates.get(start..end).unwrap_or_default())
+    }
+
+    /// Finds the ranges containing the address by looking up its network for each prefix length
+    /// in the column.
+    fn containing(&self, address: IpAddr) -> Vec<usize> {
+        let (version, width, address) = match address {
+            IpAddr::V4(address) => (4, 32, u128::from(u32::from(address))),
+            IpAddr::V6(address) => (6, 128, u128::from(address)),
+        };
+
+        let mut rows = self
+            .networks
+            .range((version, 0)..=(version, u8::MAX))
+            .filter_map(|((_, bits), networks)| {
+                let mask = u128::MAX.checked_shl(width - u32::from(*bits)).unwrap_or(0);
+                networks.get(&(address & mask))
+            })
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        rows.sort_unstable();
+        rows
+    }
+}
+
+/// Returns the rows in the order they are in the table.
+fn sorted_rows<T>(rows: &[(T, usize)]) -> Vec<usize> {
+    let mut rows = rows.iter().map(|(_, idx)| *idx).collect::<Vec<_>>();
+    rows.sort_unstable();
+    rows
+}
+
 #[derive(Clone)]
 pub struct File {
     config: FileConfig,
     last_modified: SystemTime,
-    data: Vec<Vec<Value>>,
+    data: Arc<Vec<Vec<Value>>>,
     headers: Vec<String>,
     indexes: Vec<(
         Case,
         Vec<usize>,
         HashMap<u64, Vec<usize>, hash_hasher::HashBuildHasher>,
     )>,
+    range_indexes: Vec<(usize, RangeIndex)>,
 }
 
 impl File {
@@ -204,9 +368,10 @@ impl File {
         Self {
             config,
             last_modified,
-            data,
+            data: Arc::new(data),
             headers,
             indexes: Vec::new(),
+            range_indexes: Vec::new(),
         }
     }
 
@@ -237,6 +402,18 @@ impl File {
                     _ => false,
                 },
             },
+            Condition::BetweenNumbers { field, from, to } => match self.column_index(field) {
+                None => false,
+                Some(idx) => match row[idx] {
+                    Value::Integer(number) => *from <= number as f64 && number as f64 <= *to,
+                    Value::Float(number) => *from <= *number && *number <= *to,
+                    _ => false,
+                },
+            },
+            Condition::ContainsIp { field, value } => match self.column_index(field) {
+                None => false,
+                Some(idx) => parse_cidr(&row[idx]).map_or(false, |cidr| cidr.contains(*value)),
+            },
         })
     }
 
@@ -365,6 +542,35 @@ impl File {
         let IndexHandle(handle) = handle;
         Ok(self.indexes[handle].2.get(&key))
     }
+
+    /// Searches the range indexes for the rows matching the range conditions, returning those of
+    /// the condition that matches the fewest rows, or `None` if none of the conditions are on a
+    /// field with a range index.
+    fn range_indexed(&self, condition: &[Condition]) -> Option<Vec<usize>> {
+        condition
+            .iter()
+            .filter_map(|condition| match condition {
+                Condition::Equals { .. } => None,
+                Condition::BetweenDates { field, from, to } => {
+                    self.range_index(field).map(|index| index.dates(from, to))
+                }
+                Condition::BetweenNumbers { field, from, to } => self
+                    .range_index(field)
+                    .map(|index| index.numbers(*from, *to)),
+                Condition::ContainsIp { field, value } => self
+                    .range_index(field)
+                    .map(|index| index.containing(*value)),
+            })
+            .min_by_key(Vec::len)
+    }
+
+    fn range_index(&self, field: &str) -> Option<&RangeIndex> {
+        let column = self.column_index(field)?;
+        self.range_indexes
+            .iter()
+            .find(|(indexed, _)| *indexed == column)
+            .map(|(_, index)| index)
+    }
 }
 
 /// Adds the bytes from the given value to the hash.
@@ -414,17 +620,30 @@ impl Table for File {
         select: Option<&'a [String]>,
         index: Option<IndexHandle>,
     ) -> Result<BTreeMap<String, Value>, String> {
+        let ranged = self.range_indexed(condition);
+
         match index {
-            None => {
-                // No index has been passed so we need to do a Sequential Scan.
-                single_or_err(self.sequential(self.data.iter(), case, condition, select))
-            }
+            None => match ranged {
+                // No index has been passed so we need to do a Sequential Scan, of the rows
+                // matching the range conditions if they are indexed.
+                Some(rows) => single_or_err(self.sequential(
+                    rows.iter().map(|idx| &self.data[*idx]),
+                    case,
+                    condition,
+                    select,
+                )),
+                None => single_or_err(self.sequential(self.data.iter(), case, condition, select)),
+            },
             Some(handle) => {
-                let result = self
+                let indexed = self
                     .indexed(case, condition, handle)?
-                    .ok_or_else(|| "no rows found in index".to_string())?
-                    .iter()
-                    .map(|idx| &self.data[*idx]);
+                    .ok_or_else(|| "no rows found in index".to_string())?;
+                let result = match &ranged {
+                    Some(rows) if rows.len() < indexed.len() => rows,
+                    _ => indexed,
+                }
+                .iter()
+                .map(|idx| &self.data[*idx]);
 
                 // Perform a sequential scan over the indexed result.
                 single_or_err(self.sequential(result, case, condition, select))
@@ -439,26 +658,43 @@ impl Table for File {
         select: Option<&'a [String]>,
         index: Option<IndexHandle>,
     ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        let ranged = self.range_indexed(condition);
+
         match index {
-            None => {
-                // No index has been passed so we need to do a Sequential Scan.
-                Ok(self
-                    .sequential(self.data.iter(), case, condition, select)
-                    .collect())
-            }
-            Some(handle) => {
-                // Perform a sequential scan over the indexed result.
-                Ok(self
+            None => match ranged {
+                // No index has been passed so we need to do a Sequential Scan, of the rows
+                // matching the range conditions if they are indexed.
+                Some(rows) => Ok(self
                     .sequential(
-                        self.indexed(case, condition, handle)?
-                            .iter()
-                            .flat_map(|results| results.iter().map(|idx| &self.data[*idx])),
+                        rows.iter().map(|idx| &self.data[*idx]),
                         case,
                         condition,
                         select,
                     )
-                    .collect())
-            }
+                    .collect()),
+                None => Ok(self
+                    .sequential(self.data.iter(), case, condition, select)
+                    .collect()),
+            },
+            Some(handle) => match self.indexed(case, condition, handle)? {
+                None => Ok(Vec::new()),
+                Some(indexed) => {
+                    let rows = match &ranged {
+                        Some(rows) if rows.len() < indexed.len() => rows,
+                        _ => indexed,
+                    };
+
+                    // Perform a sequential scan over the indexed result.
+                    Ok(self
+                        .sequential(
+                            rows.iter().map(|idx| &self.data[*idx]),
+                            case,
+                            condition,
+                            select,
+                        )
+                        .collect())
+                }
+            },
         }
     }
 
@@ -499,11 +735,40 @@ impl Table for File {
             .collect::<Vec<_>>()
     }
 
-    /// Checks the modified timestamp of the data file to see if data has changed.
+    fn add_range_index(&mut self, field: &str) -> Result<(), String> {
+        let column = self
+            .column_index(field)
+            .ok_or_else(|| format!("field(s) '{}' missing from dataset", field))?;
+
+        if !self
+            .range_indexes
+            .iter()
+            .any(|(indexed, _)| *indexed == column)
+        {
+            let index = RangeIndex::new(&self.data, column);
+            self.range_indexes.push((column, index));
+        }
+
+        Ok(())
+    }
+
+    /// Returns a list of the field names that have range indexes
+    fn range_index_fields(&self) -> Vec<String> {
+        self.range_indexes
+            .iter()
+            .map(|(column, _)| self.headers[*column].clone())
+            .collect()
+    }
+
+    /// Checks the modified timestamp of the data file to see if data has changed. Tables loaded
+    /// from a URL or S3 are refreshed by `RemoteFile` instead.
     fn needs_reload(&self) -> bool {
-        matches!(fs::metadata(&self.config.file.path)
-            .and_then(|metadata| metadata.modified()),
-            Ok(modified) if modified > self.last_modified)
+        match &self.config.file.source {
+            Source::Path { path } => matches!(fs::metadata(path)
+                .and_then(|metadata| metadata.modified()),
+                Ok(modified) if modified > self.last_modified),
+            Source::Url { .. } | Source::S3 { .. } => false,
+        }
     }
 }
 
@@ -590,6 +855,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_source() {
+        let config: FileConfig = toml::from_str(
+            r#"
+            file.path = "/data/info.csv"
+            file.encoding.type = "csv"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(config.file.source, Source::Path { .. }));
+
+        let config: FileConfig = toml::from_str(
+            r#"
+            file.url = "https://example.com/info.csv"
+            file.encoding.type = "csv"
+            file.refresh_interval_secs = 60
+            "#,
+        )
+        .unwrap();
+        assert!(
+            matches!(config.file.source, Source::Url { ref url } if url == "https://example.com/info.csv")
+        );
+        assert_eq!(60, config.file.refresh_interval_secs);
+
+        let config: FileConfig = toml::from_str(
+            r#"
+            file.s3.bucket = "my-bucket"
+            file.s3.key = "tables/info.csv"
+            file.s3.region = "us-east-1"
+            file.encoding.type = "csv"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(config.file.source, Source::S3 { .. }));
+        assert_eq!(300, config.file.refresh_interval_secs);
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_refresh_interval() {
+        let config: FileConfig = toml::from_str(
+            r#"
+            file.url = "https://example.com/info.csv"
+            file.encoding.type = "csv"
+            file.refresh_interval_secs = 0
+            "#,
+        )
+        .unwrap();
+        let error = config
+            .build(&crate::config::GlobalOptions::default())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "`refresh_interval_secs` must be greater than 0"
+        );
+    }
+
     #[test]
     fn seahash() {
         // Ensure we can separate fields to create a distinct hash.
@@ -896,6 +1219,154 @@ mod tests {
         );
     }
 
+    #[test]
+    fn finds_rows_containing_ip() {
+        let file = File::new(
+            Default::default(),
+            SystemTime::now(),
+            vec![
+                vec!["10.0.0.0/8".into(), "internal".into()],
+                vec!["10.1.0.0/16".into(), "office".into()],
+                vec!["192.168.0.0/16".into(), "home".into()],
+            ],
+            vec!["cidr".to_string(), "name".to_string()],
+        );
+
+        let condition = Condition::ContainsIp {
+            field: "cidr",
+            value: "10.1.2.3".parse().unwrap(),
+        };
+
+        assert_eq!(
+            Ok(vec![
+                btreemap! {
+                    "cidr" => "10.0.0.0/8",
+                    "name" => "internal",
+                },
+                btreemap! {
+                    "cidr" => "10.1.0.0/16",
+                    "name" => "office",
+                },
+            ]),
+            file.find_table_rows(Case::Sensitive, &[condition], None, None)
+        );
+    }
+
+    #[test]
+    fn finds_rows_with_range_index() {
+        let mut file = File::new(
+            Default::default(),
+            SystemTime::now(),
+            vec![
+                vec!["10.0.0.0/8".into(), 1.into(), "internal".into()],
+                vec!["10.1.0.0/16".into(), 2.into(), "office".into()],
+                vec!["10.1.2.0/24".into(), 3.into(), "printers".into()],
+                vec!["2001:db8::/32".into(), 4.into(), "documentation".into()],
+            ],
+            vec!["cidr".to_string(), "tier".to_string(), "name".to_string()],
+        );
+
+        file.add_range_index("cidr").unwrap();
+        file.add_range_index("tier").unwrap();
+        assert_eq!(
+            vec!["cidr".to_string(), "tier".to_string()],
+            file.range_index_fields()
+        );
+
+        assert_eq!(
+            Ok(vec![
+                btreemap! { "cidr" => "10.0.0.0/8", "tier" => 1, "name" => "internal" },
+                btreemap! { "cidr" => "10.1.0.0/16", "tier" => 2, "name" => "office" },
+                btreemap! { "cidr" => "10.1.2.0/24", "tier" => 3, "name" => "printers" },
+            ]),
+            file.find_table_rows(
+                Case::Sensitive,
+                &[Condition::ContainsIp {
+                    field: "cidr",
+                    value: "10.1.2.3".parse().unwrap(),
+                }],
+                None,
+                None
+            )
+        );
+
+        assert_eq!(
+            Ok(btreemap! { "cidr" => "2001:db8::/32", "tier" => 4, "name" => "documentation" }),
+            file.find_table_row(
+                Case::Sensitive,
+                &[Condition::ContainsIp {
+                    field: "cidr",
+                    value: "2001:db8::1".parse().unwrap(),
+                }],
+                None,
+                None
+            )
+        );
+
+        assert_eq!(
+            Ok(btreemap! { "cidr" => "10.1.0.0/16", "tier" => 2, "name" => "office" }),
+            file.find_table_row(
+                Case::Sensitive,
+                &[
+                    Condition::ContainsIp {
+                        field: "cidr",
+                        value: "10.1.2.3".parse().unwrap(),
+                    },
+                    Condition::BetweenNumbers {
+                        field: "tier",
+                        from: 1.5,
+                        to: 2.5,
+                    },
+                ],
+                None,
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn finds_rows_between_numbers() {
+        let file = File::new(
+            Default::default(),
+            SystemTime::now(),
+            vec![
+                vec![0.into(), "free".into()],
+                vec![100.into(), "pro".into()],
+                vec![1000.into(), "enterprise".into()],
+            ],
+            vec!["seats".to_string(), "tier".to_string()],
+        );
+
+        assert_eq!(
+            Ok(btreemap! { "seats" => 100, "tier" => "pro" }),
+            file.find_table_row(
+                Case::Sensitive,
+                &[Condition::BetweenNumbers {
+                    field: "seats",
+                    from: 50.0,
+                    to: 500.0,
+                }],
+                None,
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn range_index_missing_field() {
+        let mut file = File::new(
+            Default::default(),
+            SystemTime::now(),
+            Vec::new(),
+            vec!["field1".to_string()],
+        );
+
+        assert_eq!(
+            Err("field(s) 'apples' missing from dataset".to_string()),
+            file.add_range_index("apples")
+        );
+    }
+
     #[test]
     fn doesnt_find_row() {
         let file = File::new(
@@ -2,3 +2,5 @@ pub use enrichment::{Condition, IndexHandle, Table};
 
 #[cfg(feature = "enrichment-tables-file")]
 pub mod file;
+#[cfg(feature = "enrichment-tables-file")]
+mod remote;
@@ -0,0 +1,552 @@
+//! Enrichment tables whose data is fetched from a URL or an S3 object.
+//!
+//! The data is fetched once when the table is built, and then again on an interval by a task that
+//! runs for as long as the table is loaded. Each request is conditional on the validators of the
+//! last response, so data that hasn't changed isn't downloaded and parsed again. If a refresh
+//! fails, the table keeps the data it last loaded.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+
+use arc_swap::ArcSwap;
+use bytes::Bytes;
+use enrichment::{Case, Condition, IndexHandle, Table};
+use http::{
+    header::{HeaderName, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    HeaderValue, Request, StatusCode,
+};
+use hyper::{body::to_bytes as body_to_bytes, Body};
+use rusoto_core::{Region, RusotoError};
+use rusoto_s3::{GetObjectRequest, S3Client, S3};
+use serde::{Deserialize, Serialize};
+use shared::datetime::TimeZone;
+use tokio::io::AsyncReadExt;
+use vrl::Value;
+
+use super::file::{File, FileConfig};
+use crate::{
+    aws::{rusoto, AwsAuthentication, RegionOrEndpoint},
+    config::ProxyConfig,
+    http::HttpClient,
+    internal_events::{EnrichmentTableRefreshFailed, EnrichmentTableRefreshed},
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct S3Source {
+    bucket: String,
+    key: String,
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+    #[serde(default)]
+    auth: AwsAuthentication,
+}
+
+/// The validators of the last response, sent with the next request so that the data is only
+/// returned if it has changed.
+#[derive(Clone, Debug, Default)]
+struct Validators {
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+enum Fetched {
+    Modified(Bytes, Validators),
+    NotModified,
+}
+
+pub enum Client {
+    Http {
+        client: HttpClient,
+        url: String,
+    },
+    S3 {
+        client: S3Client,
+        bucket: String,
+        key: String,
+    },
+}
+
+impl Client {
+    pub fn http(url: &str, proxy: &ProxyConfig) -> crate::Result<Self> {
+        Ok(Self::Http {
+            client: HttpClient::new(None, proxy)?,
+            url: url.to_string(),
+        })
+    }
+
+    pub fn s3(source: &S3Source, proxy: &ProxyConfig) -> crate::Result<Self> {
+        let region: Region = (&source.region).try_into()?;
+        let client = rusoto::client(proxy)?;
+        let creds = source.auth.build(&region, None)?;
+
+        Ok(Self::S3 {
+            client: S3Client::new_with(client, creds, region),
+            bucket: source.bucket.clone(),
+            key: source.key.clone(),
+        })
+    }
+
+    async fn fetch(&self, validators: &Validators) -> crate::Result<Fetched> {
+        match self {
+            Self::Http { client, url } => {
+                let mut request = Request::get(url.as_str()).body(Body::empty())?;
+                let headers = request.headers_mut();
+                if let Some(etag) = &validators.etag {
+                    headers.insert(IF_NONE_MATCH, HeaderValue::from_str(etag)?);
+                }
+                if let Some(last_modified) = &validators.last_modified {
+                    headers.insert(IF_MODIFIED_SINCE, HeaderValue::from_str(last_modified)?);
+                }
+
+                let response = client.send(request).await?;
+                match response.status() {
+                    StatusCode::NOT_MODIFIED => Ok(Fetched::NotModified),
+                    status if status.is_success() => {
+                        let header = |name: HeaderName| {
+                            response
+                                .headers()
+                                .get(name)
+                                .and_then(|value| value.to_str().ok())
+                                .map(str::to_string)
+                        };
+                        let validators = Validators {
+                            etag: header(ETAG),
+                            last_modified: header(LAST_MODIFIED),
+                        };
+                        let data = body_to_bytes(response.into_body()).await?;
+
+                        Ok(Fetched::Modified(data, validators))
+                    }
+                    status => Err(format!("unexpected status {}", status).into()),
+                }
+            }
+            Self::S3 {
+                client,
+                bucket,
+                key,
+            } => {
+                let object = client
+                    .get_object(GetObjectRequest {
+                        bucket: bucket.clone(),
+                        key: key.clone(),
+                        if_none_match: validators.etag.clone(),
+                        if_modified_since: validators.last_modified.clone(),
+                        ..Default::default()
+                    })
+                    .await;
+
+                match object {
+                    Ok(object) => {
+                        let mut data = Vec::new();
+                        if let Some(body) = object.body {
+                            body.into_async_read().read_to_end(&mut data).await?;
+                        }
+                        let validators = Validators {
+                            etag: object.e_tag,
+                            last_modified: object.last_modified,
+                        };
+
+                        Ok(Fetched::Modified(data.into(), validators))
+                    }
+                    // S3 doesn't model a 304 as an error of `GetObject`.
+                    Err(RusotoError::Unknown(response))
+                        if response.status == StatusCode::NOT_MODIFIED =>
+                    {
+                        Ok(Fetched::NotModified)
+                    }
+                    Err(error) => Err(error.into()),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http { url, .. } => write!(f, "{}", url),
+            Self::S3 { bucket, key, .. } => write!(f, "s3://{}/{}", bucket, key),
+        }
+    }
+}
+
+struct Shared {
+    current: ArcSwap<File>,
+    /// Held whilst the data is being replaced, so that an index can't be added to data that is
+    /// about to be replaced.
+    writer: Mutex<()>,
+}
+
+impl Shared {
+    /// Replaces the data with a newly fetched copy, once the indexes of the current data have
+    /// been added to it. Adding the indexes in the same order means that the handles already given
+    /// out still refer to the same index.
+    fn replace(&self, mut file: File) -> Result<(), String> {
+        let _writer = self.writer.lock().expect("mutex poisoned");
+        let current = self.current.load();
+
+        for (case, fields) in current.index_fields() {
+            file.add_index(
+                case,
+                &fields
+                    .iter()
+                    .map(|field| field.as_ref())
+                    .collect::<Vec<_>>(),
+            )?;
+        }
+        for field in current.range_index_fields() {
+            file.add_range_index(&field)?;
+        }
+
+        self.current.store(Arc::new(file));
+        Ok(())
+    }
+
+    fn update<T>(&self, f: impl FnOnce(&mut File) -> Result<T, String>) -> Result<T, String> {
+        let _writer = self.writer.lock().expect("mutex poisoned");
+        let mut file = File::clone(&self.current.load());
+        let result = f(&mut file)?;
+        self.current.store(Arc::new(file));
+        Ok(result)
+    }
+}
+
+/// A file table that is fetched from a URL or S3 and refreshed in the background.
+#[derive(Clone)]
+pub struct RemoteFile {
+    shared: Arc<Shared>,
+}
+
+impl RemoteFile {
+    /// Fetches the data and starts refreshing it. The initial fetch has to succeed, as there is
+    /// no previous copy of the data to fall back to.
+    pub async fn load(
+        config: FileConfig,
+        client: Client,
+        refresh_interval: Duration,
+        timezone: TimeZone,
+    ) -> crate::Result<Self> {
+        let (data, validators) = match client.fetch(&Validators::default()).await? {
+            Fetched::Modified(data, validators) => (data, validators),
+            Fetched::NotModified => return Err("unexpected not modified response".into()),
+        };
+        let file = config.load_data(&data, timezone)?;
+
+        let shared = Arc::new(Shared {
+            current: ArcSwap::from_pointee(file),
+            writer: Mutex::new(()),
+        });
+
+        tokio::spawn(refresh(
+            Arc::downgrade(&shared),
+            config,
+            client,
+            validators,
+            refresh_interval,
+            timezone,
+        ));
+
+        Ok(Self { shared })
+    }
+}
+
+/// Fetches the data on the interval until the table is dropped.
+async fn refresh(
+    shared: Weak<Shared>,
+    config: FileConfig,
+    client: Client,
+    mut validators: Validators,
+    refresh_interval: Duration,
+    timezone: TimeZone,
+) {
+    let source = client.to_string();
+    let mut interval = tokio::time::interval(refresh_interval);
+    // The first tick completes immediately, and the data has only just been fetched.
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        if shared.strong_count() == 0 {
+            break;
+        }
+
+        let (data, new_validators) = match client.fetch(&validators).await {
+            Ok(Fetched::Modified(data, validators)) => (data, validators),
+            Ok(Fetched::NotModified) => {
+                debug!(message = "Enrichment table has not changed.", %source);
+                continue;
+            }
+            Err(error) => {
+                emit!(&EnrichmentTableRefreshFailed {
+                    source: &source,
+                    error,
+                });
+                continue;
+            }
+        };
+
+        let shared = match shared.upgrade() {
+            Some(shared) => shared,
+            None => break,
+        };
+
+        let replaced = config
+            .load_data(&data, timezone)
+            .and_then(|file| Ok(shared.replace(file)?));
+        match replaced {
+            Ok(()) => {
+                validators = new_validators;
+                emit!(&EnrichmentTableRefreshed { source: &source });
+            }
+            Err(error) => emit!(&EnrichmentTableRefreshFailed {
+                source: &source,
+                error,
+            }),
+        }
+    }
+}
+
+impl Table for RemoteFile {
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&'a [String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        self.shared
+            .current
+            .load()
+            .find_table_row(case, condition, select, index)
+    }
+
+    fn find_table_rows<'a>(
+        &self,
+        case: Case,
+        condition: &'a [Condition<'a>],
+        select: Option<&'a [String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        self.shared
+            .current
+            .load()
+            .find_table_rows(case, condition, select, index)
+    }
+
+    fn add_index(&mut self, case: Case, fields: &[&str]) -> Result<IndexHandle, String> {
+        self.shared.update(|file| file.add_index(case, fields))
+    }
+
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        self.shared.current.load().index_fields()
+    }
+
+    fn add_range_index(&mut self, field: &str) -> Result<(), String> {
+        self.shared.update(|file| file.add_range_index(field))
+    }
+
+    fn range_index_fields(&self) -> Vec<String> {
+        self.shared.current.load().range_index_fields()
+    }
+
+    /// The data is refreshed in the background, so the table never needs to be reloaded.
+    fn needs_reload(&self) -> bool {
+        false
+    }
+}
+
+impl fmt::Debug for RemoteFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Remote{:?}", self.shared.current.load())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::Infallible,
+        net::SocketAddr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Response, Server,
+    };
+    use shared::btreemap;
+
+    use super::*;
+    use crate::test_util::next_addr;
+
+    #[derive(Default)]
+    struct Served {
+        requests: AtomicUsize,
+        not_modified: AtomicUsize,
+    }
+
+    /// Serves each of the responses in turn, repeating the last one. A response is tagged with its
+    /// position, and requests with that tag are replied to with a 304.
+    fn serve(responses: Vec<Result<&'static str, u16>>) -> (SocketAddr, Arc<Served>) {
+        let addr = next_addr();
+        let served = Arc::new(Served::default());
+        let responses = Arc::new(responses);
+
+        let make_svc = {
+            let served = Arc::clone(&served);
+            make_service_fn(move |_| {
+                let served = Arc::clone(&served);
+                let responses = Arc::clone(&responses);
+                async move {
+                    Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                        let position = served
+                            .requests
+                            .fetch_add(1, Ordering::SeqCst)
+                            .min(responses.len() - 1);
+                        let tag = format!("\"{}\"", position);
+                        let response = match responses[position] {
+                            Ok(_)
+                                if request.headers().get(IF_NONE_MATCH)
+                                    == Some(&tag.parse().unwrap()) =>
+                            {
+                                served.not_modified.fetch_add(1, Ordering::SeqCst);
+                                Response::builder().status(304).body(Body::empty())
+                            }
+                            Ok(data) => {
+                                Response::builder().header(ETAG, tag).body(Body::from(data))
+                            }
+                            Err(status) => Response::builder().status(status).body(Body::empty()),
+                        };
+                        async move { Ok::<_, Infallible>(response.unwrap()) }
+                    }))
+                }
+            })
+        };
+        tokio::spawn(Server::bind(&addr).serve(make_svc));
+
+        (addr, served)
+    }
+
+    async fn load(addr: SocketAddr) -> crate::Result<RemoteFile> {
+        let url = format!("http://{}/data.csv", addr);
+        let config: FileConfig = toml::from_str(&format!(
+            r#"
+            file.url = "{}"
+            file.encoding.type = "csv"
+            "#,
+            url
+        ))
+        .unwrap();
+
+        RemoteFile::load(
+            config,
+            Client::http(&url, &Default::default())?,
+            Duration::from_millis(100),
+            Default::default(),
+        )
+        .await
+    }
+
+    fn find(
+        table: &RemoteFile,
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        let condition = Condition::Equals {
+            field: "field1",
+            value: "zup".into(),
+        };
+        table.find_table_row(Case::Sensitive, &[condition], None, index)
+    }
+
+    async fn wait_for(served: &Served, requests: usize) {
+        while served.requests.load(Ordering::SeqCst) < requests {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        // Give the table time to load the response.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    #[tokio::test]
+    async fn refreshes_data() {
+        let (addr, served) = serve(vec![
+            Ok("field1,field2\nzup,mop\n"),
+            Ok("field1,field2\nzup,pop\n"),
+        ]);
+        let mut table = load(addr).await.unwrap();
+        let handle = table.add_index(Case::Sensitive, &["field1"]).unwrap();
+
+        assert_eq!(
+            Ok(btreemap! { "field1" => "zup", "field2" => "mop" }),
+            find(&table, Some(handle))
+        );
+
+        wait_for(&served, 2).await;
+
+        // The refreshed data has been indexed the same way.
+        assert_eq!(
+            vec![(Case::Sensitive, vec!["field1".to_string()])],
+            table.index_fields()
+        );
+        assert_eq!(
+            Ok(btreemap! { "field1" => "zup", "field2" => "pop" }),
+            find(&table, Some(handle))
+        );
+    }
+
+    #[tokio::test]
+    async fn sends_etag() {
+        let (addr, served) = serve(vec![Ok("field1,field2\nzup,mop\n")]);
+        let table = load(addr).await.unwrap();
+
+        wait_for(&served, 3).await;
+
+        assert_eq!(2, served.not_modified.load(Ordering::SeqCst));
+        assert_eq!(
+            Ok(btreemap! { "field1" => "zup", "field2" => "mop" }),
+            find(&table, None)
+        );
+    }
+
+    #[tokio::test]
+    async fn keeps_data_on_failure() {
+        let (addr, served) = serve(vec![Ok("field1,field2\nzup,mop\n"), Err(500)]);
+        let table = load(addr).await.unwrap();
+
+        wait_for(&served, 2).await;
+
+        assert_eq!(
+            Ok(btreemap! { "field1" => "zup", "field2" => "mop" }),
+            find(&table, None)
+        );
+    }
+
+    #[tokio::test]
+    async fn keeps_data_missing_indexed_field() {
+        let (addr, served) = serve(vec![
+            Ok("field1,field2\nzup,mop\n"),
+            Ok("field3,field2\nzup,pop\n"),
+        ]);
+        let mut table = load(addr).await.unwrap();
+        let handle = table.add_index(Case::Sensitive, &["field1"]).unwrap();
+
+        wait_for(&served, 2).await;
+
+        assert_eq!(
+            Ok(btreemap! { "field1" => "zup", "field2" => "mop" }),
+            find(&table, Some(handle))
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_initial_load() {
+        let (addr, _) = serve(vec![Err(404)]);
+
+        assert_eq!(
+            "unexpected status 404 Not Found",
+            load(addr).await.unwrap_err().to_string()
+        );
+    }
+}
@@ -1,8 +1,15 @@
 use std::time::{Duration, Instant};
 
+use bytes::Bytes;
+use chrono::Utc;
 use tokio::time::interval;
 
-use crate::internal_events::Heartbeat;
+use crate::{
+    config::{log_schema, ComponentKey, DataType, SourceHeartbeatConfig},
+    event::{Event, LogEvent, Metric, MetricKind, MetricValue},
+    internal_events::Heartbeat,
+    SourceSender,
+};
 
 /// Emits Heartbeat event every second.
 pub async fn heartbeat() {
@@ -13,3 +20,93 @@ pub async fn heartbeat() {
         emit!(&Heartbeat { since });
     }
 }
+
+/// Sends heartbeat events through the default output of a source, which takes events of type
+/// `ty`. Never finishes, so it's meant to be dropped along with the source.
+pub async fn source_heartbeats(
+    config: SourceHeartbeatConfig,
+    key: ComponentKey,
+    source_type: &'static str,
+    ty: DataType,
+    mut out: SourceSender,
+) {
+    let mut interval = interval(Duration::from_secs(config.interval_secs.max(1)));
+    loop {
+        interval.tick().await;
+        let event = source_heartbeat(&config, &key, source_type, ty);
+        if out.send(event).await.is_err() {
+            // The topology is shutting down, and the source with it.
+            futures::future::pending::<()>().await;
+        }
+    }
+}
+
+fn source_heartbeat(
+    config: &SourceHeartbeatConfig,
+    key: &ComponentKey,
+    source_type: &'static str,
+    ty: DataType,
+) -> Event {
+    match ty {
+        DataType::Any | DataType::Log => {
+            let mut log = LogEvent::from(config.message.as_str());
+            log.insert("source_id", key.id());
+            log.insert(log_schema().source_type_key(), Bytes::from(source_type));
+            for (field, value) in &config.fields {
+                log.insert(field.as_str(), value.clone());
+            }
+            log.into()
+        }
+        DataType::Metric => {
+            let mut tags = config.fields.clone();
+            tags.insert("source_id".to_owned(), key.id().to_owned());
+            tags.insert("source_type".to_owned(), source_type.to_owned());
+            Metric::new(
+                config.message.clone(),
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 1.0 },
+            )
+            .with_tags(Some(tags))
+            .with_timestamp(Some(Utc::now()))
+            .into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SourceHeartbeatConfig {
+        toml::from_str(
+            r#"
+            message = "alive"
+            fields.env = "production"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn builds_log_heartbeats() {
+        let key = ComponentKey::from("in");
+        let event = source_heartbeat(&config(), &key, "stdin", DataType::Log);
+
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], "alive".into());
+        assert_eq!(log["source_id"], "in".into());
+        assert_eq!(log[log_schema().source_type_key()], "stdin".into());
+        assert_eq!(log["env"], "production".into());
+    }
+
+    #[test]
+    fn builds_metric_heartbeats() {
+        let key = ComponentKey::from("in");
+        let event = source_heartbeat(&config(), &key, "host_metrics", DataType::Metric);
+
+        let metric = event.as_metric();
+        assert_eq!(metric.name(), "alive");
+        assert_eq!(metric.tag_value("source_id"), Some("in".to_owned()));
+        assert_eq!(metric.tag_value("env"), Some("production".to_owned()));
+    }
+}
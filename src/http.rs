@@ -1,6 +1,7 @@
 use std::{
     fmt,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use futures::future::BoxFuture;
@@ -21,7 +22,7 @@ use tracing_futures::Instrument;
 use crate::{
     config::ProxyConfig,
     internal_events::http_client,
-    tls::{tls_connector_builder, MaybeTlsSettings, TlsError},
+    tls::{tls_connector_builder, MaybeTls, MaybeTlsSettings, TlsError, TlsSettings},
 };
 
 #[derive(Debug, Snafu)]
@@ -197,6 +198,106 @@ impl<B> fmt::Debug for HttpClient<B> {
     }
 }
 
+/// Configures HTTP/2, which multiplexes requests over each connection so that a slow request
+/// doesn't hold up the ones behind it.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct Http2Config {
+    enabled: Option<bool>,
+    /// Sizes the flow control windows from the measured bandwidth-delay product of each
+    /// connection, rather than using the initial window sizes throughout.
+    #[serde(default = "crate::serde::default_true")]
+    pub adaptive_window: bool,
+    pub initial_stream_window_size: Option<u32>,
+    pub initial_connection_window_size: Option<u32>,
+    pub keep_alive_interval_secs: Option<u64>,
+    /// The most requests a client can have in flight on each connection to a server.
+    pub max_concurrent_streams: Option<u32>,
+}
+
+impl Default for Http2Config {
+    fn default() -> Self {
+        Self {
+            enabled: None,
+            adaptive_window: true,
+            initial_stream_window_size: None,
+            initial_connection_window_size: None,
+            keep_alive_interval_secs: None,
+            max_concurrent_streams: None,
+        }
+    }
+}
+
+impl From<bool> for Http2Config {
+    fn from(enabled: bool) -> Self {
+        Self {
+            enabled: Some(enabled),
+            ..Self::default()
+        }
+    }
+}
+
+impl Http2Config {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    fn keep_alive_interval(&self) -> Option<Duration> {
+        self.keep_alive_interval_secs.map(Duration::from_secs)
+    }
+
+    /// Makes a client use HTTP/2 only, which is assumed to be supported over plain-text
+    /// connections, as they can't negotiate it.
+    pub fn configure_client(&self, builder: &mut client::Builder) {
+        if self.enabled() {
+            builder
+                .http2_only(true)
+                .http2_adaptive_window(self.adaptive_window)
+                .http2_initial_stream_window_size(self.initial_stream_window_size)
+                .http2_initial_connection_window_size(self.initial_connection_window_size)
+                .http2_keep_alive_interval(self.keep_alive_interval());
+        }
+    }
+
+    /// Makes a client negotiate HTTP/2 over TLS.
+    pub fn client_tls(&self, tls: TlsSettings) -> TlsSettings {
+        if self.enabled() {
+            tls.with_alpn_protocols(&["h2"])
+        } else {
+            tls
+        }
+    }
+
+    /// Tunes how a server handles HTTP/2 connections. Servers accept HTTP/2 alongside HTTP/1.1
+    /// either way.
+    pub fn configure_server<I, E>(
+        &self,
+        builder: hyper::server::Builder<I, E>,
+    ) -> hyper::server::Builder<I, E> {
+        if !self.enabled() {
+            return builder;
+        }
+
+        builder
+            .http2_adaptive_window(self.adaptive_window)
+            .http2_initial_stream_window_size(self.initial_stream_window_size)
+            .http2_initial_connection_window_size(self.initial_connection_window_size)
+            .http2_keep_alive_interval(self.keep_alive_interval())
+            .http2_max_concurrent_streams(self.max_concurrent_streams)
+    }
+
+    /// Lets a server's clients negotiate HTTP/2 over TLS, which they otherwise can only use over
+    /// plain-text connections.
+    pub fn server_tls(&self, tls: MaybeTlsSettings) -> MaybeTlsSettings {
+        match tls {
+            MaybeTls::Tls(tls) if self.enabled() => {
+                MaybeTls::Tls(tls.with_alpn_protocols(&["h2", "http/1.1"]))
+            }
+            tls => tls,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 #[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
 pub enum Auth {
@@ -0,0 +1,21 @@
+use metrics::gauge;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ComponentAllocatedBytes<'a> {
+    pub component_kind: &'a str,
+    pub component_id: &'a str,
+    pub component_type: &'a str,
+    pub bytes: i64,
+}
+
+impl<'a> InternalEvent for ComponentAllocatedBytes<'a> {
+    fn emit_metrics(&self) {
+        gauge!(
+            "component_allocated_bytes", self.bytes as f64,
+            "component_kind" => self.component_kind.to_owned(),
+            "component_id" => self.component_id.to_owned(),
+            "component_type" => self.component_type.to_owned(),
+        );
+    }
+}
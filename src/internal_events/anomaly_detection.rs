@@ -0,0 +1,11 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AnomalyDetectionEventDiscarded;
+
+impl InternalEvent for AnomalyDetectionEventDiscarded {
+    fn emit_metrics(&self) {
+        counter!("events_discarded_total", 1);
+    }
+}
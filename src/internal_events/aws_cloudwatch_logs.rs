@@ -0,0 +1,64 @@
+// ## skip check-events ##
+
+use std::path::Path;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AwsCloudWatchLogsEventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for AwsCloudWatchLogsEventsReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received events.", count = %self.count);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_received_events_total", self.count as u64);
+        counter!("events_in_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsCloudWatchLogsRequestFailed<'a> {
+    pub error: &'a crate::Error,
+}
+
+impl InternalEvent for AwsCloudWatchLogsRequestFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to read log events; retrying on next poll.",
+            error = %self.error,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("http_request_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsCloudWatchLogsCursorWriteFailed<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for AwsCloudWatchLogsCursorWriteFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to write cursors.",
+            error = %self.error,
+            path = ?self.path,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("checkpoint_write_errors_total", 1);
+    }
+}
@@ -167,4 +167,26 @@ pub mod source {
             counter!("sqs_s3_event_record_ignored_total", 1, "ignore_type" => "invalid_event_kind");
         }
     }
+
+    #[derive(Debug)]
+    pub struct SqsS3CloudTrailDigestMismatch<'a> {
+        pub digest_key: &'a str,
+        pub bucket: &'a str,
+        pub key: &'a str,
+    }
+
+    impl<'a> InternalEvent for SqsS3CloudTrailDigestMismatch<'a> {
+        fn emit_logs(&self) {
+            error!(
+                message = "CloudTrail log file does not match the hash in its digest file; it may have been modified.",
+                digest_key = %self.digest_key,
+                bucket = %self.bucket,
+                key = %self.key,
+            );
+        }
+
+        fn emit_metrics(&self) {
+            counter!("cloudtrail_digest_mismatches_total", 1);
+        }
+    }
 }
@@ -0,0 +1,31 @@
+// ## skip check-events ##
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::sinks::aws_security_lake::ValidationError;
+
+#[derive(Debug)]
+pub struct AwsSecurityLakeEventRejected<'a> {
+    pub error: &'a ValidationError,
+}
+
+impl InternalEvent for AwsSecurityLakeEventRejected<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Event does not match the OCSF class; dropping event.",
+            error_type = "validation_failed",
+            error = %self.error,
+            stage = "processing",
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "validation_failed",
+            "stage" => "processing",
+        );
+        counter!("component_discarded_events_total", 1);
+    }
+}
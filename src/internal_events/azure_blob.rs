@@ -52,3 +52,45 @@ impl InternalEvent for AzureBlobEventSent {
         counter!("processed_bytes_total", self.byte_size as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct AzureBlobAppendBlobFull<'a> {
+    pub blob: &'a str,
+}
+
+impl<'a> InternalEvent for AzureBlobAppendBlobFull<'a> {
+    fn emit_logs(&self) {
+        debug!(message = "Append blob is full, moving on to the next one.", blob = %self.blob);
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureBlobSasTokenRefreshed;
+
+impl InternalEvent for AzureBlobSasTokenRefreshed {
+    fn emit_logs(&self) {
+        debug!(message = "Refreshed SAS token.");
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureBlobSasTokenRefreshError {
+    pub error: String,
+}
+
+impl InternalEvent for AzureBlobSasTokenRefreshError {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to refresh SAS token, keeping the previous one.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "processing_errors_total", 1,
+            "error_type" => "sas_token_refresh_failed",
+        );
+    }
+}
@@ -0,0 +1,117 @@
+// ## skip check-events ##
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct AzureEventHubsEventsReceived<'a> {
+    pub count: usize,
+    pub byte_size: usize,
+    pub partition_id: &'a str,
+}
+
+impl InternalEvent for AzureEventHubsEventsReceived<'_> {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Received events.",
+            count = %self.count,
+            partition_id = %self.partition_id,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_received_events_total", self.count as u64);
+        counter!("events_in_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureEventHubsReceiveFailed<'a> {
+    pub error: &'a crate::Error,
+    pub partition_id: &'a str,
+}
+
+impl InternalEvent for AzureEventHubsReceiveFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to receive events from partition; reconnecting.",
+            error = %self.error,
+            partition_id = %self.partition_id,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureEventHubsLoadBalancingFailed<'a> {
+    pub error: &'a crate::Error,
+}
+
+impl InternalEvent for AzureEventHubsLoadBalancingFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to balance partition ownership.",
+            error = %self.error,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("ownership_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureEventHubsPartitionClaimed<'a> {
+    pub partition_id: &'a str,
+}
+
+impl InternalEvent for AzureEventHubsPartitionClaimed<'_> {
+    fn emit_logs(&self) {
+        info!(
+            message = "Claimed ownership of partition.",
+            partition_id = %self.partition_id,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureEventHubsPartitionLost<'a> {
+    pub partition_id: &'a str,
+}
+
+impl InternalEvent for AzureEventHubsPartitionLost<'_> {
+    fn emit_logs(&self) {
+        info!(
+            message = "Lost ownership of partition to another consumer.",
+            partition_id = %self.partition_id,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AzureEventHubsCheckpointFailed<'a> {
+    pub error: &'a crate::Error,
+    pub partition_id: &'a str,
+}
+
+impl InternalEvent for AzureEventHubsCheckpointFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to update checkpoint.",
+            error = %self.error,
+            partition_id = %self.partition_id,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("consumer_offset_updates_failed_total", 1);
+    }
+}
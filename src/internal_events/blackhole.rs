@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
@@ -11,3 +13,33 @@ impl InternalEvent for BlackholeEventReceived {
         counter!("processed_bytes_total", self.byte_size as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct BlackholeErrorInjected {
+    pub count: usize,
+}
+
+impl InternalEvent for BlackholeErrorInjected {
+    fn emit_logs(&self) {
+        debug!(message = "Injected request error; sending again.", count = %self.count);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("injected_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct BlackholeBackpressureInjected {
+    pub duration: Duration,
+}
+
+impl InternalEvent for BlackholeBackpressureInjected {
+    fn emit_logs(&self) {
+        debug!(message = "Injected backpressure.", duration = ?self.duration);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("injected_backpressure_total", 1);
+    }
+}
@@ -0,0 +1,50 @@
+// ## skip check-events ##
+
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use crate::sinks::util::service::CircuitBreakerState;
+
+#[derive(Debug)]
+pub struct CircuitBreakerStateChanged {
+    /// `None` when the state is reported for the first time.
+    pub from: Option<CircuitBreakerState>,
+    pub to: CircuitBreakerState,
+}
+
+impl InternalEvent for CircuitBreakerStateChanged {
+    fn emit_logs(&self) {
+        match (self.from, self.to) {
+            (None, _) => {}
+            (Some(_), CircuitBreakerState::Open) => {
+                warn!(message = "Circuit breaker opened; holding requests to the endpoint.")
+            }
+            (Some(_), CircuitBreakerState::HalfOpen) => {
+                info!(message = "Circuit breaker half-open; probing the endpoint.")
+            }
+            (Some(_), CircuitBreakerState::Closed) => {
+                info!(message = "Circuit breaker closed; endpoint recovered.")
+            }
+        }
+    }
+
+    fn emit_metrics(&self) {
+        gauge!("circuit_breaker_state", self.to.as_gauge());
+        if self.from.is_some() {
+            counter!("circuit_breaker_transitions_total", 1, "state" => self.to.as_str());
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CircuitBreakerRequestRejected;
+
+impl InternalEvent for CircuitBreakerRequestRejected {
+    fn emit_logs(&self) {
+        trace!(message = "Circuit breaker rejected request.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("circuit_breaker_rejected_requests_total", 1);
+    }
+}
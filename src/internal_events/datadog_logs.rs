@@ -1,6 +1,8 @@
 use metrics::counter;
 use vector_core::internal_event::InternalEvent;
 
+use crate::sinks::datadog::logs::FetchError;
+
 #[derive(Debug)]
 pub struct DatadogLogEventProcessed {
     pub byte_size: usize,
@@ -12,3 +14,61 @@ impl InternalEvent for DatadogLogEventProcessed {
         counter!("processed_bytes_total", self.byte_size as u64);
     }
 }
+
+#[derive(Debug)]
+pub struct DatadogLogsEventExcluded<'a> {
+    pub index: &'a str,
+    pub exclusion_filter: &'a str,
+}
+
+impl<'a> InternalEvent for DatadogLogsEventExcluded<'a> {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Event excluded by Datadog exclusion filter.",
+            index = %self.index,
+            exclusion_filter = %self.exclusion_filter,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_excluded_total", 1,
+                 "index" => self.index.to_owned(),
+                 "exclusion_filter" => self.exclusion_filter.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DatadogLogsExclusionFiltersUpdated {
+    pub count: usize,
+}
+
+impl InternalEvent for DatadogLogsExclusionFiltersUpdated {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Updated Datadog exclusion filters.",
+            count = %self.count,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct DatadogLogsExclusionFiltersFetchError {
+    pub error: FetchError,
+}
+
+impl InternalEvent for DatadogLogsExclusionFiltersFetchError {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to fetch Datadog exclusion filters, keeping the previous filters.",
+            error = %self.error,
+            internal_log_rate_secs = 60,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1,
+                 "error_type" => "exclusion_filters_fetch_failed",
+        );
+    }
+}
@@ -0,0 +1,49 @@
+// ## skip check-events ##
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct EnrichmentTableRefreshed<'a> {
+    pub source: &'a str,
+}
+
+impl InternalEvent for EnrichmentTableRefreshed<'_> {
+    fn emit_logs(&self) {
+        info!(
+            message = "Refreshed enrichment table.",
+            source = %self.source,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("enrichment_table_refreshes_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct EnrichmentTableRefreshFailed<'a> {
+    pub source: &'a str,
+    pub error: crate::Error,
+}
+
+impl InternalEvent for EnrichmentTableRefreshFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to refresh enrichment table; keeping the last loaded data.",
+            source = %self.source,
+            error = %self.error,
+            error_type = "request_failed",
+            stage = "receiving",
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "request_failed",
+            "stage" => "receiving",
+        );
+        counter!("enrichment_table_refresh_errors_total", 1);
+    }
+}
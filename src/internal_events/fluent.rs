@@ -37,3 +37,18 @@ impl<'a> InternalEvent for FluentMessageDecodeError<'a> {
         counter!("decode_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct FluentHandshakeFailed<'a> {
+    pub reason: &'a str,
+}
+
+impl<'a> InternalEvent for FluentHandshakeFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(message = "Fluent client failed to authenticate.", reason = %self.reason, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1, "mode" => "tcp");
+    }
+}
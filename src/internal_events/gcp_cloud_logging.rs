@@ -0,0 +1,64 @@
+// ## skip check-events ##
+
+use std::path::Path;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct GcpCloudLoggingEventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for GcpCloudLoggingEventsReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received events.", count = %self.count);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_received_events_total", self.count as u64);
+        counter!("events_in_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct GcpCloudLoggingRequestFailed<'a> {
+    pub error: &'a crate::Error,
+}
+
+impl InternalEvent for GcpCloudLoggingRequestFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to list log entries; retrying on next poll.",
+            error = %self.error,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("http_request_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct GcpCloudLoggingCursorWriteFailed<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for GcpCloudLoggingCursorWriteFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to write cursor.",
+            error = %self.error,
+            path = ?self.path,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("checkpoint_write_errors_total", 1);
+    }
+}
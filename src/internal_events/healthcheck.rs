@@ -0,0 +1,68 @@
+// ## skip check-events ##
+
+use metrics::gauge;
+use vector_core::internal_event::InternalEvent;
+
+fn record_health(healthy: bool, required: bool) {
+    gauge!("component_healthy", if healthy { 1.0 } else { 0.0 });
+    if required {
+        gauge!("component_degraded", if healthy { 0.0 } else { 1.0 });
+    }
+}
+
+#[derive(Debug)]
+pub struct HealthcheckPassed {
+    pub required: bool,
+}
+
+impl InternalEvent for HealthcheckPassed {
+    fn emit_logs(&self) {
+        info!(message = "Healthcheck: Passed.");
+    }
+
+    fn emit_metrics(&self) {
+        record_health(true, self.required);
+    }
+}
+
+#[derive(Debug)]
+pub struct HealthcheckFailed {
+    pub error: crate::Error,
+    pub required: bool,
+}
+
+impl InternalEvent for HealthcheckFailed {
+    fn emit_logs(&self) {
+        if self.required {
+            error!(
+                message = "Healthcheck: Failed Reason; component degraded.",
+                error = %self.error,
+            );
+        } else {
+            error!(message = "Healthcheck: Failed Reason.", error = %self.error);
+        }
+    }
+
+    fn emit_metrics(&self) {
+        record_health(false, self.required);
+    }
+}
+
+#[derive(Debug)]
+pub struct HealthcheckTimedOut {
+    pub required: bool,
+}
+
+impl InternalEvent for HealthcheckTimedOut {
+    fn emit_logs(&self) {
+        if self.required {
+            error!(message = "Healthcheck: timeout; component degraded.");
+        } else {
+            error!(message = "Healthcheck: timeout.");
+        }
+    }
+
+    fn emit_metrics(&self) {
+        record_health(false, self.required);
+    }
+}
@@ -0,0 +1,28 @@
+// ## skip check-events ##
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct HoneycombMarkerFailed {
+    pub error: crate::Error,
+}
+
+impl InternalEvent for HoneycombMarkerFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to create Honeycomb marker.",
+            error = %self.error,
+            error_type = "request_failed",
+            stage = "sending",
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "request_failed",
+            "stage" => "sending",
+        );
+    }
+}
@@ -103,6 +103,28 @@ impl InternalEvent for HttpEventMissingMessage {
     }
 }
 
+#[derive(Debug)]
+pub struct HttpInvalidRenderedValue<'a> {
+    pub field: &'a str,
+    pub value: &'a str,
+}
+
+impl InternalEvent for HttpInvalidRenderedValue<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Rendered an invalid request value; dropping event.",
+            field = %self.field,
+            value = %self.value,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_discarded_total", 1);
+        counter!("component_discarded_events_total", 1);
+    }
+}
+
 #[derive(Debug)]
 pub struct HttpEventEncoded {
     pub byte_size: usize,
@@ -145,3 +167,31 @@ impl<'a> InternalEvent for HttpDecompressError<'a> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct HttpJwksRefreshError {
+    pub error: crate::Error,
+    pub url: String,
+}
+
+impl InternalEvent for HttpJwksRefreshError {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed refreshing JWKS signing keys.",
+            url = %self.url,
+            error = %self.error,
+            error_type = "request_failed",
+            stage = "receiving",
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => "request_failed",
+            "stage" => "receiving",
+        );
+        counter!("http_request_errors_total", 1);
+    }
+}
@@ -84,3 +84,23 @@ impl InternalEvent for LogToMetricTemplateParseError {
         );
     }
 }
+
+pub struct LogToMetricInvalidMetric {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for LogToMetricInvalidMetric {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to convert log event into a metric.",
+            error = %self.error,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1,
+                 "error_type" => "parse_error",
+        );
+    }
+}
@@ -0,0 +1,23 @@
+// ## skip check-events ##
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct LogstashEncodeEventFailed {
+    pub error: serde_json::Error,
+}
+
+impl InternalEvent for LogstashEncodeEventFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Event encode failed; dropping event.",
+            error = %self.error,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("encode_errors_total", 1);
+    }
+}
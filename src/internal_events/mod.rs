@@ -4,11 +4,17 @@ mod adaptive_concurrency;
 mod add_fields;
 mod add_tags;
 mod aggregate;
+#[cfg(feature = "allocation-tracing")]
+mod allocations;
+#[cfg(feature = "transforms-anomaly_detection")]
+mod anomaly_detection;
 mod ansi_stripper;
 #[cfg(feature = "sources-apache_metrics")]
 mod apache_metrics;
 #[cfg(feature = "api")]
 mod api;
+#[cfg(feature = "sources-aws_cloudwatch_logs")]
+mod aws_cloudwatch_logs;
 #[cfg(feature = "transforms-aws_cloudwatch_logs_subscription_parser")]
 mod aws_cloudwatch_logs_subscription_parser;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -23,12 +29,20 @@ mod aws_kinesis_streams;
 pub(crate) mod aws_s3;
 #[cfg(feature = "sinks-aws_s3")]
 pub(crate) mod aws_s3_sink;
+#[cfg(feature = "sinks-aws_security_lake")]
+mod aws_security_lake;
 #[cfg(feature = "sinks-aws_sqs")]
 mod aws_sqs;
 #[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
 pub(crate) mod azure_blob;
+#[cfg(any(
+    feature = "sources-azure_event_hubs",
+    feature = "sinks-azure_event_hubs"
+))]
+mod azure_event_hubs;
 mod batch;
 mod blackhole;
+mod circuit_breaker;
 #[cfg(feature = "transforms-coercer")]
 mod coercer;
 mod common;
@@ -55,6 +69,8 @@ mod dnstap;
 mod docker_logs;
 mod elasticsearch;
 mod encoding_transcode;
+#[cfg(feature = "enrichment-tables-file")]
+mod enrichment_tables;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 mod eventstoredb_metrics;
 #[cfg(feature = "sources-exec")]
@@ -63,11 +79,16 @@ mod exec;
 mod filter;
 #[cfg(feature = "sources-fluent")]
 mod fluent;
+#[cfg(feature = "sources-gcp_cloud_logging")]
+mod gcp_cloud_logging;
 #[cfg(feature = "transforms-geoip")]
 mod geoip;
 #[cfg(feature = "transforms-grok_parser")]
 mod grok_parser;
+mod healthcheck;
 mod heartbeat;
+#[cfg(feature = "sinks-honeycomb")]
+mod honeycomb;
 #[cfg(feature = "sources-host_metrics")]
 mod host_metrics;
 mod http;
@@ -87,6 +108,8 @@ mod log_to_metric;
 #[cfg(feature = "transforms-logfmt_parser")]
 mod logfmt_parser;
 mod logplex;
+#[cfg(feature = "sinks-logstash")]
+mod logstash;
 #[cfg(feature = "sinks-loki")]
 mod loki;
 #[cfg(feature = "transforms-lua")]
@@ -95,10 +118,21 @@ mod lua;
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
+#[cfg(feature = "sources-named_pipe")]
+mod named_pipe;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 mod nats;
+#[cfg(feature = "sinks-notification")]
+mod notification;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
+#[cfg(any(
+    feature = "sinks-aws_s3",
+    feature = "sinks-azure_blob",
+    feature = "sinks-datadog_archives",
+    feature = "sinks-gcp"
+))]
+mod object_storage;
 mod open;
 #[cfg(feature = "sources-postgresql_metrics")]
 mod postgresql_metrics;
@@ -123,9 +157,14 @@ mod sample;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
 mod socket;
+mod source_silence;
+#[cfg(feature = "transforms-span_to_metrics")]
+mod span_to_metrics;
 mod split;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
+#[cfg(feature = "sources-sse")]
+mod sse;
 #[cfg(feature = "sinks-statsd")]
 mod statsd_sink;
 #[cfg(feature = "sources-statsd")]
@@ -134,15 +173,25 @@ mod stdin;
 mod syslog;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tail_sampling")]
+mod tail_sampling;
 mod tcp;
 mod template;
+#[cfg(feature = "transforms-tenant_quota")]
+mod tenant_quota;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
+#[cfg(feature = "transforms-timestamp_policy")]
+mod timestamp_policy;
 #[cfg(feature = "transforms-tokenizer")]
 mod tokenizer;
 mod udp;
 mod unix;
 mod vector;
+#[cfg(feature = "sources-websocket")]
+mod websocket;
+#[cfg(feature = "sinks-websocket_server")]
+mod websocket_server;
 
 #[cfg(any(
     feature = "sources-file",
@@ -157,10 +206,16 @@ pub mod kubernetes;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub use mongodb_metrics::*;
 
+#[cfg(feature = "allocation-tracing")]
+pub(crate) use self::allocations::*;
+#[cfg(feature = "transforms-anomaly_detection")]
+pub use self::anomaly_detection::*;
 #[cfg(feature = "sources-apache_metrics")]
 pub use self::apache_metrics::*;
 #[cfg(feature = "api")]
 pub use self::api::*;
+#[cfg(feature = "sources-aws_cloudwatch_logs")]
+pub(crate) use self::aws_cloudwatch_logs::*;
 #[cfg(feature = "transforms-aws_cloudwatch_logs_subscription_parser")]
 pub(crate) use self::aws_cloudwatch_logs_subscription_parser::*;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
@@ -173,8 +228,15 @@ pub use self::aws_kinesis_firehose::*;
 pub use self::aws_kinesis_streams::*;
 #[cfg(feature = "sinks-aws_s3")]
 pub use self::aws_s3_sink::*;
+#[cfg(feature = "sinks-aws_security_lake")]
+pub(crate) use self::aws_security_lake::*;
 #[cfg(feature = "sinks-aws_sqs")]
 pub use self::aws_sqs::*;
+#[cfg(any(
+    feature = "sources-azure_event_hubs",
+    feature = "sinks-azure_event_hubs"
+))]
+pub(crate) use self::azure_event_hubs::*;
 #[cfg(feature = "transforms-coercer")]
 pub(crate) use self::coercer::*;
 #[cfg(feature = "transforms-concat")]
@@ -197,6 +259,8 @@ pub use self::demo_logs::*;
 pub(crate) use self::dnstap::*;
 #[cfg(feature = "sources-docker_logs")]
 pub use self::docker_logs::*;
+#[cfg(feature = "enrichment-tables-file")]
+pub(crate) use self::enrichment_tables::*;
 #[cfg(feature = "sources-eventstoredb_metrics")]
 pub use self::eventstoredb_metrics::*;
 #[cfg(feature = "sources-exec")]
@@ -211,18 +275,24 @@ pub use self::file::*;
 pub use self::filter::*;
 #[cfg(feature = "sources-fluent")]
 pub use self::fluent::*;
+#[cfg(feature = "sources-gcp_cloud_logging")]
+pub(crate) use self::gcp_cloud_logging::*;
 #[cfg(feature = "transforms-geoip")]
 pub(crate) use self::geoip::*;
 #[cfg(feature = "transforms-grok_parser")]
 pub(crate) use self::grok_parser::*;
+#[cfg(feature = "sinks-honeycomb")]
+pub(crate) use self::honeycomb::*;
 #[cfg(feature = "sources-host_metrics")]
 pub(crate) use self::host_metrics::*;
 #[cfg(any(
     feature = "sources-utils-http",
+    feature = "sources-utils-http-auth",
     feature = "sources-utils-http-encoding",
     feature = "sinks-http",
     feature = "sources-datadog_agent",
     feature = "sources-splunk_hec",
+    feature = "sinks-webhook",
 ))]
 pub(crate) use self::http::*;
 #[cfg(all(unix, feature = "sources-journald"))]
@@ -239,16 +309,29 @@ pub use self::kubernetes_logs::*;
 pub(crate) use self::log_to_metric::*;
 #[cfg(feature = "transforms-logfmt_parser")]
 pub use self::logfmt_parser::*;
+#[cfg(feature = "sinks-logstash")]
+pub(crate) use self::logstash::*;
 #[cfg(feature = "sinks-loki")]
 pub(crate) use self::loki::*;
 #[cfg(feature = "transforms-lua")]
 pub use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
+#[cfg(feature = "sources-named_pipe")]
+pub(crate) use self::named_pipe::*;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub use self::nats::*;
+#[cfg(feature = "sinks-notification")]
+pub(crate) use self::notification::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
+#[cfg(any(
+    feature = "sinks-aws_s3",
+    feature = "sinks-azure_blob",
+    feature = "sinks-datadog_archives",
+    feature = "sinks-gcp"
+))]
+pub(crate) use self::object_storage::*;
 #[cfg(feature = "sources-postgresql_metrics")]
 pub(crate) use self::postgresql_metrics::*;
 #[cfg(any(feature = "sources-prometheus", feature = "sinks-prometheus"))]
@@ -268,25 +351,41 @@ pub use self::route::*;
 #[cfg(feature = "sinks-sematext")]
 pub use self::sematext_metrics::*;
 pub(crate) use self::socket::*;
+pub(crate) use self::source_silence::*;
+#[cfg(feature = "transforms-span_to_metrics")]
+pub(crate) use self::span_to_metrics::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
+#[cfg(feature = "sources-sse")]
+pub(crate) use self::sse::*;
 #[cfg(feature = "sinks-statsd")]
 pub use self::statsd_sink::*;
 #[cfg(feature = "sources-statsd")]
 pub use self::statsd_source::*;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub(crate) use self::tag_cardinality_limit::*;
+#[cfg(feature = "transforms-tail_sampling")]
+pub(crate) use self::tail_sampling::*;
+#[cfg(feature = "transforms-tenant_quota")]
+pub(crate) use self::tenant_quota::*;
 #[cfg(feature = "transforms-throttle")]
 pub use self::throttle::*;
+#[cfg(feature = "transforms-timestamp_policy")]
+pub(crate) use self::timestamp_policy::*;
 #[cfg(feature = "transforms-tokenizer")]
 pub(crate) use self::tokenizer::*;
+#[cfg(feature = "sources-websocket")]
+pub(crate) use self::websocket::*;
+#[cfg(feature = "sinks-websocket_server")]
+pub(crate) use self::websocket_server::*;
 #[cfg(windows)]
 pub use self::windows::*;
 pub use self::{
     adaptive_concurrency::*, add_fields::*, add_tags::*, aggregate::*, ansi_stripper::*, batch::*,
-    blackhole::*, common::*, conditions::*, elasticsearch::*, encoding_transcode::*, heartbeat::*,
-    logplex::*, open::*, process::*, pulsar::*, remap::*, sample::*, split::*, stdin::*, syslog::*,
-    tcp::*, template::*, udp::*, unix::*, vector::*,
+    blackhole::*, circuit_breaker::*, common::*, conditions::*, elasticsearch::*,
+    encoding_transcode::*, healthcheck::*, heartbeat::*, logplex::*, open::*, process::*,
+    pulsar::*, remap::*, sample::*, split::*, stdin::*, syslog::*, tcp::*, template::*, udp::*,
+    unix::*, vector::*,
 };
 
 // this version won't be needed once all `InternalEvent`s implement `name()`
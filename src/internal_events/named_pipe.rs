@@ -0,0 +1,46 @@
+// ## skip check-events ##
+
+use std::path::Path;
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct NamedPipeEventsReceived<'a> {
+    pub byte_size: usize,
+    pub count: usize,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for NamedPipeEventsReceived<'_> {
+    fn emit_logs(&self) {
+        trace!(message = "Received events.", count = %self.count, path = ?self.path);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_received_events_total", self.count as u64);
+        counter!("events_in_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct NamedPipeOpenError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl InternalEvent for NamedPipeOpenError<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to open named pipe.",
+            error = %self.error,
+            path = ?self.path,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1, "mode" => "named_pipe");
+    }
+}
@@ -0,0 +1,25 @@
+// ## skip check-events ##
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct NotificationSendFailed {
+    pub error: String,
+    pub count: usize,
+}
+
+impl InternalEvent for NotificationSendFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to send notification.",
+            error = %self.error,
+            count = %self.count,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1);
+    }
+}
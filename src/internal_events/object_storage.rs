@@ -0,0 +1,50 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct ObjectManifestWriteError<'a> {
+    pub key: &'a str,
+    pub error: String,
+}
+
+impl<'a> InternalEvent for ObjectManifestWriteError<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to write manifest.",
+            key = %self.key,
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "processing_errors_total", 1,
+            "error_type" => "manifest_write_failed",
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct ObjectManifestReadError<'a> {
+    pub key: &'a str,
+    pub error: String,
+}
+
+impl<'a> InternalEvent for ObjectManifestReadError<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Failed to read manifest.",
+            key = %self.key,
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "processing_errors_total", 1,
+            "error_type" => "manifest_read_failed",
+        );
+    }
+}
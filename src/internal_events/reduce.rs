@@ -9,3 +9,16 @@ impl InternalEvent for ReduceStaleEventFlushed {
         counter!("stale_events_flushed_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct ReduceLateEventPassedThrough;
+
+impl InternalEvent for ReduceLateEventPassedThrough {
+    fn emit_logs(&self) {
+        trace!(message = "Event arrived after the watermark, passing it through unreduced.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("late_events_total", 1);
+    }
+}
@@ -0,0 +1,72 @@
+// ## skip check-events ##
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct SourceSilent<'a> {
+    pub partition: Option<&'a str>,
+    pub silence: Duration,
+}
+
+impl<'a> InternalEvent for SourceSilent<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Source has sent no events for longer than its silence threshold.",
+            partition = ?self.partition,
+            silence_secs = self.silence.as_secs(),
+        );
+    }
+
+    fn emit_metrics(&self) {
+        match self.partition {
+            Some(partition) => {
+                counter!("source_silences_total", 1, "partition" => partition.to_owned())
+            }
+            None => counter!("source_silences_total", 1),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SourceResumed<'a> {
+    pub partition: Option<&'a str>,
+    pub silence: Duration,
+}
+
+impl<'a> InternalEvent for SourceResumed<'a> {
+    fn emit_logs(&self) {
+        info!(
+            message = "Source is sending events again.",
+            partition = ?self.partition,
+            silence_secs = self.silence.as_secs(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SourceLastEvent<'a> {
+    pub partition: Option<&'a str>,
+    pub timestamp: DateTime<Utc>,
+    pub silent: bool,
+}
+
+impl<'a> InternalEvent for SourceLastEvent<'a> {
+    fn emit_metrics(&self) {
+        let timestamp = self.timestamp.timestamp_millis() as f64 / 1000.0;
+        let silent = if self.silent { 1.0 } else { 0.0 };
+        match self.partition {
+            Some(partition) => {
+                gauge!("source_last_event_timestamp_seconds", timestamp, "partition" => partition.to_owned());
+                gauge!("source_silent", silent, "partition" => partition.to_owned());
+            }
+            None => {
+                gauge!("source_last_event_timestamp_seconds", timestamp);
+                gauge!("source_silent", silent);
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+// ## skip check-events ##
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+pub struct SpanToMetricsInvalidDuration<'a> {
+    pub field: &'a str,
+}
+
+impl<'a> InternalEvent for SpanToMetricsInvalidDuration<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Span duration is not a number of nanoseconds.",
+            field = %self.field,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processing_errors_total", 1,
+                 "error_type" => "invalid_duration",
+        );
+    }
+}
@@ -0,0 +1,73 @@
+// ## skip check-events ##
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::sources::sse::SseError;
+
+#[derive(Debug)]
+pub struct SseEventsReceived {
+    pub byte_size: usize,
+    pub count: usize,
+}
+
+impl InternalEvent for SseEventsReceived {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Received events.",
+            self.count,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_received_events_total", self.count as u64);
+        counter!("events_in_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct SseConnectionEstablished;
+
+impl InternalEvent for SseConnectionEstablished {
+    fn emit_logs(&self) {
+        debug!(message = "Connected.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_established_total", 1, "mode" => "sse");
+    }
+}
+
+#[derive(Debug)]
+pub struct SseConnectionFailed {
+    pub error: SseError,
+}
+
+impl InternalEvent for SseConnectionFailed {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Event stream connection failed, reconnecting.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_failed_total", 1, "mode" => "sse");
+    }
+}
+
+#[derive(Debug)]
+pub struct SseConnectionShutdown;
+
+impl InternalEvent for SseConnectionShutdown {
+    fn emit_logs(&self) {
+        debug!(message = "Event stream ended by the server, reconnecting.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_shutdown_total", 1, "mode" => "sse");
+    }
+}
@@ -0,0 +1,34 @@
+// ## skip check-events ##
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct TailSamplingTraceDropped {
+    pub span_count: usize,
+}
+
+impl InternalEvent for TailSamplingTraceDropped {
+    fn emit_metrics(&self) {
+        counter!("events_discarded_total", self.span_count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct TailSamplingTracesEvicted {
+    pub max_traces: usize,
+}
+
+impl InternalEvent for TailSamplingTracesEvicted {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Too many pending traces; deciding the oldest one early.",
+            max_traces = self.max_traces,
+            internal_log_rate_secs = 30
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("traces_evicted_total", 1);
+    }
+}
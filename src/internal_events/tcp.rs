@@ -134,3 +134,43 @@ impl InternalEvent for TcpBytesReceived {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct TcpConnectionLimitReached {
+    pub limit: &'static str,
+}
+
+impl InternalEvent for TcpConnectionLimitReached {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Connection limit reached; waiting before accepting more connections.",
+            limit = %self.limit,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_limit_reached_total", 1, "limit" => self.limit);
+    }
+}
+
+#[derive(Debug)]
+pub struct TcpConnectionRejected {
+    pub peer_addr: IpAddr,
+    pub reason: &'static str,
+}
+
+impl InternalEvent for TcpConnectionRejected {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Rejected connection.",
+            peer_addr = %self.peer_addr,
+            reason = %self.reason,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connections_rejected_total", 1, "reason" => self.reason);
+    }
+}
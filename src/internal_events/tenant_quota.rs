@@ -0,0 +1,67 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct TenantQuotaEventProcessed<'a> {
+    pub tenant: &'a str,
+    pub byte_size: usize,
+}
+
+impl<'a> InternalEvent for TenantQuotaEventProcessed<'a> {
+    fn emit_metrics(&self) {
+        counter!("tenant_events_total", 1, "tenant" => self.tenant.to_owned());
+        counter!(
+            "tenant_bytes_total", self.byte_size as u64,
+            "tenant" => self.tenant.to_owned(),
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TenantQuotaExceeded<'a> {
+    pub tenant: &'a str,
+    pub quota: &'static str,
+}
+
+impl<'a> InternalEvent for TenantQuotaExceeded<'a> {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Tenant quota exceeded; discarding event.",
+            tenant = %self.tenant,
+            quota = %self.quota,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "events_discarded_total", 1,
+            "tenant" => self.tenant.to_owned(),
+            "quota" => self.quota,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TenantQuotaTagDropped<'a> {
+    pub tenant: &'a str,
+    pub tag_key: &'a str,
+}
+
+impl<'a> InternalEvent for TenantQuotaTagDropped<'a> {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Tenant tag value limit exceeded; dropping tag.",
+            tenant = %self.tenant,
+            tag_key = %self.tag_key,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "tag_value_limit_exceeded_total", 1,
+            "tenant" => self.tenant.to_owned(),
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct TimestampPolicyOutOfBounds {
+    pub bound: &'static str,
+    pub action: &'static str,
+}
+
+impl InternalEvent for TimestampPolicyOutOfBounds {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Event timestamp out of bounds.",
+            bound = %self.bound,
+            action = %self.action,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!(
+            "timestamps_out_of_bounds_total", 1,
+            "bound" => self.bound,
+            "action" => self.action,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TimestampPolicyEventDiscarded;
+
+impl InternalEvent for TimestampPolicyEventDiscarded {
+    fn emit_metrics(&self) {
+        counter!("events_discarded_total", 1);
+    }
+}
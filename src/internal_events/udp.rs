@@ -70,3 +70,25 @@ impl InternalEvent for UdpSendIncomplete {
         counter!("connection_send_errors_total", 1, "mode" => "udp");
     }
 }
+
+#[derive(Debug)]
+pub struct UdpSpoolFull {
+    pub byte_size: usize,
+    pub max_bytes: usize,
+}
+
+impl InternalEvent for UdpSpoolFull {
+    fn emit_logs(&self) {
+        warn!(
+            message = "UDP spool is full; dropping datagram.",
+            byte_size = self.byte_size,
+            max_bytes = self.max_bytes,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("spool_dropped_datagrams_total", 1);
+        counter!("spool_dropped_bytes_total", self.byte_size as u64);
+    }
+}
@@ -35,3 +35,48 @@ impl<'a> InternalEvent for VectorProtoDecodeError<'a> {
         counter!("protobuf_decode_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct VectorEndpointEjected<'a> {
+    pub endpoint: &'a str,
+    pub reason: &'a str,
+}
+
+impl<'a> InternalEvent for VectorEndpointEjected<'a> {
+    fn emit_logs(&self) {
+        warn!(message = "Ejecting unhealthy endpoint from load balancing.", endpoint = %self.endpoint, reason = %self.reason);
+    }
+}
+
+#[derive(Debug)]
+pub struct VectorEndpointReadmitted<'a> {
+    pub endpoint: &'a str,
+}
+
+impl<'a> InternalEvent for VectorEndpointReadmitted<'a> {
+    fn emit_logs(&self) {
+        info!(message = "Readmitting healthy endpoint to load balancing.", endpoint = %self.endpoint);
+    }
+}
+
+#[derive(Debug)]
+pub struct VectorCompressionUnsupported<'a> {
+    pub endpoint: &'a str,
+}
+
+impl<'a> InternalEvent for VectorCompressionUnsupported<'a> {
+    fn emit_logs(&self) {
+        warn!(message = "Downstream Vector doesn't support compressed requests, sending them uncompressed.", endpoint = %self.endpoint);
+    }
+}
+
+#[derive(Debug)]
+pub struct VectorEventArraysUnsupported<'a> {
+    pub endpoint: &'a str,
+}
+
+impl<'a> InternalEvent for VectorEventArraysUnsupported<'a> {
+    fn emit_logs(&self) {
+        warn!(message = "Downstream Vector doesn't support event arrays, sending events individually.", endpoint = %self.endpoint);
+    }
+}
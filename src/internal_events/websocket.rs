@@ -0,0 +1,73 @@
+// ## skip check-events ##
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use crate::sources::websocket::WebSocketError;
+
+#[derive(Debug)]
+pub struct WebSocketEventsReceived {
+    pub byte_size: usize,
+    pub count: usize,
+}
+
+impl InternalEvent for WebSocketEventsReceived {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Received events.",
+            self.count,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("component_received_events_total", self.count as u64);
+        counter!("events_in_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketConnectionEstablished;
+
+impl InternalEvent for WebSocketConnectionEstablished {
+    fn emit_logs(&self) {
+        debug!(message = "Connected.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_established_total", 1, "mode" => "websocket");
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketConnectionFailed {
+    pub error: WebSocketError,
+}
+
+impl InternalEvent for WebSocketConnectionFailed {
+    fn emit_logs(&self) {
+        warn!(
+            message = "WebSocket connection failed, reconnecting.",
+            error = %self.error,
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_failed_total", 1, "mode" => "websocket");
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketConnectionShutdown;
+
+impl InternalEvent for WebSocketConnectionShutdown {
+    fn emit_logs(&self) {
+        debug!(message = "Connection closed by the server, reconnecting.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_shutdown_total", 1, "mode" => "websocket");
+    }
+}
@@ -0,0 +1,96 @@
+// ## skip check-events ##
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct WebSocketServerClientConnected;
+
+impl InternalEvent for WebSocketServerClientConnected {
+    fn emit_logs(&self) {
+        debug!(message = "Client connected.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_established_total", 1, "mode" => "websocket");
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketServerClientDisconnected;
+
+impl InternalEvent for WebSocketServerClientDisconnected {
+    fn emit_logs(&self) {
+        debug!(message = "Client disconnected.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_shutdown_total", 1, "mode" => "websocket");
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketServerClientRejected {
+    pub reason: &'static str,
+}
+
+impl InternalEvent for WebSocketServerClientRejected {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Rejected client.",
+            reason = %self.reason,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1, "mode" => "websocket");
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketServerInvalidFilter<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for WebSocketServerInvalidFilter<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Rejected client with an invalid filter.",
+            error = %self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("http_bad_requests_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketServerMessageDropped;
+
+impl InternalEvent for WebSocketServerMessageDropped {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Client is too slow, dropping event.",
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_discarded_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct WebSocketServerSlowClientDisconnected;
+
+impl InternalEvent for WebSocketServerSlowClientDisconnected {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Client is too slow, disconnecting it.",
+            internal_log_rate_secs = 10,
+        );
+    }
+}
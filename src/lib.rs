@@ -22,10 +22,20 @@ extern crate vector_core;
 #[cfg(feature = "vrl-cli")]
 extern crate vrl_cli;
 
-#[cfg(feature = "tikv-jemallocator")]
+#[cfg(all(feature = "tikv-jemallocator", not(feature = "allocation-tracing")))]
 #[global_allocator]
 static ALLOC: tikv_jemallocator::Jemalloc = tikv_jemallocator::Jemalloc;
 
+#[cfg(all(feature = "tikv-jemallocator", feature = "allocation-tracing"))]
+#[global_allocator]
+static ALLOC: allocations::TrackingAllocator<tikv_jemallocator::Jemalloc> =
+    allocations::TrackingAllocator::new(tikv_jemallocator::Jemalloc);
+
+#[cfg(all(not(feature = "tikv-jemallocator"), feature = "allocation-tracing"))]
+#[global_allocator]
+static ALLOC: allocations::TrackingAllocator<std::alloc::System> =
+    allocations::TrackingAllocator::new(std::alloc::System);
+
 #[macro_use]
 pub mod config;
 pub mod cli;
@@ -37,12 +47,21 @@ pub mod expiring_hash_map;
 pub mod generate;
 #[macro_use]
 pub mod internal_events;
+#[cfg(feature = "allocation-tracing")]
+pub mod allocations;
 #[cfg(feature = "api")]
 pub mod api;
 pub mod app;
 pub mod async_read;
+#[cfg(any(
+    feature = "sources-azure_event_hubs",
+    feature = "sinks-azure_event_hubs"
+))]
+pub(crate) mod azure_event_hubs;
 #[cfg(any(feature = "rusoto_core", feature = "aws-config"))]
 pub mod aws;
+#[cfg(all(feature = "sources-demo_logs", feature = "sinks-blackhole"))]
+pub mod bench;
 #[cfg(feature = "codecs")]
 pub mod codecs;
 pub(crate) mod common;
@@ -58,6 +77,7 @@ pub mod line_agg;
 pub mod list;
 pub(crate) mod proto;
 pub mod providers;
+pub mod schedule;
 pub mod serde;
 #[cfg(windows)]
 pub mod service;
@@ -0,0 +1,341 @@
+//! Cron schedules, and a shared scheduler that components can register with to be triggered at
+//! the times of a schedule, such as flushing aggregated state at the top of each hour.
+//!
+//! Schedules use the standard five cron fields (minute, hour, day of the month, month and day of
+//! the week) and are evaluated in UTC.
+
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
+use futures::stream::BoxStream;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::sync::broadcast;
+use tokio_stream::{wrappers::BroadcastStream, StreamExt};
+
+/// How many years ahead to look for the next time of a schedule, which is enough for schedules
+/// that only fire on the 29th of February.
+const MAX_YEARS_AHEAD: i32 = 8;
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum ScheduleError {
+    #[snafu(display("Schedule {:?} must have 5 fields, but has {}", schedule, count))]
+    FieldCount { schedule: String, count: usize },
+    #[snafu(display("Invalid {} field {:?} in schedule", field, value))]
+    InvalidField { field: &'static str, value: String },
+}
+
+/// A cron schedule, such as `0 * * * *` for the top of each hour.
+///
+/// Besides the five cron fields, the `@yearly`, `@annually`, `@monthly`, `@weekly`, `@daily`,
+/// `@midnight` and `@hourly` shorthands are supported.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CronSchedule {
+    source: String,
+    minutes: u64,
+    hours: u32,
+    days_of_month: u32,
+    months: u16,
+    days_of_week: u8,
+    /// Whether both day fields are restricted, in which case a day matches if either does.
+    either_day: bool,
+}
+
+impl CronSchedule {
+    pub fn parse(source: &str) -> Result<Self, ScheduleError> {
+        let expanded = match source.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            schedule => schedule,
+        };
+        let fields = expanded.split_whitespace().collect::<Vec<_>>();
+        let (minutes, hours, days_of_month, months, days_of_week) = match fields.as_slice() {
+            [minutes, hours, days_of_month, months, days_of_week] => {
+                (minutes, hours, days_of_month, months, days_of_week)
+            }
+            _ => {
+                return Err(ScheduleError::FieldCount {
+                    schedule: source.to_owned(),
+                    count: fields.len(),
+                })
+            }
+        };
+
+        // Sunday is either 0 or 7.
+        let mut week = parse_field("day of the week", days_of_week, 0, 7)?;
+        if week & 1 << 7 != 0 {
+            week = (week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            source: source.to_owned(),
+            minutes: parse_field("minute", minutes, 0, 59)?,
+            hours: parse_field("hour", hours, 0, 23)? as u32,
+            days_of_month: parse_field("day of the month", days_of_month, 1, 31)? as u32,
+            months: parse_field("month", months, 1, 12)? as u16,
+            days_of_week: week as u8,
+            either_day: !days_of_month.starts_with('*') && !days_of_week.starts_with('*'),
+        })
+    }
+
+    /// Returns the first time of the schedule strictly after `after`, or `None` if it never
+    /// fires, such as on the 31st of February.
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let mut time = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let last_year = after.year() + MAX_YEARS_AHEAD;
+
+        while time.year() <= last_year {
+            if self.months & 1 << time.month() == 0 {
+                let (year, month) = match time.month() {
+                    12 => (time.year() + 1, 1),
+                    month => (time.year(), month + 1),
+                };
+                time = Utc.ymd(year, month, 1).and_hms(0, 0, 0);
+            } else if !self.matches_day(time) {
+                time = (time.date() + Duration::days(1)).and_hms(0, 0, 0);
+            } else if self.hours & 1 << time.hour() == 0 {
+                time = time.with_minute(0)? + Duration::hours(1);
+            } else if self.minutes & 1 << time.minute() == 0 {
+                time += Duration::minutes(1);
+            } else {
+                return Some(time);
+            }
+        }
+        None
+    }
+
+    fn matches_day(&self, time: DateTime<Utc>) -> bool {
+        let day_of_month = self.days_of_month & 1 << time.day() != 0;
+        let day_of_week = self.days_of_week & 1 << time.weekday().num_days_from_sunday() != 0;
+        if self.either_day {
+            day_of_month || day_of_week
+        } else {
+            day_of_month && day_of_week
+        }
+    }
+}
+
+/// Parses a comma-separated list of values, `*`, ranges and steps into a bitmask.
+fn parse_field(field: &'static str, value: &str, min: u32, max: u32) -> Result<u64, ScheduleError> {
+    let invalid = || ScheduleError::InvalidField {
+        field,
+        value: value.to_owned(),
+    };
+    let number = |number: &str| {
+        number
+            .parse::<u32>()
+            .ok()
+            .filter(|number| (min..=max).contains(number))
+            .ok_or_else(invalid)
+    };
+
+    let mut mask = 0;
+    for part in value.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (part, None),
+        };
+        let step = match step {
+            Some(step) => step
+                .parse::<usize>()
+                .ok()
+                .filter(|step| *step > 0)
+                .ok_or_else(invalid)?,
+            None => 1,
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" => (min, max),
+            Some((start, end)) => (number(start)?, number(end)?),
+            // `5/15` is every 15 from 5 on.
+            None if step > 1 => (number(range)?, max),
+            None => (number(range)?, number(range)?),
+        };
+        if start > end {
+            return Err(invalid());
+        }
+        for value in (start..=end).step_by(step) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+impl TryFrom<String> for CronSchedule {
+    type Error = ScheduleError;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Self::parse(&source)
+    }
+}
+
+impl From<CronSchedule> for String {
+    fn from(schedule: CronSchedule) -> Self {
+        schedule.source
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+struct Scheduled {
+    sender: broadcast::Sender<DateTime<Utc>>,
+    running: Arc<AtomicBool>,
+}
+
+/// The schedules components are registered with, each with the task that waits for its times.
+static SCHEDULES: Lazy<Mutex<HashMap<CronSchedule, Scheduled>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Registers with the scheduler, returning a stream of the times of `schedule` from now on.
+///
+/// Components registered with the same schedule share the task waiting for it, so they're
+/// triggered together. The task stops once no components are registered with its schedule.
+pub fn ticks(schedule: &CronSchedule) -> BoxStream<'static, DateTime<Utc>> {
+    let mut schedules = SCHEDULES.lock().unwrap();
+    let receiver = match schedules.get(schedule) {
+        Some(scheduled) if scheduled.running.load(Ordering::Acquire) => {
+            scheduled.sender.subscribe()
+        }
+        _ => {
+            let (sender, receiver) = broadcast::channel(1);
+            let running = Arc::new(AtomicBool::new(true));
+            tokio::spawn(run(
+                schedule.clone(),
+                sender.clone(),
+                RunningGuard(Arc::clone(&running)),
+            ));
+            schedules.insert(schedule.clone(), Scheduled { sender, running });
+            receiver
+        }
+    };
+    Box::pin(BroadcastStream::new(receiver).filter_map(Result::ok))
+}
+
+/// Marks a schedule as no longer running when its task is dropped, such as when its runtime
+/// shuts down.
+struct RunningGuard(Arc<AtomicBool>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+async fn run(
+    schedule: CronSchedule,
+    sender: broadcast::Sender<DateTime<Utc>>,
+    _running: RunningGuard,
+) {
+    let mut next = schedule.next_after(Utc::now());
+    while let Some(time) = next {
+        let wait = (time - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        debug!(message = "Triggering schedule.", %schedule, %time);
+        if sender.send(time).is_err() {
+            let mut schedules = SCHEDULES.lock().unwrap();
+            if sender.receiver_count() == 0 {
+                schedules.remove(&schedule);
+                return;
+            }
+        }
+        next = schedule.next_after(time);
+    }
+
+    warn!(message = "Schedule never triggers.", %schedule);
+    SCHEDULES.lock().unwrap().remove(&schedule);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(schedule: &str, after: &str) -> Option<String> {
+        let after = after.parse::<DateTime<Utc>>().unwrap();
+        CronSchedule::parse(schedule)
+            .unwrap()
+            .next_after(after)
+            .map(|time| time.to_rfc3339())
+    }
+
+    #[test]
+    fn parses_schedules() {
+        assert!(CronSchedule::parse("*/15 0-6,22 1 */2 mon").is_err());
+        assert!(CronSchedule::parse("*/15 0-6,22 1 */2 1").is_ok());
+        assert!(CronSchedule::parse("@hourly").is_ok());
+        assert_eq!(
+            CronSchedule::parse("0 * * *"),
+            Err(ScheduleError::FieldCount {
+                schedule: "0 * * *".to_owned(),
+                count: 4
+            })
+        );
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("5-1 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+    }
+
+    #[test]
+    fn finds_next_times() {
+        assert_eq!(
+            next("0 * * * *", "2022-01-01T10:00:00Z"),
+            Some("2022-01-01T11:00:00+00:00".to_owned())
+        );
+        assert_eq!(
+            next("@hourly", "2022-01-01T10:59:59.5Z"),
+            Some("2022-01-01T11:00:00+00:00".to_owned())
+        );
+        assert_eq!(
+            next("*/20 9-17 * * 1-5", "2022-01-07T17:45:00Z"),
+            Some("2022-01-10T09:00:00+00:00".to_owned())
+        );
+        assert_eq!(
+            next("0 0 31 * *", "2022-01-31T00:00:00Z"),
+            Some("2022-03-31T00:00:00+00:00".to_owned())
+        );
+        assert_eq!(
+            next("0 0 29 2 *", "2022-01-01T00:00:00Z"),
+            Some("2024-02-29T00:00:00+00:00".to_owned())
+        );
+        assert_eq!(next("0 0 31 2 *", "2022-01-01T00:00:00Z"), None);
+    }
+
+    #[test]
+    fn matches_either_restricted_day() {
+        // The 15th of January 2022 is a Saturday, and the 10th a Monday.
+        assert_eq!(
+            next("0 0 15 * 1", "2022-01-08T00:00:00Z"),
+            Some("2022-01-10T00:00:00+00:00".to_owned())
+        );
+        assert_eq!(
+            next("0 0 15 * 7", "2022-01-10T00:00:00Z"),
+            Some("2022-01-15T00:00:00+00:00".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn shares_schedules() {
+        let schedule = CronSchedule::parse("0 0 1 1 *").unwrap();
+        let _first = ticks(&schedule);
+        let _second = ticks(&schedule);
+
+        let schedules = SCHEDULES.lock().unwrap();
+        assert_eq!(schedules[&schedule].sender.receiver_count(), 2);
+    }
+}
@@ -46,12 +46,13 @@ pub struct CloudwatchLogsSinkConfig {
 
 impl CloudwatchLogsSinkConfig {
     pub fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<CloudWatchLogsClient> {
+        let encoding = self.compression.try_into()?;
         let region = (&self.region).try_into()?;
 
         let client = rusoto::client(proxy)?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client = rusoto_core::Client::new_with_encoding(creds, client, encoding);
         Ok(CloudWatchLogsClient::new_with_client(client, region))
     }
 }
@@ -61,6 +62,7 @@ impl CloudwatchLogsSinkConfig {
 impl SinkConfig for CloudwatchLogsSinkConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
         let batcher_settings = self.batch.into_batcher_settings()?;
+        self.request.reject_status_overrides()?;
         let request = self.request.unwrap_with(&TowerRequestConfig::default());
         let client = self.create_client(cx.proxy())?;
         let svc = request.service(
@@ -112,6 +112,8 @@ impl CloudWatchMetricsSinkConfig {
     }
 
     fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<CloudWatchClient> {
+        let encoding = self.compression.try_into()?;
+
         let region = (&self.region).try_into()?;
         let region = if cfg!(test) {
             // Moto (used for mocking AWS) doesn't recognize 'custom' as valid region name
@@ -129,7 +131,7 @@ impl CloudWatchMetricsSinkConfig {
         let client = rusoto::client(proxy)?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client = rusoto_core::Client::new_with_encoding(creds, client, encoding);
         Ok(CloudWatchClient::new_with_client(client, region))
     }
 }
@@ -142,6 +144,7 @@ impl CloudWatchMetricsSvc {
     ) -> crate::Result<super::VectorSink> {
         let default_namespace = config.default_namespace.clone();
         let batch = config.batch.into_batch_settings()?;
+        config.request.reject_status_overrides()?;
         let request = config.request.unwrap_with(&TowerRequestConfig {
             timeout_secs: Some(30),
             rate_limit_num: Some(150),
@@ -153,7 +156,8 @@ impl CloudWatchMetricsSvc {
         let svc = request.service(CloudWatchMetricsRetryLogic, cloudwatch_metrics);
 
         let buffer = PartitionBuffer::new(MetricsBuffer::new(batch.size));
-        let mut normalizer = MetricNormalizer::<AwsCloudwatchMetricNormalize>::default();
+        let mut normalizer = MetricNormalizer::<AwsCloudwatchMetricNormalize>::default()
+            .with_expiration(cx.expire_metrics());
 
         let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
             .sink_map_err(|error| error!(message = "Fatal CloudwatchMetrics sink error.", %error))
@@ -110,6 +110,7 @@ impl SinkConfig for KinesisFirehoseSinkConfig {
             .limit_max_events(MAX_PAYLOAD_EVENTS)?
             .into_batcher_settings()?;
 
+        self.request.reject_status_overrides()?;
         let request_limits = self.request.unwrap_with(&TowerRequestConfig::default());
 
         let region = self.region.clone().try_into()?;
@@ -168,12 +169,13 @@ impl KinesisFirehoseSinkConfig {
     }
 
     pub fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<KinesisFirehoseClient> {
+        let encoding = self.compression.try_into()?;
         let region = (&self.region).try_into()?;
 
         let client = rusoto::client(proxy)?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client = rusoto_core::Client::new_with_encoding(creds, client, encoding);
         Ok(KinesisFirehoseClient::new_with_client(client, region))
     }
 }
@@ -95,12 +95,13 @@ impl KinesisSinkConfig {
     }
 
     pub fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<KinesisClient> {
+        let encoding = self.compression.try_into()?;
         let region = (&self.region).try_into()?;
 
         let client = rusoto::client(proxy)?;
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
-        let client = rusoto_core::Client::new_with_encoding(creds, client, self.compression.into());
+        let client = rusoto_core::Client::new_with_encoding(creds, client, encoding);
         Ok(KinesisClient::new_with_client(client, region))
     }
 }
@@ -114,6 +115,7 @@ impl SinkConfig for KinesisSinkConfig {
 
         let batch_settings = self.batch.into_batcher_settings()?;
 
+        self.request.reject_status_overrides()?;
         let request_limits = self.request.unwrap_with(&TowerRequestConfig::default());
 
         let region = self.region.clone().try_into()?;
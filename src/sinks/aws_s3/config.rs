@@ -1,4 +1,4 @@
-use std::convert::TryInto;
+use std::{convert::TryInto, sync::Arc};
 
 use rusoto_s3::S3Client;
 use serde::{Deserialize, Serialize};
@@ -18,6 +18,7 @@ use crate::{
         },
         util::{
             encoding::{EncodingConfig, StandardEncodings},
+            object_storage::{ManifestConfig, Manifests, ObjectNaming},
             partitioner::KeyPartitioner,
             BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt,
             TowerRequestConfig,
@@ -38,6 +39,7 @@ pub struct S3SinkConfig {
     pub filename_time_format: Option<String>,
     pub filename_append_uuid: Option<bool>,
     pub filename_extension: Option<String>,
+    pub manifest: Option<ManifestConfig>,
     #[serde(flatten)]
     pub options: S3Options,
     #[serde(flatten)]
@@ -63,6 +65,7 @@ impl GenerateConfig for S3SinkConfig {
             filename_time_format: None,
             filename_append_uuid: None,
             filename_extension: None,
+            manifest: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
             encoding: StandardEncodings::Text.into(),
@@ -105,10 +108,15 @@ impl S3SinkConfig {
         // requests into in order to ship files to S3.  We build this here in
         // order to configure the client/service with retries, concurrency
         // limits, rate limits, and whatever else the client should have.
+        self.request.reject_status_overrides()?;
         let request_limits = self.request.unwrap_with(&Default::default());
+        let manifests = self
+            .manifest
+            .as_ref()
+            .map(|manifest| Arc::new(Manifests::new(manifest)));
         let service = ServiceBuilder::new()
             .settings(request_limits, S3RetryLogic)
-            .service(service);
+            .service(service.with_manifests(manifests));
 
         // Configure our partitioning/batching.
         let batch_settings = self.batch.into_batcher_settings()?;
@@ -134,8 +142,7 @@ impl S3SinkConfig {
             bucket: self.bucket.clone(),
             api_options: self.options.clone(),
             filename_extension: self.filename_extension.clone(),
-            filename_time_format,
-            filename_append_uuid,
+            filename_naming: ObjectNaming::new(filename_time_format, filename_append_uuid),
             encoding: self.encoding.clone(),
             compression: self.compression,
         };
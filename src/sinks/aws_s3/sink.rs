@@ -1,8 +1,6 @@
 use std::io;
 
 use bytes::Bytes;
-use chrono::Utc;
-use uuid::Uuid;
 use vector_core::{event::Finalizable, ByteSizeOf};
 
 use crate::{
@@ -14,6 +12,7 @@ use crate::{
         },
         util::{
             encoding::{EncodingConfig, StandardEncodings},
+            object_storage::ObjectNaming,
             Compression, RequestBuilder,
         },
     },
@@ -22,8 +21,7 @@ use crate::{
 #[derive(Clone)]
 pub struct S3RequestOptions {
     pub bucket: String,
-    pub filename_time_format: String,
-    pub filename_append_uuid: bool,
+    pub filename_naming: ObjectNaming,
     pub filename_extension: Option<String>,
     pub api_options: S3Options,
     pub encoding: EncodingConfig<StandardEncodings>,
@@ -60,13 +58,7 @@ impl RequestBuilder<(String, Vec<Event>)> for S3RequestOptions {
     }
 
     fn build_request(&self, mut metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
-        let filename = {
-            let formatted_ts = Utc::now().format(self.filename_time_format.as_str());
-
-            self.filename_append_uuid
-                .then(|| format!("{}-{}", formatted_ts, Uuid::new_v4().to_hyphenated()))
-                .unwrap_or_else(|| formatted_ts.to_string())
-        };
+        let filename = self.filename_naming.name();
 
         let extension = self
             .filename_extension
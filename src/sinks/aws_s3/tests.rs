@@ -24,7 +24,11 @@ mod integration_tests {
         sinks::{
             aws_s3::S3SinkConfig,
             s3_common::config::S3Options,
-            util::{encoding::StandardEncodings, BatchConfig, Compression, TowerRequestConfig},
+            util::{
+                encoding::StandardEncodings,
+                object_storage::{Manifest, ManifestConfig},
+                BatchConfig, Compression, TowerRequestConfig,
+            },
         },
         test_util::{random_lines_with_stream, random_string},
     };
@@ -199,6 +203,44 @@ mod integration_tests {
         assert_eq!(lines, response_lines);
     }
 
+    #[tokio::test]
+    async fn s3_writes_manifest() {
+        let cx = SinkContext::new_test();
+
+        let bucket = uuid::Uuid::new_v4().to_string();
+
+        create_bucket(&bucket, false).await;
+
+        let config = S3SinkConfig {
+            key_prefix: Some(format!("{}/date=%F/", random_string(10))),
+            manifest: Some(ManifestConfig::default()),
+            ..config(&bucket, 10)
+        };
+        let prefix = config.key_prefix.clone();
+        let service = config.create_service(&cx.globals.proxy).unwrap();
+        let sink = config.build_processor(service, cx).unwrap();
+
+        let (_lines, events, receiver) = make_events_batch(100, 30);
+        sink.run(events).await.unwrap();
+        assert_eq!(receiver.await, BatchStatus::Delivered);
+
+        let keys = get_keys(&bucket, prefix.unwrap()).await;
+        assert_eq!(keys.len(), 4);
+
+        let manifest_key = keys
+            .iter()
+            .find(|key| key.ends_with("/manifest.json"))
+            .unwrap()
+            .clone();
+        let body = get_object_output_body(get_object(&bucket, manifest_key).await).await;
+        let manifest: Manifest = serde_json::from_reader(body).unwrap();
+        assert_eq!(manifest.objects.len(), 3);
+        assert_eq!(manifest.total_events, 30);
+        for object in &manifest.objects {
+            assert!(keys.contains(&object.key));
+        }
+    }
+
     // NOTE: this test doesn't actually validate anything because localstack
     // doesn't enforce the required Content-MD5 header on the request for
     // buckets with object lock enabled
@@ -322,6 +364,7 @@ mod integration_tests {
             filename_time_format: None,
             filename_append_uuid: None,
             filename_extension: None,
+            manifest: None,
             options: S3Options::default(),
             region: RegionOrEndpoint::with_endpoint(s3_address()),
             encoding: StandardEncodings::Text.into(),
@@ -0,0 +1,126 @@
+use std::{convert::TryInto, num::NonZeroU64};
+
+use rusoto_core::Region;
+use serde::{Deserialize, Serialize};
+use tower::ServiceBuilder;
+use vector_core::sink::VectorSink;
+
+use super::{
+    ocsf::OcsfClass,
+    sink::{SecurityLakePartitioner, SecurityLakeRequestOptions, SecurityLakeSink},
+};
+use crate::{
+    aws::rusoto::{AwsAuthentication, RegionOrEndpoint},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext},
+    sinks::{
+        s3_common::{
+            self,
+            config::{S3Options, S3RetryLogic},
+        },
+        util::{BatchConfig, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig},
+        Healthcheck,
+    },
+};
+
+// Security Lake asks for custom sources to write objects of up to 256MB, at most every five
+// minutes; batches are kept smaller than that by default as they're held in memory.
+// https://docs.aws.amazon.com/security-lake/latest/userguide/custom-sources.html
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SecurityLakeDefaultBatchSettings;
+
+impl SinkBatchSettings for SecurityLakeDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(100_000_000);
+    const TIMEOUT_SECS: NonZeroU64 = unsafe { NonZeroU64::new_unchecked(300) };
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SecurityLakeSinkConfig {
+    /// The Security Lake bucket, of the form `aws-security-data-lake-<region>-<id>`.
+    pub bucket: String,
+    /// The name the custom source was registered with.
+    pub source_name: String,
+    /// The account the events are from, used to partition them.
+    pub account_id: String,
+    /// The OCSF class the custom source was registered with. Events of any other class are
+    /// rejected.
+    pub ocsf_class: OcsfClass,
+    #[serde(flatten)]
+    pub region: RegionOrEndpoint,
+    #[serde(default)]
+    pub batch: BatchConfig<SecurityLakeDefaultBatchSettings>,
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+    #[serde(default)]
+    pub auth: AwsAuthentication,
+}
+
+impl GenerateConfig for SecurityLakeSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"bucket = "aws-security-data-lake-us-east-1-abcdefghijklmnopqrstuvwxyz1234"
+            source_name = "my-source"
+            account_id = "111122223333"
+            ocsf_class = "authentication"
+            region = "us-east-1""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_security_lake")]
+impl SinkConfig for SecurityLakeSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let service = s3_common::config::create_service(&self.region, &self.auth, None, &cx.proxy)?;
+        let healthcheck =
+            s3_common::config::build_healthcheck(self.bucket.clone(), service.client())?;
+
+        self.request.reject_status_overrides()?;
+        let request_limits = self.request.unwrap_with(&Default::default());
+        let service = ServiceBuilder::new()
+            .settings(request_limits, S3RetryLogic)
+            .service(service);
+
+        let region: Region = (&self.region).try_into()?;
+        let partitioner =
+            SecurityLakePartitioner::new(&self.source_name, region.name(), &self.account_id);
+        let request_options = SecurityLakeRequestOptions {
+            bucket: self.bucket.clone(),
+            api_options: S3Options {
+                content_type: Some("application/octet-stream".into()),
+                ..Default::default()
+            },
+        };
+
+        let sink = SecurityLakeSink::new(
+            cx,
+            service,
+            request_options,
+            partitioner,
+            self.batch.into_batcher_settings()?,
+            self.ocsf_class,
+        );
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "aws_security_lake"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SecurityLakeSinkConfig;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SecurityLakeSinkConfig>();
+    }
+}
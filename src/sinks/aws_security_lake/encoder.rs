@@ -0,0 +1,360 @@
+//! Encodes batches of events as Parquet files.
+//!
+//! The schema is inferred from the events in each batch, so every file is self-describing. Maps
+//! become groups and scalars become columns of the matching type; a field seen with types that
+//! don't agree within a batch is written as a string. Arrays are written as JSON strings, as
+//! writing repeated columns isn't supported.
+
+use std::{collections::BTreeMap, io, sync::Arc};
+
+use parquet::{
+    basic::{Compression, ConvertedType, Repetition, Type as PhysicalType},
+    column::writer::ColumnWriter,
+    data_type::ByteArray,
+    errors::ParquetError,
+    file::{properties::WriterProperties, writer::SerializedFileWriter},
+    schema::types::{Type, TypePtr},
+};
+
+use crate::{
+    event::{Event, LogEvent, Value},
+    sinks::util::encoding::Encoder,
+};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum LeafType {
+    Boolean,
+    Integer,
+    Float,
+    Timestamp,
+    String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Column {
+    Leaf(LeafType),
+    Group(BTreeMap<String, Column>),
+}
+
+impl Column {
+    /// The column a value would be written to, or `None` if there's nothing to write.
+    fn infer(value: &Value) -> Option<Self> {
+        match value {
+            Value::Boolean(_) => Some(Self::Leaf(LeafType::Boolean)),
+            Value::Integer(_) => Some(Self::Leaf(LeafType::Integer)),
+            Value::Float(_) => Some(Self::Leaf(LeafType::Float)),
+            Value::Timestamp(_) => Some(Self::Leaf(LeafType::Timestamp)),
+            // Decimals are written as their exact text rather than a lossy double.
+            Value::Bytes(_) | Value::Decimal(_) | Value::Array(_) => {
+                Some(Self::Leaf(LeafType::String))
+            }
+            Value::Map(fields) => {
+                let fields = infer_fields(fields);
+                // Parquet doesn't allow groups without any columns.
+                (!fields.is_empty()).then(|| Self::Group(fields))
+            }
+            Value::Null => None,
+        }
+    }
+
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Leaf(a), Self::Leaf(b)) if a == b => Self::Leaf(a),
+            (Self::Leaf(LeafType::Integer), Self::Leaf(LeafType::Float))
+            | (Self::Leaf(LeafType::Float), Self::Leaf(LeafType::Integer)) => {
+                Self::Leaf(LeafType::Float)
+            }
+            (Self::Group(a), Self::Group(b)) => Self::Group(merge_fields(a, b)),
+            _ => Self::Leaf(LeafType::String),
+        }
+    }
+
+    /// Append the leaf columns under this one to `leaves`, depth first, which is the order
+    /// Parquet lays them out in.
+    fn leaves(&self, path: &mut Vec<String>, leaves: &mut Vec<(Vec<String>, LeafType)>) {
+        match self {
+            Self::Leaf(leaf) => leaves.push((path.clone(), *leaf)),
+            Self::Group(fields) => {
+                for (name, column) in fields {
+                    path.push(name.clone());
+                    column.leaves(path, leaves);
+                    path.pop();
+                }
+            }
+        }
+    }
+
+    fn parquet_type(&self, name: &str) -> Result<TypePtr, ParquetError> {
+        let parquet_type = match self {
+            Self::Leaf(leaf) => {
+                let (physical_type, converted_type) = match leaf {
+                    LeafType::Boolean => (PhysicalType::BOOLEAN, ConvertedType::NONE),
+                    LeafType::Integer => (PhysicalType::INT64, ConvertedType::NONE),
+                    LeafType::Float => (PhysicalType::DOUBLE, ConvertedType::NONE),
+                    LeafType::Timestamp => (PhysicalType::INT64, ConvertedType::TIMESTAMP_MILLIS),
+                    LeafType::String => (PhysicalType::BYTE_ARRAY, ConvertedType::UTF8),
+                };
+                Type::primitive_type_builder(name, physical_type)
+                    .with_repetition(Repetition::OPTIONAL)
+                    .with_converted_type(converted_type)
+                    .build()?
+            }
+            Self::Group(fields) => Type::group_type_builder(name)
+                .with_repetition(Repetition::OPTIONAL)
+                .with_fields(&mut group_fields(fields)?)
+                .build()?,
+        };
+        Ok(Arc::new(parquet_type))
+    }
+}
+
+fn infer_fields(fields: &BTreeMap<String, Value>) -> BTreeMap<String, Column> {
+    fields
+        .iter()
+        .filter_map(|(name, value)| Some((name.clone(), Column::infer(value)?)))
+        .collect()
+}
+
+fn merge_fields(
+    mut a: BTreeMap<String, Column>,
+    b: BTreeMap<String, Column>,
+) -> BTreeMap<String, Column> {
+    for (name, column) in b {
+        let column = match a.remove(&name) {
+            Some(existing) => existing.merge(column),
+            None => column,
+        };
+        a.insert(name, column);
+    }
+    a
+}
+
+fn group_fields(fields: &BTreeMap<String, Column>) -> Result<Vec<TypePtr>, ParquetError> {
+    fields
+        .iter()
+        .map(|(name, column)| column.parquet_type(name))
+        .collect()
+}
+
+enum ColumnValues {
+    Boolean(Vec<bool>),
+    Int64(Vec<i64>),
+    Double(Vec<f64>),
+    ByteArray(Vec<ByteArray>),
+}
+
+impl ColumnValues {
+    const fn new(leaf: LeafType) -> Self {
+        match leaf {
+            LeafType::Boolean => Self::Boolean(Vec::new()),
+            LeafType::Integer | LeafType::Timestamp => Self::Int64(Vec::new()),
+            LeafType::Float => Self::Double(Vec::new()),
+            LeafType::String => Self::ByteArray(Vec::new()),
+        }
+    }
+
+    /// Add a value, returning false if it can't be converted to the column's type.
+    fn push(&mut self, value: &Value) -> bool {
+        match (self, value) {
+            (_, Value::Null) => return false,
+            (Self::Boolean(values), Value::Boolean(value)) => values.push(*value),
+            (Self::Int64(values), Value::Integer(value)) => values.push(*value),
+            (Self::Int64(values), Value::Timestamp(value)) => values.push(value.timestamp_millis()),
+            (Self::Double(values), Value::Float(value)) => values.push(*value),
+            (Self::Double(values), Value::Integer(value)) => values.push(*value as f64),
+            (Self::ByteArray(values), Value::Bytes(value)) => {
+                values.push(ByteArray::from(value.to_vec()))
+            }
+            (Self::ByteArray(values), value) => values.push(ByteArray::from(
+                serde_json::to_vec(value).expect("values are serializable"),
+            )),
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// The values of a leaf column across the events, along with the definition levels saying how
+/// far down its path each event got. Every field is optional, so an event with the value gets
+/// the full depth of the path.
+fn column_values(logs: &[LogEvent], path: &[String], leaf: LeafType) -> (ColumnValues, Vec<i16>) {
+    let mut values = ColumnValues::new(leaf);
+    let mut definition_levels = Vec::with_capacity(logs.len());
+
+    for log in logs {
+        let mut fields = log.as_map();
+        let mut level = 0;
+        for (depth, name) in path.iter().enumerate() {
+            let is_leaf = depth + 1 == path.len();
+            match fields.get(name) {
+                Some(Value::Map(inner)) if !is_leaf => {
+                    fields = inner;
+                    level += 1;
+                }
+                Some(value) if is_leaf => {
+                    if values.push(value) {
+                        level += 1;
+                    }
+                }
+                _ => break,
+            }
+        }
+        definition_levels.push(level);
+    }
+
+    (values, definition_levels)
+}
+
+pub fn encode_parquet(logs: &[LogEvent]) -> Result<Vec<u8>, ParquetError> {
+    let fields = logs
+        .iter()
+        .map(|log| infer_fields(log.as_map()))
+        .reduce(merge_fields)
+        .unwrap_or_default();
+
+    let schema = Arc::new(
+        Type::group_type_builder("schema")
+            .with_fields(&mut group_fields(&fields)?)
+            .build()?,
+    );
+    let properties = Arc::new(
+        WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build(),
+    );
+
+    let mut leaves = Vec::new();
+    Column::Group(fields).leaves(&mut Vec::new(), &mut leaves);
+
+    let mut buffer = Vec::new();
+    let mut writer = SerializedFileWriter::new(&mut buffer, schema, properties)?;
+    let mut row_group = writer.next_row_group()?;
+    for (path, leaf) in leaves {
+        let (values, definition_levels) = column_values(logs, &path, leaf);
+        let mut column = row_group
+            .next_column()?
+            .expect("the schema has a column for each leaf");
+        let definition_levels = Some(definition_levels.as_slice());
+        match (column.untyped(), values) {
+            (ColumnWriter::BoolColumnWriter(writer), ColumnValues::Boolean(values)) => {
+                writer.write_batch(&values, definition_levels, None)?;
+            }
+            (ColumnWriter::Int64ColumnWriter(writer), ColumnValues::Int64(values)) => {
+                writer.write_batch(&values, definition_levels, None)?;
+            }
+            (ColumnWriter::DoubleColumnWriter(writer), ColumnValues::Double(values)) => {
+                writer.write_batch(&values, definition_levels, None)?;
+            }
+            (ColumnWriter::ByteArrayColumnWriter(writer), ColumnValues::ByteArray(values)) => {
+                writer.write_batch(&values, definition_levels, None)?;
+            }
+            _ => unreachable!("column writers match the schema they were built from"),
+        }
+        column.close()?;
+    }
+    row_group.close()?;
+    writer.close()?;
+
+    Ok(buffer)
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ParquetEncoder;
+
+impl Encoder<Vec<Event>> for ParquetEncoder {
+    fn encode_input(&self, events: Vec<Event>, writer: &mut dyn io::Write) -> io::Result<usize> {
+        let logs = events.into_iter().map(Event::into_log).collect::<Vec<_>>();
+        let buffer =
+            encode_parquet(&logs).map_err(|error| io::Error::new(io::ErrorKind::Other, error))?;
+        writer.write_all(&buffer)?;
+        Ok(buffer.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    use super::*;
+
+    fn log(json: &str) -> LogEvent {
+        serde_json::from_str::<serde_json::Value>(json)
+            .unwrap()
+            .try_into()
+            .unwrap()
+    }
+
+    #[test]
+    fn infers_columns() {
+        let a = log(r#"{"class_uid": 3002, "ratio": 1, "user": {"name": "alice"}, "tags": ["a"]}"#);
+        let b = log(r#"{"class_uid": 3002, "ratio": 0.5, "user": "bob", "empty": {}}"#);
+
+        let fields = merge_fields(infer_fields(a.as_map()), infer_fields(b.as_map()));
+        assert_eq!(
+            fields,
+            vec![
+                ("class_uid".into(), Column::Leaf(LeafType::Integer)),
+                ("ratio".into(), Column::Leaf(LeafType::Float)),
+                ("tags".into(), Column::Leaf(LeafType::String)),
+                ("user".into(), Column::Leaf(LeafType::String)),
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn writes_parquet() {
+        let logs = vec![
+            log(
+                r#"{"class_uid": 3002, "metadata": {"product": {"name": "My App"}, "version": "1.0.0"}}"#,
+            ),
+            log(
+                r#"{"class_uid": 3002, "metadata": {"version": "1.0.0"}, "user": {"name": "alice"}}"#,
+            ),
+        ];
+
+        let buffer = encode_parquet(&logs).unwrap();
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(&buffer).unwrap();
+
+        let reader = SerializedFileReader::new(file).unwrap();
+        let metadata = reader.metadata().file_metadata();
+        assert_eq!(metadata.num_rows(), 2);
+        assert_eq!(
+            metadata
+                .schema_descr()
+                .columns()
+                .iter()
+                .map(|column| column.path().string())
+                .collect::<Vec<_>>(),
+            vec![
+                "class_uid",
+                "metadata.product.name",
+                "metadata.version",
+                "user.name"
+            ]
+        );
+    }
+
+    #[test]
+    fn levels_follow_paths() {
+        let logs = vec![
+            log(r#"{"user": {"name": "alice"}}"#),
+            log(r#"{"user": {}}"#),
+            log(r#"{}"#),
+        ];
+        let (values, definition_levels) =
+            column_values(&logs, &["user".into(), "name".into()], LeafType::String);
+
+        assert_eq!(definition_levels, vec![2, 1, 0]);
+        match values {
+            ColumnValues::ByteArray(values) => {
+                assert_eq!(values, vec![ByteArray::from(b"alice".to_vec())])
+            }
+            _ => panic!("expected a byte array column"),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Writes OCSF events to an Amazon Security Lake custom source as Parquet files.
+//!
+//! https://docs.aws.amazon.com/security-lake/latest/userguide/custom-sources.html
+
+use crate::config::SinkDescription;
+
+mod config;
+mod encoder;
+mod ocsf;
+mod sink;
+
+use self::config::SecurityLakeSinkConfig;
+pub use self::ocsf::ValidationError;
+
+inventory::submit! {
+    SinkDescription::new::<SecurityLakeSinkConfig>("aws_security_lake")
+}
@@ -0,0 +1,184 @@
+//! The subset of the Open Cybersecurity Schema Framework (OCSF) needed to check that events are
+//! of the class a Security Lake custom source was registered with.
+//!
+//! https://schema.ocsf.io/
+
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+use crate::event::{LogEvent, Value};
+
+/// The attributes every OCSF event class requires.
+const BASE_REQUIRED_ATTRIBUTES: &[&str] = &[
+    "activity_id",
+    "category_uid",
+    "class_uid",
+    "metadata.product",
+    "metadata.version",
+    "severity_id",
+    "time",
+    "type_uid",
+];
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OcsfClass {
+    FileActivity,
+    ProcessActivity,
+    SecurityFinding,
+    AccountChange,
+    Authentication,
+    NetworkActivity,
+    HttpActivity,
+    DnsActivity,
+    ApiActivity,
+}
+
+impl OcsfClass {
+    pub const fn class_uid(self) -> i64 {
+        match self {
+            Self::FileActivity => 1001,
+            Self::ProcessActivity => 1007,
+            Self::SecurityFinding => 2001,
+            Self::AccountChange => 3001,
+            Self::Authentication => 3002,
+            Self::NetworkActivity => 4001,
+            Self::HttpActivity => 4002,
+            Self::DnsActivity => 4003,
+            Self::ApiActivity => 6003,
+        }
+    }
+
+    /// Classes are numbered within their category, so the category is the leading digit.
+    pub const fn category_uid(self) -> i64 {
+        self.class_uid() / 1000
+    }
+
+    /// The attributes this class requires on top of the base ones.
+    const fn required_attributes(self) -> &'static [&'static str] {
+        match self {
+            Self::FileActivity => &["actor", "file"],
+            Self::ProcessActivity => &["actor", "process"],
+            Self::SecurityFinding => &["finding", "state_id"],
+            Self::AccountChange => &["user"],
+            Self::Authentication => &["user"],
+            Self::NetworkActivity => &["dst_endpoint", "src_endpoint"],
+            Self::HttpActivity => &["dst_endpoint", "http_request"],
+            Self::DnsActivity => &["query"],
+            Self::ApiActivity => &["actor", "api", "src_endpoint"],
+        }
+    }
+
+    /// Check that an event belongs to this class and has every attribute the class requires.
+    pub fn validate(self, log: &LogEvent) -> Result<(), ValidationError> {
+        for (attribute, expected) in [
+            ("class_uid", self.class_uid()),
+            ("category_uid", self.category_uid()),
+        ] {
+            match log.get(attribute) {
+                Some(Value::Integer(value)) if *value == expected => {}
+                None | Some(Value::Null) => {
+                    return Err(ValidationError::MissingAttribute { attribute })
+                }
+                Some(value) => {
+                    return Err(ValidationError::WrongClass {
+                        attribute,
+                        expected,
+                        actual: value.to_string_lossy(),
+                    })
+                }
+            }
+        }
+
+        BASE_REQUIRED_ATTRIBUTES
+            .iter()
+            .chain(self.required_attributes())
+            .find(|attribute| matches!(log.get(**attribute), None | Some(Value::Null)))
+            .map_or(Ok(()), |attribute| {
+                Err(ValidationError::MissingAttribute { attribute })
+            })
+    }
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+pub enum ValidationError {
+    #[snafu(display("Missing required attribute {:?}", attribute))]
+    MissingAttribute { attribute: &'static str },
+    #[snafu(display("Expected {:?} to be {}, but it was {}", attribute, expected, actual))]
+    WrongClass {
+        attribute: &'static str,
+        expected: i64,
+        actual: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authentication_event() -> LogEvent {
+        serde_json::from_str::<serde_json::Value>(
+            r#"{
+                "activity_id": 1,
+                "category_uid": 3,
+                "class_uid": 3002,
+                "metadata": {"product": {"name": "My App", "vendor_name": "Acme"}, "version": "1.0.0"},
+                "severity_id": 1,
+                "time": 1646092800000,
+                "type_uid": 300201,
+                "user": {"name": "alice"}
+            }"#,
+        )
+        .unwrap()
+        .try_into()
+        .unwrap()
+    }
+
+    #[test]
+    fn accepts_valid_events() {
+        assert_eq!(
+            OcsfClass::Authentication.validate(&authentication_event()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn rejects_events_of_other_classes() {
+        assert_eq!(
+            OcsfClass::ApiActivity.validate(&authentication_event()),
+            Err(ValidationError::WrongClass {
+                attribute: "class_uid",
+                expected: 6003,
+                actual: "3002".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_events_missing_attributes() {
+        let mut log = authentication_event();
+        log.remove("user");
+        assert_eq!(
+            OcsfClass::Authentication.validate(&log),
+            Err(ValidationError::MissingAttribute { attribute: "user" })
+        );
+
+        let mut log = authentication_event();
+        log.remove("metadata.version");
+        assert_eq!(
+            OcsfClass::Authentication.validate(&log),
+            Err(ValidationError::MissingAttribute {
+                attribute: "metadata.version"
+            })
+        );
+
+        let mut log = authentication_event();
+        log.remove("class_uid");
+        assert_eq!(
+            OcsfClass::Authentication.validate(&log),
+            Err(ValidationError::MissingAttribute {
+                attribute: "class_uid"
+            })
+        );
+    }
+}
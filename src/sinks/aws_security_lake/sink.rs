@@ -0,0 +1,241 @@
+use std::{fmt, io, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{future, stream::BoxStream, StreamExt};
+use tower::Service;
+use uuid::Uuid;
+use vector_core::{
+    buffers::Acker,
+    event::{EventStatus, Finalizable},
+    partition::Partitioner,
+    sink::StreamSink,
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf,
+};
+
+use super::{encoder::ParquetEncoder, ocsf::OcsfClass};
+use crate::{
+    config::SinkContext,
+    event::{Event, LogEvent, Value},
+    internal_events::AwsSecurityLakeEventRejected,
+    sinks::{
+        s3_common::{
+            config::S3Options,
+            service::{S3Metadata, S3Request},
+        },
+        util::{Compression, RequestBuilder, SinkBuilderExt},
+    },
+};
+
+/// Partitions events the way Security Lake expects custom sources to lay out their objects:
+/// `ext/<source name>/region=<region>/accountId=<account id>/eventDay=<yyyymmdd>/`.
+pub struct SecurityLakePartitioner {
+    prefix: String,
+}
+
+impl SecurityLakePartitioner {
+    pub fn new(source_name: &str, region: &str, account_id: &str) -> Self {
+        Self {
+            prefix: format!(
+                "ext/{}/region={}/accountId={}/",
+                source_name, region, account_id
+            ),
+        }
+    }
+}
+
+impl Partitioner for SecurityLakePartitioner {
+    type Item = Event;
+    type Key = String;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        format!(
+            "{}eventDay={}/",
+            self.prefix,
+            event_time(item.as_log()).format("%Y%m%d")
+        )
+    }
+}
+
+/// The OCSF `time` of an event, which is in milliseconds since the epoch.
+fn event_time(log: &LogEvent) -> DateTime<Utc> {
+    match log.get("time") {
+        Some(Value::Integer(millis)) => Utc.timestamp_millis_opt(*millis).single(),
+        Some(Value::Timestamp(timestamp)) => Some(*timestamp),
+        _ => None,
+    }
+    .unwrap_or_else(Utc::now)
+}
+
+#[derive(Clone)]
+pub struct SecurityLakeRequestOptions {
+    pub bucket: String,
+    pub api_options: S3Options,
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for SecurityLakeRequestOptions {
+    type Metadata = S3Metadata;
+    type Events = Vec<Event>;
+    type Encoder = ParquetEncoder;
+    type Payload = Bytes;
+    type Request = S3Request;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        // Parquet compresses each column itself.
+        Compression::None
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &ParquetEncoder
+    }
+
+    fn split_input(&self, input: (String, Vec<Event>)) -> (Self::Metadata, Self::Events) {
+        let (partition_key, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let metadata = S3Metadata {
+            partition_key,
+            count: events.len(),
+            byte_size: events.size_of(),
+            finalizers,
+        };
+
+        (metadata, events)
+    }
+
+    fn build_request(&self, mut metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
+        metadata.partition_key = format!(
+            "{}{}-{}.parquet",
+            metadata.partition_key,
+            Utc::now().timestamp(),
+            Uuid::new_v4().to_hyphenated()
+        );
+
+        trace!(
+            message = "Sending events.",
+            bytes = ?payload.len(),
+            events_len = ?metadata.count,
+            bucket = ?self.bucket,
+            key = ?metadata.partition_key
+        );
+
+        S3Request {
+            body: payload,
+            bucket: self.bucket.clone(),
+            metadata,
+            content_encoding: None,
+            options: self.api_options.clone(),
+        }
+    }
+}
+
+pub struct SecurityLakeSink<Svc> {
+    acker: Acker,
+    service: Svc,
+    request_builder: SecurityLakeRequestOptions,
+    partitioner: SecurityLakePartitioner,
+    batcher_settings: BatcherSettings,
+    ocsf_class: OcsfClass,
+}
+
+impl<Svc> SecurityLakeSink<Svc> {
+    pub fn new(
+        cx: SinkContext,
+        service: Svc,
+        request_builder: SecurityLakeRequestOptions,
+        partitioner: SecurityLakePartitioner,
+        batcher_settings: BatcherSettings,
+        ocsf_class: OcsfClass,
+    ) -> Self {
+        Self {
+            acker: cx.acker(),
+            service,
+            request_builder,
+            partitioner,
+            batcher_settings,
+            ocsf_class,
+        }
+    }
+}
+
+impl<Svc> SecurityLakeSink<Svc>
+where
+    Svc: Service<S3Request> + Send + 'static,
+    Svc::Future: Send + 'static,
+    Svc::Response: DriverResponse + Send + 'static,
+    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let Self {
+            acker,
+            service,
+            request_builder,
+            partitioner,
+            batcher_settings,
+            ocsf_class,
+        } = *self;
+
+        let builder_limit = NonZeroUsize::new(64);
+        let rejected_acker = acker.clone();
+
+        input
+            .filter_map(move |mut event| {
+                let event = match ocsf_class.validate(event.as_log()) {
+                    Ok(()) => Some(event),
+                    Err(error) => {
+                        emit!(&AwsSecurityLakeEventRejected { error: &error });
+                        event.take_finalizers().update_status(EventStatus::Rejected);
+                        rejected_acker.ack(1);
+                        None
+                    }
+                };
+                future::ready(event)
+            })
+            .batched_partitioned(partitioner, batcher_settings)
+            .request_builder(builder_limit, request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(error) => {
+                        error!("Failed to build Security Lake request: {:?}.", error);
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(service, acker)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<Svc> StreamSink<Event> for SecurityLakeSink<Svc>
+where
+    Svc: Service<S3Request> + Send + 'static,
+    Svc::Future: Send + 'static,
+    Svc::Response: DriverResponse + Send + 'static,
+    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partitions_by_event_day() {
+        let partitioner = SecurityLakePartitioner::new("my-source", "us-east-1", "111122223333");
+
+        let mut log = LogEvent::default();
+        log.insert("time", 1_646_179_199_000_i64);
+        assert_eq!(
+            partitioner.partition(&log.into()),
+            "ext/my-source/region=us-east-1/accountId=111122223333/eventDay=20220301/"
+        );
+    }
+}
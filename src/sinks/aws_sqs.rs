@@ -167,6 +167,7 @@ impl SqsSink {
         let batch: BatchConfig<SqsSinkDefaultBatchSettings> = BatchConfig::default();
         let batch_settings = batch.into_batch_settings()?;
 
+        config.request.reject_status_overrides()?;
         let request = config.request.unwrap_with(&TowerRequestConfig {
             timeout_secs: Some(30),
             ..Default::default()
@@ -1,11 +1,10 @@
-use std::{convert::TryInto, io, sync::Arc};
+use std::{convert::TryInto, io, sync::Arc, time::Duration};
 
 use azure_storage::blob::prelude::*;
 use bytes::Bytes;
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
 use tower::ServiceBuilder;
-use uuid::Uuid;
 use vector_core::ByteSizeOf;
 
 use crate::{
@@ -14,12 +13,14 @@ use crate::{
     sinks::{
         azure_common::{
             self,
+            append::AppendBlobs,
             config::{AzureBlobMetadata, AzureBlobRequest, AzureBlobRetryLogic},
             service::AzureBlobService,
             sink::AzureBlobSink,
         },
         util::{
             encoding::{EncodingConfig, StandardEncodings},
+            object_storage::{ManifestConfig, Manifests, ObjectNaming},
             partitioner::KeyPartitioner,
             BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder,
             ServiceBuilderExt, TowerRequestConfig,
@@ -37,6 +38,15 @@ pub struct AzureBlobSinkConfig {
     pub blob_prefix: Option<String>,
     pub blob_time_format: Option<String>,
     pub blob_append_uuid: Option<bool>,
+    #[serde(default)]
+    pub blob_type: BlobType,
+    /// A command printing a SAS token to authenticate with, instead of the credentials of the
+    /// connection string.
+    pub sas_token_command: Option<String>,
+    /// How often `sas_token_command` is run again, to pick up rotated tokens.
+    #[serde(default = "default_sas_token_refresh_secs")]
+    pub sas_token_refresh_secs: u64,
+    pub manifest: Option<ManifestConfig>,
     pub encoding: EncodingConfig<StandardEncodings>,
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
@@ -46,6 +56,34 @@ pub struct AzureBlobSinkConfig {
     pub request: TowerRequestConfig,
 }
 
+/// The type of the blobs batches are written to.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlobType {
+    /// Write every batch as a block blob of its own.
+    Block,
+    /// Append batches to the blob they're named after.
+    Append,
+}
+
+impl Default for BlobType {
+    fn default() -> Self {
+        Self::Block
+    }
+}
+
+const fn default_sas_token_refresh_secs() -> u64 {
+    3600
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`blob_append_uuid` can't be enabled with append blobs"))]
+    AppendBlobUuid,
+    #[snafu(display("`manifest` isn't supported with append blobs"))]
+    AppendBlobManifest,
+}
+
 impl GenerateConfig for AzureBlobSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
@@ -54,6 +92,10 @@ impl GenerateConfig for AzureBlobSinkConfig {
             blob_prefix: Some(String::from("blob")),
             blob_time_format: Some(String::from("%s")),
             blob_append_uuid: Some(true),
+            blob_type: BlobType::Block,
+            sas_token_command: None,
+            sas_token_refresh_secs: default_sas_token_refresh_secs(),
+            manifest: None,
             encoding: StandardEncodings::Ndjson.into(),
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
@@ -67,10 +109,15 @@ impl GenerateConfig for AzureBlobSinkConfig {
 #[typetag::serde(name = "azure_blob")]
 impl SinkConfig for AzureBlobSinkConfig {
     async fn build(&self, cx: SinkContext) -> Result<(VectorSink, Healthcheck)> {
-        let client = azure_common::config::build_client(
-            self.connection_string.clone(),
-            self.container_name.clone(),
-        )?;
+        let connection_string = match &self.sas_token_command {
+            Some(command) => {
+                let sas_token = azure_common::config::run_sas_token_command(command).await?;
+                azure_common::config::with_sas_token(&self.connection_string, &sas_token)
+            }
+            None => self.connection_string.clone(),
+        };
+        let client =
+            azure_common::config::build_client(connection_string, self.container_name.clone())?;
         let healthcheck = azure_common::config::build_healthcheck(
             self.container_name.clone(),
             Arc::clone(&client),
@@ -94,6 +141,7 @@ const DEFAULT_REQUEST_LIMITS: TowerRequestConfig =
 const DEFAULT_KEY_PREFIX: &str = "blob/%F/";
 const DEFAULT_FILENAME_TIME_FORMAT: &str = "%s";
 const DEFAULT_FILENAME_APPEND_UUID: bool = true;
+const DEFAULT_APPEND_BLOB_TIME_FORMAT: &str = "%FT%H";
 
 impl AzureBlobSinkConfig {
     pub fn build_processor(
@@ -101,27 +149,66 @@ impl AzureBlobSinkConfig {
         client: Arc<ContainerClient>,
         cx: SinkContext,
     ) -> crate::Result<VectorSink> {
+        self.request.reject_status_overrides()?;
         let request_limits = self.request.unwrap_with(&DEFAULT_REQUEST_LIMITS);
+        let manifests = self
+            .manifest
+            .as_ref()
+            .map(|manifest| Arc::new(Manifests::new(manifest)));
+
+        let (blob_naming, append_blobs) = match self.blob_type {
+            BlobType::Block => {
+                let blob_time_format = self
+                    .blob_time_format
+                    .as_ref()
+                    .cloned()
+                    .unwrap_or_else(|| DEFAULT_FILENAME_TIME_FORMAT.into());
+                let blob_append_uuid = self
+                    .blob_append_uuid
+                    .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
+                (ObjectNaming::new(blob_time_format, blob_append_uuid), None)
+            }
+            BlobType::Append => {
+                if self.blob_append_uuid == Some(true) {
+                    return Err(Box::new(BuildError::AppendBlobUuid));
+                }
+                if manifests.is_some() {
+                    return Err(Box::new(BuildError::AppendBlobManifest));
+                }
+                let blob_time_format = self
+                    .blob_time_format
+                    .as_ref()
+                    .cloned()
+                    .unwrap_or_else(|| DEFAULT_APPEND_BLOB_TIME_FORMAT.into());
+                (
+                    ObjectNaming::shared(blob_time_format),
+                    Some(Arc::new(AppendBlobs::default())),
+                )
+            }
+        };
+
+        let service = AzureBlobService::new(client)
+            .with_manifests(manifests)
+            .with_append_blobs(append_blobs);
+        if let Some(command) = &self.sas_token_command {
+            tokio::spawn(azure_common::config::refresh_sas_token(
+                self.connection_string.clone(),
+                self.container_name.clone(),
+                command.clone(),
+                Duration::from_secs(self.sas_token_refresh_secs.max(1)),
+                service.shared_client(),
+            ));
+        }
         let service = ServiceBuilder::new()
             .settings(request_limits, AzureBlobRetryLogic)
-            .service(AzureBlobService::new(client));
+            .service(service);
 
         // Configure our partitioning/batching.
         let batcher_settings = self.batch.into_batcher_settings()?;
 
-        let blob_time_format = self
-            .blob_time_format
-            .as_ref()
-            .cloned()
-            .unwrap_or_else(|| DEFAULT_FILENAME_TIME_FORMAT.into());
-        let blob_append_uuid = self
-            .blob_append_uuid
-            .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
-
         let request_options = AzureBlobRequestOptions {
             container_name: self.container_name.clone(),
-            blob_time_format,
-            blob_append_uuid,
+            blob_naming,
             encoding: self.encoding.clone(),
             compression: self.compression,
         };
@@ -151,8 +238,7 @@ impl AzureBlobSinkConfig {
 #[derive(Clone)]
 pub struct AzureBlobRequestOptions {
     pub container_name: String,
-    pub blob_time_format: String,
-    pub blob_append_uuid: bool,
+    pub blob_naming: ObjectNaming,
     pub encoding: EncodingConfig<StandardEncodings>,
     pub compression: Compression,
 }
@@ -187,13 +273,7 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
     }
 
     fn build_request(&self, mut metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
-        let blob_name = {
-            let formatted_ts = Utc::now().format(self.blob_time_format.as_str());
-
-            self.blob_append_uuid
-                .then(|| format!("{}-{}", formatted_ts, Uuid::new_v4().to_hyphenated()))
-                .unwrap_or_else(|| formatted_ts.to_string())
-        };
+        let blob_name = self.blob_naming.name();
 
         let extension = self.compression.extension();
         metadata.partition_key = format!("{}{}.{}", metadata.partition_key, blob_name, extension);
@@ -232,6 +312,10 @@ fn default_config(e: StandardEncodings) -> AzureBlobSinkConfig {
         blob_prefix: Default::default(),
         blob_time_format: Default::default(),
         blob_append_uuid: Default::default(),
+        blob_type: Default::default(),
+        sas_token_command: Default::default(),
+        sas_token_refresh_secs: default_sas_token_refresh_secs(),
+        manifest: Default::default(),
         encoding: e.into(),
         compression: Compression::gzip_default(),
         batch: Default::default(),
@@ -241,6 +325,7 @@ fn default_config(e: StandardEncodings) -> AzureBlobSinkConfig {
 
 #[cfg(test)]
 mod tests {
+    use chrono::Utc;
     use vector_core::partition::Partitioner;
 
     use super::*;
@@ -271,8 +356,7 @@ mod tests {
 
         let request_options = AzureBlobRequestOptions {
             container_name,
-            blob_time_format,
-            blob_append_uuid,
+            blob_naming: ObjectNaming::new(blob_time_format, blob_append_uuid),
             encoding: StandardEncodings::Text.into(),
             compression,
         };
@@ -280,7 +364,8 @@ mod tests {
         let (metadata, _events) = request_options.split_input((key, vec![log]));
         let request = request_options.build_request(metadata, Bytes::new());
 
-        assert_eq!(request.metadata.partition_key, "blob.log".to_string());
+        assert!(request.metadata.partition_key.starts_with("blob-"));
+        assert!(request.metadata.partition_key.ends_with(".log"));
         assert_eq!(request.content_encoding, None);
         assert_eq!(request.content_type, "text/plain");
     }
@@ -306,8 +391,7 @@ mod tests {
 
         let request_options = AzureBlobRequestOptions {
             container_name,
-            blob_time_format,
-            blob_append_uuid,
+            blob_naming: ObjectNaming::new(blob_time_format, blob_append_uuid),
             encoding: StandardEncodings::Text.into(),
             compression,
         };
@@ -315,7 +399,8 @@ mod tests {
         let (metadata, _events) = request_options.split_input((key, vec![log]));
         let request = request_options.build_request(metadata, Bytes::new());
 
-        assert_eq!(request.metadata.partition_key, "blob.log.gz".to_string());
+        assert!(request.metadata.partition_key.starts_with("blob-"));
+        assert!(request.metadata.partition_key.ends_with(".log.gz"));
         assert_eq!(request.content_encoding, Some("gzip"));
         assert_eq!(request.content_type, "application/gzip");
     }
@@ -341,8 +426,7 @@ mod tests {
 
         let request_options = AzureBlobRequestOptions {
             container_name,
-            blob_time_format,
-            blob_append_uuid,
+            blob_naming: ObjectNaming::new(blob_time_format, blob_append_uuid),
             encoding: StandardEncodings::Text.into(),
             compression,
         };
@@ -350,10 +434,10 @@ mod tests {
         let (metadata, _events) = request_options.split_input((key, vec![log]));
         let request = request_options.build_request(metadata, Bytes::new());
 
-        assert_eq!(
-            request.metadata.partition_key,
-            format!("blob{}.log", Utc::now().format("%F"))
-        );
+        assert!(request
+            .metadata
+            .partition_key
+            .starts_with(&format!("blob{}-", Utc::now().format("%F"))));
         assert_eq!(request.content_encoding, None);
         assert_eq!(request.content_type, "text/plain");
     }
@@ -379,8 +463,7 @@ mod tests {
 
         let request_options = AzureBlobRequestOptions {
             container_name,
-            blob_time_format,
-            blob_append_uuid,
+            blob_naming: ObjectNaming::new(blob_time_format, blob_append_uuid),
             encoding: StandardEncodings::Text.into(),
             compression,
         };
@@ -472,6 +555,30 @@ mod integration_tests {
         assert_eq!(lines, blob_lines);
     }
 
+    #[tokio::test]
+    async fn azure_blob_append_lines_to_blob() {
+        let blob_prefix = String::from("lines/append/blob");
+        let config = AzureBlobSinkConfig::new_emulator().await;
+        let mut config = AzureBlobSinkConfig {
+            blob_prefix: Some(blob_prefix.clone()),
+            blob_type: BlobType::Append,
+            ..config
+        };
+        config.batch.max_events = Some(10);
+        let sink = config.to_sink();
+        let (lines, input) = random_lines_with_stream(100, 100, None);
+
+        sink.run(input).await.expect("Failed to run sink");
+
+        // Every batch was appended to the same blob, named after the current hour.
+        let blobs = config.list_blobs(blob_prefix.as_str()).await;
+        assert_eq!(blobs.len(), 1);
+        assert!(blobs[0].clone().ends_with(".log"));
+        let (blob, blob_lines) = config.get_blob(blobs[0].clone()).await;
+        assert_eq!(blob.properties.content_type, String::from("text/plain"));
+        assert_eq!(lines, blob_lines);
+    }
+
     #[tokio::test]
     async fn azure_blob_insert_json_into_blob() {
         let blob_prefix = String::from("json/into/blob");
@@ -603,6 +710,10 @@ mod integration_tests {
                 blob_prefix: None,
                 blob_time_format: None,
                 blob_append_uuid: None,
+                blob_type: BlobType::Block,
+                sas_token_command: None,
+                sas_token_refresh_secs: default_sas_token_refresh_secs(),
+                manifest: None,
                 encoding: StandardEncodings::Text.into(),
                 compression: Compression::None,
                 batch: Default::default(),
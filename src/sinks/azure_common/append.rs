@@ -0,0 +1,239 @@
+//! Append blobs, which batches are appended to rather than written as blobs of their own.
+//!
+//! Azure limits append blobs to 50,000 blocks of at most 4 MiB each, so batches are split into
+//! blocks of that size, and once a blob is full the next batches go to a new blob, named after
+//! the original one with a `-1`, `-2`, ... suffix.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use azure_core::HttpError;
+use azure_storage::blob::prelude::*;
+use bytes::Bytes;
+use http::StatusCode;
+
+use crate::internal_events::azure_blob::AzureBlobAppendBlobFull;
+
+/// The largest block that can be appended to a blob.
+pub const MAX_BLOCK_SIZE: usize = 4 * 1024 * 1024;
+/// The most blocks an append blob can have.
+pub const MAX_BLOCKS: usize = 50_000;
+
+/// How long the state of a blob that isn't appended to is kept.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(3600);
+/// How many full blobs to move on from before giving up on a batch.
+const MAX_ROLL_OVERS: usize = 3;
+
+type Error = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug)]
+struct BlobState {
+    suffix: usize,
+    blocks: usize,
+    last_used: Instant,
+}
+
+/// The blobs being appended to, by the name batches are appended to.
+#[derive(Debug)]
+pub struct AppendBlobs {
+    max_blocks: usize,
+    blobs: Mutex<HashMap<String, BlobState>>,
+}
+
+impl Default for AppendBlobs {
+    fn default() -> Self {
+        Self::new(MAX_BLOCKS)
+    }
+}
+
+fn blob_name(name: &str, suffix: usize) -> String {
+    if suffix == 0 {
+        return name.to_owned();
+    }
+    // The suffix goes before the extension, so that the blob keeps it.
+    let (stem, extension) = match name.rfind('/') {
+        Some(slash) => match name[slash..].find('.') {
+            Some(dot) => name.split_at(slash + dot),
+            None => (name, ""),
+        },
+        None => match name.find('.') {
+            Some(dot) => name.split_at(dot),
+            None => (name, ""),
+        },
+    };
+    format!("{}-{}{}", stem, suffix, extension)
+}
+
+fn split_blocks(body: Bytes) -> Vec<Bytes> {
+    if body.is_empty() {
+        return Vec::new();
+    }
+    (0..body.len())
+        .step_by(MAX_BLOCK_SIZE)
+        .map(|start| body.slice(start..(start + MAX_BLOCK_SIZE).min(body.len())))
+        .collect()
+}
+
+/// Returns the status of the response `error` is about, if there is one.
+pub(super) fn status(error: &Error) -> Option<StatusCode> {
+    match error.downcast_ref::<HttpError>() {
+        Some(HttpError::UnexpectedStatusCode { received, .. }) => Some(*received),
+        _ => None,
+    }
+}
+
+impl AppendBlobs {
+    pub fn new(max_blocks: usize) -> Self {
+        Self {
+            max_blocks,
+            blobs: Default::default(),
+        }
+    }
+
+    /// Reserves room for `blocks` blocks in the blob `name` currently goes to, moving on to the
+    /// next blob if it doesn't have enough left, and returns the name of that blob.
+    fn reserve(&self, name: &str, blocks: usize, now: Instant) -> String {
+        let mut blobs = self.blobs.lock().expect("poisoned lock");
+        blobs.retain(|key, blob| key == name || now.duration_since(blob.last_used) < IDLE_TIMEOUT);
+
+        let blob = blobs.entry(name.to_owned()).or_insert(BlobState {
+            suffix: 0,
+            blocks: 0,
+            last_used: now,
+        });
+        if blob.blocks > 0 && blob.blocks + blocks > self.max_blocks {
+            emit!(&AzureBlobAppendBlobFull {
+                blob: &blob_name(name, blob.suffix),
+            });
+            blob.suffix += 1;
+            blob.blocks = 0;
+        }
+        blob.blocks += blocks;
+        blob.last_used = now;
+
+        blob_name(name, blob.suffix)
+    }
+
+    /// Moves `name` on to the blob after `full`, which Azure reported to be full, unless it has
+    /// already moved on.
+    fn roll_over(&self, name: &str, full: &str) {
+        let mut blobs = self.blobs.lock().expect("poisoned lock");
+        if let Some(blob) = blobs.get_mut(name) {
+            if blob_name(name, blob.suffix) == full {
+                emit!(&AzureBlobAppendBlobFull { blob: full });
+                blob.suffix += 1;
+                blob.blocks = 0;
+            }
+        }
+    }
+
+    /// Appends `body` to the blob `name` currently goes to, creating the blob if it doesn't exist
+    /// yet, and returns the ID of the last request.
+    pub async fn append(
+        &self,
+        client: &Arc<ContainerClient>,
+        name: &str,
+        body: Bytes,
+        content_type: &'static str,
+        content_encoding: Option<&'static str>,
+    ) -> Result<uuid::Uuid, Error> {
+        let blocks = split_blocks(body);
+        // Besides the blob being full, a blob created by an earlier Vector process can already be
+        // full, so move on until there's one with room left.
+        let mut roll_overs = 0;
+        loop {
+            let blob = self.reserve(name, blocks.len(), Instant::now());
+            let blob_client = Arc::clone(client).as_blob_client(blob.as_str());
+
+            match append_blocks(&blob_client, &blocks).await {
+                Ok(request_id) => return Ok(request_id),
+                Err(error) if status(&error) == Some(StatusCode::NOT_FOUND) => {
+                    let create = blob_client.put_append_blob().content_type(content_type);
+                    let create = match content_encoding {
+                        Some(encoding) => create.content_encoding(encoding),
+                        None => create,
+                    };
+                    create.execute().await?;
+                    return append_blocks(&blob_client, &blocks).await;
+                }
+                Err(error)
+                    if status(&error) == Some(StatusCode::CONFLICT)
+                        && roll_overs < MAX_ROLL_OVERS =>
+                {
+                    roll_overs += 1;
+                    self.roll_over(name, &blob);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+async fn append_blocks(
+    blob_client: &Arc<BlobClient>,
+    blocks: &[Bytes],
+) -> Result<uuid::Uuid, Error> {
+    let mut request_id = uuid::Uuid::nil();
+    for block in blocks {
+        request_id = blob_client
+            .append_block(block.clone())
+            .execute()
+            .await?
+            .request_id;
+    }
+    Ok(request_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_next_blobs() {
+        assert_eq!(
+            blob_name("blob/2022-03-01T12.log.gz", 0),
+            "blob/2022-03-01T12.log.gz"
+        );
+        assert_eq!(
+            blob_name("blob/2022-03-01T12.log.gz", 2),
+            "blob/2022-03-01T12-2.log.gz"
+        );
+        assert_eq!(blob_name("logs.d/12", 1), "logs.d/12-1");
+        assert_eq!(blob_name("12.log", 1), "12-1.log");
+    }
+
+    #[test]
+    fn splits_blocks() {
+        assert!(split_blocks(Bytes::new()).is_empty());
+
+        let blocks = split_blocks(Bytes::from(vec![0; MAX_BLOCK_SIZE * 2 + 1]));
+        assert_eq!(
+            blocks.iter().map(Bytes::len).collect::<Vec<_>>(),
+            vec![MAX_BLOCK_SIZE, MAX_BLOCK_SIZE, 1]
+        );
+    }
+
+    #[test]
+    fn moves_on_to_next_blob_when_full() {
+        let blobs = AppendBlobs::new(5);
+        let now = Instant::now();
+
+        assert_eq!(blobs.reserve("12.log", 3, now), "12.log");
+        assert_eq!(blobs.reserve("12.log", 2, now), "12.log");
+        assert_eq!(blobs.reserve("12.log", 1, now), "12-1.log");
+        assert_eq!(blobs.reserve("13.log", 1, now), "13.log");
+
+        // Batches larger than a whole blob still go to a blob of their own.
+        assert_eq!(blobs.reserve("14.log", 7, now), "14.log");
+        assert_eq!(blobs.reserve("14.log", 1, now), "14-1.log");
+
+        blobs.roll_over("13.log", "13.log");
+        assert_eq!(blobs.reserve("13.log", 1, now), "13-1.log");
+        // Rolling over a blob that was already moved on from doesn't skip the next one.
+        blobs.roll_over("13.log", "13.log");
+        assert_eq!(blobs.reserve("13.log", 1, now), "13-1.log");
+    }
+}
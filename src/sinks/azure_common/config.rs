@@ -1,10 +1,11 @@
-use std::sync::Arc;
+use std::{
+    process::Stdio,
+    sync::{Arc, RwLock, Weak},
+    time::Duration,
+};
 
 use azure_core::{prelude::*, HttpError};
-use azure_storage::{
-    blob::{blob::responses::PutBlockBlobResponse, prelude::*},
-    core::prelude::*,
-};
+use azure_storage::{blob::prelude::*, core::prelude::*};
 use bytes::Bytes;
 use futures::FutureExt;
 use http::StatusCode;
@@ -13,6 +14,7 @@ use vector_core::{buffers::Ackable, internal_event::EventsSent, stream::DriverRe
 
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
+    internal_events::azure_blob::{AzureBlobSasTokenRefreshError, AzureBlobSasTokenRefreshed},
     sinks::{util::retries::RetryLogic, Healthcheck},
 };
 
@@ -63,7 +65,7 @@ impl RetryLogic for AzureBlobRetryLogic {
 
 #[derive(Debug)]
 pub struct AzureBlobResponse {
-    pub inner: PutBlockBlobResponse,
+    pub request_id: uuid::Uuid,
     pub count: usize,
     pub events_byte_size: usize,
 }
@@ -128,3 +130,135 @@ pub fn build_client(
 
     Ok(client)
 }
+
+/// A container client that's replaced when its credentials are refreshed.
+pub type SharedContainerClient = Arc<RwLock<Arc<ContainerClient>>>;
+
+/// Returns `connection_string`, authenticating with `sas_token` instead of any credentials it
+/// has.
+pub fn with_sas_token(connection_string: &str, sas_token: &str) -> String {
+    let mut parts = connection_string
+        .split(';')
+        .filter(|part| {
+            let key = part.split('=').next().unwrap_or_default().trim();
+            !key.is_empty()
+                && !key.eq_ignore_ascii_case("AccountKey")
+                && !key.eq_ignore_ascii_case("SharedAccessSignature")
+        })
+        .collect::<Vec<_>>()
+        .join(";");
+    parts.push_str(";SharedAccessSignature=");
+    parts.push_str(sas_token.trim().trim_start_matches('?'));
+    parts
+}
+
+/// Runs `command` through the shell, and returns the SAS token it prints.
+///
+/// This lets the token come from a secrets backend, which rotates it.
+pub async fn run_sas_token_command(command: &str) -> Result<String, String> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = tokio::process::Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = tokio::process::Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    let output = shell
+        .arg(command)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|error| format!("Failed to run SAS token command: {}", error))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "SAS token command failed with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+    if token.is_empty() {
+        return Err("SAS token command printed no token".to_owned());
+    }
+    Ok(token)
+}
+
+/// Replaces `client` with one using a fresh SAS token every `interval`, until the sink holding it
+/// is dropped.
+///
+/// If refreshing the token fails, the previous client is kept.
+pub async fn refresh_sas_token(
+    connection_string: String,
+    container_name: String,
+    command: String,
+    interval: Duration,
+    client: Weak<RwLock<Arc<ContainerClient>>>,
+) {
+    let mut interval = tokio::time::interval(interval);
+    // The first tick completes right away, and the sink was just built with a fresh token.
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+
+        let refreshed = run_sas_token_command(&command).await.and_then(|token| {
+            build_client(
+                with_sas_token(&connection_string, &token),
+                container_name.clone(),
+            )
+            .map_err(|error| error.to_string())
+        });
+        let client = match client.upgrade() {
+            Some(client) => client,
+            None => break,
+        };
+
+        match refreshed {
+            Ok(refreshed) => {
+                *client.write().expect("poisoned lock") = refreshed;
+                emit!(&AzureBlobSasTokenRefreshed);
+            }
+            Err(error) => emit!(&AzureBlobSasTokenRefreshError { error }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_credentials_with_sas_token() {
+        assert_eq!(
+            with_sas_token(
+                "DefaultEndpointsProtocol=https;AccountName=account;AccountKey=key;",
+                "?sv=2020-08-04&sig=abc"
+            ),
+            "DefaultEndpointsProtocol=https;AccountName=account;SharedAccessSignature=sv=2020-08-04&sig=abc"
+        );
+        assert_eq!(
+            with_sas_token(
+                "BlobEndpoint=https://account.blob.core.windows.net/;SharedAccessSignature=old",
+                "new\n"
+            ),
+            "BlobEndpoint=https://account.blob.core.windows.net/;SharedAccessSignature=new"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_sas_token_command() {
+        assert_eq!(
+            run_sas_token_command("echo 'sv=2020-08-04&sig=abc'")
+                .await
+                .unwrap(),
+            "sv=2020-08-04&sig=abc"
+        );
+        assert!(run_sas_token_command("exit 1").await.is_err());
+        assert!(run_sas_token_command("true").await.is_err());
+    }
+}
@@ -1,28 +1,60 @@
 use std::{
     result::Result as StdResult,
-    sync::Arc,
+    sync::{Arc, RwLock, Weak},
     task::{Context, Poll},
 };
 
 use azure_core::HttpError;
 use azure_storage::blob::prelude::*;
+use bytes::Bytes;
 use futures::{future::BoxFuture, TryFutureExt};
+use http::StatusCode;
 use tower::Service;
 use tracing_futures::Instrument;
 
 use crate::{
     internal_events::azure_blob::{AzureBlobErrorResponse, AzureBlobEventSent, AzureBlobHttpError},
-    sinks::azure_common::config::{AzureBlobRequest, AzureBlobResponse},
+    sinks::{
+        azure_common::{
+            append::{status, AppendBlobs},
+            config::{AzureBlobRequest, AzureBlobResponse, SharedContainerClient},
+        },
+        util::object_storage::Manifests,
+    },
 };
 
 #[derive(Clone)]
 pub struct AzureBlobService {
-    pub client: Arc<ContainerClient>,
+    client: SharedContainerClient,
+    manifests: Option<Arc<Manifests>>,
+    append_blobs: Option<Arc<AppendBlobs>>,
 }
 
 impl AzureBlobService {
-    pub const fn new(client: Arc<ContainerClient>) -> AzureBlobService {
-        AzureBlobService { client }
+    pub fn new(client: Arc<ContainerClient>) -> AzureBlobService {
+        AzureBlobService {
+            client: Arc::new(RwLock::new(client)),
+            manifests: None,
+            append_blobs: None,
+        }
+    }
+
+    /// Keeps the manifests of the partitions written to up to date.
+    pub fn with_manifests(mut self, manifests: Option<Arc<Manifests>>) -> Self {
+        self.manifests = manifests;
+        self
+    }
+
+    /// Appends batches to the blobs they're named after, rather than writing each as a block
+    /// blob of its own.
+    pub fn with_append_blobs(mut self, append_blobs: Option<Arc<AppendBlobs>>) -> Self {
+        self.append_blobs = append_blobs;
+        self
+    }
+
+    /// Returns the client requests are sent with, which can be replaced to change credentials.
+    pub fn shared_client(&self) -> Weak<RwLock<Arc<ContainerClient>>> {
+        Arc::downgrade(&self.client)
     }
 }
 
@@ -36,42 +68,98 @@ impl Service<AzureBlobRequest> for AzureBlobService {
     }
 
     fn call(&mut self, request: AzureBlobRequest) -> Self::Future {
-        let client =
-            Arc::clone(&self.client).as_blob_client(request.metadata.partition_key.as_str());
+        let container_client = Arc::clone(&self.client.read().expect("poisoned lock"));
+        let manifests = self.manifests.clone();
+        let append_blobs = self.append_blobs.clone();
 
         Box::pin(async move {
             let byte_size = request.blob_data.len();
-            let blob = client
-                .put_block_blob(request.blob_data)
-                .content_type(request.content_type);
-            let blob = match request.content_encoding {
-                Some(encoding) => blob.content_encoding(encoding),
-                None => blob,
-            };
+            let blob_name = request.metadata.partition_key.as_str();
+            let blob_data = request.blob_data;
+            let content_type = request.content_type;
+            let content_encoding = request.content_encoding;
+            let client = Arc::clone(&container_client);
 
-            let result = blob
-                .execute()
-                .inspect_err(|reason| {
-                    match reason.downcast_ref::<HttpError>() {
-                        Some(HttpError::UnexpectedStatusCode { received, .. }) => {
-                            emit!(&AzureBlobErrorResponse { code: *received })
-                        }
-                        _ => emit!(&AzureBlobHttpError {
-                            error: reason.to_string()
-                        }),
-                    };
-                })
-                .inspect_ok(|result| {
-                    emit!(&AzureBlobEventSent {
-                        request_id: result.request_id,
-                        byte_size
-                    })
+            let result = async move {
+                match append_blobs {
+                    Some(append_blobs) => {
+                        append_blobs
+                            .append(
+                                &client,
+                                blob_name,
+                                blob_data,
+                                content_type,
+                                content_encoding,
+                            )
+                            .await
+                    }
+                    None => {
+                        let blob = client
+                            .as_blob_client(blob_name)
+                            .put_block_blob(blob_data)
+                            .content_type(content_type);
+                        let blob = match content_encoding {
+                            Some(encoding) => blob.content_encoding(encoding),
+                            None => blob,
+                        };
+                        blob.execute().await.map(|response| response.request_id)
+                    }
+                }
+            }
+            .inspect_err(|reason| {
+                match reason.downcast_ref::<HttpError>() {
+                    Some(HttpError::UnexpectedStatusCode { received, .. }) => {
+                        emit!(&AzureBlobErrorResponse { code: *received })
+                    }
+                    _ => emit!(&AzureBlobHttpError {
+                        error: reason.to_string()
+                    }),
+                };
+            })
+            .inspect_ok(|request_id| {
+                emit!(&AzureBlobEventSent {
+                    request_id: *request_id,
+                    byte_size
                 })
-                .instrument(info_span!("request"))
-                .await;
+            })
+            .instrument(info_span!("request"))
+            .await;
+
+            if let (Ok(_), Some(manifests)) = (&result, manifests) {
+                let get_client = Arc::clone(&container_client);
+                manifests
+                    .write(
+                        &request.metadata.partition_key,
+                        request.metadata.count,
+                        byte_size,
+                        |key| async move {
+                            let response = get_client
+                                .as_blob_client(key.as_str())
+                                .get()
+                                .execute()
+                                .await;
+                            match response {
+                                Ok(response) => Ok(Some(Bytes::from(response.data.to_vec()))),
+                                Err(error) if status(&error) == Some(StatusCode::NOT_FOUND) => {
+                                    Ok(None)
+                                }
+                                Err(error) => Err(error),
+                            }
+                        },
+                        |key, body| async move {
+                            container_client
+                                .as_blob_client(key.as_str())
+                                .put_block_blob(body)
+                                .content_type("application/json")
+                                .execute()
+                                .await
+                        },
+                    )
+                    .await;
+            }
 
-            result.map(|inner| AzureBlobResponse {
-                inner,
+            result.map(|request_id| AzureBlobResponse {
+                request_id,
                 count: request.metadata.count,
                 events_byte_size: request.metadata.byte_size,
             })
@@ -0,0 +1,252 @@
+use std::num::NonZeroU64;
+
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tower::ServiceBuilder;
+
+use super::{
+    service::{Auth, AzureEventHubsResponse, AzureEventHubsService, Connector},
+    sink::AzureEventHubsSink,
+    AzureEventHubsSinkError,
+};
+use crate::{
+    azure_event_hubs::{ConnectionString, EventHubsError},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext},
+    http::HttpClient,
+    sinks::{
+        util::{
+            encoding::{EncodingConfig, StandardEncodings},
+            retries::RetryLogic,
+            BatchConfig, ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+};
+
+/// The most Event Hubs accepts in a single publish, on the standard tier and above.
+pub(super) const MAX_BATCH_BYTES: usize = 1_000_000;
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AzureEventHubsDefaultBatchSettings;
+
+impl SinkBatchSettings for AzureEventHubsDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = None;
+    const MAX_BYTES: Option<usize> = Some(MAX_BATCH_BYTES);
+    const TIMEOUT_SECS: NonZeroU64 = unsafe { NonZeroU64::new_unchecked(1) };
+}
+
+#[derive(Debug, Snafu, PartialEq)]
+enum BuildError {
+    #[snafu(display("Only one of `connection_string` and `managed_identity` may be set"))]
+    ConflictingAuth,
+    #[snafu(display("One of `connection_string` or `managed_identity` must be set"))]
+    MissingAuth,
+    #[snafu(display("`namespace` must be set when authenticating with `managed_identity`"))]
+    MissingNamespace,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ManagedIdentityConfig {
+    /// Picks one of several user assigned identities, otherwise the system assigned identity
+    /// is used.
+    client_id: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AzureEventHubsSinkConfig {
+    connection_string: Option<String>,
+    /// The fully qualified namespace, such as `my-namespace.servicebus.windows.net`. Only used
+    /// with `managed_identity`, as a connection string already includes it.
+    namespace: Option<String>,
+    event_hub: Option<String>,
+    managed_identity: Option<ManagedIdentityConfig>,
+    partition_key_field: Option<String>,
+    encoding: EncodingConfig<StandardEncodings>,
+    #[serde(default)]
+    batch: BatchConfig<AzureEventHubsDefaultBatchSettings>,
+    #[serde(default)]
+    request: TowerRequestConfig,
+}
+
+impl GenerateConfig for AzureEventHubsSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection_string: Some("Endpoint=sb://my-namespace.servicebus.windows.net/;SharedAccessKeyName=RootManageSharedAccessKey;SharedAccessKey=<key>;EntityPath=my-event-hub".to_owned()),
+            namespace: None,
+            event_hub: None,
+            managed_identity: None,
+            partition_key_field: None,
+            encoding: StandardEncodings::Json.into(),
+            batch: Default::default(),
+            request: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+impl AzureEventHubsSinkConfig {
+    /// Work out how to authenticate, and the event hub to publish to.
+    fn auth(&self, cx: &SinkContext) -> crate::Result<(Auth, String)> {
+        match (&self.connection_string, &self.managed_identity) {
+            (Some(_), Some(_)) => Err(Box::new(BuildError::ConflictingAuth)),
+            (None, None) => Err(Box::new(BuildError::MissingAuth)),
+            (Some(connection_string), None) => {
+                let connection_string = connection_string.parse::<ConnectionString>()?;
+                let event_hub = connection_string.event_hub(self.event_hub.as_deref())?;
+                Ok((Auth::SharedAccessKey(connection_string), event_hub))
+            }
+            (None, Some(managed_identity)) => {
+                let namespace = self.namespace.clone().ok_or(BuildError::MissingNamespace)?;
+                let event_hub = self
+                    .event_hub
+                    .clone()
+                    .ok_or(EventHubsError::MissingEventHub)?;
+                let client = HttpClient::new(None, cx.proxy())?;
+                Ok((
+                    Auth::ManagedIdentity {
+                        namespace,
+                        client_id: managed_identity.client_id.clone(),
+                        client,
+                    },
+                    event_hub,
+                ))
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_event_hubs")]
+impl SinkConfig for AzureEventHubsSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let (auth, event_hub) = self.auth(&cx)?;
+        let connector = Connector::new(auth, event_hub);
+
+        let healthcheck: Healthcheck = {
+            let connector = connector.clone();
+            Box::pin(async move { connector.healthcheck().await })
+        };
+
+        self.request.reject_status_overrides()?;
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self
+            .batch
+            .validate()?
+            .limit_max_bytes(MAX_BATCH_BYTES)?
+            .into_batcher_settings()?;
+        let service = ServiceBuilder::new()
+            .settings(request_settings, AzureEventHubsRetryLogic)
+            .service(AzureEventHubsService::new(connector));
+
+        let sink = AzureEventHubsSink {
+            batch_settings,
+            encoding: self.encoding.clone(),
+            partition_key_field: self.partition_key_field.clone(),
+            service,
+            acker: cx.acker(),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "azure_event_hubs"
+    }
+}
+
+#[derive(Debug, Clone)]
+struct AzureEventHubsRetryLogic;
+
+impl RetryLogic for AzureEventHubsRetryLogic {
+    type Error = AzureEventHubsSinkError;
+    type Response = AzureEventHubsResponse;
+
+    // Failures drop the link, so the whole batch is sent again on a new one.
+    fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth_error(config: &str) -> String {
+        let config: AzureEventHubsSinkConfig = toml::from_str(config).unwrap();
+        config
+            .auth(&SinkContext::new_test())
+            .map(|_| ())
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn requires_a_single_auth_method() {
+        assert_eq!(
+            auth_error(
+                r#"
+                    namespace = "vector.servicebus.windows.net"
+                    event_hub = "logs"
+                    encoding.codec = "json"
+                "#
+            ),
+            BuildError::MissingAuth.to_string()
+        );
+        assert_eq!(
+            auth_error(
+                r#"
+                    connection_string = "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=key;SharedAccessKey=c2VjcmV0"
+                    event_hub = "logs"
+                    managed_identity = {}
+                    encoding.codec = "json"
+                "#
+            ),
+            BuildError::ConflictingAuth.to_string()
+        );
+    }
+
+    #[test]
+    fn managed_identity_requires_namespace_and_event_hub() {
+        assert_eq!(
+            auth_error(
+                r#"
+                    event_hub = "logs"
+                    managed_identity.client_id = "00000000-0000-0000-0000-000000000000"
+                    encoding.codec = "json"
+                "#
+            ),
+            BuildError::MissingNamespace.to_string()
+        );
+        assert_eq!(
+            auth_error(
+                r#"
+                    namespace = "vector.servicebus.windows.net"
+                    managed_identity = {}
+                    encoding.codec = "json"
+                "#
+            ),
+            EventHubsError::MissingEventHub.to_string()
+        );
+    }
+
+    #[test]
+    fn event_hub_from_connection_string() {
+        let config: AzureEventHubsSinkConfig = toml::from_str(
+            r#"
+                connection_string = "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=key;SharedAccessKey=c2VjcmV0;EntityPath=logs"
+                encoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+
+        let (auth, event_hub) = config.auth(&SinkContext::new_test()).unwrap();
+        assert!(matches!(auth, Auth::SharedAccessKey(_)));
+        assert_eq!(event_hub, "logs");
+    }
+}
@@ -0,0 +1,39 @@
+//! The `azure_event_hubs` sink publishes events to an event hub over AMQP 1.0, authenticating
+//! with either a shared access key or the managed identity of the Azure resource Vector runs on.
+//!
+//! Each event is sent as its own message, with the messages of a batch pipelined on a single
+//! link. Batches are kept within the 1MB that Event Hubs accepts in one publish.
+
+use snafu::Snafu;
+
+use crate::config::SinkDescription;
+
+mod config;
+mod service;
+mod sink;
+
+pub use config::AzureEventHubsSinkConfig;
+
+inventory::submit! {
+    SinkDescription::new::<AzureEventHubsSinkConfig>("azure_event_hubs")
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum AzureEventHubsSinkError {
+    #[snafu(display("Failed to connect: {}", source))]
+    Connect { source: crate::Error },
+
+    #[snafu(display("Failed to send events: {}", source))]
+    Send { source: crate::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureEventHubsSinkConfig>();
+    }
+}
@@ -0,0 +1,267 @@
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Duration, Utc};
+use fe2o3_amqp::{
+    connection::ConnectionHandle,
+    session::SessionHandle,
+    types::{
+        messaging::{Message, MessageAnnotations},
+        primitives::Binary,
+    },
+    Sender, Session,
+};
+use futures::future::BoxFuture;
+use snafu::ResultExt;
+use tokio::sync::Mutex;
+use vector_core::{buffers::Ackable, internal_event::EventsSent, stream::DriverResponse};
+
+use super::{AzureEventHubsSinkError, ConnectSnafu, SendSnafu};
+use crate::{
+    azure_event_hubs::{
+        connect_anonymous, managed_identity_token, partition_ids, put_token, ConnectionString,
+    },
+    event::{EventFinalizers, EventStatus, Finalizable},
+    http::HttpClient,
+    Error,
+};
+
+const PARTITION_KEY_ANNOTATION: &str = "x-opt-partition-key";
+
+/// How long before a managed identity token expires that the link is replaced by one with a
+/// fresh token.
+const TOKEN_REFRESH_MARGIN_MINS: i64 = 5;
+
+pub enum Auth {
+    SharedAccessKey(ConnectionString),
+    ManagedIdentity {
+        namespace: String,
+        client_id: Option<String>,
+        client: HttpClient,
+    },
+}
+
+/// A sending link to the event hub, along with the connection and session it belongs to.
+struct Link {
+    connection: ConnectionHandle<()>,
+    session: SessionHandle<()>,
+    sender: Sender,
+    /// When the token the connection was authorized with expires, if it was authorized with one.
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl Link {
+    fn is_expiring(&self) -> bool {
+        self.expires_at.map_or(false, |expires_at| {
+            expires_at - Duration::minutes(TOKEN_REFRESH_MARGIN_MINS) <= Utc::now()
+        })
+    }
+
+    async fn close(mut self) {
+        // The link is being replaced, so failing to close it cleanly doesn't matter.
+        let _ = self.sender.close().await;
+        let _ = self.session.end().await;
+        let _ = self.connection.close().await;
+    }
+}
+
+#[derive(Clone)]
+pub struct Connector {
+    auth: Arc<Auth>,
+    event_hub: String,
+    container_id: String,
+}
+
+impl Connector {
+    pub fn new(auth: Auth, event_hub: String) -> Self {
+        Self {
+            auth: Arc::new(auth),
+            event_hub,
+            container_id: format!("vector-{}", uuid::Uuid::new_v4()),
+        }
+    }
+
+    async fn open(&self) -> crate::Result<(ConnectionHandle<()>, Option<DateTime<Utc>>)> {
+        match self.auth.as_ref() {
+            Auth::SharedAccessKey(connection_string) => {
+                let connection = connection_string.connect(&self.container_id).await?;
+                Ok((connection, None))
+            }
+            Auth::ManagedIdentity {
+                namespace,
+                client_id,
+                client,
+            } => {
+                let token = managed_identity_token(client, client_id.as_deref()).await?;
+                let mut connection = connect_anonymous(namespace, &self.container_id).await?;
+                put_token(&mut connection, namespace, &self.event_hub, &token).await?;
+                Ok((connection, Some(token.expires_at)))
+            }
+        }
+    }
+
+    async fn connect(&self) -> crate::Result<Link> {
+        let (mut connection, expires_at) = self.open().await?;
+        let mut session = Session::begin(&mut connection).await?;
+        let sender = Sender::attach(
+            &mut session,
+            format!("{}-sender", self.container_id),
+            self.event_hub.as_str(),
+        )
+        .await?;
+
+        Ok(Link {
+            connection,
+            session,
+            sender,
+            expires_at,
+        })
+    }
+
+    /// Check that the credentials are accepted, and that the event hub exists.
+    pub async fn healthcheck(self) -> crate::Result<()> {
+        let (mut connection, _) = self.open().await?;
+        partition_ids(&mut connection, &self.event_hub).await?;
+        let _ = connection.close().await;
+
+        Ok(())
+    }
+}
+
+/// An encoded event, ready to be sent as a message.
+pub struct EventData {
+    pub body: Bytes,
+    pub partition_key: Option<String>,
+}
+
+impl EventData {
+    fn into_message(self) -> Message<Binary> {
+        let mut message = Message::builder()
+            .data(Binary::from(self.body.to_vec()))
+            .build();
+        message.message_annotations = self.partition_key.map(|partition_key| {
+            MessageAnnotations::builder()
+                .insert(PARTITION_KEY_ANNOTATION, partition_key)
+                .build()
+        });
+        message
+    }
+}
+
+#[derive(Default)]
+pub struct AzureEventHubsRequest {
+    pub events: Vec<EventData>,
+    pub finalizers: EventFinalizers,
+    pub events_byte_size: usize,
+}
+
+impl Ackable for AzureEventHubsRequest {
+    fn ack_size(&self) -> usize {
+        self.events.len()
+    }
+}
+
+impl Finalizable for AzureEventHubsRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+pub struct AzureEventHubsResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for AzureEventHubsResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.events_count,
+            byte_size: self.events_byte_size,
+        }
+    }
+}
+
+/// Sends each request over a shared link, waiting for every message in it to be accepted.
+#[derive(Clone)]
+pub struct AzureEventHubsService {
+    connector: Connector,
+    // The link is taken out while a request is in flight, so that it is dropped, rather than
+    // reused in an unknown state, if sending fails or the request times out.
+    link: Arc<Mutex<Option<Link>>>,
+}
+
+impl AzureEventHubsService {
+    pub fn new(connector: Connector) -> Self {
+        Self {
+            connector,
+            link: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    async fn send(
+        &self,
+        request: AzureEventHubsRequest,
+    ) -> Result<AzureEventHubsResponse, AzureEventHubsSinkError> {
+        let mut guard = self.link.lock().await;
+
+        let mut link = match guard.take() {
+            Some(link) if !link.is_expiring() => link,
+            expiring => {
+                if let Some(link) = expiring {
+                    link.close().await;
+                }
+                self.connector.connect().await.context(ConnectSnafu)?
+            }
+        };
+
+        let events_count = request.events.len();
+        send_all(&mut link.sender, request.events)
+            .await
+            .context(SendSnafu)?;
+
+        *guard = Some(link);
+
+        Ok(AzureEventHubsResponse {
+            events_count,
+            events_byte_size: request.events_byte_size,
+        })
+    }
+}
+
+/// Send the messages without waiting for each one to be settled before sending the next, then
+/// wait for them all.
+async fn send_all(sender: &mut Sender, events: Vec<EventData>) -> crate::Result<()> {
+    let mut outcomes = Vec::with_capacity(events.len());
+    for event in events {
+        outcomes.push(sender.send_batchable(event.into_message()).await?);
+    }
+    for outcome in outcomes {
+        outcome.await?;
+    }
+
+    Ok(())
+}
+
+impl tower::Service<AzureEventHubsRequest> for AzureEventHubsService {
+    type Response = AzureEventHubsResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Requests are serialized on the link in `call()`.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: AzureEventHubsRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move { service.send(request).await.map_err(Into::into) })
+    }
+}
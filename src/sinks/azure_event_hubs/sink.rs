@@ -0,0 +1,141 @@
+use async_trait::async_trait;
+use futures::{future, stream::BoxStream, StreamExt};
+use tower::util::BoxService;
+use vector_core::{buffers::Acker, stream::BatcherSettings, ByteSizeOf};
+
+use super::{
+    config::MAX_BATCH_BYTES,
+    service::{AzureEventHubsRequest, AzureEventHubsResponse, EventData},
+};
+use crate::{
+    event::{Event, EventStatus, Finalizable},
+    internal_events::LargeEventDropped,
+    sinks::util::{
+        encoding::{Encoder, EncodingConfig, StandardEncodings},
+        SinkBuilderExt, StreamSink,
+    },
+    Error,
+};
+
+pub struct AzureEventHubsSink {
+    pub batch_settings: BatcherSettings,
+    pub encoding: EncodingConfig<StandardEncodings>,
+    pub partition_key_field: Option<String>,
+    pub service: BoxService<AzureEventHubsRequest, AzureEventHubsResponse, Error>,
+    pub acker: Acker,
+}
+
+impl AzureEventHubsSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encoding = self.encoding;
+        let partition_key_field = self.partition_key_field;
+        let acker = self.acker.clone();
+
+        input
+            .filter_map(|mut event| {
+                let byte_size = event.size_of();
+                let finalizers = event.take_finalizers();
+                let partition_key = partition_key(&event, partition_key_field.as_deref());
+
+                let mut body = Vec::new();
+                let data = match encoding.encode_input(event, &mut body) {
+                    Ok(_) if body.len() > MAX_BATCH_BYTES => {
+                        emit!(&LargeEventDropped {
+                            length: body.len(),
+                            max_length: MAX_BATCH_BYTES,
+                        });
+                        finalizers.update_status(EventStatus::Rejected);
+                        acker.ack(1);
+                        None
+                    }
+                    Ok(_) => Some((
+                        byte_size,
+                        finalizers,
+                        EventData {
+                            body: body.into(),
+                            partition_key,
+                        },
+                    )),
+                    Err(error) => {
+                        error!(message = "Failed to encode event.", %error);
+                        finalizers.update_status(EventStatus::Errored);
+                        acker.ack(1);
+                        None
+                    }
+                };
+
+                future::ready(data)
+            })
+            .batched(self.batch_settings.into_reducer_config(
+                |(_, _, data): &(usize, _, EventData)| data.body.len(),
+                |req: &mut AzureEventHubsRequest, (byte_size, finalizers, data)| {
+                    req.events_byte_size += byte_size;
+                    req.finalizers.merge(finalizers);
+                    req.events.push(data);
+                },
+            ))
+            .into_driver(self.service, self.acker)
+            .run()
+            .await
+    }
+}
+
+/// The partition key for an event, taken from a log field or a metric tag.
+///
+/// Events with the same key are always sent to the same partition. Those without one are spread
+/// across partitions by Event Hubs.
+fn partition_key(event: &Event, field: Option<&str>) -> Option<String> {
+    let field = field?;
+    match event {
+        Event::Log(log) => log
+            .get(field)
+            .map(|value| String::from_utf8_lossy(&value.as_bytes()).into_owned()),
+        Event::Metric(metric) => metric.tags().and_then(|tags| tags.get(field)).cloned(),
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for AzureEventHubsSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{
+        metric::{MetricKind, MetricValue},
+        LogEvent, Metric,
+    };
+
+    #[test]
+    fn partition_key_from_field() {
+        let mut log = LogEvent::from("message");
+        log.insert("tenant", "acme");
+        log.insert("shard", 7);
+        let log = Event::from(log);
+
+        assert_eq!(partition_key(&log, Some("tenant")).as_deref(), Some("acme"));
+        assert_eq!(partition_key(&log, Some("shard")).as_deref(), Some("7"));
+        assert_eq!(partition_key(&log, Some("missing")), None);
+        assert_eq!(partition_key(&log, None), None);
+
+        let metric = Event::from(
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )
+            .with_tags(Some(
+                vec![("tenant".to_owned(), "acme".to_owned())]
+                    .into_iter()
+                    .collect(),
+            )),
+        );
+        assert_eq!(
+            partition_key(&metric, Some("tenant")).as_deref(),
+            Some("acme")
+        );
+    }
+}
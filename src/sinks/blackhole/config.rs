@@ -1,5 +1,9 @@
+use std::time::Duration;
+
 use futures::{future, FutureExt};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
 
 use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext},
@@ -10,6 +14,18 @@ const fn default_print_interval_secs() -> u64 {
     1
 }
 
+const fn default_backpressure_duration_ms() -> u64 {
+    1000
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`error_rate` must be at least 0 and less than 1"))]
+    InvalidErrorRate,
+    #[snafu(display("`backpressure_rate` must be between 0 and 1"))]
+    InvalidBackpressureRate,
+}
+
 #[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
 #[serde(deny_unknown_fields, default)]
 #[derivative(Default)]
@@ -18,12 +34,57 @@ pub struct BlackholeConfig {
     #[serde(default = "default_print_interval_secs")]
     pub print_interval_secs: u64,
     pub rate: Option<usize>,
+    /// How long each request takes, a request being a chunk of the events received at once.
+    pub latency: Option<LatencyConfig>,
+    /// The share of requests that fail, which are then sent again until they succeed.
+    pub error_rate: f64,
+    /// The share of requests after which no events are taken for `backpressure_duration_ms`.
+    pub backpressure_rate: f64,
+    #[derivative(Default(value = "1000"))]
+    #[serde(default = "default_backpressure_duration_ms")]
+    pub backpressure_duration_ms: u64,
+    /// How long after its request succeeded each event is acknowledged.
+    pub ack_delay_ms: u64,
+}
+
+/// The distribution the latency of requests is drawn from.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "distribution", rename_all = "snake_case", deny_unknown_fields)]
+pub enum LatencyConfig {
+    Constant { ms: u64 },
+    Uniform { min_ms: u64, max_ms: u64 },
+    Normal { mean_ms: f64, stddev_ms: f64 },
+    Exponential { mean_ms: f64 },
+}
+
+impl LatencyConfig {
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Duration {
+        let ms = match *self {
+            Self::Constant { ms } => ms as f64,
+            Self::Uniform { min_ms, max_ms } => rng.gen_range(min_ms..=max_ms.max(min_ms)) as f64,
+            Self::Normal { mean_ms, stddev_ms } => {
+                // Box-Muller transform, with `1 - u` keeping the logarithm finite.
+                let (u1, u2): (f64, f64) = (rng.gen(), rng.gen());
+                let z = (-2.0 * (1.0 - u1).ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                mean_ms + z * stddev_ms
+            }
+            Self::Exponential { mean_ms } => -mean_ms * (1.0 - rng.gen::<f64>()).ln(),
+        };
+        Duration::from_secs_f64(ms.max(0.0) / 1000.0)
+    }
 }
 
 #[async_trait::async_trait]
 #[typetag::serde(name = "blackhole")]
 impl SinkConfig for BlackholeConfig {
     async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if !(0.0..1.0).contains(&self.error_rate) {
+            return Err(BuildError::InvalidErrorRate.into());
+        }
+        if !(0.0..=1.0).contains(&self.backpressure_rate) {
+            return Err(BuildError::InvalidBackpressureRate.into());
+        }
+
         let sink = BlackholeSink::new(self.clone(), cx.acker());
         let healthcheck = future::ok(()).boxed();
 
@@ -47,10 +108,51 @@ impl GenerateConfig for BlackholeConfig {
 
 #[cfg(test)]
 mod tests {
-    use crate::sinks::blackhole::config::BlackholeConfig;
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::*;
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<BlackholeConfig>();
     }
+
+    #[test]
+    fn samples_latencies() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let sample = |config: &str, rng: &mut SmallRng| {
+            toml::from_str::<LatencyConfig>(config)
+                .unwrap()
+                .sample(rng)
+                .as_millis()
+        };
+
+        assert_eq!(sample("distribution = \"constant\"\nms = 25", &mut rng), 25);
+        for _ in 0..100 {
+            let ms = sample(
+                "distribution = \"uniform\"\nmin_ms = 10\nmax_ms = 20",
+                &mut rng,
+            );
+            assert!((10..=20).contains(&ms));
+        }
+
+        let normal = (0..1000)
+            .map(|_| {
+                sample(
+                    "distribution = \"normal\"\nmean_ms = 100.0\nstddev_ms = 10.0",
+                    &mut rng,
+                )
+            })
+            .sum::<u128>()
+            / 1000;
+        assert!((95..=105).contains(&normal));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_rates() {
+        for config in ["error_rate = 1.0", "backpressure_rate = -0.5"] {
+            let config = toml::from_str::<BlackholeConfig>(config).unwrap();
+            assert!(config.build(SinkContext::new_test()).await.is_err());
+        }
+    }
 }
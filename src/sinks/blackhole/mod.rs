@@ -11,6 +11,7 @@ inventory::submit! {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::Ordering;
 
     use vector_core::buffers::Acker;
 
@@ -27,10 +28,31 @@ mod tests {
         let config = BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            ..Default::default()
         };
         let sink = Box::new(BlackholeSink::new(config, Acker::passthrough()));
 
         let (_input_lines, events) = random_events_with_stream(100, 10, None);
         let _ = sink.run(Box::pin(events)).await.unwrap();
     }
+
+    #[tokio::test]
+    async fn blackhole_simulates_requests() {
+        let config = toml::from_str::<BlackholeConfig>(
+            r#"
+            error_rate = 0.5
+            ack_delay_ms = 50
+            latency = { distribution = "constant", ms = 1 }
+            "#,
+        )
+        .unwrap();
+        let (acker, acked) = Acker::basic();
+        let sink = Box::new(BlackholeSink::new(config, acker));
+
+        let (_input_lines, events) = random_events_with_stream(100, 10, None);
+        sink.run(Box::pin(events)).await.unwrap();
+
+        // Every event is acknowledged before the sink finishes, however late.
+        assert_eq!(acked.load(Ordering::Relaxed), 10);
+    }
 }
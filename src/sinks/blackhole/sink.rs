@@ -8,16 +8,20 @@ use std::{
 
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
 use tokio::{
     select,
-    sync::watch,
-    time::{interval, sleep_until},
+    sync::{mpsc, watch},
+    task::JoinHandle,
+    time::{interval, sleep, sleep_until},
 };
 use vector_core::{buffers::Acker, internal_event::EventsSent, ByteSizeOf};
 
 use crate::{
     event::Event,
-    internal_events::BlackholeEventReceived,
+    internal_events::{
+        BlackholeBackpressureInjected, BlackholeErrorInjected, BlackholeEventReceived,
+    },
     sinks::{blackhole::config::BlackholeConfig, util::StreamSink},
 };
 
@@ -77,6 +81,9 @@ impl StreamSink<Event> for BlackholeSink {
             None => MAX_CHUNK_SIZE,
             Some(rate) => rate.min(MAX_CHUNK_SIZE),
         };
+        let mut rng = SmallRng::from_entropy();
+        let ack_delay = Duration::from_millis(self.config.ack_delay_ms);
+        let delayed_acks = (!ack_delay.is_zero()).then(|| delay_acks(self.acker.clone()));
         let mut chunks = input.ready_chunks(chunk_size);
         while let Some(events) = chunks.next().await {
             if let Some(rate) = self.config.rate {
@@ -87,6 +94,19 @@ impl StreamSink<Event> for BlackholeSink {
                 self.last = Some(until);
             }
 
+            // Each chunk stands for a request, which is sent again until it doesn't fail.
+            loop {
+                if let Some(latency) = &self.config.latency {
+                    sleep(latency.sample(&mut rng)).await;
+                }
+                if !rng.gen_bool(self.config.error_rate) {
+                    break;
+                }
+                emit!(&BlackholeErrorInjected {
+                    count: events.len()
+                });
+            }
+
             let message_len = events.size_of();
 
             let _ = self.total_events.fetch_add(events.len(), Ordering::AcqRel);
@@ -102,7 +122,24 @@ impl StreamSink<Event> for BlackholeSink {
                 byte_size: message_len
             });
 
-            self.acker.ack(events.len());
+            match &delayed_acks {
+                Some((tx, _)) => {
+                    let _ = tx.send((Instant::now() + ack_delay, events.len()));
+                }
+                None => self.acker.ack(events.len()),
+            }
+
+            if rng.gen_bool(self.config.backpressure_rate) {
+                let duration = Duration::from_millis(self.config.backpressure_duration_ms);
+                emit!(&BlackholeBackpressureInjected { duration });
+                sleep(duration).await;
+            }
+        }
+
+        // Wait for the delayed acknowledgements before finishing.
+        if let Some((tx, handle)) = delayed_acks {
+            drop(tx);
+            let _ = handle.await;
         }
 
         // Notify the reporting task to shutdown.
@@ -111,3 +148,15 @@ impl StreamSink<Event> for BlackholeSink {
         Ok(())
     }
 }
+
+/// Acknowledges events once the deadlines sent alongside them pass, in the order they're sent.
+fn delay_acks(acker: Acker) -> (mpsc::UnboundedSender<(Instant, usize)>, JoinHandle<()>) {
+    let (tx, mut rx) = mpsc::unbounded_channel::<(Instant, usize)>();
+    let handle = tokio::spawn(async move {
+        while let Some((deadline, count)) = rx.recv().await {
+            sleep_until(deadline.into()).await;
+            acker.ack(count);
+        }
+    });
+    (tx, handle)
+}
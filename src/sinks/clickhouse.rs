@@ -229,6 +229,10 @@ impl RetryLogic for ClickhouseRetryLogic {
             _ => self.inner.should_retry_response(response),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        self.inner.response_status(response)
+    }
 }
 
 #[cfg(test)]
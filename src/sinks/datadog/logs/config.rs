@@ -1,4 +1,8 @@
-use std::{convert::TryFrom, num::NonZeroU64, sync::Arc};
+use std::{
+    convert::TryFrom,
+    num::NonZeroU64,
+    sync::{Arc, RwLock},
+};
 
 use futures::FutureExt;
 use indoc::indoc;
@@ -7,6 +11,7 @@ use tower::ServiceBuilder;
 use vector_core::config::proxy::ProxyConfig;
 
 use super::{
+    exclusion::{self, ExclusionFiltersConfig, ExclusionRules},
     service::LogApiRetry,
     sink::{DatadogLogsJsonEncoding, LogSinkBuilder},
 };
@@ -14,7 +19,10 @@ use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext},
     http::HttpClient,
     sinks::{
-        datadog::{get_api_validate_endpoint, healthcheck, logs::service::LogApiService, Region},
+        datadog::{
+            get_api_base_endpoint, get_api_validate_endpoint, healthcheck,
+            logs::service::LogApiService, Region,
+        },
         util::{
             encoding::EncodingConfigFixed, service::ServiceBuilderExt, BatchConfig, Compression,
             SinkBatchSettings, TowerRequestConfig,
@@ -71,6 +79,9 @@ pub struct DatadogLogsConfig {
 
     #[serde(default)]
     request: TowerRequestConfig,
+
+    #[serde(default)]
+    exclusion_filters: Option<ExclusionFiltersConfig>,
 }
 
 impl GenerateConfig for DatadogLogsConfig {
@@ -102,6 +113,12 @@ impl DatadogLogsConfig {
             });
         http::Uri::try_from(endpoint).expect("URI not valid")
     }
+
+    fn get_indexes_uri(&self) -> crate::Result<http::Uri> {
+        let base = get_api_base_endpoint(self.endpoint.as_ref(), self.site.as_ref(), self.region);
+        let indexes = format!("{}{}", base, "/api/v1/logs/config/indexes");
+        indexes.parse::<http::Uri>().map_err(Into::into)
+    }
 }
 
 impl DatadogLogsConfig {
@@ -111,6 +128,7 @@ impl DatadogLogsConfig {
         cx: SinkContext,
     ) -> crate::Result<VectorSink> {
         let default_api_key: Arc<str> = Arc::from(self.default_api_key.clone().as_str());
+        self.request.reject_status_overrides()?;
         let request_limits = self.request.unwrap_with(&Default::default());
 
         // We forcefully cap the provided batch configuration to the size/log line limits imposed by
@@ -122,6 +140,21 @@ impl DatadogLogsConfig {
             .limit_max_events(BATCH_MAX_EVENTS)?
             .into_batcher_settings()?;
 
+        let exclusion_rules = match &self.exclusion_filters {
+            Some(config) => {
+                let rules = Arc::new(RwLock::new(ExclusionRules::default()));
+                tokio::spawn(exclusion::refresh(
+                    client.clone(),
+                    self.get_indexes_uri()?,
+                    Arc::clone(&default_api_key),
+                    config.clone(),
+                    Arc::downgrade(&rules),
+                ));
+                Some(rules)
+            }
+            None => None,
+        };
+
         let service = ServiceBuilder::new()
             .settings(request_limits, LogApiRetry)
             .service(LogApiService::new(
@@ -129,10 +162,13 @@ impl DatadogLogsConfig {
                 self.get_uri(),
                 cx.globals.enterprise,
             ));
-        let sink = LogSinkBuilder::new(service, cx, default_api_key, batch)
+        let mut builder = LogSinkBuilder::new(service, cx, default_api_key, batch)
             .encoding(self.encoding.clone())
-            .compression(self.compression.unwrap_or_default())
-            .build();
+            .compression(self.compression.unwrap_or_default());
+        if let Some(rules) = exclusion_rules {
+            builder = builder.exclusion_rules(rules);
+        }
+        let sink = builder.build();
 
         Ok(VectorSink::from_event_streamsink(sink))
     }
@@ -0,0 +1,344 @@
+//! Exclusion filters of the Datadog log indexes.
+//!
+//! Datadog lets organizations define exclusion filters on their log indexes, which drop matching
+//! logs when they're indexed. Logs that are excluded this way are still sent, and billed as
+//! ingested, so the sink can fetch the filters from the Datadog API and apply them itself,
+//! before the logs ever leave the host.
+//!
+//! Every log is routed to the first index whose filter matches it, and only the exclusion
+//! filters of that index apply, in order. An exclusion filter drops the share of matching logs
+//! given by its sample rate.
+
+use std::{
+    sync::{Arc, RwLock, Weak},
+    time::Duration,
+};
+
+use datadog_filter::{build_matcher, Matcher};
+use datadog_search_syntax::parse;
+use http::{Request, Uri};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use vector_core::event::LogEvent;
+use vector_datadog_filter::EventFilter;
+
+use crate::{
+    http::HttpClient,
+    internal_events::{DatadogLogsExclusionFiltersFetchError, DatadogLogsExclusionFiltersUpdated},
+};
+
+const fn default_refresh_interval_secs() -> u64 {
+    300
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ExclusionFiltersConfig {
+    /// The application key used to read the configuration of the log indexes.
+    pub application_key: String,
+    /// How often the exclusion filters are fetched again.
+    #[serde(default = "default_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+}
+
+#[derive(Debug, Snafu)]
+pub enum FetchError {
+    #[snafu(display("Failed to build request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Failed to send request: {}", source))]
+    SendRequest { source: crate::http::HttpError },
+    #[snafu(display("Failed to read response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Request failed with status {}", status))]
+    UnexpectedStatus { status: http::StatusCode },
+    #[snafu(display("Failed to parse response: {}", source))]
+    ParseResponse { source: serde_json::Error },
+    #[snafu(display("Invalid query {:?}: {}", query, message))]
+    InvalidQuery { query: String, message: String },
+}
+
+#[derive(Deserialize, Debug)]
+struct IndexesResponse {
+    indexes: Vec<IndexResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+struct IndexResponse {
+    name: String,
+    filter: FilterResponse,
+    #[serde(default)]
+    exclusion_filters: Vec<ExclusionFilterResponse>,
+}
+
+#[derive(Deserialize, Debug)]
+struct ExclusionFilterResponse {
+    name: String,
+    #[serde(default)]
+    is_enabled: bool,
+    filter: FilterResponse,
+}
+
+#[derive(Deserialize, Debug)]
+struct FilterResponse {
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default = "default_sample_rate")]
+    sample_rate: f64,
+}
+
+const fn default_sample_rate() -> f64 {
+    1.0
+}
+
+/// The exclusion filters of every log index of the organization.
+#[derive(Debug, Default)]
+pub struct ExclusionRules {
+    indexes: Vec<Index>,
+}
+
+#[derive(Debug)]
+struct Index {
+    name: String,
+    filter: Box<dyn Matcher<LogEvent>>,
+    exclusion_filters: Vec<ExclusionFilter>,
+}
+
+#[derive(Debug)]
+struct ExclusionFilter {
+    name: String,
+    filter: Box<dyn Matcher<LogEvent>>,
+    sample_rate: f64,
+}
+
+/// The exclusion filter that excluded a log, and the index it belongs to.
+#[derive(Debug, PartialEq)]
+pub struct Exclusion<'a> {
+    pub index: &'a str,
+    pub exclusion_filter: &'a str,
+}
+
+fn build_filter(query: Option<&str>) -> Result<Box<dyn Matcher<LogEvent>>, FetchError> {
+    // An empty query matches every log.
+    let query = match query.map(str::trim) {
+        None | Some("") => "*",
+        Some(query) => query,
+    };
+    let node = parse(query).map_err(|error| FetchError::InvalidQuery {
+        query: query.to_owned(),
+        message: error.to_string(),
+    })?;
+
+    Ok(build_matcher(&node, &EventFilter::default()))
+}
+
+impl ExclusionRules {
+    fn from_response(response: IndexesResponse) -> Result<Self, FetchError> {
+        let indexes = response
+            .indexes
+            .into_iter()
+            .map(|index| {
+                let exclusion_filters = index
+                    .exclusion_filters
+                    .into_iter()
+                    .filter(|exclusion_filter| exclusion_filter.is_enabled)
+                    .map(|exclusion_filter| {
+                        Ok(ExclusionFilter {
+                            name: exclusion_filter.name,
+                            filter: build_filter(exclusion_filter.filter.query.as_deref())?,
+                            sample_rate: exclusion_filter.filter.sample_rate,
+                        })
+                    })
+                    .collect::<Result<_, FetchError>>()?;
+
+                Ok(Index {
+                    name: index.name,
+                    filter: build_filter(index.filter.query.as_deref())?,
+                    exclusion_filters,
+                })
+            })
+            .collect::<Result<_, FetchError>>()?;
+
+        Ok(Self { indexes })
+    }
+
+    /// Returns the number of enabled exclusion filters.
+    pub fn exclusion_filter_count(&self) -> usize {
+        self.indexes
+            .iter()
+            .map(|index| index.exclusion_filters.len())
+            .sum()
+    }
+
+    /// Returns the exclusion filter that excludes `log`, if any.
+    ///
+    /// Exclusion filters with a sample rate below `1` only exclude that share of the matching
+    /// logs, which is decided using `rng`.
+    pub fn excludes(&self, log: &LogEvent, rng: &mut impl Rng) -> Option<Exclusion<'_>> {
+        let index = self.indexes.iter().find(|index| index.filter.run(log))?;
+        let exclusion_filter = index
+            .exclusion_filters
+            .iter()
+            .find(|exclusion_filter| exclusion_filter.filter.run(log))?;
+
+        (exclusion_filter.sample_rate >= 1.0 || rng.gen::<f64>() < exclusion_filter.sample_rate)
+            .then(|| Exclusion {
+                index: &index.name,
+                exclusion_filter: &exclusion_filter.name,
+            })
+    }
+}
+
+async fn fetch(
+    client: &HttpClient,
+    uri: &Uri,
+    api_key: &str,
+    application_key: &str,
+) -> Result<ExclusionRules, FetchError> {
+    let request = Request::get(uri)
+        .header("DD-API-KEY", api_key)
+        .header("DD-APPLICATION-KEY", application_key)
+        .body(hyper::Body::empty())
+        .context(BuildRequestSnafu)?;
+
+    let response = client.send(request).await.context(SendRequestSnafu)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(FetchError::UnexpectedStatus { status });
+    }
+
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .context(ReadResponseSnafu)?;
+    let response = serde_json::from_slice(&body).context(ParseResponseSnafu)?;
+
+    ExclusionRules::from_response(response)
+}
+
+/// Keeps `rules` up to date with the exclusion filters configured in Datadog, until the sink
+/// holding them is dropped.
+///
+/// If fetching the filters fails, the previous filters are kept.
+pub async fn refresh(
+    client: HttpClient,
+    uri: Uri,
+    api_key: Arc<str>,
+    config: ExclusionFiltersConfig,
+    rules: Weak<RwLock<ExclusionRules>>,
+) {
+    let mut interval =
+        tokio::time::interval(Duration::from_secs(config.refresh_interval_secs.max(1)));
+
+    loop {
+        interval.tick().await;
+
+        let fetched = fetch(&client, &uri, &api_key, &config.application_key).await;
+        let rules = match rules.upgrade() {
+            Some(rules) => rules,
+            None => break,
+        };
+
+        match fetched {
+            Ok(fetched) => {
+                emit!(&DatadogLogsExclusionFiltersUpdated {
+                    count: fetched.exclusion_filter_count()
+                });
+                *rules.write().expect("poisoned lock") = fetched;
+            }
+            Err(error) => emit!(&DatadogLogsExclusionFiltersFetchError { error }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::*;
+
+    fn rules(response: serde_json::Value) -> ExclusionRules {
+        ExclusionRules::from_response(serde_json::from_value(response).unwrap()).unwrap()
+    }
+
+    fn log(service: &str, status: &str) -> LogEvent {
+        let mut log = LogEvent::from("message");
+        log.insert("service", service);
+        log.insert("status", status);
+        log
+    }
+
+    #[test]
+    fn excludes_matching_logs() {
+        let rules = rules(serde_json::json!({
+            "indexes": [
+                {
+                    "name": "web",
+                    "filter": { "query": "service:web" },
+                    "exclusion_filters": [
+                        {
+                            "name": "debug",
+                            "is_enabled": true,
+                            "filter": { "query": "status:debug", "sample_rate": 1.0 }
+                        },
+                        {
+                            "name": "disabled",
+                            "is_enabled": false,
+                            "filter": { "query": "status:info", "sample_rate": 1.0 }
+                        }
+                    ]
+                },
+                {
+                    "name": "main",
+                    "filter": { "query": "" },
+                    "exclusion_filters": [
+                        {
+                            "name": "everything",
+                            "is_enabled": true,
+                            "filter": { "query": "*", "sample_rate": 1.0 }
+                        }
+                    ]
+                }
+            ]
+        }));
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        assert_eq!(rules.exclusion_filter_count(), 2);
+        assert_eq!(
+            rules.excludes(&log("web", "debug"), &mut rng),
+            Some(Exclusion {
+                index: "web",
+                exclusion_filter: "debug"
+            })
+        );
+        // Disabled filters don't apply, and neither do the filters of later indexes.
+        assert_eq!(rules.excludes(&log("web", "info"), &mut rng), None);
+        assert_eq!(
+            rules.excludes(&log("api", "info"), &mut rng),
+            Some(Exclusion {
+                index: "main",
+                exclusion_filter: "everything"
+            })
+        );
+    }
+
+    #[test]
+    fn samples_exclusions() {
+        let rules = rules(serde_json::json!({
+            "indexes": [{
+                "name": "main",
+                "filter": { "query": "*" },
+                "exclusion_filters": [{
+                    "name": "half",
+                    "is_enabled": true,
+                    "filter": { "query": "*", "sample_rate": 0.5 }
+                }]
+            }]
+        }));
+        let mut rng = SmallRng::seed_from_u64(0);
+
+        let excluded = (0..1000)
+            .filter(|_| rules.excludes(&log("web", "info"), &mut rng).is_some())
+            .count();
+        assert!((400..600).contains(&excluded), "excluded {}", excluded);
+    }
+}
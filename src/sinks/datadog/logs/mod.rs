@@ -25,9 +25,11 @@ mod integration_tests;
 mod tests;
 
 mod config;
+mod exclusion;
 mod service;
 mod sink;
 
+pub(crate) use self::exclusion::FetchError;
 use crate::{config::SinkDescription, sinks::datadog::logs::config::DatadogLogsConfig};
 
 inventory::submit! {
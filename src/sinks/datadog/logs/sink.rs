@@ -2,26 +2,28 @@ use std::{
     fmt::Debug,
     io::{self, Write},
     num::NonZeroUsize,
-    sync::Arc,
+    sync::{Arc, RwLock},
 };
 
 use async_trait::async_trait;
-use futures::{stream::BoxStream, StreamExt};
+use futures::{future, stream::BoxStream, StreamExt};
+use rand::{rngs::SmallRng, SeedableRng};
 use snafu::Snafu;
 use tower::Service;
 use vector_core::{
     buffers::Acker,
     config::{log_schema, LogSchema},
-    event::{Event, EventFinalizers, Finalizable, Value},
+    event::{Event, EventFinalizers, EventStatus, Finalizable, Value},
     partition::Partitioner,
     sink::StreamSink,
     stream::{BatcherSettings, DriverResponse},
     ByteSizeOf,
 };
 
-use super::{config::MAX_PAYLOAD_BYTES, service::LogApiRequest};
+use super::{config::MAX_PAYLOAD_BYTES, exclusion::ExclusionRules, service::LogApiRequest};
 use crate::{
     config::SinkContext,
+    internal_events::DatadogLogsEventExcluded,
     sinks::util::{
         encoding::{Encoder, EncodingConfigFixed, StandardEncodings},
         Compression, Compressor, RequestBuilder, SinkBuilderExt,
@@ -47,6 +49,7 @@ pub struct LogSinkBuilder<S> {
     batch_settings: BatcherSettings,
     compression: Option<Compression>,
     default_api_key: Arc<str>,
+    exclusion_rules: Option<Arc<RwLock<ExclusionRules>>>,
 }
 
 impl<S> LogSinkBuilder<S> {
@@ -63,6 +66,7 @@ impl<S> LogSinkBuilder<S> {
             default_api_key,
             batch_settings,
             compression: None,
+            exclusion_rules: None,
         }
     }
 
@@ -77,6 +81,12 @@ impl<S> LogSinkBuilder<S> {
         self
     }
 
+    #[allow(clippy::missing_const_for_fn)] // const cannot run destructor
+    pub fn exclusion_rules(mut self, exclusion_rules: Arc<RwLock<ExclusionRules>>) -> Self {
+        self.exclusion_rules = Some(exclusion_rules);
+        self
+    }
+
     pub fn build(self) -> LogSink<S> {
         LogSink {
             default_api_key: self.default_api_key,
@@ -85,6 +95,7 @@ impl<S> LogSinkBuilder<S> {
             service: self.service,
             batch_settings: self.batch_settings,
             compression: self.compression.unwrap_or_default(),
+            exclusion_rules: self.exclusion_rules,
         }
     }
 }
@@ -107,6 +118,8 @@ pub struct LogSink<S> {
     compression: Compression,
     /// Batch settings: timeout, max events, max bytes, etc.
     batch_settings: BatcherSettings,
+    /// The exclusion filters of the Datadog log indexes, if they are applied by the sink
+    exclusion_rules: Option<Arc<RwLock<ExclusionRules>>>,
 }
 
 /// Customized encoding specific to the Datadog Logs sink, as the logs API only accepts JSON encoded
@@ -236,7 +249,39 @@ where
             compression: self.compression,
         };
 
+        // Logs excluded by an exclusion filter would be dropped by Datadog anyway, so they are
+        // acknowledged as delivered without being sent.
+        let exclusion_rules = self.exclusion_rules;
+        let excluded_acker = self.acker.clone();
+        let mut rng = SmallRng::from_entropy();
+
         let sink = input
+            .filter_map(move |mut event| {
+                let excluded = exclusion_rules.as_ref().map_or(false, |rules| {
+                    let rules = rules.read().expect("poisoned lock");
+                    match rules.excludes(event.as_log(), &mut rng) {
+                        Some(exclusion) => {
+                            emit!(&DatadogLogsEventExcluded {
+                                index: exclusion.index,
+                                exclusion_filter: exclusion.exclusion_filter,
+                            });
+                            true
+                        }
+                        None => false,
+                    }
+                });
+
+                let event = if excluded {
+                    event
+                        .take_finalizers()
+                        .update_status(EventStatus::Delivered);
+                    excluded_acker.ack(1);
+                    None
+                } else {
+                    Some(event)
+                };
+                future::ready(event)
+            })
             .batched_partitioned(partitioner, self.batch_settings)
             .request_builder(builder_limit, request_builder)
             .filter_map(|request| async move {
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{fmt, time::Duration};
 
 use async_trait::async_trait;
 use futures_util::{
@@ -52,6 +52,7 @@ pub struct DatadogMetricsSink<S> {
     acker: Acker,
     request_builder: DatadogMetricsRequestBuilder,
     batch_settings: BatcherSettings,
+    expire_metrics: Option<Duration>,
 }
 
 impl<S> DatadogMetricsSink<S>
@@ -73,6 +74,7 @@ where
             acker: cx.acker(),
             request_builder,
             batch_settings,
+            expire_metrics: cx.expire_metrics(),
         }
     }
 
@@ -84,6 +86,7 @@ where
             // aggregated histograms into sketches so that we can send them in a more DD-native
             // format and thus avoid needing to directly specify what quantiles to generate, etc.
             .normalized_with_default::<DatadogMetricsNormalizer>()
+            .with_expiration(self.expire_metrics)
             // We batch metrics by their endpoint i.e. series (counter, gauge, set) vs sketch
             // (distributions, aggregated histograms, metrics that are already sketches)
             .batched_partitioned(DatadogMetricsTypePartitioner, self.batch_settings)
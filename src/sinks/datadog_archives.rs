@@ -175,6 +175,7 @@ impl DatadogArchivesSinkConfig {
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         match &self.service[..] {
             "aws_s3" => {
+                self.request.reject_status_overrides()?;
                 let s3_config = self.aws_s3.as_ref().expect("s3 config wasn't provided");
                 let service = create_service(&s3_config.region, &s3_config.auth, None, &cx.proxy)?;
                 let client = service.client();
@@ -187,6 +188,7 @@ impl DatadogArchivesSinkConfig {
                 ))
             }
             "azure_blob" => {
+                self.request.reject_status_overrides()?;
                 let azure_config = self
                     .azure_blob
                     .as_ref()
@@ -87,6 +87,10 @@ impl RetryLogic for ElasticSearchRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        Some(response.http_response.status())
+    }
 }
 
 fn get_error_reason(body: &str) -> String {
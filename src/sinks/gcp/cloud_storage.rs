@@ -2,16 +2,15 @@ use std::{
     collections::HashMap,
     convert::TryFrom,
     io::{self},
+    sync::Arc,
 };
 
 use bytes::Bytes;
-use chrono::Utc;
 use http::header::{HeaderName, HeaderValue};
 use indoc::indoc;
 use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
 use tower::ServiceBuilder;
-use uuid::Uuid;
 use vector_core::{event::Finalizable, ByteSizeOf};
 
 use super::{GcpAuthConfig, GcpCredentials, Scope};
@@ -32,6 +31,7 @@ use crate::{
         util::{
             batch::BatchConfig,
             encoding::{EncodingConfig, EncodingConfiguration, StandardEncodings},
+            object_storage::{ManifestConfig, Manifests, ObjectNaming},
             partitioner::KeyPartitioner,
             BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder, ServiceBuilderExt,
             TowerRequestConfig,
@@ -55,6 +55,7 @@ pub struct GcsSinkConfig {
     filename_time_format: Option<String>,
     filename_append_uuid: Option<bool>,
     filename_extension: Option<String>,
+    manifest: Option<ManifestConfig>,
     encoding: EncodingConfig<StandardEncodings>,
     #[serde(default)]
     compression: Compression,
@@ -78,6 +79,7 @@ fn default_config(e: StandardEncodings) -> GcsSinkConfig {
         filename_time_format: Default::default(),
         filename_append_uuid: Default::default(),
         filename_extension: Default::default(),
+        manifest: Default::default(),
         encoding: e.into(),
         compression: Compression::gzip_default(),
         batch: Default::default(),
@@ -150,9 +152,13 @@ impl GcsSinkConfig {
 
         let partitioner = self.key_partitioner()?;
 
+        let manifests = self
+            .manifest
+            .as_ref()
+            .map(|manifest| Arc::new(Manifests::new(manifest)));
         let svc = ServiceBuilder::new()
             .settings(request, GcsRetryLogic)
-            .service(GcsService::new(client, base_url, creds));
+            .service(GcsService::new(client, base_url, creds).with_manifests(manifests));
 
         let request_settings = RequestSettings::new(self)?;
 
@@ -180,8 +186,7 @@ struct RequestSettings {
     storage_class: HeaderValue,
     headers: Vec<(HeaderName, HeaderValue)>,
     extension: String,
-    time_format: String,
-    append_uuid: bool,
+    naming: ObjectNaming,
     encoding: EncodingConfig<StandardEncodings>,
     compression: Compression,
 }
@@ -217,16 +222,7 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 
     fn build_request(&self, mut metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
         // TODO: pull the seconds from the last event
-        let filename = {
-            let seconds = Utc::now().format(&self.time_format);
-
-            if self.append_uuid {
-                let uuid = Uuid::new_v4();
-                format!("{}-{}", seconds, uuid.to_hyphenated())
-            } else {
-                seconds.to_string()
-            }
-        };
+        let filename = self.naming.name();
 
         metadata.key = format!("{}{}.{}", metadata.key, filename, self.extension);
 
@@ -284,8 +280,7 @@ impl RequestSettings {
             storage_class,
             headers: metadata,
             extension,
-            time_format,
-            append_uuid,
+            naming: ObjectNaming::new(time_format, append_uuid),
             compression: config.compression,
             encoding: config.encoding.clone(),
         })
@@ -359,13 +354,16 @@ mod tests {
     #[test]
     fn gcs_build_request() {
         let req = build_request(Some("ext"), false, Compression::None);
-        assert_eq!(req.metadata.key, "key/date.ext".to_string());
+        assert!(req.metadata.key.starts_with("key/date-"));
+        assert!(req.metadata.key.ends_with(".ext"));
 
         let req = build_request(None, false, Compression::None);
-        assert_eq!(req.metadata.key, "key/date.log".to_string());
+        assert!(req.metadata.key.starts_with("key/date-"));
+        assert!(req.metadata.key.ends_with(".log"));
 
         let req = build_request(None, false, Compression::gzip_default());
-        assert_eq!(req.metadata.key, "key/date.log.gz".to_string());
+        assert!(req.metadata.key.starts_with("key/date-"));
+        assert!(req.metadata.key.ends_with(".log.gz"));
 
         let req = build_request(None, true, Compression::gzip_default());
         assert_ne!(req.metadata.key, "key/date.log.gz".to_string());
@@ -0,0 +1,156 @@
+//! Credentials for [workload identity federation][federation], where a token issued by another
+//! identity provider is exchanged for a GCP access token.
+//!
+//! [federation]: https://cloud.google.com/iam/docs/workload-identity-federation
+
+use std::{collections::HashMap, path::PathBuf};
+
+use goauth::{auth::Token, scopes::Scope};
+use serde::Deserialize;
+use snafu::ResultExt;
+
+use super::{impersonate, parse_token, send_token_request};
+use crate::sinks::gcs_common::config::{GcpError, ReadSubjectTokenSnafu};
+
+const TOKEN_EXCHANGE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:token-exchange";
+const ACCESS_TOKEN_TYPE: &str = "urn:ietf:params:oauth:token-type:access_token";
+
+/// An `external_account` credentials file, as generated by `gcloud iam
+/// workload-identity-pools create-cred-config`.
+#[derive(Debug, Deserialize)]
+pub(super) struct ExternalAccount {
+    audience: String,
+    subject_token_type: String,
+    token_url: String,
+    service_account_impersonation_url: Option<String>,
+    credential_source: CredentialSource,
+}
+
+/// Where the token of the other identity provider is read from.
+#[derive(Debug, Deserialize)]
+struct CredentialSource {
+    file: Option<PathBuf>,
+    url: Option<String>,
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    #[serde(default)]
+    format: SubjectTokenFormat,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum SubjectTokenFormat {
+    Text,
+    Json { subject_token_field_name: String },
+}
+
+impl Default for SubjectTokenFormat {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+impl ExternalAccount {
+    /// Exchanges the subject token for an access token, impersonating the service account of
+    /// the credentials if there is one.
+    pub(super) async fn token(&self, scope: &Scope) -> Result<Token, GcpError> {
+        let subject_token = self.subject_token().await?;
+
+        // The exchanged token is only used to impersonate the service account, which needs
+        // the `cloud-platform` scope.
+        let exchange_scope = match self.service_account_impersonation_url {
+            Some(_) => Scope::CloudPlatform,
+            None => scope.clone(),
+        };
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .append_pair("grant_type", TOKEN_EXCHANGE_GRANT_TYPE)
+            .append_pair("audience", &self.audience)
+            .append_pair("scope", &exchange_scope.url())
+            .append_pair("requested_token_type", ACCESS_TOKEN_TYPE)
+            .append_pair("subject_token", &subject_token)
+            .append_pair("subject_token_type", &self.subject_token_type)
+            .finish();
+        let request = http::Request::post(&self.token_url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(hyper::Body::from(body));
+        let token = parse_token(&send_token_request(request).await?)?;
+
+        match &self.service_account_impersonation_url {
+            Some(url) => impersonate(&token, url, scope).await,
+            None => Ok(token),
+        }
+    }
+
+    async fn subject_token(&self) -> Result<String, GcpError> {
+        let source = &self.credential_source;
+        let contents = match (&source.file, &source.url) {
+            (Some(path), _) => tokio::fs::read_to_string(path)
+                .await
+                .context(ReadSubjectTokenSnafu { path })?,
+            (None, Some(url)) => {
+                let mut request = http::Request::get(url);
+                for (name, value) in &source.headers {
+                    request = request.header(name.as_str(), value.as_str());
+                }
+                let bytes = send_token_request(request.body(hyper::Body::empty())).await?;
+                String::from_utf8_lossy(&bytes).into_owned()
+            }
+            (None, None) => return Err(GcpError::UnsupportedCredentialSource),
+        };
+
+        match &source.format {
+            SubjectTokenFormat::Text => Ok(contents.trim().to_owned()),
+            SubjectTokenFormat::Json {
+                subject_token_field_name,
+            } => serde_json::from_str::<HashMap<String, serde_json::Value>>(&contents)
+                .ok()
+                .and_then(|fields| match fields.get(subject_token_field_name) {
+                    Some(serde_json::Value::String(token)) => Some(token.clone()),
+                    _ => None,
+                })
+                .ok_or_else(|| GcpError::MissingSubjectTokenField {
+                    field: subject_token_field_name.clone(),
+                }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reads_json_subject_tokens_from_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token.json");
+        std::fs::write(&path, r#"{"access_token": "abc", "expires_in": 3600}"#).unwrap();
+
+        let account: ExternalAccount = serde_json::from_value(serde_json::json!({
+            "type": "external_account",
+            "audience": "//iam.googleapis.com/projects/1/locations/global/workloadIdentityPools/pool/providers/provider",
+            "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+            "token_url": "https://sts.googleapis.com/v1/token",
+            "credential_source": {
+                "file": path,
+                "format": { "type": "json", "subject_token_field_name": "access_token" }
+            }
+        }))
+        .unwrap();
+        assert_eq!(account.subject_token().await.unwrap(), "abc");
+    }
+
+    #[tokio::test]
+    async fn fails_without_a_credential_source() {
+        let account: ExternalAccount = serde_json::from_value(serde_json::json!({
+            "audience": "audience",
+            "subject_token_type": "urn:ietf:params:aws:token-type:aws4_request",
+            "token_url": "https://sts.googleapis.com/v1/token",
+            "credential_source": { "environment_id": "aws1" }
+        }))
+        .unwrap();
+        assert!(matches!(
+            account.subject_token().await,
+            Err(GcpError::UnsupportedCredentialSource)
+        ));
+    }
+}
@@ -1,30 +1,38 @@
 use std::{
-    sync::{Arc, RwLock},
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
     time::Duration,
 };
 
-use futures::StreamExt;
+use bytes::Bytes;
 use goauth::{
     auth::{JwtClaims, Token, TokenErr},
     credentials::Credentials,
     scopes::Scope,
 };
 use hyper::header::AUTHORIZATION;
+use once_cell::sync::Lazy;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use smpl_jwt::Jwt;
 use snafu::ResultExt;
-use tokio_stream::wrappers::IntervalStream;
 
+use self::external_account::ExternalAccount;
 use crate::{
     config::ProxyConfig,
     http::HttpClient,
     sinks::gcs_common::config::{
-        BuildHttpClientSnafu, GcpError, GetImplicitTokenSnafu, GetTokenBytesSnafu, GetTokenSnafu,
-        InvalidCredentials1Snafu, InvalidRsaKeySnafu,
+        BuildHttpClientSnafu, BuildTokenRequestSnafu, GcpError, GetImplicitTokenSnafu,
+        GetTokenBytesSnafu, GetTokenSnafu, InvalidCredentials1Snafu, InvalidExternalAccountSnafu,
+        InvalidRsaKeySnafu, ReadCredentialsSnafu, RequestTokenSnafu, TokenJsonFromStrSnafu,
     },
 };
 
 pub mod cloud_storage;
+mod external_account;
 pub mod pubsub;
 pub mod stackdriver_logs;
 pub mod stackdriver_metrics;
@@ -32,29 +40,123 @@ pub mod stackdriver_metrics;
 const SERVICE_ACCOUNT_TOKEN_URL: &str =
     "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
 
+const IMPERSONATION_URL: &str =
+    "https://iamcredentials.googleapis.com/v1/projects/-/serviceAccounts";
+
+/// How long impersonated tokens are requested for, which is also the maximum.
+const IMPERSONATION_LIFETIME: &str = "3600s";
+
+/// How long to wait before trying again when a token couldn't be renewed.
+const RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// Credentials by credentials file, impersonated service account and scope, so that components
+/// authenticating the same way share a token and the task renewing it.
+static CREDENTIALS: Lazy<Mutex<HashMap<(Option<String>, Option<String>, String), GcpCredentials>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct GcpAuthConfig {
     pub api_key: Option<String>,
     pub credentials_path: Option<String>,
+    pub impersonate_service_account: Option<String>,
 }
 
 impl GcpAuthConfig {
     pub async fn make_credentials(&self, scope: Scope) -> crate::Result<Option<GcpCredentials>> {
         let gap = std::env::var("GOOGLE_APPLICATION_CREDENTIALS").ok();
         let creds_path = self.credentials_path.as_ref().or_else(|| gap.as_ref());
-        Ok(match (&creds_path, &self.api_key) {
-            (Some(path), _) => Some(GcpCredentials::from_file(path, scope).await?),
-            (None, Some(_)) => None,
-            (None, None) => Some(GcpCredentials::new_implicit(scope).await?),
+        if creds_path.is_none() && self.api_key.is_some() {
+            return Ok(None);
+        }
+
+        let key = (
+            creds_path.cloned(),
+            self.impersonate_service_account.clone(),
+            scope.url(),
+        );
+        let cached = CREDENTIALS.lock().unwrap().get(&key).cloned();
+        if cached.is_some() {
+            return Ok(cached);
+        }
+
+        let source = match creds_path {
+            Some(path) => TokenSource::from_file(path).await?,
+            None => TokenSource::Implicit,
+        };
+        let creds =
+            GcpCredentials::new(source, self.impersonate_service_account.clone(), scope).await?;
+        Ok(Some(
+            CREDENTIALS
+                .lock()
+                .unwrap()
+                .entry(key)
+                .or_insert(creds)
+                .clone(),
+        ))
+    }
+}
+
+/// Where the tokens of a set of credentials come from.
+#[derive(Debug)]
+enum TokenSource {
+    /// A `service_account` credentials file.
+    ServiceAccount(Credentials),
+    /// An `external_account` credentials file, for workload identity federation.
+    ExternalAccount(ExternalAccount),
+    /// The service account of the instance, from the metadata server.
+    Implicit,
+}
+
+#[derive(Deserialize)]
+struct CredentialsFile {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+impl TokenSource {
+    async fn from_file(path: &str) -> crate::Result<Self> {
+        let contents = tokio::fs::read(path)
+            .await
+            .context(ReadCredentialsSnafu { path })?;
+        let kind = serde_json::from_slice::<CredentialsFile>(&contents)
+            .ok()
+            .and_then(|file| file.kind);
+        Ok(match kind.as_deref() {
+            Some("external_account") => Self::ExternalAccount(
+                serde_json::from_slice(&contents).context(InvalidExternalAccountSnafu)?,
+            ),
+            _ => Self::ServiceAccount(
+                Credentials::from_file(path).context(InvalidCredentials1Snafu)?,
+            ),
+        })
+    }
+
+    async fn token(&self, scope: &Scope) -> crate::Result<Token> {
+        Ok(match self {
+            Self::ServiceAccount(creds) => {
+                let jwt = make_jwt(creds, scope)?;
+                goauth::get_token(&jwt, creds)
+                    .await
+                    .context(GetTokenSnafu)?
+            }
+            Self::ExternalAccount(account) => account.token(scope).await?,
+            Self::Implicit => get_token_implicit().await?,
         })
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct GcpCredentials {
-    creds: Option<Credentials>,
+    source: Arc<TokenSource>,
+    impersonate: Option<String>,
     scope: Scope,
     token: Arc<RwLock<Token>>,
+    regenerating: Arc<AtomicBool>,
+}
+
+fn http_client() -> Result<HttpClient, GcpError> {
+    let proxy = ProxyConfig::from_env();
+    HttpClient::new(None, &proxy).context(BuildHttpClientSnafu)
 }
 
 async fn get_token_implicit() -> Result<Token, GcpError> {
@@ -63,9 +165,7 @@ async fn get_token_implicit() -> Result<Token, GcpError> {
         .body(hyper::Body::empty())
         .unwrap();
 
-    let proxy = ProxyConfig::from_env();
-    let res = HttpClient::new(None, &proxy)
-        .context(BuildHttpClientSnafu)?
+    let res = http_client()?
         .send(req)
         .await
         .context(GetImplicitTokenSnafu)?;
@@ -75,36 +175,121 @@ async fn get_token_implicit() -> Result<Token, GcpError> {
         .await
         .context(GetTokenBytesSnafu)?;
 
+    parse_token(&bytes)
+}
+
+fn parse_token(bytes: &[u8]) -> Result<Token, GcpError> {
     // Token::from_str is irresponsible and may panic!
-    match serde_json::from_slice::<Token>(&bytes) {
+    match serde_json::from_slice::<Token>(bytes) {
         Ok(token) => Ok(token),
-        Err(error) => Err(match serde_json::from_slice::<TokenErr>(&bytes) {
+        Err(error) => Err(match serde_json::from_slice::<TokenErr>(bytes) {
             Ok(error) => GcpError::TokenFromJson { source: error },
             Err(_) => GcpError::TokenJsonFromStr { source: error },
         }),
     }
 }
 
-impl GcpCredentials {
-    async fn from_file(path: &str, scope: Scope) -> crate::Result<Self> {
-        let creds = Credentials::from_file(path).context(InvalidCredentials1Snafu)?;
-        let jwt = make_jwt(&creds, &scope)?;
-        let token = goauth::get_token(&jwt, &creds)
-            .await
-            .context(GetTokenSnafu)?;
-        Ok(Self {
-            creds: Some(creds),
-            scope,
-            token: Arc::new(RwLock::new(token)),
+/// Sends a request to a token endpoint, returning the body of the response if it succeeded.
+async fn send_token_request(
+    request: Result<http::Request<hyper::Body>, http::Error>,
+) -> Result<Bytes, GcpError> {
+    let request = request.context(BuildTokenRequestSnafu)?;
+    let url = request.uri().to_string();
+    let response = http_client()?
+        .send(request)
+        .await
+        .context(RequestTokenSnafu { url: url.clone() })?;
+    let status = response.status();
+    let bytes = hyper::body::to_bytes(response.into_body())
+        .await
+        .context(GetTokenBytesSnafu)?;
+    if status.is_success() {
+        Ok(bytes)
+    } else {
+        Err(GcpError::TokenRequestFailed {
+            url,
+            status,
+            body: String::from_utf8_lossy(&bytes).into_owned(),
         })
     }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ImpersonatedToken {
+    access_token: String,
+    expire_time: chrono::DateTime<chrono::Utc>,
+}
+
+/// Uses `token` to generate a token for a service account, from its `generateAccessToken` URL.
+async fn impersonate(token: &Token, url: &str, scope: &Scope) -> Result<Token, GcpError> {
+    let body = serde_json::json!({
+        "scope": [scope.url()],
+        "lifetime": IMPERSONATION_LIFETIME,
+    });
+    let request = http::Request::post(url)
+        .header(
+            AUTHORIZATION,
+            format!("{} {}", token.token_type(), token.access_token()),
+        )
+        .header("Content-Type", "application/json")
+        .body(hyper::Body::from(body.to_string()));
+    let bytes = send_token_request(request).await?;
+    let impersonated =
+        serde_json::from_slice::<ImpersonatedToken>(&bytes).context(TokenJsonFromStrSnafu)?;
+
+    let expires_in = (impersonated.expire_time - chrono::Utc::now())
+        .num_seconds()
+        .max(0);
+    serde_json::from_value(serde_json::json!({
+        "access_token": impersonated.access_token,
+        "token_type": "Bearer",
+        "expires_in": expires_in,
+    }))
+    .context(TokenJsonFromStrSnafu)
+}
+
+async fn fetch_token(
+    source: &TokenSource,
+    impersonate_service_account: Option<&str>,
+    scope: &Scope,
+) -> crate::Result<Token> {
+    match impersonate_service_account {
+        Some(service_account) => {
+            // Tokens used to impersonate service accounts need the `cloud-platform` scope.
+            let token = source.token(&Scope::CloudPlatform).await?;
+            let url = format!(
+                "{}/{}:generateAccessToken",
+                IMPERSONATION_URL, service_account
+            );
+            Ok(impersonate(&token, &url, scope).await?)
+        }
+        None => source.token(scope).await,
+    }
+}
+
+/// Returns how long to wait before renewing a token that expires in `expires_in` seconds.
+///
+/// Tokens are renewed about halfway through their lifetime, with some jitter so that
+/// components sharing credentials don't all renew their tokens at the same time.
+fn regenerate_delay(expires_in: u32) -> Duration {
+    let jitter = rand::thread_rng().gen_range(0.9..1.1);
+    Duration::from_secs_f64(f64::from(expires_in) / 2.0 * jitter).max(Duration::from_secs(1))
+}
 
-    async fn new_implicit(scope: Scope) -> crate::Result<Self> {
-        let token = get_token_implicit().await?;
+impl GcpCredentials {
+    async fn new(
+        source: TokenSource,
+        impersonate: Option<String>,
+        scope: Scope,
+    ) -> crate::Result<Self> {
+        let token = fetch_token(&source, impersonate.as_deref(), &scope).await?;
         Ok(Self {
-            creds: None,
+            source: Arc::new(source),
+            impersonate,
             scope,
             token: Arc::new(RwLock::new(token)),
+            regenerating: Arc::new(AtomicBool::new(false)),
         })
     }
 
@@ -117,35 +302,36 @@ impl GcpCredentials {
     }
 
     async fn regenerate_token(&self) -> crate::Result<()> {
-        let token = match &self.creds {
-            Some(creds) => {
-                let jwt = make_jwt(creds, &self.scope).unwrap(); // Errors caught above
-                goauth::get_token(&jwt, creds).await?
-            }
-            None => get_token_implicit().await?,
-        };
+        let token = fetch_token(&self.source, self.impersonate.as_deref(), &self.scope).await?;
         *self.token.write().unwrap() = token;
         Ok(())
     }
 
+    /// Spawns the task that renews the token before it expires. As credentials are shared,
+    /// only the first call spawns a task.
     pub fn spawn_regenerate_token(&self) {
-        let this = self.clone();
+        if self.regenerating.swap(true, Ordering::SeqCst) {
+            return;
+        }
 
-        let period = this.token.read().unwrap().expires_in() as u64 / 2;
-        let interval = IntervalStream::new(tokio::time::interval(Duration::from_secs(period)));
-        let task = interval.for_each(move |_| {
-            let this = this.clone();
-            async move {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut delay = regenerate_delay(this.token.read().unwrap().expires_in());
+            loop {
+                tokio::time::sleep(delay).await;
                 debug!("Renewing GCP authentication token.");
-                if let Err(error) = this.regenerate_token().await {
-                    error!(
-                        message = "Failed to update GCP authentication token.",
-                        %error
-                    );
-                }
+                delay = match this.regenerate_token().await {
+                    Ok(()) => regenerate_delay(this.token.read().unwrap().expires_in()),
+                    Err(error) => {
+                        error!(
+                            message = "Failed to update GCP authentication token.",
+                            %error
+                        );
+                        RETRY_DELAY
+                    }
+                };
             }
         });
-        tokio::spawn(task);
     }
 }
 
@@ -263,4 +449,37 @@ mod tests {
             Err(err) => assert_downcast_matches!(err, GcpError, GcpError::GetImplicitToken { .. }), // This should be a more relevant error
         }
     }
+
+    #[test]
+    fn jitters_regenerate_delay() {
+        for _ in 0..100 {
+            let delay = regenerate_delay(3600);
+            assert!(delay >= Duration::from_secs(1620));
+            assert!(delay <= Duration::from_secs(1980));
+        }
+        assert_eq!(regenerate_delay(0), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn detects_external_account_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials.json");
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                "type": "external_account",
+                "audience": "//iam.googleapis.com/projects/1/locations/global/workloadIdentityPools/pool/providers/provider",
+                "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+                "token_url": "https://sts.googleapis.com/v1/token",
+                "credential_source": { "file": "/var/run/token" }
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let source = TokenSource::from_file(path.to_str().unwrap())
+            .await
+            .unwrap();
+        assert!(matches!(source, TokenSource::ExternalAccount(_)));
+    }
 }
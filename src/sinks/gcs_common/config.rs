@@ -71,6 +71,34 @@ pub enum GcpError {
     TokenJsonFromStr { source: serde_json::Error },
     #[snafu(display("Failed to build HTTP client"))]
     BuildHttpClient { source: HttpError },
+    #[snafu(display("Failed to read GCP credentials from {:?}: {}", path, source))]
+    ReadCredentials {
+        source: std::io::Error,
+        path: String,
+    },
+    #[snafu(display("Invalid external account GCP credentials: {}", source))]
+    InvalidExternalAccount { source: serde_json::Error },
+    #[snafu(display(
+        "External account GCP credentials must have a `file` or `url` credential source"
+    ))]
+    UnsupportedCredentialSource,
+    #[snafu(display("Failed to read subject token from {:?}: {}", path, source))]
+    ReadSubjectToken {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
+    #[snafu(display("Subject token has no {:?} field", field))]
+    MissingSubjectTokenField { field: String },
+    #[snafu(display("Failed to build token request: {}", source))]
+    BuildTokenRequest { source: http::Error },
+    #[snafu(display("Failed to request token from {:?}: {}", url, source))]
+    RequestToken { source: HttpError, url: String },
+    #[snafu(display("Token request to {:?} failed with {}: {}", url, status, body))]
+    TokenRequestFailed {
+        url: String,
+        status: http::StatusCode,
+        body: String,
+    },
 }
 
 #[derive(Debug, Snafu)]
@@ -155,4 +183,8 @@ impl RetryLogic for GcsRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        Some(response.inner.status())
+    }
 }
@@ -1,10 +1,10 @@
-use std::task::Poll;
+use std::{sync::Arc, task::Poll};
 
 use bytes::Bytes;
 use futures::future::BoxFuture;
 use http::{
     header::{HeaderName, HeaderValue},
-    Request, Uri,
+    Request, StatusCode, Uri,
 };
 use hyper::Body;
 use tower::Service;
@@ -13,7 +13,7 @@ use vector_core::{buffers::Ackable, internal_event::EventsSent, stream::DriverRe
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
     http::{HttpClient, HttpError},
-    sinks::gcp::GcpCredentials,
+    sinks::{gcp::GcpCredentials, util::object_storage::Manifests},
 };
 
 #[derive(Debug, Clone)]
@@ -21,6 +21,7 @@ pub struct GcsService {
     client: HttpClient,
     base_url: String,
     creds: Option<GcpCredentials>,
+    manifests: Option<Arc<Manifests>>,
 }
 
 impl GcsService {
@@ -33,8 +34,15 @@ impl GcsService {
             client,
             base_url,
             creds,
+            manifests: None,
         }
     }
+
+    /// Keeps the manifests of the partitions written to up to date.
+    pub fn with_manifests(mut self, manifests: Option<Arc<Manifests>>) -> Self {
+        self.manifests = manifests;
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -111,16 +119,25 @@ impl Service<GcsRequest> for GcsService {
         let uri = format!("{}{}", self.base_url, request.metadata.key)
             .parse::<Uri>()
             .unwrap();
+        let body_size = request.body.len();
         let mut builder = Request::put(uri);
         let headers = builder.headers_mut().unwrap();
         headers.insert("content-type", settings.content_type);
         headers.insert(
             "content-length",
-            HeaderValue::from_str(&format!("{}", request.body.len())).unwrap(),
+            HeaderValue::from_str(&format!("{}", body_size)).unwrap(),
         );
         settings
             .content_encoding
             .map(|ce| headers.insert("content-encoding", ce));
+        // The manifest is written with the same access settings as the objects it lists.
+        let manifest = self.manifests.clone().map(|manifests| {
+            (
+                manifests,
+                settings.acl.clone(),
+                settings.storage_class.clone(),
+            )
+        });
         settings.acl.map(|acl| headers.insert("x-goog-acl", acl));
         headers.insert("x-goog-storage-class", settings.storage_class);
         for (p, v) in settings.headers {
@@ -133,8 +150,47 @@ impl Service<GcsRequest> for GcsService {
         }
 
         let mut client = self.client.clone();
+        let base_url = self.base_url.clone();
+        let creds = self.creds.clone();
         Box::pin(async move {
             let result = client.call(http_request).await;
+
+            if let (Ok(response), Some((manifests, acl, storage_class))) = (&result, manifest) {
+                if response.status().is_success() {
+                    manifests
+                        .write(
+                            &request.metadata.key,
+                            request.metadata.count,
+                            body_size,
+                            |key| {
+                                let uri = format!("{}{}", base_url, key).parse::<Uri>().unwrap();
+                                let mut http_request =
+                                    Request::get(uri).body(Body::empty()).unwrap();
+                                if let Some(creds) = &creds {
+                                    creds.apply(&mut http_request);
+                                }
+                                get_manifest(client.clone(), http_request)
+                            },
+                            |key, body| {
+                                let uri = format!("{}{}", base_url, key).parse::<Uri>().unwrap();
+                                let mut builder = Request::put(uri)
+                                    .header("content-type", "application/json")
+                                    .header("content-length", body.len())
+                                    .header("x-goog-storage-class", storage_class);
+                                if let Some(acl) = acl {
+                                    builder = builder.header("x-goog-acl", acl);
+                                }
+                                let mut http_request = builder.body(Body::from(body)).unwrap();
+                                if let Some(creds) = &creds {
+                                    creds.apply(&mut http_request);
+                                }
+                                put_manifest(client.clone(), http_request)
+                            },
+                        )
+                        .await;
+                }
+            }
+
             result.map(|inner| GcsResponse {
                 inner,
                 count: request.metadata.count,
@@ -143,3 +199,34 @@ impl Service<GcsRequest> for GcsService {
         })
     }
 }
+
+/// Reads the manifest `request` points to, or `None` if there is no manifest yet.
+async fn get_manifest(
+    mut client: HttpClient,
+    request: Request<Body>,
+) -> Result<Option<Bytes>, String> {
+    let response = client
+        .call(request)
+        .await
+        .map_err(|error| error.to_string())?;
+    match response.status() {
+        StatusCode::NOT_FOUND => Ok(None),
+        status if status.is_success() => hyper::body::to_bytes(response.into_body())
+            .await
+            .map(Some)
+            .map_err(|error| error.to_string()),
+        status => Err(format!("Unexpected status {}", status)),
+    }
+}
+
+async fn put_manifest(mut client: HttpClient, request: Request<Body>) -> Result<(), String> {
+    let response = client
+        .call(request)
+        .await
+        .map_err(|error| error.to_string())?;
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("Unexpected status {}", response.status()))
+    }
+}
@@ -2,31 +2,46 @@ use std::num::NonZeroU64;
 
 use futures::{FutureExt, SinkExt};
 use http::{Request, StatusCode, Uri};
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use super::util::SinkBatchSettings;
 use crate::{
+    conditions::{AnyCondition, Condition},
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::{Event, Value},
     http::HttpClient,
+    internal_events::{HoneycombMarkerFailed, TemplateRenderingFailed},
     sinks::util::{
-        http::{BatchedHttpSink, HttpSink},
-        BatchConfig, BoxedRawValue, JsonArrayBuffer, TowerRequestConfig,
+        http::{HttpSink, PartitionHttpSink},
+        BatchConfig, BoxedRawValue, JsonArrayBuffer, PartitionBuffer, PartitionInnerBuffer,
+        TowerRequestConfig,
     },
+    template::Template,
 };
 
 lazy_static::lazy_static! {
     static ref HOST: Uri = Uri::from_static("https://api.honeycomb.io/1/batch");
+    static ref MARKERS_HOST: Uri = Uri::from_static("https://api.honeycomb.io/1/markers");
+    static ref AUTH_HOST: Uri = Uri::from_static("https://api.honeycomb.io/1/auth");
 }
 
+/// The characters that are escaped in a dataset name when it is used in a URI.
+const DATASET: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'_').remove(b'.');
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HoneycombConfig {
     api_key: String,
 
-    // TODO: we probably want to make this a template
-    // but this limits us in how we can do our healthcheck.
-    dataset: String,
+    /// The dataset to send each event to, which can be templated from the event's fields.
+    dataset: Template,
+
+    /// The field holding the rate the event was sampled at, which is sent as the event's sample
+    /// rate rather than as one of its fields.
+    sample_rate_key: Option<String>,
+
+    markers: Option<MarkersConfig>,
 
     #[serde(default)]
     batch: BatchConfig<HoneycombDefaultBatchSettings>,
@@ -35,6 +50,18 @@ pub struct HoneycombConfig {
     request: TowerRequestConfig,
 }
 
+/// Creates a marker in the event's dataset for each event that matches the condition, such as
+/// deploys.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MarkersConfig {
+    condition: AnyCondition,
+    message: Option<Template>,
+    #[serde(rename = "type")]
+    kind: Option<Template>,
+    url: Option<Template>,
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct HoneycombDefaultBatchSettings;
 
@@ -68,12 +95,28 @@ impl SinkConfig for HoneycombConfig {
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batch_settings()?;
 
-        let buffer = JsonArrayBuffer::new(batch_settings.size);
+        let buffer = PartitionBuffer::new(JsonArrayBuffer::new(batch_settings.size));
 
         let client = HttpClient::new(None, cx.proxy())?;
 
-        let sink = BatchedHttpSink::new(
-            self.clone(),
+        let markers = self
+            .markers
+            .as_ref()
+            .map(|markers| {
+                Ok::<_, crate::Error>(Markers {
+                    // Sinks don't have access to enrichment tables.
+                    condition: markers.condition.build(&Default::default())?,
+                    config: markers.clone(),
+                    client: client.clone(),
+                })
+            })
+            .transpose()?;
+
+        let sink = PartitionHttpSink::new(
+            HoneycombSink {
+                config: self.clone(),
+                markers,
+            },
             buffer,
             request_settings,
             batch_settings.timeout,
@@ -96,12 +139,42 @@ impl SinkConfig for HoneycombConfig {
     }
 }
 
+struct Markers {
+    condition: Box<dyn Condition>,
+    config: MarkersConfig,
+    client: HttpClient,
+}
+
+struct HoneycombSink {
+    config: HoneycombConfig,
+    markers: Option<Markers>,
+}
+
 #[async_trait::async_trait]
-impl HttpSink for HoneycombConfig {
-    type Input = serde_json::Value;
-    type Output = Vec<BoxedRawValue>;
+impl HttpSink for HoneycombSink {
+    type Input = PartitionInnerBuffer<serde_json::Value, String>;
+    type Output = PartitionInnerBuffer<Vec<BoxedRawValue>, String>;
 
     fn encode_event(&self, event: Event) -> Option<Self::Input> {
+        let dataset = self
+            .config
+            .dataset
+            .render_string(&event)
+            .map_err(|error| {
+                emit!(&TemplateRenderingFailed {
+                    error,
+                    field: Some("dataset"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
+        if let Some(markers) = &self.markers {
+            if markers.condition.check(&event) {
+                self.create_marker(markers, &event, &dataset);
+            }
+        }
+
         let mut log = event.into_log();
 
         let timestamp = if let Some(Value::Timestamp(ts)) = log.remove(log_schema().timestamp_key())
@@ -111,45 +184,147 @@ impl HttpSink for HoneycombConfig {
             chrono::Utc::now()
         };
 
-        let data = json!({
+        let sample_rate = self
+            .config
+            .sample_rate_key
+            .as_ref()
+            .and_then(|key| log.remove(key))
+            .and_then(parse_sample_rate);
+
+        let mut data = json!({
             "timestamp": timestamp.to_rfc3339_opts(chrono::SecondsFormat::Nanos, true),
             "data": log.all_fields(),
         });
+        if let Some(sample_rate) = sample_rate {
+            data["samplerate"] = json!(sample_rate);
+        }
 
-        Some(data)
+        Some(PartitionInnerBuffer::new(data, dataset))
     }
 
-    async fn build_request(&self, events: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
-        let uri = self.build_uri();
-        let request = Request::post(uri).header("X-Honeycomb-Team", self.api_key.clone());
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
+        let (events, dataset) = output.into_parts();
+        self.config.build_request(&dataset, events)
+    }
+}
 
-        let buf = serde_json::to_vec(&events).unwrap();
+impl HoneycombSink {
+    /// Creates the marker in the background, as it isn't part of the batch.
+    fn create_marker(&self, markers: &Markers, event: &Event, dataset: &str) {
+        let request = match self
+            .config
+            .build_marker_request(&markers.config, event, dataset)
+        {
+            Some(request) => request.map(hyper::Body::from),
+            None => return,
+        };
 
-        request.body(buf).map_err(Into::into)
+        let client = markers.client.clone();
+        tokio::spawn(async move {
+            match client.send(request).await {
+                Ok(response) if response.status().is_success() => (),
+                Ok(response) => emit!(&HoneycombMarkerFailed {
+                    error: format!("Server returned unexpected status: {}", response.status())
+                        .into(),
+                }),
+                Err(error) => emit!(&HoneycombMarkerFailed {
+                    error: error.into()
+                }),
+            }
+        });
     }
 }
 
+/// Honeycomb weights each event by its sample rate, which has to be a positive integer.
+fn parse_sample_rate(value: Value) -> Option<i64> {
+    let sample_rate = match value {
+        Value::Integer(sample_rate) => sample_rate,
+        Value::Float(sample_rate) => sample_rate.round() as i64,
+        Value::Bytes(bytes) => std::str::from_utf8(&bytes).ok()?.trim().parse().ok()?,
+        _ => return None,
+    };
+
+    (sample_rate > 0).then(|| sample_rate)
+}
+
 impl HoneycombConfig {
-    fn build_uri(&self) -> Uri {
-        let uri = format!("{}/{}", HOST.clone(), self.dataset);
+    fn build_uri(&self, host: &Uri, dataset: &str) -> Uri {
+        let uri = format!("{}/{}", host, utf8_percent_encode(dataset, DATASET));
 
         uri.parse::<http::Uri>()
             .expect("This should be a valid uri")
     }
+
+    fn build_request(
+        &self,
+        dataset: &str,
+        events: Vec<BoxedRawValue>,
+    ) -> crate::Result<http::Request<Vec<u8>>> {
+        let uri = self.build_uri(&HOST, dataset);
+        let request = Request::post(uri).header("X-Honeycomb-Team", self.api_key.clone());
+
+        let buf = serde_json::to_vec(&events).unwrap();
+
+        request.body(buf).map_err(Into::into)
+    }
+
+    fn build_marker_request(
+        &self,
+        markers: &MarkersConfig,
+        event: &Event,
+        dataset: &str,
+    ) -> Option<http::Request<Vec<u8>>> {
+        let mut marker = serde_json::Map::new();
+        for (field, template) in [
+            ("message", &markers.message),
+            ("type", &markers.kind),
+            ("url", &markers.url),
+        ] {
+            if let Some(template) = template {
+                let value = template
+                    .render_string(event)
+                    .map_err(|error| {
+                        emit!(&TemplateRenderingFailed {
+                            error,
+                            field: Some(field),
+                            drop_event: false,
+                        });
+                    })
+                    .ok()?;
+                marker.insert(field.to_string(), json!(value));
+            }
+        }
+        if let Some(Value::Timestamp(timestamp)) = event.as_log().get(log_schema().timestamp_key())
+        {
+            marker.insert("start_time".to_string(), json!(timestamp.timestamp()));
+        }
+
+        let uri = self.build_uri(&MARKERS_HOST, dataset);
+        Request::post(uri)
+            .header("X-Honeycomb-Team", self.api_key.clone())
+            .body(serde_json::to_vec(&marker).unwrap())
+            .ok()
+    }
 }
 
 async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Result<()> {
-    let req = config
-        .build_request(Vec::new())
-        .await?
-        .map(hyper::Body::from);
+    // Without a dataset to send an empty batch to, only the API key can be checked.
+    let req = if config.dataset.is_dynamic() {
+        Request::get(AUTH_HOST.clone())
+            .header("X-Honeycomb-Team", config.api_key.clone())
+            .body(hyper::Body::empty())?
+    } else {
+        config
+            .build_request(config.dataset.get_ref(), Vec::new())?
+            .map(hyper::Body::from)
+    };
 
     let res = client.send(req).await?;
 
     let status = res.status();
     let body = hyper::body::to_bytes(res.into_body()).await?;
 
-    if status == StatusCode::BAD_REQUEST {
+    if status == StatusCode::BAD_REQUEST || (config.dataset.is_dynamic() && status.is_success()) {
         Ok(())
     } else if status == StatusCode::UNAUTHORIZED {
         let json: serde_json::Value = serde_json::from_slice(&body[..])?;
@@ -175,10 +350,142 @@ async fn healthcheck(config: HoneycombConfig, client: HttpClient) -> crate::Resu
         .into())
     }
 }
+
 #[cfg(test)]
 mod test {
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+    use crate::event::LogEvent;
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<super::HoneycombConfig>();
     }
+
+    fn sink(config: &str) -> HoneycombSink {
+        let config: HoneycombConfig = toml::from_str(config).unwrap();
+        HoneycombSink {
+            config,
+            markers: None,
+        }
+    }
+
+    fn event(fields: &[(&str, Value)]) -> Event {
+        let mut log = LogEvent::default();
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2022, 3, 1).and_hms(0, 0, 0),
+        );
+        for (field, value) in fields {
+            log.insert(*field, value.clone());
+        }
+        log.into()
+    }
+
+    #[test]
+    fn renders_dataset() {
+        let sink = sink(
+            r#"
+            api_key = "key"
+            dataset = "{{ service }}-logs"
+            "#,
+        );
+
+        let (_, dataset) = sink
+            .encode_event(event(&[("service", "api".into())]))
+            .unwrap()
+            .into_parts();
+        assert_eq!("api-logs", dataset);
+
+        let request = sink.config.build_request("api logs", Vec::new()).unwrap();
+        assert_eq!(
+            "https://api.honeycomb.io/1/batch/api%20logs",
+            request.uri().to_string()
+        );
+
+        assert!(sink.encode_event(event(&[])).is_none());
+    }
+
+    #[test]
+    fn passes_sample_rate() {
+        let sink = sink(
+            r#"
+            api_key = "key"
+            dataset = "logs"
+            sample_rate_key = "rate"
+            "#,
+        );
+
+        let (data, _) = sink
+            .encode_event(event(&[("rate", 10.into()), ("message", "hello".into())]))
+            .unwrap()
+            .into_parts();
+        assert_eq!(
+            json!({
+                "timestamp": "2022-03-01T00:00:00.000000000Z",
+                "data": { "message": "hello" },
+                "samplerate": 10,
+            }),
+            data
+        );
+
+        let (data, _) = sink
+            .encode_event(event(&[("rate", "20".into())]))
+            .unwrap()
+            .into_parts();
+        assert_eq!(json!(20), data["samplerate"]);
+
+        let (data, _) = sink
+            .encode_event(event(&[("rate", 0.into())]))
+            .unwrap()
+            .into_parts();
+        assert!(data.get("samplerate").is_none());
+    }
+
+    #[test]
+    fn builds_marker_request() {
+        let config: HoneycombConfig = toml::from_str(
+            r#"
+            api_key = "key"
+            dataset = "logs"
+            markers.condition = '.type == "deploy"'
+            markers.message = "Deployed {{ version }}"
+            markers.type = "deploy"
+            "#,
+        )
+        .unwrap();
+        let markers = config.markers.clone().unwrap();
+
+        let request = config
+            .build_marker_request(
+                &markers,
+                &event(&[("type", "deploy".into()), ("version", "1.2.3".into())]),
+                "logs",
+            )
+            .unwrap();
+
+        assert_eq!(
+            "https://api.honeycomb.io/1/markers/logs",
+            request.uri().to_string()
+        );
+        assert_eq!("key", request.headers()["X-Honeycomb-Team"]);
+        assert_eq!(
+            json!({
+                "message": "Deployed 1.2.3",
+                "type": "deploy",
+                "start_time": 1646092800,
+            }),
+            serde_json::from_slice::<serde_json::Value>(request.body()).unwrap()
+        );
+    }
+
+    #[test]
+    fn parses_sample_rate() {
+        assert_eq!(Some(5), parse_sample_rate(5.into()));
+        assert_eq!(Some(3), parse_sample_rate(2.6.into()));
+        assert_eq!(None, parse_sample_rate((-1).into()));
+        assert_eq!(None, parse_sample_rate("often".into()));
+        assert_eq!(None, parse_sample_rate(true.into()));
+    }
 }
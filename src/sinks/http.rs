@@ -1,6 +1,5 @@
 use std::io::Write;
 
-use flate2::write::GzEncoder;
 use futures::{future, FutureExt, SinkExt};
 use http::{
     header::{self, HeaderName, HeaderValue},
@@ -8,23 +7,40 @@ use http::{
 };
 use hyper::Body;
 use indexmap::IndexMap;
+use percent_encoding::{utf8_percent_encode, AsciiSet, CONTROLS};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 
 use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
-    http::{Auth, HttpClient, MaybeAuth},
-    internal_events::{HttpEventEncoded, HttpEventMissingMessage},
+    http::{Auth, Http2Config, HttpClient, MaybeAuth},
+    internal_events::{
+        HttpEventEncoded, HttpEventMissingMessage, HttpInvalidRenderedValue,
+        TemplateRenderingFailed,
+    },
     sinks::util::{
         encoding::{EncodingConfig, EncodingConfiguration},
-        http::{BatchedHttpSink, HttpSink, RequestConfig},
-        BatchConfig, Buffer, Compression, RealtimeSizeBasedDefaultBatchSettings,
-        TowerRequestConfig, UriSerde,
+        http::{HttpSink, PartitionHttpSink, RequestConfig},
+        BatchConfig, Buffer, Compression, Compressor, PartitionBuffer, PartitionInnerBuffer,
+        RealtimeSizeBasedDefaultBatchSettings, TowerRequestConfig, UriSerde,
     },
+    template::Template,
     tls::{TlsOptions, TlsSettings},
 };
 
+/// The characters percent-encoded in rendered paths, which leaves the `/`, `?`, `&` and `=`
+/// that structure them alone.
+const PATH_ENCODE_SET: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'<')
+    .add(b'>')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("{}: {}", source, name))]
@@ -37,6 +53,11 @@ enum BuildError {
         value: String,
         source: header::InvalidHeaderValue,
     },
+    #[snafu(display(
+        "Header {:?} is set both in `request.headers` and `templates.headers`",
+        name
+    ))]
+    DuplicateHeader { name: String },
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -54,9 +75,34 @@ pub struct HttpSinkConfig {
     pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
     #[serde(default)]
     pub request: RequestConfig,
+    #[serde(default)]
+    pub templates: RequestTemplates,
+    #[serde(default, deserialize_with = "crate::serde::bool_or_struct")]
+    pub http2: Http2Config,
     pub tls: Option<TlsOptions>,
 }
 
+/// Parts of each request that are rendered from the events it's made of. Events are batched
+/// separately for each distinct request they render.
+#[derive(Deserialize, Serialize, Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct RequestTemplates {
+    /// The path, and optionally the query, appended to `uri`.
+    pub path: Option<Template>,
+    /// Overrides `method` with the method named by the rendered value.
+    pub method: Option<Template>,
+    #[serde(default)]
+    pub headers: IndexMap<String, Template>,
+}
+
+/// The rendered parts of a request, which partition the events sent.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct RequestKey {
+    uri: Option<Uri>,
+    method: Option<HttpMethod>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
 #[cfg(test)]
 fn default_config(e: Encoding) -> HttpSinkConfig {
     HttpSinkConfig {
@@ -68,11 +114,13 @@ fn default_config(e: Encoding) -> HttpSinkConfig {
         batch: Default::default(),
         encoding: e.into(),
         request: Default::default(),
+        templates: Default::default(),
+        http2: Default::default(),
         tls: Default::default(),
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
+#[derive(Deserialize, Serialize, Debug, Eq, Hash, PartialEq, Clone, Derivative)]
 #[serde(rename_all = "snake_case")]
 #[derivative(Default)]
 pub enum HttpMethod {
@@ -87,6 +135,38 @@ pub enum HttpMethod {
     Patch,
 }
 
+impl HttpMethod {
+    /// Parses a method name, such as one rendered from an event, ignoring case.
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "get" => Some(Self::Get),
+            "head" => Some(Self::Head),
+            "post" => Some(Self::Post),
+            "put" => Some(Self::Put),
+            "delete" => Some(Self::Delete),
+            "options" => Some(Self::Options),
+            "trace" => Some(Self::Trace),
+            "patch" => Some(Self::Patch),
+            _ => None,
+        }
+    }
+}
+
+impl From<&HttpMethod> for Method {
+    fn from(method: &HttpMethod) -> Self {
+        match method {
+            HttpMethod::Get => Self::GET,
+            HttpMethod::Head => Self::HEAD,
+            HttpMethod::Post => Self::POST,
+            HttpMethod::Put => Self::PUT,
+            HttpMethod::Delete => Self::DELETE,
+            HttpMethod::Options => Self::OPTIONS,
+            HttpMethod::Trace => Self::TRACE,
+            HttpMethod::Patch => Self::PATCH,
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum Encoding {
@@ -111,8 +191,89 @@ impl GenerateConfig for HttpSinkConfig {
 
 impl HttpSinkConfig {
     fn build_http_client(&self, cx: &SinkContext) -> crate::Result<HttpClient> {
-        let tls = TlsSettings::from_options(&self.tls)?;
-        Ok(HttpClient::new(tls, cx.proxy())?)
+        let tls = self.http2.client_tls(TlsSettings::from_options(&self.tls)?);
+        let mut builder = hyper::Client::builder();
+        self.http2.configure_client(&mut builder);
+        Ok(HttpClient::new_with_custom_client(
+            tls,
+            cx.proxy(),
+            &mut builder,
+        )?)
+    }
+
+    /// Renders the templated parts of the request `event` is sent in, or returns `None` if
+    /// they're invalid for it, in which case the event is dropped.
+    fn render_key(&self, event: &Event) -> Option<RequestKey> {
+        let render = |template: &Template, field: &str| {
+            template
+                .render_string(event)
+                .map_err(|error| {
+                    emit!(&TemplateRenderingFailed {
+                        error,
+                        field: Some(field),
+                        drop_event: true,
+                    });
+                })
+                .ok()
+        };
+        let invalid = |field: &str, value: &str| {
+            emit!(&HttpInvalidRenderedValue { field, value });
+        };
+
+        let uri = match &self.templates.path {
+            Some(template) => {
+                let path = render(template, "templates.path")?;
+                let path = utf8_percent_encode(path.trim_start_matches('/'), PATH_ENCODE_SET);
+                let uri = format!(
+                    "{}/{}",
+                    self.uri.uri.to_string().trim_end_matches('/'),
+                    path
+                );
+                match uri.parse::<Uri>() {
+                    Ok(uri) => Some(uri),
+                    Err(_) => {
+                        invalid("templates.path", &uri);
+                        return None;
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let method = match &self.templates.method {
+            Some(template) => {
+                let method = render(template, "templates.method")?;
+                match HttpMethod::from_name(&method) {
+                    Some(method) => Some(method),
+                    None => {
+                        invalid("templates.method", &method);
+                        return None;
+                    }
+                }
+            }
+            None => None,
+        };
+
+        let mut headers = Vec::with_capacity(self.templates.headers.len());
+        for (name, template) in &self.templates.headers {
+            let value = render(template, "templates.headers")?;
+            match (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(&value),
+            ) {
+                (Ok(name), Ok(value)) => headers.push((name, value)),
+                _ => {
+                    invalid("templates.headers", &value);
+                    return None;
+                }
+            }
+        }
+
+        Some(RequestKey {
+            uri,
+            method,
+            headers,
+        })
     }
 }
 
@@ -140,15 +301,16 @@ impl SinkConfig for HttpSinkConfig {
 
         config.request.add_old_option(config.headers.take());
         validate_headers(&config.request.headers, &config.auth)?;
+        validate_templated_headers(&config.templates, &config.request.headers, &config.auth)?;
 
         let batch = config.batch.into_batch_settings()?;
         let request = config
             .request
             .tower
             .unwrap_with(&TowerRequestConfig::default());
-        let sink = BatchedHttpSink::new(
+        let sink = PartitionHttpSink::new(
             config,
-            Buffer::new(batch.size, Compression::None),
+            PartitionBuffer::new(Buffer::new(batch.size, Compression::None)),
             request,
             batch.timeout,
             client,
@@ -161,6 +323,18 @@ impl SinkConfig for HttpSinkConfig {
         Ok((sink, healthcheck))
     }
 
+    fn recheck(&self, cx: &SinkContext) -> crate::Result<Option<super::HealthcheckFn>> {
+        let client = self.build_http_client(cx)?;
+        let auth = self.auth.clone();
+        let recheck: super::HealthcheckFn = match cx.healthcheck.uri.clone() {
+            Some(uri) => {
+                Box::new(move || healthcheck(uri.clone(), auth.clone(), client.clone()).boxed())
+            }
+            None => Box::new(|| future::ok(()).boxed()),
+        };
+        Ok(Some(recheck))
+    }
+
     fn input_type(&self) -> DataType {
         DataType::Log
     }
@@ -172,10 +346,12 @@ impl SinkConfig for HttpSinkConfig {
 
 #[async_trait::async_trait]
 impl HttpSink for HttpSinkConfig {
-    type Input = Vec<u8>;
-    type Output = Vec<u8>;
+    type Input = PartitionInnerBuffer<Vec<u8>, RequestKey>;
+    type Output = PartitionInnerBuffer<Vec<u8>, RequestKey>;
 
     fn encode_event(&self, mut event: Event) -> Option<Self::Input> {
+        let key = self.render_key(&event)?;
+
         self.encoding.apply_rules(&mut event);
         let event = event.into_log();
 
@@ -212,21 +388,18 @@ impl HttpSink for HttpSinkConfig {
             byte_size: body.len(),
         });
 
-        Some(body)
+        Some(PartitionInnerBuffer::new(body, key))
     }
 
-    async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
-        let method = match &self.method.clone().unwrap_or(HttpMethod::Post) {
-            HttpMethod::Get => Method::GET,
-            HttpMethod::Head => Method::HEAD,
-            HttpMethod::Post => Method::POST,
-            HttpMethod::Put => Method::PUT,
-            HttpMethod::Delete => Method::DELETE,
-            HttpMethod::Options => Method::OPTIONS,
-            HttpMethod::Trace => Method::TRACE,
-            HttpMethod::Patch => Method::PATCH,
-        };
-        let uri: Uri = self.uri.uri.clone();
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
+        let (mut body, key) = output.into_parts();
+        let method: Method = key
+            .method
+            .as_ref()
+            .or_else(|| self.method.as_ref())
+            .unwrap_or(&HttpMethod::Post)
+            .into();
+        let uri: Uri = key.uri.unwrap_or_else(|| self.uri.uri.clone());
 
         let ct = match self.encoding.codec() {
             Encoding::Text => "text/plain",
@@ -244,20 +417,20 @@ impl HttpSink for HttpSinkConfig {
             .uri(uri)
             .header("Content-Type", ct);
 
-        match self.compression {
-            Compression::Gzip(level) => {
-                builder = builder.header("Content-Encoding", "gzip");
+        if let Some(content_encoding) = self.compression.content_encoding() {
+            builder = builder.header("Content-Encoding", content_encoding);
 
-                let mut w = GzEncoder::new(Vec::new(), level);
-                w.write_all(&body).expect("Writing to Vec can't fail");
-                body = w.finish().expect("Writing to Vec can't fail");
-            }
-            Compression::None => {}
+            let mut w = Compressor::from(self.compression);
+            w.write_all(&body).expect("Writing to Vec can't fail");
+            body = w.finish().expect("Writing to Vec can't fail");
         }
 
         for (header, value) in self.request.headers.iter() {
             builder = builder.header(header.as_str(), value.as_str());
         }
+        for (header, value) in key.headers {
+            builder = builder.header(header, value);
+        }
 
         let mut request = builder.body(body).unwrap();
 
@@ -301,6 +474,29 @@ fn validate_headers(map: &IndexMap<String, String>, auth: &Option<Auth>) -> crat
     Ok(())
 }
 
+fn validate_templated_headers(
+    templates: &RequestTemplates,
+    headers: &IndexMap<String, String>,
+    auth: &Option<Auth>,
+) -> crate::Result<()> {
+    for name in templates.headers.keys() {
+        if auth.is_some() && name.eq_ignore_ascii_case("Authorization") {
+            return Err("Authorization header can not be used with defined auth options".into());
+        }
+        if headers
+            .keys()
+            .any(|header| header.eq_ignore_ascii_case(name))
+        {
+            return Err(BuildError::DuplicateHeader { name: name.clone() }.into());
+        }
+
+        HeaderName::from_bytes(name.as_bytes())
+            .with_context(|_| InvalidHeaderNameSnafu { name })?;
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::{
@@ -343,7 +539,7 @@ mod tests {
 
         let mut config = default_config(Encoding::Text);
         config.encoding = encoding;
-        let bytes = config.encode_event(event).unwrap();
+        let (bytes, _) = config.encode_event(event).unwrap().into_parts();
 
         assert_eq!(bytes, Vec::from("hello world\n"));
     }
@@ -355,7 +551,7 @@ mod tests {
 
         let mut config = default_config(Encoding::Json);
         config.encoding = encoding;
-        let bytes = config.encode_event(event).unwrap();
+        let (bytes, _) = config.encode_event(event).unwrap().into_parts();
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -491,6 +687,117 @@ mod tests {
         .await;
     }
 
+    #[test]
+    fn http_renders_request_templates() {
+        let config = r#"
+        uri = "http://example.com/api/"
+        encoding = "ndjson"
+        [templates]
+        path = "/{{ index }}/docs?op={{ op }}"
+        method = "{{ op }}"
+        headers.X-Tenant = "{{ tenant }}"
+        "#;
+        let config: HttpSinkConfig = toml::from_str(config).unwrap();
+
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("index", "web logs");
+        event.as_mut_log().insert("op", "PUT");
+        event.as_mut_log().insert("tenant", "acme");
+        let key = config.render_key(&event).unwrap();
+        assert_eq!(
+            key.uri.unwrap(),
+            "http://example.com/api/web%20logs/docs?op=PUT"
+        );
+        assert_eq!(key.method, Some(HttpMethod::Put));
+        assert_eq!(key.headers[0].1, "acme");
+
+        event.as_mut_log().insert("op", "fetch");
+        assert!(config.render_key(&event).is_none());
+        event.as_mut_log().remove("tenant");
+        assert!(config.render_key(&event).is_none());
+    }
+
+    #[test]
+    fn http_catches_duplicate_templated_headers() {
+        let config = r#"
+        uri = "http://example.com/"
+        encoding = "text"
+        request.headers.X-Tenant = "acme"
+        templates.headers.x-tenant = "{{ tenant }}"
+        "#;
+        let config: HttpSinkConfig = toml::from_str(config).unwrap();
+
+        assert_downcast_matches!(
+            super::validate_templated_headers(&config.templates, &config.request.headers, &None)
+                .unwrap_err(),
+            BuildError,
+            BuildError::DuplicateHeader { .. }
+        );
+    }
+
+    #[tokio::test]
+    async fn http_batches_by_rendered_request() {
+        let in_addr = next_addr();
+        let config = format!(
+            r#"
+                uri = "http://{}/frames"
+                encoding = "ndjson"
+                templates.path = "{{{{ stream }}}}"
+            "#,
+            in_addr
+        );
+        let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let (rx, trigger, server) = build_test_server(in_addr);
+        tokio::spawn(server);
+
+        let events = (0..10).map(|index| {
+            let mut event = Event::from(format!("line {}", index));
+            let stream = if index % 2 == 0 { "even" } else { "odd" };
+            event.as_mut_log().insert("stream", stream);
+            event
+        });
+        components::run_sink(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
+        drop(trigger);
+
+        let requests = rx
+            .map(|(parts, body)| {
+                let lines = BufReader::new(body.reader())
+                    .lines()
+                    .map(|line| {
+                        let line: serde_json::Value = serde_json::from_str(&line.unwrap()).unwrap();
+                        line["stream"].as_str().unwrap().to_owned()
+                    })
+                    .collect::<Vec<_>>();
+                (parts.uri.path().to_owned(), lines)
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(requests.len(), 2);
+        for (path, streams) in requests {
+            assert_eq!(streams.len(), 5);
+            assert!(streams
+                .iter()
+                .all(|stream| path == format!("/frames/{}", stream)));
+        }
+    }
+
+    #[tokio::test]
+    async fn http_sends_over_http2() {
+        run_sink(
+            r#"
+        http2.enabled = true
+        http2.keep_alive_interval_secs = 10
+    "#,
+            |parts| {
+                assert_eq!(http::Version::HTTP_2, parts.version);
+                assert_eq!("/frames", parts.uri.path());
+            },
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn retries_on_no_connection() {
         let num_lines = 10;
@@ -648,6 +955,52 @@ mod tests {
         assert_eq!(input_lines, output_lines);
     }
 
+    #[tokio::test]
+    async fn zstd_compression() {
+        let num_lines = 1000;
+
+        let in_addr = next_addr();
+
+        let config = r#"
+        uri = "http://$IN_ADDR/frames"
+        compression.algorithm = "zstd"
+        compression.level = 9
+        encoding = "ndjson"
+    "#
+        .replace("$IN_ADDR", &format!("{}", in_addr));
+        let config: HttpSinkConfig = toml::from_str(&config).unwrap();
+
+        let cx = SinkContext::new_test();
+
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (rx, trigger, server) = build_test_server(in_addr);
+
+        let (input_lines, events) = random_lines_with_stream(100, num_lines, None);
+        let pump = sink.run(events);
+
+        tokio::spawn(server);
+
+        pump.await.unwrap();
+        drop(trigger);
+
+        let output_lines = rx
+            .flat_map(|(parts, body)| {
+                assert_eq!("zstd", parts.headers["Content-Encoding"]);
+
+                let body = zstd::stream::decode_all(body.reader()).unwrap();
+                stream::iter(BufReader::new(body.as_slice()).lines().collect::<Vec<_>>())
+            })
+            .map(Result::unwrap)
+            .map(|line| {
+                let val: serde_json::Value = serde_json::from_str(&line).unwrap();
+                val.get("message").unwrap().as_str().unwrap().to_owned()
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(input_lines, output_lines);
+    }
+
     async fn get_received(
         rx: mpsc::Receiver<(Parts, Bytes)>,
         assert_parts: impl Fn(Parts),
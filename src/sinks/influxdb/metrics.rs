@@ -147,7 +147,8 @@ impl InfluxDbSvc {
             protocol_version,
             inner: http_service,
         };
-        let mut normalizer = MetricNormalizer::<InfluxMetricNormalize>::default();
+        let mut normalizer = MetricNormalizer::<InfluxMetricNormalize>::default()
+            .with_expiration(cx.expire_metrics());
 
         let sink = request
             .batch_sink(
@@ -0,0 +1,149 @@
+use std::num::NonZeroU64;
+
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tower::ServiceBuilder;
+
+use super::{
+    service::{LogstashResponse, LogstashService},
+    sink::LogstashSink,
+    LogstashSinkError,
+};
+use crate::{
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext},
+    sinks::{
+        util::{
+            encoding::EncodingConfigFixed, retries::RetryLogic, tcp::TcpConnector, BatchConfig,
+            ServiceBuilderExt, SinkBatchSettings, TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+
+// Beats send windows of up to 2048 events by default (`bulk_max_size`).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogstashDefaultBatchSettings;
+
+impl SinkBatchSettings for LogstashDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(2048);
+    const MAX_BYTES: Option<usize> = None;
+    const TIMEOUT_SECS: NonZeroU64 = unsafe { NonZeroU64::new_unchecked(1) };
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Missing host in address field"))]
+    MissingHost,
+    #[snafu(display("Missing port in address field"))]
+    MissingPort,
+    #[snafu(display("Compression level must be between 0 and 9, got {}", level))]
+    InvalidCompressionLevel { level: u32 },
+}
+
+/// Events are always sent as JSON frames, so the encoding only controls which fields are sent.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct LogstashEncoding;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct LogstashSinkConfig {
+    address: String,
+    keepalive: Option<TcpKeepaliveConfig>,
+    tls: Option<TlsConfig>,
+    send_buffer_bytes: Option<usize>,
+    #[serde(default = "default_compression_level")]
+    compression_level: u32,
+    #[serde(
+        skip_serializing_if = "crate::serde::skip_serializing_if_default",
+        default
+    )]
+    encoding: EncodingConfigFixed<LogstashEncoding>,
+    #[serde(default)]
+    batch: BatchConfig<LogstashDefaultBatchSettings>,
+    #[serde(default)]
+    request: TowerRequestConfig,
+}
+
+// Matches the default `compression_level` of the Beats Logstash output.
+const fn default_compression_level() -> u32 {
+    3
+}
+
+impl GenerateConfig for LogstashSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "127.0.0.1:5044".to_owned(),
+            keepalive: None,
+            tls: None,
+            send_buffer_bytes: None,
+            compression_level: default_compression_level(),
+            encoding: Default::default(),
+            batch: Default::default(),
+            request: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "logstash")]
+impl SinkConfig for LogstashSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.compression_level > 9 {
+            return Err(Box::new(BuildError::InvalidCompressionLevel {
+                level: self.compression_level,
+            }));
+        }
+
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(BuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(BuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let connector = TcpConnector::new(host, port, self.keepalive, tls, self.send_buffer_bytes);
+
+        let healthcheck: Healthcheck = {
+            let connector = connector.clone();
+            Box::pin(async move { connector.healthcheck().await })
+        };
+
+        self.request.reject_status_overrides()?;
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batcher_settings()?;
+        let service = ServiceBuilder::new()
+            .settings(request_settings, LogstashRetryLogic)
+            .service(LogstashService::new(connector, self.compression_level));
+
+        let sink = LogstashSink {
+            batch_settings,
+            encoding: self.encoding.clone(),
+            service,
+            acker: cx.acker(),
+        };
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "logstash"
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LogstashRetryLogic;
+
+impl RetryLogic for LogstashRetryLogic {
+    type Error = LogstashSinkError;
+    type Response = LogstashResponse;
+
+    // Every error leaves the connection in an unknown state, so it is dropped and the whole
+    // window is sent again on a new connection.
+    fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+        true
+    }
+}
@@ -0,0 +1,154 @@
+//! The `logstash` sink sends events using version 2 of the lumberjack protocol, as spoken by
+//! Elastic Beats, so that they can be received by the Logstash `beats` input.
+//!
+//! Protocol: https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md
+
+use snafu::Snafu;
+
+use crate::{config::SinkDescription, sinks::util::tcp::TcpError};
+
+mod config;
+mod service;
+mod sink;
+
+pub use config::LogstashSinkConfig;
+
+inventory::submit! {
+    SinkDescription::new::<LogstashSinkConfig>("logstash")
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum LogstashSinkError {
+    #[snafu(display("Connect error: {}", source))]
+    Connect { source: TcpError },
+
+    #[snafu(display("I/O error: {}", source))]
+    Io { source: std::io::Error },
+
+    #[snafu(display("Unexpected frame type {:?}, expected an ack", frame_type as char))]
+    UnexpectedFrame { frame_type: u8 },
+
+    #[snafu(display(
+        "Received an ack for sequence number {}, but only {} events were sent",
+        sequence_number,
+        count
+    ))]
+    InvalidAck { sequence_number: u32, count: u32 },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::TryInto, io::Read};
+
+    use bytes::Bytes;
+    use flate2::read::ZlibDecoder;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+    use vector_core::event::{BatchNotifier, BatchStatus};
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        test_util::{next_addr, random_lines_with_stream},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogstashSinkConfig>();
+    }
+
+    fn ack(sequence_number: u32) -> Vec<u8> {
+        let mut frame = b"2A".to_vec();
+        frame.extend(sequence_number.to_be_bytes());
+        frame
+    }
+
+    #[test]
+    fn encodes_compressed_window() {
+        let events = vec![Bytes::from(r#"{"message":"one"}"#), Bytes::from("{}")];
+
+        let plain = service::encode_window(&events, 0);
+        let compressed = service::encode_window(&events, 6);
+
+        // The window size frame is never compressed.
+        assert_eq!(&plain[..6], b"2W\0\0\0\x02");
+        assert_eq!(&compressed[..6], b"2W\0\0\0\x02");
+        assert_eq!(&compressed[6..8], b"2C");
+
+        let length = u32::from_be_bytes(compressed[8..12].try_into().unwrap()) as usize;
+        assert_eq!(compressed.len(), 12 + length);
+
+        let mut frames = Vec::new();
+        ZlibDecoder::new(&compressed[12..])
+            .read_to_end(&mut frames)
+            .unwrap();
+        assert_eq!(frames, &plain[6..]);
+        assert_eq!(&frames[..10], b"2J\0\0\0\x01\0\0\0\x11");
+    }
+
+    #[tokio::test]
+    async fn waits_for_window_ack() {
+        let num_lines = 10;
+        let address = next_addr();
+        let listener = TcpListener::bind(address).await.unwrap();
+
+        let config: LogstashSinkConfig = toml::from_str(&format!(
+            r#"
+                address = "{}"
+                compression_level = 0
+            "#,
+            address
+        ))
+        .unwrap();
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut messages = Vec::new();
+
+            while messages.len() < num_lines {
+                let mut header = [0u8; 6];
+                socket.read_exact(&mut header).await.unwrap();
+                assert_eq!(&header[..2], b"2W");
+                let count = u32::from_be_bytes(header[2..].try_into().unwrap());
+
+                for sequence_number in 1..=count {
+                    let mut frame = [0u8; 10];
+                    socket.read_exact(&mut frame).await.unwrap();
+                    assert_eq!(&frame[..2], b"2J");
+                    assert_eq!(
+                        u32::from_be_bytes(frame[2..6].try_into().unwrap()),
+                        sequence_number
+                    );
+
+                    let length = u32::from_be_bytes(frame[6..].try_into().unwrap());
+                    let mut payload = vec![0u8; length as usize];
+                    socket.read_exact(&mut payload).await.unwrap();
+                    let event: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+                    messages.push(event["message"].as_str().unwrap().to_owned());
+
+                    // Keepalives and partial acks don't complete the window.
+                    if sequence_number == 1 {
+                        socket.write_all(&ack(0)).await.unwrap();
+                        socket.write_all(&ack(1)).await.unwrap();
+                    }
+                }
+
+                socket.write_all(&ack(count)).await.unwrap();
+            }
+
+            messages
+        });
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input_lines, events) = random_lines_with_stream(8, num_lines, Some(batch));
+
+        sink.run(events).await.unwrap();
+
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+        assert_eq!(server.await.unwrap(), input_lines);
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    io::Write,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use flate2::write::ZlibEncoder;
+use futures::future::BoxFuture;
+use snafu::ResultExt;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::Mutex,
+};
+use vector_core::{buffers::Ackable, internal_event::EventsSent, stream::DriverResponse};
+
+use super::{ConnectSnafu, IoSnafu, LogstashSinkError};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    internal_events::TcpSocketConnectionEstablished,
+    sinks::util::tcp::TcpConnector,
+    tls::MaybeTlsStream,
+    Error,
+};
+
+const PROTOCOL_VERSION: u8 = b'2';
+const FRAME_ACK: u8 = b'A';
+const FRAME_COMPRESSED: u8 = b'C';
+const FRAME_JSON: u8 = b'J';
+const FRAME_WINDOW_SIZE: u8 = b'W';
+
+#[derive(Clone, Default)]
+pub struct LogstashRequest {
+    /// Each event, encoded as a JSON object.
+    pub events: Vec<Bytes>,
+    pub finalizers: EventFinalizers,
+    pub events_byte_size: usize,
+}
+
+impl Ackable for LogstashRequest {
+    fn ack_size(&self) -> usize {
+        self.events.len()
+    }
+}
+
+impl Finalizable for LogstashRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+pub struct LogstashResponse {
+    events_count: usize,
+    events_byte_size: usize,
+}
+
+impl DriverResponse for LogstashResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> EventsSent {
+        EventsSent {
+            count: self.events_count,
+            byte_size: self.events_byte_size,
+        }
+    }
+}
+
+/// Sends each request as a single window over a shared connection, and waits for the server to
+/// acknowledge the whole window before responding.
+#[derive(Clone)]
+pub struct LogstashService {
+    connector: TcpConnector,
+    compression_level: u32,
+    // The connection is taken out while a window is in flight, so that it is dropped, rather than
+    // reused in an unknown state, if sending fails or the request times out.
+    connection: Arc<Mutex<Option<MaybeTlsStream<TcpStream>>>>,
+}
+
+impl LogstashService {
+    pub fn new(connector: TcpConnector, compression_level: u32) -> Self {
+        Self {
+            connector,
+            compression_level,
+            connection: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    async fn send(&self, request: LogstashRequest) -> Result<LogstashResponse, LogstashSinkError> {
+        let mut connection = self.connection.lock().await;
+
+        let mut stream = match connection.take() {
+            Some(stream) => stream,
+            None => {
+                let stream = self.connector.connect().await.context(ConnectSnafu)?;
+                emit!(&TcpSocketConnectionEstablished {
+                    peer_addr: stream.peer_addr().ok(),
+                });
+                stream
+            }
+        };
+
+        let window = encode_window(&request.events, self.compression_level);
+        stream.write_all(&window).await.context(IoSnafu)?;
+        stream.flush().await.context(IoSnafu)?;
+        await_ack(&mut stream, request.events.len() as u32).await?;
+
+        *connection = Some(stream);
+
+        Ok(LogstashResponse {
+            events_count: request.events.len(),
+            events_byte_size: request.events_byte_size,
+        })
+    }
+}
+
+impl tower::Service<LogstashRequest> for LogstashService {
+    type Response = LogstashResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Requests are serialized on the connection in `call()`.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: LogstashRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move { service.send(request).await.map_err(Into::into) })
+    }
+}
+
+/// Encode the events as a window: a window size frame, followed by a JSON frame for each event,
+/// optionally wrapped in a compressed frame.
+pub(super) fn encode_window(events: &[Bytes], compression_level: u32) -> Bytes {
+    let mut frames = BytesMut::new();
+    for (index, event) in events.iter().enumerate() {
+        frames.put_u8(PROTOCOL_VERSION);
+        frames.put_u8(FRAME_JSON);
+        // Sequence numbers start at 1 in each window.
+        frames.put_u32(index as u32 + 1);
+        frames.put_u32(event.len() as u32);
+        frames.put_slice(event);
+    }
+
+    let mut window = BytesMut::with_capacity(frames.len() + 12);
+    window.put_u8(PROTOCOL_VERSION);
+    window.put_u8(FRAME_WINDOW_SIZE);
+    window.put_u32(events.len() as u32);
+
+    if compression_level == 0 {
+        window.put(frames);
+    } else {
+        let mut encoder = ZlibEncoder::new(
+            Vec::with_capacity(frames.len()),
+            flate2::Compression::new(compression_level),
+        );
+        encoder
+            .write_all(&frames)
+            .expect("Writing to Vec can't fail");
+        let compressed = encoder.finish().expect("Writing to Vec can't fail");
+
+        window.put_u8(PROTOCOL_VERSION);
+        window.put_u8(FRAME_COMPRESSED);
+        window.put_u32(compressed.len() as u32);
+        window.put_slice(&compressed);
+    }
+
+    window.freeze()
+}
+
+/// Wait for the ack of the last event in a window of `count` events.
+///
+/// The server may acknowledge part of the window first, and sends acks for sequence number 0 as
+/// keepalives while its pipeline is blocked. Both are skipped.
+async fn await_ack(
+    stream: &mut MaybeTlsStream<TcpStream>,
+    count: u32,
+) -> Result<(), LogstashSinkError> {
+    let mut frame = [0u8; 6];
+
+    while count > 0 {
+        stream.read_exact(&mut frame).await.context(IoSnafu)?;
+
+        if frame[0] != PROTOCOL_VERSION || frame[1] != FRAME_ACK {
+            return Err(LogstashSinkError::UnexpectedFrame {
+                frame_type: frame[1],
+            });
+        }
+
+        let sequence_number = u32::from_be_bytes([frame[2], frame[3], frame[4], frame[5]]);
+        if sequence_number == count {
+            break;
+        } else if sequence_number > count {
+            return Err(LogstashSinkError::InvalidAck {
+                sequence_number,
+                count,
+            });
+        }
+    }
+
+    Ok(())
+}
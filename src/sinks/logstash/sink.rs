@@ -0,0 +1,80 @@
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures::{future, stream::BoxStream, StreamExt};
+use tower::util::BoxService;
+use vector_core::{buffers::Acker, stream::BatcherSettings, ByteSizeOf};
+
+use super::{
+    config::LogstashEncoding,
+    service::{LogstashRequest, LogstashResponse},
+};
+use crate::{
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
+    internal_events::LogstashEncodeEventFailed,
+    sinks::util::{
+        encoding::{EncodingConfigFixed, EncodingConfiguration},
+        SinkBuilderExt, StreamSink,
+    },
+    Error,
+};
+
+struct EventData {
+    byte_size: usize,
+    finalizers: EventFinalizers,
+    payload: Bytes,
+}
+
+pub struct LogstashSink {
+    pub batch_settings: BatcherSettings,
+    pub encoding: EncodingConfigFixed<LogstashEncoding>,
+    pub service: BoxService<LogstashRequest, LogstashResponse, Error>,
+    pub acker: Acker,
+}
+
+impl LogstashSink {
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let encoding = self.encoding;
+        let acker = self.acker.clone();
+
+        input
+            .filter_map(|mut event| {
+                let byte_size = event.size_of();
+                let finalizers = event.take_finalizers();
+                encoding.apply_rules(&mut event);
+
+                let data = match serde_json::to_vec(event.as_log()) {
+                    Ok(payload) => Some(EventData {
+                        byte_size,
+                        finalizers,
+                        payload: payload.into(),
+                    }),
+                    Err(error) => {
+                        emit!(&LogstashEncodeEventFailed { error });
+                        finalizers.update_status(EventStatus::Errored);
+                        acker.ack(1);
+                        None
+                    }
+                };
+
+                future::ready(data)
+            })
+            .batched(self.batch_settings.into_reducer_config(
+                |data: &EventData| data.payload.len(),
+                |req: &mut LogstashRequest, item: EventData| {
+                    req.events_byte_size += item.byte_size;
+                    req.finalizers.merge(item.finalizers);
+                    req.events.push(item.payload);
+                },
+            ))
+            .into_driver(self.service, self.acker)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for LogstashSink {
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -13,12 +13,16 @@ pub mod aws_kinesis_firehose;
 pub mod aws_kinesis_streams;
 #[cfg(feature = "sinks-aws_s3")]
 pub mod aws_s3;
+#[cfg(feature = "sinks-aws_security_lake")]
+pub mod aws_security_lake;
 #[cfg(feature = "sinks-aws_sqs")]
 pub mod aws_sqs;
 #[cfg(feature = "sinks-azure_blob")]
 pub mod azure_blob;
 #[cfg(any(feature = "sinks-azure_blob", feature = "sinks-datadog_archives"))]
 pub mod azure_common;
+#[cfg(feature = "sinks-azure_event_hubs")]
+pub mod azure_event_hubs;
 #[cfg(feature = "sinks-azure_monitor_logs")]
 pub mod azure_monitor_logs;
 #[cfg(feature = "sinks-blackhole")]
@@ -55,6 +59,8 @@ pub mod influxdb;
 pub mod kafka;
 #[cfg(feature = "sinks-logdna")]
 pub mod logdna;
+#[cfg(feature = "sinks-logstash")]
+pub mod logstash;
 #[cfg(feature = "sinks-loki")]
 pub mod loki;
 #[cfg(feature = "sinks-nats")]
@@ -63,6 +69,8 @@ pub mod nats;
 pub mod new_relic;
 #[cfg(feature = "sinks-new_relic_logs")]
 pub mod new_relic_logs;
+#[cfg(feature = "sinks-notification")]
+pub mod notification;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
 #[cfg(feature = "sinks-prometheus")]
@@ -83,11 +91,18 @@ pub mod splunk_hec;
 pub mod statsd;
 #[cfg(feature = "sinks-vector")]
 pub mod vector;
+#[cfg(feature = "sinks-webhook")]
+pub mod webhook;
+#[cfg(feature = "sinks-websocket_server")]
+pub mod websocket_server;
 
 pub use vector_core::sink::VectorSink;
 
 pub type Healthcheck = BoxFuture<'static, crate::Result<()>>;
 
+/// Makes a new healthcheck every time it's called. See `SinkConfig::recheck`.
+pub type HealthcheckFn = Box<dyn Fn() -> Healthcheck + Send + Sync>;
+
 /// Common build errors
 #[derive(Debug, Snafu)]
 pub enum BuildError {
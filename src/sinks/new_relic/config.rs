@@ -13,7 +13,7 @@ use crate::{
 use futures::FutureExt;
 use http::Uri;
 use serde::{Deserialize, Serialize};
-use std::{fmt::Debug, num::NonZeroU64, sync::Arc};
+use std::{collections::BTreeMap, fmt::Debug, num::NonZeroU64, sync::Arc};
 use tower::ServiceBuilder;
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy, Derivative)]
@@ -23,6 +23,7 @@ pub enum NewRelicRegion {
     #[derivative(Default)]
     Us,
     Eu,
+    Fedramp,
 }
 
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy, Derivative)]
@@ -33,6 +34,19 @@ pub enum NewRelicApi {
     Events,
     Metrics,
     Logs,
+    Traces,
+}
+
+/// The format spans are sent to the Trace API in.
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum NewRelicTraceFormat {
+    /// New Relic's own format, where each event is a span with `id` and `trace.id` fields.
+    #[derivative(Default)]
+    Newrelic,
+    /// Each event is a Zipkin v2 span, which is sent as is.
+    Zipkin,
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -64,6 +78,11 @@ pub struct NewRelicConfig {
     pub account_id: String,
     pub region: Option<NewRelicRegion>,
     pub api: NewRelicApi,
+    #[serde(default)]
+    pub trace_format: NewRelicTraceFormat,
+    /// Attributes added to every metric, log, or span of a payload in its common block.
+    #[serde(default)]
+    pub common_attributes: BTreeMap<String, String>,
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
     #[serde(
@@ -104,6 +123,7 @@ impl SinkConfig for NewRelicConfig {
             .limit_max_events(self.batch.max_events.unwrap_or(50))?
             .into_batcher_settings()?;
 
+        self.request.reject_status_overrides()?;
         let request_limits = self.request.unwrap_with(&Default::default());
         let tls_settings = TlsSettings::from_options(&None)?;
         let client = HttpClient::new(tls_settings, &cx.proxy)?;
@@ -120,6 +140,7 @@ impl SinkConfig for NewRelicConfig {
             acker: cx.acker(),
             encoding,
             credentials,
+            common_attributes: self.common_attributes.clone(),
             compression: self.compression,
             batcher_settings,
         };
@@ -142,6 +163,7 @@ pub struct NewRelicCredentials {
     pub account_id: String,
     pub api: NewRelicApi,
     pub region: NewRelicRegion,
+    pub trace_format: NewRelicTraceFormat,
 }
 
 impl NewRelicCredentials {
@@ -160,19 +182,49 @@ impl NewRelicCredentials {
                 )
                 .parse::<Uri>()
                 .unwrap(),
+                NewRelicRegion::Fedramp => format!(
+                    "https://gov-insights-collector.newrelic.com/v1/accounts/{}/events",
+                    self.account_id
+                )
+                .parse::<Uri>()
+                .unwrap(),
             },
             NewRelicApi::Metrics => match self.region {
                 NewRelicRegion::Us => Uri::from_static("https://metric-api.newrelic.com/metric/v1"),
                 NewRelicRegion::Eu => {
                     Uri::from_static("https://metric-api.eu.newrelic.com/metric/v1")
                 }
+                NewRelicRegion::Fedramp => {
+                    Uri::from_static("https://gov-metric-api.newrelic.com/metric/v1")
+                }
             },
             NewRelicApi::Logs => match self.region {
                 NewRelicRegion::Us => Uri::from_static("https://log-api.newrelic.com/log/v1"),
                 NewRelicRegion::Eu => Uri::from_static("https://log-api.eu.newrelic.com/log/v1"),
+                NewRelicRegion::Fedramp => {
+                    Uri::from_static("https://gov-log-api.newrelic.com/log/v1")
+                }
+            },
+            NewRelicApi::Traces => match self.region {
+                NewRelicRegion::Us => Uri::from_static("https://trace-api.newrelic.com/trace/v1"),
+                NewRelicRegion::Eu => {
+                    Uri::from_static("https://trace-api.eu.newrelic.com/trace/v1")
+                }
+                NewRelicRegion::Fedramp => {
+                    Uri::from_static("https://gov-trace-api.newrelic.com/trace/v1")
+                }
             },
         }
     }
+
+    /// The `Data-Format` and `Data-Format-Version` headers the Trace API needs to parse spans.
+    pub const fn data_format(&self) -> Option<(&'static str, &'static str)> {
+        match (self.api, self.trace_format) {
+            (NewRelicApi::Traces, NewRelicTraceFormat::Newrelic) => Some(("newrelic", "1")),
+            (NewRelicApi::Traces, NewRelicTraceFormat::Zipkin) => Some(("zipkin", "2")),
+            _ => None,
+        }
+    }
 }
 
 impl From<&NewRelicConfig> for NewRelicCredentials {
@@ -182,6 +234,7 @@ impl From<&NewRelicConfig> for NewRelicCredentials {
             account_id: config.account_id.clone(),
             api: config.api,
             region: config.region.unwrap_or(NewRelicRegion::Us),
+            trace_format: config.trace_format,
         }
     }
 }
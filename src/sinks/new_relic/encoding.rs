@@ -21,6 +21,7 @@ impl Encoder<Result<NewRelicApiModel, NewRelicSinkError>> for EncodingConfigFixe
             NewRelicApiModel::Events(ev_api_model) => to_json(&ev_api_model)?,
             NewRelicApiModel::Metrics(met_api_model) => to_json(&met_api_model)?,
             NewRelicApiModel::Logs(log_api_model) => to_json(&log_api_model)?,
+            NewRelicApiModel::Traces(trace_api_model) => to_json(&trace_api_model)?,
         };
         let size = as_tracked_write::<_, _, io::Error>(writer, &json, |writer, json| {
             writer.write_all(json)?;
@@ -1,48 +1,79 @@
 use super::NewRelicSinkError;
-use crate::event::{Event, MetricValue, Value};
+use crate::event::{Event, LogEvent, MetricValue, Value};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, convert::TryFrom, fmt::Debug, time::SystemTime};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    fmt::Debug,
+    time::SystemTime,
+};
 
 #[derive(Debug)]
 pub enum NewRelicApiModel {
     Metrics(MetricsApiModel),
     Events(EventsApiModel),
     Logs(LogsApiModel),
+    Traces(TracesApiModel),
 }
 
 type KeyValData = HashMap<String, Value>;
-type DataStore = HashMap<String, Vec<KeyValData>>;
+
+/// A block of data of the Metric, Log, or Trace APIs, with the attributes common to all of its
+/// data.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct DataStore {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub common: Option<KeyValData>,
+    #[serde(flatten)]
+    pub data: HashMap<String, Vec<KeyValData>>,
+}
+
+impl DataStore {
+    fn new(key: &str, data: Vec<KeyValData>) -> Self {
+        Self {
+            common: None,
+            data: HashMap::from([(key.to_owned(), data)]),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Vec<KeyValData>> {
+        self.data.get(key)
+    }
+
+    fn set_common_attributes(&mut self, attributes: &BTreeMap<String, String>) {
+        if !attributes.is_empty() {
+            let attributes = attributes
+                .iter()
+                .map(|(key, value)| (key.clone(), Value::from(value.as_str())))
+                .collect::<BTreeMap<_, _>>();
+            self.common = Some(HashMap::from([(
+                "attributes".to_owned(),
+                Value::from(attributes),
+            )]));
+        }
+    }
+}
+
+fn timestamp_secs(timestamp: Option<DateTime<Utc>>) -> i64 {
+    timestamp
+        .unwrap_or_else(|| DateTime::<Utc>::from(SystemTime::now()))
+        .timestamp()
+}
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct MetricsApiModel(pub Vec<DataStore>);
 
 impl MetricsApiModel {
-    pub fn new(metric_array: Vec<(Value, Value, Value)>) -> Self {
-        let mut metric_data_array = vec![];
-        for (m_name, m_value, m_timestamp) in metric_array {
-            let mut metric_data = KeyValData::new();
-            metric_data.insert("name".to_owned(), m_name);
-            metric_data.insert("value".to_owned(), m_value);
-            match m_timestamp {
-                Value::Timestamp(ts) => {
-                    metric_data.insert("timestamp".to_owned(), Value::from(ts.timestamp()));
-                }
-                Value::Integer(i) => {
-                    metric_data.insert("timestamp".to_owned(), Value::from(i));
-                }
-                _ => {
-                    metric_data.insert(
-                        "timestamp".to_owned(),
-                        Value::from(DateTime::<Utc>::from(SystemTime::now()).timestamp()),
-                    );
-                }
-            }
-            metric_data_array.push(metric_data);
+    pub fn new(metric_array: Vec<KeyValData>) -> Self {
+        Self(vec![DataStore::new("metrics", metric_array)])
+    }
+
+    pub fn with_common_attributes(mut self, attributes: &BTreeMap<String, String>) -> Self {
+        for store in &mut self.0 {
+            store.set_common_attributes(attributes);
         }
-        let mut metric_store = DataStore::new();
-        metric_store.insert("metrics".to_owned(), metric_data_array);
-        Self(vec![metric_store])
+        self
     }
 }
 
@@ -54,26 +85,51 @@ impl TryFrom<Vec<Event>> for MetricsApiModel {
 
         for buf_event in buf_events {
             if let Event::Metric(metric) = buf_event {
-                // Future improvement: put metric type. If type = count, NR metric model requiere an interval.ms field, that is not provided by the Vector Metric model.
-                match metric.value() {
-                    MetricValue::Gauge { value } => {
-                        metric_array.push((
-                            Value::from(metric.name().to_owned()),
-                            Value::from(*value),
-                            Value::from(metric.timestamp()),
-                        ));
+                // Counters are sent as gauges, as a count requires an `interval.ms` field that is
+                // not provided by the Vector Metric model.
+                let (metric_type, value) = match metric.value() {
+                    MetricValue::Gauge { value } | MetricValue::Counter { value } => {
+                        ("gauge", Value::from(*value))
                     }
-                    MetricValue::Counter { value } => {
-                        metric_array.push((
-                            Value::from(metric.name().to_owned()),
-                            Value::from(*value),
-                            Value::from(metric.timestamp()),
-                        ));
+                    MetricValue::Distribution { samples, .. } if !samples.is_empty() => {
+                        let (mut count, mut sum) = (0.0, 0.0);
+                        let (mut min, mut max) = (f64::INFINITY, f64::NEG_INFINITY);
+                        for sample in samples {
+                            count += f64::from(sample.rate);
+                            sum += sample.value * f64::from(sample.rate);
+                            min = min.min(sample.value);
+                            max = max.max(sample.value);
+                        }
+                        let summary = BTreeMap::from([
+                            ("count".to_owned(), Value::from(count)),
+                            ("sum".to_owned(), Value::from(sum)),
+                            ("min".to_owned(), Value::from(min)),
+                            ("max".to_owned(), Value::from(max)),
+                        ]);
+                        ("summary", Value::from(summary))
                     }
                     _ => {
                         // Unrecognized metric type
+                        continue;
                     }
+                };
+
+                let mut metric_data = KeyValData::new();
+                metric_data.insert("name".to_owned(), Value::from(metric.name().to_owned()));
+                metric_data.insert("type".to_owned(), Value::from(metric_type));
+                metric_data.insert("value".to_owned(), value);
+                metric_data.insert(
+                    "timestamp".to_owned(),
+                    Value::from(timestamp_secs(metric.timestamp())),
+                );
+                if let Some(tags) = metric.tags() {
+                    let attributes = tags
+                        .iter()
+                        .map(|(key, value)| (key.clone(), Value::from(value.as_str())))
+                        .collect::<BTreeMap<_, _>>();
+                    metric_data.insert("attributes".to_owned(), Value::from(attributes));
                 }
+                metric_array.push(metric_data);
             }
         }
 
@@ -156,9 +212,14 @@ pub struct LogsApiModel(pub Vec<DataStore>);
 
 impl LogsApiModel {
     pub fn new(logs_array: Vec<KeyValData>) -> Self {
-        let mut logs_store = DataStore::new();
-        logs_store.insert("logs".to_owned(), logs_array);
-        Self(vec![logs_store])
+        Self(vec![DataStore::new("logs", logs_array)])
+    }
+
+    pub fn with_common_attributes(mut self, attributes: &BTreeMap<String, String>) -> Self {
+        for store in &mut self.0 {
+            store.set_common_attributes(attributes);
+        }
+        self
     }
 }
 
@@ -190,3 +251,92 @@ impl TryFrom<Vec<Event>> for LogsApiModel {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+pub enum TracesApiModel {
+    /// Spans in New Relic's format, where the fields of a span other than its `id`, `trace.id`
+    /// and `timestamp` are its attributes.
+    Newrelic(Vec<DataStore>),
+    /// Zipkin v2 spans, sent as they are.
+    Zipkin(Vec<Value>),
+}
+
+impl TracesApiModel {
+    pub fn zipkin(buf_events: Vec<Event>) -> Result<Self, NewRelicSinkError> {
+        let spans = buf_events
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Log(log) => Some(Value::from(log.into_parts().0)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if !spans.is_empty() {
+            Ok(Self::Zipkin(spans))
+        } else {
+            Err(NewRelicSinkError::new("No valid spans to generate"))
+        }
+    }
+
+    pub fn with_common_attributes(mut self, attributes: &BTreeMap<String, String>) -> Self {
+        if let Self::Newrelic(stores) = &mut self {
+            for store in stores {
+                store.set_common_attributes(attributes);
+            }
+        }
+        self
+    }
+}
+
+impl TryFrom<Vec<Event>> for TracesApiModel {
+    type Error = NewRelicSinkError;
+
+    fn try_from(buf_events: Vec<Event>) -> Result<Self, Self::Error> {
+        let mut spans_array = vec![];
+        for buf_event in buf_events {
+            if let Event::Log(log) = buf_event {
+                if let Some(span) = span_model(&log) {
+                    spans_array.push(span);
+                }
+            }
+        }
+
+        if !spans_array.is_empty() {
+            Ok(Self::Newrelic(vec![DataStore::new("spans", spans_array)]))
+        } else {
+            Err(NewRelicSinkError::new("No valid spans to generate"))
+        }
+    }
+}
+
+/// Maps a log to a span, if it has the IDs every span needs.
+fn span_model(log: &LogEvent) -> Option<KeyValData> {
+    let id = log.get("id")?;
+    let trace_id = log.get("trace.id")?;
+
+    let mut span_model = KeyValData::new();
+    span_model.insert("id".to_owned(), id.clone());
+    span_model.insert("trace.id".to_owned(), trace_id.clone());
+
+    match log.get("timestamp") {
+        Some(Value::Timestamp(ts)) => {
+            span_model.insert("timestamp".to_owned(), Value::from(ts.timestamp_millis()));
+        }
+        Some(Value::Integer(i)) => {
+            span_model.insert("timestamp".to_owned(), Value::from(*i));
+        }
+        _ => {}
+    }
+
+    let attributes = log
+        .all_fields()
+        .filter(|(key, _)| !matches!(key.as_str(), "id" | "trace.id" | "timestamp"))
+        .map(|(key, value)| (key, value.clone()))
+        .collect::<BTreeMap<_, _>>();
+    if !attributes.is_empty() {
+        span_model.insert("attributes".to_owned(), Value::from(attributes));
+    }
+
+    Some(span_model)
+}
@@ -92,6 +92,14 @@ impl Service<NewRelicApiRequest> for NewRelicApiService {
             http_request
         };
 
+        let http_request = if let Some((format, version)) = request.credentials.data_format() {
+            http_request
+                .header("Data-Format", format)
+                .header("Data-Format-Version", version)
+        } else {
+            http_request
+        };
+
         let payload_len = request.payload.len();
         let http_request = http_request
             .header(CONTENT_LENGTH, payload_len)
@@ -100,11 +108,15 @@ impl Service<NewRelicApiRequest> for NewRelicApiService {
 
         Box::pin(async move {
             match client.call(http_request).in_current_span().await {
-                Ok(_) => Ok(NewRelicApiResponse {
+                Ok(response) if response.status().is_success() => Ok(NewRelicApiResponse {
                     event_status: EventStatus::Delivered,
                     count: request.batch_size,
                     events_byte_size: payload_len,
                 }),
+                Ok(response) => Err(NewRelicSinkError::new(&format!(
+                    "HTTP request failed with status {}",
+                    response.status()
+                ))),
                 Err(_) => Err(NewRelicSinkError::new("HTTP request error")),
             }
         })
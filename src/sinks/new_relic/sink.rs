@@ -1,17 +1,20 @@
 use super::{
     Encoding, EventsApiModel, LogsApiModel, MetricsApiModel, NewRelicApi, NewRelicApiModel,
-    NewRelicApiRequest, NewRelicCredentials,
+    NewRelicApiRequest, NewRelicCredentials, NewRelicTraceFormat, TracesApiModel,
 };
 use crate::{
     event::Event,
     sinks::util::{
-        builder::SinkBuilderExt, encoding::EncodingConfigFixed, Compression, RequestBuilder,
-        StreamSink,
+        builder::SinkBuilderExt,
+        encoding::{Encoder, EncodingConfigFixed},
+        Compression, Compressor, RequestBuilder, StreamSink,
     },
 };
 use async_trait::async_trait;
 use futures::stream::{BoxStream, StreamExt};
-use std::{convert::TryFrom, fmt::Debug, num::NonZeroUsize, sync::Arc};
+use std::{
+    collections::BTreeMap, convert::TryFrom, fmt::Debug, io::Write, num::NonZeroUsize, sync::Arc,
+};
 use tower::Service;
 use vector_core::{
     buffers::Acker,
@@ -62,17 +65,33 @@ impl From<NewRelicSinkError> for std::io::Error {
     }
 }
 
+/// An encoded payload, and the compression that was applied to it.
+pub struct NewRelicPayload {
+    data: Vec<u8>,
+    compression: Compression,
+}
+
+impl From<Vec<u8>> for NewRelicPayload {
+    fn from(data: Vec<u8>) -> Self {
+        Self {
+            data,
+            compression: Compression::None,
+        }
+    }
+}
+
 struct NewRelicRequestBuilder {
     encoding: EncodingConfigFixed<Encoding>,
     compression: Compression,
     credentials: Arc<NewRelicCredentials>,
+    common_attributes: BTreeMap<String, String>,
 }
 
 impl RequestBuilder<Vec<Event>> for NewRelicRequestBuilder {
     type Metadata = (Arc<NewRelicCredentials>, usize, EventFinalizers);
     type Events = Result<NewRelicApiModel, Self::Error>;
     type Encoder = EncodingConfigFixed<Encoding>;
-    type Payload = Vec<u8>;
+    type Payload = NewRelicPayload;
     type Request = NewRelicApiRequest;
     type Error = NewRelicSinkError;
 
@@ -87,29 +106,61 @@ impl RequestBuilder<Vec<Event>> for NewRelicRequestBuilder {
     fn split_input(&self, mut input: Vec<Event>) -> (Self::Metadata, Self::Events) {
         let events_len = input.len();
         let finalizers = input.take_finalizers();
+        let common_attributes = &self.common_attributes;
         let api_model = || -> Result<NewRelicApiModel, Self::Error> {
             match self.credentials.api {
                 NewRelicApi::Events => {
                     Ok(NewRelicApiModel::Events(EventsApiModel::try_from(input)?))
                 }
-                NewRelicApi::Metrics => {
-                    Ok(NewRelicApiModel::Metrics(MetricsApiModel::try_from(input)?))
-                }
-                NewRelicApi::Logs => Ok(NewRelicApiModel::Logs(LogsApiModel::try_from(input)?)),
+                NewRelicApi::Metrics => Ok(NewRelicApiModel::Metrics(
+                    MetricsApiModel::try_from(input)?.with_common_attributes(common_attributes),
+                )),
+                NewRelicApi::Logs => Ok(NewRelicApiModel::Logs(
+                    LogsApiModel::try_from(input)?.with_common_attributes(common_attributes),
+                )),
+                NewRelicApi::Traces => Ok(NewRelicApiModel::Traces(
+                    match self.credentials.trace_format {
+                        NewRelicTraceFormat::Newrelic => TracesApiModel::try_from(input)?,
+                        NewRelicTraceFormat::Zipkin => TracesApiModel::zipkin(input)?,
+                    }
+                    .with_common_attributes(common_attributes),
+                )),
             }
         }();
         let metadata = (Arc::clone(&self.credentials), events_len, finalizers);
         (metadata, api_model)
     }
 
+    /// Small payloads often don't get any smaller when compressed, so the compression is chosen
+    /// for each payload, and it is only compressed if that makes it smaller.
+    fn encode_events(&self, events: Self::Events) -> Result<Self::Payload, Self::Error> {
+        let mut data = Vec::new();
+        self.encoder().encode_input(events, &mut data)?;
+
+        if self.compression.is_compressed() {
+            let mut compressor = Compressor::from(self.compression);
+            compressor.write_all(&data)?;
+            let compressed = compressor.finish()?;
+
+            if compressed.len() < data.len() {
+                return Ok(NewRelicPayload {
+                    data: compressed,
+                    compression: self.compression,
+                });
+            }
+        }
+
+        Ok(data.into())
+    }
+
     fn build_request(&self, metadata: Self::Metadata, payload: Self::Payload) -> Self::Request {
         let (_credentials, events_len, finalizers) = metadata;
         NewRelicApiRequest {
             batch_size: events_len,
             finalizers,
             credentials: Arc::clone(&self.credentials),
-            payload,
-            compression: self.compression,
+            payload: payload.data,
+            compression: payload.compression,
         }
     }
 }
@@ -119,6 +170,7 @@ pub struct NewRelicSink<S> {
     pub acker: Acker,
     pub encoding: EncodingConfigFixed<Encoding>,
     pub credentials: Arc<NewRelicCredentials>,
+    pub common_attributes: BTreeMap<String, String>,
     pub compression: Compression,
     pub batcher_settings: BatcherSettings,
 }
@@ -136,6 +188,7 @@ where
             encoding: self.encoding,
             compression: self.compression,
             credentials: Arc::clone(&self.credentials),
+            common_attributes: self.common_attributes,
         };
 
         let sink = input
@@ -1,7 +1,11 @@
 use super::*;
-use crate::event::{Event, LogEvent, Metric, MetricKind, MetricValue, Value};
-use chrono::{DateTime, Utc};
-use std::{collections::HashMap, convert::TryFrom, time::SystemTime};
+use crate::event::{Event, LogEvent, Metric, MetricKind, MetricValue, StatisticKind, Value};
+use chrono::{DateTime, TimeZone, Utc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    time::SystemTime,
+};
 
 #[test]
 fn generate_config() {
@@ -187,3 +191,131 @@ fn generate_metric_api_model() {
     assert_eq!(metrics[0].get("value").unwrap(), &Value::Float(100.0));
     assert!(metrics[0].get("timestamp").is_some());
 }
+
+#[test]
+fn generate_dimensional_metric_api_model() {
+    let event = Event::Metric(
+        Metric::new(
+            "my_metric",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.5 },
+        )
+        .with_tags(Some(
+            vec![("host".to_owned(), "web-1".to_owned())]
+                .into_iter()
+                .collect(),
+        )),
+    );
+    let distribution = Event::Metric(Metric::new(
+        "my_distribution",
+        MetricKind::Incremental,
+        MetricValue::Distribution {
+            samples: vector_core::samples![1.0 => 2, 4.0 => 1],
+            statistic: StatisticKind::Histogram,
+        },
+    ));
+    let model = MetricsApiModel::try_from(vec![event, distribution])
+        .expect("Failed mapping metrics into API model")
+        .with_common_attributes(&BTreeMap::from([(
+            "service.name".to_owned(),
+            "vector".to_owned(),
+        )]));
+    let json = serde_json::to_value(&model).unwrap();
+
+    assert_eq!(
+        json[0]["common"],
+        serde_json::json!({ "attributes": { "service.name": "vector" } })
+    );
+    let metrics = &json[0]["metrics"];
+    assert_eq!(metrics[0]["type"], "gauge");
+    assert_eq!(metrics[0]["value"], 1.5);
+    assert_eq!(
+        metrics[0]["attributes"],
+        serde_json::json!({ "host": "web-1" })
+    );
+    assert_eq!(metrics[1]["type"], "summary");
+    assert_eq!(
+        metrics[1]["value"],
+        serde_json::json!({ "count": 3.0, "sum": 6.0, "min": 1.0, "max": 4.0 })
+    );
+}
+
+#[test]
+fn generate_log_api_model_without_common_attributes() {
+    let event = Event::Log(LogEvent::from("This is a message"));
+    let model = LogsApiModel::try_from(vec![event])
+        .expect("Failed mapping logs into API model")
+        .with_common_attributes(&BTreeMap::new());
+    let json = serde_json::to_value(&model).unwrap();
+
+    assert!(json[0].get("common").is_none());
+    assert_eq!(json[0]["logs"][0]["message"], "This is a message");
+}
+
+#[test]
+fn generate_trace_api_model() {
+    let mut span = LogEvent::default();
+    span.insert("id", "span-1");
+    span.insert("trace.id", "trace-1");
+    span.insert("timestamp", Utc.timestamp(1_600_000_000, 0));
+    span.insert("name", "GET /");
+    span.insert("duration.ms", 12.5);
+    let mut orphan = LogEvent::default();
+    orphan.insert("id", "span-2");
+    let model = TracesApiModel::try_from(vec![Event::Log(span), Event::Log(orphan)])
+        .expect("Failed mapping spans into API model");
+
+    assert_eq!(
+        serde_json::to_value(&model).unwrap(),
+        serde_json::json!([{
+            "spans": [{
+                "id": "span-1",
+                "trace.id": "trace-1",
+                "timestamp": 1_600_000_000_000_i64,
+                "attributes": { "duration.ms": 12.5, "name": "GET /" },
+            }],
+        }])
+    );
+
+    let mut span = LogEvent::default();
+    span.insert("traceId", "5af7183fb1d4cf5f");
+    span.insert("localEndpoint.serviceName", "web");
+    let model = TracesApiModel::zipkin(vec![Event::Log(span)])
+        .expect("Failed mapping spans into API model");
+
+    assert_eq!(
+        serde_json::to_value(&model).unwrap(),
+        serde_json::json!([{
+            "localEndpoint": { "serviceName": "web" },
+            "traceId": "5af7183fb1d4cf5f",
+        }])
+    );
+}
+
+#[test]
+fn regional_endpoints() {
+    let credentials = |api, region| NewRelicCredentials {
+        license_key: "key".to_owned(),
+        account_id: "1234".to_owned(),
+        api,
+        region,
+        trace_format: NewRelicTraceFormat::Zipkin,
+    };
+
+    assert_eq!(
+        credentials(NewRelicApi::Events, NewRelicRegion::Fedramp).get_uri(),
+        "https://gov-insights-collector.newrelic.com/v1/accounts/1234/events"
+    );
+    assert_eq!(
+        credentials(NewRelicApi::Traces, NewRelicRegion::Eu).get_uri(),
+        "https://trace-api.eu.newrelic.com/trace/v1"
+    );
+    assert_eq!(
+        credentials(NewRelicApi::Traces, NewRelicRegion::Us).data_format(),
+        Some(("zipkin", "2"))
+    );
+    assert_eq!(
+        credentials(NewRelicApi::Logs, NewRelicRegion::Us).data_format(),
+        None
+    );
+}
@@ -156,6 +156,8 @@ impl NewRelicLogsConfig {
             encoding: EncodingConfig::<Encoding>::from(self.encoding.clone()).into_encoding(),
             batch: batch_settings.into(),
             request,
+            templates: Default::default(),
+            http2: Default::default(),
             tls: None,
         })
     }
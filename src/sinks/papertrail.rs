@@ -165,6 +165,8 @@ mod tests {
                 only_fields: None,
                 except_fields: Some(vec!["magic".into()]),
                 timestamp_format: None,
+                bytes_format: None,
+                decimal_format: None,
             },
         );
 
@@ -457,6 +457,13 @@ impl StreamSink<Event> for PrometheusExporter {
 
             // Now process the metric we got.
             let metric = event.into_metric();
+            if metric.series_ended() {
+                // The series won't be updated anymore, so stop exposing it right away rather than
+                // waiting for it to expire.
+                let mut metrics = self.metrics.write().unwrap();
+                metrics.retain(|metric_ref, _| metric_ref.series != *metric.series());
+            }
+
             if let Some(normalized) = normalizer.apply(metric) {
                 // We have a normalized metric, in absolute form.  If we're already aware of this
                 // metric, update its expiration deadline, otherwise, start tracking it.
@@ -689,6 +696,52 @@ mod tests {
         assert_eq!(expected_m2.0.value(), &expected_m2_value);
     }
 
+    #[tokio::test]
+    async fn sink_removes_ended_series() {
+        let config = PrometheusExporterConfig {
+            address: next_addr(), // Not actually bound, just needed to fill config
+            tls: None,
+            ..Default::default()
+        };
+        let cx = SinkContext::new_test();
+
+        let sink = Box::new(PrometheusExporter::new(config, cx.acker()));
+
+        let m1 = Metric::new(
+            "absolute",
+            MetricKind::Absolute,
+            MetricValue::Counter { value: 32. },
+        )
+        .with_tags(Some(
+            vec![("tag1".to_owned(), "value1".to_owned())]
+                .into_iter()
+                .collect(),
+        ));
+
+        let m2 = m1.clone().with_tags(Some(
+            vec![("tag1".to_owned(), "value2".to_owned())]
+                .into_iter()
+                .collect(),
+        ));
+
+        let metrics = vec![
+            Event::Metric(m1.clone()),
+            Event::Metric(m2.clone()),
+            Event::Metric(m1.clone().with_series_ended(true)),
+        ];
+
+        let metrics_handle = Arc::clone(&sink.metrics);
+
+        sink.run(Box::pin(futures::stream::iter(metrics)))
+            .await
+            .unwrap();
+
+        let metrics_after = metrics_handle.read().unwrap();
+
+        assert!(metrics_after.get(&MetricRef::from_metric(&m1)).is_none());
+        assert!(metrics_after.get(&MetricRef::from_metric(&m2)).is_some());
+    }
+
     #[tokio::test]
     async fn sink_distributions_as_histograms() {
         // When we get summary distributions, unless we've been configured to actually emit
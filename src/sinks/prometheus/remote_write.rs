@@ -107,7 +107,8 @@ impl SinkConfig for RemoteWriteConfig {
             let service = request.service(HttpRetryLogic, service);
             let service = ServiceBuilder::new().service(service);
             let buffer = PartitionBuffer::new(MetricsBuffer::new(batch.size));
-            let mut normalizer = MetricNormalizer::<PrometheusMetricNormalize>::default();
+            let mut normalizer = MetricNormalizer::<PrometheusMetricNormalize>::default()
+                .with_expiration(cx.expire_metrics());
 
             PartitionBatchSink::new(service, buffer, batch.timeout, cx.acker())
                 .with_flat_map(move |event: Event| {
@@ -383,6 +383,8 @@ mod tests {
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
+                bytes_format: None,
+                decimal_format: None,
             },
             &None,
         )
@@ -151,6 +151,7 @@ impl RedisSinkConfig {
         conn: ConnectionManager,
         cx: SinkContext,
     ) -> crate::Result<super::VectorSink> {
+        self.request.reject_status_overrides()?;
         let request = self.request.unwrap_with(&TowerRequestConfig {
             concurrency: Concurrency::Fixed(1),
             ..Default::default()
@@ -407,6 +408,8 @@ mod tests {
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
+                bytes_format: None,
+                decimal_format: None,
             },
         )
         .unwrap()
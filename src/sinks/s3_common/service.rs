@@ -1,10 +1,14 @@
-use std::task::{Context, Poll};
+use std::{
+    sync::Arc,
+    task::{Context, Poll},
+};
 
 use bytes::Bytes;
 use futures::{future::BoxFuture, stream};
 use md5::Digest;
 use rusoto_core::{ByteStream, Region, RusotoError};
-use rusoto_s3::{PutObjectError, PutObjectRequest, S3Client, S3};
+use rusoto_s3::{GetObjectError, GetObjectRequest, PutObjectError, PutObjectRequest, S3Client, S3};
+use tokio::io::AsyncReadExt;
 use tower::Service;
 use tracing_futures::Instrument;
 use vector_core::{
@@ -15,7 +19,9 @@ use vector_core::{
 };
 
 use super::config::S3Options;
-use crate::{internal_events::AwsBytesSent, serde::to_string};
+use crate::{
+    internal_events::AwsBytesSent, serde::to_string, sinks::util::object_storage::Manifests,
+};
 
 #[derive(Debug, Clone)]
 pub struct S3Request {
@@ -75,11 +81,22 @@ impl DriverResponse for S3Response {
 pub struct S3Service {
     client: S3Client,
     region: Region,
+    manifests: Option<Arc<Manifests>>,
 }
 
 impl S3Service {
     pub const fn new(client: S3Client, region: Region) -> S3Service {
-        S3Service { client, region }
+        S3Service {
+            client,
+            region,
+            manifests: None,
+        }
+    }
+
+    /// Keeps the manifests of the partitions written to up to date.
+    pub fn with_manifests(mut self, manifests: Option<Arc<Manifests>>) -> Self {
+        self.manifests = manifests;
+        self
     }
 
     pub fn client(&self) -> S3Client {
@@ -140,26 +157,90 @@ impl Service<S3Request> for S3Service {
             ..Default::default()
         };
 
+        // The manifest is written with the same access settings as the objects it lists.
+        let manifest = self.manifests.clone().map(|manifests| {
+            let template = PutObjectRequest {
+                bucket: request.bucket.clone(),
+                key: request.key.clone(),
+                acl: request.acl.clone(),
+                server_side_encryption: request.server_side_encryption.clone(),
+                ssekms_key_id: request.ssekms_key_id.clone(),
+                storage_class: request.storage_class.clone(),
+                ..Default::default()
+            };
+            (manifests, template)
+        });
+
         let region = self.region.clone();
         Box::pin(async move {
-            client
-                .put_object(request)
-                .in_current_span()
-                .await
-                .map(|_inner| {
-                    emit!(&AwsBytesSent {
-                        byte_size: request_size,
-                        region,
-                    });
-                    S3Response {
+            let result = client.put_object(request).in_current_span().await;
+
+            if let (Ok(_), Some((manifests, template))) = (&result, manifest) {
+                let object_key = template.key.clone();
+                let bucket = template.bucket.clone();
+                manifests
+                    .write(
+                        &object_key,
                         count,
-                        events_byte_size,
-                    }
-                })
+                        request_size,
+                        |key| {
+                            let request = GetObjectRequest {
+                                bucket,
+                                key,
+                                ..Default::default()
+                            };
+                            get_manifest(client.clone(), request).in_current_span()
+                        },
+                        |key, body| {
+                            let request = PutObjectRequest {
+                                key,
+                                content_type: Some("application/json".to_owned()),
+                                content_md5: Some(base64::encode(md5::Md5::digest(&body))),
+                                body: Some(bytes_to_bytestream(body)),
+                                ..template
+                            };
+                            client.put_object(request).in_current_span()
+                        },
+                    )
+                    .await;
+            }
+
+            result.map(|_inner| {
+                emit!(&AwsBytesSent {
+                    byte_size: request_size,
+                    region,
+                });
+                S3Response {
+                    count,
+                    events_byte_size,
+                }
+            })
         })
     }
 }
 
+/// Reads the manifest `request` points to, or `None` if there is no manifest yet.
+async fn get_manifest(
+    client: S3Client,
+    request: GetObjectRequest,
+) -> Result<Option<Bytes>, RusotoError<GetObjectError>> {
+    let output = match client.get_object(request).await {
+        Ok(output) => output,
+        Err(RusotoError::Service(GetObjectError::NoSuchKey(_))) => return Ok(None),
+        Err(error) => return Err(error),
+    };
+
+    let mut body = Vec::new();
+    if let Some(stream) = output.body {
+        stream
+            .into_async_read()
+            .read_to_end(&mut body)
+            .await
+            .map_err(|error| RusotoError::ParseError(error.to_string()))?;
+    }
+    Ok(Some(body.into()))
+}
+
 fn bytes_to_bytestream(buf: Bytes) -> ByteStream {
     // We _have_ to provide the size hint, because without it, Rusoto can't
     // generate the Content-Length header which is required for the S3 PutObject
@@ -152,7 +152,8 @@ impl SematextMetricsService {
             config,
             inner: http_service,
         };
-        let mut normalizer = MetricNormalizer::<SematextMetricNormalize>::default();
+        let mut normalizer = MetricNormalizer::<SematextMetricNormalize>::default()
+            .with_expiration(cx.expire_metrics());
 
         let sink = request
             .batch_sink(
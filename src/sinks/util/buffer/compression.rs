@@ -8,12 +8,18 @@ pub const GZIP_FAST: u32 = 1;
 pub const GZIP_DEFAULT: u32 = 6;
 pub const GZIP_BEST: u32 = 9;
 
+pub const ZSTD_FAST: i32 = 1;
+pub const ZSTD_DEFAULT: i32 = 3;
+pub const ZSTD_BEST: i32 = 21;
+
 #[derive(Debug, Derivative, Copy, Clone, Eq, PartialEq)]
 #[derivative(Default)]
 pub enum Compression {
     #[derivative(Default)]
     None,
     Gzip(flate2::Compression),
+    Zlib(flate2::Compression),
+    Zstd(i32),
 }
 
 impl Compression {
@@ -36,10 +42,22 @@ impl Compression {
         Compression::Gzip(flate2::Compression::new(6))
     }
 
+    pub const fn zlib_default() -> Compression {
+        Compression::Zlib(flate2::Compression::new(6))
+    }
+
+    pub const fn zstd_default() -> Compression {
+        Compression::Zstd(ZSTD_DEFAULT)
+    }
+
+    /// The `Content-Encoding` of payloads compressed with this compression.
     pub const fn content_encoding(self) -> Option<&'static str> {
         match self {
             Self::None => None,
             Self::Gzip(_) => Some("gzip"),
+            // HTTP's `deflate` is zlib-wrapped, despite the name.
+            Self::Zlib(_) => Some("deflate"),
+            Self::Zstd(_) => Some("zstd"),
         }
     }
 
@@ -47,6 +65,8 @@ impl Compression {
         match self {
             Self::None => "log",
             Self::Gzip(_) => "log.gz",
+            Self::Zlib(_) => "log.zz",
+            Self::Zstd(_) => "log.zst",
         }
     }
 }
@@ -55,17 +75,28 @@ impl fmt::Display for Compression {
         match *self {
             Compression::None => write!(f, "none"),
             Compression::Gzip(ref level) => write!(f, "gzip({})", level.level()),
+            Compression::Zlib(ref level) => write!(f, "zlib({})", level.level()),
+            Compression::Zstd(level) => write!(f, "zstd({})", level),
         }
     }
 }
 
 #[cfg(feature = "rusoto_core")]
-impl From<Compression> for rusoto_core::encoding::ContentEncoding {
-    fn from(compression: Compression) -> Self {
+impl TryFrom<Compression> for rusoto_core::encoding::ContentEncoding {
+    type Error = String;
+
+    fn try_from(compression: Compression) -> Result<Self, Self::Error> {
         match compression {
-            Compression::None => rusoto_core::encoding::ContentEncoding::Identity,
-            Compression::Gzip(level) => {
-                rusoto_core::encoding::ContentEncoding::Gzip(None, level.level())
+            Compression::None => Ok(rusoto_core::encoding::ContentEncoding::Identity),
+            Compression::Gzip(level) => Ok(rusoto_core::encoding::ContentEncoding::Gzip(
+                None,
+                level.level(),
+            )),
+            Compression::Zlib(_) => {
+                Err("Compression zlib is not supported by AWS, use gzip instead".into())
+            }
+            Compression::Zstd(_) => {
+                Err("Compression zstd is not supported by AWS, use gzip instead".into())
             }
         }
     }
@@ -92,9 +123,11 @@ impl<'de> de::Deserialize<'de> for Compression {
                 match s {
                     "none" => Ok(Compression::None),
                     "gzip" => Ok(Compression::gzip_default()),
+                    "zlib" => Ok(Compression::zlib_default()),
+                    "zstd" => Ok(Compression::zstd_default()),
                     _ => Err(de::Error::invalid_value(
                         de::Unexpected::Str(s),
-                        &r#""none" or "gzip""#,
+                        &r#""none", "gzip", "zlib" or "zstd""#,
                     )),
                 }
             }
@@ -118,37 +151,8 @@ impl<'de> de::Deserialize<'de> for Compression {
                             if level.is_some() {
                                 return Err(de::Error::duplicate_field("level"));
                             }
-                            level = Some(match map.next_value::<Value>()? {
-                                Value::Number(level) => match level.as_u64() {
-                                    Some(value) if value <= 9 => {
-                                        flate2::Compression::new(value as u32)
-                                    }
-                                    Some(_) | None => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Other(&level.to_string()),
-                                            &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
-                                        ))
-                                    }
-                                },
-                                Value::String(level) => match level.as_str() {
-                                    "none" => flate2::Compression::none(),
-                                    "fast" => flate2::Compression::fast(),
-                                    "default" => flate2::Compression::default(),
-                                    "best" => flate2::Compression::best(),
-                                    level => {
-                                        return Err(de::Error::invalid_value(
-                                            de::Unexpected::Str(level),
-                                            &r#""none", "fast", "best" or "default""#,
-                                        ))
-                                    }
-                                },
-                                value => {
-                                    return Err(de::Error::invalid_type(
-                                        de::Unexpected::Other(&value.to_string()),
-                                        &"integer or string",
-                                    ));
-                                }
-                            });
+                            // The valid levels depend on the algorithm, which might come after.
+                            level = Some(map.next_value::<Value>()?);
                         }
                         _ => return Err(de::Error::unknown_field(key, &["algorithm", "level"])),
                     };
@@ -159,8 +163,13 @@ impl<'de> de::Deserialize<'de> for Compression {
                         Some(_) => Err(de::Error::unknown_field("level", &[])),
                         None => Ok(Compression::None),
                     },
-                    "gzip" => Ok(Compression::Gzip(level.unwrap_or_default())),
-                    algorithm => Err(de::Error::unknown_variant(algorithm, &["none", "gzip"])),
+                    "gzip" => Ok(Compression::Gzip(flate2_level(level)?)),
+                    "zlib" => Ok(Compression::Zlib(flate2_level(level)?)),
+                    "zstd" => Ok(Compression::Zstd(zstd_level(level)?)),
+                    algorithm => Err(de::Error::unknown_variant(
+                        algorithm,
+                        &["none", "gzip", "zlib", "zstd"],
+                    )),
                 }
             }
         }
@@ -169,6 +178,63 @@ impl<'de> de::Deserialize<'de> for Compression {
     }
 }
 
+/// Parses the level of gzip or zlib compression.
+fn flate2_level<E: de::Error>(level: Option<Value>) -> Result<flate2::Compression, E> {
+    match level {
+        None => Ok(flate2::Compression::default()),
+        Some(Value::Number(level)) => match level.as_u64() {
+            Some(value) if value <= 9 => Ok(flate2::Compression::new(value as u32)),
+            Some(_) | None => Err(de::Error::invalid_value(
+                de::Unexpected::Other(&level.to_string()),
+                &"0, 1, 2, 3, 4, 5, 6, 7, 8 or 9",
+            )),
+        },
+        Some(Value::String(level)) => match level.as_str() {
+            "none" => Ok(flate2::Compression::none()),
+            "fast" => Ok(flate2::Compression::fast()),
+            "default" => Ok(flate2::Compression::default()),
+            "best" => Ok(flate2::Compression::best()),
+            level => Err(de::Error::invalid_value(
+                de::Unexpected::Str(level),
+                &r#""none", "fast", "best" or "default""#,
+            )),
+        },
+        Some(value) => Err(de::Error::invalid_type(
+            de::Unexpected::Other(&value.to_string()),
+            &"integer or string",
+        )),
+    }
+}
+
+/// Parses the level of zstd compression.
+fn zstd_level<E: de::Error>(level: Option<Value>) -> Result<i32, E> {
+    match level {
+        None => Ok(ZSTD_DEFAULT),
+        Some(Value::Number(level)) => match level.as_u64() {
+            Some(value) if (ZSTD_FAST as u64..=ZSTD_BEST as u64).contains(&value) => {
+                Ok(value as i32)
+            }
+            Some(_) | None => Err(de::Error::invalid_value(
+                de::Unexpected::Other(&level.to_string()),
+                &"an integer from 1 to 21",
+            )),
+        },
+        Some(Value::String(level)) => match level.as_str() {
+            "fast" => Ok(ZSTD_FAST),
+            "default" => Ok(ZSTD_DEFAULT),
+            "best" => Ok(ZSTD_BEST),
+            level => Err(de::Error::invalid_value(
+                de::Unexpected::Str(level),
+                &r#""fast", "best" or "default""#,
+            )),
+        },
+        Some(value) => Err(de::Error::invalid_type(
+            de::Unexpected::Other(&value.to_string()),
+            &"integer or string",
+        )),
+    }
+}
+
 impl ser::Serialize for Compression {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -191,6 +257,25 @@ impl ser::Serialize for Compression {
                     level => map.serialize_entry("level", &level)?,
                 };
             }
+            Compression::Zlib(level) => {
+                map.serialize_entry("algorithm", "zlib")?;
+                match level.level() {
+                    GZIP_NONE => map.serialize_entry("level", "none")?,
+                    GZIP_FAST => map.serialize_entry("level", "fast")?,
+                    GZIP_DEFAULT => {}
+                    GZIP_BEST => map.serialize_entry("level", "best")?,
+                    level => map.serialize_entry("level", &level)?,
+                };
+            }
+            Compression::Zstd(level) => {
+                map.serialize_entry("algorithm", "zstd")?;
+                match *level {
+                    ZSTD_FAST => map.serialize_entry("level", "fast")?,
+                    ZSTD_DEFAULT => {}
+                    ZSTD_BEST => map.serialize_entry("level", "best")?,
+                    level => map.serialize_entry("level", &level)?,
+                };
+            }
         };
         map.end()
     }
@@ -217,6 +302,23 @@ mod test {
                 r#"{"algorithm": "gzip", "level": 8}"#,
                 Compression::Gzip(flate2::Compression::new(8)),
             ),
+            (
+                r#""zlib""#,
+                Compression::Zlib(flate2::Compression::default()),
+            ),
+            (
+                r#"{"level": "fast", "algorithm": "zlib"}"#,
+                Compression::Zlib(flate2::Compression::fast()),
+            ),
+            (r#""zstd""#, Compression::Zstd(3)),
+            (
+                r#"{"algorithm": "zstd", "level": 19}"#,
+                Compression::Zstd(19),
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": "best"}"#,
+                Compression::Zstd(21),
+            ),
         ];
         for (sources, result) in fixtures_valid.iter() {
             let deserialized: Result<Compression, _> = serde_json::from_str(sources);
@@ -230,11 +332,11 @@ mod test {
             ),
             (
                 r#""b42""#,
-                r#"invalid value: string "b42", expected "none" or "gzip" at line 1 column 5"#,
+                r#"invalid value: string "b42", expected "none", "gzip", "zlib" or "zstd" at line 1 column 5"#,
             ),
             (
                 r#"{"algorithm": "b42"}"#,
-                r#"unknown variant `b42`, expected `none` or `gzip` at line 1 column 20"#,
+                r#"unknown variant `b42`, expected one of `none`, `gzip`, `zlib`, `zstd` at line 1 column 20"#,
             ),
             (
                 r#"{"algorithm": "none", "level": "default"}"#,
@@ -252,6 +354,14 @@ mod test {
                 r#"{"algorithm": "gzip", "level": {}}"#,
                 r#"invalid type: {}, expected integer or string at line 1 column 34"#,
             ),
+            (
+                r#"{"algorithm": "zstd", "level": 22}"#,
+                r#"invalid value: 22, expected an integer from 1 to 21 at line 1 column 34"#,
+            ),
+            (
+                r#"{"algorithm": "zstd", "level": "none"}"#,
+                r#"invalid value: string "none", expected "fast", "best" or "default" at line 1 column 38"#,
+            ),
             (
                 r#"{"algorithm": "gzip", "level": "default", "key": 42}"#,
                 r#"unknown field `key`, expected `algorithm` or `level` at line 1 column 47"#,
@@ -263,4 +373,23 @@ mod test {
             assert_eq!(error.to_string().as_str(), *result);
         }
     }
+
+    #[cfg(feature = "rusoto_core")]
+    #[test]
+    fn rusoto_content_encoding() {
+        use rusoto_core::encoding::ContentEncoding;
+
+        assert!(matches!(
+            ContentEncoding::try_from(Compression::None),
+            Ok(ContentEncoding::Identity)
+        ));
+        assert!(matches!(
+            ContentEncoding::try_from(Compression::Gzip(flate2::Compression::new(8))),
+            Ok(ContentEncoding::Gzip(None, 8))
+        ));
+        assert_eq!(
+            ContentEncoding::try_from(Compression::Zstd(3)).unwrap_err(),
+            "Compression zstd is not supported by AWS, use gzip instead"
+        );
+    }
 }
@@ -1,4 +1,8 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    time::{Duration, Instant},
+};
 
 use vector_core::event::{
     metric::{Metric, MetricData, MetricKind, MetricSeries, MetricValue, Sample},
@@ -92,6 +96,7 @@ impl Batch for MetricsBuffer {
 pub struct MetricNormalizer<N> {
     state: MetricSet,
     normalizer: N,
+    expiration: Option<Expiration>,
 }
 
 impl<N> MetricNormalizer<N> {
@@ -99,6 +104,21 @@ impl<N> MetricNormalizer<N> {
     pub fn get_state_mut(&mut self) -> &mut MetricSet {
         &mut self.state
     }
+
+    /// Forgets series that haven't been seen for `expire_after`, if it's set, so that the state
+    /// doesn't keep growing with series that aren't sent anymore.
+    pub fn with_expiration(mut self, expire_after: Option<Duration>) -> Self {
+        self.expiration = expire_after.map(Expiration::new);
+        self
+    }
+
+    /// Forgets the given series.
+    fn remove(&mut self, series: &MetricSeries) {
+        self.state.remove(series);
+        if let Some(expiration) = &mut self.expiration {
+            expiration.last_seen.remove(series);
+        }
+    }
 }
 
 impl<N: MetricNormalize> MetricNormalizer<N> {
@@ -108,17 +128,31 @@ impl<N: MetricNormalize> MetricNormalizer<N> {
     /// a metric from absolute to incremental, a metric must be seen twice in order to generate an
     /// incremental delta, so the first call for the the same metric would return `None` while the
     /// second call would return `Some(...)`.
+    ///
+    /// Metrics marked as ending their series are never returned, and make the normalizer forget
+    /// the series.
     pub fn apply(&mut self, metric: Metric) -> Option<Metric> {
+        self.apply_at(metric, Instant::now())
+    }
+
+    fn apply_at(&mut self, metric: Metric, now: Instant) -> Option<Metric> {
+        if metric.series_ended() {
+            self.remove(metric.series());
+            return None;
+        }
+
+        if let Some(expiration) = &mut self.expiration {
+            expiration.expire(&mut self.state, now);
+            expiration.touch(metric.series(), now);
+        }
+
         self.normalizer.apply_state(&mut self.state, metric)
     }
 }
 
 impl<N: Default> MetricNormalizer<N> {
     pub fn default() -> Self {
-        Self {
-            state: MetricSet::default(),
-            normalizer: N::default(),
-        }
+        Self::from(N::default())
     }
 }
 
@@ -127,8 +161,55 @@ impl<N> From<N> for MetricNormalizer<N> {
         Self {
             state: MetricSet::default(),
             normalizer,
+            expiration: None,
+        }
+    }
+}
+
+/// Tracks when each series of a `MetricNormalizer` was last seen.
+struct Expiration {
+    expire_after: Duration,
+    last_seen: HashMap<MetricSeries, Instant>,
+    last_swept: Instant,
+}
+
+impl Expiration {
+    fn new(expire_after: Duration) -> Self {
+        Self {
+            expire_after,
+            last_seen: HashMap::new(),
+            last_swept: Instant::now(),
+        }
+    }
+
+    fn touch(&mut self, series: &MetricSeries, now: Instant) {
+        match self.last_seen.get_mut(series) {
+            Some(last_seen) => *last_seen = now,
+            None => {
+                self.last_seen.insert(series.clone(), now);
+            }
         }
     }
+
+    /// Removes the expired series from `state`.
+    ///
+    /// Going through every series is only done once per expiration period, so series are kept for
+    /// up to twice as long as they should be.
+    fn expire(&mut self, state: &mut MetricSet, now: Instant) {
+        if now.saturating_duration_since(self.last_swept) < self.expire_after {
+            return;
+        }
+        self.last_swept = now;
+
+        let expire_after = self.expire_after;
+        self.last_seen.retain(|series, last_seen| {
+            let expired = now.saturating_duration_since(*last_seen) >= expire_after;
+            if expired {
+                state.remove(series);
+            }
+            !expired
+        });
+    }
 }
 
 /// The metrics state trait abstracts how data point normalization is
@@ -412,6 +493,53 @@ mod test {
         rebuffer::<State>(events)
     }
 
+    #[test]
+    fn series_ended_resets_state() {
+        let mut normalizer = MetricNormalizer::<IncrementalMetricNormalizer>::default();
+
+        assert_eq!(normalizer.apply(sample_gauge(1, Absolute, 1.0)), None);
+        assert_eq!(
+            normalizer.apply(sample_gauge(1, Absolute, 3.0)),
+            Some(sample_gauge(1, Incremental, 2.0))
+        );
+        assert_eq!(
+            normalizer.apply(sample_gauge(1, Absolute, 4.0).with_series_ended(true)),
+            None
+        );
+        // The series was forgotten, so this is a new reference value.
+        assert_eq!(normalizer.apply(sample_gauge(1, Absolute, 5.0)), None);
+    }
+
+    #[test]
+    fn expired_series_reset_state() {
+        let expire_after = Duration::from_secs(10);
+        let mut normalizer = MetricNormalizer::<IncrementalMetricNormalizer>::default()
+            .with_expiration(Some(expire_after));
+        let start = Instant::now();
+
+        assert_eq!(
+            normalizer.apply_at(sample_gauge(1, Absolute, 1.0), start),
+            None
+        );
+        assert_eq!(
+            normalizer.apply_at(sample_gauge(2, Absolute, 1.0), start),
+            None
+        );
+        assert_eq!(
+            normalizer.apply_at(sample_gauge(2, Absolute, 2.0), start + expire_after / 2),
+            Some(sample_gauge(2, Incremental, 1.0))
+        );
+        // The first series expired, while the second one was updated just in time.
+        assert_eq!(
+            normalizer.apply_at(sample_gauge(1, Absolute, 3.0), start + expire_after),
+            None
+        );
+        assert_eq!(
+            normalizer.apply_at(sample_gauge(2, Absolute, 4.0), start + expire_after),
+            Some(sample_gauge(2, Incremental, 2.0))
+        );
+    }
+
     #[test]
     fn abs_buffer_incremental_counters() {
         let buffer = rebuffer_incremental_counters::<AbsoluteMetricNormalizer>();
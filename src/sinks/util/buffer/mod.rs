@@ -1,8 +1,9 @@
 use std::io::Write;
 
-use flate2::write::GzEncoder;
-
-use super::batch::{err_event_too_large, Batch, BatchSize, PushResult};
+use super::{
+    batch::{err_event_too_large, Batch, BatchSize, PushResult},
+    Compressor,
+};
 
 pub mod compression;
 pub mod json;
@@ -15,19 +16,13 @@ pub use partition::{Partition, PartitionBuffer, PartitionInnerBuffer};
 
 #[derive(Debug)]
 pub struct Buffer {
-    inner: Option<InnerBuffer>,
+    inner: Option<Compressor>,
     num_items: usize,
     num_bytes: usize,
     settings: BatchSize<Self>,
     compression: Compression,
 }
 
-#[derive(Debug)]
-pub enum InnerBuffer {
-    Plain(Vec<u8>),
-    Gzip(GzEncoder<Vec<u8>>),
-}
-
 impl Buffer {
     pub const fn new(settings: BatchSize<Self>, compression: Compression) -> Self {
         Self {
@@ -39,37 +34,21 @@ impl Buffer {
         }
     }
 
-    fn buffer(&mut self) -> &mut InnerBuffer {
-        let bytes = self.settings.bytes;
+    fn buffer(&mut self) -> &mut Compressor {
         let compression = self.compression;
-        self.inner.get_or_insert_with(|| {
-            let buffer = Vec::with_capacity(bytes);
-            match compression {
-                Compression::None => InnerBuffer::Plain(buffer),
-                Compression::Gzip(level) => InnerBuffer::Gzip(GzEncoder::new(buffer, level)),
-            }
-        })
+        self.inner
+            .get_or_insert_with(|| Compressor::from(compression))
     }
 
     pub fn push(&mut self, input: &[u8]) {
         self.num_items += 1;
-        match self.buffer() {
-            InnerBuffer::Plain(inner) => {
-                inner.extend_from_slice(input);
-            }
-            InnerBuffer::Gzip(inner) => {
-                inner.write_all(input).unwrap();
-            }
-        }
+        self.buffer().write_all(input).unwrap();
     }
 
     pub fn is_empty(&self) -> bool {
         self.inner
             .as_ref()
-            .map(|inner| match inner {
-                InnerBuffer::Plain(inner) => inner.is_empty(),
-                InnerBuffer::Gzip(inner) => inner.get_ref().is_empty(),
-            })
+            .map(|inner| inner.get_ref().is_empty())
             .unwrap_or(true)
     }
 }
@@ -105,13 +84,7 @@ impl Batch for Buffer {
     }
 
     fn finish(self) -> Self::Output {
-        match self.inner {
-            Some(InnerBuffer::Plain(inner)) => inner,
-            Some(InnerBuffer::Gzip(inner)) => inner
-                .finish()
-                .expect("This can't fail because the inner writer is a Vec"),
-            None => Vec::new(),
-        }
+        self.inner.map(Compressor::into_inner).unwrap_or_default()
     }
 
     fn num_items(&self) -> usize {
@@ -130,7 +103,7 @@ mod test {
     use tokio::time::Duration;
     use vector_core::buffers::Acker;
 
-    use super::{Buffer, Compression};
+    use super::{Batch, Buffer, Compression};
     use crate::sinks::util::{BatchSettings, BatchSink, EncodedEvent};
 
     #[tokio::test]
@@ -193,4 +166,29 @@ mod test {
         .take(100_000)
         .flatten()));
     }
+
+    #[test]
+    fn zlib_and_zstd() {
+        use flate2::read::ZlibDecoder;
+
+        for compression in [Compression::zlib_default(), Compression::Zstd(19)] {
+            let mut buffer = Buffer::new(BatchSettings::default().size, compression);
+            buffer.push(b"first line\n");
+            buffer.push(b"second line\n");
+
+            let compressed = Batch::finish(buffer);
+            let mut decompressed = vec![];
+            match compression {
+                Compression::Zstd(_) => {
+                    decompressed = zstd::stream::decode_all(compressed.as_slice()).unwrap();
+                }
+                _ => {
+                    ZlibDecoder::new(compressed.as_slice())
+                        .read_to_end(&mut decompressed)
+                        .unwrap();
+                }
+            }
+            assert_eq!(decompressed, b"first line\nsecond line\n");
+        }
+    }
 }
@@ -1,4 +1,4 @@
-use std::io;
+use std::{fmt, io};
 
 use flate2::write::{GzEncoder, ZlibEncoder};
 
@@ -8,6 +8,7 @@ enum Writer {
     Plain(Vec<u8>),
     Gzip(GzEncoder<Vec<u8>>),
     Zlib(ZlibEncoder<Vec<u8>>),
+    Zstd(zstd::stream::write::Encoder<Vec<u8>>),
 }
 
 impl Writer {
@@ -16,6 +17,7 @@ impl Writer {
             Writer::Plain(inner) => inner,
             Writer::Gzip(inner) => inner.get_ref(),
             Writer::Zlib(inner) => inner.get_ref(),
+            Writer::Zstd(inner) => inner.get_ref(),
         }
     }
 }
@@ -26,6 +28,13 @@ impl From<Compression> for Writer {
         match compression {
             Compression::None => Writer::Plain(buffer),
             Compression::Gzip(level) => Writer::Gzip(GzEncoder::new(buffer, level)),
+            Compression::Zlib(level) => Writer::Zlib(ZlibEncoder::new(buffer, level)),
+            // Levels are validated when the config is loaded, so this only fails if zstd can't
+            // allocate its context.
+            Compression::Zstd(level) => Writer::Zstd(
+                zstd::stream::write::Encoder::new(buffer, level)
+                    .expect("zstd encoder should not fail to initialize"),
+            ),
         }
     }
 }
@@ -36,6 +45,7 @@ impl io::Write for Writer {
             Writer::Plain(inner_buf) => inner_buf.write(buf),
             Writer::Gzip(writer) => writer.write(buf),
             Writer::Zlib(writer) => writer.write(buf),
+            Writer::Zstd(writer) => writer.write(buf),
         }
     }
 
@@ -44,6 +54,7 @@ impl io::Write for Writer {
             Writer::Plain(_) => Ok(()),
             Writer::Gzip(writer) => writer.flush(),
             Writer::Zlib(writer) => writer.flush(),
+            Writer::Zstd(writer) => writer.flush(),
         }
     }
 }
@@ -79,6 +90,7 @@ impl Compressor {
             Writer::Plain(buf) => buf,
             Writer::Gzip(writer) => writer.finish()?,
             Writer::Zlib(writer) => writer.finish()?,
+            Writer::Zstd(writer) => writer.finish()?,
         };
 
         Ok(buf)
@@ -102,6 +114,9 @@ impl Compressor {
             Writer::Zlib(writer) => writer
                 .finish()
                 .expect("zlib writer should not fail to finish"),
+            Writer::Zstd(writer) => writer
+                .finish()
+                .expect("zstd writer should not fail to finish"),
         }
     }
 }
@@ -116,6 +131,21 @@ impl io::Write for Compressor {
     }
 }
 
+impl fmt::Debug for Compressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let compression = match self.inner {
+            Writer::Plain(_) => "none",
+            Writer::Gzip(_) => "gzip",
+            Writer::Zlib(_) => "zlib",
+            Writer::Zstd(_) => "zstd",
+        };
+        f.debug_struct("Compressor")
+            .field("compression", &compression)
+            .field("len", &self.get_ref().len())
+            .finish()
+    }
+}
+
 impl From<Compression> for Compressor {
     fn from(compression: Compression) -> Self {
         Compressor {
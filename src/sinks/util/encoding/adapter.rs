@@ -1,6 +1,6 @@
 #![deny(missing_docs)]
 
-use super::{EncodingConfiguration, TimestampFormat};
+use super::{BytesFormat, DecimalFormat, EncodingConfiguration, TimestampFormat};
 use crate::{
     codecs::encoding::{Framer, FramingConfig, Serializer, SerializerConfig},
     event::{Event, PathComponent},
@@ -56,6 +56,8 @@ where
                 encoding,
                 filter: None,
                 timestamp_format: None,
+                bytes_format: None,
+                decimal_format: None,
             },
         })
     }
@@ -102,11 +104,15 @@ where
                             _ => None,
                         });
                 let timestamp_format = config.encoding.timestamp_format;
+                let bytes_format = config.encoding.bytes_format;
+                let decimal_format = config.encoding.decimal_format;
 
                 Transformer {
                     only_fields,
                     except_fields,
                     timestamp_format,
+                    bytes_format,
+                    decimal_format,
                 }
             }
             Self::LegacyEncodingConfig(config) => Transformer {
@@ -123,6 +129,8 @@ where
                 }),
                 except_fields: config.encoding.except_fields().clone(),
                 timestamp_format: *config.encoding.timestamp_format(),
+                bytes_format: *config.encoding.bytes_format(),
+                decimal_format: *config.encoding.decimal_format(),
             },
         }
     }
@@ -175,6 +183,8 @@ pub struct EncodingWithTransformationConfig {
     #[serde(flatten)]
     filter: Option<OnlyOrExceptFieldsConfig>,
     timestamp_format: Option<TimestampFormat>,
+    bytes_format: Option<BytesFormat>,
+    decimal_format: Option<DecimalFormat>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -198,6 +208,8 @@ pub struct Transformer {
     only_fields: Option<Vec<Vec<PathComponent<'static>>>>,
     except_fields: Option<Vec<String>>,
     timestamp_format: Option<TimestampFormat>,
+    bytes_format: Option<BytesFormat>,
+    decimal_format: Option<DecimalFormat>,
 }
 
 impl Transformer {
@@ -228,6 +240,14 @@ impl EncodingConfiguration for Transformer {
     fn timestamp_format(&self) -> &Option<TimestampFormat> {
         &self.timestamp_format
     }
+
+    fn bytes_format(&self) -> &Option<BytesFormat> {
+        &self.bytes_format
+    }
+
+    fn decimal_format(&self) -> &Option<DecimalFormat> {
+        &self.decimal_format
+    }
 }
 
 #[cfg(test)]
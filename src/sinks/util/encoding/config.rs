@@ -12,7 +12,8 @@ use crate::{
     event::{PathComponent, PathIter},
     serde::skip_serializing_if_default,
     sinks::util::encoding::{
-        with_default::EncodingConfigWithDefault, EncodingConfiguration, TimestampFormat,
+        with_default::EncodingConfigWithDefault, BytesFormat, DecimalFormat, EncodingConfiguration,
+        TimestampFormat,
     },
 };
 
@@ -33,6 +34,10 @@ pub struct EncodingConfig<E> {
     pub(crate) except_fields: Option<Vec<String>>,
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
     pub(crate) timestamp_format: Option<TimestampFormat>,
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) bytes_format: Option<BytesFormat>,
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) decimal_format: Option<DecimalFormat>,
 }
 
 impl<E> EncodingConfiguration for EncodingConfig<E> {
@@ -58,6 +63,14 @@ impl<E> EncodingConfiguration for EncodingConfig<E> {
     fn timestamp_format(&self) -> &Option<TimestampFormat> {
         &self.timestamp_format
     }
+
+    fn bytes_format(&self) -> &Option<BytesFormat> {
+        &self.bytes_format
+    }
+
+    fn decimal_format(&self) -> &Option<DecimalFormat> {
+        &self.decimal_format
+    }
 }
 
 impl<E> From<EncodingConfigWithDefault<E>> for EncodingConfig<E>
@@ -71,6 +84,8 @@ where
             only_fields: encoding.only_fields,
             except_fields: encoding.except_fields,
             timestamp_format: encoding.timestamp_format,
+            bytes_format: encoding.bytes_format,
+            decimal_format: encoding.decimal_format,
         }
     }
 }
@@ -87,6 +102,8 @@ impl<E> EncodingConfig<E> {
             only_fields: self.only_fields,
             except_fields: self.except_fields,
             timestamp_format: self.timestamp_format,
+            bytes_format: self.bytes_format,
+            decimal_format: self.decimal_format,
         }
     }
 }
@@ -99,6 +116,8 @@ impl<E> From<E> for EncodingConfig<E> {
             only_fields: Default::default(),
             except_fields: Default::default(),
             timestamp_format: Default::default(),
+            bytes_format: Default::default(),
+            decimal_format: Default::default(),
         }
     }
 }
@@ -140,6 +159,8 @@ where
                     only_fields: Default::default(),
                     except_fields: Default::default(),
                     timestamp_format: Default::default(),
+                    bytes_format: Default::default(),
+                    decimal_format: Default::default(),
                 })
             }
 
@@ -173,6 +194,8 @@ where
             }),
             except_fields: inner.except_fields,
             timestamp_format: inner.timestamp_format,
+            bytes_format: inner.bytes_format,
+            decimal_format: inner.decimal_format,
         };
 
         concrete.validate().map_err(serde::de::Error::custom)?;
@@ -191,4 +214,8 @@ pub struct Inner<E> {
     except_fields: Option<Vec<String>>,
     #[serde(default)]
     timestamp_format: Option<TimestampFormat>,
+    #[serde(default)]
+    bytes_format: Option<BytesFormat>,
+    #[serde(default)]
+    decimal_format: Option<DecimalFormat>,
 }
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     event::PathComponent,
     serde::skip_serializing_if_default,
-    sinks::util::encoding::{EncodingConfiguration, TimestampFormat},
+    sinks::util::encoding::{BytesFormat, DecimalFormat, EncodingConfiguration, TimestampFormat},
 };
 
 /// A structure to wrap sink encodings and enforce field privacy.
@@ -30,6 +30,12 @@ pub struct EncodingConfigFixed<E: Default + PartialEq> {
     /// Format for outgoing timestamps.
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
     pub(crate) timestamp_format: Option<TimestampFormat>,
+    /// How to encode byte values that aren't valid UTF-8.
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) bytes_format: Option<BytesFormat>,
+    /// How to encode decimal values.
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) decimal_format: Option<DecimalFormat>,
 }
 
 impl<E: Default + PartialEq> EncodingConfiguration for EncodingConfigFixed<E> {
@@ -55,6 +61,14 @@ impl<E: Default + PartialEq> EncodingConfiguration for EncodingConfigFixed<E> {
     fn timestamp_format(&self) -> &Option<TimestampFormat> {
         &self.timestamp_format
     }
+
+    fn bytes_format(&self) -> &Option<BytesFormat> {
+        &self.bytes_format
+    }
+
+    fn decimal_format(&self) -> &Option<DecimalFormat> {
+        &self.decimal_format
+    }
 }
 
 impl<E> From<E> for EncodingConfigFixed<E>
@@ -68,6 +82,8 @@ where
             only_fields: Default::default(),
             except_fields: Default::default(),
             timestamp_format: Default::default(),
+            bytes_format: Default::default(),
+            decimal_format: Default::default(),
         }
     }
 }
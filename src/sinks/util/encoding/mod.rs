@@ -120,6 +120,8 @@ pub trait EncodingConfiguration {
     fn only_fields(&self) -> &Option<Vec<Vec<PathComponent>>>;
     fn except_fields(&self) -> &Option<Vec<String>>;
     fn timestamp_format(&self) -> &Option<TimestampFormat>;
+    fn bytes_format(&self) -> &Option<BytesFormat>;
+    fn decimal_format(&self) -> &Option<DecimalFormat>;
 
     fn apply_only_fields(&self, log: &mut LogEvent) {
         if let Some(only_fields) = &self.only_fields() {
@@ -170,6 +172,46 @@ pub trait EncodingConfiguration {
             }
         }
     }
+    fn apply_bytes_format(&self, log: &mut LogEvent) {
+        if let Some(bytes_format) = &self.bytes_format() {
+            match bytes_format {
+                BytesFormat::Base64 => {
+                    let mut encoded = Vec::new();
+                    for (k, v) in log.all_fields() {
+                        if let Value::Bytes(bytes) = v {
+                            if std::str::from_utf8(bytes).is_err() {
+                                encoded.push((k.clone(), Value::from(base64::encode(bytes))));
+                            }
+                        }
+                    }
+                    for (k, v) in encoded {
+                        log.insert(k, v);
+                    }
+                }
+                // Invalid UTF-8 is replaced when bytes are serialized as strings by default.
+                BytesFormat::Lossy => (),
+            }
+        }
+    }
+    fn apply_decimal_format(&self, log: &mut LogEvent) {
+        if let Some(decimal_format) = &self.decimal_format() {
+            match decimal_format {
+                DecimalFormat::String => {
+                    let mut encoded = Vec::new();
+                    for (k, v) in log.all_fields() {
+                        if let Value::Decimal(d) = v {
+                            encoded.push((k.clone(), Value::from(d.to_string())));
+                        }
+                    }
+                    for (k, v) in encoded {
+                        log.insert(k, v);
+                    }
+                }
+                // Decimals are serialized as numbers by default.
+                DecimalFormat::Number => (),
+            }
+        }
+    }
 
     /// Check that the configuration is valid.
     ///
@@ -205,6 +247,8 @@ pub trait EncodingConfiguration {
             self.apply_except_fields(log);
             self.apply_only_fields(log);
             self.apply_timestamp_format(log);
+            self.apply_bytes_format(log);
+            self.apply_decimal_format(log);
         }
     }
 }
@@ -272,6 +316,28 @@ pub enum TimestampFormat {
     Rfc3339,
 }
 
+/// How byte values that aren't valid UTF-8, such as binary payloads, are encoded by codecs that
+/// only support strings.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BytesFormat {
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`.
+    Lossy,
+    /// The whole value is base64 encoded. Values that are valid UTF-8 are left as they are.
+    Base64,
+}
+
+/// How exact decimal values, such as integers too large for a signed 64-bit integer, are encoded.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DecimalFormat {
+    /// Decimals are encoded as numbers. Integral values are exact, fractional values may be
+    /// rounded to the nearest float by codecs such as JSON.
+    Number,
+    /// Decimals are encoded as strings holding their exact value.
+    String,
+}
+
 #[cfg(test)]
 mod tests {
     use indoc::indoc;
@@ -458,4 +524,42 @@ mod tests {
             ),
         }
     }
+
+    const TOML_BYTES_FORMAT: &str = indoc! {r#"
+        encoding.codec = "Snoot"
+        encoding.bytes_format = "base64"
+    "#};
+
+    #[test]
+    fn test_bytes_format() {
+        let config: TestConfig = toml::from_str(TOML_BYTES_FORMAT).unwrap();
+        config.encoding.validate().unwrap();
+        let mut event = Event::from("Demo");
+        let binary = bytes::Bytes::from_static(&[0xde, 0xad, 0xbe, 0xef]);
+        event.as_mut_log().insert("a.b", binary.clone());
+
+        config.encoding.apply_rules(&mut event);
+
+        assert_eq!(event.as_log()["a.b"], Value::from(base64::encode(&binary)));
+        assert_eq!(event.as_log()["message"], Value::from("Demo"));
+    }
+
+    const TOML_DECIMAL_FORMAT: &str = indoc! {r#"
+        encoding.codec = "Snoot"
+        encoding.decimal_format = "string"
+    "#};
+
+    #[test]
+    fn test_decimal_format() {
+        let config: TestConfig = toml::from_str(TOML_DECIMAL_FORMAT).unwrap();
+        config.encoding.validate().unwrap();
+        let mut event = Event::from("Demo");
+        event.as_mut_log().insert("a.b", Value::from(u64::MAX));
+        event.as_mut_log().insert("c", 5);
+
+        config.encoding.apply_rules(&mut event);
+
+        assert_eq!(event.as_log()["a.b"], Value::from("18446744073709551615"));
+        assert_eq!(event.as_log()["c"], Value::from(5));
+    }
 }
@@ -11,7 +11,7 @@ use serde::{
 use crate::{
     event::{PathComponent, PathIter},
     serde::skip_serializing_if_default,
-    sinks::util::encoding::{EncodingConfiguration, TimestampFormat},
+    sinks::util::encoding::{BytesFormat, DecimalFormat, EncodingConfiguration, TimestampFormat},
 };
 
 /// A structure to wrap sink encodings and enforce field privacy.
@@ -36,6 +36,12 @@ pub struct EncodingConfigWithDefault<E: Default + PartialEq> {
     /// Format for outgoing timestamps.
     #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
     pub(crate) timestamp_format: Option<TimestampFormat>,
+    /// How to encode byte values that aren't valid UTF-8.
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) bytes_format: Option<BytesFormat>,
+    /// How to encode decimal values.
+    #[serde(default, skip_serializing_if = "skip_serializing_if_default")]
+    pub(crate) decimal_format: Option<DecimalFormat>,
 }
 
 impl<E: Default + PartialEq> EncodingConfiguration for EncodingConfigWithDefault<E> {
@@ -61,6 +67,14 @@ impl<E: Default + PartialEq> EncodingConfiguration for EncodingConfigWithDefault
     fn timestamp_format(&self) -> &Option<TimestampFormat> {
         &self.timestamp_format
     }
+
+    fn bytes_format(&self) -> &Option<BytesFormat> {
+        &self.bytes_format
+    }
+
+    fn decimal_format(&self) -> &Option<DecimalFormat> {
+        &self.decimal_format
+    }
 }
 
 impl<E> From<E> for EncodingConfigWithDefault<E>
@@ -74,6 +88,8 @@ where
             only_fields: Default::default(),
             except_fields: Default::default(),
             timestamp_format: Default::default(),
+            bytes_format: Default::default(),
+            decimal_format: Default::default(),
         }
     }
 }
@@ -117,6 +133,8 @@ where
                     only_fields: Default::default(),
                     except_fields: Default::default(),
                     timestamp_format: Default::default(),
+                    bytes_format: Default::default(),
+                    decimal_format: Default::default(),
                 })
             }
 
@@ -150,6 +168,8 @@ where
             }),
             except_fields: inner.except_fields,
             timestamp_format: inner.timestamp_format,
+            bytes_format: inner.bytes_format,
+            decimal_format: inner.decimal_format,
         };
 
         concrete.validate().map_err(de::Error::custom)?;
@@ -169,4 +189,8 @@ pub struct InnerWithDefault<E: Default> {
     except_fields: Option<Vec<String>>,
     #[serde(default)]
     timestamp_format: Option<TimestampFormat>,
+    #[serde(default)]
+    bytes_format: Option<BytesFormat>,
+    #[serde(default)]
+    decimal_format: Option<DecimalFormat>,
 }
@@ -470,6 +470,10 @@ impl RetryLogic for HttpRetryLogic {
             _ => RetryAction::DontRetry(format!("response status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+        Some(response.status())
+    }
 }
 
 /// A more generic version of `HttpRetryLogic` that accepts anything that can be converted
@@ -520,6 +524,10 @@ where
             _ => RetryAction::DontRetry(format!("Http status: {}", status).into()),
         }
     }
+
+    fn response_status(&self, response: &T) -> Option<StatusCode> {
+        Some((self.func)(response))
+    }
 }
 
 impl<F, T> Clone for HttpStatusRetryLogic<F, T>
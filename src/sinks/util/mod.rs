@@ -6,6 +6,13 @@ pub mod compressor;
 pub mod encoding;
 pub mod http;
 pub mod normalizer;
+#[cfg(any(
+    feature = "sinks-aws_s3",
+    feature = "sinks-azure_blob",
+    feature = "sinks-datadog_archives",
+    feature = "sinks-gcp"
+))]
+pub mod object_storage;
 pub mod partitioner;
 pub mod processed_event;
 pub mod request_builder;
@@ -1,6 +1,7 @@
 use std::{
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use futures_util::{ready, stream::Fuse, Stream, StreamExt};
@@ -29,6 +30,12 @@ where
             normalizer: MetricNormalizer::from(normalizer),
         }
     }
+
+    /// Forgets series that haven't been seen for `expire_after`, if it's set.
+    pub fn with_expiration(mut self, expire_after: Option<Duration>) -> Self {
+        self.normalizer = self.normalizer.with_expiration(expire_after);
+        self
+    }
 }
 
 impl<St, N> Stream for Normalizer<St, N>
@@ -0,0 +1,485 @@
+//! Naming and manifests of the objects written by the object storage sinks.
+//!
+//! Every batch is written as its own object, named after the time it's written at. Since several
+//! batches can be written within the resolution of the time format, a UUID is appended to the
+//! name by default. Without one, the time in milliseconds is appended instead, bumped so that it
+//! never repeats within a sink. As long as the clock doesn't go backwards, names then stay unique
+//! across restarts of a single Vector process writing to the prefix.
+//!
+//! Sinks can also keep a manifest of the objects written to each partition, the part of the
+//! object keys up to their last `/`, so that downstream loaders can find every object of a
+//! partition without listing them. The manifest is rewritten after every object, and so only ever
+//! lists objects that were fully written. The first time a partition is written to, the manifest
+//! already stored there is read and the new objects are added to it, so that objects written
+//! before a restart, or before the partition went idle, stay listed.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::internal_events::{ObjectManifestReadError, ObjectManifestWriteError};
+
+/// Names the objects written by a sink.
+#[derive(Clone, Debug)]
+pub struct ObjectNaming {
+    time_format: String,
+    append_uuid: bool,
+    unique: bool,
+    /// The last milliseconds appended to a name.
+    last_millis: Arc<Mutex<i64>>,
+}
+
+impl ObjectNaming {
+    pub fn new(time_format: String, append_uuid: bool) -> Self {
+        Self {
+            time_format,
+            append_uuid,
+            unique: true,
+            last_millis: Default::default(),
+        }
+    }
+
+    /// Names objects after the time alone, for objects that are appended to rather than
+    /// replaced, so that every batch written within the resolution of the time format goes to
+    /// the same object.
+    pub fn shared(time_format: String) -> Self {
+        Self {
+            unique: false,
+            ..Self::new(time_format, false)
+        }
+    }
+
+    /// Returns the name of the next object, without its prefix and extension.
+    pub fn name(&self) -> String {
+        self.name_at(Utc::now())
+    }
+
+    fn name_at(&self, now: DateTime<Utc>) -> String {
+        let formatted_time = now.format(&self.time_format).to_string();
+        if self.append_uuid {
+            return format!("{}-{}", formatted_time, Uuid::new_v4().to_hyphenated());
+        }
+        if !self.unique {
+            return formatted_time;
+        }
+
+        // Unlike a counter, the time keeps moving on across restarts, so names written by an
+        // earlier process aren't given out again.
+        let mut last_millis = self.last_millis.lock().expect("poisoned lock");
+        let millis = now.timestamp_millis().max(*last_millis + 1);
+        *last_millis = millis;
+        format!("{}-{}", formatted_time, millis)
+    }
+}
+
+const fn default_idle_timeout_secs() -> u64 {
+    3600
+}
+
+fn default_manifest_filename() -> String {
+    "manifest.json".to_owned()
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ManifestConfig {
+    /// The name of the manifest object of each partition.
+    #[serde(default = "default_manifest_filename")]
+    pub filename: String,
+    /// How long a partition may go without new objects before its manifest is no longer kept
+    /// in memory.
+    #[serde(default = "default_idle_timeout_secs")]
+    pub idle_timeout_secs: u64,
+}
+
+impl Default for ManifestConfig {
+    fn default() -> Self {
+        Self {
+            filename: default_manifest_filename(),
+            idle_timeout_secs: default_idle_timeout_secs(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ManifestObject {
+    pub key: String,
+    pub events: usize,
+    pub bytes: usize,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct Manifest {
+    pub partition: String,
+    pub updated_at: DateTime<Utc>,
+    pub total_events: usize,
+    pub total_bytes: usize,
+    pub objects: Vec<ManifestObject>,
+}
+
+#[derive(Debug)]
+struct Partition {
+    objects: Vec<ManifestObject>,
+    /// Whether the manifest stored in the partition has been read into `objects`.
+    loaded: bool,
+    last_written: Instant,
+    /// Held while the manifest is written, so that an older manifest never overwrites a newer one.
+    write_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+/// The manifests of the partitions a sink writes to.
+#[derive(Debug)]
+pub struct Manifests {
+    filename: String,
+    idle_timeout: Duration,
+    partitions: Mutex<HashMap<String, Partition>>,
+}
+
+/// Returns the partition of `key`, the part of it up to its last `/`.
+fn partition_of(key: &str) -> &str {
+    key.rfind('/').map_or("", |index| &key[..=index])
+}
+
+impl Manifests {
+    pub fn new(config: &ManifestConfig) -> Self {
+        Self {
+            filename: config.filename.clone(),
+            idle_timeout: Duration::from_secs(config.idle_timeout_secs),
+            partitions: Default::default(),
+        }
+    }
+
+    fn record(
+        &self,
+        object: ManifestObject,
+        now: Instant,
+    ) -> (String, Arc<tokio::sync::Mutex<()>>) {
+        let partition_key = partition_of(&object.key).to_owned();
+        let mut partitions = self.partitions.lock().expect("poisoned lock");
+
+        let idle_timeout = self.idle_timeout;
+        partitions.retain(|key, partition| {
+            *key == partition_key || now.duration_since(partition.last_written) < idle_timeout
+        });
+
+        let partition = partitions
+            .entry(partition_key.clone())
+            .or_insert_with(|| Partition {
+                objects: Vec::new(),
+                loaded: false,
+                last_written: now,
+                write_lock: Default::default(),
+            });
+        partition.objects.push(object);
+        partition.last_written = now;
+
+        (partition_key, Arc::clone(&partition.write_lock))
+    }
+
+    fn is_loaded(&self, partition_key: &str) -> bool {
+        let partitions = self.partitions.lock().expect("poisoned lock");
+        partitions
+            .get(partition_key)
+            .map_or(true, |partition| partition.loaded)
+    }
+
+    /// Adds the objects of the manifest stored in the partition, if there is one, ahead of the
+    /// objects written since.
+    fn load(&self, partition_key: &str, stored: Option<&[u8]>) -> Result<(), serde_json::Error> {
+        let stored = stored.map(serde_json::from_slice::<Manifest>).transpose()?;
+
+        let mut partitions = self.partitions.lock().expect("poisoned lock");
+        if let Some(partition) = partitions.get_mut(partition_key) {
+            if let Some(stored) = stored {
+                let mut objects = stored
+                    .objects
+                    .into_iter()
+                    .filter(|object| !partition.objects.iter().any(|new| new.key == object.key))
+                    .collect::<Vec<_>>();
+                objects.append(&mut partition.objects);
+                partition.objects = objects;
+            }
+            partition.loaded = true;
+        }
+        Ok(())
+    }
+
+    fn render(&self, partition_key: &str) -> Option<Bytes> {
+        let partitions = self.partitions.lock().expect("poisoned lock");
+        let partition = partitions.get(partition_key)?;
+        let manifest = Manifest {
+            partition: partition_key.to_owned(),
+            updated_at: Utc::now(),
+            total_events: partition.objects.iter().map(|object| object.events).sum(),
+            total_bytes: partition.objects.iter().map(|object| object.bytes).sum(),
+            objects: partition.objects.clone(),
+        };
+        serde_json::to_vec(&manifest).ok().map(Bytes::from)
+    }
+
+    /// Adds a newly written object to the manifest of its partition, and writes the manifest
+    /// using `put`, which is given its key and body. If the partition isn't known yet, the
+    /// manifest stored there is first read with `get`, which is given its key and returns `None`
+    /// if there is no manifest yet.
+    ///
+    /// Failing to write the manifest doesn't fail the object itself, since its events were
+    /// delivered, and the next object of the partition writes the manifest again. Neither is the
+    /// manifest written if reading it fails, since that would drop the objects it lists.
+    pub async fn write<G, GetFut, GetError, F, Fut, T, E>(
+        &self,
+        key: &str,
+        events: usize,
+        bytes: usize,
+        get: G,
+        put: F,
+    ) where
+        G: FnOnce(String) -> GetFut,
+        GetFut: Future<Output = Result<Option<Bytes>, GetError>>,
+        GetError: std::fmt::Display,
+        F: FnOnce(String, Bytes) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: std::fmt::Display,
+    {
+        let object = ManifestObject {
+            key: key.to_owned(),
+            events,
+            bytes,
+        };
+        let (partition_key, write_lock) = self.record(object, Instant::now());
+
+        let _guard = write_lock.lock().await;
+        let manifest_key = format!("{}{}", partition_key, self.filename);
+        if !self.is_loaded(&partition_key) {
+            let loaded = match get(manifest_key.clone()).await {
+                Ok(stored) => self
+                    .load(&partition_key, stored.as_deref())
+                    .map_err(|error| format!("invalid manifest: {}", error)),
+                Err(error) => Err(error.to_string()),
+            };
+            if let Err(error) = loaded {
+                emit!(&ObjectManifestReadError {
+                    key: &manifest_key,
+                    error,
+                });
+                return;
+            }
+        }
+
+        // Render the manifest once the lock is held, so it includes every object recorded so far.
+        let body = match self.render(&partition_key) {
+            Some(body) => body,
+            None => return,
+        };
+        if let Err(error) = put(manifest_key.clone(), body).await {
+            emit!(&ObjectManifestWriteError {
+                key: &manifest_key,
+                error: error.to_string(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn names_without_uuid_are_unique() {
+        let naming = ObjectNaming::new("%F".to_owned(), false);
+        let day = Utc.ymd(2022, 3, 1).and_hms(12, 0, 0);
+
+        assert_eq!(naming.name_at(day), "2022-03-01-1646136000000");
+        assert_eq!(naming.name_at(day), "2022-03-01-1646136000001");
+
+        let later = day + chrono::Duration::seconds(1);
+        assert_eq!(naming.name_at(later), "2022-03-01-1646136001000");
+    }
+
+    #[test]
+    fn names_without_uuid_survive_restarts() {
+        let day = Utc.ymd(2022, 3, 1).and_hms(12, 0, 0);
+        let before = ObjectNaming::new("%F".to_owned(), false);
+        let first = before.name_at(day);
+        before.name_at(day);
+
+        let after = ObjectNaming::new("%F".to_owned(), false);
+        assert_ne!(
+            after.name_at(day + chrono::Duration::milliseconds(1)),
+            first
+        );
+    }
+
+    #[test]
+    fn shared_names() {
+        let naming = ObjectNaming::shared("%FT%H".to_owned());
+        let hour = Utc.ymd(2022, 3, 1).and_hms(12, 0, 0);
+
+        assert_eq!(naming.name_at(hour), "2022-03-01T12");
+        assert_eq!(naming.name_at(hour), "2022-03-01T12");
+    }
+
+    #[test]
+    fn names_with_uuid() {
+        let naming = ObjectNaming::new("%F".to_owned(), true);
+        let day = Utc.ymd(2022, 3, 1).and_hms(12, 0, 0);
+
+        let first = naming.name_at(day);
+        assert!(first.starts_with("2022-03-01-"));
+        assert_ne!(first, naming.name_at(day));
+    }
+
+    #[test]
+    fn partitions() {
+        assert_eq!(
+            partition_of("date=2022-03-01/1646136000.log"),
+            "date=2022-03-01/"
+        );
+        assert_eq!(partition_of("a/b/c.log"), "a/b/");
+        assert_eq!(partition_of("c.log"), "");
+    }
+
+    fn manifest(body: &Bytes) -> Manifest {
+        serde_json::from_slice(body).unwrap()
+    }
+
+    fn not_stored(_key: String) -> impl Future<Output = Result<Option<Bytes>, std::io::Error>> {
+        async { Ok(None) }
+    }
+
+    #[tokio::test]
+    async fn writes_manifests_per_partition() {
+        let manifests = Manifests::new(&ManifestConfig::default());
+        let written = Mutex::new(Vec::new());
+        let put = |key: String, body: Bytes| {
+            written.lock().unwrap().push((key, body));
+            async { Ok::<_, std::io::Error>(()) }
+        };
+
+        manifests.write("a/1.log", 2, 10, not_stored, put).await;
+        manifests.write("b/1.log", 3, 20, not_stored, put).await;
+        manifests.write("a/2.log", 4, 30, not_stored, put).await;
+
+        let written = written.into_inner().unwrap();
+        assert_eq!(written.len(), 3);
+        assert_eq!(written[1].0, "b/manifest.json");
+        assert_eq!(manifest(&written[1].1).objects.len(), 1);
+
+        let (key, body) = &written[2];
+        let manifest = manifest(body);
+        assert_eq!(key, "a/manifest.json");
+        assert_eq!(manifest.partition, "a/");
+        assert_eq!(manifest.total_events, 6);
+        assert_eq!(manifest.total_bytes, 40);
+        assert_eq!(
+            manifest.objects,
+            vec![
+                ManifestObject {
+                    key: "a/1.log".to_owned(),
+                    events: 2,
+                    bytes: 10
+                },
+                ManifestObject {
+                    key: "a/2.log".to_owned(),
+                    events: 4,
+                    bytes: 30
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn forgets_idle_partitions() {
+        let manifests = Manifests::new(&ManifestConfig {
+            idle_timeout_secs: 60,
+            ..Default::default()
+        });
+        let object = |key: &str| ManifestObject {
+            key: key.to_owned(),
+            events: 1,
+            bytes: 1,
+        };
+        let start = Instant::now();
+
+        manifests.record(object("a/1.log"), start);
+        manifests.record(object("b/1.log"), start + Duration::from_secs(30));
+        manifests.record(object("b/2.log"), start + Duration::from_secs(90));
+
+        assert!(manifests.render("a/").is_none());
+        assert!(manifests.render("b/").is_some());
+    }
+
+    #[tokio::test]
+    async fn merges_stored_manifests() {
+        let stored = serde_json::to_vec(&Manifest {
+            partition: "a/".to_owned(),
+            updated_at: Utc::now(),
+            total_events: 5,
+            total_bytes: 50,
+            objects: vec![ManifestObject {
+                key: "a/0.log".to_owned(),
+                events: 5,
+                bytes: 50,
+            }],
+        })
+        .unwrap();
+        let reads = Mutex::new(Vec::new());
+        let get = |key: String| {
+            reads.lock().unwrap().push(key);
+            let stored = Bytes::from(stored.clone());
+            async { Ok::<_, std::io::Error>(Some(stored)) }
+        };
+        let written = Mutex::new(Vec::new());
+        let put = |key: String, body: Bytes| {
+            written.lock().unwrap().push((key, body));
+            async { Ok::<_, std::io::Error>(()) }
+        };
+
+        let manifests = Manifests::new(&ManifestConfig::default());
+        manifests.write("a/1.log", 2, 10, get, put).await;
+        manifests.write("a/2.log", 4, 30, get, put).await;
+
+        // The stored manifest is only read the first time the partition is written to.
+        assert_eq!(reads.into_inner().unwrap(), vec!["a/manifest.json"]);
+
+        let written = written.into_inner().unwrap();
+        let manifest = manifest(&written[1].1);
+        assert_eq!(manifest.total_events, 11);
+        assert_eq!(
+            manifest
+                .objects
+                .iter()
+                .map(|object| object.key.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a/0.log", "a/1.log", "a/2.log"]
+        );
+    }
+
+    #[tokio::test]
+    async fn doesnt_write_manifests_that_cant_be_read() {
+        let get = |_key: String| async { Err::<Option<Bytes>, _>("access denied") };
+        let written = Mutex::new(Vec::new());
+        let put = |key: String, body: Bytes| {
+            written.lock().unwrap().push((key, body));
+            async { Ok::<_, std::io::Error>(()) }
+        };
+
+        let manifests = Manifests::new(&ManifestConfig::default());
+        manifests.write("a/1.log", 2, 10, get, put).await;
+        assert!(written.lock().unwrap().is_empty());
+
+        // The next object tries reading it again.
+        manifests.write("a/2.log", 4, 30, not_stored, put).await;
+        let written = written.into_inner().unwrap();
+        assert_eq!(manifest(&written[0].1).objects.len(), 2);
+    }
+}
@@ -1,17 +1,24 @@
 use std::{
     borrow::Cow,
-    cmp,
+    cmp, fmt,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
 
 use futures::FutureExt;
+use http::StatusCode;
+use rand::Rng;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use tokio::time::{sleep, Sleep};
-use tower::{retry::Policy, timeout::error::Elapsed};
+use tower::{
+    retry::{budget::Budget, Policy},
+    timeout::error::Elapsed,
+};
 
-use crate::Error;
+use crate::{sinks::util::service::CircuitBreakerOpen, Error};
 
 pub enum RetryAction {
     /// Indicate that this request should be retried with a reason
@@ -32,6 +39,93 @@ pub trait RetryLogic: Clone + Send + Sync + 'static {
         // Treat the default as the request is successful
         RetryAction::Successful
     }
+
+    /// The HTTP status of the response, if it has one, which lets `retry_on_status` and
+    /// `no_retry_on_status` override `should_retry_response`.
+    fn response_status(&self, _response: &Self::Response) -> Option<StatusCode> {
+        None
+    }
+}
+
+/// How much of the back-off is randomized.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JitterMode {
+    /// Wait for the whole back-off.
+    None,
+    /// Wait for a random time between zero and the back-off, which keeps clients that failed
+    /// together from retrying together.
+    Full,
+}
+
+impl Default for JitterMode {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// A set of HTTP status codes. It's kept as a bitmap so that request settings stay `Copy`.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatusCodeSet([u64; 8]);
+
+impl StatusCodeSet {
+    const MIN: u16 = 100;
+    const MAX: u16 = 599;
+
+    pub const fn empty() -> Self {
+        Self([0; 8])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|bits| *bits == 0)
+    }
+
+    pub fn contains(&self, status: StatusCode) -> bool {
+        let code = status.as_u16();
+        (Self::MIN..=Self::MAX).contains(&code) && {
+            let index = usize::from(code - Self::MIN);
+            self.0[index / 64] & (1 << (index % 64)) != 0
+        }
+    }
+
+    fn insert(&mut self, code: u16) {
+        let index = usize::from(code - Self::MIN);
+        self.0[index / 64] |= 1 << (index % 64);
+    }
+
+    fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        (Self::MIN..=Self::MAX).filter(move |code| {
+            StatusCode::from_u16(*code).map_or(false, |status| self.contains(status))
+        })
+    }
+}
+
+impl fmt::Debug for StatusCodeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl Serialize for StatusCodeSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for StatusCodeSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut set = Self::empty();
+        for code in Vec::<u16>::deserialize(deserializer)? {
+            if !(Self::MIN..=Self::MAX).contains(&code) {
+                return Err(de::Error::custom(format!(
+                    "{} is not an HTTP status code",
+                    code
+                )));
+            }
+            set.insert(code);
+        }
+        Ok(set)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +134,11 @@ pub struct FixedRetryPolicy<L> {
     previous_duration: Duration,
     current_duration: Duration,
     max_duration: Duration,
+    jitter: JitterMode,
+    retry_on_status: StatusCodeSet,
+    no_retry_on_status: StatusCodeSet,
+    budget: Option<Arc<Budget>>,
+    is_retry: bool,
     logic: L,
 }
 
@@ -60,10 +159,48 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
             previous_duration: Duration::from_secs(0),
             current_duration: initial_backoff,
             max_duration,
+            jitter: JitterMode::None,
+            retry_on_status: StatusCodeSet::empty(),
+            no_retry_on_status: StatusCodeSet::empty(),
+            budget: None,
+            is_retry: false,
             logic,
         }
     }
 
+    // https://github.com/rust-lang/rust/issues/73255
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn with_jitter(mut self, jitter: JitterMode) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Overrides the retry logic for responses with the given statuses, with `no_retry_on_status`
+    /// taking precedence.
+    // https://github.com/rust-lang/rust/issues/73255
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn with_status_overrides(
+        mut self,
+        retry_on_status: StatusCodeSet,
+        no_retry_on_status: StatusCodeSet,
+    ) -> Self {
+        self.retry_on_status = retry_on_status;
+        self.no_retry_on_status = no_retry_on_status;
+        self
+    }
+
+    /// Limits retries to `ratio` of the requests made, on top of a minimum of ten a second, over a
+    /// window of ten seconds shared by every request made with clones of this policy.
+    pub fn with_budget(mut self, ratio: f64) -> Self {
+        self.budget = Some(Arc::new(Budget::new(
+            Duration::from_secs(10),
+            10,
+            // `Budget` only allows ratios up to 1000.
+            ratio.max(0.0).min(1000.0) as f32,
+        )));
+        self
+    }
+
     fn advance(&self) -> FixedRetryPolicy<L> {
         let next_duration: Duration = self.previous_duration + self.current_duration;
 
@@ -72,6 +209,11 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
             previous_duration: self.current_duration,
             current_duration: cmp::min(next_duration, self.max_duration),
             max_duration: self.max_duration,
+            jitter: self.jitter,
+            retry_on_status: self.retry_on_status,
+            no_retry_on_status: self.no_retry_on_status,
+            budget: self.budget.clone(),
+            is_retry: true,
             logic: self.logic.clone(),
         }
     }
@@ -80,12 +222,54 @@ impl<L: RetryLogic> FixedRetryPolicy<L> {
         self.current_duration
     }
 
-    fn build_retry(&self) -> RetryPolicyFuture<L> {
+    fn jittered_backoff(&self) -> Duration {
+        match self.jitter {
+            JitterMode::None => self.backoff(),
+            JitterMode::Full => {
+                rand::thread_rng().gen_range(Duration::from_secs(0)..=self.backoff())
+            }
+        }
+    }
+
+    fn build_retry(&self) -> Option<RetryPolicyFuture<L>> {
+        if let Some(budget) = &self.budget {
+            if budget.withdraw().is_err() {
+                error!(message = "Retry budget exhausted; dropping the request.");
+                return None;
+            }
+        }
+
         let policy = self.advance();
-        let delay = Box::pin(sleep(self.backoff()));
+        let backoff = self.jittered_backoff();
+        let delay = Box::pin(sleep(backoff));
+
+        debug!(message = "Retrying request.", delay_ms = %backoff.as_millis());
+        Some(RetryPolicyFuture { delay, policy })
+    }
+
+    /// Requests held back by an open circuit breaker were never sent, so waiting for it doesn't
+    /// use up an attempt or the retry budget.
+    fn wait_for_circuit_breaker(&self, delay: Duration) -> RetryPolicyFuture<L> {
+        let policy = FixedRetryPolicy {
+            is_retry: true,
+            ..self.clone()
+        };
+        RetryPolicyFuture {
+            delay: Box::pin(sleep(delay)),
+            policy,
+        }
+    }
 
-        debug!(message = "Retrying request.", delay_ms = %self.backoff().as_millis());
-        RetryPolicyFuture { delay, policy }
+    fn should_retry_response(&self, response: &L::Response) -> RetryAction {
+        match self.logic.response_status(response) {
+            Some(status) if self.no_retry_on_status.contains(status) => {
+                RetryAction::DontRetry(format!("response status: {}", status).into())
+            }
+            Some(status) if self.retry_on_status.contains(status) => {
+                RetryAction::Retry(format!("response status: {}", status).into())
+            }
+            _ => self.logic.should_retry_response(response),
+        }
     }
 }
 
@@ -97,8 +281,14 @@ where
     type Future = RetryPolicyFuture<L>;
 
     fn retry(&self, _: &Req, result: Result<&Res, &Error>) -> Option<Self::Future> {
+        if !self.is_retry {
+            if let Some(budget) = &self.budget {
+                budget.deposit();
+            }
+        }
+
         match result {
-            Ok(response) => match self.logic.should_retry_response(response) {
+            Ok(response) => match self.should_retry_response(response) {
                 RetryAction::Retry(reason) => {
                     if self.remaining_attempts == 0 {
                         error!(
@@ -109,7 +299,7 @@ where
                     }
 
                     warn!(message = "Retrying after response.", reason = %reason);
-                    Some(self.build_retry())
+                    self.build_retry()
                 }
 
                 RetryAction::DontRetry(reason) => {
@@ -120,6 +310,10 @@ where
                 RetryAction::Successful => None,
             },
             Err(error) => {
+                if let Some(open) = error.downcast_ref::<CircuitBreakerOpen>() {
+                    return Some(self.wait_for_circuit_breaker(open.retry_after));
+                }
+
                 if self.remaining_attempts == 0 {
                     error!(message = "Retries exhausted; dropping the request.", %error);
                     return None;
@@ -128,7 +322,7 @@ where
                 if let Some(expected) = error.downcast_ref::<L::Error>() {
                     if self.logic.is_retriable_error(expected) {
                         warn!(message = "Retrying after error.", error = %expected);
-                        Some(self.build_retry())
+                        self.build_retry()
                     } else {
                         error!(
                             message = "Non-retriable error; dropping the request.",
@@ -138,7 +332,7 @@ where
                     }
                 } else if error.downcast_ref::<Elapsed>().is_some() {
                     warn!("Request timed out. If this happens often while the events are actually reaching their destination, try decreasing `batch.max_bytes` and/or using `compression` if applicable. Alternatively `request.timeout_secs` can be increased.");
-                    Some(self.build_retry())
+                    self.build_retry()
                 } else {
                     error!(
                         message = "Unexpected error type; dropping the request.",
@@ -348,6 +542,36 @@ mod tests {
         assert_eq!(fut.await.unwrap(), "world");
     }
 
+    #[tokio::test]
+    async fn open_circuit_breaker_does_not_use_attempts() {
+        trace_init();
+
+        time::pause();
+
+        let policy = FixedRetryPolicy::new(
+            0,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        );
+
+        let (mut svc, mut handle) = mock::spawn_layer(RetryLayer::new(policy));
+
+        assert_ready_ok!(svc.poll_ready());
+
+        let mut fut = task::spawn(svc.call("hello"));
+        assert_request_eq!(handle, "hello").send_error(CircuitBreakerOpen {
+            retry_after: Duration::from_secs(5),
+        });
+        assert_pending!(fut.poll());
+
+        time::advance(Duration::from_secs(6)).await;
+        assert_pending!(fut.poll());
+
+        assert_request_eq!(handle, "hello").send_response("world");
+        assert_eq!(fut.await.unwrap(), "world");
+    }
+
     #[test]
     fn backoff_grows_to_max() {
         let mut policy = FixedRetryPolicy::new(
@@ -380,6 +604,97 @@ mod tests {
         assert_eq!(Duration::from_secs(10), policy.backoff());
     }
 
+    #[tokio::test]
+    async fn status_overrides() {
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            StatusRetryLogic,
+        )
+        .with_status_overrides(
+            serde_json::from_str("[400]").unwrap(),
+            serde_json::from_str("[503]").unwrap(),
+        );
+        let retries = |status: StatusCode| {
+            Policy::<(), _, crate::Error>::retry(&policy, &(), Ok(&status)).is_some()
+        };
+
+        assert!(retries(StatusCode::BAD_REQUEST));
+        assert!(!retries(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(retries(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!retries(StatusCode::NOT_FOUND));
+    }
+
+    #[tokio::test]
+    async fn retry_budget_limits_retries() {
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(1),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        )
+        .with_budget(0.0);
+        let error: crate::Error = Box::new(Error(true));
+
+        let retried = (0..1000)
+            .filter(|_| Policy::<(), _, crate::Error>::retry(&policy, &(), Err(&error)).is_some())
+            .count();
+        assert!(retried > 0);
+        assert!(retried < 1000);
+    }
+
+    #[test]
+    fn full_jitter_stays_within_backoff() {
+        let policy = FixedRetryPolicy::new(
+            5,
+            Duration::from_secs(4),
+            Duration::from_secs(10),
+            SvcRetryLogic,
+        )
+        .with_jitter(JitterMode::Full);
+        for _ in 0..100 {
+            assert!(policy.jittered_backoff() <= Duration::from_secs(4));
+        }
+    }
+
+    #[test]
+    fn status_code_sets() {
+        let set: StatusCodeSet = serde_json::from_str("[599, 100, 429]").unwrap();
+        assert!(set.contains(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!set.contains(StatusCode::INTERNAL_SERVER_ERROR));
+        assert_eq!(serde_json::to_string(&set).unwrap(), "[100,429,599]");
+
+        assert!(serde_json::from_str::<StatusCodeSet>("[600]").is_err());
+        assert!(serde_json::from_str::<StatusCodeSet>("[99]").is_err());
+    }
+
+    #[derive(Debug, Clone)]
+    struct StatusRetryLogic;
+
+    impl RetryLogic for StatusRetryLogic {
+        type Error = Error;
+        type Response = StatusCode;
+
+        fn is_retriable_error(&self, error: &Self::Error) -> bool {
+            error.0
+        }
+
+        fn should_retry_response(&self, response: &Self::Response) -> RetryAction {
+            if response.is_success() {
+                RetryAction::Successful
+            } else if response.is_server_error() {
+                RetryAction::Retry("server error".into())
+            } else {
+                RetryAction::DontRetry("client error".into())
+            }
+        }
+
+        fn response_status(&self, response: &Self::Response) -> Option<StatusCode> {
+            Some(*response)
+        }
+    }
+
     #[derive(Debug, Clone)]
     struct SvcRetryLogic;
 
@@ -12,6 +12,10 @@ use tower::{
 use vector_core::buffers::Acker;
 
 pub use crate::sinks::util::service::{
+    circuit_breaker::{
+        CircuitBreaker, CircuitBreakerConfig, CircuitBreakerLayer, CircuitBreakerOpen,
+        CircuitBreakerState,
+    },
     concurrency::{concurrency_is_none, Concurrency},
     map::Map,
 };
@@ -19,16 +23,19 @@ use crate::sinks::util::{
     adaptive_concurrency::{
         AdaptiveConcurrencyLimit, AdaptiveConcurrencyLimitLayer, AdaptiveConcurrencySettings,
     },
-    retries::{FixedRetryPolicy, RetryLogic},
+    retries::{FixedRetryPolicy, JitterMode, RetryLogic, StatusCodeSet},
     service::map::MapLayer,
     sink::{Response, ServiceLogic},
     Batch, BatchSink, Partition, PartitionBatchSink,
 };
 
+mod circuit_breaker;
 mod concurrency;
 mod map;
 
-pub type Svc<S, L> = RateLimit<AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, Timeout<S>>, L>>;
+pub type Svc<S, L> = RateLimit<
+    AdaptiveConcurrencyLimit<Retry<FixedRetryPolicy<L>, CircuitBreaker<Timeout<S>, L>>, L>,
+>;
 pub type TowerBatchedSink<S, B, RL, SL> = BatchSink<Svc<S, RL>, B, SL>;
 pub type TowerPartitionSink<S, B, RL, K, SL> = PartitionBatchSink<Svc<S, RL>, B, K, SL>;
 
@@ -77,6 +84,14 @@ pub struct TowerRequestConfig {
     pub retry_attempts: Option<usize>,         // isize::MAX
     pub retry_max_duration_secs: Option<u64>,
     pub retry_initial_backoff_secs: Option<u64>, // 1
+    pub retry_jitter: Option<JitterMode>,        // none
+    /// The most retries to make, as a share of the requests made.
+    pub retry_budget_ratio: Option<f64>,
+    #[serde(default, skip_serializing_if = "StatusCodeSet::is_empty")]
+    pub retry_on_status: StatusCodeSet,
+    #[serde(default, skip_serializing_if = "StatusCodeSet::is_empty")]
+    pub no_retry_on_status: StatusCodeSet,
+    pub circuit_breaker: Option<CircuitBreakerConfig>, // disabled
     #[serde(default)]
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
 }
@@ -105,6 +120,11 @@ impl TowerRequestConfig {
             retry_attempts: Some(RETRY_ATTEMPTS_DEFAULT),
             retry_max_duration_secs: Some(RETRY_MAX_DURATION_SECONDS_DEFAULT),
             retry_initial_backoff_secs: Some(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
+            retry_jitter: Some(JitterMode::None),
+            retry_budget_ratio: None,
+            retry_on_status: StatusCodeSet::empty(),
+            no_retry_on_status: StatusCodeSet::empty(),
+            circuit_breaker: None,
             adaptive_concurrency: AdaptiveConcurrencySettings::const_default(),
         }
     }
@@ -174,9 +194,35 @@ impl TowerRequestConfig {
                     .or(defaults.retry_initial_backoff_secs)
                     .unwrap_or(RETRY_INITIAL_BACKOFF_SECONDS_DEFAULT),
             ),
+            retry_jitter: self
+                .retry_jitter
+                .or(defaults.retry_jitter)
+                .unwrap_or_default(),
+            retry_budget_ratio: self.retry_budget_ratio.or(defaults.retry_budget_ratio),
+            retry_on_status: if self.retry_on_status.is_empty() {
+                defaults.retry_on_status
+            } else {
+                self.retry_on_status
+            },
+            no_retry_on_status: if self.no_retry_on_status.is_empty() {
+                defaults.no_retry_on_status
+            } else {
+                self.no_retry_on_status
+            },
+            circuit_breaker: self.circuit_breaker.or(defaults.circuit_breaker),
             adaptive_concurrency: self.adaptive_concurrency,
         }
     }
+
+    /// Fails if `retry_on_status` or `no_retry_on_status` are set, for sinks whose responses
+    /// don't carry an HTTP status for them to apply to.
+    pub fn reject_status_overrides(&self) -> crate::Result<()> {
+        if self.retry_on_status.is_empty() && self.no_retry_on_status.is_empty() {
+            Ok(())
+        } else {
+            Err("`request.retry_on_status` and `request.no_retry_on_status` aren't supported by this sink".into())
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -188,17 +234,28 @@ pub struct TowerRequestSettings {
     pub retry_attempts: usize,
     pub retry_max_duration_secs: Duration,
     pub retry_initial_backoff_secs: Duration,
+    pub retry_jitter: JitterMode,
+    pub retry_budget_ratio: Option<f64>,
+    pub retry_on_status: StatusCodeSet,
+    pub no_retry_on_status: StatusCodeSet,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
     pub adaptive_concurrency: AdaptiveConcurrencySettings,
 }
 
 impl TowerRequestSettings {
     pub fn retry_policy<L: RetryLogic>(&self, logic: L) -> FixedRetryPolicy<L> {
-        FixedRetryPolicy::new(
+        let policy = FixedRetryPolicy::new(
             self.retry_attempts,
             self.retry_initial_backoff_secs,
             self.retry_max_duration_secs,
             logic,
         )
+        .with_jitter(self.retry_jitter)
+        .with_status_overrides(self.retry_on_status, self.no_retry_on_status);
+        match self.retry_budget_ratio {
+            Some(ratio) => policy.with_budget(ratio),
+            None => policy,
+        }
     }
 
     pub fn partition_sink<B, RL, S, K, SL>(
@@ -274,9 +331,10 @@ impl TowerRequestSettings {
             .layer(AdaptiveConcurrencyLimitLayer::new(
                 self.concurrency,
                 self.adaptive_concurrency,
-                retry_logic,
+                retry_logic.clone(),
             ))
             .retry(policy)
+            .layer(CircuitBreakerLayer::new(self.circuit_breaker, retry_logic))
             .timeout(self.timeout)
             .service(service)
     }
@@ -310,6 +368,10 @@ where
                 self.settings.rate_limit_duration,
             )
             .retry(policy)
+            .layer(CircuitBreakerLayer::new(
+                self.settings.circuit_breaker,
+                self.retry_logic.clone(),
+            ))
             .timeout(self.settings.timeout)
             .service(inner);
 
@@ -370,6 +432,18 @@ mod tests {
         assert_eq!(cfg.concurrency, None);
     }
 
+    #[test]
+    fn rejects_status_overrides() {
+        assert!(TowerRequestConfig::default()
+            .reject_status_overrides()
+            .is_ok());
+
+        for toml in ["retry_on_status = [409]", "no_retry_on_status = [503]"] {
+            let cfg = toml::from_str::<TowerRequestConfig>(toml).unwrap();
+            assert!(cfg.reject_status_overrides().is_err());
+        }
+    }
+
     #[tokio::test]
     async fn partition_sink_retry_concurrency() {
         let cfg = TowerRequestConfig {
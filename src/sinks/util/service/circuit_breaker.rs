@@ -0,0 +1,444 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures::{future::BoxFuture, FutureExt};
+use serde::{de, Deserialize, Deserializer, Serialize};
+use snafu::Snafu;
+use tower::{Layer, Service};
+
+use crate::{
+    internal_events::{CircuitBreakerRequestRejected, CircuitBreakerStateChanged},
+    sinks::util::retries::RetryLogic,
+};
+
+/// How long requests wait for the result of a half-open probe before trying again.
+const PROBE_WAIT: Duration = Duration::from_secs(1);
+
+/// Stops sending requests to an endpoint that keeps failing, so that retries don't pile up
+/// against it, and lets a single request through once in a while to check whether it recovered.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CircuitBreakerConfig {
+    /// Opens the breaker after this many failed requests in a row.
+    #[serde(default = "default_consecutive_failures")]
+    pub consecutive_failures: u32,
+    /// Opens the breaker once this share of the last `error_rate_window` requests failed.
+    #[serde(default, deserialize_with = "deserialize_error_rate")]
+    pub error_rate: Option<f64>,
+    #[serde(
+        default = "default_error_rate_window",
+        deserialize_with = "deserialize_error_rate_window"
+    )]
+    pub error_rate_window: u32,
+    /// How long the breaker stays open before a request is let through to probe the endpoint.
+    #[serde(default = "default_open_duration_secs")]
+    pub open_duration_secs: u64,
+}
+
+const fn default_consecutive_failures() -> u32 {
+    5
+}
+
+const fn default_error_rate_window() -> u32 {
+    100
+}
+
+const fn default_open_duration_secs() -> u64 {
+    30
+}
+
+fn deserialize_error_rate<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    let error_rate = Option::<f64>::deserialize(deserializer)?;
+    match error_rate {
+        Some(rate) if rate.is_nan() || rate <= 0.0 || rate > 1.0 => Err(de::Error::invalid_value(
+            de::Unexpected::Float(rate),
+            &"a share of requests greater than 0 and at most 1",
+        )),
+        _ => Ok(error_rate),
+    }
+}
+
+// Without any requests in the window, every failure would exceed the error rate.
+fn deserialize_error_rate_window<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u32, D::Error> {
+    match u32::deserialize(deserializer)? {
+        0 => Err(de::Error::invalid_value(
+            de::Unexpected::Unsigned(0),
+            &"a number of requests greater than 0",
+        )),
+        window => Ok(window),
+    }
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            consecutive_failures: default_consecutive_failures(),
+            error_rate: None,
+            error_rate_window: default_error_rate_window(),
+            open_duration_secs: default_open_duration_secs(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CircuitBreakerState {
+    Closed,
+    HalfOpen,
+    Open,
+}
+
+impl CircuitBreakerState {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Closed => "closed",
+            Self::HalfOpen => "half_open",
+            Self::Open => "open",
+        }
+    }
+
+    /// The value of the `circuit_breaker_state` gauge.
+    pub const fn as_gauge(self) -> f64 {
+        match self {
+            Self::Closed => 0.0,
+            Self::HalfOpen => 1.0,
+            Self::Open => 2.0,
+        }
+    }
+}
+
+/// Returned instead of sending a request while the breaker is open. The retry policy waits for
+/// `retry_after` and tries again, without counting it as an attempt.
+#[derive(Debug, Snafu)]
+#[snafu(display("Circuit breaker is open, retrying in {:?}.", retry_after))]
+pub struct CircuitBreakerOpen {
+    pub retry_after: Duration,
+}
+
+#[derive(Debug)]
+struct Breaker {
+    config: CircuitBreakerConfig,
+    state: CircuitBreakerState,
+    open_until: Option<Instant>,
+    probing: bool,
+    consecutive_failures: u32,
+    outcomes: VecDeque<bool>,
+    failures: usize,
+    reported: bool,
+}
+
+impl Breaker {
+    fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: CircuitBreakerState::Closed,
+            open_until: None,
+            probing: false,
+            consecutive_failures: 0,
+            outcomes: VecDeque::new(),
+            failures: 0,
+            reported: false,
+        }
+    }
+
+    /// Decides whether a request may be sent, returning whether it's the half-open probe.
+    fn admit(&mut self, now: Instant) -> Result<bool, Duration> {
+        // The state is first reported here rather than on creation so that it's tagged with the
+        // sink, which isn't running yet when its service is built.
+        if !self.reported {
+            self.reported = true;
+            emit!(&CircuitBreakerStateChanged {
+                from: None,
+                to: self.state,
+            });
+        }
+
+        match self.state {
+            CircuitBreakerState::Closed => Ok(false),
+            CircuitBreakerState::Open => match self.open_until {
+                Some(until) if until > now => Err(until - now),
+                _ => {
+                    self.transition(CircuitBreakerState::HalfOpen);
+                    self.probing = true;
+                    Ok(true)
+                }
+            },
+            CircuitBreakerState::HalfOpen if self.probing => Err(PROBE_WAIT),
+            CircuitBreakerState::HalfOpen => {
+                self.probing = true;
+                Ok(true)
+            }
+        }
+    }
+
+    fn record(&mut self, probe: bool, failed: bool, now: Instant) {
+        match self.state {
+            CircuitBreakerState::HalfOpen if probe => {
+                self.probing = false;
+                if failed {
+                    self.open(now);
+                } else {
+                    self.transition(CircuitBreakerState::Closed);
+                }
+            }
+            CircuitBreakerState::Closed => {
+                if failed {
+                    self.consecutive_failures += 1;
+                } else {
+                    self.consecutive_failures = 0;
+                }
+
+                let too_many_consecutive = self.config.consecutive_failures > 0
+                    && self.consecutive_failures >= self.config.consecutive_failures;
+                if too_many_consecutive || self.exceeds_error_rate(failed) {
+                    self.open(now);
+                }
+            }
+            // Requests sent before the breaker opened, or while another request was probing.
+            _ => {}
+        }
+    }
+
+    /// Tracks the outcomes of the last `error_rate_window` requests, which only count once there
+    /// are that many of them.
+    fn exceeds_error_rate(&mut self, failed: bool) -> bool {
+        let error_rate = match self.config.error_rate {
+            Some(error_rate) => error_rate,
+            None => return false,
+        };
+        let window = self.config.error_rate_window as usize;
+
+        self.outcomes.push_back(failed);
+        self.failures += failed as usize;
+        if self.outcomes.len() > window {
+            self.failures -= self.outcomes.pop_front().unwrap_or_default() as usize;
+        }
+        self.outcomes.len() >= window && self.failures as f64 >= error_rate * window as f64
+    }
+
+    /// A probe that was dropped before finishing gives its turn to the next request.
+    fn abandon_probe(&mut self) {
+        if self.state == CircuitBreakerState::HalfOpen {
+            self.probing = false;
+        }
+    }
+
+    fn open(&mut self, now: Instant) {
+        self.open_until = Some(now + Duration::from_secs(self.config.open_duration_secs));
+        self.consecutive_failures = 0;
+        self.outcomes.clear();
+        self.failures = 0;
+        self.transition(CircuitBreakerState::Open);
+    }
+
+    fn transition(&mut self, to: CircuitBreakerState) {
+        let from = std::mem::replace(&mut self.state, to);
+        emit!(&CircuitBreakerStateChanged {
+            from: Some(from),
+            to,
+        });
+    }
+}
+
+/// Releases the half-open probe if its request is dropped before it finishes.
+struct Probe(Option<Arc<Mutex<Breaker>>>);
+
+impl Probe {
+    fn finish(mut self, failed: bool) {
+        if let Some(breaker) = self.0.take() {
+            breaker
+                .lock()
+                .expect("circuit breaker mutex poisoned")
+                .record(true, failed, Instant::now());
+        }
+    }
+}
+
+impl Drop for Probe {
+    fn drop(&mut self) {
+        if let Some(breaker) = self.0.take() {
+            breaker
+                .lock()
+                .expect("circuit breaker mutex poisoned")
+                .abandon_probe();
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CircuitBreakerLayer<L> {
+    config: Option<CircuitBreakerConfig>,
+    logic: L,
+}
+
+impl<L> CircuitBreakerLayer<L> {
+    pub const fn new(config: Option<CircuitBreakerConfig>, logic: L) -> Self {
+        Self { config, logic }
+    }
+}
+
+impl<S, L: Clone> Layer<S> for CircuitBreakerLayer<L> {
+    type Service = CircuitBreaker<S, L>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CircuitBreaker {
+            inner,
+            logic: self.logic.clone(),
+            breaker: self
+                .config
+                .map(|config| Arc::new(Mutex::new(Breaker::new(config)))),
+        }
+    }
+}
+
+/// Fails requests straight away with [`CircuitBreakerOpen`] while the breaker is open. The
+/// breaker is shared between clones, and passes every request through when it isn't configured.
+#[derive(Clone, Debug)]
+pub struct CircuitBreaker<S, L> {
+    inner: S,
+    logic: L,
+    breaker: Option<Arc<Mutex<Breaker>>>,
+}
+
+impl<S, L, Request> Service<Request> for CircuitBreaker<S, L>
+where
+    S: Service<Request, Error = crate::Error>,
+    S::Future: Send + 'static,
+    L: RetryLogic<Response = S::Response>,
+{
+    type Response = S::Response;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<S::Response, crate::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let breaker = match &self.breaker {
+            Some(breaker) => Arc::clone(breaker),
+            None => return self.inner.call(request).boxed(),
+        };
+
+        let admitted = breaker
+            .lock()
+            .expect("circuit breaker mutex poisoned")
+            .admit(Instant::now());
+        let probe = match admitted {
+            Ok(probe) => probe,
+            Err(retry_after) => {
+                emit!(&CircuitBreakerRequestRejected);
+                return futures::future::err(CircuitBreakerOpen { retry_after }.into()).boxed();
+            }
+        };
+
+        let logic = self.logic.clone();
+        let future = self.inner.call(request);
+        let guard = Probe(probe.then(|| Arc::clone(&breaker)));
+        Box::pin(async move {
+            let result = future.await;
+            let failed = match &result {
+                Ok(response) => logic.should_retry_response(response).is_retryable(),
+                Err(error) => error
+                    .downcast_ref::<L::Error>()
+                    .map_or(true, |error| logic.is_retriable_error(error)),
+            };
+            if probe {
+                guard.finish(failed);
+            } else {
+                breaker
+                    .lock()
+                    .expect("circuit breaker mutex poisoned")
+                    .record(false, failed, Instant::now());
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(config: &str) -> Breaker {
+        Breaker::new(toml::from_str(config).unwrap())
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let mut breaker = breaker("consecutive_failures = 3");
+        let now = Instant::now();
+
+        for failed in [true, true, false, true, true] {
+            assert_eq!(breaker.admit(now), Ok(false));
+            breaker.record(false, failed, now);
+        }
+        assert_eq!(breaker.state, CircuitBreakerState::Closed);
+
+        breaker.record(false, true, now);
+        assert_eq!(breaker.state, CircuitBreakerState::Open);
+        assert_eq!(breaker.admit(now), Err(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn opens_on_error_rate() {
+        let mut breaker =
+            breaker("consecutive_failures = 0\nerror_rate = 0.5\nerror_rate_window = 4");
+        let now = Instant::now();
+
+        for failed in [true, false, true] {
+            breaker.record(false, failed, now);
+        }
+        assert_eq!(breaker.state, CircuitBreakerState::Closed);
+
+        breaker.record(false, false, now);
+        assert_eq!(breaker.state, CircuitBreakerState::Open);
+    }
+
+    #[test]
+    fn rejects_invalid_error_rates() {
+        for config in [
+            "error_rate = 0.0",
+            "error_rate = 1.5",
+            "error_rate = -0.5",
+            "error_rate = nan",
+            "error_rate = 0.5\nerror_rate_window = 0",
+        ] {
+            assert!(toml::from_str::<CircuitBreakerConfig>(config).is_err());
+        }
+
+        let config = toml::from_str::<CircuitBreakerConfig>("error_rate = 1.0").unwrap();
+        assert_eq!(config.error_rate, Some(1.0));
+    }
+
+    #[test]
+    fn probes_when_half_open() {
+        let mut breaker = breaker("consecutive_failures = 1\nopen_duration_secs = 10");
+        let now = Instant::now();
+        breaker.record(false, true, now);
+
+        let later = now + Duration::from_secs(10);
+        assert_eq!(breaker.admit(later), Ok(true));
+        assert_eq!(breaker.state, CircuitBreakerState::HalfOpen);
+        assert_eq!(breaker.admit(later), Err(PROBE_WAIT));
+
+        // A failed probe opens the breaker again.
+        breaker.record(true, true, later);
+        assert_eq!(breaker.state, CircuitBreakerState::Open);
+
+        let later = later + Duration::from_secs(10);
+        assert_eq!(breaker.admit(later), Ok(true));
+        breaker.abandon_probe();
+        assert_eq!(breaker.admit(later), Ok(true));
+        breaker.record(true, false, later);
+        assert_eq!(breaker.state, CircuitBreakerState::Closed);
+        assert_eq!(breaker.admit(later), Ok(false));
+    }
+}
@@ -2,10 +2,10 @@ use std::fmt;
 
 use serde::{
     de::{self, Unexpected, Visitor},
-    Deserialize, Deserializer, Serialize,
+    Deserialize, Deserializer, Serialize, Serializer,
 };
 
-#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
 pub enum Concurrency {
     None,
     Adaptive,
@@ -38,6 +38,20 @@ pub const fn concurrency_is_none(concurrency: &Concurrency) -> bool {
     matches!(concurrency, Concurrency::None)
 }
 
+// Serialize the same way as the configuration is written, so that it can be deserialized again.
+impl Serialize for Concurrency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Concurrency::None => serializer.serialize_none(),
+            Concurrency::Adaptive => serializer.serialize_str("adaptive"),
+            Concurrency::Fixed(limit) => serializer.serialize_u64(*limit as u64),
+        }
+    }
+}
+
 impl<'de> Deserialize<'de> for Concurrency {
     // Deserialize either a positive integer or the string "adaptive"
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -53,6 +67,14 @@ impl<'de> Deserialize<'de> for Concurrency {
                 formatter.write_str(r#"positive integer or "adaptive""#)
             }
 
+            fn visit_none<E: de::Error>(self) -> Result<Concurrency, E> {
+                Ok(Concurrency::None)
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Concurrency, E> {
+                Ok(Concurrency::None)
+            }
+
             fn visit_str<E: de::Error>(self, value: &str) -> Result<Concurrency, E> {
                 if value == "adaptive" {
                     Ok(Concurrency::Adaptive)
@@ -0,0 +1,333 @@
+use std::{
+    num::NonZeroU32,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use tokio::time::timeout;
+use tower::Service;
+
+use super::{
+    config::{LoadBalancingConfig, LoadBalancingStrategy, VectorGrpcRetryLogic},
+    service::{VectorRequest, VectorResponse, VectorService},
+    VectorSinkError,
+};
+use crate::{
+    internal_events::{VectorEndpointEjected, VectorEndpointReadmitted},
+    sinks::util::retries::RetryLogic,
+    Error,
+};
+
+struct Endpoint {
+    service: VectorService,
+    weight: u32,
+    healthy: AtomicBool,
+    consecutive_failures: AtomicU32,
+    outstanding: AtomicUsize,
+}
+
+impl Endpoint {
+    fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    fn eject(&self, reason: &str) {
+        if self.healthy.swap(false, Ordering::Relaxed) {
+            emit!(&VectorEndpointEjected {
+                endpoint: &self.service.endpoint,
+                reason,
+            });
+        }
+    }
+
+    fn readmit(&self) {
+        self.consecutive_failures.store(0, Ordering::Relaxed);
+        if !self.healthy.swap(true, Ordering::Relaxed) {
+            emit!(&VectorEndpointReadmitted {
+                endpoint: &self.service.endpoint,
+            });
+        }
+    }
+}
+
+/// Balances requests across several downstream Vector instances.
+///
+/// Endpoints are ejected after `failure_threshold` consecutive failed requests, or a failed
+/// health check, and are only readmitted once a health check succeeds again. If every endpoint
+/// has been ejected, requests are balanced across all of them so that they keep being retried.
+#[derive(Clone)]
+pub struct VectorBalancer {
+    endpoints: Arc<Vec<Endpoint>>,
+    // Endpoint indices in the order round robin visits them, with each endpoint appearing as many
+    // times as its weight.
+    schedule: Arc<Vec<usize>>,
+    next: Arc<AtomicUsize>,
+    strategy: LoadBalancingStrategy,
+    failure_threshold: u32,
+}
+
+impl VectorBalancer {
+    pub fn new(endpoints: Vec<(VectorService, NonZeroU32)>, config: LoadBalancingConfig) -> Self {
+        let endpoints = endpoints
+            .into_iter()
+            .map(|(service, weight)| Endpoint {
+                service,
+                weight: weight.get(),
+                healthy: AtomicBool::new(true),
+                consecutive_failures: AtomicU32::new(0),
+                outstanding: AtomicUsize::new(0),
+            })
+            .collect::<Vec<_>>();
+
+        Self {
+            schedule: Arc::new(build_schedule(&endpoints)),
+            endpoints: Arc::new(endpoints),
+            next: Arc::new(AtomicUsize::new(0)),
+            strategy: config.strategy,
+            failure_threshold: config.failure_threshold.max(1),
+        }
+    }
+
+    /// Check the health of each endpoint at the given interval, for as long as the balancer is
+    /// in use.
+    pub fn spawn_health_checks(&self, interval: Duration) {
+        let endpoints = Arc::downgrade(&self.endpoints);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick completes immediately, and the endpoints start out healthy.
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+
+                let endpoints = match endpoints.upgrade() {
+                    Some(endpoints) => endpoints,
+                    None => break,
+                };
+
+                for endpoint in endpoints.iter() {
+                    let mut service = endpoint.service.clone();
+                    match timeout(interval, service.is_serving()).await {
+                        Ok(true) => endpoint.readmit(),
+                        _ => endpoint.eject("health check failed"),
+                    }
+                }
+            }
+        });
+    }
+
+    fn select(&self) -> usize {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let any_healthy = self.endpoints.iter().any(Endpoint::is_healthy);
+        let available = |index: usize| !any_healthy || self.endpoints[index].is_healthy();
+
+        let index = match self.strategy {
+            LoadBalancingStrategy::RoundRobin => (0..self.schedule.len())
+                .map(|offset| self.schedule[(start + offset) % self.schedule.len()])
+                .find(|index| available(*index)),
+            // Start from a different endpoint each time, so that ties are spread out too.
+            LoadBalancingStrategy::LeastOutstanding => (0..self.endpoints.len())
+                .map(|offset| (start + offset) % self.endpoints.len())
+                .filter(|index| available(*index))
+                .min_by(|a, b| {
+                    let (a, b) = (&self.endpoints[*a], &self.endpoints[*b]);
+                    let a_load = a.outstanding.load(Ordering::Relaxed) as u64 * b.weight as u64;
+                    let b_load = b.outstanding.load(Ordering::Relaxed) as u64 * a.weight as u64;
+                    a_load.cmp(&b_load)
+                }),
+        };
+
+        index.expect("at least one endpoint is available")
+    }
+
+    fn record(&self, index: usize, result: &Result<VectorResponse, Error>) {
+        let endpoint = &self.endpoints[index];
+
+        match result {
+            Ok(_) => endpoint.consecutive_failures.store(0, Ordering::Relaxed),
+            Err(error) if is_endpoint_failure(error) => {
+                let failures = endpoint
+                    .consecutive_failures
+                    .fetch_add(1, Ordering::Relaxed)
+                    + 1;
+                if failures >= self.failure_threshold {
+                    endpoint.eject("too many consecutive failed requests");
+                }
+            }
+            // The endpoint refused the request itself, which says nothing about its health.
+            Err(_) => {}
+        }
+    }
+}
+
+/// Interleave the endpoints by weight, so that weights of 3 and 1 give `[0, 1, 0, 0]`.
+fn build_schedule(endpoints: &[Endpoint]) -> Vec<usize> {
+    let max_weight = endpoints.iter().map(|e| e.weight).max().unwrap_or(0);
+
+    (0..max_weight)
+        .flat_map(|round| {
+            endpoints
+                .iter()
+                .enumerate()
+                .filter(move |(_, endpoint)| endpoint.weight > round)
+                .map(|(index, _)| index)
+        })
+        .collect()
+}
+
+fn is_endpoint_failure(error: &Error) -> bool {
+    match error.downcast_ref::<VectorSinkError>() {
+        Some(error) => VectorGrpcRetryLogic.is_retriable_error(error),
+        None => true,
+    }
+}
+
+/// Tracks a request in flight to an endpoint, for as long as the request isn't dropped.
+struct Outstanding {
+    endpoints: Arc<Vec<Endpoint>>,
+    index: usize,
+}
+
+impl Outstanding {
+    fn new(endpoints: Arc<Vec<Endpoint>>, index: usize) -> Self {
+        endpoints[index].outstanding.fetch_add(1, Ordering::Relaxed);
+        Self { endpoints, index }
+    }
+}
+
+impl Drop for Outstanding {
+    fn drop(&mut self) {
+        self.endpoints[self.index]
+            .outstanding
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl Service<VectorRequest> for VectorBalancer {
+    type Response = VectorResponse;
+    type Error = Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Each endpoint's `VectorService` is always ready, see its `poll_ready()`.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: VectorRequest) -> Self::Future {
+        let index = self.select();
+        let outstanding = Outstanding::new(Arc::clone(&self.endpoints), index);
+        let future = self.endpoints[index].service.clone().call(request);
+        let balancer = self.clone();
+
+        Box::pin(async move {
+            let result = future.await;
+            drop(outstanding);
+            balancer.record(index, &result);
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::ProxyConfig, sinks::vector::v2::config::new_client, tls::MaybeTlsSettings,
+    };
+
+    fn balancer(weights: &[u32], strategy: LoadBalancingStrategy) -> VectorBalancer {
+        let client = new_client(&MaybeTlsSettings::Raw(()), &ProxyConfig::default()).unwrap();
+        let endpoints = weights
+            .iter()
+            .enumerate()
+            .map(|(index, weight)| {
+                let uri = format!("http://10.0.0.{}:6000/", index).parse().unwrap();
+                (
+                    VectorService::new(client.clone(), uri, false, false),
+                    NonZeroU32::new(*weight).unwrap(),
+                )
+            })
+            .collect();
+
+        VectorBalancer::new(
+            endpoints,
+            LoadBalancingConfig {
+                strategy,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn selections(balancer: &VectorBalancer, count: usize) -> Vec<usize> {
+        (0..count).map(|_| balancer.select()).collect()
+    }
+
+    #[test]
+    fn round_robin_follows_weights() {
+        let balancer = balancer(&[3, 1], LoadBalancingStrategy::RoundRobin);
+
+        assert_eq!(selections(&balancer, 8), vec![0, 1, 0, 0, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn round_robin_skips_ejected_endpoints() {
+        let balancer = balancer(&[1, 1, 1], LoadBalancingStrategy::RoundRobin);
+        balancer.endpoints[1].eject("test");
+
+        assert_eq!(selections(&balancer, 4), vec![0, 2, 2, 0]);
+
+        balancer.endpoints[1].readmit();
+        assert!(selections(&balancer, 3).contains(&1));
+    }
+
+    #[test]
+    fn uses_all_endpoints_when_all_ejected() {
+        let balancer = balancer(&[1, 1], LoadBalancingStrategy::RoundRobin);
+        balancer.endpoints[0].eject("test");
+        balancer.endpoints[1].eject("test");
+
+        assert_eq!(selections(&balancer, 2), vec![0, 1]);
+    }
+
+    #[test]
+    fn least_outstanding_prefers_idle_endpoints() {
+        let balancer = balancer(&[1, 2], LoadBalancingStrategy::LeastOutstanding);
+        let _first = Outstanding::new(Arc::clone(&balancer.endpoints), 0);
+        let _second = Outstanding::new(Arc::clone(&balancer.endpoints), 1);
+
+        // Endpoint 1 has one request in flight for its weight of 2, so it is less loaded.
+        assert_eq!(selections(&balancer, 2), vec![1, 1]);
+    }
+
+    #[test]
+    fn ejects_after_consecutive_failures() {
+        let balancer = balancer(&[1, 1], LoadBalancingStrategy::RoundRobin);
+        let failure = || -> Result<VectorResponse, Error> {
+            Err(VectorSinkError::Request {
+                source: tonic::Status::unavailable("down"),
+            }
+            .into())
+        };
+        let rejection = || -> Result<VectorResponse, Error> {
+            Err(VectorSinkError::Request {
+                source: tonic::Status::invalid_argument("bad"),
+            }
+            .into())
+        };
+
+        balancer.record(0, &failure());
+        balancer.record(0, &rejection());
+        balancer.record(0, &failure());
+        assert!(balancer.endpoints[0].is_healthy());
+
+        balancer.record(0, &failure());
+        assert!(!balancer.endpoints[0].is_healthy());
+        assert!(balancer.endpoints[1].is_healthy());
+    }
+}
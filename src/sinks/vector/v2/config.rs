@@ -1,20 +1,23 @@
+use std::{num::NonZeroU32, time::Duration};
+
 use http::Uri;
 use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
 use tonic::body::BoxBody;
 use tower::ServiceBuilder;
 
 use crate::{
     config::{GenerateConfig, ProxyConfig, SinkContext, SinkHealthcheckOptions},
-    proto::vector as proto,
     sinks::{
         util::{
             retries::RetryLogic, BatchConfig, RealtimeEventBasedDefaultBatchSettings,
             ServiceBuilderExt, TowerRequestConfig,
         },
         vector::v2::{
+            balance::VectorBalancer,
             service::{VectorResponse, VectorService},
             sink::VectorSink,
             VectorSinkError,
@@ -24,10 +27,27 @@ use crate::{
     tls::{tls_connector_builder, MaybeTlsSettings, TlsConfig},
 };
 
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Exactly one of `address` or `endpoints` must be set"))]
+    AddressOrEndpoints,
+    #[snafu(display("`load_balancing.health_check_interval_secs` must be greater than 0"))]
+    ZeroHealthCheckInterval,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct VectorConfig {
-    address: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    endpoints: Vec<EndpointConfig>,
+    #[serde(default)]
+    load_balancing: LoadBalancingConfig,
+    #[serde(default)]
+    compression: bool,
+    #[serde(default)]
+    event_arrays: bool,
     #[serde(default)]
     pub batch: BatchConfig<RealtimeEventBasedDefaultBatchSettings>,
     #[serde(default)]
@@ -42,9 +62,71 @@ impl GenerateConfig for VectorConfig {
     }
 }
 
+/// A downstream Vector instance to balance events across.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct EndpointConfig {
+    address: String,
+    #[serde(default = "default_weight")]
+    weight: NonZeroU32,
+}
+
+const fn default_weight() -> NonZeroU32 {
+    unsafe { NonZeroU32::new_unchecked(1) }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadBalancingStrategy {
+    /// Send requests to each endpoint in turn, in proportion to their weights.
+    RoundRobin,
+    /// Send each request to the endpoint with the fewest requests in flight, relative to its
+    /// weight.
+    LeastOutstanding,
+}
+
+impl Default for LoadBalancingStrategy {
+    fn default() -> Self {
+        Self::RoundRobin
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy)]
+#[serde(deny_unknown_fields)]
+pub struct LoadBalancingConfig {
+    #[serde(default)]
+    pub strategy: LoadBalancingStrategy,
+    #[serde(default = "default_health_check_interval_secs")]
+    pub health_check_interval_secs: u64,
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+}
+
+impl Default for LoadBalancingConfig {
+    fn default() -> Self {
+        Self {
+            strategy: LoadBalancingStrategy::default(),
+            health_check_interval_secs: default_health_check_interval_secs(),
+            failure_threshold: default_failure_threshold(),
+        }
+    }
+}
+
+const fn default_health_check_interval_secs() -> u64 {
+    10
+}
+
+const fn default_failure_threshold() -> u32 {
+    3
+}
+
 fn default_config(address: &str) -> VectorConfig {
     VectorConfig {
-        address: address.to_owned(),
+        address: Some(address.to_owned()),
+        endpoints: Vec::new(),
+        load_balancing: LoadBalancingConfig::default(),
+        compression: false,
+        event_arrays: false,
         batch: BatchConfig::default(),
         request: TowerRequestConfig::default(),
         tls: None,
@@ -56,26 +138,73 @@ impl VectorConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(VectorSinkType, Healthcheck)> {
-        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
-        let uri = with_default_scheme(&self.address, tls.is_tls())?;
+        if self.load_balancing.health_check_interval_secs == 0 {
+            return Err(Box::new(BuildError::ZeroHealthCheckInterval));
+        }
 
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
         let client = new_client(&tls, cx.proxy())?;
-
-        let healthcheck_uri = cx
-            .healthcheck
-            .uri
-            .clone()
-            .map(|uri| uri.uri)
-            .unwrap_or_else(|| uri.clone());
-        let healthcheck_client = VectorService::new(client.clone(), healthcheck_uri);
-        let healthcheck = healthcheck(healthcheck_client, cx.healthcheck.clone());
-        let service = VectorService::new(client, uri);
+        self.request.reject_status_overrides()?;
         let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
         let batch_settings = self.batch.into_batcher_settings()?;
-        //
-        let service = ServiceBuilder::new()
-            .settings(request_settings, VectorGrpcRetryLogic)
-            .service(service);
+
+        let (service, healthcheck) = match (&self.address, self.endpoints.is_empty()) {
+            (Some(address), true) => {
+                let uri = with_default_scheme(address, tls.is_tls())?;
+
+                let healthcheck_uri = cx
+                    .healthcheck
+                    .uri
+                    .clone()
+                    .map(|uri| uri.uri)
+                    .unwrap_or_else(|| uri.clone());
+                let healthcheck_client = VectorService::new(
+                    client.clone(),
+                    healthcheck_uri,
+                    self.compression,
+                    self.event_arrays,
+                );
+                let healthcheck = healthcheck(vec![healthcheck_client], cx.healthcheck.clone());
+                let service = VectorService::new(client, uri, self.compression, self.event_arrays);
+
+                let service = ServiceBuilder::new()
+                    .settings(request_settings, VectorGrpcRetryLogic)
+                    .service(service);
+                (service, healthcheck)
+            }
+            (None, false) => {
+                let endpoints = self
+                    .endpoints
+                    .iter()
+                    .map(|endpoint| {
+                        let uri = with_default_scheme(&endpoint.address, tls.is_tls())?;
+                        let service = VectorService::new(
+                            client.clone(),
+                            uri,
+                            self.compression,
+                            self.event_arrays,
+                        );
+                        Ok((service, endpoint.weight))
+                    })
+                    .collect::<crate::Result<Vec<_>>>()?;
+
+                let healthcheck_clients = endpoints
+                    .iter()
+                    .map(|(service, _)| service.clone())
+                    .collect();
+                let healthcheck = healthcheck(healthcheck_clients, cx.healthcheck.clone());
+                let balancer = VectorBalancer::new(endpoints, self.load_balancing);
+                balancer.spawn_health_checks(Duration::from_secs(
+                    self.load_balancing.health_check_interval_secs,
+                ));
+
+                let service = ServiceBuilder::new()
+                    .settings(request_settings, VectorGrpcRetryLogic)
+                    .service(balancer);
+                (service, healthcheck)
+            }
+            _ => return Err(Box::new(BuildError::AddressOrEndpoints)),
+        };
 
         let sink = VectorSink {
             batch_settings,
@@ -90,21 +219,17 @@ impl VectorConfig {
     }
 }
 
-/// Check to see if the remote service accepts new events.
+/// Check to see if any of the remote services accept new events.
 async fn healthcheck(
-    mut service: VectorService,
+    services: Vec<VectorService>,
     options: SinkHealthcheckOptions,
 ) -> crate::Result<()> {
     if !options.enabled {
         return Ok(());
     }
 
-    let request = service.client.health_check(proto::HealthCheckRequest {});
-
-    if let Ok(response) = request.await {
-        let status = proto::ServingStatus::from_i32(response.into_inner().status);
-
-        if let Some(proto::ServingStatus::Serving) = status {
+    for mut service in services {
+        if service.is_serving().await {
             return Ok(());
         }
     }
@@ -146,7 +271,7 @@ pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
     }
 }
 
-fn new_client(
+pub(super) fn new_client(
     tls_settings: &MaybeTlsSettings,
     proxy_config: &ProxyConfig,
 ) -> crate::Result<hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>> {
@@ -172,7 +297,7 @@ fn new_client(
 }
 
 #[derive(Debug, Clone)]
-struct VectorGrpcRetryLogic;
+pub(super) struct VectorGrpcRetryLogic;
 
 impl RetryLogic for VectorGrpcRetryLogic {
     type Error = VectorSinkError;
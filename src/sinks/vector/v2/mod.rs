@@ -1,5 +1,6 @@
 use snafu::Snafu;
 
+mod balance;
 mod config;
 mod service;
 mod sink;
@@ -121,6 +122,186 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
     }
 
+    #[tokio::test]
+    async fn falls_back_without_compression() {
+        let in_addr = next_addr();
+
+        let config = format!(
+            r#"address = "http://{}/"
+            compression = true"#,
+            in_addr
+        );
+        let config: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let (rx, trigger, server) = build_test_server_generic(in_addr, move || {
+            hyper::Response::builder()
+                .header("grpc-status", "12") // unimplemented
+                .header("content-type", "application/grpc")
+                .body(tonic::body::empty_body())
+                .unwrap()
+        });
+
+        tokio::spawn(server);
+
+        let (_, events) = random_lines_with_stream(8, 10, None);
+        sink.run(events).await.unwrap();
+        drop(trigger);
+
+        let paths = rx
+            .map(|(parts, _)| parts.uri.path().to_owned())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            paths,
+            vec![
+                "/vector.Vector/PushCompressedEvents",
+                "/vector.Vector/PushEvents"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn delivers_event_arrays() {
+        let num_lines = 10;
+
+        let in_addr = next_addr();
+
+        let config = format!(
+            r#"address = "http://{}/"
+            event_arrays = true"#,
+            in_addr
+        );
+        let config: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let (rx, trigger, server) = build_test_server_generic(in_addr, move || {
+            hyper::Response::builder()
+                .header("grpc-status", "0") // OK
+                .header("content-type", "application/grpc")
+                .body(hyper::Body::from(encode_body(proto::PushEventsResponse {})))
+                .unwrap()
+        });
+
+        tokio::spawn(server);
+
+        let (input_lines, events) = random_lines_with_stream(8, num_lines, None);
+        sink.run(events).await.unwrap();
+        drop(trigger);
+
+        let output_lines = rx
+            .map(|(parts, body)| {
+                assert_eq!("/vector.Vector/PushEventArrays", parts.uri.path());
+
+                let req = proto::PushEventArraysRequest::decode(body.slice(GRPC_HEADER_SIZE..))
+                    .unwrap();
+                assert_eq!(req.compression, proto::Compression::None as i32);
+                let arrays = proto::EventArrays::decode(&req.payload[..]).unwrap();
+                // Consecutive logs share a single array.
+                assert_eq!(arrays.arrays.len(), 1);
+
+                arrays
+                    .arrays
+                    .into_iter()
+                    .flat_map(|array| array.into_wrappers())
+                    .map(|wrapper| {
+                        let event: Event = wrapper.into();
+                        event.as_log().get("message").unwrap().to_string_lossy()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        assert_eq!(input_lines, output_lines);
+    }
+
+    #[tokio::test]
+    async fn falls_back_without_event_arrays() {
+        let in_addr = next_addr();
+
+        let config = format!(
+            r#"address = "http://{}/"
+            compression = true
+            event_arrays = true"#,
+            in_addr
+        );
+        let config: VectorConfig = toml::from_str(&config).unwrap();
+
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let (rx, trigger, server) = build_test_server_generic(in_addr, move || {
+            hyper::Response::builder()
+                .header("grpc-status", "12") // unimplemented
+                .header("content-type", "application/grpc")
+                .body(tonic::body::empty_body())
+                .unwrap()
+        });
+
+        tokio::spawn(server);
+
+        let (_, events) = random_lines_with_stream(8, 10, None);
+        sink.run(events).await.unwrap();
+        drop(trigger);
+
+        let paths = rx
+            .map(|(parts, _)| parts.uri.path().to_owned())
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(
+            paths,
+            vec![
+                "/vector.Vector/PushEventArrays",
+                "/vector.Vector/PushCompressedEvents",
+                "/vector.Vector/PushEvents"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn requires_address_or_endpoints() {
+        for config in [
+            "",
+            r#"
+            address = "127.0.0.1:6000"
+            endpoints = [{ address = "127.0.0.1:6001" }]
+            "#,
+        ] {
+            let config: VectorConfig = toml::from_str(config).unwrap();
+            assert!(config.build(SinkContext::new_test()).await.is_err());
+        }
+
+        let config: VectorConfig = toml::from_str(
+            r#"
+            endpoints = [
+                { address = "127.0.0.1:6000", weight = 2 },
+                { address = "127.0.0.1:6001" },
+            ]
+            load_balancing.strategy = "least_outstanding"
+            "#,
+        )
+        .unwrap();
+        assert!(config.build(SinkContext::new_test()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_health_check_interval() {
+        let config: VectorConfig = toml::from_str(
+            r#"
+            endpoints = [{ address = "127.0.0.1:6000" }, { address = "127.0.0.1:6001" }]
+            load_balancing.health_check_interval_secs = 0
+            "#,
+        )
+        .unwrap();
+        let error = config.build(SinkContext::new_test()).await.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "`load_balancing.health_check_interval_secs` must be greater than 0"
+        );
+    }
+
     #[test]
     fn test_with_default_scheme() {
         assert_eq!(
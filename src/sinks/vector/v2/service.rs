@@ -1,4 +1,10 @@
-use std::task::{Context, Poll};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
 
 use futures::{future::BoxFuture, TryFutureExt};
 use http::Uri;
@@ -6,8 +12,8 @@ use hyper::client::HttpConnector;
 use hyper_openssl::HttpsConnector;
 use hyper_proxy::ProxyConnector;
 use prost::Message;
-use proto_event::EventWrapper;
-use tonic::{body::BoxBody, IntoRequest};
+use proto_event::{EventArray, EventWrapper};
+use tonic::{body::BoxBody, Code, IntoRequest, Status};
 use vector_core::{
     buffers::Ackable, event::proto as proto_event, internal_event::EventsSent,
     stream::DriverResponse,
@@ -15,7 +21,9 @@ use vector_core::{
 
 use crate::{
     event::{EventFinalizers, EventStatus, Finalizable},
-    internal_events::EndpointBytesSent,
+    internal_events::{
+        EndpointBytesSent, VectorCompressionUnsupported, VectorEventArraysUnsupported,
+    },
     proto::vector as proto_vector,
     sinks::{util::uri, vector::v2::VectorSinkError},
     Error,
@@ -26,6 +34,10 @@ pub struct VectorService {
     pub client: proto_vector::Client<HyperSvc>,
     pub protocol: String,
     pub endpoint: String,
+    // Turned off for good if the source turns out not to support compressed requests.
+    compression: Arc<AtomicBool>,
+    // Likewise, turned off for good if the source doesn't support event arrays.
+    event_arrays: Arc<AtomicBool>,
 }
 
 pub struct VectorResponse {
@@ -48,14 +60,39 @@ impl DriverResponse for VectorResponse {
 
 #[derive(Clone, Default)]
 pub struct VectorRequest {
-    pub events: Vec<EventWrapper>,
+    /// The events, in arrays of consecutive events of the same type.
+    pub arrays: Vec<EventArray>,
+    pub events_count: usize,
     pub finalizers: EventFinalizers,
     pub events_byte_size: usize,
 }
 
+impl VectorRequest {
+    /// Adds the event to the last array, or starts a new array if the event is of another type.
+    pub fn push(&mut self, wrapper: EventWrapper) {
+        let event = match wrapper.event {
+            Some(event) => event,
+            None => return,
+        };
+        self.events_count += 1;
+
+        let event = match self.arrays.last_mut() {
+            Some(array) => match array.try_push(event) {
+                Ok(()) => return,
+                Err(event) => event,
+            },
+            None => event,
+        };
+        let mut array = EventArray::default();
+        if array.try_push(event).is_ok() {
+            self.arrays.push(array);
+        }
+    }
+}
+
 impl Ackable for VectorRequest {
     fn ack_size(&self) -> usize {
-        self.events.len()
+        self.events_count
     }
 }
 
@@ -69,6 +106,8 @@ impl VectorService {
     pub fn new(
         hyper_client: hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>,
         uri: Uri,
+        compression: bool,
+        event_arrays: bool,
     ) -> Self {
         let (protocol, endpoint) = uri::protocol_endpoint(uri.clone());
         let proto_client = proto_vector::Client::new(HyperSvc {
@@ -79,6 +118,97 @@ impl VectorService {
             client: proto_client,
             protocol,
             endpoint,
+            compression: Arc::new(AtomicBool::new(compression)),
+            event_arrays: Arc::new(AtomicBool::new(event_arrays)),
+        }
+    }
+
+    /// Send the events, in their arrays if the source supports it, returning the number of bytes
+    /// sent.
+    async fn push(&mut self, arrays: Vec<EventArray>) -> Result<usize, Status> {
+        if self.event_arrays.load(Ordering::Relaxed) {
+            let request = proto_vector::EventArrays { arrays };
+            let encoded = request.encode_to_vec();
+            let (compression, payload) = if self.compression.load(Ordering::Relaxed) {
+                let payload =
+                    zstd::stream::encode_all(&encoded[..], zstd::DEFAULT_COMPRESSION_LEVEL)
+                        .expect("Compressing into a Vec can't fail");
+                (proto_vector::Compression::Zstd, payload)
+            } else {
+                (proto_vector::Compression::None, encoded)
+            };
+            let byte_size = payload.len();
+            let pushed = proto_vector::PushEventArraysRequest {
+                compression: compression.into(),
+                payload,
+            };
+
+            match self.client.push_event_arrays(pushed.into_request()).await {
+                Err(status) if status.code() == Code::Unimplemented => {
+                    emit!(&VectorEventArraysUnsupported {
+                        endpoint: &self.endpoint
+                    });
+                    self.event_arrays.store(false, Ordering::Relaxed);
+                    return self.push_events(flatten(request.arrays)).await;
+                }
+                result => return result.map(|_| byte_size),
+            }
+        }
+
+        self.push_events(flatten(arrays)).await
+    }
+
+    /// Send the events, compressed if the source supports it, returning the number of bytes sent.
+    async fn push_events(
+        &mut self,
+        request: proto_vector::PushEventsRequest,
+    ) -> Result<usize, Status> {
+        if self.compression.load(Ordering::Relaxed) {
+            let payload = zstd::stream::encode_all(
+                &request.encode_to_vec()[..],
+                zstd::DEFAULT_COMPRESSION_LEVEL,
+            )
+            .expect("Compressing into a Vec can't fail");
+            let byte_size = payload.len();
+            let compressed = proto_vector::PushCompressedEventsRequest {
+                compression: proto_vector::Compression::Zstd.into(),
+                payload,
+            };
+
+            match self
+                .client
+                .push_compressed_events(compressed.into_request())
+                .await
+            {
+                Err(status) if status.code() == Code::Unimplemented => {
+                    emit!(&VectorCompressionUnsupported {
+                        endpoint: &self.endpoint
+                    });
+                    self.compression.store(false, Ordering::Relaxed);
+                }
+                result => return result.map(|_| byte_size),
+            }
+        }
+
+        let byte_size = request.encoded_len();
+        self.client
+            .push_events(request.into_request())
+            .await
+            .map(|_| byte_size)
+    }
+
+    /// Check to see if the remote service accepts new events.
+    pub async fn is_serving(&mut self) -> bool {
+        let request = self
+            .client
+            .health_check(proto_vector::HealthCheckRequest {});
+
+        match request.await {
+            Ok(response) => matches!(
+                proto_vector::ServingStatus::from_i32(response.into_inner().status),
+                Some(proto_vector::ServingStatus::Serving)
+            ),
+            Err(_) => false,
         }
     }
 }
@@ -99,17 +229,12 @@ impl tower::Service<VectorRequest> for VectorService {
 
     fn call(&mut self, list: VectorRequest) -> Self::Future {
         let mut service = self.clone();
-        let events_count = list.events.len();
+        let events_count = list.events_count;
 
-        let request = proto_vector::PushEventsRequest {
-            events: list.events,
-        };
-        let byte_size = request.encoded_len();
         let future = async move {
             service
-                .client
-                .push_events(request.into_request())
-                .map_ok(|_response| {
+                .push(list.arrays)
+                .map_ok(|byte_size| {
                     emit!(&EndpointBytesSent {
                         byte_size,
                         protocol: &service.protocol,
@@ -128,6 +253,15 @@ impl tower::Service<VectorRequest> for VectorService {
     }
 }
 
+fn flatten(arrays: Vec<EventArray>) -> proto_vector::PushEventsRequest {
+    proto_vector::PushEventsRequest {
+        events: arrays
+            .into_iter()
+            .flat_map(EventArray::into_wrappers)
+            .collect(),
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HyperSvc {
     uri: Uri,
@@ -38,7 +38,7 @@ impl VectorSink {
                 |req: &mut VectorRequest, item: EventData| {
                     req.events_byte_size += item.byte_size;
                     req.finalizers.merge(item.finalizers);
-                    req.events.push(item.wrapper);
+                    req.push(item.wrapper);
                 },
             ))
             .into_driver(self.service, self.acker)
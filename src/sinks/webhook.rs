@@ -0,0 +1,616 @@
+//! The `webhook` sink sends batches of events to URLs rendered from the events themselves, with
+//! bodies rendered from templates and, optionally, signed with HMAC so that receivers can verify
+//! where they came from.
+//!
+//! Unlike the `http` sink, which sends events encoded as a whole to a single URI, each event is
+//! rendered into its part of the body, and events are batched separately for each URL they're
+//! sent to.
+
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{future, FutureExt, SinkExt};
+use http::{
+    header::{HeaderName, HeaderValue, CONTENT_TYPE},
+    Request,
+};
+use hyper::Client;
+use indexmap::IndexMap;
+use openssl::{
+    hash::MessageDigest,
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use vector_core::ByteSizeOf;
+
+use crate::{
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::Event,
+    http::{Auth, HttpClient},
+    internal_events::{HttpEventEncoded, TemplateRenderingFailed},
+    sinks::util::{
+        buffer::vec::EncodedLength,
+        encoding::{EncodingConfigWithDefault, EncodingConfiguration},
+        http::{HttpSink, PartitionHttpSink},
+        BatchConfig, PartitionBuffer, PartitionInnerBuffer, RealtimeSizeBasedDefaultBatchSettings,
+        TowerRequestConfig, VecBuffer,
+    },
+    template::Template,
+    tls::{TlsOptions, TlsSettings},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid header name {:?}: {}", name, source))]
+    InvalidHeaderName {
+        name: String,
+        source: http::header::InvalidHeaderName,
+    },
+    #[snafu(display("Invalid value for header {:?}: {}", name, source))]
+    InvalidHeaderValue {
+        name: String,
+        source: http::header::InvalidHeaderValue,
+    },
+    #[snafu(display("`body.wrapper` must contain `{}` once", EVENTS_PLACEHOLDER))]
+    InvalidWrapper,
+    #[snafu(display("Invalid signing secret: {}", source))]
+    InvalidSecret { source: openssl::error::ErrorStack },
+}
+
+/// Where the events of a batch go in `body.wrapper`.
+const EVENTS_PLACEHOLDER: &str = "{{ events }}";
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookSinkConfig {
+    pub uri: Template,
+    #[serde(default)]
+    pub method: WebhookMethod,
+    pub auth: Option<Auth>,
+    #[serde(default)]
+    pub headers: IndexMap<String, String>,
+    #[serde(default)]
+    pub body: BodyConfig,
+    pub signing: Option<SigningConfig>,
+    /// The most idle connections kept open to each host.
+    #[serde(default = "default_max_idle_connections_per_host")]
+    pub max_idle_connections_per_host: usize,
+    #[serde(
+        skip_serializing_if = "crate::serde::skip_serializing_if_default",
+        default
+    )]
+    pub encoding: EncodingConfigWithDefault<Encoding>,
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+    pub tls: Option<TlsOptions>,
+}
+
+const fn default_max_idle_connections_per_host() -> usize {
+    8
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookMethod {
+    Post,
+    Put,
+    Patch,
+}
+
+impl Default for WebhookMethod {
+    fn default() -> Self {
+        Self::Post
+    }
+}
+
+impl From<WebhookMethod> for http::Method {
+    fn from(method: WebhookMethod) -> Self {
+        match method {
+            WebhookMethod::Post => Self::POST,
+            WebhookMethod::Put => Self::PUT,
+            WebhookMethod::Patch => Self::PATCH,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
+#[serde(rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum Encoding {
+    #[derivative(Default)]
+    Json,
+}
+
+/// How the body of each request is rendered.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BodyConfig {
+    /// The template each event is rendered with. Without one, events are encoded as JSON.
+    pub template: Option<Template>,
+    /// What the rendered events of a batch are joined with.
+    #[serde(default = "default_separator")]
+    pub separator: String,
+    /// Wraps the rendered events of a batch, which replace its `{{ events }}`.
+    pub wrapper: Option<String>,
+    #[serde(default = "default_content_type")]
+    pub content_type: String,
+}
+
+impl Default for BodyConfig {
+    fn default() -> Self {
+        Self {
+            template: None,
+            separator: default_separator(),
+            wrapper: None,
+            content_type: default_content_type(),
+        }
+    }
+}
+
+fn default_separator() -> String {
+    "\n".to_owned()
+}
+
+fn default_content_type() -> String {
+    "application/json".to_owned()
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SigningAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl Default for SigningAlgorithm {
+    fn default() -> Self {
+        Self::Sha256
+    }
+}
+
+impl SigningAlgorithm {
+    fn digest(self) -> MessageDigest {
+        match self {
+            Self::Sha1 => MessageDigest::sha1(),
+            Self::Sha256 => MessageDigest::sha256(),
+            Self::Sha512 => MessageDigest::sha512(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureEncoding {
+    Hex,
+    Base64,
+}
+
+impl Default for SignatureEncoding {
+    fn default() -> Self {
+        Self::Hex
+    }
+}
+
+/// Signs the body of each request with HMAC, into a header.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SigningConfig {
+    pub secret: String,
+    pub header: String,
+    #[serde(default)]
+    pub algorithm: SigningAlgorithm,
+    #[serde(default)]
+    pub encoding: SignatureEncoding,
+    /// Put before the signature in the header, such as `sha256=`.
+    pub prefix: Option<String>,
+}
+
+impl SigningConfig {
+    fn build(&self) -> crate::Result<RequestSigner> {
+        Ok(RequestSigner {
+            key: PKey::hmac(self.secret.as_bytes()).context(InvalidSecretSnafu)?,
+            header: HeaderName::from_bytes(self.header.as_bytes()).context(
+                InvalidHeaderNameSnafu {
+                    name: self.header.clone(),
+                },
+            )?,
+            digest: self.algorithm.digest(),
+            encoding: self.encoding,
+            prefix: self.prefix.clone().unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Clone)]
+struct RequestSigner {
+    key: PKey<Private>,
+    header: HeaderName,
+    digest: MessageDigest,
+    encoding: SignatureEncoding,
+    prefix: String,
+}
+
+impl RequestSigner {
+    fn signature(&self, body: &[u8]) -> crate::Result<HeaderValue> {
+        let mut signer = Signer::new(self.digest, &self.key)?;
+        signer.update(body)?;
+        let signature = signer.sign_to_vec()?;
+        let signature = match self.encoding {
+            SignatureEncoding::Hex => hex::encode(signature),
+            SignatureEncoding::Base64 => base64::encode(signature),
+        };
+        Ok(HeaderValue::from_str(&format!(
+            "{}{}",
+            self.prefix, signature
+        ))?)
+    }
+}
+
+inventory::submit! {
+    SinkDescription::new::<WebhookSinkConfig>("webhook")
+}
+
+impl GenerateConfig for WebhookSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"uri = "https://hooks.example.com/{{ team }}"
+            body.template = '{"text": "{{ message }}"}'"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "webhook")]
+impl SinkConfig for WebhookSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let batch_settings = self.batch.into_batch_settings()?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new_with_custom_client(
+            tls,
+            cx.proxy(),
+            Client::builder().pool_max_idle_per_host(self.max_idle_connections_per_host),
+        )?;
+
+        let sink = WebhookSink::new(self)?;
+        let sink = PartitionHttpSink::new(
+            sink,
+            PartitionBuffer::new(VecBuffer::new(batch_settings.size)),
+            request_settings,
+            batch_settings.timeout,
+            client,
+            cx.acker(),
+        )
+        .sink_map_err(|error| error!(message = "Fatal webhook sink error.", %error));
+
+        // The URLs events are sent to aren't known until they're rendered.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((super::VectorSink::from_event_sink(sink), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "webhook"
+    }
+}
+
+/// An event rendered into its part of a body.
+#[derive(Clone, Debug)]
+pub struct RenderedEvent(Bytes);
+
+impl EncodedLength for RenderedEvent {
+    fn encoded_length(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl ByteSizeOf for RenderedEvent {
+    fn allocated_bytes(&self) -> usize {
+        self.0.len()
+    }
+}
+
+struct WebhookSink {
+    uri: Template,
+    method: http::Method,
+    auth: Option<Auth>,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    template: Option<Template>,
+    separator: String,
+    /// The parts of the wrapper before and after the events.
+    wrapper: Option<(String, String)>,
+    content_type: HeaderValue,
+    signer: Option<RequestSigner>,
+    encoding: EncodingConfigWithDefault<Encoding>,
+}
+
+impl WebhookSink {
+    fn new(config: &WebhookSinkConfig) -> crate::Result<Self> {
+        let headers = config
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                Ok((
+                    HeaderName::from_bytes(name.as_bytes())
+                        .context(InvalidHeaderNameSnafu { name: name.clone() })?,
+                    HeaderValue::from_str(value)
+                        .context(InvalidHeaderValueSnafu { name: name.clone() })?,
+                ))
+            })
+            .collect::<Result<_, BuildError>>()?;
+
+        let wrapper = match &config.body.wrapper {
+            Some(wrapper) if wrapper.matches(EVENTS_PLACEHOLDER).count() == 1 => {
+                let (before, after) = wrapper
+                    .split_once(EVENTS_PLACEHOLDER)
+                    .expect("placeholder is present");
+                Some((before.to_owned(), after.to_owned()))
+            }
+            Some(_) => return Err(BuildError::InvalidWrapper.into()),
+            None => None,
+        };
+
+        Ok(Self {
+            uri: config.uri.clone(),
+            method: config.method.into(),
+            auth: config.auth.clone(),
+            headers,
+            template: config.body.template.clone(),
+            separator: config.body.separator.clone(),
+            wrapper,
+            content_type: HeaderValue::from_str(&config.body.content_type).context(
+                InvalidHeaderValueSnafu {
+                    name: CONTENT_TYPE.as_str(),
+                },
+            )?,
+            signer: config
+                .signing
+                .as_ref()
+                .map(SigningConfig::build)
+                .transpose()?,
+            encoding: config.encoding.clone(),
+        })
+    }
+
+    fn render(&self, event: &Event) -> Option<Bytes> {
+        match &self.template {
+            Some(template) => template
+                .render(event)
+                .map_err(|error| {
+                    emit!(&TemplateRenderingFailed {
+                        error,
+                        field: Some("body.template"),
+                        drop_event: true,
+                    });
+                })
+                .ok(),
+            None => match event {
+                Event::Log(log) => serde_json::to_vec(log),
+                Event::Metric(metric) => serde_json::to_vec(metric),
+            }
+            .map(Bytes::from)
+            .map_err(|error| error!(message = "Failed to encode event as JSON.", %error))
+            .ok(),
+        }
+    }
+
+    fn body(&self, events: Vec<RenderedEvent>) -> Vec<u8> {
+        let mut body = BytesMut::new();
+        if let Some((before, _)) = &self.wrapper {
+            body.put(before.as_bytes());
+        }
+        for (index, event) in events.into_iter().enumerate() {
+            if index > 0 {
+                body.put(self.separator.as_bytes());
+            }
+            body.put(event.0);
+        }
+        if let Some((_, after)) = &self.wrapper {
+            body.put(after.as_bytes());
+        }
+        body.to_vec()
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpSink for WebhookSink {
+    type Input = PartitionInnerBuffer<RenderedEvent, String>;
+    type Output = PartitionInnerBuffer<Vec<RenderedEvent>, String>;
+
+    fn encode_event(&self, mut event: Event) -> Option<Self::Input> {
+        let uri = self
+            .uri
+            .render_string(&event)
+            .map_err(|error| {
+                emit!(&TemplateRenderingFailed {
+                    error,
+                    field: Some("uri"),
+                    drop_event: true,
+                });
+            })
+            .ok()?;
+
+        self.encoding.apply_rules(&mut event);
+        let rendered = self.render(&event)?;
+        emit!(&HttpEventEncoded {
+            byte_size: rendered.len(),
+        });
+
+        Some(PartitionInnerBuffer::new(RenderedEvent(rendered), uri))
+    }
+
+    async fn build_request(&self, output: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
+        let (events, uri) = output.into_parts();
+        let body = self.body(events);
+
+        let mut builder = Request::builder()
+            .method(self.method.clone())
+            .uri(uri)
+            .header(CONTENT_TYPE, self.content_type.clone());
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(signer) = &self.signer {
+            builder = builder.header(&signer.header, signer.signature(&body)?);
+        }
+
+        let mut request = builder.body(body)?;
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::{stream, StreamExt};
+    use http::request::Parts;
+    use hyper::Method;
+
+    use super::*;
+    use crate::{
+        sinks::util::test::{build_test_server, load_sink},
+        test_util::{components, next_addr},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebhookSinkConfig>();
+    }
+
+    fn sink(config: &str) -> WebhookSink {
+        let (config, _cx) = load_sink::<WebhookSinkConfig>(config).unwrap();
+        WebhookSink::new(&config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn renders_and_wraps_bodies() {
+        let sink = sink(
+            r#"
+            uri = "https://hooks.example.com/{{ team }}"
+            body.template = '{"text": "{{ message }}"}'
+            body.separator = ","
+            body.wrapper = '{"events": [{{ events }}]}'
+            "#,
+        );
+
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("team", "core");
+        let first = sink.encode_event(event).unwrap();
+        // Events the URI can't be rendered for are dropped.
+        let second = sink.encode_event(Event::from("world"));
+        assert!(second.is_none());
+
+        let (rendered, uri) = first.into_parts();
+        assert_eq!(uri, "https://hooks.example.com/core");
+
+        let request = sink
+            .build_request(PartitionInnerBuffer::new(
+                vec![rendered.clone(), rendered],
+                uri,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(request.body().clone()).unwrap(),
+            r#"{"events": [{"text": "hello"},{"text": "hello"}]}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn signs_bodies() {
+        let sink = sink(
+            r#"
+            uri = "https://hooks.example.com/"
+            signing.secret = "It's a Secret to Everybody"
+            signing.header = "X-Hub-Signature-256"
+            signing.prefix = "sha256="
+            "#,
+        );
+
+        let request = sink
+            .build_request(PartitionInnerBuffer::new(
+                vec![RenderedEvent(Bytes::from("Hello, World!"))],
+                "https://hooks.example.com/".to_owned(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(
+            request.headers()["X-Hub-Signature-256"],
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+        );
+    }
+
+    #[test]
+    fn rejects_wrappers_without_events() {
+        let (config, _cx) = load_sink::<WebhookSinkConfig>(
+            r#"
+            uri = "https://hooks.example.com/"
+            body.wrapper = '{"events": []}'
+            "#,
+        )
+        .unwrap();
+        assert!(WebhookSink::new(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn sends_batches_to_each_url() {
+        let in_addr = next_addr();
+        let (config, cx) = load_sink::<WebhookSinkConfig>(&format!(
+            r#"
+            uri = "http://{}/{{{{ team }}}}"
+            body.template = "{{{{ message }}}}"
+            "#,
+            in_addr
+        ))
+        .unwrap();
+        let (sink, _) = config.build(cx).await.unwrap();
+        let (rx, trigger, server) = build_test_server(in_addr);
+        tokio::spawn(server);
+
+        let events = ["a", "b", "c", "d"]
+            .iter()
+            .enumerate()
+            .map(|(index, message)| {
+                let mut event = Event::from(*message);
+                let team = if index % 2 == 0 { "core" } else { "edge" };
+                event.as_mut_log().insert("team", team);
+                event
+            });
+        components::run_sink(sink, stream::iter(events), &components::HTTP_SINK_TAGS).await;
+        drop(trigger);
+
+        let mut requests = rx
+            .map(|(parts, body): (Parts, _)| {
+                assert_eq!(parts.method, Method::POST);
+                (
+                    parts.uri.path().to_owned(),
+                    String::from_utf8(body.to_vec()).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .await;
+        requests.sort();
+        assert_eq!(
+            requests,
+            vec![
+                ("/core".to_owned(), "a\nc".to_owned()),
+                ("/edge".to_owned(), "b\nd".to_owned()),
+            ]
+        );
+    }
+}
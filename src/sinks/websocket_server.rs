@@ -0,0 +1,648 @@
+//! A sink serving events to the WebSocket clients connected to it.
+//!
+//! Clients subscribe by connecting, and can pass a VRL condition as the `filter` query parameter
+//! to only receive the events matching it. Events are encoded once, and queued for every client
+//! they're sent to. Delivery is best effort: events are acknowledged once they're queued, and
+//! events that arrive while no client is connected are dropped.
+//!
+//! Filters are checked against every event, in turn, so the number of clients is capped by
+//! `max_clients`, and `auth` can restrict who gets to connect.
+
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use futures::{future, stream::BoxStream, FutureExt, SinkExt, StreamExt};
+use hyper::{server::accept, service::make_service_fn, Server};
+use serde::{Deserialize, Serialize};
+use stream_cancel::{Trigger, Tripwire};
+use tokio::sync::mpsc::{self, error::TrySendError};
+use vector_core::{
+    buffers::Acker,
+    internal_event::{BytesSent, EventsSent},
+    ByteSizeOf,
+};
+use warp::{
+    http::{header::AUTHORIZATION, HeaderMap, StatusCode},
+    ws::{Message, WebSocket, Ws},
+    Filter, Reply,
+};
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{DataType, GenerateConfig, Resource, SinkConfig, SinkContext, SinkDescription},
+    event::Event,
+    http::Auth,
+    internal_events::{
+        ConnectionOpen, OpenGauge, WebSocketServerClientConnected,
+        WebSocketServerClientDisconnected, WebSocketServerClientRejected,
+        WebSocketServerInvalidFilter, WebSocketServerMessageDropped,
+        WebSocketServerSlowClientDisconnected,
+    },
+    sinks::{
+        util::{
+            encoding::{Encoder, EncodingConfig, StandardEncodings},
+            StreamSink,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketServerSinkConfig {
+    pub address: SocketAddr,
+    pub tls: Option<TlsConfig>,
+    /// The credentials clients have to send in their `Authorization` header to connect.
+    pub auth: Option<Auth>,
+    pub encoding: EncodingConfig<StandardEncodings>,
+    /// How many clients can be connected at once.
+    #[serde(default = "default_max_clients")]
+    pub max_clients: usize,
+    /// How many messages can be queued for a client before it's considered too slow.
+    #[serde(default = "default_max_queued_messages")]
+    pub max_queued_messages: usize,
+    #[serde(default)]
+    pub slow_client_policy: SlowClientPolicy,
+}
+
+/// What to do with clients that don't keep up with the events sent to them.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowClientPolicy {
+    /// Drop the messages that don't fit in the queue of the client.
+    DropMessages,
+    /// Disconnect the client.
+    Disconnect,
+}
+
+impl Default for SlowClientPolicy {
+    fn default() -> Self {
+        Self::DropMessages
+    }
+}
+
+const fn default_max_clients() -> usize {
+    100
+}
+
+const fn default_max_queued_messages() -> usize {
+    1000
+}
+
+inventory::submit! {
+    SinkDescription::new::<WebSocketServerSinkConfig>("websocket_server")
+}
+
+impl GenerateConfig for WebSocketServerSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:8080".parse().unwrap(),
+            tls: None,
+            auth: None,
+            encoding: StandardEncodings::Json.into(),
+            max_clients: default_max_clients(),
+            max_queued_messages: default_max_queued_messages(),
+            slow_client_policy: SlowClientPolicy::DropMessages,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket_server")]
+impl SinkConfig for WebSocketServerSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.max_clients == 0 {
+            return Err("`max_clients` must be greater than 0".into());
+        }
+
+        let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let sink = WebSocketServerSink {
+            address: self.address,
+            tls,
+            authorization: authorization(self.auth.as_ref())?,
+            encoding: self.encoding.clone(),
+            max_clients: self.max_clients,
+            max_queued_messages: self.max_queued_messages.max(1),
+            slow_client_policy: self.slow_client_policy,
+            subscriptions: Default::default(),
+            acker: cx.acker(),
+        };
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "websocket_server"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+}
+
+struct Client {
+    filter: Option<Box<dyn Condition>>,
+    messages: mpsc::Sender<Message>,
+}
+
+/// The clients connected to the sink.
+#[derive(Default)]
+struct Subscriptions {
+    next_id: u64,
+    clients: HashMap<u64, Client>,
+}
+
+impl Subscriptions {
+    fn subscribe(
+        &mut self,
+        filter: Option<Box<dyn Condition>>,
+        max_queued_messages: usize,
+    ) -> (u64, mpsc::Receiver<Message>) {
+        let (messages, receiver) = mpsc::channel(max_queued_messages);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.clients.insert(id, Client { filter, messages });
+        (id, receiver)
+    }
+
+    /// Subscribes a client, unless `max_clients` are already subscribed.
+    fn try_subscribe(
+        &mut self,
+        filter: Option<Box<dyn Condition>>,
+        max_queued_messages: usize,
+        max_clients: usize,
+    ) -> Option<(u64, mpsc::Receiver<Message>)> {
+        if self.clients.len() >= max_clients {
+            // Clients are subscribed before their connections are upgraded, so some may have
+            // failed to upgrade without being removed yet.
+            self.clients
+                .retain(|_, client| !client.messages.is_closed());
+        }
+        (self.clients.len() < max_clients).then(|| self.subscribe(filter, max_queued_messages))
+    }
+
+    /// Queues `message` for every client `event` matches the filter of, and returns how many it
+    /// was queued for.
+    fn publish(
+        &mut self,
+        event: &Event,
+        message: impl FnOnce() -> Option<String>,
+        slow_client_policy: SlowClientPolicy,
+    ) -> usize {
+        let matching = self
+            .clients
+            .iter()
+            .filter(|(_, client)| {
+                client
+                    .filter
+                    .as_ref()
+                    .map_or(true, |filter| filter.check(event))
+            })
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        if matching.is_empty() {
+            return 0;
+        }
+        // The event is only encoded once some client wants it.
+        let message = match message() {
+            Some(message) => message,
+            None => return 0,
+        };
+
+        let mut queued = 0;
+        for id in matching {
+            let sent = self.clients[&id]
+                .messages
+                .try_send(Message::text(message.clone()));
+            match sent {
+                Ok(()) => queued += 1,
+                Err(TrySendError::Full(_)) => match slow_client_policy {
+                    SlowClientPolicy::DropMessages => emit!(&WebSocketServerMessageDropped),
+                    SlowClientPolicy::Disconnect => {
+                        // Dropping the client closes its connection, once the messages already
+                        // queued for it are sent.
+                        self.clients.remove(&id);
+                        emit!(&WebSocketServerSlowClientDisconnected);
+                    }
+                },
+                Err(TrySendError::Closed(_)) => {
+                    self.clients.remove(&id);
+                }
+            }
+        }
+        queued
+    }
+}
+
+struct WebSocketServerSink {
+    address: SocketAddr,
+    tls: MaybeTlsSettings,
+    /// The `Authorization` header clients have to send, if any.
+    authorization: Option<String>,
+    encoding: EncodingConfig<StandardEncodings>,
+    max_clients: usize,
+    max_queued_messages: usize,
+    slow_client_policy: SlowClientPolicy,
+    subscriptions: Arc<Mutex<Subscriptions>>,
+    acker: Acker,
+}
+
+fn authorization(auth: Option<&Auth>) -> crate::Result<Option<String>> {
+    let auth = match auth {
+        Some(auth) => auth,
+        None => return Ok(None),
+    };
+    let mut headers = HeaderMap::new();
+    auth.apply_headers_map(&mut headers);
+    let header = headers
+        .get(AUTHORIZATION)
+        .ok_or("`auth.token` isn't a valid bearer token")?;
+    Ok(Some(header.to_str()?.to_owned()))
+}
+
+fn build_filter(query: &HashMap<String, String>) -> crate::Result<Option<Box<dyn Condition>>> {
+    match query.get("filter").map(|filter| filter.trim()) {
+        None | Some("") => Ok(None),
+        Some(filter) => AnyCondition::String(filter.to_owned())
+            .build(&Default::default())
+            .map(Some),
+    }
+}
+
+async fn serve_client(
+    socket: WebSocket,
+    id: u64,
+    mut messages: mpsc::Receiver<Message>,
+    subscriptions: Arc<Mutex<Subscriptions>>,
+    open_gauge: OpenGauge,
+) {
+    let _open_token = open_gauge.open(|count| emit!(&ConnectionOpen { count }));
+    emit!(&WebSocketServerClientConnected);
+
+    let (mut sender, mut receiver) = socket.split();
+    loop {
+        tokio::select! {
+            message = messages.recv() => match message {
+                Some(message) => {
+                    if sender.send(message).await.is_err() {
+                        break;
+                    }
+                }
+                // The sink stopped, or disconnected the client for being too slow.
+                None => {
+                    let _ = sender.close().await;
+                    break;
+                }
+            },
+            received = receiver.next() => match received {
+                // Messages from clients are ignored, they're only read to tell when clients
+                // disconnect.
+                Some(Ok(message)) if !message.is_close() => {}
+                _ => break,
+            },
+        }
+    }
+
+    subscriptions
+        .lock()
+        .expect("poisoned lock")
+        .clients
+        .remove(&id);
+    emit!(&WebSocketServerClientDisconnected);
+}
+
+impl WebSocketServerSink {
+    async fn start_server(&self) -> Result<Trigger, ()> {
+        let listener = self
+            .tls
+            .bind(&self.address)
+            .await
+            .map_err(|error| error!(message = "Failed to bind WebSocket server.", %error))?;
+
+        let subscriptions = Arc::clone(&self.subscriptions);
+        let authorization = self.authorization.clone();
+        let max_clients = self.max_clients;
+        let max_queued_messages = self.max_queued_messages;
+        let open_gauge = OpenGauge::new();
+        let routes = warp::path::end()
+            .and(warp::ws())
+            .and(warp::header::optional::<String>("authorization"))
+            .and(warp::query::<HashMap<String, String>>())
+            .map(
+                move |ws: Ws, header: Option<String>, query: HashMap<String, String>| {
+                    if authorization.is_some() && header != authorization {
+                        emit!(&WebSocketServerClientRejected {
+                            reason: "unauthorized"
+                        });
+                        return warp::reply::with_status("Unauthorized", StatusCode::UNAUTHORIZED)
+                            .into_response();
+                    }
+                    let filter = match build_filter(&query) {
+                        Ok(filter) => filter,
+                        Err(error) => {
+                            let error = error.to_string();
+                            emit!(&WebSocketServerInvalidFilter { error: &error });
+                            return warp::reply::with_status(error, StatusCode::BAD_REQUEST)
+                                .into_response();
+                        }
+                    };
+                    let subscription = subscriptions.lock().expect("poisoned lock").try_subscribe(
+                        filter,
+                        max_queued_messages,
+                        max_clients,
+                    );
+                    let (id, messages) = match subscription {
+                        Some(subscription) => subscription,
+                        None => {
+                            emit!(&WebSocketServerClientRejected {
+                                reason: "too many clients"
+                            });
+                            return warp::reply::with_status(
+                                "Too many clients",
+                                StatusCode::SERVICE_UNAVAILABLE,
+                            )
+                            .into_response();
+                        }
+                    };
+                    let subscriptions = Arc::clone(&subscriptions);
+                    let open_gauge = open_gauge.clone();
+                    ws.on_upgrade(move |socket| {
+                        serve_client(socket, id, messages, subscriptions, open_gauge)
+                    })
+                    .into_response()
+                },
+            );
+
+        let (trigger, tripwire) = Tripwire::new();
+        let service = warp::service(routes);
+        tokio::spawn(async move {
+            let make_service = make_service_fn(move |_| {
+                let service = service.clone();
+                async move { Ok::<_, Infallible>(service) }
+            });
+            Server::builder(accept::from_stream(listener.accept_stream()))
+                .serve(make_service)
+                .with_graceful_shutdown(tripwire.then(crate::stream::tripwire_handler))
+                .await
+                .map_err(|error| error!(message = "WebSocket server failed.", %error))
+        });
+
+        Ok(trigger)
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for WebSocketServerSink {
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let _server_shutdown_trigger = self.start_server().await?;
+
+        while let Some(event) = input.next().await {
+            let byte_size = event.size_of();
+            let mut message_len = 0;
+            let queued = self.subscriptions.lock().expect("poisoned lock").publish(
+                &event,
+                || {
+                    let message = self.encoding.encode_input_to_string(event.clone()).ok()?;
+                    message_len = message.len();
+                    Some(message)
+                },
+                self.slow_client_policy,
+            );
+            self.acker.ack(1);
+
+            if queued > 0 {
+                emit!(&EventsSent {
+                    count: 1,
+                    byte_size,
+                });
+                emit!(&BytesSent {
+                    byte_size: message_len * queued,
+                    protocol: "websocket",
+                });
+            }
+        }
+
+        // Disconnect every client, since no more events will be sent to them.
+        self.subscriptions
+            .lock()
+            .expect("poisoned lock")
+            .clients
+            .clear();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketServerSinkConfig>();
+    }
+
+    fn filter(filter: &str) -> Option<Box<dyn Condition>> {
+        let query = vec![("filter".to_owned(), filter.to_owned())]
+            .into_iter()
+            .collect();
+        build_filter(&query).unwrap()
+    }
+
+    fn event(service: &str) -> Event {
+        let mut log = LogEvent::from("message");
+        log.insert("service", service);
+        log.into()
+    }
+
+    fn publish(
+        subscriptions: &mut Subscriptions,
+        event: &Event,
+        policy: SlowClientPolicy,
+    ) -> usize {
+        subscriptions.publish(event, || Some("message".to_owned()), policy)
+    }
+
+    #[test]
+    fn builds_filters() {
+        assert!(build_filter(&HashMap::new()).unwrap().is_none());
+        assert!(filter(" ").is_none());
+        assert!(filter(r#".service == "api""#).is_some());
+
+        let query = vec![("filter".to_owned(), ".service ==".to_owned())]
+            .into_iter()
+            .collect();
+        assert!(build_filter(&query).is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_max_clients() {
+        let config: WebSocketServerSinkConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:8080"
+            encoding.codec = "json"
+            max_clients = 0
+            "#,
+        )
+        .unwrap();
+        assert!(config.build(SinkContext::new_test()).await.is_err());
+    }
+
+    #[test]
+    fn builds_authorization() {
+        assert_eq!(authorization(None).unwrap(), None);
+        assert_eq!(
+            authorization(Some(&Auth::Bearer {
+                token: "secret".to_owned()
+            }))
+            .unwrap(),
+            Some("Bearer secret".to_owned())
+        );
+        assert_eq!(
+            authorization(Some(&Auth::Basic {
+                user: "user".to_owned(),
+                password: "password".to_owned()
+            }))
+            .unwrap(),
+            Some("Basic dXNlcjpwYXNzd29yZA==".to_owned())
+        );
+        assert!(authorization(Some(&Auth::Bearer {
+            token: "not\na token".to_owned()
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn limits_clients() {
+        let mut subscriptions = Subscriptions::default();
+        let (_, _first) = subscriptions.try_subscribe(None, 10, 2).unwrap();
+        let (_, second) = subscriptions.try_subscribe(None, 10, 2).unwrap();
+        assert!(subscriptions.try_subscribe(None, 10, 2).is_none());
+
+        // A client that's gone frees up its slot.
+        drop(second);
+        assert!(subscriptions.try_subscribe(None, 10, 2).is_some());
+        assert_eq!(subscriptions.clients.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn publishes_to_matching_clients() {
+        let mut subscriptions = Subscriptions::default();
+        let (_, mut all) = subscriptions.subscribe(None, 10);
+        let (_, mut api) = subscriptions.subscribe(filter(r#".service == "api""#), 10);
+
+        assert_eq!(
+            publish(
+                &mut subscriptions,
+                &event("api"),
+                SlowClientPolicy::DropMessages
+            ),
+            2
+        );
+        assert_eq!(
+            publish(
+                &mut subscriptions,
+                &event("web"),
+                SlowClientPolicy::DropMessages
+            ),
+            1
+        );
+
+        assert!(all.recv().await.is_some());
+        assert!(all.recv().await.is_some());
+        assert!(api.recv().await.is_some());
+        assert!(api.try_recv().is_err());
+    }
+
+    #[test]
+    fn only_encodes_events_clients_want() {
+        let mut subscriptions = Subscriptions::default();
+        let (_api_id, _api) = subscriptions.subscribe(filter(r#".service == "api""#), 10);
+
+        let queued = subscriptions.publish(
+            &event("web"),
+            || panic!("encoded an event no client wants"),
+            SlowClientPolicy::DropMessages,
+        );
+        assert_eq!(queued, 0);
+    }
+
+    #[test]
+    fn drops_messages_for_slow_clients() {
+        let mut subscriptions = Subscriptions::default();
+        let (_, _slow) = subscriptions.subscribe(None, 1);
+
+        assert_eq!(
+            publish(
+                &mut subscriptions,
+                &event("api"),
+                SlowClientPolicy::DropMessages
+            ),
+            1
+        );
+        assert_eq!(
+            publish(
+                &mut subscriptions,
+                &event("api"),
+                SlowClientPolicy::DropMessages
+            ),
+            0
+        );
+        assert_eq!(subscriptions.clients.len(), 1);
+    }
+
+    #[test]
+    fn disconnects_slow_clients() {
+        let mut subscriptions = Subscriptions::default();
+        let (_, _slow) = subscriptions.subscribe(None, 1);
+        let (_, mut fast) = subscriptions.subscribe(None, 10);
+
+        assert_eq!(
+            publish(
+                &mut subscriptions,
+                &event("api"),
+                SlowClientPolicy::Disconnect
+            ),
+            2
+        );
+        assert!(fast.try_recv().is_ok());
+        assert_eq!(
+            publish(
+                &mut subscriptions,
+                &event("api"),
+                SlowClientPolicy::Disconnect
+            ),
+            1
+        );
+        assert_eq!(subscriptions.clients.len(), 1);
+    }
+
+    #[test]
+    fn forgets_closed_clients() {
+        let mut subscriptions = Subscriptions::default();
+        let (_, closed) = subscriptions.subscribe(None, 10);
+        drop(closed);
+
+        assert_eq!(
+            publish(
+                &mut subscriptions,
+                &event("api"),
+                SlowClientPolicy::DropMessages
+            ),
+            0
+        );
+        assert!(subscriptions.clients.is_empty());
+    }
+}
@@ -0,0 +1,524 @@
+//! Pulls log events from CloudWatch Logs groups with the `FilterLogEvents` API, for accounts
+//! where creating subscription filters that deliver to Kinesis Firehose isn't an option.
+//!
+//! `FilterLogEvents` has no notion of following a group, so new events are found by filtering
+//! for those with a timestamp past a per-group cursor, which is persisted so that a restart picks
+//! up where the last run left off. The `StartLiveTail` API would avoid polling, but it isn't
+//! available in the AWS SDK Vector uses.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryInto,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::StreamExt;
+use rusoto_core::Region;
+use rusoto_logs::{
+    CloudWatchLogs, CloudWatchLogsClient, DescribeLogGroupsRequest, FilterLogEventsRequest,
+    FilteredLogEvent,
+};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::time::interval;
+use tokio_stream::wrappers::IntervalStream;
+use vector_core::{event::BatchStatus, ByteSizeOf};
+
+use crate::{
+    aws::{
+        auth::AwsAuthentication,
+        rusoto::{self, RegionOrEndpoint},
+    },
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, SourceConfig,
+        SourceContext, SourceDescription,
+    },
+    event::{BatchNotifier, Event, LogEvent},
+    internal_events::{
+        AwsCloudWatchLogsCursorWriteFailed, AwsCloudWatchLogsEventsReceived,
+        AwsCloudWatchLogsRequestFailed,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+const CURSORS_FILENAME: &str = "cursors.json";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one of `log_group_names` or `log_group_name_prefix` must be set"))]
+    MissingLogGroups,
+    #[snafu(display("`poll_interval_secs` must be greater than 0"))]
+    ZeroPollInterval,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AwsCloudWatchLogsConfig {
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+    #[serde(default)]
+    auth: AwsAuthentication,
+    /// The log groups to read events from.
+    #[serde(default)]
+    log_group_names: Vec<String>,
+    /// Log groups whose name starts with this prefix are read too, including those created after
+    /// Vector starts.
+    log_group_name_prefix: Option<String>,
+    /// Only events matching this CloudWatch Logs filter pattern are read.
+    filter_pattern: Option<String>,
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+    /// How far behind the current time to read up to, giving CloudWatch Logs time to make events
+    /// available before the cursor moves past their timestamp.
+    #[serde(default = "default_ingestion_delay_secs")]
+    ingestion_delay_secs: u64,
+    data_dir: Option<PathBuf>,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    15
+}
+
+const fn default_ingestion_delay_secs() -> u64 {
+    30
+}
+
+inventory::submit! {
+    SourceDescription::new::<AwsCloudWatchLogsConfig>("aws_cloudwatch_logs")
+}
+
+impl GenerateConfig for AwsCloudWatchLogsConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            region: RegionOrEndpoint::with_region("us-east-1".to_owned()),
+            auth: Default::default(),
+            log_group_names: vec!["/aws/lambda/my-function".to_owned()],
+            log_group_name_prefix: None,
+            filter_pattern: None,
+            poll_interval_secs: default_poll_interval_secs(),
+            ingestion_delay_secs: default_ingestion_delay_secs(),
+            data_dir: None,
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_cloudwatch_logs")]
+impl SourceConfig for AwsCloudWatchLogsConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.log_group_names.is_empty() && self.log_group_name_prefix.is_none() {
+            return Err(Box::new(BuildError::MissingLogGroups));
+        }
+        if self.poll_interval_secs == 0 {
+            return Err(Box::new(BuildError::ZeroPollInterval));
+        }
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+
+        let region: Region = (&self.region).try_into()?;
+        let client = rusoto::client(&cx.proxy)?;
+        let creds = self.auth.build(&region, None)?;
+        let client = CloudWatchLogsClient::new_with(client, creds, region);
+        let acknowledgements = cx.globals.acknowledgements.merge(&self.acknowledgements);
+
+        let source = CloudWatchLogsSource {
+            client,
+            log_group_names: self.log_group_names.clone(),
+            log_group_name_prefix: self.log_group_name_prefix.clone(),
+            filter_pattern: self.filter_pattern.clone(),
+            ingestion_delay: chrono::Duration::seconds(self.ingestion_delay_secs as i64),
+            cursors_path: data_dir.join(CURSORS_FILENAME),
+            acknowledgements: acknowledgements.enabled(),
+            out: cx.out,
+        };
+
+        Ok(Box::pin(source.run(
+            Duration::from_secs(self.poll_interval_secs),
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "aws_cloudwatch_logs"
+    }
+}
+
+/// How far through a log group the source has read.
+///
+/// Events can share a timestamp, so the ids of those already read at exactly `timestamp` are
+/// kept too, to skip them when filtering from `timestamp` again. Timestamps are in milliseconds,
+/// as CloudWatch Logs has them.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct Cursor {
+    timestamp: i64,
+    event_ids: Vec<String>,
+}
+
+impl Cursor {
+    const fn new(timestamp: i64) -> Self {
+        Self {
+            timestamp,
+            event_ids: Vec::new(),
+        }
+    }
+
+    fn has_read(&self, timestamp: i64, event_id: &str) -> bool {
+        timestamp < self.timestamp
+            || (timestamp == self.timestamp && self.event_ids.iter().any(|id| id == event_id))
+    }
+
+    fn advance(&mut self, timestamp: i64, event_id: String) {
+        if timestamp > self.timestamp {
+            *self = Self::new(timestamp);
+        }
+        if timestamp == self.timestamp {
+            self.event_ids.push(event_id);
+        }
+    }
+}
+
+/// The cursors of every log group read so far, keyed by group name.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+struct Cursors(BTreeMap<String, Cursor>);
+
+impl Cursors {
+    async fn load(path: &Path) -> std::io::Result<Self> {
+        match tokio::fs::read(path).await {
+            Ok(contents) => Ok(serde_json::from_slice(&contents).unwrap_or_default()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Write the cursors to a temporary file first, so that a crash never leaves a partial one.
+    async fn save(&self, path: &Path) -> std::io::Result<()> {
+        let tmp_path = path.with_extension("json.tmp");
+        let contents = serde_json::to_vec(self).expect("cursors are serializable");
+        tokio::fs::write(&tmp_path, contents).await?;
+        tokio::fs::rename(&tmp_path, path).await
+    }
+}
+
+struct CloudWatchLogsSource {
+    client: CloudWatchLogsClient,
+    log_group_names: Vec<String>,
+    log_group_name_prefix: Option<String>,
+    filter_pattern: Option<String>,
+    ingestion_delay: chrono::Duration,
+    cursors_path: PathBuf,
+    acknowledgements: bool,
+    out: SourceSender,
+}
+
+impl CloudWatchLogsSource {
+    async fn run(mut self, poll_interval: Duration, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let mut cursors = match Cursors::load(&self.cursors_path).await {
+            Ok(cursors) => cursors,
+            Err(error) => {
+                error!(
+                    message = "Unable to read cursors.",
+                    %error,
+                    path = ?self.cursors_path,
+                );
+                return Err(());
+            }
+        };
+
+        let mut ticks = IntervalStream::new(interval(poll_interval)).take_until(shutdown);
+        while ticks.next().await.is_some() {
+            let log_groups = match self.log_groups().await {
+                Ok(log_groups) => log_groups,
+                Err(error) => {
+                    emit!(&AwsCloudWatchLogsRequestFailed { error: &error });
+                    continue;
+                }
+            };
+
+            let until = (Utc::now() - self.ingestion_delay).timestamp_millis();
+            for log_group in log_groups {
+                // Groups seen for the first time are read from now on, rather than from the
+                // start of their retention.
+                let mut cursor = cursors
+                    .0
+                    .get(&log_group)
+                    .cloned()
+                    .unwrap_or_else(|| Cursor::new(until));
+
+                let result = self.poll(&log_group, &mut cursor, until).await;
+                if cursors.0.get(&log_group) != Some(&cursor) {
+                    cursors.0.insert(log_group, cursor);
+                    if let Err(error) = cursors.save(&self.cursors_path).await {
+                        emit!(&AwsCloudWatchLogsCursorWriteFailed {
+                            error,
+                            path: &self.cursors_path,
+                        });
+                    }
+                }
+
+                match result {
+                    Ok(true) => {}
+                    // The pipeline is shutting down.
+                    Ok(false) => return Ok(()),
+                    Err(error) => emit!(&AwsCloudWatchLogsRequestFailed { error: &error }),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The configured log groups, along with those matching the prefix.
+    async fn log_groups(&self) -> crate::Result<BTreeSet<String>> {
+        let mut log_groups = self
+            .log_group_names
+            .iter()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        if let Some(prefix) = &self.log_group_name_prefix {
+            let mut next_token = None;
+            loop {
+                let response = self
+                    .client
+                    .describe_log_groups(DescribeLogGroupsRequest {
+                        log_group_name_prefix: Some(prefix.clone()),
+                        next_token: next_token.take(),
+                        ..Default::default()
+                    })
+                    .await?;
+
+                log_groups.extend(
+                    response
+                        .log_groups
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter_map(|group| group.log_group_name),
+                );
+
+                next_token = response.next_token.filter(|token| !token.is_empty());
+                if next_token.is_none() {
+                    break;
+                }
+            }
+        }
+
+        Ok(log_groups)
+    }
+
+    /// Read every event of the log group between the cursor and `until`, a page at a time,
+    /// moving the cursor along once each page is sent. Returns false if the pipeline has shut
+    /// down.
+    async fn poll(
+        &mut self,
+        log_group: &str,
+        cursor: &mut Cursor,
+        until: i64,
+    ) -> crate::Result<bool> {
+        if until <= cursor.timestamp {
+            return Ok(true);
+        }
+
+        // Events aren't strictly ordered by timestamp across pages, so the events to skip are
+        // always decided against the cursor as it was before the first page.
+        let start = cursor.clone();
+        let mut next_token = None;
+
+        loop {
+            let response = self
+                .client
+                .filter_log_events(FilterLogEventsRequest {
+                    log_group_name: log_group.to_owned(),
+                    filter_pattern: self.filter_pattern.clone(),
+                    start_time: Some(start.timestamp),
+                    // The end time is inclusive.
+                    end_time: Some(until - 1),
+                    next_token: next_token.take(),
+                    ..Default::default()
+                })
+                .await?;
+
+            let mut next = cursor.clone();
+            let events = response
+                .events
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|event| {
+                    let (timestamp, event_id, log) = event_to_log(event, log_group)?;
+                    if start.has_read(timestamp, &event_id) {
+                        return None;
+                    }
+                    next.advance(timestamp, event_id);
+                    Some(log)
+                })
+                .collect::<Vec<_>>();
+
+            if !events.is_empty() {
+                match self.send(events).await {
+                    Some(BatchStatus::Delivered) => {}
+                    // Leave the cursor where it is, so that the events are read again.
+                    Some(_) => return Ok(true),
+                    None => return Ok(false),
+                }
+            }
+
+            next_token = response.next_token.filter(|token| !token.is_empty());
+            if next_token.is_none() {
+                // Every event before `until` has been read.
+                if next.timestamp < until {
+                    next = Cursor::new(until);
+                }
+                *cursor = next;
+                return Ok(true);
+            }
+            *cursor = next;
+        }
+    }
+
+    /// Send the events, waiting for them to be delivered if acknowledgements are enabled.
+    /// Returns `None` if the pipeline has shut down.
+    async fn send(&mut self, events: Vec<LogEvent>) -> Option<BatchStatus> {
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let events = events
+            .into_iter()
+            .map(|log| Event::from(log.with_batch_notifier_option(&batch)))
+            .collect::<Vec<_>>();
+        drop(batch);
+
+        emit!(&AwsCloudWatchLogsEventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        });
+
+        if let Err(error) = self.out.send_all(futures::stream::iter(events)).await {
+            error!(message = "Error sending to sink.", %error);
+            return None;
+        }
+
+        match receiver {
+            Some(receiver) => Some(receiver.await),
+            None => Some(BatchStatus::Delivered),
+        }
+    }
+}
+
+/// Convert a log event into an event. Events without a timestamp or id are skipped, as the
+/// cursor can't account for them.
+fn event_to_log(event: FilteredLogEvent, log_group: &str) -> Option<(i64, String, LogEvent)> {
+    let timestamp = event.timestamp?;
+    let event_id = event.event_id?;
+
+    let schema = log_schema();
+    let mut log = LogEvent::default();
+    log.insert(schema.message_key(), event.message.unwrap_or_default());
+    log.insert(schema.timestamp_key(), Utc.timestamp_millis(timestamp));
+    log.insert("log_group", log_group.to_owned());
+    if let Some(log_stream) = event.log_stream_name {
+        log.insert("log_stream", log_stream);
+    }
+    log.insert("event_id", event_id.clone());
+    log.insert(schema.source_type_key(), Bytes::from("aws_cloudwatch_logs"));
+
+    Some((timestamp, event_id, log))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsCloudWatchLogsConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_poll_interval() {
+        let config: AwsCloudWatchLogsConfig = toml::from_str(
+            r#"
+                region = "us-east-1"
+                log_group_names = ["/aws/lambda/my-function"]
+                poll_interval_secs = 0
+            "#,
+        )
+        .unwrap();
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(config.build(SourceContext::new_test(tx)).await.is_err());
+    }
+
+    #[test]
+    fn cursor_skips_events_already_read() {
+        let start = 1_600_000_000_000;
+        let mut cursor = Cursor::new(start);
+
+        cursor.advance(start, "a".into());
+        cursor.advance(start + 1, "b".into());
+        cursor.advance(start + 1, "c".into());
+
+        assert!(cursor.has_read(start, "a"));
+        assert!(cursor.has_read(start, "z"));
+        assert!(cursor.has_read(start + 1, "c"));
+        assert!(!cursor.has_read(start + 1, "d"));
+        assert!(!cursor.has_read(start + 2, "a"));
+        assert_eq!(cursor.event_ids, vec!["b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn cursors_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CURSORS_FILENAME);
+        assert_eq!(Cursors::load(&path).await.unwrap(), Cursors::default());
+
+        let mut cursors = Cursors::default();
+        let mut cursor = Cursor::new(1_600_000_000_000);
+        cursor.advance(cursor.timestamp, "a".into());
+        cursors.0.insert("/aws/lambda/my-function".into(), cursor);
+        cursors.save(&path).await.unwrap();
+
+        assert_eq!(Cursors::load(&path).await.unwrap(), cursors);
+    }
+
+    #[test]
+    fn converts_events() {
+        let event = FilteredLogEvent {
+            event_id: Some("abc123".into()),
+            ingestion_time: Some(1_600_000_000_500),
+            log_stream_name: Some("2020/09/13/[$LATEST]abc".into()),
+            message: Some("START RequestId: 1234".into()),
+            timestamp: Some(1_600_000_000_123),
+        };
+
+        let (timestamp, event_id, log) = event_to_log(event, "/aws/lambda/my-function").unwrap();
+        assert_eq!(event_id, "abc123");
+        assert_eq!(timestamp, 1_600_000_000_123);
+        assert_eq!(log["message"], "START RequestId: 1234".into());
+        assert_eq!(
+            log["timestamp"],
+            Utc.timestamp_millis(1_600_000_000_123).into()
+        );
+        assert_eq!(log["log_group"], "/aws/lambda/my-function".into());
+        assert_eq!(log["log_stream"], "2020/09/13/[$LATEST]abc".into());
+        assert_eq!(log["event_id"], "abc123".into());
+        assert_eq!(log["source_type"], "aws_cloudwatch_logs".into());
+
+        let event = FilteredLogEvent {
+            message: Some("no id".into()),
+            timestamp: Some(1_600_000_000_123),
+            ..Default::default()
+        };
+        assert!(event_to_log(event, "/aws/lambda/my-function").is_none());
+    }
+}
@@ -0,0 +1,179 @@
+//! Support for reading the log files AWS CloudTrail delivers to S3.
+//!
+//! CloudTrail writes each log file as a single gzipped JSON document holding a `Records` array,
+//! so rather than being split into lines, the whole object is parsed and each record becomes an
+//! event. Every hour CloudTrail also writes a digest file listing the SHA-256 hashes of the log
+//! files it delivered, which can be used to check they haven't been changed since.
+//!
+//! https://docs.aws.amazon.com/awscloudtrail/latest/userguide/cloudtrail-log-file-examples.html
+//! https://docs.aws.amazon.com/awscloudtrail/latest/userguide/cloudtrail-log-file-validation-digest-file-structure.html
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+
+use crate::{config::log_schema, event::LogEvent};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct Config {
+    /// When a digest file is delivered, fetch each log file it lists and check its hash.
+    #[serde(default)]
+    pub(super) validate_digests: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct LogFile {
+    records: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Digest {
+    pub(super) log_files: Vec<DigestLogFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct DigestLogFile {
+    pub(super) s3_bucket: String,
+    pub(super) s3_object: String,
+    /// The hex encoded SHA-256 hash of the uncompressed log file.
+    pub(super) hash_value: String,
+}
+
+/// Digest files are delivered alongside log files, under a `CloudTrail-Digest` prefix.
+pub(super) fn is_digest(key: &str) -> bool {
+    key.split('/').any(|part| part == "CloudTrail-Digest")
+}
+
+/// The account and region a log file was delivered for, from its key, which is of the form
+/// `AWSLogs/[<org id>/]<account id>/CloudTrail/<region>/<yyyy>/<mm>/<dd>/<file name>`.
+pub(super) fn key_account_and_region(key: &str) -> Option<(&str, &str)> {
+    let parts = key.split('/').collect::<Vec<_>>();
+    let index = parts.iter().position(|part| *part == "CloudTrail")?;
+    let account_id = parts.get(index.checked_sub(1)?)?;
+    let region = parts.get(index + 1)?;
+    Some((account_id, region))
+}
+
+/// Expand a log file into an event for each of its records. Records are timestamped with their
+/// `eventTime`, falling back to `default_timestamp`.
+pub(super) fn parse_log_file(
+    contents: &[u8],
+    default_timestamp: DateTime<Utc>,
+) -> Result<Vec<LogEvent>, serde_json::Error> {
+    let log_file: LogFile = serde_json::from_slice(contents)?;
+
+    Ok(log_file
+        .records
+        .into_iter()
+        .filter_map(|record| {
+            let timestamp = record
+                .get("eventTime")
+                .and_then(|time| time.as_str())
+                .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+                .map(|time| time.with_timezone(&Utc))
+                .unwrap_or(default_timestamp);
+
+            // Records are always objects, anything else is skipped.
+            let mut log = LogEvent::try_from(record).ok()?;
+            log.insert(log_schema().timestamp_key(), timestamp);
+            Some(log)
+        })
+        .collect())
+}
+
+pub(super) fn hash_matches(contents: &[u8], hash_value: &str) -> bool {
+    hex::encode(Sha256::digest(contents)).eq_ignore_ascii_case(hash_value)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn finds_digests() {
+        assert!(is_digest(
+            "AWSLogs/111122223333/CloudTrail-Digest/us-east-1/2022/03/01/111122223333_CloudTrail-Digest_us-east-1_trail_us-east-1_20220301T000000Z.json.gz"
+        ));
+        assert!(!is_digest(
+            "AWSLogs/111122223333/CloudTrail/us-east-1/2022/03/01/111122223333_CloudTrail_us-east-1_20220301T0000Z_abc.json.gz"
+        ));
+    }
+
+    #[test]
+    fn parses_key_account_and_region() {
+        assert_eq!(
+            key_account_and_region(
+                "AWSLogs/111122223333/CloudTrail/us-east-1/2022/03/01/111122223333_CloudTrail_us-east-1_20220301T0000Z_abc.json.gz"
+            ),
+            Some(("111122223333", "us-east-1"))
+        );
+        assert_eq!(
+            key_account_and_region(
+                "prefix/AWSLogs/o-abcdef1234/111122223333/CloudTrail/eu-west-1/2022/03/01/file.json.gz"
+            ),
+            Some(("111122223333", "eu-west-1"))
+        );
+        assert_eq!(
+            key_account_and_region("CloudTrail/us-east-1/file.json.gz"),
+            None
+        );
+        assert_eq!(key_account_and_region("some/other/object.json.gz"), None);
+    }
+
+    #[test]
+    fn expands_records() {
+        let default_timestamp = Utc.ymd(2022, 3, 1).and_hms(0, 5, 0);
+        let contents = br#"{"Records": [
+            {"eventVersion": "1.08", "eventTime": "2022-03-01T00:01:02Z", "eventName": "ConsoleLogin", "awsRegion": "us-east-1"},
+            {"eventVersion": "1.08", "eventName": "AssumeRole"}
+        ]}"#;
+
+        let logs = parse_log_file(contents, default_timestamp).unwrap();
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0]["eventName"], "ConsoleLogin".into());
+        assert_eq!(logs[0]["awsRegion"], "us-east-1".into());
+        assert_eq!(
+            logs[0]["timestamp"],
+            Utc.ymd(2022, 3, 1).and_hms(0, 1, 2).into()
+        );
+        assert_eq!(logs[1]["eventName"], "AssumeRole".into());
+        assert_eq!(logs[1]["timestamp"], default_timestamp.into());
+
+        assert!(parse_log_file(b"not json", default_timestamp).is_err());
+    }
+
+    #[test]
+    fn parses_digests() {
+        let digest: Digest = serde_json::from_str(
+            r#"{
+                "awsAccountId": "111122223333",
+                "digestStartTime": "2022-03-01T00:00:00Z",
+                "digestEndTime": "2022-03-01T01:00:00Z",
+                "digestS3Bucket": "trail-bucket",
+                "digestS3Object": "AWSLogs/111122223333/CloudTrail-Digest/us-east-1/2022/03/01/digest.json.gz",
+                "previousDigestSignature": null,
+                "logFiles": [{
+                    "s3Bucket": "trail-bucket",
+                    "s3Object": "AWSLogs/111122223333/CloudTrail/us-east-1/2022/03/01/log.json.gz",
+                    "hashValue": "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08",
+                    "hashAlgorithm": "SHA-256",
+                    "newestEventTime": "2022-03-01T00:59:00Z",
+                    "oldestEventTime": "2022-03-01T00:01:00Z"
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(digest.log_files.len(), 1);
+        let log_file = &digest.log_files[0];
+        assert_eq!(log_file.s3_bucket, "trail-bucket");
+        assert!(hash_matches(b"test", &log_file.hash_value));
+        assert!(!hash_matches(b"tested", &log_file.hash_value));
+    }
+}
@@ -22,6 +22,7 @@ use crate::{
     serde::bool_or_struct,
 };
 
+mod cloudtrail;
 pub mod sqs;
 
 #[derive(Derivative, Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -62,6 +63,9 @@ struct AwsS3Config {
 
     multiline: Option<MultilineConfig>,
 
+    /// Parse objects as AWS CloudTrail log files, rather than splitting them into lines.
+    cloudtrail: Option<cloudtrail::Config>,
+
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
 }
@@ -138,6 +142,7 @@ impl AwsS3Config {
                     sqs.clone(),
                     self.compression,
                     multiline,
+                    self.cloudtrail.clone(),
                 )
                 .await
                 .context(InitializeSnafu {})
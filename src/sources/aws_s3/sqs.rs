@@ -5,25 +5,28 @@ use chrono::{DateTime, TimeZone, Utc};
 use futures::{FutureExt, Stream, StreamExt, TryFutureExt};
 use lazy_static::lazy_static;
 use rusoto_core::{Region, RusotoError};
-use rusoto_s3::{GetObjectError, GetObjectRequest, S3Client, S3};
+use rusoto_s3::{GetObjectError, GetObjectOutput, GetObjectRequest, S3Client, S3};
 use rusoto_sqs::{
     DeleteMessageBatchError, DeleteMessageBatchRequest, DeleteMessageBatchRequestEntry,
     DeleteMessageBatchResult, Message, ReceiveMessageError, ReceiveMessageRequest, Sqs, SqsClient,
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use snafu::{ResultExt, Snafu};
-use tokio::{pin, select};
+use tokio::{io::AsyncReadExt, pin, select};
 use tokio_util::codec::FramedRead;
 use tracing::Instrument;
+use vector_core::ByteSizeOf;
 
+use super::cloudtrail;
 use crate::{
     codecs::{decoding::FramingError, CharacterDelimitedDecoder},
     config::{log_schema, AcknowledgementsConfig, SourceContext},
-    event::{BatchNotifier, BatchStatus, LogEvent},
+    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event, LogEvent},
     internal_events::aws_s3::source::{
         SqsMessageDeleteBatchFailed, SqsMessageDeletePartialFailure, SqsMessageDeleteSucceeded,
         SqsMessageProcessingFailed, SqsMessageProcessingSucceeded, SqsMessageReceiveFailed,
-        SqsMessageReceiveSucceeded, SqsS3EventReceived, SqsS3EventRecordInvalidEventIgnored,
+        SqsMessageReceiveSucceeded, SqsS3CloudTrailDigestMismatch, SqsS3EventReceived,
+        SqsS3EventRecordInvalidEventIgnored,
     },
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
@@ -126,6 +129,28 @@ pub enum ProcessingError {
         bucket: String,
         key: String,
     },
+    #[snafu(display(
+        "Could not parse s3://{}/{} as a CloudTrail log file: {}",
+        bucket,
+        key,
+        source
+    ))]
+    InvalidCloudTrailLogFile {
+        source: serde_json::Error,
+        bucket: String,
+        key: String,
+    },
+    #[snafu(display(
+        "Could not parse s3://{}/{} as a CloudTrail digest file: {}",
+        bucket,
+        key,
+        source
+    ))]
+    InvalidCloudTrailDigest {
+        source: serde_json::Error,
+        bucket: String,
+        key: String,
+    },
     #[snafu(display("Unsupported S3 event version: {}.", version,))]
     UnsupportedS3EventVersion { version: semver::Version },
     #[snafu(display("Sink reported an error sending events"))]
@@ -140,6 +165,7 @@ pub struct State {
 
     multiline: Option<line_agg::Config>,
     compression: super::Compression,
+    cloudtrail: Option<cloudtrail::Config>,
 
     queue_url: String,
     poll_secs: u32,
@@ -160,6 +186,7 @@ impl Ingestor {
         config: Config,
         compression: super::Compression,
         multiline: Option<line_agg::Config>,
+        cloudtrail: Option<cloudtrail::Config>,
     ) -> Result<Ingestor, IngestorNewError> {
         let visibility_timeout_secs: i64 = config.visibility_timeout_secs.into();
 
@@ -171,6 +198,7 @@ impl Ingestor {
 
             compression,
             multiline,
+            cloudtrail,
 
             queue_url: config.queue_url,
             poll_secs: config.poll_secs,
@@ -379,6 +407,21 @@ impl IngestorProcess {
             });
         }
 
+        if let Some(config) = &self.state.cloudtrail {
+            if cloudtrail::is_digest(&s3_event.s3.object.key) {
+                // Digest files aren't log files, so are never sent on as events.
+                return if config.validate_digests {
+                    self.validate_cloudtrail_digest(
+                        &s3_event.s3.bucket.name,
+                        &s3_event.s3.object.key,
+                    )
+                    .await
+                } else {
+                    Ok(())
+                };
+            }
+        }
+
         let object = self
             .state
             .s3_client
@@ -403,6 +446,12 @@ impl IngestorProcess {
             })
             .unwrap_or_else(Utc::now);
 
+        if self.state.cloudtrail.is_some() {
+            return self
+                .handle_cloudtrail_log_file(&s3_event, object, timestamp)
+                .await;
+        }
+
         match object.body {
             Some(body) => {
                 let (batch, receiver) =
@@ -500,27 +549,149 @@ impl IngestorProcess {
                         key: s3_event.s3.object.key.clone(),
                     })
                 } else {
-                    match receiver {
-                        None => Ok(()),
-                        Some(receiver) => match receiver.await {
-                            BatchStatus::Delivered => Ok(()),
-                            BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
-                            BatchStatus::Rejected => {
-                                error!(
-                                    message = "Sink reported events were rejected.",
-                                    internal_log_rate_secs = 5
-                                );
-                                // Failed events cannot be retried, so continue to delete the SQS source message.
-                                Ok(())
-                            }
-                        },
-                    }
+                    handle_batch_status(receiver).await
                 }
             }
             None => Ok(()),
         }
     }
 
+    async fn handle_cloudtrail_log_file(
+        &mut self,
+        s3_event: &S3EventRecord,
+        object: GetObjectOutput,
+        timestamp: DateTime<Utc>,
+    ) -> Result<(), ProcessingError> {
+        let bucket = &s3_event.s3.bucket.name;
+        let key = &s3_event.s3.object.key;
+
+        let contents = self.read_object(bucket, key, object).await?;
+        let logs = cloudtrail::parse_log_file(&contents, timestamp).context(
+            InvalidCloudTrailLogFileSnafu {
+                bucket: bucket.clone(),
+                key: key.clone(),
+            },
+        )?;
+
+        // Trails covering several regions or accounts deliver them all to the same bucket, so
+        // the region of the bucket isn't necessarily the region of the log file.
+        let (account_id, region) = match cloudtrail::key_account_and_region(key) {
+            Some((account_id, region)) => (Some(account_id), region),
+            None => (None, s3_event.aws_region.as_str()),
+        };
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let events = logs
+            .into_iter()
+            .map(|log| {
+                emit!(&SqsS3EventReceived {
+                    byte_size: log.size_of()
+                });
+
+                let mut log = log.with_batch_notifier_option(&batch);
+                log.insert_flat("bucket", bucket.clone());
+                log.insert_flat("object", key.clone());
+                log.insert_flat("region", region.to_owned());
+                if let Some(account_id) = account_id {
+                    log.insert_flat("account_id", account_id.to_owned());
+                }
+                log.insert_flat(log_schema().source_type_key(), Bytes::from("aws_s3"));
+                Event::from(log)
+            })
+            .collect::<Vec<_>>();
+        drop(batch);
+
+        self.out
+            .send_all(futures::stream::iter(events))
+            .await
+            .context(PipelineSendSnafu {
+                bucket: bucket.clone(),
+                key: key.clone(),
+            })?;
+
+        handle_batch_status(receiver).await
+    }
+
+    /// Check the hash of each log file listed in a CloudTrail digest file. Mismatches are
+    /// reported, but don't fail the digest, as the log files they refer to have already been
+    /// read.
+    async fn validate_cloudtrail_digest(
+        &mut self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<(), ProcessingError> {
+        let object = self.get_object(bucket, key).await?;
+        let contents = self.read_object(bucket, key, object).await?;
+        let digest: cloudtrail::Digest =
+            serde_json::from_slice(&contents).context(InvalidCloudTrailDigestSnafu {
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+            })?;
+
+        for log_file in digest.log_files {
+            let object = self
+                .get_object(&log_file.s3_bucket, &log_file.s3_object)
+                .await?;
+            let contents = self
+                .read_object(&log_file.s3_bucket, &log_file.s3_object, object)
+                .await?;
+
+            if !cloudtrail::hash_matches(&contents, &log_file.hash_value) {
+                emit!(&SqsS3CloudTrailDigestMismatch {
+                    digest_key: key,
+                    bucket: &log_file.s3_bucket,
+                    key: &log_file.s3_object,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn get_object(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<GetObjectOutput, ProcessingError> {
+        self.state
+            .s3_client
+            .get_object(GetObjectRequest {
+                bucket: bucket.to_owned(),
+                key: key.to_owned(),
+                ..Default::default()
+            })
+            .await
+            .context(GetObjectSnafu { bucket, key })
+    }
+
+    /// Read the whole of an object into memory, decompressing it.
+    async fn read_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        object: GetObjectOutput,
+    ) -> Result<Vec<u8>, ProcessingError> {
+        let mut contents = Vec::new();
+        if let Some(body) = object.body {
+            let mut reader = super::s3_object_decoder(
+                self.state.compression,
+                key,
+                object.content_encoding.as_deref(),
+                object.content_type.as_deref(),
+                body,
+            )
+            .await;
+            reader.read_to_end(&mut contents).await.map_err(|error| {
+                ProcessingError::ReadObject {
+                    source: Box::new(error),
+                    bucket: bucket.to_owned(),
+                    key: key.to_owned(),
+                }
+            })?;
+        }
+        Ok(contents)
+    }
+
     async fn receive_messages(&mut self) -> Result<Vec<Message>, RusotoError<ReceiveMessageError>> {
         self.state
             .sqs_client
@@ -549,6 +720,24 @@ impl IngestorProcess {
     }
 }
 
+async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), ProcessingError> {
+    match receiver {
+        None => Ok(()),
+        Some(receiver) => match receiver.await {
+            BatchStatus::Delivered => Ok(()),
+            BatchStatus::Errored => Err(ProcessingError::ErrorAcknowledgement),
+            BatchStatus::Rejected => {
+                error!(
+                    message = "Sink reported events were rejected.",
+                    internal_log_rate_secs = 5
+                );
+                // Failed events cannot be retried, so continue to delete the SQS source message.
+                Ok(())
+            }
+        },
+    }
+}
+
 // https://docs.aws.amazon.com/AmazonS3/latest/dev/notification-content-structure.html
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "PascalCase")]
@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use azure_core::{prelude::*, HttpError};
+use azure_storage::{blob::prelude::*, core::prelude::*};
+use bytes::Bytes;
+use http::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use super::ownership::Ownership;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointStoreConfig {
+    pub connection_string: String,
+    pub container_name: String,
+}
+
+/// The position in a partition up to which events have been processed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Checkpoint {
+    pub offset: String,
+    pub sequence_number: i64,
+}
+
+/// Keeps checkpoints and partition ownership as blobs in an Azure Storage container.
+///
+/// The layout and metadata match the blob checkpoint stores of the Azure SDKs, so that a
+/// consumer group can be moved between them and Vector without starting over.
+pub struct BlobCheckpointStore {
+    client: Arc<ContainerClient>,
+    prefix: String,
+}
+
+impl BlobCheckpointStore {
+    pub fn new(
+        config: &CheckpointStoreConfig,
+        namespace: &str,
+        event_hub: &str,
+        consumer_group: &str,
+    ) -> crate::Result<Self> {
+        let client = StorageAccountClient::new_connection_string(
+            new_http_client(),
+            config.connection_string.as_str(),
+        )?
+        .as_storage_client()
+        .as_container_client(config.container_name.as_str());
+
+        Ok(Self {
+            client,
+            prefix: format!("{}/{}/{}", namespace, event_hub, consumer_group).to_lowercase(),
+        })
+    }
+
+    pub async fn list_ownership(&self) -> crate::Result<Vec<Ownership>> {
+        let prefix = format!("{}/ownership/", self.prefix);
+
+        // An event hub has at most a few thousand partitions, so they fit in a single page.
+        let response = self
+            .client
+            .list_blobs()
+            .prefix(prefix.as_str())
+            .include_metadata(true)
+            .execute()
+            .await?;
+
+        Ok(response
+            .blobs
+            .blobs
+            .into_iter()
+            .map(|blob| {
+                let metadata = blob.metadata.unwrap_or_default();
+                Ownership {
+                    partition_id: blob.name.trim_start_matches(prefix.as_str()).to_owned(),
+                    owner_id: metadata.get("ownerid").cloned().unwrap_or_default(),
+                    last_modified: blob.properties.last_modified.timestamp(),
+                    etag: Some(blob.properties.etag),
+                }
+            })
+            .collect())
+    }
+
+    /// Claim a partition, or renew a claim on it, as long as nobody else has updated its
+    /// ownership since it was listed. Returns the new ownership if the claim succeeded.
+    pub async fn claim(
+        &self,
+        partition_id: &str,
+        owner_id: &str,
+        etag: Option<&str>,
+    ) -> crate::Result<Option<Ownership>> {
+        let mut metadata = Metadata::new();
+        metadata.insert("ownerid", owner_id);
+
+        let blob = format!("{}/ownership/{}", self.prefix, partition_id);
+        let condition = match etag {
+            Some(etag) => IfMatchCondition::Match(etag.to_owned()),
+            // Only create the blob if it doesn't exist yet.
+            None => IfMatchCondition::NotMatch("*".to_owned()),
+        };
+
+        let result = self
+            .client
+            .as_blob_client(blob.as_str())
+            .put_block_blob(Bytes::new())
+            .metadata(&metadata)
+            .if_match_condition(condition)
+            .execute()
+            .await;
+
+        match result {
+            Ok(response) => Ok(Some(Ownership {
+                partition_id: partition_id.to_owned(),
+                owner_id: owner_id.to_owned(),
+                last_modified: response.last_modified.timestamp(),
+                etag: Some(response.etag),
+            })),
+            Err(error) => match error.downcast_ref::<HttpError>() {
+                // Someone else got there first.
+                Some(HttpError::UnexpectedStatusCode { received, .. })
+                    if *received == StatusCode::PRECONDITION_FAILED
+                        || *received == StatusCode::CONFLICT =>
+                {
+                    Ok(None)
+                }
+                _ => Err(error),
+            },
+        }
+    }
+
+    pub async fn checkpoint(&self, partition_id: &str) -> crate::Result<Option<Checkpoint>> {
+        let blob = format!("{}/checkpoint/{}", self.prefix, partition_id);
+
+        let response = self
+            .client
+            .list_blobs()
+            .prefix(blob.as_str())
+            .include_metadata(true)
+            .execute()
+            .await?;
+
+        Ok(response
+            .blobs
+            .blobs
+            .into_iter()
+            .filter(|found| found.name == blob)
+            .find_map(|found| {
+                let metadata = found.metadata?;
+                Some(Checkpoint {
+                    offset: metadata.get("offset")?.clone(),
+                    sequence_number: metadata.get("sequencenumber")?.parse().ok()?,
+                })
+            }))
+    }
+
+    pub async fn update_checkpoint(
+        &self,
+        partition_id: &str,
+        checkpoint: &Checkpoint,
+    ) -> crate::Result<()> {
+        let mut metadata = Metadata::new();
+        metadata.insert("offset", checkpoint.offset.as_str());
+        metadata.insert(
+            "sequencenumber",
+            checkpoint.sequence_number.to_string().as_str(),
+        );
+
+        let blob = format!("{}/checkpoint/{}", self.prefix, partition_id);
+        self.client
+            .as_blob_client(blob.as_str())
+            .put_block_blob(Bytes::new())
+            .metadata(&metadata)
+            .execute()
+            .await?;
+
+        Ok(())
+    }
+}
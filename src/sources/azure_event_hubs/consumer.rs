@@ -0,0 +1,389 @@
+use std::{collections::BTreeMap, io::Cursor, time::Duration};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use fe2o3_amqp::{
+    types::{
+        messaging::{Body, Message, MessageId, Source},
+        primitives::{Symbol, Value as AmqpValue},
+    },
+    Delivery, Receiver, Session,
+};
+use futures::{future::Shared, StreamExt};
+use serde_amqp::{described::Described, descriptor::Descriptor};
+use tokio_util::codec::FramedRead;
+use vector_core::ByteSizeOf;
+
+use super::{Checkpoint, PendingCheckpoints, StartPosition};
+use crate::{
+    azure_event_hubs::{body_bytes, simple_to_value, to_value, ulong_to_value, ConnectionString},
+    codecs::Decoder,
+    config::log_schema,
+    event::{BatchNotifier, Event, LogEvent, Value},
+    internal_events::{AzureEventHubsEventsReceived, AzureEventHubsReceiveFailed},
+    shutdown::ShutdownSignal,
+    sources::util::{finalizer::OrderedFinalizer, StreamDecodingError},
+    SourceSender,
+};
+
+const SELECTOR_FILTER: &str = "apache.org:selector-filter:string";
+const OFFSET_ANNOTATION: &str = "x-opt-offset";
+const SEQUENCE_NUMBER_ANNOTATION: &str = "x-opt-sequence-number";
+const ENQUEUED_TIME_ANNOTATION: &str = "x-opt-enqueued-time";
+const PARTITION_KEY_ANNOTATION: &str = "x-opt-partition-key";
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Where to start reading a partition from.
+#[derive(Clone, Debug, PartialEq)]
+enum Position {
+    /// Just after the event at this offset.
+    After(String),
+    Start(StartPosition),
+}
+
+impl Position {
+    /// The filter on the receiving link that has Event Hubs skip to this position.
+    fn filter_expression(&self) -> String {
+        let offset = match self {
+            Self::After(offset) => offset.as_str(),
+            Self::Start(StartPosition::Earliest) => "-1",
+            Self::Start(StartPosition::Latest) => "@latest",
+        };
+        format!("amqp.annotation.{} > '{}'", OFFSET_ANNOTATION, offset)
+    }
+}
+
+/// The system properties Event Hubs attaches to each event.
+#[derive(Debug, Default)]
+struct SystemProperties {
+    offset: Option<String>,
+    sequence_number: Option<i64>,
+    enqueued_time: Option<DateTime<Utc>>,
+    partition_key: Option<String>,
+    /// Any other annotations and message properties.
+    other: BTreeMap<String, Value>,
+}
+
+impl SystemProperties {
+    fn from_message(message: &Message<Body<AmqpValue>>) -> Self {
+        let mut properties = Self::default();
+
+        let annotations = message
+            .message_annotations
+            .iter()
+            .flat_map(|annotations| annotations.0.iter());
+        for (key, value) in annotations {
+            let key = match key {
+                fe2o3_amqp::types::messaging::annotations::OwnedKey::Symbol(key) => key.as_str(),
+                _ => continue,
+            };
+
+            match (key, to_value(value)) {
+                (OFFSET_ANNOTATION, Value::Bytes(offset)) => {
+                    properties.offset = Some(String::from_utf8_lossy(&offset).into_owned())
+                }
+                (SEQUENCE_NUMBER_ANNOTATION, Value::Integer(sequence_number)) => {
+                    properties.sequence_number = Some(sequence_number)
+                }
+                (ENQUEUED_TIME_ANNOTATION, Value::Timestamp(enqueued_time)) => {
+                    properties.enqueued_time = Some(enqueued_time)
+                }
+                (PARTITION_KEY_ANNOTATION, Value::Bytes(partition_key)) => {
+                    properties.partition_key =
+                        Some(String::from_utf8_lossy(&partition_key).into_owned())
+                }
+                (key, value) => {
+                    properties.other.insert(key.to_owned(), value);
+                }
+            }
+        }
+
+        if let Some(message_properties) = &message.properties {
+            let mut insert = |key: &str, value: Option<Value>| {
+                if let Some(value) = value {
+                    properties.other.insert(key.to_owned(), value);
+                }
+            };
+            insert(
+                "message_id",
+                message_properties.message_id.as_ref().map(message_id_value),
+            );
+            insert(
+                "correlation_id",
+                message_properties
+                    .correlation_id
+                    .as_ref()
+                    .map(message_id_value),
+            );
+            insert(
+                "content_type",
+                message_properties
+                    .content_type
+                    .as_ref()
+                    .map(|content_type| Value::from(content_type.as_str())),
+            );
+            insert(
+                "content_encoding",
+                message_properties
+                    .content_encoding
+                    .as_ref()
+                    .map(|content_encoding| Value::from(content_encoding.as_str())),
+            );
+            insert(
+                "subject",
+                message_properties.subject.as_deref().map(Value::from),
+            );
+            insert("to", message_properties.to.as_deref().map(Value::from));
+            insert(
+                "reply_to",
+                message_properties.reply_to.as_deref().map(Value::from),
+            );
+        }
+
+        properties
+    }
+}
+
+fn message_id_value(id: &MessageId) -> Value {
+    match id {
+        MessageId::String(id) => Value::from(id.as_str()),
+        MessageId::Ulong(id) => ulong_to_value(*id),
+        MessageId::Uuid(id) => Value::from(uuid::Uuid::from_bytes(*id.as_inner()).to_string()),
+        MessageId::Binary(id) => Value::from(Bytes::copy_from_slice(id)),
+    }
+}
+
+/// Consumes a single partition, for as long as this instance owns it.
+pub(super) struct PartitionConsumer {
+    pub connection_string: ConnectionString,
+    pub owner_id: String,
+    pub event_hub: String,
+    pub consumer_group: String,
+    pub partition_id: String,
+    pub decoder: Decoder,
+    pub acknowledgements: bool,
+    pub out: SourceSender,
+    pub pending: PendingCheckpoints,
+}
+
+impl PartitionConsumer {
+    pub async fn run(
+        mut self,
+        checkpoint: Option<Checkpoint>,
+        start_position: StartPosition,
+        shutdown: Shared<ShutdownSignal>,
+    ) {
+        let mut position = match checkpoint {
+            Some(checkpoint) => Position::After(checkpoint.offset),
+            None => Position::Start(start_position),
+        };
+
+        let finalizer = self.acknowledgements.then(|| {
+            let pending = self.pending.clone();
+            let partition_id = self.partition_id.clone();
+            OrderedFinalizer::new(shutdown.clone(), move |checkpoint: Checkpoint| {
+                pending
+                    .lock()
+                    .expect("mutex is not poisoned")
+                    .insert(partition_id.clone(), checkpoint);
+            })
+        });
+
+        loop {
+            let result = tokio::select! {
+                _ = shutdown.clone() => break,
+                result = self.consume(&mut position, finalizer.as_ref()) => result,
+            };
+
+            match result {
+                Ok(()) => break,
+                Err(error) => {
+                    emit!(&AzureEventHubsReceiveFailed {
+                        error: &error,
+                        partition_id: &self.partition_id,
+                    });
+
+                    tokio::select! {
+                        _ = shutdown.clone() => break,
+                        _ = tokio::time::sleep(RECONNECT_DELAY) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// Receive events until the connection fails, keeping `position` just past the last event
+    /// received so that a new connection picks up where this one left off.
+    async fn consume(
+        &mut self,
+        position: &mut Position,
+        finalizer: Option<&OrderedFinalizer<Checkpoint>>,
+    ) -> crate::Result<()> {
+        let mut connection = self.connection_string.connect(&self.owner_id).await?;
+        let mut session = Session::begin(&mut connection).await?;
+
+        let address = format!(
+            "{}/ConsumerGroups/{}/Partitions/{}",
+            self.event_hub, self.consumer_group, self.partition_id
+        );
+        let filter = Described {
+            descriptor: Descriptor::Name(Symbol::from(SELECTOR_FILTER)),
+            value: AmqpValue::String(position.filter_expression()),
+        };
+        let mut receiver = Receiver::builder()
+            .name(format!("vector-{}-{}", self.owner_id, self.partition_id))
+            .source(
+                Source::builder()
+                    .address(address)
+                    .add_to_filter(Symbol::from(SELECTOR_FILTER), Some(filter))
+                    .build(),
+            )
+            .attach(&mut session)
+            .await?;
+
+        loop {
+            let delivery: Delivery<AmqpValue> = receiver.recv().await?;
+            receiver.accept(&delivery).await?;
+
+            let properties = SystemProperties::from_message(delivery.message());
+            let payload = match body_bytes(delivery.body()) {
+                Some(payload) => payload,
+                None => continue,
+            };
+
+            if let Some(offset) = &properties.offset {
+                *position = Position::After(offset.clone());
+            }
+            let checkpoint = properties
+                .offset
+                .clone()
+                .zip(properties.sequence_number)
+                .map(|(offset, sequence_number)| Checkpoint {
+                    offset,
+                    sequence_number,
+                });
+
+            let events = self.decode(payload, delivery.message(), &properties).await;
+            emit!(&AzureEventHubsEventsReceived {
+                count: events.len(),
+                byte_size: events.size_of(),
+                partition_id: &self.partition_id,
+            });
+
+            let sent = match (finalizer, checkpoint) {
+                (Some(finalizer), Some(checkpoint)) => {
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    let events = events
+                        .into_iter()
+                        .map(|event| event.with_batch_notifier(&batch));
+                    let sent = self.out.send_all(futures::stream::iter(events)).await;
+                    finalizer.add(checkpoint, receiver);
+                    sent
+                }
+                (_, checkpoint) => {
+                    let sent = self.out.send_all(futures::stream::iter(events)).await;
+                    if let (Ok(()), Some(checkpoint)) = (&sent, checkpoint) {
+                        self.pending
+                            .lock()
+                            .expect("mutex is not poisoned")
+                            .insert(self.partition_id.clone(), checkpoint);
+                    }
+                    sent
+                }
+            };
+
+            // The pipeline is shutting down, so there is nowhere left to send events.
+            if let Err(error) = sent {
+                error!(message = "Error sending to sink.", %error);
+                return Ok(());
+            }
+        }
+    }
+
+    async fn decode(
+        &self,
+        payload: Bytes,
+        message: &Message<Body<AmqpValue>>,
+        properties: &SystemProperties,
+    ) -> Vec<Event> {
+        let application_properties = message
+            .application_properties
+            .iter()
+            .flat_map(|properties| properties.0.iter())
+            .map(|(key, value)| (key.clone(), simple_to_value(value)))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut events = Vec::new();
+        let mut stream = FramedRead::new(Cursor::new(payload), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((decoded, _)) => {
+                    for mut event in decoded {
+                        if let Event::Log(ref mut log) = event {
+                            self.enrich(log, properties, &application_properties);
+                        }
+                        events.push(event);
+                    }
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs::Decoder`, no further handling
+                    // is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        events
+    }
+
+    fn enrich(
+        &self,
+        log: &mut LogEvent,
+        properties: &SystemProperties,
+        application_properties: &BTreeMap<String, Value>,
+    ) {
+        let schema = log_schema();
+
+        log.insert(schema.source_type_key(), Bytes::from("azure_event_hubs"));
+        log.insert(
+            schema.timestamp_key(),
+            properties.enqueued_time.unwrap_or_else(Utc::now),
+        );
+        log.insert("partition_id", self.partition_id.as_str());
+        if let Some(offset) = &properties.offset {
+            log.insert("offset", offset.as_str());
+        }
+        if let Some(sequence_number) = properties.sequence_number {
+            log.insert("sequence_number", sequence_number);
+        }
+        if let Some(partition_key) = &properties.partition_key {
+            log.insert("partition_key", partition_key.as_str());
+        }
+        log.insert("properties", Value::from(application_properties.clone()));
+        log.insert("system_properties", Value::from(properties.other.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters_from_position() {
+        assert_eq!(
+            Position::After("1024".into()).filter_expression(),
+            "amqp.annotation.x-opt-offset > '1024'"
+        );
+        assert_eq!(
+            Position::Start(StartPosition::Earliest).filter_expression(),
+            "amqp.annotation.x-opt-offset > '-1'"
+        );
+        assert_eq!(
+            Position::Start(StartPosition::Latest).filter_expression(),
+            "amqp.annotation.x-opt-offset > '@latest'"
+        );
+    }
+}
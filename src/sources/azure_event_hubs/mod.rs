@@ -0,0 +1,423 @@
+//! The `azure_event_hubs` source consumes an event hub directly over AMQP 1.0.
+//!
+//! Partitions are shared between every Vector instance reading with the same consumer group, by
+//! keeping ownership and checkpoints in an Azure Storage container, as the Azure SDKs do.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::Utc;
+use futures::{future::Shared, FutureExt};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::task::JoinHandle;
+
+use crate::{
+    azure_event_hubs::ConnectionString,
+    codecs::{
+        decoding::{DecodingConfig, DeserializerConfig, FramingConfig},
+        Decoder,
+    },
+    config::{
+        AcknowledgementsConfig, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    internal_events::{
+        AzureEventHubsCheckpointFailed, AzureEventHubsLoadBalancingFailed,
+        AzureEventHubsPartitionClaimed, AzureEventHubsPartitionLost,
+    },
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+mod checkpoint;
+mod consumer;
+mod ownership;
+
+use checkpoint::{BlobCheckpointStore, Checkpoint, CheckpointStoreConfig};
+use consumer::PartitionConsumer;
+use ownership::{next_claim, Ownership};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`{}` must be greater than 0", option))]
+    ZeroInterval { option: &'static str },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StartPosition {
+    Earliest,
+    Latest,
+}
+
+impl Default for StartPosition {
+    fn default() -> Self {
+        Self::Latest
+    }
+}
+
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct AzureEventHubsSourceConfig {
+    connection_string: String,
+    event_hub: Option<String>,
+    #[serde(default = "default_consumer_group")]
+    #[derivative(Default(value = "default_consumer_group()"))]
+    consumer_group: String,
+    #[serde(default)]
+    start_position: StartPosition,
+    #[derivative(Default(value = "default_checkpoint_store()"))]
+    checkpoint_store: CheckpointStoreConfig,
+    #[serde(default = "default_checkpoint_interval_secs")]
+    #[derivative(Default(value = "default_checkpoint_interval_secs()"))]
+    checkpoint_interval_secs: u64,
+    #[serde(default = "default_load_balancing_interval_secs")]
+    #[derivative(Default(value = "default_load_balancing_interval_secs()"))]
+    load_balancing_interval_secs: u64,
+    #[serde(default = "default_ownership_expiration_secs")]
+    #[derivative(Default(value = "default_ownership_expiration_secs()"))]
+    ownership_expiration_secs: u64,
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: Box<dyn FramingConfig>,
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: Box<dyn DeserializerConfig>,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_consumer_group() -> String {
+    "$Default".into()
+}
+
+fn default_checkpoint_store() -> CheckpointStoreConfig {
+    CheckpointStoreConfig {
+        connection_string: "DefaultEndpointsProtocol=https;AccountName=some-account-name;AccountKey=some-account-key;".into(),
+        container_name: "checkpoints".into(),
+    }
+}
+
+const fn default_checkpoint_interval_secs() -> u64 {
+    10
+}
+
+const fn default_load_balancing_interval_secs() -> u64 {
+    10
+}
+
+// Matches the Azure SDKs, so that a mix of consumers agrees on when an owner has gone away.
+const fn default_ownership_expiration_secs() -> u64 {
+    60
+}
+
+inventory::submit! {
+    SourceDescription::new::<AzureEventHubsSourceConfig>("azure_event_hubs")
+}
+
+impl GenerateConfig for AzureEventHubsSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection_string: "Endpoint=sb://some-namespace.servicebus.windows.net/;SharedAccessKeyName=some-key-name;SharedAccessKey=some-key;EntityPath=some-event-hub".into(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "azure_event_hubs")]
+impl SourceConfig for AzureEventHubsSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        for (option, interval) in [
+            ("checkpoint_interval_secs", self.checkpoint_interval_secs),
+            ("load_balancing_interval_secs", self.load_balancing_interval_secs),
+        ] {
+            if interval == 0 {
+                return Err(Box::new(BuildError::ZeroInterval { option }));
+            }
+        }
+
+        let connection_string = self.connection_string.parse::<ConnectionString>()?;
+        let event_hub = connection_string.event_hub(self.event_hub.as_deref())?;
+        let store = BlobCheckpointStore::new(
+            &self.checkpoint_store,
+            &connection_string.namespace,
+            &event_hub,
+            &self.consumer_group,
+        )?;
+        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
+        let acknowledgements = cx.globals.acknowledgements.merge(&self.acknowledgements);
+
+        let source = EventHubsSource {
+            owner_id: uuid::Uuid::new_v4().to_string(),
+            connection_string,
+            event_hub,
+            consumer_group: self.consumer_group.clone(),
+            start_position: self.start_position,
+            store,
+            checkpoint_interval: Duration::from_secs(self.checkpoint_interval_secs),
+            load_balancing_interval: Duration::from_secs(self.load_balancing_interval_secs),
+            ownership_expiration_secs: self.ownership_expiration_secs as i64,
+            decoder,
+            acknowledgements: acknowledgements.enabled(),
+            out: cx.out,
+        };
+
+        Ok(Box::pin(source.run(cx.shutdown)))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "azure_event_hubs"
+    }
+}
+
+/// A partition this instance owns, and the task consuming it.
+struct OwnedPartition {
+    ownership: Ownership,
+    task: JoinHandle<()>,
+}
+
+struct EventHubsSource {
+    owner_id: String,
+    connection_string: ConnectionString,
+    event_hub: String,
+    consumer_group: String,
+    start_position: StartPosition,
+    store: BlobCheckpointStore,
+    checkpoint_interval: Duration,
+    load_balancing_interval: Duration,
+    ownership_expiration_secs: i64,
+    decoder: Decoder,
+    acknowledgements: bool,
+    out: SourceSender,
+}
+
+/// The checkpoints reached by each partition's consumer that have yet to be stored.
+type PendingCheckpoints = Arc<Mutex<HashMap<String, Checkpoint>>>;
+
+impl EventHubsSource {
+    async fn run(self, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let shutdown = shutdown.shared();
+        let pending = PendingCheckpoints::default();
+        let mut partition_ids = Vec::new();
+        let mut owned = HashMap::new();
+
+        let mut balance = tokio::time::interval(self.load_balancing_interval);
+        let mut checkpoint = tokio::time::interval(self.checkpoint_interval);
+
+        loop {
+            tokio::select! {
+                _ = shutdown.clone() => break,
+                _ = balance.tick() => {
+                    if let Err(error) = self
+                        .balance(&mut partition_ids, &mut owned, &pending, &shutdown)
+                        .await
+                    {
+                        emit!(&AzureEventHubsLoadBalancingFailed { error: &error });
+                    }
+                }
+                _ = checkpoint.tick() => self.store_checkpoints(&pending).await,
+            }
+        }
+
+        // The consumers stop on shutdown too, once their last events have been acknowledged.
+        for (_, partition) in owned.drain() {
+            let OwnedPartition { ownership, task } = partition;
+            let _ = task.await;
+            self.store_checkpoints(&pending).await;
+
+            // Give up the partition, so that another consumer can take it over straight away.
+            if let Err(error) = self
+                .store
+                .claim(&ownership.partition_id, "", ownership.etag.as_deref())
+                .await
+            {
+                emit!(&AzureEventHubsLoadBalancingFailed { error: &error });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Renew the claims on the partitions this instance owns, stop consuming any that have been
+    /// taken over, and claim another partition if this instance owns fewer than its share.
+    async fn balance(
+        &self,
+        partition_ids: &mut Vec<String>,
+        owned: &mut HashMap<String, OwnedPartition>,
+        pending: &PendingCheckpoints,
+        shutdown: &Shared<ShutdownSignal>,
+    ) -> crate::Result<()> {
+        if partition_ids.is_empty() {
+            let mut connection = self.connection_string.connect(&self.owner_id).await?;
+            *partition_ids =
+                crate::azure_event_hubs::partition_ids(&mut connection, &self.event_hub).await?;
+            connection.close().await?;
+        }
+
+        let mut ownerships = self.store.list_ownership().await?;
+
+        for ownership in ownerships.iter_mut() {
+            let partition = match owned.get_mut(&ownership.partition_id) {
+                Some(partition) => partition,
+                None => continue,
+            };
+
+            let renewed = if ownership.owner_id == self.owner_id
+                && ownership.etag == partition.ownership.etag
+            {
+                self.store
+                    .claim(
+                        &ownership.partition_id,
+                        &self.owner_id,
+                        ownership.etag.as_deref(),
+                    )
+                    .await?
+            } else {
+                None
+            };
+
+            match renewed {
+                Some(renewed) => {
+                    *ownership = renewed.clone();
+                    partition.ownership = renewed;
+                }
+                None => {
+                    emit!(&AzureEventHubsPartitionLost {
+                        partition_id: &ownership.partition_id
+                    });
+                    partition.task.abort();
+                    owned.remove(&ownership.partition_id);
+                }
+            }
+        }
+
+        // Claims that have disappeared altogether can't be renewed either.
+        owned.retain(|partition_id, partition| {
+            let listed = ownerships
+                .iter()
+                .any(|ownership| &ownership.partition_id == partition_id);
+            if !listed {
+                emit!(&AzureEventHubsPartitionLost { partition_id });
+                partition.task.abort();
+            }
+            listed
+        });
+
+        let now = Utc::now().timestamp();
+        let claim = next_claim(
+            partition_ids,
+            &ownerships,
+            &self.owner_id,
+            now,
+            self.ownership_expiration_secs,
+        );
+        if let Some((partition_id, previous)) = claim {
+            let etag = previous.and_then(|ownership| ownership.etag.as_deref());
+            if let Some(ownership) = self.store.claim(partition_id, &self.owner_id, etag).await? {
+                emit!(&AzureEventHubsPartitionClaimed { partition_id });
+
+                let checkpoint = self.store.checkpoint(partition_id).await?;
+                let consumer = PartitionConsumer {
+                    connection_string: self.connection_string.clone(),
+                    owner_id: self.owner_id.clone(),
+                    event_hub: self.event_hub.clone(),
+                    consumer_group: self.consumer_group.clone(),
+                    partition_id: partition_id.to_owned(),
+                    decoder: self.decoder.clone(),
+                    acknowledgements: self.acknowledgements,
+                    out: self.out.clone(),
+                    pending: Arc::clone(pending),
+                };
+                let task =
+                    tokio::spawn(consumer.run(checkpoint, self.start_position, shutdown.clone()));
+
+                owned.insert(partition_id.to_owned(), OwnedPartition { ownership, task });
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn store_checkpoints(&self, pending: &PendingCheckpoints) {
+        let checkpoints = std::mem::take(&mut *pending.lock().expect("mutex is not poisoned"));
+
+        for (partition_id, checkpoint) in checkpoints {
+            if let Err(error) = self
+                .store
+                .update_checkpoint(&partition_id, &checkpoint)
+                .await
+            {
+                emit!(&AzureEventHubsCheckpointFailed {
+                    error: &error,
+                    partition_id: &partition_id,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AzureEventHubsSourceConfig>();
+    }
+
+    #[tokio::test]
+    async fn requires_event_hub() {
+        let config: AzureEventHubsSourceConfig = toml::from_str(
+            r#"
+            connection_string = "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=key;SharedAccessKey=c2VjcmV0"
+            checkpoint_store.connection_string = "DefaultEndpointsProtocol=https;AccountName=vector;AccountKey=c2VjcmV0;"
+            checkpoint_store.container_name = "checkpoints"
+            "#,
+        )
+        .unwrap();
+        let (out, _) = SourceSender::new_test();
+
+        let error = config
+            .build(SourceContext::new_test(out))
+            .await
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("EntityPath"));
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_intervals() {
+        for option in ["checkpoint_interval_secs", "load_balancing_interval_secs"] {
+            let config: AzureEventHubsSourceConfig = toml::from_str(&format!(
+                r#"
+                connection_string = "Endpoint=sb://vector.servicebus.windows.net/;SharedAccessKeyName=key;SharedAccessKey=c2VjcmV0;EntityPath=logs"
+                checkpoint_store.connection_string = "DefaultEndpointsProtocol=https;AccountName=vector;AccountKey=c2VjcmV0;"
+                checkpoint_store.container_name = "checkpoints"
+                {} = 0
+                "#,
+                option
+            ))
+            .unwrap();
+            let (out, _) = SourceSender::new_test();
+
+            let error = config
+                .build(SourceContext::new_test(out))
+                .await
+                .err()
+                .unwrap();
+            assert_eq!(error.to_string(), format!("`{}` must be greater than 0", option));
+        }
+    }
+}
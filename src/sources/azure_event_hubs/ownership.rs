@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use rand::{seq::SliceRandom, thread_rng};
+
+/// A consumer's claim on a partition, which lapses unless it is renewed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ownership {
+    pub partition_id: String,
+    /// Empty when the partition has been given up.
+    pub owner_id: String,
+    /// When the claim was last made or renewed, in seconds since the epoch.
+    pub last_modified: i64,
+    pub etag: Option<String>,
+}
+
+/// Pick the partition that `owner_id` should claim next, if it owns fewer than its share of them.
+///
+/// Only one partition is claimed at a time, so that consumers starting together converge on an
+/// even split instead of all grabbing every partition. Unowned partitions, or those whose claims
+/// have expired, are preferred. Otherwise a partition is taken from a consumer owning more than
+/// its share. Returns the partition along with the ownership being replaced, if there is one.
+pub fn next_claim<'a>(
+    partition_ids: &'a [String],
+    ownerships: &'a [Ownership],
+    owner_id: &str,
+    now: i64,
+    expiration_secs: i64,
+) -> Option<(&'a str, Option<&'a Ownership>)> {
+    let current = ownerships
+        .iter()
+        .filter(|ownership| partition_ids.contains(&ownership.partition_id))
+        .map(|ownership| (ownership.partition_id.as_str(), ownership))
+        .collect::<HashMap<_, _>>();
+    let is_active = |ownership: &Ownership| {
+        !ownership.owner_id.is_empty() && now - ownership.last_modified < expiration_secs
+    };
+
+    let mut owned: HashMap<&str, Vec<&Ownership>> = HashMap::new();
+    owned.insert(owner_id, Vec::new());
+    for ownership in current.values().filter(|ownership| is_active(ownership)) {
+        owned
+            .entry(ownership.owner_id.as_str())
+            .or_default()
+            .push(ownership);
+    }
+
+    // Every consumer should own `min` partitions, and the first `extra` of them one more.
+    let min = partition_ids.len() / owned.len();
+    let extra = partition_ids.len() % owned.len();
+    let mine = owned[owner_id].len();
+    let over_min = owned
+        .iter()
+        .filter(|(owner, partitions)| **owner != owner_id && partitions.len() > min)
+        .count();
+    if mine > min || (mine == min && over_min >= extra) {
+        return None;
+    }
+
+    let mut rng = thread_rng();
+
+    let available = partition_ids
+        .iter()
+        .filter(|id| !current.get(id.as_str()).map_or(false, |o| is_active(o)))
+        .collect::<Vec<_>>();
+    if let Some(id) = available.choose(&mut rng) {
+        return Some((id.as_str(), current.get(id.as_str()).copied()));
+    }
+
+    // Take from a consumer with more than its share. When this consumer is short of even the
+    // minimum, owning exactly one extra partition counts as more than a share too.
+    let steal_above = if mine < min { min } else { min + 1 };
+    let victims = owned
+        .iter()
+        .filter(|(owner, partitions)| **owner != owner_id && partitions.len() > steal_above)
+        .collect::<Vec<_>>();
+    let most = victims
+        .iter()
+        .map(|(_, partitions)| partitions.len())
+        .max()?;
+    let partitions = victims
+        .into_iter()
+        .filter(|(_, partitions)| partitions.len() == most)
+        .flat_map(|(_, partitions)| partitions.iter())
+        .collect::<Vec<_>>();
+
+    partitions
+        .choose(&mut rng)
+        .map(|ownership| (ownership.partition_id.as_str(), Some(**ownership)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_600_000_000;
+    const EXPIRATION: i64 = 60;
+
+    fn partitions(count: usize) -> Vec<String> {
+        (0..count).map(|id| id.to_string()).collect()
+    }
+
+    fn owned(owner: &str, partition_ids: &[usize], last_modified: i64) -> Vec<Ownership> {
+        partition_ids
+            .iter()
+            .map(|id| Ownership {
+                partition_id: id.to_string(),
+                owner_id: owner.into(),
+                last_modified,
+                etag: Some(format!("etag-{}", id)),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn claims_unowned_partitions() {
+        let partitions = partitions(4);
+        let ownerships = owned("other", &[0, 1], NOW);
+
+        let (id, previous) = next_claim(&partitions, &ownerships, "me", NOW, EXPIRATION).unwrap();
+        assert!(id == "2" || id == "3");
+        assert_eq!(previous, None);
+    }
+
+    #[test]
+    fn claims_expired_partitions() {
+        let partitions = partitions(2);
+        let mut ownerships = owned("other", &[0], NOW);
+        ownerships.extend(owned("gone", &[1], NOW - EXPIRATION));
+
+        let (id, previous) = next_claim(&partitions, &ownerships, "me", NOW, EXPIRATION).unwrap();
+        assert_eq!(id, "1");
+        assert_eq!(previous.unwrap().etag.as_deref(), Some("etag-1"));
+    }
+
+    #[test]
+    fn stops_at_fair_share() {
+        let partitions = partitions(5);
+        let mut ownerships = owned("me", &[0, 1], NOW);
+        ownerships.extend(owned("other", &[2, 3, 4], NOW));
+
+        assert_eq!(
+            next_claim(&partitions, &ownerships, "me", NOW, EXPIRATION),
+            None
+        );
+    }
+
+    #[test]
+    fn steals_from_overloaded_owners() {
+        let partitions = partitions(4);
+        let mut ownerships = owned("me", &[0], NOW);
+        ownerships.extend(owned("other", &[1, 2, 3], NOW));
+
+        let (id, previous) = next_claim(&partitions, &ownerships, "me", NOW, EXPIRATION).unwrap();
+        assert!(["1", "2", "3"].contains(&id));
+        assert_eq!(previous.unwrap().owner_id, "other");
+    }
+
+    #[test]
+    fn new_owner_takes_a_share() {
+        let partitions = partitions(4);
+        let ownerships = owned("other", &[0, 1, 2, 3], NOW);
+
+        assert!(next_claim(&partitions, &ownerships, "me", NOW, EXPIRATION).is_some());
+    }
+
+    #[test]
+    fn ignores_released_and_unknown_partitions() {
+        let partitions = partitions(1);
+        let mut ownerships = owned("", &[0], NOW);
+        ownerships.extend(owned("other", &[7], NOW));
+
+        let (id, _) = next_claim(&partitions, &ownerships, "me", NOW, EXPIRATION).unwrap();
+        assert_eq!(id, "0");
+    }
+}
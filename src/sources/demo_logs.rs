@@ -1,12 +1,15 @@
-use std::task::Poll;
+use std::{
+    path::{Path, PathBuf},
+    task::Poll,
+};
 
 use bytes::Bytes;
-use chrono::Utc;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use fakedata::logs::*;
 use futures::StreamExt;
-use rand::seq::SliceRandom;
+use rand::{seq::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
-use snafu::Snafu;
+use snafu::{ResultExt, Snafu};
 use tokio::time::{self, Duration};
 use tokio_util::codec::FramedRead;
 
@@ -52,6 +55,66 @@ const fn default_count() -> usize {
 pub enum DemoLogsConfigError {
     #[snafu(display("A non-empty list of lines is required for the shuffle format"))]
     ShuffleDemoLogsItemsEmpty,
+    #[snafu(display("The cardinality of a profile must be at least 1"))]
+    ProfileCardinalityZero,
+    #[snafu(display(
+        "The error rate of a profile must be between 0 and 1, got {}",
+        error_rate
+    ))]
+    ProfileErrorRateOutOfRange { error_rate: f64 },
+    #[snafu(display("The replay speed must be greater than 0, got {}", speed))]
+    ReplaySpeedNotPositive { speed: f64 },
+}
+
+#[derive(Debug, Snafu)]
+pub enum ReplayError {
+    #[snafu(display("Could not read replay file {:?}: {}", path, source))]
+    ReadFile {
+        source: std::io::Error,
+        path: PathBuf,
+    },
+    #[snafu(display("Replay file {:?} has no lines", path))]
+    NoLines { path: PathBuf },
+    #[snafu(display(
+        "Could not parse the timestamp of line {} of the replay file: {}",
+        line,
+        source
+    ))]
+    ParseTimestamp {
+        source: chrono::ParseError,
+        line: usize,
+    },
+}
+
+const fn default_speed() -> f64 {
+    1.0
+}
+
+/// Tunes the lines generated by the scenario profiles.
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize)]
+#[derivative(Default)]
+#[serde(default)]
+pub struct ProfileOptions {
+    /// How many distinct clients, hosts, users and paths appear in the lines.
+    #[derivative(Default(value = "100"))]
+    cardinality: usize,
+    /// The share of lines that describe a failure.
+    #[derivative(Default(value = "0.05"))]
+    error_rate: f64,
+}
+
+impl ProfileOptions {
+    fn validate(&self) -> Result<(), DemoLogsConfigError> {
+        if self.cardinality == 0 {
+            Err(DemoLogsConfigError::ProfileCardinalityZero)
+        } else if !(0.0..=1.0).contains(&self.error_rate) {
+            Err(DemoLogsConfigError::ProfileErrorRateOutOfRange {
+                error_rate: self.error_rate,
+            })
+        } else {
+            Ok(())
+        }
+    }
 }
 
 #[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
@@ -71,6 +134,19 @@ pub enum OutputFormat {
     BsdSyslog,
     #[derivative(Default)]
     Json,
+    /// Lines read from a file, in order, looping back to the start once all of them were sent.
+    Replay {
+        path: PathBuf,
+        /// How many times faster than the original timing to replay lines.
+        #[serde(default = "default_speed")]
+        speed: f64,
+        /// The format of the timestamp each line starts with, which is removed from the line and
+        /// used to reproduce the time between lines. Without it, lines are sent every `interval`.
+        timestamp_format: Option<String>,
+    },
+    NginxAccess(ProfileOptions),
+    JsonApp(ProfileOptions),
+    SyslogAuth(ProfileOptions),
 }
 
 impl OutputFormat {
@@ -87,6 +163,10 @@ impl OutputFormat {
             Self::Syslog => syslog_5424_log_line(),
             Self::BsdSyslog => syslog_3164_log_line(),
             Self::Json => json_log_line(),
+            Self::NginxAccess(options) => nginx_access_line(options),
+            Self::JsonApp(options) => json_app_line(options),
+            Self::SyslogAuth(options) => syslog_auth_line(options),
+            Self::Replay { .. } => unreachable!("replayed lines are read when the source is built"),
         }
     }
 
@@ -111,11 +191,242 @@ impl OutputFormat {
                     Ok(())
                 }
             }
+            Self::Replay { speed, .. } if *speed <= 0.0 => {
+                Err(DemoLogsConfigError::ReplaySpeedNotPositive { speed: *speed })
+            }
+            Self::NginxAccess(options) | Self::JsonApp(options) | Self::SyslogAuth(options) => {
+                options.validate()
+            }
             _ => Ok(()),
         }
     }
 }
 
+/// The lines sent by the source: either generated from a format, or replayed from a file.
+#[derive(Debug)]
+enum Lines {
+    Generated(OutputFormat),
+    Replayed(Replay),
+}
+
+impl Lines {
+    async fn new(format: &OutputFormat) -> Result<Self, ReplayError> {
+        match format {
+            OutputFormat::Replay {
+                path,
+                speed,
+                timestamp_format,
+            } => Replay::load(path, *speed, timestamp_format.as_deref())
+                .await
+                .map(Self::Replayed),
+            format => Ok(Self::Generated(format.clone())),
+        }
+    }
+
+    /// How long to wait before sending line `n`, when it isn't set by `interval`.
+    fn delay(&self, n: usize) -> Option<Duration> {
+        match self {
+            Self::Generated(_) => None,
+            Self::Replayed(replay) => replay
+                .delays
+                .as_ref()
+                .map(|delays| delays[n % delays.len()]),
+        }
+    }
+
+    fn line(&self, n: usize) -> String {
+        match self {
+            Self::Generated(format) => format.generate_line(n),
+            Self::Replayed(replay) => {
+                emit!(&DemoLogsEventProcessed);
+                replay.lines[n % replay.lines.len()].clone()
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Replay {
+    lines: Vec<String>,
+    /// The time between each line and the one before it, scaled by the replay speed. The first
+    /// line is sent straight away, including when looping back to it.
+    delays: Option<Vec<Duration>>,
+}
+
+impl Replay {
+    async fn load(
+        path: &Path,
+        speed: f64,
+        timestamp_format: Option<&str>,
+    ) -> Result<Self, ReplayError> {
+        let contents = tokio::fs::read_to_string(path)
+            .await
+            .context(ReadFileSnafu { path })?;
+        let lines = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>();
+        if lines.is_empty() {
+            return Err(ReplayError::NoLines { path: path.into() });
+        }
+
+        let timestamp_format = match timestamp_format {
+            Some(timestamp_format) => timestamp_format,
+            None => {
+                return Ok(Self {
+                    lines: lines.into_iter().map(Into::into).collect(),
+                    delays: None,
+                })
+            }
+        };
+
+        let words = timestamp_format.split_whitespace().count();
+        let mut texts = Vec::with_capacity(lines.len());
+        let mut delays = Vec::with_capacity(lines.len());
+        let mut previous = None;
+        for (index, line) in lines.into_iter().enumerate() {
+            let (timestamp, rest) = split_words(line, words);
+            let timestamp = parse_timestamp(timestamp, timestamp_format)
+                .context(ParseTimestampSnafu { line: index + 1 })?;
+
+            // Lines that are out of order are sent straight away rather than going back in time.
+            let delay = previous
+                .and_then(|previous: DateTime<Utc>| (timestamp - previous).to_std().ok())
+                .map_or(Duration::ZERO, |delay| delay.div_f64(speed));
+            previous = Some(timestamp);
+
+            texts.push(rest.into());
+            delays.push(delay);
+        }
+        Ok(Self {
+            lines: texts,
+            delays: Some(delays),
+        })
+    }
+}
+
+/// Splits the first `words` whitespace separated words off the start of `line`.
+fn split_words(line: &str, words: usize) -> (&str, &str) {
+    let line = line.trim_start();
+    let mut end = 0;
+    for _ in 0..words {
+        let rest = &line[end..];
+        let start = end + rest.len() - rest.trim_start().len();
+        end = line[start..]
+            .find(char::is_whitespace)
+            .map_or(line.len(), |len| start + len);
+    }
+    (&line[..end], line[end..].trim_start())
+}
+
+/// Parses a timestamp, which is taken to be in UTC when the format has no time zone.
+fn parse_timestamp(timestamp: &str, format: &str) -> Result<DateTime<Utc>, chrono::ParseError> {
+    DateTime::parse_from_str(timestamp, format)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(timestamp, format)
+                .map(|timestamp| DateTime::from_utc(timestamp, Utc))
+        })
+}
+
+const HTTP_METHODS: [&str; 4] = ["GET", "GET", "POST", "PUT"];
+const USER_AGENTS: [&str; 4] = [
+    "curl/7.79.1",
+    "Mozilla/5.0 (X11; Linux x86_64; rv:95.0) Gecko/20100101 Firefox/95.0",
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 Safari/605.1.15",
+    "python-requests/2.26.0",
+];
+
+/// An address that is the same for the same `index`, so that their number follows cardinality.
+fn client_address(index: usize) -> String {
+    format!(
+        "10.{}.{}.{}",
+        (index >> 16) & 0xff,
+        (index >> 8) & 0xff,
+        index & 0xff
+    )
+}
+
+fn nginx_access_line(options: &ProfileOptions) -> String {
+    let mut rng = rand::thread_rng();
+    let status = if rng.gen_bool(options.error_rate) {
+        [500, 502, 503, 504].choose(&mut rng)
+    } else {
+        [200, 200, 200, 201, 204, 301, 304].choose(&mut rng)
+    };
+
+    format!(
+        "{} - - [{}] \"{} /api/v1/items/{} HTTP/1.1\" {} {} \"-\" \"{}\"",
+        client_address(rng.gen_range(0..options.cardinality)),
+        Utc::now().format("%d/%b/%Y:%H:%M:%S %z"),
+        HTTP_METHODS.choose(&mut rng).unwrap(),
+        rng.gen_range(0..options.cardinality),
+        status.unwrap(),
+        rng.gen_range(0..50_000),
+        USER_AGENTS.choose(&mut rng).unwrap(),
+    )
+}
+
+fn json_app_line(options: &ProfileOptions) -> String {
+    let mut rng = rand::thread_rng();
+    let (level, message, status, duration_ms) = if rng.gen_bool(options.error_rate) {
+        (
+            "error",
+            "Request failed: upstream timed out",
+            500,
+            rng.gen_range(1000..30_000),
+        )
+    } else {
+        ("info", "Request completed", 200, rng.gen_range(1..500))
+    };
+
+    serde_json::json!({
+        "timestamp": Utc::now().to_rfc3339(),
+        "level": level,
+        "service": "checkout",
+        "message": message,
+        "user_id": format!("user{}", rng.gen_range(0..options.cardinality)),
+        "request_id": format!("{:016x}", rng.gen::<u64>()),
+        "status": status,
+        "duration_ms": duration_ms,
+    })
+    .to_string()
+}
+
+fn syslog_auth_line(options: &ProfileOptions) -> String {
+    let mut rng = rand::thread_rng();
+    let user = format!("user{}", rng.gen_range(0..options.cardinality));
+    let address = client_address(rng.gen_range(0..options.cardinality));
+    let port = rng.gen_range(1024..65536);
+    // The `auth` facility, at `warning` severity for failures and `info` otherwise.
+    let (priority, message) = if rng.gen_bool(options.error_rate) {
+        (
+            36,
+            format!(
+                "Failed password for invalid user {} from {} port {} ssh2",
+                user, address, port
+            ),
+        )
+    } else {
+        (
+            38,
+            format!(
+                "Accepted publickey for {} from {} port {} ssh2",
+                user, address, port
+            ),
+        )
+    };
+
+    format!(
+        "<{}>{} host{} sshd[{}]: {}",
+        priority,
+        Utc::now().format("%b %e %H:%M:%S"),
+        rng.gen_range(0..options.cardinality),
+        rng.gen_range(1000..32768),
+        message
+    )
+}
+
 impl DemoLogsConfig {
     #[allow(dead_code)] // to make check-component-features pass
     pub fn repeat(lines: Vec<String>, count: usize, interval: f64) -> Self {
@@ -135,7 +446,7 @@ impl DemoLogsConfig {
 async fn demo_logs_source(
     interval: f64,
     count: usize,
-    format: OutputFormat,
+    lines: Lines,
     decoder: codecs::Decoder,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
@@ -153,11 +464,15 @@ async fn demo_logs_source(
             break;
         }
 
-        if let Some(interval) = &mut interval {
-            interval.tick().await;
+        match (lines.delay(n), &mut interval) {
+            (Some(delay), _) => time::sleep(delay).await,
+            (None, Some(interval)) => {
+                interval.tick().await;
+            }
+            (None, None) => {}
         }
 
-        let line = format.generate_line(n);
+        let line = lines.line(n);
 
         let mut stream = FramedRead::new(line.as_bytes(), decoder.clone());
         while let Some(next) = stream.next().await {
@@ -207,11 +522,12 @@ impl_generate_config_from_default!(DemoLogsConfig);
 impl SourceConfig for DemoLogsConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         self.format.validate()?;
+        let lines = Lines::new(&self.format).await?;
         let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
         Ok(Box::pin(demo_logs_source(
             self.interval,
             self.count,
-            self.format.clone(),
+            lines,
             decoder,
             cx.shutdown,
             cx.out,
@@ -273,7 +589,7 @@ mod tests {
         demo_logs_source(
             config.interval,
             config.count,
-            config.format,
+            Lines::new(&config.format).await.unwrap(),
             decoder,
             ShutdownSignal::noop(),
             tx,
@@ -461,4 +777,180 @@ mod tests {
         }
         assert_eq!(poll!(rx.next()), Poll::Ready(None));
     }
+
+    #[test]
+    fn config_profile_options_validated() {
+        let config: DemoLogsConfig = toml::from_str(
+            r#"format = "nginx_access"
+               error_rate = 1.5"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.format.validate(),
+            Err(DemoLogsConfigError::ProfileErrorRateOutOfRange { error_rate: 1.5 })
+        );
+
+        let config: DemoLogsConfig = toml::from_str(
+            r#"format = "syslog_auth"
+               cardinality = 0"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.format.validate(),
+            Err(DemoLogsConfigError::ProfileCardinalityZero)
+        );
+    }
+
+    #[tokio::test]
+    async fn nginx_access_profile_follows_error_rate() {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(
+            r#"format = "nginx_access"
+               error_rate = 1.0
+               cardinality = 1
+               count = 5"#,
+        )
+        .await;
+
+        for _ in 0..5 {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let message = event.as_log()[&message_key].to_string_lossy();
+            assert!(message.starts_with("10.0.0.0 - - ["));
+            assert!(message.contains("/api/v1/items/0 HTTP/1.1\" 50"));
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[tokio::test]
+    async fn json_app_profile_generates_output() {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(
+            r#"format = "json_app"
+               error_rate = 0.0
+               count = 5"#,
+        )
+        .await;
+
+        for _ in 0..5 {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let message = event.as_log()[&message_key].to_string_lossy();
+            let line: serde_json::Value = serde_json::from_str(&message).unwrap();
+            assert_eq!(line["level"], "info");
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[tokio::test]
+    async fn syslog_auth_profile_generates_output() {
+        let message_key = log_schema().message_key();
+        let mut rx = runit(
+            r#"format = "syslog_auth"
+               error_rate = 1.0
+               count = 5"#,
+        )
+        .await;
+
+        for _ in 0..5 {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            let message = event.as_log()[&message_key].to_string_lossy();
+            assert!(message.starts_with("<36>"));
+            assert!(message.contains("Failed password for invalid user"));
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[test]
+    fn split_words_separates_timestamp() {
+        assert_eq!(
+            split_words("  2022-01-01 10:00:00   hello  world", 2),
+            ("2022-01-01 10:00:00", "hello  world")
+        );
+        assert_eq!(split_words("lonely", 2), ("lonely", ""));
+    }
+
+    #[tokio::test]
+    async fn replay_scales_original_timing() {
+        let path = crate::test_util::temp_file();
+        std::fs::write(
+            &path,
+            "2022-01-01T00:00:00Z one\n2022-01-01T00:00:02Z two\n\n2022-01-01T00:00:04Z three\n",
+        )
+        .unwrap();
+
+        let message_key = log_schema().message_key();
+        let start = Instant::now();
+        let mut rx = runit(&format!(
+            r#"format = "replay"
+               path = "{}"
+               speed = 2.0
+               timestamp_format = "%+"
+               interval = 0.0
+               count = 4"#,
+            path.display()
+        ))
+        .await;
+        let duration = start.elapsed();
+
+        for expected in ["one", "two", "three", "one"] {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            assert_eq!(event.as_log()[&message_key].to_string_lossy(), expected);
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+
+        // Four seconds of original timing at twice the speed, with no wait when looping.
+        assert!(duration >= Duration::from_secs(2));
+        assert!(duration < Duration::from_secs(3));
+    }
+
+    #[tokio::test]
+    async fn replay_without_timestamps_uses_interval() {
+        let path = crate::test_util::temp_file();
+        std::fs::write(&path, "one\ntwo\n").unwrap();
+
+        let message_key = log_schema().message_key();
+        let mut rx = runit(&format!(
+            r#"format = "replay"
+               path = "{}"
+               interval = 0.0
+               count = 3"#,
+            path.display()
+        ))
+        .await;
+
+        for expected in ["one", "two", "one"] {
+            let event = match poll!(rx.next()) {
+                Poll::Ready(event) => event.unwrap(),
+                _ => unreachable!(),
+            };
+            assert_eq!(event.as_log()[&message_key].to_string_lossy(), expected);
+        }
+        assert_eq!(poll!(rx.next()), Poll::Ready(None));
+    }
+
+    #[tokio::test]
+    async fn replay_rejects_unparseable_timestamps() {
+        let path = crate::test_util::temp_file();
+        std::fs::write(&path, "2022-01-01T00:00:00Z one\nyesterday two\n").unwrap();
+
+        let error = Lines::new(&OutputFormat::Replay {
+            path,
+            speed: 1.0,
+            timestamp_format: Some("%+".into()),
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(error, ReplayError::ParseTimestamp { line: 2, .. }));
+    }
 }
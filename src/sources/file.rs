@@ -468,6 +468,7 @@ fn create_event(
 
     // Add source type
     event.insert(log_schema().source_type_key(), Bytes::from("file"));
+    event.metadata_mut().set_source_field("path", file.as_str());
 
     if let Some(file_key) = &file_key {
         event.insert(file_key.clone(), file);
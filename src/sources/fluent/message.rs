@@ -10,7 +10,8 @@ use vector_core::event::Value;
 /// The spec refers to 4 ways, but really CompressedPackedForward is encoded the
 /// same as PackedForward, it just has an additional decompression step.
 ///
-/// Not yet handled are the handshake messages.
+/// The handshake messages, used in secure forward mode, are handled separately by
+/// `FluentPing`.
 ///
 /// https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#event-modes
 #[derive(Debug, Deserialize, Serialize)]
@@ -35,6 +36,21 @@ pub(super) enum FluentMessage {
     Heartbeat(rmpv::Value), // should be Nil if heartbeat
 }
 
+/// Handshake message sent by the client in reply to the server's `HELO`: the message type
+/// (always `PING`), the client hostname, the shared key salt, the shared key digest, and the
+/// username and password digest for user authentication.
+///
+/// https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#ping
+#[derive(Debug, Deserialize, Serialize)]
+pub(super) struct FluentPing(
+    pub(super) String,
+    pub(super) String,
+    pub(super) serde_bytes::ByteBuf,
+    pub(super) String,
+    pub(super) String,
+    pub(super) String,
+);
+
 /// Server options sent by client.
 ///
 /// https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#option
@@ -144,8 +160,9 @@ impl From<FluentValue> for Value {
             rmpv::Value::Integer(i) => i
                 .as_i64()
                 .map(Value::Integer)
-                // unwrap large numbers to string similar to how
+                // keep large unsigned numbers exact, similar to how
                 // `From<serde_json::Value> for Value` handles it
+                .or_else(|| i.as_u64().map(Value::from))
                 .unwrap_or_else(|| Value::Bytes(i.to_string().into())),
             rmpv::Value::F32(f) => Value::Float(f.into()),
             rmpv::Value::F64(f) => Value::Float(f),
@@ -238,7 +255,7 @@ mod test {
         fn from_u64(input: u64) -> () {
             if input > i64::max_value() as u64 {
                 assert_eq!(Value::from(FluentValue(rmpv::Value::Integer(rmpv::Integer::from(input)))),
-                           Value::Bytes(input.to_string().into()))
+                           Value::Decimal(input.into()))
             } else {
                 assert_eq!(Value::from(FluentValue(rmpv::Value::Integer(rmpv::Integer::from(input)))),
                            Value::Integer(input as i64))
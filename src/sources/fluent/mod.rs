@@ -6,25 +6,31 @@ use std::{
 use bytes::{Buf, Bytes, BytesMut};
 use flate2::read::MultiGzDecoder;
 use rmp_serde::{decode, Deserializer};
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha512};
 use smallvec::{smallvec, SmallVec};
 use tokio_util::codec::Decoder;
 
-use super::util::{SocketListenAddr, StreamDecodingError, TcpSource, TcpSourceAck, TcpSourceAcker};
+use super::util::{
+    ConnectionLimits, SocketListenAddr, StreamDecodingError, TcpSource, TcpSourceAck,
+    TcpSourceAcker,
+};
 use crate::{
     config::{
         log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource,
         SourceConfig, SourceContext, SourceDescription,
     },
     event::{Event, LogEvent},
-    internal_events::{FluentMessageDecodeError, FluentMessageReceived},
+    internal_events::{FluentHandshakeFailed, FluentMessageDecodeError, FluentMessageReceived},
     serde::bool_or_struct,
-    tcp::TcpKeepaliveConfig,
+    tcp::{ProxyProtocolConfig, TcpKeepaliveConfig},
     tls::{MaybeTlsSettings, TlsConfig},
 };
 
 mod message;
-use self::message::{FluentEntry, FluentMessage, FluentRecord, FluentTag, FluentTimestamp};
+use self::message::{
+    FluentEntry, FluentMessage, FluentPing, FluentRecord, FluentTag, FluentTimestamp,
+};
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct FluentConfig {
@@ -34,7 +40,21 @@ pub struct FluentConfig {
     receive_buffer_bytes: Option<usize>,
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+    #[serde(alias = "max_connections")]
     connection_limit: Option<u32>,
+    connection_limit_per_ip: Option<u32>,
+    connection_rate_limit: Option<u32>,
+    security: Option<FluentSecurityConfig>,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    proxy_protocol: ProxyProtocolConfig,
+}
+
+/// Settings for the handshake used by Fluentd's secure forward mode.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSecurityConfig {
+    shared_key: String,
+    self_hostname: String,
 }
 
 inventory::submit! {
@@ -50,6 +70,10 @@ impl GenerateConfig for FluentConfig {
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: Some(2),
+            connection_limit_per_ip: None,
+            connection_rate_limit: None,
+            security: None,
+            proxy_protocol: ProxyProtocolConfig::default(),
         })
         .unwrap()
     }
@@ -59,7 +83,10 @@ impl GenerateConfig for FluentConfig {
 #[typetag::serde(name = "fluent")]
 impl SourceConfig for FluentConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let source = FluentSource {};
+        let source = FluentSource {
+            security: self.security.clone(),
+            proxy_protocol: self.proxy_protocol.clone(),
+        };
         let shutdown_secs = 30;
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
         source.run(
@@ -70,7 +97,12 @@ impl SourceConfig for FluentConfig {
             self.receive_buffer_bytes,
             cx,
             self.acknowledgements,
-            self.connection_limit,
+            ConnectionLimits {
+                max_connections: self.connection_limit,
+                max_connections_per_ip: self.connection_limit_per_ip,
+                max_accepts_per_sec: self.connection_rate_limit,
+                ..Default::default()
+            },
         )
     }
 
@@ -88,16 +120,22 @@ impl SourceConfig for FluentConfig {
 }
 
 #[derive(Debug, Clone)]
-struct FluentSource;
+struct FluentSource {
+    security: Option<FluentSecurityConfig>,
+    proxy_protocol: ProxyProtocolConfig,
+}
 
 impl TcpSource for FluentSource {
     type Error = DecodeError;
-    type Item = FluentFrame;
+    type Item = FluentItem;
     type Decoder = FluentDecoder;
     type Acker = FluentAcker;
 
     fn decoder(&self) -> Self::Decoder {
-        FluentDecoder::new()
+        match &self.security {
+            Some(security) => FluentDecoder::with_handshake(security.clone()),
+            None => FluentDecoder::new(),
+        }
     }
 
     fn handle_events(&self, events: &mut [Event], host: Bytes, _byte_size: usize) {
@@ -113,6 +151,14 @@ impl TcpSource for FluentSource {
     fn build_acker(&self, frame: &[Self::Item]) -> Self::Acker {
         FluentAcker::new(frame)
     }
+
+    fn build_greeting(&self, decoder: &Self::Decoder) -> Option<Bytes> {
+        decoder.helo()
+    }
+
+    fn proxy_protocol(&self) -> Option<&ProxyProtocolConfig> {
+        Some(&self.proxy_protocol)
+    }
 }
 
 #[derive(Debug)]
@@ -121,6 +167,7 @@ pub enum DecodeError {
     Decode(decode::Error),
     UnknownCompression(String),
     UnexpectedValue(rmpv::Value),
+    Unauthenticated,
 }
 
 impl std::fmt::Display for DecodeError {
@@ -134,6 +181,7 @@ impl std::fmt::Display for DecodeError {
             DecodeError::UnexpectedValue(value) => {
                 write!(f, "unexpected msgpack value, ignoring: {}", value)
             }
+            DecodeError::Unauthenticated => write!(f, "client failed to authenticate"),
         }
     }
 }
@@ -145,6 +193,7 @@ impl StreamDecodingError for DecodeError {
             DecodeError::Decode(_) => true,
             DecodeError::UnknownCompression(_) => true,
             DecodeError::UnexpectedValue(_) => true,
+            DecodeError::Unauthenticated => false,
         }
     }
 }
@@ -165,15 +214,112 @@ impl From<decode::Error> for DecodeError {
 struct FluentDecoder {
     // unread frames from previous fluent message
     unread_frames: VecDeque<(FluentFrame, usize)>,
+    handshake: HandshakeState,
+}
+
+/// State of the secure forward handshake on a connection. Events are only decoded once the
+/// client is authenticated.
+///
+/// https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1#handshake-messages
+#[derive(Debug)]
+enum HandshakeState {
+    Pending {
+        security: FluentSecurityConfig,
+        nonce: [u8; 16],
+    },
+    Authenticated,
+    Failed,
 }
 
 impl FluentDecoder {
     fn new() -> Self {
         FluentDecoder {
             unread_frames: VecDeque::new(),
+            handshake: HandshakeState::Authenticated,
         }
     }
 
+    fn with_handshake(security: FluentSecurityConfig) -> Self {
+        FluentDecoder {
+            unread_frames: VecDeque::new(),
+            handshake: HandshakeState::Pending {
+                security,
+                nonce: rand::random(),
+            },
+        }
+    }
+
+    /// The `HELO` message that starts the handshake, if one is pending.
+    fn helo(&self) -> Option<Bytes> {
+        match &self.handshake {
+            HandshakeState::Pending { nonce, .. } => {
+                Some(encode_msgpack(rmpv::Value::Array(vec![
+                    "HELO".into(),
+                    rmpv::Value::Map(vec![
+                        ("nonce".into(), rmpv::Value::Binary(nonce.to_vec())),
+                        // User authentication isn't supported, so no salt is sent for it.
+                        ("auth".into(), "".into()),
+                        ("keepalive".into(), true.into()),
+                    ]),
+                ])))
+            }
+            _ => None,
+        }
+    }
+
+    /// Decode the client's `PING`, and build the `PONG` reply with the outcome.
+    fn decode_ping(
+        &mut self,
+        src: &mut BytesMut,
+    ) -> Result<Option<(FluentItem, usize)>, DecodeError> {
+        let (byte_size, res) = match deserialize::<FluentPing>(src) {
+            Some(res) => res,
+            None => return Ok(None),
+        };
+
+        src.advance(byte_size);
+
+        let (security, nonce) = match std::mem::replace(&mut self.handshake, HandshakeState::Failed)
+        {
+            HandshakeState::Pending { security, nonce } => (security, nonce),
+            _ => return Err(DecodeError::Unauthenticated),
+        };
+
+        let pong = match res
+            .map_err(|_| "invalid ping message".to_owned())
+            .and_then(|ping| check_ping(ping, &security, &nonce))
+        {
+            Ok(shared_key_salt) => {
+                self.handshake = HandshakeState::Authenticated;
+                rmpv::Value::Array(vec![
+                    "PONG".into(),
+                    true.into(),
+                    "".into(),
+                    security.self_hostname.as_str().into(),
+                    shared_key_digest(
+                        &shared_key_salt,
+                        &security.self_hostname,
+                        &nonce,
+                        &security.shared_key,
+                    )
+                    .into(),
+                ])
+            }
+            Err(reason) => {
+                emit!(&FluentHandshakeFailed { reason: &reason });
+                rmpv::Value::Array(vec![
+                    "PONG".into(),
+                    false.into(),
+                    reason.into(),
+                    "".into(),
+                    "".into(),
+                ])
+            }
+        };
+
+        Ok(Some((FluentItem::Pong(encode_msgpack(pong)), byte_size)))
+    }
+
     fn handle_message(
         &mut self,
         message: FluentMessage,
@@ -291,42 +437,37 @@ impl FluentDecoder {
 }
 
 impl Decoder for FluentDecoder {
-    type Item = (FluentFrame, usize);
+    type Item = (FluentItem, usize);
     type Error = DecodeError;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        if let Some(item) = self.unread_frames.pop_front() {
-            return Ok(Some(item));
+        if let Some((frame, byte_size)) = self.unread_frames.pop_front() {
+            return Ok(Some((FluentItem::Frame(frame), byte_size)));
         }
 
         if src.is_empty() {
             return Ok(None);
         }
 
-        let (byte_size, res) = {
-            let mut des = Deserializer::new(io::Cursor::new(&src[..]));
-
-            let res = Deserialize::deserialize(&mut des).map_err(DecodeError::Decode);
-
-            // check for unexpected EOF to indicate that we need more data
-            if let Err(DecodeError::Decode(
-                decode::Error::InvalidDataRead(ref custom)
-                | decode::Error::InvalidMarkerRead(ref custom),
-            )) = res
-            {
-                if custom.kind() == io::ErrorKind::UnexpectedEof {
-                    return Ok(None);
-                }
-            }
+        match self.handshake {
+            HandshakeState::Authenticated => {}
+            HandshakeState::Pending { .. } => return self.decode_ping(src),
+            HandshakeState::Failed => return Err(DecodeError::Unauthenticated),
+        }
 
-            (des.position() as usize, res)
+        let (byte_size, res) = match deserialize(src) {
+            Some(res) => res,
+            None => return Ok(None),
         };
 
         src.advance(byte_size);
 
         res.and_then(|message| {
-            self.handle_message(message, byte_size)
-                .map(|_| self.unread_frames.pop_front())
+            self.handle_message(message, byte_size).map(|_| {
+                self.unread_frames
+                    .pop_front()
+                    .map(|(frame, byte_size)| (FluentItem::Frame(frame), byte_size))
+            })
         })
         .map_err(|error| {
             let base64_encoded_message = base64::encode(&src);
@@ -339,6 +480,66 @@ impl Decoder for FluentDecoder {
     }
 }
 
+/// Deserialize a msgpack value from the start of `src`, returning the number of bytes it took
+/// up, or `None` if more data is needed.
+fn deserialize<T: DeserializeOwned>(src: &BytesMut) -> Option<(usize, Result<T, DecodeError>)> {
+    let mut des = Deserializer::new(io::Cursor::new(&src[..]));
+
+    let res = Deserialize::deserialize(&mut des).map_err(DecodeError::Decode);
+
+    // check for unexpected EOF to indicate that we need more data
+    if let Err(DecodeError::Decode(
+        decode::Error::InvalidDataRead(ref custom) | decode::Error::InvalidMarkerRead(ref custom),
+    )) = res
+    {
+        if custom.kind() == io::ErrorKind::UnexpectedEof {
+            return None;
+        }
+    }
+
+    Some((des.position() as usize, res))
+}
+
+fn encode_msgpack(value: rmpv::Value) -> Bytes {
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &value).expect("Writing to Vec can't fail");
+    buf.into()
+}
+
+/// Check the client's `PING`, returning its shared key salt if it authenticated, or the reason
+/// to send back to it if it didn't.
+fn check_ping(
+    ping: FluentPing,
+    security: &FluentSecurityConfig,
+    nonce: &[u8],
+) -> Result<Vec<u8>, String> {
+    let FluentPing(message_type, hostname, shared_key_salt, shared_key_hexdigest, _, _) = ping;
+
+    if message_type != "PING" {
+        return Err("invalid ping message".to_owned());
+    }
+
+    if hostname == security.self_hostname {
+        return Err("same hostname between input and output: invalid configuration".to_owned());
+    }
+
+    let expected = shared_key_digest(&shared_key_salt, &hostname, nonce, &security.shared_key);
+    if shared_key_hexdigest != expected {
+        return Err("shared_key mismatch".to_owned());
+    }
+
+    Ok(shared_key_salt.into_vec())
+}
+
+fn shared_key_digest(salt: &[u8], hostname: &str, nonce: &[u8], shared_key: &str) -> String {
+    let mut hasher = Sha512::new();
+    hasher.update(salt);
+    hasher.update(hostname.as_bytes());
+    hasher.update(nonce);
+    hasher.update(shared_key.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
 /// Decoder for decoding MessagePackEventStream which are just a stream of Entries
 #[derive(Clone, Debug)]
 struct FluentEntryStreamDecoder;
@@ -378,18 +579,31 @@ impl Decoder for FluentEntryStreamDecoder {
 
 struct FluentAcker {
     chunks: Vec<String>,
+    pong: Option<Bytes>,
 }
 
 impl FluentAcker {
-    fn new(frames: &[FluentFrame]) -> Self {
-        Self {
-            chunks: frames.iter().filter_map(|f| f.chunk.clone()).collect(),
+    fn new(items: &[FluentItem]) -> Self {
+        let mut chunks = Vec::new();
+        let mut pong = None;
+        for item in items {
+            match item {
+                FluentItem::Frame(frame) => chunks.extend(frame.chunk.clone()),
+                FluentItem::Pong(message) => pong = Some(message.clone()),
+            }
         }
+        Self { chunks, pong }
     }
 }
 
 impl TcpSourceAcker for FluentAcker {
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
+        // The client waits for the `PONG` before sending any events, so it is never batched with
+        // chunks to acknowledge.
+        if self.pong.is_some() {
+            return self.pong;
+        }
+
         if self.chunks.is_empty() {
             return None;
         }
@@ -406,6 +620,23 @@ impl TcpSourceAcker for FluentAcker {
     }
 }
 
+/// Item decoded from a fluent connection.
+#[derive(Debug, PartialEq)]
+enum FluentItem {
+    Frame(FluentFrame),
+    /// Reply to the client's `PING`, sent back through the acker.
+    Pong(Bytes),
+}
+
+impl From<FluentItem> for SmallVec<[Event; 1]> {
+    fn from(item: FluentItem) -> Self {
+        match item {
+            FluentItem::Frame(frame) => frame.into(),
+            FluentItem::Pong(_) => smallvec![],
+        }
+    }
+}
+
 /// Normalized fluent message.
 #[derive(Debug, PartialEq)]
 struct FluentFrame {
@@ -758,12 +989,164 @@ mod tests {
         let mut decoder = FluentDecoder::new();
 
         let mut frames = vec![];
-        while let Some((frame, byte_size)) = decoder.decode(&mut buf)? {
-            frames.push((LogEvent::from(frame), byte_size))
+        while let Some((item, byte_size)) = decoder.decode(&mut buf)? {
+            match item {
+                FluentItem::Frame(frame) => frames.push((LogEvent::from(frame), byte_size)),
+                FluentItem::Pong(_) => panic!("unexpected handshake reply"),
+            }
         }
         Ok(frames)
     }
 
+    fn security() -> FluentSecurityConfig {
+        FluentSecurityConfig {
+            shared_key: "secret".into(),
+            self_hostname: "vector".into(),
+        }
+    }
+
+    fn build_ping(nonce: &[u8], shared_key: &str) -> Vec<u8> {
+        let salt = b"salt";
+        let ping = rmpv::Value::Array(vec![
+            "PING".into(),
+            "client".into(),
+            rmpv::Value::Binary(salt.to_vec()),
+            shared_key_digest(salt, "client", nonce, shared_key).into(),
+            "".into(),
+            "".into(),
+        ]);
+        encode_msgpack(ping).to_vec()
+    }
+
+    fn read_nonce(helo: &[u8]) -> Vec<u8> {
+        let helo = rmpv::decode::read_value(&mut &helo[..]).unwrap();
+        assert_eq!(helo[0].as_str(), Some("HELO"));
+        let options = helo[1].as_map().unwrap();
+        options
+            .iter()
+            .find(|(key, _)| key.as_str() == Some("nonce"))
+            .and_then(|(_, nonce)| nonce.as_slice())
+            .unwrap()
+            .to_vec()
+    }
+
+    #[test]
+    fn handshake_authenticates_client() {
+        let mut decoder = FluentDecoder::with_handshake(security());
+        let nonce = read_nonce(&decoder.helo().unwrap());
+
+        let mut buf = BytesMut::from(&build_ping(&nonce, "secret")[..]);
+        buf.extend_from_slice(&build_req("tag", &[("message", "foo")], false));
+
+        let pong = match decoder.decode(&mut buf).unwrap() {
+            Some((FluentItem::Pong(pong), _)) => pong,
+            item => panic!("expected pong, got {:?}", item),
+        };
+        let pong = rmpv::decode::read_value(&mut &pong[..]).unwrap();
+        assert_eq!(
+            pong,
+            rmpv::Value::Array(vec![
+                "PONG".into(),
+                true.into(),
+                "".into(),
+                "vector".into(),
+                shared_key_digest(b"salt", "vector", &nonce, "secret").into(),
+            ])
+        );
+        assert!(decoder.helo().is_none());
+
+        match decoder.decode(&mut buf).unwrap() {
+            Some((FluentItem::Frame(frame), _)) => assert_eq!(frame.tag, "tag"),
+            item => panic!("expected frame, got {:?}", item),
+        }
+    }
+
+    #[test]
+    fn handshake_rejects_wrong_shared_key() {
+        let mut decoder = FluentDecoder::with_handshake(security());
+        let nonce = read_nonce(&decoder.helo().unwrap());
+
+        let mut buf = BytesMut::from(&build_ping(&nonce, "wrong")[..]);
+        buf.extend_from_slice(&build_req("tag", &[("message", "foo")], false));
+
+        let pong = match decoder.decode(&mut buf).unwrap() {
+            Some((FluentItem::Pong(pong), _)) => pong,
+            item => panic!("expected pong, got {:?}", item),
+        };
+        let pong = rmpv::decode::read_value(&mut &pong[..]).unwrap();
+        assert_eq!(pong[1].as_bool(), Some(false));
+        assert_eq!(pong[2].as_str(), Some("shared_key mismatch"));
+
+        assert!(matches!(
+            decoder.decode(&mut buf),
+            Err(DecodeError::Unauthenticated)
+        ));
+    }
+
+    #[test]
+    fn handshake_rejects_events_before_ping() {
+        let mut decoder = FluentDecoder::with_handshake(security());
+
+        let mut buf = BytesMut::from(&build_req("tag", &[("message", "foo")], false)[..]);
+
+        let pong = match decoder.decode(&mut buf).unwrap() {
+            Some((FluentItem::Pong(pong), _)) => pong,
+            item => panic!("expected pong, got {:?}", item),
+        };
+        let pong = rmpv::decode::read_value(&mut &pong[..]).unwrap();
+        assert_eq!(pong[1].as_bool(), Some(false));
+        assert_eq!(pong[2].as_str(), Some("invalid ping message"));
+    }
+
+    #[tokio::test]
+    async fn secure_forward_handshake() {
+        trace_init();
+
+        let (sender, recv) = SourceSender::new_test();
+        let address = next_addr();
+        let source = FluentConfig {
+            address: address.into(),
+            tls: None,
+            keepalive: None,
+            receive_buffer_bytes: None,
+            acknowledgements: false.into(),
+            connection_limit: None,
+            connection_limit_per_ip: None,
+            connection_rate_limit: None,
+            security: Some(security()),
+            proxy_protocol: Default::default(),
+        }
+        .build(SourceContext::new_test(sender))
+        .await
+        .unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let mut socket = tokio::net::TcpStream::connect(address).await.unwrap();
+
+        let mut helo = BytesMut::new();
+        socket.read_buf(&mut helo).await.unwrap();
+        let nonce = read_nonce(&helo);
+
+        socket
+            .write_all(&build_ping(&nonce, "secret"))
+            .await
+            .unwrap();
+        let mut pong = BytesMut::new();
+        socket.read_buf(&mut pong).await.unwrap();
+        let pong = rmpv::decode::read_value(&mut &pong[..]).unwrap();
+        assert_eq!(pong[1].as_bool(), Some(true));
+
+        socket
+            .write_all(&build_req("tag", &[("message", "foo")], false))
+            .await
+            .unwrap();
+
+        let events = test_util::collect_n(recv, 1).await;
+        assert_eq!(events[0].as_log()["message"], "foo".into());
+        assert_eq!(events[0].as_log()["tag"], "tag".into());
+    }
+
     #[tokio::test]
     async fn ack_delivered_without_chunk() {
         let (result, output) = check_acknowledgements(EventStatus::Delivered, false).await;
@@ -807,6 +1190,10 @@ mod tests {
             receive_buffer_bytes: None,
             acknowledgements: true.into(),
             connection_limit: None,
+            connection_limit_per_ip: None,
+            connection_rate_limit: None,
+            security: None,
+            proxy_protocol: Default::default(),
         }
         .build(SourceContext::new_test(sender))
         .await
@@ -1052,6 +1439,10 @@ mod integration_tests {
                 receive_buffer_bytes: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
+                connection_limit_per_ip: None,
+                connection_rate_limit: None,
+                security: None,
+                proxy_protocol: Default::default(),
             }
             .build(SourceContext::new_test(sender))
             .await
@@ -0,0 +1,558 @@
+//! Pulls log entries from Cloud Logging with the `entries.list` API, for projects where routing
+//! logs to Pub/Sub with a log sink isn't an option.
+//!
+//! Cloud Logging has no REST equivalent of tailing, so new entries are found by listing those
+//! with a timestamp past a cursor, which is persisted so that a restart picks up where the last
+//! run left off.
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::StreamExt;
+use goauth::scopes::Scope;
+use http::{Request, Uri};
+use hyper::Body;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value as JsonValue};
+use snafu::Snafu;
+use tokio::time::interval;
+use tokio_stream::wrappers::IntervalStream;
+use vector_core::{event::BatchStatus, ByteSizeOf};
+
+use crate::{
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, SourceConfig,
+        SourceContext, SourceDescription,
+    },
+    event::{BatchNotifier, Event, LogEvent, Value},
+    http::HttpClient,
+    internal_events::{
+        GcpCloudLoggingCursorWriteFailed, GcpCloudLoggingEventsReceived,
+        GcpCloudLoggingRequestFailed,
+    },
+    serde::bool_or_struct,
+    shutdown::ShutdownSignal,
+    sinks::gcp::{GcpAuthConfig, GcpCredentials},
+    tls::{TlsOptions, TlsSettings},
+    SourceSender,
+};
+
+const CURSOR_FILENAME: &str = "cursor.json";
+const DEFAULT_ENDPOINT: &str = "https://logging.googleapis.com";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one resource name must be set in `resource_names`"))]
+    MissingResourceNames,
+    #[snafu(display("`page_size` must be between 1 and 1000, got {}", page_size))]
+    InvalidPageSize { page_size: u32 },
+    #[snafu(display("`poll_interval_secs` must be greater than 0"))]
+    ZeroPollInterval,
+}
+
+#[derive(Debug, Snafu)]
+enum ListError {
+    #[snafu(display("Unexpected status code {}: {}", status, body))]
+    UnexpectedStatus {
+        status: http::StatusCode,
+        body: String,
+    },
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct GcpCloudLoggingConfig {
+    /// The projects, folders, organizations or billing accounts to read entries from, such as
+    /// `projects/my-project`.
+    resource_names: Vec<String>,
+    /// Only entries matching this Logging query language filter are read.
+    filter: Option<String>,
+    #[serde(default = "default_poll_interval_secs")]
+    poll_interval_secs: u64,
+    /// How far behind the current time to read up to, giving Cloud Logging time to make entries
+    /// available before the cursor moves past their timestamp.
+    #[serde(default = "default_ingestion_delay_secs")]
+    ingestion_delay_secs: u64,
+    #[serde(default = "default_page_size")]
+    page_size: u32,
+    endpoint: Option<String>,
+    #[serde(default)]
+    skip_authentication: bool,
+    #[serde(flatten)]
+    auth: GcpAuthConfig,
+    data_dir: Option<PathBuf>,
+    tls: Option<TlsOptions>,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+const fn default_poll_interval_secs() -> u64 {
+    15
+}
+
+const fn default_ingestion_delay_secs() -> u64 {
+    30
+}
+
+const fn default_page_size() -> u32 {
+    1000
+}
+
+inventory::submit! {
+    SourceDescription::new::<GcpCloudLoggingConfig>("gcp_cloud_logging")
+}
+
+impl GenerateConfig for GcpCloudLoggingConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            resource_names: vec!["projects/my-project".to_owned()],
+            filter: Some(r#"resource.type = "cloud_run_revision""#.to_owned()),
+            poll_interval_secs: default_poll_interval_secs(),
+            ingestion_delay_secs: default_ingestion_delay_secs(),
+            page_size: default_page_size(),
+            endpoint: None,
+            skip_authentication: false,
+            auth: Default::default(),
+            data_dir: None,
+            tls: None,
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "gcp_cloud_logging")]
+impl SourceConfig for GcpCloudLoggingConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.resource_names.is_empty() {
+            return Err(Box::new(BuildError::MissingResourceNames));
+        }
+        if !(1..=1000).contains(&self.page_size) {
+            return Err(Box::new(BuildError::InvalidPageSize {
+                page_size: self.page_size,
+            }));
+        }
+        if self.poll_interval_secs == 0 {
+            return Err(Box::new(BuildError::ZeroPollInterval));
+        }
+
+        let data_dir = cx
+            .globals
+            .resolve_and_make_data_subdir(self.data_dir.as_ref(), cx.key.id())?;
+
+        let creds = if self.skip_authentication {
+            None
+        } else {
+            self.auth.make_credentials(Scope::LoggingRead).await?
+        };
+        if let Some(creds) = &creds {
+            creds.spawn_regenerate_token();
+        }
+
+        let mut uri = format!(
+            "{}/v2/entries:list",
+            self.endpoint.as_deref().unwrap_or(DEFAULT_ENDPOINT)
+        );
+        if let Some(key) = &self.auth.api_key {
+            uri = format!("{}?key={}", uri, key);
+        }
+        let uri = uri.parse::<Uri>()?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+        let acknowledgements = cx.globals.acknowledgements.merge(&self.acknowledgements);
+
+        let source = CloudLoggingSource {
+            client,
+            creds,
+            uri,
+            resource_names: self.resource_names.clone(),
+            filter: self.filter.clone(),
+            page_size: self.page_size,
+            ingestion_delay: chrono::Duration::seconds(self.ingestion_delay_secs as i64),
+            cursor_path: data_dir.join(CURSOR_FILENAME),
+            acknowledgements: acknowledgements.enabled(),
+            out: cx.out,
+        };
+
+        Ok(Box::pin(source.run(
+            Duration::from_secs(self.poll_interval_secs),
+            cx.shutdown,
+        )))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "gcp_cloud_logging"
+    }
+}
+
+/// How far through the logs the source has read.
+///
+/// Entries can share a timestamp, so the ids of those already read at exactly `timestamp` are
+/// kept too, to skip them when listing from `timestamp` again.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct Cursor {
+    timestamp: DateTime<Utc>,
+    insert_ids: Vec<String>,
+}
+
+impl Cursor {
+    const fn new(timestamp: DateTime<Utc>) -> Self {
+        Self {
+            timestamp,
+            insert_ids: Vec::new(),
+        }
+    }
+
+    fn has_read(&self, timestamp: DateTime<Utc>, insert_id: &str) -> bool {
+        timestamp < self.timestamp
+            || (timestamp == self.timestamp && self.insert_ids.iter().any(|id| id == insert_id))
+    }
+
+    fn advance(&mut self, timestamp: DateTime<Utc>, insert_id: String) {
+        if timestamp > self.timestamp {
+            *self = Self::new(timestamp);
+        }
+        if timestamp == self.timestamp {
+            self.insert_ids.push(insert_id);
+        }
+    }
+
+    /// The filter for entries from the cursor up to, but not including, `until`.
+    fn filter(&self, until: DateTime<Utc>, filter: Option<&str>) -> String {
+        let mut filter = filter
+            .map(|filter| format!("({}) AND ", filter))
+            .unwrap_or_default();
+        filter.push_str(&format!(
+            r#"timestamp >= "{}" AND timestamp < "{}""#,
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            until.to_rfc3339_opts(SecondsFormat::Nanos, true),
+        ));
+        filter
+    }
+
+    async fn load(path: &Path) -> std::io::Result<Option<Self>> {
+        match tokio::fs::read(path).await {
+            Ok(contents) => Ok(serde_json::from_slice(&contents).ok()),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Write the cursor to a temporary file first, so that a crash never leaves a partial one.
+    async fn save(&self, path: &Path) -> std::io::Result<()> {
+        let tmp_path = path.with_extension("json.tmp");
+        let contents = serde_json::to_vec(self).expect("cursor is serializable");
+        tokio::fs::write(&tmp_path, contents).await?;
+        tokio::fs::rename(&tmp_path, path).await
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListEntriesResponse {
+    #[serde(default)]
+    entries: Vec<Map<String, JsonValue>>,
+    next_page_token: Option<String>,
+}
+
+struct CloudLoggingSource {
+    client: HttpClient,
+    creds: Option<GcpCredentials>,
+    uri: Uri,
+    resource_names: Vec<String>,
+    filter: Option<String>,
+    page_size: u32,
+    ingestion_delay: chrono::Duration,
+    cursor_path: PathBuf,
+    acknowledgements: bool,
+    out: SourceSender,
+}
+
+impl CloudLoggingSource {
+    async fn run(mut self, poll_interval: Duration, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let mut cursor = match Cursor::load(&self.cursor_path).await {
+            Ok(Some(cursor)) => cursor,
+            Ok(None) => Cursor::new(Utc::now() - self.ingestion_delay),
+            Err(error) => {
+                error!(
+                    message = "Unable to read cursor.",
+                    %error,
+                    path = ?self.cursor_path,
+                );
+                return Err(());
+            }
+        };
+
+        let mut ticks = IntervalStream::new(interval(poll_interval)).take_until(shutdown);
+        while ticks.next().await.is_some() {
+            let until = Utc::now() - self.ingestion_delay;
+            match self.poll(&mut cursor, until).await {
+                Ok(true) => {}
+                // The pipeline is shutting down.
+                Ok(false) => break,
+                Err(error) => emit!(&GcpCloudLoggingRequestFailed { error: &error }),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read every entry between the cursor and `until`, a page at a time, moving the cursor
+    /// along once each page is sent. Returns false if the pipeline has shut down.
+    async fn poll(&mut self, cursor: &mut Cursor, until: DateTime<Utc>) -> crate::Result<bool> {
+        if until <= cursor.timestamp {
+            return Ok(true);
+        }
+
+        let filter = cursor.filter(until, self.filter.as_deref());
+        let mut page_token = None;
+
+        loop {
+            let response = self.list(&filter, page_token.take()).await?;
+
+            let mut next = cursor.clone();
+            let events = response
+                .entries
+                .into_iter()
+                .filter_map(|entry| {
+                    let (timestamp, insert_id, log) = entry_to_log(entry)?;
+                    if cursor.has_read(timestamp, &insert_id) {
+                        return None;
+                    }
+                    next.advance(timestamp, insert_id);
+                    Some(log)
+                })
+                .collect::<Vec<_>>();
+
+            if !events.is_empty() {
+                match self.send(events).await {
+                    Some(BatchStatus::Delivered) => {}
+                    // Leave the cursor where it is, so that the entries are read again.
+                    Some(_) => return Ok(true),
+                    None => return Ok(false),
+                }
+            }
+
+            page_token = response.next_page_token.filter(|token| !token.is_empty());
+            if page_token.is_none() {
+                // Every entry before `until` has been read.
+                if next.timestamp < until {
+                    next = Cursor::new(until);
+                }
+            }
+
+            if next != *cursor {
+                *cursor = next;
+                if let Err(error) = cursor.save(&self.cursor_path).await {
+                    emit!(&GcpCloudLoggingCursorWriteFailed {
+                        error,
+                        path: &self.cursor_path,
+                    });
+                }
+            }
+
+            if page_token.is_none() {
+                return Ok(true);
+            }
+        }
+    }
+
+    async fn list(
+        &self,
+        filter: &str,
+        page_token: Option<String>,
+    ) -> crate::Result<ListEntriesResponse> {
+        let mut body = json!({
+            "resourceNames": self.resource_names,
+            "filter": filter,
+            "orderBy": "timestamp asc",
+            "pageSize": self.page_size,
+        });
+        if let Some(page_token) = page_token {
+            body["pageToken"] = JsonValue::from(page_token);
+        }
+
+        let mut request = Request::post(self.uri.clone())
+            .header("Content-Type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body)?))?;
+        if let Some(creds) = &self.creds {
+            creds.apply(&mut request);
+        }
+
+        let response = self.client.send(request).await?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        if !status.is_success() {
+            return Err(Box::new(ListError::UnexpectedStatus {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            }));
+        }
+
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    /// Send the events, waiting for them to be delivered if acknowledgements are enabled.
+    /// Returns `None` if the pipeline has shut down.
+    async fn send(&mut self, events: Vec<LogEvent>) -> Option<BatchStatus> {
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let events = events
+            .into_iter()
+            .map(|log| Event::from(log.with_batch_notifier_option(&batch)))
+            .collect::<Vec<_>>();
+        drop(batch);
+
+        emit!(&GcpCloudLoggingEventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        });
+
+        if let Err(error) = self.out.send_all(futures::stream::iter(events)).await {
+            error!(message = "Error sending to sink.", %error);
+            return None;
+        }
+
+        match receiver {
+            Some(receiver) => Some(receiver.await),
+            None => Some(BatchStatus::Delivered),
+        }
+    }
+}
+
+/// Convert a log entry into an event, keeping all of its fields. Entries without a timestamp or
+/// insert id are skipped, as the cursor can't account for them.
+fn entry_to_log(entry: Map<String, JsonValue>) -> Option<(DateTime<Utc>, String, LogEvent)> {
+    let timestamp = entry
+        .get("timestamp")
+        .and_then(JsonValue::as_str)
+        .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())?
+        .with_timezone(&Utc);
+    let insert_id = entry
+        .get("insertId")
+        .and_then(JsonValue::as_str)?
+        .to_owned();
+
+    let schema = log_schema();
+    let mut log = LogEvent::default();
+    for (key, value) in entry {
+        if key == "textPayload" {
+            if let JsonValue::String(text) = &value {
+                log.insert(schema.message_key(), text.as_str());
+                continue;
+            }
+        }
+        log.insert_flat(key, Value::from(value));
+    }
+    log.insert(schema.timestamp_key(), timestamp);
+    log.insert(schema.source_type_key(), Bytes::from("gcp_cloud_logging"));
+
+    Some((timestamp, insert_id, log))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GcpCloudLoggingConfig>();
+    }
+
+    #[tokio::test]
+    async fn rejects_zero_poll_interval() {
+        let config: GcpCloudLoggingConfig = toml::from_str(
+            r#"
+                resource_names = ["projects/my-project"]
+                poll_interval_secs = 0
+            "#,
+        )
+        .unwrap();
+        let (tx, _rx) = SourceSender::new_test();
+        assert!(config.build(SourceContext::new_test(tx)).await.is_err());
+    }
+
+    #[test]
+    fn cursor_skips_entries_already_read() {
+        let start = Utc.timestamp(1_600_000_000, 0);
+        let mut cursor = Cursor::new(start);
+
+        cursor.advance(start, "a".into());
+        cursor.advance(start + chrono::Duration::seconds(1), "b".into());
+        cursor.advance(start + chrono::Duration::seconds(1), "c".into());
+
+        assert!(cursor.has_read(start, "a"));
+        assert!(cursor.has_read(start, "z"));
+        assert!(cursor.has_read(start + chrono::Duration::seconds(1), "c"));
+        assert!(!cursor.has_read(start + chrono::Duration::seconds(1), "d"));
+        assert!(!cursor.has_read(start + chrono::Duration::seconds(2), "a"));
+        assert_eq!(cursor.insert_ids, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn cursor_filter() {
+        let cursor = Cursor::new(Utc.timestamp(1_600_000_000, 0));
+        let until = Utc.timestamp(1_600_000_060, 0);
+
+        assert_eq!(
+            cursor.filter(until, Some(r#"severity >= ERROR"#)),
+            r#"(severity >= ERROR) AND timestamp >= "2020-09-13T12:26:40.000000000Z" AND timestamp < "2020-09-13T12:27:40.000000000Z""#
+        );
+        assert_eq!(
+            cursor.filter(until, None),
+            r#"timestamp >= "2020-09-13T12:26:40.000000000Z" AND timestamp < "2020-09-13T12:27:40.000000000Z""#
+        );
+    }
+
+    #[tokio::test]
+    async fn cursor_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CURSOR_FILENAME);
+        assert_eq!(Cursor::load(&path).await.unwrap(), None);
+
+        let mut cursor = Cursor::new(Utc.timestamp(1_600_000_000, 0));
+        cursor.advance(cursor.timestamp, "a".into());
+        cursor.save(&path).await.unwrap();
+
+        assert_eq!(Cursor::load(&path).await.unwrap(), Some(cursor));
+    }
+
+    #[test]
+    fn converts_entries() {
+        let entry = serde_json::from_str(
+            r#"{
+                "insertId": "abc123",
+                "logName": "projects/my-project/logs/run.googleapis.com%2Fstdout",
+                "resource": {"type": "cloud_run_revision", "labels": {"service_name": "api"}},
+                "severity": "INFO",
+                "textPayload": "Listening on port 8080",
+                "timestamp": "2020-09-13T12:26:40.123456Z"
+            }"#,
+        )
+        .unwrap();
+
+        let (timestamp, insert_id, log) = entry_to_log(entry).unwrap();
+        assert_eq!(insert_id, "abc123");
+        assert_eq!(timestamp, Utc.timestamp(1_600_000_000, 123_456_000));
+        assert_eq!(log["message"], "Listening on port 8080".into());
+        assert_eq!(log["timestamp"], timestamp.into());
+        assert_eq!(log["severity"], "INFO".into());
+        assert_eq!(log["resource.labels.service_name"], "api".into());
+        assert_eq!(log["source_type"], "gcp_cloud_logging".into());
+        assert!(log.get("textPayload").is_none());
+
+        let entry = serde_json::from_str(r#"{"textPayload": "no insert id"}"#).unwrap();
+        assert!(entry_to_log(entry).is_none());
+    }
+}
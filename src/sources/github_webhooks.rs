@@ -0,0 +1,429 @@
+use std::{collections::HashMap, net::SocketAddr};
+
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use warp::http::{HeaderMap, StatusCode};
+
+use crate::{
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource,
+        SourceConfig, SourceContext, SourceDescription,
+    },
+    event::{Event, LogEvent},
+    http::Http2Config,
+    serde::bool_or_struct,
+    sources::util::{ErrorMessage, HmacAuth, HmacAuthConfig, HttpMethod, HttpSource},
+    tls::TlsConfig,
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct GithubWebhooksConfig {
+    address: SocketAddr,
+    /// The secret the webhook was configured with, which GitHub signs deliveries with.
+    secret: String,
+    #[serde(default = "default_path")]
+    path: String,
+    /// Whether to keep the whole payload of the delivery in the `payload` field.
+    #[serde(default = "crate::serde::default_true")]
+    include_payload: bool,
+    tls: Option<TlsConfig>,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+inventory::submit! {
+    SourceDescription::new::<GithubWebhooksConfig>("github_webhooks")
+}
+
+impl GenerateConfig for GithubWebhooksConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:8080".parse().unwrap(),
+            secret: "${GITHUB_WEBHOOK_SECRET}".to_owned(),
+            path: default_path(),
+            include_payload: true,
+            tls: None,
+            acknowledgements: AcknowledgementsConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+fn default_path() -> String {
+    "/".to_owned()
+}
+
+#[derive(Clone)]
+struct GithubWebhooksSource {
+    signature: HmacAuth,
+    include_payload: bool,
+}
+
+impl HttpSource for GithubWebhooksSource {
+    fn authenticate(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), ErrorMessage> {
+        self.signature.is_valid(headers, body)
+    }
+
+    fn build_events(
+        &self,
+        body: Bytes,
+        header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        _path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        let header = |name: &str| {
+            header_map
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let event = header("X-GitHub-Event").ok_or_else(|| {
+            ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                "Missing X-GitHub-Event header".to_owned(),
+            )
+        })?;
+        let payload = serde_json::from_slice::<JsonValue>(&body).map_err(|error| {
+            ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid JSON payload: {}", error),
+            )
+        })?;
+
+        let mut log = normalize(&event, &payload);
+        log.insert("delivery_id", header("X-GitHub-Delivery"));
+        log.insert("hook_id", header("X-GitHub-Hook-ID"));
+        if self.include_payload {
+            log.insert("payload", payload);
+        }
+        log.insert(
+            log_schema().source_type_key(),
+            Bytes::from("github_webhooks"),
+        );
+        log.insert(log_schema().timestamp_key(), Utc::now());
+
+        Ok(vec![log.into()])
+    }
+}
+
+/// Picks out the fields of a delivery that are most useful for CI/CD analytics, so they don't
+/// need to be dug out of the payload of each event type.
+fn normalize(event: &str, payload: &JsonValue) -> LogEvent {
+    let mut log = LogEvent::default();
+    log.insert("event", event);
+
+    let mut copy = |field: &str, pointer: &str| {
+        if let Some(value) = payload.pointer(pointer).filter(|value| !value.is_null()) {
+            log.insert(field, value.clone());
+        }
+    };
+    copy("action", "/action");
+    copy("repository", "/repository/full_name");
+    copy("organization", "/organization/login");
+    copy("sender", "/sender/login");
+
+    match event {
+        "push" => {
+            copy("push.ref", "/ref");
+            copy("push.before", "/before");
+            copy("push.after", "/after");
+            copy("push.created", "/created");
+            copy("push.deleted", "/deleted");
+            copy("push.forced", "/forced");
+            copy("push.pusher", "/pusher/name");
+            copy("push.compare", "/compare");
+            copy("push.head_commit_id", "/head_commit/id");
+            copy("push.head_commit_message", "/head_commit/message");
+        }
+        "pull_request" => {
+            copy("pull_request.number", "/pull_request/number");
+            copy("pull_request.title", "/pull_request/title");
+            copy("pull_request.state", "/pull_request/state");
+            copy("pull_request.draft", "/pull_request/draft");
+            copy("pull_request.merged", "/pull_request/merged");
+            copy("pull_request.author", "/pull_request/user/login");
+            copy("pull_request.base_ref", "/pull_request/base/ref");
+            copy("pull_request.head_ref", "/pull_request/head/ref");
+            copy("pull_request.head_sha", "/pull_request/head/sha");
+            copy("pull_request.url", "/pull_request/html_url");
+            copy("pull_request.created_at", "/pull_request/created_at");
+            copy("pull_request.merged_at", "/pull_request/merged_at");
+            copy("pull_request.closed_at", "/pull_request/closed_at");
+            copy("pull_request.additions", "/pull_request/additions");
+            copy("pull_request.deletions", "/pull_request/deletions");
+            copy("pull_request.changed_files", "/pull_request/changed_files");
+        }
+        "workflow_run" => {
+            copy("workflow_run.id", "/workflow_run/id");
+            copy("workflow_run.name", "/workflow_run/name");
+            copy("workflow_run.event", "/workflow_run/event");
+            copy("workflow_run.status", "/workflow_run/status");
+            copy("workflow_run.conclusion", "/workflow_run/conclusion");
+            copy("workflow_run.head_branch", "/workflow_run/head_branch");
+            copy("workflow_run.head_sha", "/workflow_run/head_sha");
+            copy("workflow_run.run_number", "/workflow_run/run_number");
+            copy("workflow_run.run_attempt", "/workflow_run/run_attempt");
+            copy("workflow_run.url", "/workflow_run/html_url");
+            copy(
+                "workflow_run.run_started_at",
+                "/workflow_run/run_started_at",
+            );
+            copy("workflow_run.updated_at", "/workflow_run/updated_at");
+        }
+        _ => {}
+    }
+
+    if let ("push", Some(JsonValue::Array(commits))) = (event, payload.get("commits")) {
+        log.insert("push.commit_count", commits.len() as i64);
+    }
+    if let ("push", Some(JsonValue::String(git_ref))) = (event, payload.get("ref")) {
+        if let Some(branch) = git_ref.strip_prefix("refs/heads/") {
+            log.insert("push.branch", branch);
+        } else if let Some(tag) = git_ref.strip_prefix("refs/tags/") {
+            log.insert("push.tag", tag);
+        }
+    }
+    if event == "workflow_run" {
+        if let Some(duration) = workflow_run_duration(payload) {
+            log.insert("workflow_run.duration_secs", duration);
+        }
+    }
+
+    log
+}
+
+/// How long a completed workflow run took, from when it started to when it last changed.
+fn workflow_run_duration(payload: &JsonValue) -> Option<i64> {
+    let run = payload.get("workflow_run")?;
+    if run.get("status")?.as_str()? != "completed" {
+        return None;
+    }
+    let timestamp = |name: &str| run.get(name)?.as_str()?.parse::<DateTime<Utc>>().ok();
+    Some((timestamp("updated_at")? - timestamp("run_started_at")?).num_seconds())
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "github_webhooks")]
+impl SourceConfig for GithubWebhooksConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let signature = HmacAuthConfig {
+            secret: self.secret.clone(),
+            header: "X-Hub-Signature-256".to_owned(),
+            algorithm: Default::default(),
+            encoding: Default::default(),
+            prefix: Some("sha256=".to_owned()),
+            scheme: Default::default(),
+            tolerance_secs: 0,
+        }
+        .build()?;
+        let source = GithubWebhooksSource {
+            signature,
+            include_payload: self.include_payload,
+        };
+        source.run(
+            self.address,
+            &self.path,
+            HttpMethod::Post,
+            true,
+            &self.tls,
+            &None,
+            &Http2Config::default(),
+            cx,
+            self.acknowledgements,
+        )
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "github_webhooks"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Stream;
+    use openssl::{hash::MessageDigest, pkey::PKey, sign::Signer};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{components, next_addr, spawn_collect_n, wait_for_tcp},
+        SourceSender,
+    };
+
+    const SECRET: &str = "It's a Secret to Everybody";
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<GithubWebhooksConfig>();
+    }
+
+    async fn source() -> (impl Stream<Item = Event>, SocketAddr) {
+        components::init_test();
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let context = SourceContext::new_test(sender);
+        tokio::spawn(async move {
+            GithubWebhooksConfig {
+                address,
+                secret: SECRET.to_owned(),
+                path: default_path(),
+                include_payload: true,
+                tls: None,
+                acknowledgements: true.into(),
+            }
+            .build(context)
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    async fn send(address: SocketAddr, event: &str, body: &str, secret: &str) -> u16 {
+        let key = PKey::hmac(secret.as_bytes()).unwrap();
+        let mut signer = Signer::new(MessageDigest::sha256(), &key).unwrap();
+        signer.update(body.as_bytes()).unwrap();
+        let signature = hex::encode(signer.sign_to_vec().unwrap());
+
+        reqwest::Client::new()
+            .post(&format!("http://{}/", address))
+            .header("X-GitHub-Event", event)
+            .header("X-GitHub-Delivery", "72d3162e-cc78-11e3-81ab-4c9367dc0958")
+            .header("X-GitHub-Hook-ID", "292430182")
+            .header("X-Hub-Signature-256", format!("sha256={}", signature))
+            .body(body.to_owned())
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
+    #[tokio::test]
+    async fn receives_signed_deliveries() {
+        let (rx, addr) = source().await;
+        let body = json!({
+            "zen": "Design for failure.",
+            "hook_id": 292430182,
+            "repository": { "full_name": "vectordotdev/vector" },
+            "sender": { "login": "octocat" },
+        })
+        .to_string();
+
+        let events = spawn_collect_n(
+            async move {
+                assert_eq!(401, send(addr, "ping", &body, "wrong secret").await);
+                assert_eq!(200, send(addr, "ping", &body, SECRET).await);
+            },
+            rx,
+            1,
+        )
+        .await;
+        components::SOURCE_TESTS.assert(&["http_path"]);
+
+        let log = events[0].as_log();
+        assert_eq!(log["event"], "ping".into());
+        assert_eq!(
+            log["delivery_id"],
+            "72d3162e-cc78-11e3-81ab-4c9367dc0958".into()
+        );
+        assert_eq!(log["hook_id"], "292430182".into());
+        assert_eq!(log["repository"], "vectordotdev/vector".into());
+        assert_eq!(log["sender"], "octocat".into());
+        assert_eq!(log["payload.zen"], "Design for failure.".into());
+        assert_eq!(
+            log[log_schema().source_type_key()],
+            "github_webhooks".into()
+        );
+    }
+
+    #[test]
+    fn normalizes_push() {
+        let log = normalize(
+            "push",
+            &json!({
+                "ref": "refs/heads/master",
+                "before": "6113728f27ae82c7b1a177c8d03f9e96e0adf246",
+                "after": "0000000000000000000000000000000000000001",
+                "forced": false,
+                "pusher": { "name": "octocat" },
+                "commits": [{ "id": "a" }, { "id": "b" }],
+                "head_commit": { "id": "b", "message": "Fix all the bugs" },
+            }),
+        );
+
+        assert_eq!(log["push.branch"], "master".into());
+        assert_eq!(log["push.commit_count"], 2.into());
+        assert_eq!(log["push.forced"], false.into());
+        assert_eq!(log["push.pusher"], "octocat".into());
+        assert_eq!(log["push.head_commit_message"], "Fix all the bugs".into());
+        assert!(log.get("push.tag").is_none());
+    }
+
+    #[test]
+    fn normalizes_pull_request() {
+        let log = normalize(
+            "pull_request",
+            &json!({
+                "action": "closed",
+                "pull_request": {
+                    "number": 1347,
+                    "state": "closed",
+                    "merged": true,
+                    "merged_at": "2022-03-01T10:00:00Z",
+                    "closed_at": "2022-03-01T10:00:00Z",
+                    "user": { "login": "octocat" },
+                    "base": { "ref": "master" },
+                    "head": { "ref": "feature", "sha": "abc123" },
+                },
+            }),
+        );
+
+        assert_eq!(log["action"], "closed".into());
+        assert_eq!(log["pull_request.number"], 1347.into());
+        assert_eq!(log["pull_request.merged"], true.into());
+        assert_eq!(log["pull_request.author"], "octocat".into());
+        assert_eq!(log["pull_request.base_ref"], "master".into());
+        assert_eq!(log["pull_request.head_sha"], "abc123".into());
+    }
+
+    #[test]
+    fn normalizes_workflow_run() {
+        let log = normalize(
+            "workflow_run",
+            &json!({
+                "action": "completed",
+                "workflow_run": {
+                    "id": 30433642,
+                    "name": "Build",
+                    "status": "completed",
+                    "conclusion": "failure",
+                    "head_branch": "master",
+                    "run_attempt": 2,
+                    "run_started_at": "2022-03-01T10:00:00Z",
+                    "updated_at": "2022-03-01T10:12:30Z",
+                },
+            }),
+        );
+
+        assert_eq!(log["workflow_run.name"], "Build".into());
+        assert_eq!(log["workflow_run.conclusion"], "failure".into());
+        assert_eq!(log["workflow_run.run_attempt"], 2.into());
+        assert_eq!(log["workflow_run.duration_secs"], 750.into());
+    }
+}
@@ -22,10 +22,12 @@ use crate::{
         SourceConfig, SourceContext, SourceDescription,
     },
     event::Event,
+    http::Http2Config,
     internal_events::{HerokuLogplexRequestReadError, HerokuLogplexRequestReceived},
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
     sources::util::{
-        add_query_parameters, ErrorMessage, HttpSource, HttpSourceAuthConfig, StreamDecodingError,
+        add_query_parameters, ErrorMessage, HttpMethod, HttpSource, HttpSourceAuthConfig,
+        StreamDecodingError,
     },
     tls::TlsConfig,
 };
@@ -83,7 +85,13 @@ impl HttpSource for LogplexSource {
         _full_path: &str,
     ) -> Result<Vec<Event>, ErrorMessage> {
         let mut events = decode_message(self.decoder.clone(), body, header_map)?;
-        add_query_parameters(&mut events, &self.query_parameters, query_parameters);
+        add_query_parameters(
+            &mut events,
+            self.query_parameters
+                .iter()
+                .map(|name| (name.as_str(), name.as_str())),
+            query_parameters,
+        );
         Ok(events)
     }
 }
@@ -100,9 +108,11 @@ impl SourceConfig for LogplexConfig {
         source.run(
             self.address,
             "events",
+            HttpMethod::Post,
             true,
             &self.tls,
             &self.auth,
+            &Http2Config::default(),
             cx,
             self.acknowledgements,
         )
@@ -0,0 +1,368 @@
+use std::{
+    net::SocketAddr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chrono::Utc;
+use futures::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use shared::btreemap;
+use snafu::{ResultExt, Snafu};
+use tokio::net::{lookup_host, UdpSocket};
+
+use super::HostMetrics;
+use crate::event::metric::Metric;
+
+const NTP_PORT: u16 = 123;
+const NTP_PACKET_LEN: usize = 48;
+/// Seconds between the NTP epoch (1900) and the UNIX epoch (1970).
+const NTP_UNIX_OFFSET_SECS: u64 = 2_208_988_800;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(super) struct ClockConfig {
+    /// NTP servers to measure the offset of the system clock against, as `host` or `host:port`.
+    #[serde(default)]
+    ntp_servers: Vec<String>,
+    #[serde(default = "default_ntp_timeout_secs")]
+    ntp_timeout_secs: u64,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            ntp_servers: Vec::new(),
+            ntp_timeout_secs: default_ntp_timeout_secs(),
+        }
+    }
+}
+
+const fn default_ntp_timeout_secs() -> u64 {
+    5
+}
+
+#[derive(Debug, Snafu)]
+enum NtpError {
+    #[snafu(display("Failed to resolve server: {}", source))]
+    Resolve { source: std::io::Error },
+    #[snafu(display("Server resolved to no address"))]
+    NoAddress,
+    #[snafu(display("Failed to exchange packets with server: {}", source))]
+    Exchange { source: std::io::Error },
+    #[snafu(display("Timed out waiting for the server to respond"))]
+    Timeout,
+    #[snafu(display("Invalid response: {}", reason))]
+    InvalidResponse { reason: &'static str },
+}
+
+/// The offset of the system clock from the clock of an NTP server, and the round trip delay of
+/// the exchange used to measure it, in seconds.
+#[derive(Debug, PartialEq)]
+struct NtpMeasurement {
+    offset: f64,
+    delay: f64,
+}
+
+impl HostMetrics {
+    pub async fn clock_metrics(&self) -> Vec<Metric> {
+        let mut metrics = Vec::new();
+
+        #[cfg(target_os = "linux")]
+        metrics.extend(self.kernel_clock_metrics());
+
+        let timeout = Duration::from_secs(self.config.clock.ntp_timeout_secs);
+        let measurements = stream::iter(&self.config.clock.ntp_servers)
+            .then(|server| async move { (server, query_ntp_server(server, timeout).await) })
+            .collect::<Vec<_>>()
+            .await;
+
+        for (server, measurement) in measurements {
+            match measurement {
+                Ok(measurement) => {
+                    let timestamp = Utc::now();
+                    metrics.push(self.gauge(
+                        "clock_ntp_offset_seconds",
+                        timestamp,
+                        measurement.offset,
+                        btreemap! { "server" => server },
+                    ));
+                    metrics.push(self.gauge(
+                        "clock_ntp_delay_seconds",
+                        timestamp,
+                        measurement.delay,
+                        btreemap! { "server" => server },
+                    ));
+                }
+                Err(error) => {
+                    error!(message = "Failed to query NTP server.", %server, %error, internal_log_rate_secs = 60);
+                }
+            }
+        }
+
+        metrics
+    }
+
+    /// Reports the state of the kernel clock, as disciplined by chronyd, ntpd or any other NTP
+    /// daemon.
+    #[cfg(target_os = "linux")]
+    fn kernel_clock_metrics(&self) -> Vec<Metric> {
+        // SAFETY: `timex` is plain old data, and with no `modes` set `adjtimex` only reads the
+        // state of the kernel clock into it.
+        let mut timex: libc::timex = unsafe { std::mem::zeroed() };
+        let state = unsafe { libc::adjtimex(&mut timex) };
+        if state == -1 {
+            let error = std::io::Error::last_os_error();
+            error!(message = "Failed to load kernel clock info.", %error, internal_log_rate_secs = 60);
+            return vec![];
+        }
+
+        let synchronized = state != libc::TIME_ERROR && timex.status & libc::STA_UNSYNC == 0;
+        // The offset is in microseconds, unless the kernel clock runs in nanosecond mode.
+        let offset_unit = if timex.status & libc::STA_NANO == 0 {
+            1e-6
+        } else {
+            1e-9
+        };
+
+        let timestamp = Utc::now();
+        vec![
+            self.gauge(
+                "clock_synchronized",
+                timestamp,
+                if synchronized { 1.0 } else { 0.0 },
+                btreemap! {},
+            ),
+            self.gauge(
+                "clock_offset_seconds",
+                timestamp,
+                timex.offset as f64 * offset_unit,
+                btreemap! {},
+            ),
+            self.gauge(
+                "clock_max_error_seconds",
+                timestamp,
+                timex.maxerror as f64 * 1e-6,
+                btreemap! {},
+            ),
+            self.gauge(
+                "clock_estimated_error_seconds",
+                timestamp,
+                timex.esterror as f64 * 1e-6,
+                btreemap! {},
+            ),
+            self.gauge(
+                "clock_frequency_offset_ppm",
+                timestamp,
+                // The frequency offset is in parts per million, with a 16 bit fractional part.
+                timex.freq as f64 / 65536.0,
+                btreemap! {},
+            ),
+        ]
+    }
+}
+
+async fn resolve(server: &str) -> Result<SocketAddr, NtpError> {
+    // Servers may be given without a port, in which case the default NTP port is used.
+    let mut addresses = match lookup_host(server).await {
+        Ok(addresses) => addresses,
+        Err(_) => lookup_host((server, NTP_PORT))
+            .await
+            .context(ResolveSnafu)?,
+    };
+    addresses.next().ok_or(NtpError::NoAddress)
+}
+
+/// Measures the offset of the system clock from the clock of `server`, using SNTP (RFC 4330).
+async fn query_ntp_server(server: &str, timeout: Duration) -> Result<NtpMeasurement, NtpError> {
+    let address = resolve(server).await?;
+    let local_address: SocketAddr = if address.is_ipv4() {
+        ([0, 0, 0, 0], 0).into()
+    } else {
+        ([0u16; 8], 0).into()
+    };
+
+    let exchange = async {
+        let socket = UdpSocket::bind(local_address).await?;
+        socket.connect(address).await?;
+
+        let transmit = ntp_timestamp(SystemTime::now());
+        socket.send(&ntp_request(transmit)).await?;
+
+        let mut response = [0; NTP_PACKET_LEN];
+        let len = socket.recv(&mut response).await?;
+        let received = ntp_timestamp(SystemTime::now());
+        Ok::<_, std::io::Error>((transmit, response, len, received))
+    };
+
+    let (transmit, response, len, received) = tokio::time::timeout(timeout, exchange)
+        .await
+        .map_err(|_| NtpError::Timeout)?
+        .context(ExchangeSnafu)?;
+
+    parse_ntp_response(&response[..len], transmit, received)
+}
+
+/// Converts a system time to an NTP timestamp, which counts seconds since 1900 in 32.32 fixed
+/// point.
+fn ntp_timestamp(time: SystemTime) -> u64 {
+    let since_unix_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_unix_epoch.as_secs() + NTP_UNIX_OFFSET_SECS;
+    let fraction = (u64::from(since_unix_epoch.subsec_nanos()) << 32) / 1_000_000_000;
+    (secs << 32) | fraction
+}
+
+/// The signed difference between two NTP timestamps, in seconds.
+fn ntp_difference(later: u64, earlier: u64) -> f64 {
+    // Wrapping keeps the difference right across the rollover of the NTP era.
+    later.wrapping_sub(earlier) as i64 as f64 / 4_294_967_296.0
+}
+
+fn ntp_request(transmit: u64) -> [u8; NTP_PACKET_LEN] {
+    let mut request = [0; NTP_PACKET_LEN];
+    // No leap second warning, version 4, client mode.
+    request[0] = 0x23;
+    // The server copies the transmit timestamp into the originate timestamp of its response,
+    // which identifies the response.
+    request[40..48].copy_from_slice(&transmit.to_be_bytes());
+    request
+}
+
+fn parse_ntp_response(
+    response: &[u8],
+    transmit: u64,
+    received: u64,
+) -> Result<NtpMeasurement, NtpError> {
+    if response.len() < NTP_PACKET_LEN {
+        return Err(NtpError::InvalidResponse {
+            reason: "packet too short",
+        });
+    }
+
+    let timestamp = |offset: usize| {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&response[offset..offset + 8]);
+        u64::from_be_bytes(bytes)
+    };
+
+    let mode = response[0] & 0b111;
+    if mode != 4 {
+        return Err(NtpError::InvalidResponse {
+            reason: "not a server response",
+        });
+    }
+    // A stratum of 0 is a "kiss-o'-death" message, telling the client to go away.
+    if response[1] == 0 {
+        return Err(NtpError::InvalidResponse {
+            reason: "server refused the request",
+        });
+    }
+    if timestamp(24) != transmit {
+        return Err(NtpError::InvalidResponse {
+            reason: "response doesn't match the request",
+        });
+    }
+
+    let server_received = timestamp(32);
+    let server_transmitted = timestamp(40);
+
+    Ok(NtpMeasurement {
+        offset: (ntp_difference(server_received, transmit)
+            + ntp_difference(server_transmitted, received))
+            / 2.0,
+        delay: ntp_difference(received, transmit)
+            - ntp_difference(server_transmitted, server_received),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::HostMetricsConfig, *};
+    use crate::event::metric::MetricValue;
+
+    fn ntp_response(request: &[u8], server_received: u64, server_transmitted: u64) -> Vec<u8> {
+        let mut response = vec![0; NTP_PACKET_LEN];
+        // No leap second warning, version 4, server mode.
+        response[0] = 0x24;
+        response[1] = 2;
+        response[24..32].copy_from_slice(&request[40..48]);
+        response[32..40].copy_from_slice(&server_received.to_be_bytes());
+        response[40..48].copy_from_slice(&server_transmitted.to_be_bytes());
+        response
+    }
+
+    #[test]
+    fn converts_to_ntp_timestamps() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_500);
+        assert_eq!(
+            ntp_timestamp(time),
+            ((NTP_UNIX_OFFSET_SECS + 1) << 32) | (1 << 31)
+        );
+    }
+
+    #[test]
+    fn measures_offset_and_delay() {
+        let second = 1 << 32;
+        let transmit = ntp_timestamp(SystemTime::now());
+        let request = ntp_request(transmit);
+        // The server clock is 10 seconds ahead, each way takes 1 second, and the server takes
+        // 1 second to respond.
+        let response = ntp_response(&request, transmit + 11 * second, transmit + 12 * second);
+
+        assert_eq!(
+            parse_ntp_response(&response, transmit, transmit + 3 * second).unwrap(),
+            NtpMeasurement {
+                offset: 10.0,
+                delay: 2.0,
+            }
+        );
+        // The clock of the server can also be behind.
+        let response = ntp_response(&request, transmit - 9 * second, transmit - 8 * second);
+        assert_eq!(
+            parse_ntp_response(&response, transmit, transmit + 3 * second).unwrap(),
+            NtpMeasurement {
+                offset: -10.0,
+                delay: 2.0,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unrelated_responses() {
+        let transmit = ntp_timestamp(SystemTime::now());
+        let response = ntp_response(&ntp_request(transmit + 1), transmit, transmit);
+
+        assert!(matches!(
+            parse_ntp_response(&response, transmit, transmit),
+            Err(NtpError::InvalidResponse { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn queries_ntp_servers() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let address = server.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut request = [0; NTP_PACKET_LEN];
+            let (_, peer) = server.recv_from(&mut request).await.unwrap();
+            let now = ntp_timestamp(SystemTime::now() + Duration::from_secs(60));
+            let response = ntp_response(&request, now, now);
+            server.send_to(&response, peer).await.unwrap();
+        });
+
+        let mut config = HostMetricsConfig::default();
+        config.clock.ntp_servers = vec![address.to_string()];
+        let metrics = HostMetrics::new(config).clock_metrics().await;
+
+        let offset = metrics
+            .iter()
+            .find(|metric| metric.name() == "clock_ntp_offset_seconds")
+            .expect("Missing offset metric");
+        assert_eq!(
+            offset.tag_value("server").as_deref(),
+            Some(address.to_string().as_str())
+        );
+        match offset.value() {
+            MetricValue::Gauge { value } => assert!((*value - 60.0).abs() < 1.0, "{}", value),
+            value => panic!("Unexpected value {:?}", value),
+        }
+    }
+}
@@ -28,6 +28,7 @@ use crate::{
 
 #[cfg(target_os = "linux")]
 mod cgroups;
+mod clock;
 mod cpu;
 mod disk;
 mod filesystem;
@@ -39,6 +40,7 @@ mod network;
 enum Collector {
     #[cfg(target_os = "linux")]
     CGroups,
+    Clock,
     Cpu,
     Disk,
     Filesystem,
@@ -81,6 +83,8 @@ pub struct HostMetricsConfig {
     #[serde(default)]
     cgroups: cgroups::CGroupsConfig,
     #[serde(default)]
+    clock: clock::ClockConfig,
+    #[serde(default)]
     disk: disk::DiskConfig,
     #[serde(default)]
     filesystem: filesystem::FilesystemConfig,
@@ -191,6 +195,9 @@ impl HostMetrics {
         if self.config.has_collector(Collector::CGroups) {
             metrics.extend(add_collector("cgroups", self.cgroups_metrics().await));
         }
+        if self.config.has_collector(Collector::Clock) {
+            metrics.extend(add_collector("clock", self.clock_metrics().await));
+        }
         if self.config.has_collector(Collector::Cpu) {
             metrics.extend(add_collector("cpu", self.cpu_metrics().await));
         }
@@ -555,6 +562,7 @@ pub(self) mod tests {
         for collector in &[
             #[cfg(target_os = "linux")]
             Collector::CGroups,
+            Collector::Clock,
             Collector::Cpu,
             Collector::Disk,
             Collector::Filesystem,
@@ -3,9 +3,14 @@ use std::{collections::HashMap, net::SocketAddr};
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
 use http::StatusCode;
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use snafu::Snafu;
 use tokio_util::codec::Decoder;
-use warp::http::{HeaderMap, HeaderValue};
+use warp::{
+    http::{HeaderMap, HeaderName, HeaderValue},
+    reply::Response,
+};
 
 use crate::{
     codecs::{
@@ -19,24 +24,155 @@ use crate::{
         SourceConfig, SourceContext, SourceDescription,
     },
     event::{Event, Value},
+    http::Http2Config,
     serde::{bool_or_struct, default_decoding, default_framing_stream_based},
     sources::util::{
-        add_query_parameters, Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
+        add_query_parameters, Encoding, ErrorMessage, HmacAuth, HmacAuthConfig, HttpMethod,
+        HttpSource, HttpSourceAuthConfig, JwtAuth, JwtAuthConfig,
     },
     tls::TlsConfig,
 };
 
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Response status code {} is not a 2xx status code", status_code))]
+    InvalidStatusCode { status_code: u16 },
+    #[snafu(display("Invalid response header {:?}", name))]
+    InvalidHeader { name: String },
+    #[snafu(display(
+        "Only one of `auth` and `jwt` may be set, as both use the Authorization header"
+    ))]
+    ConflictingAuth,
+}
+
+/// A request header or query parameter to capture, either into a field of the same name or,
+/// with `{ name = "...", field = "..." }`, into a field of its own.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum CaptureField {
+    Name(String),
+    Renamed { name: String, field: String },
+}
+
+impl CaptureField {
+    fn name(&self) -> &str {
+        match self {
+            Self::Name(name) | Self::Renamed { name, .. } => name,
+        }
+    }
+
+    fn field(&self) -> &str {
+        match self {
+            Self::Name(name) => name,
+            Self::Renamed { field, .. } => field,
+        }
+    }
+}
+
+impl From<String> for CaptureField {
+    fn from(name: String) -> Self {
+        Self::Name(name)
+    }
+}
+
+/// The response sent once a request's events have been accepted.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct ResponseConfig {
+    #[serde(default = "default_status_code")]
+    status_code: u16,
+    /// The response body, in which `{{ count }}` is replaced with the number of events
+    /// received in the request.
+    body: Option<String>,
+    #[serde(default)]
+    headers: IndexMap<String, String>,
+}
+
+impl Default for ResponseConfig {
+    fn default() -> Self {
+        Self {
+            status_code: default_status_code(),
+            body: None,
+            headers: IndexMap::new(),
+        }
+    }
+}
+
+const fn default_status_code() -> u16 {
+    200
+}
+
+impl ResponseConfig {
+    fn build(&self) -> crate::Result<HttpResponse> {
+        let status = StatusCode::from_u16(self.status_code)
+            .ok()
+            .filter(StatusCode::is_success)
+            .ok_or(BuildError::InvalidStatusCode {
+                status_code: self.status_code,
+            })?;
+
+        let headers = self
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let header_name = HeaderName::from_bytes(name.as_bytes());
+                let header_value = HeaderValue::from_str(value);
+                match (header_name, header_value) {
+                    (Ok(name), Ok(value)) => Ok((name, value)),
+                    _ => Err(BuildError::InvalidHeader { name: name.clone() }),
+                }
+            })
+            .collect::<Result<HeaderMap, _>>()?;
+
+        Ok(HttpResponse {
+            status,
+            body: self.body.clone(),
+            headers,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+struct HttpResponse {
+    status: StatusCode,
+    body: Option<String>,
+    headers: HeaderMap,
+}
+
+impl HttpResponse {
+    fn render(&self, events_count: usize) -> Response {
+        let body = self.body.as_ref().map_or_else(String::new, |body| {
+            let count = events_count.to_string();
+            body.replace("{{ count }}", &count)
+                .replace("{{count}}", &count)
+        });
+
+        let mut response = Response::new(body.into());
+        *response.status_mut() = self.status;
+        response.headers_mut().extend(self.headers.clone());
+        response
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct SimpleHttpConfig {
     address: SocketAddr,
     #[serde(default)]
     encoding: Option<Encoding>,
     #[serde(default)]
-    headers: Vec<String>,
+    headers: Vec<CaptureField>,
+    #[serde(default)]
+    query_parameters: Vec<CaptureField>,
     #[serde(default)]
-    query_parameters: Vec<String>,
+    method: HttpMethod,
+    #[serde(default)]
+    response: ResponseConfig,
     tls: Option<TlsConfig>,
     auth: Option<HttpSourceAuthConfig>,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    http2: Http2Config,
+    hmac: Option<HmacAuthConfig>,
+    jwt: Option<JwtAuthConfig>,
     #[serde(default = "crate::serde::default_true")]
     strict_path: bool,
     #[serde(default = "default_path")]
@@ -60,8 +196,13 @@ impl GenerateConfig for SimpleHttpConfig {
             encoding: None,
             headers: Vec::new(),
             query_parameters: Vec::new(),
+            method: HttpMethod::Post,
+            response: ResponseConfig::default(),
             tls: None,
             auth: None,
+            http2: Http2Config::default(),
+            hmac: None,
+            jwt: None,
             path_key: "path".to_string(),
             path: "/".to_string(),
             strict_path: true,
@@ -83,10 +224,13 @@ fn default_path_key() -> String {
 
 #[derive(Clone)]
 struct SimpleHttpSource {
-    headers: Vec<String>,
-    query_parameters: Vec<String>,
+    headers: Vec<CaptureField>,
+    query_parameters: Vec<CaptureField>,
     path_key: String,
     decoder: codecs::Decoder,
+    response: HttpResponse,
+    hmac: Option<HmacAuth>,
+    jwt: Option<JwtAuth>,
 }
 
 impl HttpSource for SimpleHttpSource {
@@ -118,7 +262,13 @@ impl HttpSource for SimpleHttpSource {
         }
 
         add_headers(&mut events, &self.headers, header_map);
-        add_query_parameters(&mut events, &self.query_parameters, query_parameters);
+        add_query_parameters(
+            &mut events,
+            self.query_parameters
+                .iter()
+                .map(|capture| (capture.name(), capture.field())),
+            query_parameters,
+        );
         add_path(&mut events, self.path_key.as_str(), request_path);
 
         let now = Utc::now();
@@ -131,6 +281,20 @@ impl HttpSource for SimpleHttpSource {
 
         Ok(events)
     }
+
+    fn authenticate(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), ErrorMessage> {
+        if let Some(hmac) = &self.hmac {
+            hmac.is_valid(headers, body)?;
+        }
+        if let Some(jwt) = &self.jwt {
+            jwt.is_valid(headers)?;
+        }
+        Ok(())
+    }
+
+    fn build_response(&self, events_count: usize) -> Response {
+        self.response.render(events_count)
+    }
 }
 
 #[async_trait::async_trait]
@@ -173,19 +337,32 @@ impl SourceConfig for SimpleHttpConfig {
             )
         };
 
+        if self.auth.is_some() && self.jwt.is_some() {
+            return Err(BuildError::ConflictingAuth.into());
+        }
+
         let decoder = DecodingConfig::new(framing, decoding).build()?;
         let source = SimpleHttpSource {
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
             path_key: self.path_key.clone(),
             decoder,
+            response: self.response.build()?,
+            hmac: self.hmac.as_ref().map(HmacAuthConfig::build).transpose()?,
+            jwt: self
+                .jwt
+                .as_ref()
+                .map(|jwt| jwt.build(&cx.proxy))
+                .transpose()?,
         };
         source.run(
             self.address,
             self.path.as_str(),
+            self.method,
             self.strict_path,
             &self.tls,
             &self.auth,
+            &self.http2,
             cx,
             self.acknowledgements,
         )
@@ -212,13 +389,13 @@ fn add_path(events: &mut [Event], key: &str, path: &str) {
     }
 }
 
-fn add_headers(events: &mut [Event], headers_config: &[String], headers: HeaderMap) {
-    for header_name in headers_config {
-        let value = headers.get(header_name).map(HeaderValue::as_bytes);
+fn add_headers(events: &mut [Event], headers_config: &[CaptureField], headers: HeaderMap) {
+    for header in headers_config {
+        let value = headers.get(header.name()).map(HeaderValue::as_bytes);
 
         for event in events.iter_mut() {
             event.as_mut_log().try_insert_flat(
-                header_name as &str,
+                header.field(),
                 Value::from(value.map(Bytes::copy_from_slice)),
             );
         }
@@ -274,11 +451,16 @@ mod tests {
         tokio::spawn(async move {
             SimpleHttpConfig {
                 address,
-                headers,
+                headers: headers.into_iter().map(Into::into).collect(),
                 encoding: None,
-                query_parameters,
+                query_parameters: query_parameters.into_iter().map(Into::into).collect(),
+                method: Default::default(),
+                response: Default::default(),
                 tls: None,
                 auth: None,
+                http2: Default::default(),
+                hmac: None,
+                jwt: None,
                 strict_path,
                 path_key,
                 path,
@@ -944,4 +1126,194 @@ mod tests {
 
         assert_eq!(events.len(), 1);
     }
+
+    async fn source_from_toml(config: &str) -> (impl Stream<Item = Event>, SocketAddr) {
+        components::init_test();
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let config: SimpleHttpConfig =
+            toml::from_str(&format!("address = \"{}\"\n{}", address, config)).unwrap();
+        let context = SourceContext::new_test(sender);
+        tokio::spawn(async move {
+            config.build(context).await.unwrap().await.unwrap();
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    #[tokio::test]
+    async fn http_put() {
+        let (rx, addr) = source_from_toml(r#"method = "PUT""#).await;
+
+        let events = spawn_collect_n(
+            async move {
+                let client = reqwest::Client::new();
+                let url = format!("http://{}/", addr);
+                let status = client.post(&url).body("test body").send().await.unwrap();
+                assert_eq!(405, status.status().as_u16());
+                let status = client.put(&url).body("test body").send().await.unwrap();
+                assert_eq!(200, status.status().as_u16());
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "test body".into());
+    }
+
+    #[tokio::test]
+    async fn http2_prior_knowledge() {
+        let (rx, addr) = source_from_toml("http2 = true").await;
+
+        let events = spawn_collect_n(
+            async move {
+                let response = reqwest::Client::builder()
+                    .http2_prior_knowledge()
+                    .build()
+                    .unwrap()
+                    .post(&format!("http://{}/", addr))
+                    .body("test body")
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(response.version(), reqwest::Version::HTTP_2);
+                assert_eq!(200, response.status().as_u16());
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        assert_eq!(events.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn http_response_template() {
+        let (rx, addr) = source_from_toml(
+            r#"
+                [response]
+                status_code = 202
+                body = '{"accepted":{{ count }}}'
+                headers.content-type = "application/json"
+            "#,
+        )
+        .await;
+
+        spawn_collect_n(
+            async move {
+                let response = reqwest::Client::new()
+                    .post(&format!("http://{}/", addr))
+                    .body("one\ntwo\nthree")
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(202, response.status().as_u16());
+                assert_eq!(
+                    response.headers()["content-type"].to_str().unwrap(),
+                    "application/json"
+                );
+                assert_eq!(response.text().await.unwrap(), r#"{"accepted":3}"#);
+            },
+            rx,
+            3,
+        )
+        .await;
+    }
+
+    #[test]
+    fn http_response_rejects_non_success_status() {
+        let config: SimpleHttpConfig = toml::from_str(
+            r#"
+                address = "0.0.0.0:8080"
+                response.status_code = 500
+            "#,
+        )
+        .unwrap();
+        assert!(config.response.build().is_err());
+    }
+
+    #[tokio::test]
+    async fn http_renamed_headers_and_query_parameters() {
+        let (rx, addr) = source_from_toml(
+            r#"
+                headers = ["User-Agent", { name = "X-Request-Id", field = "request_id" }]
+                query_parameters = [{ name = "src", field = "source" }]
+            "#,
+        )
+        .await;
+
+        let events = spawn_collect_n(
+            async move {
+                let mut headers = HeaderMap::new();
+                headers.insert("User-Agent", "test_client".parse().unwrap());
+                headers.insert("X-Request-Id", "abc123".parse().unwrap());
+                let response = reqwest::Client::new()
+                    .post(&format!("http://{}/?src=staging", addr))
+                    .headers(headers)
+                    .body("test body")
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(200, response.status().as_u16());
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        let log = events[0].as_log();
+        assert_eq!(log["User-Agent"], "test_client".into());
+        assert_eq!(log["request_id"], "abc123".into());
+        assert_eq!(log["source"], "staging".into());
+        assert!(log.get("X-Request-Id").is_none());
+        assert!(log.get("src").is_none());
+    }
+
+    #[tokio::test]
+    async fn http_hmac_signature() {
+        let (rx, addr) = source_from_toml(
+            r#"
+                hmac.secret = "It's a Secret to Everybody"
+                hmac.header = "X-Hub-Signature-256"
+                hmac.prefix = "sha256="
+            "#,
+        )
+        .await;
+
+        let events = spawn_collect_n(
+            async move {
+                let client = reqwest::Client::new();
+                let url = format!("http://{}/", addr);
+                let unsigned = client
+                    .post(&url)
+                    .body("Hello, World!")
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(401, unsigned.status().as_u16());
+                let signed = client
+                    .post(&url)
+                    .header(
+                        "X-Hub-Signature-256",
+                        "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17",
+                    )
+                    .body("Hello, World!")
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(200, signed.status().as_u16());
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "Hello, World!".into()
+        );
+    }
 }
@@ -0,0 +1,156 @@
+use bytes::Bytes;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tracing::Instrument;
+
+use crate::{
+    config::{log_schema, DataType, Output, SourceConfig, SourceContext, SourceDescription},
+    event::Event,
+    shutdown::ShutdownSignal,
+    trace, SourceSender,
+};
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct InternalTracesConfig {
+    host_key: Option<String>,
+    pid_key: Option<String>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<InternalTracesConfig>("internal_traces")
+}
+
+impl_generate_config_from_default!(InternalTracesConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "internal_traces")]
+impl SourceConfig for InternalTracesConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let host_key = self
+            .host_key
+            .as_deref()
+            .unwrap_or_else(|| log_schema().host_key())
+            .to_owned();
+        let pid_key = self.pid_key.as_deref().unwrap_or("pid").to_owned();
+
+        // Spans created while forwarding spans would otherwise be traced in turn.
+        let span = info_span!("internal_traces");
+        Ok(Box::pin(
+            run(host_key, pid_key, cx.out, cx.shutdown).instrument(span),
+        ))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "internal_traces"
+    }
+}
+
+async fn run(
+    host_key: String,
+    pid_key: String,
+    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let hostname = crate::get_hostname();
+    let pid = std::process::id();
+
+    let mut rx = BroadcastStream::new(trace::subscribe_spans()).take_until(shutdown);
+
+    while let Some(res) = rx.next().await {
+        match res {
+            Ok(mut log) => {
+                if let Ok(hostname) = &hostname {
+                    log.insert(host_key.clone(), hostname.to_owned());
+                }
+                log.insert(pid_key.clone(), pid);
+                log.try_insert(
+                    log_schema().source_type_key(),
+                    Bytes::from("internal_traces"),
+                );
+                if let Err(error) = out.send(Event::from(log)).await {
+                    error!(message = "Error sending span.", %error);
+                    return Err(());
+                }
+            }
+            Err(BroadcastStreamRecvError::Lagged(_)) => (),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::time::{sleep, Duration};
+    use vector_core::event::Value;
+
+    use super::*;
+    use crate::{event::Event, source_sender::ReceiverStream, test_util::collect_ready, trace};
+
+    #[test]
+    fn generates_config() {
+        crate::test_util::test_generate_config::<InternalTracesConfig>();
+    }
+
+    #[tokio::test]
+    async fn receives_spans() {
+        let test_id: u8 = rand::random();
+        let start = chrono::Utc::now();
+        trace::init(false, false, "debug");
+
+        let rx = start_source().await;
+
+        error_span!("outer", %test_id).in_scope(|| {
+            let _inner = error_span!("inner", step = 1).entered();
+        });
+
+        sleep(Duration::from_millis(1)).await;
+        let mut events = collect_ready(rx).await;
+        let test_id = Value::from(test_id.to_string());
+        let end = chrono::Utc::now();
+        events.retain(|event| {
+            let log = event.as_log();
+            log["message"] == "inner".into() || log.get("fields.test_id") == Some(&test_id)
+        });
+
+        assert_eq!(events.len(), 2);
+        let inner = events[0].as_log();
+        let outer = events[1].as_log();
+        assert_eq!(inner["message"], "inner".into());
+        assert_eq!(inner["fields.step"], 1.into());
+        assert_eq!(outer["message"], "outer".into());
+        assert_eq!(inner["parent_span_id"], outer["span_id"]);
+        assert_eq!(inner["trace_id"], outer["trace_id"]);
+        assert!(outer.get("parent_span_id").is_none());
+
+        for event in events {
+            let log = event.as_log();
+            let timestamp = *log["timestamp"]
+                .as_timestamp()
+                .expect("timestamp isn't a timestamp");
+            assert!(timestamp >= start);
+            assert!(timestamp <= end);
+            assert_eq!(log["metadata.kind"], "span".into());
+            assert_eq!(log["metadata.level"], "ERROR".into());
+            assert_eq!(log["source_type"], "internal_traces".into());
+        }
+    }
+
+    async fn start_source() -> ReceiverStream<Event> {
+        let (tx, rx) = SourceSender::new_test();
+
+        let source = InternalTracesConfig::default()
+            .build(SourceContext::new_test(tx))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        sleep(Duration::from_millis(1)).await;
+        rx
+    }
+}
@@ -0,0 +1,163 @@
+use std::net::SocketAddr;
+
+use futures::{FutureExt, StreamExt};
+use tonic::{
+    transport::{server::Connected, Server},
+    Request, Response, Status,
+};
+use vector_core::{
+    event::{BatchNotifier, BatchStatus},
+    ByteSizeOf,
+};
+
+use super::proto;
+use crate::{
+    internal_events::{EventsReceived, TcpBytesReceived},
+    shutdown::{ShutdownSignal, ShutdownSignalToken},
+    sources::util::AfterReadExt as _,
+    tls::MaybeTlsSettings,
+    SourceSender,
+};
+
+#[derive(Debug, Clone)]
+struct Service {
+    pipeline: SourceSender,
+    acknowledgements: bool,
+}
+
+#[tonic::async_trait]
+impl proto::Service for Service {
+    async fn post_spans(
+        &self,
+        request: Request<proto::PostSpansRequest>,
+    ) -> Result<Response<proto::PostSpansResponse>, Status> {
+        let batch = request
+            .into_inner()
+            .batch
+            .ok_or_else(|| Status::invalid_argument("Missing batch"))?;
+        let mut events = super::batch_to_events(batch);
+
+        emit!(&EventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        });
+
+        let receiver = BatchNotifier::maybe_apply_to_events(self.acknowledgements, &mut events);
+
+        self.pipeline
+            .clone()
+            .send_batch(events)
+            .await
+            .map_err(|error| Status::unavailable(error.to_string()))?;
+
+        match receiver {
+            Some(receiver) => match receiver.await {
+                BatchStatus::Errored => Err(Status::internal("Delivery error")),
+                BatchStatus::Rejected => Err(Status::data_loss("Delivery failed")),
+                BatchStatus::Delivered => Ok(Response::new(proto::PostSpansResponse {})),
+            },
+            None => Ok(Response::new(proto::PostSpansResponse {})),
+        }
+    }
+}
+
+pub(super) async fn run(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    pipeline: SourceSender,
+    shutdown: ShutdownSignal,
+    acknowledgements: bool,
+) -> crate::Result<()> {
+    let _span = crate::trace::current_span();
+
+    let service = proto::Server::new(Service {
+        pipeline,
+        acknowledgements,
+    });
+    let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
+
+    let listener = tls_settings.bind(&address).await?;
+    let stream = listener.accept_stream().map(|result| {
+        result.map(|socket| {
+            let peer_addr = socket.connect_info().remote_addr.ip();
+            socket.after_read(move |byte_size| {
+                emit!(&TcpBytesReceived {
+                    byte_size,
+                    peer_addr
+                })
+            })
+        })
+    });
+
+    Server::builder()
+        .add_service(service)
+        .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
+        .await?;
+
+    drop(rx.await);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::SourceContext,
+        event::EventStatus,
+        sources::jaeger::{GrpcConfig, JaegerConfig},
+        test_util::{next_addr, spawn_collect_n, wait_for_tcp},
+    };
+
+    #[tokio::test]
+    async fn receives_spans() {
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let config = JaegerConfig {
+            grpc: Some(GrpcConfig { address, tls: None }),
+            thrift_http: None,
+            acknowledgements: true.into(),
+        };
+        let source = config.build(SourceContext::new_test(sender)).await.unwrap();
+        tokio::spawn(source);
+        wait_for_tcp(address).await;
+
+        let request = proto::PostSpansRequest {
+            batch: Some(proto::Batch {
+                spans: vec![proto::Span {
+                    trace_id: vec![1; 16],
+                    span_id: vec![2; 8],
+                    operation_name: "get /api".to_owned(),
+                    ..Default::default()
+                }],
+                process: Some(proto::Process {
+                    service_name: "backend".to_owned(),
+                    tags: Vec::new(),
+                }),
+            }),
+        };
+
+        let events = spawn_collect_n(
+            async move {
+                proto::collector_service_client::CollectorServiceClient::connect(format!(
+                    "http://{}",
+                    address
+                ))
+                .await
+                .unwrap()
+                .post_spans(request)
+                .await
+                .unwrap();
+            },
+            recv,
+            1,
+        )
+        .await;
+
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "get /api".into());
+        assert_eq!(log["trace_id"], "01010101010101010101010101010101".into());
+        assert_eq!(log["service"], "backend".into());
+    }
+}
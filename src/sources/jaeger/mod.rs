@@ -0,0 +1,378 @@
+use std::{collections::BTreeMap, net::SocketAddr};
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{future::try_join_all, FutureExt};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+use crate::{
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource,
+        SourceConfig, SourceContext, SourceDescription,
+    },
+    event::{Event, LogEvent, Value},
+    http::Http2Config,
+    serde::bool_or_struct,
+    sources::util::{HttpMethod, HttpSource},
+    tls::{MaybeTlsSettings, TlsConfig},
+};
+
+mod grpc;
+mod thrift;
+
+#[allow(clippy::clone_on_ref_ptr)]
+mod proto {
+    tonic::include_proto!("jaeger.api_v2");
+
+    pub use collector_service_server::{
+        CollectorService as Service, CollectorServiceServer as Server,
+    };
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct JaegerConfig {
+    /// Receives batches from the Jaeger agent, or from clients exporting with gRPC.
+    grpc: Option<GrpcConfig>,
+    /// Receives Thrift batches posted by clients that report straight to the collector.
+    thrift_http: Option<ThriftHttpConfig>,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct GrpcConfig {
+    address: SocketAddr,
+    tls: Option<TlsConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct ThriftHttpConfig {
+    address: SocketAddr,
+    #[serde(default = "default_thrift_http_path")]
+    path: String,
+    tls: Option<TlsConfig>,
+}
+
+fn default_thrift_http_path() -> String {
+    "/api/traces".to_owned()
+}
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one of `grpc` or `thrift_http` must be configured"))]
+    NoProtocols,
+}
+
+inventory::submit! {
+    SourceDescription::new::<JaegerConfig>("jaeger")
+}
+
+impl GenerateConfig for JaegerConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            grpc: Some(GrpcConfig {
+                address: "0.0.0.0:14250".parse().unwrap(),
+                tls: None,
+            }),
+            thrift_http: Some(ThriftHttpConfig {
+                address: "0.0.0.0:14268".parse().unwrap(),
+                path: default_thrift_http_path(),
+                tls: None,
+            }),
+            acknowledgements: AcknowledgementsConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "jaeger")]
+impl SourceConfig for JaegerConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.grpc.is_none() && self.thrift_http.is_none() {
+            return Err(BuildError::NoProtocols.into());
+        }
+
+        let acknowledgements = cx.globals.acknowledgements.merge(&self.acknowledgements);
+        let mut sources = Vec::new();
+
+        if let Some(grpc) = &self.grpc {
+            let tls_settings = MaybeTlsSettings::from_config(&grpc.tls, true)?;
+            let source = grpc::run(
+                grpc.address,
+                tls_settings,
+                cx.out.clone(),
+                cx.shutdown.clone(),
+                acknowledgements.enabled(),
+            )
+            .map(|result| {
+                result.map_err(|error| {
+                    error!(message = "Source future failed.", %error);
+                })
+            });
+            sources.push(source.boxed());
+        }
+
+        if let Some(thrift_http) = &self.thrift_http {
+            sources.push(thrift::ThriftHttpSource.run(
+                thrift_http.address,
+                &thrift_http.path,
+                HttpMethod::Post,
+                true,
+                &thrift_http.tls,
+                &None,
+                &Http2Config::default(),
+                cx,
+                acknowledgements,
+            )?);
+        }
+
+        Ok(Box::pin(
+            try_join_all(sources).map(|result| result.map(|_| ())),
+        ))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "jaeger"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        self.grpc
+            .iter()
+            .map(|grpc| Resource::tcp(grpc.address))
+            .chain(
+                self.thrift_http
+                    .iter()
+                    .map(|thrift_http| Resource::tcp(thrift_http.address)),
+            )
+            .collect()
+    }
+}
+
+/// Converts each span of a batch to a log event, with the same layout for the IDs and timings as
+/// the `internal_traces` and `zipkin` sources.
+fn batch_to_events(batch: proto::Batch) -> Vec<Event> {
+    let process = batch.process;
+    batch
+        .spans
+        .into_iter()
+        .map(|span| Event::from(span_to_log(span, process.as_ref())))
+        .collect()
+}
+
+fn span_to_log(span: proto::Span, batch_process: Option<&proto::Process>) -> LogEvent {
+    let mut log = LogEvent::default();
+    log.insert(log_schema().message_key(), span.operation_name);
+    log.insert(
+        log_schema().timestamp_key(),
+        span.start_time
+            .and_then(to_timestamp)
+            .unwrap_or_else(Utc::now),
+    );
+    log.insert(log_schema().source_type_key(), Bytes::from("jaeger"));
+    log.insert("trace_id", hex::encode(&span.trace_id));
+    log.insert("span_id", hex::encode(&span.span_id));
+
+    // The parent is the first span this one is a child of within the same trace.
+    if let Some(parent) = span.references.iter().find(|reference| {
+        reference.ref_type == proto::SpanRefType::ChildOf as i32
+            && reference.trace_id == span.trace_id
+    }) {
+        log.insert("parent_span_id", hex::encode(&parent.span_id));
+    }
+    if !span.references.is_empty() {
+        let references = span
+            .references
+            .iter()
+            .map(|reference| {
+                let ref_type = match proto::SpanRefType::from_i32(reference.ref_type) {
+                    Some(proto::SpanRefType::FollowsFrom) => "follows_from",
+                    _ => "child_of",
+                };
+                let mut map = BTreeMap::new();
+                map.insert(
+                    "trace_id".to_owned(),
+                    hex::encode(&reference.trace_id).into(),
+                );
+                map.insert("span_id".to_owned(), hex::encode(&reference.span_id).into());
+                map.insert("ref_type".to_owned(), ref_type.into());
+                Value::Map(map)
+            })
+            .collect::<Vec<_>>();
+        log.insert("references", references);
+    }
+
+    if let Some(duration) = span.duration {
+        log.insert(
+            "duration_ns",
+            duration.seconds * 1_000_000_000 + duration.nanos as i64,
+        );
+    }
+
+    let tags = key_values(span.tags);
+    if let Some(Value::Bytes(kind)) = tags.get("span.kind") {
+        log.insert("kind", kind.clone());
+    }
+    if !tags.is_empty() {
+        log.insert("tags", tags);
+    }
+
+    if !span.logs.is_empty() {
+        let logs = span
+            .logs
+            .into_iter()
+            .map(|span_log| {
+                let mut map = BTreeMap::new();
+                if let Some(timestamp) = span_log.timestamp.and_then(to_timestamp) {
+                    map.insert("timestamp".to_owned(), timestamp.into());
+                }
+                map.insert("fields".to_owned(), Value::Map(key_values(span_log.fields)));
+                Value::Map(map)
+            })
+            .collect::<Vec<_>>();
+        log.insert("logs", logs);
+    }
+
+    // Spans only carry their own process when it differs from the batch's.
+    if let Some(process) = span.process.as_ref().or(batch_process) {
+        log.insert("service", process.service_name.clone());
+        if !process.tags.is_empty() {
+            log.insert("process_tags", key_values(process.tags.clone()));
+        }
+    }
+
+    if !span.warnings.is_empty() {
+        log.insert("warnings", span.warnings);
+    }
+
+    log
+}
+
+fn to_timestamp(timestamp: prost_types::Timestamp) -> Option<DateTime<Utc>> {
+    let nanos = u32::try_from(timestamp.nanos).ok()?;
+    Utc.timestamp_opt(timestamp.seconds, nanos).single()
+}
+
+fn key_values(key_values: Vec<proto::KeyValue>) -> BTreeMap<String, Value> {
+    key_values
+        .into_iter()
+        .map(|key_value| {
+            let value = match proto::ValueType::from_i32(key_value.v_type) {
+                Some(proto::ValueType::Bool) => Value::from(key_value.v_bool),
+                Some(proto::ValueType::Int64) => Value::from(key_value.v_int64),
+                Some(proto::ValueType::Float64) => Value::from(key_value.v_float64),
+                Some(proto::ValueType::Binary) => Value::from(Bytes::from(key_value.v_binary)),
+                Some(proto::ValueType::String) | None => Value::from(key_value.v_str),
+            };
+            (key_value.key, value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<JaegerConfig>();
+    }
+
+    #[tokio::test]
+    async fn requires_a_protocol() {
+        let config = toml::from_str::<JaegerConfig>("").unwrap();
+        let (sender, _) = crate::SourceSender::new_test();
+        let error = config
+            .build(SourceContext::new_test(sender))
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "At least one of `grpc` or `thrift_http` must be configured"
+        );
+    }
+
+    fn key_value(key: &str, v_type: proto::ValueType) -> proto::KeyValue {
+        proto::KeyValue {
+            key: key.to_owned(),
+            v_type: v_type as i32,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn converts_spans() {
+        let trace_id = hex::decode("5982fe77008310cc80f1da5e10147517").unwrap();
+        let batch = proto::Batch {
+            spans: vec![proto::Span {
+                trace_id: trace_id.clone(),
+                span_id: hex::decode("67fae42571535f60").unwrap(),
+                operation_name: "get /api".to_owned(),
+                references: vec![proto::SpanRef {
+                    trace_id,
+                    span_id: hex::decode("90394f6bcffb5d13").unwrap(),
+                    ref_type: proto::SpanRefType::ChildOf as i32,
+                }],
+                start_time: Some(prost_types::Timestamp {
+                    seconds: 1_600_000_000,
+                    nanos: 123_456_000,
+                }),
+                duration: Some(prost_types::Duration {
+                    seconds: 1,
+                    nanos: 500,
+                }),
+                tags: vec![
+                    proto::KeyValue {
+                        v_str: "server".to_owned(),
+                        ..key_value("span.kind", proto::ValueType::String)
+                    },
+                    proto::KeyValue {
+                        v_int64: 200,
+                        ..key_value("http.status_code", proto::ValueType::Int64)
+                    },
+                    proto::KeyValue {
+                        v_bool: true,
+                        ..key_value("error", proto::ValueType::Bool)
+                    },
+                ],
+                ..Default::default()
+            }],
+            process: Some(proto::Process {
+                service_name: "backend".to_owned(),
+                tags: vec![proto::KeyValue {
+                    v_str: "host-1".to_owned(),
+                    ..key_value("hostname", proto::ValueType::String)
+                }],
+            }),
+        };
+
+        let events = batch_to_events(batch);
+        assert_eq!(events.len(), 1);
+
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "get /api".into());
+        assert_eq!(
+            log["timestamp"],
+            Utc.timestamp(1_600_000_000, 123_456_000).into()
+        );
+        assert_eq!(log["trace_id"], "5982fe77008310cc80f1da5e10147517".into());
+        assert_eq!(log["span_id"], "67fae42571535f60".into());
+        assert_eq!(log["parent_span_id"], "90394f6bcffb5d13".into());
+        assert_eq!(log["references[0].ref_type"], "child_of".into());
+        assert_eq!(log["duration_ns"], 1_000_000_500.into());
+        assert_eq!(log["kind"], "server".into());
+        assert_eq!(log["tags.error"], true.into());
+        assert_eq!(log["service"], "backend".into());
+        assert_eq!(log["process_tags.hostname"], "host-1".into());
+        assert_eq!(log[log_schema().source_type_key()], "jaeger".into());
+    }
+}
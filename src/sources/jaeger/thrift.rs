@@ -0,0 +1,539 @@
+//! Decoding of the Jaeger Thrift batches that clients post to the collector's HTTP endpoint, which
+//! are encoded with the Thrift binary protocol. Batches are decoded into the generic structure of
+//! the protocol first, and then converted to the protobuf model shared with the gRPC endpoint.
+
+use std::collections::{BTreeMap, HashMap};
+
+use bytes::{Buf, Bytes};
+use snafu::Snafu;
+use warp::{
+    http::{HeaderMap, StatusCode},
+    reply::Response,
+    Reply,
+};
+
+use super::proto;
+use crate::{
+    event::Event,
+    sources::util::{ErrorMessage, HttpSource},
+};
+
+#[derive(Clone)]
+pub(super) struct ThriftHttpSource;
+
+impl HttpSource for ThriftHttpSource {
+    fn build_events(
+        &self,
+        body: Bytes,
+        _header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        _path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        let batch = decode_batch(body).map_err(|error| {
+            ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid Thrift payload: {}", error),
+            )
+        })?;
+        Ok(super::batch_to_events(batch))
+    }
+
+    fn build_response(&self, _events_count: usize) -> Response {
+        // Matches the `202 Accepted` the Jaeger collector responds with.
+        StatusCode::ACCEPTED.into_response()
+    }
+}
+
+#[derive(Debug, PartialEq, Snafu)]
+enum DecodeError {
+    #[snafu(display("Unexpected end of payload"))]
+    UnexpectedEof,
+    #[snafu(display("Unknown field type {}", field_type))]
+    UnknownType { field_type: u8 },
+    #[snafu(display("Payload is nested too deeply"))]
+    TooDeep,
+    #[snafu(display("Invalid `{}` field", field))]
+    InvalidField { field: &'static str },
+}
+
+/// Thrift structs are only nested a few levels deep in Jaeger batches.
+const MAX_DEPTH: usize = 16;
+
+const STOP: u8 = 0;
+const BOOL: u8 = 2;
+const BYTE: u8 = 3;
+const DOUBLE: u8 = 4;
+const I16: u8 = 6;
+const I32: u8 = 8;
+const I64: u8 = 10;
+const STRING: u8 = 11;
+const STRUCT: u8 = 12;
+const MAP: u8 = 13;
+const SET: u8 = 14;
+const LIST: u8 = 15;
+
+#[derive(Debug, PartialEq)]
+enum Thrift {
+    Bool(bool),
+    Byte(i8),
+    Double(f64),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Binary(Bytes),
+    Struct(Fields),
+    List(Vec<Thrift>),
+    Map(Vec<(Thrift, Thrift)>),
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct Fields(BTreeMap<i16, Thrift>);
+
+impl Fields {
+    fn i32(&self, id: i16) -> Option<i32> {
+        match self.0.get(&id) {
+            Some(Thrift::I32(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn i64(&self, id: i16) -> Option<i64> {
+        match self.0.get(&id) {
+            Some(Thrift::I64(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn bool(&self, id: i16) -> Option<bool> {
+        match self.0.get(&id) {
+            Some(Thrift::Bool(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn double(&self, id: i16) -> Option<f64> {
+        match self.0.get(&id) {
+            Some(Thrift::Double(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn binary(&self, id: i16) -> Option<&Bytes> {
+        match self.0.get(&id) {
+            Some(Thrift::Binary(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    fn string(&self, id: i16) -> Option<String> {
+        self.binary(id)
+            .map(|value| String::from_utf8_lossy(value).into_owned())
+    }
+
+    fn get_struct(&self, id: i16) -> Option<&Fields> {
+        match self.0.get(&id) {
+            Some(Thrift::Struct(value)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The structs in a list field, skipping any other elements.
+    fn structs(&self, id: i16) -> impl Iterator<Item = &Fields> {
+        let items = match self.0.get(&id) {
+            Some(Thrift::List(items)) => items.as_slice(),
+            _ => &[],
+        };
+        items.iter().filter_map(|item| match item {
+            Thrift::Struct(fields) => Some(fields),
+            _ => None,
+        })
+    }
+}
+
+struct Reader {
+    buf: Bytes,
+}
+
+impl Reader {
+    fn ensure(&self, len: usize) -> Result<(), DecodeError> {
+        if self.buf.remaining() < len {
+            Err(DecodeError::UnexpectedEof)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DecodeError> {
+        self.ensure(1)?;
+        Ok(self.buf.get_u8())
+    }
+
+    fn read_i16(&mut self) -> Result<i16, DecodeError> {
+        self.ensure(2)?;
+        Ok(self.buf.get_i16())
+    }
+
+    fn read_i32(&mut self) -> Result<i32, DecodeError> {
+        self.ensure(4)?;
+        Ok(self.buf.get_i32())
+    }
+
+    fn read_i64(&mut self) -> Result<i64, DecodeError> {
+        self.ensure(8)?;
+        Ok(self.buf.get_i64())
+    }
+
+    /// Reads the size of a string or collection, which can't be larger than what's left of the
+    /// payload since every element takes at least a byte.
+    fn read_size(&mut self) -> Result<usize, DecodeError> {
+        let size = self.read_i32()?;
+        let size = usize::try_from(size).map_err(|_| DecodeError::UnexpectedEof)?;
+        self.ensure(size)?;
+        Ok(size)
+    }
+
+    fn read_value(&mut self, field_type: u8, depth: usize) -> Result<Thrift, DecodeError> {
+        if depth > MAX_DEPTH {
+            return Err(DecodeError::TooDeep);
+        }
+
+        Ok(match field_type {
+            BOOL => Thrift::Bool(self.read_u8()? != 0),
+            BYTE => Thrift::Byte(self.read_u8()? as i8),
+            DOUBLE => {
+                self.ensure(8)?;
+                Thrift::Double(self.buf.get_f64())
+            }
+            I16 => Thrift::I16(self.read_i16()?),
+            I32 => Thrift::I32(self.read_i32()?),
+            I64 => Thrift::I64(self.read_i64()?),
+            STRING => {
+                let len = self.read_size()?;
+                Thrift::Binary(self.buf.split_to(len))
+            }
+            STRUCT => Thrift::Struct(self.read_struct(depth + 1)?),
+            MAP => {
+                let key_type = self.read_u8()?;
+                let value_type = self.read_u8()?;
+                let size = self.read_size()?;
+                let mut entries = Vec::with_capacity(size);
+                for _ in 0..size {
+                    let key = self.read_value(key_type, depth + 1)?;
+                    let value = self.read_value(value_type, depth + 1)?;
+                    entries.push((key, value));
+                }
+                Thrift::Map(entries)
+            }
+            SET | LIST => {
+                let element_type = self.read_u8()?;
+                let size = self.read_size()?;
+                let mut items = Vec::with_capacity(size);
+                for _ in 0..size {
+                    items.push(self.read_value(element_type, depth + 1)?);
+                }
+                Thrift::List(items)
+            }
+            field_type => return Err(DecodeError::UnknownType { field_type }),
+        })
+    }
+
+    fn read_struct(&mut self, depth: usize) -> Result<Fields, DecodeError> {
+        let mut fields = Fields::default();
+        loop {
+            let field_type = self.read_u8()?;
+            if field_type == STOP {
+                return Ok(fields);
+            }
+            let id = self.read_i16()?;
+            let value = self.read_value(field_type, depth)?;
+            fields.0.insert(id, value);
+        }
+    }
+}
+
+fn decode_batch(body: Bytes) -> Result<proto::Batch, DecodeError> {
+    let batch = Reader { buf: body }.read_struct(0)?;
+
+    let process = batch
+        .get_struct(1)
+        .map(decode_process)
+        .ok_or(DecodeError::InvalidField { field: "process" })?;
+    let spans = batch
+        .structs(2)
+        .map(decode_span)
+        .collect::<Result<_, _>>()?;
+
+    Ok(proto::Batch {
+        spans,
+        process: Some(process),
+    })
+}
+
+fn decode_process(process: &Fields) -> proto::Process {
+    proto::Process {
+        service_name: process.string(1).unwrap_or_default(),
+        tags: process.structs(2).map(decode_tag).collect(),
+    }
+}
+
+fn trace_id(low: i64, high: i64) -> Vec<u8> {
+    let mut id = high.to_be_bytes().to_vec();
+    id.extend_from_slice(&low.to_be_bytes());
+    id
+}
+
+fn decode_span(span: &Fields) -> Result<proto::Span, DecodeError> {
+    let trace_id_low = span.i64(1).ok_or(DecodeError::InvalidField {
+        field: "traceIdLow",
+    })?;
+    let trace_id_high = span.i64(2).unwrap_or_default();
+    let span_id = span
+        .i64(3)
+        .ok_or(DecodeError::InvalidField { field: "spanId" })?;
+    let trace_id = trace_id(trace_id_low, trace_id_high);
+
+    let mut references = span
+        .structs(6)
+        .map(|reference| proto::SpanRef {
+            trace_id: self::trace_id(
+                reference.i64(2).unwrap_or_default(),
+                reference.i64(3).unwrap_or_default(),
+            ),
+            span_id: reference.i64(4).unwrap_or_default().to_be_bytes().to_vec(),
+            ref_type: match reference.i32(1) {
+                Some(1) => proto::SpanRefType::FollowsFrom as i32,
+                _ => proto::SpanRefType::ChildOf as i32,
+            },
+        })
+        .collect::<Vec<_>>();
+
+    // Older clients only set the parent span ID rather than a reference to it.
+    let parent_span_id = span.i64(4).unwrap_or_default();
+    if parent_span_id != 0
+        && !references
+            .iter()
+            .any(|reference| reference.span_id == parent_span_id.to_be_bytes())
+    {
+        references.insert(
+            0,
+            proto::SpanRef {
+                trace_id: trace_id.clone(),
+                span_id: parent_span_id.to_be_bytes().to_vec(),
+                ref_type: proto::SpanRefType::ChildOf as i32,
+            },
+        );
+    }
+
+    Ok(proto::Span {
+        trace_id,
+        span_id: span_id.to_be_bytes().to_vec(),
+        operation_name: span.string(5).unwrap_or_default(),
+        references,
+        flags: span.i32(7).unwrap_or_default() as u32,
+        start_time: span.i64(8).map(micros_to_timestamp),
+        duration: span.i64(9).map(micros_to_duration),
+        tags: span.structs(10).map(decode_tag).collect(),
+        logs: span
+            .structs(11)
+            .map(|log| proto::Log {
+                timestamp: log.i64(1).map(micros_to_timestamp),
+                fields: log.structs(2).map(decode_tag).collect(),
+            })
+            .collect(),
+        ..Default::default()
+    })
+}
+
+/// Thrift tags number their value types differently than the protobuf model.
+fn decode_tag(tag: &Fields) -> proto::KeyValue {
+    let mut key_value = proto::KeyValue {
+        key: tag.string(1).unwrap_or_default(),
+        ..Default::default()
+    };
+    match tag.i32(2) {
+        Some(1) => {
+            key_value.v_type = proto::ValueType::Float64 as i32;
+            key_value.v_float64 = tag.double(4).unwrap_or_default();
+        }
+        Some(2) => {
+            key_value.v_type = proto::ValueType::Bool as i32;
+            key_value.v_bool = tag.bool(5).unwrap_or_default();
+        }
+        Some(3) => {
+            key_value.v_type = proto::ValueType::Int64 as i32;
+            key_value.v_int64 = tag.i64(6).unwrap_or_default();
+        }
+        Some(4) => {
+            key_value.v_type = proto::ValueType::Binary as i32;
+            key_value.v_binary = tag
+                .binary(7)
+                .map(|value| value.to_vec())
+                .unwrap_or_default();
+        }
+        _ => {
+            key_value.v_type = proto::ValueType::String as i32;
+            key_value.v_str = tag.string(3).unwrap_or_default();
+        }
+    }
+    key_value
+}
+
+fn micros_to_timestamp(micros: i64) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: micros.div_euclid(1_000_000),
+        nanos: (micros.rem_euclid(1_000_000) * 1_000) as i32,
+    }
+}
+
+fn micros_to_duration(micros: i64) -> prost_types::Duration {
+    prost_types::Duration {
+        seconds: micros / 1_000_000,
+        nanos: (micros % 1_000_000 * 1_000) as i32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{BufMut, BytesMut};
+
+    use super::*;
+
+    /// Writes Thrift binary protocol fields for the tests.
+    #[derive(Default)]
+    struct Writer(BytesMut);
+
+    impl Writer {
+        fn header(&mut self, field_type: u8, id: i16) -> &mut Self {
+            self.0.put_u8(field_type);
+            self.0.put_i16(id);
+            self
+        }
+
+        fn i32(&mut self, id: i16, value: i32) -> &mut Self {
+            self.header(I32, id).0.put_i32(value);
+            self
+        }
+
+        fn i64(&mut self, id: i16, value: i64) -> &mut Self {
+            self.header(I64, id).0.put_i64(value);
+            self
+        }
+
+        fn string(&mut self, id: i16, value: &str) -> &mut Self {
+            self.header(STRING, id).0.put_i32(value.len() as i32);
+            self.0.put_slice(value.as_bytes());
+            self
+        }
+
+        fn begin_struct(&mut self, id: i16) -> &mut Self {
+            self.header(STRUCT, id)
+        }
+
+        fn begin_list(&mut self, id: i16, size: i32) -> &mut Self {
+            self.header(LIST, id).0.put_u8(STRUCT);
+            self.0.put_i32(size);
+            self
+        }
+
+        fn end(&mut self) -> &mut Self {
+            self.0.put_u8(STOP);
+            self
+        }
+    }
+
+    fn batch() -> Bytes {
+        let mut writer = Writer::default();
+        writer
+            .begin_struct(1)
+            .string(1, "backend")
+            .begin_list(2, 1)
+            .string(1, "hostname")
+            .i32(2, 0)
+            .string(3, "host-1")
+            .end()
+            .end()
+            .begin_list(2, 1)
+            .i64(1, 0x80f1da5e10147517_u64 as i64)
+            .i64(2, 0x5982fe77008310cc)
+            .i64(3, 0x67fae42571535f60)
+            .i64(4, 0x90394f6bcffb5d13_u64 as i64)
+            .string(5, "get /api")
+            .i32(7, 1)
+            .i64(8, 1_600_000_000_123_456)
+            .i64(9, 1_500)
+            .begin_list(10, 2)
+            .string(1, "span.kind")
+            .i32(2, 0)
+            .string(3, "server")
+            .end()
+            .string(1, "http.status_code")
+            .i32(2, 3)
+            .i64(6, 200)
+            .end()
+            .end()
+            .end();
+        writer.0.freeze()
+    }
+
+    #[test]
+    fn decodes_batches() {
+        let batch = decode_batch(batch()).unwrap();
+        let process = batch.process.unwrap();
+        assert_eq!(process.service_name, "backend");
+        assert_eq!(process.tags[0].v_str, "host-1");
+
+        let span = &batch.spans[0];
+        assert_eq!(
+            hex::encode(&span.trace_id),
+            "5982fe77008310cc80f1da5e10147517"
+        );
+        assert_eq!(hex::encode(&span.span_id), "67fae42571535f60");
+        assert_eq!(hex::encode(&span.references[0].span_id), "90394f6bcffb5d13");
+        assert_eq!(span.operation_name, "get /api");
+        assert_eq!(
+            span.start_time,
+            Some(prost_types::Timestamp {
+                seconds: 1_600_000_000,
+                nanos: 123_456_000,
+            })
+        );
+        assert_eq!(
+            span.duration,
+            Some(prost_types::Duration {
+                seconds: 0,
+                nanos: 1_500_000,
+            })
+        );
+        assert_eq!(span.tags[0].v_str, "server");
+        assert_eq!(span.tags[1].v_type, proto::ValueType::Int64 as i32);
+        assert_eq!(span.tags[1].v_int64, 200);
+    }
+
+    #[test]
+    fn rejects_truncated_batches() {
+        let batch = batch();
+        assert_eq!(
+            decode_batch(batch.slice(..batch.len() - 3)),
+            Err(DecodeError::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_collections() {
+        let mut writer = Writer::default();
+        writer.begin_list(2, i32::MAX);
+        assert_eq!(
+            decode_batch(writer.0.freeze()),
+            Err(DecodeError::UnexpectedEof)
+        );
+    }
+
+    #[test]
+    fn rejects_deep_nesting() {
+        let mut writer = Writer::default();
+        for _ in 0..MAX_DEPTH + 2 {
+            writer.begin_struct(1);
+        }
+        assert_eq!(decode_batch(writer.0.freeze()), Err(DecodeError::TooDeep));
+    }
+}
@@ -247,6 +247,11 @@ async fn kafka_source(
                                         log.insert(partition_key, Value::from(msg_partition));
                                         log.insert(offset_key, Value::from(msg_offset));
                                         log.insert(headers_key, Value::from(headers_map.clone()));
+
+                                        let metadata = log.metadata_mut();
+                                        metadata.set_source_field("topic", msg_topic.clone());
+                                        metadata.set_source_field("partition", msg_partition);
+                                        metadata.set_source_field("offset", msg_offset);
                                     }
 
                                     yield event;
@@ -2,6 +2,7 @@ use std::{
     collections::{BTreeMap, VecDeque},
     convert::TryFrom,
     io::{self, Read},
+    time::Duration,
 };
 
 use bytes::{Buf, Bytes, BytesMut};
@@ -11,7 +12,10 @@ use smallvec::{smallvec, SmallVec};
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::Decoder;
 
-use super::util::{SocketListenAddr, StreamDecodingError, TcpSource, TcpSourceAck, TcpSourceAcker};
+use super::util::{
+    ConnectionLimits, SocketListenAddr, StreamDecodingError, TcpSource, TcpSourceAck,
+    TcpSourceAcker,
+};
 use crate::{
     config::{
         log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource,
@@ -29,10 +33,14 @@ pub struct LogstashConfig {
     address: SocketListenAddr,
     keepalive: Option<TcpKeepaliveConfig>,
     tls: Option<TlsConfig>,
+    tls_client_metadata_key: Option<String>,
     receive_buffer_bytes: Option<usize>,
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
+    #[serde(alias = "max_connections")]
     connection_limit: Option<u32>,
+    connection_limit_per_ip: Option<u32>,
+    connection_rate_limit: Option<u32>,
 }
 
 inventory::submit! {
@@ -45,9 +53,12 @@ impl GenerateConfig for LogstashConfig {
             address: SocketListenAddr::SocketAddr("0.0.0.0:5044".parse().unwrap()),
             keepalive: None,
             tls: None,
+            tls_client_metadata_key: None,
             receive_buffer_bytes: None,
             acknowledgements: Default::default(),
             connection_limit: None,
+            connection_limit_per_ip: None,
+            connection_rate_limit: None,
         })
         .unwrap()
     }
@@ -59,6 +70,7 @@ impl SourceConfig for LogstashConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let source = LogstashSource {
             timestamp_converter: types::Conversion::Timestamp(cx.globals.timezone),
+            tls_client_metadata_key: self.tls_client_metadata_key.clone(),
         };
         let shutdown_secs = 30;
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
@@ -70,7 +82,12 @@ impl SourceConfig for LogstashConfig {
             self.receive_buffer_bytes,
             cx,
             self.acknowledgements,
-            self.connection_limit,
+            ConnectionLimits {
+                max_connections: self.connection_limit,
+                max_connections_per_ip: self.connection_limit_per_ip,
+                max_accepts_per_sec: self.connection_rate_limit,
+                ..Default::default()
+            },
         )
     }
 
@@ -90,6 +107,7 @@ impl SourceConfig for LogstashConfig {
 #[derive(Debug, Clone)]
 struct LogstashSource {
     timestamp_converter: crate::types::Conversion,
+    tls_client_metadata_key: Option<String>,
 }
 
 impl TcpSource for LogstashSource {
@@ -126,20 +144,36 @@ impl TcpSource for LogstashSource {
     fn build_acker(&self, frames: &[Self::Item]) -> Self::Acker {
         LogstashAcker::new(frames)
     }
+
+    fn tls_client_metadata_key(&self) -> Option<&str> {
+        self.tls_client_metadata_key.as_deref()
+    }
 }
 
+// Logstash sends a keepalive ack when it hasn't written to a connection for this long while it
+// still has events to acknowledge, so that clients waiting on a slow pipeline don't time out.
+//
+// https://github.com/logstash-plugins/logstash-input-beats/blob/27bad62a26a81fc000a9d21495b8dc7174ab63e9/src/main/java/org/logstash/beats/BeatsHandler.java
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(5);
+
 struct LogstashAcker {
-    sequence_number: u32,
+    // The highest sequence number received from each window, in the order the windows were
+    // received. Sequence numbers restart at 1 with each window, and clients that pipeline windows
+    // expect an ack for the end of each of them.
+    sequence_numbers: Vec<u32>,
     protocol_version: Option<LogstashProtocolVersion>,
 }
 
 impl LogstashAcker {
     fn new(frames: &[LogstashEventFrame]) -> Self {
-        let mut sequence_number = 0;
+        let mut sequence_numbers: Vec<u32> = Vec::new();
         let mut protocol_version = None;
 
         for frame in frames {
-            sequence_number = std::cmp::max(sequence_number, frame.sequence_number);
+            match sequence_numbers.last_mut() {
+                Some(last) if frame.sequence_number > *last => *last = frame.sequence_number,
+                _ => sequence_numbers.push(frame.sequence_number),
+            }
             // We assume that it's valid to ack via any of the protocol versions that we've seen in
             // a set of frames from a single stream, so here we just take the last. In reality, we
             // do not expect stream with multiple protocol versions to occur.
@@ -147,26 +181,52 @@ impl LogstashAcker {
         }
 
         Self {
-            sequence_number,
+            sequence_numbers,
             protocol_version,
         }
     }
+
+    fn ack_frame(
+        bytes: &mut Vec<u8>,
+        protocol_version: LogstashProtocolVersion,
+        sequence_number: u32,
+    ) {
+        bytes.push(protocol_version.into());
+        bytes.push(LogstashFrameType::Ack.into());
+        bytes.extend(sequence_number.to_be_bytes().iter());
+    }
 }
 
 impl TcpSourceAcker for LogstashAcker {
     // https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md#ack-frame-type
+    //
+    // Events may be acknowledged before a whole window has been received, for example when the
+    // rest of the window hasn't arrived yet because of backpressure. The client then treats it as
+    // a partial ack and keeps waiting for the rest.
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes> {
         match (ack, self.protocol_version) {
             (TcpSourceAck::Ack, Some(protocol_version)) => {
-                let mut bytes: Vec<u8> = Vec::with_capacity(6);
-                bytes.push(protocol_version.into());
-                bytes.push(LogstashFrameType::Ack.into());
-                bytes.extend(self.sequence_number.to_be_bytes().iter());
+                let mut bytes: Vec<u8> = Vec::with_capacity(6 * self.sequence_numbers.len());
+                for sequence_number in self.sequence_numbers {
+                    Self::ack_frame(&mut bytes, protocol_version, sequence_number);
+                }
                 Some(Bytes::from(bytes))
             }
             _ => None,
         }
     }
+
+    // Version 2 clients ignore acks for sequence number 0, other than resetting their timeout.
+    fn build_keepalive(&self) -> Option<(Duration, Bytes)> {
+        match self.protocol_version {
+            Some(LogstashProtocolVersion::V2) => {
+                let mut bytes: Vec<u8> = Vec::with_capacity(6);
+                Self::ack_frame(&mut bytes, LogstashProtocolVersion::V2, 0);
+                Some((KEEPALIVE_INTERVAL, Bytes::from(bytes)))
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -369,8 +429,9 @@ impl Decoder for LogstashDecoder {
                     }
                 }
                 // The window size indicates how many events the writer will send before waiting
-                // for acks. As we forward events as we get them, and ack as they are received, we
-                // do not need to keep track of this.
+                // for acks. As we forward events as we get them, and ack as they are delivered, we
+                // do not need to keep track of this. The end of a window is instead detected by the
+                // sequence numbers restarting, see `LogstashAcker`.
                 //
                 // https://github.com/logstash-plugins/logstash-input-beats/blob/master/PROTOCOL.md#window-size-frame-type
                 LogstashDecoderReadState::ReadFrame(_protocol, LogstashFrameType::WindowSize) => {
@@ -406,7 +467,7 @@ impl Decoder for LogstashDecoder {
 
                     let mut fields: BTreeMap<String, serde_json::Value> = BTreeMap::new();
                     for _ in 0..pair_count {
-                        if src.remaining() < 4 {
+                        if rest.remaining() < 4 {
                             return Ok(None);
                         }
                         let key_length = rest.get_u32() as usize;
@@ -417,7 +478,7 @@ impl Decoder for LogstashDecoder {
                         let (key, right) = rest.split_at(key_length);
                         rest = right;
 
-                        if src.remaining() < 4 {
+                        if rest.remaining() < 4 {
                             return Ok(None);
                         }
                         let value_length = rest.get_u32() as usize;
@@ -591,10 +652,13 @@ mod test {
         let source = LogstashConfig {
             address: address.into(),
             tls: None,
+            tls_client_metadata_key: None,
             keepalive: None,
             receive_buffer_bytes: None,
             acknowledgements: true.into(),
             connection_limit: None,
+            connection_limit_per_ip: None,
+            connection_rate_limit: None,
         }
         .build(SourceContext::new_test(sender))
         .await
@@ -623,6 +687,88 @@ mod test {
         assert!(log.get("timestamp").is_some());
     }
 
+    #[test]
+    fn decode_compressed_frames() {
+        use flate2::{write::ZlibEncoder, Compression};
+        use std::io::Write;
+
+        let mut payload = BytesMut::new();
+        payload.put(encode_req(1, &[("message", "one")]));
+        payload.put(encode_req(2, &[("message", "two")]));
+
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&payload).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let mut src = BytesMut::new();
+        src.put_u8(b'2');
+        src.put_u8(b'W');
+        src.put_u32(2);
+        src.put_u8(b'2');
+        src.put_u8(b'C');
+        src.put_u32(compressed.len() as u32);
+        src.put(&compressed[..]);
+
+        let mut decoder = LogstashDecoder::new();
+        let mut frames = Vec::new();
+        while let Some((frame, _)) = decoder.decode(&mut src).unwrap() {
+            frames.push(frame);
+        }
+
+        assert_eq!(src.len(), 0);
+        assert_eq!(
+            frames
+                .iter()
+                .map(|frame| (frame.sequence_number, frame.fields["message"].clone()))
+                .collect::<Vec<_>>(),
+            vec![(1, "one".into()), (2, "two".into())]
+        );
+    }
+
+    #[test]
+    fn decode_partial_data_frame() {
+        let req = encode_req(1, &[("message", "Hello, world!")]);
+        let mut decoder = LogstashDecoder::new();
+
+        let mut src = BytesMut::from(&req[..req.len() - 4]);
+        assert!(decoder.decode(&mut src).unwrap().is_none());
+
+        src.put(&req[req.len() - 4..]);
+        let (frame, _) = decoder.decode(&mut src).unwrap().unwrap();
+        assert_eq!(frame.sequence_number, 1);
+        assert_eq!(src.len(), 0);
+    }
+
+    fn frame(sequence_number: u32) -> LogstashEventFrame {
+        LogstashEventFrame {
+            protocol: LogstashProtocolVersion::V2,
+            sequence_number,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn acks_end_of_each_window() {
+        let frames = [frame(3), frame(4), frame(1), frame(2)];
+        let ack = LogstashAcker::new(&frames)
+            .build_ack(TcpSourceAck::Ack)
+            .unwrap();
+
+        assert_eq!(&ack[..], b"2A\0\0\0\x042A\0\0\0\x02");
+    }
+
+    #[test]
+    fn keepalive_acks_sequence_zero() {
+        let (_, keepalive) = LogstashAcker::new(&[frame(1)]).build_keepalive().unwrap();
+        assert_eq!(&keepalive[..], b"2A\0\0\0\0");
+
+        let v1 = LogstashEventFrame {
+            protocol: LogstashProtocolVersion::V1,
+            ..frame(1)
+        };
+        assert!(LogstashAcker::new(&[v1]).build_keepalive().is_none());
+    }
+
     fn encode_req(seq: u32, pairs: &[(&str, &str)]) -> Bytes {
         let mut req = BytesMut::new();
         req.put_u8(b'2');
@@ -742,10 +888,13 @@ mod integration_tests {
             LogstashConfig {
                 address: address.into(),
                 tls,
+                tls_client_metadata_key: None,
                 keepalive: None,
                 receive_buffer_bytes: None,
                 acknowledgements: false.into(),
                 connection_limit: None,
+                connection_limit_per_ip: None,
+                connection_rate_limit: None,
             }
             .build(SourceContext::new_test(sender))
             .await
@@ -2,6 +2,8 @@ use snafu::Snafu;
 
 #[cfg(feature = "sources-apache_metrics")]
 pub mod apache_metrics;
+#[cfg(feature = "sources-aws_cloudwatch_logs")]
+pub mod aws_cloudwatch_logs;
 #[cfg(feature = "sources-aws_ecs_metrics")]
 pub mod aws_ecs_metrics;
 #[cfg(feature = "sources-aws_kinesis_firehose")]
@@ -10,6 +12,8 @@ pub mod aws_kinesis_firehose;
 pub mod aws_s3;
 #[cfg(feature = "sources-aws_sqs")]
 pub mod aws_sqs;
+#[cfg(feature = "sources-azure_event_hubs")]
+pub mod azure_event_hubs;
 #[cfg(any(feature = "sources-datadog_agent"))]
 pub mod datadog;
 #[cfg(feature = "sources-demo_logs")]
@@ -26,6 +30,10 @@ pub mod exec;
 pub mod file;
 #[cfg(feature = "sources-fluent")]
 pub mod fluent;
+#[cfg(feature = "sources-gcp_cloud_logging")]
+pub mod gcp_cloud_logging;
+#[cfg(feature = "sources-github_webhooks")]
+pub mod github_webhooks;
 #[cfg(feature = "sources-heroku_logs")]
 pub mod heroku_logs;
 #[cfg(feature = "sources-host_metrics")]
@@ -36,6 +44,10 @@ pub mod http;
 pub mod internal_logs;
 #[cfg(feature = "sources-internal_metrics")]
 pub mod internal_metrics;
+#[cfg(feature = "sources-internal_traces")]
+pub mod internal_traces;
+#[cfg(feature = "sources-jaeger")]
+pub mod jaeger;
 #[cfg(all(unix, feature = "sources-journald"))]
 pub mod journald;
 #[cfg(all(feature = "sources-kafka", feature = "rdkafka"))]
@@ -46,6 +58,8 @@ pub mod kubernetes_logs;
 pub mod logstash;
 #[cfg(feature = "sources-mongodb_metrics")]
 pub mod mongodb_metrics;
+#[cfg(all(feature = "sources-named_pipe", any(unix, windows)))]
+pub mod named_pipe;
 #[cfg(all(feature = "sources-nats"))]
 pub mod nats;
 #[cfg(feature = "sources-nginx_metrics")]
@@ -58,6 +72,8 @@ pub mod prometheus;
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
 pub mod splunk_hec;
+#[cfg(feature = "sources-sse")]
+pub mod sse;
 #[cfg(feature = "sources-statsd")]
 pub mod statsd;
 #[cfg(feature = "sources-stdin")]
@@ -66,6 +82,10 @@ pub mod stdin;
 pub mod syslog;
 #[cfg(feature = "sources-vector")]
 pub mod vector;
+#[cfg(feature = "sources-websocket")]
+pub mod websocket;
+#[cfg(feature = "sources-zipkin")]
+pub mod zipkin;
 
 pub(crate) mod util;
 
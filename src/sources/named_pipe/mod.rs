@@ -0,0 +1,269 @@
+use std::{path::PathBuf, time::Duration};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::FramedRead;
+
+use crate::{
+    codecs::{
+        decoding::{DecodingConfig, DeserializerConfig, FramingConfig},
+        Decoder,
+    },
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    internal_events::{NamedPipeEventsReceived, NamedPipeOpenError},
+    serde::{default_decoding, default_framing_stream_based},
+    shutdown::ShutdownSignal,
+    sources::util::StreamDecodingError,
+    SourceSender,
+};
+
+#[cfg(unix)]
+mod unix;
+#[cfg(unix)]
+use unix::Listener;
+#[cfg(windows)]
+mod windows;
+#[cfg(windows)]
+use windows::Listener;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct NamedPipeConfig {
+    /// The FIFO to read from on Unix, or the name of the pipe to create on Windows, such as
+    /// `\\.\pipe\vector`.
+    pub path: PathBuf,
+    pub host_key: Option<String>,
+    /// How long to wait before trying again when the pipe can't be opened.
+    #[serde(default = "default_reopen_delay_ms")]
+    pub reopen_delay_ms: u64,
+    #[serde(default = "default_framing_stream_based")]
+    pub framing: Box<dyn FramingConfig>,
+    #[serde(default = "default_decoding")]
+    pub decoding: Box<dyn DeserializerConfig>,
+}
+
+const fn default_reopen_delay_ms() -> u64 {
+    1000
+}
+
+inventory::submit! {
+    SourceDescription::new::<NamedPipeConfig>("named_pipe")
+}
+
+impl GenerateConfig for NamedPipeConfig {
+    fn generate_config() -> toml::Value {
+        let path = if cfg!(windows) {
+            r"\\.\pipe\vector"
+        } else {
+            "/var/run/vector.fifo"
+        };
+        toml::Value::try_from(Self {
+            path: path.into(),
+            host_key: None,
+            reopen_delay_ms: default_reopen_delay_ms(),
+            framing: default_framing_stream_based(),
+            decoding: default_decoding(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "named_pipe")]
+impl SourceConfig for NamedPipeConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
+        let listener = Listener::new(&self.path)?;
+        Ok(Box::pin(named_pipe_source(
+            self.clone(),
+            listener,
+            decoder,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "named_pipe"
+    }
+}
+
+/// Reads from the pipe until its writers go away, then opens it again to wait for the next ones,
+/// so daemons can restart without Vector needing to.
+async fn named_pipe_source(
+    config: NamedPipeConfig,
+    mut listener: Listener,
+    decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let host_key = config
+        .host_key
+        .unwrap_or_else(|| log_schema().host_key().to_string());
+    let hostname = crate::get_hostname().ok();
+    let reopen_delay = Duration::from_millis(config.reopen_delay_ms);
+
+    info!(message = "Reading from named pipe.", path = ?config.path);
+
+    loop {
+        let connection = tokio::select! {
+            _ = &mut shutdown => break,
+            connection = listener.accept() => connection,
+        };
+        let connection = match connection {
+            Ok(connection) => connection,
+            Err(error) => {
+                emit!(&NamedPipeOpenError {
+                    error,
+                    path: &config.path,
+                });
+                tokio::select! {
+                    _ = &mut shutdown => break,
+                    _ = tokio::time::sleep(reopen_delay) => continue,
+                }
+            }
+        };
+
+        let mut stream = FramedRead::new(connection, decoder.clone()).take_until(shutdown.clone());
+        let mut received = false;
+        while let Some(result) = stream.next().await {
+            match result {
+                Ok((mut events, byte_size)) => {
+                    received = true;
+                    emit!(&NamedPipeEventsReceived {
+                        byte_size,
+                        count: events.len(),
+                        path: &config.path,
+                    });
+
+                    let now = Utc::now();
+                    for event in &mut events {
+                        let log = event.as_mut_log();
+
+                        log.try_insert(log_schema().source_type_key(), Bytes::from("named_pipe"));
+                        log.try_insert(log_schema().timestamp_key(), now);
+
+                        if let Some(hostname) = &hostname {
+                            log.try_insert(&host_key, hostname.clone());
+                        }
+                    }
+
+                    if let Err(error) = out.send_batch(events).await {
+                        error!(message = "Unable to send event to out.", %error);
+                        return Err(());
+                    }
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs::Decoder`, no
+                    // further handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        // Some platforms report the end of a FIFO straight away while it has no writers, so
+        // back off rather than spin until one turns up.
+        if !received {
+            tokio::select! {
+                _ = &mut shutdown => break,
+                _ = tokio::time::sleep(reopen_delay) => {}
+            }
+        }
+    }
+
+    info!("Finished reading from named pipe.");
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::path::Path;
+
+    use tokio::io::AsyncWriteExt;
+
+    use super::*;
+    use crate::{
+        event::Event,
+        source_sender::ReceiverStream,
+        test_util::{collect_n, trace_init},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NamedPipeConfig>();
+    }
+
+    fn source(path: &Path) -> ReceiverStream<Event> {
+        let config =
+            toml::from_str::<NamedPipeConfig>(&format!("path = {:?}\nreopen_delay_ms = 10", path))
+                .unwrap();
+        let decoder = DecodingConfig::new(config.framing.clone(), config.decoding.clone())
+            .build()
+            .unwrap();
+        let listener = Listener::new(&config.path).unwrap();
+        let (tx, rx) = SourceSender::new_test();
+        tokio::spawn(named_pipe_source(
+            config,
+            listener,
+            decoder,
+            ShutdownSignal::noop(),
+            tx,
+        ));
+        rx
+    }
+
+    async fn write(path: &Path, data: &str) {
+        let mut fifo = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(path)
+            .await
+            .unwrap();
+        fifo.write_all(data.as_bytes()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reads_lines_across_writers() {
+        trace_init();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vector.fifo");
+        nix::unistd::mkfifo(&path, nix::sys::stat::Mode::S_IRWXU).unwrap();
+        let rx = source(&path);
+
+        // The second writer opens the pipe after the first has closed it, so the source has to
+        // reopen it, and the unterminated line of the first must not run into the second.
+        write(&path, "hello\nworld").await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        write(&path, "again\n").await;
+
+        let events = collect_n(rx, 3).await;
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["hello", "world", "again"]);
+        assert_eq!(
+            events[0].as_log()[log_schema().source_type_key()],
+            "named_pipe".into()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_regular_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("regular");
+        std::fs::write(&path, "hello\n").unwrap();
+        assert!(Listener::new(&path).unwrap().accept().await.is_err());
+    }
+}
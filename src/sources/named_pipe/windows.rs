@@ -0,0 +1,57 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use tokio::{
+    io::{AsyncRead, ReadBuf},
+    net::windows::named_pipe::{NamedPipeServer, ServerOptions},
+};
+
+pub(super) struct Listener {
+    path: PathBuf,
+    next: NamedPipeServer,
+}
+
+impl Listener {
+    pub(super) fn new(path: &Path) -> crate::Result<Self> {
+        let next = ServerOptions::new()
+            .first_pipe_instance(true)
+            .access_outbound(false)
+            .create(path)?;
+        Ok(Self {
+            path: path.to_owned(),
+            next,
+        })
+    }
+
+    /// Waits for a client to connect. The next instance of the pipe is created before handing
+    /// this one out, so clients reconnecting straight away don't find the pipe missing.
+    pub(super) async fn accept(&mut self) -> io::Result<Connection> {
+        self.next.connect().await?;
+        let next = ServerOptions::new()
+            .access_outbound(false)
+            .create(&self.path)?;
+        Ok(Connection(std::mem::replace(&mut self.next, next)))
+    }
+}
+
+pub(super) struct Connection(NamedPipeServer);
+
+impl AsyncRead for Connection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        // A client disconnecting is the end of its stream rather than an error.
+        match Pin::new(&mut self.0).poll_read(cx, buf) {
+            Poll::Ready(Err(error)) if error.kind() == io::ErrorKind::BrokenPipe => {
+                Poll::Ready(Ok(()))
+            }
+            poll => poll,
+        }
+    }
+}
@@ -13,11 +13,12 @@ use crate::{
         SourceDescription,
     },
     event::Event,
+    http::Http2Config,
     internal_events::PrometheusRemoteWriteParseError,
     serde::bool_or_struct,
     sources::{
         self,
-        util::{decode, ErrorMessage, HttpSource, HttpSourceAuthConfig},
+        util::{decode, ErrorMessage, HttpMethod, HttpSource, HttpSourceAuthConfig},
     },
     tls::TlsConfig,
 };
@@ -60,9 +61,11 @@ impl SourceConfig for PrometheusRemoteWriteConfig {
         source.run(
             self.address,
             "",
+            HttpMethod::Post,
             true,
             &self.tls,
             &self.auth,
+            &Http2Config::default(),
             cx,
             self.acknowledgements,
         )
@@ -15,7 +15,7 @@ use crate::{
         log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext,
         SourceDescription,
     },
-    sources::util::TcpSource,
+    sources::util::{ConnectionLimits, TcpSource},
     tls::MaybeTlsSettings,
 };
 
@@ -112,7 +112,12 @@ impl SourceConfig for SocketConfig {
                     config.receive_buffer_bytes(),
                     cx,
                     false.into(),
-                    config.connection_limit,
+                    ConnectionLimits {
+                        max_connections: config.connection_limit,
+                        max_connections_per_ip: config.connection_limit_per_ip,
+                        max_accepts_per_sec: config.connection_rate_limit,
+                        ..Default::default()
+                    },
                 )
             }
             Mode::Udp(config) => {
@@ -128,6 +133,9 @@ impl SourceConfig for SocketConfig {
                     config.max_length(),
                     host_key,
                     config.receive_buffer_bytes(),
+                    config.sockets(),
+                    config.receive_batch_size(),
+                    config.spool(),
                     decoder,
                     cx.shutdown,
                     cx.out,
@@ -248,6 +256,7 @@ mod test {
         event::Event,
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
+        tcp::ProxyProtocolConfig,
         test_util::{
             collect_n,
             components::{self, SOURCE_TESTS, TCP_SOURCE_TAGS},
@@ -286,6 +295,43 @@ mod test {
         SOURCE_TESTS.assert(&TCP_SOURCE_TAGS);
     }
 
+    #[tokio::test]
+    async fn tcp_proxy_protocol() {
+        let (tx, mut rx) = SourceSender::new_test();
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config.set_proxy_protocol(ProxyProtocolConfig {
+            enabled: true,
+            client_ip_key: Some("client_ip".to_owned()),
+        });
+        let server = SocketConfig::from(config)
+            .build(SourceContext::new_test(tx))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        let header = format!("PROXY TCP4 192.0.2.1 {} 56324 {}\r", addr.ip(), addr.port());
+        send_lines(addr, vec![header, "test".to_owned()].into_iter())
+            .await
+            .unwrap();
+
+        let event = rx.next().await.unwrap();
+        let log = event.as_log();
+        assert_eq!(log[log_schema().message_key()], "test".into());
+        assert_eq!(log[log_schema().host_key()], "192.0.2.1".into());
+        assert_eq!(log["client_ip"], "192.0.2.1".into());
+        assert_eq!(
+            log.metadata().source_field("peer_ip"),
+            Some(&"192.0.2.1".into())
+        );
+        assert_eq!(
+            log.metadata().source_field("proxy_ip"),
+            Some(&"127.0.0.1".into())
+        );
+    }
+
     #[tokio::test]
     async fn tcp_splits_on_newline() {
         let (tx, rx) = SourceSender::new_test();
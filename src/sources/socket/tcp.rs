@@ -12,9 +12,9 @@ use crate::{
     config::log_schema,
     event::Event,
     internal_events::{SocketEventsReceived, SocketMode},
-    serde::default_decoding,
+    serde::{bool_or_struct, default_decoding},
     sources::util::{SocketListenAddr, TcpNullAcker, TcpSource},
-    tcp::TcpKeepaliveConfig,
+    tcp::{ProxyProtocolConfig, TcpKeepaliveConfig},
     tls::TlsConfig,
 };
 
@@ -40,7 +40,13 @@ pub struct TcpConfig {
     #[serde(default = "default_decoding")]
     #[getset(get = "pub", set = "pub")]
     decoding: Box<dyn DeserializerConfig>,
+    #[serde(alias = "max_connections")]
     pub connection_limit: Option<u32>,
+    pub connection_limit_per_ip: Option<u32>,
+    pub connection_rate_limit: Option<u32>,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    #[getset(get = "pub", set = "pub")]
+    proxy_protocol: ProxyProtocolConfig,
 }
 
 const fn default_shutdown_timeout_secs() -> u64 {
@@ -59,6 +65,9 @@ impl TcpConfig {
         framing: Option<Box<dyn FramingConfig>>,
         decoding: Box<dyn DeserializerConfig>,
         connection_limit: Option<u32>,
+        connection_limit_per_ip: Option<u32>,
+        connection_rate_limit: Option<u32>,
+        proxy_protocol: ProxyProtocolConfig,
     ) -> Self {
         Self {
             address,
@@ -71,6 +80,9 @@ impl TcpConfig {
             framing,
             decoding,
             connection_limit,
+            connection_limit_per_ip,
+            connection_rate_limit,
+            proxy_protocol,
         }
     }
 
@@ -86,6 +98,9 @@ impl TcpConfig {
             framing: None,
             decoding: default_decoding(),
             connection_limit: None,
+            connection_limit_per_ip: None,
+            connection_rate_limit: None,
+            proxy_protocol: ProxyProtocolConfig::default(),
         }
     }
 }
@@ -137,4 +152,8 @@ impl TcpSource for RawTcpSource {
     fn build_acker(&self, _: &[Self::Item]) -> Self::Acker {
         TcpNullAcker
     }
+
+    fn proxy_protocol(&self) -> Option<&ProxyProtocolConfig> {
+        Some(&self.config.proxy_protocol)
+    }
 }
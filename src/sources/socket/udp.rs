@@ -1,6 +1,6 @@
 use std::net::SocketAddr;
 
-use bytes::{Bytes, BytesMut};
+use bytes::Bytes;
 use chrono::Utc;
 use futures::{stream, StreamExt};
 use getset::{CopyGetters, Getters};
@@ -36,6 +36,18 @@ pub struct UdpConfig {
     host_key: Option<String>,
     #[get_copy = "pub"]
     receive_buffer_bytes: Option<usize>,
+    /// The number of sockets to receive on, which share the port with `SO_REUSEPORT`.
+    #[serde(default = "udp::default_sockets")]
+    #[get_copy = "pub"]
+    sockets: usize,
+    /// The most datagrams to receive with each system call.
+    #[serde(default = "udp::default_receive_batch_size")]
+    #[get_copy = "pub"]
+    receive_batch_size: usize,
+    /// Receive datagrams ahead of decoding them, so that they aren't dropped while the pipeline is
+    /// slow to take events.
+    #[get_copy = "pub"]
+    spool: Option<udp::SpoolConfig>,
     #[serde(default = "default_framing_message_based")]
     #[get = "pub"]
     framing: Box<dyn FramingConfig>,
@@ -51,6 +63,9 @@ impl UdpConfig {
             max_length: crate::serde::default_max_length(),
             host_key: None,
             receive_buffer_bytes: None,
+            sockets: udp::default_sockets(),
+            receive_batch_size: udp::default_receive_batch_size(),
+            spool: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
         }
@@ -62,88 +77,110 @@ pub fn udp(
     max_length: usize,
     host_key: String,
     receive_buffer_bytes: Option<usize>,
+    sockets: usize,
+    receive_batch_size: usize,
+    spool: Option<udp::SpoolConfig>,
     decoder: Decoder,
-    mut shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    shutdown: ShutdownSignal,
+    out: SourceSender,
 ) -> Source {
     Box::pin(async move {
-        let socket = UdpSocket::bind(&address)
+        let sockets = udp::bind_sockets(address, sockets, receive_buffer_bytes)
             .await
             .expect("Failed to bind to udp listener socket");
 
-        if let Some(receive_buffer_bytes) = receive_buffer_bytes {
-            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-            }
-        }
-
         let max_length = if let Some(receive_buffer_bytes) = receive_buffer_bytes {
             std::cmp::min(max_length, receive_buffer_bytes)
         } else {
             max_length
         };
 
-        info!(message = "Listening.", address = %address);
+        info!(message = "Listening.", address = %address, sockets = sockets.len());
+
+        udp::receive_all(sockets, |socket| {
+            receive(
+                socket,
+                max_length,
+                receive_batch_size,
+                spool,
+                host_key.clone(),
+                decoder.clone(),
+                shutdown.clone(),
+                out.clone(),
+            )
+        })
+        .await
+    })
+}
 
-        let mut buf = BytesMut::with_capacity(max_length);
-        loop {
-            buf.resize(max_length, 0);
-            tokio::select! {
-                recv = socket.recv_from(&mut buf) => {
-                    let (byte_size, address) = recv.map_err(|error| {
+async fn receive(
+    socket: UdpSocket,
+    max_length: usize,
+    receive_batch_size: usize,
+    spool: Option<udp::SpoolConfig>,
+    host_key: String,
+    decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut datagrams = udp::datagrams(socket, max_length, receive_batch_size, spool);
+    loop {
+        tokio::select! {
+            datagram = datagrams.next() => {
+                let (payload, address) = match datagram {
+                    Some(datagram) => datagram.map_err(|error| {
                         let error = codecs::decoding::Error::FramingError(error.into());
                         emit!(&SocketReceiveError {
                             mode: SocketMode::Udp,
                             error: &error
                         })
-                    })?;
-
-                    let payload = buf.split_to(byte_size);
+                    })?,
+                    None => return Ok(()),
+                };
 
-                    let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
+                let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
 
-                    loop {
-                        match stream.next().await {
-                            Some(Ok((mut events, byte_size))) => {
-                                emit!(&SocketEventsReceived {
-                                    mode: SocketMode::Udp,
-                                    byte_size,
-                                    count: events.len()
-                                });
+                loop {
+                    match stream.next().await {
+                        Some(Ok((mut events, byte_size))) => {
+                            emit!(&SocketEventsReceived {
+                                mode: SocketMode::Udp,
+                                byte_size,
+                                count: events.len()
+                            });
 
-                                let now = Utc::now();
+                            let now = Utc::now();
 
-                                for event in &mut events {
-                                    if let Event::Log(ref mut log) = event {
-                                        log.try_insert(log_schema().source_type_key(), Bytes::from("socket"));
-                                        log.try_insert(log_schema().timestamp_key(), now);
-                                        log.try_insert(host_key.clone(), address.to_string());
-                                    }
+                            for event in &mut events {
+                                if let Event::Log(ref mut log) = event {
+                                    log.try_insert(log_schema().source_type_key(), Bytes::from("socket"));
+                                    log.try_insert(log_schema().timestamp_key(), now);
+                                    log.try_insert(host_key.clone(), address.to_string());
                                 }
+                            }
 
-                                tokio::select!{
-                                    result = out.send_all(stream::iter(events)) => {
-                                        if let Err(error) = result {
-                                            error!(message = "Error sending event.", %error);
-                                            return Ok(())
-                                        }
+                            tokio::select!{
+                                result = out.send_all(stream::iter(events)) => {
+                                    if let Err(error) = result {
+                                        error!(message = "Error sending event.", %error);
+                                        return Ok(())
                                     }
-                                    _ = &mut shutdown => return Ok(()),
                                 }
+                                _ = &mut shutdown => return Ok(()),
                             }
-                            Some(Err(error)) => {
-                                // Error is logged by `crate::codecs::Decoder`, no
-                                // further handling is needed here.
-                                if !error.can_continue() {
-                                    break;
-                                }
+                        }
+                        Some(Err(error)) => {
+                            // Error is logged by `crate::codecs::Decoder`, no
+                            // further handling is needed here.
+                            if !error.can_continue() {
+                                break;
                             }
-                            None => break,
                         }
+                        None => break,
                     }
                 }
-                _ = &mut shutdown => return Ok(()),
             }
+            _ = &mut shutdown => return Ok(()),
         }
-    })
+    }
 }
@@ -0,0 +1,565 @@
+//! A source receiving events from a remote server-sent events (`text/event-stream`) endpoint.
+//!
+//! The source keeps a request open to the endpoint and reconnects whenever it ends, after the
+//! delay the endpoint asked for with its `retry` field. The ID of the last event received is sent
+//! back in the `Last-Event-ID` header when reconnecting, so that endpoints that support it resume
+//! the stream where it was left off.
+
+use std::time::Duration;
+
+use bytes::{Buf, Bytes, BytesMut};
+use chrono::Utc;
+use futures::StreamExt;
+use http::{
+    header::{HeaderName, ACCEPT, CACHE_CONTROL, CONTENT_TYPE},
+    HeaderValue, Request, StatusCode, Uri,
+};
+use hyper::{body::HttpBody, Body};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+
+use crate::{
+    codecs::{
+        self,
+        decoding::{DecodingConfig, DeserializerConfig, FramingConfig},
+    },
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    event::Event,
+    http::{HttpClient, HttpError},
+    internal_events::{
+        SseConnectionEstablished, SseConnectionFailed, SseConnectionShutdown, SseEventsReceived,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sinks::util::retries::ExponentialBackoff,
+    sources::util::StreamDecodingError,
+    tls::{TlsOptions, TlsSettings},
+    SourceSender,
+};
+
+const LAST_EVENT_ID: &str = "last-event-id";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid endpoint {:?}: {}", endpoint, source))]
+    InvalidEndpoint {
+        endpoint: String,
+        source: http::uri::InvalidUri,
+    },
+    #[snafu(display("Endpoint {:?} must use the `http` or `https` scheme", endpoint))]
+    UnsupportedScheme { endpoint: String },
+    #[snafu(display("Invalid header {:?}", name))]
+    InvalidHeader { name: String },
+}
+
+#[derive(Debug, Snafu)]
+pub enum SseError {
+    #[snafu(display("Failed to build request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("Unexpected status: {}", status))]
+    UnexpectedStatus { status: StatusCode },
+    #[snafu(display("Unexpected content type {:?}", content_type))]
+    UnexpectedContentType { content_type: String },
+    #[snafu(display("Failed to read response: {}", source))]
+    Read { source: hyper::Error },
+}
+
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct SseSourceConfig {
+    /// The `http://` or `https://` URI of the event stream.
+    endpoint: String,
+    /// Headers sent with every request, such as those authenticating it.
+    #[serde(default)]
+    headers: IndexMap<String, String>,
+    tls: Option<TlsOptions>,
+    /// The types of the events to receive, all of them if empty.
+    #[serde(default)]
+    event_types: Vec<String>,
+    /// How long to wait before reconnecting, unless the endpoint sets it with a `retry` field.
+    #[serde(default = "default_retry_secs")]
+    #[derivative(Default(value = "default_retry_secs()"))]
+    retry_secs: u64,
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: Box<dyn FramingConfig>,
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: Box<dyn DeserializerConfig>,
+}
+
+const fn default_retry_secs() -> u64 {
+    3
+}
+
+inventory::submit! {
+    SourceDescription::new::<SseSourceConfig>("sse")
+}
+
+impl GenerateConfig for SseSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "https://example.com/events""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "sse")]
+impl SourceConfig for SseSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let endpoint = self.endpoint.parse::<Uri>().context(InvalidEndpointSnafu {
+            endpoint: self.endpoint.clone(),
+        })?;
+        if !matches!(endpoint.scheme_str(), Some("http") | Some("https")) {
+            return Err(Box::new(BuildError::UnsupportedScheme {
+                endpoint: self.endpoint.clone(),
+            }));
+        }
+        let headers = build_headers(&self.headers)?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, &cx.proxy)?;
+        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
+
+        let source = SseSource {
+            client,
+            endpoint,
+            headers,
+            event_types: self.event_types.clone(),
+            retry: Duration::from_secs(self.retry_secs),
+            decoder,
+        };
+        Ok(Box::pin(source.run(cx.shutdown, cx.out)))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "sse"
+    }
+}
+
+fn build_headers(
+    headers: &IndexMap<String, String>,
+) -> Result<Vec<(HeaderName, HeaderValue)>, BuildError> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            match (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value),
+            ) {
+                (Ok(name), Ok(value)) => Ok((name, value)),
+                _ => Err(BuildError::InvalidHeader { name: name.clone() }),
+            }
+        })
+        .collect()
+}
+
+/// An event received from the endpoint.
+#[derive(Debug, PartialEq)]
+struct SseEvent {
+    event_type: String,
+    data: String,
+    id: Option<String>,
+}
+
+/// Parses the `text/event-stream` format, as specified by the HTML standard.
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: BytesMut,
+    /// Whether the last line ended with a `\r`, which a `\n` starting the next chunk belongs to.
+    after_cr: bool,
+    event_type: String,
+    data: String,
+    has_data: bool,
+    /// The ID of the last event, kept across connections.
+    last_event_id: Option<String>,
+    /// The reconnection delay set by the endpoint, kept across connections.
+    retry: Option<Duration>,
+}
+
+impl SseParser {
+    /// Forgets the event being parsed when the connection was lost.
+    fn reconnected(&mut self) {
+        self.buffer.clear();
+        self.after_cr = false;
+        self.event_type.clear();
+        self.data.clear();
+        self.has_data = false;
+    }
+
+    /// Parses the next chunk of the stream, returning the events it completes.
+    fn parse(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        loop {
+            if self.after_cr && self.buffer.first() == Some(&b'\n') {
+                self.buffer.advance(1);
+                self.after_cr = false;
+            }
+            let end = match self
+                .buffer
+                .iter()
+                .position(|byte| *byte == b'\n' || *byte == b'\r')
+            {
+                Some(end) => end,
+                None => break,
+            };
+            self.after_cr = self.buffer[end] == b'\r';
+            let line = self.buffer.split_to(end + 1);
+            let line = String::from_utf8_lossy(&line[..end]);
+            if let Some(event) = self.parse_line(&line) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    fn parse_line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            // A comment, which endpoints often send to keep the connection alive.
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event_type = value.to_owned(),
+            "data" => {
+                if self.has_data {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value);
+                self.has_data = true;
+            }
+            "id" if !value.contains('\0') => self.last_event_id = Some(value.to_owned()),
+            "retry" => {
+                if let Ok(millis) = value.parse::<u64>() {
+                    self.retry = Some(Duration::from_millis(millis));
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        let event_type = std::mem::take(&mut self.event_type);
+        if !std::mem::replace(&mut self.has_data, false) {
+            return None;
+        }
+        Some(SseEvent {
+            event_type: if event_type.is_empty() {
+                "message".to_owned()
+            } else {
+                event_type
+            },
+            data: std::mem::take(&mut self.data),
+            id: self.last_event_id.clone(),
+        })
+    }
+}
+
+enum Closed {
+    /// The source is shutting down.
+    Shutdown,
+    /// The endpoint ended the stream.
+    Disconnected,
+}
+
+struct SseSource {
+    client: HttpClient,
+    endpoint: Uri,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    event_types: Vec<String>,
+    retry: Duration,
+    decoder: codecs::Decoder,
+}
+
+impl SseSource {
+    const fn fresh_backoff() -> ExponentialBackoff {
+        ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60))
+    }
+
+    async fn run(self, mut shutdown: ShutdownSignal, mut out: SourceSender) -> Result<(), ()> {
+        let mut parser = SseParser::default();
+        let mut backoff = Self::fresh_backoff();
+
+        loop {
+            let connected = tokio::select! {
+                connected = self.connect(parser.last_event_id.as_deref()) => connected,
+                _ = &mut shutdown => return Ok(()),
+            };
+
+            let received = match connected {
+                Ok(body) => {
+                    emit!(&SseConnectionEstablished);
+                    backoff = Self::fresh_backoff();
+                    self.receive(body, &mut parser, &mut shutdown, &mut out)
+                        .await
+                }
+                Err(error) => Err(error),
+            };
+            parser.reconnected();
+
+            let retry = parser.retry.unwrap_or(self.retry);
+            let delay = match received {
+                Ok(Closed::Shutdown) => return Ok(()),
+                Ok(Closed::Disconnected) => {
+                    emit!(&SseConnectionShutdown);
+                    retry
+                }
+                Err(error) => {
+                    emit!(&SseConnectionFailed { error });
+                    backoff.next().unwrap().max(retry)
+                }
+            };
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = &mut shutdown => return Ok(()),
+            }
+        }
+    }
+
+    async fn connect(&self, last_event_id: Option<&str>) -> Result<Body, SseError> {
+        let mut request = Request::get(&self.endpoint)
+            .header(ACCEPT, "text/event-stream")
+            .header(CACHE_CONTROL, "no-cache")
+            .body(Body::empty())
+            .context(BuildRequestSnafu)?;
+        let headers = request.headers_mut();
+        for (name, value) in &self.headers {
+            headers.insert(name.clone(), value.clone());
+        }
+        if let Some(value) = last_event_id.and_then(|id| HeaderValue::from_str(id).ok()) {
+            headers.insert(LAST_EVENT_ID, value);
+        }
+
+        let response = self.client.send(request).await.context(RequestSnafu)?;
+        let status = response.status();
+        if status != StatusCode::OK {
+            return Err(SseError::UnexpectedStatus { status });
+        }
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .map(|value| String::from_utf8_lossy(value.as_bytes()).into_owned())
+            .unwrap_or_default();
+        if !content_type.starts_with("text/event-stream") {
+            return Err(SseError::UnexpectedContentType { content_type });
+        }
+        Ok(response.into_body())
+    }
+
+    async fn receive(
+        &self,
+        mut body: Body,
+        parser: &mut SseParser,
+        shutdown: &mut ShutdownSignal,
+        out: &mut SourceSender,
+    ) -> Result<Closed, SseError> {
+        loop {
+            let chunk = tokio::select! {
+                chunk = body.data() => chunk,
+                _ = &mut *shutdown => return Ok(Closed::Shutdown),
+            };
+            let chunk = match chunk {
+                Some(chunk) => chunk.context(ReadSnafu)?,
+                None => return Ok(Closed::Disconnected),
+            };
+
+            for event in parser.parse(&chunk) {
+                if !self.event_types.is_empty() && !self.event_types.contains(&event.event_type) {
+                    continue;
+                }
+                if self.handle_event(event, out).await.is_err() {
+                    // The source is shutting down.
+                    return Ok(Closed::Shutdown);
+                }
+            }
+        }
+    }
+
+    async fn handle_event(&self, event: SseEvent, out: &mut SourceSender) -> Result<(), ()> {
+        let mut stream = FramedRead::new(event.data.as_bytes(), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((events, byte_size)) => {
+                    emit!(&SseEventsReceived {
+                        byte_size,
+                        count: events.len()
+                    });
+
+                    let now = Utc::now();
+
+                    for mut decoded in events {
+                        if let Event::Log(ref mut log) = decoded {
+                            log.try_insert("sse.event", event.event_type.clone());
+                            if let Some(id) = &event.id {
+                                log.try_insert("sse.id", id.clone());
+                            }
+                            log.try_insert(log_schema().source_type_key(), Bytes::from("sse"));
+                            log.try_insert(log_schema().timestamp_key(), now);
+                        }
+
+                        out.send(decoded).await.map_err(
+                            |error: crate::source_sender::ClosedError| {
+                                error!(message = "Error sending to sink.", %error);
+                            },
+                        )?;
+                    }
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs::Decoder`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SseSourceConfig>();
+    }
+
+    fn event(event_type: &str, data: &str, id: Option<&str>) -> SseEvent {
+        SseEvent {
+            event_type: event_type.to_owned(),
+            data: data.to_owned(),
+            id: id.map(ToOwned::to_owned),
+        }
+    }
+
+    #[test]
+    fn parses_events() {
+        let mut parser = SseParser::default();
+        let events = parser.parse(
+            b": keep-alive\n\
+              data: first\n\
+              data:second\n\
+              \n\
+              event: update\n\
+              id: 7\n\
+              retry: 5000\n\
+              data: {\"a\":1}\n\
+              \n\
+              event: ignored\n\
+              \n",
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                event("message", "first\nsecond", None),
+                event("update", "{\"a\":1}", Some("7")),
+            ]
+        );
+        assert_eq!(parser.retry, Some(Duration::from_millis(5000)));
+    }
+
+    #[test]
+    fn parses_events_across_chunks() {
+        let mut parser = SseParser::default();
+        let stream = b"id: 1\r\ndata: a\r\n\r\ndata: b\r\rdata: c\n\n";
+
+        // Any split of the stream, including within line endings, gives the same events.
+        for split in 0..stream.len() {
+            parser.reconnected();
+            let mut events = parser.parse(&stream[..split]);
+            events.extend(parser.parse(&stream[split..]));
+            assert_eq!(
+                events,
+                vec![
+                    event("message", "a", Some("1")),
+                    event("message", "b", Some("1")),
+                    event("message", "c", Some("1")),
+                ],
+                "split at {}",
+                split
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_last_event_id_across_connections() {
+        let mut parser = SseParser::default();
+        parser.parse(b"id: 41\ndata: a\n\ndata: cut off");
+        parser.reconnected();
+
+        assert_eq!(parser.last_event_id.as_deref(), Some("41"));
+        assert_eq!(
+            parser.parse(b"data: b\n\n"),
+            vec![event("message", "b", Some("41"))]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        let mut headers = IndexMap::new();
+        headers.insert("bad header".to_owned(), "value".to_owned());
+        assert!(build_headers(&headers).is_err());
+    }
+
+    #[tokio::test]
+    async fn decodes_events() {
+        let config = toml::from_str::<SseSourceConfig>(
+            r#"
+                endpoint = "https://example.com/events"
+                decoding.codec = "json"
+            "#,
+        )
+        .unwrap();
+        let source = SseSource {
+            client: HttpClient::new(None, &Default::default()).unwrap(),
+            endpoint: config.endpoint.parse().unwrap(),
+            headers: Vec::new(),
+            event_types: Vec::new(),
+            retry: Duration::from_secs(config.retry_secs),
+            decoder: DecodingConfig::new(config.framing, config.decoding)
+                .build()
+                .unwrap(),
+        };
+        let (mut out, rx) = SourceSender::new_test();
+
+        source
+            .handle_event(event("update", r#"{"a":1}"#, Some("7")), &mut out)
+            .await
+            .unwrap();
+
+        let events = crate::test_util::collect_ready(rx).await;
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["a"], 1.into());
+        assert_eq!(log["sse.event"], "update".into());
+        assert_eq!(log["sse.id"], "7".into());
+        assert_eq!(log[log_schema().source_type_key()], "sse".into());
+    }
+}
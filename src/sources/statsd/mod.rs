@@ -5,10 +5,9 @@ use futures::{StreamExt, TryFutureExt};
 use serde::{Deserialize, Serialize};
 use smallvec::{smallvec, SmallVec};
 use tokio::net::UdpSocket;
-use tokio_util::udp::UdpFramed;
 
 use self::parser::ParseError;
-use super::util::{SocketListenAddr, TcpNullAcker, TcpSource};
+use super::util::{ConnectionLimits, SocketListenAddr, TcpNullAcker, TcpSource};
 use crate::{
     codecs::{self, decoding::Deserializer, NewlineDelimitedDecoder},
     config::{
@@ -43,6 +42,11 @@ enum StatsdConfig {
 pub struct UdpConfig {
     address: SocketAddr,
     receive_buffer_bytes: Option<usize>,
+    #[serde(default = "udp::default_sockets")]
+    sockets: usize,
+    #[serde(default = "udp::default_receive_batch_size")]
+    receive_batch_size: usize,
+    spool: Option<udp::SpoolConfig>,
 }
 
 impl UdpConfig {
@@ -50,6 +54,9 @@ impl UdpConfig {
         Self {
             address,
             receive_buffer_bytes: None,
+            sockets: udp::default_sockets(),
+            receive_batch_size: udp::default_receive_batch_size(),
+            spool: None,
         }
     }
 }
@@ -63,7 +70,10 @@ struct TcpConfig {
     #[serde(default = "default_shutdown_timeout_secs")]
     shutdown_timeout_secs: u64,
     receive_buffer_bytes: Option<usize>,
+    #[serde(alias = "max_connections")]
     connection_limit: Option<u32>,
+    connection_limit_per_ip: Option<u32>,
+    connection_rate_limit: Option<u32>,
 }
 
 impl TcpConfig {
@@ -77,6 +87,8 @@ impl TcpConfig {
             shutdown_timeout_secs: default_shutdown_timeout_secs(),
             receive_buffer_bytes: None,
             connection_limit: None,
+            connection_limit_per_ip: None,
+            connection_rate_limit: None,
         }
     }
 }
@@ -116,7 +128,12 @@ impl SourceConfig for StatsdConfig {
                     config.receive_buffer_bytes,
                     cx,
                     false.into(),
-                    config.connection_limit,
+                    ConnectionLimits {
+                        max_connections: config.connection_limit,
+                        max_connections_per_ip: config.connection_limit_per_ip,
+                        max_accepts_per_sec: config.connection_rate_limit,
+                        ..Default::default()
+                    },
                 )
             }
             #[cfg(unix)]
@@ -171,29 +188,44 @@ impl Deserializer for StatsdDeserializer {
 async fn statsd_udp(
     config: UdpConfig,
     shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    out: SourceSender,
 ) -> Result<(), ()> {
-    let socket = UdpSocket::bind(&config.address)
+    let sockets = udp::bind_sockets(config.address, config.sockets, config.receive_buffer_bytes)
         .map_err(|error| emit!(&StatsdSocketError::bind(error)))
         .await?;
 
-    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
-        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-        }
-    }
-
     info!(
         message = "Listening.",
         addr = %config.address,
-        r#type = "udp"
+        r#type = "udp",
+        sockets = sockets.len(),
     );
 
+    udp::receive_all(sockets, |socket| {
+        statsd_udp_receive(
+            socket,
+            config.receive_batch_size,
+            config.spool,
+            shutdown.clone(),
+            out.clone(),
+        )
+    })
+    .await
+}
+
+async fn statsd_udp_receive(
+    socket: UdpSocket,
+    receive_batch_size: usize,
+    spool: Option<udp::SpoolConfig>,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
     let codec = codecs::Decoder::new(
         Box::new(NewlineDelimitedDecoder::new()),
         Box::new(StatsdDeserializer),
     );
-    let mut stream = UdpFramed::new(socket, codec).take_until(shutdown);
+    let stream = udp::framed(socket, codec, receive_batch_size, spool).take_until(shutdown);
+    tokio::pin!(stream);
     while let Some(frame) = stream.next().await {
         match frame {
             Ok(((events, _byte_size), _sock)) => {
@@ -10,7 +10,6 @@ use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use tokio::net::UdpSocket;
-use tokio_util::udp::UdpFramed;
 
 #[cfg(unix)]
 use crate::sources::util::build_unix_stream_source;
@@ -22,9 +21,10 @@ use crate::{
     },
     event::Event,
     internal_events::{SyslogEventReceived, SyslogUdpReadError},
+    serde::bool_or_struct,
     shutdown::ShutdownSignal,
-    sources::util::{SocketListenAddr, TcpNullAcker, TcpSource},
-    tcp::TcpKeepaliveConfig,
+    sources::util::{ConnectionLimits, SocketListenAddr, TcpNullAcker, TcpSource},
+    tcp::{ProxyProtocolConfig, TcpKeepaliveConfig},
     tls::{MaybeTlsSettings, TlsConfig},
     udp, SourceSender,
 };
@@ -49,11 +49,21 @@ pub enum Mode {
         keepalive: Option<TcpKeepaliveConfig>,
         tls: Option<TlsConfig>,
         receive_buffer_bytes: Option<usize>,
+        #[serde(alias = "max_connections")]
         connection_limit: Option<u32>,
+        connection_limit_per_ip: Option<u32>,
+        connection_rate_limit: Option<u32>,
+        #[serde(default, deserialize_with = "bool_or_struct")]
+        proxy_protocol: ProxyProtocolConfig,
     },
     Udp {
         address: SocketAddr,
         receive_buffer_bytes: Option<usize>,
+        #[serde(default = "udp::default_sockets")]
+        sockets: usize,
+        #[serde(default = "udp::default_receive_batch_size")]
+        receive_batch_size: usize,
+        spool: Option<udp::SpoolConfig>,
     },
     #[cfg(unix)]
     Unix { path: PathBuf },
@@ -82,6 +92,9 @@ impl GenerateConfig for SyslogConfig {
                 tls: None,
                 receive_buffer_bytes: None,
                 connection_limit: None,
+                connection_limit_per_ip: None,
+                connection_rate_limit: None,
+                proxy_protocol: ProxyProtocolConfig::default(),
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
@@ -106,10 +119,14 @@ impl SourceConfig for SyslogConfig {
                 tls,
                 receive_buffer_bytes,
                 connection_limit,
+                connection_limit_per_ip,
+                connection_rate_limit,
+                proxy_protocol,
             } => {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
                     host_key,
+                    proxy_protocol,
                 };
                 let shutdown_secs = 30;
                 let tls = MaybeTlsSettings::from_config(&tls, true)?;
@@ -121,17 +138,28 @@ impl SourceConfig for SyslogConfig {
                     receive_buffer_bytes,
                     cx,
                     false.into(),
-                    connection_limit,
+                    ConnectionLimits {
+                        max_connections: connection_limit,
+                        max_connections_per_ip: connection_limit_per_ip,
+                        max_accepts_per_sec: connection_rate_limit,
+                        ..Default::default()
+                    },
                 )
             }
             Mode::Udp {
                 address,
                 receive_buffer_bytes,
+                sockets,
+                receive_batch_size,
+                spool,
             } => Ok(udp(
                 address,
                 self.max_length,
                 host_key,
                 receive_buffer_bytes,
+                sockets,
+                receive_batch_size,
+                spool,
                 cx.shutdown,
                 cx.out,
             )),
@@ -177,6 +205,7 @@ impl SourceConfig for SyslogConfig {
 struct SyslogTcpSource {
     max_length: usize,
     host_key: String,
+    proxy_protocol: ProxyProtocolConfig,
 }
 
 impl TcpSource for SyslogTcpSource {
@@ -199,6 +228,10 @@ impl TcpSource for SyslogTcpSource {
     fn build_acker(&self, _: &[Self::Item]) -> Self::Acker {
         TcpNullAcker
     }
+
+    fn proxy_protocol(&self) -> Option<&ProxyProtocolConfig> {
+        Some(&self.proxy_protocol)
+    }
 }
 
 pub fn udp(
@@ -206,60 +239,81 @@ pub fn udp(
     _max_length: usize,
     host_key: String,
     receive_buffer_bytes: Option<usize>,
+    sockets: usize,
+    receive_batch_size: usize,
+    spool: Option<udp::SpoolConfig>,
     shutdown: ShutdownSignal,
-    mut out: SourceSender,
+    out: SourceSender,
 ) -> super::Source {
     Box::pin(async move {
-        let socket = UdpSocket::bind(&addr)
+        let sockets = udp::bind_sockets(addr, sockets, receive_buffer_bytes)
             .await
             .expect("Failed to bind to UDP listener socket");
 
-        if let Some(receive_buffer_bytes) = receive_buffer_bytes {
-            if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
-                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
-            }
-        }
-
         info!(
             message = "Listening.",
             addr = %addr,
-            r#type = "udp"
+            r#type = "udp",
+            sockets = sockets.len(),
         );
 
-        let mut stream = UdpFramed::new(
-            socket,
-            codecs::Decoder::new(Box::new(BytesDecoder::new()), Box::new(SyslogDeserializer)),
-        )
-        .take_until(shutdown)
-        .filter_map(|frame| {
-            let host_key = host_key.clone();
-            async move {
-                match frame {
-                    Ok(((mut events, byte_size), received_from)) => {
-                        let received_from = received_from.ip().to_string().into();
-                        handle_events(&mut events, &host_key, Some(received_from), byte_size);
-                        Some(events.remove(0))
-                    }
-                    Err(error) => {
-                        emit!(&SyslogUdpReadError { error });
-                        None
-                    }
-                }
-            }
+        udp::receive_all(sockets, |socket| {
+            receive_udp(
+                socket,
+                receive_batch_size,
+                spool,
+                host_key.clone(),
+                shutdown.clone(),
+                out.clone(),
+            )
         })
-        .boxed();
+        .await
+    })
+}
 
-        match out.send_all(&mut stream).await {
-            Ok(()) => {
-                info!("Finished sending.");
-                Ok(())
-            }
-            Err(error) => {
-                error!(message = "Error sending line.", %error);
-                Err(())
+async fn receive_udp(
+    socket: UdpSocket,
+    receive_batch_size: usize,
+    spool: Option<udp::SpoolConfig>,
+    host_key: String,
+    shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut stream = udp::framed(
+        socket,
+        codecs::Decoder::new(Box::new(BytesDecoder::new()), Box::new(SyslogDeserializer)),
+        receive_batch_size,
+        spool,
+    )
+    .take_until(shutdown)
+    .filter_map(|frame| {
+        let host_key = host_key.clone();
+        async move {
+            match frame {
+                Ok(((mut events, byte_size), received_from)) => {
+                    let received_from = received_from.ip().to_string().into();
+                    handle_events(&mut events, &host_key, Some(received_from), byte_size);
+                    Some(events.remove(0))
+                }
+                Err(error) => {
+                    emit!(&SyslogUdpReadError { error });
+                    None
+                }
             }
         }
     })
+    .boxed();
+
+    match out.send_all(&mut stream).await {
+        Ok(()) => {
+            info!("Finished sending.");
+            Ok(())
+        }
+        Err(error) => {
+            error!(message = "Error sending line.", %error);
+            Err(())
+        }
+    }
 }
 
 fn handle_events(
@@ -440,6 +494,25 @@ mod test {
         assert_eq!(receive_buffer_bytes, Some(256));
     }
 
+    #[test]
+    fn config_udp_with_sockets() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "udp"
+            address = "127.0.0.1:1235"
+            sockets = 4
+          "#,
+        )
+        .unwrap();
+
+        let sockets = match config.mode {
+            Mode::Udp { sockets, .. } => sockets,
+            _ => panic!("expected Mode::Udp"),
+        };
+
+        assert_eq!(sockets, 4);
+    }
+
     #[cfg(unix)]
     #[test]
     fn config_unix() {
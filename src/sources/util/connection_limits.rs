@@ -0,0 +1,300 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_stream::stream;
+use futures::Stream;
+use once_cell::sync::Lazy;
+use tokio::{
+    net::TcpStream,
+    sync::{OwnedSemaphorePermit, Semaphore},
+    time::{sleep_until, Instant},
+};
+
+use crate::{
+    internal_events::{TcpConnectionLimitReached, TcpConnectionRejected},
+    tls::{MaybeTlsIncomingStream, MaybeTlsListener},
+};
+
+/// Limits on the connections a TCP-based source accepts. Until the `max_connections` limits
+/// allow another connection, or the rate allows another accept, no more connections are accepted,
+/// so clients wait in the listen backlog rather than using up file descriptors.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ConnectionLimits {
+    /// The most connections this source has open at once.
+    pub max_connections: Option<u32>,
+    /// The most connections open at once from any one IP address. Connections over this are
+    /// closed as soon as they are accepted.
+    pub max_connections_per_ip: Option<u32>,
+    /// The most connections accepted each second, with up to a second's worth in a burst.
+    pub max_accepts_per_sec: Option<u32>,
+    /// The most connections all TCP-based sources have open at once, set by the global
+    /// `max_connections` option.
+    pub global_max_connections: Option<u32>,
+}
+
+impl ConnectionLimits {
+    pub const fn new(max_connections: Option<u32>) -> Self {
+        Self {
+            max_connections,
+            max_connections_per_ip: None,
+            max_accepts_per_sec: None,
+            global_max_connections: None,
+        }
+    }
+}
+
+/// Shared by every TCP-based source, and replaced when a reload changes the limit. Connections
+/// holding permits from the previous semaphore keep them until they close.
+static GLOBAL_CONNECTIONS: Lazy<Mutex<Option<(u32, Arc<Semaphore>)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+fn global_semaphore(max_connections: u32) -> Arc<Semaphore> {
+    let mut global = GLOBAL_CONNECTIONS.lock().expect("poisoned lock");
+    match &*global {
+        Some((current, semaphore)) if *current == max_connections => Arc::clone(semaphore),
+        _ => {
+            let semaphore = Arc::new(Semaphore::new(max_connections as usize));
+            *global = Some((max_connections, Arc::clone(&semaphore)));
+            semaphore
+        }
+    }
+}
+
+/// Held for as long as a connection is open, to count it against the limits.
+pub struct ConnectionPermit {
+    _global: Option<OwnedSemaphorePermit>,
+    _source: Option<OwnedSemaphorePermit>,
+    _ip: Option<IpPermit>,
+}
+
+struct IpPermit {
+    ip: IpAddr,
+    counts: Arc<Mutex<HashMap<IpAddr, u32>>>,
+}
+
+impl Drop for IpPermit {
+    fn drop(&mut self) {
+        let mut counts = self.counts.lock().expect("poisoned lock");
+        if let Some(count) = counts.get_mut(&self.ip) {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&self.ip);
+            }
+        }
+    }
+}
+
+/// Spaces out accepts with the generic cell rate algorithm, which allows a burst of up to
+/// `burst` ahead of the steady rate.
+struct AcceptRate {
+    interval: Duration,
+    burst: Duration,
+    theoretical_arrival: Instant,
+}
+
+impl AcceptRate {
+    fn new(per_sec: u32) -> Self {
+        let per_sec = per_sec.max(1);
+        let interval = Duration::from_secs(1) / per_sec;
+        Self {
+            interval,
+            burst: interval * (per_sec - 1),
+            theoretical_arrival: Instant::now(),
+        }
+    }
+
+    async fn wait(&mut self) {
+        let now = Instant::now();
+        let arrival = self.theoretical_arrival.max(now);
+        if let Some(allowed) = arrival.checked_sub(self.burst) {
+            if allowed > now {
+                emit!(&TcpConnectionLimitReached { limit: "rate" });
+                sleep_until(allowed).await;
+            }
+        }
+        self.theoretical_arrival = arrival + self.interval;
+    }
+}
+
+struct ConnectionLimiter {
+    global: Option<Arc<Semaphore>>,
+    source: Option<Arc<Semaphore>>,
+    max_per_ip: Option<u32>,
+    ip_counts: Arc<Mutex<HashMap<IpAddr, u32>>>,
+    rate: Option<AcceptRate>,
+}
+
+impl ConnectionLimiter {
+    fn new(limits: ConnectionLimits) -> Self {
+        Self {
+            global: limits.global_max_connections.map(global_semaphore),
+            source: limits
+                .max_connections
+                .map(|max| Arc::new(Semaphore::new(max as usize))),
+            max_per_ip: limits.max_connections_per_ip,
+            ip_counts: Arc::default(),
+            rate: limits.max_accepts_per_sec.map(AcceptRate::new),
+        }
+    }
+
+    /// Waits until the limits allow another connection to be accepted.
+    async fn acquire(&mut self) -> ConnectionPermit {
+        let global = acquire(&self.global, "global").await;
+        let source = acquire(&self.source, "source").await;
+        if let Some(rate) = &mut self.rate {
+            rate.wait().await;
+        }
+        ConnectionPermit {
+            _global: global,
+            _source: source,
+            _ip: None,
+        }
+    }
+
+    /// Counts an accepted connection against the limit for its IP address, returning None if that
+    /// is already reached.
+    fn acquire_ip(&self, permit: ConnectionPermit, ip: IpAddr) -> Option<ConnectionPermit> {
+        let max_per_ip = match self.max_per_ip {
+            Some(max_per_ip) => max_per_ip,
+            None => return Some(permit),
+        };
+
+        let mut counts = self.ip_counts.lock().expect("poisoned lock");
+        let count = counts.entry(ip).or_insert(0);
+        if *count >= max_per_ip {
+            return None;
+        }
+        *count += 1;
+
+        Some(ConnectionPermit {
+            _ip: Some(IpPermit {
+                ip,
+                counts: Arc::clone(&self.ip_counts),
+            }),
+            ..permit
+        })
+    }
+}
+
+async fn acquire(
+    semaphore: &Option<Arc<Semaphore>>,
+    limit: &'static str,
+) -> Option<OwnedSemaphorePermit> {
+    let semaphore = semaphore.as_ref()?;
+    match Arc::clone(semaphore).try_acquire_owned() {
+        Ok(permit) => Some(permit),
+        Err(_) => {
+            emit!(&TcpConnectionLimitReached { limit });
+            // The semaphore is never closed, so this always gets a permit.
+            Arc::clone(semaphore).acquire_owned().await.ok()
+        }
+    }
+}
+
+/// Accepts connections as the limits allow, pairing each with the permit it holds.
+pub(super) fn accept_stream_limited(
+    mut listener: MaybeTlsListener,
+    limits: ConnectionLimits,
+) -> impl Stream<
+    Item = (
+        crate::tls::Result<MaybeTlsIncomingStream<TcpStream>>,
+        ConnectionPermit,
+    ),
+> {
+    let mut limiter = ConnectionLimiter::new(limits);
+    stream! {
+        loop {
+            let permit = limiter.acquire().await;
+            let connection = listener.accept().await;
+            let permit = match &connection {
+                Ok(socket) => {
+                    let peer_addr = socket.peer_addr().ip();
+                    match limiter.acquire_ip(permit, peer_addr) {
+                        Some(permit) => permit,
+                        None => {
+                            emit!(&TcpConnectionRejected {
+                                peer_addr,
+                                reason: "per_ip_limit",
+                            });
+                            continue;
+                        }
+                    }
+                }
+                Err(_) => permit,
+            };
+            yield (connection, permit);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn limits_connections_per_ip() {
+        let limiter = ConnectionLimiter::new(ConnectionLimits {
+            max_connections_per_ip: Some(2),
+            ..Default::default()
+        });
+        let first: IpAddr = "192.0.2.1".parse().unwrap();
+        let second: IpAddr = "192.0.2.2".parse().unwrap();
+        let permit = || ConnectionPermit {
+            _global: None,
+            _source: None,
+            _ip: None,
+        };
+
+        let a = limiter.acquire_ip(permit(), first).unwrap();
+        let _b = limiter.acquire_ip(permit(), first).unwrap();
+        assert!(limiter.acquire_ip(permit(), first).is_none());
+        assert!(limiter.acquire_ip(permit(), second).is_some());
+
+        drop(a);
+        assert!(limiter.acquire_ip(permit(), first).is_some());
+    }
+
+    #[tokio::test]
+    async fn waits_for_source_permits() {
+        let mut limiter = ConnectionLimiter::new(ConnectionLimits::new(Some(1)));
+
+        let permit = limiter.acquire().await;
+        assert!(limiter.acquire().now_or_never().is_none());
+
+        drop(permit);
+        assert!(limiter.acquire().now_or_never().is_some());
+    }
+
+    #[tokio::test]
+    async fn limits_accept_rate() {
+        tokio::time::pause();
+        let mut limiter = ConnectionLimiter::new(ConnectionLimits {
+            max_accepts_per_sec: Some(10),
+            ..Default::default()
+        });
+
+        // A second's worth can be accepted straight away, then one every 100 milliseconds.
+        let start = Instant::now();
+        for _ in 0..10 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        limiter.acquire().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn shares_global_semaphore() {
+        let a = global_semaphore(100);
+        let b = global_semaphore(100);
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &global_semaphore(200)));
+    }
+}
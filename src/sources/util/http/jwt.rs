@@ -0,0 +1,449 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock, Weak},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use http::{Request, Uri};
+use hyper::Body;
+use openssl::{
+    bn::BigNum,
+    ec::{EcGroup, EcKey},
+    ecdsa::EcdsaSig,
+    hash::MessageDigest,
+    nid::Nid,
+    pkey::{PKey, Public},
+    rsa::Rsa,
+    sign::Verifier,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use warp::http::{HeaderMap, StatusCode};
+
+use super::error::ErrorMessage;
+use crate::{
+    config::ProxyConfig,
+    http::HttpClient,
+    internal_events::HttpJwksRefreshError,
+    tls::{TlsConfig, TlsSettings},
+};
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct JwtAuthConfig {
+    pub jwks_url: String,
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
+    #[serde(default = "default_refresh_interval_secs")]
+    pub refresh_interval_secs: u64,
+    pub tls: Option<TlsConfig>,
+}
+
+const fn default_refresh_interval_secs() -> u64 {
+    300
+}
+
+impl JwtAuthConfig {
+    /// Builds the validator, which fetches the signing keys from `jwks_url` in the background
+    /// until it is dropped.
+    pub fn build(&self, proxy: &ProxyConfig) -> crate::Result<JwtAuth> {
+        let uri = self.jwks_url.parse::<Uri>()?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, proxy)?;
+        let keys = Arc::new(RwLock::new(HashMap::new()));
+
+        tokio::spawn(refresh_keys(
+            client,
+            uri,
+            Arc::downgrade(&keys),
+            Duration::from_secs(self.refresh_interval_secs),
+        ));
+
+        Ok(JwtAuth {
+            keys,
+            issuer: self.issuer.clone(),
+            audience: self.audience.clone(),
+        })
+    }
+}
+
+type Keys = HashMap<Option<String>, PKey<Public>>;
+
+/// Validates bearer tokens against the keys published at a JWKS URL.
+#[derive(Clone)]
+pub struct JwtAuth {
+    keys: Arc<RwLock<Keys>>,
+    issuer: Option<String>,
+    audience: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Header {
+    alg: String,
+    kid: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Claims {
+    exp: Option<u64>,
+    nbf: Option<u64>,
+    iss: Option<String>,
+    #[serde(default)]
+    aud: Value,
+}
+
+impl JwtAuth {
+    pub fn is_valid(&self, headers: &HeaderMap) -> Result<(), ErrorMessage> {
+        let token = headers
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| unauthorized("No bearer token"))?;
+
+        let mut parts = token.split('.');
+        let (header, claims, signature) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(header), Some(claims), Some(signature)) if parts.next().is_none() => {
+                (header, claims, signature)
+            }
+            _ => return Err(invalid_token()),
+        };
+
+        let decoded_header: Header = decode_json(header)?;
+        let signature = decode(signature)?;
+        let signed = &token[..header.len() + 1 + claims.len()];
+        {
+            let keys = self.keys.read().expect("poisoned lock");
+            let key = keys
+                .get(&decoded_header.kid)
+                .ok_or_else(|| unauthorized("Unknown signing key"))?;
+            verify_signature(&decoded_header.alg, key, signed.as_bytes(), &signature)?;
+        }
+
+        let claims: Claims = decode_json(claims)?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if claims.exp.map_or(false, |exp| exp <= now) {
+            return Err(unauthorized("Token has expired"));
+        }
+        if claims.nbf.map_or(false, |nbf| nbf > now) {
+            return Err(unauthorized("Token is not valid yet"));
+        }
+        if let Some(issuer) = &self.issuer {
+            if claims.iss.as_ref() != Some(issuer) {
+                return Err(unauthorized("Token has the wrong issuer"));
+            }
+        }
+        if let Some(audience) = &self.audience {
+            let matches = match &claims.aud {
+                Value::String(aud) => aud == audience,
+                Value::Array(auds) => auds.iter().any(|aud| aud.as_str() == Some(audience)),
+                _ => false,
+            };
+            if !matches {
+                return Err(unauthorized("Token has the wrong audience"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn verify_signature(
+    alg: &str,
+    key: &PKey<Public>,
+    signed: &[u8],
+    signature: &[u8],
+) -> Result<(), ErrorMessage> {
+    let (digest, ec_size) = match alg {
+        "RS256" => (MessageDigest::sha256(), None),
+        "RS384" => (MessageDigest::sha384(), None),
+        "RS512" => (MessageDigest::sha512(), None),
+        "ES256" => (MessageDigest::sha256(), Some(32)),
+        "ES384" => (MessageDigest::sha384(), Some(48)),
+        _ => return Err(unauthorized("Unsupported signing algorithm")),
+    };
+    // A key must only be used with the kind of algorithm it was published for.
+    if ec_size.is_some() != key.ec_key().is_ok() {
+        return Err(invalid_token());
+    }
+
+    let verified = (|| {
+        // JWTs carry ECDSA signatures as `r || s`, where OpenSSL expects DER.
+        let signature = match ec_size {
+            Some(size) if signature.len() == size * 2 => EcdsaSig::from_private_components(
+                BigNum::from_slice(&signature[..size])?,
+                BigNum::from_slice(&signature[size..])?,
+            )?
+            .to_der()?,
+            Some(_) => return Ok(false),
+            None => signature.to_vec(),
+        };
+        let mut verifier = Verifier::new(digest, key)?;
+        verifier.update(signed)?;
+        verifier.verify(&signature)
+    })();
+
+    match verified {
+        Ok(true) => Ok(()),
+        _ => Err(invalid_token()),
+    }
+}
+
+fn decode(part: &str) -> Result<Vec<u8>, ErrorMessage> {
+    base64::decode_config(part, base64::URL_SAFE_NO_PAD).map_err(|_| invalid_token())
+}
+
+fn decode_json<T: for<'de> Deserialize<'de>>(part: &str) -> Result<T, ErrorMessage> {
+    serde_json::from_slice(&decode(part)?).map_err(|_| invalid_token())
+}
+
+fn invalid_token() -> ErrorMessage {
+    unauthorized("Invalid token")
+}
+
+fn unauthorized(message: &str) -> ErrorMessage {
+    ErrorMessage::new(StatusCode::UNAUTHORIZED, message.to_owned())
+}
+
+#[derive(Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Deserialize)]
+struct Jwk {
+    kty: String,
+    kid: Option<String>,
+    #[serde(rename = "use")]
+    use_: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+    crv: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+}
+
+impl Jwk {
+    /// The public key, if it's one that can verify signatures.
+    fn public_key(&self) -> Option<PKey<Public>> {
+        if self.use_.as_deref().map_or(false, |use_| use_ != "sig") {
+            return None;
+        }
+
+        let component = |value: &Option<String>| {
+            let bytes = decode(value.as_deref()?).ok()?;
+            BigNum::from_slice(&bytes).ok()
+        };
+        match self.kty.as_str() {
+            "RSA" => {
+                let rsa = Rsa::from_public_components(component(&self.n)?, component(&self.e)?);
+                PKey::from_rsa(rsa.ok()?).ok()
+            }
+            "EC" => {
+                let curve = match self.crv.as_deref()? {
+                    "P-256" => Nid::X9_62_PRIME256V1,
+                    "P-384" => Nid::SECP384R1,
+                    _ => return None,
+                };
+                let group = EcGroup::from_curve_name(curve).ok()?;
+                let ec = EcKey::from_public_key_affine_coordinates(
+                    &group,
+                    &component(&self.x)?,
+                    &component(&self.y)?,
+                );
+                PKey::from_ec_key(ec.ok()?).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+fn parse_keys(body: &[u8]) -> crate::Result<Keys> {
+    let set: JwkSet = serde_json::from_slice(body)?;
+    let keys: Keys = set
+        .keys
+        .iter()
+        .filter_map(|jwk| Some((jwk.kid.clone(), jwk.public_key()?)))
+        .collect();
+    if keys.is_empty() {
+        return Err("No usable signing keys".into());
+    }
+    Ok(keys)
+}
+
+async fn fetch_keys(client: &HttpClient, uri: &Uri) -> crate::Result<Keys> {
+    let request = Request::get(uri).body(Body::empty())?;
+    let response = client.send(request).await?;
+    let status = response.status();
+    let body = hyper::body::to_bytes(response.into_body()).await?;
+    if !status.is_success() {
+        return Err(format!("Unexpected status: {}", status).into());
+    }
+    parse_keys(&body)
+}
+
+async fn refresh_keys(client: HttpClient, uri: Uri, keys: Weak<RwLock<Keys>>, interval: Duration) {
+    loop {
+        let fetched = fetch_keys(&client, &uri).await;
+        let keys = match keys.upgrade() {
+            Some(keys) => keys,
+            None => break,
+        };
+        match fetched {
+            Ok(fetched) => *keys.write().expect("poisoned lock") = fetched,
+            Err(error) => emit!(&HttpJwksRefreshError {
+                error,
+                url: uri.to_string(),
+            }),
+        }
+        drop(keys);
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use openssl::{pkey::Private, sign::Signer};
+    use serde_json::json;
+
+    use super::*;
+
+    fn encode(bytes: &[u8]) -> String {
+        base64::encode_config(bytes, base64::URL_SAFE_NO_PAD)
+    }
+
+    fn token(alg: &str, kid: &str, claims: Value, sign: impl FnOnce(&[u8]) -> Vec<u8>) -> String {
+        let signed = format!(
+            "{}.{}",
+            encode(json!({ "alg": alg, "kid": kid }).to_string().as_bytes()),
+            encode(claims.to_string().as_bytes())
+        );
+        let signature = sign(signed.as_bytes());
+        format!("{}.{}", signed, encode(&signature))
+    }
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        headers
+    }
+
+    fn rsa_sign(key: &PKey<Private>) -> impl FnOnce(&[u8]) -> Vec<u8> + '_ {
+        move |data| {
+            let mut signer = Signer::new(MessageDigest::sha256(), key).unwrap();
+            signer.update(data).unwrap();
+            signer.sign_to_vec().unwrap()
+        }
+    }
+
+    fn auth(jwks: Value) -> JwtAuth {
+        JwtAuth {
+            keys: Arc::new(RwLock::new(
+                parse_keys(jwks.to_string().as_bytes()).unwrap(),
+            )),
+            issuer: Some("https://issuer.example.com".to_owned()),
+            audience: Some("vector".to_owned()),
+        }
+    }
+
+    #[test]
+    fn validates_rsa_tokens() {
+        let rsa = Rsa::generate(2048).unwrap();
+        let auth = auth(json!({
+            "keys": [{
+                "kty": "RSA",
+                "kid": "rsa",
+                "use": "sig",
+                "n": encode(&rsa.n().to_vec()),
+                "e": encode(&rsa.e().to_vec()),
+            }]
+        }));
+        let key = PKey::from_rsa(rsa).unwrap();
+        let claims = json!({
+            "iss": "https://issuer.example.com",
+            "aud": ["other", "vector"],
+            "exp": 4102444800u64,
+        });
+
+        let valid = token("RS256", "rsa", claims.clone(), rsa_sign(&key));
+        assert!(auth.is_valid(&bearer(&valid)).is_ok());
+
+        let tampered = valid.replacen('.', ".e30", 1);
+        assert!(auth.is_valid(&bearer(&tampered)).is_err());
+
+        let unknown_key = token("RS256", "other", claims.clone(), rsa_sign(&key));
+        assert!(auth.is_valid(&bearer(&unknown_key)).is_err());
+
+        let expired = token(
+            "RS256",
+            "rsa",
+            json!({ "iss": "https://issuer.example.com", "aud": "vector", "exp": 1 }),
+            rsa_sign(&key),
+        );
+        assert!(auth.is_valid(&bearer(&expired)).is_err());
+
+        let wrong_audience = token(
+            "RS256",
+            "rsa",
+            json!({ "iss": "https://issuer.example.com", "aud": "other" }),
+            rsa_sign(&key),
+        );
+        assert!(auth.is_valid(&bearer(&wrong_audience)).is_err());
+
+        let unsigned = token("none", "rsa", claims, |_| Vec::new());
+        assert!(auth.is_valid(&bearer(&unsigned)).is_err());
+
+        assert!(auth.is_valid(&HeaderMap::new()).is_err());
+    }
+
+    #[test]
+    fn validates_ec_tokens() {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let ec = EcKey::generate(&group).unwrap();
+        let mut ctx = openssl::bn::BigNumContext::new().unwrap();
+        let (mut x, mut y) = (BigNum::new().unwrap(), BigNum::new().unwrap());
+        ec.public_key()
+            .affine_coordinates_gfp(&group, &mut x, &mut y, &mut ctx)
+            .unwrap();
+        let auth = auth(json!({
+            "keys": [{
+                "kty": "EC",
+                "kid": "ec",
+                "crv": "P-256",
+                "x": encode(&x.to_vec_padded(32).unwrap()),
+                "y": encode(&y.to_vec_padded(32).unwrap()),
+            }]
+        }));
+
+        let claims = json!({ "iss": "https://issuer.example.com", "aud": "vector" });
+        let valid = token("ES256", "ec", claims, |data| {
+            let digest = openssl::hash::hash(MessageDigest::sha256(), data).unwrap();
+            let signature = EcdsaSig::sign(&digest, &ec).unwrap();
+            let mut raw = signature.r().to_vec_padded(32).unwrap();
+            raw.extend(signature.s().to_vec_padded(32).unwrap());
+            raw
+        });
+        assert!(auth.is_valid(&bearer(&valid)).is_ok());
+
+        let rs256 = valid.replacen(
+            &encode(
+                json!({ "alg": "ES256", "kid": "ec" })
+                    .to_string()
+                    .as_bytes(),
+            ),
+            &encode(
+                json!({ "alg": "RS256", "kid": "ec" })
+                    .to_string()
+                    .as_bytes(),
+            ),
+            1,
+        );
+        assert!(auth.is_valid(&bearer(&rs256)).is_err());
+    }
+}
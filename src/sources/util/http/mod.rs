@@ -4,6 +4,8 @@ mod auth;
 mod encoding;
 #[cfg(feature = "sources-utils-http-error")]
 mod error;
+#[cfg(feature = "sources-utils-http-auth")]
+mod jwt;
 #[cfg(feature = "sources-utils-http-prelude")]
 mod prelude;
 #[cfg(any(
@@ -12,6 +14,8 @@ mod prelude;
     feature = "sources-utils-http-query"
 ))]
 mod query;
+#[cfg(feature = "sources-utils-http-auth")]
+mod signature;
 
 #[cfg(feature = "sources-utils-http-auth")]
 pub use auth::{HttpSourceAuth, HttpSourceAuthConfig};
@@ -19,7 +23,11 @@ pub use auth::{HttpSourceAuth, HttpSourceAuthConfig};
 pub use encoding::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use error::ErrorMessage;
+#[cfg(feature = "sources-utils-http-auth")]
+pub use jwt::{JwtAuth, JwtAuthConfig};
 #[cfg(feature = "sources-utils-http-prelude")]
-pub use prelude::HttpSource;
+pub use prelude::{HttpMethod, HttpSource};
 #[cfg(feature = "sources-utils-http-query")]
 pub use query::add_query_parameters;
+#[cfg(feature = "sources-utils-http-auth")]
+pub use signature::{HmacAuth, HmacAuthConfig};
@@ -1,8 +1,15 @@
-use std::{collections::HashMap, convert::TryFrom, fmt, net::SocketAddr};
+use std::{
+    collections::HashMap,
+    convert::{Infallible, TryFrom},
+    fmt,
+    net::SocketAddr,
+};
 
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::{FutureExt, TryFutureExt};
+use hyper::{server::accept, service::make_service_fn, Server};
+use serde::{Deserialize, Serialize};
 use vector_core::{
     event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
     ByteSizeOf,
@@ -14,7 +21,8 @@ use warp::{
     },
     http::{HeaderMap, StatusCode},
     reject::Rejection,
-    Filter,
+    reply::Response,
+    Filter, Reply,
 };
 
 use super::{
@@ -24,11 +32,39 @@ use super::{
 };
 use crate::{
     config::{AcknowledgementsConfig, SourceContext},
+    http::Http2Config,
     internal_events::{HttpBadRequest, HttpBytesReceived, HttpEventsReceived},
     tls::{MaybeTlsSettings, TlsConfig},
     SourceSender,
 };
 
+/// The HTTP method a source accepts requests with.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl Default for HttpMethod {
+    fn default() -> Self {
+        Self::Post
+    }
+}
+
+impl HttpMethod {
+    fn filter(self) -> BoxedFilter<()> {
+        match self {
+            Self::Post => warp::post().boxed(),
+            Self::Put => warp::put().boxed(),
+            Self::Patch => warp::patch().boxed(),
+            Self::Delete => warp::delete().boxed(),
+        }
+    }
+}
+
 #[async_trait]
 pub trait HttpSource: Clone + Send + Sync + 'static {
     fn build_events(
@@ -39,24 +75,37 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         path: &str,
     ) -> Result<Vec<Event>, ErrorMessage>;
 
+    /// Checks a request beyond the basic `auth`, before its body is decompressed or decoded.
+    fn authenticate(&self, _headers: &HeaderMap, _body: &[u8]) -> Result<(), ErrorMessage> {
+        Ok(())
+    }
+
+    /// The response to a request once its events have been accepted.
+    fn build_response(&self, _events_count: usize) -> Response {
+        warp::reply().into_response()
+    }
+
     fn run(
         self,
         address: SocketAddr,
         path: &str,
+        method: HttpMethod,
         strict_path: bool,
         tls: &Option<TlsConfig>,
         auth: &Option<HttpSourceAuthConfig>,
+        http2: &Http2Config,
         cx: SourceContext,
         acknowledgements: AcknowledgementsConfig,
     ) -> crate::Result<crate::sources::Source> {
-        let tls = MaybeTlsSettings::from_config(tls, true)?;
+        let tls = http2.server_tls(MaybeTlsSettings::from_config(tls, true)?);
+        let http2 = http2.clone();
         let protocol = tls.http_protocol_name();
         let auth = HttpSourceAuth::try_from(auth.as_ref())?;
         let path = path.to_owned();
         let acknowledgements = cx.globals.acknowledgements.merge(&acknowledgements);
         Ok(Box::pin(async move {
             let span = crate::trace::current_span();
-            let mut filter: BoxedFilter<()> = warp::post().boxed();
+            let mut filter: BoxedFilter<()> = method.filter();
             for s in path.split('/').filter(|&x| !x.is_empty()) {
                 filter = filter.and(warp::path(s.to_string())).boxed()
             }
@@ -97,6 +146,7 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
 
                         let events = auth
                             .is_valid(&auth_header)
+                            .and_then(|()| self.authenticate(&headers, &body))
                             .and_then(|()| decode(&encoding_header, body))
                             .and_then(|body| {
                                 self.build_events(body, headers, query_parameters, path.as_str())
@@ -108,7 +158,8 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
                                     http_path,
                                     protocol,
                                 });
-                                events
+                                let response = self.build_response(events.len());
+                                (events, response)
                             });
 
                         handle_request(events, acknowledgements.enabled(), cx.out.clone())
@@ -130,13 +181,19 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
             info!(message = "Building HTTP server.", address = %address);
 
             let listener = tls.bind(&address).await.unwrap();
-            warp::serve(routes)
-                .serve_incoming_with_graceful_shutdown(
+            let service = warp::service(routes);
+            let make_service = make_service_fn(move |_| {
+                let service = service.clone();
+                async move { Ok::<_, Infallible>(service) }
+            });
+            http2
+                .configure_server(Server::builder(accept::from_stream(
                     listener.accept_stream(),
-                    cx.shutdown.map(|_| ()),
-                )
-                .await;
-            Ok(())
+                )))
+                .serve(make_service)
+                .with_graceful_shutdown(cx.shutdown.map(|_| ()))
+                .await
+                .map_err(|error| error!(message = "HTTP server failed.", %error))
         }))
     }
 }
@@ -152,12 +209,12 @@ impl fmt::Debug for RejectShuttingDown {
 impl warp::reject::Reject for RejectShuttingDown {}
 
 async fn handle_request(
-    events: Result<Vec<Event>, ErrorMessage>,
+    events: Result<(Vec<Event>, Response), ErrorMessage>,
     acknowledgements: bool,
     mut out: SourceSender,
-) -> Result<impl warp::Reply, Rejection> {
+) -> Result<Response, Rejection> {
     match events {
-        Ok(mut events) => {
+        Ok((mut events, response)) => {
             let receiver = BatchNotifier::maybe_apply_to_events(acknowledgements, &mut events);
 
             out.send_all(&mut futures::stream::iter(events))
@@ -168,7 +225,7 @@ async fn handle_request(
                     error!(message = "Tried to send the following event.", %error);
                     warp::reject::custom(RejectShuttingDown)
                 })
-                .and_then(|_| handle_batch_status(receiver))
+                .and_then(|_| handle_batch_status(receiver, response))
                 .await
         }
         Err(error) => {
@@ -183,11 +240,12 @@ async fn handle_request(
 
 async fn handle_batch_status(
     receiver: Option<BatchStatusReceiver>,
-) -> Result<impl warp::Reply, Rejection> {
+    response: Response,
+) -> Result<Response, Rejection> {
     match receiver {
-        None => Ok(warp::reply()),
+        None => Ok(response),
         Some(receiver) => match receiver.await {
-            BatchStatus::Delivered => Ok(warp::reply()),
+            BatchStatus::Delivered => Ok(response),
             BatchStatus::Errored => Err(warp::reject::custom(ErrorMessage::new(
                 StatusCode::INTERNAL_SERVER_ERROR,
                 "Error delivering contents to sink".into(),
@@ -2,16 +2,18 @@ use std::collections::HashMap;
 
 use vector_core::event::Event;
 
-pub fn add_query_parameters(
+/// Add the value of each configured query parameter to the events, as pairs of the parameter
+/// name and the field to put it in.
+pub fn add_query_parameters<'a>(
     events: &mut [Event],
-    query_parameters_config: &[String],
+    query_parameters_config: impl IntoIterator<Item = (&'a str, &'a str)>,
     query_parameters: HashMap<String, String>,
 ) {
-    for query_parameter_name in query_parameters_config {
+    for (query_parameter_name, field) in query_parameters_config {
         let value = query_parameters.get(query_parameter_name);
         for event in events.iter_mut() {
             event.as_mut_log().insert(
-                query_parameter_name as &str,
+                field,
                 crate::event::Value::from(value.map(String::to_owned)),
             );
         }
@@ -0,0 +1,287 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use openssl::{
+    hash::MessageDigest,
+    memcmp,
+    pkey::{PKey, Private},
+    sign::Signer,
+};
+use serde::{Deserialize, Serialize};
+use warp::http::{HeaderMap, HeaderName, StatusCode};
+
+use super::error::ErrorMessage;
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HmacAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl Default for HmacAlgorithm {
+    fn default() -> Self {
+        Self::Sha256
+    }
+}
+
+impl HmacAlgorithm {
+    fn digest(self) -> MessageDigest {
+        match self {
+            Self::Sha1 => MessageDigest::sha1(),
+            Self::Sha256 => MessageDigest::sha256(),
+            Self::Sha512 => MessageDigest::sha512(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureEncoding {
+    Hex,
+    Base64,
+}
+
+impl Default for SignatureEncoding {
+    fn default() -> Self {
+        Self::Hex
+    }
+}
+
+/// How the signature is laid out in its header.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureScheme {
+    /// The header holds a signature of the body, after an optional `prefix`, as GitHub sends.
+    Plain,
+    /// The header holds `t=<timestamp>,v1=<signature>`, where the signature covers
+    /// `<timestamp>.<body>`, as Stripe sends.
+    Stripe,
+}
+
+impl Default for SignatureScheme {
+    fn default() -> Self {
+        Self::Plain
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct HmacAuthConfig {
+    pub secret: String,
+    pub header: String,
+    #[serde(default)]
+    pub algorithm: HmacAlgorithm,
+    #[serde(default)]
+    pub encoding: SignatureEncoding,
+    pub prefix: Option<String>,
+    #[serde(default)]
+    pub scheme: SignatureScheme,
+    /// How old a `stripe` signature's timestamp may be before the request is rejected.
+    #[serde(default = "default_tolerance_secs")]
+    pub tolerance_secs: u64,
+}
+
+const fn default_tolerance_secs() -> u64 {
+    300
+}
+
+impl HmacAuthConfig {
+    pub fn build(&self) -> crate::Result<HmacAuth> {
+        Ok(HmacAuth {
+            key: PKey::hmac(self.secret.as_bytes())?,
+            header: HeaderName::from_bytes(self.header.as_bytes())?,
+            digest: self.algorithm.digest(),
+            encoding: self.encoding,
+            prefix: self.prefix.clone(),
+            scheme: self.scheme,
+            tolerance_secs: self.tolerance_secs,
+        })
+    }
+}
+
+/// Verifies that requests are signed with a shared secret.
+#[derive(Clone)]
+pub struct HmacAuth {
+    key: PKey<Private>,
+    header: HeaderName,
+    digest: MessageDigest,
+    encoding: SignatureEncoding,
+    prefix: Option<String>,
+    scheme: SignatureScheme,
+    tolerance_secs: u64,
+}
+
+impl HmacAuth {
+    /// Checks the signature of the raw request body, before it is decompressed.
+    pub fn is_valid(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), ErrorMessage> {
+        let header = headers
+            .get(&self.header)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| unauthorized(format!("Missing {} header", self.header)))?;
+
+        match self.scheme {
+            SignatureScheme::Plain => {
+                let signature = match &self.prefix {
+                    Some(prefix) => header.strip_prefix(prefix.as_str()),
+                    None => Some(header),
+                }
+                .and_then(|signature| self.decode(signature))
+                .ok_or_else(invalid_signature)?;
+
+                let expected = self.sign(&[body]).map_err(|_| invalid_signature())?;
+                verify(&expected, &signature)
+            }
+            SignatureScheme::Stripe => {
+                let mut timestamp = None;
+                let mut signatures = Vec::new();
+                for (key, value) in header.split(',').filter_map(|part| part.split_once('=')) {
+                    match key.trim() {
+                        "t" => timestamp = Some(value.trim()),
+                        "v1" => signatures.extend(self.decode(value.trim())),
+                        _ => {}
+                    }
+                }
+                let timestamp = timestamp.ok_or_else(invalid_signature)?;
+
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs();
+                match timestamp.parse::<u64>() {
+                    Ok(signed_at) if now.saturating_sub(signed_at) <= self.tolerance_secs => {}
+                    _ => return Err(unauthorized("Signature timestamp is too old".to_owned())),
+                }
+
+                let expected = self
+                    .sign(&[timestamp.as_bytes(), b".", body])
+                    .map_err(|_| invalid_signature())?;
+                signatures
+                    .iter()
+                    .find_map(|signature| verify(&expected, signature).ok())
+                    .ok_or_else(invalid_signature)
+            }
+        }
+    }
+
+    fn sign(&self, parts: &[&[u8]]) -> Result<Vec<u8>, openssl::error::ErrorStack> {
+        let mut signer = Signer::new(self.digest, &self.key)?;
+        for part in parts {
+            signer.update(part)?;
+        }
+        signer.sign_to_vec()
+    }
+
+    fn decode(&self, signature: &str) -> Option<Vec<u8>> {
+        match self.encoding {
+            SignatureEncoding::Hex => hex::decode(signature).ok(),
+            SignatureEncoding::Base64 => base64::decode(signature).ok(),
+        }
+    }
+}
+
+fn verify(expected: &[u8], signature: &[u8]) -> Result<(), ErrorMessage> {
+    // `memcmp::eq` panics when given slices of different lengths.
+    if expected.len() == signature.len() && memcmp::eq(expected, signature) {
+        Ok(())
+    } else {
+        Err(invalid_signature())
+    }
+}
+
+fn invalid_signature() -> ErrorMessage {
+    unauthorized("Invalid signature".to_owned())
+}
+
+fn unauthorized(message: String) -> ErrorMessage {
+    ErrorMessage::new(StatusCode::UNAUTHORIZED, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn auth(config: &str) -> HmacAuth {
+        toml::from_str::<HmacAuthConfig>(config)
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+
+    fn headers(name: &'static str, value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(name, value.parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn github_style_signature() {
+        let auth = auth(
+            r#"
+                secret = "It's a Secret to Everybody"
+                header = "X-Hub-Signature-256"
+                prefix = "sha256="
+            "#,
+        );
+        let body = b"Hello, World!";
+        let signature = "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17";
+
+        assert!(auth
+            .is_valid(&headers("X-Hub-Signature-256", signature), body)
+            .is_ok());
+        assert!(auth
+            .is_valid(&headers("X-Hub-Signature-256", signature), b"Goodbye")
+            .is_err());
+        assert!(auth
+            .is_valid(&headers("X-Hub-Signature-256", &signature[7..]), body)
+            .is_err());
+        assert!(auth.is_valid(&HeaderMap::new(), body).is_err());
+    }
+
+    #[test]
+    fn base64_signature() {
+        let auth = auth(
+            r#"
+                secret = "secret"
+                header = "X-Signature"
+                algorithm = "sha1"
+                encoding = "base64"
+            "#,
+        );
+        let signature = base64::encode(auth.sign(&[b"body"]).unwrap());
+
+        assert!(auth
+            .is_valid(&headers("X-Signature", &signature), b"body")
+            .is_ok());
+        assert!(auth
+            .is_valid(&headers("X-Signature", "AAAA"), b"body")
+            .is_err());
+    }
+
+    #[test]
+    fn stripe_style_signature() {
+        let auth = auth(
+            r#"
+                secret = "whsec_test"
+                header = "Stripe-Signature"
+                scheme = "stripe"
+            "#,
+        );
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            .to_string();
+        let signature = hex::encode(auth.sign(&[now.as_bytes(), b".", b"body"]).unwrap());
+
+        let header = format!("t={},v1=00,v1={},v0=ignored", now, signature);
+        assert!(auth
+            .is_valid(&headers("Stripe-Signature", &header), b"body")
+            .is_ok());
+
+        let stale = format!("t=1000,v1={}", signature);
+        assert!(auth
+            .is_valid(&headers("Stripe-Signature", &stale), b"body")
+            .is_err());
+    }
+}
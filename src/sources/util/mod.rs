@@ -5,6 +5,8 @@ mod body_decoding;
     feature = "codecs",
 ))]
 mod codecs;
+#[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
+mod connection_limits;
 mod encoding_config;
 #[cfg(any(
     feature = "sources-file",
@@ -37,6 +39,8 @@ mod wrappers;
     feature = "codecs",
 ))]
 pub use codecs::StreamDecodingError;
+#[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
+pub use connection_limits::ConnectionLimits;
 pub use encoding_config::EncodingConfig;
 pub use multiline_config::MultilineConfig;
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
@@ -60,6 +64,6 @@ pub use self::http::decode;
 #[cfg(feature = "sources-utils-http-error")]
 pub use self::http::ErrorMessage;
 #[cfg(feature = "sources-utils-http-prelude")]
-pub use self::http::HttpSource;
+pub use self::http::{HttpMethod, HttpSource};
 #[cfg(feature = "sources-utils-http-auth")]
-pub use self::http::HttpSourceAuthConfig;
+pub use self::http::{HmacAuth, HmacAuthConfig, HttpSourceAuthConfig, JwtAuth, JwtAuthConfig};
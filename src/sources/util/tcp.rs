@@ -5,7 +5,7 @@ use serde::{de, Deserialize, Deserializer, Serialize};
 use smallvec::SmallVec;
 use socket2::SockRef;
 use std::net::{IpAddr, SocketAddr};
-use std::{fmt, io, mem::drop, sync::Arc, time::Duration};
+use std::{collections::BTreeMap, fmt, io, mem::drop, sync::Arc, time::Duration};
 use tokio::{
     io::AsyncWriteExt,
     net::{TcpListener, TcpStream},
@@ -14,17 +14,20 @@ use tokio::{
 use tokio_util::codec::{Decoder, FramedRead};
 use tracing_futures::Instrument;
 
-use super::{AfterReadExt as _, StreamDecodingError};
+use super::{
+    connection_limits::accept_stream_limited, AfterReadExt as _, ConnectionLimits,
+    StreamDecodingError,
+};
 use crate::{
     codecs::ReadyFrames,
     config::{AcknowledgementsConfig, Resource, SourceContext},
-    event::{BatchNotifier, BatchStatus, Event},
+    event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
         ConnectionOpen, OpenGauge, TcpBytesReceived, TcpSendAckError, TcpSocketConnectionError,
     },
     shutdown::ShutdownSignal,
-    tcp::TcpKeepaliveConfig,
-    tls::{MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
+    tcp::{ProxyProtocolConfig, TcpKeepaliveConfig},
+    tls::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
     SourceSender,
 };
 
@@ -70,6 +73,13 @@ pub enum TcpSourceAck {
 
 pub trait TcpSourceAcker {
     fn build_ack(self, ack: TcpSourceAck) -> Option<Bytes>;
+
+    // This function builds a message that is sent at the returned interval
+    // while waiting for events to be acknowledged, for protocols where the
+    // client would otherwise give up on a slow acknowledgement.
+    fn build_keepalive(&self) -> Option<(Duration, Bytes)> {
+        None
+    }
 }
 
 pub struct TcpNullAcker;
@@ -104,6 +114,24 @@ where
 
     fn build_acker(&self, item: &[Self::Item]) -> Self::Acker;
 
+    // The field to store the subject of the client's TLS certificate in, if any.
+    fn tls_client_metadata_key(&self) -> Option<&str> {
+        None
+    }
+
+    // Whether connections start with a PROXY protocol header, and where to store the address of
+    // the original client it passes on.
+    fn proxy_protocol(&self) -> Option<&ProxyProtocolConfig> {
+        None
+    }
+
+    // This function builds a message that is sent to the client as soon as
+    // the connection is established, before anything is read, for protocols
+    // where the server starts a handshake.
+    fn build_greeting(&self, _decoder: &Self::Decoder) -> Option<Bytes> {
+        None
+    }
+
     fn run(
         self,
         addr: SocketListenAddr,
@@ -113,16 +141,23 @@ where
         receive_buffer_bytes: Option<usize>,
         cx: SourceContext,
         acknowledgements: AcknowledgementsConfig,
-        max_connections: Option<u32>,
+        connection_limits: ConnectionLimits,
     ) -> crate::Result<crate::sources::Source> {
         let acknowledgements = cx.globals.acknowledgements.merge(&acknowledgements);
+        let connection_limits = ConnectionLimits {
+            global_max_connections: cx.globals.max_connections,
+            ..connection_limits
+        };
 
         let listenfd = ListenFd::from_env();
+        let proxy_protocol = self
+            .proxy_protocol()
+            .map_or(false, |proxy_protocol| proxy_protocol.enabled);
 
         Ok(Box::pin(async move {
             let listener = match make_listener(addr, listenfd, &tls).await {
                 None => return Err(()),
-                Some(listener) => listener,
+                Some(listener) => listener.with_proxy_protocol(proxy_protocol),
             };
 
             info!(
@@ -143,8 +178,7 @@ where
             let connection_gauge = OpenGauge::new();
             let shutdown_clone = cx.shutdown.clone();
 
-            listener
-                .accept_stream_limited(max_connections)
+            accept_stream_limited(listener, connection_limits)
                 .take_until(shutdown_clone)
                 .for_each(move |(connection, permit)| {
                     let shutdown_signal = cx.shutdown.clone();
@@ -237,6 +271,15 @@ async fn handle_stream<T>(
         }
     };
 
+    // Behind a proxy, the peer is the proxy, so the client it passed on is used in its place.
+    let proxy_addr = socket.proxied_peer_addr().map(|_| peer_addr);
+    let peer_addr = socket
+        .proxied_peer_addr()
+        .map_or(peer_addr, |client_addr| client_addr.ip());
+    let client_ip_key = source
+        .proxy_protocol()
+        .and_then(|proxy_protocol| proxy_protocol.client_ip_key.clone());
+
     if let Some(keepalive) = keepalive {
         if let Err(error) = socket.set_keepalive(keepalive) {
             warn!(message = "Failed configuring TCP keepalive.", %error);
@@ -249,13 +292,25 @@ async fn handle_stream<T>(
         }
     }
 
+    let certificate_metadata = socket
+        .peer_certificate_metadata()
+        .map(certificate_metadata_value);
+
+    let decoder = source.decoder();
+    if let Some(greeting) = source.build_greeting(&decoder) {
+        if let Err(error) = socket.write_all(&greeting).await {
+            emit!(&TcpSendAckError { error });
+            return;
+        }
+    }
+
     let socket = socket.after_read(move |byte_size| {
         emit!(&TcpBytesReceived {
             byte_size,
             peer_addr
         });
     });
-    let reader = FramedRead::new(socket, source.decoder());
+    let reader = FramedRead::new(socket, decoder);
     let mut reader = ReadyFrames::new(reader);
     let host = Bytes::from(peer_addr.to_string());
 
@@ -290,12 +345,47 @@ async fn handle_stream<T>(
                             }
                         }
                         source.handle_events(&mut events, host.clone(), byte_size);
+                        for event in &mut events {
+                            if let Event::Log(log) = event {
+                                let metadata = log.metadata_mut();
+                                metadata.set_source_field("peer_ip", peer_addr.to_string());
+                                if let Some(proxy_addr) = proxy_addr {
+                                    metadata.set_source_field("proxy_ip", proxy_addr.to_string());
+                                }
+                                if let Some(certificate) = &certificate_metadata {
+                                    metadata.set_source_field("tls_subject", certificate.clone());
+                                }
+                                if let (Some(key), Some(certificate)) = (source.tls_client_metadata_key(), &certificate_metadata) {
+                                    log.insert(key, certificate.clone());
+                                }
+                                if let (Some(key), Some(_)) = (&client_ip_key, proxy_addr) {
+                                    log.insert(key.as_str(), peer_addr.to_string());
+                                }
+                            }
+                        }
                         match out.send_all(&mut stream::iter(events)).await {
                             Ok(_) => {
                                 let ack = match receiver {
                                     None => TcpSourceAck::Ack,
-                                    Some(receiver) =>
-                                        match receiver.await {
+                                    Some(mut receiver) => {
+                                        let mut keepalive = acker.build_keepalive();
+                                        let status = loop {
+                                            let (interval, message) = match &keepalive {
+                                                Some((interval, message)) => (*interval, message.clone()),
+                                                None => break (&mut receiver).await,
+                                            };
+                                            tokio::select! {
+                                                status = &mut receiver => break status,
+                                                _ = sleep(interval) => {
+                                                    let stream = reader.get_mut().get_mut();
+                                                    if let Err(error) = stream.write_all(&message).await {
+                                                        emit!(&TcpSendAckError{ error });
+                                                        keepalive = None;
+                                                    }
+                                                }
+                                            }
+                                        };
+                                        match status {
                                             BatchStatus::Delivered => TcpSourceAck::Ack,
                                             BatchStatus::Errored => {
                                                 warn!(message = "Error delivering events to sink.",
@@ -308,6 +398,7 @@ async fn handle_stream<T>(
                                                 TcpSourceAck::Reject
                                             }
                                         }
+                                    }
                                 };
                                 if let Some(ack_bytes) = acker.build_ack(ack){
                                     let stream = reader.get_mut().get_mut();
@@ -343,6 +434,30 @@ async fn handle_stream<T>(
     }
 }
 
+fn certificate_metadata_value(metadata: CertificateMetadata) -> Value {
+    let CertificateMetadata {
+        country_name,
+        state_or_province_name,
+        locality_name,
+        organization_name,
+        organizational_unit_name,
+        common_name,
+    } = metadata;
+
+    [
+        ("country_name", country_name),
+        ("state_or_province_name", state_or_province_name),
+        ("locality_name", locality_name),
+        ("organization_name", organization_name),
+        ("organizational_unit_name", organizational_unit_name),
+        ("common_name", common_name),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| value.map(|value| (key.to_owned(), Value::from(value))))
+    .collect::<BTreeMap<_, _>>()
+    .into()
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum SocketListenAddr {
@@ -10,7 +10,7 @@ use crate::{
     event::{proto, Event},
     internal_events::{VectorEventReceived, VectorProtoDecodeError},
     sources::{
-        util::{SocketListenAddr, TcpNullAcker, TcpSource},
+        util::{ConnectionLimits, SocketListenAddr, TcpNullAcker, TcpSource},
         Source,
     },
     tcp::TcpKeepaliveConfig,
@@ -66,7 +66,7 @@ impl VectorConfig {
             self.receive_buffer_bytes,
             cx,
             false.into(),
-            None,
+            ConnectionLimits::default(),
         )
     }
 
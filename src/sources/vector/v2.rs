@@ -1,14 +1,14 @@
-use std::net::SocketAddr;
+use std::{io::Read, net::SocketAddr};
 
 use futures::{FutureExt, StreamExt, TryFutureExt};
+use prost::Message;
 use serde::{Deserialize, Serialize};
-use tokio::net::TcpStream;
 use tonic::{
-    transport::{server::Connected, Certificate, Server},
+    transport::{server::Connected, Server},
     Request, Response, Status,
 };
 use vector_core::{
-    event::{BatchNotifier, BatchStatus, BatchStatusReceiver, Event},
+    event::{self, BatchNotifier, BatchStatus, BatchStatusReceiver, Event, EventContainer},
     ByteSizeOf,
 };
 
@@ -19,10 +19,14 @@ use crate::{
     serde::bool_or_struct,
     shutdown::ShutdownSignalToken,
     sources::{util::AfterReadExt as _, Source},
-    tls::{MaybeTlsIncomingStream, MaybeTlsSettings, TlsConfig},
+    tls::{MaybeTlsSettings, TlsConfig},
     SourceSender,
 };
 
+/// The largest payload a compressed request may decompress to, the same as gRPC's default maximum
+/// message size, so that small requests can't expand into arbitrarily large allocations.
+const MAX_DECOMPRESSED_BYTES: usize = 4 * 1024 * 1024;
+
 #[derive(Debug, Clone)]
 pub struct Service {
     pipeline: SourceSender,
@@ -35,28 +39,40 @@ impl proto::Service for Service {
         &self,
         request: Request<proto::PushEventsRequest>,
     ) -> Result<Response<proto::PushEventsResponse>, Status> {
-        let mut events: Vec<Event> = request
-            .into_inner()
-            .events
-            .into_iter()
-            .map(Event::from)
-            .collect();
+        let events = request.into_inner().events.into_iter().map(Event::from);
+        self.handle_request(vec![events.collect()]).await
+    }
 
-        emit!(&EventsReceived {
-            count: events.len(),
-            byte_size: events.size_of(),
-        });
+    async fn push_compressed_events(
+        &self,
+        request: Request<proto::PushCompressedEventsRequest>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        let request = request.into_inner();
+        let payload = decompress(request.compression, request.payload)?;
 
-        let receiver = BatchNotifier::maybe_apply_to_events(self.acknowledgements, &mut events);
+        let request = proto::PushEventsRequest::decode(&payload[..])
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+
+        let events = request.events.into_iter().map(Event::from);
+        self.handle_request(vec![events.collect()]).await
+    }
 
-        self.pipeline
-            .clone()
-            .send_all(&mut futures::stream::iter(events))
-            .map_err(|err| Status::unavailable(err.to_string()))
-            .and_then(|_| handle_batch_status(receiver))
-            .await?;
+    async fn push_event_arrays(
+        &self,
+        request: Request<proto::PushEventArraysRequest>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        let request = request.into_inner();
+        let payload = decompress(request.compression, request.payload)?;
 
-        Ok(Response::new(proto::PushEventsResponse {}))
+        let request = proto::EventArrays::decode(&payload[..])
+            .map_err(|error| Status::invalid_argument(error.to_string()))?;
+
+        let arrays = request
+            .arrays
+            .into_iter()
+            .map(|array| event::EventArray::from(array).into_events().collect())
+            .collect();
+        self.handle_request(arrays).await
     }
 
     // TODO: figure out a way to determine if the current Vector instance is "healthy".
@@ -72,6 +88,72 @@ impl proto::Service for Service {
     }
 }
 
+impl Service {
+    /// Forwards the arrays of events, keeping each array together, and waits for their delivery
+    /// if acknowledgements are enabled.
+    async fn handle_request(
+        &self,
+        arrays: Vec<Vec<Event>>,
+    ) -> Result<Response<proto::PushEventsResponse>, Status> {
+        let lengths: Vec<usize> = arrays.iter().map(Vec::len).collect();
+        let mut events: Vec<Event> = arrays.into_iter().flatten().collect();
+
+        emit!(&EventsReceived {
+            count: events.len(),
+            byte_size: events.size_of(),
+        });
+
+        let receiver = BatchNotifier::maybe_apply_to_events(self.acknowledgements, &mut events);
+
+        let mut pipeline = self.pipeline.clone();
+        let mut events = events.into_iter();
+        for length in lengths {
+            let batch = events.by_ref().take(length).collect();
+            pipeline
+                .send_batch(batch)
+                .await
+                .map_err(|err| Status::unavailable(err.to_string()))?;
+        }
+        handle_batch_status(receiver).await?;
+
+        Ok(Response::new(proto::PushEventsResponse {}))
+    }
+}
+
+fn decompress(compression: i32, payload: Vec<u8>) -> Result<Vec<u8>, Status> {
+    match proto::Compression::from_i32(compression) {
+        Some(proto::Compression::Zstd) => decompress_zstd(&payload, MAX_DECOMPRESSED_BYTES),
+        Some(proto::Compression::None) => Ok(payload),
+        // Lets the sink fall back to uncompressed requests.
+        None => Err(Status::unimplemented(format!(
+            "Unsupported compression: {}",
+            compression
+        ))),
+    }
+}
+
+fn decompress_zstd(payload: &[u8], max_bytes: usize) -> Result<Vec<u8>, Status> {
+    let invalid = |error: std::io::Error| {
+        Status::invalid_argument(format!("Invalid zstd payload: {}", error))
+    };
+
+    // Reading one byte past the limit tells payloads that fit exactly apart from larger ones.
+    let mut decompressed = Vec::new();
+    zstd::stream::read::Decoder::new(payload)
+        .map_err(invalid)?
+        .take(max_bytes as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(invalid)?;
+
+    if decompressed.len() > max_bytes {
+        return Err(Status::resource_exhausted(format!(
+            "Decompressed payload exceeds {} bytes",
+            max_bytes
+        )));
+    }
+    Ok(decompressed)
+}
+
 async fn handle_batch_status(receiver: Option<BatchStatusReceiver>) -> Result<(), Status> {
     let status = match receiver {
         Some(receiver) => receiver.await,
@@ -175,31 +257,6 @@ async fn run(
     Ok(())
 }
 
-#[derive(Clone)]
-pub struct MaybeTlsConnectInfo {
-    pub remote_addr: SocketAddr,
-    pub peer_certs: Option<Vec<Certificate>>,
-}
-
-impl Connected for MaybeTlsIncomingStream<TcpStream> {
-    type ConnectInfo = MaybeTlsConnectInfo;
-
-    fn connect_info(&self) -> Self::ConnectInfo {
-        MaybeTlsConnectInfo {
-            remote_addr: self.peer_addr(),
-            peer_certs: self
-                .ssl_stream()
-                .and_then(|s| s.ssl().peer_cert_chain())
-                .map(|s| {
-                    s.into_iter()
-                        .filter_map(|c| c.to_pem().ok())
-                        .map(Certificate::from_pem)
-                        .collect()
-                }),
-        }
-    }
-}
-
 #[cfg(feature = "sinks-vector")]
 #[cfg(test)]
 mod tests {
@@ -213,8 +270,42 @@ mod tests {
         SourceSender,
     };
 
+    #[test]
+    fn decompress_zstd_limits_size() {
+        let payload = zstd::stream::encode_all(&[0_u8; 1024][..], 0).unwrap();
+
+        assert_eq!(decompress_zstd(&payload, 1024).unwrap().len(), 1024);
+        assert_eq!(
+            decompress_zstd(&payload, 1023).unwrap_err().code(),
+            tonic::Code::ResourceExhausted
+        );
+        assert_eq!(
+            decompress_zstd(b"not zstd", 1024).unwrap_err().code(),
+            tonic::Code::InvalidArgument
+        );
+    }
+
     #[tokio::test]
     async fn receive_message() {
+        run_test("").await;
+    }
+
+    #[tokio::test]
+    async fn receive_compressed_message() {
+        run_test("compression = true").await;
+    }
+
+    #[tokio::test]
+    async fn receive_event_arrays() {
+        run_test("event_arrays = true").await;
+    }
+
+    #[tokio::test]
+    async fn receive_compressed_event_arrays() {
+        run_test("compression = true\nevent_arrays = true").await;
+    }
+
+    async fn run_test(sink_options: &str) {
         let addr = test_util::next_addr();
         let config = format!(r#"address = "{}""#, addr);
         let source: VectorConfig = toml::from_str(&config).unwrap();
@@ -228,7 +319,11 @@ mod tests {
         // Ideally, this would be a fully custom agent to send the data,
         // but the sink side already does such a test and this is good
         // to ensure interoperability.
-        let config = format!(r#"address = "{}""#, addr);
+        let config = format!(
+            r#"address = "{}"
+            {}"#,
+            addr, sink_options
+        );
         let sink: SinkConfig = toml::from_str(&config).unwrap();
         let cx = SinkContext::new_test();
         let (sink, _) = sink.build(cx).await.unwrap();
@@ -0,0 +1,536 @@
+//! A source receiving events from a remote WebSocket endpoint.
+//!
+//! The source keeps a connection open to the endpoint, pinging it to tell when the connection was
+//! lost, and reconnects with a backoff whenever it is. Some endpoints can resume a stream from the
+//! last message received before reconnecting, given a token taken from that message, which the
+//! source passes back as a query parameter of the URI.
+
+use std::{net::SocketAddr, time::Duration};
+
+use bytes::Bytes;
+use chrono::Utc;
+use futures::{SinkExt, StreamExt};
+use http::{header::HeaderName, HeaderValue, Uri};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use tokio::{net::TcpStream, time::Instant};
+use tokio_tungstenite::{
+    client_async,
+    tungstenite::{self, client::IntoClientRequest, Message},
+    WebSocketStream,
+};
+use tokio_util::codec::FramedRead;
+
+use crate::{
+    codecs::{
+        self,
+        decoding::{DecodingConfig, DeserializerConfig, FramingConfig},
+    },
+    config::{
+        log_schema, DataType, GenerateConfig, Output, SourceConfig, SourceContext,
+        SourceDescription,
+    },
+    dns,
+    event::Event,
+    internal_events::{
+        WebSocketConnectionEstablished, WebSocketConnectionFailed, WebSocketConnectionShutdown,
+        WebSocketEventsReceived,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    sinks::util::retries::ExponentialBackoff,
+    sources::util::StreamDecodingError,
+    tls::{MaybeTlsSettings, MaybeTlsStream, TlsOptions, TlsSettings},
+    SourceSender,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid URI {:?}: {}", uri, source))]
+    InvalidUri {
+        uri: String,
+        source: http::uri::InvalidUri,
+    },
+    #[snafu(display("URI {:?} must use the `ws` or `wss` scheme", uri))]
+    UnsupportedScheme { uri: String },
+    #[snafu(display("URI {:?} has no host", uri))]
+    MissingHost { uri: String },
+    #[snafu(display("Invalid header {:?}", name))]
+    InvalidHeader { name: String },
+}
+
+#[derive(Debug, Snafu)]
+pub enum WebSocketError {
+    #[snafu(display("Failed to resolve host: {}", source))]
+    Dns { source: dns::DnsError },
+    #[snafu(display("No addresses returned for host"))]
+    NoAddresses,
+    #[snafu(display("Failed to connect: {}", source))]
+    Connect { source: crate::tls::TlsError },
+    #[snafu(display("Failed to build request: {}", source))]
+    BuildRequest { source: tungstenite::Error },
+    #[snafu(display("Handshake failed: {}", source))]
+    Handshake { source: tungstenite::Error },
+    #[snafu(display("Connection failed: {}", source))]
+    Receive { source: tungstenite::Error },
+    #[snafu(display("Failed to send message: {}", source))]
+    Send { source: tungstenite::Error },
+    #[snafu(display("No pong received within {:?} of pinging", timeout))]
+    PongTimeout { timeout: Duration },
+}
+
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketSourceConfig {
+    /// The `ws://` or `wss://` URI to connect to.
+    uri: String,
+    /// Headers sent with the handshake, such as those authenticating the connection.
+    #[serde(default)]
+    headers: IndexMap<String, String>,
+    tls: Option<TlsOptions>,
+    /// A message sent after every connection, for endpoints that expect the stream to be
+    /// subscribed to.
+    subscribe_message: Option<String>,
+    #[serde(default = "default_ping_interval_secs")]
+    #[derivative(Default(value = "default_ping_interval_secs()"))]
+    ping_interval_secs: u64,
+    #[serde(default = "default_ping_timeout_secs")]
+    #[derivative(Default(value = "default_ping_timeout_secs()"))]
+    ping_timeout_secs: u64,
+    resume_token: Option<ResumeTokenConfig>,
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: Box<dyn FramingConfig>,
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: Box<dyn DeserializerConfig>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResumeTokenConfig {
+    /// The field of the received events holding the token to resume from.
+    field: String,
+    /// The query parameter the token is passed in when reconnecting.
+    query_parameter: String,
+}
+
+const fn default_ping_interval_secs() -> u64 {
+    30
+}
+
+const fn default_ping_timeout_secs() -> u64 {
+    10
+}
+
+inventory::submit! {
+    SourceDescription::new::<WebSocketSourceConfig>("websocket")
+}
+
+impl GenerateConfig for WebSocketSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"uri = "wss://example.com/stream""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket")]
+impl SourceConfig for WebSocketSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let connector = Connector::new(self)?;
+        let decoder = DecodingConfig::new(self.framing.clone(), self.decoding.clone()).build()?;
+
+        Ok(Box::pin(websocket_source(
+            connector,
+            self.resume_token.clone(),
+            decoder,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "websocket"
+    }
+}
+
+/// Connects to the endpoint, and keeps the connection alive.
+struct Connector {
+    uri: Uri,
+    host: String,
+    port: u16,
+    tls: MaybeTlsSettings,
+    headers: Vec<(HeaderName, HeaderValue)>,
+    subscribe_message: Option<String>,
+    ping_interval: Duration,
+    ping_timeout: Duration,
+}
+
+impl Connector {
+    fn new(config: &WebSocketSourceConfig) -> crate::Result<Self> {
+        let uri = config.uri.parse::<Uri>().context(InvalidUriSnafu {
+            uri: config.uri.clone(),
+        })?;
+        let (tls, default_port) = match uri.scheme_str() {
+            Some("ws") => (MaybeTlsSettings::Raw(()), 80),
+            Some("wss") => (
+                MaybeTlsSettings::Tls(TlsSettings::from_options(&config.tls)?),
+                443,
+            ),
+            _ => {
+                return Err(Box::new(BuildError::UnsupportedScheme {
+                    uri: config.uri.clone(),
+                }))
+            }
+        };
+        let host = uri
+            .host()
+            .ok_or_else(|| BuildError::MissingHost {
+                uri: config.uri.clone(),
+            })?
+            .to_owned();
+        let port = uri.port_u16().unwrap_or(default_port);
+
+        let headers = config
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                match (
+                    HeaderName::from_bytes(name.as_bytes()),
+                    HeaderValue::from_str(value),
+                ) {
+                    (Ok(name), Ok(value)) => Ok((name, value)),
+                    _ => Err(BuildError::InvalidHeader { name: name.clone() }),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            uri,
+            host,
+            port,
+            tls,
+            headers,
+            subscribe_message: config.subscribe_message.clone(),
+            ping_interval: Duration::from_secs(config.ping_interval_secs.max(1)),
+            ping_timeout: Duration::from_secs(config.ping_timeout_secs.max(1)),
+        })
+    }
+
+    async fn connect(
+        &self,
+        resume: Option<(&str, &str)>,
+    ) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, WebSocketError> {
+        let ip = dns::Resolver
+            .lookup_ip(self.host.clone())
+            .await
+            .context(DnsSnafu)?
+            .next()
+            .ok_or(WebSocketError::NoAddresses)?;
+        let stream = self
+            .tls
+            .connect(&self.host, &SocketAddr::new(ip, self.port))
+            .await
+            .context(ConnectSnafu)?;
+
+        let uri = match resume {
+            Some((parameter, token)) => with_query_parameter(&self.uri, parameter, token),
+            None => self.uri.to_string(),
+        };
+        let mut request = uri.into_client_request().context(BuildRequestSnafu)?;
+        for (name, value) in &self.headers {
+            request.headers_mut().insert(name.clone(), value.clone());
+        }
+
+        let (mut socket, _) = client_async(request, stream)
+            .await
+            .context(HandshakeSnafu)?;
+        if let Some(message) = &self.subscribe_message {
+            socket
+                .send(Message::Text(message.clone()))
+                .await
+                .context(SendSnafu)?;
+        }
+        Ok(socket)
+    }
+
+    const fn fresh_backoff() -> ExponentialBackoff {
+        ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(60))
+    }
+}
+
+/// Returns `uri` with the query parameter `name` set to `value`.
+fn with_query_parameter(uri: &Uri, name: &str, value: &str) -> String {
+    let mut url = url::Url::parse(&uri.to_string()).expect("URI is a valid URL");
+    let pairs = url
+        .query_pairs()
+        .filter(|(key, _)| key != name)
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect::<Vec<_>>();
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair(name, value);
+    url.into()
+}
+
+enum Closed {
+    /// The source is shutting down.
+    Shutdown,
+    /// The endpoint closed the connection.
+    Disconnected,
+}
+
+async fn websocket_source(
+    connector: Connector,
+    resume_token: Option<ResumeTokenConfig>,
+    decoder: codecs::Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let mut backoff = Connector::fresh_backoff();
+    let mut token = None;
+
+    loop {
+        let resume = resume_token
+            .as_ref()
+            .zip(token.as_deref())
+            .map(|(config, token)| (config.query_parameter.as_str(), token));
+        let connected = tokio::select! {
+            connected = connector.connect(resume) => connected,
+            _ = &mut shutdown => return Ok(()),
+        };
+
+        let error = match connected {
+            Ok(socket) => {
+                emit!(&WebSocketConnectionEstablished);
+                backoff = Connector::fresh_backoff();
+                let received = receive(
+                    &connector,
+                    socket,
+                    &decoder,
+                    resume_token.as_ref(),
+                    &mut token,
+                    &mut shutdown,
+                    &mut out,
+                )
+                .await;
+                match received {
+                    Ok(Closed::Shutdown) => return Ok(()),
+                    Ok(Closed::Disconnected) => {
+                        emit!(&WebSocketConnectionShutdown);
+                        None
+                    }
+                    Err(error) => Some(error),
+                }
+            }
+            Err(error) => Some(error),
+        };
+        if let Some(error) = error {
+            emit!(&WebSocketConnectionFailed { error });
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(backoff.next().unwrap()) => {}
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+async fn receive(
+    connector: &Connector,
+    mut socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    decoder: &codecs::Decoder,
+    resume_token: Option<&ResumeTokenConfig>,
+    token: &mut Option<String>,
+    shutdown: &mut ShutdownSignal,
+    out: &mut SourceSender,
+) -> Result<Closed, WebSocketError> {
+    let mut ping = tokio::time::interval(connector.ping_interval);
+    // The first tick completes right away, and the connection was just checked by connecting.
+    ping.tick().await;
+    let pong_timeout = tokio::time::sleep(connector.ping_timeout);
+    tokio::pin!(pong_timeout);
+    let mut awaiting_pong = false;
+
+    loop {
+        tokio::select! {
+            _ = ping.tick() => {
+                if !awaiting_pong {
+                    socket.send(Message::Ping(Vec::new())).await.context(SendSnafu)?;
+                    pong_timeout.as_mut().reset(Instant::now() + connector.ping_timeout);
+                    awaiting_pong = true;
+                }
+            }
+            _ = &mut pong_timeout, if awaiting_pong => {
+                return Err(WebSocketError::PongTimeout { timeout: connector.ping_timeout });
+            }
+            message = socket.next() => {
+                // Any message shows that the connection is alive.
+                awaiting_pong = false;
+                let payload = match message {
+                    Some(Ok(Message::Text(text))) => Bytes::from(text),
+                    Some(Ok(Message::Binary(binary))) => Bytes::from(binary),
+                    Some(Ok(Message::Close(_))) | None => return Ok(Closed::Disconnected),
+                    // Pings are answered by the socket itself.
+                    Some(Ok(_)) => continue,
+                    Some(Err(error)) => return Err(WebSocketError::Receive { source: error }),
+                };
+                if handle_payload(payload, decoder, resume_token, token, out).await.is_err() {
+                    // The source is shutting down.
+                    return Ok(Closed::Shutdown);
+                }
+            }
+            _ = &mut *shutdown => {
+                let _ = socket.close(None).await;
+                return Ok(Closed::Shutdown);
+            }
+        }
+    }
+}
+
+async fn handle_payload(
+    payload: Bytes,
+    decoder: &codecs::Decoder,
+    resume_token: Option<&ResumeTokenConfig>,
+    token: &mut Option<String>,
+    out: &mut SourceSender,
+) -> Result<(), ()> {
+    let mut stream = FramedRead::new(payload.as_ref(), decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, byte_size)) => {
+                emit!(&WebSocketEventsReceived {
+                    byte_size,
+                    count: events.len()
+                });
+
+                let now = Utc::now();
+
+                for mut event in events {
+                    if let Event::Log(ref mut log) = event {
+                        if let Some(resume_token) = resume_token {
+                            if let Some(value) = log.get(resume_token.field.as_str()) {
+                                *token = Some(value.to_string_lossy());
+                            }
+                        }
+                        log.try_insert(log_schema().source_type_key(), Bytes::from("websocket"));
+                        log.try_insert(log_schema().timestamp_key(), now);
+                    }
+
+                    out.send(event)
+                        .await
+                        .map_err(|error: crate::source_sender::ClosedError| {
+                            error!(message = "Error sending to sink.", %error);
+                        })?;
+                }
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs::Decoder`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketSourceConfig>();
+    }
+
+    fn config(config: &str) -> WebSocketSourceConfig {
+        toml::from_str(config).unwrap()
+    }
+
+    #[test]
+    fn parses_uris() {
+        let connector = Connector::new(&config(r#"uri = "wss://example.com/stream""#)).unwrap();
+        assert_eq!(connector.host, "example.com");
+        assert_eq!(connector.port, 443);
+
+        let connector = Connector::new(&config(r#"uri = "ws://localhost:9000/""#)).unwrap();
+        assert_eq!(connector.port, 9000);
+
+        assert!(Connector::new(&config(r#"uri = "http://example.com/""#)).is_err());
+        assert!(Connector::new(&config(r#"uri = "/stream""#)).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_headers() {
+        let config = config(
+            r#"
+                uri = "wss://example.com/stream"
+                headers."bad header" = "value"
+            "#,
+        );
+        assert!(Connector::new(&config).is_err());
+    }
+
+    #[test]
+    fn sets_resume_token() {
+        let uri = "wss://example.com/stream?channel=trades&since=1"
+            .parse::<Uri>()
+            .unwrap();
+        assert_eq!(
+            with_query_parameter(&uri, "since", "a b"),
+            "wss://example.com/stream?channel=trades&since=a+b"
+        );
+
+        let uri = "wss://example.com/stream".parse::<Uri>().unwrap();
+        assert_eq!(
+            with_query_parameter(&uri, "since", "2"),
+            "wss://example.com/stream?since=2"
+        );
+    }
+
+    #[tokio::test]
+    async fn remembers_resume_token() {
+        let decoder = DecodingConfig::new(
+            default_framing_message_based(),
+            Box::new(codecs::JsonDeserializerConfig::new()),
+        )
+        .build()
+        .unwrap();
+        let resume_token = ResumeTokenConfig {
+            field: "sequence".to_owned(),
+            query_parameter: "since".to_owned(),
+        };
+        let (mut out, rx) = SourceSender::new_test();
+        let mut token = None;
+
+        handle_payload(
+            Bytes::from(r#"{"sequence":41,"price":"1.5"}"#),
+            &decoder,
+            Some(&resume_token),
+            &mut token,
+            &mut out,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(token.as_deref(), Some("41"));
+        let events = crate::test_util::collect_ready(rx).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["price"], "1.5".into());
+        assert_eq!(
+            events[0].as_log()[log_schema().source_type_key()],
+            "websocket".into()
+        );
+    }
+}
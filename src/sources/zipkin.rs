@@ -0,0 +1,506 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr},
+};
+
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use warp::{
+    http::{header::CONTENT_TYPE, HeaderMap, StatusCode},
+    reply::Response,
+    Reply,
+};
+
+use crate::{
+    config::{
+        log_schema, AcknowledgementsConfig, DataType, GenerateConfig, Output, Resource,
+        SourceConfig, SourceContext, SourceDescription,
+    },
+    event::{Event, LogEvent, Value},
+    http::Http2Config,
+    serde::bool_or_struct,
+    sources::util::{ErrorMessage, HttpMethod, HttpSource},
+    tls::TlsConfig,
+};
+
+mod proto {
+    include!(concat!(env!("OUT_DIR"), "/zipkin.proto3.rs"));
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ZipkinConfig {
+    address: SocketAddr,
+    #[serde(default = "default_path")]
+    path: String,
+    tls: Option<TlsConfig>,
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+inventory::submit! {
+    SourceDescription::new::<ZipkinConfig>("zipkin")
+}
+
+impl GenerateConfig for ZipkinConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:9411".parse().unwrap(),
+            path: default_path(),
+            tls: None,
+            acknowledgements: AcknowledgementsConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+fn default_path() -> String {
+    "/api/v2/spans".to_owned()
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "zipkin")]
+impl SourceConfig for ZipkinConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        ZipkinSource.run(
+            self.address,
+            &self.path,
+            HttpMethod::Post,
+            true,
+            &self.tls,
+            &None,
+            &Http2Config::default(),
+            cx,
+            self.acknowledgements,
+        )
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn source_type(&self) -> &'static str {
+        "zipkin"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::tcp(self.address)]
+    }
+}
+
+#[derive(Clone)]
+struct ZipkinSource;
+
+impl HttpSource for ZipkinSource {
+    fn build_events(
+        &self,
+        body: Bytes,
+        header_map: HeaderMap,
+        _query_parameters: HashMap<String, String>,
+        _path: &str,
+    ) -> Result<Vec<Event>, ErrorMessage> {
+        let protobuf = header_map
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map_or(false, |value| value.starts_with("application/x-protobuf"));
+
+        let spans = if protobuf {
+            proto::ListOfSpans::decode(body)
+                .map(|spans| spans.spans.into_iter().map(Span::from).collect())
+                .map_err(|error| {
+                    ErrorMessage::new(
+                        StatusCode::BAD_REQUEST,
+                        format!("Invalid protobuf payload: {}", error),
+                    )
+                })?
+        } else {
+            serde_json::from_slice::<Vec<Span>>(&body).map_err(|error| {
+                ErrorMessage::new(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid JSON payload: {}", error),
+                )
+            })?
+        };
+
+        Ok(spans
+            .into_iter()
+            .map(|span| Event::from(span.into_log()))
+            .collect())
+    }
+
+    fn build_response(&self, _events_count: usize) -> Response {
+        // Matches the `202 Accepted` the Zipkin collector responds with.
+        StatusCode::ACCEPTED.into_response()
+    }
+}
+
+/// A span in the Zipkin v2 JSON format, which protobuf spans are converted to.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Span {
+    trace_id: String,
+    #[serde(default)]
+    parent_id: Option<String>,
+    id: String,
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    /// Epoch microseconds.
+    #[serde(default)]
+    timestamp: Option<u64>,
+    /// Microseconds.
+    #[serde(default)]
+    duration: Option<u64>,
+    #[serde(default)]
+    local_endpoint: Option<Endpoint>,
+    #[serde(default)]
+    remote_endpoint: Option<Endpoint>,
+    #[serde(default)]
+    annotations: Vec<Annotation>,
+    #[serde(default)]
+    tags: BTreeMap<String, String>,
+    #[serde(default)]
+    debug: bool,
+    #[serde(default)]
+    shared: bool,
+}
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct Endpoint {
+    #[serde(default)]
+    service_name: Option<String>,
+    #[serde(default)]
+    ipv4: Option<String>,
+    #[serde(default)]
+    ipv6: Option<String>,
+    #[serde(default)]
+    port: Option<u16>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Annotation {
+    timestamp: u64,
+    value: String,
+}
+
+impl Span {
+    /// Spans are kept as log events, with the same layout for the IDs and timings as the
+    /// `internal_traces` source, so they can be remapped for sinks with tracing APIs.
+    fn into_log(self) -> LogEvent {
+        let mut log = LogEvent::default();
+        if let Some(name) = self.name {
+            log.insert(log_schema().message_key(), name);
+        }
+        log.insert(
+            log_schema().timestamp_key(),
+            self.timestamp.map_or_else(Utc::now, micros_to_timestamp),
+        );
+        log.insert(log_schema().source_type_key(), Bytes::from("zipkin"));
+        log.insert("trace_id", self.trace_id);
+        log.insert("span_id", self.id);
+        if let Some(parent_id) = self.parent_id {
+            log.insert("parent_span_id", parent_id);
+        }
+        if let Some(kind) = self.kind {
+            log.insert("kind", kind.to_lowercase());
+        }
+        if let Some(duration) = self.duration {
+            log.insert("duration_ns", (duration * 1_000) as i64);
+        }
+        if let Some(service) = self
+            .local_endpoint
+            .as_ref()
+            .and_then(|endpoint| endpoint.service_name.clone())
+        {
+            log.insert("service", service);
+        }
+        if let Some(endpoint) = self.local_endpoint {
+            log.insert("local_endpoint", endpoint.into_value());
+        }
+        if let Some(endpoint) = self.remote_endpoint {
+            log.insert("remote_endpoint", endpoint.into_value());
+        }
+        if !self.annotations.is_empty() {
+            let annotations = self
+                .annotations
+                .into_iter()
+                .map(|annotation| {
+                    let mut map = BTreeMap::new();
+                    map.insert(
+                        "timestamp".to_owned(),
+                        micros_to_timestamp(annotation.timestamp).into(),
+                    );
+                    map.insert("value".to_owned(), annotation.value.into());
+                    Value::Map(map)
+                })
+                .collect::<Vec<_>>();
+            log.insert("annotations", annotations);
+        }
+        if !self.tags.is_empty() {
+            let tags = self
+                .tags
+                .into_iter()
+                .map(|(key, value)| (key, Value::from(value)))
+                .collect::<BTreeMap<_, _>>();
+            log.insert("tags", tags);
+        }
+        if self.debug {
+            log.insert("debug", true);
+        }
+        if self.shared {
+            log.insert("shared", true);
+        }
+        log
+    }
+}
+
+impl Endpoint {
+    fn into_value(self) -> Value {
+        let mut map = BTreeMap::new();
+        if let Some(service_name) = self.service_name {
+            map.insert("service_name".to_owned(), service_name.into());
+        }
+        if let Some(ipv4) = self.ipv4 {
+            map.insert("ipv4".to_owned(), ipv4.into());
+        }
+        if let Some(ipv6) = self.ipv6 {
+            map.insert("ipv6".to_owned(), ipv6.into());
+        }
+        if let Some(port) = self.port {
+            map.insert("port".to_owned(), (port as i64).into());
+        }
+        Value::Map(map)
+    }
+}
+
+fn micros_to_timestamp(micros: u64) -> DateTime<Utc> {
+    Utc.timestamp_nanos((micros as i64).saturating_mul(1_000))
+}
+
+/// Zero values are the protobuf encoding of a missing field.
+fn non_empty<T: Default + PartialEq>(value: T) -> Option<T> {
+    (value != T::default()).then(|| value)
+}
+
+impl From<proto::Span> for Span {
+    fn from(span: proto::Span) -> Self {
+        let kind = match proto::span::Kind::from_i32(span.kind) {
+            Some(proto::span::Kind::Client) => Some("CLIENT"),
+            Some(proto::span::Kind::Server) => Some("SERVER"),
+            Some(proto::span::Kind::Producer) => Some("PRODUCER"),
+            Some(proto::span::Kind::Consumer) => Some("CONSUMER"),
+            _ => None,
+        };
+
+        Self {
+            trace_id: hex::encode(span.trace_id),
+            parent_id: non_empty(span.parent_id).map(hex::encode),
+            id: hex::encode(span.id),
+            kind: kind.map(str::to_owned),
+            name: non_empty(span.name),
+            timestamp: non_empty(span.timestamp),
+            duration: non_empty(span.duration),
+            local_endpoint: span.local_endpoint.map(Endpoint::from),
+            remote_endpoint: span.remote_endpoint.map(Endpoint::from),
+            annotations: span
+                .annotations
+                .into_iter()
+                .map(|annotation| Annotation {
+                    timestamp: annotation.timestamp,
+                    value: annotation.value,
+                })
+                .collect(),
+            tags: span.tags,
+            debug: span.debug,
+            shared: span.shared,
+        }
+    }
+}
+
+impl From<proto::Endpoint> for Endpoint {
+    fn from(endpoint: proto::Endpoint) -> Self {
+        let ipv4 = <[u8; 4]>::try_from(endpoint.ipv4.as_slice())
+            .ok()
+            .map(|octets| Ipv4Addr::from(octets).to_string());
+        let ipv6 = <[u8; 16]>::try_from(endpoint.ipv6.as_slice())
+            .ok()
+            .map(|octets| Ipv6Addr::from(octets).to_string());
+
+        Self {
+            service_name: non_empty(endpoint.service_name),
+            ipv4,
+            ipv6,
+            port: non_empty(endpoint.port as u16),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::Stream;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::{
+        event::EventStatus,
+        test_util::{components, next_addr, spawn_collect_n, wait_for_tcp},
+        SourceSender,
+    };
+
+    const JSON: &str = r#"[{
+        "traceId": "5982fe77008310cc80f1da5e10147517",
+        "parentId": "90394f6bcffb5d13",
+        "id": "67fae42571535f60",
+        "kind": "SERVER",
+        "name": "get /api",
+        "timestamp": 1600000000123456,
+        "duration": 1500,
+        "localEndpoint": { "serviceName": "backend", "ipv4": "192.168.99.101", "port": 9000 },
+        "annotations": [{ "timestamp": 1600000000124000, "value": "ws" }],
+        "tags": { "component": "api" }
+    }]"#;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ZipkinConfig>();
+    }
+
+    async fn source() -> (impl Stream<Item = Event>, SocketAddr) {
+        components::init_test();
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let context = SourceContext::new_test(sender);
+        tokio::spawn(async move {
+            ZipkinConfig {
+                address,
+                path: default_path(),
+                tls: None,
+                acknowledgements: true.into(),
+            }
+            .build(context)
+            .await
+            .unwrap()
+            .await
+            .unwrap()
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    async fn send(address: SocketAddr, content_type: &str, body: Vec<u8>) -> u16 {
+        reqwest::Client::new()
+            .post(&format!("http://{}/api/v2/spans", address))
+            .header("Content-Type", content_type)
+            .body(body)
+            .send()
+            .await
+            .unwrap()
+            .status()
+            .as_u16()
+    }
+
+    fn assert_span(log: &LogEvent) {
+        assert_eq!(log["message"], "get /api".into());
+        assert_eq!(
+            log["timestamp"],
+            Utc.timestamp(1_600_000_000, 123_456_000).into()
+        );
+        assert_eq!(log["trace_id"], "5982fe77008310cc80f1da5e10147517".into());
+        assert_eq!(log["span_id"], "67fae42571535f60".into());
+        assert_eq!(log["parent_span_id"], "90394f6bcffb5d13".into());
+        assert_eq!(log["kind"], "server".into());
+        assert_eq!(log["duration_ns"], 1_500_000.into());
+        assert_eq!(log["service"], "backend".into());
+        assert_eq!(log["local_endpoint.ipv4"], "192.168.99.101".into());
+        assert_eq!(log["local_endpoint.port"], 9000.into());
+        assert_eq!(log["annotations[0].value"], "ws".into());
+        assert_eq!(log["tags.component"], "api".into());
+        assert_eq!(log[log_schema().source_type_key()], "zipkin".into());
+    }
+
+    #[tokio::test]
+    async fn receives_json_spans() {
+        let (rx, addr) = source().await;
+
+        let events = spawn_collect_n(
+            async move {
+                assert_eq!(400, send(addr, "application/json", b"{}".to_vec()).await);
+                assert_eq!(
+                    202,
+                    send(addr, "application/json", JSON.as_bytes().to_vec()).await
+                );
+            },
+            rx,
+            1,
+        )
+        .await;
+        components::SOURCE_TESTS.assert(&["http_path"]);
+
+        assert_span(events[0].as_log());
+    }
+
+    #[tokio::test]
+    async fn receives_protobuf_spans() {
+        let (rx, addr) = source().await;
+        let spans = proto::ListOfSpans {
+            spans: vec![proto::Span {
+                trace_id: hex::decode("5982fe77008310cc80f1da5e10147517").unwrap(),
+                parent_id: hex::decode("90394f6bcffb5d13").unwrap(),
+                id: hex::decode("67fae42571535f60").unwrap(),
+                kind: proto::span::Kind::Server as i32,
+                name: "get /api".to_owned(),
+                timestamp: 1_600_000_000_123_456,
+                duration: 1_500,
+                local_endpoint: Some(proto::Endpoint {
+                    service_name: "backend".to_owned(),
+                    ipv4: vec![192, 168, 99, 101],
+                    ipv6: Vec::new(),
+                    port: 9000,
+                }),
+                remote_endpoint: None,
+                annotations: vec![proto::Annotation {
+                    timestamp: 1_600_000_000_124_000,
+                    value: "ws".to_owned(),
+                }],
+                tags: vec![("component".to_owned(), "api".to_owned())]
+                    .into_iter()
+                    .collect(),
+                debug: false,
+                shared: false,
+            }],
+        };
+
+        let events = spawn_collect_n(
+            async move {
+                assert_eq!(
+                    202,
+                    send(addr, "application/x-protobuf", spans.encode_to_vec()).await
+                );
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        let log = events[0].as_log();
+        assert_span(log);
+        assert!(log.get("remote_endpoint").is_none());
+        assert!(log.get("debug").is_none());
+    }
+
+    #[test]
+    fn json_and_protobuf_spans_match() {
+        let json =
+            serde_json::from_str::<Vec<Span>>(r#"[{"traceId": "0a0b", "id": "0c"}]"#).unwrap();
+        let protobuf = Span::from(proto::Span {
+            trace_id: vec![10, 11],
+            id: vec![12],
+            ..Default::default()
+        });
+        assert_eq!(json[0], protobuf);
+    }
+}
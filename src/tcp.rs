@@ -1,6 +1,12 @@
+use std::{
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    str,
+};
+
 use serde::{Deserialize, Serialize};
 use socket2::SockRef;
-use tokio::net::TcpStream;
+use tokio::{io::AsyncReadExt, net::TcpStream};
 
 /// Configuration for keepalive probes in a TCP stream.
 ///
@@ -12,6 +18,120 @@ pub struct TcpKeepaliveConfig {
     pub time_secs: Option<u64>,
 }
 
+/// Configuration for reading a [PROXY protocol][proxy_protocol] header at the start of each
+/// connection, as sent by load balancers such as HAProxy and AWS NLB to pass on the address of the
+/// original client. Both version 1 and version 2 headers are accepted.
+///
+/// [proxy_protocol]: https://www.haproxy.org/download/2.5/doc/proxy-protocol.txt
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProxyProtocolConfig {
+    #[serde(default = "crate::serde::default_true")]
+    pub enabled: bool,
+    /// The field to store the IP address of the original client in, if any.
+    pub client_ip_key: Option<String>,
+}
+
+impl From<bool> for ProxyProtocolConfig {
+    fn from(enabled: bool) -> Self {
+        Self {
+            enabled,
+            client_ip_key: None,
+        }
+    }
+}
+
+const PROXY_V1_PREFIX: &[u8] = b"PROXY ";
+const PROXY_V1_MAX_LENGTH: usize = 107;
+const PROXY_V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+
+/// Reads a PROXY protocol header from the start of the stream, without reading past its end.
+/// Returns the address of the original client, or None if the proxy didn't pass one on, such as
+/// for its own health checks.
+pub async fn read_proxy_header(stream: &mut TcpStream) -> io::Result<Option<SocketAddr>> {
+    let mut start = [0; 12];
+    stream.read_exact(&mut start).await?;
+
+    if start == PROXY_V2_SIGNATURE {
+        let mut header = [0; 4];
+        stream.read_exact(&mut header).await?;
+        let mut addresses = vec![0; u16::from_be_bytes([header[2], header[3]]) as usize];
+        stream.read_exact(&mut addresses).await?;
+        parse_proxy_v2(header[0], header[1], &addresses)
+    } else if start.starts_with(PROXY_V1_PREFIX) {
+        let mut line = start.to_vec();
+        while !line.ends_with(b"\r\n") {
+            if line.len() == PROXY_V1_MAX_LENGTH {
+                return Err(invalid_proxy_header("line is too long"));
+            }
+            line.push(stream.read_u8().await?);
+        }
+        parse_proxy_v1(&line[PROXY_V1_PREFIX.len()..line.len() - 2])
+    } else {
+        Err(invalid_proxy_header("missing signature"))
+    }
+}
+
+fn parse_proxy_v1(line: &[u8]) -> io::Result<Option<SocketAddr>> {
+    let line = str::from_utf8(line).map_err(|_| invalid_proxy_header("line is not ASCII"))?;
+    let mut parts = line.split(' ');
+    match parts.next() {
+        Some("TCP4") | Some("TCP6") => {
+            let ip = parts
+                .next()
+                .and_then(|ip| ip.parse::<IpAddr>().ok())
+                .ok_or_else(|| invalid_proxy_header("invalid source address"))?;
+            let port = parts
+                .nth(1)
+                .and_then(|port| port.parse::<u16>().ok())
+                .ok_or_else(|| invalid_proxy_header("invalid source port"))?;
+            Ok(Some(SocketAddr::new(ip, port)))
+        }
+        Some("UNKNOWN") => Ok(None),
+        _ => Err(invalid_proxy_header("unknown protocol")),
+    }
+}
+
+fn parse_proxy_v2(
+    version_command: u8,
+    family: u8,
+    addresses: &[u8],
+) -> io::Result<Option<SocketAddr>> {
+    if version_command >> 4 != 2 {
+        return Err(invalid_proxy_header("unknown version"));
+    }
+    match version_command & 0x0f {
+        // LOCAL connections come from the proxy itself.
+        0 => return Ok(None),
+        1 => (),
+        _ => return Err(invalid_proxy_header("unknown command")),
+    }
+
+    let address = match family >> 4 {
+        1 if addresses.len() >= 12 => {
+            let ip = <[u8; 4]>::try_from(&addresses[0..4]).expect("slice is 4 bytes");
+            let port = u16::from_be_bytes([addresses[8], addresses[9]]);
+            SocketAddr::new(Ipv4Addr::from(ip).into(), port)
+        }
+        2 if addresses.len() >= 36 => {
+            let ip = <[u8; 16]>::try_from(&addresses[0..16]).expect("slice is 16 bytes");
+            let port = u16::from_be_bytes([addresses[32], addresses[33]]);
+            SocketAddr::new(Ipv6Addr::from(ip).into(), port)
+        }
+        1 | 2 => return Err(invalid_proxy_header("addresses are truncated")),
+        // Unix sockets and unspecified families have no address to pass on.
+        _ => return Ok(None),
+    };
+    Ok(Some(address))
+}
+
+fn invalid_proxy_header(reason: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid PROXY protocol header: {}", reason),
+    )
+}
+
 // This function will be obsolete after tokio/mio internally use `socket2` and expose the methods to
 // apply options to a socket.
 pub fn set_keepalive(socket: &TcpStream, params: &socket2::TcpKeepalive) -> std::io::Result<()> {
@@ -29,3 +149,45 @@ pub fn set_receive_buffer_size(socket: &TcpStream, size: usize) -> std::io::Resu
 pub fn set_send_buffer_size(socket: &TcpStream, size: usize) -> std::io::Result<()> {
     SockRef::from(socket).set_send_buffer_size(size)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_proxy_v1() {
+        assert_eq!(
+            parse_proxy_v1(b"TCP4 192.0.2.1 198.51.100.1 56324 443").unwrap(),
+            Some("192.0.2.1:56324".parse().unwrap())
+        );
+        assert_eq!(
+            parse_proxy_v1(b"TCP6 2001:db8::1 2001:db8::2 56324 443").unwrap(),
+            Some("[2001:db8::1]:56324".parse().unwrap())
+        );
+        assert_eq!(parse_proxy_v1(b"UNKNOWN").unwrap(), None);
+        assert!(parse_proxy_v1(b"TCP4 192.0.2.1").is_err());
+        assert!(parse_proxy_v1(b"UDP4 192.0.2.1 198.51.100.1 56324 443").is_err());
+    }
+
+    #[test]
+    fn parses_proxy_v2() {
+        let ipv4 = [192, 0, 2, 1, 198, 51, 100, 1, 0xdc, 0x04, 0x01, 0xbb];
+        assert_eq!(
+            parse_proxy_v2(0x21, 0x11, &ipv4).unwrap(),
+            Some("192.0.2.1:56324".parse().unwrap())
+        );
+
+        let mut ipv6 = vec![0; 36];
+        ipv6[0..2].copy_from_slice(&[0x20, 0x01]);
+        ipv6[15] = 1;
+        ipv6[32..34].copy_from_slice(&[0xdc, 0x04]);
+        assert_eq!(
+            parse_proxy_v2(0x21, 0x21, &ipv6).unwrap(),
+            Some("[2001::1]:56324".parse().unwrap())
+        );
+
+        assert_eq!(parse_proxy_v2(0x20, 0x00, &[]).unwrap(), None);
+        assert!(parse_proxy_v2(0x21, 0x11, &ipv4[..8]).is_err());
+        assert!(parse_proxy_v2(0x11, 0x11, &ipv4).is_err());
+    }
+}
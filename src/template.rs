@@ -22,17 +22,161 @@ lazy_static! {
     static ref RE: Regex = Regex::new(r"\{\{(?P<key>[^\}]+)\}\}").unwrap();
 }
 
+/// The prefix used to refer to an event's source metadata, rather than its fields.
+const SOURCE_METADATA_PREFIX: &str = "%source.";
+
 #[derive(Debug, Default, Eq, PartialEq, Hash, Clone)]
 pub struct Template {
     src: String,
     has_ts: bool,
     has_fields: bool,
+    expressions: Vec<Expression>,
 }
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Snafu)]
+#[derive(Clone, Debug, Eq, PartialEq, Snafu)]
 pub enum TemplateParseError {
     #[snafu(display("Invalid strftime item"))]
     StrftimeError,
+    #[snafu(display("Unknown template function {:?}", name))]
+    UnknownFunction { name: String },
+    #[snafu(display("Invalid default value in {:?}, expected a quoted string", expression))]
+    InvalidDefault { expression: String },
+}
+
+/// A `{{ ... }}` reference in a template.
+///
+/// Besides a plain field name, this accepts a small subset of VRL: the field may be written as a
+/// path (`.service`), may refer to the event's source metadata (`%source.topic`), may be wrapped
+/// in functions (`downcase(.service)`), and may be given a default for when it is missing
+/// (`.service ?? "unknown"`).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct Expression {
+    /// The expression as written, used to report it as missing.
+    src: String,
+    reference: Reference,
+    /// Applied in order, innermost first.
+    functions: Vec<TemplateFunction>,
+    default: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+enum Reference {
+    Field(String),
+    SourceMetadata(String),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+enum TemplateFunction {
+    Downcase,
+    Upcase,
+    StripWhitespace,
+}
+
+impl TemplateFunction {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "downcase" => Some(Self::Downcase),
+            "upcase" => Some(Self::Upcase),
+            "strip_whitespace" => Some(Self::StripWhitespace),
+            _ => None,
+        }
+    }
+
+    fn apply(self, value: String) -> String {
+        match self {
+            Self::Downcase => value.to_lowercase(),
+            Self::Upcase => value.to_uppercase(),
+            Self::StripWhitespace => value.trim().to_owned(),
+        }
+    }
+}
+
+impl Expression {
+    fn parse(src: &str) -> Result<Self, TemplateParseError> {
+        let (mut inner, default) = match src.split_once("??") {
+            Some((inner, default)) => {
+                let default = parse_string_literal(default.trim()).ok_or_else(|| {
+                    TemplateParseError::InvalidDefault {
+                        expression: src.to_owned(),
+                    }
+                })?;
+                (inner.trim(), Some(default))
+            }
+            None => (src, None),
+        };
+
+        let mut functions = Vec::new();
+        while let Some((name, argument)) = parse_call(inner) {
+            let function = TemplateFunction::from_name(name).ok_or_else(|| {
+                TemplateParseError::UnknownFunction {
+                    name: name.to_owned(),
+                }
+            })?;
+            functions.push(function);
+            inner = argument;
+        }
+        functions.reverse();
+
+        let reference = match inner.strip_prefix(SOURCE_METADATA_PREFIX) {
+            Some(name) => Reference::SourceMetadata(name.to_owned()),
+            None => Reference::Field(inner.strip_prefix('.').unwrap_or(inner).to_owned()),
+        };
+
+        Ok(Self {
+            src: src.to_owned(),
+            reference,
+            functions,
+            default,
+        })
+    }
+
+    fn render(&self, event: EventRef<'_>) -> Option<String> {
+        let value = match (&self.reference, event) {
+            (Reference::Field(key), EventRef::Log(log)) => {
+                log.get(key).map(|val| val.to_string_lossy())
+            }
+            (Reference::Field(key), EventRef::Metric(metric)) => render_metric_field(key, metric),
+            (Reference::SourceMetadata(name), EventRef::Log(log)) => log
+                .metadata()
+                .source_field(name)
+                .map(|val| val.to_string_lossy()),
+            (Reference::SourceMetadata(name), EventRef::Metric(metric)) => metric
+                .metadata()
+                .source_field(name)
+                .map(|val| val.to_string_lossy()),
+        };
+
+        value
+            .map(|value| {
+                self.functions
+                    .iter()
+                    .fold(value, |value, function| function.apply(value))
+            })
+            .or_else(|| self.default.clone())
+    }
+}
+
+/// Splits `name(argument)` into its parts, if `src` is a function call.
+fn parse_call(src: &str) -> Option<(&str, &str)> {
+    let (name, rest) = src.split_once('(')?;
+    let argument = rest.strip_suffix(')')?;
+    let name = name.trim();
+    (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+        .then(|| (name, argument.trim()))
+}
+
+fn parse_string_literal(src: &str) -> Option<String> {
+    let inner = src.strip_prefix('"')?.strip_suffix('"')?;
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.push(chars.next()?),
+            '"' => return None,
+            c => out.push(c),
+        }
+    }
+    Some(out)
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Snafu)]
@@ -74,14 +218,26 @@ impl TryFrom<Cow<'_, str>> for Template {
                 (error || is_error(&item), dynamic || is_dynamic(&item))
             });
         if has_error {
-            Err(TemplateParseError::StrftimeError)
-        } else {
-            Ok(Template {
-                has_fields: RE.is_match(&src),
-                src: src.into_owned(),
-                has_ts: is_dynamic,
-            })
+            return Err(TemplateParseError::StrftimeError);
         }
+
+        let expressions = RE
+            .captures_iter(&src)
+            .map(|caps| {
+                let key = caps
+                    .get(1)
+                    .map(|s| s.as_str().trim())
+                    .expect("src should match regex");
+                Expression::parse(key)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Template {
+            has_fields: !expressions.is_empty(),
+            src: src.into_owned(),
+            has_ts: is_dynamic,
+            expressions,
+        })
     }
 }
 
@@ -114,22 +270,23 @@ impl Template {
         let event = event.into();
         match (self.has_fields, self.has_ts) {
             (false, false) => Ok(self.src.clone()),
-            (true, false) => render_fields(&self.src, event),
+            (true, false) => self.render_fields(event),
             (false, true) => Ok(render_timestamp(&self.src, event)),
             (true, true) => {
-                let tmp = render_fields(&self.src, event)?;
+                let tmp = self.render_fields(event)?;
                 Ok(render_timestamp(&tmp, event))
             }
         }
     }
 
+    /// The event fields the template reads, not including source metadata.
     pub fn get_fields(&self) -> Option<Vec<String>> {
         if self.has_fields {
-            RE.captures_iter(&self.src)
-                .map(|c| {
-                    c.get(1)
-                        .map(|s| s.as_str().trim().to_string())
-                        .expect("src should match regex")
+            self.expressions
+                .iter()
+                .filter_map(|expression| match &expression.reference {
+                    Reference::Field(key) => Some(key.clone()),
+                    Reference::SourceMetadata(_) => None,
                 })
                 .collect::<Vec<_>>()
                 .into()
@@ -145,30 +302,27 @@ impl Template {
     pub fn get_ref(&self) -> &str {
         &self.src
     }
-}
 
-fn render_fields<'a>(src: &str, event: EventRef<'a>) -> Result<String, TemplateRenderingError> {
-    let mut missing_keys = Vec::new();
-    let out = RE
-        .replace_all(src, |caps: &Captures<'_>| {
-            let key = caps
-                .get(1)
-                .map(|s| s.as_str().trim())
-                .expect("src should match regex");
-            match event {
-                EventRef::Log(log) => log.get(&key).map(|val| val.to_string_lossy()),
-                EventRef::Metric(metric) => render_metric_field(key, metric),
-            }
-            .unwrap_or_else(|| {
-                missing_keys.push(key.to_owned());
-                String::new()
+    fn render_fields(&self, event: EventRef<'_>) -> Result<String, TemplateRenderingError> {
+        let mut missing_keys = Vec::new();
+        // Matches are replaced in order, which is the order the expressions were parsed in.
+        let mut expressions = self.expressions.iter();
+        let out = RE
+            .replace_all(&self.src, |_: &Captures<'_>| {
+                let expression = expressions
+                    .next()
+                    .expect("an expression is parsed for every match");
+                expression.render(event).unwrap_or_else(|| {
+                    missing_keys.push(expression.src.clone());
+                    String::new()
+                })
             })
-        })
-        .into_owned();
-    if missing_keys.is_empty() {
-        Ok(out)
-    } else {
-        Err(TemplateRenderingError::MissingKeys { missing_keys })
+            .into_owned();
+        if missing_keys.is_empty() {
+            Ok(out)
+        } else {
+            Err(TemplateRenderingError::MissingKeys { missing_keys })
+        }
     }
 }
 
@@ -478,6 +632,71 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_log_source_metadata() {
+        let mut event = Event::from("hello world");
+        event
+            .as_mut_log()
+            .metadata_mut()
+            .set_source_field("topic", "orders");
+        let template = Template::try_from("{{ %source.topic }}-{{ %source.partition }}").unwrap();
+
+        assert_eq!(
+            Err(TemplateRenderingError::MissingKeys {
+                missing_keys: vec!["%source.partition".to_string()]
+            }),
+            template.render(&event)
+        );
+
+        event
+            .as_mut_log()
+            .metadata_mut()
+            .set_source_field("partition", 2);
+        assert_eq!(Ok(Bytes::from("orders-2")), template.render(&event));
+        assert_eq!(template.get_fields(), Some(vec![]));
+    }
+
+    #[test]
+    fn render_log_functions_and_defaults() {
+        let mut event = Event::from("hello world");
+        event.as_mut_log().insert("service", " Billing ");
+        let template = Template::try_from(
+            r#"{{ downcase(strip_whitespace(.service)) }}-{{ upcase(.env) ?? "dev" }}"#,
+        )
+        .unwrap();
+
+        assert_eq!(Ok(Bytes::from("billing-dev")), template.render(&event));
+        assert_eq!(
+            template.get_fields(),
+            Some(vec!["service".to_string(), "env".to_string()])
+        );
+
+        event.as_mut_log().insert("env", "prod");
+        assert_eq!(Ok(Bytes::from("billing-PROD")), template.render(&event));
+    }
+
+    #[test]
+    fn render_metric_with_default() {
+        let template = Template::try_from(r#"{{ tags.component ?? "none" }}"#).unwrap();
+        assert_eq!(Ok(Bytes::from("none")), template.render(&sample_metric()));
+    }
+
+    #[test]
+    fn expression_errors() {
+        assert_eq!(
+            Template::try_from("{{ reverse(.foo) }}").unwrap_err(),
+            TemplateParseError::UnknownFunction {
+                name: "reverse".into()
+            }
+        );
+        assert_eq!(
+            Template::try_from("{{ .foo ?? bar }}").unwrap_err(),
+            TemplateParseError::InvalidDefault {
+                expression: ".foo ?? bar".into()
+            }
+        );
+    }
+
     fn sample_metric() -> Metric {
         Metric::new(
             "a-counter",
@@ -1,4 +1,3 @@
-use std::sync::Arc;
 use std::{
     future::Future,
     net::SocketAddr,
@@ -9,7 +8,6 @@ use std::{
 use futures::{future::BoxFuture, stream, FutureExt, Stream};
 use openssl::ssl::{Ssl, SslAcceptor, SslMethod};
 use snafu::ResultExt;
-use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio::{
     io::{self, AsyncRead, AsyncWrite, ReadBuf},
     net::{TcpListener, TcpStream},
@@ -18,7 +16,7 @@ use tokio_openssl::SslStream;
 
 use super::{
     CreateAcceptorSnafu, HandshakeSnafu, IncomingListenerSnafu, MaybeTlsSettings, MaybeTlsStream,
-    SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
+    ProxyProtocolSnafu, SslBuildSnafu, TcpBindSnafu, TlsError, TlsSettings,
 };
 #[cfg(feature = "sources-utils-tcp-socket")]
 use crate::tcp;
@@ -48,13 +46,18 @@ impl MaybeTlsSettings {
             Self::Raw(()) => None,
         };
 
-        Ok(MaybeTlsListener { listener, acceptor })
+        Ok(MaybeTlsListener {
+            listener,
+            acceptor,
+            proxy_protocol: false,
+        })
     }
 }
 
 pub struct MaybeTlsListener {
     listener: TcpListener,
     acceptor: Option<SslAcceptor>,
+    proxy_protocol: bool,
 }
 
 impl MaybeTlsListener {
@@ -63,11 +66,26 @@ impl MaybeTlsListener {
             .accept()
             .await
             .map(|(stream, peer_addr)| {
-                MaybeTlsIncomingStream::new(stream, peer_addr, self.acceptor.clone())
+                MaybeTlsIncomingStream::new(
+                    stream,
+                    peer_addr,
+                    self.acceptor.clone(),
+                    self.proxy_protocol,
+                )
             })
             .context(IncomingListenerSnafu)
     }
 
+    /// Expect each connection to start with a PROXY protocol header, which is read before the TLS
+    /// handshake as load balancers send it ahead of the client's own bytes.
+    // https://github.com/rust-lang/rust/issues/73255
+    #[allow(clippy::missing_const_for_fn)]
+    #[cfg(feature = "listenfd")]
+    pub(crate) fn with_proxy_protocol(mut self, proxy_protocol: bool) -> Self {
+        self.proxy_protocol = proxy_protocol;
+        self
+    }
+
     async fn into_accept(
         mut self,
     ) -> (crate::tls::Result<MaybeTlsIncomingStream<TcpStream>>, Self) {
@@ -88,44 +106,6 @@ impl MaybeTlsListener {
         })
     }
 
-    #[allow(unused)]
-    pub(crate) fn accept_stream_limited(
-        self,
-        max_connections: Option<u32>,
-    ) -> impl Stream<
-        Item = (
-            crate::tls::Result<MaybeTlsIncomingStream<TcpStream>>,
-            Option<OwnedSemaphorePermit>,
-        ),
-    > {
-        let connection_semaphore =
-            max_connections.map(|max| Arc::new(Semaphore::new(max as usize)));
-
-        let mut semaphore_future = connection_semaphore
-            .clone()
-            .map(|x| Box::pin(x.acquire_owned()));
-        let mut accept = Box::pin(self.into_accept());
-        stream::poll_fn(move |context| {
-            let permit = match semaphore_future.as_mut() {
-                Some(semaphore) => match semaphore.as_mut().poll(context) {
-                    Poll::Ready(permit) => {
-                        semaphore.set(connection_semaphore.clone().unwrap().acquire_owned());
-                        permit.ok()
-                    }
-                    Poll::Pending => return Poll::Pending,
-                },
-                None => None,
-            };
-            match accept.as_mut().poll(context) {
-                Poll::Ready((item, this)) => {
-                    accept.set(this.into_accept());
-                    Poll::Ready(Some((item, permit)))
-                }
-                Poll::Pending => Poll::Pending,
-            }
-        })
-    }
-
     #[cfg(feature = "listenfd")]
     pub(crate) fn local_addr(&self) -> Result<SocketAddr, std::io::Error> {
         self.listener.local_addr()
@@ -137,6 +117,43 @@ impl From<TcpListener> for MaybeTlsListener {
         Self {
             listener,
             acceptor: None,
+            proxy_protocol: false,
+        }
+    }
+}
+
+/// Subject of the certificate presented by a TLS client.
+#[cfg(feature = "listenfd")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CertificateMetadata {
+    pub country_name: Option<String>,
+    pub state_or_province_name: Option<String>,
+    pub locality_name: Option<String>,
+    pub organization_name: Option<String>,
+    pub organizational_unit_name: Option<String>,
+    pub common_name: Option<String>,
+}
+
+#[cfg(feature = "listenfd")]
+impl CertificateMetadata {
+    fn from_subject(subject: &openssl::x509::X509NameRef) -> Self {
+        use openssl::nid::Nid;
+
+        let entry = |nid| {
+            subject
+                .entries_by_nid(nid)
+                .next()
+                .and_then(|entry| entry.data().as_utf8().ok())
+                .map(|data| data.to_string())
+        };
+
+        Self {
+            country_name: entry(Nid::COUNTRYNAME),
+            state_or_province_name: entry(Nid::STATEORPROVINCENAME),
+            locality_name: entry(Nid::LOCALITYNAME),
+            organization_name: entry(Nid::ORGANIZATIONNAME),
+            organizational_unit_name: entry(Nid::ORGANIZATIONALUNITNAME),
+            common_name: entry(Nid::COMMONNAME),
         }
     }
 }
@@ -147,11 +164,19 @@ pub struct MaybeTlsIncomingStream<S> {
     // of MaybeTlsIncomingStream want access to the peer address while
     // still handshaking, so we have to cache it here.
     peer_addr: SocketAddr,
+    // The address of the original client, as passed on in a PROXY protocol header.
+    #[cfg_attr(not(feature = "listenfd"), allow(dead_code))]
+    proxied_peer_addr: Option<SocketAddr>,
+}
+
+struct AcceptedStream<S> {
+    stream: MaybeTlsStream<S>,
+    proxied_peer_addr: Option<SocketAddr>,
 }
 
 enum StreamState<S> {
     Accepted(MaybeTlsStream<S>),
-    Accepting(BoxFuture<'static, Result<SslStream<S>, TlsError>>),
+    Accepting(BoxFuture<'static, Result<AcceptedStream<S>, TlsError>>),
     AcceptError(String),
     Closed,
 }
@@ -162,6 +187,18 @@ impl<S> MaybeTlsIncomingStream<S> {
         self.peer_addr
     }
 
+    /// The address of the original client, if the listener expects a PROXY protocol header and the
+    /// proxy passed one on. None if the connection still hasn't been established.
+    #[cfg(feature = "listenfd")]
+    pub(crate) const fn proxied_peer_addr(&self) -> Option<SocketAddr> {
+        self.proxied_peer_addr
+    }
+
+    fn accepted(&mut self, accepted: AcceptedStream<S>) {
+        self.state = StreamState::Accepted(accepted.stream);
+        self.proxied_peer_addr = accepted.proxied_peer_addr;
+    }
+
     /// None if connection still hasn't been established.
     #[cfg(any(
         feature = "listenfd",
@@ -182,6 +219,21 @@ impl<S> MaybeTlsIncomingStream<S> {
         }
     }
 
+    /// The subject of the certificate the client presented, if the connection is using TLS and
+    /// the client sent one. None if the connection still hasn't been established.
+    #[cfg(feature = "listenfd")]
+    pub(crate) fn peer_certificate_metadata(&self) -> Option<CertificateMetadata> {
+        use super::MaybeTls;
+
+        match &self.state {
+            StreamState::Accepted(MaybeTls::Tls(stream)) => stream
+                .ssl()
+                .peer_certificate()
+                .map(|certificate| CertificateMetadata::from_subject(certificate.subject_name())),
+            _ => None,
+        }
+    }
+
     #[cfg(feature = "sources-vector")]
     pub(crate) const fn ssl_stream(&self) -> Option<&SslStream<S>> {
         use super::MaybeTls;
@@ -218,34 +270,55 @@ impl<S> MaybeTlsIncomingStream<S> {
 
 impl MaybeTlsIncomingStream<TcpStream> {
     pub(super) fn new(
-        stream: TcpStream,
+        mut stream: TcpStream,
         peer_addr: SocketAddr,
         acceptor: Option<SslAcceptor>,
+        proxy_protocol: bool,
     ) -> Self {
-        let state = match acceptor {
-            Some(acceptor) => StreamState::Accepting(
+        let state = match (acceptor, proxy_protocol) {
+            (None, false) => StreamState::Accepted(MaybeTlsStream::Raw(stream)),
+            (acceptor, proxy_protocol) => StreamState::Accepting(
                 async move {
-                    let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
-                    let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
-                    Pin::new(&mut stream)
-                        .accept()
-                        .await
-                        .context(HandshakeSnafu)?;
-                    Ok(stream)
+                    let proxied_peer_addr = if proxy_protocol {
+                        crate::tcp::read_proxy_header(&mut stream)
+                            .await
+                            .context(ProxyProtocolSnafu)?
+                    } else {
+                        None
+                    };
+                    let stream = match acceptor {
+                        Some(acceptor) => {
+                            let ssl = Ssl::new(acceptor.context()).context(SslBuildSnafu)?;
+                            let mut stream = SslStream::new(ssl, stream).context(SslBuildSnafu)?;
+                            Pin::new(&mut stream)
+                                .accept()
+                                .await
+                                .context(HandshakeSnafu)?;
+                            MaybeTlsStream::Tls(stream)
+                        }
+                        None => MaybeTlsStream::Raw(stream),
+                    };
+                    Ok(AcceptedStream {
+                        stream,
+                        proxied_peer_addr,
+                    })
                 }
                 .boxed(),
             ),
-            None => StreamState::Accepted(MaybeTlsStream::Raw(stream)),
         };
-        Self { state, peer_addr }
+        Self {
+            state,
+            peer_addr,
+            proxied_peer_addr: None,
+        }
     }
 
     // Explicit handshake method
     #[cfg(feature = "listenfd")]
     pub(crate) async fn handshake(&mut self) -> crate::tls::Result<()> {
         if let StreamState::Accepting(fut) = &mut self.state {
-            let stream = fut.await?;
-            self.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+            let accepted = fut.await?;
+            self.accepted(accepted);
         }
 
         Ok(())
@@ -291,8 +364,8 @@ impl MaybeTlsIncomingStream<TcpStream> {
             return match &mut this.state {
                 StreamState::Accepted(stream) => poll_fn(Pin::new(stream), cx),
                 StreamState::Accepting(fut) => match futures::ready!(fut.as_mut().poll(cx)) {
-                    Ok(stream) => {
-                        this.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+                    Ok(accepted) => {
+                        this.accepted(accepted);
                         continue;
                     }
                     Err(error) => {
@@ -340,8 +413,8 @@ impl AsyncWrite for MaybeTlsIncomingStream<TcpStream> {
                 poll_result => poll_result,
             },
             StreamState::Accepting(fut) => match futures::ready!(fut.as_mut().poll(cx)) {
-                Ok(stream) => {
-                    this.state = StreamState::Accepted(MaybeTlsStream::Tls(stream));
+                Ok(accepted) => {
+                    this.accepted(accepted);
                     Poll::Pending
                 }
                 Err(error) => {
@@ -357,3 +430,38 @@ impl AsyncWrite for MaybeTlsIncomingStream<TcpStream> {
         }
     }
 }
+
+#[cfg(feature = "tonic")]
+mod tonic {
+    use std::net::SocketAddr;
+
+    use tokio::net::TcpStream;
+    use tonic::transport::{server::Connected, Certificate};
+
+    use super::MaybeTlsIncomingStream;
+
+    #[derive(Clone)]
+    pub struct MaybeTlsConnectInfo {
+        pub remote_addr: SocketAddr,
+        pub peer_certs: Option<Vec<Certificate>>,
+    }
+
+    impl Connected for MaybeTlsIncomingStream<TcpStream> {
+        type ConnectInfo = MaybeTlsConnectInfo;
+
+        fn connect_info(&self) -> Self::ConnectInfo {
+            MaybeTlsConnectInfo {
+                remote_addr: self.peer_addr(),
+                peer_certs: self
+                    .ssl_stream()
+                    .and_then(|s| s.ssl().peer_cert_chain())
+                    .map(|s| {
+                        s.into_iter()
+                            .filter_map(|c| c.to_pem().ok())
+                            .map(Certificate::from_pem)
+                            .collect()
+                    }),
+            }
+        }
+    }
+}
@@ -17,7 +17,7 @@ mod outgoing;
 mod settings;
 
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
-pub(crate) use incoming::{MaybeTlsIncomingStream, MaybeTlsListener};
+pub(crate) use incoming::{CertificateMetadata, MaybeTlsIncomingStream, MaybeTlsListener};
 pub(crate) use maybe_tls::MaybeTls;
 pub use settings::{MaybeTlsSettings, TlsConfig, TlsOptions, TlsSettings};
 #[cfg(test)]
@@ -81,6 +81,8 @@ pub enum TlsError {
     Handshake { source: openssl::ssl::Error },
     #[snafu(display("Incoming listener failed: {}", source))]
     IncomingListener { source: tokio::io::Error },
+    #[snafu(display("Could not read PROXY protocol header: {}", source))]
+    ProxyProtocol { source: tokio::io::Error },
     #[snafu(display("Creating the TLS acceptor failed: {}", source))]
     CreateAcceptor { source: ErrorStack },
     #[snafu(display("Error building SSL context: {}", source))]
@@ -97,6 +99,8 @@ pub enum TlsError {
     AddCertToStore { source: ErrorStack },
     #[snafu(display("Error setting up the verification certificate: {}", source))]
     SetVerifyCert { source: ErrorStack },
+    #[snafu(display("Error setting up the application protocols: {}", source))]
+    SetAlpnProtocols { source: ErrorStack },
     #[snafu(display("PKCS#12 parse failed: {}", source))]
     ParsePkcs12 { source: ErrorStack },
     #[snafu(display("TCP bind failed: {}", source))]
@@ -8,7 +8,7 @@ use std::{
 use openssl::{
     pkcs12::{ParsedPkcs12, Pkcs12},
     pkey::{PKey, Private},
-    ssl::{ConnectConfiguration, SslContextBuilder, SslVerifyMode},
+    ssl::{self, AlpnError, ConnectConfiguration, SslContextBuilder, SslVerifyMode},
     stack::Stack,
     x509::{store::X509StoreBuilder, X509},
 };
@@ -18,8 +18,9 @@ use snafu::ResultExt;
 use super::{
     AddCertToStoreSnafu, AddExtraChainCertSnafu, CaStackPushSnafu, DerExportSnafu,
     FileOpenFailedSnafu, FileReadFailedSnafu, MaybeTls, NewCaStackSnafu, NewStoreBuilderSnafu,
-    ParsePkcs12Snafu, Pkcs12Snafu, PrivateKeyParseSnafu, Result, SetCertificateSnafu,
-    SetPrivateKeySnafu, SetVerifyCertSnafu, TlsError, TlsIdentitySnafu, X509ParseSnafu,
+    ParsePkcs12Snafu, Pkcs12Snafu, PrivateKeyParseSnafu, Result, SetAlpnProtocolsSnafu,
+    SetCertificateSnafu, SetPrivateKeySnafu, SetVerifyCertSnafu, TlsError, TlsIdentitySnafu,
+    X509ParseSnafu,
 };
 
 const PEM_START_MARKER: &str = "-----BEGIN ";
@@ -88,6 +89,9 @@ pub struct TlsSettings {
     pub(super) verify_hostname: bool,
     authorities: Vec<X509>,
     pub(super) identity: Option<IdentityStore>, // openssl::pkcs12::ParsedPkcs12 doesn't impl Clone yet
+    /// The application protocols offered by clients, or selected from by servers, in the wire
+    /// format of ALPN.
+    alpn_protocols: Option<Vec<u8>>,
 }
 
 #[derive(Clone)]
@@ -124,9 +128,21 @@ impl TlsSettings {
             verify_hostname: options.verify_hostname.unwrap_or(!for_server),
             authorities: options.load_authorities()?,
             identity: options.load_identity()?,
+            alpn_protocols: None,
         })
     }
 
+    /// Negotiates one of `protocols`, such as `h2`, with ALPN, in order of preference.
+    pub fn with_alpn_protocols(mut self, protocols: &[&str]) -> Self {
+        let mut wire = Vec::new();
+        for protocol in protocols {
+            wire.push(protocol.len() as u8);
+            wire.extend_from_slice(protocol.as_bytes());
+        }
+        self.alpn_protocols = Some(wire);
+        self
+    }
+
     fn identity(&self) -> Option<ParsedPkcs12> {
         // This data was test-built previously, so we can just use it
         // here and expect the results will not fail. This can all be
@@ -180,6 +196,17 @@ impl TlsSettings {
             #[cfg(target_os = "macos")]
             load_mac_certs(context).unwrap();
         }
+        if let Some(protocols) = &self.alpn_protocols {
+            // Clients offer the protocols, and servers pick the first of them that the client
+            // offered too.
+            context
+                .set_alpn_protos(protocols)
+                .context(SetAlpnProtocolsSnafu)?;
+            let protocols = protocols.clone();
+            context.set_alpn_select_callback(move |_, offered| {
+                ssl::select_next_proto(&protocols, offered).ok_or(AlpnError::NOACK)
+            });
+        }
 
         Ok(())
     }
@@ -358,6 +385,7 @@ impl fmt::Debug for TlsSettings {
         f.debug_struct("TlsSettings")
             .field("verify_certificate", &self.verify_certificate)
             .field("verify_hostname", &self.verify_hostname)
+            .field("alpn_protocols", &self.alpn_protocols)
             .finish()
     }
 }
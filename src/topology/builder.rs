@@ -1,18 +1,21 @@
 use std::{
-    collections::HashMap,
+    collections::{hash_map::DefaultHasher, HashMap},
     future::ready,
+    hash::{Hash, Hasher},
+    num::NonZeroUsize,
     sync::{Arc, Mutex},
     time::Instant,
 };
 
-use futures::{stream::FuturesOrdered, FutureExt, SinkExt, StreamExt, TryFutureExt};
+use futures::{
+    stream::{BoxStream, FuturesOrdered},
+    FutureExt, SinkExt, StreamExt, TryFutureExt,
+};
 use lazy_static::lazy_static;
 use once_cell::sync::Lazy;
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
-use tokio::{
-    select,
-    time::{timeout, Duration},
-};
+use tokio::{select, sync::mpsc, time::Duration};
+use tracing::Instrument;
 use vector_core::{
     buffers::{
         topology::{
@@ -22,20 +25,24 @@ use vector_core::{
         BufferType, WhenFull,
     },
     internal_event::EventsSent,
+    stream::{batcher::Batcher, BatcherSettings},
     ByteSizeOf,
 };
 
 use super::{
     fanout::{self, Fanout},
+    healthcheck::{self, Recheck},
+    silence::SilenceTracker,
     task::{Task, TaskOutput},
     BuiltBuffer, ConfigDiff,
 };
 use crate::{
     config::{
-        ComponentKey, DataType, Output, OutputId, ProxyConfig, SinkContext, SourceContext,
-        TransformContext,
+        ComponentKey, DataType, Output, OutputId, Parallelism, ProxyConfig, SinkContext,
+        SourceContext, TransformContext,
     },
     event::Event,
+    heartbeat,
     internal_events::EventsReceived,
     shutdown::SourceShutdownCoordinator,
     transforms::{SyncTransform, TaskTransform, Transform, TransformOutputs, TransformOutputsBuf},
@@ -70,7 +77,10 @@ pub async fn load_enrichment_tables<'a>(
             let indexes = if !diff.enrichment_tables.contains_new(name) {
                 // If this is an existing enrichment table, we need to store the indexes to reapply
                 // them again post load.
-                Some(ENRICHMENT_TABLES.index_fields(&table_name))
+                Some((
+                    ENRICHMENT_TABLES.index_fields(&table_name),
+                    ENRICHMENT_TABLES.range_index_fields(&table_name),
+                ))
             } else {
                 None
             };
@@ -83,7 +93,7 @@ pub async fn load_enrichment_tables<'a>(
                 }
             };
 
-            if let Some(indexes) = indexes {
+            if let Some((indexes, range_indexes)) = indexes {
                 for (case, index) in indexes {
                     match table
                         .add_index(case, &index.iter().map(|s| s.as_ref()).collect::<Vec<_>>())
@@ -100,6 +110,15 @@ pub async fn load_enrichment_tables<'a>(
                         }
                     }
                 }
+
+                for field in range_indexes {
+                    if let Err(error) = table.add_range_index(&field) {
+                        error!(message = "Unable to add range index to reloaded enrichment table.",
+                                table = ?name.to_string(),
+                                %error);
+                        continue 'tables;
+                    }
+                }
             }
 
             enrichment_tables.insert(table_name, table);
@@ -149,16 +168,32 @@ pub async fn build_pieces(
     {
         let typetag = source.inner.source_type();
         let source_outputs = source.inner.outputs();
+        let default_output = source_outputs
+            .iter()
+            .find(|output| output.port.is_none())
+            .map(|output| output.ty);
 
         let mut builder = SourceSender::builder().with_buffer(SOURCE_SENDER_BUFFER_SIZE);
         let mut pumps = Vec::new();
+        let silence = source
+            .silence
+            .as_ref()
+            .map(|config| Arc::new(SilenceTracker::new(config)));
         let mut controls = HashMap::new();
         for output in source_outputs {
             let mut rx = builder.add_output(output.clone());
 
             let (mut fanout, control) = Fanout::new();
+            let event_time = source.event_time.clone();
+            let silence = silence.clone();
             let pump = async move {
-                while let Some(event) = rx.next().await {
+                while let Some(mut event) = rx.next().await {
+                    if let Some(event_time) = &event_time {
+                        event_time.stamp(&mut event);
+                    }
+                    if let Some(silence) = &silence {
+                        silence.record(&event);
+                    }
                     fanout.feed(event).await?;
                 }
                 fanout.flush().await?;
@@ -176,19 +211,38 @@ pub async fn build_pieces(
         }
 
         let pump = async move {
+            let watcher = silence.map(|silence| tokio::spawn(silence.watch().in_current_span()));
             let mut handles = Vec::new();
             for pump in pumps {
                 handles.push(tokio::spawn(pump));
             }
-            for handle in handles {
-                handle.await.expect("join error")?;
+            let result: Result<TaskOutput, ()> = async {
+                for handle in handles {
+                    handle.await.expect("join error")?;
+                }
+                Ok(TaskOutput::Source)
             }
-            Ok(TaskOutput::Source)
+            .await;
+            if let Some(watcher) = watcher {
+                watcher.abort();
+            }
+            result
         };
         let pump = Task::new(key.clone(), typetag, pump);
 
         let pipeline = builder.build();
 
+        let heartbeats = match (&config.global.source_heartbeat, default_output) {
+            (Some(heartbeat), Some(ty)) => Some(heartbeat::source_heartbeats(
+                heartbeat.clone(),
+                key.clone(),
+                typetag,
+                ty,
+                pipeline.clone(),
+            )),
+            _ => None,
+        };
+
         let (shutdown_signal, force_shutdown_tripwire) = shutdown_coordinator.register_source(key);
 
         let context = SourceContext {
@@ -206,6 +260,18 @@ pub async fn build_pieces(
             Ok(server) => server,
         };
 
+        // Heartbeats are sent for as long as the source runs.
+        let server = match heartbeats {
+            Some(heartbeats) => async move {
+                select! {
+                    result = server => result,
+                    _ = heartbeats => unreachable!("heartbeats never finish"),
+                }
+            }
+            .boxed(),
+            None => server,
+        };
+
         // The force_shutdown_tripwire is a Future that when it resolves means that this source
         // has failed to shut down gracefully within its allotted time window and instead should be
         // forcibly shut down. We accomplish this by select()-ing on the server Task with the
@@ -236,6 +302,9 @@ pub async fn build_pieces(
         source_tasks.insert(key.clone(), server);
     }
 
+    // Events of ordered sources are transformed in order, which sharding doesn't do.
+    let ordered_transforms = config.ordered_transforms();
+
     // Build transforms
     for (key, transform) in config
         .transforms
@@ -255,6 +324,11 @@ pub async fn build_pieces(
             input_type: transform.inner.input_type(),
             outputs: transform.inner.outputs(),
             enable_concurrency: transform.inner.enable_concurrency(),
+            parallelism: transform
+                .inner
+                .parallelism()
+                .filter(|_| !ordered_transforms.contains(key)),
+            coalesce_latency: config.global.coalesce_latency_ms.map(Duration::from_millis),
         };
 
         let transform = match transform.inner.build(&context).await {
@@ -284,6 +358,11 @@ pub async fn build_pieces(
         let sink_inputs = &sink.inputs;
         let healthcheck = sink.healthcheck();
         let enable_healthcheck = healthcheck.enabled && config.healthchecks.enabled;
+        let healthcheck_required = healthcheck.required;
+        let recheck_interval = healthcheck
+            .interval_secs
+            .filter(|_| enable_healthcheck)
+            .map(Duration::from_secs);
 
         let typetag = sink.inner.sink_type();
         let input_type = sink.inner.input_type();
@@ -321,6 +400,18 @@ pub async fn build_pieces(
             healthcheck,
             globals: config.global.clone(),
             proxy: ProxyConfig::merge_with_env(&config.global.proxy, sink.proxy()),
+            expire_metrics: sink.expire_metrics(&config.global),
+        };
+
+        let recheck = recheck_interval
+            .map(|interval| Recheck::new(sink.inner.as_ref(), &cx, interval, healthcheck_required))
+            .transpose();
+        let recheck = match recheck {
+            Err(error) => {
+                errors.push(format!("Sink \"{}\": {}", key, error));
+                continue;
+            }
+            Ok(recheck) => recheck,
         };
 
         let (sink, healthcheck) = match sink.inner.build(cx).await {
@@ -348,19 +439,22 @@ pub async fn build_pieces(
 
             let mut rx = crate::utilization::wrap(rx);
 
-            sink.run(
-                rx.by_ref()
-                    .filter(|event| ready(filter_event_type(event, input_type)))
-                    .inspect(|event| {
-                        emit!(&EventsReceived {
-                            count: 1,
-                            byte_size: event.size_of(),
+            // The healthcheck is re-run for as long as the sink runs.
+            let result = select! {
+                result = sink.run(
+                    rx.by_ref()
+                        .filter(|event| ready(filter_event_type(event, input_type)))
+                        .inspect(|event| {
+                            emit!(&EventsReceived {
+                                count: 1,
+                                byte_size: event.size_of(),
+                            })
                         })
-                    })
-                    .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
+                        .take_until_if(tripwire),
+                ) => result,
+                _ = Recheck::run(recheck) => unreachable!("healthcheck rechecks never finish"),
+            };
+            result.map(|_| {
                 debug!("Finished.");
                 TaskOutput::Sink(rx, acker)
             })
@@ -368,46 +462,25 @@ pub async fn build_pieces(
 
         let task = Task::new(key.clone(), typetag, sink);
 
-        let component_key = key.clone();
+        let healthcheck_span = error_span!(
+            "sink",
+            component_kind = "sink",
+            component_id = %key.id(),
+            component_type = typetag,
+            // maintained for compatibility
+            component_name = %key.id(),
+        );
         let healthcheck_task = async move {
-            if enable_healthcheck {
-                let duration = Duration::from_secs(10);
-                timeout(duration, healthcheck)
-                    .map(|result| match result {
-                        Ok(Ok(_)) => {
-                            info!("Healthcheck: Passed.");
-                            Ok(TaskOutput::Healthcheck)
-                        }
-                        Ok(Err(error)) => {
-                            error!(
-                                msg = "Healthcheck: Failed Reason.",
-                                %error,
-                                component_kind = "sink",
-                                component_type = typetag,
-                                component_id = %component_key.id(),
-                                // maintained for compatibility
-                                component_name = %component_key.id(),
-                            );
-                            Err(())
-                        }
-                        Err(_) => {
-                            error!(
-                                msg = "Healthcheck: timeout.",
-                                component_kind = "sink",
-                                component_type = typetag,
-                                component_id = %component_key.id(),
-                                // maintained for compatibility
-                                component_name = %component_key.id(),
-                            );
-                            Err(())
-                        }
-                    })
-                    .await
-            } else {
+            if !enable_healthcheck {
                 info!("Healthcheck: Disabled.");
                 Ok(TaskOutput::Healthcheck)
+            } else if healthcheck::check(healthcheck, healthcheck_required).await {
+                Ok(TaskOutput::Healthcheck)
+            } else {
+                Err(())
             }
-        };
+        }
+        .instrument(healthcheck_span);
 
         let healthcheck_task = Task::new(key.clone(), typetag, healthcheck_task);
 
@@ -463,6 +536,8 @@ struct TransformNode {
     input_type: DataType,
     outputs: Vec<Output>,
     enable_concurrency: bool,
+    parallelism: Option<Parallelism>,
+    coalesce_latency: Option<Duration>,
 }
 
 fn build_transform(
@@ -487,8 +562,10 @@ fn build_sync_transform(
 ) -> (Task, HashMap<OutputId, fanout::ControlChannel>) {
     let (outputs, controls) = TransformOutputs::new(node.outputs);
 
-    let runner = Runner::new(t, input_rx, node.input_type, outputs);
-    let transform = if node.enable_concurrency {
+    let runner = Runner::new(t, input_rx, node.input_type, node.coalesce_latency, outputs);
+    let transform = if let Some(parallelism) = node.parallelism {
+        runner.run_sharded(parallelism).boxed()
+    } else if node.enable_concurrency {
         runner.run_concurrently().boxed()
     } else {
         runner.run_inline().boxed()
@@ -511,6 +588,7 @@ struct Runner {
     transform: Box<dyn SyncTransform>,
     input_rx: Option<BufferReceiver<Event>>,
     input_type: DataType,
+    coalesce_latency: Option<Duration>,
     outputs: TransformOutputs,
     timer: crate::utilization::Timer,
     last_report: Instant,
@@ -521,12 +599,14 @@ impl Runner {
         transform: Box<dyn SyncTransform>,
         input_rx: BufferReceiver<Event>,
         input_type: DataType,
+        coalesce_latency: Option<Duration>,
         outputs: TransformOutputs,
     ) -> Self {
         Self {
             transform,
             input_rx: Some(input_rx),
             input_type,
+            coalesce_latency,
             outputs,
             timer: crate::utilization::Timer::new(),
             last_report: Instant::now(),
@@ -546,6 +626,27 @@ impl Runner {
         });
     }
 
+    /// Returns the events of the input in batches of at most `batch_size` events. With a coalescing
+    /// latency, batches are held back for up to that long to be filled.
+    fn input_batches(&mut self, batch_size: usize) -> BoxStream<'static, Vec<Event>> {
+        let input_type = self.input_type;
+        let input_rx = self
+            .input_rx
+            .take()
+            .expect("can't run runner twice")
+            .filter(move |event| ready(filter_event_type(event, input_type)));
+
+        match self.coalesce_latency {
+            Some(latency) => {
+                let batch_size = NonZeroUsize::new(batch_size).expect("batch size can't be zero");
+                let settings = BatcherSettings::new(latency, batch_size, batch_size)
+                    .into_item_size_config(|_: &Event| 1);
+                Batcher::new(input_rx, settings).boxed()
+            }
+            None => input_rx.ready_chunks(batch_size).boxed(),
+        }
+    }
+
     async fn send_outputs(&mut self, outputs_buf: &mut TransformOutputsBuf) {
         // TODO: account for named outputs separately?
         let count = outputs_buf.len();
@@ -564,12 +665,7 @@ impl Runner {
 
         let mut outputs_buf = self.outputs.new_buf_with_capacity(INLINE_BATCH_SIZE);
 
-        let mut input_rx = self
-            .input_rx
-            .take()
-            .expect("can't run runner twice")
-            .filter(move |event| ready(filter_event_type(event, self.input_type)))
-            .ready_chunks(INLINE_BATCH_SIZE);
+        let mut input_rx = self.input_batches(INLINE_BATCH_SIZE);
 
         self.timer.start_wait();
         while let Some(events) = input_rx.next().await {
@@ -591,12 +687,7 @@ impl Runner {
         // try to balance out the increased overhead of spawning tasks
         const CONCURRENT_BATCH_SIZE: usize = 1024;
 
-        let mut input_rx = self
-            .input_rx
-            .take()
-            .expect("can't run runner twice")
-            .filter(move |event| ready(filter_event_type(event, self.input_type)))
-            .ready_chunks(CONCURRENT_BATCH_SIZE);
+        let mut input_rx = self.input_batches(CONCURRENT_BATCH_SIZE);
 
         let mut in_flight = FuturesOrdered::new();
         let mut shutting_down = false;
@@ -650,6 +741,108 @@ impl Runner {
         debug!("Finished.");
         Ok(TaskOutput::Transform)
     }
+
+    /// Runs the transform on a fixed number of tasks. Events with the same value in the key field
+    /// always go to the same task, so that their order is kept. Without a key field, batches of
+    /// events are sent to each task in turn.
+    async fn run_sharded(mut self, parallelism: Parallelism) -> Result<TaskOutput, ()> {
+        // Same as the concurrent runner, as the batches are also handed over to other tasks
+        const SHARDED_BATCH_SIZE: usize = 1024;
+
+        let mut input_rx = self.input_batches(SHARDED_BATCH_SIZE);
+
+        // The outputs are unbounded so that the shards never wait on this task while it waits on
+        // them, but there are never more of them than batches handed to the shards.
+        let (outputs_tx, mut outputs_rx) = mpsc::unbounded_channel();
+        let mut shards = (0..parallelism.tasks.get())
+            .map(|_| {
+                let (shard_tx, mut shard_rx) =
+                    mpsc::channel::<(Vec<Event>, TransformOutputsBuf)>(1);
+                let outputs_tx = outputs_tx.clone();
+                let mut t = self.transform.clone();
+                tokio::spawn(async move {
+                    while let Some((events, mut outputs_buf)) = shard_rx.recv().await {
+                        for event in events {
+                            t.transform(event, &mut outputs_buf);
+                        }
+                        if outputs_tx.send(outputs_buf).is_err() {
+                            break;
+                        }
+                    }
+                });
+                shard_tx
+            })
+            .collect::<Vec<_>>();
+        drop(outputs_tx);
+
+        let mut next_shard = 0;
+
+        self.timer.start_wait();
+        loop {
+            tokio::select! {
+                biased;
+
+                outputs_buf = outputs_rx.recv() => {
+                    match outputs_buf {
+                        Some(mut outputs_buf) => self.send_outputs(&mut outputs_buf).await,
+                        // All of the shards have finished.
+                        None => break,
+                    }
+                }
+
+                input_events = input_rx.next(), if !shards.is_empty() => {
+                    match input_events {
+                        Some(events) => {
+                            self.on_events_received(&events);
+
+                            let batches = match &parallelism.key_field {
+                                Some(key_field) => {
+                                    let mut batches = vec![Vec::new(); shards.len()];
+                                    for event in events {
+                                        let shard = shard_for(&event, key_field, shards.len());
+                                        batches[shard].push(event);
+                                    }
+                                    batches.into_iter().enumerate().collect::<Vec<_>>()
+                                }
+                                None => {
+                                    let shard = next_shard;
+                                    next_shard = (next_shard + 1) % shards.len();
+                                    vec![(shard, events)]
+                                }
+                            };
+
+                            for (shard, events) in batches {
+                                if events.is_empty() {
+                                    continue;
+                                }
+                                let outputs_buf = self.outputs.new_buf_with_capacity(events.len());
+                                if shards[shard].send((events, outputs_buf)).await.is_err() {
+                                    error!(message = "Transform shard stopped unexpectedly.", %shard);
+                                    return Err(());
+                                }
+                            }
+                        }
+                        // Dropping the shards' senders lets them finish once they're done with
+                        // the batches they've been handed.
+                        None => shards.clear(),
+                    }
+                }
+            }
+        }
+
+        debug!("Finished.");
+        Ok(TaskOutput::Transform)
+    }
+}
+
+/// Returns the shard that events with the value of `key_field` in `event` go to.
+fn shard_for(event: &Event, key_field: &str, shards: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    match event {
+        Event::Log(log) => log.get(key_field).hash(&mut hasher),
+        Event::Metric(metric) => metric.tag_value(key_field).hash(&mut hasher),
+    }
+    (hasher.finish() % shards as u64) as usize
 }
 
 fn build_task_transform(
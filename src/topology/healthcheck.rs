@@ -0,0 +1,80 @@
+//! Healthchecks of sinks, which are run when the sinks are built and then, for sinks with a
+//! `healthcheck.interval_secs`, for as long as they run.
+
+use std::time::Duration;
+
+use tokio::time::{sleep, timeout};
+
+use crate::{
+    config::{SinkConfig, SinkContext},
+    internal_events::{HealthcheckFailed, HealthcheckPassed, HealthcheckTimedOut},
+    sinks::{Healthcheck, HealthcheckFn},
+};
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs a healthcheck, returning whether it passed. A sink failing a `required` healthcheck is
+/// reported as degraded until one passes.
+pub(super) async fn check(healthcheck: Healthcheck, required: bool) -> bool {
+    match timeout(TIMEOUT, healthcheck).await {
+        Ok(Ok(())) => {
+            emit!(&HealthcheckPassed { required });
+            true
+        }
+        Ok(Err(error)) => {
+            emit!(&HealthcheckFailed { error, required });
+            false
+        }
+        Err(_) => {
+            emit!(&HealthcheckTimedOut { required });
+            false
+        }
+    }
+}
+
+/// Re-runs the healthcheck of a sink every `interval`.
+pub(super) struct Recheck {
+    healthcheck: HealthcheckFn,
+    interval: Duration,
+    required: bool,
+}
+
+impl Recheck {
+    /// Only sinks that hand out a healthcheck to re-run support rechecks. Building the whole sink
+    /// again for each one would set up its clients, credentials and background tasks every time.
+    pub(super) fn new(
+        config: &dyn SinkConfig,
+        cx: &SinkContext,
+        interval: Duration,
+        required: bool,
+    ) -> crate::Result<Self> {
+        if interval.is_zero() {
+            return Err("`healthcheck.interval_secs` must be greater than 0".into());
+        }
+        let healthcheck = config.recheck(cx)?.ok_or_else(|| {
+            format!(
+                "`healthcheck.interval_secs` isn't supported by the `{}` sink",
+                config.sink_type()
+            )
+        })?;
+        Ok(Self {
+            healthcheck,
+            interval,
+            required,
+        })
+    }
+
+    /// Never finishes, and never starts without a `Recheck`, so that it can be raced against the
+    /// sink it checks.
+    pub(super) async fn run(recheck: Option<Self>) {
+        let recheck = match recheck {
+            Some(recheck) => recheck,
+            None => return futures::future::pending().await,
+        };
+
+        loop {
+            sleep(recheck.interval).await;
+            check((recheck.healthcheck)(), recheck.required).await;
+        }
+    }
+}
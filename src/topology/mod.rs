@@ -8,7 +8,9 @@
 
 pub mod builder;
 pub use vector_core::fanout;
+mod healthcheck;
 mod running;
+pub mod silence;
 mod task;
 
 #[cfg(test)]
@@ -22,6 +24,7 @@ use std::{
 
 use futures::{Future, FutureExt};
 pub use running::RunningTopology;
+pub use task::{measure_poll_time, poll_times};
 use tokio::sync::{mpsc, watch};
 use vector_core::buffers::{
     topology::channel::{BufferReceiver, BufferSender},
@@ -192,8 +192,10 @@ impl RunningTopology {
         // Now let's actually build the new pieces.
         if let Some(mut new_pieces) = build_or_log_errors(&new_config, &diff, buffers.clone()).await
         {
+            let mut healthchecks = new_config.healthchecks;
+            healthchecks.require_healthy |= healthchecks.require_healthy_on_reload;
             if self
-                .run_healthchecks(&diff, &mut new_pieces, new_config.healthchecks)
+                .run_healthchecks(&diff, &mut new_pieces, healthchecks)
                 .await
             {
                 self.connect_diff(&diff, &mut new_pieces).await;
@@ -0,0 +1,205 @@
+//! Detects sources, or partitions of their events such as the files a `file` source tails, that
+//! send no events for longer than a threshold.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::time::Instant;
+
+use crate::{
+    event::Event,
+    internal_events::{SourceLastEvent, SourceResumed, SourceSilent},
+};
+
+/// The most partitions tracked per source, so that sources partitioned by a field with
+/// unbounded values don't use unbounded memory.
+const MAX_PARTITIONS: usize = 1000;
+
+/// How often silences are checked for, at most.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Configures when a source is considered silent.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SilenceConfig {
+    /// How long a source, or a partition of its events, can go without events before it's
+    /// considered silent.
+    pub threshold_secs: u64,
+    /// The log field or metric tag whose values partition the source's events, such as `file`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition_key: Option<String>,
+}
+
+#[derive(Debug)]
+struct Partition {
+    last_event: Instant,
+    last_event_time: DateTime<Utc>,
+    silent: bool,
+}
+
+impl Partition {
+    fn new(now: Instant) -> Self {
+        Self {
+            last_event: now,
+            last_event_time: Utc::now(),
+            silent: false,
+        }
+    }
+}
+
+/// Tracks when a source last sent events, in total and by partition.
+#[derive(Debug)]
+pub struct SilenceTracker {
+    threshold: Duration,
+    partition_key: Option<String>,
+    /// The partitions by value of the partition key, with `None` for the source as a whole.
+    partitions: Mutex<HashMap<Option<String>, Partition>>,
+}
+
+impl SilenceTracker {
+    pub fn new(config: &SilenceConfig) -> Self {
+        // The source is tracked from the start, so that sources that never send any events are
+        // detected too.
+        let mut partitions = HashMap::new();
+        partitions.insert(None, Partition::new(Instant::now()));
+        Self {
+            threshold: Duration::from_secs(config.threshold_secs),
+            partition_key: config.partition_key.clone(),
+            partitions: Mutex::new(partitions),
+        }
+    }
+
+    /// Records that the source sent `event`.
+    pub fn record(&self, event: &Event) {
+        let partition = self.partition_key.as_ref().and_then(|key| match event {
+            Event::Log(log) => log.get(key.as_str()).map(|value| value.to_string_lossy()),
+            Event::Metric(metric) => metric.tag_value(key),
+        });
+
+        let now = Instant::now();
+        let mut partitions = self.partitions.lock().unwrap();
+        if let Some(partition) = partition {
+            if partitions.contains_key(&Some(partition.clone()))
+                || partitions.len() <= MAX_PARTITIONS
+            {
+                self.seen(&mut partitions, Some(partition), now);
+            }
+        }
+        self.seen(&mut partitions, None, now);
+    }
+
+    fn seen(
+        &self,
+        partitions: &mut HashMap<Option<String>, Partition>,
+        key: Option<String>,
+        now: Instant,
+    ) {
+        let partition = partitions
+            .entry(key.clone())
+            .or_insert_with(|| Partition::new(now));
+        if partition.silent {
+            partition.silent = false;
+            emit!(&SourceResumed {
+                partition: key.as_deref(),
+                silence: now.duration_since(partition.last_event),
+            });
+        }
+        partition.last_event = now;
+        partition.last_event_time = Utc::now();
+    }
+
+    /// Reports when the source and its partitions last sent events, and which of them went
+    /// silent since the last check, which are returned.
+    pub fn check(&self, now: Instant) -> Vec<Option<String>> {
+        let mut silenced = Vec::new();
+        let mut partitions = self.partitions.lock().unwrap();
+        for (key, partition) in partitions.iter_mut() {
+            let silence = now.saturating_duration_since(partition.last_event);
+            if !partition.silent && silence >= self.threshold {
+                partition.silent = true;
+                emit!(&SourceSilent {
+                    partition: key.as_deref(),
+                    silence,
+                });
+                silenced.push(key.clone());
+            }
+            emit!(&SourceLastEvent {
+                partition: key.as_deref(),
+                timestamp: partition.last_event_time,
+                silent: partition.silent,
+            });
+        }
+        silenced
+    }
+
+    /// Checks for silences until dropped.
+    pub async fn watch(self: Arc<Self>) {
+        let period = (self.threshold / 2).clamp(Duration::from_secs(1), MAX_CHECK_INTERVAL);
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            self.check(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::LogEvent;
+
+    fn tracker() -> SilenceTracker {
+        SilenceTracker::new(&SilenceConfig {
+            threshold_secs: 60,
+            partition_key: Some("file".to_owned()),
+        })
+    }
+
+    fn event(file: &str) -> Event {
+        let mut log = LogEvent::from("line");
+        log.insert("file", file);
+        log.into()
+    }
+
+    #[tokio::test]
+    async fn detects_silent_partitions() {
+        tokio::time::pause();
+        let tracker = tracker();
+        tracker.record(&event("a.log"));
+        tracker.record(&event("b.log"));
+
+        tokio::time::advance(Duration::from_secs(30)).await;
+        tracker.record(&event("a.log"));
+        assert!(tracker.check(Instant::now()).is_empty());
+
+        tokio::time::advance(Duration::from_secs(40)).await;
+        assert_eq!(
+            tracker.check(Instant::now()),
+            vec![Some("b.log".to_owned())]
+        );
+        // Silences are only reported once.
+        assert!(tracker.check(Instant::now()).is_empty());
+
+        tokio::time::advance(Duration::from_secs(60)).await;
+        let mut silenced = tracker.check(Instant::now());
+        silenced.sort();
+        assert_eq!(silenced, vec![None, Some("a.log".to_owned())]);
+
+        tracker.record(&event("b.log"));
+        assert!(!tracker.partitions.lock().unwrap()[&None].silent);
+    }
+
+    #[tokio::test]
+    async fn detects_sources_without_events() {
+        tokio::time::pause();
+        let tracker = tracker();
+
+        tokio::time::advance(Duration::from_secs(61)).await;
+        assert_eq!(tracker.check(Instant::now()), vec![None]);
+    }
+}
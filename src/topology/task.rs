@@ -1,11 +1,18 @@
 use std::{
+    collections::HashMap,
     fmt,
     future::Future,
     pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
     task::{Context, Poll},
+    time::{Duration, Instant},
 };
 
 use futures::{future::BoxFuture, FutureExt};
+use once_cell::sync::Lazy;
 use pin_project::pin_project;
 use vector_core::{
     buffers::{topology::channel::BufferReceiver, Acker},
@@ -14,6 +21,34 @@ use vector_core::{
 
 use crate::{config::ComponentKey, utilization::Utilization};
 
+static MEASURE_POLL_TIME: AtomicBool = AtomicBool::new(false);
+
+/// The nanoseconds spent polling the tasks of each component, once measuring is enabled.
+static POLL_TIMES: Lazy<Mutex<HashMap<ComponentKey, Arc<AtomicU64>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Starts measuring how long the tasks created from now on spend being polled, which is how
+/// `vector bench` attributes CPU time to components. Work a component spawns onto tasks of its
+/// own isn't included.
+pub fn measure_poll_time() {
+    MEASURE_POLL_TIME.store(true, Ordering::Relaxed);
+}
+
+/// The time spent polling the tasks of each component since measuring was enabled.
+pub fn poll_times() -> HashMap<ComponentKey, Duration> {
+    POLL_TIMES
+        .lock()
+        .expect("poisoned lock")
+        .iter()
+        .map(|(key, nanos)| {
+            (
+                key.clone(),
+                Duration::from_nanos(nanos.load(Ordering::Relaxed)),
+            )
+        })
+        .collect()
+}
+
 pub enum TaskOutput {
     Source,
     Transform,
@@ -29,6 +64,7 @@ pub struct Task {
     inner: BoxFuture<'static, Result<TaskOutput, ()>>,
     key: ComponentKey,
     typetag: String,
+    poll_time: Option<Arc<AtomicU64>>,
 }
 
 impl Task {
@@ -37,10 +73,15 @@ impl Task {
         S: Into<String>,
         Fut: Future<Output = Result<TaskOutput, ()>> + Send + 'static,
     {
+        let poll_time = MEASURE_POLL_TIME.load(Ordering::Relaxed).then(|| {
+            let mut poll_times = POLL_TIMES.lock().expect("poisoned lock");
+            Arc::clone(poll_times.entry(key.clone()).or_default())
+        });
         Self {
             inner: inner.boxed(),
             key,
             typetag: typetag.into(),
+            poll_time,
         }
     }
 
@@ -62,7 +103,15 @@ impl Future for Task {
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let this: &mut Task = self.get_mut();
-        this.inner.as_mut().poll(cx)
+        match &this.poll_time {
+            Some(poll_time) => {
+                let start = Instant::now();
+                let result = this.inner.as_mut().poll(cx);
+                poll_time.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+                result
+            }
+            None => this.inner.as_mut().poll(cx),
+        }
     }
 }
 
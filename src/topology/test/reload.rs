@@ -123,6 +123,7 @@ async fn topology_reuse_old_port_sink() {
             namespace: None,
             tags: None,
         })],
+        all_metrics: false,
     };
 
     let mut old_config = Config::builder();
@@ -173,6 +174,7 @@ async fn topology_reuse_old_port_cross_dependency() {
             namespace: None,
             tags: None,
         })],
+        all_metrics: false,
     };
 
     let mut old_config = Config::builder();
@@ -236,6 +238,7 @@ async fn topology_disk_buffer_conflict() {
                 namespace: None,
                 tags: None,
             })],
+            all_metrics: false,
         },
     );
     old_config.add_sink(
@@ -81,6 +81,7 @@ async fn closed_source() {
         BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            ..Default::default()
         },
     );
     old_config.add_sink(
@@ -89,6 +90,7 @@ async fn closed_source() {
         BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            ..Default::default()
         },
     );
 
@@ -109,6 +111,7 @@ async fn closed_source() {
         BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            ..Default::default()
         },
     );
 
@@ -144,6 +147,7 @@ async fn remove_sink() {
         BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            ..Default::default()
         },
     );
     old_config.add_sink(
@@ -152,6 +156,7 @@ async fn remove_sink() {
         BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            ..Default::default()
         },
     );
 
@@ -171,6 +176,7 @@ async fn remove_sink() {
         BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            ..Default::default()
         },
     );
 
@@ -209,6 +215,7 @@ async fn remove_transform() {
         BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            ..Default::default()
         },
     );
     old_config.add_sink(
@@ -217,6 +224,7 @@ async fn remove_transform() {
         BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            ..Default::default()
         },
     );
 
@@ -236,6 +244,7 @@ async fn remove_transform() {
         BlackholeConfig {
             print_interval_secs: 10,
             rate: None,
+            ..Default::default()
         },
     );
 
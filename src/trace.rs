@@ -1,5 +1,14 @@
-use std::sync::{Mutex, MutexGuard};
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
 
+use chrono::{DateTime, Utc};
 use metrics_tracing_context::MetricsLayer;
 use once_cell::sync::OnceCell;
 use tokio::sync::broadcast::{self, Receiver, Sender};
@@ -13,10 +22,13 @@ use tracing_core::span;
 pub use tracing_futures::Instrument;
 use tracing_limit::RateLimitedLayer;
 use tracing_log::LogTracer;
-use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::{
+    layer::{Context, Layer, SubscriberExt},
+    registry::LookupSpan,
+};
 pub use tracing_tower::{InstrumentableService, InstrumentedService};
 
-use crate::event::LogEvent;
+use crate::event::{LogEvent, Value};
 
 /// BUFFER contains all of the internal log events generated by Vector
 /// before the topology has been initialized. It will be cleared (set to
@@ -28,6 +40,14 @@ static BUFFER: OnceCell<Mutex<Option<Vec<LogEvent>>>> = OnceCell::new();
 /// initialized.
 static SENDER: OnceCell<Sender<LogEvent>> = OnceCell::new();
 
+/// SPAN_SENDER receives a log event for each span as it closes, while
+/// anything is subscribed to spans.
+static SPAN_SENDER: OnceCell<Sender<LogEvent>> = OnceCell::new();
+
+/// Spans with this name, and their children, aren't sent to span
+/// subscribers, so that the `internal_traces` source doesn't trace itself.
+pub const UNTRACED_SPAN_NAME: &str = "internal_traces";
+
 fn metrics_layer_enabled() -> bool {
     !matches!(std::env::var("DISABLE_INTERNAL_METRICS_TRACING_INTEGRATION"), Ok(x) if x == "true")
 }
@@ -53,6 +73,11 @@ pub fn init(color: bool, json: bool, levels: &str) {
     let subscriber = tracing_subscriber::registry::Registry::default()
         .with(tracing_subscriber::filter::EnvFilter::from(levels));
 
+    let subscriber = subscriber.with(SpanBroadcastLayer);
+
+    #[cfg(feature = "allocation-tracing")]
+    let subscriber = subscriber.with(crate::allocations::AllocationLayer);
+
     // dev note: we attempted to refactor to reduce duplication but it was starting to seem like
     // the refactored code would be introducing more complexity than it was worth to remove this
     // bit of duplication as we started to create a generic struct to wrap the formatters that also
@@ -137,6 +162,189 @@ pub fn subscribe() -> TraceSubscription {
     TraceSubscription { buffer, receiver }
 }
 
+/// Subscribes to the spans closed from now on.
+pub fn subscribe_spans() -> Receiver<LogEvent> {
+    SPAN_SENDER
+        .get_or_init(|| broadcast::channel(99).0)
+        .subscribe()
+}
+
+fn spans_subscribed() -> bool {
+    SPAN_SENDER
+        .get()
+        .map_or(false, |sender| sender.receiver_count() > 0)
+}
+
+/// Gives each recorded span an ID that, unlike its tracing `Id`, isn't reused once it closes.
+static NEXT_SPAN_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Marks a span that shouldn't be sent to span subscribers.
+struct Untraced;
+
+struct SpanTiming {
+    span_id: u64,
+    trace_id: u64,
+    parent_span_id: Option<u64>,
+    start_time: DateTime<Utc>,
+    start: Instant,
+    entered: Option<Instant>,
+    busy: Duration,
+    fields: SpanFields,
+}
+
+#[derive(Default)]
+struct SpanFields(BTreeMap<String, Value>);
+
+impl tracing::field::Visit for SpanFields {
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &tracing::field::Field, value: i64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+        self.0
+            .insert(field.name().to_owned(), (value as i64).into());
+    }
+
+    fn record_bool(&mut self, field: &tracing::field::Field, value: bool) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_f64(&mut self, field: &tracing::field::Field, value: f64) {
+        self.0.insert(field.name().to_owned(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn Debug) {
+        self.0
+            .insert(field.name().to_owned(), format!("{:?}", value).into());
+    }
+}
+
+/// Records the timing of spans, and sends a log event describing each one as it closes. This
+/// does nothing while there are no span subscribers.
+struct SpanBroadcastLayer;
+
+impl<S> Layer<S> for SpanBroadcastLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+        let parent = span.parent();
+        let untraced = attrs.metadata().name() == UNTRACED_SPAN_NAME
+            || parent.as_ref().map_or(false, |parent| {
+                parent.extensions().get::<Untraced>().is_some()
+            });
+        if untraced {
+            span.extensions_mut().insert(Untraced);
+            return;
+        }
+        if !spans_subscribed() {
+            return;
+        }
+
+        let span_id = NEXT_SPAN_ID.fetch_add(1, Ordering::Relaxed);
+        let (trace_id, parent_span_id) = parent
+            .as_ref()
+            .and_then(|parent| {
+                parent
+                    .extensions()
+                    .get::<SpanTiming>()
+                    .map(|timing| (timing.trace_id, Some(timing.span_id)))
+            })
+            .unwrap_or((span_id, None));
+
+        let mut fields = SpanFields::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(SpanTiming {
+            span_id,
+            trace_id,
+            parent_span_id,
+            start_time: Utc::now(),
+            start: Instant::now(),
+            entered: None,
+            busy: Duration::default(),
+            fields,
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+                values.record(&mut timing.fields);
+            }
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+                timing.entered = Some(Instant::now());
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(timing) = span.extensions_mut().get_mut::<SpanTiming>() {
+                if let Some(entered) = timing.entered.take() {
+                    timing.busy += entered.elapsed();
+                }
+            }
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(&id) {
+            Some(span) => span,
+            None => return,
+        };
+        let timing = match span.extensions_mut().remove::<SpanTiming>() {
+            Some(timing) => timing,
+            None => return,
+        };
+        let sender = match SPAN_SENDER.get() {
+            Some(sender) if sender.receiver_count() > 0 => sender,
+            _ => return,
+        };
+
+        let duration = timing.start.elapsed();
+        let metadata = span.metadata();
+        let mut log = LogEvent::default();
+        log.insert("message", metadata.name());
+        log.insert("timestamp", Utc::now());
+        log.insert("trace_id", format!("{:016x}", timing.trace_id));
+        log.insert("span_id", format!("{:016x}", timing.span_id));
+        if let Some(parent_span_id) = timing.parent_span_id {
+            log.insert("parent_span_id", format!("{:016x}", parent_span_id));
+        }
+        log.insert("start_time", timing.start_time);
+        log.insert("duration_ns", duration.as_nanos() as i64);
+        log.insert("busy_ns", timing.busy.as_nanos() as i64);
+        log.insert(
+            "idle_ns",
+            duration.saturating_sub(timing.busy).as_nanos() as i64,
+        );
+        log.insert("fields", Value::Map(timing.fields.0));
+        log.insert("metadata.kind", "span");
+        log.insert("metadata.level", metadata.level().to_string());
+        log.insert(
+            "metadata.module_path",
+            metadata
+                .module_path()
+                .map_or(Value::Null, |module_path| module_path.into()),
+        );
+        log.insert("metadata.target", metadata.target());
+        let _ = sender.send(log); // Ignore errors
+    }
+}
+
 struct BroadcastSubscriber<S> {
     subscriber: S,
 }
@@ -7,11 +7,13 @@ use std::{
 use async_stream::stream;
 use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use tokio_stream::wrappers::IntervalStream;
 
 use crate::{
     config::{DataType, Output, TransformConfig, TransformContext, TransformDescription},
     event::{metric, Event, EventMetadata},
     internal_events::{AggregateEventRecorded, AggregateFlushed, AggregateUpdateFailed},
+    schedule::{self, CronSchedule},
     transforms::{TaskTransform, Transform},
 };
 
@@ -21,6 +23,8 @@ pub struct AggregateConfig {
     /// The interval between flushes in milliseconds.
     #[serde(default = "default_interval_ms")]
     pub interval_ms: u64,
+    /// A cron schedule to flush at instead of every `interval_ms`.
+    pub flush_schedule: Option<CronSchedule>,
 }
 
 const fn default_interval_ms() -> u64 {
@@ -60,6 +64,7 @@ type MetricEntry = (metric::MetricData, EventMetadata);
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
+    flush_schedule: Option<CronSchedule>,
     map: HashMap<metric::MetricSeries, MetricEntry>,
 }
 
@@ -67,6 +72,7 @@ impl Aggregate {
     pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
+            flush_schedule: config.flush_schedule.clone(),
             map: HashMap::new(),
         })
     }
@@ -117,14 +123,19 @@ impl TaskTransform for Aggregate {
     where
         Self: 'static,
     {
-        let mut flush_stream = tokio::time::interval(self.interval);
+        let mut flush_stream = match &self.flush_schedule {
+            Some(flush_schedule) => schedule::ticks(flush_schedule).map(|_| ()).boxed(),
+            None => IntervalStream::new(tokio::time::interval(self.interval))
+                .map(|_| ())
+                .boxed(),
+        };
 
         Box::pin(stream! {
             let mut output = Vec::new();
             let mut done = false;
             while !done {
                 tokio::select! {
-                    _ = flush_stream.tick() => {
+                    Some(()) = flush_stream.next() => {
                         self.flush_into(&mut output);
                     },
                     maybe_event = input_rx.next() => {
@@ -171,6 +182,7 @@ mod tests {
     fn incremental() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            flush_schedule: None,
         })
         .unwrap();
 
@@ -241,6 +253,7 @@ mod tests {
     fn absolute() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            flush_schedule: None,
         })
         .unwrap();
 
@@ -306,6 +319,7 @@ mod tests {
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            flush_schedule: None,
         })
         .unwrap();
 
@@ -363,6 +377,7 @@ mod tests {
     fn conflicting_kinds() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            flush_schedule: None,
         })
         .unwrap();
 
@@ -413,6 +428,17 @@ mod tests {
         assert_eq!(&summed, &out[0]);
     }
 
+    #[test]
+    fn parses_flush_schedule() {
+        let config = toml::from_str::<AggregateConfig>(r#"flush_schedule = "0 * * * *""#).unwrap();
+        assert_eq!(
+            config.flush_schedule,
+            Some(CronSchedule::parse("0 * * * *").unwrap())
+        );
+
+        assert!(toml::from_str::<AggregateConfig>(r#"flush_schedule = "60 * * * *""#).is_err());
+    }
+
     #[tokio::test]
     async fn transform_shutdown() {
         let agg = toml::from_str::<AggregateConfig>(
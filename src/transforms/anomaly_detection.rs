@@ -0,0 +1,485 @@
+use std::{collections::HashMap, time::Duration};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::time::Instant;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::AnomalyDetectionEventDiscarded,
+    transforms::{FunctionTransform, Transform},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`alpha` must be greater than 0 and at most 1, got {}", alpha))]
+    InvalidAlpha { alpha: f64 },
+    #[snafu(display("`window_secs` must be greater than 0, got {}", window_secs))]
+    InvalidWindow { window_secs: f64 },
+    #[snafu(display("`idle_timeout_secs` must be greater than 0"))]
+    ZeroIdleTimeout,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct AnomalyDetectionConfig {
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    pub field: Option<String>,
+    #[serde(default = "default_window_secs")]
+    pub window_secs: f64,
+    #[serde(default = "default_alpha")]
+    pub alpha: f64,
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,
+    #[serde(default = "default_min_samples")]
+    pub min_samples: u64,
+    #[serde(default = "default_score_field")]
+    pub score_field: String,
+    #[serde(default = "default_anomaly_field")]
+    pub anomaly_field: String,
+    #[serde(default)]
+    pub anomalies_only: bool,
+    #[serde(default = "default_idle_timeout_secs")]
+    pub idle_timeout_secs: u64,
+}
+
+const fn default_window_secs() -> f64 {
+    1.0
+}
+
+const fn default_alpha() -> f64 {
+    0.1
+}
+
+const fn default_threshold() -> f64 {
+    3.0
+}
+
+const fn default_min_samples() -> u64 {
+    10
+}
+
+const fn default_idle_timeout_secs() -> u64 {
+    600
+}
+
+fn default_score_field() -> String {
+    "anomaly_score".to_owned()
+}
+
+fn default_anomaly_field() -> String {
+    "is_anomaly".to_owned()
+}
+
+inventory::submit! {
+    TransformDescription::new::<AnomalyDetectionConfig>("anomaly_detection")
+}
+
+impl GenerateConfig for AnomalyDetectionConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            group_by: vec!["host".to_owned()],
+            field: None,
+            window_secs: default_window_secs(),
+            alpha: default_alpha(),
+            threshold: default_threshold(),
+            min_samples: default_min_samples(),
+            score_field: default_score_field(),
+            anomaly_field: default_anomaly_field(),
+            anomalies_only: false,
+            idle_timeout_secs: default_idle_timeout_secs(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "anomaly_detection")]
+impl TransformConfig for AnomalyDetectionConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        AnomalyDetection::new(self).map(Transform::function)
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "anomaly_detection"
+    }
+}
+
+/// Exponentially weighted moving mean and variance of a series.
+#[derive(Debug, Default)]
+struct Ewma {
+    mean: f64,
+    variance: f64,
+    samples: u64,
+}
+
+impl Ewma {
+    /// The z-score of `value` against the series so far. There is no score until the series has
+    /// varied, since any deviation from a constant series would be infinitely far off.
+    fn score(&self, value: f64) -> Option<f64> {
+        let std_dev = self.variance.sqrt();
+        (std_dev > 0.0).then(|| (value - self.mean) / std_dev)
+    }
+
+    fn update(&mut self, value: f64, alpha: f64) {
+        if self.samples == 0 {
+            self.mean = value;
+        } else {
+            let diff = value - self.mean;
+            let increment = alpha * diff;
+            self.mean += increment;
+            self.variance = (1.0 - alpha) * (self.variance + diff * increment);
+        }
+        self.samples += 1;
+    }
+}
+
+#[derive(Debug)]
+struct KeyState {
+    ewma: Ewma,
+    // The start of the current window, and the number of events in it so far, in rate mode.
+    window_start: Option<f64>,
+    count: u64,
+    last_seen: Instant,
+}
+
+impl KeyState {
+    fn new(now: Instant) -> Self {
+        Self {
+            ewma: Ewma::default(),
+            window_start: None,
+            count: 0,
+            last_seen: now,
+        }
+    }
+}
+
+// Beyond this many empty windows in a row, the estimate has already decayed to almost nothing.
+const MAX_EMPTY_WINDOWS: u64 = 1000;
+
+#[derive(Debug)]
+pub struct AnomalyDetection {
+    group_by: Vec<String>,
+    field: Option<String>,
+    window_secs: f64,
+    alpha: f64,
+    threshold: f64,
+    min_samples: u64,
+    score_field: String,
+    anomaly_field: String,
+    anomalies_only: bool,
+    idle_timeout: Duration,
+    state: HashMap<Vec<Option<String>>, KeyState>,
+    last_sweep: Instant,
+}
+
+impl AnomalyDetection {
+    pub fn new(config: &AnomalyDetectionConfig) -> crate::Result<Self> {
+        if !(config.alpha > 0.0 && config.alpha <= 1.0) {
+            return Err(Box::new(BuildError::InvalidAlpha {
+                alpha: config.alpha,
+            }));
+        }
+        if !(config.window_secs > 0.0) {
+            return Err(Box::new(BuildError::InvalidWindow {
+                window_secs: config.window_secs,
+            }));
+        }
+        if config.idle_timeout_secs == 0 {
+            return Err(Box::new(BuildError::ZeroIdleTimeout));
+        }
+
+        Ok(Self {
+            group_by: config.group_by.clone(),
+            field: config.field.clone(),
+            window_secs: config.window_secs,
+            alpha: config.alpha,
+            threshold: config.threshold,
+            min_samples: config.min_samples,
+            score_field: config.score_field.clone(),
+            anomaly_field: config.anomaly_field.clone(),
+            anomalies_only: config.anomalies_only,
+            idle_timeout: Duration::from_secs(config.idle_timeout_secs),
+            state: HashMap::new(),
+            last_sweep: Instant::now(),
+        })
+    }
+
+    /// Forgets the history of keys that haven't had an event for `idle_timeout`.
+    fn sweep(&mut self, now: Instant) {
+        if now.duration_since(self.last_sweep) < self.idle_timeout {
+            return;
+        }
+        self.last_sweep = now;
+        let idle_timeout = self.idle_timeout;
+        self.state
+            .retain(|_, state| now.duration_since(state.last_seen) < idle_timeout);
+    }
+
+    /// Score the event against its key's history, and fold it into that history. Returns the
+    /// score along with whether the event is anomalous, if it could be scored.
+    fn score(&mut self, log: &LogEvent) -> Option<(f64, bool)> {
+        // Infinite and NaN values would poison the history of their key for good.
+        let value = match &self.field {
+            Some(field) => match log.get(field)? {
+                Value::Integer(value) => Some(*value as f64),
+                Value::Float(value) if value.is_finite() => Some(*value),
+                _ => return None,
+            },
+            None => None,
+        };
+
+        let now = Instant::now();
+        self.sweep(now);
+
+        let key = self
+            .group_by
+            .iter()
+            .map(|field| log.get(field).map(Value::to_string_lossy))
+            .collect();
+        let state = self.state.entry(key).or_insert_with(|| KeyState::new(now));
+        state.last_seen = now;
+
+        match value {
+            Some(value) => {
+                let score = (state.ewma.samples >= self.min_samples)
+                    .then(|| state.ewma.score(value))
+                    .flatten();
+                state.ewma.update(value, self.alpha);
+
+                score.map(|score| (score, score.abs() > self.threshold))
+            }
+            None => {
+                let timestamp = match log.get(log_schema().timestamp_key()) {
+                    Some(Value::Timestamp(timestamp)) => *timestamp,
+                    _ => Utc::now(),
+                };
+                let now = timestamp.timestamp_millis() as f64 / 1000.0;
+
+                let window_start = *state.window_start.get_or_insert(now);
+                if now >= window_start + self.window_secs {
+                    let elapsed = ((now - window_start) / self.window_secs).floor() as u64;
+
+                    state.ewma.update(state.count as f64, self.alpha);
+                    for _ in 1..elapsed.min(MAX_EMPTY_WINDOWS) {
+                        state.ewma.update(0.0, self.alpha);
+                    }
+
+                    state.window_start = Some(window_start + elapsed as f64 * self.window_secs);
+                    state.count = 0;
+                }
+                state.count += 1;
+
+                // The window is still filling up, so only a rate that is already too high can be
+                // told apart from one that is too low.
+                let score = (state.ewma.samples >= self.min_samples)
+                    .then(|| state.ewma.score(state.count as f64))
+                    .flatten();
+
+                score.map(|score| (score, score > self.threshold))
+            }
+        }
+    }
+}
+
+impl FunctionTransform for AnomalyDetection {
+    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+        let scored = self.score(event.as_log());
+
+        if let Some((score, anomalous)) = scored {
+            let log = event.as_mut_log();
+            log.insert(self.score_field.as_str(), score);
+            log.insert(self.anomaly_field.as_str(), anomalous);
+        }
+
+        if self.anomalies_only && !matches!(scored, Some((_, true))) {
+            emit!(&AnomalyDetectionEventDiscarded);
+        } else {
+            output.push(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, TimeZone};
+
+    use super::*;
+    use crate::transforms::test::transform_one;
+
+    fn transform(config: &str) -> AnomalyDetection {
+        let config: AnomalyDetectionConfig = toml::from_str(config).unwrap();
+        AnomalyDetection::new(&config).unwrap()
+    }
+
+    fn value_event(host: &str, value: f64) -> Event {
+        let mut event = Event::from("message");
+        event.as_mut_log().insert("host", host);
+        event.as_mut_log().insert("value", value);
+        event
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AnomalyDetectionConfig>();
+    }
+
+    #[test]
+    fn rejects_invalid_alpha() {
+        let config: AnomalyDetectionConfig = toml::from_str("alpha = 0.0").unwrap();
+        assert!(AnomalyDetection::new(&config).is_err());
+    }
+
+    #[test]
+    fn skips_non_finite_values() {
+        let mut transform = transform(
+            r#"
+            field = "value"
+            min_samples = 5
+            "#,
+        );
+
+        for value in [10.0, 11.0, 9.0, 10.0, 11.0, 9.0] {
+            transform_one(&mut transform, value_event("a", value)).unwrap();
+        }
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let event = transform_one(&mut transform, value_event("a", value)).unwrap();
+            assert!(event.as_log().get("anomaly_score").is_none());
+        }
+
+        let event = transform_one(&mut transform, value_event("a", 10.0)).unwrap();
+        assert_eq!(event.as_log()["is_anomaly"], Value::Boolean(false));
+        let event = transform_one(&mut transform, value_event("a", 50.0)).unwrap();
+        assert_eq!(event.as_log()["is_anomaly"], Value::Boolean(true));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn forgets_idle_keys() {
+        let mut transform = transform(
+            r#"
+            group_by = ["host"]
+            field = "value"
+            idle_timeout_secs = 60
+            "#,
+        );
+
+        transform_one(&mut transform, value_event("a", 1.0)).unwrap();
+        tokio::time::advance(std::time::Duration::from_secs(30)).await;
+        transform_one(&mut transform, value_event("b", 1.0)).unwrap();
+        assert_eq!(transform.state.len(), 2);
+
+        tokio::time::advance(std::time::Duration::from_secs(40)).await;
+        transform_one(&mut transform, value_event("b", 1.0)).unwrap();
+        assert_eq!(transform.state.len(), 1);
+        assert!(transform.state.contains_key(&vec![Some("b".to_owned())]));
+    }
+
+    #[test]
+    fn flags_outlying_values() {
+        let mut transform = transform(
+            r#"
+            field = "value"
+            min_samples = 5
+            "#,
+        );
+
+        for value in [10.0, 11.0, 9.0, 10.0, 11.0, 9.0, 10.0] {
+            let event = transform_one(&mut transform, value_event("a", value)).unwrap();
+            if let Some(anomalous) = event.as_log().get("is_anomaly") {
+                assert_eq!(anomalous, &Value::Boolean(false));
+            }
+        }
+
+        let event = transform_one(&mut transform, value_event("a", 50.0)).unwrap();
+        assert_eq!(event.as_log()["is_anomaly"], Value::Boolean(true));
+        assert!(matches!(event.as_log()["anomaly_score"], Value::Float(score) if score > 3.0));
+    }
+
+    #[test]
+    fn waits_for_min_samples() {
+        let mut transform = transform(
+            r#"
+            field = "value"
+            min_samples = 3
+            "#,
+        );
+
+        for value in [1.0, 100.0, 1.0] {
+            let event = transform_one(&mut transform, value_event("a", value)).unwrap();
+            assert!(event.as_log().get("anomaly_score").is_none());
+        }
+
+        let event = transform_one(&mut transform, value_event("a", 1.0)).unwrap();
+        assert!(event.as_log().get("anomaly_score").is_some());
+    }
+
+    #[test]
+    fn tracks_keys_separately() {
+        let mut transform = transform(
+            r#"
+            group_by = ["host"]
+            field = "value"
+            min_samples = 5
+            "#,
+        );
+
+        for value in [10.0, 11.0, 9.0, 10.0, 11.0, 9.0] {
+            transform_one(&mut transform, value_event("a", value)).unwrap();
+            transform_one(&mut transform, value_event("b", value * 10.0)).unwrap();
+        }
+
+        let event = transform_one(&mut transform, value_event("b", 100.0)).unwrap();
+        assert_eq!(event.as_log()["is_anomaly"], Value::Boolean(false));
+
+        let event = transform_one(&mut transform, value_event("a", 100.0)).unwrap();
+        assert_eq!(event.as_log()["is_anomaly"], Value::Boolean(true));
+    }
+
+    #[test]
+    fn flags_rate_spikes() {
+        let mut transform = transform(
+            r#"
+            min_samples = 5
+            anomalies_only = true
+            "#,
+        );
+        let start = Utc.timestamp(1_600_000_000, 0);
+        let mut emitted = 0;
+
+        // Ten windows alternating between 2 and 3 events each, then a burst of 20 events.
+        for window in 0..10 {
+            for _ in 0..(2 + window % 2) {
+                let mut event = Event::from("message");
+                event.as_mut_log().insert(
+                    log_schema().timestamp_key(),
+                    start + Duration::seconds(window),
+                );
+                emitted += transform_one(&mut transform, event).into_iter().count();
+            }
+        }
+        assert_eq!(emitted, 0);
+
+        for _ in 0..20 {
+            let mut event = Event::from("message");
+            event
+                .as_mut_log()
+                .insert(log_schema().timestamp_key(), start + Duration::seconds(10));
+            emitted += transform_one(&mut transform, event).into_iter().count();
+        }
+        assert!(emitted > 0);
+    }
+}
@@ -0,0 +1,419 @@
+use std::{collections::HashMap, pin::Pin, time::Duration};
+
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::time::Instant;
+
+use super::event_groups::{CloseReason, EventGroup};
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{
+        DataType, GenerateConfig, Output, TransformConfig, TransformContext, TransformDescription,
+    },
+    event::{discriminant::Discriminant, Event},
+    transforms::{TaskTransform, Transform},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one of `starts_when` and `ends_when` must be set"))]
+    NoBoundaries,
+    #[snafu(display("`timeout_ms` must be greater than 0"))]
+    ZeroTimeout,
+    #[snafu(display("`flush_period_ms` must be greater than 0"))]
+    ZeroFlushPeriod,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct BatchBoundariesConfig {
+    /// Matches the events that start a batch, such as a `BEGIN REPORT` line.
+    pub starts_when: Option<AnyCondition>,
+    /// Matches the events that end a batch, such as an `END REPORT` line.
+    pub ends_when: Option<AnyCondition>,
+    /// The fields that identify which feed an event belongs to, so that interleaved feeds are
+    /// batched separately.
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    #[serde(default = "default_batch_id_field")]
+    pub batch_id_field: String,
+    /// Whether to pass on the boundary events themselves.
+    #[serde(default = "crate::serde::default_true")]
+    pub include_boundaries: bool,
+    /// Whether to emit a summary event for each batch once it closes.
+    #[serde(default)]
+    pub emit_summaries: bool,
+    /// How long a batch may go without events before it closes, incomplete. Batches whose end
+    /// never comes would otherwise be kept forever, one for each value of the `group_by` fields.
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    #[serde(default = "default_flush_period_ms")]
+    pub flush_period_ms: u64,
+}
+
+fn default_batch_id_field() -> String {
+    "batch_id".to_owned()
+}
+
+const fn default_timeout_ms() -> u64 {
+    5 * 60 * 1000
+}
+
+const fn default_flush_period_ms() -> u64 {
+    1000
+}
+
+inventory::submit! {
+    TransformDescription::new::<BatchBoundariesConfig>("batch_boundaries")
+}
+
+impl GenerateConfig for BatchBoundariesConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"starts_when = "starts_with!(.message, \"BEGIN REPORT\")"
+            ends_when = "starts_with!(.message, \"END REPORT\")""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "batch_boundaries")]
+impl TransformConfig for BatchBoundariesConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        BatchBoundaries::new(self, &context.enrichment_tables).map(Transform::task)
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "batch_boundaries"
+    }
+}
+
+pub struct BatchBoundaries {
+    starts_when: Option<Box<dyn Condition>>,
+    ends_when: Option<Box<dyn Condition>>,
+    group_by: Vec<String>,
+    batch_id_field: String,
+    include_boundaries: bool,
+    emit_summaries: bool,
+    timeout: Duration,
+    flush_period: Duration,
+    batches: HashMap<Discriminant, EventGroup>,
+}
+
+impl BatchBoundaries {
+    pub fn new(
+        config: &BatchBoundariesConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        if config.starts_when.is_none() && config.ends_when.is_none() {
+            return Err(Box::new(BuildError::NoBoundaries));
+        }
+        if config.timeout_ms == 0 {
+            return Err(Box::new(BuildError::ZeroTimeout));
+        }
+        if config.flush_period_ms == 0 {
+            return Err(Box::new(BuildError::ZeroFlushPeriod));
+        }
+
+        let starts_when = config
+            .starts_when
+            .as_ref()
+            .map(|c| c.build(enrichment_tables))
+            .transpose()?;
+        let ends_when = config
+            .ends_when
+            .as_ref()
+            .map(|c| c.build(enrichment_tables))
+            .transpose()?;
+
+        Ok(Self {
+            starts_when,
+            ends_when,
+            group_by: config.group_by.clone(),
+            batch_id_field: config.batch_id_field.clone(),
+            include_boundaries: config.include_boundaries,
+            emit_summaries: config.emit_summaries,
+            timeout: Duration::from_millis(config.timeout_ms),
+            flush_period: Duration::from_millis(config.flush_period_ms),
+            batches: HashMap::new(),
+        })
+    }
+
+    fn close(&self, output: &mut Vec<Event>, batch: EventGroup, reason: CloseReason) {
+        if self.emit_summaries {
+            // Without an end boundary, batches are complete once the next one starts.
+            let complete = match reason {
+                CloseReason::EndBoundary => true,
+                CloseReason::StartBoundary => self.ends_when.is_none(),
+                _ => false,
+            };
+            let mut summary = batch.summary("batch", &self.batch_id_field, reason);
+            summary.insert("complete", complete);
+            output.push(summary.into());
+        }
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let now = Instant::now();
+        let expired = self
+            .batches
+            .iter()
+            .filter(|(_, batch)| now.duration_since(batch.last_seen) >= self.timeout)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in expired {
+            if let Some(batch) = self.batches.remove(&key) {
+                self.close(output, batch, CloseReason::Timeout);
+            }
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        for (_, batch) in std::mem::take(&mut self.batches) {
+            self.close(output, batch, CloseReason::Shutdown);
+        }
+    }
+
+    fn transform_one(&mut self, output: &mut Vec<Event>, event: Event) {
+        let starts_here = self
+            .starts_when
+            .as_ref()
+            .map(|c| c.check(&event))
+            .unwrap_or(false);
+        let ends_here = self
+            .ends_when
+            .as_ref()
+            .map(|c| c.check(&event))
+            .unwrap_or(false);
+
+        let mut log = event.into_log();
+        let key = Discriminant::from_log_event(&log, &self.group_by);
+        let now = Instant::now();
+
+        if starts_here {
+            if let Some(batch) = self.batches.remove(&key) {
+                self.close(output, batch, CloseReason::StartBoundary);
+            }
+        }
+
+        // With a start boundary, events between the end of one batch and the start of the next
+        // don't belong to any batch and are passed on as they are.
+        if !self.batches.contains_key(&key) && !starts_here && self.starts_when.is_some() {
+            output.push(log.into());
+            return;
+        }
+
+        let batch = self
+            .batches
+            .entry(key.clone())
+            .or_insert_with(|| EventGroup::new(&log, &self.group_by, now));
+        batch.add(&log, now);
+        let boundary = starts_here || ends_here;
+        if !boundary {
+            batch.count += 1;
+        }
+        if !boundary || self.include_boundaries {
+            log.insert(self.batch_id_field.as_str(), batch.id.clone());
+            output.push(log.into());
+        }
+
+        if ends_here {
+            let batch = self.batches.remove(&key).expect("batch exists");
+            self.close(output, batch, CloseReason::EndBoundary);
+        }
+    }
+}
+
+impl TaskTransform for BatchBoundaries {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let mut flush_stream = tokio::time::interval(me.flush_period);
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    _ = flush_stream.tick() => {
+                      me.flush_into(&mut output);
+                      false
+                    }
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.flush_all_into(&mut output);
+                          true
+                        }
+                        Some(event) => {
+                          me.transform_one(&mut output, event);
+                          false
+                        }
+                      }
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{LogEvent, Value};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<BatchBoundariesConfig>();
+    }
+
+    fn batch_boundaries(config: &str) -> BatchBoundaries {
+        BatchBoundaries::new(
+            &toml::from_str(config).unwrap(),
+            &enrichment::TableRegistry::default(),
+        )
+        .unwrap()
+    }
+
+    fn event(message: &str, feed: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("feed", feed);
+        log.into()
+    }
+
+    fn batch_id(event: &Event) -> Option<&Value> {
+        event.as_log().get("batch_id")
+    }
+
+    const REPORTS: &str = r#"
+        starts_when = "starts_with!(.message, \"BEGIN REPORT\")"
+        ends_when = "starts_with!(.message, \"END REPORT\")"
+        group_by = ["feed"]
+        emit_summaries = true
+    "#;
+
+    #[test]
+    fn batches_between_boundaries() {
+        let mut transform = batch_boundaries(REPORTS);
+
+        let mut output = Vec::new();
+        for message in [
+            "header",
+            "BEGIN REPORT 1",
+            "line 1",
+            "line 2",
+            "END REPORT 1",
+            "trailer",
+        ] {
+            transform.transform_one(&mut output, event(message, "a"));
+        }
+
+        assert_eq!(output.len(), 7);
+        assert_eq!(batch_id(&output[0]), None);
+        let id = batch_id(&output[1]).unwrap();
+        assert!(output[2..5].iter().all(|event| batch_id(event) == Some(id)));
+        let summary = output[5].as_log();
+        assert_eq!(summary.get("batch_id"), Some(id));
+        assert_eq!(summary["feed"], "a".into());
+        assert_eq!(summary["event_count"], 2.into());
+        assert_eq!(summary["close_reason"], "end_boundary".into());
+        assert_eq!(summary["complete"], true.into());
+        assert_eq!(batch_id(&output[6]), None);
+        assert!(transform.batches.is_empty());
+    }
+
+    #[test]
+    fn batches_feeds_separately() {
+        let mut transform = batch_boundaries(REPORTS);
+
+        let mut output = Vec::new();
+        transform.transform_one(&mut output, event("BEGIN REPORT", "a"));
+        transform.transform_one(&mut output, event("BEGIN REPORT", "b"));
+        transform.transform_one(&mut output, event("line", "a"));
+        // A batch that starts before the last one ended is incomplete.
+        transform.transform_one(&mut output, event("BEGIN REPORT", "a"));
+
+        assert_ne!(batch_id(&output[0]), batch_id(&output[1]));
+        assert_eq!(batch_id(&output[0]), batch_id(&output[2]));
+        let summary = output[3].as_log();
+        assert_eq!(summary.get("batch_id"), batch_id(&output[0]));
+        assert_eq!(summary["close_reason"], "start_boundary".into());
+        assert_eq!(summary["complete"], false.into());
+        assert_ne!(batch_id(&output[4]), batch_id(&output[0]));
+
+        transform.flush_all_into(&mut output);
+        assert_eq!(output.len(), 7);
+    }
+
+    #[test]
+    fn drops_boundaries() {
+        let mut transform = batch_boundaries(
+            r#"
+                ends_when = "starts_with!(.message, \"END REPORT\")"
+                include_boundaries = false
+            "#,
+        );
+
+        let mut output = Vec::new();
+        for message in ["line 1", "END REPORT", "line 2", "END REPORT"] {
+            transform.transform_one(&mut output, event(message, "a"));
+        }
+
+        // Without a start boundary, each batch starts with the event after the last one ended.
+        assert_eq!(output.len(), 2);
+        assert!(batch_id(&output[0]).is_some());
+        assert_ne!(batch_id(&output[0]), batch_id(&output[1]));
+    }
+
+    #[tokio::test]
+    async fn closes_batches_after_timeout() {
+        tokio::time::pause();
+        let mut transform = batch_boundaries(&format!("{}\ntimeout_ms = 1000", REPORTS));
+
+        let mut output = Vec::new();
+        transform.transform_one(&mut output, event("BEGIN REPORT", "a"));
+        tokio::time::advance(Duration::from_millis(600)).await;
+        transform.flush_into(&mut output);
+        assert_eq!(output.len(), 1);
+
+        tokio::time::advance(Duration::from_millis(600)).await;
+        transform.flush_into(&mut output);
+        assert_eq!(output.len(), 2);
+        assert_eq!(output[1].as_log()["close_reason"], "timeout".into());
+        assert_eq!(output[1].as_log()["complete"], false.into());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let config = |config: &str| {
+            BatchBoundaries::new(
+                &toml::from_str(config).unwrap(),
+                &enrichment::TableRegistry::default(),
+            )
+        };
+        assert!(config("group_by = [\"feed\"]").is_err());
+        assert!(config(&format!("{}\ntimeout_ms = 0", REPORTS)).is_err());
+        assert!(config(&format!("{}\nflush_period_ms = 0", REPORTS)).is_err());
+    }
+}
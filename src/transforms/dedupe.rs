@@ -141,6 +141,7 @@ const fn type_id_for_value(val: &Value) -> TypeId {
         Value::Map(_) => 5,
         Value::Array(_) => 6,
         Value::Null => 7,
+        Value::Decimal(_) => 8,
     }
 }
 
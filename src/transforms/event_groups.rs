@@ -0,0 +1,101 @@
+//! What `sessionize` and `batch_boundaries` share: both assign events with the same `group_by`
+//! fields to a group with an ID, and can summarize each group once it closes.
+
+use chrono::{DateTime, Utc};
+use tokio::time::Instant;
+
+use crate::{
+    config::log_schema,
+    event::{LogEvent, Value},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum CloseReason {
+    Inactivity,
+    MaxDuration,
+    EndBoundary,
+    StartBoundary,
+    Timeout,
+    Shutdown,
+}
+
+impl CloseReason {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Inactivity => "inactivity",
+            Self::MaxDuration => "max_duration",
+            Self::EndBoundary => "end_boundary",
+            Self::StartBoundary => "start_boundary",
+            Self::Timeout => "timeout",
+            Self::Shutdown => "shutdown",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct EventGroup {
+    pub(super) id: String,
+    /// The `group_by` fields of the first event, to identify the group in its summary.
+    key: Vec<(String, Value)>,
+    pub(super) started: Instant,
+    pub(super) last_seen: Instant,
+    first_timestamp: DateTime<Utc>,
+    last_timestamp: DateTime<Utc>,
+    /// The number of events counted towards the group, which is up to its transform.
+    pub(super) count: u64,
+}
+
+impl EventGroup {
+    pub(super) fn new(log: &LogEvent, group_by: &[String], now: Instant) -> Self {
+        let timestamp = timestamp(log);
+        Self {
+            id: uuid::Uuid::new_v4().to_hyphenated().to_string(),
+            key: group_by
+                .iter()
+                .filter_map(|field| Some((field.clone(), log.get(field)?.clone())))
+                .collect(),
+            started: now,
+            last_seen: now,
+            first_timestamp: timestamp,
+            last_timestamp: timestamp,
+            count: 0,
+        }
+    }
+
+    /// Records that `log` arrived, without counting it.
+    pub(super) fn add(&mut self, log: &LogEvent, now: Instant) {
+        let timestamp = timestamp(log);
+        self.first_timestamp = self.first_timestamp.min(timestamp);
+        self.last_timestamp = self.last_timestamp.max(timestamp);
+        self.last_seen = now;
+    }
+
+    /// How long passed between the first and last events of the group, by their timestamps.
+    pub(super) fn duration(&self) -> chrono::Duration {
+        self.last_timestamp - self.first_timestamp
+    }
+
+    /// A summary of the group, with the times of its first and last events in `<kind>_start` and
+    /// `<kind>_end`.
+    pub(super) fn summary(self, kind: &str, id_field: &str, reason: CloseReason) -> LogEvent {
+        let mut log = LogEvent::default();
+        for (field, value) in self.key {
+            log.insert(field.as_str(), value);
+        }
+        log.insert(id_field, self.id);
+        log.insert(format!("{}_start", kind), self.first_timestamp);
+        log.insert(format!("{}_end", kind), self.last_timestamp);
+        log.insert("event_count", self.count as i64);
+        log.insert("close_reason", reason.as_str());
+        log.insert(log_schema().timestamp_key(), Utc::now());
+        log
+    }
+}
+
+/// The time the event happened, falling back to now for events without a timestamp.
+fn timestamp(log: &LogEvent) -> DateTime<Utc> {
+    match log.get(log_schema().timestamp_key()) {
+        Some(Value::Timestamp(timestamp)) => *timestamp,
+        _ => Utc::now(),
+    }
+}
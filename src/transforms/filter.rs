@@ -1,9 +1,12 @@
+use std::num::NonZeroUsize;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{
-        DataType, GenerateConfig, Output, TransformConfig, TransformContext, TransformDescription,
+        DataType, GenerateConfig, Output, Parallelism, TransformConfig, TransformContext,
+        TransformDescription,
     },
     event::Event,
     internal_events::FilterEventDiscarded,
@@ -14,11 +17,19 @@ use crate::{
 #[serde(deny_unknown_fields)]
 pub struct FilterConfig {
     condition: AnyCondition,
+    #[serde(default)]
+    parallelism: Option<NonZeroUsize>,
+    #[serde(default)]
+    parallelism_key_field: Option<String>,
 }
 
 impl From<AnyCondition> for FilterConfig {
     fn from(condition: AnyCondition) -> Self {
-        Self { condition }
+        Self {
+            condition,
+            parallelism: None,
+            parallelism_key_field: None,
+        }
     }
 }
 
@@ -57,6 +68,13 @@ impl TransformConfig for FilterConfig {
         true
     }
 
+    fn parallelism(&self) -> Option<Parallelism> {
+        self.parallelism.map(|tasks| Parallelism {
+            tasks,
+            key_field: self.parallelism_key_field.clone(),
+        })
+    }
+
     fn transform_type(&self) -> &'static str {
         "filter"
     }
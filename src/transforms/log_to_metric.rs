@@ -13,8 +13,8 @@ use crate::{
         Event, Value,
     },
     internal_events::{
-        LogToMetricFieldNotFound, LogToMetricFieldNull, LogToMetricParseFloatError,
-        LogToMetricTemplateParseError, TemplateRenderingFailed,
+        LogToMetricFieldNotFound, LogToMetricFieldNull, LogToMetricInvalidMetric,
+        LogToMetricParseFloatError, LogToMetricTemplateParseError, TemplateRenderingFailed,
     },
     template::{Template, TemplateParseError, TemplateRenderingError},
     transforms::{FunctionTransform, Transform},
@@ -23,7 +23,12 @@ use crate::{
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct LogToMetricConfig {
+    #[serde(default)]
     pub metrics: Vec<MetricConfig>,
+    /// Converts each log event created by `metric_to_log` back into the metric it was created
+    /// from, instead of creating the metrics configured in `metrics`.
+    #[serde(default)]
+    pub all_metrics: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -120,6 +125,7 @@ impl GenerateConfig for LogToMetricConfig {
                 kind: MetricKind::Incremental,
                 tags: None,
             })],
+            all_metrics: false,
         })
         .unwrap()
     }
@@ -381,8 +387,34 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
     }
 }
 
+/// Converts a log event created by `metric_to_log` back into the metric it was created from.
+fn to_metric_from_log(event: &Event) -> Result<Metric, serde_json::Error> {
+    let mut log = event.as_log().clone();
+
+    // `metric_to_log` moves the timestamp and the host tag to the keys of the log schema.
+    if let Some(timestamp) = log.remove(log_schema().timestamp_key()) {
+        log.insert_flat("timestamp", timestamp);
+    }
+    if let Some(host) = log.remove(log_schema().host_key()) {
+        log.insert(format!("tags.{}", log_schema().host_key()).as_str(), host);
+    }
+
+    let mut metric: Metric = serde_json::from_value(serde_json::to_value(&log)?)?;
+    *metric.metadata_mut() = event.metadata().clone();
+
+    Ok(metric)
+}
+
 impl FunctionTransform for LogToMetric {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        if self.config.all_metrics {
+            match to_metric_from_log(&event) {
+                Ok(metric) => output.push(Event::Metric(metric)),
+                Err(error) => emit!(&LogToMetricInvalidMetric { error }),
+            }
+            return;
+        }
+
         for config in self.config.metrics.iter() {
             match to_metric(config, &event) {
                 Ok(metric) => {
@@ -900,4 +932,76 @@ mod tests {
             .with_timestamp(Some(ts()))
         );
     }
+
+    #[cfg(feature = "transforms-metric_to_log")]
+    fn round_trip(metric: Metric) -> Option<Metric> {
+        use crate::transforms::metric_to_log::MetricToLog;
+
+        let log = MetricToLog::new(None, Default::default())
+            .transform_one(metric)
+            .unwrap();
+        let mut transform = LogToMetric::new(parse_config("all_metrics = true"));
+
+        transform_one(&mut transform, log.into()).map(Event::into_metric)
+    }
+
+    #[cfg(feature = "transforms-metric_to_log")]
+    #[test]
+    fn all_metrics_round_trip() {
+        use vector_core::metrics::AgentDDSketch;
+
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert_many(&[1.0, 2.5, 10.0]);
+
+        let values = vec![
+            MetricValue::Counter { value: 1.0 },
+            MetricValue::Gauge { value: -3.25 },
+            MetricValue::Set {
+                values: vec!["a".to_owned(), "b".to_owned()].into_iter().collect(),
+            },
+            MetricValue::Distribution {
+                samples: vector_core::samples![1.0 => 3, 2.5 => 1],
+                statistic: StatisticKind::Histogram,
+            },
+            MetricValue::AggregatedHistogram {
+                buckets: vector_core::buckets![1.0 => 2, 4.0 => 5],
+                count: 7,
+                sum: 15.5,
+            },
+            MetricValue::AggregatedSummary {
+                quantiles: vector_core::quantiles![0.5 => 2.0, 0.99 => 9.5],
+                count: 7,
+                sum: 15.5,
+            },
+            MetricValue::Sketch {
+                sketch: crate::event::metric::MetricSketch::AgentDDSketch(sketch),
+            },
+        ];
+
+        for value in values {
+            let metric = Metric::new("requests", MetricKind::Incremental, value)
+                .with_namespace(Some("app"))
+                .with_tags(Some(
+                    vec![
+                        ("host".to_owned(), "localhost".to_owned()),
+                        ("code".to_owned(), "200".to_owned()),
+                    ]
+                    .into_iter()
+                    .collect(),
+                ))
+                .with_timestamp(Some(ts()));
+
+            assert_eq!(round_trip(metric.clone()), Some(metric));
+        }
+    }
+
+    #[test]
+    fn all_metrics_skips_other_logs() {
+        let mut transform = LogToMetric::new(parse_config("all_metrics = true"));
+
+        assert_eq!(
+            transform_one(&mut transform, create_event("status", "42")),
+            None
+        );
+    }
 }
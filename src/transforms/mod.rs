@@ -6,12 +6,16 @@ pub mod add_fields;
 pub mod add_tags;
 #[cfg(feature = "transforms-aggregate")]
 pub mod aggregate;
+#[cfg(feature = "transforms-anomaly_detection")]
+pub mod anomaly_detection;
 #[cfg(feature = "transforms-ansi_stripper")]
 pub mod ansi_stripper;
 #[cfg(feature = "transforms-aws_cloudwatch_logs_subscription_parser")]
 pub mod aws_cloudwatch_logs_subscription_parser;
 #[cfg(feature = "transforms-aws_ec2_metadata")]
 pub mod aws_ec2_metadata;
+#[cfg(feature = "transforms-batch_boundaries")]
+pub mod batch_boundaries;
 #[cfg(feature = "transforms-coercer")]
 pub mod coercer;
 #[cfg(feature = "transforms-compound")]
@@ -20,6 +24,11 @@ pub mod compound;
 pub mod concat;
 #[cfg(feature = "transforms-dedupe")]
 pub mod dedupe;
+#[cfg(any(
+    feature = "transforms-batch_boundaries",
+    feature = "transforms-sessionize"
+))]
+mod event_groups;
 #[cfg(feature = "transforms-field_filter")]
 pub mod field_filter;
 #[cfg(feature = "transforms-filter")]
@@ -61,12 +70,22 @@ pub mod rename_fields;
 pub mod route;
 #[cfg(feature = "transforms-sample")]
 pub mod sample;
+#[cfg(feature = "transforms-sessionize")]
+pub mod sessionize;
+#[cfg(feature = "transforms-span_to_metrics")]
+pub mod span_to_metrics;
 #[cfg(feature = "transforms-split")]
 pub mod split;
 #[cfg(feature = "transforms-tag_cardinality_limit")]
 pub mod tag_cardinality_limit;
+#[cfg(feature = "transforms-tail_sampling")]
+pub mod tail_sampling;
+#[cfg(feature = "transforms-tenant_quota")]
+pub mod tenant_quota;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-timestamp_policy")]
+pub mod timestamp_policy;
 #[cfg(feature = "transforms-tokenizer")]
 pub mod tokenizer;
 
@@ -2,6 +2,7 @@ use std::collections::HashSet;
 
 use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 use serde::{Deserialize, Serialize};
 
 use crate::event::{LogEvent, Value};
@@ -322,6 +323,25 @@ impl ReduceValueMerger for TimestampWindowMerger {
 enum NumberMergerValue {
     Int(i64),
     Float(f64),
+    Decimal(Decimal),
+}
+
+impl NumberMergerValue {
+    fn as_f64(&self) -> f64 {
+        match self {
+            NumberMergerValue::Int(i) => *i as f64,
+            NumberMergerValue::Float(f) => *f,
+            NumberMergerValue::Decimal(d) => d.to_f64().unwrap_or(f64::NAN),
+        }
+    }
+
+    fn into_value(self) -> Value {
+        match self {
+            NumberMergerValue::Int(i) => Value::Integer(i),
+            NumberMergerValue::Float(f) => Value::Float(f),
+            NumberMergerValue::Decimal(d) => Value::Decimal(d),
+        }
+    }
 }
 
 impl From<i64> for NumberMergerValue {
@@ -336,6 +356,12 @@ impl From<f64> for NumberMergerValue {
     }
 }
 
+impl From<Decimal> for NumberMergerValue {
+    fn from(v: Decimal) -> Self {
+        NumberMergerValue::Decimal(v)
+    }
+}
+
 //------------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
@@ -347,20 +373,34 @@ impl AddNumbersMerger {
     const fn new(v: NumberMergerValue) -> Self {
         Self { v }
     }
+
+    fn add_decimals(&mut self, a: Decimal, b: Decimal) {
+        self.v = match a.checked_add(b) {
+            Some(sum) => NumberMergerValue::Decimal(sum),
+            None => NumberMergerValue::Float(
+                a.to_f64().unwrap_or(f64::NAN) + b.to_f64().unwrap_or(f64::NAN),
+            ),
+        };
+    }
 }
 
 impl ReduceValueMerger for AddNumbersMerger {
     fn add(&mut self, v: Value) -> Result<(), String> {
-        // Try and keep max precision with integer values, but once we've
-        // received a float downgrade to float precision.
+        // Try and keep max precision with integer and decimal values, but once
+        // we've received a float downgrade to float precision.
         match v {
             Value::Integer(i) => match self.v {
                 NumberMergerValue::Int(j) => self.v = NumberMergerValue::Int(i + j),
                 NumberMergerValue::Float(j) => self.v = NumberMergerValue::Float(i as f64 + j),
+                NumberMergerValue::Decimal(j) => self.add_decimals(Decimal::from(i), j),
             },
-            Value::Float(f) => match self.v {
-                NumberMergerValue::Int(j) => self.v = NumberMergerValue::Float(f + j as f64),
-                NumberMergerValue::Float(j) => self.v = NumberMergerValue::Float(f + j),
+            Value::Float(f) => self.v = NumberMergerValue::Float(f + self.v.as_f64()),
+            Value::Decimal(d) => match self.v {
+                NumberMergerValue::Int(j) => self.add_decimals(d, Decimal::from(j)),
+                NumberMergerValue::Float(j) => {
+                    self.v = NumberMergerValue::Float(d.to_f64().unwrap_or(f64::NAN) + j)
+                }
+                NumberMergerValue::Decimal(j) => self.add_decimals(d, j),
             },
             _ => {
                 return Err(format!(
@@ -373,10 +413,7 @@ impl ReduceValueMerger for AddNumbersMerger {
     }
 
     fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
-        match self.v {
-            NumberMergerValue::Float(f) => v.insert(k, Value::Float(f)),
-            NumberMergerValue::Int(i) => v.insert(k, Value::Integer(i)),
-        };
+        v.insert(k, self.v.into_value());
         Ok(())
     }
 }
@@ -412,17 +449,28 @@ impl ReduceValueMerger for MaxNumberMerger {
                             self.v = NumberMergerValue::Float(f);
                         }
                     }
+                    NumberMergerValue::Decimal(d2) => {
+                        if Decimal::from(i) > d2 {
+                            self.v = NumberMergerValue::Int(i);
+                        }
+                    }
                 };
             }
             Value::Float(f) => {
-                let f2 = match self.v {
-                    NumberMergerValue::Int(i2) => i2 as f64,
-                    NumberMergerValue::Float(f2) => f2,
-                };
-                if f > f2 {
+                if f > self.v.as_f64() {
                     self.v = NumberMergerValue::Float(f);
                 }
             }
+            Value::Decimal(d) => {
+                let replace = match self.v {
+                    NumberMergerValue::Int(i2) => d > Decimal::from(i2),
+                    NumberMergerValue::Float(f2) => d.to_f64().map_or(false, |f| f > f2),
+                    NumberMergerValue::Decimal(d2) => d > d2,
+                };
+                if replace {
+                    self.v = NumberMergerValue::Decimal(d);
+                }
+            }
             _ => {
                 return Err(format!(
                     "expected numeric value, found: '{}'",
@@ -434,10 +482,7 @@ impl ReduceValueMerger for MaxNumberMerger {
     }
 
     fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
-        match self.v {
-            NumberMergerValue::Float(f) => v.insert(k, Value::Float(f)),
-            NumberMergerValue::Int(i) => v.insert(k, Value::Integer(i)),
-        };
+        v.insert(k, self.v.into_value());
         Ok(())
     }
 }
@@ -473,17 +518,28 @@ impl ReduceValueMerger for MinNumberMerger {
                             self.v = NumberMergerValue::Float(f);
                         }
                     }
+                    NumberMergerValue::Decimal(d2) => {
+                        if Decimal::from(i) < d2 {
+                            self.v = NumberMergerValue::Int(i);
+                        }
+                    }
                 };
             }
             Value::Float(f) => {
-                let f2 = match self.v {
-                    NumberMergerValue::Int(i2) => i2 as f64,
-                    NumberMergerValue::Float(f2) => f2,
-                };
-                if f < f2 {
+                if f < self.v.as_f64() {
                     self.v = NumberMergerValue::Float(f);
                 }
             }
+            Value::Decimal(d) => {
+                let replace = match self.v {
+                    NumberMergerValue::Int(i2) => d < Decimal::from(i2),
+                    NumberMergerValue::Float(f2) => d.to_f64().map_or(false, |f| f < f2),
+                    NumberMergerValue::Decimal(d2) => d < d2,
+                };
+                if replace {
+                    self.v = NumberMergerValue::Decimal(d);
+                }
+            }
             _ => {
                 return Err(format!(
                     "expected numeric value, found: '{}'",
@@ -495,10 +551,7 @@ impl ReduceValueMerger for MinNumberMerger {
     }
 
     fn insert_into(self: Box<Self>, k: String, v: &mut LogEvent) -> Result<(), String> {
-        match self.v {
-            NumberMergerValue::Float(f) => v.insert(k, Value::Float(f)),
-            NumberMergerValue::Int(i) => v.insert(k, Value::Integer(i)),
-        };
+        v.insert(k, self.v.into_value());
         Ok(())
     }
 }
@@ -515,6 +568,7 @@ impl From<Value> for Box<dyn ReduceValueMerger> {
         match v {
             Value::Integer(i) => Box::new(AddNumbersMerger::new(i.into())),
             Value::Float(f) => Box::new(AddNumbersMerger::new(f.into())),
+            Value::Decimal(d) => Box::new(AddNumbersMerger::new(d.into())),
             Value::Timestamp(ts) => Box::new(TimestampWindowMerger::new(ts)),
             Value::Map(_) => Box::new(DiscardMerger::new(v)),
             Value::Null => Box::new(DiscardMerger::new(v)),
@@ -530,6 +584,7 @@ pub fn get_value_merger(v: Value, m: &MergeStrategy) -> Result<Box<dyn ReduceVal
         MergeStrategy::Sum => match v {
             Value::Integer(i) => Ok(Box::new(AddNumbersMerger::new(i.into()))),
             Value::Float(f) => Ok(Box::new(AddNumbersMerger::new(f.into()))),
+            Value::Decimal(d) => Ok(Box::new(AddNumbersMerger::new(d.into()))),
             _ => Err(format!(
                 "expected number value, found: '{}'",
                 v.to_string_lossy()
@@ -538,6 +593,7 @@ pub fn get_value_merger(v: Value, m: &MergeStrategy) -> Result<Box<dyn ReduceVal
         MergeStrategy::Max => match v {
             Value::Integer(i) => Ok(Box::new(MaxNumberMerger::new(i.into()))),
             Value::Float(f) => Ok(Box::new(MaxNumberMerger::new(f.into()))),
+            Value::Decimal(d) => Ok(Box::new(MaxNumberMerger::new(d.into()))),
             _ => Err(format!(
                 "expected number value, found: '{}'",
                 v.to_string_lossy()
@@ -546,6 +602,7 @@ pub fn get_value_merger(v: Value, m: &MergeStrategy) -> Result<Box<dyn ReduceVal
         MergeStrategy::Min => match v {
             Value::Integer(i) => Ok(Box::new(MinNumberMerger::new(i.into()))),
             Value::Float(f) => Ok(Box::new(MinNumberMerger::new(f.into()))),
+            Value::Decimal(d) => Ok(Box::new(MinNumberMerger::new(d.into()))),
             _ => Err(format!(
                 "expected number value, found: '{}'",
                 v.to_string_lossy()
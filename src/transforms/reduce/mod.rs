@@ -12,8 +12,8 @@ use serde::{Deserialize, Serialize};
 use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, Output, TransformConfig, TransformContext, TransformDescription},
-    event::{discriminant::Discriminant, Event, EventMetadata, LogEvent},
-    internal_events::ReduceStaleEventFlushed,
+    event::{discriminant::Discriminant, watermark::Watermark, Event, EventMetadata, LogEvent},
+    internal_events::{ReduceLateEventPassedThrough, ReduceStaleEventFlushed},
     transforms::{TaskTransform, Transform},
 };
 
@@ -30,6 +30,11 @@ pub struct ReduceConfig {
 
     pub flush_period_ms: Option<u64>,
 
+    /// How far behind the latest observed event time an event may arrive
+    /// before it is considered late. Late events are passed through without
+    /// being reduced. Requires the source to be configured with `event_time`.
+    pub allowed_lateness_ms: Option<u64>,
+
     /// An ordered list of fields to distinguish reduces by. Each
     /// reduce has a separate event merging state.
     #[serde(default)]
@@ -154,6 +159,7 @@ pub struct Reduce {
     reduce_merge_states: HashMap<Discriminant, ReduceState>,
     ends_when: Option<Box<dyn Condition>>,
     starts_when: Option<Box<dyn Condition>>,
+    watermark: Option<Watermark>,
 }
 
 impl Reduce {
@@ -185,6 +191,9 @@ impl Reduce {
             reduce_merge_states: HashMap::new(),
             ends_when,
             starts_when,
+            watermark: config
+                .allowed_lateness_ms
+                .map(|ms| Watermark::new(chrono::Duration::milliseconds(ms as i64))),
         })
     }
 
@@ -221,6 +230,15 @@ impl Reduce {
     }
 
     fn transform_one(&mut self, output: &mut Vec<Event>, event: Event) {
+        if let Some(watermark) = self.watermark.as_mut() {
+            if watermark.observe_event(&event) {
+                emit!(&ReduceLateEventPassedThrough);
+                output.push(event);
+                self.flush_into(output);
+                return;
+            }
+        }
+
         let starts_here = self
             .starts_when
             .as_ref()
@@ -377,6 +395,52 @@ group_by = [ "request_id" ]
         assert_eq!(output_2.metadata(), &metadata_2);
     }
 
+    #[tokio::test]
+    async fn reduce_passes_through_late_events() {
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+allowed_lateness_ms = 10000
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap()
+        .build(&TransformContext::default())
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        let event_at = |message: &str, secs: i64| {
+            let mut event = LogEvent::from(message);
+            event.insert("counter", 1);
+            event.insert("request_id", "1");
+            event
+                .metadata_mut()
+                .set_event_time(Some(chrono::TimeZone::timestamp(&chrono::Utc, secs, 0)));
+            event
+        };
+
+        let e_1 = event_at("test message 1", 100);
+        let e_2 = event_at("test message 2", 80);
+        let mut e_3 = event_at("test message 3", 95);
+        e_3.insert("test_end", "yep");
+
+        let inputs = vec![e_1.into(), e_2.into(), e_3.into()];
+        let in_stream = Box::pin(stream::iter(inputs));
+        let mut out_stream = reduce.transform(in_stream);
+
+        let output_1 = out_stream.next().await.unwrap().into_log();
+        assert_eq!(output_1["message"], "test message 2".into());
+        assert_eq!(output_1["counter"], Value::from(1));
+
+        let output_2 = out_stream.next().await.unwrap().into_log();
+        assert_eq!(output_2["message"], "test message 1".into());
+        assert_eq!(output_2["counter"], Value::from(2));
+    }
+
     #[tokio::test]
     async fn reduce_merge_strategies() {
         let reduce = toml::from_str::<ReduceConfig>(
@@ -1,17 +1,24 @@
 use std::{
+    collections::HashMap,
     fs::File,
     io::{self, Read},
+    num::NonZeroUsize,
     path::PathBuf,
+    sync::{Arc, Mutex, Weak},
 };
 
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use shared::TimeZone;
 use snafu::{ResultExt, Snafu};
-use vrl::{diagnostic::Formatter, prelude::ExpressionError, Program, Runtime, Terminate};
+use vrl::{
+    diagnostic::Formatter, prelude::ExpressionError, state, value::IntegerOverflow, Program,
+    Runtime, Terminate,
+};
 
 use crate::{
     config::{
-        log_schema, ComponentKey, DataType, Output, TransformConfig, TransformContext,
+        log_schema, ComponentKey, DataType, Output, Parallelism, TransformConfig, TransformContext,
         TransformDescription,
     },
     event::{Event, VrlTarget},
@@ -22,6 +29,16 @@ use crate::{
 
 const DROPPED: &str = "dropped";
 
+/// Programs compiled by running `remap` transforms, keyed by their source and how they handle
+/// integer overflow.
+///
+/// Aggregators often run many `remap` transforms with the same program, which then only get
+/// compiled once, and share the static data of the program, such as regexes and grok patterns.
+/// Entries are weak, so a program is dropped once no transform uses it anymore.
+static PROGRAMS: Lazy<Mutex<HashMap<ProgramKey, Weak<Program>>>> = Lazy::new(Default::default);
+
+type ProgramKey = (String, IntegerOverflow);
+
 #[derive(Deserialize, Serialize, Debug, Clone, Derivative)]
 #[serde(deny_unknown_fields, default)]
 #[derivative(Default)]
@@ -34,6 +51,9 @@ pub struct RemapConfig {
     #[serde(default = "crate::serde::default_true")]
     pub drop_on_abort: bool,
     pub reroute_dropped: bool,
+    pub parallelism: Option<NonZeroUsize>,
+    pub parallelism_key_field: Option<String>,
+    pub integer_overflow: IntegerOverflow,
 }
 
 inventory::submit! {
@@ -72,12 +92,19 @@ impl TransformConfig for RemapConfig {
     fn enable_concurrency(&self) -> bool {
         true
     }
+
+    fn parallelism(&self) -> Option<Parallelism> {
+        self.parallelism.map(|tasks| Parallelism {
+            tasks,
+            key_field: self.parallelism_key_field.clone(),
+        })
+    }
 }
 
 #[derive(Debug)]
 pub struct Remap {
     component_key: Option<ComponentKey>,
-    program: Program,
+    program: Arc<Program>,
     runtime: Runtime,
     timezone: TimeZone,
     drop_on_error: bool,
@@ -102,16 +129,7 @@ impl Remap {
             _ => return Err(Box::new(BuildError::SourceAndOrFile)),
         };
 
-        let mut functions = vrl_stdlib::all();
-        functions.append(&mut enrichment::vrl_functions());
-        functions.append(&mut vector_vrl_functions::vrl_functions());
-
-        let program = vrl::compile(
-            &source,
-            &functions,
-            Some(Box::new(context.enrichment_tables.clone())),
-        )
-        .map_err(|diagnostics| Formatter::new(&source, diagnostics).colored().to_string())?;
+        let program = compile(source, config.integer_overflow, context)?;
 
         Ok(Remap {
             component_key: context.key.clone(),
@@ -162,6 +180,54 @@ impl Remap {
     }
 }
 
+/// Compiles `source`, or returns the program another transform compiled from it.
+fn compile(
+    source: String,
+    integer_overflow: IntegerOverflow,
+    context: &TransformContext,
+) -> crate::Result<Arc<Program>> {
+    // Enrichment functions register the indexes they search with while they're compiled, and
+    // tables are loaded again on every reload, so programs using them can't be shared.
+    if !context.enrichment_tables.table_ids().is_empty() {
+        return compile_uncached(&source, integer_overflow, context).map(Arc::new);
+    }
+
+    let key = (source, integer_overflow);
+    let mut programs = PROGRAMS.lock().expect("poisoned lock");
+    if let Some(program) = programs.get(&key).and_then(Weak::upgrade) {
+        debug!(message = "Reusing compiled VRL program.");
+        return Ok(program);
+    }
+
+    let program = compile_uncached(&key.0, integer_overflow, context).map(Arc::new)?;
+
+    programs.retain(|_, program| program.strong_count() > 0);
+    programs.insert(key, Arc::downgrade(&program));
+
+    Ok(program)
+}
+
+fn compile_uncached(
+    source: &str,
+    integer_overflow: IntegerOverflow,
+    context: &TransformContext,
+) -> crate::Result<Program> {
+    let mut functions = vrl_stdlib::all();
+    functions.append(&mut enrichment::vrl_functions());
+    functions.append(&mut vector_vrl_functions::vrl_functions());
+
+    let mut state = state::Compiler::new();
+    state.set_external_context(Some(Box::new(context.enrichment_tables.clone())));
+    state.set_integer_overflow(integer_overflow);
+
+    vrl::compile_with_state(source, &functions, &mut state).map_err(|diagnostics| {
+        Formatter::new(source, diagnostics)
+            .colored()
+            .to_string()
+            .into()
+    })
+}
+
 impl Clone for Remap {
     fn clone(&self) -> Self {
         Self {
@@ -309,6 +375,56 @@ mod tests {
         event.as_log().get(field).unwrap().to_string_lossy()
     }
 
+    #[test]
+    fn shares_programs_with_the_same_source() {
+        let config = |source: &str| RemapConfig {
+            source: Some(source.to_owned()),
+            ..Default::default()
+        };
+        let first = Remap::new(config(".shared = 1"), &Default::default()).unwrap();
+        let second = Remap::new(config(".shared = 1"), &Default::default()).unwrap();
+        let other = Remap::new(config(".shared = 2"), &Default::default()).unwrap();
+
+        assert!(Arc::ptr_eq(&first.program, &second.program));
+        assert!(!Arc::ptr_eq(&first.program, &other.program));
+    }
+
+    #[cfg(feature = "enrichment-tables-file")]
+    #[test]
+    fn doesnt_share_programs_using_enrichment_tables() {
+        use crate::enrichment_tables::file::{File, FileConfig};
+
+        let file: FileConfig = toml::from_str(indoc! {r#"
+            file.path = "/data/info.csv"
+            file.encoding.type = "csv"
+        "#})
+        .unwrap();
+        let table = File::new(
+            file,
+            std::time::SystemTime::now(),
+            vec![vec!["result".into()]],
+            vec!["field".to_owned()],
+        );
+
+        let context = TransformContext::default();
+        let mut tables: HashMap<String, Box<dyn enrichment::Table + Send + Sync>> = HashMap::new();
+        tables.insert("table".to_owned(), Box::new(table));
+        context.enrichment_tables.load(tables);
+
+        // Each transform has to register the index it searches with, since tables are loaded
+        // again on every reload.
+        let config = RemapConfig {
+            source: Some(
+                r#".result = get_enrichment_table_record!("table", {"field": .field})"#.to_owned(),
+            ),
+            ..Default::default()
+        };
+        let first = Remap::new(config.clone(), &context).unwrap();
+        let second = Remap::new(config, &context).unwrap();
+
+        assert!(!Arc::ptr_eq(&first.program, &second.program));
+    }
+
     #[test]
     fn check_remap_doesnt_share_state_between_events() {
         let conf = RemapConfig {
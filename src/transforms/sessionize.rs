@@ -0,0 +1,384 @@
+use std::{
+    collections::{hash_map, HashMap},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::time::Instant;
+
+use super::event_groups::{CloseReason, EventGroup};
+use crate::{
+    config::{
+        DataType, GenerateConfig, Output, TransformConfig, TransformContext, TransformDescription,
+    },
+    event::{discriminant::Discriminant, Event},
+    transforms::{TaskTransform, Transform},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`group_by` must name at least one field"))]
+    EmptyGroupBy,
+    #[snafu(display("`max_duration_ms` must be at least `inactivity_timeout_ms`"))]
+    MaxDurationTooShort,
+    #[snafu(display("`flush_period_ms` must be greater than 0"))]
+    ZeroFlushPeriod,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SessionizeConfig {
+    /// The fields that identify whose session an event belongs to, such as a user ID and IP.
+    pub group_by: Vec<String>,
+    /// How long a session may go without events before it closes.
+    #[serde(default = "default_inactivity_timeout_ms")]
+    pub inactivity_timeout_ms: u64,
+    /// How long a session may last, however active it is.
+    pub max_duration_ms: Option<u64>,
+    #[serde(default = "default_session_id_field")]
+    pub session_id_field: String,
+    /// Whether to emit a summary event for each session once it closes.
+    #[serde(default)]
+    pub emit_summaries: bool,
+    #[serde(default = "default_flush_period_ms")]
+    pub flush_period_ms: u64,
+}
+
+const fn default_inactivity_timeout_ms() -> u64 {
+    30 * 60 * 1000
+}
+
+fn default_session_id_field() -> String {
+    "session_id".to_owned()
+}
+
+const fn default_flush_period_ms() -> u64 {
+    1000
+}
+
+inventory::submit! {
+    TransformDescription::new::<SessionizeConfig>("sessionize")
+}
+
+impl GenerateConfig for SessionizeConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            group_by: vec!["user_id".to_owned()],
+            inactivity_timeout_ms: default_inactivity_timeout_ms(),
+            max_duration_ms: None,
+            session_id_field: default_session_id_field(),
+            emit_summaries: false,
+            flush_period_ms: default_flush_period_ms(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "sessionize")]
+impl TransformConfig for SessionizeConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Sessionize::new(self).map(Transform::task)
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "sessionize"
+    }
+}
+
+pub struct Sessionize {
+    group_by: Vec<String>,
+    inactivity_timeout: Duration,
+    max_duration: Option<Duration>,
+    session_id_field: String,
+    emit_summaries: bool,
+    flush_period: Duration,
+    sessions: HashMap<Discriminant, EventGroup>,
+}
+
+impl Sessionize {
+    pub fn new(config: &SessionizeConfig) -> crate::Result<Self> {
+        if config.group_by.is_empty() {
+            return Err(Box::new(BuildError::EmptyGroupBy));
+        }
+        if matches!(config.max_duration_ms, Some(max) if max < config.inactivity_timeout_ms) {
+            return Err(Box::new(BuildError::MaxDurationTooShort));
+        }
+        if config.flush_period_ms == 0 {
+            return Err(Box::new(BuildError::ZeroFlushPeriod));
+        }
+
+        Ok(Self {
+            group_by: config.group_by.clone(),
+            inactivity_timeout: Duration::from_millis(config.inactivity_timeout_ms),
+            max_duration: config.max_duration_ms.map(Duration::from_millis),
+            session_id_field: config.session_id_field.clone(),
+            emit_summaries: config.emit_summaries,
+            flush_period: Duration::from_millis(config.flush_period_ms),
+            sessions: HashMap::new(),
+        })
+    }
+
+    /// Why the session should be closed, if it should be.
+    fn close_reason(&self, session: &EventGroup, now: Instant) -> Option<CloseReason> {
+        if now.duration_since(session.last_seen) >= self.inactivity_timeout {
+            Some(CloseReason::Inactivity)
+        } else if matches!(self.max_duration, Some(max) if now.duration_since(session.started) >= max)
+        {
+            Some(CloseReason::MaxDuration)
+        } else {
+            None
+        }
+    }
+
+    fn close(&self, output: &mut Vec<Event>, session: EventGroup, reason: CloseReason) {
+        if self.emit_summaries {
+            let duration = session.duration();
+            let mut summary = session.summary("session", &self.session_id_field, reason);
+            summary.insert("duration_ms", duration.num_milliseconds());
+            output.push(summary.into());
+        }
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let now = Instant::now();
+        let closed = self
+            .sessions
+            .iter()
+            .filter_map(|(key, session)| Some((key.clone(), self.close_reason(session, now)?)))
+            .collect::<Vec<_>>();
+        for (key, reason) in closed {
+            if let Some(session) = self.sessions.remove(&key) {
+                self.close(output, session, reason);
+            }
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        for (_, session) in std::mem::take(&mut self.sessions) {
+            self.close(output, session, CloseReason::Shutdown);
+        }
+    }
+
+    fn transform_one(&mut self, output: &mut Vec<Event>, event: Event) {
+        let mut log = event.into_log();
+        let key = Discriminant::from_log_event(&log, &self.group_by);
+        let now = Instant::now();
+
+        // A session that has run its course is closed before the event starts a new one, so
+        // that its summary comes first.
+        if let Some(session) = self.sessions.get(&key) {
+            if let Some(reason) = self.close_reason(session, now) {
+                let session = self.sessions.remove(&key).expect("session exists");
+                self.close(output, session, reason);
+            }
+        }
+
+        let session = match self.sessions.entry(key) {
+            hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(EventGroup::new(&log, &self.group_by, now))
+            }
+        };
+        session.add(&log, now);
+        session.count += 1;
+        log.insert(self.session_id_field.as_str(), session.id.clone());
+        output.push(log.into());
+    }
+}
+
+impl TaskTransform for Sessionize {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let mut flush_stream = tokio::time::interval(me.flush_period);
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    _ = flush_stream.tick() => {
+                      me.flush_into(&mut output);
+                      false
+                    }
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.flush_all_into(&mut output);
+                          true
+                        }
+                        Some(event) => {
+                          me.transform_one(&mut output, event);
+                          false
+                        }
+                      }
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{LogEvent, Value};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SessionizeConfig>();
+    }
+
+    fn sessionize(config: &str) -> Sessionize {
+        Sessionize::new(&toml::from_str(config).unwrap()).unwrap()
+    }
+
+    fn event(user: &str, ip: &str) -> Event {
+        let mut log = LogEvent::from("click");
+        log.insert("user_id", user);
+        log.insert("ip", ip);
+        log.into()
+    }
+
+    fn session_id(event: &Event) -> Value {
+        event.as_log()["session_id"].clone()
+    }
+
+    #[tokio::test]
+    async fn sessions_by_key_and_inactivity() {
+        tokio::time::pause();
+        let mut sessionize = sessionize(
+            r#"
+                group_by = ["user_id", "ip"]
+                inactivity_timeout_ms = 1000
+                emit_summaries = true
+            "#,
+        );
+
+        let mut output = Vec::new();
+        sessionize.transform_one(&mut output, event("alice", "10.0.0.1"));
+        tokio::time::advance(Duration::from_millis(600)).await;
+        sessionize.transform_one(&mut output, event("alice", "10.0.0.1"));
+        sessionize.transform_one(&mut output, event("alice", "10.0.0.2"));
+        assert_eq!(output.len(), 3);
+        assert_eq!(session_id(&output[0]), session_id(&output[1]));
+        assert_ne!(session_id(&output[0]), session_id(&output[2]));
+
+        // The first session is still active, as it saw an event 600ms in.
+        tokio::time::advance(Duration::from_millis(600)).await;
+        sessionize.flush_into(&mut output);
+        assert_eq!(output.len(), 3);
+
+        tokio::time::advance(Duration::from_millis(600)).await;
+        sessionize.flush_into(&mut output);
+        assert_eq!(output.len(), 5);
+        let summaries = output[3..]
+            .iter()
+            .map(|event| event.as_log())
+            .collect::<Vec<_>>();
+        let first = summaries
+            .iter()
+            .find(|log| log["ip"] == "10.0.0.1".into())
+            .unwrap();
+        assert_eq!(first["session_id"], session_id(&output[0]));
+        assert_eq!(first["user_id"], "alice".into());
+        assert_eq!(first["event_count"], 2.into());
+        assert_eq!(first["close_reason"], "inactivity".into());
+        assert!(first.get("message").is_none());
+
+        sessionize.transform_one(&mut output, event("alice", "10.0.0.1"));
+        assert_ne!(session_id(&output[5]), session_id(&output[0]));
+    }
+
+    #[tokio::test]
+    async fn sessions_end_after_max_duration() {
+        tokio::time::pause();
+        let mut sessionize = sessionize(
+            r#"
+                group_by = ["user_id"]
+                inactivity_timeout_ms = 1000
+                max_duration_ms = 2000
+                emit_summaries = true
+            "#,
+        );
+
+        let mut output = Vec::new();
+        for _ in 0..4 {
+            sessionize.transform_one(&mut output, event("bob", "10.0.0.1"));
+            tokio::time::advance(Duration::from_millis(900)).await;
+        }
+        sessionize.transform_one(&mut output, event("bob", "10.0.0.1"));
+
+        // The session is closed by the event 2.7s in, which starts a new one.
+        assert_eq!(output.len(), 6);
+        assert_eq!(session_id(&output[0]), session_id(&output[2]));
+        let summary = output[3].as_log();
+        assert_eq!(summary["close_reason"], "max_duration".into());
+        assert_eq!(summary["event_count"], 3.into());
+        assert_ne!(session_id(&output[4]), session_id(&output[0]));
+        assert_eq!(session_id(&output[4]), session_id(&output[5]));
+    }
+
+    #[tokio::test]
+    async fn summaries_are_optional() {
+        tokio::time::pause();
+        let mut sessionize = sessionize(
+            r#"
+                group_by = ["user_id"]
+                inactivity_timeout_ms = 1000
+            "#,
+        );
+
+        let mut output = Vec::new();
+        sessionize.transform_one(&mut output, event("carol", "10.0.0.1"));
+        tokio::time::advance(Duration::from_millis(1500)).await;
+        sessionize.flush_into(&mut output);
+        sessionize.flush_all_into(&mut output);
+        assert_eq!(output.len(), 1);
+        assert!(sessionize.sessions.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let config = |config: &str| Sessionize::new(&toml::from_str(config).unwrap());
+        assert!(config("group_by = []").is_err());
+        assert!(config(
+            r#"
+                group_by = ["user_id"]
+                inactivity_timeout_ms = 1000
+                max_duration_ms = 500
+            "#
+        )
+        .is_err());
+        assert!(config(
+            r#"
+                group_by = ["user_id"]
+                flush_period_ms = 0
+            "#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,403 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{
+        metric::{Metric, MetricKind, MetricValue, StatisticKind},
+        Event, LogEvent, Value,
+    },
+    internal_events::SpanToMetricsInvalidDuration,
+    transforms::{FunctionTransform, Transform},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`duration.buckets` must be a non-empty list of increasing bounds"))]
+    InvalidBuckets,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct SpanToMetricsConfig {
+    #[serde(default = "default_namespace")]
+    pub namespace: Option<String>,
+    #[serde(default = "default_service_field")]
+    pub service_field: String,
+    #[serde(default = "default_operation_field")]
+    pub operation_field: String,
+    /// The field marking failed spans, which are any that have it set to something other than
+    /// `false`, an empty string, `ok` or `unset`.
+    #[serde(default = "default_error_field")]
+    pub error_field: String,
+    /// The field holding the duration of spans, in nanoseconds.
+    #[serde(default = "default_duration_field")]
+    pub duration_field: String,
+    #[serde(default)]
+    pub duration: DurationConfig,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum DurationConfig {
+    Histogram {
+        /// The upper bounds of the buckets, in seconds.
+        #[serde(default = "default_buckets")]
+        buckets: Vec<f64>,
+    },
+    Sketch,
+}
+
+impl Default for DurationConfig {
+    fn default() -> Self {
+        Self::Histogram {
+            buckets: default_buckets(),
+        }
+    }
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn default_namespace() -> Option<String> {
+    Some("span".to_owned())
+}
+
+fn default_service_field() -> String {
+    "service".to_owned()
+}
+
+fn default_operation_field() -> String {
+    log_schema().message_key().to_owned()
+}
+
+fn default_error_field() -> String {
+    "tags.error".to_owned()
+}
+
+fn default_duration_field() -> String {
+    "duration_ns".to_owned()
+}
+
+/// The default buckets of the Prometheus client libraries.
+fn default_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
+inventory::submit! {
+    TransformDescription::new::<SpanToMetricsConfig>("span_to_metrics")
+}
+
+impl GenerateConfig for SpanToMetricsConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            namespace: default_namespace(),
+            service_field: default_service_field(),
+            operation_field: default_operation_field(),
+            error_field: default_error_field(),
+            duration_field: default_duration_field(),
+            duration: DurationConfig::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "span_to_metrics")]
+impl TransformConfig for SpanToMetricsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if let DurationConfig::Histogram { buckets } = &self.duration {
+            if buckets.is_empty() || buckets.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err(BuildError::InvalidBuckets.into());
+            }
+        }
+        Ok(Transform::function(SpanToMetrics {
+            config: self.clone(),
+        }))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "span_to_metrics"
+    }
+}
+
+/// Derives request, error and duration (RED) metrics from spans, such as the ones output by the
+/// `zipkin`, `jaeger` and `internal_traces` sources. Each span is turned into incremental metrics,
+/// so they can be aggregated by the sinks or an `aggregate` transform.
+#[derive(Clone, Debug)]
+pub struct SpanToMetrics {
+    config: SpanToMetricsConfig,
+}
+
+impl SpanToMetrics {
+    fn metric(
+        &self,
+        name: &str,
+        value: MetricValue,
+        log: &LogEvent,
+        tags: &BTreeMap<String, String>,
+    ) -> Metric {
+        let timestamp = log
+            .get(log_schema().timestamp_key())
+            .and_then(Value::as_timestamp)
+            .cloned();
+        Metric::new_with_metadata(name, MetricKind::Incremental, value, log.metadata().clone())
+            .with_namespace(self.config.namespace.clone())
+            .with_tags(Some(tags.clone()))
+            .with_timestamp(timestamp)
+    }
+
+    fn duration(&self, log: &LogEvent) -> Option<MetricValue> {
+        let field = &self.config.duration_field;
+        let nanos = match log.get(field.as_str())? {
+            Value::Integer(nanos) if *nanos >= 0 => *nanos as f64,
+            Value::Float(nanos) if *nanos >= 0.0 => *nanos,
+            _ => {
+                emit!(&SpanToMetricsInvalidDuration { field });
+                return None;
+            }
+        };
+
+        let distribution = MetricValue::Distribution {
+            samples: vector_core::samples![nanos / 1e9 => 1],
+            statistic: StatisticKind::Histogram,
+        };
+        match &self.config.duration {
+            DurationConfig::Histogram { buckets } => {
+                distribution.distribution_to_agg_histogram(buckets)
+            }
+            DurationConfig::Sketch => distribution.distribution_to_sketch(),
+        }
+    }
+}
+
+impl FunctionTransform for SpanToMetrics {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        let log = event.as_log();
+
+        let error = log
+            .get(self.config.error_field.as_str())
+            .map_or(false, is_error);
+        let mut tags = BTreeMap::new();
+        if let Some(service) = log.get(self.config.service_field.as_str()) {
+            tags.insert("service".to_owned(), service.to_string_lossy());
+        }
+        if let Some(operation) = log.get(self.config.operation_field.as_str()) {
+            tags.insert("operation".to_owned(), operation.to_string_lossy());
+        }
+        tags.insert(
+            "status".to_owned(),
+            if error { "error" } else { "ok" }.to_owned(),
+        );
+
+        output.push(Event::Metric(self.metric(
+            "requests_total",
+            MetricValue::Counter { value: 1.0 },
+            log,
+            &tags,
+        )));
+        if error {
+            output.push(Event::Metric(self.metric(
+                "errors_total",
+                MetricValue::Counter { value: 1.0 },
+                log,
+                &tags,
+            )));
+        }
+        if let Some(duration) = self.duration(log) {
+            output.push(Event::Metric(self.metric(
+                "duration_seconds",
+                duration,
+                log,
+                &tags,
+            )));
+        }
+    }
+}
+
+/// Tracers mark failed spans differently: Jaeger sets an `error` tag to `true`, Zipkin sets an
+/// `error` tag to the error message, and OpenTelemetry sets `otel.status_code` to `ERROR`.
+fn is_error(value: &Value) -> bool {
+    match value {
+        Value::Boolean(error) => *error,
+        Value::Null => false,
+        value => !matches!(
+            value.to_string_lossy().to_lowercase().as_str(),
+            "" | "false" | "ok" | "unset"
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_core::metrics::AgentDDSketch;
+
+    use super::*;
+    use crate::event::metric::{Bucket, MetricSketch};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SpanToMetricsConfig>();
+    }
+
+    fn transform(config: &str, log: LogEvent) -> Vec<Metric> {
+        let config = toml::from_str::<SpanToMetricsConfig>(config).unwrap();
+        let mut transform = SpanToMetrics { config };
+        let mut output = Vec::new();
+        transform.transform(&mut output, log.into());
+        output.into_iter().map(Event::into_metric).collect()
+    }
+
+    fn span(error: Option<Value>) -> LogEvent {
+        let mut log = LogEvent::from("get /api");
+        log.insert("timestamp", Utc.timestamp(1_600_000_000, 0));
+        log.insert("service", "backend");
+        log.insert("duration_ns", 30_000_000);
+        if let Some(error) = error {
+            log.insert("tags.error", error);
+        }
+        log
+    }
+
+    fn tags(status: &str) -> BTreeMap<String, String> {
+        vec![
+            ("service".to_owned(), "backend".to_owned()),
+            ("operation".to_owned(), "get /api".to_owned()),
+            ("status".to_owned(), status.to_owned()),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn counts_requests() {
+        let metrics = transform("", span(None));
+        assert_eq!(metrics.len(), 2);
+
+        assert_eq!(metrics[0].name(), "requests_total");
+        assert_eq!(metrics[0].namespace(), Some("span"));
+        assert_eq!(metrics[0].tags(), Some(&tags("ok")));
+        assert_eq!(metrics[0].value(), &MetricValue::Counter { value: 1.0 });
+        assert_eq!(
+            metrics[0].timestamp(),
+            Some(Utc.timestamp(1_600_000_000, 0))
+        );
+
+        assert_eq!(metrics[1].name(), "duration_seconds");
+        assert_eq!(
+            metrics[1].value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+                    .into_iter()
+                    .map(|upper_limit| Bucket {
+                        upper_limit,
+                        count: if upper_limit >= 0.03 { 1 } else { 0 },
+                    })
+                    .collect(),
+                count: 1,
+                sum: 0.03,
+            }
+        );
+    }
+
+    #[test]
+    fn counts_errors() {
+        for error in [Value::from(true), Value::from("connection refused")] {
+            let metrics = transform("", span(Some(error)));
+            assert_eq!(metrics.len(), 3);
+            assert_eq!(metrics[0].tags(), Some(&tags("error")));
+            assert_eq!(metrics[1].name(), "errors_total");
+            assert_eq!(metrics[1].tags(), Some(&tags("error")));
+        }
+
+        for ok in [Value::from(false), Value::from("OK"), Value::from("")] {
+            let metrics = transform("", span(Some(ok)));
+            assert_eq!(metrics.len(), 2);
+            assert_eq!(metrics[0].tags(), Some(&tags("ok")));
+        }
+    }
+
+    #[test]
+    fn custom_buckets() {
+        let metrics = transform(
+            r#"duration = { type = "histogram", buckets = [0.01, 0.1] }"#,
+            span(None),
+        );
+        assert_eq!(
+            metrics[1].value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 0.01,
+                        count: 0
+                    },
+                    Bucket {
+                        upper_limit: 0.1,
+                        count: 1
+                    },
+                ],
+                count: 1,
+                sum: 0.03,
+            }
+        );
+    }
+
+    #[test]
+    fn sketches() {
+        let metrics = transform(r#"duration.type = "sketch""#, span(None));
+        let mut sketch = AgentDDSketch::with_agent_defaults();
+        sketch.insert(0.03);
+        assert_eq!(
+            metrics[1].value(),
+            &MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(sketch)
+            }
+        );
+    }
+
+    #[test]
+    fn skips_missing_and_invalid_durations() {
+        let mut log = span(None);
+        log.remove("duration_ns");
+        assert_eq!(transform("", log).len(), 1);
+
+        let mut log = span(None);
+        log.insert("duration_ns", "slow");
+        assert_eq!(transform("", log).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_unordered_buckets() {
+        let config = toml::from_str::<SpanToMetricsConfig>(
+            r#"duration = { type = "histogram", buckets = [1.0, 0.5] }"#,
+        )
+        .unwrap();
+        let error = config
+            .build(&TransformContext::default())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "`duration.buckets` must be a non-empty list of increasing bounds"
+        );
+    }
+}
@@ -0,0 +1,492 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::time::Instant;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Output, TransformConfig, TransformContext, TransformDescription,
+    },
+    event::{Event, LogEvent, Value},
+    internal_events::{TailSamplingTraceDropped, TailSamplingTracesEvicted},
+    transforms::{TaskTransform, Transform},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one policy must be configured"))]
+    NoPolicies,
+    #[snafu(display("The `rate` of a `probabilistic` policy must be between 0 and 1"))]
+    InvalidRate,
+    #[snafu(display("`flush_period_ms` must be greater than 0"))]
+    ZeroFlushPeriod,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TailSamplingConfig {
+    #[serde(default = "default_trace_id_field")]
+    pub trace_id_field: String,
+    /// How long to wait for the spans of a trace after its first one arrives, before deciding
+    /// whether to keep it.
+    #[serde(default = "default_decision_wait_ms")]
+    pub decision_wait_ms: u64,
+    /// How many traces to hold at once. When more arrive, the oldest trace is decided early.
+    #[serde(default = "default_max_traces")]
+    pub max_traces: usize,
+    #[serde(default = "default_flush_period_ms")]
+    pub flush_period_ms: u64,
+    /// A trace is kept if any of the policies match it.
+    pub policies: Vec<PolicyConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum PolicyConfig {
+    /// Keeps traces with a failed span.
+    Error {
+        #[serde(default = "default_error_field")]
+        field: String,
+    },
+    /// Keeps traces with a span that took at least `threshold_ms`.
+    Latency {
+        threshold_ms: u64,
+        #[serde(default = "default_duration_field")]
+        duration_field: String,
+    },
+    /// Keeps a share of traces, picked by their IDs so separate instances keep the same ones.
+    Probabilistic { rate: f64 },
+}
+
+fn default_trace_id_field() -> String {
+    "trace_id".to_owned()
+}
+
+const fn default_decision_wait_ms() -> u64 {
+    10_000
+}
+
+const fn default_max_traces() -> usize {
+    50_000
+}
+
+const fn default_flush_period_ms() -> u64 {
+    1000
+}
+
+fn default_error_field() -> String {
+    "tags.error".to_owned()
+}
+
+fn default_duration_field() -> String {
+    "duration_ns".to_owned()
+}
+
+inventory::submit! {
+    TransformDescription::new::<TailSamplingConfig>("tail_sampling")
+}
+
+impl GenerateConfig for TailSamplingConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            trace_id_field: default_trace_id_field(),
+            decision_wait_ms: default_decision_wait_ms(),
+            max_traces: default_max_traces(),
+            flush_period_ms: default_flush_period_ms(),
+            policies: vec![
+                PolicyConfig::Error {
+                    field: default_error_field(),
+                },
+                PolicyConfig::Probabilistic { rate: 0.1 },
+            ],
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "tail_sampling")]
+impl TransformConfig for TailSamplingConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TailSampling::new(self).map(Transform::task)
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "tail_sampling"
+    }
+}
+
+impl PolicyConfig {
+    fn matches(&self, trace_id: &str, spans: &[Event]) -> bool {
+        match self {
+            Self::Error { field } => spans
+                .iter()
+                .any(|span| span.as_log().get(field.as_str()).map_or(false, is_error)),
+            Self::Latency {
+                threshold_ms,
+                duration_field,
+            } => {
+                let threshold = *threshold_ms as f64 * 1e6;
+                spans
+                    .iter()
+                    .any(|span| match span.as_log().get(duration_field.as_str()) {
+                        Some(Value::Integer(nanos)) => *nanos as f64 >= threshold,
+                        Some(Value::Float(nanos)) => *nanos >= threshold,
+                        _ => false,
+                    })
+            }
+            Self::Probabilistic { rate } => {
+                (seahash::hash(trace_id.as_bytes()) as f64) < rate * u64::MAX as f64
+            }
+        }
+    }
+}
+
+/// Tracers mark failed spans differently: Jaeger sets an `error` tag to `true`, Zipkin sets an
+/// `error` tag to the error message, and OpenTelemetry sets `otel.status_code` to `ERROR`.
+fn is_error(value: &Value) -> bool {
+    match value {
+        Value::Boolean(error) => *error,
+        Value::Null => false,
+        value => !matches!(
+            value.to_string_lossy().to_lowercase().as_str(),
+            "" | "false" | "ok" | "unset"
+        ),
+    }
+}
+
+#[derive(Debug)]
+struct Trace {
+    spans: Vec<Event>,
+    first_seen: Instant,
+}
+
+pub struct TailSampling {
+    trace_id_field: String,
+    decision_wait: Duration,
+    max_traces: usize,
+    flush_period: Duration,
+    policies: Vec<PolicyConfig>,
+    traces: HashMap<String, Trace>,
+    /// The IDs of the pending traces, in the order they arrived.
+    pending: VecDeque<String>,
+    /// Decisions are remembered for another `decision_wait`, so that spans arriving after their
+    /// trace has been decided are kept or dropped with the rest of it.
+    decisions: HashMap<String, bool>,
+    decided: VecDeque<(String, Instant)>,
+}
+
+impl TailSampling {
+    pub fn new(config: &TailSamplingConfig) -> crate::Result<Self> {
+        if config.policies.is_empty() {
+            return Err(Box::new(BuildError::NoPolicies));
+        }
+        if config.policies.iter().any(
+            |policy| matches!(policy, PolicyConfig::Probabilistic { rate } if !(0.0..=1.0).contains(rate)),
+        ) {
+            return Err(Box::new(BuildError::InvalidRate));
+        }
+        if config.flush_period_ms == 0 {
+            return Err(Box::new(BuildError::ZeroFlushPeriod));
+        }
+
+        Ok(Self {
+            trace_id_field: config.trace_id_field.clone(),
+            decision_wait: Duration::from_millis(config.decision_wait_ms),
+            max_traces: config.max_traces.max(1),
+            flush_period: Duration::from_millis(config.flush_period_ms),
+            policies: config.policies.clone(),
+            traces: HashMap::new(),
+            pending: VecDeque::new(),
+            decisions: HashMap::new(),
+            decided: VecDeque::new(),
+        })
+    }
+
+    fn trace_id(&self, log: &LogEvent) -> Option<String> {
+        log.get(self.trace_id_field.as_str())
+            .map(|trace_id| trace_id.to_string_lossy())
+    }
+
+    fn decide(&mut self, output: &mut Vec<Event>, trace_id: String, now: Instant) {
+        let trace = match self.traces.remove(&trace_id) {
+            Some(trace) => trace,
+            None => return,
+        };
+
+        let keep = self
+            .policies
+            .iter()
+            .any(|policy| policy.matches(&trace_id, &trace.spans));
+        if keep {
+            output.extend(trace.spans);
+        } else {
+            emit!(&TailSamplingTraceDropped {
+                span_count: trace.spans.len()
+            });
+        }
+
+        self.decisions.insert(trace_id.clone(), keep);
+        self.decided.push_back((trace_id, now));
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let now = Instant::now();
+
+        while let Some((_, decided)) = self.decided.front() {
+            if now.duration_since(*decided) < self.decision_wait {
+                break;
+            }
+            let (trace_id, _) = self.decided.pop_front().expect("decision exists");
+            self.decisions.remove(&trace_id);
+        }
+
+        while let Some(trace_id) = self.pending.front() {
+            match self.traces.get(trace_id) {
+                Some(trace) if now.duration_since(trace.first_seen) < self.decision_wait => break,
+                _ => {
+                    let trace_id = self.pending.pop_front().expect("trace exists");
+                    self.decide(output, trace_id, now);
+                }
+            }
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        let now = Instant::now();
+        while let Some(trace_id) = self.pending.pop_front() {
+            self.decide(output, trace_id, now);
+        }
+    }
+
+    fn transform_one(&mut self, output: &mut Vec<Event>, event: Event) {
+        // Events that aren't spans of a trace can't be sampled with it.
+        let trace_id = match self.trace_id(event.as_log()) {
+            Some(trace_id) => trace_id,
+            None => {
+                output.push(event);
+                return;
+            }
+        };
+
+        if let Some(keep) = self.decisions.get(&trace_id) {
+            if *keep {
+                output.push(event);
+            } else {
+                emit!(&TailSamplingTraceDropped { span_count: 1 });
+            }
+            return;
+        }
+
+        let now = Instant::now();
+        if let Some(trace) = self.traces.get_mut(&trace_id) {
+            trace.spans.push(event);
+            return;
+        }
+
+        if self.traces.len() >= self.max_traces {
+            if let Some(oldest) = self.pending.pop_front() {
+                emit!(&TailSamplingTracesEvicted {
+                    max_traces: self.max_traces
+                });
+                self.decide(output, oldest, now);
+            }
+        }
+        self.traces.insert(
+            trace_id.clone(),
+            Trace {
+                spans: vec![event],
+                first_seen: now,
+            },
+        );
+        self.pending.push_back(trace_id);
+    }
+}
+
+impl TaskTransform for TailSampling {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let mut flush_stream = tokio::time::interval(me.flush_period);
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    _ = flush_stream.tick() => {
+                      me.flush_into(&mut output);
+                      false
+                    }
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.flush_all_into(&mut output);
+                          true
+                        }
+                        Some(event) => {
+                          me.transform_one(&mut output, event);
+                          false
+                        }
+                      }
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TailSamplingConfig>();
+    }
+
+    fn tail_sampling(config: &str) -> TailSampling {
+        TailSampling::new(&toml::from_str(config).unwrap()).unwrap()
+    }
+
+    fn span(trace_id: &str, duration_ns: i64, error: bool) -> Event {
+        let mut log = LogEvent::from("get /api");
+        log.insert("trace_id", trace_id);
+        log.insert("duration_ns", duration_ns);
+        if error {
+            log.insert("tags.error", true);
+        }
+        log.into()
+    }
+
+    fn trace_ids(output: &[Event]) -> Vec<Value> {
+        output
+            .iter()
+            .map(|event| event.as_log()["trace_id"].clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn keeps_whole_traces_matching_policies() {
+        tokio::time::pause();
+        let mut sampling = tail_sampling(
+            r#"
+                decision_wait_ms = 1000
+                policies = [
+                    { type = "error" },
+                    { type = "latency", threshold_ms = 500 },
+                ]
+            "#,
+        );
+
+        let mut output = Vec::new();
+        sampling.transform_one(&mut output, span("a", 1_000_000, false));
+        sampling.transform_one(&mut output, span("b", 1_000_000, false));
+        sampling.transform_one(&mut output, span("c", 600_000_000, false));
+        sampling.transform_one(&mut output, span("a", 1_000_000, true));
+        sampling.transform_one(&mut output, span("b", 2_000_000, false));
+        assert!(output.is_empty());
+
+        tokio::time::advance(Duration::from_millis(1100)).await;
+        sampling.flush_into(&mut output);
+        assert_eq!(trace_ids(&output), vec!["a".into(), "a".into(), "c".into()]);
+        assert!(sampling.traces.is_empty());
+
+        // Late spans follow the decision made for their trace.
+        output.clear();
+        sampling.transform_one(&mut output, span("a", 1_000_000, false));
+        sampling.transform_one(&mut output, span("b", 1_000_000, false));
+        assert_eq!(trace_ids(&output), vec!["a".into()]);
+
+        // Until the decision is forgotten.
+        tokio::time::advance(Duration::from_millis(1100)).await;
+        sampling.flush_into(&mut output);
+        assert!(sampling.decisions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn passes_events_without_trace_ids() {
+        let mut sampling = tail_sampling(r#"policies = [{ type = "probabilistic", rate = 0.0 }]"#);
+
+        let mut output = Vec::new();
+        sampling.transform_one(&mut output, LogEvent::from("not a span").into());
+        assert_eq!(output.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn samples_traces_by_id() {
+        let mut sampling = tail_sampling(r#"policies = [{ type = "probabilistic", rate = 0.25 }]"#);
+
+        let mut output = Vec::new();
+        for i in 0..1000 {
+            sampling.transform_one(&mut output, span(&format!("{:032x}", i), 0, false));
+        }
+        sampling.flush_all_into(&mut output);
+        assert!((200..300).contains(&output.len()), "{}", output.len());
+
+        let mut sampling = tail_sampling(r#"policies = [{ type = "probabilistic", rate = 1.0 }]"#);
+        let mut all = Vec::new();
+        for i in 0..10 {
+            sampling.transform_one(&mut all, span(&format!("{:032x}", i), 0, false));
+        }
+        sampling.flush_all_into(&mut all);
+        assert_eq!(all.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn decides_oldest_trace_when_full() {
+        tokio::time::pause();
+        let mut sampling = tail_sampling(
+            r#"
+                max_traces = 2
+                policies = [{ type = "error" }]
+            "#,
+        );
+
+        let mut output = Vec::new();
+        sampling.transform_one(&mut output, span("a", 0, true));
+        sampling.transform_one(&mut output, span("b", 0, false));
+        sampling.transform_one(&mut output, span("c", 0, false));
+        assert_eq!(trace_ids(&output), vec!["a".into()]);
+        assert_eq!(sampling.traces.len(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        let config = |config: &str| TailSampling::new(&toml::from_str(config).unwrap());
+        assert!(config("policies = []").is_err());
+        assert!(config(r#"policies = [{ type = "probabilistic", rate = 1.5 }]"#).is_err());
+        assert!(config(
+            r#"
+                flush_period_ms = 0
+                policies = [{ type = "probabilistic", rate = 0.5 }]
+            "#
+        )
+        .is_err());
+    }
+}
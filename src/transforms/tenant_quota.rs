@@ -0,0 +1,562 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use tokio::time::Instant;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Output, TransformConfig, TransformContext, TransformDescription,
+    },
+    event::Event,
+    internal_events::{
+        TemplateRenderingFailed, TenantQuotaEventProcessed, TenantQuotaExceeded,
+        TenantQuotaTagDropped,
+    },
+    template::Template,
+    transforms::{FunctionTransform, Transform},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`window_secs` must be a finite number greater than zero"))]
+    InvalidWindow,
+    #[snafu(display("`max_tenants` must be greater than zero"))]
+    ZeroMaxTenants,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TenantQuotaConfig {
+    /// Renders the tenant an event belongs to, such as `{{ %source.tenant }}`.
+    pub tenant_key: Template,
+    /// The length of the window that the event and byte quotas apply to.
+    #[serde(default = "default_window_secs")]
+    pub window_secs: f64,
+    /// The quotas of every tenant without quotas of its own.
+    #[serde(default)]
+    pub limits: QuotaLimits,
+    /// Quotas for specific tenants. Any quota left unset falls back to `limits`.
+    #[serde(default)]
+    pub tenants: IndexMap<String, QuotaLimits>,
+    #[serde(default)]
+    pub tag_limit_exceeded_action: TagLimitExceededAction,
+    /// The most tenants whose usage is tracked at once. The tenant seen least recently is
+    /// forgotten to make room for a new one.
+    #[serde(default = "default_max_tenants")]
+    pub max_tenants: usize,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct QuotaLimits {
+    /// The most events a tenant may send in each window.
+    pub events_per_window: Option<u64>,
+    /// The most bytes a tenant may send in each window, as estimated for buffering.
+    pub bytes_per_window: Option<u64>,
+    /// The most distinct values each metric tag may have for a tenant.
+    pub tag_value_limit: Option<usize>,
+    /// The most distinct metric tag keys a tenant may use, when `tag_value_limit` is set.
+    pub tag_key_limit: Option<usize>,
+}
+
+impl QuotaLimits {
+    fn or(&self, defaults: &Self) -> Self {
+        Self {
+            events_per_window: self.events_per_window.or(defaults.events_per_window),
+            bytes_per_window: self.bytes_per_window.or(defaults.bytes_per_window),
+            tag_value_limit: self.tag_value_limit.or(defaults.tag_value_limit),
+            tag_key_limit: self.tag_key_limit.or(defaults.tag_key_limit),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TagLimitExceededAction {
+    DropTag,
+    DropEvent,
+}
+
+impl Default for TagLimitExceededAction {
+    fn default() -> Self {
+        Self::DropTag
+    }
+}
+
+const fn default_window_secs() -> f64 {
+    1.0
+}
+
+const fn default_max_tenants() -> usize {
+    10_000
+}
+
+/// The tag values of each key are remembered, so the number of keys is bounded too.
+const DEFAULT_TAG_KEY_LIMIT: usize = 100;
+
+inventory::submit! {
+    TransformDescription::new::<TenantQuotaConfig>("tenant_quota")
+}
+
+impl GenerateConfig for TenantQuotaConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            tenant_key: Template::try_from("{{ %source.tenant }}").unwrap(),
+            window_secs: default_window_secs(),
+            limits: QuotaLimits {
+                events_per_window: Some(1000),
+                bytes_per_window: None,
+                tag_value_limit: None,
+                tag_key_limit: None,
+            },
+            tenants: IndexMap::new(),
+            tag_limit_exceeded_action: TagLimitExceededAction::default(),
+            max_tenants: default_max_tenants(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "tenant_quota")]
+impl TransformConfig for TenantQuotaConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TenantQuota::new(self).map(Transform::function)
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Any)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "tenant_quota"
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Quota {
+    Events,
+    Bytes,
+    TagValues,
+}
+
+impl Quota {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Events => "events",
+            Self::Bytes => "bytes",
+            Self::TagValues => "tag_values",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct TenantUsage {
+    window_start: Instant,
+    last_seen: Instant,
+    events: u64,
+    bytes: u64,
+    /// The accepted values of each metric tag, which unlike the other usage is never reset.
+    tag_values: HashMap<String, HashSet<String>>,
+}
+
+impl TenantUsage {
+    fn new(now: Instant) -> Self {
+        Self {
+            window_start: now,
+            last_seen: now,
+            events: 0,
+            bytes: 0,
+            tag_values: HashMap::new(),
+        }
+    }
+
+    fn roll_window(&mut self, now: Instant, window: Duration) {
+        if now.duration_since(self.window_start) >= window {
+            self.window_start = now;
+            self.events = 0;
+            self.bytes = 0;
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TenantQuota {
+    tenant_key: Template,
+    window: Duration,
+    limits: QuotaLimits,
+    tenants: HashMap<String, QuotaLimits>,
+    tag_limit_exceeded_action: TagLimitExceededAction,
+    max_tenants: usize,
+    usage: HashMap<String, TenantUsage>,
+    last_sweep: Instant,
+}
+
+impl TenantQuota {
+    pub fn new(config: &TenantQuotaConfig) -> crate::Result<Self> {
+        // Also rejects NaN, and windows too long for a `Duration`.
+        if !(config.window_secs > 0.0 && config.window_secs < Duration::MAX.as_secs_f64()) {
+            return Err(Box::new(BuildError::InvalidWindow));
+        }
+        if config.max_tenants == 0 {
+            return Err(Box::new(BuildError::ZeroMaxTenants));
+        }
+
+        Ok(Self {
+            tenant_key: config.tenant_key.clone(),
+            window: Duration::from_secs_f64(config.window_secs),
+            limits: config.limits.clone(),
+            tenants: config
+                .tenants
+                .iter()
+                .map(|(tenant, limits)| (tenant.clone(), limits.or(&config.limits)))
+                .collect(),
+            tag_limit_exceeded_action: config.tag_limit_exceeded_action,
+            max_tenants: config.max_tenants,
+            usage: HashMap::new(),
+            last_sweep: Instant::now(),
+        })
+    }
+
+    fn tenant(&self, event: &Event) -> String {
+        self.tenant_key
+            .render_string(event)
+            .unwrap_or_else(|error| {
+                emit!(&TemplateRenderingFailed {
+                    error,
+                    field: Some("tenant_key"),
+                    drop_event: false,
+                });
+                "none".to_owned()
+            })
+    }
+
+    /// Forgets tenants that haven't sent anything for a whole window and have no tag values to
+    /// remember. Those with tag values are only forgotten to make room for new tenants.
+    fn sweep(&mut self, now: Instant) {
+        if now.duration_since(self.last_sweep) < self.window {
+            return;
+        }
+        self.last_sweep = now;
+        let window = self.window;
+        self.usage.retain(|_, usage| {
+            now.duration_since(usage.window_start) < window || !usage.tag_values.is_empty()
+        });
+    }
+
+    /// Forgets the tenant seen least recently if there's no room for another.
+    fn make_room(&mut self) {
+        if self.usage.len() < self.max_tenants {
+            return;
+        }
+        let oldest = self
+            .usage
+            .iter()
+            .min_by_key(|(_, usage)| usage.last_seen)
+            .map(|(tenant, _)| tenant.clone());
+        if let Some(tenant) = oldest {
+            self.usage.remove(&tenant);
+        }
+    }
+
+    /// Checks the metric's tags against the tenant's tag value and key limits, returning whether
+    /// the event may be kept.
+    fn check_tags(
+        event: &mut Event,
+        tenant: &str,
+        value_limit: usize,
+        key_limit: usize,
+        action: TagLimitExceededAction,
+        tag_values: &mut HashMap<String, HashSet<String>>,
+    ) -> bool {
+        let metric = match event {
+            Event::Metric(metric) => metric,
+            Event::Log(_) => return true,
+        };
+        let tags = match metric.tags() {
+            Some(tags) => tags,
+            None => return true,
+        };
+
+        let mut new_keys = 0;
+        let rejected = tags
+            .iter()
+            .filter(|(key, value)| match tag_values.get(key.as_str()) {
+                Some(values) => !values.contains(value.as_str()) && values.len() >= value_limit,
+                None => {
+                    let accepted = value_limit > 0 && tag_values.len() + new_keys < key_limit;
+                    new_keys += usize::from(accepted);
+                    !accepted
+                }
+            })
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        if !rejected.is_empty() && action == TagLimitExceededAction::DropEvent {
+            return false;
+        }
+
+        let accepted = tags
+            .iter()
+            .filter(|(key, _)| !rejected.contains(key))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<Vec<_>>();
+        for (key, value) in accepted {
+            tag_values.entry(key).or_default().insert(value);
+        }
+        for key in rejected {
+            emit!(&TenantQuotaTagDropped {
+                tenant,
+                tag_key: &key,
+            });
+            metric.remove_tag(&key);
+        }
+        true
+    }
+}
+
+impl FunctionTransform for TenantQuota {
+    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+        let now = Instant::now();
+        self.sweep(now);
+
+        let tenant = self.tenant(&event);
+        if !self.usage.contains_key(&tenant) {
+            self.make_room();
+        }
+        let limits = self.tenants.get(&tenant).unwrap_or(&self.limits);
+        let usage = self
+            .usage
+            .entry(tenant.clone())
+            .or_insert_with(|| TenantUsage::new(now));
+        usage.last_seen = now;
+        usage.roll_window(now, self.window);
+
+        let byte_size = event.size_of() as u64;
+        let exceeded = if matches!(limits.events_per_window, Some(limit) if usage.events >= limit) {
+            Some(Quota::Events)
+        } else if matches!(limits.bytes_per_window, Some(limit) if usage.bytes + byte_size > limit)
+        {
+            Some(Quota::Bytes)
+        } else {
+            match limits.tag_value_limit {
+                Some(limit) => (!Self::check_tags(
+                    &mut event,
+                    &tenant,
+                    limit,
+                    limits.tag_key_limit.unwrap_or(DEFAULT_TAG_KEY_LIMIT),
+                    self.tag_limit_exceeded_action,
+                    &mut usage.tag_values,
+                ))
+                .then(|| Quota::TagValues),
+                None => None,
+            }
+        };
+
+        if let Some(quota) = exceeded {
+            emit!(&TenantQuotaExceeded {
+                tenant: &tenant,
+                quota: quota.as_str(),
+            });
+            return;
+        }
+
+        usage.events += 1;
+        usage.bytes += byte_size;
+        emit!(&TenantQuotaEventProcessed {
+            tenant: &tenant,
+            byte_size: byte_size as usize,
+        });
+        output.push(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::event::{LogEvent, Metric, MetricKind, MetricValue};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TenantQuotaConfig>();
+    }
+
+    fn quota(config: &str) -> TenantQuota {
+        TenantQuota::new(&toml::from_str(config).unwrap()).unwrap()
+    }
+
+    fn log(tenant: &str) -> Event {
+        let mut log = LogEvent::from("hello");
+        log.metadata_mut().set_source_field("tenant", tenant);
+        log.into()
+    }
+
+    fn metric(tenant: &str, host: &str) -> Event {
+        let mut metric = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        )
+        .with_tags(Some(
+            vec![("host".to_owned(), host.to_owned())]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>(),
+        ));
+        metric.metadata_mut().set_source_field("tenant", tenant);
+        metric.into()
+    }
+
+    fn run(quota: &mut TenantQuota, events: Vec<Event>) -> Vec<Event> {
+        let mut output = Vec::new();
+        for event in events {
+            quota.transform(&mut output, event);
+        }
+        output
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_events_per_tenant_and_window() {
+        let mut quota = quota(
+            r#"
+            tenant_key = "{{ %source.tenant }}"
+            window_secs = 10
+            limits.events_per_window = 2
+
+            [tenants.big]
+            events_per_window = 3
+            "#,
+        );
+
+        let output = run(
+            &mut quota,
+            vec![
+                log("a"),
+                log("a"),
+                log("a"),
+                log("big"),
+                log("big"),
+                log("big"),
+                log("big"),
+            ],
+        );
+        assert_eq!(output.len(), 5);
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        let output = run(&mut quota, vec![log("a"), log("a"), log("a")]);
+        assert_eq!(output.len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_bytes() {
+        let byte_size = log("a").size_of() as u64;
+        let mut quota = quota(&format!(
+            r#"
+            tenant_key = "{{{{ %source.tenant }}}}"
+            limits.bytes_per_window = {}
+            "#,
+            byte_size * 2 + 1
+        ));
+
+        let output = run(&mut quota, vec![log("a"), log("a"), log("a"), log("b")]);
+        assert_eq!(output.len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_tag_values() {
+        let mut quota = quota(
+            r#"
+            tenant_key = "{{ %source.tenant }}"
+            limits.tag_value_limit = 2
+            "#,
+        );
+
+        let output = run(
+            &mut quota,
+            vec![
+                metric("a", "one"),
+                metric("a", "two"),
+                metric("a", "three"),
+                metric("a", "one"),
+                metric("b", "three"),
+            ],
+        );
+        assert_eq!(output.len(), 5);
+        assert!(output[2].as_metric().tags().unwrap().get("host").is_none());
+        assert_eq!(
+            output[4].as_metric().tags().unwrap().get("host").unwrap(),
+            "three"
+        );
+
+        let mut quota = super::TenantQuota {
+            tag_limit_exceeded_action: TagLimitExceededAction::DropEvent,
+            ..quota
+        };
+        let output = run(&mut quota, vec![metric("a", "four"), metric("a", "two")]);
+        assert_eq!(output.len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn limits_tag_keys() {
+        let mut quota = quota(
+            r#"
+            tenant_key = "{{ %source.tenant }}"
+            limits.tag_value_limit = 10
+            limits.tag_key_limit = 1
+            "#,
+        );
+
+        let mut event = metric("a", "one");
+        event
+            .as_mut_metric()
+            .insert_tag("region".to_owned(), "us".to_owned());
+        let output = run(&mut quota, vec![event, metric("a", "two")]);
+        let tags = output[0].as_metric().tags().unwrap();
+        assert_eq!(tags.len(), 1);
+        assert_eq!(output[1].as_metric().tags().unwrap().len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn forgets_least_recent_tenants() {
+        let mut quota = quota(
+            r#"
+            tenant_key = "{{ %source.tenant }}"
+            limits.tag_value_limit = 1
+            max_tenants = 2
+            "#,
+        );
+
+        run(&mut quota, vec![metric("a", "one")]);
+        tokio::time::advance(Duration::from_secs(1)).await;
+        run(&mut quota, vec![metric("b", "one")]);
+        tokio::time::advance(Duration::from_secs(1)).await;
+        run(&mut quota, vec![metric("a", "one"), metric("c", "one")]);
+
+        assert_eq!(quota.usage.len(), 2);
+        assert!(quota.usage.contains_key("a"));
+        assert!(quota.usage.contains_key("c"));
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        for invalid in ["window_secs = 0", "window_secs = inf", "max_tenants = 0"] {
+            let config = toml::from_str::<TenantQuotaConfig>(&format!(
+                "tenant_key = \"{{{{ tenant }}}}\"\n{}",
+                invalid
+            ))
+            .unwrap();
+            assert!(TenantQuota::new(&config).is_err());
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Output, TransformConfig, TransformContext,
+        TransformDescription,
+    },
+    event::{Event, Value},
+    internal_events::{TimestampPolicyEventDiscarded, TimestampPolicyOutOfBounds},
+    transforms::{FunctionTransform, Transform},
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one of `max_past_secs` and `max_future_secs` must be set"))]
+    NoBounds,
+    #[snafu(display("The `annotate` action requires `annotation_field` to be set"))]
+    MissingAnnotationField,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct TimestampPolicyConfig {
+    /// The field holding the timestamp to check, the timestamp of the log schema by default.
+    pub field: Option<String>,
+    /// How far in the past a timestamp may be.
+    pub max_past_secs: Option<u64>,
+    /// How far in the future a timestamp may be.
+    pub max_future_secs: Option<u64>,
+    #[serde(default)]
+    pub action: Action,
+    /// The field to record why a timestamp was out of bounds, and its original value, in.
+    pub annotation_field: Option<String>,
+}
+
+/// What to do with events whose timestamp is out of bounds.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Replace the timestamp with the current time.
+    Replace,
+    /// Replace the timestamp with the bound it crossed.
+    Clamp,
+    /// Keep the timestamp, and only annotate the event.
+    Annotate,
+    /// Drop the event.
+    Drop,
+}
+
+impl Default for Action {
+    fn default() -> Self {
+        Self::Replace
+    }
+}
+
+impl Action {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Replace => "replace",
+            Self::Clamp => "clamp",
+            Self::Annotate => "annotate",
+            Self::Drop => "drop",
+        }
+    }
+}
+
+inventory::submit! {
+    TransformDescription::new::<TimestampPolicyConfig>("timestamp_policy")
+}
+
+impl GenerateConfig for TimestampPolicyConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            field: None,
+            max_past_secs: Some(7 * 24 * 60 * 60),
+            max_future_secs: Some(60 * 60),
+            action: Action::Replace,
+            annotation_field: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "timestamp_policy")]
+impl TransformConfig for TimestampPolicyConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TimestampPolicy::new(self).map(Transform::function)
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn outputs(&self) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn transform_type(&self) -> &'static str {
+        "timestamp_policy"
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Bound {
+    Past,
+    Future,
+}
+
+impl Bound {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Past => "past",
+            Self::Future => "future",
+        }
+    }
+
+    const fn reason(self) -> &'static str {
+        match self {
+            Self::Past => "too_old",
+            Self::Future => "too_new",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TimestampPolicy {
+    field: String,
+    max_past: Option<Duration>,
+    max_future: Option<Duration>,
+    action: Action,
+    annotation_field: Option<String>,
+}
+
+fn duration_secs(secs: u64) -> Duration {
+    Duration::seconds(secs.min(i64::MAX as u64 / 1000) as i64)
+}
+
+impl TimestampPolicy {
+    pub fn new(config: &TimestampPolicyConfig) -> crate::Result<Self> {
+        if config.max_past_secs.is_none() && config.max_future_secs.is_none() {
+            return Err(Box::new(BuildError::NoBounds));
+        }
+        if config.action == Action::Annotate && config.annotation_field.is_none() {
+            return Err(Box::new(BuildError::MissingAnnotationField));
+        }
+
+        Ok(Self {
+            field: config
+                .field
+                .clone()
+                .unwrap_or_else(|| log_schema().timestamp_key().to_owned()),
+            max_past: config.max_past_secs.map(duration_secs),
+            max_future: config.max_future_secs.map(duration_secs),
+            action: config.action,
+            annotation_field: config.annotation_field.clone(),
+        })
+    }
+
+    /// Returns the bound `timestamp` crosses at `now`, and the value of that bound.
+    fn check(
+        &self,
+        timestamp: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<(Bound, DateTime<Utc>)> {
+        if let Some(earliest) = self
+            .max_past
+            .and_then(|max_past| now.checked_sub_signed(max_past))
+        {
+            if timestamp < earliest {
+                return Some((Bound::Past, earliest));
+            }
+        }
+        if let Some(latest) = self
+            .max_future
+            .and_then(|max_future| now.checked_add_signed(max_future))
+        {
+            if timestamp > latest {
+                return Some((Bound::Future, latest));
+            }
+        }
+        None
+    }
+
+    fn apply(&self, output: &mut Vec<Event>, mut event: Event, now: DateTime<Utc>) {
+        // Missing timestamps, and values that aren't timestamps, are left as they are.
+        let crossed = match event.as_log().get(&self.field) {
+            Some(Value::Timestamp(timestamp)) => self
+                .check(*timestamp, now)
+                .map(|crossed| (*timestamp, crossed)),
+            _ => None,
+        };
+        let (timestamp, (bound, limit)) = match crossed {
+            Some(crossed) => crossed,
+            None => {
+                output.push(event);
+                return;
+            }
+        };
+
+        emit!(&TimestampPolicyOutOfBounds {
+            bound: bound.as_str(),
+            action: self.action.as_str(),
+        });
+
+        let log = event.as_mut_log();
+        match self.action {
+            Action::Drop => {
+                emit!(&TimestampPolicyEventDiscarded);
+                return;
+            }
+            Action::Replace => {
+                log.insert(self.field.as_str(), now);
+            }
+            Action::Clamp => {
+                log.insert(self.field.as_str(), limit);
+            }
+            Action::Annotate => {}
+        }
+
+        if let Some(annotation_field) = &self.annotation_field {
+            let mut annotation = BTreeMap::new();
+            annotation.insert("reason".to_owned(), Value::from(bound.reason()));
+            annotation.insert("original".to_owned(), Value::Timestamp(timestamp));
+            log.insert(annotation_field.as_str(), Value::Map(annotation));
+        }
+
+        output.push(event);
+    }
+}
+
+impl FunctionTransform for TimestampPolicy {
+    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+        self.apply(output, event, Utc::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::event::LogEvent;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TimestampPolicyConfig>();
+    }
+
+    fn policy(config: &str) -> TimestampPolicy {
+        TimestampPolicy::new(&toml::from_str(config).unwrap()).unwrap()
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.ymd(2022, 3, 1).and_hms(12, 0, 0)
+    }
+
+    fn event(timestamp: DateTime<Utc>) -> Event {
+        let mut log = LogEvent::from("message");
+        log.insert(log_schema().timestamp_key(), timestamp);
+        log.into()
+    }
+
+    fn apply(policy: &TimestampPolicy, event: Event) -> Option<Event> {
+        let mut output = Vec::new();
+        policy.apply(&mut output, event, now());
+        assert!(output.len() < 2);
+        output.pop()
+    }
+
+    fn timestamp(event: &Event) -> Value {
+        event.as_log()[log_schema().timestamp_key()].clone()
+    }
+
+    #[test]
+    fn requires_a_bound() {
+        let config = toml::from_str::<TimestampPolicyConfig>("action = \"drop\"").unwrap();
+        assert!(TimestampPolicy::new(&config).is_err());
+    }
+
+    #[test]
+    fn annotate_requires_a_field() {
+        let config = toml::from_str::<TimestampPolicyConfig>(
+            r#"
+                max_future_secs = 60
+                action = "annotate"
+            "#,
+        )
+        .unwrap();
+        assert!(TimestampPolicy::new(&config).is_err());
+    }
+
+    #[test]
+    fn passes_timestamps_within_bounds() {
+        let policy = policy(
+            r#"
+                max_past_secs = 3600
+                max_future_secs = 60
+            "#,
+        );
+
+        for offset in [-3600, 0, 60] {
+            let ts = now() + Duration::seconds(offset);
+            let event = apply(&policy, event(ts)).unwrap();
+            assert_eq!(timestamp(&event), Value::Timestamp(ts));
+        }
+    }
+
+    #[test]
+    fn replaces_out_of_bounds_timestamps() {
+        let policy = policy(
+            r#"
+                max_past_secs = 3600
+                max_future_secs = 60
+                annotation_field = "timestamp_policy"
+            "#,
+        );
+
+        let original = now() + Duration::days(365);
+        let event = apply(&policy, event(original)).unwrap();
+        assert_eq!(timestamp(&event), Value::Timestamp(now()));
+        assert_eq!(
+            event.as_log()["timestamp_policy.reason"],
+            Value::from("too_new")
+        );
+        assert_eq!(
+            event.as_log()["timestamp_policy.original"],
+            Value::Timestamp(original)
+        );
+    }
+
+    #[test]
+    fn clamps_out_of_bounds_timestamps() {
+        let policy = policy(
+            r#"
+                max_past_secs = 3600
+                max_future_secs = 60
+                action = "clamp"
+            "#,
+        );
+
+        let event_old = apply(&policy, event(now() - Duration::days(2))).unwrap();
+        assert_eq!(
+            timestamp(&event_old),
+            Value::Timestamp(now() - Duration::seconds(3600))
+        );
+        let event_new = apply(&policy, event(now() + Duration::days(2))).unwrap();
+        assert_eq!(
+            timestamp(&event_new),
+            Value::Timestamp(now() + Duration::seconds(60))
+        );
+        assert!(!event_new.as_log().contains("timestamp_policy"));
+    }
+
+    #[test]
+    fn annotates_out_of_bounds_timestamps() {
+        let policy = policy(
+            r#"
+                max_past_secs = 3600
+                action = "annotate"
+                annotation_field = "timestamp_policy"
+            "#,
+        );
+
+        let original = now() - Duration::days(2);
+        let annotated = apply(&policy, event(original)).unwrap();
+        assert_eq!(timestamp(&annotated), Value::Timestamp(original));
+        assert_eq!(
+            annotated.as_log()["timestamp_policy.reason"],
+            Value::from("too_old")
+        );
+        // Without `max_future_secs`, timestamps in the future are fine.
+        assert!(apply(&policy, event(now() + Duration::days(365))).is_some());
+    }
+
+    #[test]
+    fn drops_out_of_bounds_events() {
+        let policy = policy(
+            r#"
+                max_future_secs = 60
+                action = "drop"
+            "#,
+        );
+
+        assert!(apply(&policy, event(now() + Duration::days(1))).is_none());
+        assert!(apply(&policy, event(now())).is_some());
+    }
+
+    #[test]
+    fn ignores_missing_and_non_timestamp_fields() {
+        let policy = policy(
+            r#"
+                field = "received_at"
+                max_future_secs = 60
+                action = "drop"
+            "#,
+        );
+
+        assert!(apply(&policy, event(now() + Duration::days(1))).is_some());
+
+        let mut log = LogEvent::from("message");
+        log.insert("received_at", "2100-01-01T00:00:00Z");
+        assert!(apply(&policy, log.into()).is_some());
+    }
+}
@@ -1,5 +1,22 @@
-use socket2::SockRef;
-use tokio::net::UdpSocket;
+use std::{
+    future::Future,
+    io,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use async_stream::stream;
+use bytes::BytesMut;
+use futures::{stream::BoxStream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use socket2::{Domain, Protocol, SockRef, Socket, Type};
+use tokio::{net::UdpSocket, sync::mpsc, task::JoinHandle};
+use tokio_util::codec::Decoder;
+
+use crate::internal_events::UdpSpoolFull;
 
 // This function will be obsolete after tokio/mio internally use `socket2` and expose the methods to
 // apply options to a socket.
@@ -12,3 +29,463 @@ pub fn set_receive_buffer_size(socket: &UdpSocket, size: usize) -> std::io::Resu
 pub fn set_send_buffer_size(socket: &UdpSocket, size: usize) -> std::io::Result<()> {
     SockRef::from(socket).set_send_buffer_size(size)
 }
+
+pub const fn default_sockets() -> usize {
+    1
+}
+
+pub const fn default_receive_batch_size() -> usize {
+    1
+}
+
+/// The size of the buffer `tokio_util::udp::UdpFramed` reads datagrams into.
+const FRAMED_MAX_LENGTH: usize = 64 * 1024;
+
+/// Holds datagrams that have been received but not yet decoded.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SpoolConfig {
+    /// The most bytes of datagrams to hold, beyond which new ones are dropped.
+    #[serde(default = "default_spool_max_bytes")]
+    pub max_bytes: usize,
+}
+
+const fn default_spool_max_bytes() -> usize {
+    10 * 1024 * 1024
+}
+
+/// Binds `count` sockets to `address` for a source to receive on. When there is more than one they
+/// share the port with `SO_REUSEPORT`, and the kernel spreads datagrams across them by the address
+/// they come from.
+pub async fn bind_sockets(
+    address: SocketAddr,
+    count: usize,
+    receive_buffer_bytes: Option<usize>,
+) -> io::Result<Vec<UdpSocket>> {
+    let sockets = if count <= 1 {
+        vec![UdpSocket::bind(address).await?]
+    } else {
+        let first = bind_reuse_port(address)?;
+        // Binding to port 0 picks a port for the first socket, which the rest have to share.
+        let address = first.local_addr()?;
+        let mut sockets = vec![first];
+        for _ in 1..count {
+            sockets.push(bind_reuse_port(address)?);
+        }
+        sockets
+    };
+
+    if let Some(receive_buffer_bytes) = receive_buffer_bytes {
+        for socket in &sockets {
+            if let Err(error) = set_receive_buffer_size(socket, receive_buffer_bytes) {
+                warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+            }
+        }
+    }
+
+    Ok(sockets)
+}
+
+#[cfg(unix)]
+fn bind_reuse_port(address: SocketAddr) -> io::Result<UdpSocket> {
+    let socket = Socket::new(
+        Domain::for_address(address),
+        Type::DGRAM,
+        Some(Protocol::UDP),
+    )?;
+    socket.set_reuse_port(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&address.into())?;
+    UdpSocket::from_std(socket.into())
+}
+
+#[cfg(not(unix))]
+fn bind_reuse_port(_address: SocketAddr) -> io::Result<UdpSocket> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "more than one socket requires SO_REUSEPORT, which is only available on Unix",
+    ))
+}
+
+/// Runs `receive` for each of the sockets in a task of its own, so that they are decoded on as many
+/// of the runtime's worker threads, and waits for all of them to finish. The tasks are aborted if
+/// the returned future is dropped first.
+pub async fn receive_all<F, Fut>(sockets: Vec<UdpSocket>, receive: F) -> Result<(), ()>
+where
+    F: Fn(UdpSocket) -> Fut,
+    Fut: Future<Output = Result<(), ()>> + Send + 'static,
+{
+    let mut sockets = sockets.into_iter();
+    let first = match sockets.next() {
+        Some(socket) => receive(socket),
+        None => return Ok(()),
+    };
+    let tasks = sockets
+        .map(|socket| AbortOnDrop(tokio::spawn(receive(socket))))
+        .collect::<Vec<_>>();
+
+    let mut result = first.await;
+    for mut task in tasks {
+        result = match (&mut task.0).await {
+            Ok(task_result) => result.and(task_result),
+            Err(error) => {
+                error!(message = "UDP receive task failed.", %error);
+                Err(())
+            }
+        };
+    }
+    result
+}
+
+struct AbortOnDrop<T>(JoinHandle<T>);
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Buffers for up to `batch_size` datagrams, which are taken off a socket with a single
+/// `recvmmsg` system call on Linux. Elsewhere the first datagram is waited for, and any others
+/// already queued on the socket are read without waiting.
+struct RecvBatch {
+    buffers: Vec<BytesMut>,
+    received: Vec<(usize, SocketAddr)>,
+    max_length: usize,
+}
+
+impl RecvBatch {
+    fn new(batch_size: usize, max_length: usize) -> Self {
+        let batch_size = batch_size.max(1);
+        Self {
+            buffers: (0..batch_size)
+                .map(|_| BytesMut::with_capacity(max_length))
+                .collect(),
+            received: Vec::with_capacity(batch_size),
+            max_length,
+        }
+    }
+
+    /// Waits for a datagram, and receives it along with as many others as are queued and fit in
+    /// the batch.
+    async fn recv(&mut self, socket: &UdpSocket) -> io::Result<()> {
+        self.received.clear();
+        for buffer in &mut self.buffers {
+            // Buffers that weren't filled last time are still the right length.
+            if buffer.len() < self.max_length {
+                buffer.resize(self.max_length, 0);
+            }
+        }
+
+        if self.buffers.len() == 1 {
+            let received = socket.recv_from(&mut self.buffers[0]).await?;
+            self.received.push(received);
+            Ok(())
+        } else {
+            recv_many(socket, &mut self.buffers, &mut self.received).await
+        }
+    }
+
+    /// Takes the datagrams received by the last call to `recv`, with the addresses they came from.
+    fn drain(&mut self) -> impl Iterator<Item = (BytesMut, SocketAddr)> + '_ {
+        self.buffers
+            .iter_mut()
+            .zip(self.received.drain(..))
+            .map(|(buffer, (length, address))| (buffer.split_to(length), address))
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn recv_many(
+    socket: &UdpSocket,
+    buffers: &mut [BytesMut],
+    received: &mut Vec<(usize, SocketAddr)>,
+) -> io::Result<()> {
+    loop {
+        socket.readable().await?;
+        match socket.try_io(tokio::io::Interest::READABLE, || {
+            recvmmsg(socket, buffers, received)
+        }) {
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => continue,
+            result => return result,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn recvmmsg(
+    socket: &UdpSocket,
+    buffers: &mut [BytesMut],
+    received: &mut Vec<(usize, SocketAddr)>,
+) -> io::Result<()> {
+    use std::{mem, os::unix::io::AsRawFd, ptr};
+
+    use nix::libc;
+    use socket2::SockAddr;
+
+    // SAFETY: these are plain C structs, for which all zeroes is a valid value.
+    let mut addresses = vec![unsafe { mem::zeroed::<libc::sockaddr_storage>() }; buffers.len()];
+    let mut iovecs = buffers
+        .iter_mut()
+        .map(|buffer| libc::iovec {
+            iov_base: buffer.as_mut_ptr().cast(),
+            iov_len: buffer.len(),
+        })
+        .collect::<Vec<_>>();
+    let mut messages = iovecs
+        .iter_mut()
+        .zip(&mut addresses)
+        .map(|(iovec, address)| {
+            // SAFETY: as above.
+            let mut message = unsafe { mem::zeroed::<libc::mmsghdr>() };
+            message.msg_hdr.msg_name = (address as *mut libc::sockaddr_storage).cast();
+            message.msg_hdr.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as _;
+            message.msg_hdr.msg_iov = iovec;
+            message.msg_hdr.msg_iovlen = 1;
+            message
+        })
+        .collect::<Vec<_>>();
+
+    // SAFETY: every message points at an address and a buffer of the lengths given, which outlive
+    // the call.
+    let count = unsafe {
+        libc::recvmmsg(
+            socket.as_raw_fd(),
+            messages.as_mut_ptr(),
+            messages.len() as _,
+            0,
+            ptr::null_mut(),
+        )
+    };
+    if count < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    for (message, address) in messages.iter().zip(addresses).take(count as usize) {
+        // SAFETY: the kernel wrote an address of this length.
+        let address = unsafe { SockAddr::new(address, message.msg_hdr.msg_namelen) };
+        let address = address.as_socket().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "received from a non-IP address")
+        })?;
+        received.push((message.msg_len as usize, address));
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn recv_many(
+    socket: &UdpSocket,
+    buffers: &mut [BytesMut],
+    received: &mut Vec<(usize, SocketAddr)>,
+) -> io::Result<()> {
+    let (first, rest) = buffers
+        .split_first_mut()
+        .expect("batches have at least one buffer");
+    received.push(socket.recv_from(first).await?);
+    for buffer in rest {
+        // Any error is left for the next call to come across, so as not to lose the datagrams
+        // already received.
+        match socket.try_recv_from(buffer) {
+            Ok(datagram) => received.push(datagram),
+            Err(_) => break,
+        }
+    }
+    Ok(())
+}
+
+/// Receives datagrams from the socket, `batch_size` at a time. With a spool they are received by
+/// a task of its own, which keeps the socket drained while they wait to be decoded, so that bursts
+/// aren't dropped by the kernel while the rest of the pipeline catches up.
+pub fn datagrams(
+    socket: UdpSocket,
+    max_length: usize,
+    batch_size: usize,
+    spool: Option<SpoolConfig>,
+) -> BoxStream<'static, io::Result<(BytesMut, SocketAddr)>> {
+    let datagrams = stream! {
+        let mut batch = RecvBatch::new(batch_size, max_length);
+        loop {
+            match batch.recv(&socket).await {
+                Ok(()) => {
+                    for datagram in batch.drain() {
+                        yield Ok(datagram);
+                    }
+                }
+                Err(error) => yield Err(error),
+            }
+        }
+    };
+    match spool {
+        Some(spool) => spooled(datagrams, spool).boxed(),
+        None => datagrams.boxed(),
+    }
+}
+
+fn spooled(
+    datagrams: impl Stream<Item = io::Result<(BytesMut, SocketAddr)>> + Send + 'static,
+    config: SpoolConfig,
+) -> impl Stream<Item = io::Result<(BytesMut, SocketAddr)>> {
+    // Errors aren't counted against the spool, but they are few and keep their place among the
+    // datagrams.
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let spooled_bytes = Arc::new(AtomicUsize::new(0));
+
+    let receiver = {
+        let spooled_bytes = Arc::clone(&spooled_bytes);
+        tokio::spawn(async move {
+            tokio::pin!(datagrams);
+            while let Some(datagram) = datagrams.next().await {
+                // Received datagrams share the allocation of the whole receive buffer, which the
+                // spool would keep alive while only counting the datagram, so each is copied.
+                let datagram =
+                    datagram.map(|(payload, address)| (BytesMut::from(&payload[..]), address));
+                if let Ok((payload, _)) = &datagram {
+                    let byte_size = payload.len();
+                    if spooled_bytes.load(Ordering::Acquire) + byte_size > config.max_bytes {
+                        emit!(&UdpSpoolFull {
+                            byte_size,
+                            max_bytes: config.max_bytes,
+                        });
+                        continue;
+                    }
+                    spooled_bytes.fetch_add(byte_size, Ordering::AcqRel);
+                }
+                if tx.send(datagram).is_err() {
+                    break;
+                }
+            }
+        })
+    };
+
+    stream! {
+        let _receiver = AbortOnDrop(receiver);
+        while let Some(datagram) = rx.recv().await {
+            if let Ok((payload, _)) = &datagram {
+                spooled_bytes.fetch_sub(payload.len(), Ordering::AcqRel);
+            }
+            yield datagram;
+        }
+    }
+}
+
+/// Decodes the datagrams received on the socket the way `tokio_util::udp::UdpFramed` does, but
+/// receives them `batch_size` at a time and optionally spools them before they're decoded.
+pub fn framed<D: Decoder>(
+    socket: UdpSocket,
+    mut decoder: D,
+    batch_size: usize,
+    spool: Option<SpoolConfig>,
+) -> impl Stream<Item = Result<(D::Item, SocketAddr), D::Error>> {
+    let mut datagrams = datagrams(socket, FRAMED_MAX_LENGTH, batch_size, spool);
+    stream! {
+        while let Some(datagram) = datagrams.next().await {
+            let (mut datagram, address) = match datagram {
+                Ok(datagram) => datagram,
+                Err(error) => {
+                    yield Err(error.into());
+                    continue;
+                }
+            };
+            loop {
+                match decoder.decode_eof(&mut datagram) {
+                    Ok(Some(frame)) => yield Ok((frame, address)),
+                    Ok(None) => break,
+                    Err(error) => {
+                        yield Err(error);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_util::next_addr;
+
+    #[tokio::test]
+    async fn binds_sockets_sharing_a_port() {
+        let address = next_addr();
+        let sockets = bind_sockets(address, 4, None).await.unwrap();
+        assert_eq!(sockets.len(), 4);
+        for socket in &sockets {
+            assert_eq!(socket.local_addr().unwrap(), address);
+        }
+    }
+
+    #[tokio::test]
+    async fn shares_a_picked_port() {
+        let sockets = bind_sockets("127.0.0.1:0".parse().unwrap(), 2, None)
+            .await
+            .unwrap();
+        let address = sockets[0].local_addr().unwrap();
+        assert_ne!(address.port(), 0);
+        assert_eq!(sockets[1].local_addr().unwrap(), address);
+    }
+
+    #[tokio::test]
+    async fn receives_batches() {
+        let address = next_addr();
+        let socket = UdpSocket::bind(address).await.unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        for datagram in ["one", "two", "three"] {
+            sender.send_to(datagram.as_bytes(), address).await.unwrap();
+        }
+        // Give the kernel time to queue all of them.
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let mut batch = RecvBatch::new(2, 16);
+        let mut datagrams = Vec::new();
+        while datagrams.len() < 3 {
+            batch.recv(&socket).await.unwrap();
+            let received = batch.drain().collect::<Vec<_>>();
+            assert!(received.len() <= 2);
+            for (datagram, from) in received {
+                assert_eq!(from, sender.local_addr().unwrap());
+                datagrams.push(datagram);
+            }
+        }
+        assert_eq!(datagrams, vec!["one", "two", "three"]);
+    }
+
+    #[tokio::test]
+    async fn spool_drops_datagrams_beyond_max_bytes() {
+        let address = next_addr();
+        let socket = UdpSocket::bind(address).await.unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut datagrams = datagrams(socket, 16, 1, Some(SpoolConfig { max_bytes: 8 }));
+
+        // Nothing is taken from the spool while these arrive, so only the first two fit.
+        for datagram in ["1234", "5678", "9"] {
+            sender.send_to(datagram.as_bytes(), address).await.unwrap();
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let mut received = Vec::new();
+        for _ in 0..2 {
+            let (datagram, _) = datagrams.next().await.unwrap().unwrap();
+            received.push(datagram);
+        }
+        // Taking them makes room for more.
+        sender.send_to(b"last", address).await.unwrap();
+        let (datagram, _) = datagrams.next().await.unwrap().unwrap();
+        received.push(datagram);
+
+        assert_eq!(received, vec!["1234", "5678", "last"]);
+    }
+
+    #[tokio::test]
+    async fn spool_holds_only_the_datagrams() {
+        let address = next_addr();
+        let socket = UdpSocket::bind(address).await.unwrap();
+        let sender = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut datagrams = datagrams(socket, 65536, 1, Some(SpoolConfig { max_bytes: 1024 }));
+
+        sender.send_to(b"small", address).await.unwrap();
+        let (datagram, _) = datagrams.next().await.unwrap().unwrap();
+        assert_eq!(datagram, "small");
+        assert_eq!(datagram.capacity(), datagram.len());
+    }
+}
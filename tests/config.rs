@@ -587,6 +587,115 @@ async fn cycle() {
     )
 }
 
+#[cfg(all(feature = "sources-socket", feature = "sinks-socket"))]
+#[tokio::test]
+async fn ordered_source_with_several_paths() {
+    let errors = load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+        ordered = true
+
+        [sinks.out1]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["in"]
+        encoding = "text"
+        address = "127.0.0.1:9999"
+
+        [sinks.out2]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["in"]
+        encoding = "text"
+        address = "127.0.0.1:9998"
+        "#,
+        Format::Toml,
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].starts_with(
+        "Source \"in\" is ordered, but its events reach sinks through more than one path: "
+    ));
+    assert!(errors[0].contains("in -> out1"));
+    assert!(errors[0].contains("in -> out2"));
+}
+
+#[cfg(all(feature = "sources-socket", feature = "sinks-http"))]
+#[tokio::test]
+async fn ordered_source_with_concurrent_requests() {
+    let config = r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+        ordered = true
+
+        [sinks.out]
+        type = "http"
+        inputs = ["in"]
+        encoding = "text"
+        uri = "http://127.0.0.1:9999"
+        "#;
+
+    let errors = load(config, Format::Toml).await.unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            "Sink \"out\" receives the events of ordered source \"in\", but may send several requests at once. Set its `request.concurrency` to 1"
+        ]
+    );
+
+    let config = format!("{}\n        request.concurrency = 1\n", config);
+    let warnings = load(&config, Format::Toml).await.unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "transforms-remap",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn ordered_source_ignores_parallelism() {
+    let warnings = load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+        ordered = true
+
+        [transforms.remap]
+        type = "remap"
+        inputs = ["in"]
+        source = ".ordered = true"
+        parallelism = 4
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["remap"]
+        encoding = "text"
+        address = "127.0.0.1:9999"
+        "#,
+        Format::Toml,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        warnings,
+        vec![
+            "Transform \"remap\" ignores `parallelism`, as it transforms the events of an ordered source"
+        ]
+    )
+}
+
 #[cfg(all(feature = "sources-socket", feature = "sinks-socket"))]
 #[tokio::test]
 async fn disabled_healthcheck() {
@@ -844,3 +953,62 @@ async fn route() {
 
     assert_eq!(0, warnings.len());
 }
+
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "sinks-http",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn healthcheck_rechecks() {
+    let config = r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+
+        [sinks.out]
+        type = "http"
+        inputs = ["in"]
+        encoding = "text"
+        uri = "http://127.0.0.1:9999"
+        healthcheck.interval_secs = 60
+        "#;
+    let warnings = load(config, Format::Toml).await.unwrap();
+    assert!(warnings.is_empty());
+
+    let errors = load(
+        &config.replace("interval_secs = 60", "interval_secs = 0"),
+        Format::Toml,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        errors,
+        vec!["Sink \"out\": `healthcheck.interval_secs` must be greater than 0"]
+    );
+
+    let errors = load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["in"]
+        encoding = "text"
+        address = "127.0.0.1:9999"
+        healthcheck.interval_secs = 60
+        "#,
+        Format::Toml,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        errors,
+        vec!["Sink \"out\": `healthcheck.interval_secs` isn't supported by the `socket` sink"]
+    );
+}
@@ -268,6 +268,7 @@ impl FunctionTransform for MockTransform {
                         kind: metric.kind(),
                         timestamp: metric.timestamp(),
                         value: increment,
+                        series_ended: false,
                     }));
                 }
             }
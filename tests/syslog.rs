@@ -38,6 +38,9 @@ async fn test_tcp_syslog() {
             tls: None,
             receive_buffer_bytes: None,
             connection_limit: None,
+            connection_limit_per_ip: None,
+            connection_rate_limit: None,
+            proxy_protocol: Default::default(),
         }),
     );
     config.add_sink("out", &["in"], tcp_json_sink(out_addr.to_string()));
@@ -157,6 +160,9 @@ async fn test_octet_counting_syslog() {
             tls: None,
             receive_buffer_bytes: None,
             connection_limit: None,
+            connection_limit_per_ip: None,
+            connection_rate_limit: None,
+            proxy_protocol: Default::default(),
         }),
     );
     config.add_sink("out", &["in"], tcp_json_sink(out_addr.to_string()));
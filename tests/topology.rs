@@ -170,6 +170,55 @@ async fn topology_multiple_sinks() {
     assert_eq!(vec![event], res2);
 }
 
+#[cfg(feature = "transforms-remap")]
+#[tokio::test]
+async fn topology_parallel_transform_keeps_key_order() {
+    use vector::transforms::remap::RemapConfig;
+
+    let (mut in1, source1) = source();
+    let (out1, sink1) = sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_transform(
+        "t1",
+        &["in1"],
+        RemapConfig {
+            source: Some(".transformed = true".to_owned()),
+            parallelism: std::num::NonZeroUsize::new(4),
+            parallelism_key_field: Some("key".to_owned()),
+            ..Default::default()
+        },
+    );
+    config.add_sink("out1", &["t1"], sink1);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+    let collected = tokio::spawn(out1.collect::<Vec<_>>());
+
+    for sequence in 0..1000 {
+        let mut event = Event::from("this");
+        event.as_mut_log().insert("key", sequence % 10);
+        event.as_mut_log().insert("sequence", sequence);
+        in1.send(event).await.unwrap();
+    }
+
+    topology.stop().await;
+
+    let events = collected.await.unwrap();
+    assert_eq!(events.len(), 1000);
+
+    let mut last_sequences = HashMap::new();
+    for event in events {
+        let log = event.as_log();
+        assert_eq!(log["transformed"], true.into());
+        let key = log["key"].to_string_lossy();
+        let sequence = log["sequence"].to_string_lossy().parse::<i64>().unwrap();
+        if let Some(last_sequence) = last_sequences.insert(key, sequence) {
+            assert!(last_sequence < sequence);
+        }
+    }
+}
+
 #[tokio::test]
 async fn topology_transform_chain() {
     let (mut in1, source1) = source();
@@ -196,6 +245,34 @@ async fn topology_transform_chain() {
     assert_eq!(vec!["this first second"], res);
 }
 
+#[tokio::test]
+async fn topology_transform_coalesces_within_latency() {
+    let (mut in1, source1) = source();
+    let (mut out1, sink1) = sink(10);
+
+    let mut config = Config::builder();
+    config.global.coalesce_latency_ms = Some(50);
+    config.add_source("in1", source1);
+    config.add_transform("t1", &["in1"], transform(" first", 0.0));
+    config.add_sink("out1", &["t1"], sink1);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    // A batch that never fills up is still sent on once the latency is up.
+    in1.send(Event::from("this")).await.unwrap();
+    let event = tokio::time::timeout(Duration::from_secs(5), out1.next())
+        .await
+        .expect("event wasn't sent on within the latency")
+        .unwrap();
+    assert_eq!(into_message(event), "this first");
+
+    in1.send(Event::from("that")).await.unwrap();
+    topology.stop().await;
+
+    let res = out1.map(into_message).collect::<Vec<_>>().await;
+    assert_eq!(vec!["that first"], res);
+}
+
 #[tokio::test]
 async fn topology_remove_one_source() {
     let (mut in1, source1) = source();
@@ -580,3 +657,20 @@ async fn topology_healthcheck_run_for_changes_on_reload() {
     config.healthchecks.require_healthy = true;
     assert!(!topology.reload_config_and_respawn(config).await.unwrap());
 }
+
+#[tokio::test]
+async fn topology_healthcheck_required_on_reload_only() {
+    let mut config = basic_config_with_sink_failing_healthcheck();
+    config.healthchecks.require_healthy_on_reload = true;
+    let (mut topology, _crash) = start_topology(config, false).await;
+
+    let mut config = Config::builder();
+    // We can't just drop the sender side since that will close the source.
+    let (_ch1, src) = source();
+    config.add_source("in1", src);
+    config.add_sink("out2", &["in1"], sink_failing_healthcheck(10).1);
+
+    let mut config = config.build().unwrap();
+    config.healthchecks.require_healthy_on_reload = true;
+    assert!(!topology.reload_config_and_respawn(config).await.unwrap());
+}
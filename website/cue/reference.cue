@@ -18,9 +18,12 @@ _values: {
 //
 // * `none` - compression is not applied
 // * `gzip` - gzip compression applied
-#CompressionAlgorithm: "none" | "gzip" | "lz4" | "snappy" | "zstd"
+// * `zlib` - zlib compression applied
+// * `zstd` - zstd compression applied
+#CompressionAlgorithm: "none" | "gzip" | "lz4" | "snappy" | "zlib" | "zstd"
 
-#CompressionLevel: "none" | "fast" | "default" | "best" | >=0 & <=9
+// Levels above 9 are only valid for zstd, which supports levels 1 to 21.
+#CompressionLevel: "none" | "fast" | "default" | "best" | >=0 & <=21
 
 #Date: =~"^\\d{4}-\\d{2}-\\d{2}"
 
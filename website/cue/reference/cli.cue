@@ -89,6 +89,10 @@ cli: {
 	name: "vector"
 
 	flags: _default_flags & {
+		"allocation-tracing": {
+			description: env_vars.VECTOR_ALLOCATION_TRACING.description
+			env_var:     "VECTOR_ALLOCATION_TRACING"
+		}
 		"quiet": {
 			_short: "q"
 			description: """
@@ -164,6 +168,38 @@ cli: {
 	options: _core_options
 
 	commands: {
+		"bench": {
+			description: """
+				Run a configuration in-process and report how fast events go through it, so that
+				changes to it can be performance tested before rolling them out. Each source is
+				replaced with a `demo_logs` source and each sink with a `blackhole` sink, keeping
+				its inputs and buffer. The report shows the throughput of the whole pipeline and,
+				for each component, the events it received and sent and the CPU time spent running
+				it. Allocations are included when Vector runs with `--allocation-tracing`.
+				"""
+
+			example: "vector bench --config /etc/vector/vector.toml --events 1M"
+
+			flags: {
+				"json": {
+					description: "Print the report as JSON"
+				}
+			}
+
+			options: _core_options & {
+				"events": {
+					description: "The number of events to generate, split between the sources. Accepts `k`, `M` and `G` suffixes."
+					type:        "string"
+					default:     "1M"
+				}
+				"format": {
+					description: "The `demo_logs` format of the generated events"
+					type:        "string"
+					default:     "json"
+				}
+			}
+		}
+
 		"graph": {
 			description: """
 				Generate a visual representation of topologies. The output is in the [DOT format](\(urls.dot_format)),
@@ -461,6 +497,14 @@ cli: {
 				examples: ["/mnt/host/sys"]
 			}
 		}
+		VECTOR_ALLOCATION_TRACING: {
+			description: """
+				Attribute heap allocations to the components that made them, and report them with the
+				`component_allocated_bytes` internal metric. Only available when Vector is built with
+				the `allocation-tracing` feature.
+				"""
+			type: bool: default: false
+		}
 		VECTOR_COLOR: {
 			description: "Control when ANSI terminal formatting is used."
 			type: string: {
@@ -562,6 +562,69 @@ components: {
 				}
 			}
 
+			_proxy_protocol: {
+				common:      false
+				description: "Read a [PROXY protocol](\(urls.haproxy_proxy_protocol)) header, version 1 or 2, at the start of each connection, so that the address of the original client survives load balancers such as HAProxy and AWS NLB. The client replaces the load balancer as the peer, including in the `host` field, and the load balancer's address is kept in the `proxy_ip` metadata field. This can be set to `true` instead of a table."
+				required:    false
+				type: object: options: {
+					enabled: {
+						common:      true
+						description: "Require each connection to start with a PROXY protocol header. Connections without one are closed."
+						required:    false
+						type: bool: default: true
+					}
+					client_ip_key: {
+						common:      false
+						description: "The field to store the IP address of the original client in."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["client_ip"]
+						}
+					}
+				}
+			}
+
+			_udp_receive_batch_size: {
+				common:        false
+				description:   "The most datagrams to receive from a socket with each system call. On Linux larger batches are received with `recvmmsg`, which saves system calls at high packet rates; elsewhere datagrams already queued are read without waiting. Every datagram in a batch has a receive buffer of its own, so larger batches use more memory."
+				relevant_when: "mode = `udp`"
+				required:      false
+				type: uint: {
+					default: 1
+					unit:    null
+				}
+			}
+
+			_udp_spool: {
+				common:        false
+				description:   "Receive datagrams ahead of decoding them, and hold them in memory until they are. This keeps the socket drained while the rest of the pipeline is slow to take events, so bursts aren't dropped by the kernel. Datagrams that don't fit in the spool are dropped and counted by the `spool_dropped_datagrams_total` metric. The spool isn't kept across restarts."
+				relevant_when: "mode = `udp`"
+				required:      false
+				type: object: options: {
+					max_bytes: {
+						common:      true
+						description: "The most bytes of datagrams to hold."
+						required:    false
+						type: uint: {
+							default: 10485760
+							unit:    "bytes"
+						}
+					}
+				}
+			}
+
+			_udp_sockets: {
+				common:        false
+				description:   "The number of sockets to receive on. More than one share the port with `SO_REUSEPORT`, and the kernel spreads datagrams across them by the address they come from, so that they can be decoded on several threads. This is only supported on Unix."
+				relevant_when: "mode = `udp`"
+				required:      false
+				type: uint: {
+					default: 1
+					unit:    null
+				}
+			}
+
 			_tls_accept: {
 				_args: {
 					can_enable:             bool
@@ -833,6 +896,76 @@ components: {
 				}
 			}
 
+			_http2: {
+				_args: server: bool
+				let Args = _args
+
+				common:      false
+				description: """
+					Configures HTTP/2, which multiplexes requests over each connection so that a slow request
+					doesn't hold up the ones behind it. This can be set to `true` to enable HTTP/2 with the
+					default settings.
+					"""
+				required: false
+				type: object: options: {
+					adaptive_window: {
+						common:      false
+						description: "Sizes the flow control windows from the measured bandwidth-delay product of each connection, which suits high-latency links. The initial window sizes are only used when this is disabled."
+						required:    false
+						type: bool: default: true
+					}
+					enabled: {
+						common: true
+						if Args.server {
+							description: "Tunes HTTP/2 connections with these settings, and lets clients negotiate HTTP/2 over TLS. Clients can use HTTP/2 over plain-text connections either way."
+						}
+						if !Args.server {
+							description: "Sends requests with HTTP/2 only. Over TLS, it's negotiated with the server, and over plain-text connections the server is assumed to support it."
+						}
+						required: false
+						type: bool: default: false
+					}
+					initial_connection_window_size: {
+						common:      false
+						description: "The initial flow control window of each connection."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    "bytes"
+						}
+					}
+					initial_stream_window_size: {
+						common:      false
+						description: "The initial flow control window of each request."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    "bytes"
+						}
+					}
+					keep_alive_interval_secs: {
+						common:      false
+						description: "How often to send pings to keep idle connections alive. Connections aren't pinged if unset."
+						required:    false
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
+					if Args.server {
+						max_concurrent_streams: {
+							common:      false
+							description: "The most requests a client can have in flight on each connection."
+							required:    false
+							type: uint: {
+								default: null
+								unit:    "requests"
+							}
+						}
+					}
+				}
+			}
+
 			_timezone: {
 				common:      false
 				description: """
@@ -848,6 +981,37 @@ components: {
 				}
 			}
 
+			_parallelism: {
+				common:      false
+				description: """
+					The number of tasks the transform runs on. Without it, batches of events are
+					transformed concurrently on as many tasks as needed, while keeping their order.
+					With it, events are spread over that many tasks, and are only kept in order for
+					each value of `parallelism_key_field`.
+					"""
+				required:    false
+				type: uint: {
+					default: null
+					examples: [4]
+					unit: null
+				}
+			}
+
+			_parallelism_key_field: {
+				common:      false
+				description: """
+					The field whose value picks the task an event is transformed on when
+					`parallelism` is set, so that events with the same value keep their order. For
+					metrics, this is the name of a tag. Without it, batches of events are spread
+					over the tasks in turn, and aren't kept in order.
+					"""
+				required:    false
+				type: string: {
+					default: null
+					examples: ["tenant", "host"]
+				}
+			}
+
 			_types: {
 				common:      true
 				description: _coercing_fields
@@ -39,6 +39,105 @@ components: _aws: {
 							examples: ["arn:aws:iam::123456789098:role/my_role"]
 						}
 					}
+					external_id: {
+						category:      "Auth"
+						common:        false
+						description:   "The external ID to pass to STS when assuming `assume_role`, as required by some cross-account roles."
+						relevant_when: "assume_role != null"
+						required:      false
+						type: string: {
+							default: null
+							examples: ["my-external-id"]
+						}
+					}
+					role_chain: {
+						category: "Auth"
+						common:   false
+						description: """
+							Roles to assume in turn after `assume_role`, each with the credentials of the role before
+							it. Components with the same roles and options share a single STS session.
+							"""
+						relevant_when: "assume_role != null"
+						required:      false
+						type: array: {
+							default: []
+							items: type: object: {
+								examples: [{role_arn: "arn:aws:iam::210987654321:role/target_role", external_id: "my-external-id"}]
+								options: {
+									role_arn: {
+										description: "The ARN of the role to assume."
+										required:    true
+										type: string: examples: ["arn:aws:iam::210987654321:role/target_role"]
+									}
+									external_id: {
+										common:      false
+										description: "The external ID to pass to STS when assuming the role."
+										required:    false
+										type: string: {
+											default: null
+											examples: ["my-external-id"]
+										}
+									}
+								}
+							}
+						}
+					}
+					web_identity_token_file: {
+						category:    "Auth"
+						common:      false
+						description: "The path to a web identity token, such as the one mounted by IAM roles for service accounts (IRSA), to assume `role_arn` with."
+						required:    false
+						type: string: {
+							default: null
+							examples: ["/var/run/secrets/eks.amazonaws.com/serviceaccount/token"]
+						}
+					}
+					role_arn: {
+						category:      "Auth"
+						common:        false
+						description:   "The ARN of the role to assume with the web identity token."
+						relevant_when: "web_identity_token_file != null"
+						required:      false
+						type: string: {
+							default: null
+							examples: ["arn:aws:iam::123456789098:role/my_role"]
+						}
+					}
+					session_name: {
+						category:    "Auth"
+						common:      false
+						description: "The name of the STS session when assuming a role."
+						required:    false
+						type: string: {
+							default: "default"
+							examples: ["vector"]
+						}
+					}
+					session_duration_secs: {
+						category:    "Auth"
+						common:      false
+						description: "How long the credentials of assumed roles are valid for. By default, the role's own default is used."
+						required:    false
+						type: uint: {
+							default: null
+							examples: [900, 3600]
+							unit: "seconds"
+						}
+					}
+					credential_process: {
+						category: "Auth"
+						common:   false
+						description: """
+							A command printing credentials in the format of the AWS CLI's
+							[`credential_process`](https://docs.aws.amazon.com/cli/latest/userguide/cli-configure-sourcing-external.html).
+							The command is run through the shell again when the credentials expire.
+							"""
+						required: false
+						type: string: {
+							default: null
+							examples: ["/usr/local/bin/get-credentials --profile production"]
+						}
+					}
 					credentials_file: {
 						category:    "Auth"
 						common:      false
@@ -62,6 +62,36 @@ components: sinks: [Name=string]: {
 				type: object: {
 					examples: []
 					options: {
+						flush_every_n_records: {
+							common:        false
+							description:   """
+								The maximum number of events written to the buffer between flushes to disk, in addition to
+								`flush_interval_ms`. Events written since the last flush are lost if Vector crashes, so a lower
+								value bounds that loss, at the cost of more frequent flushes.
+								"""
+							required:      false
+							relevant_when: "type = \"disk_v2\""
+							type: uint: {
+								default: null
+								examples: [1000]
+								unit: "events"
+							}
+						}
+						flush_interval_ms: {
+							common:        false
+							description:   """
+								The maximum time between flushes of the buffer to disk. Events written since the last flush
+								are lost if Vector crashes, so this bounds the window of data loss. Flushing less often lets
+								more events be committed at once, which improves throughput on disks where each flush is
+								slow, such as network-attached volumes.
+								"""
+							required:      false
+							relevant_when: "type = \"disk_v2\""
+							type: uint: {
+								default: 500
+								unit:    "milliseconds"
+							}
+						}
 						max_events: {
 							common:        true
 							description:   "The maximum number of [events](\(urls.vector_data_model)) allowed in the buffer."
@@ -75,7 +105,7 @@ components: sinks: [Name=string]: {
 						max_size: {
 							description:   "The maximum size of the buffer on the disk."
 							required:      true
-							relevant_when: "type = \"disk\""
+							relevant_when: "type = \"disk\" or type = \"disk_v2\""
 							type: uint: {
 								examples: [104900000]
 								unit: "bytes"
@@ -96,6 +126,11 @@ components: sinks: [Name=string]: {
 									WARNING: This may stall the sink if disk performance isn't on par with the throughput.
 									For comparison, AWS gp2 volumes are usually too slow for common cases.
 									"""
+									disk_v2: """
+									Stores the sink's buffer on disk, flushing it to disk periodically rather than for every
+									event, as configured by `flush_interval_ms` and `flush_every_n_records`.
+									WARNING: This buffer type is not yet stable.
+									"""
 								}
 							}
 						}
@@ -108,6 +143,7 @@ components: sinks: [Name=string]: {
 								enum: {
 									block:       "Applies back pressure when the buffer is full. This prevents data loss, but will cause data to pile up on the edge."
 									drop_newest: "Drops new data as it's received. This data is lost. This should be used when performance is the highest priority."
+									overflow:    "Sends new data to the next buffer stage when the buffer is full. Only valid when several buffer stages are configured."
 								}
 							}
 						}
@@ -143,6 +179,9 @@ components: sinks: [Name=string]: {
 								if algo == "lz4" {
 									lz4: "[lz4](\(urls.lz4)) compression."
 								}
+								if algo == "zlib" {
+									zlib: "[zlib](\(urls.zlib)) compression, sent with the `deflate` content encoding."
+								}
 								if algo == "zstd" {
 									zstd: "[zstd](\(urls.zstd)) compression."
 								}
@@ -240,6 +279,32 @@ components: sinks: [Name=string]: {
 								}
 							}
 						}
+
+						bytes_format: {
+							common:      false
+							description: "How to encode string values that aren't valid UTF-8, such as binary payloads."
+							required:    false
+							type: string: {
+								default: "lossy"
+								enum: {
+									base64: "Encodes them as base64 strings. Values that are valid UTF-8 are left as they are."
+									lossy:  "Replaces the invalid bytes with the `U+FFFD` replacement character."
+								}
+							}
+						}
+
+						decimal_format: {
+							common:      false
+							description: "How to encode exact decimal values, such as integers too large for a signed 64-bit integer."
+							required:    false
+							type: string: {
+								default: "number"
+								enum: {
+									number: "Encodes them as numbers. Integers are exact, fractional values may be rounded by codecs such as JSON."
+									string: "Encodes them as strings holding their exact value."
+								}
+							}
+						}
 					}
 				}
 			}
@@ -260,6 +325,22 @@ components: sinks: [Name=string]: {
 								required:    false
 								type: bool: default: true
 							}
+							interval_secs: {
+								common:      false
+								description: "How often to run the healthcheck again while the sink is running. By default it's only run when the sink starts. Only the `http` sink supports it so far, and it can't be `0`."
+								required:    false
+								type: uint: {
+									default: null
+									examples: [60]
+									unit: "seconds"
+								}
+							}
+							required: {
+								common:      false
+								description: "Reports the sink as degraded, through the `component_degraded` internal metric and the API, for as long as its healthcheck fails. The sink keeps running either way."
+								required:    false
+								type: bool: default: false
+							}
 						}
 					}
 				}
@@ -314,6 +395,52 @@ components: sinks: [Name=string]: {
 									}
 								}
 							}
+							circuit_breaker: {
+								common:      false
+								description: "Stops sending requests to an endpoint that keeps failing, instead of piling retries onto it. While the breaker is open, requests wait to be retried without using up `retry_attempts`. Once `open_duration_secs` have passed, a single request is sent to probe the endpoint, and the breaker closes if it succeeds or opens again if it fails. The breaker is disabled by default."
+								required:    false
+								type: object: {
+									examples: []
+									options: {
+										consecutive_failures: {
+											common:      false
+											description: "Opens the breaker after this many failed requests in a row. Set to `0` to only open it on `error_rate`."
+											required:    false
+											type: uint: {
+												default: 5
+												unit:    "requests"
+											}
+										}
+										error_rate: {
+											common:      false
+											description: "Opens the breaker once this share of the last `error_rate_window` requests failed. Must be greater than `0` and at most `1`."
+											required:    false
+											type: float: {
+												default: null
+												examples: [0.5]
+											}
+										}
+										error_rate_window: {
+											common:      false
+											description: "The number of most recent requests `error_rate` is measured over. Must be greater than `0`."
+											required:    false
+											type: uint: {
+												default: 100
+												unit:    "requests"
+											}
+										}
+										open_duration_secs: {
+											common:      false
+											description: "How long the breaker stays open before a request is sent to probe the endpoint."
+											required:    false
+											type: uint: {
+												default: 30
+												unit:    "seconds"
+											}
+										}
+									}
+								}
+							}
 							concurrency: {
 								common: true
 								if features.send.request.adaptive_concurrency {
@@ -355,6 +482,15 @@ components: sinks: [Name=string]: {
 									unit:    null
 								}
 							}
+							retry_budget_ratio: {
+								common:      false
+								description: "The most retries to make, as a share of the requests made over the last ten seconds, on top of ten retries a second. For example, `0.2` allows one retry for every five requests. This stops retries from multiplying the load on a service that is already failing. By default retries aren't budgeted."
+								required:    false
+								type: float: {
+									default: null
+									examples: [0.2]
+								}
+							}
 							retry_initial_backoff_secs: {
 								common:      false
 								description: "The amount of time to wait before attempting the first retry for a failed request. Once, the first retry has failed the fibonacci sequence will be used to select future backoffs."
@@ -364,6 +500,18 @@ components: sinks: [Name=string]: {
 									unit:    "seconds"
 								}
 							}
+							retry_jitter: {
+								common:      false
+								description: "How much of each back-off is randomized."
+								required:    false
+								type: string: {
+									default: "none"
+									enum: {
+										none: "Wait for the whole back-off."
+										full: "Wait for a random time between zero and the back-off, so that requests that failed together aren't retried together."
+									}
+								}
+							}
 							retry_max_duration_secs: {
 								common:      false
 								description: "The maximum amount of time, in seconds, to wait between retries."
@@ -373,6 +521,30 @@ components: sinks: [Name=string]: {
 									unit:    "seconds"
 								}
 							}
+							retry_on_status: {
+								common:      false
+								description: "HTTP statuses to retry requests on, whatever the sink would otherwise do with them. This is only supported by sinks that retry according to the HTTP status of responses. Other sinks, such as the `aws_*`, `azure_*`, `datadog_logs`, `logstash`, `new_relic`, `redis`, and `vector` sinks, fail to start if it's set."
+								required:    false
+								type: array: {
+									default: []
+									items: type: uint: {
+										examples: [400, 409]
+										unit: null
+									}
+								}
+							}
+							no_retry_on_status: {
+								common:      false
+								description: "HTTP statuses not to retry requests on, whatever the sink would otherwise do with them. These take precedence over `retry_on_status`. This is only supported by sinks that retry according to the HTTP status of responses. Other sinks, such as the `aws_*`, `azure_*`, `datadog_logs`, `logstash`, `new_relic`, `redis`, and `vector` sinks, fail to start if it's set."
+								required:    false
+								type: array: {
+									default: []
+									items: type: uint: {
+										examples: [500, 503]
+										unit: null
+									}
+								}
+							}
 							timeout_secs: {
 								common:      true
 								description: "The maximum time a request can take before being aborted. It is highly recommended that you do not lower this value below the service's internal timeout, as this could create orphaned requests, pile on retries, and result in duplicate data downstream."
@@ -516,6 +688,16 @@ components: sinks: [Name=string]: {
 							```
 							"""
 					},
+					{
+						title: "Periodic health checks"
+						body: """
+							Set `healthcheck.interval_secs` to keep running the health check while the sink is running.
+							Only the `http` sink supports it so far; other sinks fail to build with it set.
+							With `healthcheck.required` set too, a sink that fails it is reported as degraded through
+							the `component_degraded` internal metric and the `health` field of the API until it passes
+							again.
+							"""
+					},
 					{
 						title: "Disable health checks"
 						body: """
@@ -640,3 +822,71 @@ components: sinks: [Name=string]: {
 		buffer_discarded_events_total:        components.sources.internal_metrics.output.metrics.buffer_discarded_events_total
 	}
 }
+
+components: sinks: _expire_metrics_secs: {
+	common:      false
+	description: """
+		How long the sink keeps the state of metric series that aren't updated anymore, such as
+		the last value used to convert absolute metrics to incremental ones. Overrides the global
+		[`expire_metrics_secs`](\(urls.vector_configuration)/global-options#expire_metrics_secs)
+		option. Series ended explicitly are always forgotten right away.
+		"""
+	required:    false
+	type: uint: {
+		default: null
+		examples: [3600]
+		unit: "seconds"
+	}
+}
+
+components: sinks: _object_manifest: {
+	category:    "File Naming"
+	common:      false
+	description: """
+		Keep a manifest object in each partition, listing the objects written to it with their
+		number of events and size, so that downstream loaders can find them without listing the
+		partition. The partition of an object is the part of its name up to its last `/`.
+		"""
+	required:    false
+	type: object: options: {
+		filename: {
+			common:      false
+			description: "The name of the manifest object of each partition."
+			required:    false
+			type: string: default: "manifest.json"
+		}
+		idle_timeout_secs: {
+			common:      false
+			description: """
+				How long a partition may go without new objects before its manifest is no longer
+				kept in memory. Objects written to the partition after that are added to the
+				manifest stored in the partition.
+				"""
+			required:    false
+			type: uint: {
+				default: 3600
+				unit:    "seconds"
+			}
+		}
+	}
+}
+
+components: sinks: _object_manifests: {
+	title: "Object manifests"
+	body:  """
+		With the `manifest` option set, Vector rewrites the manifest of a partition after every
+		object it writes to it, so the manifest only ever lists objects that were fully written.
+		It's a JSON object with the `partition`, the `objects` of the partition with their `key`,
+		number of `events` and size in `bytes`, their `total_events` and `total_bytes`, and the
+		time it was `updated_at`.
+
+		The first time Vector writes to a partition, after it starts or after the partition went
+		idle, it reads the manifest already stored there and adds the new objects to it. If that
+		manifest can't be read, the manifest isn't written rather than losing the objects it
+		lists, and it's read again with the next object of the partition.
+
+		Each Vector instance keeps its own manifests, so instances writing to the same partitions
+		should use different manifest filenames. Failing to write a manifest doesn't fail the
+		object itself, since the next object of the partition writes the manifest again.
+		"""
+}
@@ -77,6 +77,7 @@ components: sinks: aws_cloudwatch_metrics: components._aws & {
 	}
 
 	configuration: {
+		expire_metrics_secs: sinks._expire_metrics_secs
 		default_namespace: {
 			description: """
 				A [namespace](https://docs.aws.amazon.com/AmazonCloudWatch/latest/monitoring/cloudwatch_concepts.html#Namespace) that will isolate different metrics from each other.
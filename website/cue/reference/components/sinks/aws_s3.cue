@@ -25,8 +25,8 @@ components: sinks: aws_s3: components._aws & {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
-				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
 			}
 			encoding: {
 				enabled: true
@@ -115,7 +115,7 @@ components: sinks: aws_s3: components._aws & {
 		filename_append_uuid: {
 			category:    "File Naming"
 			common:      false
-			description: "Whether or not to append a UUID v4 token to the end of the file. This ensures there are no name collisions high volume use cases."
+			description: "Whether or not to append a UUID v4 token to the end of the file. This ensures there are no name collisions high volume use cases. Without it, the time in milliseconds is appended instead, and bumped whenever it would repeat a name already used by this Vector instance."
 			required:    false
 			type: bool: default: true
 		}
@@ -189,6 +189,7 @@ components: sinks: aws_s3: components._aws & {
 				syntax: "template"
 			}
 		}
+		manifest: sinks._object_manifest
 		server_side_encryption: {
 			category:    "Encryption"
 			common:      false
@@ -247,6 +248,8 @@ components: sinks: aws_s3: components._aws & {
 	}
 
 	how_it_works: {
+		object_manifests: sinks._object_manifests
+
 		cross_account: {
 			title: "Cross account object writing"
 			body:  """
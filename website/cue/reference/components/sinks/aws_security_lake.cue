@@ -0,0 +1,167 @@
+package metadata
+
+components: sinks: aws_security_lake: components._aws & {
+	title: "Amazon Security Lake"
+
+	description: """
+		Writes [OCSF](\(urls.ocsf)) events to an Amazon Security Lake
+		[custom source](\(urls.aws_security_lake_custom_sources)) as Parquet files, laid out the way Security Lake
+		expects.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["AWS"]
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    100000000
+				timeout_secs: 300
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			proxy: enabled:       true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.aws_security_lake
+
+				interface: {
+					socket: {
+						api: {
+							title:            "AWS S3 API"
+							url:   urls.aws_s3_endpoints
+						}
+						direction:        "outgoing"
+						protocols: ["http"]
+						ssl:              "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				The custom source must already be registered with Security Lake, which creates the Glue table and
+				the IAM role Vector writes with.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		account_id: {
+			description: "The AWS account the events come from. Security Lake partitions custom source data by account."
+			required:    true
+			type: string: {
+				examples: ["111122223333"]
+			}
+		}
+		bucket: {
+			description: "The Security Lake S3 bucket."
+			required:    true
+			type: string: {
+				examples: ["aws-security-data-lake-us-east-1-abcdefghijklmnopqrstuvwxyz1234"]
+			}
+		}
+		ocsf_class: {
+			description: "The OCSF event class the custom source was registered with. Events that aren't of this class, or are missing attributes the class requires, are rejected."
+			required:    true
+			type: string: {
+				enum: {
+					file_activity:    "File System Activity (`class_uid` 1001)."
+					process_activity: "Process Activity (`class_uid` 1007)."
+					security_finding: "Security Finding (`class_uid` 2001)."
+					account_change:   "Account Change (`class_uid` 3001)."
+					authentication:   "Authentication (`class_uid` 3002)."
+					network_activity: "Network Activity (`class_uid` 4001)."
+					http_activity:    "HTTP Activity (`class_uid` 4002)."
+					dns_activity:     "DNS Activity (`class_uid` 4003)."
+					api_activity:     "API Activity (`class_uid` 6003)."
+				}
+			}
+		}
+		source_name: {
+			description: "The name the custom source was registered with."
+			required:    true
+			type: string: {
+				examples: ["my-source"]
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		object_layout: {
+			title: "Object layout"
+			body: """
+				Objects are written under
+				`ext/<source_name>/region=<region>/accountId=<account_id>/eventDay=<YYYYMMDD>/`, where the day
+				comes from the event's OCSF `time` attribute, as Security Lake requires of custom sources.
+				"""
+		}
+		parquet_schema: {
+			title: "Parquet schema"
+			body: """
+				The Parquet schema of each object is inferred from the events in it. Objects become groups and
+				scalars become columns of the matching type, while arrays, and attributes whose type differs between
+				events of the same object, are written as JSON strings.
+				"""
+		}
+		validation: {
+			title: "Validation"
+			body: """
+				Each event is checked against the configured `ocsf_class`: its `class_uid` and `category_uid` must
+				match the class, and the attributes OCSF requires of every event, along with those the class
+				requires, must be set. Events that fail are dropped, and reported as rejected when acknowledgements
+				are enabled.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform:      "aws"
+			_service:      "s3"
+			_docs_tag:     "AmazonS3"
+			_url_fragment: "API"
+
+			policies: [
+				{
+					_action:          "HeadBucket"
+					required_for: ["healthcheck"]
+				},
+				{
+					_action:          "PutObject"
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+	}
+}
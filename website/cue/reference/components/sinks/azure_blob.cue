@@ -25,8 +25,8 @@ components: sinks: azure_blob: {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
-				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
 			}
 			encoding: {
 				enabled: true
@@ -68,7 +68,7 @@ components: sinks: azure_blob: {
 
 	configuration: {
 		connection_string: {
-			description: "The Azure Blob Storage Account connection string. Only authentication with access key supported."
+			description: "The Azure Blob Storage Account connection string. Only authentication with access key, or with a SAS token from [`sas_token_command`](#sas_token_command), is supported."
 			required:    true
 			type: string: {
 				examples: ["DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"]
@@ -95,20 +95,53 @@ components: sinks: azure_blob: {
 		blob_append_uuid: {
 			category:    "File Naming"
 			common:      false
-			description: "Whether or not to append a UUID v4 token to the end of the file. This ensures there are no name collisions high volume use cases."
+			description: "Whether or not to append a UUID v4 token to the end of the file. This ensures there are no name collisions high volume use cases. Without it, the time in milliseconds is appended instead, and bumped whenever it would repeat a name already used by this Vector instance. Can't be enabled with append blobs."
 			required:    false
 			type: bool: default: true
 		}
 		blob_time_format: {
 			category:    "File Naming"
 			common:      false
-			description: "The format of the resulting object file name. [`strftime` specifiers](\(urls.strptime_specifiers)) are supported."
+			description: "The format of the resulting object file name. [`strftime` specifiers](\(urls.strptime_specifiers)) are supported. With append blobs, this defaults to `%FT%H`, so that batches are appended to a blob per hour."
 			required:    false
 			type: string: {
 				default: "%s"
 				syntax:  "strftime"
 			}
 		}
+		blob_type: {
+			common:      false
+			description: "The type of the blobs to write."
+			required:    false
+			type: string: {
+				default: "block"
+				enum: {
+					block:  "Write every batch as a block blob of its own."
+					append: "Append batches to an append blob, named after the time without a UUID, so that every batch written within the resolution of [`blob_time_format`](#blob_time_format) goes to the same blob."
+				}
+			}
+		}
+		manifest: sinks._object_manifest
+		sas_token_command: {
+			category:    "Auth"
+			common:      false
+			description: "A shell command printing a SAS token to authenticate with, instead of the credentials in the connection string. This lets the token come from a secrets backend. The command is run again every [`sas_token_refresh_secs`](#sas_token_refresh_secs) seconds, to pick up rotated tokens."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vault kv get -field=sas_token secret/azure/logs"]
+			}
+		}
+		sas_token_refresh_secs: {
+			category:    "Auth"
+			common:      false
+			description: "How often, in seconds, [`sas_token_command`](#sas_token_command) is run again. If it fails, the previous token keeps being used."
+			required:    false
+			type: uint: {
+				default: 3600
+				unit:    "seconds"
+			}
+		}
 	}
 
 	input: {
@@ -117,6 +150,34 @@ components: sinks: azure_blob: {
 	}
 
 	how_it_works: {
+		append_blobs: {
+			title: "Append blobs"
+			body:  """
+				With [`blob_type`](#blob_type) set to `append`, batches are appended to an append blob
+				rather than written as many small block blobs. Blobs are named after the time without a
+				UUID, so by default every batch written within the same hour goes to the same blob.
+
+				Azure limits append blobs to 50,000 blocks of at most 4 MiB each. Vector splits larger
+				batches into several blocks, and once a blob is full it moves on to a new blob, named
+				after the original one with a `-1`, `-2`, ... suffix. Blobs created with compression
+				enabled are gzip streams made of several members, which most tools read as one.
+				"""
+		}
+
+		object_manifests: sinks._object_manifests
+
+		sas_token_rotation: {
+			title: "SAS token rotation"
+			body:  """
+				With [`sas_token_command`](#sas_token_command), Vector authenticates with the SAS token
+				printed by the command rather than with the credentials in the connection string. The
+				command is run when the sink starts, and again every
+				[`sas_token_refresh_secs`](#sas_token_refresh_secs) seconds, so that tokens rotated by a
+				secrets backend are picked up without restarting Vector. Requests already in flight
+				finish with the token they started with.
+				"""
+		}
+
 		object_naming: {
 			title: "Object naming"
 			body:  """
@@ -0,0 +1,163 @@
+package metadata
+
+components: sinks: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	description: """
+		Publishes events to an [Azure Event Hubs](\(urls.azure_event_hubs)) event hub directly over AMQP 1.0,
+		authenticating with either a shared access key or the [managed identity](\(urls.azure_managed_identity))
+		of the Azure resource Vector runs on.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: ["Azure"]
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1000000
+				max_events:   null
+				timeout_secs: 1
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text", "ndjson"]
+				}
+			}
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: enabled: false
+			to: {
+				service: services.azure_event_hubs
+				interface: {
+					socket: {
+						api: {
+							title: "AMQP 1.0"
+							url:   urls.azure_event_hubs_amqp
+						}
+						direction: "outgoing"
+						port:      5671
+						protocols: ["tcp"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	configuration: {
+		connection_string: {
+			common:      true
+			description: "The connection string of a shared access policy on the namespace or event hub, with the `Send` claim. Exactly one of `connection_string` or `managed_identity` must be set."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["Endpoint=sb://my-namespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=keybase64encoded;EntityPath=my-event-hub"]
+			}
+		}
+		event_hub: {
+			common:      true
+			description: "The event hub to publish to. Defaults to the `EntityPath` of the connection string, and is required with `managed_identity`."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["my-event-hub"]
+			}
+		}
+		managed_identity: {
+			common:      false
+			description: "Authenticate with the managed identity of the Azure resource Vector runs on, using a token from the Azure Instance Metadata Service. The identity needs the `Azure Event Hubs Data Sender` role. Exactly one of `connection_string` or `managed_identity` must be set."
+			required:    false
+			type: object: options: {
+				client_id: {
+					common:      false
+					description: "The client ID of the user assigned identity to use. The system assigned identity is used if unset."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["00000000-0000-0000-0000-000000000000"]
+					}
+				}
+			}
+		}
+		namespace: {
+			common:      false
+			description: "The fully qualified Event Hubs namespace. Required with `managed_identity`, and ignored otherwise."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["my-namespace.servicebus.windows.net"]
+			}
+		}
+		partition_key_field: {
+			common:      true
+			description: "The log field or metric tag to use as the partition key. Events with the same key are always sent to the same partition. If unset, or the field is missing, Event Hubs spreads events across partitions."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["user_id"]
+			}
+		}
+	}
+
+	how_it_works: {
+		batching: {
+			title: "Batching"
+			body: """
+				Each event is sent as its own message, but the messages of a batch are sent without waiting for each
+				other, and the batch is only considered delivered once Event Hubs has accepted all of them. Batches are
+				limited to 1MB, which is the most Event Hubs accepts in one publish, and events that encode to more than
+				that are dropped. If any message is rejected, the whole batch is sent again over a new connection, so
+				events may be delivered more than once.
+				"""
+		}
+		managed_identity: {
+			title: "Managed identity"
+			body: """
+				With `managed_identity`, Vector gets an Azure AD token from the Azure Instance Metadata Service and
+				authorizes the connection with it using claims based security. Tokens are only valid for a limited
+				time, so the connection is replaced with a freshly authorized one a few minutes before the token
+				expires.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
@@ -30,6 +30,86 @@ components: sinks: blackhole: {
 	}
 
 	configuration: {
+		ack_delay_ms: {
+			common:      false
+			description: "How long to wait after each request succeeds before acknowledging its events, as a slow downstream service would. Events are still taken in the meantime."
+			required:    false
+			type: uint: {
+				default: 0
+				unit:    "milliseconds"
+			}
+		}
+		backpressure_duration_ms: {
+			common:      false
+			description: "How long to stop taking events for after a request, for the share of requests set by `backpressure_rate`."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "milliseconds"
+			}
+		}
+		backpressure_rate: {
+			common:      false
+			description: "The share of requests, between `0` and `1`, after which the sink stops taking events for `backpressure_duration_ms`."
+			required:    false
+			type: float: default: 0.0
+		}
+		error_rate: {
+			common:      false
+			description: "The share of requests, at least `0` and less than `1`, that fail. Failed requests are sent again, after another `latency`, until they succeed."
+			required:    false
+			type: float: default: 0.0
+		}
+		latency: {
+			common:      false
+			description: "How long each request takes. Each chunk of events received at once, of at most `rate` events, makes up a request."
+			required:    false
+			type: object: {
+				examples: [{distribution: "normal", mean_ms: 100.0, stddev_ms: 20.0}]
+				options: {
+					distribution: {
+						description: "The distribution latencies are drawn from."
+						required:    true
+						type: string: enum: {
+							constant:    "Every request takes `ms`."
+							exponential: "Requests take `mean_ms` on average, with occasional much slower ones."
+							normal:      "Requests take `mean_ms` on average, with a standard deviation of `stddev_ms`."
+							uniform:     "Requests take between `min_ms` and `max_ms`."
+						}
+					}
+					max_ms: {
+						description:   "The longest a request takes."
+						relevant_when: "distribution = \"uniform\""
+						required:      true
+						type: uint: unit: "milliseconds"
+					}
+					mean_ms: {
+						description:   "How long requests take on average, in milliseconds."
+						relevant_when: "distribution = \"normal\" or distribution = \"exponential\""
+						required:      true
+						type: float: examples: [100.0]
+					}
+					min_ms: {
+						description:   "The shortest a request takes."
+						relevant_when: "distribution = \"uniform\""
+						required:      true
+						type: uint: unit: "milliseconds"
+					}
+					ms: {
+						description:   "How long every request takes."
+						relevant_when: "distribution = \"constant\""
+						required:      true
+						type: uint: unit: "milliseconds"
+					}
+					stddev_ms: {
+						description:   "The standard deviation of how long requests take, in milliseconds."
+						relevant_when: "distribution = \"normal\""
+						required:      true
+						type: float: examples: [20.0]
+					}
+				}
+			}
+		}
 		print_interval_secs: {
 			common:      false
 			description: "The number of seconds between reporting a summary of activity."
@@ -58,7 +138,9 @@ components: sinks: blackhole: {
 	}
 
 	telemetry: metrics: {
-		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
-		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
+		injected_backpressure_total: components.sources.internal_metrics.output.metrics.injected_backpressure_total
+		injected_errors_total:       components.sources.internal_metrics.output.metrics.injected_errors_total
+		processed_bytes_total:       components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:      components.sources.internal_metrics.output.metrics.processed_events_total
 	}
 }
@@ -134,10 +134,11 @@ components: sinks: datadog_archives: {
 			type: object: {
 				examples: []
 				options: {
-					acl:              sinks.gcp_cloud_storage.configuration.acl
-					credentials_path: sinks.gcp_cloud_storage.configuration.credentials_path
-					metadata:         sinks.gcp_cloud_storage.configuration.metadata
-					storage_class:    sinks.gcp_cloud_storage.configuration.storage_class
+					acl:                         sinks.gcp_cloud_storage.configuration.acl
+					credentials_path:            sinks.gcp_cloud_storage.configuration.credentials_path
+					impersonate_service_account: sinks.gcp_cloud_storage.configuration.impersonate_service_account
+					metadata:                    sinks.gcp_cloud_storage.configuration.metadata
+					storage_class:               sinks.gcp_cloud_storage.configuration.storage_class
 				}
 			}
 		}
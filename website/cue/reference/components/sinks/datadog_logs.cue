@@ -19,8 +19,8 @@ components: sinks: datadog_logs: {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
-				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
 			}
 			encoding: {
 				enabled: true
@@ -68,6 +68,40 @@ components: sinks: datadog_logs: {
 			}
 		}
 		endpoint: sinks._datadog.configuration.endpoint
+		exclusion_filters: {
+			common:      false
+			description: """
+				Fetches the exclusion filters of the log indexes of your Datadog organization and
+				drops the logs they exclude before sending them. Excluded logs are never sent to
+				Datadog, so they don't show up in Live Tail, archives, or metrics generated from
+				logs, unlike logs excluded by Datadog itself.
+				"""
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					application_key: {
+						description: "The Datadog [application key](https://docs.datadoghq.com/account_management/api-app-keys/#application-keys) used to read the configuration of the log indexes."
+						required:    true
+						warnings: []
+						type: string: {
+							examples: ["${DATADOG_APP_KEY_ENV_VAR}"]
+						}
+					}
+					refresh_interval_secs: {
+						common:      false
+						description: "How often the exclusion filters are fetched again. If fetching them fails, the previous filters are kept. No logs are excluded until they are fetched successfully."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 300
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
 		region:   sinks._datadog.configuration.region
 		site:     sinks._datadog.configuration.site
 	}
@@ -76,4 +110,9 @@ components: sinks: datadog_logs: {
 		logs:    true
 		metrics: null
 	}
+
+	telemetry: metrics: {
+		events_excluded_total:   components.sources.internal_metrics.output.metrics.events_excluded_total
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
 }
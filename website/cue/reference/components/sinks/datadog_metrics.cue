@@ -53,6 +53,7 @@ components: sinks: datadog_metrics: {
 		endpoint: sinks._datadog.configuration.endpoint
 		region:   sinks._datadog.configuration.region
 		site:     sinks._datadog.configuration.site
+		expire_metrics_secs: sinks._expire_metrics_secs
 		default_namespace: {
 			common: true
 			description: """
@@ -32,6 +32,15 @@ components: sinks: [Name=string]: {
 
 						If credentials aren't found, Vector's health checks fail and an error is
 						[logged](\(urls.vector_monitoring)).
+
+						Credentials files can be either service account keys or `external_account`
+						configurations for [workload identity federation](https://cloud.google.com/iam/docs/workload-identity-federation),
+						reading the token of the other identity provider from a file or a URL. If the
+						[`impersonate_service_account`](#impersonate_service_account) option is set, the credentials
+						found are used to impersonate that service account instead.
+
+						Components using the same credentials share their token, which is renewed about halfway
+						through its lifetime.
 						"""
 			}
 		}
@@ -25,8 +25,8 @@ components: sinks: gcp_cloud_storage: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["gzip"]
-				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+				algorithms: ["gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
 			}
 			encoding: {
 				enabled: true
@@ -108,10 +108,19 @@ components: sinks: gcp_cloud_storage: {
 				examples: ["/path/to/credentials.json"]
 			}
 		}
+		impersonate_service_account: {
+			common:      false
+			description: "The email of a service account to impersonate, using the credentials found as described in [GCP authentication](#gcp-authentication). The account authenticating needs the `roles/iam.serviceAccountTokenCreator` role on the impersonated service account."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector@my-project.iam.gserviceaccount.com"]
+			}
+		}
 		filename_append_uuid: {
 			category:    "File Naming"
 			common:      false
-			description: "Whether or not to append a UUID v4 token to the end of the file. This ensures there are no name collisions high volume use cases."
+			description: "Whether or not to append a UUID v4 token to the end of the file. This ensures there are no name collisions high volume use cases. Without it, the time in milliseconds is appended instead, and bumped whenever it would repeat a name already used by this Vector instance."
 			required:    false
 			type: bool: default: true
 		}
@@ -144,6 +153,7 @@ components: sinks: gcp_cloud_storage: {
 				syntax: "template"
 			}
 		}
+		manifest: sinks._object_manifest
 		metadata: {
 			common:      false
 			description: "The set of metadata `key:value` pairs for the created objects. See the [GCS custom metadata](\(urls.gcs_custom_metadata)) documentation for more details."
@@ -176,6 +186,8 @@ components: sinks: gcp_cloud_storage: {
 	}
 
 	how_it_works: {
+		object_manifests: sinks._object_manifests
+
 		object_access_control_list: {
 			title: "Object access control list (ACL)"
 			body:  """
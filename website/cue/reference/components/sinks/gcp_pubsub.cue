@@ -83,6 +83,15 @@ components: sinks: gcp_pubsub: {
 				examples: ["/path/to/credentials.json"]
 			}
 		}
+		impersonate_service_account: {
+			common:      false
+			description: "The email of a service account to impersonate, using the credentials found as described in [GCP authentication](#gcp-authentication). The account authenticating needs the `roles/iam.serviceAccountTokenCreator` role on the impersonated service account."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector@my-project.iam.gserviceaccount.com"]
+			}
+		}
 		endpoint: {
 			common:      false
 			description: "The endpoint to which to send data."
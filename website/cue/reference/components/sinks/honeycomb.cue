@@ -69,7 +69,63 @@ components: sinks: honeycomb: {
 			description: "The dataset that Vector will send logs to."
 			required:    true
 			type: string: {
-				examples: ["my-honeycomb-dataset"]
+				examples: ["my-honeycomb-dataset", "{{ service }}-logs"]
+				syntax: "template"
+			}
+		}
+		sample_rate_key: {
+			common:      false
+			description: """
+				The field holding the rate the event was sampled at. The field is removed from the event and sent
+				as its sample rate, which Honeycomb weights the event by. Values that aren't a positive integer are
+				ignored.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["sample_rate"]
+			}
+		}
+		markers: {
+			common:      false
+			description: "Creates a [marker](\(urls.honeycomb_markers)) in the event's dataset for each event that matches `condition`, such as deploys. The events are also sent to the dataset."
+			required:    false
+			type: object: options: {
+				condition: {
+					description: "The condition an event has to match for a marker to be created."
+					required:    true
+					type: condition: {}
+				}
+				message: {
+					description: "The message of the marker."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["Deployed {{ version }}"]
+						syntax: "template"
+					}
+				}
+				type: {
+					description: "The type of the marker, which markers are grouped by."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["deploy"]
+						syntax: "template"
+					}
+				}
+				url: {
+					description: "A URL linked from the marker."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["https://github.com/vectordotdev/vector/releases/tag/v{{ version }}"]
+						syntax: "template"
+					}
+				}
 			}
 		}
 	}
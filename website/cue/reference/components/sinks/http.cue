@@ -25,8 +25,8 @@ components: sinks: http: {
 			compression: {
 				enabled: true
 				default: "none"
-				algorithms: ["none", "gzip"]
-				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
 			}
 			encoding: {
 				enabled: true
@@ -78,6 +78,43 @@ components: sinks: http: {
 			password_example: "${HTTP_PASSWORD}"
 			username_example: "${HTTP_USERNAME}"
 		}}
+		templates: {
+			common:      false
+			description: "Parts of each request that are rendered from the events it's made of. Events are batched separately for each distinct request they render, and events that can't be rendered into a valid request are dropped."
+			required:    false
+			type: object: options: {
+				headers: {
+					common:      false
+					description: "Headers rendered from each event. They can't also be set in `request.headers`."
+					required:    false
+					type: object: {
+						examples: [{"X-Tenant": "{{ tenant }}"}]
+						options: {}
+					}
+				}
+				method: {
+					common:      false
+					description: "The method of each request, such as `put`, rendered from each event. It overrides `method`."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["{{ operation }}"]
+						syntax: "template"
+					}
+				}
+				path: {
+					common:      false
+					description: "The path, and optionally the query, of each request, appended to `uri`. Characters that aren't allowed in URIs, such as spaces, are percent-encoded."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["/{{ index }}/_doc?pipeline={{ pipeline }}"]
+						syntax: "template"
+					}
+				}
+			}
+		}
+		http2: configuration._http2 & {_args: server: false}
 		uri: {
 			description: """
 				The full URI to make HTTP requests to. This should include the protocol and host,
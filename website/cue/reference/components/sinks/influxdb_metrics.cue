@@ -43,6 +43,7 @@ components: sinks: influxdb_metrics: {
 	}
 
 	configuration: sinks._influxdb.configuration & {
+		expire_metrics_secs: sinks._expire_metrics_secs
 		default_namespace: {
 			common: true
 			description: """
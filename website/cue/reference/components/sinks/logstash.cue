@@ -0,0 +1,112 @@
+package metadata
+
+components: sinks: logstash: {
+	_port: 5044
+
+	title: "Logstash"
+
+	description: """
+		Sends logs to [Logstash](\(urls.logstash)), or anything else that accepts events from Elastic Beats, using
+		version 2 of the [Lumberjack protocol](\(urls.logstash_protocol)).
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_events:   2048
+				timeout_secs: 1
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			send_buffer_bytes: enabled: true
+			keepalive: enabled:         true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.logstash
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	configuration: {
+		address: {
+			description: "The address of the Logstash `beats` input to connect to. The address _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["92.12.333.224:\(_port)"]
+			}
+		}
+		compression_level: {
+			common:      false
+			description: "The zlib compression level used for each window of events, from `0` (no compression) to `9`."
+			required:    false
+			type: uint: {
+				default: 3
+				unit:    null
+			}
+		}
+	}
+
+	how_it_works: {
+		windows: {
+			title: "Windows and acknowledgements"
+			body: """
+				Each batch of events is sent as a single window, and is only considered delivered once the server has
+				acknowledged the last event in the window. Partial acknowledgements and keepalives sent by the server
+				while its pipeline is blocked are waited through, up to `request.timeout_secs`. If the connection fails,
+				or the window isn't acknowledged in time, the connection is closed and the whole window is sent again
+				on a new connection, so events may be delivered more than once.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		encode_errors_total:              components.sources.internal_metrics.output.metrics.encode_errors_total
+	}
+}
@@ -25,8 +25,8 @@ components: sinks: new_relic: {
 			compression: {
 				enabled: true
 				default: "gzip"
-				algorithms: ["none", "gzip"]
-				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+				algorithms: ["none", "gzip", "zlib", "zstd"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21]
 			}
 			encoding: {
 				enabled: true
@@ -45,7 +45,7 @@ components: sinks: new_relic: {
 				interface: {
 					socket: {
 						api: {
-							title: "New Relic Event, Metric and Log API"
+							title: "New Relic Event, Metric, Log and Trace API"
 							url:   urls.new_relic_apis
 						}
 						direction: "outgoing"
@@ -90,8 +90,9 @@ components: sinks: new_relic: {
 			type: string: {
 				default: "us"
 				enum: {
-					us: "United States"
-					eu: "Europe"
+					us:      "United States"
+					eu:      "Europe"
+					fedramp: "The FedRAMP-compliant endpoints"
 				}
 				syntax: "literal"
 			}
@@ -105,10 +106,52 @@ components: sinks: new_relic: {
 					events:  "Event API"
 					metrics: "Metric API"
 					logs:    "Log API"
+					traces:  "Trace API"
 				}
 				syntax: "literal"
 			}
 		}
+		trace_format: {
+			common:      false
+			description: "The format of the spans sent to the Trace API."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "newrelic"
+				enum: {
+					newrelic: "New Relic's format. Each log event is a span with `id` and `trace.id` fields, and its other fields are sent as the span's attributes. Events without both IDs are dropped."
+					zipkin:   "Each log event is a Zipkin v2 span, which is sent as is."
+				}
+				syntax: "literal"
+			}
+		}
+		common_attributes: {
+			common:      false
+			description: "Attributes added to every metric, log, or span of a payload, sent once in the payload's `common` block. Not supported by the Event API or the `zipkin` trace format."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [{"service.name": "checkout", "environment": "production"}]
+				options: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		metrics: {
+			title: "Metrics"
+			body: """
+				Gauges and counters are sent to the Metric API as gauges, and distributions as summaries of their
+				samples. Metric tags are sent as the metric's attributes. Other metric types are dropped.
+				"""
+		}
+		compression: {
+			title: "Compression"
+			body: """
+				Each payload is only compressed if that makes it smaller, which small payloads often aren't. The
+				`Content-Encoding` header of each request says whether its payload was compressed.
+				"""
+		}
 	}
 
 	input: {
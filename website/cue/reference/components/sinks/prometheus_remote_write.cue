@@ -88,6 +88,7 @@ components: sinks: prometheus_remote_write: {
 			password_example: "${HTTP_PASSWORD}"
 			username_example: "${HTTP_USERNAME}"
 		}}
+		expire_metrics_secs: sinks._expire_metrics_secs
 		default_namespace: {
 			common:      true
 			description: """
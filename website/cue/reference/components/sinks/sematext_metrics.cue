@@ -48,6 +48,7 @@ components: sinks: sematext_metrics: {
 	}
 
 	configuration: sinks._sematext.configuration & {
+		expire_metrics_secs: sinks._expire_metrics_secs
 		default_namespace: {
 			description: "Used as a namespace for metrics that don't have it."
 			required:    true
@@ -78,12 +78,92 @@ components: sinks: vector: {
 
 	configuration: {
 		address: {
-			description: "The downstream Vector address to connect to. The address _must_ include a port."
-			required:    true
+			description: "The downstream Vector address to connect to. The address _must_ include a port. Exactly one of `address` or `endpoints` must be set."
+			required:    false
+			common:      true
 			type: string: {
 				examples: ["92.12.333.224:\(_port)"]
 			}
 		}
+		compression: {
+			common:        false
+			description:   "Compress each batch of events with zstd. If the downstream Vector is too old to support compressed requests, batches are sent uncompressed instead. The downstream Vector rejects compressed batches that decompress to more than 4 MiB."
+			relevant_when: "version = \"2\""
+			required:      false
+			type: bool: default: false
+		}
+		event_arrays: {
+			common:        false
+			description:   "Send each batch as arrays of consecutive events of the same type, which the downstream Vector forwards together. If the downstream Vector is too old to support event arrays, events are sent individually instead."
+			relevant_when: "version = \"2\""
+			required:      false
+			type: bool: default: false
+		}
+		endpoints: {
+			common:        false
+			description:   "Several downstream Vector instances to balance events across, instead of a single `address`."
+			relevant_when: "version = \"2\""
+			required:      false
+			type: array: {
+				default: null
+				items: type: object: options: {
+					address: {
+						description: "The downstream Vector address to connect to. The address _must_ include a port."
+						required:    true
+						type: string: {
+							examples: ["92.12.333.224:\(_port)"]
+						}
+					}
+					weight: {
+						common:      false
+						description: "The share of requests sent to this endpoint, relative to the weights of the other endpoints."
+						required:    false
+						type: uint: {
+							default: 1
+							unit:    null
+						}
+					}
+				}
+			}
+		}
+		load_balancing: {
+			common:        false
+			description:   "Options for balancing events across `endpoints`."
+			relevant_when: "endpoints is set"
+			required:      false
+			type: object: options: {
+				strategy: {
+					common:      false
+					description: "How to pick the endpoint each request is sent to."
+					required:    false
+					type: string: {
+						default: "round_robin"
+						enum: {
+							round_robin:       "Send requests to each endpoint in turn, in proportion to their weights."
+							least_outstanding: "Send each request to the endpoint with the fewest requests in flight, relative to its weight."
+						}
+					}
+				}
+				health_check_interval_secs: {
+					common:      false
+					description: "How often the health of each endpoint is checked. Ejected endpoints are readmitted once a health check succeeds. Must be greater than 0."
+					required:    false
+					type: uint: {
+						default: 10
+						unit:    "seconds"
+					}
+				}
+				failure_threshold: {
+					common:      false
+					description: "The number of consecutive failed requests after which an endpoint is ejected."
+					required:    false
+					type: uint: {
+						default: 3
+						unit:    null
+					}
+				}
+			}
+		}
 		version: {
 			description: "Sink API version. Specifying this version ensures that Vector does not break backward compatibility."
 			common:      true
@@ -99,7 +179,18 @@ components: sinks: vector: {
 		}
 	}
 
-	how_it_works: components.sources.vector.how_it_works
+	how_it_works: components.sources.vector.how_it_works & {
+		load_balancing: {
+			title: "Load balancing"
+			body: """
+				With `endpoints`, requests are balanced across several downstream Vector instances, such as an aggregator
+				tier, without an external load balancer. An endpoint is ejected after `load_balancing.failure_threshold`
+				consecutive requests fail with a retriable error, or when a periodic health check fails, and is readmitted
+				once a health check succeeds. A failed request is retried on the next endpoint. If every endpoint has been
+				ejected, requests are sent to all of them until one recovers.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
@@ -0,0 +1,255 @@
+package metadata
+
+components: sinks: webhook: {
+	title: "Webhook"
+
+	description: """
+		Sends batches of events to webhooks. Unlike the `http` sink, each event is rendered into the body with a
+		template, events are batched separately for each URL they're sent to, and request bodies can be signed with
+		[HMAC](\(urls.hmac)) so that receivers can verify them.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       true
+				max_bytes:    10_000_000
+				timeout_secs: 1
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: enabled: false
+			}
+			proxy: enabled: true
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: {
+					name:     "HTTP"
+					thing:    "an \(name) server"
+					url:      urls.http_server
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	configuration: {
+		auth: configuration._http_auth & {_args: {
+			password_example: "${WEBHOOK_PASSWORD}"
+			username_example: "${WEBHOOK_USERNAME}"
+		}}
+		body: {
+			common:      true
+			description: "How the body of each request is rendered."
+			required:    false
+			type: object: options: {
+				content_type: {
+					common:      false
+					description: "The `Content-Type` header of each request."
+					required:    false
+					type: string: {
+						default: "application/json"
+						examples: ["text/plain"]
+					}
+				}
+				separator: {
+					common:      false
+					description: "What the rendered events of a batch are joined with."
+					required:    false
+					type: string: {
+						default: "\\n"
+						examples: [","]
+					}
+				}
+				template: {
+					common:      true
+					description: "The template each event is rendered with. Without one, events are encoded as JSON."
+					required:    false
+					type: string: {
+						default: null
+						examples: [#"{"text": "{{ host }}: {{ message }}"}"#]
+						syntax: "template"
+					}
+				}
+				wrapper: {
+					common:      false
+					description: "Wraps the rendered events of each batch, which replace its `{{ events }}`. It must contain `{{ events }}` exactly once."
+					required:    false
+					type: string: {
+						default: null
+						examples: [#"{"events": [{{ events }}]}"#]
+					}
+				}
+			}
+		}
+		headers: {
+			common:      false
+			description: "Headers to add to each request."
+			required:    false
+			type: object: {
+				examples: [{"X-Powered-By": "Vector"}]
+				options: {}
+			}
+		}
+		max_idle_connections_per_host: {
+			common:      false
+			description: "The most idle connections kept open to each host that events are sent to."
+			required:    false
+			type: uint: {
+				default: 8
+				unit:    null
+			}
+		}
+		method: {
+			common:      false
+			description: "The HTTP method of each request."
+			required:    false
+			type: string: {
+				default: "post"
+				enum: {
+					post:  "`POST`"
+					put:   "`PUT`"
+					patch: "`PATCH`"
+				}
+			}
+		}
+		signing: {
+			common:      false
+			description: "Signs the body of each request with HMAC, into a header."
+			required:    false
+			type: object: options: {
+				algorithm: {
+					common:      false
+					description: "The hash function HMAC is used with."
+					required:    false
+					type: string: {
+						default: "sha256"
+						enum: {
+							sha1:   "SHA-1"
+							sha256: "SHA-256"
+							sha512: "SHA-512"
+						}
+					}
+				}
+				encoding: {
+					common:      false
+					description: "How the signature is encoded in the header."
+					required:    false
+					type: string: {
+						default: "hex"
+						enum: {
+							hex:    "Lowercase hexadecimal."
+							base64: "Base64, with padding."
+						}
+					}
+				}
+				header: {
+					description: "The header the signature is sent in."
+					required:    true
+					type: string: {
+						examples: ["X-Hub-Signature-256"]
+					}
+				}
+				prefix: {
+					common:      false
+					description: "Put before the signature in the header."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["sha256="]
+					}
+				}
+				secret: {
+					description: "The secret the body is signed with."
+					required:    true
+					type: string: {
+						examples: ["${WEBHOOK_SECRET}"]
+					}
+				}
+			}
+		}
+		uri: {
+			description: "The URI to send each event to. Events are batched separately for each URI they render to."
+			required:    true
+			type: string: {
+				examples: ["https://hooks.example.com/{{ team }}"]
+				syntax: "template"
+			}
+		}
+	}
+
+	how_it_works: {
+		batching: {
+			title: "Batching by URI"
+			body: """
+				Each event is sent to the `uri` rendered from it, and events are batched separately for each
+				URI. Events that `uri` can't be rendered for are dropped. Connections are pooled for each host,
+				with at most `max_idle_connections_per_host` idle connections kept open to it.
+				"""
+		}
+		signing: {
+			title: "Signing"
+			body: """
+				When `signing` is set, the HMAC of the whole body of each request, including its wrapper, is sent in
+				`signing.header`. For example, the `X-Hub-Signature-256` header GitHub sends can be reproduced with
+				`signing.algorithm = "sha256"` and `signing.prefix = "sha256="`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+		processed_bytes_total:            components.sources.internal_metrics.output.metrics.processed_bytes_total
+	}
+}
@@ -0,0 +1,199 @@
+package metadata
+
+components: sinks: websocket_server: {
+	_port: 8080
+
+	title: "WebSocket Server"
+
+	description: """
+		Serves events to the [WebSocket](\(urls.websocket)) clients connected to it, each of which
+		can subscribe to only the events matching a filter. This is well suited to live debugging
+		dashboards, which want to watch events as they flow through Vector.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "expose"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      false
+		healthcheck: enabled: false
+		exposes: {
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+
+			for: {
+				service: {
+					name:     "WebSocket"
+					thing:    "\(name) clients"
+					url:      urls.websocket
+					versions: null
+				}
+
+				interface: {
+					socket: {
+						direction: "incoming"
+						port:      _port
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		address: {
+			description: "The address to listen for WebSocket connections on."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+		auth: configuration._http_auth & {_args: {
+			password_example: "${WEBSOCKET_PASSWORD}"
+			username_example: "${WEBSOCKET_USERNAME}"
+		}}
+		encoding: {
+			description: "Configures the encoding specific sink behavior."
+			required:    true
+			type: object: options: {
+				codec: {
+					description: "The encoding codec used to serialize each event into a message."
+					required:    true
+					type: string: {
+						enum: {
+							json: "JSON encoded event."
+							text: "The message field from the event."
+						}
+					}
+				}
+			}
+		}
+		max_clients: {
+			common:      false
+			description: "How many clients can be connected at once. Further clients are rejected with a `503 Service Unavailable` response. Must be greater than `0`."
+			required:    false
+			type: uint: {
+				default: 100
+				unit:    null
+			}
+		}
+		max_queued_messages: {
+			common:      false
+			description: "How many messages can be queued for a client before it's considered too slow, and [`slow_client_policy`](#slow_client_policy) applies."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "events"
+			}
+		}
+		slow_client_policy: {
+			common:      false
+			description: "What to do with clients that don't keep up with the events sent to them."
+			required:    false
+			type: string: {
+				default: "drop_messages"
+				enum: {
+					drop_messages: "Drop the events that don't fit in the queue of the client, and keep it connected."
+					disconnect:    "Disconnect the client."
+				}
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	how_it_works: {
+		subscriptions: {
+			title: "Subscriptions"
+			body:  """
+				Clients subscribe by connecting to the root path of the sink. By default, they're sent
+				every event the sink receives, each encoded as a text message of its own. To only
+				receive some of them, clients can pass a [VRL boolean expression](\(urls.vrl_boolean_expression))
+				as the `filter` query parameter, such as:
+
+				```text
+				ws://localhost:\(_port)/?filter=.status%20%3D%3D%20%22error%22
+				```
+
+				Connections with a filter that fails to compile are rejected with a `400 Bad Request`
+				response that explains the error. Filters can't use enrichment tables.
+
+				If [`auth`](#auth) is set, clients have to send the matching `Authorization` header
+				when connecting, or are rejected with a `401 Unauthorized` response. Browsers can't set headers on WebSocket connections, so they need to
+				connect through a proxy that adds it.
+				"""
+		}
+
+		filter_cost: {
+			title: "The cost of filters"
+			body:  """
+				The filter of every client is run against every event the sink receives, one after
+				the other and on the same task that receives events, so the sink slows down with the
+				number of clients, and with how expensive their filters are. A slow sink holds up the
+				components sending events to it.
+
+				[`max_clients`](#max_clients) caps the number of clients, and so the number of filters
+				run per event. Since clients choose their own filters, also set [`auth`](#auth)
+				unless everyone who can reach the sink is trusted.
+				"""
+		}
+
+		delivery: {
+			title: "Delivery"
+			body:  """
+				Events are acknowledged as soon as they're queued for the clients they match, and are
+				dropped if no client is connected, so this sink is meant for watching events live
+				rather than delivering them.
+				"""
+		}
+
+		slow_clients: {
+			title: "Slow clients"
+			body:  """
+				Every client has a queue of up to [`max_queued_messages`](#max_queued_messages)
+				messages, so that a slow client never holds up the other clients, or the rest of the
+				pipeline. Once the queue of a client is full, events are either dropped for that
+				client, or the client is disconnected, depending on
+				[`slow_client_policy`](#slow_client_policy).
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		connection_errors_total:          components.sources.internal_metrics.output.metrics.connection_errors_total
+		connection_established_total:     components.sources.internal_metrics.output.metrics.connection_established_total
+		connection_shutdown_total:        components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		events_discarded_total:           components.sources.internal_metrics.output.metrics.events_discarded_total
+		http_bad_requests_total:          components.sources.internal_metrics.output.metrics.http_bad_requests_total
+		open_connections:                 components.sources.internal_metrics.output.metrics.open_connections
+	}
+}
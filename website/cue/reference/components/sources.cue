@@ -5,6 +5,69 @@ components: sources: [Name=string]: {
 	features: _
 
 	configuration: {
+		event_time: {
+			common: false
+			description: """
+				Configures which field of this source's events carries the time at which the event happened.
+				The event time is tracked in the event metadata and used by time-based transforms, such as
+				`reduce`, to detect events arriving after their watermark. Metric events always use their own
+				timestamp.
+				"""
+			required: false
+			type: object: options: {
+				field: {
+					description: "The log field containing the event time, either as a timestamp or as an integer Unix timestamp in seconds."
+					required:    true
+					type: string: {
+						examples: ["timestamp", "event.created"]
+						syntax: "literal"
+					}
+				}
+			}
+		}
+
+		ordered: {
+			common: false
+			description: """
+				Keeps the order of this source's events through the topology. The events must reach sinks
+				through a single path of transforms, and transforms they go through ignore `parallelism`.
+				Sinks that send requests must have their `request.concurrency` set to 1, as requests sent
+				concurrently can complete in any order.
+				"""
+			required: false
+			type: bool: default: false
+		}
+
+		silence: {
+			common: false
+			description: """
+				Detects when this source sends no events for longer than a threshold, such as when a tailed file
+				stops being written to. Silences are logged as warnings and reported by the
+				`source_silences_total`, `source_silent` and `source_last_event_timestamp_seconds` internal
+				metrics, in total and for each partition of the source's events.
+				"""
+			required: false
+			type: object: options: {
+				threshold_secs: {
+					description: "How long the source, or a partition of its events, can go without events before it's considered silent."
+					required:    true
+					type: uint: {
+						examples: [300, 3600]
+						unit: "seconds"
+					}
+				}
+				partition_key: {
+					common:      false
+					description: "The log field or metric tag whose values partition the source's events, such as `file` for the `file` source or `topic` for the `kafka` source. Up to 1000 partitions are tracked."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["file", "topic"]
+					}
+				}
+			}
+		}
+
 		if features.collect != _|_ {
 			if features.collect.checkpoint.enabled {
 				data_dir: {
@@ -0,0 +1,199 @@
+package metadata
+
+components: sources: aws_cloudwatch_logs: components._aws & {
+	title: "AWS CloudWatch Logs"
+
+	description: """
+		Pulls log events from [AWS CloudWatch Logs](\(urls.aws_cloudwatch_logs)) groups using the
+		[`FilterLogEvents`](\(urls.aws_cloudwatch_logs_filter_log_events)) API. This is useful where subscription
+		filters delivering to Kinesis Firehose aren't permitted.
+		"""
+
+	features: {
+		collect: {
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			tls: enabled:        false
+			from: {
+				service: services.aws_cloudwatch_logs
+				interface: {
+					socket: {
+						api: {
+							title: "AWS CloudWatch Logs API"
+							url:   urls.aws_cloudwatch_logs_api
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				`FilterLogEvents` is limited to a handful of requests a second per account and region, which is
+				shared with anything else filtering log events. Each poll makes at least one request per log group,
+				so with many groups raise `poll_interval_secs` rather than lowering it. See the
+				[service quotas](\(urls.aws_cloudwatch_logs_service_limits)).
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._acknowledgements
+		filter_pattern: {
+			common:      true
+			description: "Only read log events matching this [filter pattern](\(urls.aws_cloudwatch_logs_filter_pattern))."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["ERROR", #"{ $.level = "error" }"#]
+			}
+		}
+		ingestion_delay_secs: {
+			common:      false
+			description: "How far behind the current time to read up to. CloudWatch Logs can take a little while to make log events available, and those that only become available after Vector has read past their timestamp are missed."
+			required:    false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+		log_group_name_prefix: {
+			common:      true
+			description: "Also read the log groups whose names start with this prefix, including those created while Vector is running. Either this or `log_group_names` must be set."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/aws/lambda/"]
+			}
+		}
+		log_group_names: {
+			common:      true
+			description: "The names of the [log groups](\(urls.aws_cloudwatch_logs_group_name)) to read log events from. Either this or `log_group_name_prefix` must be set."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["/aws/lambda/my-function"]
+				}
+			}
+		}
+		poll_interval_secs: {
+			common:      true
+			description: "How often to check for new log events. Must be greater than `0`."
+			required:    false
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "A CloudWatch Logs log event."
+		fields: {
+			event_id: {
+				description: "The id of the log event."
+				required:    true
+				type: string: {
+					examples: ["36838436386373829384732948302938473625374839203847362514"]
+				}
+			}
+			log_group: {
+				description: "The log group the log event was read from."
+				required:    true
+				type: string: {
+					examples: ["/aws/lambda/my-function"]
+				}
+			}
+			log_stream: {
+				description: "The log stream the log event was written to."
+				required:    true
+				type: string: {
+					examples: ["2022/03/01/[$LATEST]0123456789abcdef0123456789abcdef"]
+				}
+			}
+			message: {
+				description: "The message of the log event."
+				required:    true
+				type: string: {
+					examples: ["START RequestId: 6a8d4c1f-1f3e-4bd4-9b5c-3c9e1a6f0e2d Version: $LATEST"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["aws_cloudwatch_logs"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the log event was logged."
+			}
+		}
+	}
+
+	how_it_works: {
+		cursor: {
+			title: "Cursor"
+			body: """
+				For each log group, the source filters for log events with a timestamp past a cursor: the timestamp of
+				the last log event read, along with the ids of those read at exactly that timestamp. The cursor moves
+				forward once the log events before it have been sent on, or delivered when acknowledgements are
+				enabled, and the cursors are saved in the data directory so that a restart carries on from them. Log
+				groups are read from the current time when first seen.
+				"""
+		}
+		log_group_discovery: {
+			title: "Log group discovery"
+			body: """
+				When `log_group_name_prefix` is set, the log groups matching it are listed at every poll, so groups
+				created while Vector is running are picked up without a restart.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "aws"
+			_service: "AmazonCloudWatchLogs"
+
+			policies: [
+				{
+					_action:       "DescribeLogGroups"
+					required_when: "[`log_group_name_prefix`](#log_group_name_prefix) is set"
+				},
+				{
+					_action: "FilterLogEvents"
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		checkpoint_write_errors_total:   components.sources.internal_metrics.output.metrics.checkpoint_write_errors_total
+		component_received_events_total: components.sources.internal_metrics.output.metrics.component_received_events_total
+		http_request_errors_total:       components.sources.internal_metrics.output.metrics.http_request_errors_total
+		processed_bytes_total:           components.sources.internal_metrics.output.metrics.processed_bytes_total
+	}
+}
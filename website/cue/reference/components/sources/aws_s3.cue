@@ -39,6 +39,22 @@ components: sources: aws_s3: components._aws & {
 
 	configuration: {
 		acknowledgements: configuration._acknowledgements
+		cloudtrail: {
+			common:      false
+			description: "Parse objects as [AWS CloudTrail](\(urls.aws_cloudtrail)) log files. Each record in a log file becomes an event, rather than each line. Set this to an empty table (`cloudtrail = {}`) to enable it with the defaults."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					validate_digests: {
+						common:      false
+						description: "When CloudTrail delivers a [digest file](\(urls.aws_cloudtrail_digest_files)), fetch each log file it lists and check that its SHA-256 hash matches. Mismatches are logged and counted in `cloudtrail_digest_mismatches_total`. The signature of the digest file itself isn't checked. Digest files are never sent on as events."
+						required:    false
+						type: bool: default: false
+					}
+				}
+			}
+		}
 		strategy: {
 			common:      false
 			description: "The strategy to use to consume objects from AWS S3."
@@ -136,12 +152,20 @@ components: sources: aws_s3: components._aws & {
 				}
 			}
 			region: {
-				description: "The AWS region bucket is in."
+				description: "The AWS region bucket is in. With `cloudtrail` set, the region of the CloudTrail log file, from its key."
 				required:    true
 				type: string: {
 					examples: ["us-east-1"]
 				}
 			}
+			account_id: {
+				description: "With `cloudtrail` set, the AWS account the CloudTrail log file was delivered for, from its key."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["111122223333"]
+				}
+			}
 		}
 	}
 
@@ -225,6 +249,16 @@ components: sources: aws_s3: components._aws & {
 				```
 				"""
 		}
+		cloudtrail: {
+			title: "AWS CloudTrail"
+			body:  """
+				With the `cloudtrail` option set, objects are read as CloudTrail log files: the whole object is
+				decompressed and parsed, and each entry of its `Records` array becomes an event, timestamped with
+				its `eventTime`. Point the bucket notifications at the prefix CloudTrail delivers to, usually
+				`AWSLogs/`. When `validate_digests` is enabled the hourly digest files are used to check the log
+				files they list, which reads each log file a second time.
+				"""
+		}
 	}
 
 	permissions: iam: [
@@ -270,5 +304,6 @@ components: sources: aws_s3: components._aws & {
 		sqs_message_receive_succeeded_total:    components.sources.internal_metrics.output.metrics.sqs_message_receive_succeeded_total
 		sqs_message_received_messages_total:    components.sources.internal_metrics.output.metrics.sqs_message_received_messages_total
 		sqs_s3_event_record_ignored_total:      components.sources.internal_metrics.output.metrics.sqs_s3_event_record_ignored_total
+		cloudtrail_digest_mismatches_total:     components.sources.internal_metrics.output.metrics.cloudtrail_digest_mismatches_total
 	}
 }
@@ -0,0 +1,242 @@
+package metadata
+
+components: sources: azure_event_hubs: {
+	title: "Azure Event Hubs"
+
+	description: """
+		Consumes events from an [Azure Event Hubs](\(urls.azure_event_hubs)) event hub directly over AMQP 1.0,
+		capturing the system properties Event Hubs attaches to each event. The partitions are balanced between
+		every Vector instance reading with the same consumer group, with ownership and checkpoints kept in an
+		Azure Blob Storage container.
+		"""
+
+	features: {
+		collect: {
+			checkpoint: enabled: true
+			tls: enabled:        false
+			from: {
+				service: services.azure_event_hubs
+				interface: {
+					socket: {
+						api: {
+							title: "AMQP 1.0"
+							url:   urls.azure_event_hubs_amqp
+						}
+						direction: "outgoing"
+						port:      5671
+						protocols: ["tcp"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._acknowledgements
+		checkpoint_interval_secs: {
+			common:      false
+			description: "How often the position reached in each partition is written to the checkpoint store. Must be greater than `0`."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    "seconds"
+			}
+		}
+		checkpoint_store: {
+			description: "The Azure Blob Storage container to keep partition ownership and checkpoints in."
+			required:    true
+			type: object: options: {
+				connection_string: {
+					description: "The connection string of the storage account."
+					required:    true
+					type: string: {
+						examples: ["DefaultEndpointsProtocol=https;AccountName=mylogstorage;AccountKey=storageaccountkeybase64encoded;EndpointSuffix=core.windows.net"]
+					}
+				}
+				container_name: {
+					description: "The container to keep ownership and checkpoints in. It must already exist."
+					required:    true
+					type: string: {
+						examples: ["event-hubs-checkpoints"]
+					}
+				}
+			}
+		}
+		connection_string: {
+			description: "The connection string of a shared access policy on the namespace or event hub, with the `Listen` claim."
+			required:    true
+			type: string: {
+				examples: ["Endpoint=sb://my-namespace.servicebus.windows.net/;SharedAccessKeyName=vector;SharedAccessKey=keybase64encoded;EntityPath=my-event-hub"]
+			}
+		}
+		consumer_group: {
+			common:      true
+			description: "The consumer group to read with. Every instance reading with the same consumer group shares the partitions between them."
+			required:    false
+			type: string: {
+				default: "$Default"
+				examples: ["vector"]
+			}
+		}
+		event_hub: {
+			common:      true
+			description: "The event hub to read from. Defaults to the `EntityPath` of the connection string."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["my-event-hub"]
+			}
+		}
+		load_balancing_interval_secs: {
+			common:      false
+			description: "How often partition ownership is renewed and rebalanced. Must be greater than `0`."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    "seconds"
+			}
+		}
+		ownership_expiration_secs: {
+			common:      false
+			description: "How long after an owner last renewed its claim on a partition the partition is considered to be unowned. This must be longer than `load_balancing_interval_secs`."
+			required:    false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		start_position: {
+			common:      false
+			description: "Where to start reading partitions that have no checkpoint yet."
+			required:    false
+			type: string: {
+				default: "latest"
+				enum: {
+					earliest: "Start with the oldest event retained in the partition."
+					latest:   "Start with events enqueued after the partition was claimed."
+				}
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual Event Hubs event"
+		fields: {
+			message: {
+				description: "The body of the event."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			offset: {
+				description: "The offset of the event in its partition."
+				required:    true
+				type: string: {
+					examples: ["4294967296"]
+				}
+			}
+			partition_id: {
+				description: "The partition that the event came from."
+				required:    true
+				type: string: {
+					examples: ["0"]
+				}
+			}
+			partition_key: {
+				description: "The key that the event was published with, if any."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["host-1"]
+				}
+			}
+			properties: {
+				description: "The application properties that the event was published with."
+				required:    true
+				type: object: {
+					examples: [{"source": "app"}]
+					options: {}
+				}
+			}
+			sequence_number: {
+				description: "The sequence number of the event in its partition."
+				required:    true
+				type: uint: {
+					examples: [42]
+					unit: null
+				}
+			}
+			system_properties: {
+				description: "The remaining system properties of the event, such as `message_id`, `content_type` and `correlation_id`, along with any other annotations set by Event Hubs."
+				required:    true
+				type: object: {
+					examples: [{"content_type": "application/json"}]
+					options: {}
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the event was enqueued, or the current time if it isn't known."
+			}
+		}
+	}
+
+	how_it_works: {
+		load_balancing: {
+			title: "Partition ownership"
+			body: """
+				Each Vector instance claims its share of the event hub's partitions, renewing its claims every
+				`load_balancing_interval_secs`. Claims are kept as blobs in the checkpoint store, and are only made
+				if nobody else has updated the blob since it was last read, so that two instances never own the
+				same partition. Partitions whose owner hasn't renewed its claim within `ownership_expiration_secs`
+				are claimed by others, and instances owning more than their share of the partitions give some up to
+				newly started ones. A partition is given up straight away when Vector shuts down.
+				"""
+		}
+		checkpoints: {
+			title: "Checkpoints"
+			body: """
+				The position reached in each partition is written to the checkpoint store every
+				`checkpoint_interval_secs`, and a partition is read from its last checkpoint when it is claimed. With
+				acknowledgements enabled, a position is only checkpointed once the events before it have been
+				delivered. Ownership and checkpoints use the same layout as the
+				[Azure SDK checkpoint stores](\(urls.azure_event_hubs_checkpoints)), so a consumer group can be moved
+				between them and Vector.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		consumer_offset_updates_failed_total: components.sources.internal_metrics.output.metrics.consumer_offset_updates_failed_total
+		events_failed_total:                  components.sources.internal_metrics.output.metrics.events_failed_total
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		ownership_errors_total:               components.sources.internal_metrics.output.metrics.ownership_errors_total
+		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
+	}
+}
@@ -49,6 +49,10 @@ components: sources: demo_logs: {
 					"syslog":        "Randomly generated logs in Syslog format ([RFC 5424](\(urls.syslog_5424)))."
 					"bsd_syslog":    "Randomly generated logs in Syslog format ([RFC 3164](\(urls.syslog_3164)))."
 					"json":          "Randomly generated HTTP server logs in [JSON](\(urls.json)) format."
+					"nginx_access":  "Nginx access logs in combined format, shaped by `cardinality` and `error_rate`."
+					"json_app":      "Application logs in [JSON](\(urls.json)) format, shaped by `cardinality` and `error_rate`."
+					"syslog_auth":   "SSH login attempts in Syslog format, shaped by `cardinality` and `error_rate`."
+					"replay":        "Lines replayed from the file at `path`, looping back to the start once all of them were sent."
 				}
 			}
 		}
@@ -65,6 +69,16 @@ components: sources: demo_logs: {
 				examples: [1.0, 0.1, 0.01]
 			}
 		}
+		cardinality: {
+			common:        false
+			description:   "How many distinct clients, hosts, users and paths appear in the generated lines."
+			relevant_when: "`format` = `nginx_access` or `format` = `json_app` or `format` = `syslog_auth`"
+			required:      false
+			type: uint: {
+				default: 100
+				unit:    null
+			}
+		}
 		count: {
 			common:      false
 			description: "The total number of lines to output. By default the source continuously prints logs (infinitely)."
@@ -74,6 +88,16 @@ components: sources: demo_logs: {
 				unit:    null
 			}
 		}
+		error_rate: {
+			common:        false
+			description:   "The share of generated lines that describe a failure, such as a 5xx response or a failed login."
+			relevant_when: "`format` = `nginx_access` or `format` = `json_app` or `format` = `syslog_auth`"
+			required:      false
+			type: float: {
+				default: 0.05
+				examples: [0.0, 0.05, 0.5]
+			}
+		}
 		lines: {
 			common:        false
 			description:   "The list of lines to output."
@@ -86,6 +110,45 @@ components: sources: demo_logs: {
 				}
 			}
 		}
+		path: {
+			common:        false
+			description:   "The file to replay lines from."
+			relevant_when: "`format` = `replay`"
+			required:      false
+			type: string: {
+				default: null
+				examples: ["/var/log/captured.log"]
+			}
+		}
+		speed: {
+			common: false
+			description: """
+				How many times faster than the original timing to replay lines, for example `2.0` to
+				halve the time between them. Only used with `timestamp_format`.
+				"""
+			relevant_when: "`format` = `replay`"
+			required:      false
+			type: float: {
+				default: 1.0
+				examples: [0.5, 1.0, 10.0]
+			}
+		}
+		timestamp_format: {
+			common: false
+			description: """
+				The [strptime format](\(urls.chrono_time_formats)) of the timestamp each replayed line
+				starts with. The timestamp is removed from the line, and the time between
+				timestamps is reproduced between lines, scaled by `speed`. Timestamps without a
+				time zone are taken to be in UTC. Without this option, lines are sent every
+				`interval`.
+				"""
+			relevant_when: "`format` = `replay`"
+			required:      false
+			type: string: {
+				default: null
+				examples: ["%+", "%Y-%m-%d %H:%M:%S"]
+			}
+		}
 		sequence: {
 			common:        false
 			relevant_when: "`format` = `shuffle`"
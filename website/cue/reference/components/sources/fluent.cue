@@ -68,6 +68,48 @@ components: sources: fluent: {
 				unit:    "concurrency"
 			}
 		}
+		connection_limit_per_ip: {
+			common:        false
+			description:   "The max number of TCP connections that will be processed from a single IP address. When the `proxy_protocol` option is enabled this applies to the load balancer's address rather than the client's."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: uint: {
+				default: null
+				unit:    "concurrency"
+			}
+		}
+		connection_rate_limit: {
+			common:        false
+			description:   "The max number of TCP connections that will be accepted per second. Connections beyond it wait in the operating system's backlog until they can be accepted."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: uint: {
+				default: null
+				unit:    null
+			}
+		}
+		proxy_protocol: configuration._proxy_protocol
+		security: {
+			common:      false
+			description: "Require clients to authenticate with a shared key, using the handshake of Fluentd's secure forward mode."
+			required:    false
+			type: object: options: {
+				shared_key: {
+					description: "The shared key clients must authenticate with."
+					required:    true
+					type: string: {
+						examples: ["${FLUENT_SHARED_KEY}"]
+					}
+				}
+				self_hostname: {
+					description: "The hostname Vector identifies itself with to clients. This must differ from the client's own hostname."
+					required:    true
+					type: string: {
+						examples: ["vector.example.com"]
+					}
+				}
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -179,25 +221,35 @@ components: sources: fluent: {
 		secure_mode: {
 			title: "Secure forward mode support"
 			body:  """
-				The `fluent` source currently supports using TLS, but does not support the authentication part of the
-				Fluent protocol including:
-
-				- Shared key
-				- Username and password
+				When `security` is configured, clients must complete the handshake of the Fluent forward protocol, and
+				authenticate with the configured shared key, before any events are accepted. The handshake can be used
+				together with TLS, as with Fluentd's `transport tls` and `<security>` settings, or Fluent Bit's
+				`Shared_Key` and `Self_Hostname` options.
 
-				And so these options of the secure forward output plugins for Fluent and Fluent Bit cannot be used.
+				Username and password authentication isn't supported.
+				"""
+		}
 
-				If you would find this useful, [please let us know](\(urls.vector_repo)/issues/7532).
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Clients that send a `chunk` option with their events, such as Fluentd with `require_ack_response`
+				enabled, are sent an `ack` for each chunk. If `acknowledgements` is enabled, the `ack` is only sent
+				once the events have been delivered to their sinks, and is sent without an `ack` field if delivery
+				failed, so the client sends the chunk again.
 				"""
 		}
 	}
 
 	telemetry: metrics: {
+		connection_errors_total:         components.sources.internal_metrics.output.metrics.connection_errors_total
 		events_in_total:                 components.sources.internal_metrics.output.metrics.events_in_total
 		decode_errors_total:             components.sources.internal_metrics.output.metrics.decode_errors_total
 		processed_bytes_total:           components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:          components.sources.internal_metrics.output.metrics.processed_events_total
 		component_received_bytes_total:  components.sources.internal_metrics.output.metrics.component_received_bytes_total
 		component_received_events_total: components.sources.internal_metrics.output.metrics.component_received_events_total
+		connection_limit_reached_total:  components.sources.internal_metrics.output.metrics.connection_limit_reached_total
+		connections_rejected_total:      components.sources.internal_metrics.output.metrics.connections_rejected_total
 	}
 }
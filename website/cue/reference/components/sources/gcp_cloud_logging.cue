@@ -0,0 +1,211 @@
+package metadata
+
+components: sources: gcp_cloud_logging: {
+	title: "GCP Cloud Logging"
+
+	description: """
+		Pulls log entries from [GCP Cloud Logging](\(urls.gcp_stackdriver_logging_rest)), such as the logs of Cloud
+		Run services, using the [`entries.list`](\(urls.gcp_logging_entries_list)) API. This is useful where routing
+		logs to Pub/Sub with a log sink isn't permitted.
+		"""
+
+	features: {
+		collect: {
+			checkpoint: enabled: true
+			proxy: enabled:      true
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			from: {
+				service: services.gcp_operations_logs
+				interface: {
+					socket: {
+						api: {
+							title: "Cloud Logging API"
+							url:   urls.gcp_logging_entries_list
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "required"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				The `entries.list` API is limited to 60 requests a minute per project by default, which is shared
+				with anything else listing entries in the project. Lower `poll_interval_secs` with care.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._acknowledgements
+		api_key: {
+			common:      false
+			description: "A [Google Cloud API key](\(urls.gcp_authentication_api_key)) used to authenticate access to the Cloud Logging API. Either this or `credentials_path` must be set."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["${GCP_API_KEY}", "ef8d5de700e7989468166c40fc8a0ccd"]
+			}
+		}
+		credentials_path: {
+			common:      true
+			description: "The filename for a Google Cloud service account credentials JSON file used to authenticate access to the Cloud Logging API. If this is unset, Vector checks the `GOOGLE_APPLICATION_CREDENTIALS` environment variable for a filename.\n\nIf no filename is named, Vector will attempt to fetch an instance service account for the compute instance the program is running on. If Vector is not running on a GCE instance, you must define a credentials file as above."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["/path/to/credentials.json"]
+			}
+		}
+		impersonate_service_account: {
+			common:      false
+			description: "The email of a service account to impersonate, using the credentials found as described in [GCP authentication](#gcp-authentication). The account authenticating needs the `roles/iam.serviceAccountTokenCreator` role on the impersonated service account."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["vector@my-project.iam.gserviceaccount.com"]
+			}
+		}
+		endpoint: {
+			common:      false
+			description: "The Cloud Logging API endpoint to read from."
+			required:    false
+			type: string: {
+				default: "https://logging.googleapis.com"
+				examples: ["https://logging.googleapis.com"]
+			}
+		}
+		filter: {
+			common:      true
+			description: "Only read entries matching this [Logging query language](\(urls.gcp_logging_query_language)) filter. Timestamp conditions are added by Vector to track its position, so shouldn't be included."
+			required:    false
+			type: string: {
+				default: null
+				examples: [#"resource.type = "cloud_run_revision" AND severity >= WARNING"#]
+			}
+		}
+		ingestion_delay_secs: {
+			common:      false
+			description: "How far behind the current time to read up to. Cloud Logging can take a little while to make entries available, and entries that only become available after Vector has read past their timestamp are missed."
+			required:    false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+		page_size: {
+			common:      false
+			description: "The most entries to read in each request, up to `1000`."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    null
+			}
+		}
+		poll_interval_secs: {
+			common:      true
+			description: "How often to check for new entries. Must be greater than `0`."
+			required:    false
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		resource_names: {
+			description: "The projects, folders, organizations or billing accounts to read entries from."
+			required:    true
+			type: array: items: type: string: {
+				examples: ["projects/my-project", "folders/1234567890"]
+			}
+		}
+		skip_authentication: {
+			common:      false
+			description: "Skip all authentication handling. For use with an emulator or a proxy that adds credentials."
+			required:    false
+			type: bool: default: false
+		}
+	}
+
+	output: logs: record: {
+		description: "A Cloud Logging entry. All of the fields of the entry are kept as they are, other than those below."
+		fields: {
+			message: {
+				description: "The `textPayload` of the entry, if it has one. Other payloads are kept in `jsonPayload` or `protoPayload`."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["Listening on port 8080"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["gcp_cloud_logging"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the entry was logged."
+			}
+		}
+	}
+
+	how_it_works: {
+		cursor: {
+			title: "Cursor"
+			body: """
+				Cloud Logging has no REST API for tailing logs, so the source lists entries in timestamp order,
+				starting from a cursor: the timestamp of the last entry read, along with the ids of entries read at
+				exactly that timestamp. The cursor moves forward once the entries before it have been sent on, or
+				delivered when acknowledgements are enabled, and is saved in the data directory so that a restart
+				carries on from it. On first start, reading begins from the current time.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform: "gcp"
+			_service: "logging"
+
+			policies: [
+				{
+					_action: "logEntries.list"
+					required_for: ["operation"]
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		checkpoint_write_errors_total:   components.sources.internal_metrics.output.metrics.checkpoint_write_errors_total
+		component_received_events_total: components.sources.internal_metrics.output.metrics.component_received_events_total
+		http_request_errors_total:       components.sources.internal_metrics.output.metrics.http_request_errors_total
+		processed_bytes_total:           components.sources.internal_metrics.output.metrics.processed_bytes_total
+	}
+}
@@ -0,0 +1,197 @@
+package metadata
+
+components: sources: github_webhooks: {
+	_port: 8080
+
+	title: "GitHub Webhooks"
+
+	description: """
+		Receives deliveries from [GitHub webhooks](\(urls.github_webhooks)), checking that each one is signed
+		with the webhook's secret and picking the most useful fields of `push`, `pull_request` and
+		`workflow_run` events out of their payloads.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.github
+
+				interface: socket: {
+					api: {
+						title: "GitHub webhooks"
+						url:   urls.github_webhooks
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._acknowledgements
+		address:          sources.http.configuration.address
+		include_payload: {
+			common:      false
+			description: "Whether to keep the whole payload of each delivery in the `payload` field, as well as the fields picked out of it."
+			required:    false
+			type: bool: default: true
+		}
+		path: {
+			common:      false
+			description: "The URL path the webhook delivers to."
+			required:    false
+			type: string: {
+				default: "/"
+				examples: ["/github"]
+			}
+		}
+		secret: {
+			description: "The secret the webhook was created with. Deliveries without a valid `X-Hub-Signature-256` signature are rejected with a `401`."
+			required:    true
+			type: string: {
+				examples: ["${GITHUB_WEBHOOK_SECRET}"]
+			}
+		}
+	}
+
+	output: logs: delivery: {
+		description: "A webhook delivery."
+		fields: {
+			action: {
+				description: "The `action` of the event, such as `opened` for a `pull_request` event."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["opened", "completed"]
+				}
+			}
+			delivery_id: {
+				description: "The unique ID of the delivery, from the `X-GitHub-Delivery` header."
+				required:    true
+				type: string: {
+					examples: ["72d3162e-cc78-11e3-81ab-4c9367dc0958"]
+				}
+			}
+			event: {
+				description: "The type of event, from the `X-GitHub-Event` header."
+				required:    true
+				type: string: {
+					examples: ["push", "pull_request", "workflow_run"]
+				}
+			}
+			hook_id: {
+				description: "The ID of the webhook, from the `X-GitHub-Hook-ID` header."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["292430182"]
+				}
+			}
+			payload: {
+				description: "The whole payload of the delivery, unless `include_payload` is `false`."
+				required:    false
+				type: object: {
+					examples: []
+					options: {}
+				}
+			}
+			pull_request: {
+				description: "For `pull_request` events, the `number`, `title`, `state`, `draft`, `merged`, `author`, `base_ref`, `head_ref`, `head_sha`, `url`, `created_at`, `merged_at`, `closed_at`, `additions`, `deletions` and `changed_files` of the pull request."
+				required:    false
+				type: object: {
+					examples: []
+					options: {}
+				}
+			}
+			push: {
+				description: "For `push` events, the `ref`, `branch` or `tag`, `before`, `after`, `created`, `deleted`, `forced`, `pusher`, `compare`, `commit_count`, `head_commit_id` and `head_commit_message` of the push."
+				required:    false
+				type: object: {
+					examples: []
+					options: {}
+				}
+			}
+			repository: {
+				description: "The full name of the repository the event happened in."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["vectordotdev/vector"]
+				}
+			}
+			sender: {
+				description: "The login of the user that caused the event."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["octocat"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["github_webhooks"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			workflow_run: {
+				description: "For `workflow_run` events, the `id`, `name`, `event`, `status`, `conclusion`, `head_branch`, `head_sha`, `run_number`, `run_attempt`, `url`, `run_started_at` and `updated_at` of the run, and its `duration_secs` once it has completed."
+				required:    false
+				type: object: {
+					examples: []
+					options: {}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		signatures: {
+			title: "Signature verification"
+			body: """
+				GitHub signs each delivery with the webhook's secret and sends the signature in the
+				`X-Hub-Signature-256` header, as described in [securing your webhooks](\(urls.github_webhooks_securing)).
+				The source checks the signature against the body as it was received, and rejects deliveries that
+				aren't signed with `secret` before decoding them.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		http_bad_requests_total:              components.sources.internal_metrics.output.metrics.http_bad_requests_total
+	}
+}
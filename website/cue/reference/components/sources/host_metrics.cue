@@ -71,10 +71,11 @@ components: sources: host_metrics: {
 			common:      true
 			required:    false
 			type: array: {
-				default: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
+				default: ["cgroups", "clock", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
 				items: type: string: {
 					enum: {
 						cgroups:    "Metrics related to Linux control groups."
+						clock:      "Metrics related to the synchronization of the system clock."
 						cpu:        "Metrics related to CPU utilization."
 						disk:       "Metrics related to disk I/O utilization."
 						filesystem: "Metrics related to filesystem space utilization."
@@ -352,6 +353,39 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		clock: {
+			common:      false
+			description: #"Options for the "clock" metrics collector."#
+			required:    false
+			type: object: options: {
+				ntp_servers: {
+					common:      false
+					required:    false
+					description: """
+						The NTP servers to measure the offset of the system clock against, as `host` or
+						`host:port`. Each server is queried once per scrape, using SNTP.
+
+						Defaults to no servers, in which case only the state of the kernel clock is
+						reported (Linux only).
+						"""
+					type: array: {
+						default: []
+						items: type: string: {
+							examples: ["pool.ntp.org", "10.0.0.1:123"]
+						}
+					}
+				}
+				ntp_timeout_secs: {
+					common:      false
+					required:    false
+					description: "How long to wait for each NTP server to respond."
+					type: uint: {
+						default: 5
+						unit:    "seconds"
+					}
+				}
+			}
+		}
 		network: {
 			common:      false
 			description: #"Options for the "network" metrics collector."#
@@ -415,6 +449,15 @@ components: sources: host_metrics: {
 			}
 		}
 
+		// Host clock
+		clock_synchronized:            _host & _clock_linux & {description: "Whether the kernel reports the system clock as synchronized, as `1` or `0`."}
+		clock_offset_seconds:          _host & _clock_linux & {description: "The offset of the system clock last estimated by the NTP daemon, such as chronyd or ntpd, that disciplines it."}
+		clock_max_error_seconds:       _host & _clock_linux & {description: "The maximum error of the system clock, as reported by the kernel."}
+		clock_estimated_error_seconds: _host & _clock_linux & {description: "The estimated error of the system clock, as reported by the kernel."}
+		clock_frequency_offset_ppm:    _host & _clock_linux & {description: "The frequency adjustment the kernel applies to the system clock, in parts per million."}
+		clock_ntp_offset_seconds:      _host & _clock_ntp & {description:   "The offset of the system clock from the clock of the NTP server. Positive values mean the system clock is behind."}
+		clock_ntp_delay_seconds:       _host & _clock_ntp & {description:   "The round trip delay of the exchange with the NTP server used to measure the offset."}
+
 		// Host CPU
 		host_cpu_seconds_total: _host & {
 			description: "The number of CPU seconds accumulated in different operating modes."
@@ -518,6 +561,25 @@ components: sources: host_metrics: {
 			examples: ["/", "user.slice", "system.slice/snapd.service"]
 		}
 
+		_clock_linux: {
+			type: "gauge"
+			tags: _host_metrics_tags & {
+				collector: examples: ["clock"]
+			}
+			relevant_when: "OS is Linux"
+		}
+		_clock_ntp: {
+			type: "gauge"
+			tags: _host_metrics_tags & {
+				collector: examples: ["clock"]
+				server: {
+					description: "The NTP server, as configured."
+					required:    true
+					examples: ["pool.ntp.org"]
+				}
+			}
+		}
+
 		_disk_device: {
 			description: "The disk device name."
 			required:    true
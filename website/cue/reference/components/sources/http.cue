@@ -78,7 +78,7 @@ components: sources: http: {
 		}
 		headers: {
 			common:      false
-			description: "A list of HTTP headers to include in the log event. These will override any values included in the JSON payload with conflicting names."
+			description: "A list of HTTP headers to include in the log event. These will override any values included in the JSON payload with conflicting names. A header can be stored in a field with a different name with `{ name = \"X-Request-Id\", field = \"request_id\" }`."
 			required:    false
 			type: array: {
 				default: null
@@ -88,9 +88,133 @@ components: sources: http: {
 			}
 		}
 		auth: configuration._http_basic_auth
+		hmac: {
+			common:      false
+			description: "Require requests to be signed with a shared secret, as webhooks from GitHub, Stripe and many others are. Requests without a valid signature are rejected with a `401` before their body is decoded."
+			required:    false
+			type: object: options: {
+				algorithm: {
+					common:      false
+					description: "The hash algorithm of the signature."
+					required:    false
+					type: string: {
+						default: "sha256"
+						enum: {
+							sha1:   "HMAC-SHA1"
+							sha256: "HMAC-SHA256"
+							sha512: "HMAC-SHA512"
+						}
+					}
+				}
+				encoding: {
+					common:      false
+					description: "How the signature is encoded in the header. `stripe` signatures are always hex encoded."
+					required:    false
+					type: string: {
+						default: "hex"
+						enum: {
+							hex:    "Hex encoded."
+							base64: "Base64 encoded."
+						}
+					}
+				}
+				header: {
+					description: "The request header holding the signature."
+					required:    true
+					type: string: {
+						examples: ["X-Hub-Signature-256", "Stripe-Signature"]
+					}
+				}
+				prefix: {
+					common:      false
+					description: "A prefix to strip from the header before decoding the signature."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["sha256="]
+					}
+				}
+				scheme: {
+					common:      false
+					description: "How the signature is laid out in the header."
+					required:    false
+					type: string: {
+						default: "plain"
+						enum: {
+							plain:  "The header holds a signature of the request body, after `prefix`."
+							stripe: "The header holds `t=<timestamp>,v1=<signature>`, where the signature covers `<timestamp>.<body>`. Requests with a timestamp older than `tolerance_secs` are rejected."
+						}
+					}
+				}
+				secret: {
+					description: "The shared secret requests are signed with."
+					required:    true
+					type: string: {
+						examples: ["${WEBHOOK_SECRET}"]
+					}
+				}
+				tolerance_secs: {
+					common:      false
+					description: "How old the timestamp of a `stripe` signature may be."
+					required:    false
+					type: uint: {
+						default: 300
+						unit:    "seconds"
+					}
+				}
+			}
+		}
+		http2: configuration._http2 & {_args: server: true}
+		jwt: {
+			common:      false
+			description: "Require requests to carry a JSON Web Token in an `Authorization: Bearer` header, signed by one of the keys published at `jwks_url`. `RS256`, `RS384`, `RS512`, `ES256` and `ES384` signatures are supported. Requests without a valid token are rejected with a `401` before their body is decoded. Can't be used together with `auth`."
+			required:    false
+			type: object: options: {
+				audience: {
+					common:      false
+					description: "The audience tokens must have been issued for, in their `aud` claim."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["vector"]
+					}
+				}
+				issuer: {
+					common:      false
+					description: "The issuer tokens must have been issued by, in their `iss` claim."
+					required:    false
+					type: string: {
+						default: null
+						examples: ["https://accounts.google.com"]
+					}
+				}
+				jwks_url: {
+					description: "The URL of the JSON Web Key Set holding the keys tokens are signed with."
+					required:    true
+					type: string: {
+						examples: ["https://www.googleapis.com/oauth2/v3/certs"]
+					}
+				}
+				refresh_interval_secs: {
+					common:      false
+					description: "How often to fetch the keys again, to pick up rotated keys."
+					required:    false
+					type: uint: {
+						default: 300
+						unit:    "seconds"
+					}
+				}
+				tls: configuration._tls_connect & {_args: {
+					can_enable:             false
+					can_verify_certificate: true
+					can_verify_hostname:    true
+					enabled_default:        false
+				}}
+			}
+		}
 		query_parameters: {
 			common:      false
-			description: "A list of URL query parameters to include in the log event. These will override any values included in the body with conflicting names."
+			description: "A list of URL query parameters to include in the log event. These will override any values included in the body with conflicting names. A parameter can be stored in a field with a different name with `{ name = \"src\", field = \"source\" }`."
 			required:    false
 			type: array: {
 				default: null
@@ -99,6 +223,20 @@ components: sources: http: {
 				}
 			}
 		}
+		method: {
+			common:      false
+			description: "The HTTP method that requests must be sent with."
+			required:    false
+			type: string: {
+				default: "POST"
+				enum: {
+					POST:   "HTTP POST method"
+					PUT:    "HTTP PUT method"
+					PATCH:  "HTTP PATCH method"
+					DELETE: "HTTP DELETE method"
+				}
+			}
+		}
 		path: {
 			common:      false
 			description: "The URL path on which log event POST requests shall be sent."
@@ -128,6 +266,44 @@ components: sources: http: {
 				examples: ["vector_http_path"]
 			}
 		}
+		response: {
+			common:      false
+			description: "The response sent once the events of a request have been accepted. Requests that fail still receive an error response."
+			required:    false
+			type: object: options: {
+				body: {
+					common:      false
+					description: "The response body. `{{ count }}` is replaced with the number of events received in the request."
+					required:    false
+					type: string: {
+						default: null
+						examples: [#"{"accepted":{{ count }}}"#]
+					}
+				}
+				headers: {
+					common:      false
+					description: "Headers to add to the response."
+					required:    false
+					type: object: {
+						examples: [
+							{
+								"Content-Type": "application/json"
+							},
+						]
+						options: {}
+					}
+				}
+				status_code: {
+					common:      false
+					description: "The response status code, which must be a `2xx` status code."
+					required:    false
+					type: uint: {
+						default: 200
+						unit:    null
+					}
+				}
+			}
+		}
 	}
 	output: logs: {
 		text: {
@@ -238,6 +238,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		cloudtrail_digest_mismatches_total: {
+			description:       "The total number of CloudTrail log files whose contents didn't match the hash in their digest file."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		checkpoint_write_errors_total: {
 			description:       "The total number of errors writing checkpoints. This metric is deprecated in favor of `component_errors_total`."
 			type:              "counter"
@@ -258,6 +264,34 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		circuit_breaker_rejected_requests_total: {
+			description:       "The total number of requests held back by an open circuit breaker. Each is retried once the breaker lets requests through again."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		circuit_breaker_state: {
+			description:       "The state of the circuit breaker of a sink's requests: `0` when closed, `1` when half-open and `2` when open."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		circuit_breaker_transitions_total: {
+			description:       "The total number of times the circuit breaker of a sink's requests changed state."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				state: {
+					description: "The state the breaker changed to."
+					required:    true
+					enum: {
+						closed:    "Requests are sent to the endpoint again."
+						half_open: "A single request is probing the endpoint."
+						open:      "Requests are held back from the endpoint."
+					}
+				}
+			}
+		}
 		collect_completed_total: {
 			description:       "The total number of metrics collections completed for this component."
 			type:              "counter"
@@ -288,6 +322,22 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		connection_limit_reached_total: {
+			description:       "The total number of times a TCP source stopped accepting connections because it reached one of its connection limits."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				limit: {
+					description: "The limit that was reached."
+					required:    true
+					enum: {
+						global: "The `max_connections` global option."
+						rate:   "The `connection_rate_limit` option."
+						source: "The `connection_limit` option."
+					}
+				}
+			}
+		}
 		connection_read_errors_total: {
 			description:       "The total number of errors reading datagram."
 			type:              "counter"
@@ -302,6 +352,20 @@ components: sources: internal_metrics: {
 				}
 			}
 		}
+		connections_rejected_total: {
+			description:       "The total number of TCP connections closed as soon as they were accepted."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				reason: {
+					description: "Why the connection was rejected."
+					required:    true
+					enum: {
+						per_ip_limit: "The `connection_limit_per_ip` option was reached for the IP address of the connection."
+					}
+				}
+			}
+		}
 		consumer_offset_updates_failed_total: {
 			description:       "The total number of failures to update a Kafka consumer offset."
 			type:              "counter"
@@ -429,6 +493,21 @@ components: sources: internal_metrics: {
 				reason: _reason
 			}
 		}
+		events_excluded_total: {
+			description:       "The total number of events dropped by a Datadog exclusion filter."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				index: {
+					description: "The Datadog log index the exclusion filter belongs to."
+					required:    true
+				}
+				exclusion_filter: {
+					description: "The name of the exclusion filter."
+					required:    true
+				}
+			}
+		}
 		events_failed_total: {
 			description:       "The total number of failures to read a Kafka message."
 			type:              "counter"
@@ -509,12 +588,39 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		component_allocated_bytes: {
+			description: """
+				The number of bytes currently allocated on the heap by this component. Only
+				reported when Vector is built with the `allocation-tracing` feature and started
+				with `--allocation-tracing`. Memory is attributed to the component that allocated
+				it, even after it is passed on to another component.
+				"""
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _internal_metrics_tags & {
+				component_kind: _component_kind
+				component_id:   _component_id
+				component_type: _component_type
+			}
+		}
+		component_degraded: {
+			description:       "Whether a sink with a `required` health check is degraded: `1` if its last health check failed, `0` otherwise."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_discarded_events_total: {
 			description:       "The number of events dropped by this component."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		component_healthy: {
+			description:       "Whether the last health check of a sink passed: `1` if it did, `0` otherwise."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		component_received_bytes_total: {
 			description:       "The number of raw bytes accepted by this component from source origins."
 			type:              "counter"
@@ -612,6 +718,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		late_events_total: {
+			description:       "The number of events that arrived after the event-time watermark."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		kafka_queue_messages: {
 			description:       "Current number of messages in producer queues."
 			type:              "gauge"
@@ -782,6 +894,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		injected_backpressure_total: {
+			description:       "The total number of times the `blackhole` sink stopped taking events to simulate backpressure."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		injected_errors_total: {
+			description:       "The total number of requests the `blackhole` sink failed to simulate errors."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		invalid_record_total: {
 			description:       "The total number of invalid records that have been discarded."
 			type:              "counter"
@@ -827,6 +951,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		ownership_errors_total: {
+			description:       "The total number of errors claiming, renewing, or listing partition ownership."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		parse_errors_total: {
 			description:       "The total number of errors parsing metrics for this component."
 			type:              "counter"
@@ -930,6 +1060,51 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		spool_dropped_bytes_total: {
+			description:       "The total number of bytes of datagrams dropped because a UDP source's spool was full."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		spool_dropped_datagrams_total: {
+			description:       "The total number of datagrams dropped because a UDP source's spool was full."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		source_last_event_timestamp_seconds: {
+			description:       "The Unix timestamp of the last event a source with a `silence` option sent, in total or for a partition of its events."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				partition: {
+					description: "The value of the source's `silence.partition_key` the metric is about, if any."
+					required:    false
+				}
+			}
+		}
+		source_silences_total: {
+			description:       "The number of times a source with a `silence` option, or a partition of its events, sent no events for longer than its threshold."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				partition: {
+					description: "The value of the source's `silence.partition_key` the metric is about, if any."
+					required:    false
+				}
+			}
+		}
+		source_silent: {
+			description:       "Whether a source with a `silence` option, or a partition of its events, is currently silent, as 1, or not, as 0."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				partition: {
+					description: "The value of the source's `silence.partition_key` the metric is about, if any."
+					required:    false
+				}
+			}
+		}
 		splunk_pending_acks: {
 			description:       "The number of outstanding Splunk HEC indexer acknowledgement acks."
 			type:              "gauge"
@@ -1020,12 +1195,59 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		tenant_bytes_total: {
+			description:       "The total number of bytes, as estimated for buffering, accepted for each tenant by the `tenant_quota` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				tenant: _tenant
+			}
+		}
+		tenant_events_total: {
+			description:       "The total number of events accepted for each tenant by the `tenant_quota` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				tenant: _tenant
+			}
+		}
 		timestamp_parse_errors_total: {
 			description:       "The total number of errors encountered parsing [RFC 3339](\(urls.rfc_3339)) timestamps."
 			type:              "counter"
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		timestamps_out_of_bounds_total: {
+			description:       "The total number of events whose timestamp was outside the bounds of the `timestamp_policy` transform."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				action: {
+					description: "The action taken on the event."
+					required:    true
+					enum: {
+						annotate: "The event was only annotated."
+						clamp:    "The timestamp was replaced with the bound it crossed."
+						drop:     "The event was dropped."
+						replace:  "The timestamp was replaced with the current time."
+					}
+				}
+				bound: {
+					description: "The bound the timestamp crossed."
+					required:    true
+					enum: {
+						future: "The timestamp was further in the future than `max_future_secs`."
+						past:   "The timestamp was further in the past than `max_past_secs`."
+					}
+				}
+			}
+		}
+		traces_evicted_total: {
+			description:       "The total number of traces decided early because the `tail_sampling` transform held `max_traces` traces."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		uptime_seconds: {
 			description:       "The total number of seconds the Vector instance has been up."
 			type:              "gauge"
@@ -1216,6 +1438,11 @@ components: sources: internal_metrics: {
 			description: "The path that produced the error."
 			required:    true
 		}
+		_tenant: {
+			description: "The tenant the event belongs to, as rendered by `tenant_key`."
+			required:    true
+			examples: ["acme"]
+		}
 		_reason: {
 			description: "The type of the error"
 			required:    true
@@ -0,0 +1,216 @@
+package metadata
+
+components: sources: internal_traces: {
+	title:       "Internal Traces"
+	description: "The internal traces source exposes the tracing spans recorded by the running Vector instance, such as the spans around each component."
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			from: service: {
+				name:     "Vector instance"
+				thing:    "a \(name)"
+				url:      urls.vector_docs
+				versions: null
+			}
+		}
+		multiline: enabled: false
+	}
+
+	support: {
+		notices: []
+		requirements: []
+		warnings: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		host_key: {
+			category:    "Context"
+			common:      false
+			description: """
+				The key name added to each event representing the current host. This can also be globally set via the
+				[global `host_key` option](\(urls.vector_configuration)/global-options#log_schema.host_key).
+
+				Set to "" to suppress this key.
+				"""
+			required:    false
+			type: string: {
+				default: "host"
+			}
+		}
+		pid_key: {
+			category: "Context"
+			common:   false
+			description: """
+				The key name added to each event representing the current process ID.
+
+				Set to "" to suppress this key.
+				"""
+			required: false
+			type: string: {
+				default: "pid"
+			}
+		}
+	}
+
+	output: logs: span: {
+		description: "A span, emitted when it closes."
+		fields: {
+			message: {
+				description: "The name of the span."
+				required:    true
+				type: string: {
+					examples: ["source", "transform", "sink"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The exact time the span closed."
+			}
+			start_time: {
+				description: "The exact time the span was created."
+				required:    true
+				type: timestamp: {}
+			}
+			trace_id: {
+				description: "The ID of the outermost span this span is nested in, or of this span when it has no parent."
+				required:    true
+				type: string: {
+					examples: ["000000000000002a"]
+				}
+			}
+			span_id: {
+				description: "The ID of this span. IDs are unique for the lifetime of the Vector process."
+				required:    true
+				type: string: {
+					examples: ["000000000000002b"]
+				}
+			}
+			parent_span_id: {
+				description: "The ID of the span this span is nested in, if any."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["000000000000002a"]
+				}
+			}
+			duration_ns: {
+				description: "How long the span was open."
+				required:    true
+				type: uint: {
+					examples: [1500000]
+					unit: "nanoseconds"
+				}
+			}
+			busy_ns: {
+				description: "How long the span was entered, such as while its task was being polled."
+				required:    true
+				type: uint: {
+					examples: [250000]
+					unit: "nanoseconds"
+				}
+			}
+			idle_ns: {
+				description: "How long the span was open but not entered."
+				required:    true
+				type: uint: {
+					examples: [1250000]
+					unit: "nanoseconds"
+				}
+			}
+			fields: {
+				description: "The fields recorded on the span, such as `component_id` on component spans."
+				required:    true
+				type: object: {
+					examples: [{"component_id": "my_source", "component_kind": "source", "component_type": "file"}]
+					options: {}
+				}
+			}
+			host: fields._local_host
+			pid: {
+				description: "The process ID of the Vector instance."
+				required:    true
+				type: uint: {
+					examples: [4232]
+					unit: null
+				}
+			}
+			metadata: {
+				description: "Metadata from the span's call site."
+				required:    true
+				type: object: {
+					examples: []
+					options: {
+						kind: {
+							description: "What kind of call site caused this event. Always `span`."
+							required:    true
+							type: string: {
+								enum: {
+									span: "The call site is a span."
+								}
+							}
+						}
+						level: {
+							description: "The level of verbosity of the span."
+							required:    true
+							type: string: {
+								enum: {
+									TRACE: "Designates very low priority, often extremely verbose, information."
+									DEBUG: "Designates lower priority information."
+									INFO:  "Designates useful information."
+									WARN:  "Designates hazardous situations."
+									ERROR: "Designates very serious errors."
+								}
+							}
+						}
+						module_path: {
+							description: "The path to the internal module where the span was created."
+							required:    true
+							type: string: {
+								examples: ["vector::topology::builder"]
+							}
+						}
+						target: {
+							description: "Describes the part of the system where the span was created."
+							required:    true
+							type: string: {
+								examples: ["vector"]
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		limited_spans: {
+			title: "Spans are limited by startup options"
+			body: """
+				Like logs, the spans recorded by Vector are selected by the `VECTOR_LOG` environment variable
+				and the `--quiet` and `--verbose` command-line options. Component spans are recorded at the
+				`error` level, so they are always available.
+				"""
+		}
+		overhead: {
+			title: "Overhead"
+			body: """
+				Spans are only timed while an `internal_traces` source is running. The source's own work
+				isn't traced, so it doesn't observe itself.
+				"""
+		}
+	}
+}
@@ -0,0 +1,253 @@
+package metadata
+
+components: sources: jaeger: {
+	_grpc_port:        14250
+	_thrift_http_port: 14268
+
+	title: "Jaeger"
+
+	description: """
+		Receives spans from [Jaeger](\(urls.jaeger)) agents and clients over gRPC, and from clients posting
+		Thrift batches over HTTP, in place of a Jaeger collector.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.jaeger
+
+				interface: socket: {
+					api: {
+						title: "Jaeger collector APIs"
+						url:   urls.jaeger_apis
+					}
+					direction: "incoming"
+					port:      _grpc_port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._acknowledgements
+		grpc: {
+			common:      true
+			description: "Receives batches with the `jaeger.api_v2.CollectorService` gRPC service, which the Jaeger agent and clients exporting over gRPC report to. At least one of `grpc` or `thrift_http` must be set."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					address: {
+						description: "The address to accept gRPC connections on. The address _must_ include a port."
+						required:    true
+						type: string: {
+							examples: ["0.0.0.0:\(_grpc_port)"]
+						}
+					}
+					tls: configuration._tls_accept & {_args: {
+						can_enable:             true
+						can_verify_certificate: true
+						enabled_default:        false
+					}}
+				}
+			}
+		}
+		thrift_http: {
+			common:      true
+			description: "Receives Thrift batches, encoded with the binary protocol, posted by clients that report straight to the collector. At least one of `grpc` or `thrift_http` must be set."
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					address: {
+						description: "The address to accept HTTP connections on. The address _must_ include a port."
+						required:    true
+						type: string: {
+							examples: ["0.0.0.0:\(_thrift_http_port)"]
+						}
+					}
+					path: {
+						common:      false
+						description: "The URL path clients post batches to."
+						required:    false
+						type: string: {
+							default: "/api/traces"
+						}
+					}
+					tls: configuration._tls_accept & {_args: {
+						can_enable:             true
+						can_verify_certificate: true
+						enabled_default:        false
+					}}
+				}
+			}
+		}
+	}
+
+	output: logs: span: {
+		description: "A span reported by a client."
+		fields: {
+			message: {
+				description: "The operation name of the span."
+				required:    true
+				type: string: {
+					examples: ["get /api"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the span started, or the time it was received if the client didn't record it."
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["jaeger"]
+				}
+			}
+			trace_id: {
+				description: "The ID of the trace the span belongs to, as 32 hex characters."
+				required:    true
+				type: string: {
+					examples: ["5982fe77008310cc80f1da5e10147517"]
+				}
+			}
+			span_id: {
+				description: "The ID of the span, as 16 hex characters."
+				required:    true
+				type: string: {
+					examples: ["67fae42571535f60"]
+				}
+			}
+			parent_span_id: {
+				description: "The ID of the first span in the same trace that this span is a child of, if any."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["90394f6bcffb5d13"]
+				}
+			}
+			references: {
+				description: "The spans this span refers to, each with a `trace_id`, `span_id` and a `ref_type` of `child_of` or `follows_from`."
+				required:    false
+				type: array: {
+					default: null
+					items: type: object: {
+						examples: [{"trace_id": "5982fe77008310cc80f1da5e10147517", "span_id": "90394f6bcffb5d13", "ref_type": "child_of"}]
+						options: {}
+					}
+				}
+			}
+			kind: {
+				description: "The `span.kind` tag of the span, if it has one."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["server", "client"]
+				}
+			}
+			duration_ns: {
+				description: "How long the span took."
+				required:    false
+				type: uint: {
+					default: null
+					examples: [1500000]
+					unit: "nanoseconds"
+				}
+			}
+			service: {
+				description: "The service name of the process that recorded the span."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["backend"]
+				}
+			}
+			process_tags: {
+				description: "The tags of the process that recorded the span, such as `hostname`."
+				required:    false
+				type: object: {
+					examples: [{"hostname": "host-1", "jaeger.version": "Go-2.30.0"}]
+					options: {}
+				}
+			}
+			tags: {
+				description: "The tags of the span, keeping the type of their values."
+				required:    false
+				type: object: {
+					examples: [{"span.kind": "server", "http.status_code": 200}]
+					options: {}
+				}
+			}
+			logs: {
+				description: "The logs recorded during the span, each with a `timestamp` and its `fields`."
+				required:    false
+				type: array: {
+					default: null
+					items: type: object: {
+						examples: [{"timestamp": "2020-09-13T12:26:40.124Z", "fields": {"event": "error"}}]
+						options: {}
+					}
+				}
+			}
+			warnings: {
+				description: "Warnings the client recorded about the span."
+				required:    false
+				type: array: {
+					default: null
+					items: type: string: {}
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		spans_as_logs: {
+			title: "Spans are log events"
+			body: """
+				Each span is output as a log event, with its IDs and duration in the same fields as the
+				`internal_traces` and `zipkin` sources, so they can be remapped for sinks with tracing APIs.
+				"""
+		}
+		protocols: {
+			title: "Protocols"
+			body: """
+				Either or both of the gRPC and Thrift over HTTP endpoints of the Jaeger collector can be served.
+				The agent's UDP endpoints, which take Thrift batches in the compact protocol, aren't supported,
+				so clients that report to a local agent should keep doing so and point the agent at the `grpc`
+				address.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		http_bad_requests_total:              components.sources.internal_metrics.output.metrics.http_bad_requests_total
+	}
+}
@@ -68,6 +68,35 @@ components: sources: logstash: {
 				unit:    "concurrency"
 			}
 		}
+		connection_limit_per_ip: {
+			common:        false
+			description:   "The max number of TCP connections that will be processed from a single IP address. When the `proxy_protocol` option is enabled this applies to the load balancer's address rather than the client's."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: uint: {
+				default: null
+				unit:    "concurrency"
+			}
+		}
+		connection_rate_limit: {
+			common:        false
+			description:   "The max number of TCP connections that will be accepted per second. Connections beyond it wait in the operating system's backlog until they can be accepted."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: uint: {
+				default: null
+				unit:    null
+			}
+		}
+		tls_client_metadata_key: {
+			common:      false
+			description: "The key to store the subject of the client's TLS certificate in, when `tls.verify_certificate` is enabled and the client presents a certificate."
+			required:    false
+			type: string: {
+				default:  null
+				examples: ["tls_client"]
+			}
+		}
 	}
 
 	output: logs: line: {
@@ -301,6 +330,20 @@ components: sources: logstash: {
 				"""
 		}
 
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Events are acknowledged to the client once they have been received, or once they have been delivered to
+				all connected sinks when `acknowledgements` is enabled. Beats send events in windows and wait for the last
+				event of a window to be acknowledged before sending more. Vector may acknowledge part of a window first,
+				for example when the rest of it is held up by backpressure, and acknowledges the end of each window
+				separately for clients that have `pipelining` enabled.
+
+				While waiting for events to be delivered, Vector sends a keepalive acknowledgement every five seconds to
+				clients using version 2 of the protocol, so that they don't time out waiting on slow sinks.
+				"""
+		}
+
 		acking: {
 			title: "Acknowledgement support"
 			body: """
@@ -321,5 +364,7 @@ components: sources: logstash: {
 		processed_events_total:           components.sources.internal_metrics.output.metrics.processed_events_total
 		component_received_bytes_total:   components.sources.internal_metrics.output.metrics.component_received_bytes_total
 		component_received_events_total:  components.sources.internal_metrics.output.metrics.component_received_events_total
+		connection_limit_reached_total:   components.sources.internal_metrics.output.metrics.connection_limit_reached_total
+		connections_rejected_total:       components.sources.internal_metrics.output.metrics.connections_rejected_total
 	}
 }
@@ -0,0 +1,109 @@
+package metadata
+
+components: sources: named_pipe: {
+	title: "Named Pipe"
+
+	description: """
+		Reads from a named pipe: a FIFO on Unix, or a pipe that Vector creates on Windows. The pipe is
+		opened again whenever its writers close it, so applications that only log to a FIFO can restart
+		without Vector needing to.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
+		receive: {
+			from: {
+				service: services.named_pipe
+				interface: file_system: {
+					directory: "/var/run"
+				}
+			}
+
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				On Unix, the FIFO at `path` must already exist, for example by creating it with `mkfifo`.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		host_key: {
+			category:    "Context"
+			common:      false
+			description: """
+				The key name added to each event representing the current host. This can also be globally set via the
+				[global `host_key` option](\(urls.vector_configuration)/global-options#log_schema.host_key).
+				"""
+			required:    false
+			type: string: {
+				default: "host"
+			}
+		}
+		path: {
+			description: "The FIFO to read from on Unix, or the name of the pipe to create on Windows."
+			required:    true
+			type: string: examples: ["/var/run/vector.fifo", #"\\.\pipe\vector"#]
+		}
+		reopen_delay_ms: {
+			common:      false
+			description: "How long to wait before trying again when the pipe can't be opened."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "milliseconds"
+			}
+		}
+	}
+
+	output: logs: line: {
+		description: "An individual event read from the pipe."
+		fields: {
+			host:      fields._local_host
+			message:   fields._raw_line
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		reopening: {
+			title: "Reopening"
+			body: """
+				A pipe reaches its end once all of its writers have closed it. Rather than stopping there, the
+				source flushes any unterminated line and opens the pipe again to wait for the next writer, so
+				each writer's output is framed separately. On Windows, the next instance of the pipe is created
+				before the current one is read, so writers reconnecting straight away always find it.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_events_total: components.sources.internal_metrics.output.metrics.component_received_events_total
+		connection_errors_total:         components.sources.internal_metrics.output.metrics.connection_errors_total
+		events_in_total:                 components.sources.internal_metrics.output.metrics.events_in_total
+		processed_bytes_total:           components.sources.internal_metrics.output.metrics.processed_bytes_total
+	}
+}
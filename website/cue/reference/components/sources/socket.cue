@@ -124,6 +124,30 @@ components: sources: socket: {
 				unit:    "concurrency"
 			}
 		}
+		connection_limit_per_ip: {
+			common:        false
+			description:   "The max number of TCP connections that will be processed from a single IP address. When the `proxy_protocol` option is enabled this applies to the load balancer's address rather than the client's."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: uint: {
+				default: null
+				unit:    "concurrency"
+			}
+		}
+		connection_rate_limit: {
+			common:        false
+			description:   "The max number of TCP connections that will be accepted per second. Connections beyond it wait in the operating system's backlog until they can be accepted."
+			relevant_when: "mode = `tcp`"
+			required:      false
+			type: uint: {
+				default: null
+				unit:    null
+			}
+		}
+		proxy_protocol:     configuration._proxy_protocol & {relevant_when: "mode = `tcp`"}
+		receive_batch_size: configuration._udp_receive_batch_size
+		sockets:            configuration._udp_sockets
+		spool:              configuration._udp_spool
 	}
 
 	output: logs: line: {
@@ -164,5 +188,9 @@ components: sources: socket: {
 		connection_shutdown_total:        components.sources.internal_metrics.output.metrics.connection_shutdown_total
 		component_received_bytes_total:   components.sources.internal_metrics.output.metrics.component_received_bytes_total
 		component_received_events_total:  components.sources.internal_metrics.output.metrics.component_received_events_total
+		connection_limit_reached_total:   components.sources.internal_metrics.output.metrics.connection_limit_reached_total
+		connections_rejected_total:       components.sources.internal_metrics.output.metrics.connections_rejected_total
+		spool_dropped_bytes_total:        components.sources.internal_metrics.output.metrics.spool_dropped_bytes_total
+		spool_dropped_datagrams_total:    components.sources.internal_metrics.output.metrics.spool_dropped_datagrams_total
 	}
 }
@@ -0,0 +1,174 @@
+package metadata
+
+components: sources: sse: {
+	title: "Server-Sent Events"
+
+	description: """
+		Connects to a remote [server-sent events](\(urls.server_sent_events)) endpoint, one
+		responding with a `text/event-stream`, and decodes the data of the events it streams, such
+		as those of Kubernetes watch proxies and firehose-style APIs. The stream is re-established
+		whenever it ends, resuming from the last event received for endpoints that support it.
+		"""
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			from: {
+				service: {
+					name:     "Server-Sent Events"
+					thing:    "a \(name) endpoint"
+					url:      urls.server_sent_events
+					versions: null
+				}
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The `http://` or `https://` URI of the event stream."
+			required:    true
+			type: string: {
+				examples: ["https://stream.example.com/v1/events"]
+			}
+		}
+		event_types: {
+			common:      false
+			description: "The types of the events to receive, as set by their `event` field. Events without one have the `message` type. All events are received if empty."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["update", "message"]
+				}
+			}
+		}
+		headers: {
+			common:      false
+			description: "Headers sent with every request, such as those authenticating it."
+			required:    false
+			type: object: {
+				examples: [{"Authorization": "Bearer ${API_TOKEN}"}]
+				options: {}
+			}
+		}
+		retry_secs: {
+			common:      false
+			description: "How long to wait before reconnecting once the endpoint ends the stream, unless the endpoint sets it with a `retry` field."
+			required:    false
+			type: uint: {
+				default: 3
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "The data of an individual event, or each event decoded from it."
+		fields: {
+			message: {
+				description: "The data of the event. Data split over several `data` fields is joined with newlines."
+				required:    true
+				type: string: {
+					examples: [#"{"type":"ADDED","object":{"kind":"Pod"}}"#]
+				}
+			}
+			sse: {
+				description: "The event the data was received in."
+				required:    true
+				type: object: options: {
+					event: {
+						description: "The type of the event, `message` unless set by its `event` field."
+						required:    true
+						type: string: {
+							examples: ["message", "update"]
+						}
+					}
+					id: {
+						description: "The ID of the last event that set one."
+						required:    false
+						type: string: {
+							examples: ["41"]
+						}
+					}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["sse"]
+				}
+			}
+			timestamp: {
+				description: "The time the event was received."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		reconnecting: {
+			title: "Reconnecting"
+			body:  """
+				Once the endpoint ends the stream, Vector reconnects after the delay the endpoint set
+				with the `retry` field of its events, or [`retry_secs`](#retry_secs) if it didn't.
+				Failed requests, responses other than `200 OK`, and responses that aren't a
+				`text/event-stream` are retried with an exponential backoff, waiting at least as
+				long.
+
+				When reconnecting, Vector sends the ID of the last event received in the
+				`Last-Event-ID` header, so that endpoints that support it resume the stream where it
+				was left off. Events sent while disconnected are otherwise lost.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_events_total: components.sources.internal_metrics.output.metrics.component_received_events_total
+		connection_established_total:    components.sources.internal_metrics.output.metrics.connection_established_total
+		connection_failed_total:         components.sources.internal_metrics.output.metrics.connection_failed_total
+		connection_shutdown_total:       components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		events_in_total:                 components.sources.internal_metrics.output.metrics.events_in_total
+		processed_bytes_total:           components.sources.internal_metrics.output.metrics.processed_bytes_total
+	}
+}
@@ -0,0 +1,183 @@
+package metadata
+
+components: sources: websocket: {
+	title: "WebSocket"
+
+	description: """
+		Connects to a remote [WebSocket](\(urls.websocket)) endpoint, and decodes the messages it
+		streams. Many market data and vendor APIs only stream over WebSocket. The connection is kept
+		alive with pings, and re-established with a backoff whenever it's lost, optionally resuming
+		the stream from the last message received.
+		"""
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			from: {
+				service: {
+					name:     "WebSocket"
+					thing:    "a \(name) endpoint"
+					url:      urls.websocket
+					versions: null
+				}
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		headers: {
+			common:      false
+			description: "Headers sent with the opening handshake, such as those authenticating the connection."
+			required:    false
+			type: object: {
+				examples: [{"Authorization": "Bearer ${API_TOKEN}"}]
+				options: {}
+			}
+		}
+		ping_interval_secs: {
+			common:      false
+			description: "How often the endpoint is pinged, to tell when the connection was lost."
+			required:    false
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+		ping_timeout_secs: {
+			common:      false
+			description: "How long to wait for a message after pinging the endpoint, before reconnecting."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    "seconds"
+			}
+		}
+		resume_token: {
+			common:      false
+			description: "Resumes the stream from the last message received when reconnecting, for endpoints that support it."
+			required:    false
+			type: object: options: {
+				field: {
+					description: "The field of the decoded events holding the token to resume from."
+					required:    true
+					type: string: {
+						examples: ["sequence"]
+						syntax: "field_path"
+					}
+				}
+				query_parameter: {
+					description: "The query parameter of the URI the token is passed in when reconnecting."
+					required:    true
+					type: string: {
+						examples: ["since"]
+					}
+				}
+			}
+		}
+		subscribe_message: {
+			common:      false
+			description: "A text message sent after every connection, for endpoints that expect the stream to be subscribed to."
+			required:    false
+			type: string: {
+				default: null
+				examples: [#"{"type":"subscribe","channels":["trades"]}"#]
+			}
+		}
+		uri: {
+			description: "The `ws://` or `wss://` URI of the endpoint to connect to."
+			required:    true
+			type: string: {
+				examples: ["wss://stream.example.com/v1/trades"]
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual WebSocket message, or each event decoded from it."
+		fields: {
+			message: {
+				description: "The raw message, as text."
+				required:    true
+				type: string: {
+					examples: [#"{"sequence":41,"price":"1.5"}"#]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["websocket"]
+				}
+			}
+			timestamp: {
+				description: "The time the message was received."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		reconnecting: {
+			title: "Reconnecting"
+			body:  """
+				The endpoint is pinged every [`ping_interval_secs`](#ping_interval_secs) seconds. If
+				no message arrives within [`ping_timeout_secs`](#ping_timeout_secs) seconds of a ping,
+				or the endpoint closes the connection, Vector reconnects with an exponential backoff,
+				and sends [`subscribe_message`](#subscribe_message) again.
+
+				Messages sent by the endpoint while disconnected are lost, unless it can resume the
+				stream. With [`resume_token`](#resume_token), Vector remembers the value of the given
+				field of the last event received, and passes it in the given query parameter of the
+				URI when reconnecting.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_events_total: components.sources.internal_metrics.output.metrics.component_received_events_total
+		connection_established_total:    components.sources.internal_metrics.output.metrics.connection_established_total
+		connection_failed_total:         components.sources.internal_metrics.output.metrics.connection_failed_total
+		connection_shutdown_total:       components.sources.internal_metrics.output.metrics.connection_shutdown_total
+		events_in_total:                 components.sources.internal_metrics.output.metrics.events_in_total
+		processed_bytes_total:           components.sources.internal_metrics.output.metrics.processed_bytes_total
+	}
+}
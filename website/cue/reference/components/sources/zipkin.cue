@@ -0,0 +1,224 @@
+package metadata
+
+components: sources: zipkin: {
+	_port: 9411
+
+	title: "Zipkin"
+
+	description: """
+		Receives spans from [Zipkin](\(urls.zipkin)) tracers in the Zipkin v2 JSON and protobuf formats,
+		in place of a Zipkin collector.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.zipkin
+
+				interface: socket: {
+					api: {
+						title: "Zipkin v2 API"
+						url:   urls.zipkin_api
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: configuration._acknowledgements
+		address: {
+			description: "The address to accept connections on. The address _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)", "localhost:\(_port)"]
+			}
+		}
+		path: {
+			common:      false
+			description: "The URL path tracers post spans to."
+			required:    false
+			type: string: {
+				default: "/api/v2/spans"
+			}
+		}
+	}
+
+	output: logs: span: {
+		description: "A span reported by a tracer."
+		fields: {
+			message: {
+				description: "The name of the span."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["get /api"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the span started, or the time it was received if the tracer didn't record it."
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["zipkin"]
+				}
+			}
+			trace_id: {
+				description: "The ID of the trace the span belongs to, as 16 or 32 hex characters."
+				required:    true
+				type: string: {
+					examples: ["5982fe77008310cc80f1da5e10147517"]
+				}
+			}
+			span_id: {
+				description: "The ID of the span, as 16 hex characters."
+				required:    true
+				type: string: {
+					examples: ["67fae42571535f60"]
+				}
+			}
+			parent_span_id: {
+				description: "The ID of the span's parent, unless it's the root span of the trace."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["90394f6bcffb5d13"]
+				}
+			}
+			kind: {
+				description: "The kind of span, if the tracer set one."
+				required:    false
+				type: string: {
+					default: null
+					enum: {
+						client:   "The client side of an RPC."
+						server:   "The server side of an RPC."
+						producer: "The sending side of a message."
+						consumer: "The receiving side of a message."
+					}
+				}
+			}
+			duration_ns: {
+				description: "How long the span took. Zipkin records durations in microseconds."
+				required:    false
+				type: uint: {
+					default: null
+					examples: [1500000]
+					unit: "nanoseconds"
+				}
+			}
+			service: {
+				description: "The service name of the span's local endpoint."
+				required:    false
+				type: string: {
+					default: null
+					examples: ["backend"]
+				}
+			}
+			local_endpoint: {
+				description: "The `service_name`, `ipv4`, `ipv6` and `port` of the host that recorded the span."
+				required:    false
+				type: object: {
+					examples: [{"service_name": "backend", "ipv4": "192.168.99.101", "port": 9000}]
+					options: {}
+				}
+			}
+			remote_endpoint: {
+				description: "The `service_name`, `ipv4`, `ipv6` and `port` of the other side of an RPC or message."
+				required:    false
+				type: object: {
+					examples: [{"service_name": "frontend"}]
+					options: {}
+				}
+			}
+			annotations: {
+				description: "The events recorded during the span, each with a `timestamp` and `value`."
+				required:    false
+				type: array: {
+					default: null
+					items: type: object: {
+						examples: [{"timestamp": "2020-09-13T12:26:40.124Z", "value": "ws"}]
+						options: {}
+					}
+				}
+			}
+			tags: {
+				description: "The tags of the span."
+				required:    false
+				type: object: {
+					examples: [{"http.method": "GET", "http.path": "/api"}]
+					options: {}
+				}
+			}
+			debug: {
+				description: "Set to `true` when the tracer forced the span to be sampled."
+				required:    false
+				type: bool: default: null
+			}
+			shared: {
+				description: "Set to `true` when the span shares its ID with a span started by the client."
+				required:    false
+				type: bool: default: null
+			}
+		}
+	}
+
+	how_it_works: {
+		spans_as_logs: {
+			title: "Spans are log events"
+			body: """
+				Each span is output as a log event, with its IDs and duration in the same fields as the
+				`internal_traces` and `jaeger` sources, so they can be remapped for sinks with tracing APIs.
+				"""
+		}
+		formats: {
+			title: "Formats"
+			body: """
+				Requests with a `Content-Type` of `application/x-protobuf` are decoded as a protobuf `ListOfSpans`,
+				and any other request as a JSON array of spans. Zipkin v1 spans aren't supported. Gzipped requests
+				are decompressed as set by their `Content-Encoding`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		http_bad_requests_total:              components.sources.internal_metrics.output.metrics.http_bad_requests_total
+	}
+}
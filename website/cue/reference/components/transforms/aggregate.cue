@@ -27,6 +27,20 @@ components: transforms: aggregate: {
 	}
 
 	configuration: {
+		flush_schedule: {
+			common: false
+			description: """
+				A cron schedule, evaluated in UTC, at which aggregated metrics are flushed instead of every
+				`interval_ms`, such as `0 * * * *` for the top of each hour. The five standard cron fields are
+				supported, as are the `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly` shorthands.
+				Components with the same schedule are flushed together.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: ["0 * * * *", "*/15 * * * *", "@daily"]
+			}
+		}
 		interval_ms: {
 			common: true
 			description: """
@@ -0,0 +1,129 @@
+package metadata
+
+components: transforms: anomaly_detection: {
+	title: "Anomaly Detection"
+
+	description: """
+		Flags events whose rate, or the value of a numeric field, deviates from its recent history, tracked
+		separately for each group of events.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		alpha: {
+			common:      false
+			description: "The weight given to each new sample in the exponentially weighted moving average (EWMA) of the series, greater than 0 and at most 1. Smaller values make the history adjust more slowly."
+			required:    false
+			type: float: default: 0.1
+		}
+		anomalies_only: {
+			common:      false
+			description: "Drop events that are not anomalous, so that only anomalies are forwarded."
+			required:    false
+			type: bool: default: false
+		}
+		anomaly_field: {
+			common:      false
+			description: "The field to store whether the event is anomalous in."
+			required:    false
+			type: string: default: "is_anomaly"
+		}
+		field: {
+			common:      true
+			description: "The numeric field to track. If not set, the rate of events is tracked instead. Events without a finite number in the field are passed on without being scored."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["duration_ms"]
+			}
+		}
+		group_by: {
+			common:      true
+			description: "The fields whose values identify each series. Every combination of values is tracked separately."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: examples: ["host", "service"]
+			}
+		}
+		idle_timeout_secs: {
+			common:      false
+			description: "How long a series may go without events before its history is forgotten. A series that gets events again starts over."
+			required:    false
+			type: uint: {
+				default: 600
+				unit:    "seconds"
+			}
+		}
+		min_samples: {
+			common:      false
+			description: "The number of samples a series needs before its events are scored."
+			required:    false
+			type: uint: {
+				default: 10
+				unit:    null
+			}
+		}
+		score_field: {
+			common:      false
+			description: "The field to store the z-score of the event in."
+			required:    false
+			type: string: default: "anomaly_score"
+		}
+		threshold: {
+			common:      true
+			description: "The z-score beyond which an event is anomalous."
+			required:    false
+			type: float: default: 3.0
+		}
+		window_secs: {
+			common:      false
+			description: "The window events are counted over when tracking the rate of events."
+			required:    false
+			type: float: default: 1.0
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		scoring: {
+			title: "Scoring"
+			body: """
+				Each series keeps an exponentially weighted moving average and variance of its samples. An event is
+				scored with the z-score of its sample, the number of standard deviations it lies from the average,
+				before the sample is added to the series. Both the score and whether it exceeds `threshold` are added
+				to the event. Events are not scored until the series has `min_samples` samples, and has varied at
+				all.
+
+				When `field` is set, each event is a sample. Otherwise the number of events in each window of
+				`window_secs`, by event timestamp, is a sample, and events are scored against the count of their
+				window so far. Since a window that is still filling up can't be told apart from a quiet one, only
+				rates that are too high are flagged in this mode.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
@@ -0,0 +1,158 @@
+package metadata
+
+components: transforms: batch_boundaries: {
+	title: "Batch Boundaries"
+
+	description: """
+		Detects the records that mark the boundaries of a batch, such as the `BEGIN REPORT` and `END REPORT`
+		lines of mainframe-style feeds, and assigns the events between them a shared batch ID. A summary of each
+		batch can be emitted once it closes.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		batch_id_field: {
+			common:      false
+			description: "The field to store the batch ID in."
+			required:    false
+			type: string: default: "batch_id"
+		}
+		emit_summaries: {
+			common:      true
+			description: "Emit a summary event for each batch once it closes, alongside the events themselves."
+			required:    false
+			type: bool: default: false
+		}
+		ends_when: {
+			common: true
+			description: """
+				A condition matching the events that end a batch. At least one of `starts_when` and `ends_when`
+				must be set. Without `starts_when`, the next batch starts with the event after the end of the last
+				one.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: [#"starts_with!(.message, "END REPORT")"#]
+			}
+		}
+		flush_period_ms: {
+			common:      false
+			description: "How often to check for batches that have timed out. Must be greater than `0`."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "milliseconds"
+			}
+		}
+		group_by: {
+			common:      false
+			description: "The fields that identify which feed an event belongs to, so that interleaved feeds are batched separately."
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: examples: ["file", "host"]
+			}
+		}
+		include_boundaries: {
+			common:      false
+			description: "Pass on the boundary events themselves, with the ID of the batch they start or end."
+			required:    false
+			type: bool: default: true
+		}
+		starts_when: {
+			common: true
+			description: """
+				A condition matching the events that start a batch. If a batch is still open, it's closed first.
+				With `starts_when` set, events outside of any batch are passed on without a batch ID.
+				"""
+			required: false
+			type: string: {
+				default: null
+				examples: [#"starts_with!(.message, "BEGIN REPORT")"#]
+			}
+		}
+		timeout_ms: {
+			common:      false
+			description: "How long a batch may go without events before it's closed as incomplete. Without it, batches whose end never comes would be kept in memory forever. Must be greater than `0`."
+			required:    false
+			type: uint: {
+				default: 300000
+				unit:    "milliseconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	output: logs: summary: {
+		description: "The summary of a batch, emitted when it closes if `emit_summaries` is set. It also has the `group_by` fields of the batch, and its ID in `batch_id_field`."
+		fields: {
+			batch_end: {
+				description: "The latest timestamp of the events of the batch."
+				required:    true
+				type: timestamp: {}
+			}
+			batch_start: {
+				description: "The earliest timestamp of the events of the batch."
+				required:    true
+				type: timestamp: {}
+			}
+			close_reason: {
+				description: "Why the batch closed."
+				required:    true
+				type: string: {
+					enum: {
+						end_boundary:   "An event matched `ends_when`."
+						shutdown:       "Vector stopped, or the transform was reloaded."
+						start_boundary: "An event matched `starts_when`, starting the next batch."
+						timeout:        "No events arrived for `timeout_ms`."
+					}
+				}
+			}
+			complete: {
+				description: "Whether the batch ended at its end boundary, or at the start of the next batch if there's no `ends_when`."
+				required:    true
+				type: bool: {}
+			}
+			event_count: {
+				description: "The number of events in the batch, not counting its boundaries."
+				required:    true
+				type: uint: unit: null
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		batches: {
+			title: "Batches"
+			body: """
+				Open batches are tracked in memory, one for each value of the `group_by` fields. An event matching
+				`starts_when` opens a new batch with a new ID, and one matching `ends_when` closes it, as does
+				`timeout_ms` passing without events for the batch. Summaries go
+				to the same output as the events, so they can be told apart by the `close_reason` field, which
+				events don't have.
+				"""
+		}
+	}
+}
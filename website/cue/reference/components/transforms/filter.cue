@@ -33,6 +33,8 @@ components: transforms: filter: {
 			required: true
 			type: condition: {}
 		}
+		parallelism:           configuration._parallelism
+		parallelism_key_field: configuration._parallelism_key_field
 	}
 
 	input: {
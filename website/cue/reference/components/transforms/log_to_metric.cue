@@ -25,100 +25,114 @@ components: transforms: log_to_metric: {
 	}
 
 	configuration: {
+		all_metrics: {
+			description: """
+				Converts each log event created by the `metric_to_log` transform back into the
+				metric it was created from, ignoring `metrics`. All metric types are supported,
+				so metrics can be modified with `remap` in between without losing data.
+				"""
+			required: false
+			common:   false
+			type: bool: default: false
+		}
 		metrics: {
-			description: "A table of key/value pairs representing the keys to be added to the event."
-			required:    true
-			type: array: items: type: object: {
-				examples: []
-				options: {
-					field: {
-						description: "The log field to use as the metric."
-						required:    true
-						type: string: {
-							examples: ["duration", "parent.child"]
+			description: "A table of key/value pairs representing the keys to be added to the event. Required unless `all_metrics` is `true`."
+			required:    false
+			common:      true
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: []
+					options: {
+						field: {
+							description: "The log field to use as the metric."
+							required:    true
+							type: string: {
+								examples: ["duration", "parent.child"]
+							}
 						}
-					}
-					increment_by_value: {
-						description: """
-							If `true` the metric will be incremented by the `field` value.
-							If `false` the metric will be incremented by 1 regardless of the `field` value.
-							"""
-						required:      false
-						common:        false
-						relevant_when: #"type = "counter""#
-						type: bool: {
-							default: false
+						increment_by_value: {
+							description: """
+								If `true` the metric will be incremented by the `field` value.
+								If `false` the metric will be incremented by 1 regardless of the `field` value.
+								"""
+							required:      false
+							common:        false
+							relevant_when: #"type = "counter""#
+							type: bool: {
+								default: false
+							}
 						}
-					}
-					kind: {
-						description: """
-							The kind of the metric.
-							"""
-						required:      false
-						common:        false
-						relevant_when: #"type = "counter""#
-						type: string: {
-							enum: {
-								absolute:    "An absolute counter value."
-								incremental: "In incremental counter value."
+						kind: {
+							description: """
+								The kind of the metric.
+								"""
+							required:      false
+							common:        false
+							relevant_when: #"type = "counter""#
+							type: string: {
+								enum: {
+									absolute:    "An absolute counter value."
+									incremental: "In incremental counter value."
+								}
+								default: "incremental"
 							}
-							default: "incremental"
 						}
-					}
-					name: {
-						description: "The name of the metric. Defaults to `<field>_total` for `counter` and `<field>` for `gauge`."
-						required:    false
-						common:      true
-						type: string: {
-							default: null
-							examples: ["duration_total"]
-							syntax: "template"
+						name: {
+							description: "The name of the metric. Defaults to `<field>_total` for `counter` and `<field>` for `gauge`."
+							required:    false
+							common:      true
+							type: string: {
+								default: null
+								examples: ["duration_total"]
+								syntax: "template"
+							}
 						}
-					}
-					namespace: {
-						description: "The namespace of the metric."
-						required:    false
-						common:      true
-						type: string: {
-							default: null
-							examples: ["service"]
-							syntax: "template"
+						namespace: {
+							description: "The namespace of the metric."
+							required:    false
+							common:      true
+							type: string: {
+								default: null
+								examples: ["service"]
+								syntax: "template"
+							}
 						}
-					}
-					tags: {
-						description: "Key/value pairs representing [metric tags](\(urls.vector_metric)#tags)."
-						required:    false
-						common:      true
-						type: object: {
-							examples: [
-								{
-									host:   "${HOSTNAME}"
-									region: "us-east-1"
-									status: "{{status}}"
-								},
-							]
-							options: {
-								"*": {
-									description: """
-	                      Key/value pairs representing [metric tags](\(urls.vector_metric)#tags).
-	                      Environment variables and field interpolation is allowed.
-	                      """
-									required:    true
-									type: "*": {}
+						tags: {
+							description: "Key/value pairs representing [metric tags](\(urls.vector_metric)#tags)."
+							required:    false
+							common:      true
+							type: object: {
+								examples: [
+									{
+										host:   "${HOSTNAME}"
+										region: "us-east-1"
+										status: "{{status}}"
+									},
+								]
+								options: {
+									"*": {
+										description: """
+		                      Key/value pairs representing [metric tags](\(urls.vector_metric)#tags).
+		                      Environment variables and field interpolation is allowed.
+		                      """
+										required:    true
+										type: "*": {}
+									}
 								}
 							}
 						}
-					}
-					type: {
-						description: "The metric type."
-						required:    true
-						type: string: {
-							enum: {
-								counter:   "A [counter metric type](\(urls.vector_metric)#counter)."
-								gauge:     "A [gauge metric type](\(urls.vector_metric)#gauge)."
-								histogram: "A [distribution metric type](\(urls.vector_metric)#histogram) with histogram statistic."
-								set:       "A [set metric type](\(urls.vector_metric)#set)."
-								summary:   "A [distribution metric type](\(urls.vector_metric)#distribution) with summary statistic."
+						type: {
+							description: "The metric type."
+							required:    true
+							type: string: {
+								enum: {
+									counter:   "A [counter metric type](\(urls.vector_metric)#counter)."
+									gauge:     "A [gauge metric type](\(urls.vector_metric)#gauge)."
+									histogram: "A [distribution metric type](\(urls.vector_metric)#histogram) with histogram statistic."
+									set:       "A [set metric type](\(urls.vector_metric)#set)."
+									summary:   "A [distribution metric type](\(urls.vector_metric)#distribution) with summary statistic."
+								}
 							}
 						}
 					}
@@ -26,6 +26,19 @@ components: transforms: reduce: {
 	}
 
 	configuration: {
+		allowed_lateness_ms: {
+			common: false
+			description: """
+				How far behind the latest observed event time an event may arrive before it is considered late.
+				Late events are passed through unreduced instead of being merged into an already advanced
+				transaction. Requires the upstream source to be configured with `event_time`.
+				"""
+			required: false
+			type: uint: {
+				default: null
+				unit:    "milliseconds"
+			}
+		}
 		ends_when: {
 			common: false
 			description: """
@@ -251,6 +264,7 @@ components: transforms: reduce: {
 	]
 
 	telemetry: metrics: {
+		late_events_total:          components.sources.internal_metrics.output.metrics.late_events_total
 		stale_events_flushed_total: components.sources.internal_metrics.output.metrics.stale_events_flushed_total
 	}
 }
@@ -35,7 +35,9 @@ components: transforms: "remap": {
 	}
 
 	configuration: {
-		timezone: configuration._timezone
+		timezone:              configuration._timezone
+		parallelism:           configuration._parallelism
+		parallelism_key_field: configuration._parallelism_key_field
 		source: {
 			description: """
 				The [Vector Remap Language](\(urls.vrl_reference)) (VRL) program to execute for each event.
@@ -104,6 +106,22 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: false
 		}
+		integer_overflow: {
+			common:   false
+			required: false
+			description: """
+				How integer arithmetic (`+`, `-`, `*` and `%`) handles results that don't fit in
+				a signed 64-bit integer. With `error`, these operations are fallible and the
+				program must handle their errors, for example with `!` or `??`.
+				"""
+			type: string: {
+				default: "wrap"
+				enum: {
+					wrap:  "The result wraps around, so `9223372036854775807 + 1` is `-9223372036854775808`."
+					error: "The operation returns an error."
+				}
+			}
+		}
 	}
 
 	input: {
@@ -145,6 +163,17 @@ components: transforms: "remap": {
 				[Vector Remap Language reference](\#(urls.vrl_reference)).
 				"""#
 		}
+		shared_programs: {
+			title: "Shared programs"
+			body:  """
+				`remap` transforms with identical programs share a single compiled copy of the
+				program, including its static data such as regular expressions and grok
+				patterns. Running many `remap` transforms with the same program, such as one
+				per tenant, therefore only compiles the program once. Programs are not shared
+				while enrichment tables are configured, as each transform registers the indexes
+				it searches the tables with.
+				"""
+		}
 		event_data_model: {
 			title: "Event Data Model"
 			body:  """
@@ -169,6 +198,15 @@ components: transforms: "remap": {
 				It's important to note that if you try to perform a disallowed action, such as deleting the `type`
 				field using `del(.type)`, Vector doesn't abort the VRL program or throw an error. Instead, it ignores
 				the disallowed action.
+
+				Spans from the `internal_traces`, `zipkin` and `jaeger` sources are log events with one span each,
+				so the same access applies to them. Their IDs are in `.trace_id`, `.span_id` and `.parent_span_id`,
+				and their attributes in `.tags`, which can be scrubbed or enriched like any other field:
+
+				```coffee
+				del(.tags."http.url")
+				.tags.environment = "production"
+				```
 				"""
 		}
 		lazy_event_mutation: {
@@ -0,0 +1,131 @@
+package metadata
+
+components: transforms: sessionize: {
+	title: "Sessionize"
+
+	description: """
+		Assigns a session ID to each event, shared by the events with the same `group_by` values until they go
+		quiet for `inactivity_timeout_ms`. Unlike `reduce`, every event is passed on, and a summary of each session
+		can be emitted once it closes.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		emit_summaries: {
+			common:      true
+			description: "Emit a summary event for each session once it closes, alongside the events themselves."
+			required:    false
+			type: bool: default: false
+		}
+		flush_period_ms: {
+			common:      false
+			description: "How often to check for sessions that have closed. Must be greater than `0`."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "milliseconds"
+			}
+		}
+		group_by: {
+			description: "The fields that identify whose session an event belongs to. Events with the same values for all of them share sessions."
+			required:    true
+			type: array: items: type: string: examples: ["user_id", "ip"]
+		}
+		inactivity_timeout_ms: {
+			common:      true
+			description: "How long a session may go without events before it closes. The next event with its key starts a new session."
+			required:    false
+			type: uint: {
+				default: 1800000
+				unit:    "milliseconds"
+			}
+		}
+		max_duration_ms: {
+			common:      false
+			description: "How long a session may last, however active it is. Must be at least `inactivity_timeout_ms`."
+			required:    false
+			type: uint: {
+				default: null
+				unit:    "milliseconds"
+			}
+		}
+		session_id_field: {
+			common:      false
+			description: "The field to store the session ID in."
+			required:    false
+			type: string: default: "session_id"
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	output: logs: summary: {
+		description: "The summary of a session, emitted when it closes if `emit_summaries` is set. It also has the `group_by` fields of the session, and its ID in `session_id_field`."
+		fields: {
+			close_reason: {
+				description: "Why the session closed."
+				required:    true
+				type: string: {
+					enum: {
+						inactivity:   "No events arrived for `inactivity_timeout_ms`."
+						max_duration: "The session lasted for `max_duration_ms`."
+						shutdown:     "Vector stopped, or the transform was reloaded."
+					}
+				}
+			}
+			duration_ms: {
+				description: "The time between the first and last events of the session, by event timestamp."
+				required:    true
+				type: uint: unit: "milliseconds"
+			}
+			event_count: {
+				description: "The number of events in the session."
+				required:    true
+				type: uint: unit: null
+			}
+			session_end: {
+				description: "The timestamp of the last event of the session."
+				required:    true
+				type: timestamp: {}
+			}
+			session_start: {
+				description: "The timestamp of the first event of the session."
+				required:    true
+				type: timestamp: {}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		sessions: {
+			title: "Sessions"
+			body: """
+				Sessions are tracked in memory. An event joins the open session of its key, or starts a new one
+				with a new ID if there isn't one. A session closes once it has gone `inactivity_timeout_ms`
+				without events, or has been open for `max_duration_ms`, as measured by when events arrive rather
+				than their timestamps. Summaries go to the same output as the events, so they can be told apart by
+				the `close_reason` field, which events don't have.
+				"""
+		}
+	}
+}
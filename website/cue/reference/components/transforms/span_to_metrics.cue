@@ -0,0 +1,155 @@
+package metadata
+
+components: transforms: span_to_metrics: {
+	title: "Span to Metrics"
+
+	description: """
+		Derives request, error and duration (RED) metrics from spans, such as the ones output by the
+		`zipkin`, `jaeger` and `internal_traces` sources, tagged with the service, operation and status
+		of each span.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		duration: {
+			common:      true
+			description: "How span durations are recorded."
+			required:    false
+			type: object: {
+				examples: [{"type": "histogram", "buckets": [0.1, 0.5, 1.0]}, {"type": "sketch"}]
+				options: {
+					buckets: {
+						common:        false
+						description:   "The upper bounds of the histogram buckets, in increasing order."
+						relevant_when: "type = \"histogram\""
+						required:      false
+						type: array: {
+							default: [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+							items: type: float: unit: "seconds"
+						}
+					}
+					type: {
+						common:      true
+						description: "The type of metric to record durations as."
+						required:    false
+						type: string: {
+							default: "histogram"
+							enum: {
+								histogram: "An aggregated histogram with fixed `buckets`, for sinks like `prometheus_exporter`."
+								sketch:    "A sketch, for sinks like `datadog_metrics` that keep the full distribution."
+							}
+						}
+					}
+				}
+			}
+		}
+		duration_field: {
+			common:      false
+			description: "The field holding the duration of each span, in nanoseconds. Spans without it are counted but not timed."
+			required:    false
+			type: string: default: "duration_ns"
+		}
+		error_field: {
+			common:      true
+			description: "The field marking failed spans. A span has failed if this field is set to anything other than `false`, an empty string, `ok` or `unset`, so Jaeger's `error` tag, Zipkin's `error` tag and OpenTelemetry's `otel.status_code` tag can all be used."
+			required:    false
+			type: string: {
+				default: "tags.error"
+				examples: ["tags.\"otel.status_code\""]
+			}
+		}
+		namespace: {
+			common:      true
+			description: "The namespace of the metrics."
+			required:    false
+			type: string: default: "span"
+		}
+		operation_field: {
+			common:      false
+			description: "The field holding the operation name of each span."
+			required:    false
+			type: string: default: "message"
+		}
+		service_field: {
+			common:      false
+			description: "The field holding the service name of each span."
+			required:    false
+			type: string: default: "service"
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	output: metrics: {
+		_span_tags: {
+			operation: {
+				description: "The operation name of the span."
+				required:    false
+				examples: ["get /api"]
+			}
+			service: {
+				description: "The service name of the span."
+				required:    false
+				examples: ["backend"]
+			}
+			status: {
+				description: "Whether the span failed, as `ok` or `error`."
+				required:    true
+				examples: ["ok", "error"]
+			}
+		}
+
+		requests_total: {
+			description:       "The number of spans."
+			type:              "counter"
+			default_namespace: "span"
+			tags:              _span_tags
+		}
+		errors_total: {
+			description:       "The number of failed spans."
+			type:              "counter"
+			default_namespace: "span"
+			tags:              _span_tags
+		}
+		duration_seconds: {
+			description:       "The duration of spans, as a histogram or a sketch depending on `duration.type`."
+			type:              "histogram"
+			default_namespace: "span"
+			tags:              _span_tags
+		}
+	}
+
+	how_it_works: {
+		incremental_metrics: {
+			title: "Incremental metrics"
+			body: """
+				Each span is turned into incremental metrics as it arrives, so the transform keeps no state.
+				Sinks such as `prometheus_exporter` aggregate them, and an `aggregate` transform can be added
+				after this one to reduce the number of metrics sent to sinks that don't.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		processing_errors_total: components.sources.internal_metrics.output.metrics.processing_errors_total
+	}
+}
@@ -0,0 +1,134 @@
+package metadata
+
+components: transforms: tail_sampling: {
+	title: "Tail Sampling"
+
+	description: """
+		Samples whole traces rather than single spans. The spans of each trace are held for
+		`decision_wait_ms` after the first one arrives, then the trace is kept if any of the `policies`
+		match it, such as having a failed or slow span, and dropped otherwise.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		decision_wait_ms: {
+			common:      true
+			description: "How long to wait for the spans of a trace after its first one arrives, before deciding whether to keep it."
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    "milliseconds"
+			}
+		}
+		flush_period_ms: {
+			common:      false
+			description: "How often to check for traces that are ready to be decided. Must be greater than `0`."
+			required:    false
+			type: uint: {
+				default: 1000
+				unit:    "milliseconds"
+			}
+		}
+		max_traces: {
+			common:      false
+			description: "The most traces to hold at once. When a new trace would go over this, the oldest one is decided early."
+			required:    false
+			type: uint: {
+				default: 50000
+				unit:    null
+			}
+		}
+		policies: {
+			description: "The policies to sample traces by. A trace is kept if any of them match it."
+			required:    true
+			type: array: items: type: object: {
+				examples: [{"type": "error"}, {"type": "latency", "threshold_ms": 500}, {"type": "probabilistic", "rate": 0.1}]
+				options: {
+					duration_field: {
+						common:        false
+						description:   "The field holding the duration of each span, in nanoseconds."
+						relevant_when: #"type = "latency""#
+						required:      false
+						type: string: default: "duration_ns"
+					}
+					field: {
+						common:        false
+						description:   "The field marking failed spans. A span has failed if this field is set to anything other than `false`, an empty string, `ok` or `unset`."
+						relevant_when: #"type = "error""#
+						required:      false
+						type: string: default: "tags.error"
+					}
+					rate: {
+						description:   "The share of traces to keep, between 0 and 1."
+						relevant_when: #"type = "probabilistic""#
+						required:      true
+						type: float: examples: [0.1]
+					}
+					threshold_ms: {
+						description:   "How long a span must take for its trace to be kept."
+						relevant_when: #"type = "latency""#
+						required:      true
+						type: uint: {
+							examples: [500]
+							unit: "milliseconds"
+						}
+					}
+					type: {
+						description: "The type of policy."
+						required:    true
+						type: string: enum: {
+							error:         "Keeps traces with a failed span."
+							latency:       "Keeps traces with a span that took at least `threshold_ms`."
+							probabilistic: "Keeps a `rate` share of traces, picked by hashing their IDs."
+						}
+					}
+				}
+			}
+		}
+		trace_id_field: {
+			common:      false
+			description: "The field holding the ID of the trace a span belongs to. Events without it are passed on straight away."
+			required:    false
+			type: string: default: "trace_id"
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+		traces_evicted_total:   components.sources.internal_metrics.output.metrics.traces_evicted_total
+	}
+
+	how_it_works: {
+		decisions: {
+			title: "Decisions"
+			body: """
+				Traces are held in memory until they are decided, so a trace is either passed on or dropped as
+				a whole. Spans that arrive after their trace has been decided follow the same decision for
+				another `decision_wait_ms`, after which they start a new trace. Probabilistic sampling hashes
+				the trace ID, so every Vector instance keeps the same traces, and it can be combined with the
+				other policies to keep a baseline of healthy traces alongside the interesting ones.
+				"""
+		}
+	}
+}
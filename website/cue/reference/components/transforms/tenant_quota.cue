@@ -0,0 +1,170 @@
+package metadata
+
+components: transforms: tenant_quota: {
+	title: "Tenant Quota"
+
+	description: """
+		Enforces per-tenant quotas on the number of events, bytes and metric tag values passing through a topology,
+		and reports each tenant's usage.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		limits: {
+			common:      true
+			description: "The quotas of every tenant that doesn't have quotas of its own in `tenants`. Quotas that are left unset aren't enforced."
+			required:    false
+			type: object: options: {
+				bytes_per_window: {
+					common:      false
+					description: "The most bytes a tenant may send in each window. Bytes are estimated in the same way as for buffering."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [10485760]
+						unit: "bytes"
+					}
+				}
+				events_per_window: {
+					common:      true
+					description: "The most events a tenant may send in each window."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [1000]
+						unit: "events"
+					}
+				}
+				tag_key_limit: {
+					common:      false
+					description: "The most distinct metric tag keys a tenant may use, when `tag_value_limit` is set. Tags with keys beyond it are handled as `tag_limit_exceeded_action` says."
+					required:    false
+					type: uint: {
+						default: 100
+						unit:    null
+					}
+				}
+				tag_value_limit: {
+					common:      false
+					description: "The most distinct values each metric tag may have for a tenant. Unlike the other quotas, this isn't reset between windows."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [500]
+						unit: null
+					}
+				}
+			}
+		}
+		max_tenants: {
+			common:      false
+			description: "The most tenants whose usage is tracked at once. When a new tenant arrives with no room left, the tenant seen least recently is forgotten, along with its counts and tag values."
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    null
+			}
+		}
+		tag_limit_exceeded_action: {
+			common:      false
+			description: "What to do with a metric that has a tag value beyond its tenant's `tag_value_limit`, or a tag key beyond its `tag_key_limit`."
+			required:    false
+			type: string: {
+				default: "drop_tag"
+				enum: {
+					drop_tag:   "Remove the tag from the metric and keep the metric."
+					drop_event: "Discard the metric."
+				}
+			}
+		}
+		tenant_key: {
+			description: "The tenant an event belongs to. This is usually taken from the event's source metadata, so that it doesn't need to be added to the event."
+			required:    true
+			type: string: {
+				examples: ["{{ %source.tenant }}", "{{ downcase(.team) ?? \"shared\" }}"]
+				syntax: "template"
+			}
+		}
+		tenants: {
+			common:      false
+			description: "Quotas for specific tenants, keyed by tenant. These take the same options as `limits`, and any quota left unset falls back to the one in `limits`."
+			required:    false
+			type: object: {
+				examples: [{acme: {events_per_window: 10000}}]
+				options: {}
+			}
+		}
+		window_secs: {
+			common:      true
+			description: "The length of the window that `events_per_window` and `bytes_per_window` apply to. Must be a finite number greater than `0`."
+			required:    false
+			type: float: {
+				default: 1.0
+				examples: [1.0, 60.0]
+			}
+		}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total:         components.sources.internal_metrics.output.metrics.events_discarded_total
+		tag_value_limit_exceeded_total: components.sources.internal_metrics.output.metrics.tag_value_limit_exceeded_total
+		tenant_bytes_total:             components.sources.internal_metrics.output.metrics.tenant_bytes_total
+		tenant_events_total:            components.sources.internal_metrics.output.metrics.tenant_events_total
+	}
+
+	how_it_works: {
+		windows: {
+			title: "Windows"
+			body: """
+				Each tenant's event and byte counts start when its first event arrives and reset once `window_secs`
+				has passed, so a tenant can send up to its quota at any point in a window. Events beyond a quota are
+				discarded and counted by the `events_discarded_total` metric, tagged with the `tenant` and the
+				`quota` that was exceeded. Events that were discarded don't count towards the tenant's usage.
+				"""
+		}
+		usage: {
+			title: "Usage"
+			body: """
+				Every event the transform accepts is counted by the `tenant_events_total` and `tenant_bytes_total`
+				metrics, tagged with its tenant, so usage can be reported per tenant whether or not it has quotas.
+				Events whose `tenant_key` can't be rendered belong to the `none` tenant.
+				"""
+		}
+		memory: {
+			title: "Memory use"
+			body: """
+				Usage is tracked for at most `max_tenants` tenants. Tenants without tag values are forgotten once
+				they've sent nothing for a whole window, and the others when room is needed for a new tenant, so a
+				forgotten tenant starts again with fresh quotas and no remembered tag values.
+				"""
+		}
+	}
+}
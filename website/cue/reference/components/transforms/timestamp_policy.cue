@@ -0,0 +1,112 @@
+package metadata
+
+components: transforms: timestamp_policy: {
+	title: "Timestamp Policy"
+
+	description: """
+		Enforces bounds on how far in the past or the future the timestamps of events may be, and repairs,
+		annotates, or drops the events whose timestamps are out of bounds. This protects time-partitioned
+		sinks, such as object stores and time-series databases, from data with skewed clocks.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		action: {
+			common:      true
+			description: "What to do with events whose timestamp is out of bounds."
+			required:    false
+			type: string: {
+				default: "replace"
+				enum: {
+					annotate: "Keep the timestamp, and only annotate the event. Requires `annotation_field`."
+					clamp:    "Replace the timestamp with the bound it crossed."
+					drop:     "Drop the event."
+					replace:  "Replace the timestamp with the current time."
+				}
+			}
+		}
+		annotation_field: {
+			common:      false
+			description: "The field to record why the timestamp of an event was out of bounds, and its original value, in. Events aren't annotated by default."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["timestamp_policy"]
+			}
+		}
+		field: {
+			common:      false
+			description: "The field holding the timestamp to check. Defaults to the `timestamp_key` of the global [log schema](\(urls.vector_log_schema))."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["timestamp", "received_at"]
+			}
+		}
+		max_future_secs: {
+			common:      true
+			description: "How far in the future a timestamp may be. At least one of `max_past_secs` and `max_future_secs` must be set."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [3600]
+				unit: "seconds"
+			}
+		}
+		max_past_secs: {
+			common:      true
+			description: "How far in the past a timestamp may be. At least one of `max_past_secs` and `max_future_secs` must be set."
+			required:    false
+			type: uint: {
+				default: null
+				examples: [604800]
+				unit: "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		bounds: {
+			title: "Bounds"
+			body: """
+				The bounds are relative to the time the event goes through the transform. Only timestamp values
+				are checked: events without the field, or whose field holds another type of value, such as a
+				string that hasn't been parsed yet, are passed on untouched.
+				"""
+		}
+		annotations: {
+			title: "Annotations"
+			body: """
+				If `annotation_field` is set, events whose timestamp was out of bounds get an object in that
+				field, with the `reason` the timestamp was out of bounds, `too_old` or `too_new`, and the
+				`original` timestamp.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total:         components.sources.internal_metrics.output.metrics.events_discarded_total
+		timestamps_out_of_bounds_total: components.sources.internal_metrics.output.metrics.timestamps_out_of_bounds_total
+	}
+}
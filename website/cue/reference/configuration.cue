@@ -50,6 +50,11 @@ configuration: {
 				drops back to a sequential scan of the data. A sequential scan shouldn't impact performance
 				significantly provided that there are only a few possible rows returned by the exact matches in the
 				condition. We don't recommend using a condition that uses only date range searches.
+
+				The file can be loaded from a local `path`, a `url`, or an object in `s3`. Files loaded from a URL or
+				S3 are fetched again every `refresh_interval_secs`, using the `ETag` and `Last-Modified` headers of the
+				last response so that data that hasn't changed isn't downloaded again. If a refresh fails, the table
+				keeps using the data it last loaded.
 				"""
 			required:    false
 			type: object: options: {
@@ -60,13 +65,15 @@ configuration: {
 						path: {
 							description: """
 								The path of the enrichment table file. Currently, only [CSV](\(urls.csv)) files are
-								supported.
+								supported. One of `path`, `url`, or `s3` must be set.
 								"""
 							warnings: [
 								"In order to be used by Vector, you need to assign read access to the enrichment table file.",
 							]
-							required: true
+							required: false
+							common:   true
 							type: string: {
+								default: null
 								examples: [
 									"/data/info.csv",
 									"./info.csv",
@@ -74,6 +81,68 @@ configuration: {
 							}
 						}
 
+						url: {
+							description: "The HTTP or HTTPS URL to fetch the enrichment table file from."
+							required:    false
+							common:      false
+							type: string: {
+								default: null
+								examples: ["https://example.com/info.csv"]
+							}
+						}
+
+						s3: {
+							description: "The S3 object to fetch the enrichment table file from."
+							required:    false
+							common:      false
+							type: object: options: {
+								bucket: {
+									description: "The name of the bucket."
+									required:    true
+									type: string: examples: ["my-bucket"]
+								}
+								key: {
+									description: "The key of the object."
+									required:    true
+									type: string: examples: ["tables/info.csv"]
+								}
+								region: {
+									description: "The [AWS region](\(urls.aws_regions)) of the bucket."
+									required:    false
+									common:      true
+									type: string: {
+										default: null
+										examples: ["us-east-1"]
+									}
+								}
+								endpoint: {
+									description: "Custom endpoint for use with AWS-compatible services."
+									required:    false
+									common:      false
+									type: string: {
+										default: null
+										examples: ["http://127.0.0.0:5000/path/to/service"]
+									}
+								}
+								auth: {
+									description: "Options for the authentication strategy, as for the AWS components."
+									required:    false
+									common:      false
+									type: object: options: {}
+								}
+							}
+						}
+
+						refresh_interval_secs: {
+							description: "How often a file loaded from a `url` or `s3` is fetched again. Must be greater than `0`."
+							required:    false
+							common:      false
+							type: uint: {
+								default: 300
+								unit:    "seconds"
+							}
+						}
+
 						encoding: {
 							description: "Configuration options for the encoding of the enrichment table's file."
 							required:    true
@@ -245,6 +314,18 @@ configuration: {
 							default: false
 						}
 					}
+
+					require_healthy_on_reload: {
+						common: false
+						description: """
+							Roll back a reload if the health check of any new or changed sink fails,
+							without failing startup as `require_healthy` does.
+							"""
+						required: false
+						type: bool: {
+							default: false
+						}
+					}
 				}
 			}
 		}
@@ -263,6 +344,53 @@ configuration: {
 			}
 		}
 
+		coalesce_latency_ms: {
+			common:      false
+			description: """
+				The longest time transforms wait for more events to fill a batch with, once they have
+				received the first event of the batch. Bursty inputs otherwise make transforms process,
+				and send on, many small batches of events, which adds overhead to each hop of deep
+				topologies. Raising it trades latency for throughput.
+				"""
+			required:    false
+			type: uint: {
+				default: null
+				examples: [5, 50]
+				unit: "milliseconds"
+			}
+		}
+
+		expire_metrics_secs: {
+			common:      false
+			description: """
+				How long sinks keep the state of metric series that aren't updated anymore, such as
+				the last value used to convert absolute metrics to incremental ones. Without it, the
+				state is kept until a metric marked with `series_ended` ends the series, so it keeps
+				growing with short-lived series. Sinks can override it with their own
+				`expire_metrics_secs` option.
+				"""
+			required:    false
+			type: uint: {
+				default: null
+				examples: [3600]
+				unit: "seconds"
+			}
+		}
+
+		max_connections: {
+			common:      false
+			description: """
+				The max number of TCP connections that will be processed across all TCP-based sources
+				together. Once it's reached, sources stop accepting new connections until others close,
+				in addition to any `connection_limit` they have of their own.
+				"""
+			required:    false
+			type: uint: {
+				default: null
+				unit:    "concurrency"
+			}
+		}
+
 		proxy: {
 			common:      false
 			description: "Configures an HTTP(S) proxy for Vector to use."
@@ -316,6 +444,44 @@ configuration: {
 				}
 			}
 		}
+
+		source_heartbeat: {
+			common:      false
+			description: """
+				Sends a heartbeat event through the default output of every source at a regular interval, for as
+				long as the source runs, so that alerts downstream can tell a silent source from a stopped one.
+				Sources of logs send log events with the `message`, a `source_id` field with the ID of the source,
+				the source type, and the `fields`. Sources of metrics send a gauge named after the `message`, with
+				the value 1, tagged with the `source_id`, the `source_type` and the `fields`.
+				"""
+			required:    false
+			type: object: options: {
+				interval_secs: {
+					common:      true
+					description: "The interval between heartbeats."
+					required:    false
+					type: uint: {
+						default: 60
+						unit:    "seconds"
+					}
+				}
+				message: {
+					common:      true
+					description: "The message of heartbeat log events, and the name of heartbeat metrics."
+					required:    false
+					type: string: default: "heartbeat"
+				}
+				fields: {
+					common:      false
+					description: "Fields added to heartbeat log events, and tags added to heartbeat metrics."
+					required:    false
+					type: object: {
+						examples: [{environment: "production"}]
+						options: {}
+					}
+				}
+			}
+		}
 	}
 
 	how_it_works: {
@@ -237,6 +237,19 @@ data_model: schema: {
 					}
 				}
 
+				series_ended: {
+					common: false
+					description: """
+						Marks the last data point of the series. Sinks that keep state
+						per series, such as the last value used to convert absolute
+						metrics to incremental ones, forget the series when they receive
+						it rather than waiting for it to expire. Sinks that don't keep
+						such state handle the metric like any other.
+						"""
+					required: false
+					type: bool: default: false
+				}
+
 				set: {
 					common: true
 					description: """
@@ -63,7 +63,7 @@ remap: {
 		the provided condition(s). _All_ fields need to match for rows to be returned; if any fields
 		don't match, no rows are returned.
 
-		There are currently two forms of search criteria:
+		There are currently four forms of search criteria:
 
 		1. **Exact match search**. The given field must match the value exactly. Case sensitivity
 		   can be specified using the `case_sensitive` argument. An exact match search can use an
@@ -71,11 +71,18 @@ remap: {
 		   performance perspective.
 
 		2. **Date range search**. The given field must be greater than or equal to the `from` date
-		   and less than or equal to the `to` date. Note that a date range search involves
-		   sequentially scanning through the rows that have been located via any exact match
-		   criteria. This can be an expensive operation if there are many rows returned by any exact
-		   match criteria. We recommend using date ranges as the _only_ criteria when the enrichment
-		   data set is very small.
+		   and less than or equal to the `to` date.
+
+		3. **Number range search**. The given field must be greater than or equal to the `from`
+		   number and less than or equal to the `to` number.
+
+		4. **CIDR search**. The given field must be a CIDR range, such as `10.0.0.0/8`, that contains
+		   the address given as `contains_ip`.
+
+		Range searches are indexed separately from exact match searches. The rows matching the
+		criteria that narrows the search down the most, whether an exact match or a range, are
+		then scanned sequentially to check the remaining criteria, so searches with a broad range
+		as their only criteria can still be expensive on large datasets.
 
 		To use this function, you need to update your Vector configuration to
 		include an
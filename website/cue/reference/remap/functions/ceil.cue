@@ -11,7 +11,7 @@ remap: functions: ceil: {
 			name:        "value"
 			description: "The number to round up."
 			required:    true
-			type: ["integer", "float"]
+			type: ["integer", "float", "decimal"]
 		},
 		{
 			name:        "precision"
@@ -23,7 +23,7 @@ remap: functions: ceil: {
 	]
 	internal_failure_reasons: []
 	return: {
-		types: ["integer", "float"]
+		types: ["integer", "float", "decimal"]
 		rules: [
 			"Returns an integer if `precision` is `0` (this is the default). Returns a float otherwise.",
 		]
@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: checked_add: {
+	category: "Number"
+	description: """
+		Adds `other` to `value`, returning an error if the result doesn't fit in a signed 64-bit integer.
+
+		Use this function instead of the `+` operator where a silently wrapped result
+		would corrupt data, such as when summing billing counters.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The first integer."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "other"
+			description: "The second integer."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"The result overflows a signed 64-bit integer.",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Add integers"
+			source: #"""
+				checked_add!(1, 2)
+				"""#
+			return: 3
+		},
+		{
+			title: "Handle overflow"
+			source: #"""
+				checked_add(9223372036854775807, 1) ?? 0
+				"""#
+			return: 0
+		},
+	]
+}
@@ -0,0 +1,56 @@
+package metadata
+
+remap: functions: clamp: {
+	category: "Number"
+	description: """
+		Restricts the `value` to the range from `min` to `max`, returning `min` if the `value` is below
+		it, `max` if the `value` is above it, and the `value` otherwise.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The number to clamp."
+			required:    true
+			type: ["integer", "float", "decimal"]
+		},
+		{
+			name:        "min"
+			description: "The lower bound."
+			required:    true
+			type: ["integer", "float", "decimal"]
+		},
+		{
+			name:        "max"
+			description: "The upper bound."
+			required:    true
+			type: ["integer", "float", "decimal"]
+		},
+	]
+	internal_failure_reasons: [
+		"`min` is greater than `max`.",
+	]
+	return: {
+		types: ["integer", "float", "decimal"]
+		rules: [
+			"Returns the bound in place of the `value` when the `value` is outside of the range, so the result has the type of whichever one is returned.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Clamp a number within bounds"
+			source: #"""
+				clamp!(42, 0, 100)
+				"""#
+			return: 42
+		},
+		{
+			title: "Clamp a number above the maximum"
+			source: #"""
+				clamp!(1.5, 0.0, 1.0)
+				"""#
+			return: 1.0
+		},
+	]
+}
@@ -0,0 +1,44 @@
+package metadata
+
+remap: functions: compress: {
+	category:    "Codec"
+	description: """
+		Compresses the `value` with the given `algorithm`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The data to compress."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The compression algorithm to use."
+			required:    true
+			type: ["string"]
+			enum: {
+				gzip:   "[Gzip](\(urls.gzip)) compression."
+				zlib:   "[Zlib](\(urls.zlib)) compression."
+				zstd:   "[Zstandard](\(urls.zstd)) compression."
+				snappy: "[Snappy](\(urls.snappy)) compression, in the raw block format."
+				lz4:    "[LZ4](\(urls.lz4)) compression, in the frame format."
+			}
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is too large to be compressed with `algorithm`.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Compress data with Snappy"
+			source: """
+				encode_base64(compress!("hello world", "snappy"))
+				"""
+			return: "CyhoZWxsbyB3b3JsZA=="
+		},
+	]
+}
@@ -0,0 +1,56 @@
+package metadata
+
+remap: functions: decompress: {
+	category:    "Codec"
+	description: """
+		Decompresses the `value`, compressed with the given `algorithm`, into its original data.
+
+		This is useful for payloads that arrive compressed and Base64 encoded inside of another
+		encoding, such as CloudWatch Logs subscriptions delivered through Kinesis Firehose.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The compressed data to decompress."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The compression algorithm the data was compressed with."
+			required:    true
+			type: ["string"]
+			enum: {
+				gzip:   "[Gzip](\(urls.gzip)) compression. Concatenated gzip members are all decompressed."
+				zlib:   "[Zlib](\(urls.zlib)) compression."
+				zstd:   "[Zstandard](\(urls.zstd)) compression."
+				snappy: "[Snappy](\(urls.snappy)) compression, in the raw block format."
+				lz4:    "[LZ4](\(urls.lz4)) compression, in the frame format."
+			}
+		},
+		{
+			name:        "max_bytes"
+			description: "The most bytes `value` may decompress to. A few bytes can decompress to gigabytes, so this is bounded by default."
+			required:    false
+			default:     10485760
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't validly compressed with `algorithm`.",
+		"`value` decompresses to more than `max_bytes` bytes.",
+		"`max_bytes` is negative.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decompress Base64 encoded gzip data"
+			source: """
+				decompress!(decode_base64!("H4sIAAAAAAACA8tIzcnJVyjPL8pJAQCFEUoNCwAAAA=="), "gzip")
+				"""
+			return: "hello world"
+		},
+	]
+}
@@ -0,0 +1,54 @@
+package metadata
+
+remap: functions: encode_prometheus_text: {
+	category:    "Codec"
+	description: """
+		Encodes the `value`, an array of metric objects, in the [Prometheus text exposition
+		format](\(urls.prometheus_text_based_exposition_format)).
+
+		Each metric object has a `name`, a `type` of `counter`, `gauge`, `histogram`, `summary`, or `untyped`
+		(the default), and optionally `labels`, `help`, and a `timestamp`, either a timestamp or an integer
+		number of milliseconds. Counters, gauges, and untyped metrics have a `value`. Histograms have
+		`buckets`, objects with an `upper_limit` and a cumulative `count`, and summaries have `quantiles`,
+		objects with a `quantile` and a `value`; both also have a `sum` and a `count`.
+		"""
+	notices: [
+		"""
+			Metrics with the same name are written together under a single `TYPE` line, in the order their
+			names first appear in `value`. Histograms without a `+Inf` bucket get one holding their `count`.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The metrics to encode."
+			required:    true
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` contains an element that isn't a metric object",
+		"a metric is missing a field its type requires, or a field has the wrong type",
+		"a metric or label name isn't a valid Prometheus name",
+		"metrics with the same name have different types",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode a counter"
+			source: """
+				encode_prometheus_text!([{"name": "http_requests_total", "type": "counter", "labels": {"code": "200"}, "value": 1027}])
+				"""
+			return: #"# TYPE http_requests_total counter\#nhttp_requests_total{code="200"} 1027\#n"#
+		},
+		{
+			title: "Encode a histogram"
+			source: """
+				encode_prometheus_text!([{"name": "request_duration_seconds", "type": "histogram", "buckets": [{"upper_limit": 0.5, "count": 3}, {"upper_limit": 1, "count": 5}], "sum": 2.5, "count": 6}])
+				"""
+			return: #"# TYPE request_duration_seconds histogram\#nrequest_duration_seconds_bucket{le="0.5"} 3\#nrequest_duration_seconds_bucket{le="1"} 5\#nrequest_duration_seconds_bucket{le="+Inf"} 6\#nrequest_duration_seconds_sum 2.5\#nrequest_duration_seconds_count 6\#n"#
+		},
+	]
+}
@@ -0,0 +1,53 @@
+package metadata
+
+remap: functions: encode_url: {
+	category:    "Codec"
+	description: """
+		Builds a [URL](\(urls.url)) from the `value`, an object with the fields returned by `parse_url`:
+		`scheme`, `username`, `password`, `host`, `port`, `path`, `path_segments`, `query`, and
+		`fragment`.
+		"""
+	notices: [
+		"""
+			Only `scheme` is required. `path` takes precedence over `path_segments`, whose segments are
+			percent-encoded. Query parameters with an array value are repeated for each element.
+			Unicode host names are encoded to their ASCII form.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The URL object to encode."
+			required:    true
+			type: ["object"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` has no `scheme`",
+		"a field of `value` has the wrong type",
+		"`port` isn't a valid port number",
+		"the fields don't make up a valid URL, such as a `port` without a `host`",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode a URL"
+			source: #"""
+				encode_url!({"scheme": "https", "host": "vector.dev", "path": "/docs/", "query": {"q": "remap"}})
+				"""#
+			return: "https://vector.dev/docs/?q=remap"
+		},
+		{
+			title: "Rebuild a parsed URL"
+			source: #"""
+				url = parse_url!("https://vector.dev:8080/docs?q=remap")
+				url.port = null
+				url.query.page = 2
+				encode_url!(url)
+				"""#
+			return: "https://vector.dev/docs?page=2&q=remap"
+		},
+	]
+}
@@ -0,0 +1,74 @@
+package metadata
+
+remap: functions: encode_xml: {
+	category:    "Codec"
+	description: """
+		Encodes the `value` as an XML document, using the same conventions as `parse_xml`: keys starting with
+		`attr_prefix` become attributes, the `text_key` key becomes the element's text, and arrays become repeated
+		elements.
+		"""
+	notices: [
+		"""
+			Unless `root` is set, `value` must have exactly one key, which is used as the root element.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object to encode."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "root"
+			description: "The name of the root element to wrap `value` in."
+			required:    false
+			type: ["string"]
+		},
+		{
+			name:        "attr_prefix"
+			description: "The key prefix that marks a value as an XML attribute. Set to an empty string to encode all keys as elements."
+			required:    false
+			default:     "@"
+			type: ["string"]
+		},
+		{
+			name:        "text_key"
+			description: "The key holding the text content of an element that also has attributes or children."
+			required:    false
+			default:     "text"
+			type: ["string"]
+		},
+		{
+			name:        "include_declaration"
+			description: "Whether to start the document with an `<?xml version=\"1.0\" encoding=\"UTF-8\"?>` declaration."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` has more than one key and `root` isn't set",
+		"`value` contains a key that isn't a valid XML element or attribute name",
+		"`value` contains nested arrays",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode an object"
+			source: """
+				encode_xml!({"book": {"@category": "CHILDREN", "title": {"@lang": "en", "text": "Harry Potter"}, "year": 2005}}, include_declaration: false)
+				"""
+			return: #"<book category="CHILDREN"><title lang="en">Harry Potter</title><year>2005</year></book>"#
+		},
+		{
+			title: "Encode with a custom root element"
+			source: """
+				encode_xml!({"item": ["apple", "pear"]}, root: "order")
+				"""
+			return: #"<?xml version="1.0" encoding="UTF-8"?><order><item>apple</item><item>pear</item></order>"#
+		},
+	]
+}
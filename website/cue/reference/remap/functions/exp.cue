@@ -0,0 +1,31 @@
+package metadata
+
+remap: functions: exp: {
+	category: "Number"
+	description: """
+		Returns _e_ raised to the power of the `value`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The exponent."
+			required:    true
+			type: ["integer", "float", "decimal"]
+		},
+	]
+	internal_failure_reasons: [
+		"The result is too large to be a float.",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Exponential"
+			source: #"""
+				exp!(0)
+				"""#
+			return: 1.0
+		},
+	]
+}
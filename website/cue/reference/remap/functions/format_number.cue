@@ -11,7 +11,7 @@ remap: functions: format_number: {
 			name:        "value"
 			description: "The number to format as a string."
 			required:    true
-			type: ["integer", "float"]
+			type: ["integer", "float", "decimal"]
 		},
 		{
 			name:        "scale"
@@ -0,0 +1,33 @@
+package metadata
+
+remap: functions: from_syslog_facility: {
+	category:    "Convert"
+	description: """
+		Converts the `value`, a Syslog [facility keyword](\(urls.syslog_facility)), into its corresponding
+		Syslog facility code. i.e. `"kern"` into 0, `"user"` into 1, etc. This is the inverse of
+		`to_syslog_facility`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The facility keyword."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid Syslog [facility keyword](\(urls.syslog_facility)).",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Coerce to a Syslog facility code"
+			source: """
+				from_syslog_facility!("auth")
+				"""
+			return: 4
+		},
+	]
+}
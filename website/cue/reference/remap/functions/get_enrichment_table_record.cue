@@ -76,5 +76,16 @@ remap: functions: get_enrichment_table_record: {
 				"""#
 			return: {"id": 1, "firstname": "Bob", "surname": "Smith"}
 		},
+		{
+			title: "Number range and CIDR search"
+			source: #"""
+				get_enrichment_table_record!("allocations",
+				  {
+				    "network": {"contains_ip": "10.1.2.3"},
+				    "tier": {"from": 2, "to": 3}
+				  })
+				"""#
+			return: {"network": "10.1.0.0/16", "tier": 2, "owner": "payments"}
+		},
 	]
 }
@@ -11,6 +11,12 @@ remap: functions: get_metadata_field: {
 			name: "key"
 			description: """
 				The name of the field to look up in the metadata.
+
+				Besides these, any key starting with `source.` refers to the details sources attach about
+				where an event came from, such as `source.peer_ip` from the socket based sources,
+				`source.tls_subject` when a client certificate was presented, `source.topic`,
+				`source.partition` and `source.offset` from the `kafka` source, and `source.path` from the
+				`file` source. These can be set to any value.
 				"""
 			required: true
 			enum: {
@@ -30,7 +36,7 @@ remap: functions: get_metadata_field: {
 	]
 	internal_failure_reasons: [
 	]
-	return: types: ["string"]
+	return: types: ["string", "null", "any"]
 
 	examples: [
 		{
@@ -40,5 +46,12 @@ remap: functions: get_metadata_field: {
 				"""#
 			return: "abc123"
 		},
+		{
+			title: "Get the address of the client an event was received from."
+			source: #"""
+				get_metadata_field!("source.peer_ip")
+				"""#
+			return: "10.1.2.3"
+		},
 	]
 }
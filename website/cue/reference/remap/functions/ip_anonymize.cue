@@ -0,0 +1,83 @@
+package metadata
+
+remap: functions: ip_anonymize: {
+	category: "IP"
+	description: """
+		Anonymizes `value` by keeping its network prefix and discarding the rest of the address.
+		By default, the host bits are zeroed. In `hash` mode they're replaced by bits of a
+		SHA-256 hash of the address instead, so different hosts on the same network can still be
+		told apart without being identified.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address (v4 or v6)."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "mode"
+			description: "How to replace the bits after the prefix."
+			required:    false
+			type: ["string"]
+			default: "zero"
+			enum: {
+				zero: "Set them to zero."
+				hash: "Set them to the leading bits of a SHA-256 hash of `key` followed by the address."
+			}
+		},
+		{
+			name:        "ipv4_prefix"
+			description: "The number of leading bits of IPv4 addresses to keep."
+			required:    false
+			type: ["integer"]
+			default: 24
+		},
+		{
+			name:        "ipv6_prefix"
+			description: "The number of leading bits of IPv6 addresses to keep."
+			required:    false
+			type: ["integer"]
+			default: 48
+		},
+		{
+			name: "key"
+			description: """
+				A secret to hash addresses with in `hash` mode. Without one, hashed addresses can
+				be recovered by hashing every address on the network.
+				"""
+			required: false
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address",
+		"`ipv4_prefix` isn't between 0 and 32, or `ipv6_prefix` isn't between 0 and 128",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Anonymize an IPv4 address"
+			source: #"""
+				ip_anonymize!("192.168.10.32")
+				"""#
+			return: "192.168.10.0"
+		},
+		{
+			title: "Anonymize an IPv6 address"
+			source: #"""
+				ip_anonymize!("2001:4f8:3:ba:2e0:81ff:fe22:d1f1", ipv6_prefix: 64)
+				"""#
+			return: "2001:4f8:3:ba::"
+		},
+		{
+			title: "Hash the host bits"
+			source: #"""
+				ip_anonymize!("192.168.10.32", mode: "hash")
+				"""#
+			return: "192.168.10.174"
+		},
+	]
+}
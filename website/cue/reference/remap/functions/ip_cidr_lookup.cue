@@ -0,0 +1,67 @@
+package metadata
+
+remap: functions: ip_cidr_lookup: {
+	category: "Enrichment"
+	description: """
+		Searches an [enrichment table](\(urls.enrichment_tables_concept)) of CIDR ranges for the
+		row with the most specific range containing `value`. Unlike
+		`get_enrichment_table_record`, which can only match fields exactly, rows match whenever
+		their range contains the address, and when several ranges do, the one with the longest
+		prefix is returned.
+
+		\(remap._enrichment_table_explainer)
+		"""
+
+	arguments: [
+		{
+			name:        "table"
+			description: "The [enrichment table](\(urls.enrichment_tables_concept)) to search."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "value"
+			description: "The IP address (v4 or v6) to look up."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "field"
+			description: "The field of the enrichment table holding the CIDR ranges."
+			required:    false
+			type: ["string"]
+			default: "cidr"
+		},
+		{
+			name: "select"
+			description: """
+				A subset of fields from the enrichment table to return. If not specified,
+				all fields are returned.
+				"""
+			required: false
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address",
+		"No range contains `value`",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Most specific range"
+			source: #"""
+				ip_cidr_lookup!("networks", "10.1.2.3")
+				"""#
+			return: {"cidr": "10.1.0.0/16", "name": "office"}
+		},
+		{
+			title: "Selecting fields"
+			source: #"""
+				ip_cidr_lookup!("networks", .client_ip, field: "network", select: ["name"])
+				"""#
+			return: {"name": "office"}
+		},
+	]
+}
@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: is_decimal: {
+	category: "Type"
+	description: """
+		Check if the type of a `value` is a decimal or not.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: #"The value to check"#
+			required:    true
+			type: ["any"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["boolean"]
+		rules: [
+			#"Returns `true` if `value` is a decimal."#,
+			#"Returns `false` if `value` is anything else, including a float."#,
+		]
+	}
+
+	examples: [
+		{
+			title: "Valid decimal"
+			source: """
+				is_decimal(to_decimal!("12.34"))
+				"""
+			return: true
+		},
+		{
+			title: "Non-matching type"
+			source: """
+				is_decimal(12.34)
+				"""
+			return: false
+		},
+	]
+}
@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: is_private_ip: {
+	category: "IP"
+	description: """
+		Determines whether `value` is an address that isn't routable on the internet. These are
+		the IPv4 private ranges (`10.0.0.0/8`, `172.16.0.0/12`, and `192.168.0.0/16`), the shared
+		address space used for carrier-grade NAT (`100.64.0.0/10`), the IPv6 unique local range
+		(`fc00::/7`), and loopback and link local addresses of either version. IPv4 mapped IPv6
+		addresses are checked as the IPv4 address they map.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address (v4 or v6)."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address",
+	]
+	return: types: ["boolean"]
+
+	examples: [
+		{
+			title: "Private IPv4 address"
+			source: #"""
+				is_private_ip!("192.168.10.32")
+				"""#
+			return: true
+		},
+		{
+			title: "Public IPv6 address"
+			source: #"""
+				is_private_ip!("2001:4f8:3:ba:2e0:81ff:fe22:d1f1")
+				"""#
+			return: false
+		},
+	]
+}
@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: is_valid_utf8: {
+	category: "String"
+	description: """
+		Check if a string `value` is valid UTF-8, or holds binary data such as a decoded payload.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: #"The string to check"#
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["boolean"]
+		rules: [
+			#"Returns `true` if `value` is valid UTF-8."#,
+			#"Returns `false` if `value` has bytes that aren't valid UTF-8."#,
+		]
+	}
+
+	examples: [
+		{
+			title: "Valid UTF-8"
+			source: """
+				is_valid_utf8("café")
+				"""
+			return: true
+		},
+		{
+			title: "Binary data"
+			source: """
+				is_valid_utf8(decode_base64!("3q2+7w=="))
+				"""
+			return: false
+		},
+	]
+}
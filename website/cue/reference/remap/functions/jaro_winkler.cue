@@ -0,0 +1,39 @@
+package metadata
+
+remap: functions: jaro_winkler: {
+	category:    "String"
+	description: """
+		Returns the [Jaro-Winkler similarity](\(urls.jaro_winkler)) of `value` and `other`, from `0.0`
+		for strings with nothing in common to `1.0` for equal strings.
+
+		The similarity favors strings that start with the same characters, which makes it suited
+		to comparing short strings such as usernames.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The first string."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "other"
+			description: "The second string."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Similarity of two strings"
+			source: #"""
+				round(jaro_winkler("martha", "marhta"), precision: 2)
+				"""#
+			return: 0.96
+		},
+	]
+}
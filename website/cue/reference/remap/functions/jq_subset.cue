@@ -0,0 +1,69 @@
+package metadata
+
+remap: functions: jq_subset: {
+	category:    "Object"
+	description: """
+		Runs the `program`, written in a limited subset of the [jq](\(urls.jq)) language, against `value` and returns all
+		of its outputs as an array.
+
+		This is useful for reshaping that's awkward to express in VRL, such as deep filtering or recursive descent with
+		`..`. The program is parsed when the VRL program is compiled, so syntax errors and syntax outside of the supported
+		subset are caught before any events are processed.
+		"""
+	notices: [
+		"""
+			This is not a full jq implementation. Only this subset of the jq language is supported: paths (`.foo`,
+			`."foo"`, `.[0]`, `.[]`, `.[1:3]`, `..`), `|`, `,`, `?`, `//`, arithmetic and comparison operators, `and`, `or`,
+			array and object construction, `if`/`elif`/`else`/`end`, and the builtins `add`, `all`, `any`, `ascii_downcase`, `ascii_upcase`, `empty`,
+			`endswith`, `first`, `flatten`, `from_entries`, `has`, `join`, `keys`, `last`, `length`, `map`, `map_values`,
+			`max`, `min`, `not`, `recurse`, `reverse`, `select`, `sort`, `sort_by`, `split`, `startswith`, `to_entries`,
+			`tonumber`, `tostring`, `type`, `unique`, and `with_entries`.
+			""",
+		"""
+			Anything else, such as variables, `as` bindings, assignment operators, `reduce`, `foreach`, `try`/`catch`,
+			string interpolation, formats like `@base64`, function definitions, or other builtins, fails to compile with
+			an error naming what isn't supported.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The input to the jq program."
+			required:    true
+			type: ["any"]
+		},
+		{
+			name:        "program"
+			description: "The jq program to run. Must be a string literal."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"the jq program raises an error for `value`, for example when iterating over a string",
+	]
+	return: {
+		types: ["array"]
+		rules: [
+			"Returns every output of the program, in order. A program that outputs nothing returns an empty array.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Filter and reshape"
+			source: #"""
+				jq_subset!({"users": [{"name": "ana", "age": 31}, {"name": "bo", "age": 17}]}, ".users[] | select(.age >= 18) | {name, adult: true}")
+				"""#
+			return: [{name: "ana", adult: true}]
+		},
+		{
+			title: "Recursive descent"
+			source: #"""
+				jq_subset!({"a": {"id": 1, "b": [{"id": 2}, {"c": 3}]}}, "[.. | .id? // empty]")
+				"""#
+			return: [[1, 2]]
+		},
+	]
+}
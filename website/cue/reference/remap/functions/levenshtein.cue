@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: levenshtein: {
+	category:    "String"
+	description: """
+		Returns the [Levenshtein distance](\(urls.levenshtein_distance)) between `value` and
+		`other`: the number of single character insertions, deletions, and substitutions needed to
+		turn one into the other.
+
+		A small distance between two different strings, such as a hostname and a well known
+		domain, can point at typosquatting that exact matching misses.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The first string."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "other"
+			description: "The second string."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Distance between two strings"
+			source: #"""
+				levenshtein("kitten", "sitting")
+				"""#
+			return: 3
+		},
+		{
+			title: "Detect a lookalike domain"
+			source: #"""
+				levenshtein("micros0ft.com", "microsoft.com") <= 2
+				"""#
+			return: true
+		},
+	]
+}
@@ -0,0 +1,31 @@
+package metadata
+
+remap: functions: ln: {
+	category: "Number"
+	description: """
+		Returns the natural logarithm (base _e_) of the `value`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The number to take the logarithm of."
+			required:    true
+			type: ["integer", "float", "decimal"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is zero or negative.",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Natural logarithm"
+			source: #"""
+				ln!(1)
+				"""#
+			return: 0.0
+		},
+	]
+}
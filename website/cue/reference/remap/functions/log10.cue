@@ -0,0 +1,31 @@
+package metadata
+
+remap: functions: log10: {
+	category: "Number"
+	description: """
+		Returns the common logarithm (base 10) of the `value`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The number to take the logarithm of."
+			required:    true
+			type: ["integer", "float", "decimal"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is zero or negative.",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Common logarithm"
+			source: #"""
+				log10!(1000)
+				"""#
+			return: 3.0
+		},
+	]
+}
@@ -0,0 +1,31 @@
+package metadata
+
+remap: functions: log2: {
+	category: "Number"
+	description: """
+		Returns the binary logarithm (base 2) of the `value`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The number to take the logarithm of."
+			required:    true
+			type: ["integer", "float", "decimal"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is zero or negative.",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Binary logarithm"
+			source: #"""
+				log2!(1024)
+				"""#
+			return: 10.0
+		},
+	]
+}
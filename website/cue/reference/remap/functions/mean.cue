@@ -0,0 +1,32 @@
+package metadata
+
+remap: functions: mean: {
+	category: "Number"
+	description: """
+		Returns the arithmetic mean of the numbers in the `value` array.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The numbers to average."
+			required:    true
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is empty.",
+		"`value` contains an element that isn't a number.",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Mean"
+			source: #"""
+				mean!([2, 4, 9])
+				"""#
+			return: 5.0
+		},
+	]
+}
@@ -0,0 +1,33 @@
+package metadata
+
+remap: functions: median: {
+	category: "Number"
+	description: """
+		Returns the median of the numbers in the `value` array. The median of an even number of numbers
+		is the mean of the two in the middle.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The numbers to find the median of."
+			required:    true
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is empty.",
+		"`value` contains an element that isn't a number.",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Median"
+			source: #"""
+				median!([7, 1, 3, 10])
+				"""#
+			return: 5.0
+		},
+	]
+}
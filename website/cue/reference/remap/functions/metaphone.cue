@@ -0,0 +1,46 @@
+package metadata
+
+remap: functions: metaphone: {
+	category:    "String"
+	description: """
+		Encodes the `value` with the original [Metaphone](\(urls.metaphone)) algorithm, so that
+		words that sound alike in English have the same code. Metaphone knows more about English
+		pronunciation than Soundex, and its codes aren't limited in length.
+
+		Only ASCII letters are encoded, any other characters are ignored. The "th" sound is encoded
+		as `0`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to encode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["string"]
+		rules: [
+			"Returns an empty string if the `value` has no ASCII letters.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Encode a word"
+			source: #"""
+				metaphone("Knight")
+				"""#
+			return: "NT"
+		},
+		{
+			title: "Compare names that sound alike"
+			source: #"""
+				metaphone("Catherine") == metaphone("Kathryn")
+				"""#
+			return: true
+		},
+	]
+}
@@ -0,0 +1,61 @@
+package metadata
+
+remap: functions: normalize_url: {
+	category:    "String"
+	description: """
+		Normalizes the `value` as a [URL](\(urls.url)), so that equivalent URLs compare equal. The scheme
+		and host are lowercased, default ports are removed, `.` and `..` path segments are resolved,
+		empty query parameters, queries, and fragments are removed, and the query parameters are
+		sorted by name.
+		"""
+	notices: [
+		"""
+			Query parameters are re-encoded in the `application/x-www-form-urlencoded` format, so spaces
+			are written as `+`. Repeated parameters keep their order.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The URL to normalize."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "sort_query"
+			description: "Whether to sort the query parameters by name."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
+		{
+			name:        "remove_fragment"
+			description: "Whether to remove the fragment."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a properly formatted URL",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Normalize a URL"
+			source: #"""
+				normalize_url!("HTTPS://Vector.dev:443/docs/../reference/?b=2&a=1&")
+				"""#
+			return: "https://vector.dev/reference/?a=1&b=2"
+		},
+		{
+			title: "Remove the fragment"
+			source: #"""
+				normalize_url!("https://vector.dev/docs?b=2&a=1#setup", sort_query: false, remove_fragment: true)
+				"""#
+			return: "https://vector.dev/docs?b=2&a=1"
+		},
+	]
+}
@@ -0,0 +1,58 @@
+package metadata
+
+remap: functions: parse_auditd: {
+	category:    "Parse"
+	description: """
+		Parses [Linux audit](\(urls.linux_audit)) records, joining the records that belong to the same audit event.
+
+		Records are grouped by their `msg=audit(...)` identifier, in the order the identifiers are first seen. Unquoted hex
+		values of the fields auditd encodes, such as `proctitle`, `exe`, `name`, or `EXECVE` arguments containing spaces,
+		are decoded. Other fields are left as they are. `EXECVE` records also have their
+		arguments collected into an `argv` array, with arguments split across several fields joined back together.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The audit records to parse, either as a newline-delimited string or as an array of records."
+			required:    true
+			type: ["string", "array"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` contains a record without a `type` or a valid `msg=audit(...)` identifier",
+	]
+	return: types: ["array"]
+
+	examples: [
+		{
+			title: "Parse an audit event"
+			source: #"""
+				parse_auditd!([
+					s'type=SYSCALL msg=audit(1364481363.243:24287): syscall=59 comm="ls" key=(null)',
+					s'type=EXECVE msg=audit(1364481363.243:24287): argc=2 a0="ls" a1=2F746D702F6120622063',
+				])
+				"""#
+			return: [{
+				id:        "1364481363.243:24287"
+				timestamp: "2013-03-28T14:36:03.243Z"
+				sequence:  24287
+				records: [
+					{
+						type:    "SYSCALL"
+						syscall: "59"
+						comm:    "ls"
+						key:     null
+					},
+					{
+						type: "EXECVE"
+						argc: "2"
+						a0:   "ls"
+						a1:   "/tmp/a b c"
+						argv: ["ls", "/tmp/a b c"]
+					},
+				]
+			}]
+		},
+	]
+}
@@ -0,0 +1,55 @@
+package metadata
+
+remap: functions: parse_aws_firehose_cloudwatch_logs: {
+	category: "Parse"
+	description: """
+		Unwraps the data of an AWS Kinesis Firehose record carrying AWS CloudWatch Logs events
+		(configured through AWS CloudWatch subscriptions) into the individual log events, each
+		with the log group and stream it was written to.
+
+		The data can be Base64 encoded and gzip compressed, as Firehose delivers it, or already
+		decoded and decompressed, as the `aws_kinesis_firehose` source emits it. Several
+		subscription messages in the same record are all unwrapped, and control messages, which
+		only check that the destination is reachable, are skipped.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The data of the Firehose record to unwrap."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't valid Base64, gzip, or JSON.",
+		"`value` doesn't contain properly formatted AWS CloudWatch Logs subscription messages.",
+	]
+	return: types: ["array"]
+
+	examples: [
+		{
+			title: "Unwrap a Firehose record of CloudWatch Logs events"
+			source: #"""
+				parse_aws_firehose_cloudwatch_logs!("H4sIAAAAAAACA3WOTQuCQBCG/8ucDdw+BL0JmqdOeguJTSdZ0F3ZGYsQ/3urJXhpbu/Hw7wjdEgkGyzePUIESVzEt0ua53GWggfmpdE6W2zO2a1pMmuG3iWMxLtmEYufs0XZrQF9lQc03Kmyqmdl9Fm1jJYgukLiSkrL2YVy4dMnap6zEVQ9f3YwKzeSZef+icD3hfBPQegfQm8d73oPZYlh8n7Y/i92FFuMsDK6hqmcPqgHcn4KAQAA")
+				"""#
+			return: [{
+				id:         "1"
+				timestamp:  "2020-09-14T19:09:29.039Z"
+				message:    "first"
+				owner:      "111111111111"
+				log_group:  "test-group"
+				log_stream: "test-stream"
+				subscription_filters: ["Destination"]
+			}, {
+				id:         "2"
+				timestamp:  "2020-09-14T19:09:29.041Z"
+				message:    "second"
+				owner:      "111111111111"
+				log_group:  "test-group"
+				log_stream: "test-stream"
+				subscription_filters: ["Destination"]
+			}]
+		},
+	]
+}
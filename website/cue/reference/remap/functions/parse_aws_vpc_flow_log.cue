@@ -1,9 +1,14 @@
 package metadata
 
 remap: functions: parse_aws_vpc_flow_log: {
-	category:    "Parse"
+	category: "Parse"
 	description: """
-		Parses `value` in the [VPC Flow Logs format](\(urls.aws_vpc_flow_logs)).
+		Parses `value` in the [VPC Flow Logs format](\(urls.aws_vpc_flow_logs)), versions 2
+		through 7.
+
+		Fields that are `-`, such as those of `NODATA` and `SKIPDATA` records, are parsed as
+		`null`. When the record has a `version` field, it's checked against the
+		[version each field was added in](\(urls.aws_vpc_flow_logs_fields)).
 		"""
 
 	arguments: [
@@ -15,13 +20,18 @@ remap: functions: parse_aws_vpc_flow_log: {
 		},
 		{
 			name:        "format"
-			description: "VPC Flow Log format."
+			description: """
+				VPC Flow Log format, as the space separated list of fields in the record. Fields can
+				be given the way the flow log is configured with, such as `${pkt-srcaddr}`, or by the
+				name of the parsed field, such as `pkt_srcaddr`. Defaults to the version 2 format.
+				"""
 			required:    false
 			type: ["string"]
 		},
 	]
 	internal_failure_reasons: [
 		"`value` isn't a properly formatted AWS VPC Flow log",
+		"`format` contains a field that was added in a later version than the record's `version`.",
 	]
 	return: types: ["object"]
 
@@ -33,6 +33,17 @@ remap: functions: parse_grok: {
 			default:     true
 			type: ["boolean"]
 		},
+		{
+			name: "alias_sources"
+			description: """
+				Paths to files containing additional grok pattern definitions, in the Logstash pattern file format:
+				one pattern per line, with the pattern name followed by whitespace and the pattern. Empty lines and
+				lines starting with `#` are ignored. The files are read, and every pattern in them is validated,
+				when the program is compiled.
+				"""
+			required: false
+			type: ["array"]
+		},
 	]
 	internal_failure_reasons: [
 		"`value` fails to parse using the provided `pattern`",
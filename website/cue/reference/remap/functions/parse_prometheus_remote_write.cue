@@ -0,0 +1,68 @@
+package metadata
+
+remap: functions: parse_prometheus_remote_write: {
+	category:    "Parse"
+	description: """
+		Parses the `value` as a [Prometheus remote write](\(urls.prometheus_remote_write)) request,
+		such as the body of a request received by the `http` source.
+
+		Samples are grouped into metrics the same way the `prometheus_remote_write` source groups
+		them, using the metadata in the request. Histograms and summaries are assembled from their
+		`_bucket`, `_sum`, and `_count` samples.
+
+		The metrics are returned in the structure that `encode_prometheus_text` takes, so a request
+		can be encoded into the text exposition format.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The remote write request."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "compressed"
+			description: """
+				Whether the `value` is compressed with [Snappy](\(urls.snappy)), as remote write
+				requests are. Set this to `false` if the request was already decompressed, such as
+				by the `http` source according to its `Content-Encoding` header.
+				"""
+			required: false
+			default:  true
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't validly compressed with Snappy.",
+		"`value` decompresses to more than 32 MiB.",
+		"`value` isn't a valid remote write request.",
+		"A time series in the request has no `__name__` label.",
+		"A metric is given more than one type by the request's metadata.",
+	]
+	return: {
+		types: ["array"]
+		rules: [
+			"Each metric is an object with `name`, `type`, `labels`, and `timestamp` fields.",
+			"Counters, gauges, and untyped metrics have a `value` field.",
+			"Histograms have `buckets`, an array of objects with `upper_limit` and `count` fields, as well as `sum` and `count` fields.",
+			"Summaries have `quantiles`, an array of objects with `quantile` and `value` fields, as well as `sum` and `count` fields.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Parse a Base64 encoded remote write request"
+			source: #"""
+				parse_prometheus_remote_write!(decode_base64!("W/BaCkAKHwoIX19uYW1lX18SE2h0dHBfcmVxdWVzdHNfdG90YWwKCwoEY29kZRIDMjAwEhAJAAAAAAAMkEAQgKjIjvkuGhcIARITaHR0cF9yZXF1ZXN0c190b3RhbA=="))
+				"""#
+			return: [{
+				name:      "http_requests_total"
+				type:      "counter"
+				labels: code: "200"
+				timestamp: "2021-02-11T16:00:00Z"
+				value:     1027.0
+			}]
+		},
+	]
+}
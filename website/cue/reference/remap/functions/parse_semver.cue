@@ -0,0 +1,58 @@
+package metadata
+
+remap: functions: parse_semver: {
+	category:    "Parse"
+	description: """
+		Parses the `value` as a [semantic version](\(urls.semver)).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The version to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "lenient"
+			description: "Whether to accept a leading `v`, and versions without a minor or patch number, which are taken to be zero."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid semantic version",
+		"a version number is larger than the largest integer",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse a version"
+			source: #"""
+				parse_semver!("1.2.3-rc.1+build.5")
+				"""#
+			return: {
+				major: 1
+				minor: 2
+				patch: 3
+				pre:   "rc.1"
+				build: "build.5"
+			}
+		},
+		{
+			title: "Parse a partial version"
+			source: #"""
+				parse_semver!("v7.32", lenient: true)
+				"""#
+			return: {
+				major: 7
+				minor: 32
+				patch: 0
+				pre:   null
+				build: null
+			}
+		},
+	]
+}
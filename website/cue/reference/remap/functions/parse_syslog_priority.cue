@@ -0,0 +1,46 @@
+package metadata
+
+remap: functions: parse_syslog_priority: {
+	category:    "Parse"
+	description: """
+		Decomposes a Syslog PRI value into its [facility](\(urls.syslog_facility)) and
+		[severity](\(urls.syslog_levels)) keywords. The `value` can be an integer, a numeric string, or a
+		`<PRI>` header.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The PRI value."
+			required:    true
+			type: ["string", "integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid Syslog PRI value between 0 and 191.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Parse a Syslog PRI value"
+			source: """
+				parse_syslog_priority!(34)
+				"""
+			return: {
+				facility: "auth"
+				severity: "crit"
+			}
+		},
+		{
+			title: "Parse a Syslog PRI header"
+			source: """
+				parse_syslog_priority!("<165>")
+				"""
+			return: {
+				facility: "local4"
+				severity: "notice"
+			}
+		},
+	]
+}
@@ -4,6 +4,9 @@ remap: functions: parse_timestamp: {
 	category:    "Parse"
 	description: """
 		Parses the `value` in [strptime](\(urls.strptime_specifiers)) `format`.
+
+		When `format` is an array, each format is tried in order and the first one that parses
+		successfully is used.
 		"""
 
 	arguments: [
@@ -15,16 +18,53 @@ remap: functions: parse_timestamp: {
 		},
 		{
 			name:        "format"
-			description: "The [strptime](\(urls.strptime_specifiers)) format."
+			description: "The [strptime](\(urls.strptime_specifiers)) format, or an array of formats to try in order."
 			required:    true
+			type: ["string", "array"]
+		},
+		{
+			name: "locale"
+			description: """
+				The locale of month and weekday names in `value`. Localized names, both full and abbreviated,
+				are translated to English before parsing so the regular `%b`, `%B`, `%a`, and `%A` specifiers
+				can be used.
+				"""
+			required: false
+			enum: {
+				de: "German"
+				es: "Spanish"
+				fr: "French"
+				it: "Italian"
+				nl: "Dutch"
+				pt: "Portuguese"
+			}
 			type: ["string"]
 		},
-
+		{
+			name: "year_pivot"
+			description: """
+				The pivot for two-digit years parsed with `%y`. Years below the pivot are placed in the 2000s,
+				the rest in the 1900s. Must be between 0 and 100. By default, the pivot is 70.
+				"""
+			required: false
+			type: ["integer"]
+		},
+		{
+			name: "include_format"
+			description: """
+				If `true`, an object is returned containing the parsed `timestamp` and the `format` that matched,
+				which helps debugging arrays of fallback formats. Must be a literal.
+				"""
+			required: false
+			default:  false
+			type: ["boolean"]
+		},
 	]
 	internal_failure_reasons: [
-		"`value` fails to parse using the provided `format`",
+		"`value` fails to parse using any of the provided `format`s",
+		"`year_pivot` is not between 0 and 100",
 	]
-	return: types: ["timestamp"]
+	return: types: ["timestamp", "object"]
 
 	examples: [
 		{
@@ -34,5 +74,29 @@ remap: functions: parse_timestamp: {
 				"""#
 			return: "2020-10-10T16:00:00Z"
 		},
+		{
+			title: "Parse timestamp with fallback formats"
+			source: #"""
+				parse_timestamp!("2020-10-10 16:00:00 +0000", format: ["%v %R %z", "%F %T %z"])
+				"""#
+			return: "2020-10-10T16:00:00Z"
+		},
+		{
+			title: "Parse timestamp with localized month names"
+			source: #"""
+				parse_timestamp!("10. Oktober 2020 16:00 +0000", format: "%d. %B %Y %R %z", locale: "de")
+				"""#
+			return: "2020-10-10T16:00:00Z"
+		},
+		{
+			title: "Parse timestamp and report the matching format"
+			source: #"""
+				parse_timestamp!("2020-10-10 16:00:00 +0000", format: ["%v %R %z", "%F %T %z"], include_format: true)
+				"""#
+			return: {
+				timestamp: "2020-10-10T16:00:00Z"
+				format:    "%F %T %z"
+			}
+		},
 	]
 }
@@ -3,7 +3,9 @@ package metadata
 remap: functions: parse_url: {
 	category:    "Parse"
 	description: """
-		Parses the `value` in [URL](\(urls.url)) format.
+		Parses the `value` in [URL](\(urls.url)) format. Besides the `path`, its non-empty segments
+		are returned percent-decoded in `path_segments`. Use `encode_url` to build a URL back from the
+		returned object.
 		"""
 
 	arguments: [
@@ -25,6 +27,26 @@ remap: functions: parse_url: {
 			type: ["boolean"]
 			default: false
 		},
+		{
+			name: "decode_punycode"
+			description: """
+				If true, internationalized host names, which URLs hold in their ASCII
+				([punycode](\(urls.punycode))) form, are decoded to Unicode.
+				"""
+			required: false
+			type: ["boolean"]
+			default: false
+		},
+		{
+			name: "multi_value_query"
+			description: """
+				If true, the values of query parameters given more than once are all kept, in an
+				array. Otherwise, only the last value is kept. Must be a literal.
+				"""
+			required: false
+			type: ["boolean"]
+			default: false
+		},
 	]
 	internal_failure_reasons: [
 		"`value` isn't a properly formatted URL",
@@ -44,6 +66,7 @@ remap: functions: parse_url: {
 				host:     "vector.dev"
 				port:     4343
 				path:     "/foobar"
+				path_segments: ["foobar"]
 				query: hello: "world"
 				fragment: "123"
 			}
@@ -60,9 +83,27 @@ remap: functions: parse_url: {
 				host:     "vector.dev"
 				port:     443
 				path:     "/"
+				path_segments: []
 				query: {}
 				fragment: null
 			}
 		},
+		{
+			title: "Parse internationalized URL with repeated query parameters"
+			source: #"""
+				parse_url!("https://xn--bcher-kva.example/a/b%20c?tag=x&tag=y", decode_punycode: true, multi_value_query: true)
+				"""#
+			return: {
+				scheme:   "https"
+				username: ""
+				password: ""
+				host:     "bücher.example"
+				port:     null
+				path:     "/a/b%20c"
+				path_segments: ["a", "b c"]
+				query: tag: ["x", "y"]
+				fragment: null
+			}
+		},
 	]
 }
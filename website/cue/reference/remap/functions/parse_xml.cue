@@ -27,7 +27,7 @@ remap: functions: parse_xml: {
 		},
 		{
 			name:        "attr_prefix"
-			description: "String prefix to use for XML tag attribute keys."
+			description: "String prefix to use for XML tag attribute keys. Set to an empty string to use the bare attribute names."
 			required:    false
 			default:     "@"
 			type: ["string"]
@@ -67,6 +67,20 @@ remap: functions: parse_xml: {
 			default:     true
 			type: ["boolean"]
 		},
+		{
+			name:        "always_array"
+			description: "Names of elements that are always returned as an array, even if they occur only once."
+			required:    false
+			default:     []
+			type: ["array"]
+		},
+		{
+			name:        "strip_namespaces"
+			description: "Remove namespace prefixes from element and attribute names. If `false`, names keep their prefix, for example `soap:Body`."
+			required:    false
+			default:     true
+			type: ["boolean"]
+		},
 	]
 	internal_failure_reasons: [
 		"`value` isn't a valid XML document",
@@ -93,5 +107,16 @@ remap: functions: parse_xml: {
 				}
 			}
 		},
+		{
+			title: "Parse XML with repeatable elements"
+			source: #"""
+				parse_xml!(s'<order><item>apple</item></order>', always_array: ["item"])
+				"""#
+			return: {
+				"order": {
+					"item": ["apple"]
+				}
+			}
+		},
 	]
 }
@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: percentile: {
+	category: "Number"
+	description: """
+		Returns the `percentile` of the numbers in the `value` array. When the `percentile` falls
+		between two of the numbers, the result is interpolated linearly between them.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The numbers to find the percentile of."
+			required:    true
+			type: ["array"]
+		},
+		{
+			name:        "percentile"
+			description: "The percentile to find, from `0` to `100`."
+			required:    true
+			type: ["integer", "float"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is empty.",
+		"`value` contains an element that isn't a number.",
+		"`percentile` is below `0` or above `100`.",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Percentile"
+			source: #"""
+				percentile!([15, 20, 35, 40, 50], 25)
+				"""#
+			return: 20.0
+		},
+		{
+			title: "Interpolated percentile"
+			source: #"""
+				percentile!([1, 2, 3, 4], 75)
+				"""#
+			return: 3.25
+		},
+	]
+}
@@ -0,0 +1,45 @@
+package metadata
+
+remap: functions: pow: {
+	category: "Number"
+	description: """
+		Returns the `value` raised to the power of the `exponent`, computed as a float.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The base."
+			required:    true
+			type: ["integer", "float", "decimal"]
+		},
+		{
+			name:        "exponent"
+			description: "The exponent."
+			required:    true
+			type: ["integer", "float", "decimal"]
+		},
+	]
+	internal_failure_reasons: [
+		"The result is too large to be a float.",
+		"The result isn't a real number, such as the square root of a negative number.",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Raise to a power"
+			source: #"""
+				pow!(2, 10)
+				"""#
+			return: 1024.0
+		},
+		{
+			title: "Take a square root"
+			source: #"""
+				pow!(16, 0.5)
+				"""#
+			return: 4.0
+		},
+	]
+}
@@ -11,6 +11,12 @@ remap: functions: remove_metadata_field: {
 			name: "key"
 			description: """
 				The name of the field to look up in the metadata.
+
+				Besides these, any key starting with `source.` refers to the details sources attach about
+				where an event came from, such as `source.peer_ip` from the socket based sources,
+				`source.tls_subject` when a client certificate was presented, `source.topic`,
+				`source.partition` and `source.offset` from the `kafka` source, and `source.path` from the
+				`file` source. These can be set to any value.
 				"""
 			required: true
 			enum: {
@@ -11,7 +11,7 @@ remap: functions: round: {
 			name:        "value"
 			description: "The number to round."
 			required:    true
-			type: ["integer", "float"]
+			type: ["integer", "float", "decimal"]
 		},
 		{
 			name:        "precision"
@@ -23,7 +23,7 @@ remap: functions: round: {
 	]
 	internal_failure_reasons: []
 	return: {
-		types: ["integer", "float"]
+		types: ["integer", "float", "decimal"]
 		rules: [
 			"If `precision` is `0`, then an integer is returned, otherwise a float is returned.",
 		]
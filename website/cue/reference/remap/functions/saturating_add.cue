@@ -0,0 +1,43 @@
+package metadata
+
+remap: functions: saturating_add: {
+	category: "Number"
+	description: """
+		Adds `other` to `value`. If the result doesn't fit in a signed 64-bit integer, the largest or smallest
+		possible integer is returned instead.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The first integer."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "other"
+			description: "The second integer."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Add integers"
+			source: #"""
+				saturating_add(1, 2)
+				"""#
+			return: 3
+		},
+		{
+			title: "Saturate on overflow"
+			source: #"""
+				saturating_add(9223372036854775807, 1)
+				"""#
+			return: 9223372036854775807
+		},
+	]
+}
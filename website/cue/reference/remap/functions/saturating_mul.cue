@@ -0,0 +1,43 @@
+package metadata
+
+remap: functions: saturating_mul: {
+	category: "Number"
+	description: """
+		Multiplies `value` by `other`. If the result doesn't fit in a signed 64-bit integer, the largest or smallest
+		possible integer is returned instead.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The first integer."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "other"
+			description: "The second integer."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Multiply integers"
+			source: #"""
+				saturating_mul(3, 4)
+				"""#
+			return: 12
+		},
+		{
+			title: "Saturate on overflow"
+			source: #"""
+				saturating_mul(9223372036854775807, 2)
+				"""#
+			return: 9223372036854775807
+		},
+	]
+}
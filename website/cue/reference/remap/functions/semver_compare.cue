@@ -0,0 +1,58 @@
+package metadata
+
+remap: functions: semver_compare: {
+	category:    "String"
+	description: """
+		Compares the [semantic versions](\(urls.semver)) `value` and `other` by precedence, returning
+		`-1` if `value` is older, `0` if they are equal, and `1` if `value` is newer.
+		"""
+	notices: [
+		"""
+			Pre-release versions are older than the release they precede, and build metadata is
+			ignored, so `1.0.0+linux` and `1.0.0+darwin` are equal.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The version to compare."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "other"
+			description: "The version to compare `value` to."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "lenient"
+			description: "Whether to accept a leading `v`, and versions without a minor or patch number, which are taken to be zero."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` or `other` isn't a valid semantic version",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Newer version"
+			source: #"""
+				semver_compare!("1.10.0", "1.9.3")
+				"""#
+			return: 1
+		},
+		{
+			title: "Pre-release"
+			source: #"""
+				semver_compare!("2.0.0-rc.1", "2.0.0")
+				"""#
+			return: -1
+		},
+	]
+}
@@ -0,0 +1,59 @@
+package metadata
+
+remap: functions: semver_matches: {
+	category:    "String"
+	description: """
+		Determines whether the [semantic version](\(urls.semver)) `version` satisfies the `requirement`,
+		such as `>=1.2, <2`, `^1.2`, or `~1.2.3`, using the same requirement syntax as Cargo.
+		"""
+	notices: [
+		"""
+			Pre-release versions only satisfy requirements that name a pre-release of the same
+			`major.minor.patch` version.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "version"
+			description: "The version to check."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "requirement"
+			description: "The requirement `version` must satisfy. Comparators are separated by commas, and must all be satisfied."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "lenient"
+			description: "Whether to accept a leading `v`, and versions without a minor or patch number, which are taken to be zero."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`version` isn't a valid semantic version",
+		"`requirement` isn't a valid version requirement",
+	]
+	return: types: ["boolean"]
+
+	examples: [
+		{
+			title: "Matching version"
+			source: #"""
+				semver_matches!("1.4.2", ">=1.2, <2")
+				"""#
+			return: true
+		},
+		{
+			title: "Route events from old agents"
+			source: #"""
+				semver_matches!("v0.9", "<1.0.0", lenient: true)
+				"""#
+			return: true
+		},
+	]
+}
@@ -8,9 +8,17 @@ remap: functions: set_metadata_field: {
 
 	arguments: [
 		{
-			name:        "key"
-			description: "The name of the field to set in the metadata."
-			required:    true
+			name: "key"
+			description: """
+				The name of the field to set in the metadata.
+
+				Besides these, any key starting with `source.` refers to the details sources attach about
+				where an event came from, such as `source.peer_ip` from the socket based sources,
+				`source.tls_subject` when a client certificate was presented, `source.topic`,
+				`source.partition` and `source.offset` from the `kafka` source, and `source.path` from the
+				`file` source. These can be set to any value.
+				"""
+			required: true
 			enum: {
 				datadog_api_key: """
 					The Datadog API key.
@@ -27,9 +35,9 @@ remap: functions: set_metadata_field: {
 		},
 		{
 			name:        "value"
-			description: "The value to set the field to."
+			description: "The value to set the field to. This must be a string for the `datadog_api_key` and `splunk_hec_token` fields."
 			required:    true
-			type: ["string"]
+			type: ["any"]
 		},
 	]
 	internal_failure_reasons: [
@@ -44,5 +52,12 @@ remap: functions: set_metadata_field: {
 				"""#
 			return: "null"
 		},
+		{
+			title: "Record which tenant an event belongs to, without adding it to the event."
+			source: #"""
+				set_metadata_field!("source.tenant", "acme")
+				"""#
+			return: "null"
+		},
 	]
 }
@@ -0,0 +1,40 @@
+package metadata
+
+remap: functions: snowflake_id: {
+	category:    "Random"
+	description: """
+		Generates a [Snowflake ID](\(urls.snowflake_id)): a positive 64-bit integer made of 41 bits
+		of milliseconds since the Twitter epoch (`2010-11-04T01:42:54.657Z`), the 10 bit
+		`worker_id` and a 12 bit sequence number.
+
+		IDs generated by the same Vector process are strictly increasing. When more than 4096 IDs
+		are generated in a single millisecond, the following milliseconds are borrowed rather than
+		waiting for the clock to catch up.
+		"""
+
+	arguments: [
+		{
+			name:        "worker_id"
+			description: """
+				The ID of this generator, between `0` and `1023`. Give every Vector instance
+				generating IDs its own `worker_id` to keep the IDs unique across instances.
+				"""
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`worker_id` is not between `0` and `1023`.",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Create a Snowflake ID"
+			source: #"""
+				snowflake_id!(42)
+				"""#
+			return: 1669463498417258496
+		},
+	]
+}
@@ -0,0 +1,44 @@
+package metadata
+
+remap: functions: soundex: {
+	category:    "String"
+	description: """
+		Encodes the `value` with [Soundex](\(urls.soundex)), so that names that sound alike
+		in English have the same code.
+
+		Only ASCII letters are encoded, any other characters are ignored.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to encode."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: {
+		types: ["string"]
+		rules: [
+			"Returns an empty string if the `value` has no ASCII letters.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Encode a name"
+			source: #"""
+				soundex("Robert")
+				"""#
+			return: "R163"
+		},
+		{
+			title: "Compare names that sound alike"
+			source: #"""
+				soundex("Robert") == soundex("Rupert")
+				"""#
+			return: true
+		},
+	]
+}
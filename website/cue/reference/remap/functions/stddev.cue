@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: stddev: {
+	category: "Number"
+	description: """
+		Returns the standard deviation of the numbers in the `value` array.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The numbers to find the standard deviation of."
+			required:    true
+			type: ["array"]
+		},
+		{
+			name:        "sample"
+			description: "Whether to compute the sample standard deviation, which divides by one less than the number of numbers, rather than the population standard deviation."
+			required:    false
+			default:     false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is empty.",
+		"`value` contains an element that isn't a number.",
+		"`sample` is `true` and `value` has fewer than two numbers.",
+	]
+	return: types: ["float"]
+
+	examples: [
+		{
+			title: "Population standard deviation"
+			source: #"""
+				stddev!([2, 4, 4, 4, 5, 5, 7, 9])
+				"""#
+			return: 2.0
+		},
+		{
+			title: "Sample standard deviation"
+			source: #"""
+				stddev!([1, 3, 5], sample: true)
+				"""#
+			return: 2.0
+		},
+	]
+}
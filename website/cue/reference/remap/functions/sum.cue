@@ -0,0 +1,46 @@
+package metadata
+
+remap: functions: sum: {
+	category: "Number"
+	description: """
+		Adds up the numbers in the `value` array, the same way the `+` operator adds them: integers and
+		decimals stay exact unless a float is added.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The numbers to add up."
+			required:    true
+			type: ["array"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` contains an element that isn't a number.",
+		"The sum of integers overflows a signed 64-bit integer.",
+		"The sum of floats is too large to be a float.",
+	]
+	return: {
+		types: ["integer", "float", "decimal"]
+		rules: [
+			"Returns `0` if the `value` is empty.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Add up integers"
+			source: #"""
+				sum!([3, 5, 8])
+				"""#
+			return: 16
+		},
+		{
+			title: "Add up floats"
+			source: #"""
+				sum!([0.5, 1, 2.25])
+				"""#
+			return: 3.75
+		},
+	]
+}
@@ -0,0 +1,64 @@
+package metadata
+
+remap: functions: to_decimal: {
+	category: "Coerce"
+	description: """
+		Coerces the `value` into an exact decimal number.
+
+		Decimals hold up to 28 significant digits without rounding, which makes them suitable for
+		monetary amounts and for integer IDs that don't fit in a signed 64-bit integer. Arithmetic
+		between decimals and integers stays exact, while mixing a decimal with a float results in
+		a float.
+		"""
+
+	arguments: [
+		{
+			name: "value"
+			description: """
+				The value to convert to a decimal.
+				"""
+			required: true
+			type: ["decimal", "integer", "float", "boolean", "string", "null"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is a string but the text is not a decimal number",
+		"`value` is a float that is too large to be represented as a decimal",
+		"`value` is not a string, number, boolean or null",
+	]
+	return: {
+		types: ["decimal"]
+		rules: [
+			"If `value` is a decimal, it will be returned as-is.",
+			"If `value` is an integer, it is converted without loss of precision.",
+			"If `value` is a float, the shortest decimal representing the float is returned.",
+			"If `value` is a string, it must be a number in either plain or scientific notation.",
+			"If `value` is a boolean, `0` is returned for `false` and `1` is returned for `true`.",
+			"If `value` is null, `0` is returned.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Coerce to a decimal (string)"
+			source: """
+				to_decimal!("1234.5678")
+				"""
+			return: 1234.5678
+		},
+		{
+			title: "Coerce a large unsigned integer"
+			source: """
+				to_decimal!("18446744073709551615")
+				"""
+			return: 18446744073709551615
+		},
+		{
+			title: "Exact arithmetic"
+			source: """
+				to_decimal!("0.1") + to_decimal!("0.2")
+				"""
+			return: 0.3
+		},
+	]
+}
@@ -0,0 +1,127 @@
+package metadata
+
+remap: functions: to_ocsf: {
+	category: "Convert"
+	description: """
+		Maps `value` to an event of the given [Open Cybersecurity Schema Framework](\(urls.ocsf))
+		(OCSF) class, for example to send it to the `aws_security_lake` sink. `profile` is the
+		format of `value`, and only some profiles can be mapped to each class.
+
+		The mapped event is checked to have every attribute its class requires, and fields of
+		`value` with no OCSF equivalent are either dropped or, for CloudTrail records, kept under
+		`unmapped`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The object to map."
+			required:    true
+			type: ["object"]
+		},
+		{
+			name:        "class"
+			description: "The OCSF class to map `value` to."
+			required:    true
+			type: ["string"]
+			enum: {
+				api_activity:     "API Activity (`6003`), from the `cloudtrail` profile."
+				authentication:   "Authentication (`3002`), from the `cloudtrail` or `syslog_auth` profiles."
+				network_activity: "Network Activity (`4001`), from the `vpc_flow` profile."
+			}
+		},
+		{
+			name:        "profile"
+			description: "The format of `value`."
+			required:    true
+			type: ["string"]
+			enum: {
+				cloudtrail:  "A [CloudTrail](\(urls.aws_cloudtrail)) record, such as those the `aws_s3` source reads from CloudTrail log files."
+				syslog_auth: "An `sshd` log in attempt, as parsed by `parse_syslog`."
+				vpc_flow:    "A VPC flow log, as parsed by `parse_aws_vpc_flow_log`."
+			}
+		},
+	]
+	internal_failure_reasons: [
+		"`value` is a `syslog_auth` message that isn't an `sshd` log in attempt.",
+		"The mapped event is missing an attribute its class requires.",
+	]
+	return: types: ["object"]
+
+	examples: [
+		{
+			title: "Map an sshd log in attempt"
+			source: #"""
+				to_ocsf!({
+					"appname": "sshd",
+					"hostname": "bastion",
+					"message": "Accepted publickey for alice from 10.0.0.1 port 52314 ssh2",
+					"timestamp": t'2022-03-01T00:00:00Z'
+				}, "authentication", "syslog_auth")
+				"""#
+			return: {
+				activity_id:   1
+				auth_protocol: "publickey"
+				category_uid:  3
+				class_uid:     3002
+				dst_endpoint: hostname: "bastion"
+				metadata: {
+					product: name: "sshd"
+					version: "1.0.0"
+				}
+				severity_id: 1
+				src_endpoint: {
+					ip:   "10.0.0.1"
+					port: 52314
+				}
+				status:    "Success"
+				status_id: 1
+				time:      1646092800000
+				type_uid:  300201
+				user: name: "alice"
+			}
+		},
+		{
+			title: "Map a VPC flow log"
+			source: #"""
+				to_ocsf!(parse_aws_vpc_flow_log!("2 123456789010 eni-1235b8ca123456789 172.31.16.139 172.31.16.21 20641 22 6 20 4249 1418530010 1418530070 REJECT OK"), "network_activity", "vpc_flow")
+				"""#
+			return: {
+				activity_id:  5
+				category_uid: 4
+				class_uid:    4001
+				cloud: {
+					account_uid: "123456789010"
+					provider:    "AWS"
+				}
+				connection_info: protocol_num: 6
+				dst_endpoint: {
+					ip:   "172.31.16.21"
+					port: 22
+				}
+				end_time: 1418530070000
+				metadata: {
+					product: {
+						name:        "Amazon VPC"
+						vendor_name: "AWS"
+						version:     2
+					}
+					version: "1.0.0"
+				}
+				severity_id: 1
+				src_endpoint: {
+					interface_uid: "eni-1235b8ca123456789"
+					ip:            "172.31.16.139"
+					port:          20641
+				}
+				start_time: 1418530010000
+				time:       1418530070000
+				traffic: {
+					bytes:   4249
+					packets: 20
+				}
+				type_uid: 400105
+			}
+		},
+	]
+}
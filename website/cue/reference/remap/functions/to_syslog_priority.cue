@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: to_syslog_priority: {
+	category:    "Convert"
+	description: """
+		Computes the Syslog PRI value from a [facility](\(urls.syslog_facility)) and a
+		[severity](\(urls.syslog_levels)), as used in the `<PRI>` header of a Syslog message. Both the
+		facility and the severity can be given either as a keyword or as a numeric code.
+		"""
+
+	arguments: [
+		{
+			name:        "facility"
+			description: "The facility keyword or code."
+			required:    true
+			type: ["string", "integer"]
+		},
+		{
+			name:        "severity"
+			description: "The severity keyword or code."
+			required:    true
+			type: ["string", "integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`facility` isn't a valid Syslog [facility](\(urls.syslog_facility)).",
+		"`severity` isn't a valid Syslog [severity](\(urls.syslog_levels)).",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Compute a Syslog PRI from keywords"
+			source: """
+				to_syslog_priority!("auth", "crit")
+				"""
+			return: 34
+		},
+		{
+			title: "Build a Syslog header"
+			source: """
+				"<" + to_string(to_syslog_priority!(20, 5)) + ">"
+				"""
+			return: "<165>"
+		},
+	]
+}
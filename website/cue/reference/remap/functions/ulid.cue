@@ -0,0 +1,24 @@
+package metadata
+
+remap: functions: ulid: {
+	category:    "Random"
+	description: """
+		Generates a [ULID](\(urls.ulid)): a 26 character Crockford base32 string made of the
+		current Unix time in milliseconds followed by 80 random bits. ULIDs generated in later
+		milliseconds sort lexicographically after earlier ones.
+		"""
+
+	arguments: []
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Create a ULID"
+			source: #"""
+				ulid()
+				"""#
+			return: "01H3GCJQAAFCZ8VWAJ0TS4Y2K7"
+		},
+	]
+}
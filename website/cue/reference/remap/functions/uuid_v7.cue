@@ -0,0 +1,24 @@
+package metadata
+
+remap: functions: uuid_v7: {
+	category:    "Random"
+	description: """
+		Generates a time-ordered [UUIDv7](\(urls.uuidv7)) string from the current Unix time in
+		milliseconds and random bits. UUIDs generated in later milliseconds sort after earlier
+		ones, both as strings and as bytes.
+		"""
+
+	arguments: []
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Create a UUIDv7"
+			source: #"""
+				uuid_v7()
+				"""#
+			return: "0188e0c9-5d4a-7b3e-9f2c-1a6d8e4b7c05"
+		},
+	]
+}
@@ -0,0 +1,58 @@
+package metadata
+
+remap: functions: xpath: {
+	category: "Parse"
+	description: """
+		Evaluates the XPath expression `expr` against the XML document in `value` and returns the string value of each
+		selected node, without converting the whole document to an object.
+		"""
+	notices: [
+		"""
+			A subset of XPath 1.0 is supported: child (`/`) and descendant (`//`) steps, `.` and `..`, element names and `*`,
+			attributes (`@name` and `@*`), `text()`, and the predicates `[n]`, `[last()]`, `[@attr]`, `[@attr='value']`,
+			`[name]` and `[name='value']`. Namespace prefixes are ignored when matching names.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string representation of the XML document to query."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "expr"
+			description: "The XPath expression to evaluate. Must be a string literal, and is validated at compile time."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid XML document",
+	]
+	return: {
+		types: ["array"]
+		rules: [
+			"The string value of an element is the concatenation of all the text it contains.",
+			"Returns an empty array if no nodes match.",
+		]
+	}
+
+	examples: [
+		{
+			title: "Select element text"
+			source: #"""
+				xpath!(s'<books><book><title>Dune</title></book><book><title>Emma</title></book></books>', "//title")
+				"""#
+			return: ["Dune", "Emma"]
+		},
+		{
+			title: "Select attributes with a predicate"
+			source: #"""
+				xpath!(s'<books><book id="1" lang="en"/><book id="2" lang="fr"/></books>', "/books/book[@lang='fr']/@id")
+				"""#
+			return: ["2"]
+		},
+	]
+}
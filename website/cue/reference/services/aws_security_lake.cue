@@ -0,0 +1,10 @@
+package metadata
+
+services: aws_security_lake: {
+	name:     "Amazon Security Lake"
+	thing:    "an \(name) custom source"
+	url:      urls.aws_security_lake
+	versions: null
+
+	description: "[Amazon Security Lake](\(urls.aws_security_lake)) centralizes security data from AWS, SaaS providers and custom sources into a data lake stored in your account, normalized to the Open Cybersecurity Schema Framework (OCSF)."
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: github: {
+	name:     "GitHub"
+	thing:    "a \(name) repository or organization"
+	url:      urls.github
+	versions: null
+
+	description: "[GitHub](\(urls.github)) hosts Git repositories, along with the pull requests, issues and Actions workflows built around them."
+}
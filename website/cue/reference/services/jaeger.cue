@@ -0,0 +1,10 @@
+package metadata
+
+services: jaeger: {
+	name:     "Jaeger"
+	thing:    "a \(name) client or agent"
+	url:      urls.jaeger
+	versions: null
+
+	description: "[Jaeger](\(urls.jaeger)) is a distributed tracing system, with clients and agents that report spans to its collector."
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: named_pipe: {
+	name:     "Named Pipe"
+	thing:    "a \(name)"
+	url:      urls.named_pipe
+	versions: null
+}
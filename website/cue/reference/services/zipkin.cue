@@ -0,0 +1,10 @@
+package metadata
+
+services: zipkin: {
+	name:     "Zipkin"
+	thing:    "a \(name) tracer"
+	url:      urls.zipkin
+	versions: null
+
+	description: "[Zipkin](\(urls.zipkin)) is a distributed tracing system, with tracers that report spans to its collector."
+}
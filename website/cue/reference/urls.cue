@@ -20,9 +20,13 @@ urls: {
 	aws_athena:                                               "https://aws.amazon.com/athena/"
 	aws_athena_console:                                       "https://console.aws.amazon.com/athena/home"
 	aws_canonical_user_id:                                    "\(aws_docs)/general/latest/gr/acct-identifiers.html#FindingCanonicalId"
+	aws_cloudtrail:                                           "https://aws.amazon.com/cloudtrail/"
+	aws_cloudtrail_digest_files:                              "\(aws_docs)/awscloudtrail/latest/userguide/cloudtrail-log-file-validation-digest-file-structure.html"
 	aws_cloudwatch:                                           "https://aws.amazon.com/cloudwatch/"
 	aws_cloudwatch_logs:                                      "\(aws_docs)/AmazonCloudWatch/latest/logs/WhatIsCloudWatchLogs.html"
 	aws_cloudwatch_logs_api:                                  "\(aws_docs)/AmazonCloudWatchLogs/latest/APIReference/Welcome.html"
+	aws_cloudwatch_logs_filter_log_events:                    "\(aws_docs)/AmazonCloudWatchLogs/latest/APIReference/API_FilterLogEvents.html"
+	aws_cloudwatch_logs_filter_pattern:                       "\(aws_docs)/AmazonCloudWatch/latest/logs/FilterAndPatternSyntax.html"
 	aws_cloudwatch_logs_firehose:                             "\(aws_docs)/firehose/latest/dev/writing-with-cloudwatch-logs.html"
 	aws_cloudwatch_logs_group_name:                           "\(aws_docs)/AmazonCloudWatch/latest/logs/Working-with-log-groups-and-streams.html"
 	aws_cloudwatch_logs_service_limits:                       "\(aws_docs)/AmazonCloudWatch/latest/logs/cloudwatch_limits_cwl.html"
@@ -76,13 +80,20 @@ urls: {
 	aws_s3_sse:                                               "\(aws_docs)/AmazonS3/latest/dev/UsingServerSideEncryption.html"
 	aws_s3_storage_classes:                                   "https://aws.amazon.com/s3/storage-classes/"
 	aws_s3_tags:                                              "\(aws_docs)/AmazonS3/latest/user-guide/add-object-tags.html"
+	aws_security_lake:                                        "https://aws.amazon.com/security-lake/"
+	aws_security_lake_custom_sources:                         "\(aws_docs)/security-lake/latest/userguide/custom-sources.html"
 	aws_sqs:                                                  "https://aws.amazon.com/sqs/"
 	aws_sqs_api:                                              "\(aws_docs)/AWSSimpleQueueService/latest/APIReference/Welcome.html"
 	aws_sqs_create:                                           "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/sqs-configure-create-queue.html"
 	aws_sqs_message_deduplication_id:                         "\(aws_docs)/AWSSimpleQueueService/latest/SQSDeveloperGuide/using-messagededuplicationid-property.html"
 	aws_vpc_flow_logs:                                        "\(aws_docs)/vpc/latest/userguide/flow-logs.html"
+	aws_vpc_flow_logs_fields:                                 "\(aws_docs)/vpc/latest/userguide/flow-logs.html#flow-logs-fields"
 	azure_blob:                                               "https://azure.microsoft.com/en-us/services/storage/blobs/"
 	azure_blob_endpoints:                                     "https://docs.microsoft.com/en-us/rest/api/storageservices/blob-service-rest-api"
+	azure_event_hubs:                                         "https://azure.microsoft.com/en-us/services/event-hubs/"
+	azure_event_hubs_amqp:                                    "https://docs.microsoft.com/en-us/azure/event-hubs/event-hubs-amqp-troubleshoot"
+	azure_event_hubs_checkpoints:                             "https://docs.microsoft.com/en-us/azure/event-hubs/event-processor-balance-partition-load"
+	azure_managed_identity:                                   "https://docs.microsoft.com/en-us/azure/active-directory/managed-identities-azure-resources/overview"
 	azure_monitor:                                            "https://azure.microsoft.com/en-us/services/monitor/"
 	azure_monitor_logs_endpoints:                             "https://docs.microsoft.com/en-us/rest/api/monitor/"
 	base64:                                                   "\(wikipedia)/wiki/Base64"
@@ -192,6 +203,8 @@ urls: {
 	gcp_authentication_service_account:                       "\(gcp)/docs/authentication/production#obtaining_and_providing_service_account_credentials_manually"
 	gcp_cloud_storage:                                        "\(gcp)/storage"
 	gcp_folders:                                              "\(gcp)/resource-manager/docs/creating-managing-folders"
+	gcp_logging_entries_list:                                 "\(gcp)/logging/docs/reference/v2/rest/v2/entries/list"
+	gcp_logging_query_language:                               "\(gcp)/logging/docs/view/logging-query-language"
 	gcp_pubsub:                                               "\(gcp)/pubsub/"
 	gcp_pubsub_rest:                                          "\(gcp)/pubsub/docs/reference/rest/"
 	gcp_projects:                                             "\(gcp)/resource-manager/docs/creating-managing-projects"
@@ -212,6 +225,8 @@ urls: {
 	github:                                                   "https://github.com"
 	github_protected_branches:                                "https://help.github.com/en/github/administering-a-repository/about-protected-branches"
 	github_sign_commits:                                      "https://help.github.com/en/github/authenticating-to-github/signing-commits"
+	github_webhooks:                                          "https://docs.github.com/en/developers/webhooks-and-events/webhooks/about-webhooks"
+	github_webhooks_securing:                                 "https://docs.github.com/en/developers/webhooks-and-events/webhooks/securing-your-webhooks"
 	globbing:                                                 "\(wikipedia)/wiki/Glob_(programming)"
 	glog:                                                     "\(github)/google/glog"
 	graphql:                                                  "https://graphql.org"
@@ -222,14 +237,17 @@ urls: {
 	grok_patterns:                                            "\(github)/daschl/grok/tree/master/patterns"
 	gzip:                                                     "https://www.gzip.org/"
 	haproxy:                                                  "https://www.haproxy.org/"
+	haproxy_proxy_protocol:                                   "https://www.haproxy.org/download/2.5/doc/proxy-protocol.txt"
 	helm:                                                     "https://helm.sh/"
 	heroku:                                                   "https://www.heroku.com"
 	heroku_http_log_drain:                                    "https://devcenter.heroku.com/articles/log-drains#https-drains"
 	heroku_start:                                             "https://devcenter.heroku.com/start"
+	hmac:                                                     "\(wikipedia)/wiki/HMAC"
 	homebrew:                                                 "https://brew.sh/"
 	homebrew_services:                                        "\(github)/Homebrew/homebrew-services"
 	honeycomb:                                                "https://honeycomb.io"
 	honeycomb_batch:                                          "https://docs.honeycomb.io/api/events/#batched-events"
+	honeycomb_markers:                                        "https://docs.honeycomb.io/api/markers/"
 	honeycomb_signup:                                         "https://ui.honeycomb.io/signup"
 	host:                                                     "\(wikipedia)/wiki/Host_(network)"
 	http:                                                     "https://www.w3.org/Protocols/"
@@ -255,8 +273,12 @@ urls: {
 	iso_8601:                                                 "\(wikipedia)/wiki/ISO_8601"
 	iso3166_2:                                                "\(wikipedia)/wiki/ISO_3166-2"
 	issue_1694:                                               "\(vector_repo)/issues/1694"
+	jaeger:                                                   "https://www.jaegertracing.io/"
+	jaeger_apis:                                              "https://www.jaegertracing.io/docs/latest/apis/"
+	jaro_winkler:                                             "\(wikipedia)/wiki/Jaro%E2%80%93Winkler_distance"
 	journalctl:                                               "https://www.freedesktop.org/software/systemd/man/journalctl.html"
 	journald:                                                 "https://www.freedesktop.org/software/systemd/man/systemd-journald.service.html"
+	jq:                                                       "https://stedolan.github.io/jq/manual/"
 	json:                                                     "\(wikipedia)/wiki/JSON"
 	json_types:                                               "\(wikipedia)/wiki/JSON#Data_types_and_syntax"
 	jsonnet:                                                  "https://jsonnet.org/"
@@ -281,8 +303,10 @@ urls: {
 	leveldb:                                                  "\(github)/google/leveldb"
 	leveldb_sys_2:                                            "https://crates.io/crates/leveldb-sys"
 	leveldb_sys_3:                                            "\(github)/vectordotdev/leveldb-sys/tree/v3.0.0"
+	levenshtein_distance:                                     "\(wikipedia)/wiki/Levenshtein_distance"
 	librdkafka:                                               "\(github)/edenhill/librdkafka"
 	librdkafka_config:                                        "\(github)/edenhill/librdkafka/blob/master/CONFIGURATION.md"
+	linux_audit:                                              "https://man7.org/linux/man-pages/man8/auditd.8.html"
 	linux_capability:                                         "https://man7.org/linux/man-pages/man7/capabilities.7.html"
 	logdna:                                                   "https://logdna.com/"
 	logfmt:                                                   "https://brandur.org/logfmt"
@@ -326,12 +350,14 @@ urls: {
 	maxmind_geolite2_city:                                    "https://dev.maxmind.com/geoip/geoip2/geolite2/#Download_Access"
 	memory_safety:                                            "\(wikipedia)/wiki/Memory_safety"
 	memory_safety_bugs:                                       "https://thenewstack.io/microsoft-rust-is-the-industrys-best-chance-at-safe-systems-programming/"
+	metaphone:                                                "\(wikipedia)/wiki/Metaphone"
 	metric_event_source:                                      "\(vector_repo)/blob/master/src/event/metric.rs"
 	mlua:                                                     "\(github)/khvzak/mlua"
 	mongodb:                                                  "https://www.mongodb.com"
 	mongodb_command_server_status:                            "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:                     "https://docs.mongodb.com/manual/reference/connection-string/"
 	musl_builder_docker_image:                                "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
+	named_pipe:                                               "\(wikipedia)/wiki/Named_pipe"
 	nats:                                                     "https://nats.io/"
 	nats_rs:                                                  "\(github)/nats-io/nats.rs"
 	new_bug_report:                                           "\(vector_repo)/issues/new?labels=type%3A+bug"
@@ -351,6 +377,7 @@ urls: {
 	nix:                                                      "https://nixos.org/nix/"
 	nixos:                                                    "https://nixos.org/"
 	nixpkgs_9682:                                             "\(github)/NixOS/nixpkgs/issues/9682"
+	ocsf:                                                     "https://schema.ocsf.io/"
 	openssl:                                                  "https://www.openssl.org/"
 	order_of_ops:                                             "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                               "https://www.papertrail.com/"
@@ -387,6 +414,7 @@ urls: {
 	protobuf:                                                 "https://developers.google.com/protocol-buffers"
 	pulsar:                                                   "https://pulsar.apache.org/"
 	pulsar_protocol:                                          "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	punycode:                                                 "\(wikipedia)/wiki/Punycode"
 	raspbian:                                                 "https://www.raspbian.org/"
 	rdkafka:                                                  "\(github)/edenhill/librdkafka"
 	regex:                                                    "\(wikipedia)/wiki/Regular_expression"
@@ -428,13 +456,17 @@ urls: {
 	sematext_monitoring:                                      "https://sematext.com/docs/monitoring/"
 	sematext_registration:                                    "https://apps.sematext.com/ui/registration"
 	semver:                                                   "https://semver.org/"
+	server_sent_events:                                       "https://html.spec.whatwg.org/multipage/server-sent-events.html"
 	sha1:                                                     "\(wikipedia)/wiki/SHA-1"
 	sha2:                                                     "\(wikipedia)/wiki/SHA-2"
 	sha3:                                                     "\(wikipedia)/wiki/SHA-3"
 	signal:                                                   "\(wikipedia)/wiki/Signal_(IPC)"
+	slack_webhooks:                                           "https://api.slack.com/messaging/webhooks"
 	snake_case:                                               "\(wikipedia)/wiki/Snake_case"
 	snappy:                                                   "https://google.github.io/snappy/"
+	snowflake_id:                                             "\(wikipedia)/wiki/Snowflake_ID"
 	socket:                                                   "\(wikipedia)/wiki/Network_socket"
+	soundex:                                                  "\(wikipedia)/wiki/Soundex"
 	splunk:                                                   "https://www.splunk.com"
 	splunk_hec:                                               "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
 	splunk_hec_event_endpoint:                                "https://docs.splunk.com/Documentation/Splunk/8.0.0/RESTREF/RESTinput#services.2Fcollector.2Fevent"
@@ -478,12 +510,14 @@ urls: {
 	uap:                                                      "https://github.com/ua-parser/uap-core"
 	ubuntu:                                                   "https://ubuntu.com/"
 	udp:                                                      "\(wikipedia)/wiki/User_Datagram_Protocol"
+	ulid:                                                     "https://github.com/ulid/spec"
 	uds:                                                      "\(wikipedia)/wiki/Unix_domain_socket"
 	unicode_replacement_character:                            "\(wikipedia)/wiki/Specials_(Unicode_block)#Replacement_character"
 	unicode_whitespace:                                       "\(wikipedia)/wiki/Unicode_character_property#Whitespace"
 	unix_timestamp:                                           "\(wikipedia)/wiki/Unix_time"
 	utf8:                                                     "\(wikipedia)/wiki/UTF-8"
 	uuidv4:                                                   "\(wikipedia)/wiki/Universally_unique_identifier#Version_4_(random)"
+	uuidv7:                                                   "https://www.rfc-editor.org/rfc/rfc9562#name-uuid-version-7"
 	url:                                                      "\(wikipedia)/wiki/URL"
 	us_social_security_number:                                "https://www.ssa.gov/history/ssn/geocard.html"
 	user_agent:                                               "https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/User-Agent"
@@ -611,6 +645,7 @@ urls: {
 	vote_feature:                                             "\(vector_repo)/issues?q=is%3Aissue+is%3Aopen+sort%3Areactions-%2B1-desc+label%3A%22Type%3A+New+Feature%22"
 	wasm:                                                     "https://webassembly.org/"
 	wasm_languages:                                           "\(github)/appcypher/awesome-wasm-langs"
+	websocket:                                                "https://datatracker.ietf.org/doc/html/rfc6455"
 	wikipedia:                                                "https://en.wikipedia.org"
 	windows:                                                  "https://www.microsoft.com/en-us/windows"
 	windows_installer:                                        "\(wikipedia)/wiki/Windows_Installer"
@@ -619,5 +654,7 @@ urls: {
 	yaml:                                                     "https://yaml.org/"
 	yum:                                                      "\(wikipedia)/wiki/Yum_(software)"
 	zlib:                                                     "https://www.zlib.net"
+	zipkin:                                                   "https://zipkin.io/"
+	zipkin_api:                                               "https://zipkin.io/zipkin-api/"
 	zstd:                                                     "https://zstd.net"
 }